@@ -4,17 +4,21 @@ use crate::api::model::{create_channel_unavailable_stream, create_custom_video_s
                         get_stream_response_with_headers, create_active_client_stream, AppState,
                         CustomVideoStreamType, ProviderStreamFactoryOptions,
                         SharedStreamManager, StreamError, ThrottledStream, UserApiRequest};
-use crate::api::model::{tee_stream, UserSession};
-use crate::api::model::{ProviderAllocation, ProviderConfig, ProviderStreamState, StreamDetails, StreamingStrategy};
+use crate::api::model::{tee_stream, AccountSharingDecision, ClusterState, UserSession};
+use crate::api::model::{BoxedProviderStream, ProviderAllocation, ProviderConfig, ProviderHandle, ProviderStreamInfo, ProviderStreamState, StreamDetails, StreamingStrategy};
 use crate::auth::Fingerprint;
-use crate::model::{ConfigInput};
+use crate::messaging::send_message;
+use crate::model::{ConfigInput, InputAutoProxy, InputFailoverConfig, MessageContent};
 use crate::model::{ConfigTarget, ProxyUserCredentials};
+use crate::repository::{load_channel_failovers, ChannelFailoverSource};
+use crate::tools::atomic_once_flag::AtomicOnceFlag;
 use crate::tools::lru_cache::LRUResourceCache;
-use crate::utils::request::{content_type_from_ext, parse_range, send_with_retry};
+use crate::utils::request::{apply_header_rules, content_type_from_ext, parse_range, send_with_retry};
 use crate::utils::{async_file_reader, async_file_writer, create_new_file_for_write, get_file_extension};
 use crate::utils::{debug_if_enabled, trace_if_enabled};
 use crate::utils::request;
-use crate::BUILD_TIMESTAMP;
+use crate::utils::input_client::get_input_client;
+use crate::{BUILD_TIMESTAMP, VERSION};
 
 use arc_swap::ArcSwapOption;
 use axum::body::Body;
@@ -27,13 +31,13 @@ use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use log::{debug, error, info, log_enabled, trace, warn};
 use serde::Serialize;
 use shared::concat_string;
-use shared::model::{Claims, InputFetchMethod, PlaylistEntry, PlaylistItemType, ProxyType, StreamChannel, TargetType, UserConnectionPermission, VirtualId, XtreamCluster};
-use shared::utils::{bin_serialize, human_readable_kbps, trim_slash, Internable, CONTENT_TYPE_CBOR};
+use shared::model::{ApiErrorCode, ApiErrorResponse, Claims, DeduplicationStrategy, HeaderPolicyDto, HeaderRulesDto, InputFetchMethod, PlaylistEntry, PlaylistItemType, ProxyType, StatusCheck, StreamChannel, TargetType, UserConnectionPermission, VirtualId, XtreamCluster};
+use shared::utils::{bin_serialize, current_time_secs, human_readable_kbps, trim_slash, Internable, CONTENT_TYPE_CBOR};
 use shared::utils::{
-    extract_extension_from_url, replace_url_extension, sanitize_sensitive_info, DASH_EXT, HLS_EXT,
+    extract_extension_from_url, replace_url_extension, sanitize_sensitive_info, strip_port, DASH_EXT, HLS_EXT,
 };
 use std::borrow::Cow;
-use std::collections::{HashMap};
+use std::collections::{BTreeMap, HashMap};
 use std::convert::Infallible;
 use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
@@ -141,6 +145,36 @@ pub fn get_server_time() -> String {
         .to_string()
 }
 
+pub async fn create_status_check(app_state: &Arc<AppState>) -> StatusCheck {
+    let cache = match app_state.cache.load().as_ref().as_ref() {
+        None => None,
+        Some(lock) => {
+            Some(lock.lock().await.get_size_text())
+        }
+    };
+    let (active_users, active_user_connections, active_user_streams) = {
+        let active_user = &app_state.active_users;
+        let (user_count, connection_count) = active_user.active_users_and_connections().await;
+        (user_count, connection_count, active_user.active_streams().await)
+    };
+
+    let active_provider_connections = app_state.active_provider.active_connections().await.map(|c| c.into_iter().collect::<BTreeMap<_, _>>());
+
+    StatusCheck {
+        status: "ok".to_string(),
+        version: VERSION.to_string(),
+        build_time: get_build_time(),
+        server_time: get_server_time(),
+        active_users,
+        active_user_connections,
+        active_provider_connections,
+        provider_failovers: app_state.provider_health.get_failovers(),
+        geoip_denied: app_state.geoip_access.get_denied(),
+        active_user_streams,
+        cache,
+    }
+}
+
 pub fn get_build_time() -> Option<String> {
     BUILD_TIMESTAMP
         .to_string()
@@ -444,6 +478,7 @@ async fn create_stream_response_details(
     connection_permission: UserConnectionPermission,
     force_provider: Option<&Arc<str>>,
     virtual_id: VirtualId,
+    target_header_policy: Option<&HeaderPolicyDto>,
 ) -> StreamDetails {
     let mut streaming_strategy = resolve_streaming_strategy(app_state, stream_url, fingerprint, input, force_provider).await;
     let mut grace_period_options = app_state.get_grace_options();
@@ -497,36 +532,44 @@ async fn create_stream_response_details(
         }
         ProviderStreamState::Available(_provider_name, request_url)
         | ProviderStreamState::GracePeriod(_provider_name, request_url) => {
-            let parsed_url = Url::parse(&request_url);
-            let ((stream, stream_info), reconnect_flag) = if let Ok(url) = parsed_url {
-                let default_user_agent = app_state.app_config.config.load().default_user_agent.clone();
-                let disabled_headers = app_state.get_disabled_headers();
-                let provider_stream_factory_options = ProviderStreamFactoryOptions::new(
-                    fingerprint.addr,
-                    item_type,
-                    share_stream,
-                    stream_options,
-                    &url,
-                    req_headers,
-                    streaming_strategy.input_headers.as_ref(),
-                    disabled_headers.as_ref(),
-                    default_user_agent.as_deref(),
-                );
-                let reconnect_flag = provider_stream_factory_options.get_reconnect_flag_clone();
-                let provider_stream = match create_provider_stream(
-                    app_state,
-                    &app_state.http_client.load(),
-                    provider_stream_factory_options,
-                )
-                    .await
-                {
-                    None => (None, None),
-                    Some((stream, info)) => (Some(stream), info),
-                };
-                (provider_stream, Some(reconnect_flag))
-            } else {
-                ((None, None), None)
-            };
+            let header_rules = input.get_upstream_header_rules(target_header_policy);
+            let stream_request = ProviderStreamRequest { stream_options, fingerprint, req_headers, item_type, share_stream, input_headers: streaming_strategy.input_headers.as_ref(),
+                header_rules: header_rules.as_ref(), input_name: &input.name };
+            let (mut stream, mut stream_info, mut reconnect_flag) = open_provider_stream(app_state, &stream_request, &request_url).await;
+            let mut request_url = request_url;
+            let mut provider_name = guard_provider_name.clone();
+            let mut provider_handle = streaming_strategy.provider_handle.take();
+
+            // The failover is only used for the initial request, a forced provider is never switched.
+            if let Some(failover) = input.get_failover().filter(|_| force_provider.is_none()) {
+                let mut tried = Vec::new();
+                while let Some(current_provider) = provider_name.clone() {
+                    if !is_provider_stream_failed(stream.as_ref(), &stream_info) {
+                        app_state.provider_health.record_success(&current_provider);
+                        break;
+                    }
+                    app_state.provider_health.record_failure(&current_provider);
+                    tried.push(current_provider);
+                    app_state.connection_manager.release_provider_handle(provider_handle.take()).await;
+                    let Some((next_handle, next_provider, next_url)) = acquire_failover_provider(app_state, stream_url, fingerprint, input, failover, &tried).await else {
+                        break;
+                    };
+                    info!("Failover for input {} from provider {} to {}", sanitize_sensitive_info(&input.name),
+                        sanitize_sensitive_info(tried.last().map_or("?", |name| name.as_ref())), sanitize_sensitive_info(&next_provider));
+                    app_state.provider_health.record_failover(&input.name);
+                    (stream, stream_info, reconnect_flag) = open_provider_stream(app_state, &stream_request, &next_url).await;
+                    request_url = next_url.intern();
+                    provider_handle = Some(next_handle);
+                    provider_name = Some(next_provider);
+                }
+            } else if let Some(current_provider) = provider_name.as_ref() {
+                // The health also decides between redirect and proxy for the `auto` proxy type.
+                if is_provider_stream_failed(stream.as_ref(), &stream_info) {
+                    app_state.provider_health.record_failure(current_provider);
+                } else {
+                    app_state.provider_health.record_success(current_provider);
+                }
+            }
 
             if log_enabled!(log::Level::Debug) {
                 if let Some((headers, status_code, response_url, _custom_video_type)) = stream_info.as_ref() {
@@ -543,18 +586,17 @@ async fn create_stream_response_details(
 
             // if we have no stream, we should release the provider
             let provider_handle = if stream.is_none() {
-                let provider_handle = streaming_strategy.provider_handle.take();
                 app_state.connection_manager.release_provider_handle(provider_handle).await;
                 error!("Can't open stream {}", sanitize_sensitive_info(&request_url));
                 None
             } else {
-                streaming_strategy.provider_handle.take()
+                provider_handle
             };
 
             StreamDetails {
                 stream,
                 stream_info,
-                provider_name: guard_provider_name.clone(),
+                provider_name,
                 grace_period: grace_period_options,
                 disable_provider_grace: false,
                 reconnect_flag,
@@ -564,6 +606,108 @@ async fn create_stream_response_details(
     }
 }
 
+struct ProviderStreamRequest<'a> {
+    stream_options: &'a StreamOptions,
+    fingerprint: &'a Fingerprint,
+    req_headers: &'a HeaderMap,
+    item_type: PlaylistItemType,
+    share_stream: bool,
+    input_headers: Option<&'a HashMap<String, String>>,
+    header_rules: Option<&'a HeaderRulesDto>,
+    input_name: &'a str,
+}
+
+async fn open_provider_stream(
+    app_state: &Arc<AppState>,
+    request: &ProviderStreamRequest<'_>,
+    request_url: &str,
+) -> (Option<BoxedProviderStream>, ProviderStreamInfo, Option<Arc<AtomicOnceFlag>>) {
+    let Ok(url) = Url::parse(request_url) else {
+        return (None, None, None);
+    };
+    let default_user_agent = app_state.app_config.config.load().default_user_agent.clone();
+    let disabled_headers = app_state.get_disabled_headers();
+    let provider_stream_factory_options = ProviderStreamFactoryOptions::new(
+        request.fingerprint.addr,
+        request.item_type,
+        request.share_stream,
+        request.stream_options,
+        &url,
+        request.req_headers,
+        request.input_headers,
+        request.header_rules,
+        disabled_headers.as_ref(),
+        default_user_agent.as_deref(),
+    );
+    let reconnect_flag = provider_stream_factory_options.get_reconnect_flag_clone();
+    let client = get_input_client(&app_state.app_config, &app_state.http_client.load(), request.input_name, None);
+    match create_provider_stream(
+        app_state,
+        &client,
+        provider_stream_factory_options,
+    )
+        .await
+    {
+        None => (None, None, Some(reconnect_flag)),
+        Some((stream, info)) => (Some(stream), info, Some(reconnect_flag)),
+    }
+}
+
+/// A failed provider request is answered with the channel unavailable stream or no stream at all.
+fn is_provider_stream_failed(stream: Option<&BoxedProviderStream>, stream_info: &ProviderStreamInfo) -> bool {
+    stream.is_none()
+        || stream_info.as_ref().is_some_and(|(_, _, _, custom_video_type)| matches!(custom_video_type, Some(CustomVideoStreamType::ChannelUnavailable)))
+}
+
+/// The not yet tried providers of the input and its aliases.
+/// Healthy providers come first, then the lower priority value and the lower failure score.
+fn get_failover_candidates(
+    app_state: &Arc<AppState>,
+    input: &ConfigInput,
+    failover: &InputFailoverConfig,
+    tried: &[Arc<str>],
+) -> Vec<Arc<str>> {
+    let mut candidates: Vec<(bool, i16, u16, Arc<str>)> = std::iter::once((&input.name, input.priority))
+        .chain(input.aliases.iter().flatten().filter(|alias| alias.enabled).map(|alias| (&alias.name, alias.priority)))
+        .filter(|(name, _)| !tried.contains(name))
+        .map(|(name, priority)| (
+            !app_state.provider_health.is_healthy(name, failover),
+            priority,
+            app_state.provider_health.get_score(name),
+            Arc::clone(name),
+        ))
+        .collect();
+    candidates.sort();
+    candidates.into_iter().map(|(_, _, _, name)| name).collect()
+}
+
+async fn acquire_failover_provider(
+    app_state: &Arc<AppState>,
+    stream_url: &str,
+    fingerprint: &Fingerprint,
+    input: &ConfigInput,
+    failover: &InputFailoverConfig,
+    tried: &[Arc<str>],
+) -> Option<(ProviderHandle, Arc<str>, String)> {
+    for provider_name in get_failover_candidates(app_state, input, failover, tried) {
+        let Some(handle) = app_state.active_provider.force_exact_acquire_connection(&provider_name, &fingerprint.addr).await else {
+            continue;
+        };
+        match &handle.allocation {
+            ProviderAllocation::Available(provider_cfg) | ProviderAllocation::GracePeriod(provider_cfg) => {
+                let url = if provider_cfg.id == input.id {
+                    stream_url.to_string()
+                } else {
+                    get_stream_alternative_url(stream_url, input, provider_cfg)
+                };
+                return Some((handle, provider_name, url));
+            }
+            ProviderAllocation::Exhausted => app_state.connection_manager.release_provider_handle(Some(handle)).await,
+        }
+    }
+    None
+}
+
 pub struct RedirectParams<'a, P>
 where
     P: PlaylistEntry,
@@ -578,6 +722,7 @@ where
     pub stream_ext: Option<&'a str>,
     pub req_context: ApiStreamContext,
     pub action_path: &'a str,
+    pub client_ip: &'a str,
 }
 
 impl<P> RedirectParams<'_, P>
@@ -602,6 +747,65 @@ where
     }
 }
 
+/// The `auto` proxy type redirects to a healthy provider, clients from countries
+/// without direct access to the provider are proxied.
+fn is_auto_redirect(app_state: &AppState, input: &ConfigInput, client_ip: &str) -> bool {
+    let default_auto_proxy;
+    let auto_proxy = if let Some(auto_proxy) = input.auto_proxy.as_ref() { auto_proxy } else {
+        default_auto_proxy = InputAutoProxy::default();
+        &default_auto_proxy
+    };
+    if !app_state.provider_health.is_healthy_for_redirect(&input.name, auto_proxy) {
+        debug_if_enabled!("Proxying stream of unhealthy input {}", sanitize_sensitive_info(&input.name));
+        return false;
+    }
+    let country = app_state.geoip.load().as_ref().and_then(|geoip| geoip.lookup(&strip_port(client_ip)));
+    !auto_proxy.is_proxied_country(country.as_deref())
+}
+
+/// Tracks the client of the user for the account sharing detection, exceeded limits
+/// are reported and with the `block` action the streams of the user are denied.
+pub fn account_sharing_denied(app_state: &Arc<AppState>, user: &ProxyUserCredentials, fingerprint: &Fingerprint) -> bool {
+    let config = app_state.app_config.config.load();
+    let Some(account_sharing) = config.reverse_proxy.as_ref()
+        .and_then(|reverse_proxy| reverse_proxy.account_sharing.as_ref())
+        .filter(|account_sharing| account_sharing.enabled) else {
+        return false;
+    };
+    let limits = account_sharing.limits.with_overrides(user.account_sharing.as_ref());
+    if limits.is_unlimited() {
+        return false;
+    }
+    let client_ip = strip_port(&fingerprint.client_ip);
+    let country = app_state.geoip.load().as_ref().and_then(|geoip| geoip.lookup(&client_ip));
+    match app_state.account_sharing.check(account_sharing, limits, &user.username, &client_ip,
+                                          country.as_deref(), &fingerprint.key, current_time_secs()) {
+        AccountSharingDecision::Allowed => false,
+        AccountSharingDecision::Alert(message) => {
+            warn!("{message}");
+            send_account_sharing_message(app_state, message);
+            false
+        }
+        AccountSharingDecision::Blocked(message) => {
+            if let Some(message) = message {
+                warn!("{message}");
+                send_account_sharing_message(app_state, message);
+            } else {
+                debug!("User access denied, blocked for account sharing: {}", user.username);
+            }
+            true
+        }
+    }
+}
+
+fn send_account_sharing_message(app_state: &Arc<AppState>, message: String) {
+    let app_config = Arc::clone(&app_state.app_config);
+    let client = Arc::clone(&app_state.http_client.load());
+    tokio::spawn(async move {
+        send_message(&app_config, &client, MessageContent::Info(message)).await;
+    });
+}
+
 pub async fn redirect_response<'a, P>(
     app_state: &Arc<AppState>,
     params: &'a RedirectParams<'a, P>,
@@ -612,8 +816,9 @@ where
     let item_type = params.item.get_item_type();
     let provider_url = params.item.get_provider_url();
 
-    let redirect_request =
-        params.user.proxy.is_redirect(item_type) || params.target.is_force_redirect(item_type);
+    let redirect_request = params.user.proxy.is_redirect(item_type)
+        || params.target.is_force_redirect(item_type)
+        || (params.user.proxy.is_auto() && !item_type.is_local() && is_auto_redirect(app_state, params.input, params.client_ip));
     let is_hls_request =
         item_type == PlaylistItemType::LiveHls || params.stream_ext == Some(HLS_EXT);
     let is_dash_request = (!is_hls_request && item_type == PlaylistItemType::LiveDash)
@@ -774,6 +979,7 @@ pub async fn force_provider_stream_response(
         connection_permission,
         Some(&user_session.provider),
         stream_channel.virtual_id,
+        None,
     )
         .await;
 
@@ -822,9 +1028,31 @@ pub async fn force_provider_stream_response(
     }
 }
 
+/// Streams the channel, the response is changed by the client header policies of the input and the target.
+#[allow(clippy::too_many_arguments)]
+pub async fn stream_response(
+    fingerprint: &Fingerprint,
+    app_state: &Arc<AppState>,
+    session_token: &str,
+    stream_channel: StreamChannel,
+    stream_url: &str,
+    req_headers: &HeaderMap,
+    input: &ConfigInput,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    connection_permission: UserConnectionPermission,
+    quality: Option<u32>,
+) -> impl IntoResponse + Send {
+    let client_header_rules = input.get_client_header_rules(target.get_header_policy());
+    let mut response = open_stream_response(fingerprint, app_state, session_token, stream_channel, stream_url, req_headers,
+                                            input, target, user, connection_permission, quality).await.into_response();
+    apply_header_rules(response.headers_mut(), client_header_rules.as_ref());
+    response
+}
+
 /// # Panics
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
-pub async fn stream_response(
+async fn open_stream_response(
     fingerprint: &Fingerprint,
     app_state: &Arc<AppState>,
     session_token: &str,
@@ -835,6 +1063,7 @@ pub async fn stream_response(
     target: &ConfigTarget,
     user: &ProxyUserCredentials,
     connection_permission: UserConnectionPermission,
+    quality: Option<u32>,
 ) -> impl IntoResponse + Send {
     if log_enabled!(log::Level::Trace) {
         trace!("Try to open stream {}", sanitize_sensitive_info(stream_url));
@@ -852,6 +1081,16 @@ pub async fn stream_response(
     let virtual_id = stream_channel.virtual_id;
     let item_type = stream_channel.item_type;
 
+    let variants = if item_type.is_live() { get_channel_failover_sources(app_state, target, virtual_id).await } else { Vec::new() };
+    // the client selects a lower variant of a collapsed channel with the `quality` parameter
+    let selected = quality.filter(|_| target.is_collapse_variants())
+        .and_then(|quality| select_channel_variant(&variants, &stream_channel.url, quality))
+        .and_then(|index| app_state.app_config.get_input_by_name(&variants[index].input_name).map(|variant_input| (index, variant_input)));
+    let (input, stream_url) = match selected.as_ref() {
+        Some((index, variant_input)) => (&**variant_input, &*variants[*index].url),
+        None => (input, stream_url),
+    };
+
     let share_stream = is_stream_share_enabled(item_type, target);
     let _shared_lock = if share_stream {
         let write_lock = app_state.app_config.file_locks.write_lock_str(stream_url).await;
@@ -879,8 +1118,41 @@ pub async fn stream_response(
         connection_permission,
         None,
         stream_channel.virtual_id,
+        target.get_header_policy(),
     ).await;
 
+    // the streams of the removed duplicates and the lower variants are tried when the channel can't be opened
+    let mut failover_url = None;
+    if !stream_details.has_stream() {
+        let remaining = selected.as_ref().map_or(0, |(index, _)| index + 1);
+        for source in variants.iter().skip(remaining).filter(|source| source.url != stream_channel.url) {
+            let Some(failover_input) = app_state.app_config.get_input_by_name(&source.input_name) else {
+                continue;
+            };
+            app_state.connection_manager.release_provider_handle(stream_details.provider_handle.take()).await;
+            stream_details = create_stream_response_details(
+                app_state,
+                &stream_options,
+                &source.url,
+                fingerprint,
+                req_headers,
+                &failover_input,
+                item_type,
+                share_stream,
+                connection_permission,
+                None,
+                virtual_id,
+                target.get_header_policy(),
+            ).await;
+            if stream_details.has_stream() {
+                info!("Channel failover from {} to {}", sanitize_sensitive_info(stream_url), sanitize_sensitive_info(&source.url));
+                failover_url = Some(Arc::clone(&source.url));
+                break;
+            }
+        }
+    }
+    let stream_url: &str = failover_url.as_deref().unwrap_or(stream_url);
+
     if stream_details.has_stream() {
         // let content_length = get_stream_content_length(provider_response.as_ref());
         let provider_response = stream_details
@@ -1022,6 +1294,31 @@ pub async fn stream_response(
     StatusCode::BAD_REQUEST.into_response()
 }
 
+/// Failover sources of a channel of a target with the `failover` deduplication strategy or collapsed variants.
+async fn get_channel_failover_sources(app_state: &Arc<AppState>, target: &ConfigTarget, virtual_id: VirtualId) -> Vec<ChannelFailoverSource> {
+    let is_failover = target.is_collapse_variants() || target.options.as_ref()
+        .and_then(|options| options.deduplication.as_ref())
+        .is_some_and(|deduplication| deduplication.strategy == DeduplicationStrategy::Failover);
+    if !is_failover {
+        return Vec::new();
+    }
+    let query_cache = &app_state.playlists.query_cache;
+    let failovers = if let Some(failovers) = query_cache.get_channel_failovers(&target.name) {
+        failovers
+    } else {
+        let generation = query_cache.generation();
+        let failovers = load_channel_failovers(&app_state.app_config, &target.name).await;
+        query_cache.put_channel_failovers(&target.name, generation, failovers)
+    };
+    failovers.get(&virtual_id).cloned().unwrap_or_default()
+}
+
+/// Index of the best variant of a collapsed channel not above the requested quality, `None` if it is the channel itself.
+fn select_channel_variant(variants: &[ChannelFailoverSource], channel_url: &str, quality: u32) -> Option<usize> {
+    let index = variants.iter().position(|variant| variant.quality <= quality)?;
+    (&*variants[index].url != channel_url).then_some(index)
+}
+
 fn get_stream_throttle(app_state: &Arc<AppState>) -> u64 {
     app_state
         .app_config
@@ -1246,21 +1543,78 @@ pub fn get_headers_from_request(
         .collect()
 }
 
+/// Adds the stored content to the cache and publishes the change to the cluster cache index.
+async fn store_cache_content(
+    cache: &Mutex<LRUResourceCache>,
+    cluster: Option<&ClusterState>,
+    url: &str,
+    mime_type: Option<String>,
+    size: usize,
+) -> Option<PathBuf> {
+    let (path, removed) = {
+        let mut guard = cache.lock().await;
+        let path = guard.add_content(url, mime_type.clone(), size).ok();
+        (path, guard.take_removed_keys())
+    };
+    if let Some(cluster) = cluster {
+        let content_key = LRUResourceCache::content_key(url);
+        cluster.publish_cache_changes(path.is_some().then_some((content_key.as_str(), mime_type, size)), &removed).await;
+    }
+    path
+}
+
+/// Publishes the entries removed from the cache to the cluster cache index.
+pub async fn publish_removed_cache_keys(cache: &Mutex<LRUResourceCache>, cluster: Option<&ClusterState>) {
+    if let Some(cluster) = cluster {
+        let removed = cache.lock().await.take_removed_keys();
+        cluster.publish_cache_changes(None, &removed).await;
+    }
+}
+
+/// Looks up the content in the local cache and then in the cluster cache index.
+/// A resource cached by another instance is only used when the file is in the shared cache directory.
+async fn get_cache_content(
+    cache: &Mutex<LRUResourceCache>,
+    cluster: Option<&ClusterState>,
+    url: &str,
+) -> Option<(PathBuf, Option<String>)> {
+    if let Some(content) = cache.lock().await.get_content(url) {
+        return Some(content);
+    }
+    let cluster = cluster?;
+    let entry = cluster.load_cache_entry(&LRUResourceCache::content_key(url)).await?;
+    let (path, removed) = {
+        let mut guard = cache.lock().await;
+        let path = guard.store_path(url, entry.mime_type.as_deref());
+        if !path.exists() {
+            return None;
+        }
+        trace_if_enabled!("Taking over cached resource of cluster node {} for {}", entry.node_id, sanitize_sensitive_info(url));
+        guard.add_content(url, entry.mime_type.clone(), entry.size).ok()?;
+        (path, guard.take_removed_keys())
+    };
+    cluster.publish_cache_changes(None, &removed).await;
+    Some((path, entry.mime_type))
+}
+
 fn get_add_cache_content(
     res_url: &str,
     mime_type: Option<String>,
     cache: &Arc<ArcSwapOption<Mutex<LRUResourceCache>>>,
+    cluster: Option<&Arc<ClusterState>>,
 ) -> Arc<dyn Fn(usize) + Send + Sync> {
     let resource_url = String::from(res_url);
     let cache = Arc::clone(cache);
+    let cluster = cluster.cloned();
     let add_cache_content: Arc<dyn Fn(usize) + Send + Sync> = Arc::new(move |size| {
         let res_url = resource_url.clone();
         let mime_type = mime_type.clone();
         // todo spawn, replace with unboundchannel
         let cache = Arc::clone(&cache);
+        let cluster = cluster.clone();
         tokio::spawn(async move {
             if let Some(cache) = cache.load().as_ref() {
-                let _ = cache.lock().await.add_content(&res_url, mime_type, size);
+                let _ = store_cache_content(cache, cluster.as_deref(), &res_url, mime_type, size).await;
             }
         });
     });
@@ -1330,7 +1684,7 @@ async fn build_resource_stream_response(
                 Ok(file) => {
                     debug!("Persisting resource stream {sanitized_resource_url} to {}", resource_path.display());
                     let writer = async_file_writer(file);
-                    let add_cache_content = get_add_cache_content(resource_url, mime_type, &app_state.cache);
+                    let add_cache_content = get_add_cache_content(resource_url, mime_type, &app_state.cache, app_state.cluster.as_ref());
                     let tee = tee_stream(byte_stream, writer, &resource_path, add_cache_content);
                     return try_unwrap_body!(response_builder.body(axum::body::Body::from_stream(tee)));
                 }
@@ -1419,8 +1773,7 @@ pub async fn resource_response(
     }));
     let req_headers = get_headers_from_request(req_headers, &filter);
     if let Some(cache) = app_state.cache.load().as_ref() {
-        let mut guard = cache.lock().await;
-        if let Some((resource_path, mime_type)) = guard.get_content(resource_url) {
+        if let Some((resource_path, mime_type)) = get_cache_content(cache, app_state.cluster.as_deref(), resource_url).await {
             trace_if_enabled!("Responding resource from cache {}", sanitize_sensitive_info(resource_url));
             return serve_file(&resource_path, mime_type.unwrap_or_else(|| mime::APPLICATION_OCTET_STREAM.to_string()), Some("public, max-age=14400"))
                 .await
@@ -1443,6 +1796,101 @@ pub async fn resource_response(
     StatusCode::BAD_REQUEST.into_response()
 }
 
+const LOGO_MIME_TYPE: &str = "image/webp";
+
+/// Cleared when `ffmpeg` can't be started, the logos are then served in their original size.
+static LOGO_CONVERTER_AVAILABLE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(true);
+
+fn convert_logo_to_webp(content: &[u8], width: u16, target_path: &Path) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut child = std::process::Command::new("ffmpeg")
+        .args(["-loglevel", "error", "-i", "pipe:0", "-frames:v", "1",
+            "-vf", &format!("scale='min({width},iw)':-1"), "-c:v", "libwebp", "-f", "webp", "-y"])
+        .arg(target_path)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // ffmpeg may close the pipe early for images it can't decode, the exit status tells
+        let _ = stdin.write_all(content);
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let _ = std::fs::remove_file(target_path);
+        Err(std::io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+}
+
+/// Downloads the logo, stores it downscaled as webp in the cache and returns the cache file.
+async fn create_cached_logo(app_state: &Arc<AppState>, resource_url: &str, cache_key: &str, width: u16, input: Option<&ConfigInput>) -> Option<PathBuf> {
+    let url = Url::parse(resource_url).ok()?;
+    let default_user_agent = app_state.app_config.config.load().default_user_agent.clone();
+    let disabled_headers = app_state.get_disabled_headers();
+    let response = send_with_retry(&app_state.app_config, &url, || {
+        request::get_client_request(
+            &app_state.http_client.load(),
+            input.map_or(InputFetchMethod::GET, |i| i.method),
+            input.map(|i| &i.headers),
+            &url,
+            None,
+            disabled_headers.as_ref(),
+            default_user_agent.as_deref(),
+        )
+    }).await.ok().filter(|response| response.status().is_success())?;
+    let content = response.bytes().await.ok()?;
+
+    let target_path = app_state.cache.load().as_ref()?.lock().await.store_path(cache_key, Some(LOGO_MIME_TYPE));
+    let convert_path = target_path.clone();
+    match tokio::task::spawn_blocking(move || convert_logo_to_webp(&content, width, &convert_path)).await {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                LOGO_CONVERTER_AVAILABLE.store(false, std::sync::atomic::Ordering::Relaxed);
+                warn!("Logos are not resized, ffmpeg is not available: {err}");
+            } else {
+                debug_if_enabled!("Failed to resize logo {}: {err}", sanitize_sensitive_info(resource_url));
+            }
+            return None;
+        }
+        Err(err) => {
+            error!("Failed to resize logo {}: {err}", sanitize_sensitive_info(resource_url));
+            return None;
+        }
+    }
+    let size = usize::try_from(tokio::fs::metadata(&target_path).await.ok()?.len()).unwrap_or(0);
+    store_cache_content(app_state.cache.load().as_ref()?, app_state.cluster.as_deref(), cache_key, Some(LOGO_MIME_TYPE.to_string()), size).await
+}
+
+/// Serves a logo through the cache. With `cache.logo_width` the logo is downscaled and converted to webp,
+/// logos which can't be converted are served like any other resource.
+pub async fn logo_response(
+    app_state: &Arc<AppState>,
+    resource_url: &str,
+    req_headers: &HeaderMap,
+    input: Option<&ConfigInput>,
+) -> axum::response::Response {
+    let width = app_state.app_config.get_logo_width();
+    if width == 0 || resource_url.is_empty() || !LOGO_CONVERTER_AVAILABLE.load(std::sync::atomic::Ordering::Relaxed) {
+        return resource_response(app_state, resource_url, req_headers, input).await.into_response();
+    }
+    let cache_key = format!("{resource_url}#w{width}");
+    let cached = match app_state.cache.load().as_ref() {
+        Some(cache) => get_cache_content(cache, app_state.cluster.as_deref(), &cache_key).await.map(|(path, _mime_type)| path),
+        None => return resource_response(app_state, resource_url, req_headers, input).await.into_response(),
+    };
+    let logo_path = match cached {
+        Some(path) => Some(path),
+        None => create_cached_logo(app_state, resource_url, &cache_key, width, input).await,
+    };
+    match logo_path {
+        Some(path) => serve_file(&path, LOGO_MIME_TYPE.to_string(), Some("public, max-age=14400")).await.into_response(),
+        None => resource_response(app_state, resource_url, req_headers, input).await.into_response(),
+    }
+}
+
 pub fn separate_number_and_remainder(input: &str) -> (String, Option<String>) {
     input.rfind('.').map_or_else(
         || (input.to_string(), None),
@@ -1525,6 +1973,19 @@ pub fn json_response<T: Serialize>(data: &T) -> impl IntoResponse + Send {
     (StatusCode::OK, axum::Json(data)).into_response()
 }
 
+/// New streams are rejected while the server is shutting down.
+pub fn shutdown_in_progress_response() -> axum::response::Response {
+    (StatusCode::SERVICE_UNAVAILABLE, [(axum::http::header::RETRY_AFTER, "30")]).into_response()
+}
+
+pub fn api_error_response(status: StatusCode, code: ApiErrorCode, message: &str) -> axum::response::Response {
+    (status, axum::Json(ApiErrorResponse::new(code, message))).into_response()
+}
+
+pub fn api_error_response_with_detail(status: StatusCode, code: ApiErrorCode, message: &str, detail: impl Into<String>) -> axum::response::Response {
+    (status, axum::Json(ApiErrorResponse::new(code, message).with_detail(detail))).into_response()
+}
+
 pub fn json_or_bin_response<T: Serialize>(accept: Option<&str>, data: &T) -> impl IntoResponse + Send {
     if accept.is_some_and(|a| a.contains(CONTENT_TYPE_CBOR)) {
         return bin_response(data).into_response();
@@ -1642,6 +2103,9 @@ pub fn create_api_proxy_user(app_state: &Arc<AppState>) -> ProxyUserCredentials
         status: None,
         ui_enabled: false,
         comment: None,
+        allowed_countries: Vec::new(),
+        blocked_countries: Vec::new(),
+        account_sharing: None,
     }
 }
 
@@ -0,0 +1,416 @@
+use crate::api::api_utils::get_stream_alternative_url;
+use crate::api::model::AppState;
+use crate::model::{ChannelProbeConfig, ConfigInput, ConfigTarget};
+use crate::repository::{iter_raw_m3u_target_playlist, iter_raw_xtream_target_playlist, load_channel_quarantine, save_channel_health, save_channel_quarantine};
+use crate::utils::debug_if_enabled;
+use crate::utils::impersonation::{get_impersonation_user_agent, impersonate_request};
+use crate::utils::input_client::get_input_client;
+use crate::utils::request::{apply_header_rules, get_request_headers};
+use log::{error, info};
+use rand::seq::IteratorRandom;
+use shared::model::{ChannelHealthDto, PlaylistItem, QuarantinedChannelDto, TargetType, XtreamCluster};
+use shared::utils::{sanitize_sensitive_info, Internable};
+use std::collections::HashSet;
+use std::net::{Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use url::Url;
+
+// The provider connection of a probe is not bound to a client connection.
+const PROBE_ADDR_PREFIX: u128 = 0xfd01_0000_0000_0000_0000_0000_0000_0000;
+const PROBE_READ_SIZE: usize = 64 * 1024;
+const TS_PACKET_SIZE: usize = 188;
+const TS_SYNC_BYTE: u8 = 0x47;
+
+static NEXT_PROBE_ID: AtomicU64 = AtomicU64::new(1);
+
+struct ProbeChannel {
+    input_name: Arc<str>,
+    virtual_id: u32,
+    uuid: String,
+    title: Arc<str>,
+    url: Arc<str>,
+}
+
+impl From<PlaylistItem> for ProbeChannel {
+    fn from(item: PlaylistItem) -> Self {
+        Self {
+            input_name: item.header.input_name,
+            virtual_id: item.header.virtual_id,
+            uuid: item.header.uuid.to_string(),
+            title: item.header.title,
+            url: item.header.url,
+        }
+    }
+}
+
+impl From<&QuarantinedChannelDto> for ProbeChannel {
+    fn from(channel: &QuarantinedChannelDto) -> Self {
+        Self {
+            input_name: channel.input_name.as_str().intern(),
+            virtual_id: channel.virtual_id,
+            uuid: channel.uuid.clone(),
+            title: channel.title.as_str().intern(),
+            url: channel.url.as_str().intern(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ProbeResult {
+    latency_ms: Option<u64>,
+    status: Option<u16>,
+    data: Vec<u8>,
+    error: Option<String>,
+}
+
+fn is_live(item: &PlaylistItem) -> bool {
+    item.header.item_type.is_live()
+}
+
+/// Random sample of the live channels of the target.
+async fn sample_live_channels(app_state: &AppState, target: &ConfigTarget, sample_size: usize) -> Vec<ProbeChannel> {
+    let app_config = &app_state.app_config;
+    if target.has_output(TargetType::Xtream) {
+        if let Some((_guard, items)) = iter_raw_xtream_target_playlist(app_config, target, XtreamCluster::Live).await {
+            return items.map(|item| PlaylistItem::from(&item))
+                .filter(is_live)
+                .choose_multiple(&mut rand::rng(), sample_size)
+                .into_iter().map(ProbeChannel::from).collect();
+        }
+    } else if target.has_output(TargetType::M3u) {
+        if let Some((_guard, items)) = iter_raw_m3u_target_playlist(app_config, target, Some(XtreamCluster::Live)).await {
+            return items.map(|item| PlaylistItem::from(&item))
+                .filter(is_live)
+                .choose_multiple(&mut rand::rng(), sample_size)
+                .into_iter().map(ProbeChannel::from).collect();
+        }
+    }
+    Vec::new()
+}
+
+/// Requests the stream and reads its first bytes, the latency is the time until the first bytes are received.
+async fn read_stream_start(app_state: &AppState, input: &ConfigInput, stream_url: &str, result: &mut ProbeResult) -> Result<(), String> {
+    let url = Url::parse(stream_url).map_err(|err| err.to_string())?;
+    let config = app_state.app_config.config.load();
+    let default_user_agent = input.impersonate
+        .map_or_else(|| config.default_user_agent.clone(), |profile| Some(get_impersonation_user_agent(profile).to_string()));
+    let disabled_headers = config.get_disabled_headers();
+    drop(config);
+    let client = get_input_client(&app_state.app_config, &app_state.http_client.load(), &input.name, input.impersonate);
+    let mut headers = get_request_headers(Some(&input.headers), None, disabled_headers.as_ref(), default_user_agent.as_deref());
+    apply_header_rules(&mut headers, input.get_upstream_header_rules(None).as_ref());
+    let request = client.get(url).headers(headers);
+
+    let start = Instant::now();
+    let mut response = impersonate_request(&client, request, input.impersonate).send().await.map_err(|err| err.to_string())?;
+    result.status = Some(response.status().as_u16());
+    if !response.status().is_success() {
+        return Err(format!("Provider responded with status {}", response.status()));
+    }
+    while result.data.len() < PROBE_READ_SIZE {
+        match response.chunk().await.map_err(|err| err.to_string())? {
+            Some(chunk) => {
+                if result.latency_ms.is_none() {
+                    result.latency_ms = Some(u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX));
+                }
+                result.data.extend_from_slice(&chunk);
+            }
+            None => break,
+        }
+    }
+    if result.data.is_empty() {
+        return Err("Provider sent no data".to_string());
+    }
+    Ok(())
+}
+
+async fn probe_channel(app_state: &AppState, input: &ConfigInput, channel: &ProbeChannel, timeout: Duration) -> Option<ProbeResult> {
+    let addr = SocketAddr::from((Ipv6Addr::from(PROBE_ADDR_PREFIX | u128::from(NEXT_PROBE_ID.fetch_add(1, Ordering::Relaxed))), 0));
+    let handle = app_state.active_provider.acquire_connection_with_grace_override(&input.name, &addr, false).await?;
+    let result = if let Some(provider_cfg) = handle.allocation.get_provider_config() {
+        let stream_url = get_stream_alternative_url(&channel.url, input, &provider_cfg);
+        let mut result = ProbeResult::default();
+        match tokio::time::timeout(timeout, read_stream_start(app_state, input, &stream_url, &mut result)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => result.error = Some(sanitize_sensitive_info(&err).to_string()),
+            // a live stream which sent data before the timeout is up
+            Err(_) if !result.data.is_empty() => {}
+            Err(_) => result.error = Some("Timeout".to_string()),
+        }
+        Some(result)
+    } else {
+        // the provider connections are in use, the channel is probed in the next run
+        None
+    };
+    app_state.active_provider.release_handle(&handle).await;
+    result
+}
+
+/// Probes a sample of the live channels and the quarantined channels of the target.
+async fn probe_target(app_state: &AppState, probe_config: &ChannelProbeConfig, target: &ConfigTarget,
+                      quarantine: &[QuarantinedChannelDto]) -> Vec<(ProbeChannel, ChannelHealthDto)> {
+    let mut channels = sample_live_channels(app_state, target, usize::from(probe_config.sample_size)).await;
+    channels.extend(quarantine.iter().map(ProbeChannel::from));
+    let timeout = Duration::from_secs(u64::from(probe_config.timeout_secs));
+    let mut results = Vec::with_capacity(channels.len());
+    for channel in channels {
+        let Some(input) = app_state.app_config.get_input_by_name(&channel.input_name) else {
+            continue;
+        };
+        let Some(result) = probe_channel(app_state, &input, &channel, timeout).await else {
+            debug_if_enabled!("Skipped probe of channel {}, no provider connection available", sanitize_sensitive_info(&channel.title));
+            continue;
+        };
+        let (format, codecs) = sniff_stream(&result.data);
+        let health = ChannelHealthDto {
+            target: target.name.clone(),
+            input_name: channel.input_name.to_string(),
+            virtual_id: channel.virtual_id,
+            uuid: channel.uuid.clone(),
+            title: channel.title.to_string(),
+            up: result.error.is_none(),
+            failures: 0,
+            latency_ms: result.latency_ms,
+            status: result.status,
+            format: format.map(ToString::to_string),
+            codecs: codecs.into_iter().map(ToString::to_string).collect(),
+            error: result.error,
+            ts: chrono::Utc::now().timestamp(),
+        };
+        results.push((channel, health));
+    }
+    results
+}
+
+/// Quarantines the channels which failed `remove_dead_channels` consecutive probes, recovered channels are restored.
+fn update_quarantine(remove_dead_channels: u16, quarantine: &[QuarantinedChannelDto], probed: &[(ProbeChannel, ChannelHealthDto)]) -> Vec<QuarantinedChannelDto> {
+    if remove_dead_channels == 0 {
+        return Vec::new();
+    }
+    let recovered: HashSet<&str> = probed.iter()
+        .filter(|(_, health)| health.up)
+        .map(|(_, health)| health.uuid.as_str())
+        .collect();
+    let mut result: Vec<QuarantinedChannelDto> = quarantine.iter()
+        .filter(|channel| !recovered.contains(channel.uuid.as_str()))
+        .cloned()
+        .collect();
+    for (channel, health) in probed {
+        if health.failures >= remove_dead_channels && !result.iter().any(|quarantined| quarantined.uuid == health.uuid) {
+            result.push(QuarantinedChannelDto {
+                target: health.target.clone(),
+                input_name: health.input_name.clone(),
+                uuid: health.uuid.clone(),
+                virtual_id: health.virtual_id,
+                title: health.title.clone(),
+                url: channel.url.to_string(),
+                since: health.ts,
+            });
+        }
+    }
+    result
+}
+
+async fn probe_channels(app_state: &AppState, probe_config: &ChannelProbeConfig) {
+    let targets: Vec<Arc<ConfigTarget>> = app_state.app_config.sources.load().sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .filter(|target| target.enabled && probe_config.is_probed_target(&target.name))
+        .filter(|target| target.has_output(TargetType::Xtream) || target.has_output(TargetType::M3u))
+        .map(Arc::clone)
+        .collect();
+    let target_names: Vec<String> = targets.iter().map(|target| target.name.clone()).collect();
+    let quarantine = load_channel_quarantine(&app_state.app_config).await;
+    for target in targets {
+        let remove_dead_channels = target.options.as_ref().map_or(0, |options| options.remove_dead_channels);
+        let target_quarantine: Vec<QuarantinedChannelDto> = quarantine.iter()
+            .filter(|channel| channel.target == target.name && remove_dead_channels > 0)
+            .cloned()
+            .collect();
+        let mut probed = probe_target(app_state, probe_config, &target, &target_quarantine).await;
+        let down = probed.iter().filter(|(_, health)| !health.up).count();
+        info!("Probed {} channels of target {}, {down} down", probed.len(), target.name);
+        let results = probed.iter().map(|(_, health)| health.clone()).collect();
+        match save_channel_health(&app_state.app_config, &target.name, &target_names, results).await {
+            Ok(results) => {
+                // the saved results carry the consecutive failures
+                for ((_, health), result) in probed.iter_mut().zip(results) {
+                    *health = result;
+                }
+            }
+            Err(err) => {
+                error!("{err}");
+                continue;
+            }
+        }
+
+        let new_quarantine = update_quarantine(remove_dead_channels, &target_quarantine, &probed);
+        if new_quarantine != target_quarantine || (remove_dead_channels == 0 && quarantine.iter().any(|channel| channel.target == target.name)) {
+            info!("{} dead channels of target {} are quarantined, they are removed with the next playlist update", new_quarantine.len(), target.name);
+            if let Err(err) = save_channel_quarantine(&app_state.app_config, &target.name, new_quarantine).await {
+                error!("{err}");
+            }
+        }
+    }
+}
+
+/// Probes a sample of the live channels of the targets in the configured interval.
+pub fn exec_channel_probe(app_state: &Arc<AppState>) {
+    let app_state = Arc::clone(app_state);
+    tokio::spawn({
+        async move {
+            loop {
+                let probe_config = app_state.app_config.config.load().channel_probe.clone();
+                let interval_mins = if let Some(probe_config) = probe_config {
+                    probe_channels(&app_state, &probe_config).await;
+                    probe_config.interval_mins
+                } else {
+                    // the config can be activated with a hot reload
+                    1
+                };
+                tokio::time::sleep(Duration::from_secs(u64::from(interval_mins) * 60)).await;
+            }
+        }
+    });
+}
+
+fn ts_stream_type_codec(stream_type: u8) -> Option<&'static str> {
+    match stream_type {
+        0x01 | 0x02 => Some("mpeg2"),
+        0x03 | 0x04 => Some("mp2"),
+        0x0F | 0x11 => Some("aac"),
+        0x1B => Some("h264"),
+        0x24 => Some("hevc"),
+        0x81 => Some("ac3"),
+        0x87 => Some("eac3"),
+        _ => None,
+    }
+}
+
+fn ts_section_length(section: &[u8]) -> Option<usize> {
+    Some((usize::from(*section.get(1)? & 0x0F) << 8) | usize::from(*section.get(2)?))
+}
+
+/// Codecs of the first program of a transport stream, read from the program map table.
+fn sniff_ts_codecs(data: &[u8]) -> Vec<&'static str> {
+    let mut pmt_pid = None;
+    for packet in data.chunks_exact(TS_PACKET_SIZE) {
+        if packet[0] != TS_SYNC_BYTE || packet[1] & 0x40 == 0 {
+            continue;
+        }
+        let pid = (u16::from(packet[1] & 0x1F) << 8) | u16::from(packet[2]);
+        let mut offset = 4;
+        match (packet[3] >> 4) & 0x03 {
+            0x01 => {}
+            0x03 => offset += 1 + usize::from(packet[4]),
+            _ => continue,
+        }
+        let Some(pointer) = packet.get(offset) else { continue };
+        let Some(section) = packet.get(offset + 1 + usize::from(*pointer)..) else { continue };
+        let Some(section_end) = ts_section_length(section).map(|length| (3 + length).saturating_sub(4).min(section.len())) else { continue };
+        if pid == 0 && section.first() == Some(&0x00) {
+            pmt_pid = section.get(8..section_end).unwrap_or_default().chunks_exact(4)
+                .find(|program| program[0] != 0 || program[1] != 0)
+                .map(|program| (u16::from(program[2] & 0x1F) << 8) | u16::from(program[3]));
+        } else if Some(pid) == pmt_pid && section.first() == Some(&0x02) {
+            let mut codecs = Vec::new();
+            let Some(program_info_length) = section.get(10..12).map(|b| (usize::from(b[0] & 0x0F) << 8) | usize::from(b[1])) else { break };
+            let mut index = 12 + program_info_length;
+            while let Some(stream) = section.get(index..index + 5).filter(|_| index + 5 <= section_end) {
+                if let Some(codec) = ts_stream_type_codec(stream[0]).filter(|codec| !codecs.contains(codec)) {
+                    codecs.push(codec);
+                }
+                index += 5 + ((usize::from(stream[3] & 0x0F) << 8) | usize::from(stream[4]));
+            }
+            return codecs;
+        }
+    }
+    Vec::new()
+}
+
+/// Container and codecs of the first bytes of a stream.
+fn sniff_stream(data: &[u8]) -> (Option<&'static str>, Vec<&'static str>) {
+    if data.first() == Some(&TS_SYNC_BYTE) && data.get(TS_PACKET_SIZE).is_none_or(|byte| *byte == TS_SYNC_BYTE) {
+        return (Some("mpegts"), sniff_ts_codecs(data));
+    }
+    let head = String::from_utf8_lossy(&data[..data.len().min(512)]);
+    if head.trim_start().starts_with("#EXTM3U") {
+        (Some("hls"), Vec::new())
+    } else if head.contains("<MPD") {
+        (Some("dash"), Vec::new())
+    } else if data.get(4..8) == Some(b"ftyp") {
+        (Some("mp4"), Vec::new())
+    } else if data.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        (Some("mkv"), Vec::new())
+    } else {
+        (None, Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sniff_stream, update_quarantine, ProbeChannel, TS_PACKET_SIZE};
+    use shared::model::{ChannelHealthDto, QuarantinedChannelDto};
+
+    fn ts_packet(pid: u16, section: &[u8]) -> Vec<u8> {
+        let mut packet = vec![0x47, 0x40 | u8::try_from(pid >> 8).unwrap(), u8::try_from(pid & 0xFF).unwrap(), 0x10, 0x00];
+        packet.extend_from_slice(section);
+        packet.resize(TS_PACKET_SIZE, 0xFF);
+        packet
+    }
+
+    #[test]
+    fn test_sniff_stream() {
+        // PAT with program 1 on pid 0x100, PMT with h264 and aac
+        let mut data = ts_packet(0, &[0x00, 0xB0, 0x0D, 0x00, 0x01, 0xC1, 0x00, 0x00, 0x00, 0x01, 0xE1, 0x00, 0, 0, 0, 0]);
+        data.extend(ts_packet(0x100, &[0x02, 0xB0, 0x17, 0x00, 0x01, 0xC1, 0x00, 0x00, 0xE1, 0x01, 0xF0, 0x00,
+            0x1B, 0xE1, 0x01, 0xF0, 0x00, 0x0F, 0xE1, 0x02, 0xF0, 0x00, 0, 0, 0, 0]));
+        assert_eq!(sniff_stream(&data), (Some("mpegts"), vec!["h264", "aac"]));
+
+        assert_eq!(sniff_stream(b"#EXTM3U\n#EXT-X-VERSION:3\n"), (Some("hls"), Vec::new()));
+        assert_eq!(sniff_stream(b"<?xml version=\"1.0\"?><MPD>"), (Some("dash"), Vec::new()));
+        assert_eq!(sniff_stream(b"<html></html>"), (None, Vec::new()));
+    }
+
+    fn probed(uuid: &str, up: bool, failures: u16) -> (ProbeChannel, ChannelHealthDto) {
+        let channel = ProbeChannel {
+            input_name: "provider".into(),
+            virtual_id: 1,
+            uuid: uuid.to_string(),
+            title: uuid.into(),
+            url: format!("http://provider/live/{uuid}.ts").into(),
+        };
+        let health = ChannelHealthDto {
+            target: "target".to_string(),
+            input_name: "provider".to_string(),
+            virtual_id: 1,
+            uuid: uuid.to_string(),
+            title: uuid.to_string(),
+            up,
+            failures,
+            latency_ms: None,
+            status: None,
+            format: None,
+            codecs: Vec::new(),
+            error: None,
+            ts: 100,
+        };
+        (channel, health)
+    }
+
+    #[test]
+    fn test_update_quarantine() {
+        let quarantine = update_quarantine(3, &[], &[probed("a", false, 3), probed("b", false, 2), probed("c", true, 0)]);
+        assert_eq!(quarantine.iter().map(|c| c.uuid.as_str()).collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(quarantine[0].url, "http://provider/live/a.ts");
+
+        // quarantined channels are kept until a probe succeeds
+        let quarantine: Vec<QuarantinedChannelDto> = update_quarantine(3, &quarantine, &[probed("a", false, 4), probed("b", false, 3)]);
+        assert_eq!(quarantine.iter().map(|c| (c.uuid.as_str(), c.since)).collect::<Vec<_>>(), vec![("a", 100), ("b", 100)]);
+        let quarantine = update_quarantine(3, &quarantine, &[probed("a", true, 0)]);
+        assert_eq!(quarantine.iter().map(|c| c.uuid.as_str()).collect::<Vec<_>>(), vec!["b"]);
+
+        assert!(update_quarantine(0, &quarantine, &[probed("c", false, 5)]).is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::api::endpoints::v1_api_user::clear_changed_cached_responses;
 use crate::api::model::{update_app_state_config, update_app_state_sources, AppState, EventMessage};
 use crate::model::{Config, SourcesConfig};
 use crate::utils;
@@ -6,6 +7,7 @@ use arc_swap::access::Access;
 use arc_swap::ArcSwap;
 use log::{debug, error, info};
 use shared::error::TuliproxError;
+use shared::foundation::RESOLUTION_CACHE;
 use shared::model::{ConfigPaths, ConfigType};
 use std::path::Path;
 use std::sync::Arc;
@@ -45,7 +47,11 @@ impl ConfigFile {
     async fn load_api_proxy(app_state: &Arc<AppState>) -> Result<(), TuliproxError> {
         match utils::read_api_proxy_config(&app_state.app_config, true).await {
             Ok(Some(api_proxy)) => {
+                let old_api_proxy = app_state.app_config.api_proxy.load_full();
                 app_state.app_config.set_api_proxy(api_proxy)?;
+                if let Some(new_api_proxy) = app_state.app_config.api_proxy.load_full() {
+                    clear_changed_cached_responses(app_state, old_api_proxy.as_deref(), &new_api_proxy).await;
+                }
                 let paths = <Arc<ArcSwap<ConfigPaths>> as Access<ConfigPaths>>::load(&app_state.app_config.paths);
                 info!("Loaded Api Proxy File: {:?}", &paths.api_proxy_file_path);
             }
@@ -98,6 +104,7 @@ impl ConfigFile {
 
     pub(crate) async fn reload(&self, file_path: &Path, app_state: &Arc<AppState>) -> Result<(), TuliproxError> {
         debug!("File change detected {}", file_path.display());
+        RESOLUTION_CACHE.clear();
         match self {
             ConfigFile::ApiProxy => {
                 ConfigFile::load_api_proxy(app_state).await?;
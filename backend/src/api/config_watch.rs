@@ -1,7 +1,7 @@
 use crate::api::config_file::ConfigFile;
 use crate::api::model::{AppState, EventMessage};
 use crate::model::{Config, SourcesConfig};
-use crate::utils;
+use crate::processing::processor::playlist::exec_processing;
 use crate::utils::is_directory;
 use arc_swap::access::Access;
 use arc_swap::ArcSwap;
@@ -10,7 +10,7 @@ use notify::event::{AccessKind, AccessMode};
 use notify::{recommended_watcher, EventKind, RecursiveMode, Watcher};
 use shared::error::{TuliproxError, TuliproxErrorKind};
 use shared::model::ConfigPaths;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
@@ -24,23 +24,7 @@ fn start_config_watch(
     let (std_tx, std_rx) = std::sync::mpsc::channel();
     let (tx, mut rx) = tokio::sync::mpsc::channel(100);
 
-    let paths =
-        <Arc<ArcSwap<ConfigPaths>> as Access<ConfigPaths>>::load(&app_state.app_config.paths);
-    let mapping_file_path = paths
-        .mapping_file_path
-        .as_ref()
-        .map_or_else(String::new, ToString::to_string);
-    let files = get_watch_files(app_state, &paths, mapping_file_path.as_str());
-    //
-    // // Add a path to be watched. All files and directories at that path and
-    // // below will be monitored for changes.
-    let path = Path::new(paths.config_path.as_str());
-    let recursive_mode = if !mapping_file_path.is_empty() && utils::is_directory(&mapping_file_path)
-    {
-        RecursiveMode::Recursive
-    } else {
-        RecursiveMode::NonRecursive
-    };
+    let (config_path, mut files) = get_watch_files(app_state);
 
     std::thread::spawn({
         let tx = tx.clone();
@@ -58,13 +42,15 @@ fn start_config_watch(
             format!("Failed to init config file watcher {err}"),
         )
     })?;
-    watcher.watch(path, recursive_mode).map_err(|err| {
+    watcher.watch(&config_path, RecursiveMode::NonRecursive).map_err(|err| {
         TuliproxError::new(
             TuliproxErrorKind::Info,
             format!("Failed to start config file watcher {err}"),
         )
     })?;
-    info!("Watching config file changes {}", path.display());
+    info!("Watching config file changes {}", config_path.display());
+    let mut watched_dirs = HashSet::from([config_path]);
+    watch_dirs(&mut watcher, &files, &mut watched_dirs);
 
     let event_manager = Arc::clone(&app_state.event_manager);
     let cancel = cancel_token.clone();
@@ -79,12 +65,16 @@ fn start_config_watch(
     tokio::spawn(async move {
         info!("Configuration file watcher started.");
 
-        let _keep_watcher_alive = watcher;
+        let mut watcher = watcher;
 
         let mut debounce_timer =
             Box::pin(tokio::time::sleep(tokio::time::Duration::from_millis(0)));
         let mut timer_active = false;
         let mut pending_configs: HashMap<ConfigFile, PathBuf> = HashMap::new();
+        let mut reprocess_timer =
+            Box::pin(tokio::time::sleep(tokio::time::Duration::from_millis(0)));
+        let mut reprocess_active = false;
+        let mut reprocess_targets: HashSet<String> = HashSet::new();
 
         loop {
             tokio::select! {
@@ -114,7 +104,7 @@ fn start_config_watch(
                                 let mut resolved = None;
                                 if let Some((config_file, _is_dir)) = files.get(&path) {
                                     resolved = Some(*config_file);
-                                } else if recursive_mode == RecursiveMode::Recursive && path.extension().is_some_and(|ext| ext == "yml") {
+                                } else if path.extension().is_some_and(|ext| ext == "yml") {
                                     for (key, (config_file, is_dir)) in &files {
                                         if *is_dir && path.starts_with(key) {
                                             resolved = Some(*config_file);
@@ -144,14 +134,43 @@ fn start_config_watch(
             () = &mut debounce_timer, if timer_active => {
                 timer_active = false;
                 if !pending_configs.is_empty() {
+                    // the config can change the mapping path and the sources their input files
+                    let mut refresh_files = false;
                     for (config_file, path) in pending_configs.drain() {
+                        let mappings = (config_file == ConfigFile::Mapping).then(|| get_target_mappings(&watcher_app_state));
                         if let Err(err) = config_file.reload(&path, &watcher_app_state).await {
                            handle_error(err, &path);
+                        } else {
+                           refresh_files |= matches!(config_file, ConfigFile::Config | ConfigFile::Sources);
+                           if let Some(mappings) = mappings {
+                               reprocess_targets.extend(get_changed_mapping_targets(&watcher_app_state, &mappings));
+                           }
+                        }
+                    }
+                    if refresh_files {
+                        files = get_watch_files(&watcher_app_state).1;
+                        watch_dirs(&mut watcher, &files, &mut watched_dirs);
+                    }
+                    let reprocess = <Arc<ArcSwap<Config>> as Access<Config>>::load(&watcher_app_state.app_config.config).mapping_reprocess;
+                    match reprocess {
+                        Some(reprocess) if !reprocess_targets.is_empty() => {
+                            // each mapping change restarts the delay
+                            reprocess_timer = Box::pin(tokio::time::sleep(tokio::time::Duration::from_secs(reprocess.debounce_secs)));
+                            reprocess_active = true;
                         }
+                        _ => reprocess_targets.clear(),
                     }
                 }
             }
 
+            () = &mut reprocess_timer, if reprocess_active => {
+                reprocess_active = false;
+                let targets: Vec<String> = reprocess_targets.drain().collect();
+                if let Some(reprocess) = <Arc<ArcSwap<Config>> as Access<Config>>::load(&watcher_app_state.app_config.config).mapping_reprocess {
+                    start_mapping_reprocess(&watcher_app_state, targets, reprocess.confirm);
+                }
+            }
+
             else => break, // Channel closed
             }
         }
@@ -161,11 +180,91 @@ fn start_config_watch(
     Ok(())
 }
 
-fn get_watch_files(
-    app_state: &Arc<AppState>,
-    paths: &ConfigPaths,
-    mapping_file_path: &str,
-) -> HashMap<PathBuf, (ConfigFile, bool)> {
+/// Returns the serialized mappings of the enabled targets with mappings.
+fn get_target_mappings(app_state: &Arc<AppState>) -> HashMap<String, String> {
+    let sources = <Arc<ArcSwap<SourcesConfig>> as Access<SourcesConfig>>::load(&app_state.app_config.sources);
+    sources.sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .filter(|target| target.enabled && target.mapping_ids.is_some())
+        .map(|target| {
+            let mappings = target.mapping.load();
+            // the templates are not serialized with the mapping, but they change the mapper scripts
+            let content = mappings.as_deref().map(|mappings| mappings.iter()
+                .map(|mapping| (mapping, mapping.templates.as_ref()))
+                .collect::<Vec<_>>());
+            (target.name.clone(), serde_json::to_string(&content).unwrap_or_default())
+        })
+        .collect()
+}
+
+/// Returns the targets whose mappings differ from the mappings before the reload.
+fn get_changed_mapping_targets(app_state: &Arc<AppState>, old_mappings: &HashMap<String, String>) -> Vec<String> {
+    get_target_mappings(app_state).into_iter()
+        .filter(|(name, content)| old_mappings.get(name) != Some(content))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Starts the processing of the targets or asks the web ui for confirmation.
+fn start_mapping_reprocess(app_state: &Arc<AppState>, mut targets: Vec<String>, confirm: bool) {
+    targets.sort();
+    if confirm {
+        info!("Mappings changed for targets {}, waiting for confirmation", targets.join(", "));
+        app_state.event_manager.send_event(EventMessage::MappingReprocess(targets));
+        return;
+    }
+    let sources = <Arc<ArcSwap<SourcesConfig>> as Access<SourcesConfig>>::load(&app_state.app_config.sources);
+    match sources.validate_targets(Some(&targets)) {
+        Ok(process_targets) => {
+            info!("Mappings changed, processing targets {}", targets.join(", "));
+            let client = app_state.http_client.load_full().as_ref().clone();
+            let app_config = Arc::clone(&app_state.app_config);
+            let event_manager = Arc::clone(&app_state.event_manager);
+            let playlist_state = Arc::clone(&app_state.playlists);
+            let update_guard = app_state.update_guard.clone();
+            let disabled_headers = app_state.get_disabled_headers();
+            tokio::spawn(async move {
+                exec_processing(&client, app_config, Arc::new(process_targets), Some(event_manager),
+                                Some(playlist_state), Some(update_guard), disabled_headers).await;
+            });
+        }
+        Err(err) => error!("Failed to process targets of changed mappings: {err}"),
+    }
+}
+
+/// Watches the directories of the files outside the config directory, mapping directories recursively.
+/// A directory which can't be watched is logged, the other files are still watched.
+fn watch_dirs(
+    watcher: &mut impl Watcher,
+    files: &HashMap<PathBuf, (ConfigFile, bool)>,
+    watched_dirs: &mut HashSet<PathBuf>,
+) {
+    for (path, (_config_file, is_dir)) in files {
+        let (dir, recursive_mode) = if *is_dir {
+            (path.as_path(), RecursiveMode::Recursive)
+        } else {
+            match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => (parent, RecursiveMode::NonRecursive),
+                _ => continue,
+            }
+        };
+        if watched_dirs.insert(dir.to_path_buf()) {
+            match watcher.watch(dir, recursive_mode) {
+                Ok(()) => info!("Watching config file changes {}", dir.display()),
+                Err(err) => error!("Failed to watch config file changes {}: {err}", dir.display()),
+            }
+        }
+    }
+}
+
+/// Returns the config directory and the watched files with their type and if they are a directory.
+fn get_watch_files(app_state: &Arc<AppState>) -> (PathBuf, HashMap<PathBuf, (ConfigFile, bool)>) {
+    let paths =
+        <Arc<ArcSwap<ConfigPaths>> as Access<ConfigPaths>>::load(&app_state.app_config.paths);
+    let mapping_file_path = paths
+        .mapping_file_path
+        .as_ref()
+        .map_or_else(String::new, ToString::to_string);
     let sources =
         <Arc<ArcSwap<SourcesConfig>> as Access<SourcesConfig>>::load(&app_state.app_config.sources);
     let input_files_paths = sources.get_input_files();
@@ -173,7 +272,7 @@ fn get_watch_files(
     [
         (paths.config_file_path.as_str(), ConfigFile::Config),
         (paths.api_proxy_file_path.as_str(), ConfigFile::ApiProxy),
-        (mapping_file_path, ConfigFile::Mapping),
+        (mapping_file_path.as_str(), ConfigFile::Mapping),
         (paths.sources_file_path.as_str(), ConfigFile::Sources),
     ]
     .into_iter()
@@ -184,7 +283,7 @@ fn get_watch_files(
     for path in input_files_paths {
         files.insert(path, (ConfigFile::SourceFile, false));
     }
-    files
+    (PathBuf::from(paths.config_path.as_str()), files)
 }
 
 pub fn exec_config_watch(app_state: &Arc<AppState>, cancel: &CancellationToken) {
@@ -0,0 +1,42 @@
+use crate::api::api_utils::try_unwrap_body;
+use crate::api::model::AppState;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+/// Hand maintained `OpenAPI` description of the v1 api, new endpoints have to be added here.
+const OPENAPI_SPEC: &str = include_str!("openapi.json");
+
+async fn openapi_spec() -> axum::response::Response {
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, mime::APPLICATION_JSON.to_string())
+        .body(axum::body::Body::from(OPENAPI_SPEC)))
+}
+
+pub fn api_docs_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router.route("/openapi.json", axum::routing::get(openapi_spec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OPENAPI_SPEC;
+    use shared::model::ApiErrorCode;
+
+    #[test]
+    fn test_openapi_spec() {
+        let spec: serde_json::Value = serde_json::from_str(OPENAPI_SPEC).unwrap();
+        let paths = spec["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/status"));
+        assert!(paths.keys().all(|path| path.starts_with('/')));
+        assert!(paths.values().flat_map(|ops| ops.as_object().unwrap().values()).all(|op| op["tags"].is_array()));
+    }
+
+    #[test]
+    fn test_openapi_error_codes() {
+        let spec: serde_json::Value = serde_json::from_str(OPENAPI_SPEC).unwrap();
+        let codes: Vec<&str> = spec["components"]["schemas"]["ApiErrorCode"]["enum"].as_array().unwrap()
+            .iter().filter_map(serde_json::Value::as_str).collect();
+        let expected: Vec<&str> = ApiErrorCode::ALL.iter().map(|code| code.as_str()).collect();
+        assert_eq!(codes, expected);
+    }
+}
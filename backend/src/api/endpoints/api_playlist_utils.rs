@@ -1,10 +1,9 @@
 use crate::model::{AppConfig, ConfigInput, ConfigTarget};
 use crate::utils::{m3u, xtream};
 use axum::response::IntoResponse;
-use serde_json::{json};
-use shared::model::{InputType, M3uPlaylistItem, PlaylistItemType, TargetType, UiPlaylistItem, XtreamCluster, XtreamPlaylistItem};
+use shared::model::{ApiErrorCode, InputType, M3uPlaylistItem, PlaylistItemType, TargetType, UiPlaylistItem, XtreamCluster, XtreamPlaylistItem};
 use std::sync::Arc;
-use crate::api::api_utils::{empty_json_list_response, json_or_bin_response, stream_json_or_bin_response};
+use crate::api::api_utils::{api_error_response, api_error_response_with_detail, empty_json_list_response, json_or_bin_response, stream_json_or_bin_response};
 use shared::utils::interner_gc;
 use crate::repository::{iter_raw_m3u_input_playlist, iter_raw_m3u_target_playlist, iter_raw_xtream_input_playlist, iter_raw_xtream_target_playlist};
 
@@ -35,7 +34,7 @@ pub(in crate::api::endpoints) async fn get_playlist_for_target(cfg_target: Optio
             return stream_json_or_bin_response(accept, converted_iterator).into_response();
         }
     }
-    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Invalid Arguments"}))).into_response()
+    api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid Arguments")
 }
 
 
@@ -47,7 +46,7 @@ pub(in crate::api::endpoints) async fn get_playlist_for_input(cfg_input: Option<
             };
             let converted_iterator: Box<dyn Iterator<Item=UiPlaylistItem> + Send> = Box::new(channel_iterator.map(UiPlaylistItem::from));
             return stream_json_or_bin_response(accept, converted_iterator).into_response();
-        } else if matches!(input.input_type, InputType::M3u | InputType::M3uBatch) {
+        } else if matches!(input.input_type, InputType::M3u | InputType::M3uBatch | InputType::Udp) {
             let Some((_guard, channels)) = iter_raw_m3u_input_playlist(cfg, input, Some(cluster)).await else {
               return empty_json_list_response();
             };
@@ -55,7 +54,7 @@ pub(in crate::api::endpoints) async fn get_playlist_for_input(cfg_input: Option<
             return stream_json_or_bin_response(accept, converted_iterator).into_response();
         }
     }
-    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Invalid Arguments"}))).into_response()
+    api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid Arguments")
 }
 
 pub(in crate::api::endpoints) async fn get_playlist_for_custom_provider(client: &reqwest::Client, cfg_input: Option<&Arc<ConfigInput>>, app_config: &Arc<AppConfig>, cluster: XtreamCluster, accept: Option<&str>) -> impl IntoResponse + Send {
@@ -64,24 +63,27 @@ pub(in crate::api::endpoints) async fn get_playlist_for_custom_provider(client:
         Some(input) => {
             let (result, errors) =
                 match input.input_type {
-                    InputType::M3u | InputType::M3uBatch => m3u::download_m3u_playlist(app_config, client, &cfg, input).await,
+                    InputType::M3u | InputType::M3uBatch | InputType::Udp => {
+                        let (pl, err, _) = m3u::download_m3u_playlist(app_config, client, &cfg, input).await;
+                        (pl, err)
+                    }
                     InputType::Xtream | InputType::XtreamBatch => {
                         let (pl, err, _) = xtream::download_xtream_playlist(app_config, client, input, Some(&[cluster])).await;
                         (pl, err)
                     }
                     InputType::Library => {
-                        return (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "error": "Library inputs are not supported on this endpoint"}))).into_response();
+                        return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UnsupportedOperation, "Library inputs are not supported on this endpoint");
                     }
                 };
             if result.is_empty() {
                 let error_strings: Vec<String> = errors.iter().map(ToString::to_string).collect();
-                (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": error_strings.join(", ")}))).into_response()
+                api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UpstreamError, "Failed to download playlist", error_strings.join(", "))
             } else {
                 let channels: Vec<UiPlaylistItem> = result.iter().flat_map(|g| g.channels.iter()).map(UiPlaylistItem::from).collect();
                 interner_gc();
                 json_or_bin_response(accept, &channels).into_response()
             }
         }
-        None => (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Invalid Arguments"}))).into_response(),
+        None => api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid Arguments"),
     }
 }
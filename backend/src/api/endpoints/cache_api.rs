@@ -0,0 +1,45 @@
+use crate::api::api_utils::{api_error_response, api_error_response_with_detail, publish_removed_cache_keys};
+use crate::api::model::AppState;
+use crate::auth::require_admin;
+use axum::response::IntoResponse;
+use log::info;
+use shared::model::{ApiErrorCode, CachePurgeRequest, CachePurgeResultDto};
+use shared::utils::human_readable_byte_size;
+use std::sync::Arc;
+
+fn cache_not_configured() -> axum::response::Response {
+    api_error_response(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::NotConfigured, "Cache is not enabled")
+}
+
+async fn cache_usage(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::response::Response {
+    let Some(cache) = app_state.cache.load_full() else {
+        return cache_not_configured();
+    };
+    let max_age_days = app_state.app_config.get_cache_max_age_days();
+    let usage = cache.lock().await.get_usage(max_age_days);
+    axum::Json(usage).into_response()
+}
+
+/// Removes the cached resources whose url matches the pattern, without a pattern the cache is cleared.
+async fn cache_purge(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(request): axum::extract::Json<CachePurgeRequest>,
+) -> axum::response::Response {
+    let Some(cache) = app_state.cache.load_full() else {
+        return cache_not_configured();
+    };
+    let pattern = match request.pattern.as_deref().map(str::trim).filter(|p| !p.is_empty()).map(regex::Regex::new).transpose() {
+        Ok(pattern) => pattern,
+        Err(err) => return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid pattern", err.to_string()),
+    };
+    let (removed, freed) = cache.lock().await.purge(pattern.as_ref());
+    publish_removed_cache_keys(&cache, app_state.cluster.as_deref()).await;
+    info!("Purged {removed} cache entries, freed {}", human_readable_byte_size(freed));
+    axum::Json(CachePurgeResultDto { removed, freed }).into_response()
+}
+
+pub fn cache_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/cache", axum::routing::get(cache_usage))
+        .route("/cache/purge", axum::routing::post(cache_purge).layer(axum::middleware::from_fn(require_admin)))
+}
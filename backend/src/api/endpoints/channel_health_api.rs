@@ -0,0 +1,17 @@
+use crate::api::model::AppState;
+use crate::repository::{load_channel_health, load_channel_quarantine, load_playback_errors};
+use shared::model::ChannelHealthReportDto;
+use std::sync::Arc;
+
+// The report contains the playback errors of the last day.
+const PLAYBACK_ERRORS_PERIOD_SECS: i64 = 86_400;
+
+async fn channel_health(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::Json<ChannelHealthReportDto> {
+    let app_config = &app_state.app_config;
+    let playback_errors = load_playback_errors(app_config, chrono::Utc::now().timestamp() - PLAYBACK_ERRORS_PERIOD_SECS).await;
+    axum::Json(ChannelHealthReportDto::new(load_channel_health(app_config).await, load_channel_quarantine(app_config).await, &playback_errors))
+}
+
+pub fn channel_health_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router.route("/channels/health", axum::routing::get(channel_health))
+}
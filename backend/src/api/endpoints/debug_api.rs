@@ -0,0 +1,152 @@
+use crate::api::api_utils::api_error_response;
+use crate::api::model::AppState;
+use crate::auth::require_admin;
+use crate::tools::alloc_stats::get_allocator_stats;
+use axum::response::IntoResponse;
+use shared::model::{ApiErrorCode, DebugStatsDto, ProcessStatsDto, RuntimeStatsDto, TaskStatsDto};
+use std::sync::Arc;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System};
+
+const PROFILING_ENABLED: bool = cfg!(all(unix, feature = "profiling"));
+
+/// The debug endpoints are only served when `api.debug_endpoints` is enabled.
+fn debug_endpoints_disabled(app_state: &AppState) -> Option<axum::response::Response> {
+    if app_state.app_config.config.load().api.debug_endpoints {
+        None
+    } else {
+        Some(api_error_response(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::NotConfigured, "Debug endpoints are not enabled"))
+    }
+}
+
+fn get_process_stats() -> ProcessStatsDto {
+    let pid = Pid::from_u32(std::process::id());
+    let mut sys = System::new_with_specifics(RefreshKind::nothing()
+        .with_processes(ProcessRefreshKind::nothing().with_memory()));
+    sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    let (memory, virtual_memory) = sys.process(pid).map_or((0, 0), |proc| (proc.memory(), proc.virtual_memory()));
+    ProcessStatsDto {
+        pid: pid.as_u32(),
+        memory,
+        virtual_memory,
+    }
+}
+
+fn get_runtime_stats() -> RuntimeStatsDto {
+    let metrics = tokio::runtime::Handle::current().metrics();
+    RuntimeStatsDto {
+        workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+        global_queue_depth: metrics.global_queue_depth(),
+    }
+}
+
+async fn get_task_stats(app_state: &AppState) -> TaskStatsDto {
+    let (active_users, active_user_connections) = app_state.active_users.active_users_and_connections().await;
+    TaskStatsDto {
+        active_users,
+        active_user_connections,
+        active_user_streams: app_state.active_users.active_streams().await.len(),
+        active_provider_connections: app_state.active_provider.get_provider_connections_count().await,
+    }
+}
+
+async fn debug_stats(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::response::Response {
+    if let Some(response) = debug_endpoints_disabled(&app_state) {
+        return response;
+    }
+    let stats = DebugStatsDto {
+        process: get_process_stats(),
+        allocator: get_allocator_stats(),
+        runtime: get_runtime_stats(),
+        tasks: get_task_stats(&app_state).await,
+        profiling: PROFILING_ENABLED,
+    };
+    axum::Json(stats).into_response()
+}
+
+#[cfg(all(unix, feature = "profiling"))]
+#[derive(Debug, serde::Deserialize)]
+struct CpuProfileQuery {
+    #[serde(default)]
+    seconds: Option<u64>,
+    #[serde(default)]
+    frequency: Option<i32>,
+}
+
+#[cfg(all(unix, feature = "profiling"))]
+mod cpu_profile {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static PROFILE_RUNNING: AtomicBool = AtomicBool::new(false);
+
+    pub(super) enum CpuProfileError {
+        AlreadyInProgress,
+        Failed(String),
+    }
+
+    /// Samples all threads for `seconds` and renders the stacks as flamegraph svg.
+    /// The sampling blocks a thread of the blocking pool, only one profile can be captured at a time.
+    pub(super) async fn capture_cpu_profile(seconds: u64, frequency: i32) -> Result<Vec<u8>, CpuProfileError> {
+        if PROFILE_RUNNING.swap(true, Ordering::AcqRel) {
+            return Err(CpuProfileError::AlreadyInProgress);
+        }
+        let result = tokio::task::spawn_blocking(move || {
+            let guard = pprof::ProfilerGuardBuilder::default()
+                .frequency(frequency)
+                .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+                .build()
+                .map_err(|err| err.to_string())?;
+            std::thread::sleep(std::time::Duration::from_secs(seconds));
+            let report = guard.report().build().map_err(|err| err.to_string())?;
+            let mut svg = Vec::new();
+            report.flamegraph(&mut svg).map_err(|err| err.to_string())?;
+            Ok::<Vec<u8>, String>(svg)
+        }).await;
+        PROFILE_RUNNING.store(false, Ordering::Release);
+        match result {
+            Ok(Ok(svg)) => Ok(svg),
+            Ok(Err(err)) => Err(CpuProfileError::Failed(err)),
+            Err(err) => Err(CpuProfileError::Failed(err.to_string())),
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "profiling"))]
+async fn debug_cpu_profile(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(query): axum::extract::Query<CpuProfileQuery>,
+) -> axum::response::Response {
+    use crate::api::api_utils::api_error_response_with_detail;
+    use cpu_profile::{capture_cpu_profile, CpuProfileError};
+
+    if let Some(response) = debug_endpoints_disabled(&app_state) {
+        return response;
+    }
+    let seconds = query.seconds.unwrap_or(10).clamp(1, 120);
+    let frequency = query.frequency.unwrap_or(99).clamp(1, 1000);
+    match capture_cpu_profile(seconds, frequency).await {
+        Ok(svg) => axum::response::Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .header(axum::http::header::CONTENT_TYPE, "image/svg+xml")
+            .body(axum::body::Body::from(svg))
+            .unwrap_or_else(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+        Err(CpuProfileError::AlreadyInProgress) => api_error_response(axum::http::StatusCode::CONFLICT, ApiErrorCode::AlreadyInProgress, "A cpu profile is already captured"),
+        Err(CpuProfileError::Failed(err)) => api_error_response_with_detail(axum::http::StatusCode::INTERNAL_SERVER_ERROR, ApiErrorCode::InternalError, "Failed to capture cpu profile", err),
+    }
+}
+
+#[cfg(not(all(unix, feature = "profiling")))]
+async fn debug_cpu_profile(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    if let Some(response) = debug_endpoints_disabled(&app_state) {
+        return response;
+    }
+    api_error_response(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::NotConfigured, "Tuliprox is not built with the profiling feature")
+}
+
+pub fn debug_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/debug/stats", axum::routing::get(debug_stats).layer(axum::middleware::from_fn(require_admin)))
+        .route("/debug/pprof/profile", axum::routing::get(debug_cpu_profile).layer(axum::middleware::from_fn(require_admin)))
+}
@@ -1,3 +1,4 @@
+use crate::api::api_utils::{api_error_response, api_error_response_with_detail};
 use crate::api::model::AppState;
 use crate::api::model::{DownloadQueue, FileDownload, FileDownloadRequest};
 use crate::model::{AppConfig, VideoDownloadConfig};
@@ -12,6 +13,7 @@ use std::ops::Deref;
 use std::sync::Arc;
 use tokio::fs;
 use axum::response::IntoResponse;
+use shared::model::ApiErrorCode;
 use shared::utils::bytes_to_megabytes;
 use shared::error::to_io_error;
 use crate::utils::request::create_client;
@@ -146,7 +148,7 @@ pub async fn queue_download_file(
     if let Some(video_cfg) = config.video.as_ref() {
         if let Some(download_cfg) = video_cfg.download.as_ref() {
             if download_cfg.directory.is_empty() {
-                return (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Server config missing video.download.directory configuration"}))).into_response();
+                return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::NotConfigured, "Server config missing video.download.directory configuration");
             }
             match FileDownload::new(req.url.as_str(), req.filename.as_str(), download_cfg) {
                 Some(file_download) => {
@@ -154,18 +156,18 @@ pub async fn queue_download_file(
                     if app_state.downloads.active.read().await.is_none() {
                         match run_download_queue(&app_state.app_config, download_cfg, &app_state.downloads).await {
                             Ok(()) => {}
-                            Err(err) => return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(json!({"error": err}))).into_response(),
+                            Err(err) => return api_error_response_with_detail(axum::http::StatusCode::INTERNAL_SERVER_ERROR, ApiErrorCode::InternalError, "Failed to start download", err),
                         }
                     }
                     axum::Json(download_info!(&file_download)).into_response()
                 }
-                None => (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Invalid Arguments"}))).into_response(),
+                None => api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid Arguments"),
             }
         } else {
-            (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Server config missing video.download configuration"}))).into_response()
+            api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::NotConfigured, "Server config missing video.download configuration")
         }
     } else {
-        (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Server config missing video configuration"}))).into_response()
+        api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::NotConfigured, "Server config missing video configuration")
     }
 }
 
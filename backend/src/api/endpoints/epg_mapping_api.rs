@@ -0,0 +1,68 @@
+use crate::api::api_utils::api_error_response_with_detail;
+use crate::api::model::AppState;
+use crate::auth::require_operator;
+use crate::processing::processor::epg::{get_epg_mapping_suggestions, search_epg_mapping_channels};
+use crate::repository::update_epg_mappings;
+use axum::response::IntoResponse;
+use log::error;
+use serde::Deserialize;
+use shared::model::{ApiErrorCode, EpgMappingDecisionDto};
+use std::sync::Arc;
+
+fn target_not_found(target_id: u16) -> axum::response::Response {
+    api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::TargetNotFound, "Target not found", target_id.to_string())
+}
+
+/// Returns the live channels of the target without epg and the ranked epg channel candidates.
+async fn epg_mapping_suggestions(
+    axum::extract::Path(target_id): axum::extract::Path<u16>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    match app_state.app_config.get_target_by_id(target_id) {
+        Some(target) => axum::Json(get_epg_mapping_suggestions(&app_state.app_config, &target).await).into_response(),
+        None => target_not_found(target_id),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EpgMappingSearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+/// Searches the epg channels of all epg sources of the target inputs to assign one manually.
+async fn epg_mapping_search(
+    axum::extract::Path(target_id): axum::extract::Path<u16>,
+    axum::extract::Query(query): axum::extract::Query<EpgMappingSearchQuery>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    match app_state.app_config.get_target_by_id(target_id) {
+        Some(target) => axum::Json(search_epg_mapping_channels(&app_state.app_config, &target, &query.q).await).into_response(),
+        None => target_not_found(target_id),
+    }
+}
+
+/// Stores the accepted and rejected suggestions, accepted mappings are assigned on the next playlist update.
+async fn epg_mapping_update(
+    axum::extract::Path(target_id): axum::extract::Path<u16>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::Json(decisions): axum::Json<Vec<EpgMappingDecisionDto>>,
+) -> axum::response::Response {
+    let Some(target) = app_state.app_config.get_target_by_id(target_id) else {
+        return target_not_found(target_id);
+    };
+    match update_epg_mappings(&app_state.app_config, &target.name, decisions).await {
+        Ok(mappings) => axum::Json(mappings).into_response(),
+        Err(err) => {
+            error!("{err}");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub fn epg_mapping_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/epg/mapping/{target_id}", axum::routing::get(epg_mapping_suggestions))
+        .route("/epg/mapping/{target_id}/search", axum::routing::get(epg_mapping_search))
+        .route("/epg/mapping/{target_id}", axum::routing::post(epg_mapping_update).layer(axum::middleware::from_fn(require_operator)))
+}
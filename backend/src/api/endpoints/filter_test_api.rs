@@ -0,0 +1,22 @@
+use crate::api::api_utils::api_error_response_with_detail;
+use crate::api::model::AppState;
+use crate::processing::filter_test::test_filter;
+use axum::response::IntoResponse;
+use shared::model::{ApiErrorCode, FilterTestRequest};
+use std::sync::Arc;
+
+/// Returns the items of the stored playlist the filter matches, with the clauses which matched each item.
+async fn filter_test(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(request): axum::extract::Json<FilterTestRequest>,
+) -> axum::response::Response {
+    match test_filter(&app_state.app_config, &request).await {
+        Ok(result) => axum::Json(result).into_response(),
+        Err(err) => api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidContent, "Invalid filter", err.to_string()),
+    }
+}
+
+pub fn filter_test_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/filter/test", axum::routing::post(filter_test))
+}
@@ -1,19 +1,21 @@
 use crate::api::api_utils::{try_unwrap_body, internal_server_error};
+use crate::api::endpoints::xmltv_api::{get_empty_epg_response, get_epg_path_for_target};
 use crate::api::model::HdHomerunAppState;
 use crate::auth::AuthBasic;
-use crate::model::{AppConfig, ConfigTarget, ProxyUserCredentials};
-use crate::utils::arc_str_serde;
+use crate::model::{AppConfig, ConfigTarget, PlexTargetOutput, ProxyUserCredentials, EPG_ATTRIB_ID, EPG_TAG_CHANNEL};
+use crate::utils::{arc_str_serde, format_xmltv_time_utc, get_epg_processing_options};
 use crate::processing::parser::xtream::get_xtream_url;
-use crate::repository::{iter_raw_m3u_target_playlist, M3uPlaylistIterator};
+use crate::repository::{iter_raw_m3u_target_playlist, BPlusTreeQuery, M3uPlaylistIterator, XML_PREAMBLE};
 use crate::repository::XtreamPlaylistIterator;
 use axum::response::IntoResponse;
 use bytes::Bytes;
 use futures::{stream, Stream, StreamExt};
 use log::{error, warn};
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use shared::model::{
-    M3uPlaylistItem, PlaylistItemType, TargetType, XtreamCluster, XtreamPlaylistItem,
+    EpgChannel, M3uPlaylistItem, PlaylistItemType, TargetType, XtreamCluster, XtreamPlaylistItem,
 };
 use shared::utils::{concat_path};
 use std::sync::Arc;
@@ -90,8 +92,74 @@ impl Device {
     }
 }
 
+fn xtream_lineup_url(
+    cfg: &AppConfig,
+    cluster: XtreamCluster,
+    credentials: &ProxyUserCredentials,
+    base_url: Option<&str>,
+    item: &XtreamPlaylistItem,
+) -> Arc<str> {
+    let Some(url) = base_url else {
+        return item.url.clone();
+    };
+    let input_options = cfg.get_input_options_by_name(&item.input_name);
+    let (live_stream_use_prefix, live_stream_without_extension) = input_options
+        .as_ref()
+        .map_or((true, false), |o| {
+            (
+                o.xtream_live_stream_use_prefix,
+                o.xtream_live_stream_without_extension,
+            )
+        });
+    let container_extension = item.get_container_extension();
+    get_xtream_url(
+        cluster,
+        url,
+        &credentials.username,
+        &credentials.password,
+        item.virtual_id,
+        container_extension.as_deref(),
+        live_stream_use_prefix,
+        live_stream_without_extension,
+    ).into()
+}
+
+fn m3u_lineup_url(item: &M3uPlaylistItem) -> Arc<str> {
+    if item.t_stream_url.is_empty() {
+        item.url.clone()
+    } else {
+        item.t_stream_url.clone()
+    }
+}
+
+/// Base url for the xtream stream urls, `None` when the streams are redirected to the provider.
+fn get_lineup_base_url(
+    app_state: &HdHomerunAppState,
+    credentials: &ProxyUserCredentials,
+    target: &ConfigTarget,
+    item_type: PlaylistItemType,
+) -> Option<String> {
+    if credentials.proxy.is_redirect(item_type) || target.is_force_redirect(item_type) {
+        None
+    } else {
+        Some(app_state.app_state.app_config.get_user_server_info(credentials).get_base_url())
+    }
+}
+
+/// The logical channel number of live channels when the target has a channel numbering,
+/// otherwise the epg id or the name.
+fn lineup_guide_number(use_channel_numbers: bool, item_type: PlaylistItemType, chno: u32,
+                       epg_channel_id: Option<&Arc<str>>, name: &Arc<str>) -> Arc<str> {
+    if use_channel_numbers && chno > 0 && item_type.is_live() {
+        chno.to_string().into()
+    } else {
+        Arc::clone(epg_channel_id.unwrap_or(name))
+    }
+}
+
 fn xtream_item_to_lineup_stream<I>(
     cfg: Arc<AppConfig>,
+    use_channel_numbers: bool,
     cluster: XtreamCluster,
     credentials: Arc<ProxyUserCredentials>,
     base_url: Option<String>,
@@ -103,34 +171,11 @@ where
     match channels {
         Some(chans) => {
             let mapped = chans.map(move |(item, has_next)| {
-                let input_options = cfg.get_input_options_by_name(&item.input_name);
-                let (live_stream_use_prefix, live_stream_without_extension) = input_options
-                    .as_ref()
-                    .map_or((true, false), |o| {
-                        (
-                            o.xtream_live_stream_use_prefix,
-                            o.xtream_live_stream_without_extension,
-                        )
-                    });
-                let container_extension = item.get_container_extension();
-                let stream_url = match &base_url {
-                    None => item.url.clone(),
-                    Some(url) => get_xtream_url(
-                        cluster,
-                        url,
-                        &credentials.username,
-                        &credentials.password,
-                        item.virtual_id,
-                        container_extension.as_deref(),
-                        live_stream_use_prefix,
-                        live_stream_without_extension,
-                    ).into(),
-                };
-
+                let stream_url = xtream_lineup_url(&cfg, cluster, &credentials, base_url.as_deref(), &item);
                 let lineup = Lineup {
-                    guide_number: item.epg_channel_id.unwrap_or(item.name.clone()),
+                    guide_number: lineup_guide_number(use_channel_numbers, item.item_type, item.channel_no, item.epg_channel_id.as_ref(), &item.name),
                     guide_name: item.title.clone(),
-                    url: stream_url.clone(),
+                    url: stream_url,
                 };
                 match serde_json::to_string(&lineup) {
                     Ok(mut content) => {
@@ -148,7 +193,7 @@ where
     }
 }
 
-fn m3u_item_to_lineup_stream<I>(channels: Option<I>) -> impl Stream<Item=Result<Bytes, String>>
+fn m3u_item_to_lineup_stream<I>(use_channel_numbers: bool, channels: Option<I>) -> impl Stream<Item=Result<Bytes, String>>
 where
     I: Iterator<Item=(M3uPlaylistItem, bool)> + 'static,
 {
@@ -156,13 +201,9 @@ where
         Some(chans) => {
             let mapped = chans.map(move |(item, has_next)| {
                 let lineup = Lineup {
-                    guide_number: item.epg_channel_id.clone().unwrap_or(item.name.clone()),
+                    guide_number: lineup_guide_number(use_channel_numbers, item.item_type, item.chno, item.epg_channel_id.as_ref(), &item.name),
                     guide_name: item.title.clone(),
-                    url: if item.t_stream_url.is_empty() {
-                        item.url.clone()
-                    } else {
-                        item.t_stream_url.clone()
-                    }
+                    url: m3u_lineup_url(&item),
                 };
                 match serde_json::to_string(&lineup) {
                     Ok(mut content) => {
@@ -339,12 +380,157 @@ async fn lineup_post(
     }
 }
 
+/// Live channel of the plex lineup with its assigned channel number.
+struct PlexChannel {
+    number: u32,
+    name: Arc<str>,
+    epg_channel_id: Option<Arc<str>>,
+    url: Arc<str>,
+}
+
+async fn collect_plex_channels(
+    app_state: &HdHomerunAppState,
+    cfg: &Arc<AppConfig>,
+    credentials: &Arc<ProxyUserCredentials>,
+    target: &ConfigTarget,
+    output: &PlexTargetOutput,
+) -> Vec<PlexChannel> {
+    let use_all = output.use_output.is_none();
+    let use_m3u = output.use_output == Some(TargetType::M3u);
+    let use_xtream = output.use_output == Some(TargetType::Xtream);
+    let mut numbering = output.channel_numbering();
+    let mut channels = Vec::new();
+    if (use_all || use_m3u) && target.has_output(TargetType::M3u) {
+        if let Ok(iterator) = M3uPlaylistIterator::new(cfg, target, credentials).await {
+            for (item, _) in iterator.filter(|(item, _)| item.item_type.is_live()) {
+                channels.push(PlexChannel {
+                    number: numbering.assign(&item.group, item.chno),
+                    url: m3u_lineup_url(&item),
+                    name: item.title,
+                    epg_channel_id: item.epg_channel_id,
+                });
+            }
+        }
+    } else if (use_all || use_xtream) && target.has_output(TargetType::Xtream) {
+        let base_url = get_lineup_base_url(app_state, credentials, target, PlaylistItemType::Live);
+        if let Ok(iterator) = XtreamPlaylistIterator::new(XtreamCluster::Live, cfg, target, None, credentials).await {
+            for (item, _) in iterator {
+                channels.push(PlexChannel {
+                    number: numbering.assign(&item.group, item.channel_no),
+                    url: xtream_lineup_url(cfg, XtreamCluster::Live, credentials, base_url.as_deref(), &item),
+                    name: item.title,
+                    epg_channel_id: item.epg_channel_id,
+                });
+            }
+        }
+    }
+    channels
+}
+
+fn write_xml_text_element<W: std::io::Write>(writer: &mut quick_xml::Writer<W>, name: &str, text: &str) -> std::io::Result<()> {
+    writer.write_event(Event::Start(BytesStart::new(name)))?;
+    writer.write_event(Event::Text(BytesText::new(text)))?;
+    writer.write_event(Event::End(BytesEnd::new(name)))
+}
+
+/// XMLTV guide with the channel numbers of the plex lineup as channel ids,
+/// channels sharing an epg id get their own copy of the programmes.
+fn write_plex_xmltv(channels: &[PlexChannel], query: &mut BPlusTreeQuery<Arc<str>, EpgChannel>, offset_minutes: i32) -> std::io::Result<Vec<u8>> {
+    let epg_channels: Vec<(&PlexChannel, Option<EpgChannel>)> = channels.iter()
+        .map(|channel| (channel, channel.epg_channel_id.as_ref().and_then(|epg_id| query.query(epg_id).ok().flatten())))
+        .collect();
+
+    let mut out = Vec::from(XML_PREAMBLE.as_bytes());
+    out.extend_from_slice(r#"<tv generator-info-name="X" generator-info-url="tuliprox">"#.as_bytes());
+    let mut writer = quick_xml::Writer::new(out);
+    for (channel, epg_channel) in &epg_channels {
+        let number = channel.number.to_string();
+        let mut elem = BytesStart::new(EPG_TAG_CHANNEL);
+        elem.push_attribute((EPG_ATTRIB_ID, number.as_str()));
+        writer.write_event(Event::Start(elem))?;
+        write_xml_text_element(&mut writer, "display-name", &channel.name)?;
+        write_xml_text_element(&mut writer, "display-name", &number)?;
+        if let Some(icon_url) = epg_channel.as_ref().and_then(|epg_channel| epg_channel.icon.as_ref()) {
+            let mut elem = BytesStart::new("icon");
+            elem.push_attribute(("src", icon_url.as_ref()));
+            writer.write_event(Event::Empty(elem))?;
+        }
+        writer.write_event(Event::End(BytesEnd::new(EPG_TAG_CHANNEL)))?;
+    }
+    for (channel, epg_channel) in &epg_channels {
+        let Some(epg_channel) = epg_channel else { continue; };
+        let number = channel.number.to_string();
+        for programme in &epg_channel.programmes {
+            let mut elem = BytesStart::new("programme");
+            elem.push_attribute(("start", format_xmltv_time_utc(programme.start, offset_minutes).as_str()));
+            elem.push_attribute(("stop", format_xmltv_time_utc(programme.stop, offset_minutes).as_str()));
+            elem.push_attribute(("channel", number.as_str()));
+            writer.write_event(Event::Start(elem))?;
+            if let Some(title) = &programme.title {
+                write_xml_text_element(&mut writer, "title", title)?;
+            }
+            if let Some(desc) = &programme.desc {
+                write_xml_text_element(&mut writer, "desc", desc)?;
+            }
+            writer.write_event(Event::End(BytesEnd::new("programme")))?;
+        }
+    }
+    let mut out = writer.into_inner();
+    out.extend_from_slice(b"</tv>");
+    Ok(out)
+}
+
+async fn plex_xmltv(
+    app_state: &Arc<HdHomerunAppState>,
+    cfg: &Arc<AppConfig>,
+    credentials: &Arc<ProxyUserCredentials>,
+    target: &Arc<ConfigTarget>,
+) -> axum::response::Response {
+    let Some(output) = target.get_plex_output() else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    let channels = collect_plex_channels(app_state, cfg, credentials, target, output).await;
+    let Some(epg_path) = get_epg_path_for_target(&cfg.config.load(), target) else {
+        return get_empty_epg_response();
+    };
+    let offset_minutes = get_epg_processing_options(&app_state.app_state, credentials, target).offset_minutes;
+    let _file_lock = cfg.file_locks.read_lock(&epg_path).await;
+    let mut query = match BPlusTreeQuery::<Arc<str>, EpgChannel>::try_new(&epg_path) {
+        Ok(query) => query,
+        Err(err) => {
+            error!("Failed to open epg db for target {} {} - {err}", target.name, epg_path.display());
+            return get_empty_epg_response();
+        }
+    };
+    match write_plex_xmltv(&channels, &mut query, offset_minutes) {
+        Ok(content) => try_unwrap_body!(axum::response::Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .header(axum::http::header::CONTENT_TYPE, mime::TEXT_XML.to_string())
+            .body(axum::body::Body::from(content))),
+        Err(err) => {
+            error!("Failed to write plex xmltv for target {} - {err}", target.name);
+            internal_server_error!()
+        }
+    }
+}
+
 async fn lineup(
     app_state: &Arc<HdHomerunAppState>,
     cfg: &Arc<AppConfig>,
     credentials: &Arc<ProxyUserCredentials>,
     target: &ConfigTarget,
 ) -> impl IntoResponse {
+    if let Some(plex_output) = target.get_plex_output() {
+        let lineup: Vec<Lineup> = collect_plex_channels(app_state, cfg, credentials, target, plex_output).await
+            .into_iter()
+            .map(|channel| Lineup {
+                guide_number: channel.number.to_string().into(),
+                guide_name: channel.name,
+                url: channel.url,
+            })
+            .collect();
+        return axum::Json(lineup).into_response();
+    }
     let use_output = target
         .get_hdhomerun_output()
         .as_ref()
@@ -352,11 +538,12 @@ async fn lineup(
     let use_all = use_output.is_none();
     let use_m3u = use_output.as_ref() == Some(&TargetType::M3u);
     let use_xtream = use_output.as_ref() == Some(&TargetType::Xtream);
+    let use_channel_numbers = target.channel_numbering.is_some();
     if (use_all || use_m3u) && target.has_output(TargetType::M3u) {
         let iterator = M3uPlaylistIterator::new(cfg, target, credentials)
             .await
             .ok();
-        let stream = m3u_item_to_lineup_stream(iterator);
+        let stream = m3u_item_to_lineup_stream(use_channel_numbers, iterator);
         let body_stream = stream::once(async { Ok(Bytes::from("[")) })
             .chain(stream)
             .chain(stream::once(async { Ok(Bytes::from("]")) }));
@@ -368,26 +555,8 @@ async fn lineup(
             )
             .body(axum::body::Body::from_stream(body_stream)));
     } else if (use_all || use_xtream) && target.has_output(TargetType::Xtream) {
-        let server_info = app_state
-            .app_state
-            .app_config
-            .get_user_server_info(credentials);
-        let base_url = server_info.get_base_url();
-
-        let base_url_live = if credentials.proxy.is_redirect(PlaylistItemType::Live)
-            || target.is_force_redirect(PlaylistItemType::Live)
-        {
-            None
-        } else {
-            Some(base_url.clone())
-        };
-        let base_url_vod = if credentials.proxy.is_redirect(PlaylistItemType::Video)
-            || target.is_force_redirect(PlaylistItemType::Video)
-        {
-            None
-        } else {
-            Some(base_url)
-        };
+        let base_url_live = get_lineup_base_url(app_state, credentials, target, PlaylistItemType::Live);
+        let base_url_vod = get_lineup_base_url(app_state, credentials, target, PlaylistItemType::Video);
 
         let live_channels =
             XtreamPlaylistIterator::new(XtreamCluster::Live, cfg, target, None, credentials)
@@ -399,16 +568,18 @@ async fn lineup(
                 .ok();
         let live_stream = xtream_item_to_lineup_stream(
             Arc::clone(cfg),
+            use_channel_numbers,
             XtreamCluster::Live,
             Arc::clone(credentials),
-            base_url_live.clone(),
+            base_url_live,
             live_channels,
         );
         let vod_stream = xtream_item_to_lineup_stream(
             Arc::clone(cfg),
+            use_channel_numbers,
             XtreamCluster::Video,
             Arc::clone(credentials),
-            base_url_vod.clone(),
+            base_url_vod,
             vod_channels,
         );
 
@@ -470,6 +641,32 @@ async fn lineup_json(
     axum::http::StatusCode::NOT_FOUND.into_response()
 }
 
+async fn auth_xmltv(
+    AuthBasic((username, password)): AuthBasic,
+    axum::extract::State(app_state): axum::extract::State<Arc<HdHomerunAppState>>,
+) -> impl IntoResponse {
+    let cfg = Arc::clone(&app_state.app_state.app_config);
+    if let Some((credentials, target)) = cfg.get_target_for_username(&app_state.device.t_username)
+    {
+        if !username.eq(&credentials.username) || !password.eq(&credentials.password) {
+            return axum::http::StatusCode::UNAUTHORIZED.into_response();
+        }
+        return plex_xmltv(&app_state, &cfg, &Arc::new(credentials), &target).await;
+    }
+    axum::http::StatusCode::NOT_FOUND.into_response()
+}
+
+async fn xmltv(
+    axum::extract::State(app_state): axum::extract::State<Arc<HdHomerunAppState>>,
+) -> impl IntoResponse {
+    let cfg = Arc::clone(&app_state.app_state.app_config);
+    if let Some((credentials, target)) = cfg.get_target_for_username(&app_state.device.t_username)
+    {
+        return plex_xmltv(&app_state, &cfg, &Arc::new(credentials), &target).await;
+    }
+    axum::http::StatusCode::NOT_FOUND.into_response()
+}
+
 async fn auto_channel(
     axum::extract::State(_app_state): axum::extract::State<Arc<HdHomerunAppState>>,
     axum::extract::Path(channel): axum::extract::Path<String>,
@@ -492,6 +689,14 @@ pub fn hdhr_api_register(basic_auth: bool) -> axum::Router<Arc<HdHomerunAppState
                 axum::routing::get(lineup_json)
             },
         )
+        .route(
+            "/xmltv.xml",
+            if basic_auth {
+                axum::routing::get(auth_xmltv)
+            } else {
+                axum::routing::get(xmltv)
+            },
+        )
         .route("/lineup.post", axum::routing::post(lineup_post))
         .route("/auto/{channel}", axum::routing::get(auto_channel))
         .route(
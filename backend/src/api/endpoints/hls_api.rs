@@ -1,13 +1,13 @@
-use crate::api::api_utils::{create_session_fingerprint, try_unwrap_body};
+use crate::api::api_utils::{account_sharing_denied, create_session_fingerprint, shutdown_in_progress_response, try_unwrap_body};
 use crate::api::api_utils::{
     force_provider_stream_response, get_stream_alternative_url, is_seek_request, local_stream_response,
 };
 use crate::api::api_utils::{get_headers_from_request, try_option_bad_request, HeaderFilter};
-use crate::api::model::AppState;
+use crate::api::model::{AppState, HlsRemuxIngest, HlsRemuxManager, HlsRemuxSession};
 use crate::api::model::{create_custom_video_stream_response, CustomVideoStreamType};
 use crate::api::model::{ProviderAllocation, UserSession};
 use crate::auth::Fingerprint;
-use crate::model::{ConfigInput, InputSource};
+use crate::model::{ConfigInput, HlsRemuxConfig, InputSource};
 use crate::model::{ConfigTarget, ProxyUserCredentials};
 use crate::processing::parser::hls::{
     get_hls_session_token_and_url_from_token, rewrite_hls, RewriteHlsProps,
@@ -15,12 +15,14 @@ use crate::processing::parser::hls::{
 use crate::repository::{m3u_get_item_for_stream_id, xtream_get_item_for_stream_id};
 use crate::utils::request;
 use crate::utils::debug_if_enabled;
+use crate::utils::input_client::get_input_client;
+use axum::http::header::USER_AGENT;
 use axum::http::HeaderMap;
 use axum::response::IntoResponse;
 use log::{debug, error};
 use serde::Deserialize;
-use shared::model::{PlaylistItemType, StreamChannel, TargetType, UserConnectionPermission, XtreamCluster};
-use shared::utils::{is_hls_url, replace_url_extension, sanitize_sensitive_info, Internable, CUSTOM_VIDEO_PREFIX, HLS_EXT};
+use shared::model::{HlsRemuxConfigDto, PlaylistItemType, StreamChannel, TargetType, UserConnectionPermission, XtreamCluster};
+use shared::utils::{is_dash_url, is_hls_url, replace_url_extension, sanitize_sensitive_info, Internable, CUSTOM_VIDEO_PREFIX, HLS_EXT, HLS_REMUX_PREFIX};
 use std::sync::Arc;
 use crate::utils::request::is_file_url;
 
@@ -42,13 +44,184 @@ struct HlsApiPathParams {
     token: String,
 }
 
-fn hls_response(hls_content: String) -> impl IntoResponse + Send {
+#[derive(Debug, Deserialize)]
+struct HlsRemuxPathParams {
+    username: String,
+    password: String,
+    stream_id: u32,
+    segment: String,
+}
+
+pub(in crate::api::endpoints) fn hls_response(hls_content: String) -> impl IntoResponse + Send {
     try_unwrap_body!(axum::response::Response::builder()
         .status(axum::http::StatusCode::OK)
         .header(axum::http::header::CONTENT_TYPE, "application/x-mpegurl")
         .body(hls_content))
 }
 
+fn dash_response(mpd_content: String) -> impl IntoResponse + Send {
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, "application/dash+xml")
+        .header(axum::http::header::CACHE_CONTROL, "no-cache")
+        .body(mpd_content))
+}
+
+async fn get_remux_stream_channel(app_state: &Arc<AppState>, user: &ProxyUserCredentials, virtual_id: u32, stream_url: &str) -> StreamChannel {
+    let url = stream_url.intern();
+    match app_state.app_config.get_target_for_user(&user.username, &user.password) {
+        Some((_, target)) => resolve_stream_channel(app_state, &target, virtual_id, &url).await,
+        None => unknown_stream_channel(0, virtual_id, &url),
+    }
+}
+
+/// Returns the running remux session of the user for the channel or starts a new one.
+/// A new session counts as user connection until its ingest stops.
+/// The session is ready to serve when the first segments are available.
+#[allow(clippy::too_many_arguments)]
+async fn get_or_start_remux_session(
+    fingerprint: &Fingerprint,
+    app_state: &Arc<AppState>,
+    user: &ProxyUserCredentials,
+    stream_url: &str,
+    virtual_id: u32,
+    input: &ConfigInput,
+    config: &HlsRemuxConfig,
+    req_headers: &HeaderMap,
+    connection_permission: UserConnectionPermission,
+) -> Result<Arc<HlsRemuxSession>, axum::response::Response> {
+    let session_key = HlsRemuxManager::session_key(&user.username, virtual_id);
+    let session = if let Some(session) = app_state.hls_remux_manager.get_session(&session_key) {
+        session
+    } else {
+        if connection_permission == UserConnectionPermission::Exhausted {
+            return Err(create_custom_video_stream_response(app_state, &fingerprint.addr, CustomVideoStreamType::UserConnectionsExhausted).await.into_response());
+        }
+        let session_addr = app_state.hls_remux_manager.create_session_addr();
+        let Some(provider_handle) = app_state.active_provider.acquire_connection(&input.name, &session_addr).await else {
+            return Err(create_custom_video_stream_response(app_state, &fingerprint.addr, CustomVideoStreamType::ProviderConnectionsExhausted).await.into_response());
+        };
+        let provider_cfg = match &provider_handle.allocation {
+            ProviderAllocation::Available(cfg) | ProviderAllocation::GracePeriod(cfg) => Arc::clone(cfg),
+            ProviderAllocation::Exhausted => {
+                app_state.active_provider.release_handle(&provider_handle).await;
+                return Err(create_custom_video_stream_response(app_state, &fingerprint.addr, CustomVideoStreamType::ProviderConnectionsExhausted).await.into_response());
+            }
+        };
+        let upstream_url = get_stream_alternative_url(stream_url, input, &provider_cfg);
+        debug_if_enabled!("HLS remux for user={} virtual_id={virtual_id} provider={} stream_url={}",
+            sanitize_sensitive_info(&user.username), provider_cfg.name, sanitize_sensitive_info(&upstream_url));
+        let stream_channel = get_remux_stream_channel(app_state, user, virtual_id, stream_url).await;
+        let user_agent = req_headers.get(USER_AGENT).map(|h| String::from_utf8_lossy(h.as_bytes())).unwrap_or_default();
+        let session_fingerprint = Fingerprint::new(fingerprint.key.clone(), fingerprint.client_ip.clone(), session_addr);
+        app_state.connection_manager.add_connection(&session_addr).await;
+        app_state.connection_manager.update_connection(&user.username, user.max_connections, &session_fingerprint,
+                                                       &provider_cfg.name, stream_channel, user_agent, None).await;
+        let ingest = HlsRemuxIngest {
+            app_config: Arc::clone(&app_state.app_config),
+            client: get_input_client(&app_state.app_config, &app_state.http_client.load(), &input.name, None),
+            input_source: InputSource::from(input).with_url(upstream_url),
+            provider_manager: Arc::clone(&app_state.active_provider),
+            provider_handle,
+            connection_manager: Arc::clone(&app_state.connection_manager),
+            session_addr,
+        };
+        app_state.hls_remux_manager.start_session(&session_key, config.clone(), ingest).await
+    };
+
+    session.touch();
+    if !session.wait_for_segments(config.segment_duration() * 3).await {
+        return Err(create_custom_video_stream_response(app_state, &fingerprint.addr, CustomVideoStreamType::ChannelUnavailable).await.into_response());
+    }
+    Ok(session)
+}
+
+fn get_remux_segment_base_url(app_state: &AppState, user: &ProxyUserCredentials, virtual_id: u32) -> String {
+    let server_info = app_state.app_config.get_user_server_info(user);
+    format!("{}/{HLS_REMUX_PREFIX}/{}/{}/{virtual_id}", server_info.get_base_url(), user.username, user.password)
+}
+
+/// Serves the live stream as locally generated HLS playlist, see `HlsRemuxSession`.
+#[allow(clippy::too_many_arguments)]
+async fn handle_hls_remux_request(
+    fingerprint: &Fingerprint,
+    app_state: &Arc<AppState>,
+    user: &ProxyUserCredentials,
+    stream_url: &str,
+    virtual_id: u32,
+    input: &ConfigInput,
+    config: HlsRemuxConfig,
+    req_headers: &HeaderMap,
+    connection_permission: UserConnectionPermission,
+) -> axum::response::Response {
+    let session = match get_or_start_remux_session(fingerprint, app_state, user, stream_url, virtual_id, input, &config,
+                                                   req_headers, connection_permission).await {
+        Ok(session) => session,
+        Err(response) => return response,
+    };
+    let segment_base_url = get_remux_segment_base_url(app_state, user, virtual_id);
+    let playlist = session.render_playlist(|sequence| format!("{segment_base_url}/{sequence}.ts")).await;
+    hls_response(playlist).into_response()
+}
+
+fn get_remux_config_or_default(app_state: &AppState) -> HlsRemuxConfig {
+    app_state.app_config.config.load().get_hls_remux_config()
+        .unwrap_or_else(|| HlsRemuxConfig::from(&HlsRemuxConfigDto::default()))
+}
+
+/// Serves a live channel of a target with `hls` m3u output as locally generated HLS playlist,
+/// when `hls_remux` is not enabled its default settings are used.
+#[allow(clippy::too_many_arguments)]
+pub(in crate::api) async fn handle_hls_output_stream_request(
+    fingerprint: &Fingerprint,
+    app_state: &Arc<AppState>,
+    user: &ProxyUserCredentials,
+    stream_url: &str,
+    virtual_id: u32,
+    input: &ConfigInput,
+    req_headers: &HeaderMap,
+    connection_permission: UserConnectionPermission,
+) -> axum::response::Response {
+    if app_state.active_users.is_user_blocked_for_stream(&user.username, virtual_id).await {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    }
+    let config = get_remux_config_or_default(app_state);
+    handle_hls_remux_request(fingerprint, app_state, user, stream_url, virtual_id, input, config, req_headers, connection_permission).await
+}
+
+/// Serves the live stream as DASH manifest for users with proxy type `dash`.
+/// The manifest is backed by the same remux session and segments as the HLS remux,
+/// when `hls_remux` is not enabled its default settings are used.
+/// DASH upstreams are passed through as they are.
+#[allow(clippy::too_many_arguments)]
+pub(in crate::api) async fn handle_dash_stream_request(
+    fingerprint: &Fingerprint,
+    app_state: &Arc<AppState>,
+    user: &ProxyUserCredentials,
+    user_session: Option<&UserSession>,
+    stream_url: &str,
+    virtual_id: u32,
+    input: &ConfigInput,
+    req_headers: &HeaderMap,
+    connection_permission: UserConnectionPermission,
+) -> axum::response::Response {
+    if is_dash_url(stream_url) {
+        return handle_hls_stream_request(fingerprint, app_state, user, user_session, stream_url, virtual_id,
+                                         input, req_headers, connection_permission).await.into_response();
+    }
+    if app_state.active_users.is_user_blocked_for_stream(&user.username, virtual_id).await {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    }
+    let config = get_remux_config_or_default(app_state);
+    let session = match get_or_start_remux_session(fingerprint, app_state, user, stream_url, virtual_id, input, &config,
+                                                   req_headers, connection_permission).await {
+        Ok(session) => session,
+        Err(response) => return response,
+    };
+    let media = format!("{}/$Number$.ts", get_remux_segment_base_url(app_state, user, virtual_id));
+    dash_response(session.render_dash_manifest(&media).await).into_response()
+}
+
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 pub(in crate::api) async fn handle_hls_stream_request(
     fingerprint: &Fingerprint,
@@ -65,6 +238,13 @@ pub(in crate::api) async fn handle_hls_stream_request(
         return axum::http::StatusCode::BAD_REQUEST.into_response();
     }
 
+    if !is_dash_url(hls_url) {
+        if let Some(remux_config) = app_state.app_config.config.load().get_hls_remux_config() {
+            return handle_hls_remux_request(fingerprint, app_state, user, hls_url, virtual_id, input, remux_config,
+                                            req_headers, connection_permission).await;
+        }
+    }
+
     let url = replace_url_extension(hls_url, HLS_EXT);
     let server_info = app_state.app_config.get_user_server_info(user);
 
@@ -189,29 +369,33 @@ async fn get_stream_channel(app_state: &Arc<AppState>, target: &Arc<ConfigTarget
     m3u_get_item_for_stream_id(virtual_id, app_state, target).await.ok().map(|pli| pli.to_stream_channel(target_id))
 }
 
+fn unknown_stream_channel(target_id: u16, virtual_id: u32, hls_url: &Arc<str>) -> StreamChannel {
+    let unknown = "Unknown".intern();
+    StreamChannel {
+        target_id,
+        virtual_id,
+        provider_id: 0,
+        item_type: PlaylistItemType::LiveHls,
+        cluster: XtreamCluster::Live,
+        group: unknown.clone(),
+        title: unknown,
+        url: hls_url.clone(),
+        shared: false,
+    }
+}
+
 async fn resolve_stream_channel(
     app_state: &Arc<AppState>,
     target: &Arc<ConfigTarget>,
     virtual_id: u32,
     hls_url: &Arc<str>,
 ) -> StreamChannel {
-    let unknown = "Unknown".intern();
     let mut channel = match get_stream_channel(app_state, target, virtual_id).await {
         Some(mut channel) => {
             channel.url = hls_url.clone();
             channel
         },
-        None => StreamChannel {
-            target_id: target.id,
-            virtual_id,
-            provider_id: 0,
-            item_type: PlaylistItemType::LiveHls,
-            cluster: XtreamCluster::Live,
-            group: unknown.clone(),
-            title: unknown,
-            url: hls_url.clone(),
-            shared: false,
-        },
+        None => unknown_stream_channel(target.id, virtual_id, hls_url),
     };
 
     channel.item_type = PlaylistItemType::LiveHls;
@@ -225,6 +409,10 @@ async fn hls_api_stream(
     axum::extract::Path(params): axum::extract::Path<HlsApiPathParams>,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
 ) -> impl axum::response::IntoResponse + Send {
+    if app_state.shutdown.is_draining() {
+        return shutdown_in_progress_response();
+    }
+
     let (user, target) = try_option_bad_request!(
         app_state
             .app_config
@@ -241,6 +429,14 @@ async fn hls_api_stream(
             .into_response();
     }
 
+    if user.country_denied(&app_state, &fingerprint.client_ip) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
+    if account_sharing_denied(&app_state, &user, &fingerprint) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
     let target_name = &target.name;
     let virtual_id = params.stream_id;
     let input = try_option_bad_request!(
@@ -364,10 +560,41 @@ async fn hls_api_stream(
 }
 
 
+async fn hls_remux_segment(
+    axum::extract::Path(params): axum::extract::Path<HlsRemuxPathParams>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    let (user, _target) = try_option_bad_request!(
+        app_state
+            .app_config
+            .get_target_for_user(&params.username, &params.password),
+        false,
+        format!("Could not find any user for hls segment {}", params.username)
+    );
+    let Ok(sequence) = params.segment.trim_end_matches(".ts").parse::<u64>() else {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    let session_key = HlsRemuxManager::session_key(&user.username, params.stream_id);
+    let Some(session) = app_state.hls_remux_manager.get_session(&session_key) else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    session.touch();
+    match session.get_segment(sequence).await {
+        Some(segment) => try_unwrap_body!(axum::response::Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .header(axum::http::header::CONTENT_TYPE, "video/mp2t")
+            .body(axum::body::Body::from(segment.data.clone()))),
+        None => axum::http::StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
 pub fn hls_api_register() -> axum::Router<Arc<AppState>> {
     axum::Router::new().route(
         "/hls/{username}/{password}/{input_id}/{stream_id}/{token}",
         axum::routing::get(hls_api_stream),
+    ).route(
+        &format!("/{HLS_REMUX_PREFIX}/{{username}}/{{password}}/{{stream_id}}/{{segment}}"),
+        axum::routing::get(hls_remux_segment),
     )
     //cfg.service(web::resource("/hls/{token}/{stream}").route(web::get().to(xtream_player_api_hls_stream)));
     //cfg.service(web::resource("/play/{token}/{type}").route(web::get().to(xtream_player_api_play_stream)));
@@ -0,0 +1,12 @@
+use crate::api::model::AppState;
+use crate::repository::load_job_log;
+use shared::model::JobLogEntryDto;
+use std::sync::Arc;
+
+async fn job_log(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::Json<Vec<JobLogEntryDto>> {
+    axum::Json(load_job_log(&app_state.app_config).await)
+}
+
+pub fn job_log_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router.route("/schedules/log", axum::routing::get(job_log))
+}
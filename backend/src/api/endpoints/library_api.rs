@@ -1,9 +1,10 @@
+use crate::api::api_utils::api_error_response;
 use crate::api::model::{AppState, EventMessage};
+use crate::auth::require_operator;
 use axum::response::IntoResponse;
 use log::{debug, error, info, warn};
 use std::sync::Arc;
-use serde_json::json;
-use shared::model::{LibraryScanRequest, LibraryScanSummary, LibraryScanSummaryStatus, LibraryStatus};
+use shared::model::{ApiErrorCode, LibraryScanRequest, LibraryScanSummary, LibraryScanSummaryStatus, LibraryStatus};
 use crate::library::{LibraryProcessor};
 
 // Triggers a library scan
@@ -21,7 +22,7 @@ async fn scan_library(
             result: None,
         };
         let _ = app_state.event_manager.send_event(EventMessage::LibraryScanProgress(response));
-        return (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Library update already in progress.".to_string()}))).into_response();
+        return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::AlreadyInProgress, "Library update already in progress.");
     };
 
     // Check if Library is enabled
@@ -34,7 +35,7 @@ async fn scan_library(
                 result: None,
             };
             let _ = app_state.event_manager.send_event(EventMessage::LibraryScanProgress(response));
-            return (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Library is not enabled".to_string()}))).into_response();
+            return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::NotConfigured, "Library is not enabled");
         }
     };
 
@@ -115,6 +116,6 @@ async fn get_library_status(
 /// Registers Library API routes
 pub fn library_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
     router
-        .route("/library/scan", axum::routing::post(scan_library))
+        .route("/library/scan", axum::routing::post(scan_library).layer(axum::middleware::from_fn(require_operator)))
         .route("/library/status", axum::routing::get(get_library_status))
 }
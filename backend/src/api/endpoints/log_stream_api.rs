@@ -0,0 +1,96 @@
+use crate::api::endpoints::websocket_api::{get_auth_web_ui_role, get_secret_key};
+use crate::api::model::AppState;
+use crate::utils::{subscribe_log, LogFilter};
+use axum::extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::response::IntoResponse;
+use serde::Deserialize;
+use shared::model::{LogEntryDto, WebUiRole, WsCloseCode};
+use shared::utils::concat_path_leading_slash;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Deserialize)]
+struct LogStreamQuery {
+    level: Option<String>,
+    module: Option<String>,
+}
+
+fn log_stream_handler(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(query): axum::extract::Query<LogStreamQuery>,
+    ws: WebSocketUpgrade,
+    auth_required: bool,
+) -> impl IntoResponse {
+    let filter = LogFilter::new(query.level.as_deref(), query.module.as_deref());
+    ws.on_upgrade(move |socket| handle_log_socket(socket, app_state, filter, auth_required))
+}
+
+/// The first message of the client is the token of the web ui user, only operators and admins can read the log.
+async fn authorize(socket: &mut WebSocket, app_state: &AppState, auth_required: bool) -> bool {
+    let token = match tokio::time::timeout(AUTH_TIMEOUT, socket.recv()).await {
+        Ok(Some(Ok(Message::Text(token)))) => token.to_string(),
+        _ => return false,
+    };
+    if !auth_required {
+        return true;
+    }
+    let secret_key = get_secret_key(app_state, auth_required);
+    if get_auth_web_ui_role(&token, secret_key.as_ref()).is_some_and(|role| role.is_allowed(WebUiRole::Operator)) {
+        return true;
+    }
+    let _ = socket.send(Message::Close(Some(CloseFrame {
+        code: WsCloseCode::Policy.code(),
+        reason: "Unauthorized".into(),
+    }))).await;
+    false
+}
+
+async fn send_entry(socket: &mut WebSocket, entry: &LogEntryDto) -> bool {
+    match serde_json::to_string(entry) {
+        Ok(json) => socket.send(Message::text(json)).await.is_ok(),
+        Err(_) => true,
+    }
+}
+
+// No logging in here, the log output of the stream would be streamed again.
+async fn handle_log_socket(mut socket: WebSocket, app_state: Arc<AppState>, filter: LogFilter, auth_required: bool) {
+    if !authorize(&mut socket, &app_state, auth_required).await {
+        return;
+    }
+    let (entries, mut log_rx) = subscribe_log(&filter);
+    for entry in &entries {
+        if !send_entry(&mut socket, entry).await {
+            return;
+        }
+    }
+    loop {
+        tokio::select! {
+            msg = socket.recv() => {
+                if !matches!(msg, Some(Ok(Message::Text(_) | Message::Binary(_) | Message::Ping(_) | Message::Pong(_)))) {
+                    break;
+                }
+            }
+            entry = log_rx.recv() => {
+                match entry {
+                    Ok(entry) => {
+                        if filter.matches(&entry) && !send_entry(&mut socket, &entry).await {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+}
+
+pub fn log_stream_api_register(web_auth_enabled: bool, web_ui_path: &str) -> axum::Router<Arc<AppState>> {
+    axum::Router::new().route(
+        &concat_path_leading_slash(web_ui_path, "ws/logs"),
+        axum::routing::get(move |state, query, ws| async move { log_stream_handler(state, query, ws, web_auth_enabled) }),
+    )
+}
@@ -1,30 +1,35 @@
-use crate::api::api_utils::{create_session_fingerprint, local_stream_response, try_unwrap_body};
+use crate::api::api_utils::{account_sharing_denied, create_session_fingerprint, local_stream_response, shutdown_in_progress_response, try_unwrap_body};
 use crate::api::api_utils::{
     force_provider_stream_response, get_user_target, get_user_target_by_credentials,
-    is_seek_request, redirect, redirect_response, resource_response, separate_number_and_remainder,
+    is_seek_request, logo_response, redirect, redirect_response, resource_response, separate_number_and_remainder,
     stream_response, try_result_not_found, try_option_bad_request, try_result_bad_request, RedirectParams,
 };
-use crate::api::endpoints::hls_api::handle_hls_stream_request;
+use crate::api::endpoints::hls_api::{handle_dash_stream_request, handle_hls_output_stream_request, handle_hls_stream_request, hls_response};
 use crate::api::endpoints::xtream_api::{ApiStreamContext, ApiStreamRequest};
 use crate::api::model::AppState;
 use crate::api::model::UserApiRequest;
 use crate::api::model::{create_custom_video_stream_response, CustomVideoStreamType};
 use crate::auth::Fingerprint;
-use crate::repository::{m3u_get_item_for_stream_id, m3u_load_rewrite_playlist};
+use crate::model::{ConfigTarget, ProxyUserCredentials};
+use crate::repository::{m3u_get_item_for_stream_id, m3u_load_live_groups, m3u_load_rewrite_playlist};
 use crate::repository::storage_const;
 use crate::utils::debug_if_enabled;
 use axum::response::IntoResponse;
 use bytes::Bytes;
 use futures::stream;
 use log::{debug, error};
-use shared::model::{FieldGetAccessor, PlaylistEntry, PlaylistItemType, TargetType, UserConnectionPermission, XtreamCluster};
-use shared::utils::{concat_path, extract_extension_from_url, sanitize_sensitive_info, HLS_EXT};
+use shared::model::{FieldGetAccessor, PlaylistEntry, PlaylistItemType, PlaylistStripField, TargetType, UserConnectionPermission, XtreamCluster};
+use shared::utils::{concat_path, extract_extension_from_url, sanitize_sensitive_info, DASH_EXT, HLS_EXT};
+use std::fmt::Write;
 use std::sync::Arc;
 
-async fn m3u_api(api_req: &UserApiRequest, app_state: &AppState) -> impl IntoResponse + Send {
+async fn m3u_api(fingerprint: &Fingerprint, api_req: &UserApiRequest, app_state: &AppState) -> impl IntoResponse + Send {
     match get_user_target(api_req, app_state) {
+        Some((user, _target)) if user.country_denied(app_state, &fingerprint.client_ip) => {
+            axum::http::StatusCode::FORBIDDEN.into_response()
+        }
         Some((user, target)) => {
-            match m3u_load_rewrite_playlist(&app_state.app_config, &target, &user).await {
+            match m3u_load_rewrite_playlist(&app_state.app_config, &target, &user, &PlaylistStripField::parse_list(&api_req.strip), &app_state.playlists.query_cache).await {
                 Ok(m3u_iter) => {
                     // Convert the iterator into a stream of `Bytes`
                     let content_stream = stream::iter(m3u_iter.map(|line| {
@@ -58,17 +63,19 @@ async fn m3u_api(api_req: &UserApiRequest, app_state: &AppState) -> impl IntoRes
 }
 
 async fn m3u_api_get(
+    fingerprint: Fingerprint,
     axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
 ) -> impl IntoResponse + Send {
-    m3u_api(&api_req, &app_state).await
+    m3u_api(&fingerprint, &api_req, &app_state).await
 }
 
 async fn m3u_api_post(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     axum::extract::Form(api_req): axum::extract::Form<UserApiRequest>,
 ) -> impl IntoResponse + Send {
-    m3u_api(&api_req, &app_state).await.into_response()
+    m3u_api(&fingerprint, &api_req, &app_state).await.into_response()
 }
 
 #[allow(clippy::too_many_lines)]
@@ -80,6 +87,10 @@ async fn m3u_api_stream(
     stream_req: ApiStreamRequest<'_>,
     // _addr: &std::net::SocketAddr,
 ) -> impl IntoResponse + Send {
+    if app_state.shutdown.is_draining() {
+        return shutdown_in_progress_response();
+    }
+
     let (user, target) = try_option_bad_request!(
         get_user_target_by_credentials(
             stream_req.username,
@@ -104,6 +115,14 @@ async fn m3u_api_stream(
         .into_response();
     }
 
+    if user.country_denied(app_state, &fingerprint.client_ip) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
+    if account_sharing_denied(app_state, &user, fingerprint) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
     let target_name = &target.name;
     if !target.has_output(TargetType::M3u) {
         debug!("Target has no m3u playlist {target_name}");
@@ -204,6 +223,12 @@ async fn m3u_api_stream(
         .into_response();
     }
 
+    // The live channels of the hls output are remuxed, redirects would hand out the raw ts stream.
+    if pli.item_type == PlaylistItemType::Live && stream_ext.as_deref() == Some(HLS_EXT) && has_hls_output(&target) {
+        return handle_hls_output_stream_request(fingerprint, app_state, &user, &pli.url, virtual_id, &input,
+                                                req_headers, connection_permission).await;
+    }
+
     let context = ApiStreamContext::try_from(cluster).unwrap_or(ApiStreamContext::Live);
 
     let redirect_params = RedirectParams {
@@ -217,6 +242,7 @@ async fn m3u_api_stream(
         stream_ext: stream_ext.as_deref(),
         req_context: context,
         action_path: "", // TODO is there timeshift or something like that ?
+        client_ip: &fingerprint.client_ip,
     };
 
     if let Some(response) = redirect_response(app_state, &redirect_params).await {
@@ -228,6 +254,21 @@ async fn m3u_api_stream(
             .map_or_else(String::new, std::string::ToString::to_string)
     });
 
+    if user.proxy.is_dash() && pli.item_type.is_live() && extension == DASH_EXT {
+        return handle_dash_stream_request(
+            fingerprint,
+            app_state,
+            &user,
+            user_session.as_ref(),
+            &pli.url,
+            pli.virtual_id,
+            &input,
+            req_headers,
+            connection_permission,
+        )
+        .await;
+    }
+
     let is_hls_request = pli.item_type == PlaylistItemType::LiveHls
         || pli.item_type == PlaylistItemType::LiveDash
         || extension == HLS_EXT;
@@ -259,6 +300,7 @@ async fn m3u_api_stream(
         &target,
         &user,
         connection_permission,
+        api_req.get_quality(),
     )
     .await
     .into_response()
@@ -305,17 +347,21 @@ async fn m3u_api_resource(
         };
 
     let stream_url = m3u_item.get_field(resource.as_str());
+    let is_logo = resource.eq_ignore_ascii_case("logo") || resource.eq_ignore_ascii_case("logo_small");
     match stream_url {
         None => axum::http::StatusCode::NOT_FOUND.into_response(),
         Some(url) => {
-            if user.proxy.is_redirect(m3u_item.item_type)
-                || target.is_force_redirect(m3u_item.item_type)
+            if (user.proxy.is_redirect(m3u_item.item_type)
+                || target.is_force_redirect(m3u_item.item_type))
+                && !(is_logo && app_state.app_config.is_logo_proxy_enabled())
             {
                 debug!(
                     "Redirecting stream request to {}",
                     sanitize_sensitive_info(&url)
                 );
                 redirect(&url).into_response()
+            } else if is_logo {
+                logo_response(&app_state, &url, &req_headers, None).await
             } else {
                 resource_response(&app_state, &url, &req_headers, None)
                     .await
@@ -357,6 +403,95 @@ create_m3u_api_stream!(m3u_api_live_stream, ApiStreamContext::Live);
 create_m3u_api_stream!(m3u_api_series_stream, ApiStreamContext::Series);
 create_m3u_api_stream!(m3u_api_movie_stream, ApiStreamContext::Movie);
 
+/// Nominal bandwidth of the channels in the group master playlist, the provider does not tell the real one.
+const HLS_MASTER_BANDWIDTH: u32 = 2_000_000;
+
+fn has_hls_output(target: &ConfigTarget) -> bool {
+    target.get_m3u_output().is_some_and(|output| output.hls)
+}
+
+/// Appends the segments percent encoded, group names can contain any character.
+fn create_m3u8_url(base_url: &str, segments: &[&str]) -> String {
+    match url::Url::parse(base_url) {
+        Ok(mut url) => {
+            if let Ok(mut path) = url.path_segments_mut() {
+                path.pop_if_empty().extend(segments);
+            }
+            url.to_string()
+        }
+        Err(_) => std::iter::once(base_url).chain(segments.iter().copied()).collect::<Vec<_>>().join("/"),
+    }
+}
+
+fn get_hls_output_user_target(username: &str, password: &str, app_state: &AppState) -> Result<(ProxyUserCredentials, Arc<ConfigTarget>), axum::http::StatusCode> {
+    let Some((user, target)) = app_state.app_config.get_target_for_user(username, password) else {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    };
+    if user.permission_denied(app_state) {
+        return Err(axum::http::StatusCode::FORBIDDEN);
+    }
+    if !has_hls_output(&target) {
+        debug!("Target has no m3u hls output {}", target.name);
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    Ok((user, target))
+}
+
+/// Lists the group master playlists of the hls output.
+async fn m3u8_api_index(
+    axum::extract::Path((username, password)): axum::extract::Path<(String, String)>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    let (user, target) = match get_hls_output_user_target(&username, &password, &app_state) {
+        Ok(user_target) => user_target,
+        Err(status) => return status.into_response(),
+    };
+    let groups = match m3u_load_live_groups(&app_state.app_config, &target, &user).await {
+        Ok(groups) => groups,
+        Err(err) => {
+            error!("{}", sanitize_sensitive_info(err.to_string().as_str()));
+            return axum::http::StatusCode::NO_CONTENT.into_response();
+        }
+    };
+    let base_url = app_state.app_config.get_target_base_url(&target, &user);
+    let mut content = String::from("#EXTM3U\n");
+    for group in groups.keys() {
+        let url = create_m3u8_url(&base_url, &[storage_const::M3U8_PATH, &user.username, &user.password, &format!("{group}{HLS_EXT}")]);
+        let _ = writeln!(content, "#EXTINF:-1 group-title=\"{}\",{group}\n{url}", group.replace('"', "'"));
+    }
+    hls_response(content).into_response()
+}
+
+/// HLS master playlist with the live channels of a group as variant streams.
+async fn m3u8_api_group(
+    axum::extract::Path((username, password, group)): axum::extract::Path<(String, String, String)>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    let (user, target) = match get_hls_output_user_target(&username, &password, &app_state) {
+        Ok(user_target) => user_target,
+        Err(status) => return status.into_response(),
+    };
+    let group = group.strip_suffix(HLS_EXT).unwrap_or(&group);
+    let channels = match m3u_load_live_groups(&app_state.app_config, &target, &user).await {
+        Ok(mut groups) => groups.swap_remove(group),
+        Err(err) => {
+            error!("{}", sanitize_sensitive_info(err.to_string().as_str()));
+            None
+        }
+    };
+    let Some(channels) = channels else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    let base_url = app_state.app_config.get_target_base_url(&target, &user);
+    let mut content = String::from("#EXTM3U\n#EXT-X-VERSION:3\n");
+    for pli in &channels {
+        let name = if pli.title.is_empty() { &pli.name } else { &pli.title };
+        let url = format!("{base_url}/{}/{}/{}/{}{HLS_EXT}", storage_const::M3U_STREAM_PATH, user.username, user.password, pli.virtual_id);
+        let _ = writeln!(content, "#EXT-X-STREAM-INF:BANDWIDTH={HLS_MASTER_BANDWIDTH},NAME=\"{}\"\n{url}", name.replace('"', "'"));
+    }
+    hls_response(content).into_response()
+}
+
 macro_rules! register_m3u_api_stream {
      ($router:expr, [$(($path:expr, $fn_name:ident)),*]) => {{
          $router
@@ -407,4 +542,6 @@ pub fn m3u_api_register() -> axum::Router<Arc<AppState>> {
         ),
         axum::routing::get(m3u_api_resource),
     )
+        .route(&format!("/{}/{{username}}/{{password}}", storage_const::M3U8_PATH), axum::routing::get(m3u8_api_index))
+        .route(&format!("/{}/{{username}}/{{password}}/{{group}}", storage_const::M3U8_PATH), axum::routing::get(m3u8_api_group))
 }
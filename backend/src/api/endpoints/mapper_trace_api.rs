@@ -0,0 +1,22 @@
+use crate::api::api_utils::api_error_response_with_detail;
+use crate::api::model::AppState;
+use crate::processing::mapper_trace::trace_mappings;
+use axum::response::IntoResponse;
+use shared::model::{ApiErrorCode, MapperTraceRequest};
+use std::sync::Arc;
+
+/// Runs the mappings of a target on a sample of the stored playlist and returns the steps of the scripts.
+async fn mapper_trace(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(request): axum::extract::Json<MapperTraceRequest>,
+) -> axum::response::Response {
+    match trace_mappings(&app_state.app_config, &request).await {
+        Ok(result) => axum::Json(result).into_response(),
+        Err(err) => api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidContent, "Invalid mapper script", err.to_string()),
+    }
+}
+
+pub fn mapper_trace_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/mapping/trace", axum::routing::post(mapper_trace))
+}
@@ -0,0 +1,35 @@
+use crate::api::api_utils::api_error_response_with_detail;
+use crate::api::model::AppState;
+use crate::auth::require_operator;
+use crate::utils::media_server::register_target_media_servers;
+use axum::response::IntoResponse;
+use shared::model::ApiErrorCode;
+use std::sync::Arc;
+
+/// Registers the playlist and epg of the target with its media servers without a playlist update.
+async fn media_server_register(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(target_name): axum::extract::Path<String>,
+) -> axum::response::Response {
+    let Some(target) = app_state.app_config.sources.load().sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .find(|target| target.name == target_name)
+        .cloned() else {
+        return api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::TargetNotFound, "Target not found", target_name);
+    };
+    if target.media_servers.is_empty() {
+        return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::NotConfigured, "No media servers configured for target", target_name);
+    }
+    let client = app_state.http_client.load();
+    let errors = register_target_media_servers(&client, &app_state.app_config, &target).await;
+    if errors.is_empty() {
+        axum::http::StatusCode::OK.into_response()
+    } else {
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        api_error_response_with_detail(axum::http::StatusCode::BAD_GATEWAY, ApiErrorCode::UpstreamError, "Media server registration failed", messages.join(", "))
+    }
+}
+
+pub fn media_server_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router.route("/media-server/register/{target}", axum::routing::post(media_server_register).layer(axum::middleware::from_fn(require_operator)))
+}
@@ -1,17 +1,71 @@
+#[cfg(feature = "web-ui")]
 pub(in crate::api) mod download_api;
+#[cfg(feature = "web-ui")]
 pub(in crate::api) mod v1_api;
 pub(in crate::api) mod xtream_api;
 pub(in crate::api) mod m3u_api;
 pub(in crate::api) mod xmltv_api;
+#[cfg(feature = "web-ui")]
 pub(in crate::api) mod web_index;
 pub(in crate::api) mod hls_api;
+#[cfg(feature = "web-ui")]
 mod user_api;
+#[cfg(feature = "hdhomerun")]
 pub(in crate::api) mod hdhomerun_api;
+#[cfg(feature = "web-ui")]
 mod api_playlist_utils;
 pub(in crate::api) mod websocket_api;
+#[cfg(feature = "web-ui")]
+pub(in crate::api) mod log_stream_api;
 pub(in crate::api) mod custom_video_stream_api;
+#[cfg(feature = "web-ui")]
 mod v1_api_playlist;
-mod v1_api_user;
+pub(in crate::api) mod v1_api_user;
 mod v1_api_config;
+#[cfg(feature = "web-ui")]
 mod extract_accept_header;
-mod library_api;
\ No newline at end of file
+#[cfg(feature = "web-ui")]
+mod library_api;
+#[cfg(feature = "web-ui")]
+mod web_push_api;
+#[cfg(feature = "web-ui")]
+mod playlist_export_api;
+#[cfg(feature = "web-ui")]
+mod notification_api;
+#[cfg(feature = "web-ui")]
+mod ui_preferences_api;
+#[cfg(feature = "web-ui")]
+mod privacy_api;
+#[cfg(feature = "web-ui")]
+mod epg_mapping_api;
+#[cfg(feature = "web-ui")]
+mod playlist_watermark_api;
+#[cfg(feature = "web-ui")]
+mod stream_session_api;
+#[cfg(feature = "web-ui")]
+mod filter_test_api;
+#[cfg(feature = "web-ui")]
+mod mapper_trace_api;
+#[cfg(feature = "web-ui")]
+mod job_log_api;
+#[cfg(feature = "web-ui")]
+mod processing_stats_api;
+#[cfg(feature = "web-ui")]
+mod media_server_api;
+#[cfg(feature = "web-ui")]
+mod playlist_preview_api;
+#[cfg(feature = "web-ui")]
+mod playlist_browse_api;
+#[cfg(feature = "web-ui")]
+mod sources_api;
+#[cfg(feature = "web-ui")]
+mod api_docs_api;
+#[cfg(feature = "web-ui")]
+mod channel_health_api;
+#[cfg(feature = "web-ui")]
+mod playback_error_api;
+#[cfg(feature = "web-ui")]
+mod cache_api;
+#[cfg(feature = "web-ui")]
+mod debug_api;
+pub(in crate::api) mod public_status_api;
@@ -0,0 +1,39 @@
+use crate::api::model::AppState;
+use crate::auth::WebUiUsername;
+use crate::repository::{load_notifications, mark_notifications_read};
+use axum::response::IntoResponse;
+use log::error;
+use shared::model::NotificationReadRequest;
+use std::sync::Arc;
+
+/// Without web ui authentication all requests share the state of the `admin` user.
+pub(in crate::api::endpoints) fn get_username(user: Option<axum::Extension<WebUiUsername>>) -> String {
+    user.map_or_else(|| "admin".to_string(), |axum::Extension(WebUiUsername(username))| username)
+}
+
+async fn notifications(
+    user: Option<axum::Extension<WebUiUsername>>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    axum::Json(load_notifications(&app_state.app_config, &get_username(user)).await).into_response()
+}
+
+async fn notifications_read(
+    user: Option<axum::Extension<WebUiUsername>>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::Json(request): axum::Json<NotificationReadRequest>,
+) -> axum::response::Response {
+    match mark_notifications_read(&app_state.app_config, &get_username(user), &request.ids).await {
+        Ok(()) => axum::http::StatusCode::OK.into_response(),
+        Err(err) => {
+            error!("{err}");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub fn notification_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/notifications", axum::routing::get(notifications))
+        .route("/notifications/read", axum::routing::post(notifications_read))
+}
@@ -0,0 +1,99 @@
+use crate::api::api_utils::{api_error_response_with_detail, create_api_proxy_user, get_user_target_by_username, get_username_from_auth_header, internal_server_error};
+use crate::api::model::AppState;
+use crate::auth::AuthBearer;
+use crate::model::ConfigTarget;
+use crate::repository::{append_playback_error, m3u_get_item_for_stream_id, query_stream_sessions, xtream_get_item_for_stream_id};
+use axum::response::IntoResponse;
+use log::error;
+use shared::model::{ApiErrorCode, PlaybackErrorDto, PlaybackErrorReportDto, PlaylistItem, StreamSessionQuery, TargetType};
+use shared::utils::current_time_secs;
+use std::sync::Arc;
+
+// Reports within this time after the end of a stream session are correlated with the session.
+const SESSION_CORRELATION_SECS: u64 = 300;
+const MAX_MESSAGE_LENGTH: usize = 256;
+
+async fn get_channel(app_state: &Arc<AppState>, target: &ConfigTarget, virtual_id: u32) -> Option<PlaylistItem> {
+    if target.has_output(TargetType::Xtream) {
+        xtream_get_item_for_stream_id(virtual_id, app_state, target, None).await.ok().map(|item| PlaylistItem::from(&item))
+    } else if target.has_output(TargetType::M3u) {
+        m3u_get_item_for_stream_id(virtual_id, app_state, target).await.ok().map(|item| PlaylistItem::from(&item))
+    } else {
+        None
+    }
+}
+
+/// The active stream or the last stream session of the user on the channel, returns the session start and provider.
+async fn find_session(app_state: &AppState, username: &str, target_id: u16, virtual_id: u32) -> Option<(u64, String)> {
+    if let Some(stream) = app_state.active_users.active_streams().await.into_iter()
+        .find(|stream| stream.username == username && stream.channel.target_id == target_id && stream.channel.virtual_id == virtual_id) {
+        return Some((stream.ts, stream.provider));
+    }
+    let query = StreamSessionQuery {
+        from: Some(current_time_secs().saturating_sub(SESSION_CORRELATION_SECS)),
+        to: None,
+        username: Some(username.to_string()),
+    };
+    query_stream_sessions(&app_state.app_config, &query).await.into_iter()
+        .find(|session| session.target_id == target_id && session.virtual_id == virtual_id)
+        .map(|session| (session.start, session.provider))
+}
+
+async fn record_playback_error(app_state: &Arc<AppState>, username: &str, target: &ConfigTarget, report: PlaybackErrorReportDto) -> axum::response::Response {
+    let Some(channel) = get_channel(app_state, target, report.virtual_id).await else {
+        return api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::ChannelNotFound, "Channel not found", report.virtual_id.to_string());
+    };
+    let session = find_session(app_state, username, target.id, report.virtual_id).await;
+    let playback_error = PlaybackErrorDto {
+        username: username.to_string(),
+        target: target.name.clone(),
+        input_name: channel.header.input_name.to_string(),
+        virtual_id: report.virtual_id,
+        title: channel.header.title.to_string(),
+        kind: report.kind,
+        status: report.status,
+        message: report.message.map(|message| message.chars().take(MAX_MESSAGE_LENGTH).collect()),
+        session_start: session.as_ref().map(|(start, _)| *start),
+        provider: session.map(|(_, provider)| provider),
+        ts: chrono::Utc::now().timestamp(),
+    };
+    match append_playback_error(&app_state.app_config, playback_error).await {
+        Ok(()) => axum::http::StatusCode::ACCEPTED.into_response(),
+        Err(err) => {
+            error!("{err}");
+            internal_server_error!()
+        }
+    }
+}
+
+/// Playback error of the preview player, the preview streams are opened with the api user.
+async fn playback_error(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(report): axum::extract::Json<PlaybackErrorReportDto>,
+) -> axum::response::Response {
+    let Some(target) = app_state.app_config.get_target_by_id(report.target_id) else {
+        return api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::TargetNotFound, "Target not found", report.target_id.to_string());
+    };
+    let username = create_api_proxy_user(&app_state).username.clone();
+    record_playback_error(&app_state, &username, &target, report).await
+}
+
+/// Playback error of the user portal, only the target of the user can be reported.
+pub(in crate::api::endpoints) async fn user_playback_error(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(report): axum::extract::Json<PlaybackErrorReportDto>,
+) -> axum::response::Response {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        if let Some((user, target)) = get_user_target_by_username(username.as_str(), &app_state) {
+            if !user.permission_denied(&app_state) && target.id == report.target_id {
+                return record_playback_error(&app_state, &username, &target, report).await;
+            }
+        }
+    }
+    axum::http::StatusCode::FORBIDDEN.into_response()
+}
+
+pub fn playback_error_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router.route("/playback/errors", axum::routing::post(playback_error))
+}
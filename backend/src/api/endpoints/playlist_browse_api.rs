@@ -0,0 +1,314 @@
+use crate::api::api_utils::{api_error_response, json_or_bin_response};
+use crate::api::endpoints::extract_accept_header::ExtractAcceptHeader;
+use crate::api::model::AppState;
+use crate::repository::{iter_raw_m3u_input_playlist, iter_raw_m3u_target_playlist, iter_raw_xtream_input_playlist, iter_raw_xtream_target_playlist};
+use crate::utils::{encode_base64_string, FileReadGuard};
+use axum::response::IntoResponse;
+use base64::engine::general_purpose;
+use base64::Engine;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use shared::model::{ApiErrorCode, InputType, PlaylistBrowseFilter, PlaylistBrowseGroupDto, PlaylistBrowseGroupsRequest, PlaylistBrowsePageDto,
+                    PlaylistBrowseRequest, PlaylistBrowseSortField, PlaylistBrowseSortOrder, PlaylistItemType, PlaylistRequest, TargetType,
+                    UiPlaylistItem, XtreamCluster};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const DEFAULT_PAGE_SIZE: u16 = 100;
+const MAX_PAGE_SIZE: u16 = 1000;
+
+type BrowseIterator = Box<dyn Iterator<Item=UiPlaylistItem> + Send>;
+
+/// Position of the last item of a page. The playlist position keeps the order stable for equal names.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct BrowseKey {
+    #[serde(rename = "k")]
+    text: String,
+    #[serde(rename = "p")]
+    position: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BrowseCursor {
+    #[serde(rename = "s")]
+    sort_by: PlaylistBrowseSortField,
+    #[serde(rename = "o")]
+    sort_order: PlaylistBrowseSortOrder,
+    #[serde(flatten)]
+    key: BrowseKey,
+}
+
+fn encode_cursor(sort_by: PlaylistBrowseSortField, sort_order: PlaylistBrowseSortOrder, key: BrowseKey) -> Option<String> {
+    serde_json::to_vec(&BrowseCursor { sort_by, sort_order, key }).ok().map(|json| encode_base64_string(&json))
+}
+
+/// A cursor is only valid for the sorting it was created with.
+fn decode_cursor(cursor: &str, sort_by: PlaylistBrowseSortField, sort_order: PlaylistBrowseSortOrder) -> Option<BrowseKey> {
+    let json = general_purpose::URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    let cursor: BrowseCursor = serde_json::from_slice(&json).ok()?;
+    (cursor.sort_by == sort_by && cursor.sort_order == sort_order).then_some(cursor.key)
+}
+
+struct BrowseMatcher<'a> {
+    group: Option<&'a str>,
+    name: Option<Regex>,
+    item_type: Option<PlaylistItemType>,
+}
+
+impl<'a> BrowseMatcher<'a> {
+    fn new(filter: &'a PlaylistBrowseFilter) -> Result<Self, regex::Error> {
+        let name = match filter.name.as_deref().map(str::trim).filter(|name| !name.is_empty()) {
+            Some(name) => Some(RegexBuilder::new(name).case_insensitive(true).build()?),
+            None => None,
+        };
+        Ok(Self {
+            group: filter.group.as_deref(),
+            name,
+            item_type: filter.item_type,
+        })
+    }
+
+    fn is_match(&self, item: &UiPlaylistItem) -> bool {
+        self.group.is_none_or(|group| &*item.group == group)
+            && self.item_type.is_none_or(|item_type| item.item_type == item_type)
+            && self.name.as_ref().is_none_or(|regex| regex.is_match(&item.title) || regex.is_match(&item.name))
+    }
+}
+
+fn browse_key(item: &UiPlaylistItem, position: u64, sort_by: PlaylistBrowseSortField) -> BrowseKey {
+    let text = match sort_by {
+        PlaylistBrowseSortField::Default => String::new(),
+        PlaylistBrowseSortField::Name => item.title.to_lowercase(),
+        PlaylistBrowseSortField::Group => item.group.to_lowercase(),
+    };
+    BrowseKey { text, position }
+}
+
+fn compare_keys(a: &BrowseKey, b: &BrowseKey, sort_order: PlaylistBrowseSortOrder) -> Ordering {
+    match sort_order {
+        PlaylistBrowseSortOrder::Asc => a.cmp(b),
+        PlaylistBrowseSortOrder::Desc => b.cmp(a),
+    }
+}
+
+/// Collects the page after the cursor in a single pass over the playlist.
+/// Only `2 * limit` candidates are kept in memory, the playlist itself is never loaded completely.
+fn browse_page(items: impl Iterator<Item=UiPlaylistItem>, matcher: &BrowseMatcher, sort_by: PlaylistBrowseSortField,
+               sort_order: PlaylistBrowseSortOrder, after: Option<&BrowseKey>, limit: usize) -> PlaylistBrowsePageDto {
+    let mut total = 0;
+    let mut remaining = 0;
+    let mut candidates: Vec<(BrowseKey, UiPlaylistItem)> = Vec::with_capacity(limit * 2 + 1);
+    let sort_candidates = |candidates: &mut Vec<(BrowseKey, UiPlaylistItem)>| {
+        candidates.sort_unstable_by(|(a, _), (b, _)| compare_keys(a, b, sort_order));
+        candidates.truncate(limit);
+    };
+
+    for (position, item) in (0u64..).zip(items) {
+        if !matcher.is_match(&item) {
+            continue;
+        }
+        total += 1;
+        let key = browse_key(&item, position, sort_by);
+        if after.is_some_and(|after| compare_keys(&key, after, sort_order) != Ordering::Greater) {
+            continue;
+        }
+        remaining += 1;
+        candidates.push((key, item));
+        if candidates.len() > limit * 2 {
+            sort_candidates(&mut candidates);
+        }
+    }
+    sort_candidates(&mut candidates);
+
+    let next_cursor = if remaining > limit {
+        candidates.last().and_then(|(key, _)| encode_cursor(sort_by, sort_order, key.clone()))
+    } else {
+        None
+    };
+    PlaylistBrowsePageDto {
+        items: candidates.into_iter().map(|(_, item)| item).collect(),
+        total,
+        next_cursor,
+    }
+}
+
+fn is_browsable_series_item(cluster: XtreamCluster, item_type: PlaylistItemType) -> bool {
+    cluster != XtreamCluster::Series || !matches!(item_type, PlaylistItemType::Series | PlaylistItemType::LocalSeries)
+}
+
+/// Iterates the stored playlist of a target or an input in the order of its sorted index.
+async fn iter_browse_items(app_state: &AppState, source: &PlaylistRequest, cluster: XtreamCluster)
+                           -> Result<Option<(FileReadGuard, BrowseIterator)>, axum::response::Response> {
+    let app_config = &app_state.app_config;
+    match source {
+        PlaylistRequest::Target(target_id) => {
+            let Some(target) = app_config.get_target_by_id(*target_id) else {
+                return Err(api_error_response(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::TargetNotFound, "Target not found"));
+            };
+            if target.has_output(TargetType::Xtream) {
+                Ok(iter_raw_xtream_target_playlist(app_config, &target, cluster).await.map(|(guard, items)| {
+                    let items: BrowseIterator = Box::new(items.filter(move |pli| is_browsable_series_item(cluster, pli.item_type)).map(UiPlaylistItem::from));
+                    (guard, items)
+                }))
+            } else if target.has_output(TargetType::M3u) {
+                Ok(iter_raw_m3u_target_playlist(app_config, &target, Some(cluster)).await.map(|(guard, items)| {
+                    let items: BrowseIterator = Box::new(items.filter(move |pli| is_browsable_series_item(cluster, pli.item_type)).map(UiPlaylistItem::from));
+                    (guard, items)
+                }))
+            } else {
+                Err(api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UnsupportedOperation, "Only targets with xtream or m3u output can be browsed"))
+            }
+        }
+        PlaylistRequest::Input(input_id) => {
+            let Some(input) = app_config.get_input_by_id(*input_id) else {
+                return Err(api_error_response(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::InvalidArguments, "Input not found"));
+            };
+            match input.input_type {
+                InputType::Xtream | InputType::XtreamBatch => Ok(iter_raw_xtream_input_playlist(app_config, &input, cluster).await.map(|(guard, items)| {
+                    let items: BrowseIterator = Box::new(items.map(UiPlaylistItem::from));
+                    (guard, items)
+                })),
+                InputType::M3u | InputType::M3uBatch | InputType::Udp => Ok(iter_raw_m3u_input_playlist(app_config, &input, Some(cluster)).await.map(|(guard, items)| {
+                    let items: BrowseIterator = Box::new(items.map(UiPlaylistItem::from));
+                    (guard, items)
+                })),
+                InputType::Library => Err(api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UnsupportedOperation, "Library inputs are not supported on this endpoint")),
+            }
+        }
+        PlaylistRequest::CustomXtream(_) | PlaylistRequest::CustomM3u(_) => {
+            Err(api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UnsupportedOperation, "Only stored playlists of targets and inputs can be browsed"))
+        }
+    }
+}
+
+/// Returns one page of the filtered and sorted playlist, the next page is requested with `next_cursor`.
+async fn playlist_browse(
+    ExtractAcceptHeader(accept): ExtractAcceptHeader,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(request): axum::extract::Json<PlaylistBrowseRequest>,
+) -> axum::response::Response {
+    let limit = usize::from(request.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE));
+    let Ok(matcher) = BrowseMatcher::new(&request.filter) else {
+        return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid name filter regex");
+    };
+    let after = match request.cursor.as_deref() {
+        Some(cursor) => match decode_cursor(cursor, request.sort_by, request.sort_order) {
+            Some(key) => Some(key),
+            None => return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid cursor"),
+        },
+        None => None,
+    };
+
+    let page = match iter_browse_items(&app_state, &request.source, request.cluster).await {
+        Ok(Some((_guard, items))) => browse_page(items, &matcher, request.sort_by, request.sort_order, after.as_ref(), limit),
+        Ok(None) => PlaylistBrowsePageDto { items: Vec::new(), total: 0, next_cursor: None },
+        Err(response) => return response,
+    };
+    json_or_bin_response(accept.as_deref(), &page).into_response()
+}
+
+/// Returns the groups of the playlist with their item count, the items are loaded with `/playlist/browse`.
+async fn playlist_browse_groups(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(request): axum::extract::Json<PlaylistBrowseGroupsRequest>,
+) -> axum::response::Response {
+    let groups = match iter_browse_items(&app_state, &request.source, request.cluster).await {
+        Ok(Some((_guard, items))) => collect_groups(items),
+        Ok(None) => Vec::new(),
+        Err(response) => return response,
+    };
+    axum::Json(groups).into_response()
+}
+
+/// The groups keep the order of the playlist, the id is the position of the group.
+fn collect_groups(items: impl Iterator<Item=UiPlaylistItem>) -> Vec<PlaylistBrowseGroupDto> {
+    let mut groups: Vec<PlaylistBrowseGroupDto> = Vec::new();
+    let mut group_index: HashMap<Arc<str>, usize> = HashMap::new();
+    for item in items {
+        if let Some(&index) = group_index.get(&item.group) {
+            groups[index].count += 1;
+        } else {
+            group_index.insert(Arc::clone(&item.group), groups.len());
+            groups.push(PlaylistBrowseGroupDto {
+                id: u32::try_from(groups.len() + 1).unwrap_or(u32::MAX),
+                title: item.group.to_string(),
+                count: 1,
+            });
+        }
+    }
+    groups
+}
+
+pub fn playlist_browse_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/playlist/browse", axum::routing::post(playlist_browse))
+        .route("/playlist/browse/groups", axum::routing::post(playlist_browse_groups))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{browse_page, collect_groups, decode_cursor, BrowseMatcher};
+    use shared::model::{PlaylistBrowseFilter, PlaylistBrowseSortField, PlaylistBrowseSortOrder, PlaylistItemType, UiPlaylistItem, XtreamCluster};
+
+    fn item(virtual_id: u32, title: &str, group: &str) -> UiPlaylistItem {
+        UiPlaylistItem {
+            virtual_id,
+            provider_id: virtual_id.to_string().into(),
+            name: title.into(),
+            title: title.into(),
+            group: group.into(),
+            logo: "".into(),
+            url: "".into(),
+            item_type: PlaylistItemType::Live,
+            xtream_cluster: XtreamCluster::Live,
+            category_id: 0,
+            rating: 0.0,
+        }
+    }
+
+    fn items() -> Vec<UiPlaylistItem> {
+        vec![item(1, "Delta", "News"), item(2, "alpha", "Sports"), item(3, "Charlie", "News"),
+             item(4, "Bravo", "Sports"), item(5, "Echo", "News")]
+    }
+
+    fn titles(items: &[UiPlaylistItem]) -> Vec<&str> {
+        items.iter().map(|item| &*item.title).collect()
+    }
+
+    #[test]
+    fn test_browse_page_cursor() {
+        let filter = PlaylistBrowseFilter::default();
+        let matcher = BrowseMatcher::new(&filter).unwrap();
+        let (sort_by, sort_order) = (PlaylistBrowseSortField::Name, PlaylistBrowseSortOrder::Asc);
+
+        let first = browse_page(items().into_iter(), &matcher, sort_by, sort_order, None, 2);
+        assert_eq!(first.total, 5);
+        assert_eq!(titles(&first.items), vec!["alpha", "Bravo"]);
+        let after = decode_cursor(first.next_cursor.as_deref().unwrap(), sort_by, sort_order).unwrap();
+        assert!(decode_cursor(first.next_cursor.as_deref().unwrap(), sort_by, PlaylistBrowseSortOrder::Desc).is_none());
+
+        let second = browse_page(items().into_iter(), &matcher, sort_by, sort_order, Some(&after), 2);
+        assert_eq!(titles(&second.items), vec!["Charlie", "Delta"]);
+        let after = decode_cursor(second.next_cursor.as_deref().unwrap(), sort_by, sort_order).unwrap();
+
+        let last = browse_page(items().into_iter(), &matcher, sort_by, sort_order, Some(&after), 2);
+        assert_eq!(titles(&last.items), vec!["Echo"]);
+        assert!(last.next_cursor.is_none());
+
+        let desc = browse_page(items().into_iter(), &matcher, PlaylistBrowseSortField::Default, PlaylistBrowseSortOrder::Desc, None, 10);
+        assert_eq!(titles(&desc.items), vec!["Echo", "Bravo", "Charlie", "alpha", "Delta"]);
+    }
+
+    #[test]
+    fn test_browse_page_filter() {
+        let filter = PlaylistBrowseFilter { group: Some("News".to_string()), name: Some("^[cd]".to_string()), item_type: None };
+        let matcher = BrowseMatcher::new(&filter).unwrap();
+        let page = browse_page(items().into_iter(), &matcher, PlaylistBrowseSortField::Default, PlaylistBrowseSortOrder::Asc, None, 10);
+        assert_eq!(page.total, 2);
+        assert_eq!(titles(&page.items), vec!["Delta", "Charlie"]);
+
+        let groups = collect_groups(items().into_iter());
+        assert_eq!(groups.iter().map(|group| (group.title.as_str(), group.count)).collect::<Vec<_>>(), vec![("News", 3), ("Sports", 2)]);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::api::api_utils::{api_error_response, api_error_response_with_detail, try_unwrap_body};
+use crate::api::model::{AppState, PlaylistExportRequest};
+use crate::model::{AppConfig, ConfigTarget, MediaQuality};
+use crate::ptt::ptt_parse_title;
+use crate::repository::{iter_raw_m3u_target_playlist, iter_raw_xtream_target_playlist};
+use crate::utils::{create_csv, create_xlsx};
+use axum::response::IntoResponse;
+use log::error;
+use shared::model::{ApiErrorCode, PlaylistItem, PlaylistItemType, TargetType, XtreamCluster};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const CONTENT_TYPE_CSV: &str = "text/csv; charset=utf-8";
+const CONTENT_TYPE_XLSX: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "xlsx" => Ok(Self::Xlsx),
+            _ => Err(format!("Unknown export format {s}, use csv or xlsx")),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ExportColumn {
+    Name,
+    Group,
+    TvgId,
+    Quality,
+    Input,
+    Type,
+    ChannelNo,
+}
+
+impl ExportColumn {
+    const DEFAULT: [ExportColumn; 5] = [Self::Name, Self::Group, Self::TvgId, Self::Quality, Self::Input];
+
+    fn value(self, pli: &PlaylistItem) -> String {
+        let header = &pli.header;
+        match self {
+            Self::Name => if header.title.is_empty() { header.name.to_string() } else { header.title.to_string() },
+            Self::Group => header.group.to_string(),
+            Self::TvgId => header.epg_channel_id.as_deref().unwrap_or_default().to_string(),
+            Self::Quality => get_quality(pli),
+            Self::Input => header.input_name.to_string(),
+            Self::Type => header.xtream_cluster.to_string(),
+            Self::ChannelNo => if header.chno == 0 { String::new() } else { header.chno.to_string() },
+        }
+    }
+}
+
+impl Display for ExportColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Self::Name => "name",
+            Self::Group => "group",
+            Self::TvgId => "tvg_id",
+            Self::Quality => "quality",
+            Self::Input => "input",
+            Self::Type => "type",
+            Self::ChannelNo => "chno",
+        })
+    }
+}
+
+impl FromStr for ExportColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "name" => Ok(Self::Name),
+            "group" => Ok(Self::Group),
+            "tvg_id" | "tvg-id" | "epg_channel_id" => Ok(Self::TvgId),
+            "quality" => Ok(Self::Quality),
+            "input" => Ok(Self::Input),
+            "type" => Ok(Self::Type),
+            "chno" => Ok(Self::ChannelNo),
+            _ => Err(format!("Unknown export column {s}")),
+        }
+    }
+}
+
+/// Movies and episodes have the quality from the provider info, live channels only the tags in their name.
+fn get_quality(pli: &PlaylistItem) -> String {
+    if let Some(quality) = MediaQuality::from_playlist_item(pli).map(|q| q.format_for_filename(" ")).filter(|q| !q.is_empty()) {
+        return quality;
+    }
+    let metadata = ptt_parse_title(&pli.header.name);
+    [metadata.resolution, metadata.quality].into_iter().flatten().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_columns(columns: Option<&str>) -> Result<Vec<ExportColumn>, String> {
+    match columns.map(str::trim).filter(|c| !c.is_empty()) {
+        None => Ok(ExportColumn::DEFAULT.to_vec()),
+        Some(columns) => columns.split(',').map(ExportColumn::from_str).collect(),
+    }
+}
+
+/// Series episodes are left out, the export lists the channels, movies and series.
+async fn collect_rows(app_config: &AppConfig, target: &ConfigTarget, columns: &[ExportColumn]) -> Vec<Vec<String>> {
+    let mut rows = vec![columns.iter().map(ToString::to_string).collect::<Vec<_>>()];
+    let is_listed = |pli: &PlaylistItem| !matches!(pli.header.item_type, PlaylistItemType::Series | PlaylistItemType::LocalSeries);
+    let mut add_item = |pli: PlaylistItem| {
+        if is_listed(&pli) {
+            rows.push(columns.iter().map(|column| column.value(&pli)).collect());
+        }
+    };
+    if target.has_output(TargetType::Xtream) {
+        for cluster in [XtreamCluster::Live, XtreamCluster::Video, XtreamCluster::Series] {
+            if let Some((_guard, items)) = iter_raw_xtream_target_playlist(app_config, target, cluster).await {
+                items.for_each(|item| add_item(PlaylistItem::from(&item)));
+            }
+        }
+    } else if target.has_output(TargetType::M3u) {
+        if let Some((_guard, items)) = iter_raw_m3u_target_playlist(app_config, target, None).await {
+            items.for_each(|item| add_item(PlaylistItem::from(&item)));
+        }
+    }
+    rows
+}
+
+/// Exports the processed playlist of a target as csv or xlsx file.
+async fn playlist_export(
+    axum::extract::Path(target_name): axum::extract::Path<String>,
+    axum::extract::Query(request): axum::extract::Query<PlaylistExportRequest>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    let target = app_state.app_config.sources.load().sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .find(|target| target.name == target_name)
+        .map(Arc::clone);
+    let Some(target) = target else {
+        return api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::TargetNotFound, "Target not found", target_name);
+    };
+    if !target.has_output(TargetType::Xtream) && !target.has_output(TargetType::M3u) {
+        return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UnsupportedOperation, "Only targets with xtream or m3u output can be exported");
+    }
+    let format = match request.format.as_deref().map_or(Ok(ExportFormat::Csv), ExportFormat::from_str) {
+        Ok(format) => format,
+        Err(err) => return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid export format", err),
+    };
+    let columns = match parse_columns(request.columns.as_deref()) {
+        Ok(columns) => columns,
+        Err(err) => return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid export columns", err),
+    };
+
+    let rows = collect_rows(&app_state.app_config, &target, &columns).await;
+    let (content_type, extension, body) = match format {
+        ExportFormat::Csv => (CONTENT_TYPE_CSV, "csv", create_csv(&rows).into_bytes()),
+        ExportFormat::Xlsx => match create_xlsx(&target.name, &rows) {
+            Ok(content) => (CONTENT_TYPE_XLSX, "xlsx", content),
+            Err(err) => {
+                error!("Failed to create xlsx export for target {}: {err}", target.name);
+                return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        },
+    };
+    let filename: String = target.name.chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, content_type)
+        .header(axum::http::header::CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}.{extension}\""))
+        .body(axum::body::Body::from(body)))
+}
+
+pub fn playlist_export_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router.route("/playlist/export/{target}", axum::routing::get(playlist_export))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_columns, ExportColumn};
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(parse_columns(None).unwrap(), ExportColumn::DEFAULT.to_vec());
+        assert_eq!(parse_columns(Some("name, tvg-id,type")).unwrap(), vec![ExportColumn::Name, ExportColumn::TvgId, ExportColumn::Type]);
+        assert!(parse_columns(Some("name,url")).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::api::api_utils::{api_error_response, api_error_response_with_detail, try_unwrap_body};
+use crate::api::model::AppState;
+use crate::model::{AppConfig, ConfigTarget};
+use crate::repository::{iter_raw_m3u_target_playlist, iter_raw_xtream_target_playlist};
+use axum::response::IntoResponse;
+use shared::model::{ApiErrorCode, M3uPlaylistItem, PlaylistItem, PlaylistItemType, TargetType, XtreamCluster};
+use std::sync::Arc;
+
+const CONTENT_TYPE_M3U: &str = "audio/x-mpegurl; charset=utf-8";
+
+/// The preview lists the channels, movies and series with their provider urls, series episodes are left out.
+async fn create_preview(app_config: &AppConfig, target: &ConfigTarget) -> String {
+    let mut content = String::from("#EXTM3U\n");
+    let mut add_item = |pli: &PlaylistItem| {
+        if !matches!(pli.header.item_type, PlaylistItemType::Series | PlaylistItemType::LocalSeries) {
+            content.push_str(&M3uPlaylistItem::from(pli).to_m3u(target.options.as_ref(), false));
+            content.push('\n');
+        }
+    };
+    if target.has_output(TargetType::M3u) {
+        if let Some((_guard, items)) = iter_raw_m3u_target_playlist(app_config, target, None).await {
+            items.for_each(|item| add_item(&PlaylistItem::from(&item)));
+        }
+    } else if target.has_output(TargetType::Xtream) {
+        for cluster in [XtreamCluster::Live, XtreamCluster::Video, XtreamCluster::Series] {
+            if let Some((_guard, items)) = iter_raw_xtream_target_playlist(app_config, target, cluster).await {
+                items.for_each(|item| add_item(&PlaylistItem::from(&item)));
+            }
+        }
+    }
+    content
+}
+
+/// Returns the processed playlist of a target as m3u, shadow targets are only reachable here.
+async fn playlist_preview(
+    axum::extract::Path(target_name): axum::extract::Path<String>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    let Some(target) = app_state.app_config.sources.load().sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .find(|target| target.name == target_name)
+        .map(Arc::clone) else {
+        return api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::TargetNotFound, "Target not found", target_name);
+    };
+    if !target.has_output(TargetType::Xtream) && !target.has_output(TargetType::M3u) {
+        return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UnsupportedOperation, "Only targets with xtream or m3u output can be previewed");
+    }
+
+    let content = create_preview(&app_state.app_config, &target).await;
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, CONTENT_TYPE_M3U)
+        .body(axum::body::Body::from(content)))
+}
+
+pub fn playlist_preview_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router.route("/playlist/preview/{target}", axum::routing::get(playlist_preview))
+}
@@ -0,0 +1,31 @@
+use crate::api::api_utils::{api_error_response, api_error_response_with_detail};
+use crate::api::model::AppState;
+use crate::auth::require_admin;
+use crate::repository::identify_playlist_watermark;
+use axum::response::IntoResponse;
+use serde_json::json;
+use shared::model::ApiErrorCode;
+use std::sync::Arc;
+
+// Uploaded playlists can be much larger than the default body limit.
+const MAX_PLAYLIST_UPLOAD_SIZE: usize = 256 * 1024 * 1024;
+
+/// Identifies the user from the watermark of an uploaded playlist file.
+async fn identify_watermark(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    body: axum::body::Bytes,
+) -> axum::response::Response {
+    let content = String::from_utf8_lossy(&body);
+    match identify_playlist_watermark(&app_state.app_config, &content) {
+        Some((watermark, Some(username))) => axum::Json(json!({"watermark": watermark, "username": username})).into_response(),
+        Some((watermark, None)) => api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::UserNotFound, "No user found for watermark", watermark),
+        None => api_error_response(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::WatermarkNotFound, "No watermark found"),
+    }
+}
+
+pub fn playlist_watermark_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/playlist/watermark", axum::routing::post(identify_watermark)
+            .layer(axum::extract::DefaultBodyLimit::max(MAX_PLAYLIST_UPLOAD_SIZE))
+            .layer(axum::middleware::from_fn(require_admin)))
+}
@@ -0,0 +1,44 @@
+use crate::api::api_utils::api_error_response_with_detail;
+use crate::api::model::AppState;
+use crate::auth::require_admin;
+use crate::repository::{remove_ui_preferences, remove_user_notifications, remove_user_storage, remove_user_stream_sessions};
+use axum::response::IntoResponse;
+use log::{error, info};
+use serde_json::json;
+use shared::model::ApiErrorCode;
+use shared::utils::sanitize_sensitive_info;
+use std::sync::Arc;
+
+/// Purges the stored data of a user: bouquets of the api user, web ui preferences
+/// the notifications naming the user and the stream session history of the user. The user account itself is deleted through the user api.
+async fn purge_user_data(
+    axum::extract::Path(username): axum::extract::Path<String>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    let config = app_state.app_config.config.load();
+    let bouquets = match remove_user_storage(&config, &username).await {
+        Ok(removed) => removed,
+        Err(err) => {
+            error!("Failed to purge user data of {}: {err}", sanitize_sensitive_info(&username));
+            return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Failed to purge user data", err.to_string());
+        }
+    };
+    let preferences = remove_ui_preferences(&app_state.app_config, &username).await;
+    let notifications = remove_user_notifications(&app_state.app_config, &username).await;
+    let sessions = remove_user_stream_sessions(&app_state.app_config, &username).await;
+    match (preferences, notifications, sessions) {
+        (Ok(preferences), Ok(notifications), Ok(sessions)) => {
+            info!("Purged stored data of user {}", sanitize_sensitive_info(&username));
+            axum::Json(json!({"bouquets": bouquets, "preferences": preferences, "notifications": notifications, "sessions": sessions})).into_response()
+        }
+        (Err(err), _, _) | (_, Err(err), _) | (_, _, Err(err)) => {
+            error!("Failed to purge user data of {}: {err}", sanitize_sensitive_info(&username));
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub fn privacy_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/privacy/user/{username}", axum::routing::delete(purge_user_data).layer(axum::middleware::from_fn(require_admin)))
+}
@@ -0,0 +1,12 @@
+use crate::api::model::AppState;
+use crate::repository::load_processing_stats;
+use shared::model::TargetProcessingStatsDto;
+use std::sync::Arc;
+
+async fn processing_stats(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::Json<Vec<TargetProcessingStatsDto>> {
+    axum::Json(load_processing_stats(&app_state.app_config).await)
+}
+
+pub fn processing_stats_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router.route("/stats/processing", axum::routing::get(processing_stats))
+}
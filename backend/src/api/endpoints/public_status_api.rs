@@ -0,0 +1,155 @@
+use crate::api::api_utils::{get_server_time, try_unwrap_body};
+use crate::api::model::AppState;
+use crate::model::PublicStatusConfig;
+use crate::repository::get_target_playlist_last_update;
+use axum::response::IntoResponse;
+use chrono::{DateTime, Local};
+use shared::model::{PublicServiceStatus, PublicStatusDto};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+const PUBLIC_STATUS_PAGE: &str = include_str!("public_status.html");
+
+fn get_public_status_config(app_state: &AppState) -> Option<PublicStatusConfig> {
+    app_state.app_config.config.load().web_ui.as_ref()
+        .and_then(|web_ui| web_ui.public_status.clone())
+        .filter(|public_status| public_status.enabled)
+}
+
+/// Streaming is degraded when every enabled provider account is at its connection limit.
+async fn get_streaming_status(app_state: &AppState) -> PublicServiceStatus {
+    let sources = app_state.app_config.sources.load();
+    let mut limited = false;
+    for input in sources.inputs.iter().filter(|input| input.enabled) {
+        let aliases = input.aliases.iter().flatten().filter(|alias| alias.enabled).map(|alias| &alias.name);
+        for provider_name in std::iter::once(&input.name).chain(aliases) {
+            if !app_state.active_provider.is_over_limit(provider_name).await {
+                return PublicServiceStatus::Operational;
+            }
+            limited = true;
+        }
+    }
+    if limited { PublicServiceStatus::Degraded } else { PublicServiceStatus::Operational }
+}
+
+fn get_playlist_updated_at(app_state: &AppState) -> Option<i64> {
+    let config = app_state.app_config.config.load();
+    let sources = app_state.app_config.sources.load();
+    sources.sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .filter(|target| target.enabled)
+        .filter_map(|target| get_target_playlist_last_update(&config, target))
+        .max()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+}
+
+async fn create_public_status(app_state: &AppState, public_status: &PublicStatusConfig) -> PublicStatusDto {
+    let streaming = get_streaming_status(app_state).await;
+    PublicStatusDto {
+        status: if public_status.maintenance.is_some() { PublicServiceStatus::Maintenance } else { streaming },
+        streaming,
+        playlist_updated_at: get_playlist_updated_at(app_state),
+        maintenance: public_status.maintenance.clone(),
+        server_time: get_server_time(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn status_label(status: PublicServiceStatus) -> &'static str {
+    match status {
+        PublicServiceStatus::Operational => "Operational",
+        PublicServiceStatus::Degraded => "Degraded",
+        PublicServiceStatus::Maintenance => "Maintenance",
+    }
+}
+
+fn status_class(status: PublicServiceStatus) -> &'static str {
+    match status {
+        PublicServiceStatus::Operational => "ok",
+        PublicServiceStatus::Degraded => "degraded",
+        PublicServiceStatus::Maintenance => "maintenance",
+    }
+}
+
+fn render_public_status(status: &PublicStatusDto) -> String {
+    let playlist_updated_at = status.playlist_updated_at
+        .and_then(|ts| DateTime::from_timestamp(ts, 0))
+        .map_or_else(|| "n/a".to_string(), |dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M %Z").to_string());
+    let maintenance = status.maintenance.as_deref()
+        .map(|text| format!(r#"<p class="maintenance">{}</p>"#, escape_html(text)))
+        .unwrap_or_default();
+    PUBLIC_STATUS_PAGE
+        .replace("{status_class}", status_class(status.status))
+        .replace("{status}", status_label(status.status))
+        .replace("{streaming_class}", status_class(status.streaming))
+        .replace("{streaming}", status_label(status.streaming))
+        .replace("{playlist_updated_at}", &escape_html(&playlist_updated_at))
+        .replace("{server_time}", &escape_html(&status.server_time))
+        .replace("{maintenance}", &maintenance)
+}
+
+async fn public_status_page(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    let Some(public_status) = get_public_status_config(&app_state) else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    let status = create_public_status(&app_state, &public_status).await;
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, mime::TEXT_HTML_UTF_8.as_ref())
+        .header(axum::http::header::CACHE_CONTROL, "no-cache")
+        .body(render_public_status(&status)))
+}
+
+async fn public_status_json(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    let Some(public_status) = get_public_status_config(&app_state) else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    axum::Json(create_public_status(&app_state, &public_status).await).into_response()
+}
+
+/// Registers the unauthenticated status page, it responds with not found unless `web_ui.public_status` is enabled.
+pub fn public_status_api_register() -> axum::Router<Arc<AppState>> {
+    axum::Router::new()
+        .route("/public/status", axum::routing::get(public_status_page))
+        .route("/public/status.json", axum::routing::get(public_status_json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_public_status;
+    use shared::model::{PublicServiceStatus, PublicStatusDto};
+
+    #[test]
+    fn test_render_escapes_maintenance() {
+        let status = PublicStatusDto {
+            status: PublicServiceStatus::Maintenance,
+            streaming: PublicServiceStatus::Operational,
+            playlist_updated_at: None,
+            maintenance: Some("<b>Sunday</b> 02:00".to_string()),
+            server_time: "now".to_string(),
+        };
+        let html = render_public_status(&status);
+        assert!(html.contains("&lt;b&gt;Sunday&lt;/b&gt; 02:00"));
+        assert!(html.contains(r#"class="status maintenance""#));
+        assert!(!html.contains("{status"));
+    }
+}
@@ -0,0 +1,297 @@
+use crate::api::config_file::ConfigFile;
+use crate::api::model::{AppState, EventMessage};
+use crate::auth::{require_admin, require_operator};
+use crate::model::SourcesConfig;
+use crate::utils::{persist_source_config, prepare_sources_batch, read_sources_file, resolve_sources_config};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use log::error;
+use serde_json::json;
+use shared::error::TuliproxError;
+use shared::model::{ApiErrorCode, ApiErrorResponse, ConfigInputDto, ConfigSourceDto, ConfigTargetDto, ConfigType, SourcesConfigDto};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+type EditResult<T> = Result<T, (StatusCode, ApiErrorResponse)>;
+
+fn error_response(status: StatusCode, error: ApiErrorResponse) -> axum::response::Response {
+    (status, axum::Json(error)).into_response()
+}
+
+fn internal_error(message: &str, err: &TuliproxError) -> axum::response::Response {
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, ApiErrorResponse::new(ApiErrorCode::InternalError, message).with_detail(err.to_string()))
+}
+
+/// Reads the sources file without resolving, env variables and secret references are kept when it is written back.
+fn read_raw_sources(app_state: &Arc<AppState>) -> Result<SourcesConfigDto, TuliproxError> {
+    let paths = app_state.app_config.paths.load();
+    read_sources_file(&paths.sources_file_path, false, false, None)
+}
+
+/// Validates the changed sources like they are loaded at startup.
+async fn validate_sources(app_state: &Arc<AppState>, sources: &SourcesConfigDto) -> Result<(), TuliproxError> {
+    let mut resolved = resolve_sources_config(sources)?;
+    let hdhr_config = app_state.app_config.config.load().get_hdhr_device_overview();
+    resolved.prepare(true, hdhr_config.as_ref())?;
+    prepare_sources_batch(&mut resolved, true).await?;
+    SourcesConfig::try_from(&resolved).map(|_| ())
+}
+
+/// Applies the change to the sources file, the file is only written and reloaded when the changed sources are valid.
+async fn edit_sources<T, F>(app_state: &Arc<AppState>, status: StatusCode, edit: F) -> axum::response::Response
+where
+    T: serde::Serialize + Send,
+    F: FnOnce(&mut SourcesConfigDto) -> EditResult<T> + Send,
+{
+    let sources_file = PathBuf::from(&app_state.app_config.paths.load().sources_file_path);
+    let _file_lock = app_state.app_config.file_locks.write_lock(&sources_file).await;
+    let mut sources = match read_raw_sources(app_state) {
+        Ok(sources) => sources,
+        Err(err) => return internal_error("Failed to read sources", &err),
+    };
+    let result = match edit(&mut sources) {
+        Ok(result) => result,
+        Err((status, error)) => return error_response(status, error),
+    };
+    if let Err(err) = validate_sources(app_state, &sources).await {
+        return error_response(StatusCode::BAD_REQUEST, ApiErrorResponse::new(ApiErrorCode::InvalidContent, "Invalid sources").with_detail(err.to_string()));
+    }
+    if let Err(err) = persist_source_config(app_state, None, sources).await {
+        error!("Failed to save source.yml {err}");
+        return internal_error("Failed to save sources", &err);
+    }
+    if let Err(err) = ConfigFile::load_sources(app_state).await {
+        error!("Failed to reload source.yml {err}");
+        return internal_error("Failed to reload sources", &err);
+    }
+    app_state.event_manager.send_event(EventMessage::ConfigChange(ConfigType::Sources));
+    (status, axum::Json(result)).into_response()
+}
+
+fn with_raw_sources<F>(app_state: &Arc<AppState>, read: F) -> axum::response::Response
+where
+    F: FnOnce(&SourcesConfigDto) -> EditResult<serde_json::Value>,
+{
+    match read_raw_sources(app_state) {
+        Ok(sources) => match read(&sources) {
+            Ok(value) => axum::Json(value).into_response(),
+            Err((status, error)) => error_response(status, error),
+        },
+        Err(err) => internal_error("Failed to read sources", &err),
+    }
+}
+
+fn source_not_found(index: usize) -> (StatusCode, ApiErrorResponse) {
+    (StatusCode::NOT_FOUND, ApiErrorResponse::new(ApiErrorCode::SourceNotFound, "Source not found").with_detail(index.to_string()))
+}
+
+fn input_not_found(name: &str) -> (StatusCode, ApiErrorResponse) {
+    (StatusCode::NOT_FOUND, ApiErrorResponse::new(ApiErrorCode::InputNotFound, "Input not found").with_detail(name))
+}
+
+fn target_not_found(name: &str) -> (StatusCode, ApiErrorResponse) {
+    (StatusCode::NOT_FOUND, ApiErrorResponse::new(ApiErrorCode::TargetNotFound, "Target not found").with_detail(name))
+}
+
+/// The name identifies inputs and targets in the urls, an empty name is taken from the url.
+fn check_name_unchanged(path_name: &str, name: &str) -> EditResult<()> {
+    if name.trim().is_empty() || name.trim() == path_name {
+        Ok(())
+    } else {
+        Err((StatusCode::BAD_REQUEST, ApiErrorResponse::new(ApiErrorCode::InvalidArguments, "The name can't be changed")
+            .with_detail(format!("{path_name} -> {name}"))))
+    }
+}
+
+fn has_target(sources: &SourcesConfigDto, name: &str) -> bool {
+    sources.sources.iter().flat_map(|s| s.targets.iter()).any(|t| t.name.trim() == name.trim())
+}
+
+fn find_target_mut<'a>(sources: &'a mut SourcesConfigDto, name: &str) -> Option<(usize, &'a mut ConfigTargetDto)> {
+    sources.sources.iter_mut().enumerate()
+        .find_map(|(index, source)| source.targets.iter_mut().find(|t| t.name == name).map(|target| (index, target)))
+}
+
+async fn sources_list(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::response::Response {
+    with_raw_sources(&app_state, |sources| Ok(json!(sources.sources)))
+}
+
+async fn source_get(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(index): axum::extract::Path<usize>,
+) -> axum::response::Response {
+    with_raw_sources(&app_state, |sources| sources.sources.get(index).map(|source| json!(source)).ok_or_else(|| source_not_found(index)))
+}
+
+async fn source_create(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(source): axum::extract::Json<ConfigSourceDto>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::CREATED, move |sources| {
+        if let Some(target) = source.targets.iter().find(|t| has_target(sources, &t.name)) {
+            return Err((StatusCode::CONFLICT, ApiErrorResponse::new(ApiErrorCode::Duplicate, "Target already exists").with_detail(target.name.clone())));
+        }
+        sources.sources.push(source);
+        Ok(json!({"index": sources.sources.len() - 1}))
+    }).await
+}
+
+async fn source_update(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(index): axum::extract::Path<usize>,
+    axum::extract::Json(source): axum::extract::Json<ConfigSourceDto>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::OK, move |sources| {
+        let current = sources.sources.get_mut(index).ok_or_else(|| source_not_found(index))?;
+        *current = source;
+        Ok(json!({"index": index}))
+    }).await
+}
+
+async fn source_delete(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(index): axum::extract::Path<usize>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::OK, move |sources| {
+        if index >= sources.sources.len() {
+            return Err(source_not_found(index));
+        }
+        sources.sources.remove(index);
+        Ok(json!({"index": index}))
+    }).await
+}
+
+async fn source_target_create(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(index): axum::extract::Path<usize>,
+    axum::extract::Json(target): axum::extract::Json<ConfigTargetDto>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::CREATED, move |sources| {
+        if has_target(sources, &target.name) {
+            return Err((StatusCode::CONFLICT, ApiErrorResponse::new(ApiErrorCode::Duplicate, "Target already exists").with_detail(target.name.clone())));
+        }
+        let source = sources.sources.get_mut(index).ok_or_else(|| source_not_found(index))?;
+        let name = target.name.trim().to_string();
+        source.targets.push(target);
+        Ok(json!({"source": index, "name": name}))
+    }).await
+}
+
+async fn inputs_list(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::response::Response {
+    with_raw_sources(&app_state, |sources| Ok(json!(sources.inputs)))
+}
+
+async fn input_get(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> axum::response::Response {
+    with_raw_sources(&app_state, |sources| {
+        sources.inputs.iter().find(|input| *input.name == name).map(|input| json!(input)).ok_or_else(|| input_not_found(&name))
+    })
+}
+
+async fn input_create(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(input): axum::extract::Json<ConfigInputDto>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::CREATED, move |sources| {
+        if sources.inputs.iter().any(|i| i.name.trim() == input.name.trim()) {
+            return Err((StatusCode::CONFLICT, ApiErrorResponse::new(ApiErrorCode::Duplicate, "Input already exists").with_detail(input.name.to_string())));
+        }
+        let name = input.name.trim().to_string();
+        sources.inputs.push(input);
+        Ok(json!({"name": name}))
+    }).await
+}
+
+async fn input_update(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::extract::Json(mut input): axum::extract::Json<ConfigInputDto>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::OK, move |sources| {
+        check_name_unchanged(&name, &input.name)?;
+        let current = sources.inputs.iter_mut().find(|i| *i.name == name).ok_or_else(|| input_not_found(&name))?;
+        input.name = Arc::clone(&current.name);
+        *current = input;
+        Ok(json!({"name": name}))
+    }).await
+}
+
+async fn input_delete(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::OK, move |sources| {
+        let position = sources.inputs.iter().position(|i| *i.name == name).ok_or_else(|| input_not_found(&name))?;
+        if let Some(index) = sources.sources.iter().position(|s| s.inputs.iter().any(|i| i.trim() == name)) {
+            return Err((StatusCode::CONFLICT, ApiErrorResponse::new(ApiErrorCode::InUse, "Input is used by a source")
+                .with_detail(format!("{name} -> source {index}"))));
+        }
+        sources.inputs.remove(position);
+        Ok(json!({"name": name}))
+    }).await
+}
+
+async fn targets_list(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::response::Response {
+    with_raw_sources(&app_state, |sources| {
+        Ok(json!(sources.sources.iter().enumerate()
+            .flat_map(|(index, source)| source.targets.iter().map(move |target| json!({"source": index, "target": target})))
+            .collect::<Vec<_>>()))
+    })
+}
+
+async fn target_get(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> axum::response::Response {
+    with_raw_sources(&app_state, |sources| {
+        sources.sources.iter().enumerate()
+            .find_map(|(index, source)| source.targets.iter().find(|t| t.name == name).map(|target| json!({"source": index, "target": target})))
+            .ok_or_else(|| target_not_found(&name))
+    })
+}
+
+async fn target_update(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::extract::Json(mut target): axum::extract::Json<ConfigTargetDto>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::OK, move |sources| {
+        check_name_unchanged(&name, &target.name)?;
+        let (index, current) = find_target_mut(sources, &name).ok_or_else(|| target_not_found(&name))?;
+        target.name.clone_from(&current.name);
+        *current = target;
+        Ok(json!({"source": index, "name": name}))
+    }).await
+}
+
+async fn target_delete(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> axum::response::Response {
+    edit_sources(&app_state, StatusCode::OK, move |sources| {
+        let (index, position) = sources.sources.iter().enumerate()
+            .find_map(|(index, source)| source.targets.iter().position(|t| t.name == name).map(|position| (index, position)))
+            .ok_or_else(|| target_not_found(&name))?;
+        sources.sources[index].targets.remove(position);
+        Ok(json!({"source": index, "name": name}))
+    }).await
+}
+
+/// Create, read, update and delete of the sources, inputs and targets, changes are validated and written to the sources file.
+pub fn sources_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/sources", axum::routing::get(sources_list).layer(axum::middleware::from_fn(require_operator)))
+        .route("/sources", axum::routing::post(source_create).layer(axum::middleware::from_fn(require_admin)))
+        .route("/sources/{index}", axum::routing::get(source_get).layer(axum::middleware::from_fn(require_operator)))
+        .route("/sources/{index}", axum::routing::put(source_update).delete(source_delete).layer(axum::middleware::from_fn(require_admin)))
+        .route("/sources/{index}/targets", axum::routing::post(source_target_create).layer(axum::middleware::from_fn(require_admin)))
+        .route("/inputs", axum::routing::get(inputs_list).layer(axum::middleware::from_fn(require_operator)))
+        .route("/inputs", axum::routing::post(input_create).layer(axum::middleware::from_fn(require_admin)))
+        .route("/inputs/{name}", axum::routing::get(input_get).layer(axum::middleware::from_fn(require_operator)))
+        .route("/inputs/{name}", axum::routing::put(input_update).delete(input_delete).layer(axum::middleware::from_fn(require_admin)))
+        .route("/targets", axum::routing::get(targets_list).layer(axum::middleware::from_fn(require_operator)))
+        .route("/targets/{name}", axum::routing::get(target_get).layer(axum::middleware::from_fn(require_operator)))
+        .route("/targets/{name}", axum::routing::put(target_update).delete(target_delete).layer(axum::middleware::from_fn(require_admin)))
+}
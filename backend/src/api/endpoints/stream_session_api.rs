@@ -0,0 +1,18 @@
+use crate::api::model::AppState;
+use crate::repository::query_stream_sessions;
+use axum::response::IntoResponse;
+use shared::model::StreamSessionQuery;
+use std::sync::Arc;
+
+/// Returns the finished stream sessions active within `from` and `to`, newest first.
+async fn stream_sessions(
+    axum::extract::Query(query): axum::extract::Query<StreamSessionQuery>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    axum::Json(query_stream_sessions(&app_state.app_config, &query).await).into_response()
+}
+
+pub fn stream_session_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/sessions", axum::routing::get(stream_sessions))
+}
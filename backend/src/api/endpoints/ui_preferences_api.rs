@@ -0,0 +1,34 @@
+use crate::api::endpoints::notification_api::get_username;
+use crate::api::model::AppState;
+use crate::auth::WebUiUsername;
+use crate::repository::{load_ui_preferences, update_ui_preferences};
+use axum::response::IntoResponse;
+use log::error;
+use shared::model::UiPreferencesDto;
+use std::sync::Arc;
+
+async fn ui_preferences(
+    user: Option<axum::Extension<WebUiUsername>>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    axum::Json(load_ui_preferences(&app_state.app_config, &get_username(user)).await).into_response()
+}
+
+async fn ui_preferences_update(
+    user: Option<axum::Extension<WebUiUsername>>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::Json(update): axum::Json<UiPreferencesDto>,
+) -> axum::response::Response {
+    match update_ui_preferences(&app_state.app_config, &get_username(user), update).await {
+        Ok(preferences) => axum::Json(preferences).into_response(),
+        Err(err) => {
+            error!("{err}");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub fn ui_preferences_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/preferences", axum::routing::get(ui_preferences).post(ui_preferences_update))
+}
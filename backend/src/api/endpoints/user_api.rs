@@ -1,5 +1,7 @@
 use crate::api::api_utils::try_unwrap_body;
 use crate::api::api_utils::{get_user_target_by_username, get_username_from_auth_header};
+use crate::api::endpoints::playback_error_api::user_playback_error;
+use crate::api::endpoints::v1_api_user::{clear_user_cached_responses, persist_api_proxy};
 use crate::api::model::AppState;
 use crate::auth::validator_user;
 use crate::auth::AuthBearer;
@@ -7,15 +9,21 @@ use crate::model::PlaylistXtreamCategory;
 use crate::model::{AppConfig, ConfigTarget};
 use crate::repository::{iter_raw_m3u_target_playlist, load_user_bouquet_as_json, save_user_bouquet};
 use crate::repository::xtream_get_playlist_categories;
+use crate::utils::media_server::get_user_url;
 use axum::response::IntoResponse;
 use bytes::Bytes;
 use futures::{stream, StreamExt};
 use log::error;
-use shared::model::{PlaylistBouquetDto, TargetType, XtreamCluster};
+use rand::distr::Alphanumeric;
+use rand::Rng;
+use shared::model::{PlaylistBouquetDto, TargetType, UserAccountDto, UserPasswordDto, XtreamCluster};
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::Arc;
 use shared::utils::concat_path_leading_slash;
 
+const GENERATED_PASSWORD_LENGTH: usize = 16;
+
 fn get_categories_from_xtream(categories: Option<Vec<PlaylistXtreamCategory>>) -> Vec<String> {
     let mut groups: Vec<String> = Vec::new();
     if let Some(cats) = categories {
@@ -108,6 +116,7 @@ async fn save_playlist_bouquet(
             let config = &app_state.app_config.config.load();
             match save_user_bouquet(config, &target.name, &username, &bouquet).await {
                 Ok(()) => {
+                    clear_user_cached_responses(&app_state, &target.name, &username).await;
                     return axum::http::StatusCode::OK.into_response();
                 }
                 Err(err) => {
@@ -145,6 +154,70 @@ async fn playlist_bouquet(
         .body(axum::body::Body::from("{}")))
 }
 
+async fn user_account(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        if let Some((user, target)) = get_user_target_by_username(username.as_str(), &app_state) {
+            let outputs: Vec<TargetType> = [TargetType::Xtream, TargetType::M3u, TargetType::Strm, TargetType::HdHomeRun, TargetType::Plex]
+                .into_iter().filter(|output| target.has_output(*output)).collect();
+            let server_url = app_state.app_config.get_target_base_url(&target, &user);
+            let user_url = |path: &str, extra: &[(&str, &str)]| get_user_url(&server_url, path, &user.username, &user.password, extra)
+                .ok().map(String::from);
+            let has_playlist = target.has_output(TargetType::M3u) || target.has_output(TargetType::Xtream);
+            let account = UserAccountDto {
+                username: user.username.clone(),
+                target: target.name.clone(),
+                status: user.status,
+                exp_date: user.exp_date,
+                max_connections: user.max_connections,
+                active_connections: app_state.get_active_connections_for_user(&user.username).await,
+                m3u_url: if target.has_output(TargetType::M3u) { user_url("get.php", &[("type", "m3u_plus")]) } else { None },
+                epg_url: if has_playlist { user_url("xmltv.php", &[]) } else { None },
+                outputs,
+                server_url,
+            };
+            return axum::Json(account).into_response();
+        }
+    }
+    axum::http::StatusCode::BAD_REQUEST.into_response()
+}
+
+/// Replaces the password of the user with a generated one, the playlist urls of the old password stop working.
+async fn regenerate_user_password(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    let Some(username) = get_username_from_auth_header(&token, &app_state) else {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    let Some((user, _target)) = get_user_target_by_username(username.as_str(), &app_state) else {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    if user.permission_denied(&app_state) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
+    let virtual_file = PathBuf::from("api_proxy");
+    let _lock = app_state.app_config.file_locks.write_lock(&virtual_file).await;
+    let Some(mut api_proxy) = app_state.app_config.api_proxy.load().as_ref().map(|api_proxy| (**api_proxy).clone()) else {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    let password: String = rand::rng().sample_iter(&Alphanumeric).take(GENERATED_PASSWORD_LENGTH).map(char::from).collect();
+    let Some(credentials) = api_proxy.user.iter_mut()
+        .flat_map(|target_user| target_user.credentials.iter_mut())
+        .find(|credentials| credentials.username == username) else {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    credentials.password.clone_from(&password);
+    if let Err(err) = persist_api_proxy(&app_state, api_proxy).await {
+        error!("Saving the new password for {username} failed: {err}");
+        return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    axum::Json(UserPasswordDto { password }).into_response()
+}
+
 pub fn user_api_register(app_state: Arc<AppState>, web_ui_path: &str) -> axum::Router<Arc<AppState>> {
     axum::Router::new()
         .nest(
@@ -153,6 +226,9 @@ pub fn user_api_register(app_state: Arc<AppState>, web_ui_path: &str) -> axum::R
                 .route("/playlist/categories", axum::routing::get(playlist_categories))
                 .route("/playlist/bouquet", axum::routing::get(playlist_bouquet))
                 .route("/playlist/bouquet", axum::routing::post(save_playlist_bouquet))
+                .route("/playback/errors", axum::routing::post(user_playback_error))
+                .route("/account", axum::routing::get(user_account))
+                .route("/account/password", axum::routing::post(regenerate_user_password))
                 .route_layer(axum::middleware::from_fn_with_state(app_state, validator_user)),
         )
 
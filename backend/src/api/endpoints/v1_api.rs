@@ -1,25 +1,45 @@
-use crate::api::api_utils::{json_or_bin_response, try_unwrap_body, internal_server_error};
+use crate::api::api_utils::{create_status_check, json_or_bin_response, try_unwrap_body, internal_server_error};
 use crate::api::endpoints::download_api;
 use crate::api::endpoints::user_api::user_api_register;
 use crate::api::endpoints::v1_api_playlist::v1_api_playlist_register;
 use crate::api::endpoints::v1_api_user::v1_api_user_register;
 use crate::api::model::AppState;
-use crate::auth::validator_admin;
+use crate::auth::{require_operator, validator_admin};
 use crate::utils::ip_checker::get_ips;
-use crate::{VERSION};
 use axum::response::IntoResponse;
-use shared::model::{default_geoip_url, InputFetchMethod, IpCheckDto, StatusCheck};
+use shared::model::{default_geoip_url, InputFetchMethod, IpCheckDto};
 use shared::utils::{concat_path_leading_slash, Internable};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{HashMap};
 use std::io::{Cursor};
 use std::sync::Arc;
 use log::{error, info};
 use crate::api::endpoints::extract_accept_header::ExtractAcceptHeader;
 use crate::api::endpoints::v1_api_config::v1_api_config_register;
 use crate::api::endpoints::library_api::library_api_register;
+use crate::api::endpoints::web_push_api::web_push_api_register;
+use crate::api::endpoints::playlist_export_api::playlist_export_api_register;
+use crate::api::endpoints::notification_api::notification_api_register;
+use crate::api::endpoints::ui_preferences_api::ui_preferences_api_register;
+use crate::api::endpoints::privacy_api::privacy_api_register;
+use crate::api::endpoints::playlist_watermark_api::playlist_watermark_api_register;
+use crate::api::endpoints::epg_mapping_api::epg_mapping_api_register;
+use crate::api::endpoints::stream_session_api::stream_session_api_register;
+use crate::api::endpoints::filter_test_api::filter_test_api_register;
+use crate::api::endpoints::mapper_trace_api::mapper_trace_api_register;
+use crate::api::endpoints::job_log_api::job_log_api_register;
+use crate::api::endpoints::processing_stats_api::processing_stats_api_register;
+use crate::api::endpoints::media_server_api::media_server_api_register;
+use crate::api::endpoints::playlist_preview_api::playlist_preview_api_register;
+use crate::api::endpoints::playlist_browse_api::playlist_browse_api_register;
+use crate::api::endpoints::sources_api::sources_api_register;
+use crate::api::endpoints::api_docs_api::api_docs_api_register;
+use crate::api::endpoints::channel_health_api::channel_health_api_register;
+use crate::api::endpoints::cache_api::cache_api_register;
+use crate::api::endpoints::debug_api::debug_api_register;
+use crate::api::endpoints::playback_error_api::playback_error_api_register;
 use crate::model::InputSource;
-use crate::repository::get_geoip_path;
-use crate::utils::GeoIp;
+use crate::repository::{get_geoip_path, REPOSITORY_METRICS};
+use crate::utils::{GeoIp, MetricsWriter};
 use crate::utils::request::download_text_content;
 
 async fn create_ipinfo_check(app_state: &Arc<AppState>) -> Option<(Option<String>, Option<String>)> {
@@ -32,33 +52,6 @@ async fn create_ipinfo_check(app_state: &Arc<AppState>) -> Option<(Option<String
     None
 }
 
-pub async fn create_status_check(app_state: &Arc<AppState>) -> StatusCheck {
-    let cache = match app_state.cache.load().as_ref().as_ref() {
-        None => None,
-        Some(lock) => {
-            Some(lock.lock().await.get_size_text())
-        }
-    };
-    let (active_users, active_user_connections, active_user_streams) = {
-        let active_user = &app_state.active_users;
-        let (user_count, connection_count) = active_user.active_users_and_connections().await;
-        (user_count, connection_count, active_user.active_streams().await)
-    };
-
-    let active_provider_connections = app_state.active_provider.active_connections().await.map(|c| c.into_iter().collect::<BTreeMap<_, _>>());
-
-    StatusCheck {
-        status: "ok".to_string(),
-        version: VERSION.to_string(),
-        build_time: crate::api::api_utils::get_build_time(),
-        server_time: crate::api::api_utils::get_server_time(),
-        active_users,
-        active_user_connections,
-        active_provider_connections,
-        active_user_streams,
-        cache,
-    }
-}
 async fn status(axum::extract::State(app_state): axum::extract::State<Arc<AppState>>) -> axum::response::Response {
     let status = create_status_check(&app_state).await;
     match serde_json::to_string_pretty(&status) {
@@ -89,6 +82,9 @@ async fn geoip_update(axum::extract::State(app_state): axum::extract::State<Arc<
                 password: None,
                 method: InputFetchMethod::GET,
                 headers: HashMap::default(),
+                header_rules: None,
+                basic_auth: None,
+                impersonate: None,
             };
             return match download_text_content(
                 &app_state.app_config,
@@ -150,19 +146,48 @@ async fn ipinfo(axum::extract::State(app_state): axum::extract::State<Arc<AppSta
     axum::http::StatusCode::BAD_REQUEST.into_response()
 }
 
+async fn metrics() -> axum::response::Response {
+    let mut writer = MetricsWriter::new();
+    REPOSITORY_METRICS.write(&mut writer);
+    try_unwrap_body!(axum::response::Response::builder().status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4").body(writer.finish()))
+}
+
 pub fn v1_api_register(web_auth_enabled: bool, app_state: Arc<AppState>, web_ui_path: &str) -> axum::Router<Arc<AppState>> {
     let mut router = axum::Router::new();
     router = router
         .route("/status", axum::routing::get(status))
         .route("/streams", axum::routing::get(streams))
-        .route("/geoip/update", axum::routing::get(geoip_update))
-        .route("/file/download", axum::routing::post(download_api::queue_download_file))
+        .route("/geoip/update", axum::routing::get(geoip_update).layer(axum::middleware::from_fn(require_operator)))
+        .route("/file/download", axum::routing::post(download_api::queue_download_file).layer(axum::middleware::from_fn(require_operator)))
         .route("/file/download/info", axum::routing::get(download_api::download_file_info))
-        .route("/ipinfo", axum::routing::get(ipinfo));
+        .route("/ipinfo", axum::routing::get(ipinfo))
+        .route("/metrics", axum::routing::get(metrics));
     router = v1_api_config_register(router);
     router = v1_api_user_register(router, web_ui_path);
     router = v1_api_playlist_register(router);
     router = library_api_register(router);
+    router = web_push_api_register(router);
+    router = playlist_export_api_register(router);
+    router = notification_api_register(router);
+    router = ui_preferences_api_register(router);
+    router = privacy_api_register(router);
+    router = epg_mapping_api_register(router);
+    router = playlist_watermark_api_register(router);
+    router = stream_session_api_register(router);
+    router = filter_test_api_register(router);
+    router = mapper_trace_api_register(router);
+    router = job_log_api_register(router);
+    router = processing_stats_api_register(router);
+    router = media_server_api_register(router);
+    router = playlist_preview_api_register(router);
+    router = playlist_browse_api_register(router);
+    router = sources_api_register(router);
+    router = api_docs_api_register(router);
+    router = channel_health_api_register(router);
+    router = playback_error_api_register(router);
+    router = cache_api_register(router);
+    router = debug_api_register(router);
     if web_auth_enabled {
         router = router.route_layer(axum::middleware::from_fn_with_state(Arc::clone(&app_state), validator_admin));
     }
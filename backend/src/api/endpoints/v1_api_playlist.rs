@@ -1,21 +1,22 @@
-use crate::api::api_utils::{create_api_proxy_user, json_or_bin_response};
+use crate::api::api_utils::{api_error_response, api_error_response_with_detail, create_api_proxy_user, json_or_bin_response};
 use crate::api::endpoints::api_playlist_utils::{get_playlist_for_custom_provider, get_playlist_for_input, get_playlist_for_target};
 use crate::api::endpoints::extract_accept_header::ExtractAcceptHeader;
 use crate::api::model::AppState;
-use crate::auth::create_access_token;
+use crate::auth::{create_access_token, require_operator};
 use crate::model::{parse_xmltv_for_web_ui_from_url, ConfigInput, ConfigInputOptions};
 use crate::processing::processor::playlist;
 use axum::response::IntoResponse;
 use axum::{Router};
 use log::{debug, error};
 use serde_json::json;
-use shared::model::{InputType, PlaylistEpgRequest, PlaylistRequest, ProxyType, TargetType, UiPlaylistItem, WebplayerUrlRequest, XtreamCluster};
+use shared::model::{ApiErrorCode, InputType, PlaylistEpgRequest, PlaylistRequest, ProxyType, TargetType, UiPlaylistItem, WebplayerUrlRequest, XtreamCluster};
 use shared::utils::{sanitize_sensitive_info, Internable};
 use std::sync::Arc;
 use url::Url;
 use crate::api::endpoints::xmltv_api::{serve_epg_web_ui};
 use crate::api::endpoints::xtream_api::xtream_get_stream_info_response;
-use crate::repository::xtream_get_item_for_stream_id;
+use crate::repository::{compare_target_playlists, load_playlist_changes, load_playlist_search_index, xtream_get_item_for_stream_id};
+use serde::Deserialize;
 
 fn create_config_input_for_m3u(url: &str) -> ConfigInput {
     ConfigInput {
@@ -30,6 +31,7 @@ fn create_config_input_for_m3u(url: &str) -> ConfigInput {
             xtream_skip_series: false,
             xtream_live_stream_without_extension: false,
             xtream_live_stream_use_prefix: true,
+            xtream_incremental: false,
         }),
         ..Default::default()
     }
@@ -50,6 +52,7 @@ fn create_config_input_for_xtream(username: &str, password: &str, host: &str) ->
             xtream_skip_series: false,
             xtream_live_stream_without_extension: false,
             xtream_live_stream_use_prefix: true,
+            xtream_incremental: false,
         }),
         ..Default::default()
     }
@@ -80,7 +83,7 @@ async fn playlist_update(
         }
         Err(err) => {
             error!("Failed playlist update {}", sanitize_sensitive_info(err.to_string().as_str()));
-            (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": err.to_string()}))).into_response()
+            api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid targets", err.to_string())
         }
     }
 }
@@ -107,7 +110,7 @@ async fn playlist_content(
                     get_playlist_for_custom_provider(client.as_ref(), Some(&input), &app_state.app_config, cluster, accept.as_deref()).await.into_response()
                 }
                 _ => {
-                    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Invalid url scheme; only http/https are allowed"}))).into_response()
+                    api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid url scheme; only http/https are allowed")
                 }
             }
         }
@@ -118,7 +121,7 @@ async fn playlist_content(
                     get_playlist_for_custom_provider(client.as_ref(), Some(&input), &app_state.app_config, cluster, accept.as_deref()).await.into_response()
                 }
                 _ => {
-                    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Invalid url scheme; only http/https are allowed"}))).into_response()
+                    api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidArguments, "Invalid url scheme; only http/https are allowed")
                 }
             }
         }
@@ -230,10 +233,85 @@ async fn playlist_epg(
     axum::http::StatusCode::NO_CONTENT.into_response()
 }
 
+fn target_exists(app_state: &AppState, target_name: &str) -> bool {
+    app_state.app_config.sources.load().sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .any(|target| target.name == target_name)
+}
+
+fn target_not_found(target_name: &str) -> axum::response::Response {
+    api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::TargetNotFound, "Target not found", target_name)
+}
+
+/// Returns the changes of the last processing run of the target compared to the run before.
+async fn playlist_changes(
+    axum::extract::Path(target_name): axum::extract::Path<String>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl IntoResponse + Send {
+    if !target_exists(&app_state, &target_name) {
+        return target_not_found(&target_name);
+    }
+    match load_playlist_changes(&app_state.app_config, &target_name).await {
+        Some(changes) => axum::Json(changes).into_response(),
+        None => axum::http::StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+/// Returns the channels and groups which only exist in one of the two target playlists.
+async fn playlist_compare(
+    axum::extract::Path((target_a, target_b)): axum::extract::Path<(String, String)>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl IntoResponse + Send {
+    if let Some(target_name) = [&target_a, &target_b].into_iter().find(|name| !target_exists(&app_state, name)) {
+        return target_not_found(target_name);
+    }
+    match compare_target_playlists(&app_state.app_config, &target_a, &target_b).await {
+        Some(compare) => axum::Json(compare).into_response(),
+        None => axum::http::StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+const SEARCH_DEFAULT_LIMIT: usize = 50;
+const SEARCH_MAX_LIMIT: usize = 500;
+
+#[derive(Debug, Deserialize)]
+struct PlaylistSearchQuery {
+    target: String,
+    #[serde(default)]
+    q: String,
+    cluster: Option<XtreamCluster>,
+    limit: Option<usize>,
+}
+
+/// Searches the index which is created when the playlist of the target is persisted.
+async fn playlist_search(
+    axum::extract::Query(query): axum::extract::Query<PlaylistSearchQuery>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl IntoResponse + Send {
+    if !target_exists(&app_state, &query.target) {
+        return target_not_found(&query.target);
+    }
+    let query_cache = &app_state.playlists.query_cache;
+    let index = if let Some(index) = query_cache.get_search_index(&query.target) {
+        index
+    } else {
+        let generation = query_cache.generation();
+        match load_playlist_search_index(&app_state.app_config, &query.target).await {
+            Some(index) => query_cache.put_search_index(&query.target, generation, index),
+            None => return axum::http::StatusCode::NO_CONTENT.into_response(),
+        }
+    };
+    let limit = query.limit.unwrap_or(SEARCH_DEFAULT_LIMIT).clamp(1, SEARCH_MAX_LIMIT);
+    axum::Json(index.search(&query.q, query.cluster, limit)).into_response()
+}
+
 pub fn v1_api_playlist_register(router: Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
     router
         .route("/playlist/webplayer", axum::routing::post(playlist_webplayer))
-        .route("/playlist/update", axum::routing::post(playlist_update))
+        .route("/playlist/update", axum::routing::post(playlist_update).layer(axum::middleware::from_fn(require_operator)))
+        .route("/playlist/changes/{target}", axum::routing::get(playlist_changes))
+        .route("/playlist/compare/{target_a}/{target_b}", axum::routing::get(playlist_compare))
+        .route("/playlist/search", axum::routing::get(playlist_search))
         .route("/playlist/epg", axum::routing::post(playlist_epg))
         .route("/playlist/live", axum::routing::post(playlist_content_live))
         .route("/playlist/vod", axum::routing::post(playlist_content_vod))
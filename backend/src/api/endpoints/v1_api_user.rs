@@ -1,15 +1,84 @@
+use crate::api::api_utils::api_error_response_with_detail;
 use crate::api::model::AppState;
+use crate::auth::require_operator;
 use crate::api::panel_api::{sync_panel_api_alias_pool_for_target, target_has_alias_pool_min};
+use crate::api::user_import::{import_users, parse_user_import};
 use crate::model::{ApiProxyConfig, ProxyUserCredentials, TargetUser};
-use crate::repository::store_api_user;
+use crate::repository::{store_api_user, xtream_clear_cached_responses, xtream_clear_user_cached_responses};
 use axum::response::IntoResponse;
 use axum::Router;
-use serde_json::json;
-use shared::model::{ApiProxyConfigDto, ProxyUserCredentialsDto};
+use shared::model::{ApiErrorCode, ApiProxyConfigDto, ProxyUserCredentialsDto, UserImportRequest};
 use shared::utils::{concat_path_leading_slash, mask_credentials};
 use std::path::PathBuf;
 use std::sync::Arc;
 
+/// Drops the cached xtream responses of the user from memory and disk.
+pub(in crate::api) async fn clear_user_cached_responses(app_state: &AppState, target_name: &str, username: &str) {
+    app_state.playlists.query_cache.invalidate_user(username);
+    let config = app_state.app_config.config.load();
+    xtream_clear_user_cached_responses(&config, target_name, username).await;
+}
+
+/// The cached xtream responses contain the stream urls, which are built from these fields.
+fn has_same_stream_urls(user: &ProxyUserCredentials, other: &ProxyUserCredentials) -> bool {
+    user.username == other.username
+        && user.password == other.password
+        && user.token == other.token
+        && user.proxy == other.proxy
+        && user.server == other.server
+        && user.epg_timeshift == other.epg_timeshift
+}
+
+/// Drops the cached xtream responses which are outdated by the new api proxy config.
+pub(in crate::api) async fn clear_changed_cached_responses(app_state: &AppState, old_api_proxy: Option<&ApiProxyConfig>, new_api_proxy: &ApiProxyConfig) {
+    let Some(old_api_proxy) = old_api_proxy else { return; };
+    if old_api_proxy.server != new_api_proxy.server {
+        app_state.playlists.query_cache.invalidate_responses();
+        let config = app_state.app_config.config.load();
+        let sources = app_state.app_config.sources.load();
+        for target in sources.sources.iter().flat_map(|source| source.targets.iter()) {
+            xtream_clear_cached_responses(&config, &target.name).await;
+        }
+        return;
+    }
+    for target_user in &old_api_proxy.user {
+        for user in &target_user.credentials {
+            let unchanged = new_api_proxy.user.iter()
+                .filter(|new_target_user| new_target_user.target == target_user.target)
+                .flat_map(|new_target_user| new_target_user.credentials.iter())
+                .any(|new_user| has_same_stream_urls(user, new_user));
+            if !unchanged {
+                clear_user_cached_responses(app_state, &target_user.target, &user.username).await;
+            }
+        }
+    }
+}
+
+/// Saves the users to the user db or the api proxy config and activates them.
+pub(in crate::api) async fn persist_api_proxy(app_state: &AppState, api_proxy: ApiProxyConfig) -> Result<(), String> {
+    let new_api_proxy = Arc::new(api_proxy);
+    if new_api_proxy.use_user_db {
+        store_api_user(&app_state.app_config, &new_api_proxy.user).await.map_err(|err| err.to_string())?;
+    } else {
+        let config = app_state.app_config.config.load();
+        let backup_dir = config.get_backup_dir();
+        let paths = app_state.app_config.paths.load();
+        if let Some(err) = crate::api::endpoints::v1_api_config::intern_save_config_api_proxy(
+            backup_dir.as_ref(),
+            &ApiProxyConfigDto::from(&*new_api_proxy),
+            paths.api_proxy_file_path.as_str(),
+        )
+        .await
+        {
+            return Err(err.to_string());
+        }
+    }
+    // Update state after successful save
+    let old_api_proxy = app_state.app_config.api_proxy.swap(Some(Arc::clone(&new_api_proxy)));
+    clear_changed_cached_responses(app_state, old_api_proxy.as_deref(), &new_api_proxy).await;
+    Ok(())
+}
+
 #[allow(clippy::too_many_lines)]
 async fn save_config_api_proxy_user(
     method: axum::http::Method,
@@ -26,11 +95,7 @@ async fn save_config_api_proxy_user(
 
     credential.prepare();
     if let Err(err) = credential.validate() {
-        return (
-            axum::http::StatusCode::BAD_REQUEST,
-            axum::Json(json!({"error": err.to_string()})),
-        )
-            .into_response();
+        return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidContent, "Invalid user", err.to_string());
     }
 
     let is_update = method == axum::http::Method::PUT;
@@ -53,22 +118,13 @@ async fn save_config_api_proxy_user(
         for (u_idx, user) in target_user.credentials.iter().enumerate() {
             if let (Some(u), Some(c)) = (&user.token, &credential.token) {
                 if u == c && user.username != credential.username {
-                    return (
-                        axum::http::StatusCode::BAD_REQUEST,
-                        axum::Json(
-                            json!({"error": format!("Duplicate token {}", mask_credentials(c))}),
-                        ),
-                    )
-                        .into_response();
+                    return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::Duplicate, "Duplicate token", mask_credentials(c));
                 }
             }
             if user.username == credential.username {
                 // if not an update und username exists -> Error (duplicate username)
                 if !is_update {
-                    return (
-                        axum::http::StatusCode::BAD_REQUEST,
-                        axum::Json(json!({"error": format!("Duplicate username {}", &credential.username)}))
-                    ).into_response();
+                    return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::Duplicate, "Duplicate username", credential.username.as_str());
                 }
 
                 // mark position of user (for update / move)
@@ -80,11 +136,7 @@ async fn save_config_api_proxy_user(
 
     // ---------- if update but no user found -> Error ----------
     if is_update && existing_user_index.is_none() {
-        return (
-            axum::http::StatusCode::BAD_REQUEST,
-            axum::Json(json!({"error": format!("User {} not found", credential.username)})),
-        )
-            .into_response();
+        return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UserNotFound, "User not found", credential.username.as_str());
     }
 
     // ---------- create target if new target does not exist ----------
@@ -127,41 +179,10 @@ async fn save_config_api_proxy_user(
             .push(ProxyUserCredentials::from(&credential));
     }
 
-    let new_api_proxy = Arc::new(api_proxy);
-
-    if new_api_proxy.use_user_db {
-        if let Err(err) = store_api_user(&app_state.app_config, &new_api_proxy.user).await {
-            return (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                axum::Json(json!({"error": err.to_string()})),
-            )
-                .into_response();
-        }
-    } else {
-        let config = app_state.app_config.config.load();
-        let backup_dir = config.get_backup_dir();
-        let paths = app_state.app_config.paths.load();
-        if let Some(err) = crate::api::endpoints::v1_api_config::intern_save_config_api_proxy(
-            backup_dir.as_ref(),
-            &ApiProxyConfigDto::from(&*new_api_proxy),
-            paths.api_proxy_file_path.as_str(),
-        )
-        .await
-        {
-            return (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                axum::Json(json!({"error": err.to_string()})),
-            )
-                .into_response();
-        }
+    if let Err(err) = persist_api_proxy(&app_state, api_proxy).await {
+        return api_error_response_with_detail(axum::http::StatusCode::INTERNAL_SERVER_ERROR, ApiErrorCode::InternalError, "Failed to save users", err);
     }
 
-    // Update state after successful save
-    app_state
-        .app_config
-        .api_proxy
-        .store(Some(Arc::clone(&new_api_proxy)));
-
     if target_has_alias_pool_min(&app_state, &target_name) {
         let app_state_clone = Arc::clone(&app_state);
         let target_name_clone = target_name.clone();
@@ -192,63 +213,74 @@ async fn delete_config_api_proxy_user(
             }
         }
         if modified {
-            let new_api_proxy = Arc::new(api_proxy);
-            if new_api_proxy.use_user_db {
-                if let Err(err) = store_api_user(&app_state.app_config, &new_api_proxy.user).await {
-                    return (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                        axum::Json(json!({"error": err.to_string()})),
-                    )
-                        .into_response();
-                }
-            } else {
-                let config = app_state.app_config.config.load();
-                let backup_dir = config.get_backup_dir();
-                let paths = app_state.app_config.paths.load();
-                if let Some(err) =
-                    crate::api::endpoints::v1_api_config::intern_save_config_api_proxy(
-                        backup_dir.as_ref(),
-                        &ApiProxyConfigDto::from(&*new_api_proxy),
-                        paths.api_proxy_file_path.as_str(),
-                    )
-                    .await
-                {
-                    return (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                        axum::Json(json!({"error": err.to_string()})),
-                    )
-                        .into_response();
-                }
+            if let Err(err) = persist_api_proxy(&app_state, api_proxy).await {
+                return api_error_response_with_detail(axum::http::StatusCode::INTERNAL_SERVER_ERROR, ApiErrorCode::InternalError, "Failed to save users", err);
             }
-            app_state
-                .app_config
-                .api_proxy
-                .store(Some(Arc::clone(&new_api_proxy)));
         } else {
-            return (
-                axum::http::StatusCode::BAD_REQUEST,
-                axum::Json(
-                    json!({"error": format!("User not found {username} in target {target_name}")}),
-                ),
-            )
-                .into_response();
+            return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::UserNotFound, "User not found",
+                                                  format!("{username} in target {target_name}"));
         }
     }
     axum::http::StatusCode::OK.into_response()
 }
 
+/// Creates or updates the users of a csv or an xtream panel export in bulk.
+/// The dry run returns the preview of the import without saving the users.
+async fn import_config_api_proxy_users(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(request): axum::extract::Json<UserImportRequest>,
+) -> axum::response::Response {
+    let target_exists = app_state.app_config.sources.load().sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .any(|target| target.name == request.target);
+    if !target_exists {
+        return api_error_response_with_detail(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::TargetNotFound, "Target not found", request.target);
+    }
+    let import = match parse_user_import(request.format, &request.content) {
+        Ok(import) => import,
+        Err(err) => return api_error_response_with_detail(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidContent, "Invalid user import", err),
+    };
+
+    let virtual_file = PathBuf::from("api_proxy");
+    let _lock = app_state.app_config.file_locks.write_lock(&virtual_file).await;
+
+    let mut api_proxy = app_state.app_config.api_proxy.load().as_ref()
+        .map_or_else(ApiProxyConfig::default, |api_proxy| (**api_proxy).clone());
+    let mut result = import_users(&mut api_proxy, &request.target, request.update_existing, import);
+    result.dry_run = request.dry_run;
+    if request.dry_run || result.created + result.updated == 0 {
+        return axum::Json(result).into_response();
+    }
+
+    if let Err(err) = persist_api_proxy(&app_state, api_proxy).await {
+        return api_error_response_with_detail(axum::http::StatusCode::INTERNAL_SERVER_ERROR, ApiErrorCode::InternalError, "Failed to save users", err);
+    }
+    if target_has_alias_pool_min(&app_state, &request.target) {
+        let app_state_clone = Arc::clone(&app_state);
+        let target_name = request.target.clone();
+        tokio::spawn(async move {
+            sync_panel_api_alias_pool_for_target(&app_state_clone, &target_name).await;
+        });
+    }
+    axum::Json(result).into_response()
+}
+
 pub fn v1_api_user_register(router: Router<Arc<AppState>>, web_ui_path: &str) -> axum::Router<Arc<AppState>> {
     router
         .route(
             &concat_path_leading_slash(web_ui_path, "/user/{target}"),
-            axum::routing::post(save_config_api_proxy_user),
+            axum::routing::post(save_config_api_proxy_user).layer(axum::middleware::from_fn(require_operator)),
         )
         .route(
             &concat_path_leading_slash(web_ui_path, "/user/{target}"),
-            axum::routing::put(save_config_api_proxy_user),
+            axum::routing::put(save_config_api_proxy_user).layer(axum::middleware::from_fn(require_operator)),
         )
         .route(
             &concat_path_leading_slash(web_ui_path, "/user/{target}/{username}"),
-            axum::routing::delete(delete_config_api_proxy_user),
+            axum::routing::delete(delete_config_api_proxy_user).layer(axum::middleware::from_fn(require_operator)),
+        )
+        .route(
+            "/users/import",
+            axum::routing::post(import_config_api_proxy_users).layer(axum::middleware::from_fn(require_operator)),
         )
 }
@@ -1,11 +1,11 @@
 use crate::api::api_utils::serve_file;
 use crate::api::api_utils::try_unwrap_body;
 use crate::api::model::AppState;
-use crate::auth::{create_jwt_admin, create_jwt_user, is_admin, verify_password, verify_token, AuthBearer};
+use crate::auth::{create_jwt_user, create_jwt_web_ui, get_web_ui_role, verify_password, verify_token, AuthBearer, Fingerprint};
 use axum::response::IntoResponse;
 use log::{error};
 use serde_json::json;
-use shared::model::{TokenResponse, UserCredential, TOKEN_NO_AUTH};
+use shared::model::{NotificationKind, TokenResponse, UserCredential, TOKEN_NO_AUTH};
 use shared::utils::{concat_path_leading_slash, CONSTANTS};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -27,6 +27,7 @@ fn no_web_auth_token() -> impl axum::response::IntoResponse + Send {
 }
 
 async fn token(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     axum::extract::Json(mut req): axum::extract::Json<UserCredential>,
 ) -> impl axum::response::IntoResponse + Send {
@@ -43,7 +44,7 @@ async fn token(
             if !(username.is_empty() || password.is_empty()) {
                 if let Some(hash) = web_auth.get_user_password(username) {
                     if verify_password(hash, password.as_bytes()) {
-                        if let Ok(token) = create_jwt_admin(web_auth, username) {
+                        if let Ok(token) = create_jwt_web_ui(web_auth, username) {
                             req.zeroize();
                             return axum::Json(
                                 TokenResponse {
@@ -65,6 +66,9 @@ async fn token(
                         }
                     }
                 }
+                let username: String = username.chars().filter(|c| !c.is_control()).take(64).collect();
+                app_state.event_manager.send_notification(NotificationKind::Security,
+                                                          format!("Failed login for user {username} from {}", fingerprint.client_ip));
             }
 
             req.zeroize();
@@ -88,8 +92,8 @@ async fn token_refresh(
             let maybe_token_data = verify_token(&token, secret_key);
             if let Some(token_data) = maybe_token_data {
                 let username = token_data.claims.username.clone();
-                let new_token = if is_admin(Some(token_data)) {
-                    create_jwt_admin(web_auth, &username)
+                let new_token = if get_web_ui_role(Some(&token_data)).is_some() {
+                    create_jwt_web_ui(web_auth, &username)
                 } else {
                     create_jwt_user(web_auth, &username)
                 };
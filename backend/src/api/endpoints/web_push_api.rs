@@ -0,0 +1,88 @@
+use crate::api::api_utils::api_error_response;
+use crate::api::model::AppState;
+use crate::auth::require_operator;
+use crate::messaging::send_web_push_notification;
+use crate::model::{MessageContent, WebPushMessagingConfig};
+use crate::repository::{add_web_push_subscription, load_web_push_key, remove_web_push_subscriptions};
+use axum::response::IntoResponse;
+use log::error;
+use serde_json::json;
+use shared::model::{ApiErrorCode, WebPushPublicKeyDto, WebPushSubscriptionDto, WebPushUnsubscribeDto};
+use std::sync::Arc;
+
+fn get_web_push_config(app_state: &AppState) -> Option<WebPushMessagingConfig> {
+    app_state.app_config.config.load().messaging.as_ref().and_then(|m| m.web_push.clone())
+}
+
+fn web_push_not_configured() -> axum::response::Response {
+    api_error_response(axum::http::StatusCode::NOT_FOUND, ApiErrorCode::NotConfigured, "Web push is not configured")
+}
+
+/// Returns the public VAPID key the browser needs to subscribe
+async fn get_public_key(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    if get_web_push_config(&app_state).is_none() {
+        return web_push_not_configured();
+    }
+    match load_web_push_key(&app_state.app_config).await {
+        Ok(key) => axum::Json(WebPushPublicKeyDto { public_key: key.public_key_base64() }).into_response(),
+        Err(err) => {
+            error!("{err}");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn subscribe(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::Json(subscription): axum::Json<WebPushSubscriptionDto>,
+) -> axum::response::Response {
+    if get_web_push_config(&app_state).is_none() {
+        return web_push_not_configured();
+    }
+    if !subscription.endpoint.starts_with("https://") || subscription.keys.p256dh.is_empty() || subscription.keys.auth.is_empty() {
+        return api_error_response(axum::http::StatusCode::BAD_REQUEST, ApiErrorCode::InvalidContent, "Invalid web push subscription");
+    }
+    match add_web_push_subscription(&app_state.app_config, subscription).await {
+        Ok(()) => axum::http::StatusCode::OK.into_response(),
+        Err(err) => {
+            error!("{err}");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn unsubscribe(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::Json(request): axum::Json<WebPushUnsubscribeDto>,
+) -> axum::response::Response {
+    match remove_web_push_subscriptions(&app_state.app_config, &[request.endpoint]).await {
+        Ok(()) => axum::http::StatusCode::OK.into_response(),
+        Err(err) => {
+            error!("{err}");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Sends a test notification to all subscriptions, regardless of `notify_on`
+async fn send_test_notification(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> axum::response::Response {
+    let Some(web_push) = get_web_push_config(&app_state) else {
+        return web_push_not_configured();
+    };
+    let client = app_state.http_client.load_full();
+    let content = MessageContent::Info("Web push notifications are working".to_string());
+    let delivered = send_web_push_notification(&app_state.app_config, &client, &content, &web_push).await;
+    axum::Json(json!({"delivered": delivered})).into_response()
+}
+
+/// Registers Web Push API routes
+pub fn web_push_api_register(router: axum::Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
+    router
+        .route("/webpush/key", axum::routing::get(get_public_key))
+        .route("/webpush/subscription", axum::routing::post(subscribe).delete(unsubscribe))
+        .route("/webpush/test", axum::routing::post(send_test_notification).layer(axum::middleware::from_fn(require_operator)))
+}
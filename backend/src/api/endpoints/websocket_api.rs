@@ -1,11 +1,11 @@
-use crate::api::endpoints::v1_api::create_status_check;
+use crate::api::api_utils::create_status_check;
 use crate::api::model::AppState;
 use crate::api::model::EventMessage;
-use crate::auth::{verify_token_admin, verify_token_user};
+use crate::auth::{verify_token_user, verify_token_web_ui};
 use axum::extract::ws::CloseFrame;
 use axum::{extract::ws::{Message, WebSocket, WebSocketUpgrade},response::IntoResponse};
 use log::{error, trace};
-use shared::model::{ProtocolHandler, ProtocolHandlerMemory, ProtocolMessage, UserCommand, UserRole, WsCloseCode, PROTOCOL_VERSION};
+use shared::model::{ProtocolHandler, ProtocolHandlerMemory, ProtocolMessage, UserCommand, UserRole, WebUiRole, WsCloseCode, PROTOCOL_VERSION};
 use std::sync::Arc;
 use shared::utils::{concat_path_leading_slash, default_kick_secs};
 
@@ -43,10 +43,12 @@ pub fn ws_api_register(web_auth_enabled: bool, web_ui_path: &str) -> axum::Route
 
 #[inline]
 fn verify_auth_admin_token(auth_token: &str, secret_key: Option<&Vec<u8>>) -> bool {
-    match secret_key.as_ref() {
-        None => false,
-        Some(key) => verify_token_admin(auth_token, key.as_slice()),
-    }
+    get_auth_web_ui_role(auth_token, secret_key).is_some()
+}
+
+#[inline]
+pub(super) fn get_auth_web_ui_role(auth_token: &str, secret_key: Option<&Vec<u8>>) -> Option<WebUiRole> {
+    secret_key.and_then(|key| verify_token_web_ui(auth_token, key.as_slice()))
 }
 
 #[inline]
@@ -57,7 +59,7 @@ fn verify_auth_user_token(auth_token: &str, secret_key: Option<&Vec<u8>>) -> boo
     }
 }
 
-fn get_secret_key(app_state: &AppState, auth: bool) -> Option<Vec<u8>> {
+pub(super) fn get_secret_key(app_state: &AppState, auth: bool) -> Option<Vec<u8>> {
     if !auth {
         return None;
     }
@@ -135,7 +137,7 @@ async fn handle_protocol_message(
             },
             Ok(ProtocolMessage::UserAction(cmd)) => {
                 if let Some(token) = mem.token.as_ref() {
-                    if !auth_required || verify_auth_admin_token(token, secret_key) {
+                    if !auth_required || get_auth_web_ui_role(token, secret_key).is_some_and(|role| role.is_allowed(WebUiRole::Operator)) {
                         Some(ProtocolMessage::UserActionResponse(handle_user_action(app_state, cmd).await))
                     } else {
                         Some(ProtocolMessage::UserActionResponse(false))
@@ -207,6 +209,7 @@ async fn handle_incoming_message(
     }
 }
 
+#[allow(clippy::too_many_lines)]
 async fn handle_event_message(socket: &mut WebSocket, event: EventMessage, handler: &ProtocolHandler) -> Result<(), String> {
     match handler {
         ProtocolHandler::Version(_) => {},
@@ -285,6 +288,25 @@ async fn handle_event_message(socket: &mut WebSocket, event: EventMessage, handl
                             .await
                             .map_err(|e| format!("Library scan progress event: {e} "))?;
                     }
+                    EventMessage::Notification(notification) => {
+                        let msg = ProtocolMessage::NotificationResponse(notification)
+                            .to_bytes()
+                            .map_err(|e| e.to_string())?;
+                        socket
+                            .send(Message::Binary(msg))
+                            .await
+                            .map_err(|e| format!("Notification event: {e} "))?;
+                    }
+                    EventMessage::MappingReprocess(targets) => {
+                        let msg = ProtocolMessage::MappingReprocessResponse(targets)
+                            .to_bytes()
+                            .map_err(|e| e.to_string())?;
+                        socket
+                            .send(Message::Binary(msg))
+                            .await
+                            .map_err(|e| format!("Mapping reprocess event: {e} "))?;
+                    }
+                    EventMessage::Notify(..) => {}
                 }
             }
         }
@@ -1,4 +1,4 @@
-use crate::api::api_utils::{empty_json_response_as_array, get_user_target, get_user_target_by_credentials, internal_server_error, resource_response, stream_json_or_bin_response, try_unwrap_body};
+use crate::api::api_utils::{empty_json_response_as_array, get_user_target, get_user_target_by_credentials, internal_server_error, logo_response, stream_json_or_bin_response, try_unwrap_body};
 use crate::api::model::AppState;
 use crate::api::model::UserApiRequest;
 use crate::model::{Config, EPG_ATTRIB_ID, EPG_TAG_CHANNEL};
@@ -63,7 +63,7 @@ pub(in crate::api) fn get_epg_path_for_target(config: &Config, target: &ConfigTa
                     );
                 }
             }
-            TargetOutput::Strm(_) | TargetOutput::HdHomeRun(_) => {}
+            TargetOutput::Strm(_) | TargetOutput::HdHomeRun(_) | TargetOutput::Plex(_) => {}
         }
     }
     None
@@ -136,8 +136,8 @@ async fn serve_epg_with_rewrites(
     let epg_processing_options = get_epg_processing_options(app_state, user, target);
 
     let base_url = if epg_processing_options.offset_minutes != 0 || epg_processing_options.rewrite_urls {
-        let server_info = app_state.app_config.get_user_server_info(user);
-        Some(concat_string!(&server_info.get_base_url(), "/", storage_const::EPG_RESOURCE_PATH, "/", &user.username, "/", &user.password))
+        let base_url = app_state.app_config.get_target_base_url(target, user);
+        Some(concat_string!(&base_url, "/", storage_const::EPG_RESOURCE_PATH, "/", &user.username, "/", &user.password))
     } else {
         None
     };
@@ -386,7 +386,7 @@ async fn epg_api_resource(
 
     let encrypt_secret = app_state.app_config.get_reverse_proxy_rewrite_secret().unwrap_or_else(|| app_state.app_config.encrypt_secret);
     if let Ok(resource_url) = deobscure_text(&encrypt_secret, &resource) {
-        resource_response(&app_state, &resource_url, &req_headers, None).await.into_response()
+        logo_response(&app_state, &resource_url, &req_headers, None).await
     } else {
         axum::http::StatusCode::BAD_REQUEST.into_response()
     }
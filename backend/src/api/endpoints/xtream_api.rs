@@ -1,12 +1,13 @@
 // https://github.com/tellytv/go.xtream-codes/blob/master/structs.go
 // Xtream api -> https://9tzx6f0ozj.apidog.io/
 use crate::api::api_utils;
-use crate::api::api_utils::{create_api_proxy_user, create_session_fingerprint, empty_json_response_as_array, empty_json_response_as_object, force_provider_stream_response, get_user_target, get_user_target_by_credentials, internal_server_error, is_seek_request, local_stream_response, redirect, redirect_response, resource_response, separate_number_and_remainder, stream_response, try_option_bad_request, try_result_bad_request, try_result_not_found, try_unwrap_body, RedirectParams};
-use crate::api::endpoints::hls_api::handle_hls_stream_request;
+use crate::api::api_utils::{account_sharing_denied, create_api_proxy_user, create_session_fingerprint, empty_json_response_as_array, empty_json_response_as_object, force_provider_stream_response, get_user_target, get_user_target_by_credentials, internal_server_error, is_seek_request, local_stream_response, logo_response, redirect, redirect_response, resource_response, separate_number_and_remainder, shutdown_in_progress_response, stream_response, try_option_bad_request, try_result_bad_request, try_result_not_found, try_unwrap_body, RedirectParams};
+use crate::api::endpoints::hls_api::{handle_dash_stream_request, handle_hls_stream_request};
 use crate::api::endpoints::xmltv_api::{get_empty_epg_response, get_epg_path_for_target, serve_short_epg};
 use crate::api::model::AppState;
 use crate::api::model::UserApiRequest;
 use crate::api::model::XtreamAuthorizationResponse;
+use crate::api::model::{PlaylistQueryCache, XtreamResponseKey, RESPONSE_STALE_SECS};
 use crate::api::model::{create_custom_video_stream_response, CustomVideoStreamType};
 use crate::auth::Fingerprint;
 use crate::model::{xtream_mapping_option_from_target_options, ConfigTarget};
@@ -15,7 +16,8 @@ use crate::model::{InputSource, ProxyUserCredentials};
 use crate::repository::get_target_storage_path;
 use crate::repository::storage_const;
 use crate::repository::VirtualIdRecord;
-use crate::repository::{get_target_id_mapping, user_get_bouquet_filter, xtream_get_collection_path, xtream_get_item_for_stream_id, xtream_load_rewrite_playlist};
+use crate::repository::{get_target_id_mapping, user_get_bouquet_filter, xtream_get_item_for_stream_id, xtream_load_categories, xtream_load_rewrite_playlist};
+use crate::repository::{xtream_is_playlist_locked, xtream_load_cached_response, xtream_store_cached_response};
 use crate::utils::xtream::create_vod_info_from_item;
 use crate::utils::{debug_if_enabled, file_exists_async, trace_if_enabled};
 use crate::utils::{request, xtream};
@@ -30,11 +32,14 @@ use serde_json::{json, Map, Value};
 use shared::concat_string;
 use shared::error::{info_err, info_err_res, TuliproxError};
 use shared::model::{create_stream_channel_with_type, PlaylistEntry, PlaylistItemType, ProxyType, ShortEpgResultDto, TargetType, UserConnectionPermission, XtreamCluster, XtreamPlaylistItem};
-use shared::utils::{deserialize_as_string, extract_extension_from_url, generate_playlist_uuid, sanitize_sensitive_info, trim_slash, Internable, HLS_EXT};
+use shared::utils::{current_time_secs, extract_extension_from_url, generate_playlist_uuid, sanitize_sensitive_info, trim_slash, Internable, DASH_EXT, HLS_EXT};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use std::sync::Arc;
 
+/// Client cache time of a stale response, the player fetches the updated playlist soon.
+const STALE_RESPONSE_MAX_AGE_SECS: u32 = 30;
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
 pub enum ApiStreamContext {
     LiveAlt,
@@ -134,15 +139,6 @@ impl<'a> ApiStreamRequest<'a> {
 }
 
 
-#[derive(Serialize, Deserialize)]
-struct XtreamCategoryEntry {
-    #[serde(deserialize_with = "deserialize_as_string")]
-    category_id: String,
-    category_name: String,
-    #[serde(default)]
-    parent_id: u32,
-}
-
 pub(in crate::api) fn get_xtream_player_api_stream_url(
     input: &ConfigInput,
     context: ApiStreamContext,
@@ -178,12 +174,18 @@ pub(in crate::api) fn get_xtream_player_api_stream_url(
     }
 }
 
-async fn get_user_info(user: &ProxyUserCredentials, app_state: &AppState) -> XtreamAuthorizationResponse {
+async fn get_user_info(user: &ProxyUserCredentials, target: &ConfigTarget, app_state: &AppState) -> XtreamAuthorizationResponse {
     let server_info = app_state.app_config.get_user_server_info(user);
     let active_connections = app_state.get_active_connections_for_user(&user.username).await;
+    // the timezone of the target is used when the server info of the target has none
+    let mut overrides = target.get_xtream_output().and_then(|output| output.server_info.clone()).unwrap_or_default();
+    if overrides.timezone.is_none() {
+        overrides.timezone = target.timezone.map(|tz| tz.name().to_string());
+    }
 
     XtreamAuthorizationResponse::new(
         &server_info,
+        &overrides,
         user,
         active_connections,
         app_state.app_config.config.load().user_access_control,
@@ -198,6 +200,9 @@ async fn xtream_player_api_stream(
     api_req: &UserApiRequest,
     stream_req: ApiStreamRequest<'_>,
 ) -> impl IntoResponse + Send {
+    if app_state.shutdown.is_draining() {
+        return shutdown_in_progress_response();
+    }
 
     // if log::log_enabled!(log::Level::Debug) {
     //     debug!(
@@ -225,6 +230,14 @@ async fn xtream_player_api_stream(
         return create_custom_video_stream_response(app_state, &fingerprint.addr, CustomVideoStreamType::UserAccountExpired).await.into_response();
     }
 
+    if user.country_denied(app_state, &fingerprint.client_ip) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
+    if account_sharing_denied(app_state, &user, fingerprint) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
     let target_name = &target.name;
     if !target.has_output(TargetType::Xtream) {
         debug!("Target has no xtream codes playlist {target_name}");
@@ -343,12 +356,16 @@ async fn xtream_player_api_stream(
         stream_ext: stream_ext.as_deref(),
         req_context: context,
         action_path: stream_req.action_path,
+        client_ip: &fingerprint.client_ip,
     };
     if let Some(response) = redirect_response(app_state, &redirect_params).await {
         return response.into_response();
     }
 
-    let (query_path, extension) = get_query_path(stream_req.action_path, stream_ext.as_ref(), &pli, app_state);
+    let is_dash_request = user.proxy.is_dash() && item_type.is_live() && stream_ext.as_deref() == Some(DASH_EXT);
+    // the manifest is generated locally, the provider stream is requested with its own extension
+    let query_ext = if is_dash_request { None } else { stream_ext.as_ref() };
+    let (query_path, extension) = get_query_path(stream_req.action_path, query_ext, &pli, app_state);
 
     let stream_url = try_option_bad_request!(
         get_xtream_player_api_stream_url(&input, stream_req.context, &query_path, &session_url),
@@ -359,6 +376,21 @@ async fn xtream_player_api_stream(
         )
     );
 
+    if is_dash_request {
+        return handle_dash_stream_request(
+            fingerprint,
+            app_state,
+            &user,
+            user_session.as_ref(),
+            &stream_url,
+            pli.virtual_id,
+            &input,
+            req_headers,
+            connection_permission,
+        )
+            .await;
+    }
+
     let is_hls_request = item_type == PlaylistItemType::LiveHls
         || item_type == PlaylistItemType::LiveDash
         || extension == HLS_EXT;
@@ -392,6 +424,7 @@ async fn xtream_player_api_stream(
         &target,
         &user,
         connection_permission,
+        api_req.get_quality(),
     )
         .await
         .into_response()
@@ -535,6 +568,7 @@ async fn xtream_player_api_stream_with_token(
             &target,
             &user,
             UserConnectionPermission::Allowed,
+            None,
         )
             .await
             .into_response()
@@ -584,16 +618,21 @@ async fn xtream_player_api_resource(
     );
 
     let stream_url = pli.resolve_resource_url(resource);
+    let is_logo = resource.eq_ignore_ascii_case("logo") || resource.eq_ignore_ascii_case("logo_small");
 
     match stream_url {
         None => axum::http::StatusCode::NOT_FOUND.into_response(),
         Some(url) => {
-            if user.proxy.is_redirect(pli.item_type) || target.is_force_redirect(pli.item_type) {
+            if (user.proxy.is_redirect(pli.item_type) || target.is_force_redirect(pli.item_type))
+                && !app_state.app_config.is_logo_proxy_enabled() {
                 trace_if_enabled!(
                     "Redirecting resource request to {}",
                     sanitize_sensitive_info(&url)
                 );
                 redirect(&url).into_response()
+            } else if is_logo {
+                trace_if_enabled!("Logo request to {}", sanitize_sensitive_info(&url));
+                logo_response(app_state, &url, req_headers, None).await
             } else {
                 trace_if_enabled!("Resource request to {}", sanitize_sensitive_info(&url));
                 resource_response(app_state, &url, req_headers, None).await.into_response()
@@ -799,13 +838,14 @@ pub async fn xtream_get_stream_info_response(
         target,
         Some(cluster),
     ).await {
-        if pli.item_type.is_local() {
+        // local series and the grouped series of m3u inputs have no provider info, the info is created from the details
+        if pli.item_type.is_local() || (pli.provider_id == 0 && pli.item_type == PlaylistItemType::SeriesInfo && pli.has_details()) {
             let Ok(xtream_output) = target.get_xtream_output().ok_or_else(|| info_err!("Unexpected: xtream output required for target {}", target.name)) else {
                 return try_unwrap_body!(empty_json_response_as_array());
             };
 
-            let server_info = app_state.app_config.get_user_server_info(user);
-            let options = xtream_mapping_option_from_target_options(target, xtream_output, &app_state.app_config, user, Some(server_info.get_base_url().as_str()));
+            let base_url = app_state.app_config.get_target_base_url(target, user);
+            let options = xtream_mapping_option_from_target_options(target, xtream_output, &app_state.app_config, user, Some(base_url.as_str()));
             return axum::Json(pli.to_info_document(&options)).into_response();
         }
 
@@ -941,6 +981,7 @@ async fn xtream_get_short_epg(
 
 async fn xtream_player_api_handle_content_action(
     config: &Config,
+    query_cache: &PlaylistQueryCache,
     target_name: &str,
     action: &str,
     category_id: Option<u32>,
@@ -953,29 +994,22 @@ async fn xtream_player_api_handle_content_action(
         // we dont handle this action
         _ => return None,
     };
-    if let Ok(file_path) = xtream_get_collection_path(config, target_name, collection) {
-        match tokio::fs::read_to_string(&file_path).await {
-            Ok(content) => {
-                let filter = user_get_bouquet_filter(
-                    config,
-                    &user.username,
-                    category_id,
-                    TargetType::Xtream,
-                    cluster,
-                ).await;
-
-                match serde_json::from_str::<Vec<XtreamCategoryEntry>>(&content) {
-                    Ok(mut categories) => {
-                        if let Some(fltr) = filter {
-                            categories.retain(|c| fltr.contains(&c.category_id));
-                        }
-                        return Some(axum::Json(categories).into_response());
-                    }
-                    Err(err) => error!("Failed to parse json file {}: {err}", file_path.display()),
-                }
-            }
-            Err(err) => error!("Failed to read collection file {}: {err}", file_path.display()),
-        }
+
+    let categories = xtream_load_categories(config, query_cache, target_name, collection, cluster).await;
+
+    if let Some(categories) = categories {
+        let filter = user_get_bouquet_filter(
+            config,
+            &user.username,
+            category_id,
+            TargetType::Xtream,
+            cluster,
+        ).await;
+
+        return Some(match filter {
+            Some(fltr) => axum::Json(categories.iter().filter(|c| fltr.contains(&c.category_id)).collect::<Vec<_>>()).into_response(),
+            None => axum::Json(categories.as_slice()).into_response(),
+        });
     }
 
     Some(api_utils::empty_json_list_response().into_response())
@@ -1116,18 +1150,23 @@ macro_rules! skip_flag_optional {
 
 #[allow(clippy::too_many_lines)]
 async fn xtream_player_api(
+    fingerprint: &Fingerprint,
     api_req: UserApiRequest,
     app_state: &Arc<AppState>,
 ) -> impl IntoResponse + Send {
     let user_target = get_user_target(&api_req, app_state);
     if let Some((user, target)) = user_target {
+        if user.country_denied(app_state, &fingerprint.client_ip) {
+            return axum::http::StatusCode::FORBIDDEN.into_response();
+        }
+
         if !target.has_output(TargetType::Xtream) {
-            return axum::response::Json(get_user_info(&user, app_state).await).into_response();
+            return axum::response::Json(get_user_info(&user, &target, app_state).await).into_response();
         }
 
         let action = api_req.action.trim();
         if action.is_empty() {
-            return axum::response::Json(get_user_info(&user, app_state).await).into_response();
+            return axum::response::Json(get_user_info(&user, &target, app_state).await).into_response();
         }
 
         if user.permission_denied(app_state) {
@@ -1153,7 +1192,7 @@ async fn xtream_player_api(
 
         match action {
             crate::model::XC_ACTION_GET_ACCOUNT_INFO => {
-                return axum::response::Json(get_user_info(&user, app_state).await).into_response();
+                return axum::response::Json(get_user_info(&user, &target, app_state).await).into_response();
             }
             crate::model::XC_ACTION_GET_SERIES_INFO => {
                 skip_json_response_if_flag_set!(
@@ -1212,6 +1251,7 @@ async fn xtream_player_api(
         // Handle general content actions
         if let Some(response) = xtream_player_api_handle_content_action(
             &app_state.app_config.config.load(),
+            &app_state.playlists.query_cache,
             &target.name,
             action,
             category_id,
@@ -1220,6 +1260,26 @@ async fn xtream_player_api(
             return response.into_response();
         }
 
+        let cache_ttl_secs = u64::from(app_state.app_config.get_xtream_api_cache_ttl_secs());
+        let response_key = (cache_ttl_secs > 0 && matches!(action, crate::model::XC_ACTION_GET_LIVE_STREAMS
+            | crate::model::XC_ACTION_GET_VOD_STREAMS | crate::model::XC_ACTION_GET_SERIES))
+            .then(|| XtreamResponseKey::new(&target.name, &user.username, action, category_id));
+        if let Some(key) = response_key.as_ref() {
+            if let Some(content) = xtream_get_cached_response(app_state, key, cache_ttl_secs).await {
+                return xtream_json_response(content);
+            }
+            // serve the previous response instead of waiting while the playlist is written
+            if let Some(cluster) = xtream_action_cluster(action) {
+                if xtream_is_playlist_locked(&app_state.app_config, &target.name, cluster).await {
+                    if let Some(content) = xtream_get_stale_response(app_state, key, cache_ttl_secs).await {
+                        debug!("Serving stale {action} response for target {}, playlist is locked", target.name);
+                        return xtream_stale_json_response(content);
+                    }
+                }
+            }
+        }
+        let generation = app_state.playlists.query_cache.generation();
+
         let result = match action {
             crate::model::XC_ACTION_GET_LIVE_STREAMS => skip_flag_optional!(
                 skip_live,
@@ -1228,7 +1288,8 @@ async fn xtream_player_api(
                     &app_state.app_config,
                     &target,
                     category_id,
-                    &user
+                    &user,
+                    &app_state.playlists.query_cache,
                 )
                 .await
             ),
@@ -1239,7 +1300,8 @@ async fn xtream_player_api(
                     &app_state.app_config,
                     &target,
                     category_id,
-                    &user
+                    &user,
+                    &app_state.playlists.query_cache,
                 )
                 .await
             ),
@@ -1250,7 +1312,8 @@ async fn xtream_player_api(
                     &app_state.app_config,
                     &target,
                     category_id,
-                    &user
+                    &user,
+                    &app_state.playlists.query_cache,
                 )
                 .await
             ),
@@ -1261,6 +1324,11 @@ async fn xtream_player_api(
             Some(result_iter) => {
                 match result_iter {
                     Ok(xtream_iter) => {
+                        if let Some(key) = response_key {
+                            let content = xtream_collect_content(xtream_iter);
+                            xtream_cache_response(app_state, key, generation, content.clone(), cache_ttl_secs).await;
+                            return xtream_json_response(content);
+                        }
                         // Convert the iterator into a stream of `Bytes`
                         let content_stream = xtream_create_content_stream(xtream_iter);
                         try_unwrap_body!(axum::response::Response::builder()
@@ -1311,18 +1379,92 @@ fn xtream_create_content_stream(
     )
 }
 
+fn xtream_collect_content(xtream_iter: impl Iterator<Item=(String, bool)>) -> Bytes {
+    let mut content = String::from("[");
+    for (line, has_next) in xtream_iter {
+        content.push_str(&line);
+        if has_next {
+            content.push(',');
+        }
+    }
+    content.push(']');
+    Bytes::from(content)
+}
+
+fn xtream_json_response(content: Bytes) -> axum::response::Response {
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, mime::APPLICATION_JSON.to_string())
+        .body(axum::body::Body::from(content)))
+}
+
+fn xtream_action_cluster(action: &str) -> Option<XtreamCluster> {
+    match action {
+        crate::model::XC_ACTION_GET_LIVE_STREAMS => Some(XtreamCluster::Live),
+        crate::model::XC_ACTION_GET_VOD_STREAMS => Some(XtreamCluster::Video),
+        crate::model::XC_ACTION_GET_SERIES => Some(XtreamCluster::Series),
+        _ => None,
+    }
+}
+
+fn xtream_stale_json_response(content: Bytes) -> axum::response::Response {
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, mime::APPLICATION_JSON.to_string())
+        .header(axum::http::header::CACHE_CONTROL, concat_string!("max-age=", &STALE_RESPONSE_MAX_AGE_SECS.to_string()))
+        .body(axum::body::Body::from(content)))
+}
+
+/// Looks up an expired response in the memory cache and then on disk.
+async fn xtream_get_stale_response(app_state: &Arc<AppState>, key: &XtreamResponseKey, ttl_secs: u64) -> Option<Bytes> {
+    let now = current_time_secs();
+    if let Some(content) = app_state.playlists.query_cache.get_stale_response(key, ttl_secs, now) {
+        return Some(content);
+    }
+    let config = app_state.app_config.config.load_full();
+    xtream_load_cached_response(&config, key, ttl_secs.saturating_add(RESPONSE_STALE_SECS), now).await
+        .map(|(content, _)| content)
+}
+
+/// Looks up the response in the memory cache and then on disk, disk hits are kept in memory.
+async fn xtream_get_cached_response(app_state: &Arc<AppState>, key: &XtreamResponseKey, ttl_secs: u64) -> Option<Bytes> {
+    let now = current_time_secs();
+    let query_cache = &app_state.playlists.query_cache;
+    if let Some(content) = query_cache.get_response(key, ttl_secs, now) {
+        return Some(content);
+    }
+    let generation = query_cache.generation();
+    let config = app_state.app_config.config.load_full();
+    let (content, created) = xtream_load_cached_response(&config, key, ttl_secs, now).await?;
+    query_cache.put_response(key.clone(), generation, content.clone(), created, ttl_secs);
+    Some(content)
+}
+
+async fn xtream_cache_response(app_state: &Arc<AppState>, key: XtreamResponseKey, generation: u64, content: Bytes, ttl_secs: u64) {
+    let query_cache = &app_state.playlists.query_cache;
+    // the target was processed while the response was created
+    if query_cache.generation() != generation {
+        return;
+    }
+    let config = app_state.app_config.config.load_full();
+    xtream_store_cached_response(&config, &key, &content).await;
+    query_cache.put_response(key, generation, content, current_time_secs(), ttl_secs);
+}
+
 async fn xtream_player_api_get(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
 ) -> impl IntoResponse + Send {
-    xtream_player_api(api_req, &app_state).await
+    xtream_player_api(&fingerprint, api_req, &app_state).await
 }
 
 async fn xtream_player_api_post(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     axum::extract::Form(api_req): axum::extract::Form<UserApiRequest>,
 ) -> impl IntoResponse + Send {
-    xtream_player_api(api_req, &app_state).await
+    xtream_player_api(&fingerprint, api_req, &app_state).await
 }
 
 macro_rules! register_xtream_api {
@@ -1,19 +1,34 @@
 use crate::api::api_utils::{get_build_time, get_server_time};
 use crate::api::config_watch::exec_config_watch;
+use crate::api::notification_collector::exec_notification_collector;
 use crate::api::endpoints::custom_video_stream_api::cvs_api_register;
+#[cfg(feature = "hdhomerun")]
 use crate::api::endpoints::hdhomerun_api::hdhr_api_register;
 use crate::api::endpoints::hls_api::hls_api_register;
 use crate::api::endpoints::m3u_api::m3u_api_register;
+use crate::api::endpoints::public_status_api::public_status_api_register;
+#[cfg(feature = "web-ui")]
 use crate::api::endpoints::v1_api::v1_api_register;
+#[cfg(feature = "web-ui")]
 use crate::api::endpoints::web_index::{index_register_with_path, index_register_without_path};
 use crate::api::endpoints::websocket_api::ws_api_register;
+#[cfg(feature = "web-ui")]
+use crate::api::endpoints::log_stream_api::log_stream_api_register;
 use crate::api::endpoints::xmltv_api::xmltv_api_register;
 use crate::api::endpoints::xtream_api::xtream_api_register;
+#[cfg(feature = "hdhomerun")]
 use crate::api::hdhomerun_proprietary::spawn_proprietary_tasks;
+#[cfg(feature = "hdhomerun")]
 use crate::api::hdhomerun_ssdp::spawn_ssdp_discover_task;
-use crate::api::model::{create_cache, create_http_client, ActiveProviderManager, ActiveUserManager, AppState, CancelTokens, ConnectionManager, DownloadQueue, EventManager, HdHomerunAppState, PlaylistStorageState, SharedStreamManager, UpdateGuard};
-use crate::api::scheduler::{exec_interner_prune, exec_scheduler};
+use crate::api::model::{create_cache, create_cluster_state, AccountSharingManager, ActiveProviderManager, ActiveUserManager, AppState, CancelTokens, ConnectionManager, DownloadQueue, EventManager, GeoIpAccessManager, HlsRemuxManager, PlaylistStorageState, ProviderHealthManager, SharedStreamManager, ShutdownCoordinator, UpdateGuard};
+use crate::api::scheduler::{exec_cache_cleanup, exec_cluster_sync, exec_data_retention, exec_interner_prune, exec_scheduler};
+use crate::processing::processor::epg::exec_epg_scheduler;
+#[cfg(feature = "trakt")]
+use crate::processing::processor::trakt::exec_trakt_scheduler;
+#[cfg(feature = "hdhomerun")]
+use crate::api::model::HdHomerunAppState;
 use crate::api::serve::serve;
+use crate::utils::request::create_http_client;
 use crate::model::{AppConfig, Config, Healthcheck, ProcessTargets, RateLimitConfig};
 use crate::processing::processor::playlist;
 use crate::repository::load_playlists_into_memory_cache;
@@ -22,18 +37,24 @@ use arc_swap::{ArcSwap, ArcSwapOption};
 use axum::Router;
 use axum::{middleware::Next, extract::Request};
 use axum::extract::connect_info::ConnectInfo;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use shared::utils::{concat_path_leading_slash, sanitize_sensitive_info};
 use std::io::ErrorKind;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+#[cfg(feature = "hdhomerun")]
 use std::sync::atomic::AtomicI8;
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 use tower_governor::key_extractor::SmartIpKeyExtractor;
+use tower_http::compression::predicate::{DefaultPredicate, NotForContentType, Predicate};
+use tower_http::compression::CompressionLayer;
 use tower_http::services::ServeDir;
 use crate::api::panel_api::sync_panel_api_exp_dates_on_boot;
 use crate::api::sys_usage::exec_system_usage;
+use crate::api::shutdown::exec_graceful_shutdown;
+use crate::api::channel_probe::exec_channel_probe;
+use crate::api::user_expiration::exec_user_expiration;
 use crate::repository::get_geoip_path;
 use crate::utils::{exec_file_lock_prune, GeoIp};
 
@@ -94,7 +115,10 @@ async fn create_shared_data(
     let active_provider = Arc::new(ActiveProviderManager::new(app_config, &event_manager));
     let shared_stream_manager = Arc::new(SharedStreamManager::new(Arc::clone(&active_provider)));
     let active_users = Arc::new(ActiveUserManager::new(&config,&geoip, &event_manager));
-    let connection_manager = Arc::new(ConnectionManager::new(&active_users, &active_provider, &shared_stream_manager, &event_manager));
+    let connection_manager = Arc::new(ConnectionManager::new(app_config, &active_users, &active_provider, &shared_stream_manager, &event_manager));
+
+    let cluster = create_cluster_state(&config);
+    active_users.set_cluster(cluster.clone());
 
     let client = create_http_client(app_config);
 
@@ -105,14 +129,20 @@ async fn create_shared_data(
         downloads: Arc::new(DownloadQueue::new()),
         cache: Arc::new(ArcSwapOption::from(cache)),
         shared_stream_manager,
+        hls_remux_manager: Arc::new(HlsRemuxManager::new()),
         active_users,
         active_provider,
+        provider_health: Arc::new(ProviderHealthManager::new()),
+        geoip_access: Arc::new(GeoIpAccessManager::new()),
+        account_sharing: Arc::new(AccountSharingManager::new()),
         connection_manager,
         event_manager,
         cancel_tokens: Arc::new(ArcSwap::from_pointee(CancelTokens::default())),
         playlists: Arc::new(PlaylistStorageState::new()),
         geoip,
         update_guard: UpdateGuard::new(),
+        cluster,
+        shutdown: Arc::new(ShutdownCoordinator::new()),
     }
 }
 
@@ -139,6 +169,15 @@ fn exec_update_on_boot(
     }
 }
 
+fn is_web_ui_enabled(cfg: &Config) -> bool {
+    let enabled = cfg.web_ui.as_ref().is_some_and(|c| c.enabled);
+    if enabled && !cfg!(feature = "web-ui") {
+        warn!("Web ui is enabled but not available, tuliprox was built without the `web-ui` feature");
+        return false;
+    }
+    enabled
+}
+
 fn is_web_auth_enabled(cfg: &Arc<Config>, web_ui_enabled: bool) -> bool {
     if web_ui_enabled {
         if let Some(web_auth) = &cfg.web_ui.as_ref().and_then(|c| c.auth.as_ref()) {
@@ -160,14 +199,33 @@ fn create_cors_layer() -> tower_http::cors::CorsLayer {
         .allow_headers(tower_http::cors::Any)
         .max_age(std::time::Duration::from_secs(3600))
 }
-fn create_compression_layer() -> tower_http::compression::CompressionLayer {
-    tower_http::compression::CompressionLayer::new()
+/// The encoding is negotiated with `Accept-Encoding` and the body is compressed while streaming.
+/// Media streams are passed through uncompressed.
+fn create_compression_layer() -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new()
         .br(true)
         .deflate(true)
         .gzip(true)
         .zstd(true)
+        .compress_when(DefaultPredicate::new()
+            .and(NotForContentType::const_new("video/"))
+            .and(NotForContentType::const_new("audio/"))
+            .and(NotForContentType::const_new("application/octet-stream")))
 }
 
+#[cfg(not(feature = "hdhomerun"))]
+pub(in crate::api) fn start_hdhomerun(
+    app_config: &Arc<AppConfig>,
+    _app_state: &Arc<AppState>,
+    _infos: &mut Vec<String>,
+    _cancel_token: &CancellationToken,
+) {
+    if app_config.hdhomerun.load().as_ref().is_some_and(|hdhomerun| hdhomerun.enabled) {
+        warn!("HdHomeRun is enabled but not available, tuliprox was built without the `hdhomerun` feature");
+    }
+}
+
+#[cfg(feature = "hdhomerun")]
 pub(in crate::api) fn start_hdhomerun(
     app_config: &Arc<AppConfig>,
     app_state: &Arc<AppState>,
@@ -251,7 +309,7 @@ pub async fn start_server(
     let cfg = app_config.config.load();
     let host = cfg.api.host.clone();
     let port = cfg.api.port;
-    let web_ui_enabled = cfg.web_ui.as_ref().is_some_and(|c| c.enabled);
+    let web_ui_enabled = is_web_ui_enabled(&cfg);
     let web_dir_path = match get_web_dir_path(web_ui_enabled, cfg.api.web_root.as_str()) {
         Ok(result) => result,
         Err(err) => return Err(err),
@@ -278,6 +336,10 @@ pub async fn start_server(
 
     exec_system_usage(&app_state);
 
+    if web_ui_enabled {
+        exec_notification_collector(&app_state);
+    }
+
     let client = shared_data.http_client.load();
 
     sync_panel_api_exp_dates_on_boot(&app_state).await;
@@ -289,6 +351,19 @@ pub async fn start_server(
         &cancel_token_scheduler,
     );
 
+    exec_epg_scheduler(
+        client.as_ref(),
+        &app_state,
+        &cancel_token_scheduler,
+    );
+
+    #[cfg(feature = "trakt")]
+    exec_trakt_scheduler(
+        client.as_ref(),
+        &app_state,
+        &cancel_token_scheduler,
+    );
+
     exec_update_on_boot(
         client.as_ref(),
         &app_state,
@@ -299,8 +374,20 @@ pub async fn start_server(
 
     exec_interner_prune(&app_state);
 
+    exec_data_retention(&app_state);
+
+    exec_cache_cleanup(&app_state);
+
+    exec_cluster_sync(&app_state);
+
+    exec_channel_probe(&app_state);
+
+    exec_user_expiration(&app_state);
+
     exec_config_watch(&app_state, &cancel_token_file_watch);
 
+    exec_graceful_shutdown(&app_state);
+
     let web_auth_enabled = is_web_auth_enabled(&cfg, web_ui_enabled);
 
     if app_config.api_proxy.load().is_some() {
@@ -321,11 +408,13 @@ pub async fn start_server(
     // Web Server
     let mut router = axum::Router::new()
         .route("/healthcheck", axum::routing::get(healthcheck))
+        .merge(public_status_api_register())
         .nest_service("/.well-known", ServeDir::new(web_dir_path.join("static/.well-known")))
         .merge(ws_api_register(
             web_auth_enabled,
             web_ui_path.as_str(),
         ));
+    #[cfg(feature = "web-ui")]
     if web_ui_enabled {
         router = router
             .nest_service(
@@ -336,6 +425,7 @@ pub async fn start_server(
                 &concat_path_leading_slash(&web_ui_path, "assets"),
                 tower_http::services::ServeDir::new(web_dir_path.join("assets")),
             )
+            .merge(log_stream_api_register(web_auth_enabled, web_ui_path.as_str()))
             .merge(v1_api_register(
                 web_auth_enabled,
                 Arc::clone(&shared_data),
@@ -365,18 +455,21 @@ pub async fn start_server(
 
     router = router.merge(api_router);
 
+    #[cfg(feature = "web-ui")]
     if web_ui_enabled && web_ui_path.is_empty() {
         router = router.merge(index_register_without_path(&web_dir_path));
     }
 
     router = router
         .layer(axum::middleware::from_fn(log_req))
-        .layer(create_cors_layer())
-        .layer(create_compression_layer());
+        .layer(create_cors_layer());
+    if app_config.is_reverse_proxy_compression_enabled() {
+        router = router.layer(create_compression_layer());
+    }
 
     let router: axum::Router<()> = router.with_state(shared_data.clone());
     let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
-    serve(listener, router, None, &shared_data.connection_manager).await;
+    serve(listener, router, Some(shared_data.shutdown.stop_token()), &shared_data.connection_manager).await;
     Ok(())
 }
 
@@ -1,12 +1,33 @@
 pub mod model;
+#[cfg(feature = "server")]
 pub mod api_utils;
+#[cfg(feature = "server")]
 mod panel_api;
-mod scheduler;
+#[cfg(feature = "server")]
+mod user_import;
+#[cfg(feature = "server")]
+pub(crate) mod scheduler;
+#[cfg(feature = "server")]
 mod endpoints;
+#[cfg(feature = "server")]
 pub mod main_api;
+#[cfg(feature = "server")]
 mod config_watch;
+#[cfg(feature = "server")]
+mod notification_collector;
+#[cfg(feature = "server")]
 mod serve;
+#[cfg(feature = "hdhomerun")]
 pub(crate) mod hdhomerun_ssdp;
+#[cfg(feature = "hdhomerun")]
 pub(crate) mod hdhomerun_proprietary;
+#[cfg(feature = "server")]
 mod sys_usage;
+#[cfg(feature = "server")]
+mod shutdown;
+#[cfg(feature = "server")]
+mod channel_probe;
+#[cfg(feature = "server")]
+mod user_expiration;
+#[cfg(feature = "server")]
 mod config_file;
@@ -0,0 +1,145 @@
+use crate::model::{AccountSharingConfig, AccountSharingLimits};
+use dashmap::DashMap;
+use shared::model::AccountSharingAction;
+use std::collections::HashSet;
+
+const MAX_CLIENTS_PER_USER: usize = 128;
+
+#[derive(Debug, Clone)]
+struct ClientObservation {
+    ip: String,
+    country: Option<String>,
+    device: String,
+    ts: u64,
+}
+
+#[derive(Debug, Default)]
+struct UserClients {
+    clients: Vec<ClientObservation>,
+    alert_ts: u64,
+    blocked_until: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountSharingDecision {
+    Allowed,
+    /// The limits are exceeded, the message is sent once per window.
+    Alert(String),
+    /// The limits are exceeded and the user is blocked, the message is only set when the block starts.
+    Blocked(Option<String>),
+}
+
+/// Tracks the distinct clients (ip, country and device fingerprint) of each user
+/// within a sliding window to detect shared accounts.
+#[derive(Debug, Default)]
+pub struct AccountSharingManager {
+    users: DashMap<String, UserClients>,
+}
+
+impl AccountSharingManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn check(&self, config: &AccountSharingConfig, limits: AccountSharingLimits, username: &str,
+                 ip: &str, country: Option<&str>, device: &str, now: u64) -> AccountSharingDecision {
+        let mut user = self.users.entry(username.to_string()).or_default();
+        if user.blocked_until > now {
+            return AccountSharingDecision::Blocked(None);
+        }
+
+        let window_start = now.saturating_sub(config.window_secs);
+        user.clients.retain(|client| client.ts > window_start);
+        if let Some(client) = user.clients.iter_mut().find(|client| client.device == device) {
+            client.ts = now;
+        } else {
+            if user.clients.len() >= MAX_CLIENTS_PER_USER {
+                user.clients.sort_by_key(|client| std::cmp::Reverse(client.ts));
+                user.clients.truncate(MAX_CLIENTS_PER_USER - 1);
+            }
+            user.clients.push(ClientObservation {
+                ip: ip.to_string(),
+                country: country.map(ToString::to_string),
+                device: device.to_string(),
+                ts: now,
+            });
+        }
+
+        let Some(violation) = Self::find_violation(&user.clients, limits) else {
+            return AccountSharingDecision::Allowed;
+        };
+        let message = format!("Account sharing detected for user {username}: {violation} within {}s", config.window_secs);
+        match config.action {
+            AccountSharingAction::Block => {
+                user.blocked_until = now + config.block_secs;
+                user.clients.clear();
+                AccountSharingDecision::Blocked(Some(format!("{message}, blocked for {}s", config.block_secs)))
+            }
+            AccountSharingAction::Alert => {
+                if user.alert_ts > window_start {
+                    AccountSharingDecision::Allowed
+                } else {
+                    user.alert_ts = now;
+                    AccountSharingDecision::Alert(message)
+                }
+            }
+        }
+    }
+
+    fn find_violation(clients: &[ClientObservation], limits: AccountSharingLimits) -> Option<String> {
+        let check = |limit: u16, count: usize, name: &str| {
+            (limit > 0 && count > usize::from(limit)).then(|| format!("{count} {name} (max {limit})"))
+        };
+        let ips = clients.iter().map(|client| client.ip.as_str()).collect::<HashSet<_>>().len();
+        let countries = clients.iter().filter_map(|client| client.country.as_deref()).collect::<HashSet<_>>().len();
+        check(limits.max_ips, ips, "ips")
+            .or_else(|| check(limits.max_countries, countries, "countries"))
+            .or_else(|| check(limits.max_devices, clients.len(), "devices"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountSharingDecision, AccountSharingManager};
+    use crate::model::{AccountSharingConfig, AccountSharingLimits};
+    use shared::model::AccountSharingAction;
+
+    fn config(action: AccountSharingAction) -> AccountSharingConfig {
+        AccountSharingConfig {
+            enabled: true,
+            window_secs: 100,
+            limits: AccountSharingLimits { max_ips: 2, max_countries: 0, max_devices: 0 },
+            action,
+            block_secs: 50,
+        }
+    }
+
+    #[test]
+    fn test_account_sharing_alert() {
+        let manager = AccountSharingManager::new();
+        let config = config(AccountSharingAction::Alert);
+        let limits = config.limits;
+        assert_eq!(manager.check(&config, limits, "user", "1.1.1.1", Some("DE"), "1.1.1.1|a", 10), AccountSharingDecision::Allowed);
+        assert_eq!(manager.check(&config, limits, "user", "2.2.2.2", Some("DE"), "2.2.2.2|a", 20), AccountSharingDecision::Allowed);
+        assert!(matches!(manager.check(&config, limits, "user", "3.3.3.3", Some("AT"), "3.3.3.3|a", 30), AccountSharingDecision::Alert(_)));
+        // alerted once per window
+        assert_eq!(manager.check(&config, limits, "user", "3.3.3.3", Some("AT"), "3.3.3.3|a", 40), AccountSharingDecision::Allowed);
+        // the first clients left the window
+        assert_eq!(manager.check(&config, limits, "user", "3.3.3.3", Some("AT"), "3.3.3.3|a", 125), AccountSharingDecision::Allowed);
+    }
+
+    #[test]
+    fn test_account_sharing_block() {
+        let manager = AccountSharingManager::new();
+        let config = config(AccountSharingAction::Block);
+        let limits = config.limits.with_overrides(None);
+        manager.check(&config, limits, "user", "1.1.1.1", None, "1.1.1.1|a", 10);
+        manager.check(&config, limits, "user", "2.2.2.2", None, "2.2.2.2|a", 10);
+        assert!(matches!(manager.check(&config, limits, "user", "3.3.3.3", None, "3.3.3.3|a", 10), AccountSharingDecision::Blocked(Some(_))));
+        assert_eq!(manager.check(&config, limits, "user", "1.1.1.1", None, "1.1.1.1|a", 20), AccountSharingDecision::Blocked(None));
+        assert_eq!(manager.check(&config, limits, "other", "1.1.1.1", None, "1.1.1.1|a", 20), AccountSharingDecision::Allowed);
+        // the block expired
+        assert_eq!(manager.check(&config, limits, "user", "1.1.1.1", None, "1.1.1.1|a", 61), AccountSharingDecision::Allowed);
+    }
+}
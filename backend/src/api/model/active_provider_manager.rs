@@ -348,4 +348,12 @@ impl ActiveProviderManager {
     pub async fn get_provider_connections_count(&self) -> usize {
         self.providers.active_connection_count().await
     }
+
+    pub async fn local_connections(&self) -> HashMap<Arc<str>, usize> {
+        self.providers.local_connections().await
+    }
+
+    pub async fn set_remote_connections(&self, counts: HashMap<Arc<str>, usize>) {
+        self.providers.set_remote_connections(counts).await;
+    }
 }
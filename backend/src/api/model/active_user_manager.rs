@@ -1,4 +1,4 @@
-use crate::api::model::{CustomVideoStreamType, EventManager, EventMessage};
+use crate::api::model::{ClusterState, ClusterUserSession, CustomVideoStreamType, EventManager, EventMessage};
 use crate::auth::Fingerprint;
 use crate::model::Config;
 use crate::model::ProxyUserCredentials;
@@ -80,6 +80,8 @@ struct UserConnections {
     kicked: HashMap<String, (u64, VirtualId)>,
     by_key: HashMap<String, UserConnectionData>,
     key_by_addr: HashMap<SocketAddr, String>,
+    // connections of the other cluster instances
+    remote: HashMap<String, u32>,
 }
 
 pub struct ActiveUserManager {
@@ -92,6 +94,7 @@ pub struct ActiveUserManager {
     geo_ip: Arc<ArcSwapOption<GeoIp>>,
     last_logged_user_count: AtomicUsize,
     last_logged_user_connection_count: AtomicUsize,
+    cluster: ArcSwapOption<ClusterState>,
 }
 
 impl ActiveUserManager {
@@ -111,6 +114,7 @@ impl ActiveUserManager {
             event_manager: Arc::clone(event_manager),
             last_logged_user_count: AtomicUsize::new(0),
             last_logged_user_connection_count: AtomicUsize::new(0),
+            cluster: ArcSwapOption::from(None),
         }
     }
 
@@ -172,6 +176,22 @@ impl ActiveUserManager {
         self.log_active_user.store(log_active_user, Ordering::Relaxed);
     }
 
+    pub fn set_cluster(&self, cluster: Option<Arc<ClusterState>>) {
+        self.cluster.store(cluster);
+    }
+
+    /// Connections per user of this instance, published to the other cluster instances.
+    pub async fn local_connections(&self) -> HashMap<String, u32> {
+        self.connections.read().await.by_key.iter()
+            .filter(|(_, c)| c.connections > 0)
+            .map(|(username, c)| (username.clone(), c.connections))
+            .collect()
+    }
+
+    pub async fn set_remote_connections(&self, remote: HashMap<String, u32>) {
+        self.connections.write().await.remote = remote;
+    }
+
     pub async fn user_connections(&self, username: &str) -> u32 {
         if let Some(connection_data) = self.connections.read().await.by_key.get(username) {
             return connection_data.connections;
@@ -179,8 +199,8 @@ impl ActiveUserManager {
         0
     }
 
-    fn check_connection_permission(&self, username: &str, connection_data: &mut UserConnectionData) -> UserConnectionPermission {
-        let current_connections = connection_data.connections;
+    fn check_connection_permission(&self, username: &str, connection_data: &mut UserConnectionData, remote_connections: u32) -> UserConnectionPermission {
+        let current_connections = connection_data.connections + remote_connections;
 
         if current_connections < connection_data.max_connections {
             // Reset grace period because the user is back under max_connections
@@ -221,8 +241,14 @@ impl ActiveUserManager {
         max_connections: u32,
     ) -> UserConnectionPermission {
         if max_connections > 0 {
-            if let Some(connection_data) = self.connections.write().await.by_key.get_mut(username) {
-                return self.check_connection_permission(username, connection_data);
+            let mut user_connections = self.connections.write().await;
+            let remote_connections = user_connections.remote.get(username).copied().unwrap_or(0);
+            if remote_connections > 0 && !user_connections.by_key.contains_key(username) {
+                // first connection on this instance, the user streams on other instances
+                user_connections.by_key.insert(username.to_string(), UserConnectionData::new(0, max_connections));
+            }
+            if let Some(connection_data) = user_connections.by_key.get_mut(username) {
+                return self.check_connection_permission(username, connection_data, remote_connections);
             }
         }
         UserConnectionPermission::Allowed
@@ -388,7 +414,9 @@ impl ActiveUserManager {
                 }
                 session.permission = connection_permission;
                 debug_if_enabled!("Using session for user {} with url: {}", user.username, sanitize_sensitive_info(stream_url));
-                return session.token.clone();
+                let token = session.token.clone();
+                self.publish_session(&username, session, user.max_connections);
+                return token;
             }
         }
 
@@ -397,10 +425,45 @@ impl ActiveUserManager {
             user.username, sanitize_sensitive_info(stream_url));
         let session = Self::new_user_session(session_token, virtual_id, provider, stream_url, addr, connection_permission);
         let token = session.token.clone();
+        self.publish_session(&username, &session, user.max_connections);
         connection_data.add_session(session);
         token
     }
 
+    /// Stores the session in the cluster, a seek of the client can be served by another instance.
+    fn publish_session(&self, username: &str, session: &UserSession, max_connections: u32) {
+        if let Some(cluster) = self.cluster.load_full() {
+            let username = username.to_string();
+            let token = session.token.clone();
+            let cluster_session = ClusterUserSession {
+                virtual_id: session.virtual_id,
+                provider: session.provider.to_string(),
+                stream_url: session.stream_url.to_string(),
+                max_connections,
+                permission: session.permission,
+            };
+            tokio::spawn(async move {
+                cluster.store_session(&username, &token, &cluster_session).await;
+            });
+        }
+    }
+
+    /// Takes over a session created by another cluster instance.
+    async fn adopt_cluster_session(&self, username: &str, token: &str) -> bool {
+        let Some(cluster) = self.cluster.load_full() else { return false; };
+        let Some(cluster_session) = cluster.load_session(username, token).await else { return false; };
+        debug_if_enabled!("Using cluster session {token} for user {username}");
+        let session = Self::new_user_session(token, cluster_session.virtual_id, &cluster_session.provider,
+                                             &cluster_session.stream_url, &SocketAddr::from(([0, 0, 0, 0], 0)), cluster_session.permission);
+        let mut user_connections = self.connections.write().await;
+        let connection_data = user_connections.by_key.entry(username.to_string())
+            .or_insert_with(|| UserConnectionData::new(0, cluster_session.max_connections));
+        if !connection_data.sessions.iter().any(|s| s.token == token) {
+            connection_data.add_session(session);
+        }
+        true
+    }
+
     pub async fn update_session_addr(&self, username: &str, token: &str, addr: &SocketAddr) {
         let mut user_connections = self.connections.write().await;
         if let Some(connection_data) = user_connections.by_key.get_mut(username) {
@@ -421,12 +484,19 @@ impl ActiveUserManager {
     }
 
     pub async fn get_and_update_user_session(&self, username: &str, token: &str) -> Option<UserSession> {
-        self.update_user_session(username, token).await
+        if let Some(session) = self.update_user_session(username, token).await {
+            return Some(session);
+        }
+        if self.adopt_cluster_session(username, token).await {
+            return self.update_user_session(username, token).await;
+        }
+        None
     }
 
     async fn update_user_session(&self, username: &str, token: &str) -> Option<UserSession> {
         let mut user_connections = self.connections.write().await;
 
+        let remote_connections = user_connections.remote.get(username).copied().unwrap_or(0);
         let connection_data = user_connections.by_key.get_mut(username)?;
         let now = current_time_secs();
 
@@ -439,7 +509,7 @@ impl ActiveUserManager {
         if connection_data.max_connections > 0
             && connection_data.sessions[session_index].permission == UserConnectionPermission::GracePeriod
         {
-            let new_permission = self.check_connection_permission(username, connection_data);
+            let new_permission = self.check_connection_permission(username, connection_data, remote_connections);
             connection_data.sessions[session_index].permission = new_permission;
         }
 
@@ -1,21 +1,27 @@
 use crate::api::config_watch::exec_config_watch;
-use crate::api::model::{ActiveProviderManager, ConnectionManager, EventManager, PlaylistStorage, PlaylistStorageState, SharedStreamManager};
-use crate::api::model::{ActiveUserManager, DownloadQueue};
+use crate::api::model::{AccountSharingManager, ActiveProviderManager, ConnectionManager, EventManager, GeoIpAccessManager, HlsRemuxManager, PlaylistStorage, PlaylistStorageState, ProviderHealthManager, SharedStreamManager};
+use crate::api::model::{ActiveUserManager, ClusterState, DownloadQueue, ShutdownCoordinator};
 use crate::api::scheduler::exec_scheduler;
-use crate::model::{AppConfig, Config, ConfigTarget, GracePeriodOptions, HdHomeRunConfig, HdHomeRunDeviceConfig, ProcessTargets, ReverseProxyDisabledHeaderConfig, ScheduleConfig, SourcesConfig};
+use crate::processing::processor::epg::exec_epg_scheduler;
+#[cfg(feature = "trakt")]
+use crate::processing::processor::trakt::exec_trakt_scheduler;
+use crate::model::{AppConfig, Config, ConfigTarget, GracePeriodOptions, HdHomeRunConfig, ProcessTargets, ReverseProxyDisabledHeaderConfig, ScheduleConfig, SourcesConfig};
 use crate::repository::load_target_into_memory_cache;
 use crate::tools::lru_cache::LRUResourceCache;
-use crate::utils::request::create_client;
+use crate::utils::request::create_http_client;
+use crate::utils::input_client::clear_input_clients;
 use arc_swap::{ArcSwap, ArcSwapOption};
 use log::{error, info};
 use reqwest::Client;
 use shared::error::TuliproxError;
-use shared::model::{UserConnectionPermission};
+use shared::model::{CacheEvictionPolicy, UserConnectionPermission};
 use shared::utils::{small_vecs_equal_unordered};
 use std::collections::HashMap;
+#[cfg(feature = "hdhomerun")]
 use std::sync::atomic::AtomicI8;
+#[cfg(feature = "hdhomerun")]
+use crate::model::HdHomeRunDeviceConfig;
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::{Mutex};
 use tokio::task;
 use tokio_util::sync::CancellationToken;
@@ -80,6 +86,7 @@ async fn update_target_caches(
         for target in target_changes.values() {
             match target.status {
                 TargetStatus::Old => {
+                    app_state.playlists.query_cache.invalidate(&target.name);
                     to_remove.push(target.name.clone());
                 }
                 TargetStatus::New // Normally, a new target shouldn't require any updates, but attempting to load it does no harm.
@@ -164,6 +171,17 @@ fn start_services(app_state: &Arc<AppState>, changes: &UpdateChanges) {
             &app_state.forced_targets.load(),
             &app_state.cancel_tokens.load().scheduler,
         );
+        exec_epg_scheduler(
+            &Arc::clone(&app_state.http_client.load()),
+            app_state,
+            &app_state.cancel_tokens.load().scheduler,
+        );
+        #[cfg(feature = "trakt")]
+        exec_trakt_scheduler(
+            &Arc::clone(&app_state.http_client.load()),
+            app_state,
+            &app_state.cancel_tokens.load().scheduler,
+        );
     }
 
     if changes.hdhomerun && app_state.app_config.api_proxy.load().is_some() {
@@ -181,14 +199,18 @@ fn start_services(app_state: &Arc<AppState>, changes: &UpdateChanges) {
     }
 }
 
-pub fn create_http_client(app_config: &AppConfig) -> Client {
-    let mut builder = create_client(app_config).http1_only();
-    let config = app_config.config.load(); // because of RAII connection dropping
-    if config.connect_timeout_secs > 0 {
-        builder =
-            builder.connect_timeout(Duration::from_secs(u64::from(config.connect_timeout_secs)));
+pub fn create_cluster_state(config: &Config) -> Option<Arc<ClusterState>> {
+    let cluster_config = config.cluster.as_ref().filter(|c| c.enabled)?;
+    match ClusterState::new(cluster_config) {
+        Ok(cluster) => {
+            info!("Cluster enabled, node id: {}", cluster.node_id());
+            Some(Arc::new(cluster))
+        }
+        Err(err) => {
+            error!("{err}");
+            None
+        }
     }
-    builder.build().unwrap_or_else(|_| Client::new())
 }
 
 pub fn create_cache(config: &Config) -> Option<Arc<Mutex<LRUResourceCache>>> {
@@ -198,7 +220,11 @@ pub fn create_cache(config: &Config) -> Option<Arc<Mutex<LRUResourceCache>>> {
         .and_then(|r| r.cache.as_ref())
         .and_then(|c| {
             if c.enabled {
-                Some(LRUResourceCache::new(c.size, c.dir.as_str()))
+                let mut cache = LRUResourceCache::new(c.size, c.dir.as_str(), c.eviction);
+                if config.cluster.as_ref().is_some_and(|cluster| cluster.enabled) {
+                    cache.track_removed_keys();
+                }
+                Some(cache)
             } else {
                 None
             }
@@ -257,14 +283,20 @@ pub struct AppState {
     pub downloads: Arc<DownloadQueue>,
     pub cache: Arc<ArcSwapOption<Mutex<LRUResourceCache>>>,
     pub shared_stream_manager: Arc<SharedStreamManager>,
+    pub hls_remux_manager: Arc<HlsRemuxManager>,
     pub active_users: Arc<ActiveUserManager>,
     pub active_provider: Arc<ActiveProviderManager>,
+    pub provider_health: Arc<ProviderHealthManager>,
+    pub geoip_access: Arc<GeoIpAccessManager>,
+    pub account_sharing: Arc<AccountSharingManager>,
     pub connection_manager: Arc<ConnectionManager>,
     pub event_manager: Arc<EventManager>,
     pub cancel_tokens: Arc<ArcSwap<CancelTokens>>,
     pub playlists: Arc<PlaylistStorageState>,
     pub geoip: Arc<ArcSwapOption<GeoIp>>,
     pub update_guard: UpdateGuard,
+    pub cluster: Option<Arc<ClusterState>>,
+    pub shutdown: Arc<ShutdownCoordinator>,
 }
 
 impl AppState {
@@ -301,18 +333,19 @@ impl AppState {
         // client
         let client = create_http_client(&self.app_config);
         self.http_client.store(Arc::new(client));
+        clear_input_clients();
 
         // cache
         let config = self.app_config.config.load();
-        let (enabled, size, cache_dir) = config
+        let (enabled, size, cache_dir, eviction) = config
             .reverse_proxy
             .as_ref()
             .and_then(|r| r.cache.as_ref())
-            .map_or((false, 0, ""), |c| (c.enabled, c.size, c.dir.as_str()));
+            .map_or((false, 0, "", CacheEvictionPolicy::default()), |c| (c.enabled, c.size, c.dir.as_str(), c.eviction));
 
         if let Some(cache) = self.cache.load().as_ref() {
             if enabled {
-                cache.lock().await.update_config(size, cache_dir);
+                cache.lock().await.update_config(size, cache_dir, eviction);
             } else {
                 self.cache.store(None);
             }
@@ -370,9 +403,11 @@ impl AppState {
     }
 
     fn detect_changes_for_sources(&self, sources: &SourcesConfig) -> UpdateChanges {
-        let (file_watch_changed, target_changes) = {
+        let (file_watch_changed, epg_schedules_changed, target_changes) = {
             let old_sources = self.app_config.sources.load();
             let file_watch_changed = old_sources.get_input_files() != sources.get_input_files();
+            let epg_schedules_changed = old_sources.get_epg_schedules() != sources.get_epg_schedules()
+                || old_sources.get_trakt_schedules() != sources.get_trakt_schedules();
 
             let mut target_changes = HashMap::new();
             for source in &old_sources.sources {
@@ -426,11 +461,11 @@ impl AppState {
                 }
             }
 
-            (file_watch_changed, target_changes)
+            (file_watch_changed, epg_schedules_changed, target_changes)
         };
 
         UpdateChanges {
-            scheduler: false,
+            scheduler: epg_schedules_changed,
             hdhomerun: false,
             file_watch: file_watch_changed,
             geoip: false,
@@ -490,6 +525,7 @@ fn string_changed(a: &str, b: &str) -> bool {
     a != b
 }
 
+#[cfg(feature = "hdhomerun")]
 #[derive(Clone)]
 pub struct HdHomerunAppState {
     pub app_state: Arc<AppState>,
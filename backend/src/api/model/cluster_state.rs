@@ -0,0 +1,251 @@
+use crate::model::ClusterConfig;
+use log::{info, warn};
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use shared::model::UserConnectionPermission;
+use shared::utils::current_time_secs;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Mutex;
+
+// Sessions are kept as long as the local sessions, the gc removes them after 15 minutes.
+const SESSION_TTL_SECS: u64 = 900;
+
+/// Session as it is published to the other instances, the client address is only known locally.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ClusterUserSession {
+    pub virtual_id: u32,
+    pub provider: String,
+    pub stream_url: String,
+    pub max_connections: u32,
+    pub permission: UserConnectionPermission,
+}
+
+/// Resource in the cache of an instance, the key is the file name of the cached content.
+/// Instances sharing the cache directory take the file over instead of downloading it again.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ClusterCacheEntry {
+    pub mime_type: Option<String>,
+    pub size: usize,
+    pub node_id: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ClusterCounts {
+    pub providers: HashMap<String, usize>,
+    pub users: HashMap<String, u32>,
+}
+
+/// Connection counts, user sessions and the cache index shared between the instances through redis.
+///
+/// Every instance publishes its own counts into a hash per instance with an expiry,
+/// the counts of the other instances are summed up.
+/// An instance which stops publishing drops out after a few sync intervals.
+pub struct ClusterState {
+    client: redis::Client,
+    connection: Mutex<Option<ConnectionManager>>,
+    node_id: String,
+    key_prefix: String,
+    node_ttl_secs: u64,
+    available: AtomicBool,
+}
+
+impl ClusterState {
+    pub fn new(config: &ClusterConfig) -> Result<Self, String> {
+        let client = redis::Client::open(config.redis_url.as_str()).map_err(|err| format!("Invalid cluster redis_url: {err}"))?;
+        Ok(Self {
+            client,
+            connection: Mutex::new(None),
+            node_id: config.node_id.clone(),
+            key_prefix: config.key_prefix.clone(),
+            node_ttl_secs: (config.sync_interval_millis * 3).div_ceil(1000).max(2),
+            available: AtomicBool::new(true),
+        })
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    async fn get_connection(&self) -> redis::RedisResult<ConnectionManager> {
+        let mut guard = self.connection.lock().await;
+        if let Some(con) = guard.as_ref() {
+            return Ok(con.clone());
+        }
+        let con = self.client.get_connection_manager().await?;
+        *guard = Some(con.clone());
+        Ok(con)
+    }
+
+    fn nodes_key(&self) -> String {
+        format!("{}:nodes", self.key_prefix)
+    }
+
+    fn node_key(&self, node_id: &str, kind: &str) -> String {
+        format!("{}:node:{node_id}:{kind}", self.key_prefix)
+    }
+
+    fn session_key(&self, username: &str, token: &str) -> String {
+        format!("{}:session:{username}:{token}", self.key_prefix)
+    }
+
+    fn cache_index_key(&self) -> String {
+        format!("{}:cache", self.key_prefix)
+    }
+
+    /// Logs only the transitions between reachable and unreachable, not every failed sync.
+    fn set_available(&self, available: bool, err: Option<&redis::RedisError>) {
+        if self.available.swap(available, Ordering::AcqRel) != available {
+            if available {
+                info!("Cluster redis connection restored");
+            } else if let Some(err) = err {
+                warn!("Cluster redis not reachable, connection limits are only enforced locally: {err}");
+            }
+        }
+    }
+
+    fn add_hash<K: ToString, V: ToString>(&self, pipe: &mut redis::Pipeline, kind: &str, counts: &HashMap<K, V>) {
+        let key = self.node_key(&self.node_id, kind);
+        pipe.del(&key).ignore();
+        let values: Vec<(String, String)> = counts.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        if !values.is_empty() {
+            pipe.hset_multiple(&key, &values).ignore()
+                .expire(&key, i64::try_from(self.node_ttl_secs).unwrap_or(i64::MAX)).ignore();
+        }
+    }
+
+    async fn publish(&self, con: &mut ConnectionManager, providers: &HashMap<String, usize>, users: &HashMap<String, u32>) -> redis::RedisResult<()> {
+        let now = current_time_secs();
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        self.add_hash(&mut pipe, "providers", providers);
+        self.add_hash(&mut pipe, "users", users);
+        pipe.zadd(self.nodes_key(), &self.node_id, now).ignore()
+            .zrembyscore(self.nodes_key(), 0, now.saturating_sub(self.node_ttl_secs)).ignore();
+        pipe.query_async::<()>(con).await
+    }
+
+    async fn read_remote<V>(&self, con: &mut ConnectionManager, nodes: &[String], kind: &str) -> redis::RedisResult<HashMap<String, V>>
+    where
+        V: redis::FromRedisValue + std::ops::AddAssign + Default + Copy,
+    {
+        let mut result: HashMap<String, V> = HashMap::new();
+        for node in nodes.iter().filter(|node| **node != self.node_id) {
+            let counts: HashMap<String, V> = con.hgetall(self.node_key(node, kind)).await?;
+            sum_into(&mut result, counts);
+        }
+        Ok(result)
+    }
+
+    /// Publishes the local counts and returns the summed counts of the other instances.
+    pub async fn sync(&self, providers: &HashMap<String, usize>, users: &HashMap<String, u32>) -> Option<ClusterCounts> {
+        let result = async {
+            let mut con = self.get_connection().await?;
+            self.publish(&mut con, providers, users).await?;
+            let min_ts = current_time_secs().saturating_sub(self.node_ttl_secs);
+            let nodes: Vec<String> = con.zrangebyscore(self.nodes_key(), min_ts, "+inf").await?;
+            Ok::<ClusterCounts, redis::RedisError>(ClusterCounts {
+                providers: self.read_remote(&mut con, &nodes, "providers").await?,
+                users: self.read_remote(&mut con, &nodes, "users").await?,
+            })
+        }.await;
+        match result {
+            Ok(counts) => {
+                self.set_available(true, None);
+                Some(counts)
+            }
+            Err(err) => {
+                self.set_available(false, Some(&err));
+                None
+            }
+        }
+    }
+
+    pub async fn store_session(&self, username: &str, token: &str, session: &ClusterUserSession) {
+        let Ok(value) = serde_json::to_string(session) else { return; };
+        let result = async {
+            let mut con = self.get_connection().await?;
+            con.set_ex::<_, _, ()>(self.session_key(username, token), value, SESSION_TTL_SECS).await
+        }.await;
+        if let Err(err) = result {
+            self.set_available(false, Some(&err));
+        }
+    }
+
+    pub async fn load_session(&self, username: &str, token: &str) -> Option<ClusterUserSession> {
+        let result = async {
+            let mut con = self.get_connection().await?;
+            con.get::<_, Option<String>>(self.session_key(username, token)).await
+        }.await;
+        match result {
+            Ok(value) => value.and_then(|value| serde_json::from_str(&value).ok()),
+            Err(err) => {
+                self.set_available(false, Some(&err));
+                None
+            }
+        }
+    }
+
+    /// Adds the stored entry to the shared cache index and removes the evicted ones.
+    pub async fn publish_cache_changes(&self, added: Option<(&str, Option<String>, usize)>, removed: &[String]) {
+        let added = added.and_then(|(content_key, mime_type, size)| {
+            let entry = ClusterCacheEntry { mime_type, size, node_id: self.node_id.clone() };
+            serde_json::to_string(&entry).ok().map(|value| (content_key, value))
+        });
+        if added.is_none() && removed.is_empty() {
+            return;
+        }
+        let result = async {
+            let mut con = self.get_connection().await?;
+            let key = self.cache_index_key();
+            let mut pipe = redis::pipe();
+            // an entry which evicted itself is removed again
+            if let Some((content_key, value)) = added {
+                pipe.hset(&key, content_key, value).ignore();
+            }
+            if !removed.is_empty() {
+                pipe.hdel(&key, removed).ignore();
+            }
+            pipe.query_async::<()>(&mut con).await
+        }.await;
+        if let Err(err) = result {
+            self.set_available(false, Some(&err));
+        }
+    }
+
+    pub async fn load_cache_entry(&self, content_key: &str) -> Option<ClusterCacheEntry> {
+        let result = async {
+            let mut con = self.get_connection().await?;
+            con.hget::<_, _, Option<String>>(self.cache_index_key(), content_key).await
+        }.await;
+        match result {
+            Ok(value) => value.and_then(|value| serde_json::from_str(&value).ok()),
+            Err(err) => {
+                self.set_available(false, Some(&err));
+                None
+            }
+        }
+    }
+}
+
+fn sum_into<K: Eq + Hash, V: std::ops::AddAssign + Default + Copy>(target: &mut HashMap<K, V>, counts: HashMap<K, V>) {
+    for (key, count) in counts {
+        *target.entry(key).or_default() += count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sum_into;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_sum_remote_counts() {
+        let mut result: HashMap<String, u32> = HashMap::new();
+        sum_into(&mut result, HashMap::from([("alice".to_string(), 1), ("bob".to_string(), 2)]));
+        sum_into(&mut result, HashMap::from([("alice".to_string(), 3)]));
+        assert_eq!(result.get("alice"), Some(&4));
+        assert_eq!(result.get("bob"), Some(&2));
+    }
+}
@@ -1,5 +1,6 @@
-use crate::api::model::{ActiveProviderManager, ActiveUserManager, CustomVideoStreamType, EventManager, EventMessage, ProviderHandle, SharedStreamManager};
+use crate::api::model::{ActiveProviderManager, ActiveUserManager, CustomVideoStreamType, EventManager, EventMessage, ProviderHandle, SharedStreamManager, StreamSessionRecorder};
 use crate::auth::Fingerprint;
+use crate::model::AppConfig;
 use crate::utils::debug_if_enabled;
 use log::{warn};
 use shared::model::{ActiveUserConnectionChange, StreamChannel, VirtualId};
@@ -12,12 +13,14 @@ pub struct ConnectionManager {
     pub user_manager: Arc<ActiveUserManager>,
     pub provider_manager: Arc<ActiveProviderManager>,
     pub shared_stream_manager: Arc<SharedStreamManager>,
+    pub session_recorder: StreamSessionRecorder,
     event_manager: Arc<EventManager>,
     close_socket_signal_tx: tokio::sync::broadcast::Sender<SocketAddr>,
 }
 
 impl ConnectionManager {
     pub fn new(
+        app_config: &Arc<AppConfig>,
         user_manager: &Arc<ActiveUserManager>,
         provider_manager: &Arc<ActiveProviderManager>,
         shared_stream_manager: &Arc<SharedStreamManager>,
@@ -29,6 +32,7 @@ impl ConnectionManager {
             user_manager: Arc::clone(user_manager),
             provider_manager: Arc::clone(provider_manager),
             shared_stream_manager: Arc::clone(shared_stream_manager),
+            session_recorder: StreamSessionRecorder::new(app_config),
             event_manager: Arc::clone(event_manager),
             close_socket_signal_tx,
         }
@@ -55,6 +59,14 @@ impl ConnectionManager {
         self.user_manager.release_connection(addr).await;
         self.provider_manager.release_connection(addr).await;
         self.shared_stream_manager.release_connection(addr, true).await;
+        self.session_recorder.finish_session(addr).await;
+        self.event_manager.send_event(EventMessage::ActiveUser(ActiveUserConnectionChange::Disconnected(*addr)));
+    }
+
+    /// Releases the user connection only, for connections which hold their provider connection by a handle.
+    pub async fn release_user_connection(&self, addr: &SocketAddr) {
+        self.user_manager.release_connection(addr).await;
+        self.session_recorder.finish_session(addr).await;
         self.event_manager.send_event(EventMessage::ActiveUser(ActiveUserConnectionChange::Disconnected(*addr)));
     }
 
@@ -78,6 +90,7 @@ impl ConnectionManager {
     pub async fn update_connection(&self, username: &str, max_connections: u32, fingerprint: &Fingerprint,
                                    provider: &str, stream_channel: StreamChannel, user_agent: Cow<'_, str>, session_token: Option<&str>) {
         if let Some(stream_info) = self.user_manager.update_connection(username, max_connections, fingerprint, provider, stream_channel, user_agent, session_token).await {
+            self.session_recorder.start_session(&stream_info).await;
             self.event_manager.send_event(EventMessage::ActiveUser(ActiveUserConnectionChange::Updated(stream_info)));
         } else {
             warn!("Failed to register connection for user {username} at {}; disconnecting client", fingerprint.addr);
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use log::{trace};
-use shared::model::{ActiveUserConnectionChange, ConfigType, LibraryScanSummary, PlaylistUpdateState, SystemInfo};
+use shared::model::{ActiveUserConnectionChange, ConfigType, LibraryScanSummary, NotificationDto, NotificationKind, PlaylistUpdateState, SystemInfo};
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, PartialEq)]
@@ -13,6 +13,9 @@ pub enum EventMessage {
     PlaylistUpdateProgress(String, String),
     SystemInfoUpdate(SystemInfo),
     LibraryScanProgress(LibraryScanSummary),
+    Notify(NotificationKind, String), // stored by the notification collector
+    Notification(NotificationDto), // stored notification for the web ui
+    MappingReprocess(Vec<String>), // targets of changed mappings, the web ui confirms the processing
 }
 
 pub struct EventManager {
@@ -48,6 +51,12 @@ impl EventManager {
         }
     }
 
+    pub fn send_notification(&self, kind: NotificationKind, message: String) {
+        if !self.send_event(EventMessage::Notify(kind, message)) {
+            trace!("Failed to send notification");
+        }
+    }
+
     pub fn send_system_info(&self, system_info: SystemInfo) {
         if !self.send_event(EventMessage::SystemInfoUpdate(system_info)) {
             trace!("Failed to send system info");
@@ -0,0 +1,48 @@
+use dashmap::DashMap;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Requests denied by the geoip access policies per country.
+#[derive(Debug, Default)]
+pub struct GeoIpAccessManager {
+    denied: DashMap<Arc<str>, usize>,
+}
+
+impl GeoIpAccessManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_denied(&self, country: &str) {
+        if let Some(mut count) = self.denied.get_mut(country) {
+            *count += 1;
+            return;
+        }
+        *self.denied.entry(Arc::from(country)).or_default() += 1;
+    }
+
+    /// Denied requests per country since the start.
+    pub fn get_denied(&self) -> Option<BTreeMap<Arc<str>, usize>> {
+        if self.denied.is_empty() {
+            return None;
+        }
+        Some(self.denied.iter().map(|entry| (Arc::clone(entry.key()), *entry.value())).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeoIpAccessManager;
+
+    #[test]
+    fn test_geoip_access_denied() {
+        let manager = GeoIpAccessManager::new();
+        assert!(manager.get_denied().is_none());
+        manager.record_denied("CN");
+        manager.record_denied("CN");
+        manager.record_denied("unknown");
+        let denied = manager.get_denied().unwrap();
+        assert_eq!(denied.get("CN"), Some(&2));
+        assert_eq!(denied.get("unknown"), Some(&1));
+    }
+}
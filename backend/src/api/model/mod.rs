@@ -1,33 +1,80 @@
+#[cfg(feature = "server")]
 mod app_state;
+#[cfg(feature = "server")]
 mod request;
+#[cfg(feature = "server")]
 mod download;
 mod xtream;
+#[cfg(feature = "server")]
 mod model_utils;
 mod stream_error;
 mod streams;
+#[cfg(feature = "server")]
 mod active_user_manager;
+#[cfg(feature = "server")]
 mod active_provider_manager;
+#[cfg(feature = "server")]
 mod stream;
+#[cfg(feature = "server")]
 mod provider_config;
 mod event_manager;
 mod playlist_mem_cache;
+mod playlist_query_cache;
+#[cfg(feature = "server")]
 mod provider_lineup_manager;
+#[cfg(feature = "server")]
 mod connection_manager;
 mod update_guard;
+#[cfg(feature = "server")]
+mod provider_health;
+#[cfg(feature = "server")]
+mod geoip_access;
+#[cfg(feature = "server")]
+mod account_sharing;
+#[cfg(feature = "server")]
+mod stream_session_recorder;
+#[cfg(feature = "server")]
+mod cluster_state;
+#[cfg(feature = "server")]
+mod shutdown_coordinator;
 
+#[cfg(feature = "server")]
 pub(in crate::api) use self::active_provider_manager::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::active_user_manager::*;
+#[cfg(feature = "server")]
 pub use self::app_state::*;
+#[cfg(feature = "server")]
 pub use self::connection_manager::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::download::*;
 pub use self::event_manager::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::model_utils::*;
 pub use self::playlist_mem_cache::*;
+pub use self::playlist_query_cache::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::provider_config::*;
+#[cfg(feature = "server")]
 pub use self::provider_lineup_manager::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::request::*;
+#[cfg(feature = "server")]
 pub use self::stream::*;
 pub(in crate::api) use self::stream_error::*;
 pub(crate) use self::streams::*;
 pub(in crate::api) use self::xtream::*;
+pub(crate) use self::xtream::XtreamCategoryEntry;
 pub use self::update_guard::*;
+#[cfg(feature = "server")]
+pub use self::provider_health::*;
+#[cfg(feature = "server")]
+pub use self::geoip_access::*;
+#[cfg(feature = "server")]
+pub use self::account_sharing::*;
+#[cfg(feature = "server")]
+pub use self::stream_session_recorder::*;
+#[cfg(feature = "server")]
+pub use self::cluster_state::*;
+#[cfg(feature = "server")]
+pub use self::shutdown_coordinator::*;
@@ -3,6 +3,7 @@ use tokio::sync::RwLock;
 use shared::model::{M3uPlaylistItem, PlaylistItem, XtreamCluster, XtreamPlaylistItem};
 use crate::model::ConfigTarget;
 use crate::repository::{BPlusTree, VirtualIdRecord};
+use crate::api::model::PlaylistQueryCache;
 
 pub struct PlaylistXtreamStorage {
     pub id_mapping: BPlusTree<u32, VirtualIdRecord>,
@@ -27,6 +28,7 @@ pub type TargetPlaylistStorageMap = HashMap<String, TargetPlaylistStorage>;
 
 pub struct PlaylistStorageState {
     pub data: RwLock<TargetPlaylistStorageMap>,
+    pub query_cache: PlaylistQueryCache,
 }
 
 impl PlaylistStorageState {
@@ -34,6 +36,7 @@ impl PlaylistStorageState {
     pub(crate) fn new() -> Self {
         Self {
             data: RwLock::new(HashMap::new()),
+            query_cache: PlaylistQueryCache::new(),
         }
     }
 
@@ -0,0 +1,250 @@
+use crate::api::model::XtreamCategoryEntry;
+use crate::repository::{ChannelFailoverSource, PlaylistSearchIndex};
+use bytes::Bytes;
+use dashmap::DashMap;
+use shared::model::{M3uPlaylistItem, XtreamCluster, XtreamPlaylistItem};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Upper bound of cached category item lists over all targets.
+const CATEGORY_ITEMS_MAX_ENTRIES: usize = 512;
+/// Upper bound of cached responses in memory, the disk cache is limited by `XTREAM_API_CACHE_MAX_FILES`.
+const RESPONSES_MAX_ENTRIES: usize = 64;
+/// Time after the ttl in which an expired response is served while the playlist is locked by processing.
+pub const RESPONSE_STALE_SECS: u64 = 3600;
+
+type CategoryKey = (String, XtreamCluster);
+type CategoryItemsKey = (String, XtreamCluster, u32);
+
+/// Key of a cached stream list response. The responses contain the urls of the user and are filtered
+/// by the user bouquet, they are cached per user and dropped when the bouquet or the credentials change.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XtreamResponseKey {
+    pub target_name: String,
+    pub username: String,
+    pub action: String,
+    pub category_id: Option<u32>,
+}
+
+impl XtreamResponseKey {
+    pub fn new(target_name: &str, username: &str, action: &str, category_id: Option<u32>) -> Self {
+        Self { target_name: target_name.to_string(), username: username.to_string(), action: action.to_string(), category_id }
+    }
+}
+
+/// Shared read cache for hot Xtream queries (category listings and the streams of a category)
+/// the m3u playlists of the targets with `cache_warmup`, the search indexes and the channel failover sources.
+/// The cached data is user independent, user specific filtering and rewriting is applied per request.
+/// All entries of a target are dropped when the target is processed or removed.
+///
+/// Readers fetch the `generation` before loading data from disk and pass it to `put_*`,
+/// so data loaded before an invalidation is not cached afterward.
+#[derive(Default)]
+pub struct PlaylistQueryCache {
+    generation: AtomicU64,
+    categories: DashMap<CategoryKey, Arc<Vec<XtreamCategoryEntry>>>,
+    category_items: DashMap<CategoryItemsKey, Arc<Vec<XtreamPlaylistItem>>>,
+    responses: DashMap<XtreamResponseKey, (Bytes, u64)>,
+    m3u_items: DashMap<String, Arc<Vec<M3uPlaylistItem>>>,
+    search_indexes: DashMap<String, Arc<PlaylistSearchIndex>>,
+    channel_failovers: DashMap<String, Arc<HashMap<u32, Vec<ChannelFailoverSource>>>>,
+}
+
+impl PlaylistQueryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    pub fn get_categories(&self, target_name: &str, cluster: XtreamCluster) -> Option<Arc<Vec<XtreamCategoryEntry>>> {
+        self.categories.get(&(target_name.to_string(), cluster)).map(|entry| Arc::clone(entry.value()))
+    }
+
+    pub fn put_categories(&self, target_name: &str, cluster: XtreamCluster, generation: u64, categories: Vec<XtreamCategoryEntry>) -> Arc<Vec<XtreamCategoryEntry>> {
+        let categories = Arc::new(categories);
+        if self.generation() == generation {
+            self.categories.insert((target_name.to_string(), cluster), Arc::clone(&categories));
+        }
+        categories
+    }
+
+    pub fn get_category_items(&self, target_name: &str, cluster: XtreamCluster, category_id: u32) -> Option<Arc<Vec<XtreamPlaylistItem>>> {
+        self.category_items.get(&(target_name.to_string(), cluster, category_id)).map(|entry| Arc::clone(entry.value()))
+    }
+
+    pub fn put_category_items(&self, target_name: &str, cluster: XtreamCluster, category_id: u32, generation: u64, items: Vec<XtreamPlaylistItem>) -> Arc<Vec<XtreamPlaylistItem>> {
+        let items = Arc::new(items);
+        if self.generation() == generation && self.category_items.len() < CATEGORY_ITEMS_MAX_ENTRIES {
+            self.category_items.insert((target_name.to_string(), cluster, category_id), Arc::clone(&items));
+        }
+        items
+    }
+
+    pub fn get_m3u_items(&self, target_name: &str) -> Option<Arc<Vec<M3uPlaylistItem>>> {
+        self.m3u_items.get(target_name).map(|entry| Arc::clone(entry.value()))
+    }
+
+    pub fn put_m3u_items(&self, target_name: &str, generation: u64, items: Vec<M3uPlaylistItem>) -> Arc<Vec<M3uPlaylistItem>> {
+        let items = Arc::new(items);
+        if self.generation() == generation {
+            self.m3u_items.insert(target_name.to_string(), Arc::clone(&items));
+        }
+        items
+    }
+
+    pub fn get_search_index(&self, target_name: &str) -> Option<Arc<PlaylistSearchIndex>> {
+        self.search_indexes.get(target_name).map(|entry| Arc::clone(entry.value()))
+    }
+
+    pub fn put_search_index(&self, target_name: &str, generation: u64, index: PlaylistSearchIndex) -> Arc<PlaylistSearchIndex> {
+        let index = Arc::new(index);
+        if self.generation() == generation {
+            self.search_indexes.insert(target_name.to_string(), Arc::clone(&index));
+        }
+        index
+    }
+
+    pub fn get_channel_failovers(&self, target_name: &str) -> Option<Arc<HashMap<u32, Vec<ChannelFailoverSource>>>> {
+        self.channel_failovers.get(target_name).map(|entry| Arc::clone(entry.value()))
+    }
+
+    pub fn put_channel_failovers(&self, target_name: &str, generation: u64, failovers: HashMap<u32, Vec<ChannelFailoverSource>>) -> Arc<HashMap<u32, Vec<ChannelFailoverSource>>> {
+        let failovers = Arc::new(failovers);
+        if self.generation() == generation {
+            self.channel_failovers.insert(target_name.to_string(), Arc::clone(&failovers));
+        }
+        failovers
+    }
+
+    /// Returns the response if it was created within the last `ttl_secs`.
+    /// Expired responses are kept for `RESPONSE_STALE_SECS` as fallback, see `get_stale_response`.
+    pub fn get_response(&self, key: &XtreamResponseKey, ttl_secs: u64, now: u64) -> Option<Bytes> {
+        let entry = self.responses.get(key)?;
+        let (content, created) = entry.value();
+        let age = now.saturating_sub(*created);
+        if age < ttl_secs {
+            return Some(content.clone());
+        }
+        drop(entry);
+        if age >= ttl_secs.saturating_add(RESPONSE_STALE_SECS) {
+            self.responses.remove(key);
+        }
+        None
+    }
+
+    /// Returns the response even if it is expired, as long as it is not older than `ttl_secs + RESPONSE_STALE_SECS`.
+    pub fn get_stale_response(&self, key: &XtreamResponseKey, ttl_secs: u64, now: u64) -> Option<Bytes> {
+        let entry = self.responses.get(key)?;
+        let (content, created) = entry.value();
+        (now.saturating_sub(*created) < ttl_secs.saturating_add(RESPONSE_STALE_SECS)).then(|| content.clone())
+    }
+
+    pub fn put_response(&self, key: XtreamResponseKey, generation: u64, content: Bytes, created: u64, ttl_secs: u64) {
+        if self.generation() != generation {
+            return;
+        }
+        if self.responses.len() >= RESPONSES_MAX_ENTRIES {
+            self.responses.retain(|_, (_, entry_created)| created.saturating_sub(*entry_created) < ttl_secs);
+        }
+        if self.responses.len() < RESPONSES_MAX_ENTRIES {
+            self.responses.insert(key, (content, created));
+        }
+    }
+
+    pub fn invalidate(&self, target_name: &str) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.categories.retain(|(name, _), _| name != target_name);
+        self.category_items.retain(|(name, _, _), _| name != target_name);
+        self.responses.retain(|key, _| key.target_name != target_name);
+        self.m3u_items.remove(target_name);
+        self.search_indexes.remove(target_name);
+        self.channel_failovers.remove(target_name);
+    }
+
+    /// Drops the responses of the user in all targets.
+    pub fn invalidate_user(&self, username: &str) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.responses.retain(|key, _| key.username != username);
+    }
+
+    /// Drops the responses of all users, the server info is part of the stream urls.
+    pub fn invalidate_responses(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.responses.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlaylistQueryCache, XtreamResponseKey, RESPONSE_STALE_SECS};
+    use crate::repository::PlaylistSearchIndex;
+    use bytes::Bytes;
+    use shared::model::XtreamCluster;
+
+    #[test]
+    fn test_invalidate_only_affects_target() {
+        let cache = PlaylistQueryCache::new();
+        let generation = cache.generation();
+        cache.put_category_items("a", XtreamCluster::Live, 1, generation, vec![]);
+        cache.put_category_items("b", XtreamCluster::Live, 1, generation, vec![]);
+        cache.put_categories("a", XtreamCluster::Video, generation, vec![]);
+        cache.put_m3u_items("a", generation, vec![]);
+        cache.put_search_index("a", generation, PlaylistSearchIndex::default());
+
+        cache.invalidate("a");
+        assert!(cache.get_category_items("a", XtreamCluster::Live, 1).is_none());
+        assert!(cache.get_categories("a", XtreamCluster::Video).is_none());
+        assert!(cache.get_m3u_items("a").is_none());
+        assert!(cache.get_search_index("a").is_none());
+        assert!(cache.get_category_items("b", XtreamCluster::Live, 1).is_some());
+    }
+
+    #[test]
+    fn test_response_ttl() {
+        let cache = PlaylistQueryCache::new();
+        let key = XtreamResponseKey::new("a", "user", "get_vod_streams", None);
+        cache.put_response(key.clone(), cache.generation(), Bytes::from_static(b"[]"), 100, 60);
+        assert_eq!(cache.get_response(&key, 60, 159).as_deref(), Some(&b"[]"[..]));
+        assert!(cache.get_response(&key, 60, 160).is_none());
+        assert_eq!(cache.get_stale_response(&key, 60, 160).as_deref(), Some(&b"[]"[..]));
+        assert!(cache.get_response(&key, 60, 160 + RESPONSE_STALE_SECS).is_none());
+        assert!(cache.get_stale_response(&key, 60, 160 + RESPONSE_STALE_SECS).is_none());
+
+        cache.put_response(key.clone(), cache.generation(), Bytes::from_static(b"[]"), 100, 60);
+        cache.invalidate("a");
+        assert!(cache.get_response(&key, 60, 100).is_none());
+    }
+
+    #[test]
+    fn test_invalidate_user() {
+        let cache = PlaylistQueryCache::new();
+        let key_a = XtreamResponseKey::new("a", "user1", "get_vod_streams", None);
+        let key_b = XtreamResponseKey::new("b", "user1", "get_live_streams", Some(1));
+        let key_other = XtreamResponseKey::new("a", "user2", "get_vod_streams", None);
+        for key in [&key_a, &key_b, &key_other] {
+            cache.put_response(key.clone(), cache.generation(), Bytes::from_static(b"[]"), 100, 60);
+        }
+
+        let generation = cache.generation();
+        cache.invalidate_user("user1");
+        assert!(cache.get_response(&key_a, 60, 100).is_none());
+        assert!(cache.get_response(&key_b, 60, 100).is_none());
+        assert!(cache.get_response(&key_other, 60, 100).is_some());
+        // a response created before the invalidation is not cached
+        cache.put_response(key_a.clone(), generation, Bytes::from_static(b"[]"), 100, 60);
+        assert!(cache.get_response(&key_a, 60, 100).is_none());
+    }
+
+    #[test]
+    fn test_stale_generation_is_not_cached() {
+        let cache = PlaylistQueryCache::new();
+        let generation = cache.generation();
+        cache.invalidate("a");
+        cache.put_category_items("a", XtreamCluster::Live, 1, generation, vec![]);
+        assert!(cache.get_category_items("a", XtreamCluster::Live, 1).is_none());
+    }
+}
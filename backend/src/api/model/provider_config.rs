@@ -24,10 +24,19 @@ pub enum ProviderConfigAllocation {
 #[derive(Debug, Default, Copy, Clone)]
 pub struct ProviderConfigConnection {
     pub(crate) current_connections: usize,
+    /// Connections of the other cluster instances, they are counted against `max_connections`.
+    pub(crate) remote_connections: usize,
     pub(crate) granted_grace: bool,
     pub(crate) grace_ts: u64,
 }
 
+impl ProviderConfigConnection {
+    #[inline]
+    fn total_connections(&self) -> usize {
+        self.current_connections + self.remote_connections
+    }
+}
+
 /// This struct represents an individual provider configuration with fields like:
 ///
 /// `id`, `name`, `url`, `username`, `password`
@@ -183,7 +192,7 @@ impl ProviderConfig {
         if max == 0 {
             return false;
         }
-        self.connection.read().await.current_connections >= max
+        self.connection.read().await.total_connections() >= max
     }
 
     #[inline]
@@ -193,12 +202,13 @@ impl ProviderConfig {
             return false;
         }
         let mut guard = self.connection.write().await;
-        if guard.current_connections < self.max_connections {
+        let connections = guard.total_connections();
+        if connections < self.max_connections {
             guard.granted_grace = false;
             guard.grace_ts = 0;
         }
 
-        if guard.granted_grace && guard.current_connections > max {
+        if guard.granted_grace && connections > max {
             let now = get_current_timestamp();
             if now - guard.grace_ts <= grace_period_timeout_secs {
                 // Grace timeout still active, deny connection
@@ -206,7 +216,7 @@ impl ProviderConfig {
                 return true;
             }
         }
-        guard.current_connections > max
+        connections > max
     }
 
     //
@@ -234,7 +244,7 @@ impl ProviderConfig {
             modify_connections!(self, guard, +1);
             return ProviderConfigAllocation::Available;
         }
-        let connections = guard.current_connections;
+        let connections = guard.total_connections();
         if connections < self.max_connections || (grace && connections <= self.max_connections) {
             if connections < self.max_connections {
                 guard.granted_grace = false;
@@ -245,7 +255,7 @@ impl ProviderConfig {
 
             let now = get_current_timestamp();
             if guard.granted_grace  && now - guard.grace_ts <= grace_period_timeout_secs {
-                if guard.current_connections > self.max_connections && now - guard.grace_ts <= grace_period_timeout_secs {
+                if guard.total_connections() > self.max_connections && now - guard.grace_ts <= grace_period_timeout_secs {
                     // Grace timeout still active, deny connection
                     debug!("Provider access denied, grace exhausted, too many connections: {}", self.name);
                     return ProviderConfigAllocation::Exhausted;
@@ -279,7 +289,7 @@ impl ProviderConfig {
             return true;
         }
         let mut guard = self.connection.write().await;
-        let connections = guard.current_connections;
+        let connections = guard.total_connections();
         if connections < self.max_connections || (grace && connections <= self.max_connections) {
             if connections < self.max_connections {
                 guard.granted_grace = false;
@@ -304,7 +314,7 @@ impl ProviderConfig {
 
     pub async fn release(&self) {
         let mut guard = self.connection.write().await;
-        if guard.current_connections == 1 || guard.current_connections > self.max_connections {
+        if guard.current_connections == 1 || guard.total_connections() > self.max_connections {
             guard.granted_grace = false;
             guard.grace_ts = 0;
         }
@@ -0,0 +1,96 @@
+use crate::model::{InputAutoProxy, InputFailoverConfig};
+use dashmap::DashMap;
+use shared::utils::current_time_secs;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ProviderHealth {
+    failures: u16,
+    last_failure_secs: u64,
+}
+
+/// Health of the providers for the input failover.
+/// A provider is unhealthy after `max_failures` consecutive failures
+/// until `recovery_secs` have passed since its last failure.
+#[derive(Debug, Default)]
+pub struct ProviderHealthManager {
+    health: DashMap<Arc<str>, ProviderHealth>,
+    failovers: DashMap<Arc<str>, usize>,
+}
+
+impl ProviderHealthManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_failure(&self, provider_name: &Arc<str>) {
+        self.record_failure_at(provider_name, current_time_secs());
+    }
+
+    fn record_failure_at(&self, provider_name: &Arc<str>, now: u64) {
+        let mut health = self.health.entry(Arc::clone(provider_name)).or_default();
+        health.failures = health.failures.saturating_add(1);
+        health.last_failure_secs = now;
+    }
+
+    pub fn record_success(&self, provider_name: &Arc<str>) {
+        self.health.remove(provider_name);
+    }
+
+    pub fn is_healthy(&self, provider_name: &Arc<str>, failover: &InputFailoverConfig) -> bool {
+        self.is_healthy_at(provider_name, failover.max_failures, failover.recovery_secs, current_time_secs())
+    }
+
+    /// Health check with the limits of the `auto` proxy type.
+    pub fn is_healthy_for_redirect(&self, provider_name: &Arc<str>, auto_proxy: &InputAutoProxy) -> bool {
+        self.is_healthy_at(provider_name, auto_proxy.max_failures, auto_proxy.recovery_secs, current_time_secs())
+    }
+
+    fn is_healthy_at(&self, provider_name: &Arc<str>, max_failures: u16, recovery_secs: u64, now: u64) -> bool {
+        self.health.get(provider_name).is_none_or(|health| {
+            health.failures < max_failures
+                || now.saturating_sub(health.last_failure_secs) >= recovery_secs
+        })
+    }
+
+    /// Consecutive failures, providers with a lower score are preferred.
+    pub fn get_score(&self, provider_name: &Arc<str>) -> u16 {
+        self.health.get(provider_name).map_or(0, |health| health.failures)
+    }
+
+    pub fn record_failover(&self, input_name: &Arc<str>) {
+        *self.failovers.entry(Arc::clone(input_name)).or_default() += 1;
+    }
+
+    /// Provider switches per input since the start.
+    pub fn get_failovers(&self) -> Option<BTreeMap<Arc<str>, usize>> {
+        if self.failovers.is_empty() {
+            return None;
+        }
+        Some(self.failovers.iter().map(|entry| (Arc::clone(entry.key()), *entry.value())).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProviderHealthManager;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_provider_health() {
+        let manager = ProviderHealthManager::new();
+        let provider: Arc<str> = Arc::from("provider_1");
+
+        manager.record_failure_at(&provider, 100);
+        assert!(manager.is_healthy_at(&provider, 2, 60, 100));
+        manager.record_failure_at(&provider, 110);
+        assert!(!manager.is_healthy_at(&provider, 2, 60, 120));
+        assert!(manager.is_healthy_at(&provider, 2, 60, 170));
+        assert_eq!(manager.get_score(&provider), 2);
+
+        manager.record_success(&provider);
+        assert!(manager.is_healthy_at(&provider, 2, 60, 120));
+        assert_eq!(manager.get_score(&provider), 0);
+    }
+}
@@ -709,6 +709,36 @@ impl ProviderLineupManager {
         });
     }
 
+    /// Connections of this instance per provider, shared with the other cluster instances.
+    pub async fn local_connections(&self) -> HashMap<Arc<str>, usize> {
+        let snapshot: Vec<_> = self.provider_connections.iter()
+            .map(|e| (e.key().clone(), Arc::clone(e.value())))
+            .collect();
+        let mut counts = HashMap::new();
+        for (name, conn_lock) in snapshot {
+            let count = conn_lock.read().await.current_connections;
+            if count > 0 {
+                counts.insert(name, count);
+            }
+        }
+        counts
+    }
+
+    /// Sets the connections of the other cluster instances, providers without remote connections are reset to 0.
+    pub async fn set_remote_connections(&self, mut counts: HashMap<Arc<str>, usize>) {
+        // same as reconcile, the DashMap shard locks are not held while awaiting the provider locks
+        let snapshot: Vec<_> = self.provider_connections.iter()
+            .map(|e| (e.key().clone(), Arc::clone(e.value())))
+            .collect();
+        for (name, conn_lock) in snapshot {
+            conn_lock.write().await.remote_connections = counts.remove(&name).unwrap_or(0);
+        }
+        for (name, count) in counts {
+            let conn_lock = get_or_create_provider_connection(&self.provider_connections, &name);
+            conn_lock.write().await.remote_connections = count;
+        }
+    }
+
     gen_provider_search!(get_provider_config_by_name, name, &Arc<str>);
 
 
@@ -943,6 +973,9 @@ mod tests {
             priority,
             aliases: None,
             headers: HashMap::default(),
+            header_policy: None,
+            auth: None,
+            egress: None,
             options: None,
             method: InputFetchMethod::default(),
             staged: None,
@@ -950,6 +983,11 @@ mod tests {
             t_batch_url: None,
             panel_api: None,
             cache_duration_seconds: 0,
+            failover: None,
+            impersonate: None,
+            maintenance_windows: Vec::new(),
+            guardrails: None,
+            auto_proxy: None,
         }
     }
 
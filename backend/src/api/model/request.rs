@@ -28,6 +28,21 @@ pub struct UserApiRequest {
     pub duration: String,
     #[serde(default, alias = "type")]
     pub content_type: String,
+    /// Comma separated fields left out of the m3u playlist, like `logo,attributes`
+    #[serde(default)]
+    pub strip: String,
+    /// Preferred quality of a collapsed channel, like `720`, `720p`, `sd`, `hd`, `fhd` or `uhd`
+    #[serde(default)]
+    pub quality: String,
+}
+
+/// Query of the playlist export, `columns` is a comma separated list of the export columns.
+#[derive(Debug, serde::Deserialize, Default)]
+pub struct PlaylistExportRequest {
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub columns: Option<String>,
 }
 
 impl UserApiRequest {
@@ -38,4 +53,15 @@ impl UserApiRequest {
             self.limit.parse::<u32>().unwrap_or(0)
         }
     }
+
+    pub fn get_quality(&self) -> Option<u32> {
+        match self.quality.trim().to_lowercase().as_str() {
+            "" => None,
+            "sd" => Some(576),
+            "hd" => Some(720),
+            "fhd" => Some(1080),
+            "uhd" | "4k" => Some(2160),
+            quality => quality.trim_end_matches(['p', 'i']).parse().ok(),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio_util::sync::CancellationToken;
+
+/// Coordinates the graceful shutdown of the server.
+/// While draining, new streams are rejected and the active streams can finish within the grace period.
+/// The server stops accepting connections when the stop token is cancelled.
+pub struct ShutdownCoordinator {
+    draining: AtomicBool,
+    stop_token: CancellationToken,
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self {
+            draining: AtomicBool::new(false),
+            stop_token: CancellationToken::new(),
+        }
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    /// Returns `false` when the drain was already started.
+    pub fn begin_drain(&self) -> bool {
+        !self.draining.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn stop_token(&self) -> CancellationToken {
+        self.stop_token.clone()
+    }
+
+    pub fn stop(&self) {
+        self.draining.store(true, Ordering::Release);
+        self.stop_token.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShutdownCoordinator;
+
+    #[test]
+    fn test_shutdown_coordinator() {
+        let coordinator = ShutdownCoordinator::new();
+        let token = coordinator.stop_token();
+        assert!(!coordinator.is_draining());
+        assert!(coordinator.begin_drain());
+        assert!(!coordinator.begin_drain());
+        assert!(coordinator.is_draining());
+        assert!(!token.is_cancelled());
+        coordinator.stop();
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::model::AppConfig;
+use crate::repository::append_stream_session;
+use log::error;
+use shared::model::{StreamInfo, StreamSessionDto};
+use shared::utils::{current_time_secs, strip_port};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+struct OpenStreamSession {
+    info: StreamInfo,
+    start: u64,
+    bytes: Arc<AtomicU64>,
+}
+
+impl OpenStreamSession {
+    fn is_same_stream(&self, info: &StreamInfo) -> bool {
+        self.info.username == info.username
+            && self.info.channel.target_id == info.channel.target_id
+            && self.info.channel.virtual_id == info.channel.virtual_id
+    }
+
+    fn to_session(&self, end: u64) -> StreamSessionDto {
+        let info = &self.info;
+        StreamSessionDto {
+            username: info.username.clone(),
+            target_id: info.channel.target_id,
+            virtual_id: info.channel.virtual_id,
+            item_type: info.channel.item_type,
+            cluster: info.channel.cluster,
+            title: info.channel.title.to_string(),
+            group: info.channel.group.to_string(),
+            provider: info.provider.clone(),
+            client_ip: strip_port(&info.client_ip).to_string(),
+            country: info.country.clone(),
+            user_agent: info.user_agent.clone(),
+            start: self.start,
+            end,
+            bytes: self.bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Records the stream sessions of the clients into the session history when `log.session_history_days` is set.
+/// A session ends when the connection is released or another channel is streamed over the same connection.
+pub struct StreamSessionRecorder {
+    app_config: Arc<AppConfig>,
+    sessions: Mutex<HashMap<SocketAddr, OpenStreamSession>>,
+}
+
+impl StreamSessionRecorder {
+    pub fn new(app_config: &Arc<AppConfig>) -> Self {
+        Self {
+            app_config: Arc::clone(app_config),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.app_config.config.load().log.as_ref().is_some_and(|log| log.session_history_days > 0)
+    }
+
+    async fn store(&self, session: Option<OpenStreamSession>) {
+        if let Some(session) = session {
+            if let Err(err) = append_stream_session(&self.app_config, &session.to_session(current_time_secs())).await {
+                error!("{err}");
+            }
+        }
+    }
+
+    /// Starts the session of the stream, the session of another channel on the same connection is finished.
+    pub async fn start_session(&self, info: &StreamInfo) {
+        if !self.is_enabled() {
+            return;
+        }
+        let finished = {
+            let mut sessions = self.sessions.lock().await;
+            if sessions.get(&info.addr).is_some_and(|session| session.is_same_stream(info)) {
+                return;
+            }
+            sessions.insert(info.addr, OpenStreamSession {
+                info: info.clone(),
+                start: current_time_secs(),
+                bytes: Arc::new(AtomicU64::new(0)),
+            })
+        };
+        self.store(finished).await;
+    }
+
+    pub async fn finish_session(&self, addr: &SocketAddr) {
+        let finished = self.sessions.lock().await.remove(addr);
+        self.store(finished).await;
+    }
+
+    /// Finishes all open sessions, used on shutdown.
+    pub async fn finish_all(&self) {
+        let finished: Vec<OpenStreamSession> = self.sessions.lock().await.drain().map(|(_, session)| session).collect();
+        for session in finished {
+            self.store(Some(session)).await;
+        }
+    }
+
+    /// Counter of the bytes sent to the client within the current session of the connection.
+    pub async fn get_bytes_counter(&self, addr: &SocketAddr) -> Option<Arc<AtomicU64>> {
+        self.sessions.lock().await.get(addr).map(|session| Arc::clone(&session.bytes))
+    }
+}
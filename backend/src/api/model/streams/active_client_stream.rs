@@ -370,10 +370,20 @@ pub(crate) async fn create_active_client_stream(
         provider_stopped: false,
     };
 
-    if has_provisioning {
+    let client_stream = if has_provisioning {
         ProvisionableActiveClientStream { state }.boxed()
     } else {
         ActiveClientStream { state }.boxed()
+    };
+
+    // the bytes sent to the client are recorded in the session history
+    match app_state.connection_manager.session_recorder.get_bytes_counter(&fingerprint.addr).await {
+        Some(bytes) => client_stream.inspect(move |chunk| {
+            if let Ok(data) = chunk {
+                bytes.fetch_add(data.len() as u64, Ordering::Relaxed);
+            }
+        }).boxed(),
+        None => client_stream,
     }
 }
 
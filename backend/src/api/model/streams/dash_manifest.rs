@@ -0,0 +1,101 @@
+use crate::api::model::{HlsRemuxSegment, HlsRemuxSession};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+const TIMESCALE: f64 = 1000.0;
+/// Used until the first segments are available to estimate the bandwidth.
+const DEFAULT_BANDWIDTH: u64 = 4_000_000;
+
+fn format_date_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_timescale(secs: f64) -> u64 {
+    (secs * TIMESCALE).round().max(0.0) as u64
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+fn estimate_bandwidth(segments: &[Arc<HlsRemuxSegment>]) -> u64 {
+    let duration: f64 = segments.iter().map(|s| s.duration).sum();
+    if duration <= 0.0 {
+        return DEFAULT_BANDWIDTH;
+    }
+    let bytes: usize = segments.iter().map(|s| s.data.len()).sum();
+    ((bytes as f64 * 8.0) / duration) as u64
+}
+
+/// Renders a dynamic MPEG-DASH manifest for the live segments of a remux session.
+/// The MPEG-TS segments are announced as they are, using the `mp2t-simple` profile.
+/// `media` is the segment url template, `$Number$` is replaced by the client with the segment sequence.
+fn render_dash_manifest(
+    segments: &[Arc<HlsRemuxSegment>],
+    started_at: SystemTime,
+    published_at: SystemTime,
+    segment_secs: u16,
+    media: &str,
+) -> String {
+    let segment_secs = segment_secs.max(1);
+    let buffer_secs = segments.iter().map(|s| s.duration).sum::<f64>().ceil().max(f64::from(segment_secs));
+    let start_number = segments.first().map_or(0, |s| s.sequence);
+
+    let mut mpd = String::with_capacity(1024 + segments.len() * 48);
+    mpd.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(mpd, r#"<MPD xmlns="urn:mpeg:dash:schema:mpd:2011" profiles="urn:mpeg:dash:profile:mp2t-simple:2011" type="dynamic" availabilityStartTime="{}" publishTime="{}" minimumUpdatePeriod="PT{segment_secs}S" minBufferTime="PT{segment_secs}S" timeShiftBufferDepth="PT{buffer_secs}S" suggestedPresentationDelay="PT{}S">"#,
+                         format_date_time(started_at), format_date_time(published_at), u32::from(segment_secs) * 2);
+    mpd.push_str("  <Period id=\"0\" start=\"PT0S\">\n");
+    mpd.push_str("    <AdaptationSet mimeType=\"video/mp2t\" segmentAlignment=\"true\">\n");
+    let _ = writeln!(mpd, r#"      <Representation id="0" bandwidth="{}">"#, estimate_bandwidth(segments));
+    let _ = writeln!(mpd, r#"        <SegmentTemplate timescale="{TIMESCALE}" media="{}" startNumber="{start_number}">"#,
+                     quick_xml::escape::escape(media));
+    mpd.push_str("          <SegmentTimeline>\n");
+    for segment in segments {
+        let _ = writeln!(mpd, r#"            <S t="{}" d="{}"/>"#, to_timescale(segment.start), to_timescale(segment.duration));
+    }
+    mpd.push_str("          </SegmentTimeline>\n");
+    mpd.push_str("        </SegmentTemplate>\n");
+    mpd.push_str("      </Representation>\n");
+    mpd.push_str("    </AdaptationSet>\n");
+    mpd.push_str("  </Period>\n");
+    mpd.push_str("</MPD>\n");
+    mpd
+}
+
+impl HlsRemuxSession {
+    pub async fn render_dash_manifest(&self, media: &str) -> String {
+        let segments = self.live_segments().await;
+        render_dash_manifest(&segments, self.started_at(), SystemTime::now(), self.config().segment_secs, media)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_dash_manifest;
+    use crate::api::model::HlsRemuxSegment;
+    use bytes::Bytes;
+    use std::sync::Arc;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_render_dash_manifest() {
+        let segments: Vec<Arc<HlsRemuxSegment>> = (0..3u32).map(|i| Arc::new(HlsRemuxSegment {
+            sequence: 5 + u64::from(i),
+            start: 2.0 * f64::from(i) + 10.0,
+            duration: 2.0,
+            discontinuity: false,
+            data: Bytes::from(vec![0u8; 250_000]),
+        })).collect();
+        let started_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mpd = render_dash_manifest(&segments, started_at, started_at + Duration::from_secs(16), 2,
+                                       "http://localhost/remux/user/pass/1/$Number$.ts?a=1&b=2");
+        assert!(mpd.contains(r#"availabilityStartTime="2023-11-14T22:13:20Z""#));
+        assert!(mpd.contains(r#"startNumber="5""#));
+        assert!(mpd.contains(r#"<S t="10000" d="2000"/>"#));
+        assert!(mpd.contains(r#"<S t="14000" d="2000"/>"#));
+        assert!(mpd.contains(r#"bandwidth="1000000""#));
+        assert!(mpd.contains("$Number$.ts?a=1&amp;b=2"));
+        assert!(mpd.contains(r#"timeShiftBufferDepth="PT6S""#));
+    }
+}
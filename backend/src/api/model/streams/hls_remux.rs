@@ -0,0 +1,513 @@
+use crate::api::model::{ActiveProviderManager, ConnectionManager, ProviderHandle};
+use crate::model::{AppConfig, HlsRemuxConfig, InputSource};
+use crate::processing::parser::hls::parse_hls_playlist;
+use crate::utils::debug_if_enabled;
+use crate::utils::request::{download_text_content, get_remote_content_as_stream};
+use bytes::{Bytes, BytesMut};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use log::{debug, warn};
+use shared::utils::{is_hls_url, sanitize_sensitive_info};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::io::{Error, ErrorKind};
+use std::net::{Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::io::AsyncReadExt;
+use tokio::sync::{Notify, RwLock};
+use tokio::time::{sleep, timeout, Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+const TS_PACKET_SIZE: usize = 188;
+const TS_SYNC_BYTE: u8 = 0x47;
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+/// Segments kept after they dropped out of the playlist, for clients which are slightly behind.
+const EXTRA_BUFFER_SEGMENTS: usize = 2;
+/// Segments taken from an upstream HLS playlist when the session starts.
+const HLS_START_SEGMENTS: usize = 3;
+const MAX_RECONNECT_BACKOFF_SECS: u64 = 8;
+/// Prefix of the synthetic client ids which hold the provider connection of a session.
+const SESSION_ADDR_PREFIX: u128 = 0xfd00_0000_0000_0000_0000_0000_0000_0000;
+
+pub struct HlsRemuxSegment {
+    pub sequence: u64,
+    /// Seconds since the session start
+    pub start: f64,
+    pub duration: f64,
+    pub discontinuity: bool,
+    pub data: Bytes,
+}
+
+#[derive(Default)]
+struct SegmentBuffer {
+    segments: VecDeque<Arc<HlsRemuxSegment>>,
+    next_sequence: u64,
+    next_start: f64,
+    discontinuity: bool,
+}
+
+/// A live channel re-served as locally generated HLS playlist.
+/// The upstream is ingested by a background task, clients only read from the segment buffer.
+/// Short upstream drops are bridged by the buffered segments while the task reconnects.
+pub struct HlsRemuxSession {
+    config: HlsRemuxConfig,
+    buffer: RwLock<SegmentBuffer>,
+    segment_added: Notify,
+    created: Instant,
+    started_at: SystemTime,
+    last_access_millis: AtomicU64,
+    last_segment_millis: AtomicU64,
+    cancel: CancellationToken,
+}
+
+impl HlsRemuxSession {
+    fn new(config: HlsRemuxConfig) -> Self {
+        Self {
+            config,
+            buffer: RwLock::new(SegmentBuffer::default()),
+            segment_added: Notify::new(),
+            created: Instant::now(),
+            started_at: SystemTime::now(),
+            last_access_millis: AtomicU64::new(0),
+            last_segment_millis: AtomicU64::new(0),
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    fn elapsed_millis(&self) -> u64 {
+        u64::try_from(self.created.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+
+    pub fn config(&self) -> &HlsRemuxConfig {
+        &self.config
+    }
+
+    /// Wall clock time of the session start, segment start times are relative to it.
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.cancel.is_cancelled()
+    }
+
+    pub fn touch(&self) {
+        self.last_access_millis.store(self.elapsed_millis(), Ordering::Relaxed);
+    }
+
+    fn is_idle(&self) -> bool {
+        let idle_millis = self.elapsed_millis().saturating_sub(self.last_access_millis.load(Ordering::Relaxed));
+        Duration::from_millis(idle_millis) > self.config.idle_timeout()
+    }
+
+    fn is_upstream_lost(&self) -> bool {
+        let lost_millis = self.elapsed_millis().saturating_sub(self.last_segment_millis.load(Ordering::Relaxed));
+        Duration::from_millis(lost_millis) > self.config.idle_timeout()
+    }
+
+    async fn next_sequence(&self) -> u64 {
+        self.buffer.read().await.next_sequence
+    }
+
+    async fn push_segment(&self, data: Bytes, duration: f64) {
+        if data.is_empty() {
+            return;
+        }
+        let max_segments = usize::from(self.config.playlist_segments) + EXTRA_BUFFER_SEGMENTS;
+        {
+            let mut buffer = self.buffer.write().await;
+            let segment = HlsRemuxSegment {
+                sequence: buffer.next_sequence,
+                start: buffer.next_start,
+                duration,
+                discontinuity: std::mem::take(&mut buffer.discontinuity),
+                data,
+            };
+            buffer.next_sequence += 1;
+            buffer.next_start += duration;
+            buffer.segments.push_back(Arc::new(segment));
+            while buffer.segments.len() > max_segments {
+                buffer.segments.pop_front();
+            }
+        }
+        self.last_segment_millis.store(self.elapsed_millis(), Ordering::Relaxed);
+        self.segment_added.notify_waiters();
+    }
+
+    async fn mark_discontinuity(&self) {
+        let mut buffer = self.buffer.write().await;
+        if !buffer.segments.is_empty() {
+            buffer.discontinuity = true;
+        }
+    }
+
+    pub async fn get_segment(&self, sequence: u64) -> Option<Arc<HlsRemuxSegment>> {
+        let buffer = self.buffer.read().await;
+        buffer.segments.iter().find(|s| s.sequence == sequence).cloned()
+    }
+
+    /// Waits until the first segment is available, returns false if the upstream delivered nothing in time.
+    pub async fn wait_for_segments(&self, wait: Duration) -> bool {
+        let deadline = Instant::now() + wait;
+        loop {
+            let notified = self.segment_added.notified();
+            if !self.buffer.read().await.segments.is_empty() {
+                return true;
+            }
+            if !self.is_active() {
+                return false;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || timeout(remaining, notified).await.is_err() {
+                return false;
+            }
+        }
+    }
+
+    /// Returns the segments which are announced to the clients.
+    pub async fn live_segments(&self) -> Vec<Arc<HlsRemuxSegment>> {
+        let buffer = self.buffer.read().await;
+        let count = usize::from(self.config.playlist_segments);
+        let skip = buffer.segments.len().saturating_sub(count);
+        buffer.segments.iter().skip(skip).cloned().collect()
+    }
+
+    pub async fn render_playlist<F>(&self, segment_url: F) -> String
+    where
+        F: Fn(u64) -> String,
+    {
+        let segments = self.live_segments().await;
+        let max_duration = segments.iter().map(|s| s.duration).fold(f64::from(self.config.segment_secs), f64::max);
+        let first_sequence = segments.first().map_or(0, |s| s.sequence);
+
+        let mut playlist = String::with_capacity(128 + segments.len() * 96);
+        playlist.push_str("#EXTM3U\n#EXT-X-VERSION:3\n");
+        let _ = writeln!(playlist, "#EXT-X-TARGETDURATION:{}", max_duration.ceil());
+        let _ = writeln!(playlist, "#EXT-X-MEDIA-SEQUENCE:{first_sequence}");
+        for segment in &segments {
+            if segment.discontinuity {
+                playlist.push_str("#EXT-X-DISCONTINUITY\n");
+            }
+            let _ = writeln!(playlist, "#EXTINF:{:.3},", segment.duration);
+            playlist.push_str(&segment_url(segment.sequence));
+            playlist.push('\n');
+        }
+        playlist
+    }
+}
+
+/// Cuts an MPEG-TS byte stream into segments of roughly the configured duration.
+/// Segments are cut in front of a PAT packet, so each segment can be decoded on its own.
+struct TsSegmenter {
+    target: Duration,
+    pending: BytesMut,
+    current: BytesMut,
+    started: Option<Instant>,
+}
+
+impl TsSegmenter {
+    fn new(target: Duration) -> Self {
+        Self {
+            target,
+            pending: BytesMut::new(),
+            current: BytesMut::new(),
+            started: None,
+        }
+    }
+
+    fn is_pat_start(packet: &[u8]) -> bool {
+        let pid = (u16::from(packet[1] & 0x1F) << 8) | u16::from(packet[2]);
+        pid == 0 && packet[1] & 0x40 != 0
+    }
+
+    fn push(&mut self, data: &[u8], now: Instant) -> Vec<(Bytes, f64)> {
+        let mut segments = Vec::new();
+        self.pending.extend_from_slice(data);
+        loop {
+            // resync to the next sync byte
+            match self.pending.iter().position(|b| *b == TS_SYNC_BYTE) {
+                Some(0) => {}
+                Some(pos) => {
+                    let _ = self.pending.split_to(pos);
+                }
+                None => {
+                    self.pending.clear();
+                    break;
+                }
+            }
+            if self.pending.len() < TS_PACKET_SIZE {
+                break;
+            }
+            let packet = self.pending.split_to(TS_PACKET_SIZE);
+            if let Some(started) = self.started {
+                let elapsed = now.saturating_duration_since(started);
+                if (elapsed >= self.target && Self::is_pat_start(&packet)) || elapsed >= self.target * 2 {
+                    segments.push((self.current.split().freeze(), elapsed.as_secs_f64()));
+                    self.started = Some(now);
+                }
+            } else {
+                self.started = Some(now);
+            }
+            self.current.extend_from_slice(&packet);
+        }
+        segments
+    }
+}
+
+pub struct HlsRemuxIngest {
+    pub app_config: Arc<AppConfig>,
+    pub client: reqwest::Client,
+    pub input_source: InputSource,
+    pub provider_manager: Arc<ActiveProviderManager>,
+    pub provider_handle: ProviderHandle,
+    /// The user connection of the session is registered with the session address.
+    pub connection_manager: Arc<ConnectionManager>,
+    pub session_addr: SocketAddr,
+}
+
+impl HlsRemuxIngest {
+    async fn release(&self) {
+        self.provider_manager.release_handle(&self.provider_handle).await;
+        self.connection_manager.release_user_connection(&self.session_addr).await;
+    }
+}
+
+/// Resolves when the connection of the session is kicked.
+async fn wait_for_kick(mut close_rx: tokio::sync::broadcast::Receiver<SocketAddr>, session_addr: SocketAddr) {
+    loop {
+        match close_rx.recv().await {
+            Ok(addr) if addr == session_addr => return,
+            Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => std::future::pending::<()>().await,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct HlsRemuxManager {
+    sessions: Arc<DashMap<String, Arc<HlsRemuxSession>>>,
+    next_session_id: AtomicU64,
+}
+
+impl HlsRemuxManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn session_key(username: &str, virtual_id: u32) -> String {
+        format!("{username}/{virtual_id}")
+    }
+
+    /// The provider connection of a session is not bound to the connection of the requesting client,
+    /// because HLS clients reconnect for playlist and segment requests.
+    pub fn create_session_addr(&self) -> SocketAddr {
+        let id = self.next_session_id.fetch_add(1, Ordering::Relaxed);
+        SocketAddr::from((Ipv6Addr::from(SESSION_ADDR_PREFIX | u128::from(id)), 0))
+    }
+
+    pub fn get_session(&self, key: &str) -> Option<Arc<HlsRemuxSession>> {
+        self.sessions.get(key).map(|s| Arc::clone(s.value())).filter(|s| s.is_active())
+    }
+
+    /// Starts the ingest for the given session key. If another request started a session in the meantime,
+    /// the given provider connection is released and the running session is returned.
+    pub async fn start_session(&self, key: &str, config: HlsRemuxConfig, ingest: HlsRemuxIngest) -> Arc<HlsRemuxSession> {
+        let (session, started) = match self.sessions.entry(key.to_string()) {
+            Entry::Occupied(entry) if entry.get().is_active() => (Arc::clone(entry.get()), false),
+            Entry::Occupied(mut entry) => {
+                let session = Arc::new(HlsRemuxSession::new(config));
+                entry.insert(Arc::clone(&session));
+                (session, true)
+            }
+            Entry::Vacant(entry) => {
+                let session = Arc::new(HlsRemuxSession::new(config));
+                entry.insert(Arc::clone(&session));
+                (session, true)
+            }
+        };
+
+        if !started {
+            ingest.release().await;
+            return session;
+        }
+
+        session.touch();
+        debug_if_enabled!("HLS remux session {} started", sanitize_sensitive_info(key));
+        let sessions = Arc::clone(&self.sessions);
+        let task_key = key.to_string();
+        let task_session = Arc::clone(&session);
+        let close_rx = ingest.connection_manager.get_close_connection_channel();
+        tokio::spawn(async move {
+            tokio::select! {
+                () = run_ingest(&task_session, &ingest) => {}
+                () = wait_for_kick(close_rx, ingest.session_addr) => {
+                    debug_if_enabled!("HLS remux session {} kicked", sanitize_sensitive_info(&task_key));
+                }
+            }
+            task_session.cancel.cancel();
+            sessions.remove_if(&task_key, |_, s| Arc::ptr_eq(s, &task_session));
+            ingest.release().await;
+            debug_if_enabled!("HLS remux session {} stopped", sanitize_sensitive_info(&task_key));
+        });
+        session
+    }
+}
+
+async fn run_ingest(session: &HlsRemuxSession, ingest: &HlsRemuxIngest) {
+    let mut backoff = Duration::from_secs(1);
+    loop {
+        let sequence_before = session.next_sequence().await;
+        let result = if is_hls_url(&ingest.input_source.url) {
+            ingest_hls(session, ingest).await
+        } else {
+            ingest_ts(session, ingest).await
+        };
+        if let Err(err) = result {
+            debug!("HLS remux upstream failed: {}", sanitize_sensitive_info(&err.to_string()));
+        }
+        if !session.is_active() || session.is_idle() {
+            break;
+        }
+        if session.is_upstream_lost() {
+            warn!("HLS remux upstream unavailable, stopping session for {}", sanitize_sensitive_info(&ingest.input_source.url));
+            break;
+        }
+        if session.next_sequence().await != sequence_before {
+            backoff = Duration::from_secs(1);
+        }
+        session.mark_discontinuity().await;
+        tokio::select! {
+            () = session.cancel.cancelled() => break,
+            () = sleep(backoff) => {}
+        }
+        backoff = (backoff * 2).min(Duration::from_secs(MAX_RECONNECT_BACKOFF_SECS));
+    }
+}
+
+async fn ingest_ts(session: &HlsRemuxSession, ingest: &HlsRemuxIngest) -> Result<(), Error> {
+    let url = Url::parse(&ingest.input_source.url).map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+    let (mut reader, _) = get_remote_content_as_stream(&ingest.app_config, &ingest.client, &ingest.input_source, None, &url).await?;
+    let stall_timeout = session.config.segment_duration() * 2;
+    let mut segmenter = TsSegmenter::new(session.config.segment_duration());
+    let mut buf = BytesMut::with_capacity(READ_CHUNK_SIZE);
+    loop {
+        buf.reserve(READ_CHUNK_SIZE);
+        let read = tokio::select! {
+            () = session.cancel.cancelled() => return Ok(()),
+            read = timeout(stall_timeout, reader.read_buf(&mut buf)) => read,
+        };
+        let Ok(read) = read else {
+            return Err(Error::new(ErrorKind::TimedOut, "upstream stalled"));
+        };
+        if read? == 0 {
+            return Ok(());
+        }
+        for (data, duration) in segmenter.push(&buf.split(), Instant::now()) {
+            session.push_segment(data, duration).await;
+        }
+        if session.is_idle() {
+            return Ok(());
+        }
+    }
+}
+
+async fn download_segment(ingest: &HlsRemuxIngest, segment_url: &str) -> Result<Bytes, Error> {
+    let url = Url::parse(segment_url).map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+    let input_source = ingest.input_source.clone().with_url(segment_url.to_string());
+    let (mut reader, _) = get_remote_content_as_stream(&ingest.app_config, &ingest.client, &input_source, None, &url).await?;
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).await?;
+    Ok(Bytes::from(data))
+}
+
+async fn ingest_hls(session: &HlsRemuxSession, ingest: &HlsRemuxIngest) -> Result<(), Error> {
+    let mut playlist_url = ingest.input_source.url.clone();
+    let mut last_sequence: Option<u64> = None;
+    loop {
+        if !session.is_active() || session.is_idle() {
+            return Ok(());
+        }
+        let input_source = ingest.input_source.clone().with_url(playlist_url.clone());
+        let (content, response_url) = download_text_content(&ingest.app_config, &ingest.client, &input_source, None, None, true).await?;
+        let playlist = parse_hls_playlist(&content, &response_url);
+        if let Some(variant) = playlist.variant {
+            playlist_url = variant;
+            continue;
+        }
+        if playlist.unsupported {
+            return Err(Error::new(ErrorKind::Unsupported, "encrypted or fMP4 HLS streams can't be re-muxed"));
+        }
+
+        let segment_count = playlist.segments.len() as u64;
+        // the upstream restarted its media sequence
+        if last_sequence.is_some_and(|last| playlist.media_sequence + segment_count + 1 < last) {
+            last_sequence = None;
+            session.mark_discontinuity().await;
+        }
+        let skip = if last_sequence.is_none() { playlist.segments.len().saturating_sub(HLS_START_SEGMENTS) } else { 0 };
+        for (index, segment) in playlist.segments.iter().enumerate().skip(skip) {
+            let sequence = playlist.media_sequence + index as u64;
+            if last_sequence.is_some_and(|last| sequence <= last) {
+                continue;
+            }
+            let data = download_segment(ingest, &segment.url).await?;
+            session.push_segment(data, segment.duration).await;
+            last_sequence = Some(sequence);
+        }
+        if playlist.end_list {
+            return Ok(());
+        }
+
+        let poll_interval = Duration::from_millis(playlist.target_duration.max(2) * 500);
+        tokio::select! {
+            () = session.cancel.cancelled() => return Ok(()),
+            () = sleep(poll_interval) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TsSegmenter, TS_PACKET_SIZE, TS_SYNC_BYTE};
+    use tokio::time::{Duration, Instant};
+
+    fn packet(pid: u16, payload_start: bool) -> Vec<u8> {
+        let mut packet = vec![0xFF; TS_PACKET_SIZE];
+        packet[0] = TS_SYNC_BYTE;
+        packet[1] = (if payload_start { 0x40 } else { 0 }) | ((pid >> 8) as u8 & 0x1F);
+        packet[2] = (pid & 0xFF) as u8;
+        packet
+    }
+
+    #[test]
+    fn test_segments_are_cut_before_pat() {
+        let start = Instant::now();
+        let mut segmenter = TsSegmenter::new(Duration::from_secs(4));
+        assert!(segmenter.push(&packet(0, true), start).is_empty());
+        assert!(segmenter.push(&packet(256, false), start + Duration::from_secs(1)).is_empty());
+        // target reached, but no PAT
+        assert!(segmenter.push(&packet(256, false), start + Duration::from_secs(5)).is_empty());
+        let segments = segmenter.push(&packet(0, true), start + Duration::from_secs(5));
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].0.len(), 3 * TS_PACKET_SIZE);
+        assert!((segments[0].1 - 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_resync_and_partial_packets() {
+        let start = Instant::now();
+        let mut segmenter = TsSegmenter::new(Duration::from_secs(2));
+        let mut data = vec![0x00, 0x01];
+        data.extend(packet(0, true));
+        let (first, second) = data.split_at(100);
+        assert!(segmenter.push(first, start).is_empty());
+        assert!(segmenter.push(second, start).is_empty());
+        // forced cut without PAT after twice the target duration
+        let segments = segmenter.push(&packet(256, false), start + Duration::from_secs(4));
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].0.len(), TS_PACKET_SIZE);
+    }
+}
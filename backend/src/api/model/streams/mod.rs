@@ -1,24 +1,50 @@
+#[cfg(feature = "server")]
 mod timed_client_stream;
+#[cfg(feature = "server")]
 mod buffered_stream;
+#[cfg(feature = "server")]
 mod client_stream;
+#[cfg(feature = "server")]
 mod custom_video_stream;
+#[cfg(feature = "server")]
 mod provisioning_stream;
 mod transport_stream_buffer;
 // mod chunked_buffer;
+#[cfg(feature = "server")]
 mod provider_stream;
+#[cfg(feature = "server")]
 mod provider_stream_factory;
+#[cfg(feature = "server")]
 mod shared_stream_manager;
+#[cfg(feature = "server")]
 mod active_client_stream;
+#[cfg(feature = "server")]
 mod throttled_stream;
+#[cfg(feature = "server")]
+mod hls_remux;
+#[cfg(feature = "server")]
+mod dash_manifest;
+#[cfg(feature = "server")]
+mod udp_stream;
 pub mod persist_pipe_stream;
 
 pub(in crate) use self::transport_stream_buffer::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::provider_stream::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::provider_stream_factory::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::shared_stream_manager::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::active_client_stream::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::throttled_stream::*;
+#[cfg(feature = "server")]
+pub(in crate::api) use self::hls_remux::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::timed_client_stream::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::custom_video_stream::*;
+#[cfg(feature = "server")]
 pub(in crate::api) use self::provisioning_stream::*;
 pub use self::persist_pipe_stream::*;
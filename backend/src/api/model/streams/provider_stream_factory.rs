@@ -6,13 +6,13 @@ use crate::api::model::{BoxedProviderStream, ProviderStreamFactoryResponse};
 use crate::model::{ReverseProxyDisabledHeaderConfig};
 use crate::tools::atomic_once_flag::AtomicOnceFlag;
 use crate::utils::debug_if_enabled;
-use crate::utils::request::{classify_content_type, get_request_headers, MimeCategory};
+use crate::utils::request::{apply_header_rules, classify_content_type, get_request_headers, MimeCategory};
 use futures::stream::{self};
 use futures::{StreamExt, TryStreamExt};
 use log::{debug, log_enabled, warn};
 use reqwest::header::{HeaderMap, RANGE};
 use reqwest::StatusCode;
-use shared::model::{PlaylistItemType, DEFAULT_USER_AGENT};
+use shared::model::{HeaderRulesDto, PlaylistItemType, DEFAULT_USER_AGENT};
 use shared::utils::{filter_request_header, sanitize_sensitive_info};
 use std::collections::HashMap;
 use std::net::SocketAddr;
@@ -22,6 +22,7 @@ use std::time::{Duration, Instant};
 use url::Url;
 use crate::api::model::streams::buffered_stream::BufferedStream;
 use crate::api::model::streams::client_stream::ClientStream;
+use crate::api::model::streams::udp_stream::{create_udp_stream, is_udp_stream_url};
 
 const RETRY_SECONDS: u64 = 5;
 const ERR_MAX_RETRY_COUNT: u32 = 5;
@@ -40,6 +41,7 @@ pub struct ProviderStreamFactoryOptions {
     headers: HeaderMap,
     default_user_agent: Option<axum::http::header::HeaderValue>,
     range_bytes: Arc<Option<AtomicUsize>>,
+    range_end: Option<usize>,
     range_requested: bool,
     reconnect_flag: Arc<AtomicOnceFlag>,
 }
@@ -54,6 +56,7 @@ impl ProviderStreamFactoryOptions {
         stream_url: &Url,
         req_headers: &HeaderMap,
         input_headers: Option<&HashMap<String, String>>,
+        header_rules: Option<&HeaderRulesDto>,
         disabled_headers: Option<&ReverseProxyDisabledHeaderConfig>,
         default_user_agent: Option<&str>,
     ) -> Self {
@@ -64,16 +67,20 @@ impl ProviderStreamFactoryOptions {
         };
         let filter_header = get_header_filter_for_item_type(item_type);
         let mut req_headers = get_headers_from_request(req_headers, &filter_header);
-        let requested_range = get_request_range_start_bytes(&req_headers);
+        let requested_range = get_request_range_bytes(&req_headers);
         req_headers.remove("range");
+        let is_live = matches!(item_type, PlaylistItemType::Live | PlaylistItemType::LiveUnknown);
+        let range_end = if is_live { None } else { requested_range.and_then(|(_, end)| end) };
+        let requested_range = requested_range.map(|(start, _)| start);
 
         // We merge configured input headers with the headers from the request.
-        let headers = get_request_headers(
+        let mut headers = get_request_headers(
             input_headers,
             Some(&req_headers),
             disabled_headers,
             default_user_agent,
         );
+        apply_header_rules(&mut headers, header_rules);
 
         let default_user_agent = default_user_agent
             .and_then(|ua| {
@@ -83,7 +90,7 @@ impl ProviderStreamFactoryOptions {
             .and_then(|ua| axum::http::header::HeaderValue::from_str(ua).ok());
 
         let url = stream_url.clone();
-        let range_bytes = if matches!(item_type, PlaylistItemType::Live | PlaylistItemType::LiveUnknown) {
+        let range_bytes = if is_live {
             Arc::new(requested_range.map(AtomicUsize::new))
         } else {
             Arc::new(Some(AtomicUsize::new(requested_range.unwrap_or(0))))
@@ -102,6 +109,7 @@ impl ProviderStreamFactoryOptions {
             headers,
             default_user_agent,
             range_bytes,
+            range_end,
             range_requested: requested_range.is_some(),
         }
     }
@@ -183,23 +191,86 @@ impl ProviderStreamFactoryOptions {
         self.range_requested
     }
 
+    #[inline]
+    pub fn get_range_end(&self) -> Option<usize> {
+        self.range_end
+    }
+
 }
 
-fn get_request_range_start_bytes(req_headers: &HashMap<String, Vec<u8>>) -> Option<usize> {
+fn get_request_range_bytes(req_headers: &HashMap<String, Vec<u8>>) -> Option<(usize, Option<usize>)> {
     // range header looks like  bytes=1234-5566/2345345 or bytes=0-
-    if let Some(req_range) = req_headers.get(axum::http::header::RANGE.as_str()) {
-        if let Some(bytes_range) = req_range.strip_prefix(b"bytes=") {
-            if let Some(index) = bytes_range.iter().position(|&x| x == b'-') {
-                let start_bytes = &bytes_range[..index];
-                if let Ok(start_str) = std::str::from_utf8(start_bytes) {
-                    if let Ok(bytes_requested) = start_str.parse::<usize>() {
-                        return Some(bytes_requested);
-                    }
-                }
-            }
-        }
+    let req_range = req_headers.get(axum::http::header::RANGE.as_str())?;
+    let bytes_range = req_range.strip_prefix(b"bytes=")?;
+    let range_str = std::str::from_utf8(bytes_range).ok()?;
+    // multiple ranges are not supported, only the first one is used
+    let range_str = range_str.split(',').next()?.trim();
+    let (start_str, end_str) = range_str.split_once('-')?;
+    let start = start_str.trim().parse::<usize>().ok()?;
+    let end = end_str
+        .split('/')
+        .next()
+        .and_then(|end| end.trim().parse::<usize>().ok())
+        .filter(|end| *end >= start);
+    Some((start, end))
+}
+
+fn get_range_header_value(range_start: usize, range_end: Option<usize>) -> String {
+    match range_end {
+        Some(end) if end >= range_start => format!("bytes={range_start}-{end}"),
+        _ => format!("bytes={range_start}-"),
+    }
+}
+
+/// When the provider ignores the range request and answers with the full content,
+/// the requested range is cut out of the stream and the response is turned into a partial one.
+fn slice_full_content_stream(
+    provider_stream: BoxedProviderStream,
+    response_headers: &mut Vec<(String, String)>,
+    status: &mut StatusCode,
+    range_start: usize,
+    range_end: Option<usize>,
+) -> BoxedProviderStream {
+    let Some(total) = response_headers.iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok()) else {
+        return provider_stream;
+    };
+    if total == 0 || range_start >= total {
+        return provider_stream;
     }
-    None
+    let last = range_end.map_or(total - 1, |end| end.min(total - 1));
+    let length = last - range_start + 1;
+
+    response_headers.retain(|(key, _)| !key.eq_ignore_ascii_case("content-length")
+        && !key.eq_ignore_ascii_case("content-range")
+        && !key.eq_ignore_ascii_case("accept-ranges"));
+    response_headers.push(("content-length".to_string(), length.to_string()));
+    response_headers.push(("content-range".to_string(), format!("bytes {range_start}-{last}/{total}")));
+    response_headers.push(("accept-ranges".to_string(), "bytes".to_string()));
+    *status = StatusCode::PARTIAL_CONTENT;
+
+    provider_stream
+        .scan((range_start, length), |(skip, remaining), item| {
+            if *remaining == 0 {
+                return futures::future::ready(None);
+            }
+            let item = item.map(|mut chunk| {
+                if *skip > 0 {
+                    let skipped = (*skip).min(chunk.len());
+                    *skip -= skipped;
+                    chunk = chunk.slice(skipped..);
+                }
+                if chunk.len() > *remaining {
+                    chunk.truncate(*remaining);
+                }
+                *remaining -= chunk.len();
+                chunk
+            });
+            futures::future::ready(Some(item))
+        })
+        .try_filter(|chunk| futures::future::ready(!chunk.is_empty()))
+        .boxed()
 }
 
 // fn get_host_and_optional_port(url: &Url) -> Option<String> {
@@ -250,7 +321,7 @@ fn prepare_client(
 
     let partial = if let Some(range) = range_start {
         if range > 0 || stream_options.was_range_requested() {
-            let range_header = format!("bytes={range}-");
+            let range_header = get_range_header_value(range, stream_options.get_range_end());
             if let Ok(header_value) = axum::http::header::HeaderValue::from_str(&range_header) {
                 headers.insert(RANGE, header_value);
             }
@@ -278,17 +349,37 @@ fn prepare_client(
     (request_builder, partial)
 }
 
+async fn udp_stream_request(
+    app_state: &Arc<AppState>,
+    stream_options: &ProviderStreamFactoryOptions,
+) -> Result<Option<ProviderStreamFactoryResponse>, StatusCode> {
+    match create_udp_stream(stream_options.get_url()) {
+        Ok(provider_stream) => {
+            let response_headers = vec![("content-type".to_string(), "video/mp2t".to_string())];
+            Ok(Some((provider_stream, Some((response_headers, StatusCode::OK, None, None)))))
+        }
+        Err(err) => {
+            warn!("Failed to open udp stream {}: {err}", sanitize_sensitive_info(stream_options.get_url_as_str()));
+            handle_channel_unavailable_stream(app_state, stream_options).await
+        }
+    }
+}
+
 async fn provider_stream_request(
     app_state: &Arc<AppState>,
     request_client: &reqwest::Client,
     stream_options: &ProviderStreamFactoryOptions,
 ) -> Result<Option<ProviderStreamFactoryResponse>, StatusCode> {
-    let (client, _partial_content) = prepare_client(request_client, stream_options);
+    if is_udp_stream_url(stream_options.get_url()) {
+        return udp_stream_request(app_state, stream_options).await;
+    }
+    let range_start = stream_options.get_total_bytes_send().unwrap_or(0);
+    let (client, partial_content) = prepare_client(request_client, stream_options);
     match client.send().await {
         Ok(mut response) => {
-            let status = response.status();
+            let mut status = response.status();
             if status.is_success() {
-                let response_info = {
+                let mut response_headers: Vec<(String, String)> = {
                     // Unfortunately, the HEAD request does not work, so we need this workaround.
                     // We need some header information from the provider, we extract the necessary headers and forward them to the client
                     if log_enabled!(log::Level::Debug) {
@@ -300,26 +391,26 @@ async fn provider_stream_request(
                         debug!("{}", sanitize_sensitive_info(&message));
                     }
 
-                    let response_headers: Vec<(String, String)> =
-                        get_response_headers(response.headers());
-                    //let url = stream_options.get_url();
-                    // debug!("First  headers {headers:?} {} {}", sanitize_sensitive_info(url.as_str()));
-                    Some((
-                        response_headers,
-                        response.status(),
-                        Some(response.url().clone()),
-                        None,
-                    ))
+                    get_response_headers(response.headers())
                 };
+                let response_url = response.url().clone();
 
-                let provider_stream = response
+                let mut provider_stream = response
                     .bytes_stream()
                     .map_err(|err| {
                         // error!("Stream error {err}");
                         StreamError::reqwest(&err)
                     })
                     .boxed();
-                return Ok(Some((provider_stream, response_info)));
+                if partial_content && status == StatusCode::OK
+                    && (range_start > 0 || stream_options.get_range_end().is_some()) {
+                    debug_if_enabled!("Provider ignored range request, serving range from full content {}", sanitize_sensitive_info(stream_options.get_url_as_str()));
+                    provider_stream = slice_full_content_stream(provider_stream, &mut response_headers, &mut status,
+                                                                range_start, stream_options.get_range_end());
+                }
+                //let url = stream_options.get_url();
+                // debug!("First  headers {headers:?} {} {}", sanitize_sensitive_info(url.as_str()));
+                return Ok(Some((provider_stream, Some((response_headers, status, Some(response_url), None)))));
             }
 
             if status.is_client_error() {
@@ -580,6 +671,7 @@ mod tests {
             &stream_url,
             &req_headers,
             None,
+            None,
             disabled_headers,
             None,
         );
@@ -596,6 +688,7 @@ mod tests {
             &stream_url,
             &req_headers,
             None,
+            None,
             disabled_headers,
             None,
         );
@@ -612,6 +705,7 @@ mod tests {
             &stream_url,
             &req_headers,
             None,
+            None,
             disabled_headers,
             None,
         );
@@ -629,10 +723,60 @@ mod tests {
             &stream_url,
             &req_headers,
             None,
+            None,
             disabled_headers,
             None,
         );
         assert!(!options.was_range_requested()); // Stripped by filter
         assert_eq!(options.get_total_bytes_send(), None); 
+
+        // Case 5: VOD, bounded range requested
+        let mut req_headers = HeaderMap::new();
+        req_headers.insert("Range", "bytes=100-199".parse().unwrap());
+        let options = ProviderStreamFactoryOptions::new(
+            addr,
+            PlaylistItemType::Series,
+            false,
+            &stream_options,
+            &stream_url,
+            &req_headers,
+            None,
+            None,
+            disabled_headers,
+            None,
+        );
+        assert!(options.was_range_requested());
+        assert_eq!(options.get_total_bytes_send(), Some(100));
+        assert_eq!(options.get_range_end(), Some(199));
+    }
+
+    #[test]
+    fn test_range_header_value() {
+        assert_eq!(get_range_header_value(100, None), "bytes=100-");
+        assert_eq!(get_range_header_value(100, Some(199)), "bytes=100-199");
+        // after a reconnect the start can pass the requested end
+        assert_eq!(get_range_header_value(300, Some(199)), "bytes=300-");
+    }
+
+    #[tokio::test]
+    async fn test_slice_full_content_stream() {
+        let chunks: Vec<Result<bytes::Bytes, StreamError>> = vec![
+            Ok(bytes::Bytes::from_static(b"0123")),
+            Ok(bytes::Bytes::from_static(b"4567")),
+            Ok(bytes::Bytes::from_static(b"89")),
+        ];
+        let stream = futures::stream::iter(chunks).boxed();
+        let mut headers = vec![("content-length".to_string(), "10".to_string())];
+        let mut status = StatusCode::OK;
+        let sliced = slice_full_content_stream(stream, &mut headers, &mut status, 3, Some(6));
+        let data: Vec<u8> = sliced
+            .try_collect::<Vec<bytes::Bytes>>()
+            .await
+            .unwrap()
+            .concat();
+        assert_eq!(data, b"3456");
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+        assert!(headers.contains(&("content-range".to_string(), "bytes 3-6/10".to_string())));
+        assert!(headers.contains(&("content-length".to_string(), "4".to_string())));
     }
 }
@@ -0,0 +1,201 @@
+use crate::api::model::{BoxedProviderStream, StreamError};
+use bytes::Bytes;
+use futures::{stream, StreamExt};
+use log::{debug, warn};
+use socket2::{Domain, Protocol, SockRef, Socket, Type};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket as StdUdpSocket};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use url::Url;
+
+// Without data the stream ends and the reconnect joins the group again.
+const UDP_RECEIVE_TIMEOUT: Duration = Duration::from_secs(5);
+const UDP_DATAGRAM_SIZE: usize = 65_536;
+const UDP_RECEIVE_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+const RTP_VERSION: u8 = 2;
+const RTP_HEADER_SIZE: usize = 12;
+
+/// Address of a udp stream like `udp://@239.0.0.1:1234`, `rtp://@239.0.0.1:5000`
+/// or source specific `udp://10.0.0.1@232.0.0.1:1234`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct UdpSource {
+    group: IpAddr,
+    port: u16,
+    source: Option<Ipv4Addr>,
+    rtp: bool,
+}
+
+impl UdpSource {
+    fn from_url(url: &Url) -> Option<Self> {
+        let rtp = match url.scheme() {
+            "udp" => false,
+            "rtp" => true,
+            _ => return None,
+        };
+        let group = match url.host()? {
+            url::Host::Ipv4(addr) => IpAddr::V4(addr),
+            url::Host::Ipv6(addr) => IpAddr::V6(addr),
+            // hosts of non-special schemes are not parsed by the url crate
+            url::Host::Domain(host) => host.parse::<IpAddr>().ok()?,
+        };
+        let source = if url.username().is_empty() {
+            None
+        } else {
+            Some(url.username().parse::<Ipv4Addr>().ok()?)
+        };
+        Some(Self { group, port: url.port()?, source, rtp })
+    }
+}
+
+pub fn is_udp_stream_url(url: &Url) -> bool {
+    matches!(url.scheme(), "udp" | "rtp")
+}
+
+/// Returns the payload of a rtp packet, `None` if it is no rtp packet.
+fn strip_rtp_header(packet: &[u8]) -> Option<&[u8]> {
+    if packet.len() < RTP_HEADER_SIZE || packet[0] >> 6 != RTP_VERSION {
+        return None;
+    }
+    let csrc_count = usize::from(packet[0] & 0x0f);
+    let mut start = RTP_HEADER_SIZE + csrc_count * 4;
+    if packet[0] & 0x10 != 0 {
+        let extension = packet.get(start..start + 4)?;
+        start += 4 + usize::from(u16::from_be_bytes([extension[2], extension[3]])) * 4;
+    }
+    let mut end = packet.len();
+    if packet[0] & 0x20 != 0 {
+        end = end.checked_sub(usize::from(packet[end - 1]))?;
+    }
+    packet.get(start..end)
+}
+
+/// Membership of the multicast group, the group is left when the stream is dropped.
+struct UdpReceiver {
+    socket: UdpSocket,
+    source: UdpSource,
+}
+
+impl UdpReceiver {
+    fn open(source: UdpSource) -> std::io::Result<Self> {
+        let domain = if source.group.is_ipv4() { Domain::IPV4 } else { Domain::IPV6 };
+        let socket = Socket::new(domain, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(true)?;
+        #[cfg(not(windows))]
+        socket.set_reuse_port(true)?;
+        if let Err(err) = socket.set_recv_buffer_size(UDP_RECEIVE_BUFFER_SIZE) {
+            debug!("Failed to set the receive buffer size for {}: {err}", source.group);
+        }
+
+        let multicast = source.group.is_multicast();
+        // Bound to the group only the datagrams of the group are received, windows can't bind to a group.
+        let bind_ip = if multicast && cfg!(not(windows)) {
+            source.group
+        } else if source.group.is_ipv4() {
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        } else {
+            IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+        };
+        socket.bind(&SocketAddr::new(bind_ip, source.port).into())?;
+
+        if multicast {
+            match (source.group, source.source) {
+                (IpAddr::V4(group), Some(source_addr)) => socket.join_ssm_v4(&source_addr, &group, &Ipv4Addr::UNSPECIFIED)?,
+                (IpAddr::V4(group), None) => socket.join_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED)?,
+                (IpAddr::V6(group), _) => socket.join_multicast_v6(&group, 0)?,
+            }
+            debug!("Joined multicast group {}:{}", source.group, source.port);
+        }
+
+        let std_socket: StdUdpSocket = socket.into();
+        std_socket.set_nonblocking(true)?;
+        Ok(Self { socket: UdpSocket::from_std(std_socket)?, source })
+    }
+
+    async fn receive(&self, buffer: &mut [u8]) -> Option<Result<Bytes, StreamError>> {
+        loop {
+            let len = match tokio::time::timeout(UDP_RECEIVE_TIMEOUT, self.socket.recv(buffer)).await {
+                Ok(Ok(len)) => len,
+                Ok(Err(err)) => return Some(Err(StreamError::StdIo(err.to_string()))),
+                Err(_) => {
+                    warn!("No data received from {}:{} for {} seconds", self.source.group, self.source.port, UDP_RECEIVE_TIMEOUT.as_secs());
+                    return None;
+                }
+            };
+            let packet = &buffer[..len];
+            let payload = if self.source.rtp { strip_rtp_header(packet) } else { Some(packet) };
+            if let Some(data) = payload.filter(|data| !data.is_empty()) {
+                return Some(Ok(Bytes::copy_from_slice(data)));
+            }
+        }
+    }
+}
+
+impl Drop for UdpReceiver {
+    fn drop(&mut self) {
+        if !self.source.group.is_multicast() {
+            return;
+        }
+        let socket = SockRef::from(&self.socket);
+        let result = match (self.source.group, self.source.source) {
+            (IpAddr::V4(group), Some(source_addr)) => socket.leave_ssm_v4(&source_addr, &group, &Ipv4Addr::UNSPECIFIED),
+            (IpAddr::V4(group), None) => socket.leave_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED),
+            (IpAddr::V6(group), _) => socket.leave_multicast_v6(&group, 0),
+        };
+        match result {
+            Ok(()) => debug!("Left multicast group {}:{}", self.source.group, self.source.port),
+            Err(err) => debug!("Failed to leave multicast group {}:{}: {err}", self.source.group, self.source.port),
+        }
+    }
+}
+
+/// Receives the datagrams of a `udp://` or `rtp://` address as stream.
+/// The multicast group is joined for every stream and left when the stream ends.
+pub(in crate::api) fn create_udp_stream(url: &Url) -> std::io::Result<BoxedProviderStream> {
+    let source = UdpSource::from_url(url)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid udp stream address {url}")))?;
+    let receiver = UdpReceiver::open(source)?;
+    Ok(stream::unfold(Some((receiver, vec![0u8; UDP_DATAGRAM_SIZE])), |state| async move {
+        let (receiver, mut buffer) = state?;
+        match receiver.receive(&mut buffer).await? {
+            Ok(data) => Some((Ok(data), Some((receiver, buffer)))),
+            Err(err) => Some((Err(err), None)),
+        }
+    }).boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{strip_rtp_header, UdpSource};
+    use std::net::{IpAddr, Ipv4Addr};
+    use url::Url;
+
+    #[test]
+    fn test_udp_source_from_url() {
+        let source = UdpSource::from_url(&Url::parse("rtp://10.0.0.1@232.1.2.3:5000").unwrap()).unwrap();
+        assert_eq!(source.group, IpAddr::V4(Ipv4Addr::new(232, 1, 2, 3)));
+        assert_eq!(source.port, 5000);
+        assert_eq!(source.source, Some(Ipv4Addr::new(10, 0, 0, 1)));
+        assert!(source.rtp);
+
+        let source = UdpSource::from_url(&Url::parse("udp://@239.0.0.1:1234").unwrap()).unwrap();
+        assert_eq!(source.source, None);
+        assert!(!source.rtp);
+
+        assert!(UdpSource::from_url(&Url::parse("udp://@239.0.0.1").unwrap()).is_none());
+        assert!(UdpSource::from_url(&Url::parse("http://239.0.0.1:1234").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_strip_rtp_header() {
+        let mut packet = vec![0x80, 33, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1];
+        packet.extend_from_slice(&[0x47, 1, 2]);
+        assert_eq!(strip_rtp_header(&packet), Some(&[0x47u8, 1, 2][..]));
+
+        // one csrc, extension with one word and 2 padding bytes
+        let mut packet = vec![0xb1, 33, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, 9, 9, 9, 9];
+        packet.extend_from_slice(&[0x47, 0, 2]);
+        assert_eq!(strip_rtp_header(&packet), Some(&[0x47u8][..]));
+
+        assert_eq!(strip_rtp_header(&[0x47, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]), None);
+    }
+}
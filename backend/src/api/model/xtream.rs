@@ -1,10 +1,20 @@
-use shared::utils::serialize_number_as_string;
+use shared::utils::{deserialize_as_string, serialize_number_as_string};
 use crate::model::{ApiProxyServerInfo, ProxyUserCredentials};
 use chrono::{Duration, Local};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use shared::model::ProxyUserStatus;
+use shared::model::{ProxyUserStatus, XtreamServerInfoDto};
 use shared::utils::CONSTANTS;
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct XtreamCategoryEntry {
+    #[serde(deserialize_with = "deserialize_as_string")]
+    pub category_id: String,
+    pub category_name: String,
+    #[serde(default)]
+    pub parent_id: u32,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct XtreamUserInfoResponse {
     pub username: String,
@@ -24,6 +34,8 @@ pub struct XtreamUserInfoResponse {
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct XtreamServerInfoResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
     pub url: String,
     pub port: String,
     pub https_port: String,
@@ -42,8 +54,19 @@ pub struct XtreamAuthorizationResponse {
 }
 
 impl XtreamAuthorizationResponse {
-    pub fn new(server_info: &ApiProxyServerInfo, user: &ProxyUserCredentials, active_connections: u32, access_control: bool) -> Self {
+    /// The values of `overrides` replace the values of the api proxy server, a valid timezone name is also used for `time_now`.
+    pub fn new(server_info: &ApiProxyServerInfo, overrides: &XtreamServerInfoDto, user: &ProxyUserCredentials, active_connections: u32, access_control: bool) -> Self {
         let now = Local::now();
+        let timezone = overrides.timezone.as_ref().unwrap_or(&server_info.timezone);
+        let time_now = timezone.parse::<Tz>().map_or_else(
+            |_| now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            |tz| now.with_timezone(&tz).format("%Y-%m-%d %H:%M:%S").to_string());
+        let port = overrides.port.map_or_else(
+            || if server_info.protocol == "http" { server_info.port.as_ref().map_or("80", |v| v.as_str()).to_string() } else { String::from("80") },
+            |port| port.to_string());
+        let https_port = overrides.https_port.map_or_else(
+            || if server_info.protocol == "https" { server_info.port.as_ref().map_or("443", |v| v.as_str()).to_string() } else { String::from("443") },
+            |port| port.to_string());
         let created_default = (now - Duration::days(365)).timestamp();
         let expired_default = (now + Duration::days(365)).timestamp();
 
@@ -75,7 +98,7 @@ impl XtreamAuthorizationResponse {
             user_info: XtreamUserInfoResponse {
                 username: user.username.clone(),
                 password: user.password.clone(),
-                message: server_info.message.clone(),
+                message: overrides.message.as_ref().unwrap_or(&server_info.message).clone(),
                 auth: 1,
                 status: user_status.to_string(),
                 exp_date,
@@ -86,14 +109,15 @@ impl XtreamAuthorizationResponse {
                 allowed_output_formats: CONSTANTS.allowed_output_formats.clone(),
             },
             server_info: XtreamServerInfoResponse {
+                server_name: overrides.server_name.clone(),
                 url: server_info.host.clone(),
-                port: if server_info.protocol == "http" { server_info.port.as_ref().map_or("80", |v| v.as_str()).to_string() } else { String::from("80") },
-                https_port: if server_info.protocol == "https" { server_info.port.as_ref().map_or("443", |v| v.as_str()).to_string() } else { String::from("443") },
+                port,
+                https_port,
                 server_protocol: server_info.protocol.clone(),
                 rtmp_port: String::new(),
-                timezone: server_info.timezone.clone(),
+                timezone: timezone.clone(),
                 timestamp_now: now.timestamp(),
-                time_now: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+                time_now,
                 // We don't know what this field is good for, but it is in the response from XtreamCodes, so we will include it.
                 process: true
             },
@@ -0,0 +1,41 @@
+use crate::api::model::{AppState, EventMessage};
+use crate::repository::add_notification;
+use log::error;
+use shared::model::{LibraryScanSummaryStatus, NotificationKind, PlaylistUpdateState};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+/// A failed playlist update is notified with the errors by the processing itself.
+fn to_notification(event: EventMessage) -> Option<(NotificationKind, String)> {
+    match event {
+        EventMessage::Notify(kind, message) => Some((kind, message)),
+        EventMessage::ServerError(message) => Some((NotificationKind::Error, message)),
+        EventMessage::PlaylistUpdate(PlaylistUpdateState::Success) => Some((NotificationKind::Info, "Playlist update finished".to_string())),
+        EventMessage::LibraryScanProgress(summary) => Some((match summary.status {
+            LibraryScanSummaryStatus::Success => NotificationKind::Info,
+            LibraryScanSummaryStatus::Error => NotificationKind::Error,
+        }, summary.message)),
+        _ => None,
+    }
+}
+
+/// Stores the notifications of the server events and sends them to the web ui.
+pub fn exec_notification_collector(app_state: &Arc<AppState>) {
+    let app_state = Arc::clone(app_state);
+    let mut event_rx = app_state.event_manager.get_event_channel();
+    tokio::spawn(async move {
+        loop {
+            let event = match event_rx.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+            if let Some((kind, message)) = to_notification(event) {
+                match add_notification(&app_state.app_config, kind, message).await {
+                    Ok(notification) => { app_state.event_manager.send_event(EventMessage::Notification(notification)); }
+                    Err(err) => error!("{err}"),
+                }
+            }
+        }
+    });
+}
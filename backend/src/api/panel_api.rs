@@ -74,7 +74,7 @@ struct PanelApiTimeCacheEntry {
     skew_secs: Option<i64>,
 }
 
-fn parse_boolish(value: &Value) -> bool {
+pub(in crate::api) fn parse_boolish(value: &Value) -> bool {
     match value {
         Value::Bool(b) => *b,
         Value::Number(n) => n.as_i64().unwrap_or(0) != 0,
@@ -86,7 +86,7 @@ fn parse_boolish(value: &Value) -> bool {
     }
 }
 
-fn extract_stringish(value: &Value) -> Option<String> {
+pub(in crate::api) fn extract_stringish(value: &Value) -> Option<String> {
     match value {
         Value::String(s) => {
             let trimmed = s.trim();
@@ -1,16 +1,21 @@
+use crate::api::api_utils::publish_removed_cache_keys;
 use crate::api::model::AppState;
 use crate::api::panel_api::sync_panel_api_exp_dates_on_boot;
 use crate::model::{AppConfig, ProcessTargets, ScheduleConfig};
-use crate::processing::processor::playlist::exec_processing;
+use crate::processing::processor::playlist::{exec_processing, is_target_enabled};
+use crate::repository::{add_job_log_entry, anonymize_notifications, anonymize_stream_sessions, rotate_stream_sessions};
 use crate::utils::exit;
-use chrono::{DateTime, FixedOffset, Local};
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use chrono_tz::Tz;
 use cron::Schedule;
-use log::{error};
+use log::{debug, error, info};
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime};
 use tokio_util::sync::CancellationToken;
-use shared::utils::interner_gc;
+use shared::model::{get_schedule_spread_offsets, IpAnonymization, JobLogEntryDto, SourceStats};
+use shared::utils::{human_readable_byte_size, interner_gc};
 
 pub fn datetime_to_instant(datetime: DateTime<FixedOffset>) -> Instant {
     // Convert DateTime<FixedOffset> to SystemTime
@@ -28,6 +33,27 @@ pub fn datetime_to_instant(datetime: DateTime<FixedOffset>) -> Instant {
     Instant::now() + duration_until
 }
 
+/// Next run of the schedule in the given timezone, without a timezone the server timezone is used.
+pub fn next_schedule_run(schedule: &Schedule, timezone: Option<Tz>) -> Option<DateTime<FixedOffset>> {
+    match timezone {
+        Some(tz) => schedule.upcoming(tz).next().map(|datetime| datetime.fixed_offset()),
+        None => schedule.upcoming(Local).next().map(|datetime| datetime.fixed_offset()),
+    }
+}
+
+/// Timezone of the schedule, otherwise the timezone shared by all of its targets.
+fn get_schedule_timezone(app_config: &AppConfig, schedule: &ScheduleConfig, targets: &ProcessTargets) -> Option<Tz> {
+    schedule.timezone.or_else(|| {
+        let sources = app_config.sources.load();
+        let mut timezones = sources.sources.iter()
+            .flat_map(|source| source.targets.iter())
+            .filter(|target| is_target_enabled(target, targets))
+            .map(|target| target.timezone);
+        let first = timezones.next()??;
+        timezones.all(|tz| tz == Some(first)).then_some(first)
+    })
+}
+
 pub fn exec_scheduler(client: &reqwest::Client, app_state: &Arc<AppState>, targets: &Arc<ProcessTargets>,
                       cancel: &CancellationToken) {
     let cfg = &app_state.app_config;
@@ -37,35 +63,37 @@ pub fn exec_scheduler(client: &reqwest::Client, app_state: &Arc<AppState>, targe
     } else {
         vec![]
     };
-    for schedule in schedules {
+    let expressions: Vec<&str> = schedules.iter().map(|schedule| schedule.schedule.as_str()).collect();
+    let spread_offsets = get_schedule_spread_offsets(&expressions, config.schedule_spread_mins.unwrap_or(0).saturating_mul(60));
+    for (schedule, spread_secs) in schedules.iter().zip(spread_offsets) {
         let expression = schedule.schedule.clone();
         let exec_targets = get_process_targets(cfg, targets, schedule.targets.as_ref());
+        let timezone = get_schedule_timezone(cfg, schedule, &exec_targets);
         let app_state_clone = Arc::clone(app_state);
         let http_client = client.clone();
         let cancel_token = cancel.clone();
+        if spread_secs > 0 {
+            debug!("Schedule {expression} starts {spread_secs} seconds later to spread the load");
+        }
+        if let Some(tz) = timezone {
+            debug!("Schedule {expression} runs in timezone {tz}");
+        }
         tokio::spawn(async move {
-            start_scheduler(http_client, expression.as_str(), app_state_clone, exec_targets, cancel_token).await;
+            start_scheduler(http_client, expression.as_str(), timezone, Duration::from_secs(u64::from(spread_secs)), app_state_clone, exec_targets, cancel_token).await;
         });
     }
 }
 
-async fn start_scheduler(client: reqwest::Client, expression: &str, app_state: Arc<AppState>,
+async fn start_scheduler(client: reqwest::Client, expression: &str, timezone: Option<Tz>, spread: Duration, app_state: Arc<AppState>,
                          targets: Arc<ProcessTargets>, cancel: CancellationToken) {
     match Schedule::from_str(expression) {
         Ok(schedule) => {
-            let offset = *Local::now().offset();
             loop {
-                let mut upcoming = schedule.upcoming(offset).take(1);
-                if let Some(datetime) = upcoming.next() {
+                if let Some(datetime) = next_schedule_run(&schedule, timezone) {
                     tokio::select! {
-                        () = tokio::time::sleep_until(tokio::time::Instant::from(datetime_to_instant(datetime))) => {
-                       let app_config = Arc::clone(&app_state.app_config);
-                       let event_manager = Arc::clone(&app_state.event_manager);
-                       let playlist_state = app_state.playlists.clone();
-                       let disabled_headers = app_state.get_disabled_headers();
-                       sync_panel_api_exp_dates_on_boot(&app_state).await;
-                       exec_processing(&client, app_config, Arc::clone(&targets), Some(event_manager),
-                            Some(playlist_state), Some(app_state.update_guard.clone()), disabled_headers).await;
+                        () = tokio::time::sleep_until(tokio::time::Instant::from(datetime_to_instant(datetime) + spread)) => {
+                            let next_run = next_schedule_run(&schedule, timezone);
+                            exec_scheduled_run(&client, &app_state, expression, &targets, next_run, &cancel).await;
                         }
                         () = cancel.cancelled() => {
                             break;
@@ -78,6 +106,77 @@ async fn start_scheduler(client: reqwest::Client, expression: &str, app_state: A
     }
 }
 
+async fn run_processing(client: &reqwest::Client, app_state: &Arc<AppState>, targets: Arc<ProcessTargets>) -> Vec<SourceStats> {
+    let app_config = Arc::clone(&app_state.app_config);
+    let event_manager = Arc::clone(&app_state.event_manager);
+    let playlist_state = app_state.playlists.clone();
+    let disabled_headers = app_state.get_disabled_headers();
+    sync_panel_api_exp_dates_on_boot(app_state).await;
+    exec_processing(client, app_config, targets, Some(event_manager),
+                    Some(playlist_state), Some(app_state.update_guard.clone()), disabled_headers).await
+}
+
+fn get_scheduled_target_names(app_config: &AppConfig, targets: &ProcessTargets) -> Vec<String> {
+    app_config.sources.load().sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .filter(|target| is_target_enabled(target, targets))
+        .map(|target| target.name.clone())
+        .collect()
+}
+
+/// Targets without a successful run, the targets of an empty source have no stats at all.
+fn get_failed_targets(target_names: &[String], stats: &[SourceStats]) -> Vec<String> {
+    target_names.iter()
+        .filter(|name| !stats.iter().flat_map(|source| &source.targets).any(|target| target.success && &target.name == *name))
+        .cloned()
+        .collect()
+}
+
+/// Runs the schedule and retries its failed targets with exponential backoff until the next scheduled run.
+/// Every attempt is recorded in the job log.
+async fn exec_scheduled_run(client: &reqwest::Client, app_state: &Arc<AppState>, expression: &str, targets: &Arc<ProcessTargets>,
+                            next_run: Option<DateTime<FixedOffset>>, cancel: &CancellationToken) {
+    let retry = app_state.app_config.config.load().schedule_retry.filter(|retry| retry.max_attempts > 0);
+    let mut run_targets = Arc::clone(targets);
+    let mut attempt: u8 = 1;
+    loop {
+        let target_names = get_scheduled_target_names(&app_state.app_config, &run_targets);
+        let stats = run_processing(client, app_state, Arc::clone(&run_targets)).await;
+        let failed = get_failed_targets(&target_names, &stats);
+        let now = Utc::now().timestamp();
+        let next_retry_ts = retry
+            .filter(|retry| !failed.is_empty() && attempt <= retry.max_attempts)
+            .map(|retry| now.saturating_add(i64::try_from(retry.get_delay_secs(attempt)).unwrap_or(i64::MAX)))
+            .filter(|retry_ts| next_run.is_none_or(|next_run| *retry_ts < next_run.timestamp()));
+        if let Err(err) = add_job_log_entry(&app_state.app_config, JobLogEntryDto {
+            ts: now,
+            schedule: expression.to_string(),
+            attempt,
+            targets: target_names,
+            failed: failed.clone(),
+            next_retry_ts,
+        }).await {
+            error!("{err}");
+        }
+
+        let Some(retry_ts) = next_retry_ts else { break; };
+        run_targets = match app_state.app_config.sources.load().validate_targets(Some(&failed)) {
+            Ok(failed_targets) => Arc::new(failed_targets),
+            Err(err) => {
+                error!("Can't retry the failed targets of schedule {expression}: {err}");
+                break;
+            }
+        };
+        let delay_secs = u64::try_from(retry_ts - now).unwrap_or_default();
+        info!("Retrying the failed targets {} of schedule {expression} in {delay_secs} seconds", failed.join(", "));
+        tokio::select! {
+            () = tokio::time::sleep(Duration::from_secs(delay_secs)) => {}
+            () = cancel.cancelled() => break,
+        }
+        attempt = attempt.saturating_add(1);
+    }
+}
+
 fn get_process_targets(cfg: &Arc<AppConfig>, process_targets: &Arc<ProcessTargets>, exec_targets: Option<&Vec<String>>) -> Arc<ProcessTargets> {
     let sources = cfg.sources.load();
     if let Ok(user_targets) = sources.validate_targets(exec_targets) {
@@ -127,9 +226,95 @@ pub fn exec_interner_prune(app_state: &Arc<AppState>) {
     });
 }
 
+/// Pseudonymizes the client ips of the stored data after `log.retention_days`
+/// and removes the stream session history older than `log.session_history_days`.
+pub fn exec_data_retention(app_state: &Arc<AppState>) {
+    let app_state = Arc::clone(app_state);
+    tokio::spawn({
+        async move {
+            loop {
+                let session_history_days = app_state.app_config.config.load().log.as_ref()
+                    .map_or(0, |log| log.session_history_days);
+                if session_history_days > 0 {
+                    let removed = rotate_stream_sessions(&app_state.app_config, session_history_days).await;
+                    if removed > 0 {
+                        debug!("Removed {removed} stream session history files");
+                    }
+                }
+                let retention = app_state.app_config.config.load().log.as_ref()
+                    .filter(|log| log.retention_days > 0)
+                    .map(|log| (log.retention_days, log.ip_anonymization));
+                if let Some((retention_days, ip_anonymization)) = retention {
+                    // without a configured mode the ips are truncated
+                    let mode = if ip_anonymization.is_none() { IpAnonymization::Truncate } else { ip_anonymization };
+                    let older_than_ts = chrono::Utc::now().timestamp() - i64::from(retention_days) * 86_400;
+                    match anonymize_notifications(&app_state.app_config, older_than_ts, mode).await {
+                        Ok(0) => {}
+                        Ok(count) => debug!("Pseudonymized client ips of {count} notifications"),
+                        Err(err) => error!("{err}"),
+                    }
+                    match anonymize_stream_sessions(&app_state.app_config, older_than_ts, mode).await {
+                        Ok(0) => {}
+                        Ok(count) => debug!("Pseudonymized client ips of {count} stream sessions"),
+                        Err(err) => error!("{err}"),
+                    }
+                }
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+            }
+        }
+    });
+}
+
+/// Removes the cached resources older than `cache.max_age_days`.
+pub fn exec_cache_cleanup(app_state: &Arc<AppState>) {
+    let app_state = Arc::clone(app_state);
+    tokio::spawn({
+        async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(600)).await;
+                let max_age_days = app_state.app_config.get_cache_max_age_days();
+                if max_age_days == 0 {
+                    continue;
+                }
+                if let Some(cache) = app_state.cache.load().as_ref() {
+                    let (removed, freed) = cache.lock().await.evict_expired(u64::from(max_age_days) * 86_400);
+                    publish_removed_cache_keys(cache, app_state.cluster.as_deref()).await;
+                    if removed > 0 {
+                        debug!("Removed {removed} expired cache entries, freed {}", human_readable_byte_size(freed));
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Exchanges the connection counts with the other cluster instances every `cluster.sync_interval_millis`.
+/// Without redis the remote counts are dropped and the limits are only enforced locally.
+pub fn exec_cluster_sync(app_state: &Arc<AppState>) {
+    let Some(cluster) = app_state.cluster.clone() else { return; };
+    let interval = app_state.app_config.config.load().cluster.as_ref()
+        .map_or(1000, |c| c.sync_interval_millis);
+    let app_state = Arc::clone(app_state);
+    tokio::spawn({
+        async move {
+            loop {
+                let providers: HashMap<String, usize> = app_state.active_provider.local_connections().await
+                    .into_iter().map(|(name, count)| (name.to_string(), count)).collect();
+                let users = app_state.active_users.local_connections().await;
+                let remote = cluster.sync(&providers, &users).await.unwrap_or_default();
+                app_state.active_provider.set_remote_connections(
+                    remote.providers.into_iter().map(|(name, count)| (Arc::from(name), count)).collect()).await;
+                app_state.active_users.set_remote_connections(remote.users).await;
+                tokio::time::sleep(Duration::from_millis(interval)).await;
+            }
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::api::scheduler::datetime_to_instant;
+    use crate::api::scheduler::{datetime_to_instant, get_failed_targets, next_schedule_run};
+    use shared::model::{SourceStats, TargetStats};
     use chrono::Local;
     use cron::Schedule;
     use std::str::FromStr;
@@ -162,4 +347,21 @@ mod tests {
         assert!(runs.load(Ordering::Acquire) == 6, "Failed to run");
         assert!(duration.as_secs() > 4, "Failed time");
     }
+
+    #[test]
+    fn test_get_failed_targets() {
+        let stats = vec![SourceStats { inputs: vec![], targets: vec![TargetStats::success("a"), TargetStats::failure("b")] }];
+        let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(get_failed_targets(&names, &stats), vec!["b".to_string(), "c".to_string()]);
+        assert!(get_failed_targets(&names[..1], &stats).is_empty());
+    }
+
+    #[test]
+    fn test_next_schedule_run_timezone() {
+        let schedule = Schedule::from_str("0 0 6 * * * *").unwrap();
+        let tz: chrono_tz::Tz = "Asia/Tokyo".parse().unwrap();
+        let next_run = next_schedule_run(&schedule, Some(tz)).unwrap();
+        assert_eq!(next_run.offset().local_minus_utc(), 9 * 3600);
+        assert_eq!(next_run.format("%H:%M").to_string(), "06:00");
+    }
 }
@@ -0,0 +1,114 @@
+use crate::api::model::AppState;
+use log::{info, warn};
+use shared::model::NotificationKind;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for the ctrl-c signal: {err}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => { signal.recv().await; }
+            Err(err) => {
+                warn!("Failed to listen for the terminate signal: {err}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {},
+        () = terminate => {},
+        () = crate::utils::service_stop_requested() => {},
+    }
+}
+
+async fn active_connection_count(app_state: &AppState) -> usize {
+    app_state.active_users.active_users_and_connections().await.1
+}
+
+/// Waits until the active streams are finished or the grace period is over.
+async fn drain_streams(app_state: &AppState, grace: Duration) {
+    let deadline = Instant::now() + grace;
+    while Instant::now() < deadline {
+        if active_connection_count(app_state).await == 0 {
+            return;
+        }
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+    let streams = app_state.active_users.active_streams().await;
+    if !streams.is_empty() {
+        info!("Grace period is over, closing {} active streams", streams.len());
+        for stream in &streams {
+            app_state.connection_manager.kick_connection(&stream.addr, stream.channel.virtual_id, 0).await;
+        }
+    }
+}
+
+/// Waits for running playlist updates and library scans, they write to the repository.
+async fn wait_for_pending_writes(app_state: &AppState) {
+    let deadline = Instant::now() + FLUSH_TIMEOUT;
+    loop {
+        let playlist = app_state.update_guard.try_playlist();
+        let library = app_state.update_guard.try_library();
+        if playlist.is_some() && library.is_some() {
+            return;
+        }
+        if Instant::now() >= deadline {
+            warn!("Pending repository writes did not finish within {} seconds", FLUSH_TIMEOUT.as_secs());
+            return;
+        }
+        drop(playlist);
+        drop(library);
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+}
+
+async fn graceful_shutdown(app_state: &AppState) {
+    if !app_state.shutdown.begin_drain() {
+        return;
+    }
+    let grace_secs = app_state.app_config.config.load().api.shutdown_grace_secs;
+    let active = active_connection_count(app_state).await;
+    info!("Shutdown requested, draining {active} active streams within {grace_secs} seconds");
+    app_state.event_manager.send_notification(NotificationKind::Warning,
+        format!("Server is shutting down in {grace_secs} seconds, no new streams are accepted"));
+
+    drain_streams(app_state, Duration::from_secs(u64::from(grace_secs))).await;
+
+    {
+        let cancel_tokens = app_state.cancel_tokens.load();
+        cancel_tokens.scheduler.cancel();
+        cancel_tokens.hdhomerun.cancel();
+        cancel_tokens.file_watch.cancel();
+    }
+
+    wait_for_pending_writes(app_state).await;
+    app_state.connection_manager.session_recorder.finish_all().await;
+
+    info!("Shutdown completed");
+    app_state.shutdown.stop();
+}
+
+/// Listens for the termination signals and shuts the server down gracefully.
+pub fn exec_graceful_shutdown(app_state: &Arc<AppState>) {
+    let app_state = Arc::clone(app_state);
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        graceful_shutdown(&app_state).await;
+    });
+}
@@ -0,0 +1,210 @@
+use crate::api::endpoints::v1_api_user::persist_api_proxy;
+use crate::api::model::AppState;
+use crate::messaging::send_message;
+use crate::model::{ApiProxyConfig, MessageContent, UserExpirationConfig};
+use chrono::{DateTime, Utc};
+use handlebars::Handlebars;
+use log::{error, info, warn};
+use shared::model::ProxyUserStatus;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DAY_SECS: i64 = 86_400;
+const DEFAULT_NOTIFY_TEMPLATE: &str = "User {{username}} of target {{target}} expires {{exp_date}}, {{days_left}} day(s) left";
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct ExpiringUser {
+    target: String,
+    username: String,
+    exp_date: String,
+    days_left: i64,
+    #[serde(skip)]
+    exp_ts: i64,
+}
+
+impl ExpiringUser {
+    /// Users are notified once per expiration date, a renewed user is notified again.
+    fn key(&self) -> (String, String, i64) {
+        (self.target.clone(), self.username.clone(), self.exp_ts)
+    }
+}
+
+#[derive(Debug, Default)]
+struct UserExpirationChanges {
+    disabled: Vec<String>,
+    deleted: Vec<String>,
+    expiring: Vec<ExpiringUser>,
+}
+
+impl UserExpirationChanges {
+    fn is_modified(&self) -> bool {
+        !self.disabled.is_empty() || !self.deleted.is_empty()
+    }
+}
+
+fn format_exp_date(exp_date: i64) -> String {
+    DateTime::<Utc>::from_timestamp(exp_date, 0)
+        .map_or_else(|| exp_date.to_string(), |datetime| datetime.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+/// Disables the expired users, deletes them after the retention period
+/// and collects the users which expire within the notification period.
+fn apply_user_expiration(api_proxy: &mut ApiProxyConfig, config: &UserExpirationConfig, now: i64) -> UserExpirationChanges {
+    let mut changes = UserExpirationChanges::default();
+    for target_user in &mut api_proxy.user {
+        let target = &target_user.target;
+        target_user.credentials.retain_mut(|user| {
+            let Some(exp_date) = user.exp_date else { return true; };
+            if exp_date > now {
+                let time_left = exp_date - now;
+                if config.notify_days_before.is_some_and(|days| time_left <= i64::from(days) * DAY_SECS) {
+                    changes.expiring.push(ExpiringUser {
+                        target: target.clone(),
+                        username: user.username.clone(),
+                        exp_date: format_exp_date(exp_date),
+                        days_left: (time_left + DAY_SECS - 1) / DAY_SECS,
+                        exp_ts: exp_date,
+                    });
+                }
+                return true;
+            }
+            if config.delete_after_days.is_some_and(|days| exp_date + i64::from(days) * DAY_SECS <= now) {
+                changes.deleted.push(format!("{} ({target})", user.username));
+                return false;
+            }
+            // banned or manually disabled users keep their status
+            if config.disable_expired && user.status.is_none_or(|status| matches!(status, ProxyUserStatus::Active | ProxyUserStatus::Trial)) {
+                user.status = Some(ProxyUserStatus::Expired);
+                changes.disabled.push(format!("{} ({target})", user.username));
+            }
+            true
+        });
+    }
+    changes
+}
+
+fn render_notify_message(template: Option<&str>, user: &ExpiringUser) -> String {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.render_template(template.unwrap_or(DEFAULT_NOTIFY_TEMPLATE), user).unwrap_or_else(|err| {
+        warn!("Failed to render the user expiration template: {err}");
+        format!("User {} of target {} expires {}", user.username, user.target, user.exp_date)
+    })
+}
+
+async fn check_user_expiration(app_state: &Arc<AppState>, config: &UserExpirationConfig, notified: &mut HashSet<(String, String, i64)>) {
+    let changes = {
+        let virtual_file = PathBuf::from("api_proxy");
+        let _lock = app_state.app_config.file_locks.write_lock(&virtual_file).await;
+        let Some(old_api_proxy) = app_state.app_config.api_proxy.load_full() else { return; };
+        let mut api_proxy = (*old_api_proxy).clone();
+        let changes = apply_user_expiration(&mut api_proxy, config, Utc::now().timestamp());
+        if changes.is_modified() {
+            if let Err(err) = persist_api_proxy(app_state, api_proxy).await {
+                error!("Failed to save the expired users: {err}");
+                return;
+            }
+        }
+        changes
+    };
+
+    let mut messages = Vec::new();
+    if !changes.disabled.is_empty() {
+        messages.push(format!("Disabled expired users: {}", changes.disabled.join(", ")));
+    }
+    if !changes.deleted.is_empty() {
+        messages.push(format!("Deleted expired users: {}", changes.deleted.join(", ")));
+    }
+    for user in changes.expiring.iter().filter(|user| !notified.contains(&user.key())) {
+        messages.push(render_notify_message(config.notify_template.as_deref(), user));
+    }
+    *notified = changes.expiring.iter().map(ExpiringUser::key).collect();
+
+    if messages.is_empty() {
+        return;
+    }
+    let client = app_state.http_client.load();
+    for message in messages {
+        info!("{message}");
+        send_message(&app_state.app_config, &client, MessageContent::Info(message)).await;
+    }
+}
+
+/// Checks the expiration dates of the users every `user_expiration.interval_mins`.
+/// The notified users are kept in memory, after a restart the reminder is sent again.
+pub fn exec_user_expiration(app_state: &Arc<AppState>) {
+    let app_state = Arc::clone(app_state);
+    tokio::spawn({
+        async move {
+            let mut notified = HashSet::new();
+            loop {
+                let expiration_config = app_state.app_config.config.load().user_expiration.clone();
+                let interval_mins = if let Some(expiration_config) = expiration_config {
+                    check_user_expiration(&app_state, &expiration_config, &mut notified).await;
+                    expiration_config.interval_mins
+                } else {
+                    // the config can be activated with a hot reload
+                    1
+                };
+                tokio::time::sleep(Duration::from_secs(u64::from(interval_mins) * 60)).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_user_expiration, render_notify_message, DAY_SECS};
+    use crate::model::{ApiProxyConfig, ProxyUserCredentials, TargetUser, UserExpirationConfig};
+    use shared::model::ProxyUserStatus;
+
+    fn user(username: &str, exp_date: Option<i64>, status: Option<ProxyUserStatus>) -> ProxyUserCredentials {
+        let mut user = ProxyUserCredentials::default();
+        user.username = username.to_string();
+        user.exp_date = exp_date;
+        user.status = status;
+        user
+    }
+
+    #[test]
+    fn test_apply_user_expiration() {
+        let now = 1_800_000_000;
+        let mut api_proxy = ApiProxyConfig {
+            user: vec![TargetUser {
+                target: "family".to_string(),
+                credentials: vec![
+                    user("unlimited", None, Some(ProxyUserStatus::Active)),
+                    user("soon", Some(now + 2 * DAY_SECS - 60), Some(ProxyUserStatus::Active)),
+                    user("later", Some(now + 10 * DAY_SECS), None),
+                    user("expired", Some(now - DAY_SECS), Some(ProxyUserStatus::Trial)),
+                    user("banned", Some(now - DAY_SECS), Some(ProxyUserStatus::Banned)),
+                    user("gone", Some(now - 40 * DAY_SECS), Some(ProxyUserStatus::Expired)),
+                ],
+            }],
+            ..ApiProxyConfig::default()
+        };
+        let config = UserExpirationConfig {
+            interval_mins: 60,
+            disable_expired: true,
+            delete_after_days: Some(30),
+            notify_days_before: Some(3),
+            notify_template: None,
+        };
+        let changes = apply_user_expiration(&mut api_proxy, &config, now);
+        assert_eq!(changes.disabled, vec!["expired (family)".to_string()]);
+        assert_eq!(changes.deleted, vec!["gone (family)".to_string()]);
+        assert_eq!(changes.expiring.len(), 1);
+        assert_eq!(changes.expiring[0].username, "soon");
+        assert_eq!(changes.expiring[0].days_left, 2);
+
+        let users = &api_proxy.user[0].credentials;
+        assert_eq!(users.len(), 5);
+        assert_eq!(users.iter().find(|u| u.username == "expired").and_then(|u| u.status), Some(ProxyUserStatus::Expired));
+        assert_eq!(users.iter().find(|u| u.username == "banned").and_then(|u| u.status), Some(ProxyUserStatus::Banned));
+
+        let message = render_notify_message(Some("{{username}}@{{target}}: {{days_left}} days, {{exp_date}}"), &changes.expiring[0]);
+        assert_eq!(message, format!("soon@family: 2 days, {}", changes.expiring[0].exp_date));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::api::panel_api::{extract_stringish, parse_boolish};
+use crate::model::{ApiProxyConfig, ProxyUserCredentials, TargetUser};
+use crate::utils::read_csv;
+use serde_json::Value;
+use shared::model::{ProxyType, ProxyUserCredentialsDto, ProxyUserStatus, UserImportAction, UserImportColumnDto,
+                    UserImportEntryDto, UserImportFormat, UserImportResultDto};
+use shared::utils::parse_timestamp;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UserField {
+    Username,
+    Password,
+    Token,
+    Proxy,
+    Server,
+    EpgTimeshift,
+    MaxConnections,
+    ExpDate,
+    Status,
+    UiEnabled,
+    Comment,
+}
+
+impl UserField {
+    fn from_column(column: &str) -> Option<Self> {
+        match column.trim().to_lowercase().replace([' ', '-'], "_").as_str() {
+            "username" | "user" | "login" => Some(Self::Username),
+            "password" | "pass" => Some(Self::Password),
+            "token" => Some(Self::Token),
+            "proxy" | "proxy_type" => Some(Self::Proxy),
+            "server" => Some(Self::Server),
+            "epg_timeshift" | "timeshift" => Some(Self::EpgTimeshift),
+            "max_connections" | "max_cons" | "connections" => Some(Self::MaxConnections),
+            "exp_date" | "expiry" | "expires" | "expiration" => Some(Self::ExpDate),
+            "status" => Some(Self::Status),
+            "ui_enabled" => Some(Self::UiEnabled),
+            "comment" | "notes" => Some(Self::Comment),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Username => "username",
+            Self::Password => "password",
+            Self::Token => "token",
+            Self::Proxy => "proxy",
+            Self::Server => "server",
+            Self::EpgTimeshift => "epg_timeshift",
+            Self::MaxConnections => "max_connections",
+            Self::ExpDate => "exp_date",
+            Self::Status => "status",
+            Self::UiEnabled => "ui_enabled",
+            Self::Comment => "comment",
+        }
+    }
+
+    fn assign(self, user: &mut ProxyUserCredentialsDto, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        let optional = || if value.is_empty() { None } else { Some(value.to_string()) };
+        match self {
+            Self::Username => user.username = value.to_string(),
+            Self::Password => user.password = value.to_string(),
+            Self::Token => user.token = optional(),
+            Self::Server => user.server = optional(),
+            Self::EpgTimeshift => user.epg_timeshift = optional(),
+            Self::Comment => user.comment = optional(),
+            Self::Proxy => if !value.is_empty() {
+                user.proxy = ProxyType::from_str(&value.to_lowercase()).map_err(|_| format!("Invalid proxy type {value}"))?;
+            },
+            Self::MaxConnections => user.max_connections = if value.is_empty() { 0 } else {
+                value.parse::<u32>().map_err(|_| format!("Invalid max connections {value}"))?
+            },
+            Self::ExpDate => user.exp_date = parse_timestamp(value).map_err(|_| format!("Invalid expiration date {value}"))?,
+            Self::Status => if !value.is_empty() {
+                let mut chars = value.chars();
+                let status = chars.next().map(|first| first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect::<String>()).unwrap_or_default();
+                user.status = Some(ProxyUserStatus::from_str(&status).map_err(|_| format!("Invalid status {value}"))?);
+            },
+            Self::UiEnabled => if !value.is_empty() {
+                user.ui_enabled = parse_boolish(&Value::String(value.to_string()));
+            },
+        }
+        Ok(())
+    }
+}
+
+/// The values of a row of the import, assigned to the user fields.
+#[derive(Debug)]
+pub(in crate::api) struct UserImportRecord {
+    row: usize,
+    values: Vec<(UserField, String)>,
+}
+
+impl UserImportRecord {
+    fn username(&self) -> String {
+        self.values.iter().rev().find(|(field, _)| *field == UserField::Username)
+            .map(|(_, value)| value.trim().to_string()).unwrap_or_default()
+    }
+}
+
+pub(in crate::api) struct UserImport {
+    pub columns: Vec<UserImportColumnDto>,
+    pub records: Vec<UserImportRecord>,
+}
+
+fn parse_csv_import(content: &str) -> Result<UserImport, String> {
+    let mut rows = read_csv(content).into_iter();
+    let Some(header) = rows.next() else {
+        return Err("The csv is empty".to_string());
+    };
+    let fields: Vec<Option<UserField>> = header.iter().map(|column| UserField::from_column(column)).collect();
+    if !fields.contains(&Some(UserField::Username)) || !fields.contains(&Some(UserField::Password)) {
+        return Err("The csv header needs the columns username and password".to_string());
+    }
+    let columns = header.iter().zip(&fields)
+        .map(|(column, field)| UserImportColumnDto { column: column.trim().to_string(), field: field.map(|f| f.name().to_string()) })
+        .collect();
+    // the header is the first row
+    let records = rows.enumerate().map(|(index, row)| UserImportRecord {
+        row: index + 2,
+        values: fields.iter().zip(row)
+            .filter_map(|(field, value)| field.map(|f| (f, value)))
+            .collect(),
+    }).collect();
+    Ok(UserImport { columns, records })
+}
+
+/// Maps the keys of a line of an xtream panel export to the user fields.
+fn xtream_line_field(key: &str) -> Option<UserField> {
+    match key {
+        "username" => Some(UserField::Username),
+        "password" => Some(UserField::Password),
+        "exp_date" => Some(UserField::ExpDate),
+        "max_connections" => Some(UserField::MaxConnections),
+        "admin_notes" => Some(UserField::Comment),
+        "enabled" | "admin_enabled" | "is_trial" => Some(UserField::Status),
+        _ => None,
+    }
+}
+
+fn xtream_line_status(line: &serde_json::Map<String, Value>) -> Option<ProxyUserStatus> {
+    let disabled = ["enabled", "admin_enabled"].iter().any(|key| line.get(*key).is_some_and(|value| !parse_boolish(value)));
+    if disabled {
+        Some(ProxyUserStatus::Disabled)
+    } else if line.get("is_trial").is_some_and(parse_boolish) {
+        Some(ProxyUserStatus::Trial)
+    } else {
+        None
+    }
+}
+
+fn parse_xtream_import(content: &str) -> Result<UserImport, String> {
+    let json: Value = serde_json::from_str(content).map_err(|err| format!("Invalid xtream panel export: {err}"))?;
+    // panels export the lines as array or wrapped into an object
+    let lines = match &json {
+        Value::Array(lines) => Some(lines),
+        Value::Object(object) => ["lines", "users", "data"].iter().find_map(|key| object.get(*key).and_then(Value::as_array)),
+        _ => None,
+    }.ok_or_else(|| "The xtream panel export has no lines".to_string())?;
+
+    let mut columns: Vec<UserImportColumnDto> = vec![];
+    let mut records = vec![];
+    for (index, line) in lines.iter().enumerate() {
+        let Some(line) = line.as_object() else { continue; };
+        for key in line.keys() {
+            if !columns.iter().any(|column| &column.column == key) {
+                columns.push(UserImportColumnDto { column: key.clone(), field: xtream_line_field(key).map(|f| f.name().to_string()) });
+            }
+        }
+        let mut values: Vec<(UserField, String)> = line.iter()
+            .filter_map(|(key, value)| xtream_line_field(key)
+                .filter(|field| *field != UserField::Status)
+                .map(|field| (field, extract_stringish(value).unwrap_or_default())))
+            .collect();
+        // panels use 0 for unlimited connections
+        values.retain(|(field, value)| *field != UserField::MaxConnections || value != "0");
+        if let Some(status) = xtream_line_status(line) {
+            values.push((UserField::Status, status.to_string()));
+        }
+        records.push(UserImportRecord { row: index + 1, values });
+    }
+    Ok(UserImport { columns, records })
+}
+
+pub(in crate::api) fn parse_user_import(format: UserImportFormat, content: &str) -> Result<UserImport, String> {
+    match format {
+        UserImportFormat::Csv => parse_csv_import(content),
+        UserImportFormat::Xtream => parse_xtream_import(content),
+    }
+}
+
+fn find_user(api_proxy: &ApiProxyConfig, username: &str) -> Option<(usize, usize)> {
+    api_proxy.user.iter().enumerate().find_map(|(target_idx, target_user)|
+        target_user.credentials.iter().position(|user| user.username == username).map(|user_idx| (target_idx, user_idx)))
+}
+
+fn skip_entry(row: usize, username: String, reason: String) -> UserImportEntryDto {
+    UserImportEntryDto { row, username, action: UserImportAction::Skip, reason: Some(reason), user: None }
+}
+
+fn import_record(api_proxy: &mut ApiProxyConfig, target_name: &str, update_existing: bool,
+                 record: &UserImportRecord, imported: &mut HashSet<String>) -> UserImportEntryDto {
+    let username = record.username();
+    if username.is_empty() {
+        return skip_entry(record.row, username, "Username required".to_string());
+    }
+    if !imported.insert(username.clone()) {
+        return skip_entry(record.row, username, "Duplicate username in the import".to_string());
+    }
+    let existing = find_user(api_proxy, &username);
+    let (mut user, action) = match existing {
+        Some((target_idx, _)) if !update_existing => {
+            let reason = format!("User exists in target {}", api_proxy.user[target_idx].target);
+            return skip_entry(record.row, username, reason);
+        }
+        Some((target_idx, user_idx)) => (ProxyUserCredentialsDto::from(&api_proxy.user[target_idx].credentials[user_idx]), UserImportAction::Update),
+        None => (ProxyUserCredentialsDto {
+            created_at: Some(chrono::Utc::now().timestamp()),
+            ui_enabled: true,
+            ..ProxyUserCredentialsDto::default()
+        }, UserImportAction::Create),
+    };
+
+    for (field, value) in &record.values {
+        if let Err(err) = field.assign(&mut user, value) {
+            return skip_entry(record.row, username, err);
+        }
+    }
+    user.prepare();
+    if let Err(err) = user.validate() {
+        return skip_entry(record.row, username, err.to_string());
+    }
+    if let Some(token) = user.token.as_ref() {
+        let duplicate = api_proxy.user.iter().flat_map(|target_user| target_user.credentials.iter())
+            .any(|other| other.username != user.username && other.token.as_ref() == Some(token));
+        if duplicate {
+            return skip_entry(record.row, username, "Duplicate token".to_string());
+        }
+    }
+
+    if let Some((target_idx, user_idx)) = existing {
+        api_proxy.user[target_idx].credentials.remove(user_idx);
+    }
+    let credentials = ProxyUserCredentials::from(&user);
+    match api_proxy.user.iter_mut().find(|target_user| target_user.target == target_name) {
+        Some(target_user) => target_user.credentials.push(credentials),
+        None => api_proxy.user.push(TargetUser { target: target_name.to_string(), credentials: vec![credentials] }),
+    }
+    UserImportEntryDto { row: record.row, username, action, reason: None, user: Some(user) }
+}
+
+/// Creates or updates the users of the import in the target, invalid rows are skipped.
+pub(in crate::api) fn import_users(api_proxy: &mut ApiProxyConfig, target_name: &str, update_existing: bool, import: UserImport) -> UserImportResultDto {
+    let mut imported = HashSet::new();
+    let entries: Vec<UserImportEntryDto> = import.records.iter()
+        .map(|record| import_record(api_proxy, target_name, update_existing, record, &mut imported))
+        .collect();
+    api_proxy.user.retain(|target_user| !target_user.credentials.is_empty());
+    let count = |action: UserImportAction| entries.iter().filter(|entry| entry.action == action).count();
+    UserImportResultDto {
+        dry_run: false,
+        created: count(UserImportAction::Create),
+        updated: count(UserImportAction::Update),
+        skipped: count(UserImportAction::Skip),
+        columns: import.columns,
+        entries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{import_users, parse_user_import};
+    use crate::model::{ApiProxyConfig, ProxyUserCredentials, TargetUser};
+    use shared::model::{ProxyUserCredentialsDto, ProxyUserStatus, UserImportAction, UserImportFormat};
+
+    fn api_proxy_with_user(target: &str, username: &str) -> ApiProxyConfig {
+        let user = ProxyUserCredentialsDto { username: username.to_string(), password: "old".to_string(), max_connections: 2, ..ProxyUserCredentialsDto::default() };
+        ApiProxyConfig {
+            server: vec![],
+            user: vec![TargetUser { target: target.to_string(), credentials: vec![ProxyUserCredentials::from(&user)] }],
+            use_user_db: false,
+        }
+    }
+
+    #[test]
+    fn test_import_users_csv() {
+        let csv = "User;Password;Max Connections;Status;Group\nalice;secret;3;banned;vip\nbob;;1;;\nalice;again;1;;\n";
+        let import = parse_user_import(UserImportFormat::Csv, csv).unwrap();
+        assert_eq!(import.columns[2].field.as_deref(), Some("max_connections"));
+        assert_eq!(import.columns[4].field, None);
+
+        let mut api_proxy = api_proxy_with_user("other", "carol");
+        let result = import_users(&mut api_proxy, "family", false, import);
+        assert_eq!((result.created, result.updated, result.skipped), (1, 0, 2));
+        assert_eq!(result.entries[1].row, 3);
+        let alice = &api_proxy.user[1].credentials[0];
+        assert_eq!((alice.username.as_str(), alice.max_connections, alice.status), ("alice", 3, Some(ProxyUserStatus::Banned)));
+    }
+
+    #[test]
+    fn test_import_users_xtream() {
+        let export = r#"{"data": [
+            {"id": 1, "username": "carol", "password": "new", "exp_date": "1893456000", "max_connections": "0", "enabled": "1", "is_trial": "1"},
+            {"id": 2, "username": "dave", "password": "pw", "exp_date": null, "max_connections": 2, "enabled": 0}
+        ]}"#;
+        let import = parse_user_import(UserImportFormat::Xtream, export).unwrap();
+        let mut api_proxy = api_proxy_with_user("other", "carol");
+        let result = import_users(&mut api_proxy, "family", true, import);
+        assert_eq!((result.created, result.updated, result.skipped), (1, 1, 0));
+        assert_eq!(result.entries[0].action, UserImportAction::Update);
+        // carol is moved to the target, the unlimited connections of the panel keep the old value
+        assert_eq!(api_proxy.user.len(), 1);
+        let carol = &api_proxy.user[0].credentials[0];
+        assert_eq!((carol.password.as_str(), carol.max_connections, carol.exp_date, carol.status),
+                   ("new", 2, Some(1_893_456_000), Some(ProxyUserStatus::Trial)));
+        assert_eq!(api_proxy.user[0].credentials[1].status, Some(ProxyUserStatus::Disabled));
+    }
+}
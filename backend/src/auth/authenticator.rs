@@ -1,3 +1,4 @@
+use std::future::Future;
 use std::sync::Arc;
 use chrono::{Local, Duration};
 use jsonwebtoken::{Algorithm, DecodingKey, encode, decode, EncodingKey, Header, Validation, TokenData};
@@ -6,10 +7,12 @@ use crate::model::WebAuthConfig;
 use crate::api::model::AppState;
 use crate::auth::AuthBearer;
 use shared::error::to_io_error;
-use shared::model::{Claims, ROLE_ADMIN, ROLE_USER};
+use shared::model::{Claims, WebUiRole, ROLE_USER};
 
-pub fn create_jwt_admin(web_auth_config: &WebAuthConfig, username: &str) -> Result<String, std::io::Error> {
-    create_jwt(web_auth_config, username, vec![ROLE_ADMIN.to_string()])
+/// Token for the web ui users of the userfile, the claim is the configured role of the user.
+pub fn create_jwt_web_ui(web_auth_config: &WebAuthConfig, username: &str) -> Result<String, std::io::Error> {
+    let role = web_auth_config.get_user_role(username);
+    create_jwt(web_auth_config, username, vec![role.claim().to_string()])
 }
 
 pub fn create_jwt_user(web_auth_config: &WebAuthConfig, username: &str) -> Result<String, std::io::Error> {
@@ -55,49 +58,90 @@ fn has_role(token_data: Option<TokenData<Claims>>, role: &str) -> bool {
     }
 }
 
-pub fn is_admin(token_data: Option<TokenData<Claims>>) -> bool {
-    has_role(token_data, ROLE_ADMIN)
+pub fn get_web_ui_role(token_data: Option<&TokenData<Claims>>) -> Option<WebUiRole> {
+    token_data.and_then(|data| WebUiRole::from_claims(&data.claims.roles))
 }
 
 pub fn is_user(token_data: Option<TokenData<Claims>>) -> bool {
     has_role(token_data, ROLE_USER)
 }
 
-pub fn verify_token_admin(bearer: &str, secret_key: &[u8]) -> bool {
-    has_role(verify_token(bearer, secret_key), ROLE_ADMIN)
+pub fn verify_token_web_ui(bearer: &str, secret_key: &[u8]) -> Option<WebUiRole> {
+    get_web_ui_role(verify_token(bearer, secret_key).as_ref())
+}
+
+/// Username of the authenticated web ui user, added to the request extensions by the validator.
+#[derive(Debug, Clone)]
+pub struct WebUiUsername(pub String);
+
+fn verify_web_ui_user(bearer: &str, secret_key: &[u8]) -> Option<(WebUiRole, WebUiUsername)> {
+    let token_data = verify_token(bearer, secret_key)?;
+    get_web_ui_role(Some(&token_data)).map(|role| (role, WebUiUsername(token_data.claims.username)))
 }
 
 pub fn verify_token_user(bearer: &str, secret_key: &[u8]) -> bool {
     has_role(verify_token(bearer, secret_key), ROLE_USER)
 }
 
-fn validate_request(
+fn validate_request<T>(
     app_state: &Arc<AppState>,
     token: &str,
-    verify_fn: fn(&str, &[u8]) -> bool,
-) -> Result<(), ()> {
+    verify_fn: fn(&str, &[u8]) -> Option<T>,
+) -> Result<T, ()> {
     if let Some(web_auth_config) = &app_state.app_config.config.load().web_ui.as_ref().and_then(|c| c.auth.as_ref()) {
         let secret_key = web_auth_config.secret.as_ref();
-        if verify_fn(token, secret_key) {
-            return Ok(());
+        if let Some(result) = verify_fn(token, secret_key) {
+            return Ok(result);
         }
     }
     Err(())
 }
 
+/// Accepts all web ui roles, the role and the username are added to the request extensions for the role checks of the routes.
 pub async fn validator_admin(
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     AuthBearer(token): AuthBearer,
-    request: axum::extract::Request,
+    mut request: axum::extract::Request,
     next: axum::middleware::Next,
 ) -> Result<axum::response::Response, axum::http::StatusCode> {
-    match validate_request(&app_state, &token, verify_token_admin) {
-        Ok(()) => Ok(next.run(request).await),
+    match validate_request(&app_state, &token, verify_web_ui_user) {
+        Ok((role, username)) => {
+            request.extensions_mut().insert(role);
+            request.extensions_mut().insert(username);
+            Ok(next.run(request).await)
+        }
         Err(()) => Err(axum::http::StatusCode::UNAUTHORIZED)
 
     }
 }
 
+/// Without web ui authentication there is no role in the request, everything is allowed.
+fn check_role(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+    required: WebUiRole,
+) -> Result<impl Future<Output = axum::response::Response>, axum::http::StatusCode> {
+    if request.extensions().get::<WebUiRole>().is_none_or(|role| role.is_allowed(required)) {
+        Ok(next.run(request))
+    } else {
+        Err(axum::http::StatusCode::FORBIDDEN)
+    }
+}
+
+pub async fn require_operator(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Result<axum::response::Response, axum::http::StatusCode> {
+    Ok(check_role(request, next, WebUiRole::Operator)?.await)
+}
+
+pub async fn require_admin(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Result<axum::response::Response, axum::http::StatusCode> {
+    Ok(check_role(request, next, WebUiRole::Admin)?.await)
+}
+
 pub async fn validator_user(
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     AuthBearer(token): AuthBearer,
@@ -111,7 +155,7 @@ pub async fn validator_user(
             }
         }
     }
-    match validate_request(&app_state, &token, verify_token_user) {
+    match validate_request(&app_state, &token, |bearer, secret_key| verify_token_user(bearer, secret_key).then_some(())) {
         Ok(()) => Ok(next.run(request).await),
         Err(()) => Err(axum::http::StatusCode::UNAUTHORIZED)
     }
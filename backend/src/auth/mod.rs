@@ -1,16 +1,26 @@
+#[cfg(feature = "server")]
 use axum::http::StatusCode;
 
+#[cfg(feature = "server")]
 mod authenticator;
 mod password;
+#[cfg(feature = "server")]
 mod auth_bearer;
+#[cfg(feature = "server")]
 mod auth_basic;
 mod access_token;
+#[cfg(feature = "server")]
 mod fingerprint;
+#[cfg(feature = "server")]
 type Rejection = (StatusCode, &'static str);
 
+#[cfg(feature = "server")]
 pub use self::authenticator::*;
 pub use self::access_token::*;
 pub use self::password::*;
+#[cfg(feature = "server")]
 pub use self::fingerprint::*;
+#[cfg(feature = "server")]
 pub use self::auth_basic::*;
+#[cfg(feature = "server")]
 pub use self::auth_bearer::*;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::api::model::create_http_client;
+use crate::utils::request::create_http_client;
 use crate::library::metadata::{MediaMetadata, MetadataCacheEntry};
 use crate::library::metadata_resolver::MetadataResolver;
 use crate::library::metadata_storage::MetadataStorage;
@@ -3,6 +3,8 @@
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::return_self_not_must_use)]
 #![allow(clippy::missing_errors_doc)]
+// the code used by the server or the web ui is not gated item by item, a slim build leaves it unused
+#![cfg_attr(not(all(feature = "server", feature = "web-ui")), allow(dead_code, unused_imports, unused_macros))]
 
 #[macro_use]
 mod modules;
@@ -12,12 +14,12 @@ include_modules!();
 use crate::auth::generate_password;
 use crate::library::LibraryProcessor;
 use crate::model::{
-    AppConfig, Config, Healthcheck, HealthcheckConfig, ProcessTargets, SourcesConfig,
+    AppConfig, Config, Healthcheck, HealthcheckConfig, ProcessTargets, RuntimeBootConfig, RuntimeConfig, SourcesConfig,
 };
 use crate::processing::processor::playlist;
 use crate::utils::request::create_client;
 use crate::utils::{config_file_reader, resolve_env_var};
-use crate::utils::{db_viewer, init_logger};
+use crate::utils::{db_viewer, init_crash_reporter, init_logger};
 use arc_swap::access::Access;
 use arc_swap::ArcSwap;
 use chrono::{DateTime, Utc};
@@ -86,6 +88,22 @@ struct Args {
     #[arg(long = "force-library-rescan", default_value_t = false, default_missing_value = "true")]
     force_library_rescan: bool,
 
+    /// Run in server mode with generated demo providers, channels, epg and users
+    #[arg(long = "demo", default_value_t = false, default_missing_value = "true")]
+    demo: bool,
+
+    /// Check the config, source, mapping and api-proxy files and print the found problems
+    #[arg(long = "check-config", default_value_t = false, default_missing_value = "true")]
+    check_config: bool,
+
+    /// Encrypt a secret into the secrets file, it is referenced with `!secret <name>` in the config files
+    #[arg(long = "encrypt-secret", value_name = "NAME")]
+    encrypt_secret: Option<String>,
+
+    /// Install, uninstall or run tuliprox as system service (windows service, macos launchd)
+    #[arg(long = "service", value_enum, value_name = "ACTION")]
+    service: Option<utils::ServiceAction>,
+
     #[arg(long = "dbx")]
     db_xtream_file_name: Option<String>,
 
@@ -107,10 +125,70 @@ const BUILD_TIMESTAMP: &str = env!("VERGEN_BUILD_TIMESTAMP");
 // #[export_name = "malloc_conf"]
 // pub static malloc_conf: &[u8] = b"lg_prof_interval:25,prof:true,prof_leak:true,prof_active:true,prof_prefix:/tmp/jeprof\0";
 
-#[tokio::main]
-async fn main() {
+#[cfg(feature = "profiling")]
+#[global_allocator]
+static ALLOC: tools::alloc_stats::CountingAllocator = tools::alloc_stats::CountingAllocator;
+
+fn main() {
     let args = Args::parse();
+    let result = match args.service {
+        None => {
+            run_main(args);
+            Ok(())
+        }
+        Some(utils::ServiceAction::Install) => utils::install_service(&service_args(&args)),
+        Some(utils::ServiceAction::Uninstall) => utils::uninstall_service(),
+        Some(utils::ServiceAction::Run) => utils::run_service(move || run_main(args)),
+    };
+    if let Err(err) = result {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
 
+fn run_main(args: Args) {
+    let runtime = read_runtime_config(&args).build_runtime().unwrap_or_else(|err| {
+        eprintln!("Failed to create runtime: {err}");
+        std::process::exit(1);
+    });
+    runtime.block_on(run(args));
+    // background tasks can still hold blocking threads after the server stopped
+    runtime.shutdown_timeout(std::time::Duration::from_secs(1));
+}
+
+/// The service is started from another working directory, the paths are passed as absolute paths.
+fn service_args(args: &Args) -> Vec<String> {
+    let absolute = |path: &str| std::path::absolute(path).map_or_else(|_| path.to_string(), |p| p.to_string_lossy().to_string());
+    let mut service_args = vec!["--service".to_string(), "run".to_string()];
+    for (flag, path) in [("-p", &args.config_path), ("-c", &args.config_file), ("-i", &args.source_file),
+                         ("-m", &args.mapping_file), ("-a", &args.api_proxy)] {
+        if let Some(path) = path {
+            service_args.push(flag.to_string());
+            service_args.push(absolute(path));
+        }
+    }
+    if let Some(log_level) = args.log_level.as_ref() {
+        service_args.push("-l".to_string());
+        service_args.push(log_level.clone());
+    }
+    service_args
+}
+
+/// The runtime is created before the logger and the config are initialized,
+/// the `runtime` section is read on its own and errors fall back to the defaults.
+fn read_runtime_config(args: &Args) -> RuntimeConfig {
+    if args.demo {
+        return RuntimeConfig::default();
+    }
+    let config_file = get_file_paths(args).config_file_path;
+    File::open(config_file).ok()
+        .and_then(|file| serde_saphyr::from_reader::<_, RuntimeBootConfig>(config_file_reader(file, true)).ok())
+        .and_then(|config| config.runtime)
+        .map(|runtime| RuntimeConfig::from(&runtime))
+        .unwrap_or_default()
+}
+
+async fn run(args: Args) {
     db_viewer(args.db_xtream_file_name.as_deref(), args.db_m3u_file_name.as_deref(), args.db_epg_file_name.as_deref());
 
     if args.genpwd {
@@ -121,7 +199,20 @@ async fn main() {
         return;
     }
 
-    let mut config_paths = get_file_paths(&args);
+    let mut config_paths = if args.demo {
+        utils::create_demo_config().unwrap_or_else(|err| {
+            eprintln!("Failed to create demo config: {err}");
+            std::process::exit(1);
+        })
+    } else {
+        get_file_paths(&args)
+    };
+    let server_mode = args.server || args.demo || args.service.is_some();
+
+    if let Some(name) = args.encrypt_secret.as_deref() {
+        encrypt_secret_cli(&config_paths.config_path, name);
+        return;
+    }
 
     init_logger(
         args.log_level.as_deref(),
@@ -133,6 +224,13 @@ async fn main() {
         std::process::exit(i32::from(!healthy));
     }
 
+    if args.check_config {
+        let valid = check_config_cli(&config_paths).await;
+        std::process::exit(i32::from(!valid));
+    }
+
+    init_crash_reporter(config_paths.config_file_path.as_str());
+
     // Handle Library scan before starting main application
     if args.scan_library || args.force_library_rescan {
         info!("Library scan mode requested");
@@ -150,17 +248,20 @@ async fn main() {
     {
         info!("Build time: {bts}");
     }
-    let app_config = utils::read_initial_app_config(&mut config_paths, true, true, args.server)
+    let app_config = utils::read_initial_app_config(&mut config_paths, true, true, server_mode)
         .await
         .unwrap_or_else(|err| exit!("{err}"));
     print_info(&app_config);
+    if args.demo {
+        info!("Demo mode: web ui http://localhost:{} login {}/{}", utils::DEMO_PORT, utils::DEMO_USERNAME, utils::DEMO_PASSWORD);
+    }
 
     let sources = <Arc<ArcSwap<SourcesConfig>> as Access<SourcesConfig>>::load(&app_config.sources);
     let targets = sources
         .validate_targets(args.target.as_ref())
         .unwrap_or_else(|err| exit!("{err}"));
 
-    if args.server {
+    if server_mode {
         start_in_server_mode(Arc::new(app_config), Arc::new(targets)).await;
     } else {
         start_in_cli_mode(Arc::new(app_config), Arc::new(targets)).await;
@@ -175,6 +276,7 @@ fn print_info(app_config: &AppConfig) {
         chrono::offset::Local::now().format("%Y-%m-%d %H:%M:%S")
     );
     info!("Temp dir: {}", tempfile::env::temp_dir().display());
+    info!("Worker threads: {}", tokio::runtime::Handle::current().metrics().num_workers());
     info!("Working dir: {:?}", &config.working_dir);
     info!("Config dir: {:?}", &paths.config_path);
     info!("Config file: {:?}", &paths.config_file_path);
@@ -224,12 +326,48 @@ async fn start_in_cli_mode(cfg: Arc<AppConfig>, targets: Arc<ProcessTargets>) {
     playlist::exec_processing(&client, cfg, targets, None, None, None, None).await;
 }
 
+#[cfg(feature = "server")]
 async fn start_in_server_mode(cfg: Arc<AppConfig>, targets: Arc<ProcessTargets>) {
     if let Err(err) = api::main_api::start_server(cfg, targets).await {
         exit!("Can't start server: {err}");
     }
 }
 
+// without the feature the api module and its dependencies like axum are not compiled into the binary
+#[cfg(not(feature = "server"))]
+#[allow(clippy::unused_async)]
+async fn start_in_server_mode(_cfg: Arc<AppConfig>, _targets: Arc<ProcessTargets>) {
+    exit!("Server mode is not available, tuliprox was built without the `server` feature");
+}
+
+fn encrypt_secret_cli(config_path: &str, name: &str) {
+    let result = rpassword::prompt_password(format!("{name}> "))
+        .map_err(|err| err.to_string())
+        .and_then(|value| utils::store_secret(config_path, name, &value).map_err(|err| err.to_string()));
+    match result {
+        Ok(path) => println!("Secret {name} stored in {}, reference it with: !secret {name}", path.display()),
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn check_config_cli(config_paths: &ConfigPaths) -> bool {
+    let diagnostics = utils::check_config(config_paths).await;
+    for diagnostic in &diagnostics {
+        println!("{diagnostic}");
+    }
+    let errors = diagnostics.iter().filter(|d| d.level == utils::ConfigDiagnosticLevel::Error).count();
+    let warnings = diagnostics.len() - errors;
+    if diagnostics.is_empty() {
+        println!("Config check passed");
+    } else {
+        println!("Config check: {errors} error(s), {warnings} warning(s)");
+    }
+    errors == 0
+}
+
 async fn scan_library_cli(app_config: &AppConfig, force_rescan: bool) {
     info!("Starting Library scan from CLI (force_rescan: {force_rescan})");
 
@@ -1,5 +1,6 @@
-use crate::model::{AppConfig, InputSource, MessagingConfig, MessageContent, TemplateContext};
-use crate::utils::{telegram_create_instance, telegram_send_message, SendMessageOption, SendMessageParseMode};
+use crate::model::{AppConfig, InputSource, MessagingConfig, MessageContent, TemplateContext, WebPushMessagingConfig, WebhookMessagingConfig};
+use crate::repository::{load_web_push_key, load_web_push_subscriptions, remove_web_push_subscriptions};
+use crate::utils::{telegram_create_instance, telegram_send_message, web_push_encrypt, SendMessageOption, SendMessageParseMode};
 use chrono::Utc;
 use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
 use log::{debug, error};
@@ -13,6 +14,11 @@ use std::str::FromStr;
 use std::sync::{Arc, LazyLock};
 use crate::utils::request::download_text_content;
 
+/// Push services keep undelivered web push messages for one day.
+const WEB_PUSH_TTL_SECS: u32 = 86_400;
+/// Longer message texts are cut, the encrypted payload is limited to 4KB.
+const WEB_PUSH_BODY_MAX_LEN: usize = 3000;
+
 fn is_enabled(kind: MsgKind, cfg: &MessagingConfig) -> bool {
     cfg.notify_on.contains(&kind)
 }
@@ -40,6 +46,7 @@ async fn render_template(app_config: &Arc<AppConfig>, http_client: &reqwest::Cli
         message: None,
         stats: None,
         watch: None,
+        changes: None,
         processing: None,
         flat_stats: None,
     };
@@ -55,6 +62,13 @@ async fn render_template(app_config: &Arc<AppConfig>, http_client: &reqwest::Cli
             template_context.processing = Some(stats.clone());
             if let Some(stats) = &stats.stats {
                 template_context.stats = Some(stats);
+                let changes: Vec<String> = stats.iter()
+                    .flat_map(|source| source.targets.iter())
+                    .filter_map(|target| target.changes.filter(|changes| !changes.is_empty()).map(|changes| format!("{}: {changes}", target.name)))
+                    .collect();
+                if !changes.is_empty() {
+                    template_context.changes = Some(changes);
+                }
                 if let Some(first_source) = stats.first() {
                     if let Some(first_input) = first_source.inputs.first() {
                         template_context.flat_stats = Some(first_input.clone());
@@ -124,6 +138,43 @@ async fn send_rest_message(app_config: &Arc<AppConfig>, client: &reqwest::Client
     }
 }
 
+async fn send_webhook(app_config: &Arc<AppConfig>, client: &reqwest::Client, content: &MessageContent, webhook: &WebhookMessagingConfig) {
+    let kind = content.kind();
+    let method = Method::from_str(&webhook.method).unwrap_or(Method::POST);
+    let mut rb = client.request(method.clone(), &webhook.url);
+    for (key, value) in &webhook.headers {
+        rb = rb.header(key, value);
+    }
+    if method != Method::GET {
+        let body = render_template(app_config, client, webhook.get_template(kind), content).await;
+        let has_content_type = webhook.headers.keys().any(|k| k.eq_ignore_ascii_case("content-type"));
+        if !has_content_type {
+            rb = rb.header(header::CONTENT_TYPE, mime::APPLICATION_JSON.to_string());
+        }
+        rb = rb.body(body);
+    }
+
+    let name = webhook.display_name();
+    match rb.send().await {
+        Ok(response) => {
+            if response.status().is_success() {
+                debug!("Message sent successfully to webhook {name}");
+            } else {
+                error!("Failed to send message to webhook {name}, status code {}", response.status());
+            }
+        }
+        Err(e) => error!("Message wasn't sent to webhook {name} because of: {e}"),
+    }
+}
+
+async fn send_webhook_messages(app_config: &Arc<AppConfig>, client: &reqwest::Client, content: &MessageContent, messaging: &MessagingConfig) {
+    let kind = content.kind();
+    let requests = messaging.webhooks.iter()
+        .filter(|webhook| webhook.is_enabled(kind))
+        .map(|webhook| send_webhook(app_config, client, content, webhook));
+    futures::future::join_all(requests).await;
+}
+
 async fn send_discord_message(app_config: &Arc<AppConfig>, client: &reqwest::Client, content: &MessageContent, messaging: &MessagingConfig) {
     if let Some(discord) = &messaging.discord {
         let kind = content.kind();
@@ -234,6 +285,110 @@ async fn send_pushover_message(_app_config: &Arc<AppConfig>, client: &reqwest::C
     }
 }
 
+fn truncate_at_char_boundary(text: &str, max_len: usize) -> &str {
+    if text.len() <= max_len {
+        return text;
+    }
+    let mut end = max_len;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn create_web_push_payload(content: &MessageContent) -> String {
+    let kind = content.kind();
+    let body = match content {
+        MessageContent::Info(s) | MessageContent::Error(s) => Cow::Borrowed(s.as_str()),
+        MessageContent::Watch(s) => Cow::Owned(serde_json::to_string(s).unwrap_or_default()),
+        MessageContent::ProcessingStats(ps) => match ps.errors.as_ref() {
+            Some(errors) => Cow::Borrowed(errors.as_str()),
+            None => Cow::Owned(serde_json::to_string(ps).unwrap_or_default()),
+        },
+    };
+    json!({
+        "title": format!("Tuliprox {kind}"),
+        "body": truncate_at_char_boundary(&body, WEB_PUSH_BODY_MAX_LEN),
+        "kind": kind.to_string(),
+        "timestamp": Utc::now().to_rfc3339(),
+    }).to_string()
+}
+
+/// Sends the message to all stored web push subscriptions and returns the number of delivered messages.
+/// Subscriptions rejected by the push service as expired are removed.
+pub async fn send_web_push_notification(app_config: &Arc<AppConfig>, client: &reqwest::Client, content: &MessageContent, web_push: &WebPushMessagingConfig) -> usize {
+    let subscriptions = load_web_push_subscriptions(app_config).await;
+    if subscriptions.is_empty() {
+        return 0;
+    }
+    let vapid_key = match load_web_push_key(app_config).await {
+        Ok(key) => key,
+        Err(err) => {
+            error!("Message wasn't sent to web push because of: {err}");
+            return 0;
+        }
+    };
+    let payload = create_web_push_payload(content);
+    let urgency = if content.kind() == MsgKind::Error { "high" } else { "normal" };
+
+    let requests = subscriptions.iter().map(|subscription| async {
+        let request = web_push_encrypt(subscription, payload.as_bytes())
+            .and_then(|body| vapid_key.authorization_header(&subscription.endpoint, &web_push.subject).map(|auth| (body, auth)));
+        let (body, authorization) = match request {
+            Ok(request) => request,
+            Err(err) => {
+                error!("Message wasn't sent to web push because of: {err}");
+                return (subscription.endpoint.clone(), None);
+            }
+        };
+        let result = client.post(&subscription.endpoint)
+            .header(header::AUTHORIZATION, authorization)
+            .header(header::CONTENT_ENCODING, "aes128gcm")
+            .header(header::CONTENT_TYPE, mime::APPLICATION_OCTET_STREAM.to_string())
+            .header("TTL", WEB_PUSH_TTL_SECS.to_string())
+            .header("Urgency", urgency)
+            .body(body)
+            .send()
+            .await;
+        match result {
+            Ok(response) => (subscription.endpoint.clone(), Some(response.status())),
+            Err(e) => {
+                error!("Message wasn't sent to web push because of: {e}");
+                (subscription.endpoint.clone(), None)
+            }
+        }
+    });
+
+    let mut delivered = 0;
+    let mut expired = Vec::new();
+    for (endpoint, status) in futures::future::join_all(requests).await {
+        match status {
+            Some(status) if status.is_success() => delivered += 1,
+            Some(status) if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE => {
+                debug!("Removing expired web push subscription, status code {status}");
+                expired.push(endpoint);
+            }
+            Some(status) => error!("Failed to send message to web push, status code {status}"),
+            None => {}
+        }
+    }
+    if !expired.is_empty() {
+        if let Err(err) = remove_web_push_subscriptions(app_config, &expired).await {
+            error!("{err}");
+        }
+    }
+    if delivered > 0 {
+        debug!("Message sent successfully to {delivered} web push subscriptions");
+    }
+    delivered
+}
+
+async fn send_web_push_message(app_config: &Arc<AppConfig>, client: &reqwest::Client, content: &MessageContent, messaging: &MessagingConfig) {
+    if let Some(web_push) = &messaging.web_push {
+        send_web_push_notification(app_config, client, content, web_push).await;
+    }
+}
+
 async fn dispatch_send_message(app_config: &Arc<AppConfig>, client: &reqwest::Client, content: MessageContent) {
     let cfg = app_config.config.load();
     let msg_cfg = cfg.messaging.as_ref();
@@ -244,7 +399,9 @@ async fn dispatch_send_message(app_config: &Arc<AppConfig>, client: &reqwest::Cl
                 send_telegram_message(app_config, client, &content, messaging),
                 send_rest_message(app_config, client, &content, messaging),
                 send_pushover_message(app_config, client, &content, messaging),
-                send_discord_message(app_config, client, &content, messaging)
+                send_discord_message(app_config, client, &content, messaging),
+                send_web_push_message(app_config, client, &content, messaging),
+                send_webhook_messages(app_config, client, &content, messaging)
             );
         }
     }
@@ -264,6 +421,9 @@ async fn resolve_template<'a>(app_config: &'a Arc<AppConfig>, http_client: &'a r
         password: None,
         method: InputFetchMethod::GET,
         headers: HashMap::default(),
+        header_rules: None,
+        basic_auth: None,
+        impersonate: None,
     };
     if let Ok((content, _response_url)) = download_text_content(
         app_config,
@@ -333,6 +493,47 @@ mod tests {
         assert_eq!(output, "Error: test error");
     }
 
+    #[tokio::test]
+    async fn test_render_template_playlist_changes() {
+        use shared::model::{PlaylistChangeSummary, SourceStats, TargetStats};
+
+        let changes = PlaylistChangeSummary { added: 12, removed: 3, ..PlaylistChangeSummary::default() };
+        let source_stats = SourceStats {
+            inputs: vec![],
+            targets: vec![TargetStats::success("Target 1").with_changes(Some(changes)), TargetStats::success("Target 2").with_changes(Some(PlaylistChangeSummary::default()))],
+        };
+        let content = MessageContent::event_stats(vec![source_stats]);
+        let app_cfg = create_app_config();
+        let client = reqwest::Client::new();
+        let output = render_template(&app_cfg, &client, Some("{{#each changes}}{{this}};{{/each}}{{stats.0.targets.0.changes.added}}"), &content).await;
+        assert_eq!(output, "Target 1: 12 channels added, 3 removed;12");
+    }
+
+    #[tokio::test]
+    async fn test_render_webhook_template() {
+        let webhook = WebhookMessagingConfig {
+            name: "ntfy".to_string(),
+            url: "https://ntfy.sh/tuliprox".to_string(),
+            method: "POST".to_string(),
+            headers: HashMap::new(),
+            body: Some(r#"{"text": "{{json_escape message}}"}"#.to_string()),
+            templates: HashMap::from([(MsgKind::Error, "Error: {{message}}".to_string())]),
+            notify_on: vec![MsgKind::Info, MsgKind::Error],
+        };
+        assert!(webhook.is_enabled(MsgKind::Info));
+        assert!(!webhook.is_enabled(MsgKind::Watch));
+
+        let app_cfg = create_app_config();
+        let client = reqwest::Client::new();
+        let content = MessageContent::Info("Line 1\n\"quoted\"".to_string());
+        let output = render_template(&app_cfg, &client, webhook.get_template(content.kind()), &content).await;
+        assert_eq!(output, r#"{"text": "Line 1\n\"quoted\""}"#);
+
+        let content = MessageContent::Error("failed".to_string());
+        let output = render_template(&app_cfg, &client, webhook.get_template(content.kind()), &content).await;
+        assert_eq!(output, "Error: failed");
+    }
+
     #[tokio::test]
     async fn test_render_discord_template() {
         use shared::model::{SourceStats, InputStats, InputType, PlaylistStats, TargetStats};
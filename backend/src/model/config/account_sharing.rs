@@ -0,0 +1,67 @@
+use crate::model::macros;
+use shared::model::{AccountSharingAction, AccountSharingConfigDto, AccountSharingLimitsDto};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountSharingLimits {
+    pub max_ips: u16,
+    pub max_countries: u16,
+    pub max_devices: u16,
+}
+
+impl AccountSharingLimits {
+    /// The limits of the user override the limits of the reverse proxy.
+    pub fn with_overrides(&self, overrides: Option<&AccountSharingLimitsDto>) -> Self {
+        match overrides {
+            None => *self,
+            Some(limits) => Self {
+                max_ips: limits.max_ips.unwrap_or(self.max_ips),
+                max_countries: limits.max_countries.unwrap_or(self.max_countries),
+                max_devices: limits.max_devices.unwrap_or(self.max_devices),
+            },
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_ips == 0 && self.max_countries == 0 && self.max_devices == 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountSharingConfig {
+    pub enabled: bool,
+    pub window_secs: u64,
+    pub limits: AccountSharingLimits,
+    pub action: AccountSharingAction,
+    pub block_secs: u64,
+}
+
+macros::from_impl!(AccountSharingConfig);
+impl From<&AccountSharingConfigDto> for AccountSharingConfig {
+    fn from(dto: &AccountSharingConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            window_secs: dto.window_secs,
+            limits: AccountSharingLimits {
+                max_ips: dto.max_ips,
+                max_countries: dto.max_countries,
+                max_devices: dto.max_devices,
+            },
+            action: dto.action,
+            block_secs: dto.block_secs,
+        }
+    }
+}
+
+impl From<&AccountSharingConfig> for AccountSharingConfigDto {
+    fn from(instance: &AccountSharingConfig) -> Self {
+        Self {
+            enabled: instance.enabled,
+            window_secs: instance.window_secs,
+            max_ips: instance.limits.max_ips,
+            max_countries: instance.limits.max_countries,
+            max_devices: instance.limits.max_devices,
+            action: instance.action,
+            block_secs: instance.block_secs,
+        }
+    }
+}
@@ -6,6 +6,8 @@ pub struct ConfigApi {
     pub host: String,
     pub port: u16,
     pub web_root: String,
+    pub debug_endpoints: bool,
+    pub shutdown_grace_secs: u16,
 }
 
 macros::from_impl!(ConfigApi);
@@ -15,6 +17,8 @@ impl From<&ConfigApiDto> for ConfigApi {
             host:dto.host.clone(),
             port: dto.port,
             web_root: dto.web_root.clone(),
+            debug_endpoints: dto.debug_endpoints,
+            shutdown_grace_secs: dto.shutdown_grace_secs,
         }
     }
 }
@@ -25,6 +29,8 @@ impl From<&ConfigApi> for ConfigApiDto {
             host: instance.host.clone(),
             port: instance.port,
             web_root: instance.web_root.clone(),
+            debug_endpoints: instance.debug_endpoints,
+            shutdown_grace_secs: instance.shutdown_grace_secs,
         }
     }
 }
\ No newline at end of file
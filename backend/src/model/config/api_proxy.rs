@@ -13,7 +13,7 @@ use crate::utils::file_exists_async;
 const API_USER: &str = "api";
 const TEST_USER: &str = "test";
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ApiProxyServerInfo {
     pub name: String,
     pub protocol: String,
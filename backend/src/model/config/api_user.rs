@@ -1,10 +1,12 @@
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
-use crate::model::{macros, Config};
+use crate::model::{is_country_denied, macros, Config};
 use arc_swap::access::Access;
 use arc_swap::ArcSwap;
 use chrono::Local;
-use log::debug;
-use shared::model::{ProxyType, ProxyUserCredentialsDto, ProxyUserStatus, TargetUserDto, UserConnectionPermission};
+use log::{debug, info};
+use shared::model::{AccountSharingLimitsDto, ProxyType, ProxyUserCredentialsDto, ProxyUserStatus, TargetUserDto, UserConnectionPermission};
+use shared::utils::strip_port;
 use std::sync::Arc;
 use zeroize::Zeroize;
 
@@ -22,6 +24,9 @@ pub struct ProxyUserCredentials {
     pub status: Option<ProxyUserStatus>,
     pub ui_enabled: bool,
     pub comment: Option<String>,
+    pub allowed_countries: Vec<String>,
+    pub blocked_countries: Vec<String>,
+    pub account_sharing: Option<AccountSharingLimitsDto>,
 }
 
 macros::from_impl!(ProxyUserCredentials);
@@ -40,6 +45,9 @@ impl From<&ProxyUserCredentialsDto> for ProxyUserCredentials {
             status: dto.status,
             ui_enabled: dto.ui_enabled,
             comment: dto.comment.clone(),
+            allowed_countries: dto.allowed_countries.clone(),
+            blocked_countries: dto.blocked_countries.clone(),
+            account_sharing: dto.account_sharing.clone(),
         }
     }
 }
@@ -59,6 +67,9 @@ impl From<&ProxyUserCredentials> for ProxyUserCredentialsDto {
             status: instance.status,
             ui_enabled: instance.ui_enabled,
             comment: instance.comment.clone(),
+            allowed_countries: instance.allowed_countries.clone(),
+            blocked_countries: instance.blocked_countries.clone(),
+            account_sharing: instance.account_sharing.clone(),
         }
     }
 }
@@ -75,6 +86,7 @@ impl ProxyUserCredentials {
         self.username.eq(username) && self.password.eq(password)
     }
 
+    #[cfg(feature = "server")]
     pub fn has_permissions(&self, app_state: &AppState) -> bool {
         let config = <Arc<ArcSwap<Config>> as Access<Config>>::load(&app_state.app_config.config);
         if config.user_access_control {
@@ -96,11 +108,40 @@ impl ProxyUserCredentials {
         true
     }
 
+    #[cfg(feature = "server")]
     #[inline]
     pub fn permission_denied(&self, app_state: &AppState) -> bool {
         !self.has_permissions(app_state)
     }
 
+    #[cfg(feature = "server")]
+    /// Checks the country of the client against the countries of the user and the reverse proxy,
+    /// denied requests are logged and counted. Without a loaded geoip db nothing is denied.
+    pub fn country_denied(&self, app_state: &AppState, client_ip: &str) -> bool {
+        let config = <Arc<ArcSwap<Config>> as Access<Config>>::load(&app_state.app_config.config);
+        let geoip_config = config.reverse_proxy.as_ref().and_then(|reverse_proxy| reverse_proxy.geoip.as_ref());
+        let has_policy = !self.allowed_countries.is_empty() || !self.blocked_countries.is_empty()
+            || geoip_config.is_some_and(|geoip| !geoip.allowed_countries.is_empty() || !geoip.blocked_countries.is_empty());
+        if !has_policy {
+            return false;
+        }
+        let Some(geoip) = app_state.geoip.load_full() else {
+            return false;
+        };
+        let country = geoip.lookup(&strip_port(client_ip));
+        let denied = is_country_denied(&self.allowed_countries, &self.blocked_countries, country.as_deref())
+            || geoip_config.is_some_and(|geoip| geoip.is_country_denied(country.as_deref()));
+        if denied {
+            let country = country.as_deref().unwrap_or("unknown");
+            info!("User access denied, country {country} not allowed for user: {}", self.username);
+            app_state.geoip_access.record_denied(country);
+        } else {
+            debug!("User access granted for country {} for user: {}", country.as_deref().unwrap_or("unknown"), self.username);
+        }
+        denied
+    }
+
+    #[cfg(feature = "server")]
     pub async fn connection_permission(&self, app_state: &AppState) -> UserConnectionPermission {
         let config = <Arc<ArcSwap<Config>> as Access<Config>>::load(&app_state.app_config.config);
         if self.max_connections > 0 && config.user_access_control {
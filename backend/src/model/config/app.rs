@@ -1,8 +1,8 @@
 use crate::api::model::TransportStreamBuffer;
-use crate::model::{ApiProxyConfig, ApiProxyServerInfo, Config, ConfigInput, ConfigInputOptions, ConfigTarget, CustomStreamResponse, GracePeriodOptions, HdHomeRunConfig, Mappings, ProxyUserCredentials, ReverseProxyDisabledHeaderConfig, SourcesConfig, TargetOutput};
+use crate::model::{ApiProxyConfig, ApiProxyServerInfo, Config, ConfigInput, ConfigInputOptions, InputEgress, InputTlsConfig, ConfigTarget, CustomStreamResponse, GracePeriodOptions, HdHomeRunConfig, HdHomeRunTargetOutput, Mappings, PlexTargetOutput, ProxyUserCredentials, ReverseProxyDisabledHeaderConfig, SourcesConfig, TargetOutput};
 use crate::utils;
 use arc_swap::{ArcSwap, ArcSwapOption};
-use log::{error, warn};
+use log::{debug, error, warn};
 use rand::Rng;
 use shared::info_err_res;
 use shared::error::{TuliproxError, TuliproxErrorKind};
@@ -100,21 +100,24 @@ impl AppConfig {
         let sources = self.sources.load();
         for source in &sources.sources {
             for target in &source.targets {
+                for media_server in &target.media_servers {
+                    self.check_username(Some(&media_server.username), &target.name)?;
+                }
                 for output in &target.output {
                     match output {
                         TargetOutput::Xtream(_) | TargetOutput::M3u(_) => {}
                         TargetOutput::Strm(strm_output) => {
                             self.check_username(strm_output.username.as_deref(), &target.name)?;
                         }
-                        TargetOutput::HdHomeRun(hdhomerun_output) => {
+                        TargetOutput::HdHomeRun(HdHomeRunTargetOutput { device: hdhr_name, username, .. })
+                        | TargetOutput::Plex(PlexTargetOutput { device: hdhr_name, username, .. }) => {
                             if check_homerun {
-                                let hdhr_name = &hdhomerun_output.device;
-                                self.check_username(Some(&hdhomerun_output.username), &target.name)?;
+                                self.check_username(Some(username), &target.name)?;
                                 if let Some(old_hdhomerun) = self.hdhomerun.load().clone() {
                                     let mut hdhomerun = (*old_hdhomerun).clone();
                                     for device in &mut hdhomerun.devices {
                                         if &device.name == hdhr_name {
-                                            device.t_username.clone_from(&hdhomerun_output.username);
+                                            device.t_username.clone_from(username);
                                             device.t_enabled = true;
                                         }
                                     }
@@ -143,6 +146,44 @@ impl AppConfig {
         config.reverse_proxy.as_ref().is_none_or(|r| !r.resource_rewrite_disabled)
     }
 
+    /// Logos are rewritten to the resource endpoints also for users with the `redirect` proxy type.
+    pub fn is_logo_proxy_enabled(&self) -> bool {
+        let config = self.config.load();
+        config.reverse_proxy.as_ref().is_some_and(|r| !r.resource_rewrite_disabled
+            && r.cache.as_ref().is_some_and(|c| c.enabled && c.proxy_logos))
+    }
+
+    /// Width to which cached logos are downscaled, 0 if logos are not resized.
+    pub fn get_logo_width(&self) -> u16 {
+        let config = self.config.load();
+        config.reverse_proxy.as_ref()
+            .and_then(|r| r.cache.as_ref())
+            .filter(|c| c.enabled)
+            .map_or(0, |c| c.logo_width)
+    }
+
+    /// Days the resources are kept in the cache, 0 if they are only evicted by the cache size.
+    pub fn get_cache_max_age_days(&self) -> u16 {
+        let config = self.config.load();
+        config.reverse_proxy.as_ref()
+            .and_then(|r| r.cache.as_ref())
+            .filter(|c| c.enabled)
+            .map_or(0, |c| c.max_age_days)
+    }
+
+    /// Seconds the xtream stream list responses are cached, 0 if they are not cached.
+    pub fn get_xtream_api_cache_ttl_secs(&self) -> u32 {
+        let config = self.config.load();
+        config.reverse_proxy.as_ref()
+            .and_then(|r| r.cache.as_ref())
+            .map_or(0, |c| c.xtream_api_ttl_secs)
+    }
+
+    pub fn is_reverse_proxy_compression_enabled(&self) -> bool {
+        let config = self.config.load();
+        config.reverse_proxy.as_ref().is_none_or(|r| !r.compression_disabled)
+    }
+
     pub fn get_reverse_proxy_rewrite_secret(&self) -> Option<[u8; 16]> {
         let config = self.config.load();
         config.reverse_proxy.as_ref().map(|r| r.rewrite_secret)
@@ -155,6 +196,10 @@ impl AppConfig {
                 for source in &sources.sources {
                     for target in &source.targets {
                         if target_name.eq_ignore_ascii_case(&target.name) {
+                            if target.is_shadow() {
+                                debug!("User {} is assigned to the shadow target {}, shadow targets are only served by the preview", user.username, target.name);
+                                return None;
+                            }
                             return Some((user, Arc::clone(target)));
                         }
                     }
@@ -211,6 +256,27 @@ impl AppConfig {
         None
     }
 
+    fn find_input_or_alias<F, R>(&self, input_name: &str, f: F) -> Option<R>
+    where
+        F: FnOnce(&ConfigInput) -> Option<R>,
+    {
+        let sources = self.sources.load();
+        sources.inputs.iter()
+            .find(|input| &*input.name == input_name
+                || input.aliases.as_ref().is_some_and(|aliases| aliases.iter().any(|alias| &*alias.name == input_name)))
+            .and_then(|input| f(input))
+    }
+
+    /// Client certificates of the input or the input of the alias.
+    pub fn get_input_tls_config(&self, input_name: &str) -> Option<InputTlsConfig> {
+        self.find_input_or_alias(input_name, |input| input.get_tls_config().cloned())
+    }
+
+    /// Egress interface or address of the input or the input of the alias.
+    pub fn get_input_egress(&self, input_name: &str) -> Option<InputEgress> {
+        self.find_input_or_alias(input_name, |input| input.egress.clone())
+    }
+
     pub fn get_input_options_by_name(&self, input_name: &Arc<str>) -> Option<ConfigInputOptions> {
         let sources = self.sources.load();
         for input in &sources.inputs {
@@ -296,7 +362,7 @@ impl AppConfig {
         if include_computed {
             self.access_token_secret = generate_secret();
             self.encrypt_secret = <&[u8] as TryInto<[u8; 16]>>::try_into(&generate_secret()[0..16]).map_err(|err| TuliproxError::new(TuliproxErrorKind::Info, err.to_string()))?;
-            self.prepare_paths();
+            self.prepare_mapping_path();
         } else {
             self.prepare_mapping_path();
         }
@@ -334,7 +400,8 @@ impl AppConfig {
         self.prepare_custom_stream_response();
     }
 
-    fn prepare_custom_stream_response(&self) {
+    /// The custom stream responses are only used in server mode.
+    pub(crate) fn prepare_custom_stream_response(&self) {
         let config = self.config.load();
         if let Some(custom_stream_response_path) = config.custom_stream_response_path.as_ref() {
             fn load_and_set_file(file_path: &Path) -> Option<TransportStreamBuffer> {
@@ -414,6 +481,15 @@ impl AppConfig {
         self.get_server_info(server_info_name)
     }
 
+    /// Base url of the urls in the playlists of the target, see `ConfigPublicUrl`.
+    pub fn get_target_base_url(&self, target: &ConfigTarget, user: &ProxyUserCredentials) -> String {
+        let server_base_url = self.get_user_server_info(user).get_base_url();
+        match target.public_url.as_ref() {
+            Some(public_url) => public_url.get_base_url(&user.username, &server_base_url),
+            None => server_base_url,
+        }
+    }
+
     pub fn get_disabled_headers(&self) -> Option<ReverseProxyDisabledHeaderConfig> {
         let config = self.config.load();
         config.get_disabled_headers()
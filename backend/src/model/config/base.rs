@@ -1,11 +1,13 @@
-use crate::model::{macros, ConfigApi, LibraryConfig, ReverseProxyConfig, ReverseProxyDisabledHeaderConfig, ScheduleConfig};
-use crate::model::{HdHomeRunConfig, IpCheckConfig, LogConfig, MessagingConfig, ProxyConfig, VideoConfig, WebUiConfig};
+use crate::model::{macros, ConfigApi, HlsRemuxConfig, LibraryConfig, ReverseProxyConfig, ReverseProxyDisabledHeaderConfig, ScheduleConfig,
+                   ScheduleRetryConfig};
+use crate::model::{ChannelProbeConfig, UserExpirationConfig, HdHomeRunConfig, IpCheckConfig, LogConfig, MappingReprocessConfig, MessagingConfig, NetworkRouteConfig, ProxyConfig, RuntimeConfig, VideoConfig, WebUiConfig, ClusterConfig};
 use crate::utils;
+use crate::repository::set_verify_storage_checksums;
 use log::{error, info};
 use path_clean::PathClean;
 use shared::error::TuliproxError;
-use shared::model::{ConfigDto, HdHomeRunDeviceOverview};
-use shared::utils::{default_grace_period_millis, default_grace_period_timeout_secs, set_sanitize_sensitive_info};
+use shared::model::{ConfigDto, HdHomeRunDeviceOverview, IpAnonymization};
+use shared::utils::{default_grace_period_millis, default_grace_period_timeout_secs, set_ip_anonymization, set_sanitize_sensitive_info};
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 
@@ -61,6 +63,7 @@ impl Default for GracePeriodOptions {
 #[derive(Debug, Clone, Default)]
 pub struct Config {
     pub process_parallel: bool,
+    pub process_parallel_targets: u16,
     pub api: ConfigApi,
     pub working_dir: String,
     pub default_user_agent: Option<String>,
@@ -70,13 +73,17 @@ pub struct Config {
     pub custom_stream_response_path: Option<String>,
     pub video: Option<VideoConfig>,
     pub schedules: Option<Vec<ScheduleConfig>>,
+    pub schedule_spread_mins: Option<u32>,
+    pub schedule_retry: Option<ScheduleRetryConfig>,
     pub log: Option<LogConfig>,
     pub user_access_control: bool,
     pub connect_timeout_secs: u32,
     pub sleep_timer_mins: Option<u32>,
     pub update_on_boot: bool,
     pub config_hot_reload: bool,
+    pub mapping_reprocess: Option<MappingReprocessConfig>,
     pub disk_based_processing: bool,
+    pub verify_storage_checksums: bool,
     pub accept_insecure_ssl_certificates: bool,
     pub web_ui: Option<WebUiConfig>,
     pub messaging: Option<MessagingConfig>,
@@ -84,7 +91,12 @@ pub struct Config {
     pub hdhomerun: Option<HdHomeRunConfig>,
     pub proxy: Option<ProxyConfig>,
     pub ipcheck: Option<IpCheckConfig>,
+    pub network_routes: Option<Vec<NetworkRouteConfig>>,
+    pub channel_probe: Option<ChannelProbeConfig>,
+    pub user_expiration: Option<UserExpirationConfig>,
     pub library: Option<LibraryConfig>,
+    pub runtime: Option<RuntimeConfig>,
+    pub cluster: Option<ClusterConfig>,
 }
 
 impl Config {
@@ -133,11 +145,17 @@ impl Config {
 
     pub fn update_runtime(&self) {
         set_sanitize_sensitive_info(self.log.as_ref().is_none_or(|l| l.sanitize_sensitive_info));
+        set_ip_anonymization(self.log.as_ref().map_or(IpAnonymization::None, |l| l.ip_anonymization));
+        set_verify_storage_checksums(self.verify_storage_checksums);
         let temp_path = PathBuf::from(&self.working_dir).join("tmp");
         create_directories(self, &temp_path);
         let _ = tempfile::env::override_temp_dir(&temp_path);
     }
 
+    pub fn get_network_route(&self, input_name: &str) -> Option<&NetworkRouteConfig> {
+        self.network_routes.as_ref()?.iter().find(|route| route.inputs.iter().any(|name| name == input_name))
+    }
+
     pub fn get_hdhr_device_overview(&self) -> Option<HdHomeRunDeviceOverview> {
         self.hdhomerun.as_ref().map(|hdhr|
             HdHomeRunDeviceOverview {
@@ -150,6 +168,14 @@ impl Config {
         self.reverse_proxy.as_ref().is_some_and(|r| r.geoip.as_ref().is_some_and(|g| g.enabled))
     }
 
+    pub fn get_hls_remux_config(&self) -> Option<HlsRemuxConfig> {
+        self.reverse_proxy
+            .as_ref()
+            .and_then(|r| r.hls.as_ref())
+            .filter(|h| h.enabled)
+            .cloned()
+    }
+
     pub fn get_disabled_headers(&self) -> Option<ReverseProxyDisabledHeaderConfig> {
         self.reverse_proxy
             .as_ref()
@@ -175,7 +201,9 @@ impl From<&ConfigDto> for Config {
     fn from(dto: &ConfigDto) -> Self {
         Config {
             process_parallel: dto.process_parallel,
+            process_parallel_targets: dto.process_parallel_targets,
             disk_based_processing: dto.disk_based_processing,
+            verify_storage_checksums: dto.verify_storage_checksums,
             api: ConfigApi::from(&dto.api),
             working_dir: dto.working_dir.clone(),
             default_user_agent: dto.default_user_agent.clone(),
@@ -185,12 +213,15 @@ impl From<&ConfigDto> for Config {
             custom_stream_response_path: dto.custom_stream_response_path.clone(),
             video: dto.video.as_ref().map(Into::into),
             schedules: dto.schedules.as_ref().map(|s| s.iter().map(Into::into).collect()),
+            schedule_spread_mins: dto.schedule_spread_mins,
+            schedule_retry: dto.schedule_retry.as_ref().map(Into::into),
             log: dto.log.as_ref().map(Into::into),
             user_access_control: dto.user_access_control,
             connect_timeout_secs: dto.connect_timeout_secs,
             sleep_timer_mins: dto.sleep_timer_mins,
             update_on_boot: dto.update_on_boot,
             config_hot_reload: dto.config_hot_reload,
+            mapping_reprocess: dto.mapping_reprocess.as_ref().map(Into::into),
             accept_insecure_ssl_certificates: dto.accept_insecure_ssl_certificates,
             web_ui: dto.web_ui.as_ref().map(Into::into),
             messaging: dto.messaging.as_ref().map(Into::into),
@@ -198,7 +229,12 @@ impl From<&ConfigDto> for Config {
             hdhomerun: dto.hdhomerun.as_ref().map(Into::into),
             proxy: dto.proxy.as_ref().map(Into::into),
             ipcheck: dto.ipcheck.as_ref().map(Into::into),
+            network_routes: dto.network_routes.as_ref().map(|r| r.iter().map(Into::into).collect()),
+            channel_probe: dto.channel_probe.as_ref().map(Into::into),
+            user_expiration: dto.user_expiration.as_ref().map(Into::into),
             library: dto.library.as_ref().map(Into::into),
+            runtime: dto.runtime.as_ref().map(Into::into),
+            cluster: dto.cluster.as_ref().map(Into::into),
         }
     }
 }
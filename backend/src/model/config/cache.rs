@@ -1,4 +1,4 @@
-use shared::model::CacheConfigDto;
+use shared::model::{CacheConfigDto, CacheEvictionPolicy};
 use shared::utils::parse_size_base_2;
 use crate::model::macros;
 
@@ -8,6 +8,11 @@ pub struct CacheConfig {
     pub dir: String,
     pub size: usize,
     pub size_str: Option<String>,
+    pub proxy_logos: bool,
+    pub logo_width: u16,
+    pub xtream_api_ttl_secs: u32,
+    pub max_age_days: u16,
+    pub eviction: CacheEvictionPolicy,
 }
 
 macros::from_impl!(CacheConfig);
@@ -18,7 +23,12 @@ impl From<&CacheConfigDto> for CacheConfig {
             // Dto prepare should have set the right path
             dir: dto.dir.as_ref().map_or_else(Default::default, std::string::ToString::to_string),
             size_str: dto.size.clone(),
-            size: get_size(dto)
+            size: get_size(dto),
+            proxy_logos: dto.proxy_logos,
+            logo_width: dto.logo_width,
+            xtream_api_ttl_secs: dto.xtream_api_ttl_secs,
+            max_age_days: dto.max_age_days,
+            eviction: dto.eviction,
         }
     }
 }
@@ -30,6 +40,11 @@ impl From<&CacheConfig> for CacheConfigDto {
             // Dto prepare should have set the right path
             dir: Some(instance.dir.clone()),
             size: instance.size_str.clone(),
+            proxy_logos: instance.proxy_logos,
+            logo_width: instance.logo_width,
+            xtream_api_ttl_secs: instance.xtream_api_ttl_secs,
+            max_age_days: instance.max_age_days,
+            eviction: instance.eviction,
         }
     }
 }
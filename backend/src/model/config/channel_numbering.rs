@@ -0,0 +1,55 @@
+use crate::model::macros;
+use shared::foundation::{Filter, ValueProvider};
+use shared::model::{ConfigChannelNumberingDto, PlaylistItem};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct ConfigChannelNumberRange {
+    pub group: Arc<regex::Regex>,
+    pub start: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigChannelNumberPin {
+    pub filter: Filter,
+    pub number: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigChannelNumbering {
+    pub start: u32,
+    pub preserve_upstream: bool,
+    pub ranges: Vec<ConfigChannelNumberRange>,
+    pub pins: Vec<ConfigChannelNumberPin>,
+}
+
+impl ConfigChannelNumbering {
+    /// Number of the first pin matching the channel.
+    pub fn get_pin(&self, pli: &PlaylistItem) -> Option<u32> {
+        let provider = ValueProvider { pli, match_as_ascii: false };
+        self.pins.iter().find(|pin| pin.filter.filter(&provider)).map(|pin| pin.number)
+    }
+
+    /// Index of the first range matching the group.
+    pub fn get_range_index(&self, group: &str) -> Option<usize> {
+        self.ranges.iter().position(|range| range.group.is_match(group))
+    }
+}
+
+macros::from_impl!(ConfigChannelNumbering);
+impl From<&ConfigChannelNumberingDto> for ConfigChannelNumbering {
+    fn from(dto: &ConfigChannelNumberingDto) -> Self {
+        Self {
+            start: dto.start,
+            preserve_upstream: dto.preserve_upstream,
+            // the ranges and pins are validated during prepare
+            ranges: dto.ranges.iter()
+                .filter_map(|range| shared::model::REGEX_CACHE.get_or_compile(&range.group).ok()
+                    .map(|group| ConfigChannelNumberRange { group, start: range.start }))
+                .collect(),
+            pins: dto.pins.iter()
+                .filter_map(|pin| pin.t_filter.clone().map(|filter| ConfigChannelNumberPin { filter, number: pin.number }))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::model::macros;
+use shared::model::ChannelProbeConfigDto;
+
+#[derive(Debug, Clone)]
+pub struct ChannelProbeConfig {
+    pub interval_mins: u32,
+    pub sample_size: u16,
+    pub timeout_secs: u16,
+    pub targets: Vec<String>,
+}
+
+impl ChannelProbeConfig {
+    pub fn is_probed_target(&self, target_name: &str) -> bool {
+        self.targets.is_empty() || self.targets.iter().any(|name| name == target_name)
+    }
+}
+
+macros::from_impl!(ChannelProbeConfig);
+impl From<&ChannelProbeConfigDto> for ChannelProbeConfig {
+    fn from(dto: &ChannelProbeConfigDto) -> Self {
+        Self {
+            interval_mins: dto.interval_mins,
+            sample_size: dto.sample_size,
+            timeout_secs: dto.timeout_secs,
+            targets: dto.targets.clone(),
+        }
+    }
+}
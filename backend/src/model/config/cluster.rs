@@ -0,0 +1,30 @@
+use crate::model::macros;
+use shared::model::ClusterConfigDto;
+
+#[derive(Debug, Clone)]
+pub struct ClusterConfig {
+    pub enabled: bool,
+    pub redis_url: String,
+    pub node_id: String,
+    pub key_prefix: String,
+    pub sync_interval_millis: u64,
+}
+
+fn default_node_id() -> String {
+    sysinfo::System::host_name()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+macros::from_impl!(ClusterConfig);
+impl From<&ClusterConfigDto> for ClusterConfig {
+    fn from(dto: &ClusterConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            redis_url: dto.redis_url.clone(),
+            node_id: dto.node_id.clone().unwrap_or_else(default_node_id),
+            key_prefix: dto.key_prefix.clone(),
+            sync_interval_millis: dto.sync_interval_millis,
+        }
+    }
+}
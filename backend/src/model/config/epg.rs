@@ -1,4 +1,5 @@
-use shared::model::{EpgConfigDto, EpgSourceDto};
+use shared::model::{EpgConfigDto, EpgSourceDto, EpgTvgIdNormalizationConfigDto};
+use std::collections::BTreeMap;
 use crate::model::{macros, EpgSmartMatchConfig};
 
 #[derive(Debug, Clone)]
@@ -6,6 +7,7 @@ pub struct EpgSource {
     pub url: String,
     pub priority: i16,
     pub logo_override: bool,
+    pub schedule: Option<String>,
 }
 
 macros::from_impl!(EpgSource);
@@ -15,6 +17,27 @@ impl From<&EpgSourceDto> for EpgSource {
             url: dto.url.clone(),
             priority: dto.priority,
             logo_override: dto.logo_override,
+            schedule: dto.schedule.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EpgTvgIdNormalizationConfig {
+    pub enabled: bool,
+    pub country: Option<String>,
+    pub registry: Option<String>,
+    pub mapping: BTreeMap<String, String>,
+}
+
+macros::from_impl!(EpgTvgIdNormalizationConfig);
+impl From<&EpgTvgIdNormalizationConfigDto> for EpgTvgIdNormalizationConfig {
+    fn from(dto: &EpgTvgIdNormalizationConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            country: dto.country.clone(),
+            registry: dto.registry.clone(),
+            mapping: dto.mapping.clone(),
         }
     }
 }
@@ -23,6 +46,7 @@ impl From<&EpgSourceDto> for EpgSource {
 pub struct EpgConfig {
     pub sources: Vec<EpgSource>,
     pub smart_match: Option<EpgSmartMatchConfig>,
+    pub tvg_id_normalization: Option<EpgTvgIdNormalizationConfig>,
 }
 
 macros::from_impl!(EpgConfig);
@@ -31,6 +55,7 @@ impl From<&EpgConfigDto> for EpgConfig {
         Self {
             sources: dto.t_sources.iter().map(EpgSource::from).collect(),
             smart_match: dto.smart_match.as_ref().map(EpgSmartMatchConfig::from),
+            tvg_id_normalization: dto.tvg_id_normalization.as_ref().map(EpgTvgIdNormalizationConfig::from),
         }
     }
 }
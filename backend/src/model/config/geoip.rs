@@ -5,6 +5,8 @@ use crate::model::macros;
 pub struct GeoIpConfig {
     pub(crate) enabled: bool,
     pub(crate) url: String,
+    pub(crate) allowed_countries: Vec<String>,
+    pub(crate) blocked_countries: Vec<String>,
 }
 
 macros::from_impl!(GeoIpConfig);
@@ -14,6 +16,8 @@ impl From<&GeoIpConfigDto> for GeoIpConfig {
         Self {
             enabled: dto.enabled,
             url: dto.url.clone(),
+            allowed_countries: dto.allowed_countries.clone(),
+            blocked_countries: dto.blocked_countries.clone(),
         }
     }
 }
@@ -23,6 +27,45 @@ impl From<&GeoIpConfig> for GeoIpConfigDto {
         Self {
             enabled: instance.enabled,
             url: instance.url.clone(),
+            allowed_countries: instance.allowed_countries.clone(),
+            blocked_countries: instance.blocked_countries.clone(),
         }
     }
 }
+
+impl GeoIpConfig {
+    pub fn is_country_denied(&self, country: Option<&str>) -> bool {
+        is_country_denied(&self.allowed_countries, &self.blocked_countries, country)
+    }
+}
+
+/// Clients from blocked countries are denied, an unknown country is only denied
+/// when the access is limited to some countries.
+pub fn is_country_denied(allowed_countries: &[String], blocked_countries: &[String], country: Option<&str>) -> bool {
+    match country {
+        Some(country) => {
+            blocked_countries.iter().any(|c| c.eq_ignore_ascii_case(country))
+                || (!allowed_countries.is_empty() && !allowed_countries.iter().any(|c| c.eq_ignore_ascii_case(country)))
+        }
+        None => !allowed_countries.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_country_denied;
+
+    #[test]
+    fn test_country_denied() {
+        let blocked = vec!["CN".to_string()];
+        assert!(is_country_denied(&[], &blocked, Some("CN")));
+        assert!(!is_country_denied(&[], &blocked, Some("DE")));
+        assert!(!is_country_denied(&[], &blocked, None));
+
+        let allowed = vec!["DE".to_string(), "LAN".to_string()];
+        assert!(!is_country_denied(&allowed, &[], Some("DE")));
+        assert!(!is_country_denied(&allowed, &[], Some("Lan")));
+        assert!(is_country_denied(&allowed, &[], Some("US")));
+        assert!(is_country_denied(&allowed, &[], None));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::model::macros;
+use shared::foundation::{Filter, ValueProvider};
+use shared::model::{ConfigGroupMappingDto, ConfigGroupMappingRuleDto, PlaylistItem};
+use shared::utils::Internable;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct ConfigGroupMappingRule {
+    pub group: Arc<str>,
+    pub pattern: Option<Arc<regex::Regex>>,
+    pub filter: Option<Filter>,
+    pub priority: u16,
+}
+
+impl ConfigGroupMappingRule {
+    fn matches(&self, pli: &PlaylistItem) -> bool {
+        self.pattern.as_ref().is_none_or(|pattern| pattern.is_match(&pli.header.group))
+            && self.filter.as_ref().is_none_or(|filter| filter.filter(&ValueProvider { pli, match_as_ascii: false }))
+    }
+}
+
+macros::from_impl!(ConfigGroupMappingRule);
+impl From<&ConfigGroupMappingRuleDto> for ConfigGroupMappingRule {
+    fn from(dto: &ConfigGroupMappingRuleDto) -> Self {
+        Self {
+            group: dto.group.as_str().intern(),
+            pattern: dto.pattern.as_ref().map(|pattern| shared::model::REGEX_CACHE.get_or_compile(pattern)
+                .unwrap_or_else(|_| panic!("Invalid regex pattern {pattern}"))),
+            filter: dto.t_filter.clone(),
+            priority: dto.priority,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigGroupMapping {
+    /// Rules ordered by priority, rules with the same priority keep their config order
+    pub rules: Vec<ConfigGroupMappingRule>,
+    pub catch_all: Option<Arc<str>>,
+}
+
+impl ConfigGroupMapping {
+    /// Group of the first matching rule or the catch-all group, `None` keeps the group of the channel.
+    pub fn get_group(&self, pli: &PlaylistItem) -> Option<&Arc<str>> {
+        self.rules.iter().find(|rule| rule.matches(pli)).map(|rule| &rule.group).or(self.catch_all.as_ref())
+    }
+}
+
+macros::from_impl!(ConfigGroupMapping);
+impl From<&ConfigGroupMappingDto> for ConfigGroupMapping {
+    fn from(dto: &ConfigGroupMappingDto) -> Self {
+        let mut rules: Vec<ConfigGroupMappingRule> = dto.rules.iter().map(Into::into).collect();
+        rules.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        Self {
+            rules,
+            catch_all: dto.catch_all.as_deref().map(Internable::intern),
+        }
+    }
+}
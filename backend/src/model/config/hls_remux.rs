@@ -0,0 +1,45 @@
+use shared::model::HlsRemuxConfigDto;
+use std::time::Duration;
+use crate::model::macros;
+
+#[derive(Debug, Clone)]
+pub struct HlsRemuxConfig {
+    pub enabled: bool,
+    pub segment_secs: u16,
+    pub playlist_segments: u16,
+    pub idle_timeout_secs: u16,
+}
+
+impl HlsRemuxConfig {
+    pub fn segment_duration(&self) -> Duration {
+        Duration::from_secs(u64::from(self.segment_secs.max(1)))
+    }
+
+    pub fn idle_timeout(&self) -> Duration {
+        Duration::from_secs(u64::from(self.idle_timeout_secs.max(self.segment_secs)))
+    }
+}
+
+macros::from_impl!(HlsRemuxConfig);
+
+impl From<&HlsRemuxConfigDto> for HlsRemuxConfig {
+    fn from(dto: &HlsRemuxConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            segment_secs: dto.segment_secs,
+            playlist_segments: dto.playlist_segments,
+            idle_timeout_secs: dto.idle_timeout_secs,
+        }
+    }
+}
+
+impl From<&HlsRemuxConfig> for HlsRemuxConfigDto {
+    fn from(instance: &HlsRemuxConfig) -> Self {
+        Self {
+            enabled: instance.enabled,
+            segment_secs: instance.segment_secs,
+            playlist_segments: instance.playlist_segments,
+            idle_timeout_secs: instance.idle_timeout_secs,
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use crate::model::{macros, EpgConfig, PanelApiConfig};
 use crate::repository::get_csv_file_path;
-use chrono::Utc;
+use chrono::{Local, Timelike, Utc};
 use log::warn;
 use shared::check_input_credentials;
 use shared::error::TuliproxError;
-use shared::model::{ConfigInputAliasDto, ConfigInputDto, ConfigInputOptionsDto, InputFetchMethod, InputType, StagedInputDto};
+use shared::model::{ConfigInputAliasDto, ConfigInputDto, ConfigInputOptionsDto, HeaderPolicyDto, HeaderRulesDto, ImpersonationProfile, InputAuthDto, InputBasicAuthDto, InputAutoProxyDto, InputFailoverConfigDto, InputFetchMethod, InputGuardrailsDto,
+                    InputType, MaintenanceWindow, StagedInputDto};
 use shared::utils::{get_credentials_from_url, Internable};
-use shared::{check_input_connections, info_err_res, write_if_some};
+use shared::{check_input_connections, info_err, info_err_res, notify_err_res, write_if_some};
 use std::collections::HashMap;
 use std::fmt;
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use url::Url;
@@ -21,6 +23,7 @@ pub struct ConfigInputOptions {
     pub xtream_skip_series: bool,
     pub xtream_live_stream_use_prefix: bool,
     pub xtream_live_stream_without_extension: bool,
+    pub xtream_incremental: bool,
 }
 
 macros::from_impl!(ConfigInputOptions);
@@ -32,6 +35,179 @@ impl From<&ConfigInputOptionsDto> for ConfigInputOptions {
             xtream_skip_series: dto.xtream_skip_series,
             xtream_live_stream_use_prefix: dto.xtream_live_stream_use_prefix,
             xtream_live_stream_without_extension: dto.xtream_live_stream_without_extension,
+            xtream_incremental: dto.xtream_incremental,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InputFailoverConfig {
+    pub enabled: bool,
+    pub max_failures: u16,
+    pub recovery_secs: u64,
+}
+
+macros::from_impl!(InputFailoverConfig);
+impl From<&InputFailoverConfigDto> for InputFailoverConfig {
+    fn from(dto: &InputFailoverConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            max_failures: dto.max_failures,
+            recovery_secs: dto.recovery_secs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InputGuardrails {
+    pub max_size: Option<u64>,
+    pub max_items: Option<usize>,
+    pub min_items: Option<usize>,
+}
+
+impl InputGuardrails {
+    /// Rejects a refresh with suspiciously few or too many items, the input keeps its last data.
+    pub fn check_item_count(&self, input_name: &str, item_count: usize) -> Result<(), TuliproxError> {
+        if let Some(min_items) = self.min_items.filter(|min_items| item_count < *min_items) {
+            return notify_err_res!("Input {input_name} returned {item_count} items, less than the minimum of {min_items}, keeping the last data");
+        }
+        if let Some(max_items) = self.max_items.filter(|max_items| item_count > *max_items) {
+            return notify_err_res!("Input {input_name} returned {item_count} items, more than the maximum of {max_items}, keeping the last data");
+        }
+        Ok(())
+    }
+}
+
+macros::from_impl!(InputGuardrails);
+impl From<&InputGuardrailsDto> for InputGuardrails {
+    fn from(dto: &InputGuardrailsDto) -> Self {
+        Self {
+            max_size: dto.get_max_size_bytes(),
+            max_items: dto.max_items,
+            min_items: dto.min_items,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InputAutoProxy {
+    pub max_failures: u16,
+    pub recovery_secs: u64,
+    pub proxy_countries: Vec<String>,
+    pub redirect_countries: Vec<String>,
+}
+
+impl InputAutoProxy {
+    /// Clients from countries without direct access to the provider are proxied,
+    /// an unknown country is only proxied when the redirect is limited to some countries.
+    pub fn is_proxied_country(&self, country: Option<&str>) -> bool {
+        match country {
+            Some(country) => {
+                self.proxy_countries.iter().any(|c| c == country)
+                    || (!self.redirect_countries.is_empty() && !self.redirect_countries.iter().any(|c| c == country))
+            }
+            None => !self.redirect_countries.is_empty(),
+        }
+    }
+}
+
+impl Default for InputAutoProxy {
+    fn default() -> Self {
+        Self::from(&InputAutoProxyDto::default())
+    }
+}
+
+macros::from_impl!(InputAutoProxy);
+impl From<&InputAutoProxyDto> for InputAutoProxy {
+    fn from(dto: &InputAutoProxyDto) -> Self {
+        Self {
+            max_failures: dto.max_failures,
+            recovery_secs: dto.recovery_secs,
+            proxy_countries: dto.proxy_countries.clone(),
+            redirect_countries: dto.redirect_countries.clone(),
+        }
+    }
+}
+
+/// Client certificate and root certificates of an input, the inputs with the same files share their client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputTlsConfig {
+    pub client_certificate: Option<String>,
+    pub client_key: Option<String>,
+    pub ca_certificate: Option<String>,
+}
+
+impl InputTlsConfig {
+    /// The identity of the client certificate, the private key is read from the certificate file without a `client_key`.
+    pub fn load_identity(&self) -> Result<Option<reqwest::Identity>, TuliproxError> {
+        let Some(certificate_file) = self.client_certificate.as_deref() else {
+            return Ok(None);
+        };
+        let mut pem = std::fs::read(certificate_file)
+            .map_err(|err| info_err!("Failed to read client certificate {certificate_file}: {err}"))?;
+        if let Some(key_file) = self.client_key.as_deref() {
+            let key = std::fs::read(key_file).map_err(|err| info_err!("Failed to read client key {key_file}: {err}"))?;
+            pem.push(b'\n');
+            pem.extend_from_slice(&key);
+        }
+        reqwest::Identity::from_pem(&pem)
+            .map(Some)
+            .map_err(|err| info_err!("Invalid client certificate {certificate_file}: {err}"))
+    }
+
+    pub fn load_ca_certificates(&self) -> Result<Vec<reqwest::Certificate>, TuliproxError> {
+        let Some(ca_file) = self.ca_certificate.as_deref() else {
+            return Ok(Vec::new());
+        };
+        let pem = std::fs::read(ca_file).map_err(|err| info_err!("Failed to read ca certificate {ca_file}: {err}"))?;
+        reqwest::Certificate::from_pem_bundle(&pem).map_err(|err| info_err!("Invalid ca certificate {ca_file}: {err}"))
+    }
+}
+
+/// Basic auth credentials of an input, they are only sent to the host of the input url.
+#[derive(Debug, Clone)]
+pub struct InputBasicAuth {
+    pub host: Option<String>,
+    pub username: String,
+    pub password: String,
+}
+
+impl InputBasicAuth {
+    pub fn is_for_url(&self, url: &Url) -> bool {
+        self.host.as_deref().is_some_and(|host| url.host_str() == Some(host))
+    }
+}
+
+/// Network interface or local address the upstream requests of an input are bound to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InputEgress {
+    Interface(String),
+    Address(IpAddr),
+}
+
+impl InputEgress {
+    pub fn new(egress: &str) -> Self {
+        egress.parse::<IpAddr>().map_or_else(|_| Self::Interface(egress.to_string()), Self::Address)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InputAuth {
+    pub basic: Option<InputBasicAuthDto>,
+    pub tls: Option<InputTlsConfig>,
+}
+
+macros::from_impl!(InputAuth);
+impl From<&InputAuthDto> for InputAuth {
+    fn from(dto: &InputAuthDto) -> Self {
+        let tls = InputTlsConfig {
+            client_certificate: dto.client_certificate.clone(),
+            client_key: dto.client_key.clone(),
+            ca_certificate: dto.ca_certificate.clone(),
+        };
+        Self {
+            basic: dto.basic.clone(),
+            tls: (tls.client_certificate.is_some() || tls.ca_certificate.is_some()).then_some(tls),
         }
     }
 }
@@ -131,6 +307,9 @@ pub struct ConfigInput {
     pub name: Arc<str>,
     pub input_type: InputType,
     pub headers: HashMap<String, String>,
+    pub header_policy: Option<HeaderPolicyDto>,
+    pub auth: Option<InputAuth>,
+    pub egress: Option<InputEgress>,
     pub url: String,
     pub epg: Option<EpgConfig>,
     pub username: Option<String>,
@@ -147,9 +326,26 @@ pub struct ConfigInput {
     pub t_batch_url: Option<String>,
     pub panel_api: Option<PanelApiConfig>,
     pub cache_duration_seconds: u64,
+    pub failover: Option<InputFailoverConfig>,
+    pub impersonate: Option<ImpersonationProfile>,
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    pub guardrails: Option<InputGuardrails>,
+    pub auto_proxy: Option<InputAutoProxy>,
 }
 
 impl ConfigInput {
+    /// Header rules of the provider requests, the rules of the target extend the rules of the input.
+    pub fn get_upstream_header_rules(&self, target_policy: Option<&HeaderPolicyDto>) -> Option<HeaderRulesDto> {
+        HeaderRulesDto::merged(self.header_policy.as_ref().and_then(|policy| policy.upstream.as_ref()),
+                               target_policy.and_then(|policy| policy.upstream.as_ref()))
+    }
+
+    /// Header rules of the stream responses to the clients, the rules of the target extend the rules of the input.
+    pub fn get_client_header_rules(&self, target_policy: Option<&HeaderPolicyDto>) -> Option<HeaderRulesDto> {
+        HeaderRulesDto::merged(self.header_policy.as_ref().and_then(|policy| policy.client.as_ref()),
+                               target_policy.and_then(|policy| policy.client.as_ref()))
+    }
+
     pub fn prepare(&mut self) -> Result<Option<PathBuf>, TuliproxError> {
         let batch_file_path = self.prepare_batch();
         self.name = self.name.trim().intern();
@@ -180,10 +376,28 @@ impl ConfigInput {
             if let Some(panel_api) = &mut self.panel_api {
                 panel_api.prepare()?;
             }
+
+            if let Some(tls) = self.get_tls_config() {
+                tls.load_identity()?;
+                tls.load_ca_certificates()?;
+            }
         }
         Ok(batch_file_path)
     }
 
+    pub fn get_tls_config(&self) -> Option<&InputTlsConfig> {
+        self.auth.as_ref().and_then(|auth| auth.tls.as_ref())
+    }
+
+    /// Basic auth of the requests to the host of the input url.
+    pub fn get_basic_auth(&self) -> Option<InputBasicAuth> {
+        self.auth.as_ref().and_then(|auth| auth.basic.as_ref()).map(|basic| InputBasicAuth {
+            host: Url::parse(&self.url).ok().and_then(|url| url.host_str().map(ToString::to_string)),
+            username: basic.username.clone(),
+            password: basic.password.clone(),
+        })
+    }
+
     pub fn get_user_info(&self) -> Option<InputUserInfo> {
         InputUserInfo::new(self.input_type, self.username.as_deref(), self.password.as_deref(), &self.url)
     }
@@ -257,6 +471,9 @@ impl ConfigInput {
             name: alias.name.clone(),
             input_type: self.input_type,
             headers: self.headers.clone(),
+            header_policy: self.header_policy.clone(),
+            auth: self.auth.clone(),
+            egress: self.egress.clone(),
             url: alias.url.clone(),
             epg: self.epg.clone(),
             username: alias.username.clone(),
@@ -273,9 +490,41 @@ impl ConfigInput {
             t_batch_url: None,
             panel_api: self.panel_api.clone(),
             cache_duration_seconds: self.cache_duration_seconds,
+            failover: None,
+            impersonate: self.impersonate,
+            maintenance_windows: self.maintenance_windows.clone(),
+            guardrails: self.guardrails,
+            auto_proxy: self.auto_proxy.clone(),
         }
     }
 
+    pub fn get_failover(&self) -> Option<&InputFailoverConfig> {
+        self.failover.as_ref().filter(|failover| failover.enabled)
+    }
+
+    /// The provider is in one of its maintenance windows, the input keeps its last data.
+    pub fn is_in_maintenance(&self) -> bool {
+        if self.maintenance_windows.is_empty() {
+            return false;
+        }
+        let now = Local::now();
+        let minute_of_day = u16::try_from(now.hour() * 60 + now.minute()).unwrap_or_default();
+        self.maintenance_windows.iter().any(|window| window.contains(minute_of_day))
+    }
+
+    pub fn get_max_download_size(&self) -> Option<u64> {
+        self.guardrails.as_ref().and_then(|guardrails| guardrails.max_size)
+    }
+
+    /// Checks the item count of a refresh against the guardrails of the input.
+    pub fn check_item_count(&self, item_count: usize) -> Result<(), TuliproxError> {
+        self.guardrails.as_ref().map_or(Ok(()), |guardrails| guardrails.check_item_count(&self.name, item_count))
+    }
+
+    pub fn is_xtream_incremental(&self) -> bool {
+        self.options.as_ref().is_some_and(|options| options.xtream_incremental)
+    }
+
     pub fn has_enabled_aliases(&self) -> bool {
         self.aliases
             .as_ref()
@@ -302,6 +551,9 @@ impl From<&ConfigInputDto> for ConfigInput {
             name: dto.name.clone(),
             input_type: dto.input_type,
             headers: dto.headers.clone(),
+            header_policy: dto.header_policy.clone(),
+            auth: dto.auth.as_ref().map(InputAuth::from),
+            egress: dto.egress_interface.as_deref().map(InputEgress::new),
             url: dto.url.clone(),
             epg: dto.epg.as_ref().map(EpgConfig::from),
             username: dto.username.clone(),
@@ -318,6 +570,13 @@ impl From<&ConfigInputDto> for ConfigInput {
             t_batch_url: None,
             panel_api: dto.panel_api.as_ref().map(PanelApiConfig::from),
             cache_duration_seconds: dto.cache_duration_seconds,
+            failover: dto.failover.as_ref().map(InputFailoverConfig::from),
+            impersonate: dto.impersonate,
+            maintenance_windows: dto.maintenance_windows.iter().flatten()
+                .filter_map(|window| window.parse::<MaintenanceWindow>().ok())
+                .collect(),
+            guardrails: dto.guardrails.as_ref().map(InputGuardrails::from),
+            auto_proxy: dto.auto_proxy.as_ref().map(InputAutoProxy::from),
         }
     }
 }
@@ -356,3 +615,28 @@ pub fn is_input_expired(exp_date: Option<i64>) -> bool {
         None => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{InputAutoProxy, InputEgress};
+
+    #[test]
+    fn test_auto_proxy_country() {
+        let auto_proxy = InputAutoProxy { proxy_countries: vec!["CN".to_string()], ..InputAutoProxy::default() };
+        assert!(auto_proxy.is_proxied_country(Some("CN")));
+        assert!(!auto_proxy.is_proxied_country(Some("DE")));
+        assert!(!auto_proxy.is_proxied_country(None));
+
+        let auto_proxy = InputAutoProxy { redirect_countries: vec!["DE".to_string(), "AT".to_string()], ..InputAutoProxy::default() };
+        assert!(!auto_proxy.is_proxied_country(Some("AT")));
+        assert!(auto_proxy.is_proxied_country(Some("US")));
+        assert!(auto_proxy.is_proxied_country(None));
+    }
+
+    #[test]
+    fn test_input_egress() {
+        assert_eq!(InputEgress::new("wg0"), InputEgress::Interface("wg0".to_string()));
+        assert_eq!(InputEgress::new("10.8.0.2"), InputEgress::Address("10.8.0.2".parse().unwrap()));
+        assert_eq!(InputEgress::new("fd00::2"), InputEgress::Address("fd00::2".parse().unwrap()));
+    }
+}
@@ -1,11 +1,55 @@
-use shared::model::LogConfigDto;
+use shared::model::{CrashReportConfigDto, IpAnonymization, LogConfigDto};
 use shared::utils::default_as_true;
 use crate::model::macros;
 // We need serde for these structs to read them during
 // start from the yaml file without reading the whole config.
 //
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CrashReportConfig {
+    #[serde(default = "default_as_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub dir: Option<String>,
+    #[serde(default)]
+    pub sentry_dsn: Option<String>,
+    #[serde(default)]
+    pub environment: Option<String>,
+}
 
+impl Default for CrashReportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_as_true(),
+            dir: None,
+            sentry_dsn: None,
+            environment: None,
+        }
+    }
+}
+
+macros::from_impl!(CrashReportConfig);
+impl From<&CrashReportConfigDto> for CrashReportConfig {
+    fn from(dto: &CrashReportConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            dir: dto.dir.clone(),
+            sentry_dsn: dto.sentry_dsn.clone(),
+            environment: dto.environment.clone(),
+        }
+    }
+}
+impl From<&CrashReportConfig> for CrashReportConfigDto {
+    fn from(instance: &CrashReportConfig) -> Self {
+        Self {
+            enabled: instance.enabled,
+            dir: instance.dir.clone(),
+            sentry_dsn: instance.sentry_dsn.clone(),
+            environment: instance.environment.clone(),
+        }
+    }
+}
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
 #[serde(deny_unknown_fields)]
@@ -16,10 +60,20 @@ pub struct LogConfig {
     pub log_active_user: bool,
     #[serde(default)]
     pub log_level: Option<String>,
+    #[serde(default)]
+    pub crash_report: Option<CrashReportConfig>,
+    #[serde(default)]
+    pub ip_anonymization: IpAnonymization,
+    #[serde(default)]
+    pub retention_days: u16,
+    #[serde(default)]
+    pub session_history_days: u16,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
 pub struct LogLevelConfig {
+    #[serde(default)]
+    pub working_dir: Option<String>,
     #[serde(default)]
     pub log: Option<LogConfig>,
 }
@@ -31,6 +85,10 @@ impl From<&LogConfigDto> for LogConfig {
             sanitize_sensitive_info: dto.sanitize_sensitive_info,
             log_active_user: dto.log_active_user,
             log_level: dto.log_level.clone(),
+            crash_report: dto.crash_report.as_ref().map(Into::into),
+            ip_anonymization: dto.ip_anonymization,
+            retention_days: dto.retention_days,
+            session_history_days: dto.session_history_days,
         }
     }
 }
@@ -40,6 +98,10 @@ impl From<&LogConfig> for LogConfigDto {
             sanitize_sensitive_info: instance.sanitize_sensitive_info,
             log_active_user: instance.log_active_user,
             log_level: instance.log_level.clone(),
+            crash_report: instance.crash_report.as_ref().map(Into::into),
+            ip_anonymization: instance.ip_anonymization,
+            retention_days: instance.retention_days,
+            session_history_days: instance.session_history_days,
         }
     }
-}
\ No newline at end of file
+}
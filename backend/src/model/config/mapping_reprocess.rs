@@ -0,0 +1,18 @@
+use crate::model::macros;
+use shared::model::MappingReprocessConfigDto;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MappingReprocessConfig {
+    pub debounce_secs: u64,
+    pub confirm: bool,
+}
+
+macros::from_impl!(MappingReprocessConfig);
+impl From<&MappingReprocessConfigDto> for MappingReprocessConfig {
+    fn from(dto: &MappingReprocessConfigDto) -> Self {
+        Self {
+            debounce_secs: dto.debounce_secs,
+            confirm: dto.confirm,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use log::warn;
 use crate::model::macros;
-use shared::model::{DiscordMessagingConfigDto, MessagingConfigDto, MsgKind, PushoverMessagingConfigDto, RestMessagingConfigDto, TelegramMessagingConfigDto};
+use shared::model::{DiscordMessagingConfigDto, MessagingConfigDto, MsgKind, PushoverMessagingConfigDto, RestMessagingConfigDto, TelegramMessagingConfigDto, WebPushMessagingConfigDto, WebhookMessagingConfigDto};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
@@ -147,6 +147,83 @@ impl From<&PushoverMessagingConfig> for PushoverMessagingConfigDto {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct WebPushMessagingConfig {
+    pub subject: String,
+}
+
+macros::from_impl!(WebPushMessagingConfig);
+impl From<&WebPushMessagingConfigDto> for WebPushMessagingConfig {
+    fn from(dto: &WebPushMessagingConfigDto) -> Self {
+        Self {
+            subject: dto.subject.clone(),
+        }
+    }
+}
+
+impl From<&WebPushMessagingConfig> for WebPushMessagingConfigDto {
+    fn from(instance: &WebPushMessagingConfig) -> Self {
+        Self {
+            subject: instance.subject.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookMessagingConfig {
+    pub name: String,
+    pub url: String,
+    pub method: String,
+    pub headers: std::collections::HashMap<String, String>,
+    pub body: Option<String>,
+    pub templates: std::collections::HashMap<MsgKind, String>,
+    pub notify_on: Vec<MsgKind>,
+}
+
+impl WebhookMessagingConfig {
+    pub fn is_enabled(&self, kind: MsgKind) -> bool {
+        self.notify_on.is_empty() || self.notify_on.contains(&kind)
+    }
+
+    /// The kind template has precedence over the body template.
+    pub fn get_template(&self, kind: MsgKind) -> Option<&str> {
+        self.templates.get(&kind).or(self.body.as_ref()).map(String::as_str)
+    }
+
+    pub fn display_name(&self) -> &str {
+        if self.name.is_empty() { &self.url } else { &self.name }
+    }
+}
+
+macros::from_impl!(WebhookMessagingConfig);
+impl From<&WebhookMessagingConfigDto> for WebhookMessagingConfig {
+    fn from(dto: &WebhookMessagingConfigDto) -> Self {
+        Self {
+            name: dto.name.clone(),
+            url: dto.url.clone(),
+            method: dto.method.clone().unwrap_or_else(|| "POST".to_string()),
+            headers: dto.headers.clone(),
+            body: dto.body.clone(),
+            templates: dto.templates.clone(),
+            notify_on: dto.notify_on.clone(),
+        }
+    }
+}
+
+impl From<&WebhookMessagingConfig> for WebhookMessagingConfigDto {
+    fn from(instance: &WebhookMessagingConfig) -> Self {
+        Self {
+            name: instance.name.clone(),
+            url: instance.url.clone(),
+            method: Some(instance.method.clone()),
+            headers: instance.headers.clone(),
+            body: instance.body.clone(),
+            templates: instance.templates.clone(),
+            notify_on: instance.notify_on.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MessagingConfig {
     pub notify_on: Vec<MsgKind>,
@@ -154,6 +231,8 @@ pub struct MessagingConfig {
     pub rest: Option<RestMessagingConfig>,
     pub pushover: Option<PushoverMessagingConfig>,
     pub discord: Option<DiscordMessagingConfig>,
+    pub web_push: Option<WebPushMessagingConfig>,
+    pub webhooks: Vec<WebhookMessagingConfig>,
 }
 
 impl MessagingConfig {
@@ -180,6 +259,8 @@ impl From<&MessagingConfigDto> for MessagingConfig {
             rest: dto.rest.as_ref().map(Into::into),
             pushover: dto.pushover.as_ref().map(Into::into),
             discord: dto.discord.as_ref().map(Into::into),
+            web_push: dto.web_push.as_ref().map(Into::into),
+            webhooks: dto.webhooks.iter().map(Into::into).collect(),
         }
     }
 }
@@ -192,6 +273,8 @@ impl From<&MessagingConfig> for MessagingConfigDto {
             rest: instance.rest.as_ref().map(Into::into),
             pushover: instance.pushover.as_ref().map(Into::into),
             discord: instance.discord.as_ref().map(Into::into),
+            web_push: instance.web_push.as_ref().map(Into::into),
+            webhooks: instance.webhooks.iter().map(Into::into).collect(),
         }
     }
 }
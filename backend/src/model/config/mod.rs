@@ -18,6 +18,15 @@ mod reverse_proxy;
 mod cache;
 mod rate_limit;
 mod proxy;
+mod network_route;
+mod channel_probe;
+mod user_expiration;
+mod group_mapping;
+mod channel_numbering;
+mod mapping_reprocess;
+mod runtime;
+mod cluster;
+mod public_url;
 mod schedule;
 mod api_proxy;
 mod rename;
@@ -29,6 +38,8 @@ mod trakt_api;
 pub mod macros;
 mod favourites;
 mod geoip;
+mod account_sharing;
+mod hls_remux;
 mod library;
 mod panel_api;
 
@@ -46,10 +57,21 @@ pub use ip_check::*;
 pub use log::*;
 pub use messaging::*;
 pub use proxy::*;
+pub use network_route::*;
+pub use channel_probe::*;
+pub use user_expiration::*;
+pub use group_mapping::*;
+pub use channel_numbering::*;
+pub use mapping_reprocess::*;
+pub use runtime::*;
+pub use cluster::*;
+pub use public_url::*;
 pub use rate_limit::*;
 pub use rename::*;
 pub use reverse_proxy::*;
 pub use geoip::*;
+pub use account_sharing::*;
+pub use hls_remux::*;
 pub use schedule::*;
 pub use sort::*;
 pub use source::*;
@@ -0,0 +1,33 @@
+use shared::model::NetworkRouteConfigDto;
+use crate::model::macros;
+
+#[derive(Debug, Clone)]
+pub struct NetworkRouteConfig {
+    pub name: String,
+    pub interface: String,
+    pub inputs: Vec<String>,
+    pub check_url: Option<String>,
+}
+
+macros::from_impl!(NetworkRouteConfig);
+impl From<&NetworkRouteConfigDto> for NetworkRouteConfig {
+    fn from(dto: &NetworkRouteConfigDto) -> Self {
+        Self {
+            name: dto.name.clone(),
+            interface: dto.interface.clone(),
+            inputs: dto.inputs.clone(),
+            check_url: dto.check_url.clone(),
+        }
+    }
+}
+
+impl From<&NetworkRouteConfig> for NetworkRouteConfigDto {
+    fn from(instance: &NetworkRouteConfig) -> Self {
+        Self {
+            name: instance.name.clone(),
+            interface: instance.interface.clone(),
+            inputs: instance.inputs.clone(),
+            check_url: instance.check_url.clone(),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::model::macros;
+use shared::model::ConfigPublicUrlDto;
+
+#[derive(Debug, Clone)]
+pub struct ConfigPublicUrlDomain {
+    pub url: String,
+    pub users: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigPublicUrl {
+    pub base_url: Option<String>,
+    pub path_prefix: Option<String>,
+    pub domains: Vec<ConfigPublicUrlDomain>,
+}
+
+impl ConfigPublicUrl {
+    /// Base url of the playlist urls for the user, the vanity domain of the user comes first,
+    /// then the base url of the target and the base url of the api proxy server of the user.
+    pub fn get_base_url(&self, username: &str, server_base_url: &str) -> String {
+        let base_url = self.domains.iter()
+            .find(|domain| domain.users.iter().any(|user| user == username))
+            .map(|domain| domain.url.as_str())
+            .or(self.base_url.as_deref())
+            .unwrap_or(server_base_url);
+        match self.path_prefix.as_deref() {
+            Some(prefix) => format!("{base_url}/{prefix}"),
+            None => base_url.to_string(),
+        }
+    }
+}
+
+macros::from_impl!(ConfigPublicUrl);
+impl From<&ConfigPublicUrlDto> for ConfigPublicUrl {
+    fn from(dto: &ConfigPublicUrlDto) -> Self {
+        Self {
+            base_url: dto.base_url.clone(),
+            path_prefix: dto.path_prefix.clone(),
+            domains: dto.domains.iter().map(|domain| ConfigPublicUrlDomain {
+                url: domain.url.clone(),
+                users: domain.users.clone(),
+            }).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigPublicUrl, ConfigPublicUrlDomain};
+
+    #[test]
+    fn test_public_url_base_url() {
+        let server_base_url = "http://192.168.1.5:8901";
+        let mut public_url = ConfigPublicUrl {
+            base_url: None,
+            path_prefix: Some("tv".to_string()),
+            domains: vec![ConfigPublicUrlDomain { url: "https://vip.example.com".to_string(), users: vec!["alice".to_string()] }],
+        };
+        assert_eq!(public_url.get_base_url("bob", server_base_url), "http://192.168.1.5:8901/tv");
+        assert_eq!(public_url.get_base_url("alice", server_base_url), "https://vip.example.com/tv");
+
+        public_url.base_url = Some("https://cdn.example.com".to_string());
+        public_url.path_prefix = None;
+        assert_eq!(public_url.get_base_url("bob", server_base_url), "https://cdn.example.com");
+        assert_eq!(public_url.get_base_url("alice", server_base_url), "https://vip.example.com");
+    }
+}
@@ -1,5 +1,5 @@
 use crate::model::config::cache::CacheConfig;
-use crate::model::{macros, GeoIpConfig, RateLimitConfig, StreamConfig};
+use crate::model::{macros, AccountSharingConfig, GeoIpConfig, HlsRemuxConfig, RateLimitConfig, StreamConfig};
 use shared::model::{ResourceRetryConfigDto, ReverseProxyConfigDto, ReverseProxyDisabledHeaderConfigDto};
 use shared::utils::{default_resource_retry_attempts, default_resource_retry_backoff_ms, default_resource_retry_backoff_multiplier, hex_to_u8_16, u8_16_to_hex};
 use std::cmp::max;
@@ -99,6 +99,7 @@ impl From<&ResourceRetryConfig> for ResourceRetryConfigDto {
 #[derive(Debug, Clone)]
 pub struct ReverseProxyConfig {
     pub resource_rewrite_disabled: bool,
+    pub compression_disabled: bool,
     pub rewrite_secret: [u8; 16],
     pub resource_retry: ResourceRetryConfig,
     pub disabled_header: Option<ReverseProxyDisabledHeaderConfig>,
@@ -106,6 +107,8 @@ pub struct ReverseProxyConfig {
     pub cache: Option<CacheConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub geoip: Option<GeoIpConfig>,
+    pub account_sharing: Option<AccountSharingConfig>,
+    pub hls: Option<HlsRemuxConfig>,
 }
 
 macros::from_impl!(ReverseProxyConfig);
@@ -114,6 +117,7 @@ impl From<&ReverseProxyConfigDto> for ReverseProxyConfig {
     fn from(dto: &ReverseProxyConfigDto) -> Self {
         Self {
             resource_rewrite_disabled: dto.resource_rewrite_disabled,
+            compression_disabled: dto.compression_disabled,
             rewrite_secret: hex_to_u8_16(&dto.rewrite_secret).unwrap_or_default(),
             resource_retry: dto
                 .resource_retry
@@ -129,6 +133,8 @@ impl From<&ReverseProxyConfigDto> for ReverseProxyConfig {
             cache: dto.cache.as_ref().map(Into::into),
             rate_limit: dto.rate_limit.as_ref().map(Into::into),
             geoip: dto.geoip.as_ref().map(Into::into),
+            account_sharing: dto.account_sharing.as_ref().map(Into::into),
+            hls: dto.hls.as_ref().map(Into::into),
         }
     }
 }
@@ -137,6 +143,7 @@ impl From<&ReverseProxyConfig> for ReverseProxyConfigDto {
     fn from(instance: &ReverseProxyConfig) -> Self {
         Self {
             resource_rewrite_disabled: instance.resource_rewrite_disabled,
+            compression_disabled: instance.compression_disabled,
             rewrite_secret: u8_16_to_hex(&instance.rewrite_secret),
             resource_retry: Some(ResourceRetryConfigDto::from(&instance.resource_retry)),
             disabled_header: instance.disabled_header.as_ref().map(|d| ReverseProxyDisabledHeaderConfigDto {
@@ -149,6 +156,8 @@ impl From<&ReverseProxyConfig> for ReverseProxyConfigDto {
             cache: instance.cache.as_ref().map(Into::into),
             rate_limit: instance.rate_limit.as_ref().map(Into::into),
             geoip: instance.geoip.as_ref().map(Into::into),
+            account_sharing: instance.account_sharing.as_ref().map(Into::into),
+            hls: instance.hls.as_ref().map(Into::into),
         }
     }
 }
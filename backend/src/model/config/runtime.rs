@@ -0,0 +1,45 @@
+use crate::model::macros;
+use shared::model::RuntimeConfigDto;
+
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeConfig {
+    pub worker_threads: usize,
+    pub max_blocking_threads: usize,
+    pub thread_stack_size: Option<usize>,
+}
+
+impl RuntimeConfig {
+    /// Creates the multi threaded runtime, unset values keep the tokio defaults.
+    pub fn build_runtime(&self) -> std::io::Result<tokio::runtime::Runtime> {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.enable_all();
+        if self.worker_threads > 0 {
+            builder.worker_threads(self.worker_threads);
+        }
+        if self.max_blocking_threads > 0 {
+            builder.max_blocking_threads(self.max_blocking_threads);
+        }
+        if let Some(stack_size) = self.thread_stack_size {
+            builder.thread_stack_size(stack_size);
+        }
+        builder.build()
+    }
+}
+
+macros::from_impl!(RuntimeConfig);
+impl From<&RuntimeConfigDto> for RuntimeConfig {
+    fn from(dto: &RuntimeConfigDto) -> Self {
+        Self {
+            worker_threads: usize::from(dto.worker_threads),
+            max_blocking_threads: usize::from(dto.max_blocking_threads),
+            thread_stack_size: dto.get_thread_stack_size().ok().flatten(),
+        }
+    }
+}
+
+/// Reads only the `runtime` section of the config file, the runtime is created before the config is loaded.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct RuntimeBootConfig {
+    #[serde(default)]
+    pub runtime: Option<RuntimeConfigDto>,
+}
@@ -1,10 +1,12 @@
-use shared::model::ScheduleConfigDto;
+use shared::model::{ScheduleConfigDto, ScheduleRetryConfigDto};
 use crate::model::macros;
+use chrono_tz::Tz;
 
 #[derive(Debug, Clone)]
 pub struct ScheduleConfig {
     pub schedule: String,
     pub targets: Option<Vec<String>>,
+    pub timezone: Option<Tz>,
 }
 
 macros::from_impl!(ScheduleConfig);
@@ -13,6 +15,13 @@ impl From<&ScheduleConfigDto> for ScheduleConfig {
         Self {
             schedule: dto.schedule.clone(),
             targets: dto.targets.clone(),
+            timezone: dto.timezone.as_deref().and_then(|tz| match tz.parse::<Tz>() {
+                Ok(tz) => Some(tz),
+                Err(err) => {
+                    log::warn!("Invalid timezone '{tz}' for schedule {}: {err}", dto.schedule);
+                    None
+                }
+            }),
         }
     }
 }
@@ -21,6 +30,46 @@ impl From<&ScheduleConfig> for ScheduleConfigDto {
         Self {
             schedule: dto.schedule.clone(),
             targets: dto.targets.clone(),
+            timezone: dto.timezone.map(|tz| tz.name().to_string()),
         }
     }
 }
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScheduleRetryConfig {
+    pub max_attempts: u8,
+    pub backoff_secs: u64,
+    pub max_backoff_secs: u64,
+}
+
+macros::from_impl!(ScheduleRetryConfig);
+impl From<&ScheduleRetryConfigDto> for ScheduleRetryConfig {
+    fn from(dto: &ScheduleRetryConfigDto) -> Self {
+        Self {
+            max_attempts: dto.max_attempts,
+            backoff_secs: dto.backoff_secs,
+            max_backoff_secs: dto.max_backoff_secs,
+        }
+    }
+}
+
+impl ScheduleRetryConfig {
+    /// Delay before the retry, the first retry has the number 1 and each further retry doubles the delay.
+    pub fn get_delay_secs(&self, retry: u8) -> u64 {
+        let factor = 1_u64.checked_shl(u32::from(retry.saturating_sub(1))).unwrap_or(u64::MAX);
+        self.backoff_secs.saturating_mul(factor).min(self.max_backoff_secs.max(self.backoff_secs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScheduleRetryConfig;
+
+    #[test]
+    fn test_schedule_retry_delay() {
+        let retry = ScheduleRetryConfig { max_attempts: 5, backoff_secs: 60, max_backoff_secs: 300 };
+        let delays: Vec<u64> = (1..=5).map(|retry_nr| retry.get_delay_secs(retry_nr)).collect();
+        assert_eq!(delays, vec![60, 120, 240, 300, 300]);
+        assert_eq!(retry.get_delay_secs(200), 300);
+    }
+}
@@ -45,15 +45,21 @@ impl From<&ConfigSortRule> for ConfigSortRuleDto {
 #[derive(Debug, Clone, Default)]
 pub struct ConfigSort {
     pub match_as_ascii: bool,
+    pub natural: bool,
+    pub locale: Option<String>,
     pub rules: Vec<ConfigSortRule>,
 }
 
 macros::from_impl!(ConfigSort);
+/// The rules of the preset come first, the own settings extend the preset.
 impl From<&ConfigSortDto> for ConfigSort {
     fn from(dto: &ConfigSortDto) -> Self {
+        let preset = dto.t_preset.as_deref();
         Self {
-            match_as_ascii: dto.match_as_ascii,
-            rules: dto.rules.iter().map(Into::into).collect(),
+            match_as_ascii: dto.match_as_ascii || preset.is_some_and(|p| p.match_as_ascii),
+            natural: dto.natural || preset.is_some_and(|p| p.natural),
+            locale: dto.locale.clone().or_else(|| preset.and_then(|p| p.locale.clone())),
+            rules: preset.into_iter().flat_map(|p| p.rules.iter()).chain(dto.rules.iter()).map(Into::into).collect(),
         }
     }
 }
@@ -61,8 +67,12 @@ impl From<&ConfigSortDto> for ConfigSort {
 impl From<&ConfigSort> for ConfigSortDto {
     fn from(instance: &ConfigSort) -> Self {
         Self {
+            preset: None,
             match_as_ascii: instance.match_as_ascii,
+            natural: instance.natural,
+            locale: instance.locale.clone(),
             rules: instance.rules.iter().map(Into::into).collect(),
+            t_preset: None,
         }
     }
 }
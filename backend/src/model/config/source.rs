@@ -1,4 +1,5 @@
 use crate::model::{macros, ConfigInput, ConfigTarget, ProcessTargets};
+use chrono_tz::Tz;
 use shared::error::{info_err_res, TuliproxError};
 use shared::model::{ConfigSourceDto, PatternTemplate, SourcesConfigDto};
 use std::borrow::Cow;
@@ -128,11 +129,18 @@ impl SourcesConfig {
                     for user_target in user_targets {
                         let key = user_target.to_lowercase();
                         if target.name.eq_ignore_ascii_case(key.as_str()) {
-                            targets.push(target.id);
-                            target_names.push(target.name.clone());
+                            if !targets.contains(&target.id) {
+                                targets.push(target.id);
+                                target_names.push(target.name.clone());
+                            }
                             if let Some(value) = check_targets.get(key.as_str()) {
                                 check_targets.insert(key, value + 1);
                             }
+                        } else if target.shadow_of.as_deref().is_some_and(|shadow_of| shadow_of.eq_ignore_ascii_case(key.as_str()))
+                            && !targets.contains(&target.id) {
+                            // the shadows are processed with the same data as their target
+                            targets.push(target.id);
+                            target_names.push(target.name.clone());
                         }
                     }
                 }
@@ -176,6 +184,27 @@ impl SourcesConfig {
         file_names
     }
 
+    /// Returns input name, url and cron expression of each epg source with its own schedule.
+    pub fn get_epg_schedules(&self) -> HashSet<(&str, &str, &str)> {
+        self.inputs.iter()
+            .filter_map(|input| input.epg.as_ref().map(|epg| (input, epg)))
+            .flat_map(|(input, epg)| epg.sources.iter()
+                .filter_map(|source| source.schedule.as_deref().map(|schedule| (&*input.name, source.url.as_str(), schedule))))
+            .collect()
+    }
+
+    /// Returns target name and cron expression of each target with a trakt `schedule`.
+    pub fn get_trakt_schedules(&self) -> HashSet<(&str, &str, Option<Tz>)> {
+        self.sources.iter()
+            .flat_map(|source| source.targets.iter())
+            .filter_map(|target| target.get_xtream_output()
+                .and_then(|output| output.trakt.as_ref())
+                .filter(|trakt| trakt.enabled)
+                .and_then(|trakt| trakt.schedule.as_deref())
+                .map(|schedule| (target.name.as_str(), schedule, target.timezone)))
+            .collect()
+    }
+
     pub fn get_input_by_name(&self, name: &Arc<str>) -> Option<&Arc<ConfigInput>> {
         self.inputs.iter().find(|i| &i.name == name)
     }
@@ -1,11 +1,15 @@
 use crate::model::config::favourites::ConfigFavourites;
+use crate::model::config::group_mapping::ConfigGroupMapping;
+use crate::model::config::channel_numbering::ConfigChannelNumbering;
 use crate::model::config::trakt::TraktConfig;
 use crate::model::mapping::Mapping;
-use crate::model::{macros, ConfigRename, ConfigSort};
+use crate::model::{macros, ConfigPublicUrl, ConfigRename, ConfigSort};
 use arc_swap::ArcSwapOption;
-use shared::model::{ConfigTargetDto, ConfigTargetOptions, HdHomeRunTargetOutputDto, M3uTargetOutputDto,
-                    ProcessingOrder, StrmExportStyle, StrmTargetOutputDto, TargetOutputDto, TargetType, TraktConfigDto, XtreamTargetOutputDto};
-use shared::model::PlaylistItemType;
+use chrono_tz::Tz;
+use shared::model::{ConfigTargetDto, ConfigTargetOptions, HdHomeRunTargetOutputDto, M3uTargetOutputDto, MediaServerConfigDto, PlexChannelRangeDto, PlexTargetOutputDto,
+                    ProcessingOrder, StrmExportStyle, StrmTargetOutputDto, TargetOutputDto, TargetType, TraktConfigDto, XtreamServerInfoDto, XtreamTargetOutputDto};
+use shared::model::{HeaderPolicyDto, PlaylistItemType};
+use std::collections::HashSet;
 use std::sync::Arc;
 use shared::foundation::Filter;
 use shared::foundation::ValueProvider;
@@ -40,6 +44,7 @@ pub struct XtreamTargetOutput {
     pub resolve_vod_delay: u16,
     pub trakt: Option<TraktConfig>,
     pub filter: Option<Filter>,
+    pub server_info: Option<XtreamServerInfoDto>,
 }
 
 macros::from_impl!(XtreamTargetOutput);
@@ -55,6 +60,7 @@ impl From<&XtreamTargetOutputDto> for XtreamTargetOutput {
             resolve_vod_delay: dto.resolve_vod_delay,
             trakt: dto.trakt.as_ref().map(Into::into),
             filter: dto.t_filter.clone(),
+            server_info: dto.server_info.clone(),
         }
     }
 }
@@ -71,16 +77,20 @@ impl From<&XtreamTargetOutput> for XtreamTargetOutputDto {
             resolve_vod_delay: instance.resolve_vod_delay,
             trakt: instance.trakt.as_ref().map(TraktConfigDto::from),
             filter: instance.filter.as_ref().map(ToString::to_string),
+            server_info: instance.server_info.clone(),
             t_filter: instance.filter.clone(),
         }
     }
 }
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
 pub struct M3uTargetOutput {
     pub filename: Option<String>,
     pub include_type_in_url: bool,
     pub mask_redirect_url: bool,
+    pub watermark: bool,
+    pub hls: bool,
     pub filter: Option<Filter>,
 }
 
@@ -91,6 +101,8 @@ impl From<&M3uTargetOutputDto> for M3uTargetOutput {
             filename: dto.filename.clone(),
             include_type_in_url: dto.include_type_in_url,
             mask_redirect_url: dto.mask_redirect_url,
+            watermark: dto.watermark,
+            hls: dto.hls,
             filter: dto.t_filter.clone(),
         }
     }
@@ -101,6 +113,8 @@ impl From<&M3uTargetOutput> for M3uTargetOutputDto {
             filename: instance.filename.clone(),
             include_type_in_url: instance.include_type_in_url,
             mask_redirect_url: instance.mask_redirect_url,
+            watermark: instance.watermark,
+            hls: instance.hls,
             filter: instance.filter.as_ref().map(ToString::to_string),
             t_filter: instance.filter.clone(),
         }
@@ -183,12 +197,113 @@ impl From<&HdHomeRunTargetOutput> for HdHomeRunTargetOutputDto {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct PlexChannelRange {
+    pub group: Arc<regex::Regex>,
+    pub start: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlexTargetOutput {
+    pub device: String,
+    pub username: String,
+    pub use_output: Option<TargetType>,
+    pub channel_start: u32,
+    pub channel_ranges: Vec<PlexChannelRange>,
+    pub use_channel_numbers: bool,
+}
+
+macros::from_impl!(PlexTargetOutput);
+impl From<&PlexTargetOutputDto> for PlexTargetOutput {
+    fn from(dto: &PlexTargetOutputDto) -> Self {
+        Self {
+            device: dto.device.clone(),
+            username: dto.username.clone(),
+            use_output: dto.use_output,
+            channel_start: dto.channel_start,
+            // the ranges are validated during prepare
+            channel_ranges: dto.channel_ranges.iter().flatten()
+                .filter_map(|range| regex::Regex::new(&range.group).ok()
+                    .map(|group| PlexChannelRange { group: Arc::new(group), start: range.start }))
+                .collect(),
+            use_channel_numbers: dto.use_channel_numbers,
+        }
+    }
+}
+impl From<&PlexTargetOutput> for PlexTargetOutputDto {
+    fn from(instance: &PlexTargetOutput) -> Self {
+        Self {
+            device: instance.device.clone(),
+            username: instance.username.clone(),
+            use_output: instance.use_output,
+            channel_start: instance.channel_start,
+            channel_ranges: if instance.channel_ranges.is_empty() {
+                None
+            } else {
+                Some(instance.channel_ranges.iter()
+                    .map(|range| PlexChannelRangeDto { group: range.group.as_str().to_string(), start: range.start })
+                    .collect())
+            },
+            use_channel_numbers: instance.use_channel_numbers,
+        }
+    }
+}
+
+impl PlexTargetOutput {
+    pub fn channel_numbering(&self) -> PlexChannelNumbering<'_> {
+        PlexChannelNumbering {
+            output: self,
+            next_default: self.channel_start,
+            next_in_range: self.channel_ranges.iter().map(|range| range.start).collect(),
+            taken: HashSet::new(),
+        }
+    }
+}
+
+/// Assigns the unique channel numbers of the plex lineup in playlist order.
+pub struct PlexChannelNumbering<'a> {
+    output: &'a PlexTargetOutput,
+    next_default: u32,
+    next_in_range: Vec<u32>,
+    taken: HashSet<u32>,
+}
+
+impl PlexChannelNumbering<'_> {
+    fn next_free(&self, mut number: u32) -> u32 {
+        while self.taken.contains(&number) {
+            number = number.saturating_add(1);
+        }
+        number
+    }
+
+    /// The playlist number is kept when requested and free, otherwise the next number
+    /// of the first matching channel range or the default numbering is used.
+    pub fn assign(&mut self, group: &str, chno: u32) -> u32 {
+        if self.output.use_channel_numbers && chno > 0 && !self.taken.contains(&chno) {
+            self.taken.insert(chno);
+            return chno;
+        }
+        let number = if let Some(index) = self.output.channel_ranges.iter().position(|range| range.group.is_match(group)) {
+            let number = self.next_free(self.next_in_range[index]);
+            self.next_in_range[index] = number.saturating_add(1);
+            number
+        } else {
+            let number = self.next_free(self.next_default);
+            self.next_default = number.saturating_add(1);
+            number
+        };
+        self.taken.insert(number);
+        number
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum TargetOutput {
     Xtream(XtreamTargetOutput),
     M3u(M3uTargetOutput),
     Strm(StrmTargetOutput),
     HdHomeRun(HdHomeRunTargetOutput),
+    Plex(PlexTargetOutput),
 }
 
 macros::from_impl!(TargetOutput);
@@ -199,6 +314,7 @@ impl From<&TargetOutputDto> for TargetOutput {
             TargetOutputDto::M3u(o) => TargetOutput::M3u(M3uTargetOutput::from(o)),
             TargetOutputDto::Strm(o) => TargetOutput::Strm(StrmTargetOutput::from(o)),
             TargetOutputDto::HdHomeRun(o) => TargetOutput::HdHomeRun(HdHomeRunTargetOutput::from(o)),
+            TargetOutputDto::Plex(o) => TargetOutput::Plex(PlexTargetOutput::from(o)),
         }
     }
 }
@@ -210,6 +326,7 @@ impl From<&TargetOutput> for TargetOutputDto {
             TargetOutput::M3u(o) => TargetOutputDto::M3u(M3uTargetOutputDto::from(o)),
             TargetOutput::Strm(o) => TargetOutputDto::Strm(StrmTargetOutputDto::from(o)),
             TargetOutput::HdHomeRun(o) => TargetOutputDto::HdHomeRun(HdHomeRunTargetOutputDto::from(o)),
+            TargetOutput::Plex(o) => TargetOutputDto::Plex(PlexTargetOutputDto::from(o)),
         }
     }
 }
@@ -227,9 +344,15 @@ pub struct ConfigTarget {
     pub mapping_ids: Option<Vec<String>>,
     pub mapping: Arc<ArcSwapOption<Vec<Mapping>>>,
     pub favourites: Option<Vec<ConfigFavourites>>,
+    pub group_mapping: Option<ConfigGroupMapping>,
+    pub channel_numbering: Option<ConfigChannelNumbering>,
     pub processing_order: ProcessingOrder,
     pub watch: Option<Vec<Arc<regex::Regex>>>,
     pub use_memory_cache: bool,
+    pub media_servers: Vec<MediaServerConfigDto>,
+    pub shadow_of: Option<String>,
+    pub public_url: Option<ConfigPublicUrl>,
+    pub timezone: Option<Tz>,
 }
 
 impl ConfigTarget {
@@ -261,6 +384,7 @@ impl ConfigTarget {
     //     }
     // }
 
+    #[cfg(feature = "hdhomerun")]
     pub(crate) fn get_hdhomerun_output(&self) -> Option<&HdHomeRunTargetOutput> {
         if let Some(TargetOutput::HdHomeRun(output)) = self.output.iter().find(|o| matches!(o, TargetOutput::HdHomeRun(_))) {
             Some(output)
@@ -269,6 +393,15 @@ impl ConfigTarget {
         }
     }
 
+    #[cfg(feature = "hdhomerun")]
+    pub(crate) fn get_plex_output(&self) -> Option<&PlexTargetOutput> {
+        if let Some(TargetOutput::Plex(output)) = self.output.iter().find(|o| matches!(o, TargetOutput::Plex(_))) {
+            Some(output)
+        } else {
+            None
+        }
+    }
+
     pub fn has_output(&self, tt: TargetType) -> bool {
         for target_output in &self.output {
             match target_output {
@@ -276,11 +409,33 @@ impl ConfigTarget {
                 TargetOutput::M3u(_) => { if tt == TargetType::M3u { return true; } }
                 TargetOutput::Strm(_) => { if tt == TargetType::Strm { return true; } }
                 TargetOutput::HdHomeRun(_) => { if tt == TargetType::HdHomeRun { return true; } }
+                TargetOutput::Plex(_) => { if tt == TargetType::Plex { return true; } }
             }
         }
         false
     }
 
+    /// A shadow target is processed like its shadowed target but it is not served to users.
+    pub fn is_shadow(&self) -> bool {
+        self.shadow_of.is_some()
+    }
+
+    pub fn is_cache_warmup(&self) -> bool {
+        self.options.as_ref().is_some_and(|options| options.cache_warmup)
+    }
+
+    pub fn is_group_series(&self) -> bool {
+        self.options.as_ref().is_some_and(|options| options.group_series)
+    }
+
+    pub fn is_collapse_variants(&self) -> bool {
+        self.options.as_ref().is_some_and(|options| options.collapse_variants)
+    }
+
+    pub fn get_header_policy(&self) -> Option<&HeaderPolicyDto> {
+        self.options.as_ref().and_then(|options| options.header_policy.as_ref())
+    }
+
     pub fn is_force_redirect(&self, item_type: PlaylistItemType) -> bool {
         if item_type.is_local() {
             return false;
@@ -307,6 +462,8 @@ impl From<&ConfigTargetDto> for ConfigTarget {
             mapping_ids: dto.mapping.clone(),
             mapping: Arc::new(ArcSwapOption::new(None)),
             favourites: dto.favourites.as_ref().map(|f| f.iter().map(Into::into).collect()),
+            group_mapping: dto.group_mapping.as_ref().map(Into::into),
+            channel_numbering: dto.channel_numbering.as_ref().map(Into::into),
             processing_order: dto.processing_order,
             watch: dto.watch.as_ref().map(|list| list.iter().filter_map(|s|
                 match shared::model::REGEX_CACHE.get_or_compile(s) {
@@ -317,6 +474,39 @@ impl From<&ConfigTargetDto> for ConfigTarget {
                     }
                 }).collect()),
             use_memory_cache: dto.use_memory_cache,
+            media_servers: dto.media_servers.iter().flatten().filter(|media_server| media_server.enabled).cloned().collect(),
+            shadow_of: dto.shadow_of.clone(),
+            public_url: dto.public_url.as_ref().map(Into::into),
+            timezone: dto.options.as_ref().and_then(|options| options.timezone.as_deref()).and_then(|tz| match tz.parse::<Tz>() {
+                Ok(tz) => Some(tz),
+                Err(err) => {
+                    log::warn!("Invalid timezone '{tz}' for target {}: {err}", dto.name);
+                    None
+                }
+            }),
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::PlexTargetOutput;
+    use shared::model::{PlexChannelRangeDto, PlexTargetOutputDto};
+
+    #[test]
+    fn test_plex_channel_numbering() {
+        let output = PlexTargetOutput::from(&PlexTargetOutputDto {
+            channel_start: 100,
+            channel_ranges: Some(vec![PlexChannelRangeDto { group: "^Sports".to_string(), start: 500 }]),
+            use_channel_numbers: true,
+            ..PlexTargetOutputDto::default()
+        });
+        let mut numbering = output.channel_numbering();
+        assert_eq!(numbering.assign("News", 0), 100);
+        assert_eq!(numbering.assign("Sports DE", 0), 500);
+        assert_eq!(numbering.assign("News", 101), 101);
+        assert_eq!(numbering.assign("News", 0), 102);
+        // taken vanity number falls back to the range
+        assert_eq!(numbering.assign("Sports UK", 500), 501);
+        assert_eq!(numbering.assign("Kids", 0), 103);
+    }
+}
@@ -72,6 +72,7 @@ pub struct TraktConfig {
     pub enabled: bool,
     pub api: TraktApiConfig,
     pub lists: Vec<TraktListConfig>,
+    pub schedule: Option<String>,
 }
 
 macros::from_impl!(TraktConfig);
@@ -81,6 +82,7 @@ impl From<&TraktConfigDto>  for TraktConfig {
             enabled: dto.enabled,
             api: TraktApiConfig::from(&dto.api),
             lists: dto.lists.iter().map(Into::into).collect(),
+            schedule: dto.schedule.clone(),
         }
     }
 }
@@ -90,6 +92,7 @@ impl From<&TraktConfig>  for TraktConfigDto {
             enabled: dto.enabled,
             api: TraktApiConfigDto::from(&dto.api),
             lists: dto.lists.iter().map(TraktListConfigDto::from).collect(),
+            schedule: dto.schedule.clone(),
         }
     }
 }
@@ -1,5 +1,6 @@
 use shared::utils::is_blank_optional_string;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use shared::model::TraktContentType;
 use crate::utils::normalize_title_for_matching;
 
@@ -66,6 +67,7 @@ pub struct TraktMatchItem<'a> {
     pub trakt_id: u32,
     pub content_type: TraktContentType,
     pub rank: Option<u32>,
+    pub ids: &'a TraktIds,
 }
 
 impl<'a> TraktMatchItem<'a> {
@@ -80,6 +82,7 @@ impl<'a> TraktMatchItem<'a> {
                     trakt_id: movie.ids.trakt,
                     content_type: TraktContentType::Vod,
                     rank: item.rank,
+                    ids: &movie.ids,
                 })
             }
             "show" => {
@@ -91,9 +94,36 @@ impl<'a> TraktMatchItem<'a> {
                     trakt_id: show.ids.trakt,
                     content_type: TraktContentType::Series,
                     rank: item.rank,
+                    ids: &show.ids,
                 })
             }
             _ => None,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Ids of the Trakt items matched with the playlist, by normalized playlist title.
+/// The strm export writes them into the NFO files.
+#[derive(Debug, Default)]
+pub struct TraktMatchedIds {
+    movies: HashMap<String, TraktIds>,
+    series: HashMap<String, TraktIds>,
+}
+
+impl TraktMatchedIds {
+    pub fn insert(&mut self, content_type: TraktContentType, normalized_title: String, ids: &TraktIds) {
+        match content_type {
+            TraktContentType::Vod => { self.movies.entry(normalized_title).or_insert_with(|| ids.clone()); }
+            TraktContentType::Series => { self.series.entry(normalized_title).or_insert_with(|| ids.clone()); }
+            TraktContentType::Both => {}
+        }
+    }
+
+    pub fn get_movie(&self, title: &str) -> Option<&TraktIds> {
+        self.movies.get(&normalize_title_for_matching(title))
+    }
+
+    pub fn get_series(&self, title: &str) -> Option<&TraktIds> {
+        self.series.get(&normalize_title_for_matching(title))
+    }
+}
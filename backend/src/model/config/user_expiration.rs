@@ -0,0 +1,24 @@
+use crate::model::macros;
+use shared::model::UserExpirationConfigDto;
+
+#[derive(Debug, Clone)]
+pub struct UserExpirationConfig {
+    pub interval_mins: u32,
+    pub disable_expired: bool,
+    pub delete_after_days: Option<u32>,
+    pub notify_days_before: Option<u32>,
+    pub notify_template: Option<String>,
+}
+
+macros::from_impl!(UserExpirationConfig);
+impl From<&UserExpirationConfigDto> for UserExpirationConfig {
+    fn from(dto: &UserExpirationConfigDto) -> Self {
+        Self {
+            interval_mins: dto.interval_mins,
+            disable_expired: dto.disable_expired,
+            delete_after_days: dto.delete_after_days,
+            notify_days_before: dto.notify_days_before,
+            notify_template: dto.notify_template.clone(),
+        }
+    }
+}
@@ -1,8 +1,9 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufRead;
 use std::path::PathBuf;
 use shared::error::{TuliproxError, info_err_res};
-use shared::model::{UserCredential, WebAuthConfigDto};
+use shared::model::{UserCredential, WebAuthConfigDto, WebUiRole};
 use crate::model::macros;
 use crate::utils;
 
@@ -13,6 +14,7 @@ pub struct WebAuthConfig {
     pub secret: String,
     pub token_ttl_mins: u32,
     pub userfile: Option<String>,
+    pub roles: HashMap<String, WebUiRole>,
     pub t_users: Option<Vec<UserCredential>>,
 }
 
@@ -25,6 +27,7 @@ impl From<&WebAuthConfigDto> for WebAuthConfig {
             secret: dto.secret.clone(),
             token_ttl_mins: dto.token_ttl_mins,
             userfile: dto.userfile.clone(),
+            roles: dto.roles.clone(),
             t_users: None,
         }
     }
@@ -38,6 +41,7 @@ impl From<&WebAuthConfig> for WebAuthConfigDto {
             secret: instance.secret.clone(),
             token_ttl_mins: instance.token_ttl_mins,
             userfile: instance.userfile.clone(),
+            roles: instance.roles.clone(),
         }
     }
 }
@@ -87,4 +91,11 @@ impl WebAuthConfig {
         }
         None
     }
-}
\ No newline at end of file
+
+    /// Users without a configured role are admins.
+    pub fn get_user_role(&self, username: &str) -> WebUiRole {
+        self.roles.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(username))
+            .map_or(WebUiRole::Admin, |(_, role)| *role)
+    }
+}
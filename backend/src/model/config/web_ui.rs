@@ -1,5 +1,5 @@
 use shared::error::TuliproxError;
-use shared::model::{ContentSecurityPolicyConfigDto, WebUiConfigDto};
+use shared::model::{ContentSecurityPolicyConfigDto, PublicStatusConfigDto, WebUiConfigDto};
 use shared::utils::default_kick_secs;
 use crate::model::{macros, WebAuthConfig};
 
@@ -9,6 +9,12 @@ pub struct ContentSecurityPolicyConfig {
     pub custom_attributes: Option<Vec<String>>,
 }
 
+#[derive(Debug, Clone)]
+pub struct PublicStatusConfig {
+    pub enabled: bool,
+    pub maintenance: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct WebUiConfig {
     pub enabled: bool,
@@ -18,6 +24,7 @@ pub struct WebUiConfig {
     pub auth: Option<WebAuthConfig>,
     pub player_server: Option<String>,
     pub kick_secs: u64,
+    pub public_status: Option<PublicStatusConfig>,
 }
 
 impl WebUiConfig {
@@ -56,6 +63,26 @@ impl From<&ContentSecurityPolicyConfig> for ContentSecurityPolicyConfigDto {
     }
 }
 
+macros::from_impl!(PublicStatusConfig);
+
+impl From<&PublicStatusConfigDto> for PublicStatusConfig {
+    fn from(dto: &PublicStatusConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            maintenance: dto.maintenance.clone(),
+        }
+    }
+}
+
+impl From<&PublicStatusConfig> for PublicStatusConfigDto {
+    fn from(instance: &PublicStatusConfig) -> Self {
+        Self {
+            enabled: instance.enabled,
+            maintenance: instance.maintenance.clone(),
+        }
+    }
+}
+
 macros::from_impl!(WebUiConfig);
 impl From<&WebUiConfigDto> for WebUiConfig {
     fn from(dto: &WebUiConfigDto) -> Self {
@@ -67,6 +94,7 @@ impl From<&WebUiConfigDto> for WebUiConfig {
             auth: dto.auth.as_ref().map(Into::into),
             player_server: dto.player_server.clone(),
             kick_secs: dto.kick_secs,
+            public_status: dto.public_status.as_ref().map(Into::into),
         }
     }
 }
@@ -80,6 +108,7 @@ impl From<&WebUiConfig> for WebUiConfigDto {
             auth: instance.auth.as_ref().map(Into::into),
             player_server: instance.player_server.clone(),
             kick_secs: instance.kick_secs,
+            public_status: instance.public_status.as_ref().map(Into::into),
         }
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use shared::model::InputFetchMethod;
-use crate::model::{ConfigInput, StagedInput};
+use shared::model::{HeaderRulesDto, ImpersonationProfile, InputFetchMethod};
+use crate::model::{ConfigInput, InputBasicAuth, StagedInput};
 
 #[derive(Clone, Debug)]
 pub struct InputSource {
@@ -11,6 +11,9 @@ pub struct InputSource {
     pub password: Option<String>,
     pub method: InputFetchMethod,
     pub headers: HashMap<String, String>,
+    pub header_rules: Option<HeaderRulesDto>,
+    pub basic_auth: Option<InputBasicAuth>,
+    pub impersonate: Option<ImpersonationProfile>,
 }
 
 impl InputSource {
@@ -22,26 +25,32 @@ impl InputSource {
             password: self.password.clone(),
             method: self.method,
             headers: self.headers.clone(),
+            header_rules: self.header_rules.clone(),
+            basic_auth: self.basic_auth.clone(),
+            impersonate: self.impersonate,
         }
     }
 }
 
 macro_rules! impl_input_source_from {
-    ($input_type:ty) => {
+    ($input_type:ty, $input:ident => $impersonate:expr, $header_rules:expr, $basic_auth:expr) => {
         impl From<&$input_type> for InputSource {
-            fn from(input: &$input_type) -> Self {
+            fn from($input: &$input_type) -> Self {
                 Self {
-                    name: input.name.clone(),
-                    url: input.url.clone(),
-                    username: input.username.clone(),
-                    password: input.password.clone(),
-                    method: input.method,
-                    headers: input.headers.clone(),
+                    name: $input.name.clone(),
+                    url: $input.url.clone(),
+                    username: $input.username.clone(),
+                    password: $input.password.clone(),
+                    method: $input.method,
+                    headers: $input.headers.clone(),
+                    header_rules: $header_rules,
+                    basic_auth: $basic_auth,
+                    impersonate: $impersonate,
                 }
             }
         }
     };
 }
 
-impl_input_source_from!(ConfigInput);
-impl_input_source_from!(StagedInput);
\ No newline at end of file
+impl_input_source_from!(ConfigInput, input => input.impersonate, input.get_upstream_header_rules(None), input.get_basic_auth());
+impl_input_source_from!(StagedInput, input => None, None, None);
\ No newline at end of file
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use shared::model::{PlaylistItem, StreamProperties};
 use std::fmt;
 
 // Enum for Video Resolution
@@ -236,6 +237,19 @@ impl MediaQuality {
         Some((resolution, video_codec, dynamic_range))
     }
 
+    /// Quality of a movie or episode from the stream properties of the provider.
+    pub fn from_playlist_item(pli: &PlaylistItem) -> Option<Self> {
+        let (audio, video) = match pli.header.additional_properties.as_ref()? {
+            StreamProperties::Live(_)
+            | StreamProperties::Series(_) => (None, None),
+            StreamProperties::Video(video) =>
+                video.details.as_ref().map_or_else(|| (None, None), |d| (d.audio.as_deref(), d.video.as_deref())),
+            StreamProperties::Episode(episode) =>
+                (episode.audio.as_deref(), episode.video.as_deref())
+        };
+        Self::from_ffprobe_info(audio, video)
+    }
+
     /// Extracts media quality information from an `ffprobe` info block.
     /// The `info_block` is expected to be a `serde_json::Value` object.
     pub fn from_ffprobe_info(audio: Option<&str>, video: Option<&str>) -> Option<Self> {
@@ -71,6 +71,9 @@ pub struct TemplateContext<'a> {
     pub stats: Option<&'a Vec<SourceStats>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub watch: Option<&'a WatchChanges>,
+    // Playlist change summary per target, like `my_target: 12 channels added, 3 removed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<Vec<String>>,
     // For manual error json or other json events embedded in string
     #[serde(skip_serializing_if = "Option::is_none")]
     pub processing: Option<ProcessingStats>,
@@ -1,3 +1,4 @@
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
 use crate::model::xmltv::XmlTagIcon::Undefined;
 use crate::model::InputSource;
@@ -212,6 +213,7 @@ pub async fn parse_xmltv_for_web_ui_from_file(path: &Path) -> Result<Vec<EpgChan
     parse_xmltv_for_web_ui(file).await
 }
 
+#[cfg(feature = "server")]
 pub async fn parse_xmltv_for_web_ui_from_url(app_state: &Arc<AppState>, url: &str) -> Result<Vec<EpgChannel>, TuliproxError> {
     if let Ok(request_url) = Url::parse(url) {
         let client = app_state.http_client.load();
@@ -222,6 +224,9 @@ pub async fn parse_xmltv_for_web_ui_from_url(app_state: &Arc<AppState>, url: &st
             password: None,
             method: InputFetchMethod::GET,
             headers: HashMap::default(),
+            header_rules: None,
+            basic_auth: None,
+            impersonate: None,
         };
 
         match get_remote_content_as_stream(
@@ -49,11 +49,13 @@ pub fn xtream_mapping_option_from_target_options(target: &ConfigTarget, target_o
         skip_video_direct_source: target_output.skip_video_direct_source,
         skip_series_direct_source: target_output.skip_series_direct_source,
         rewrite_resource_url: cfg.is_reverse_proxy_resource_rewrite_enabled(),
+        proxy_logos: cfg.is_logo_proxy_enabled(),
         force_redirect,
         reverse_item_types,
         username: user.username.clone(),
         password: user.password.clone(),
         base_url: resource_url.map(ToString::to_string),
+        strip_fields: target.options.as_ref().map(|o| o.strip_fields.clone()).unwrap_or_default(),
     }
 }
 
@@ -0,0 +1,75 @@
+use crate::model::{AppConfig, ConfigInput, SourcesConfig};
+use crate::repository::for_each_stored_input_item;
+use shared::error::{info_err_res, TuliproxError};
+use shared::foundation::{get_filter, Filter, FilterClauseMatch, ValueProvider};
+use shared::model::{FilterClauseMatchDto, FilterTestMatchDto, FilterTestRequest, FilterTestResultDto, PatternTemplate, TemplateValue,
+                    FILTER_TEST_DEFAULT_LIMIT};
+use std::sync::Arc;
+
+const FILTER_TEST_MAX_LIMIT: usize = 1000;
+
+/// The inputs of the source of the target or the requested input.
+fn get_test_inputs(sources: &SourcesConfig, request: &FilterTestRequest) -> Result<Vec<Arc<ConfigInput>>, TuliproxError> {
+    if let Some(target_name) = request.target.as_deref() {
+        let Some(source) = sources.sources.iter().find(|source| source.targets.iter().any(|target| target.name == target_name)) else {
+            return info_err_res!("Target not found {target_name}");
+        };
+        return Ok(source.inputs.iter().filter_map(|name| sources.get_input_by_name(name)).cloned().collect());
+    }
+    if let Some(input_name) = request.input.as_deref() {
+        return match sources.inputs.iter().find(|input| &*input.name == input_name) {
+            Some(input) => Ok(vec![Arc::clone(input)]),
+            None => info_err_res!("Input not found {input_name}"),
+        };
+    }
+    info_err_res!("Either a target or an input is required")
+}
+
+/// Templates referenced by the filter text whose value is part of the regex of the clause.
+fn get_clause_templates(clause: &Filter, templates: &[&PatternTemplate]) -> Vec<String> {
+    let Filter::FieldComparison(_, rewc) = clause else { return vec![]; };
+    templates.iter()
+        .filter(|template| matches!(&template.value, TemplateValue::Single(value) if !value.is_empty() && rewc.restr.contains(value.as_str())))
+        .map(|template| template.name.clone())
+        .collect()
+}
+
+fn to_clause_dto(clause: &FilterClauseMatch, templates: &[&PatternTemplate]) -> FilterClauseMatchDto {
+    FilterClauseMatchDto {
+        clause: clause.clause.to_string(),
+        value: clause.value.as_deref().map(ToString::to_string),
+        templates: get_clause_templates(clause.clause, templates),
+    }
+}
+
+/// Runs the filter against the stored playlists without touching them.
+/// Each match lists the clauses which decided it, the counts include the matches beyond the limit.
+pub async fn test_filter(app_config: &AppConfig, request: &FilterTestRequest) -> Result<FilterTestResultDto, TuliproxError> {
+    let sources = app_config.sources.load();
+    let inputs = get_test_inputs(&sources, request)?;
+    let filter = get_filter(&request.filter, sources.templates.as_ref())?;
+    let templates: Vec<&PatternTemplate> = sources.templates.iter().flatten()
+        .filter(|template| request.filter.contains(&format!("!{}!", template.name)))
+        .collect();
+    let limit = request.limit.unwrap_or(FILTER_TEST_DEFAULT_LIMIT).min(FILTER_TEST_MAX_LIMIT);
+
+    let mut result = FilterTestResultDto::default();
+    for input in &inputs {
+        for_each_stored_input_item(app_config, input, |pli| {
+            result.total += 1;
+            let Some(clauses) = filter.explain(&ValueProvider { pli, match_as_ascii: false }) else { return; };
+            result.matched += 1;
+            if result.items.len() < limit {
+                let header = &pli.header;
+                result.items.push(FilterTestMatchDto {
+                    input: input.name.to_string(),
+                    group: header.group.to_string(),
+                    title: if header.title.is_empty() { header.name.to_string() } else { header.title.to_string() },
+                    cluster: header.xtream_cluster,
+                    clauses: clauses.iter().map(|clause| to_clause_dto(clause, &templates)).collect(),
+                });
+            }
+        }).await;
+    }
+    Ok(result)
+}
@@ -0,0 +1,64 @@
+use crate::model::{AppConfig, Mapper, Mapping};
+use crate::repository::for_each_stored_input_item;
+use shared::error::{info_err_res, TuliproxError};
+use shared::foundation::{get_trace_fields, ValueAccessor, ValueProvider};
+use shared::model::{MapperItemTraceDto, MapperTraceDto, MapperTraceRequest, MapperTraceResultDto, MAPPER_TRACE_DEFAULT_SAMPLE};
+
+const MAPPER_TRACE_MAX_SAMPLE: usize = 100;
+
+/// Runs the mappings of the target on a copy of the stored input items and records every step of the scripts
+/// for the first matches of each mapper. Like the processing, the mappers are applied one after another to the same item,
+/// the virtual items created by a script are not mapped again.
+pub async fn trace_mappings(app_config: &AppConfig, request: &MapperTraceRequest) -> Result<MapperTraceResultDto, TuliproxError> {
+    let sources = app_config.sources.load();
+    let Some((source, target)) = sources.sources.iter()
+        .find_map(|source| source.targets.iter().find(|target| target.name == request.target).map(|target| (source, target))) else {
+        return info_err_res!("Target not found {}", request.target);
+    };
+    let mapping_binding = target.mapping.load();
+    let mappers: Vec<(&Mapping, usize, &Mapper)> = mapping_binding.iter().flat_map(|mappings| mappings.iter())
+        .filter(|mapping| request.mapping.as_ref().is_none_or(|id| &mapping.id == id))
+        .flat_map(|mapping| mapping.mapper.iter().flatten().enumerate().map(move |(index, mapper)| (mapping, index, mapper)))
+        .filter(|(_, _, mapper)| mapper.t_filter.is_some() && mapper.t_script.is_some())
+        .collect();
+    if mappers.is_empty() {
+        return info_err_res!("No mapper found for target {}", request.target);
+    }
+    let sample = request.sample.unwrap_or(MAPPER_TRACE_DEFAULT_SAMPLE).min(MAPPER_TRACE_MAX_SAMPLE);
+
+    let mut result = MapperTraceResultDto {
+        total: 0,
+        mappers: mappers.iter().map(|(mapping, index, _)| MapperTraceDto { mapping: mapping.id.clone(), mapper: *index, matched: 0, items: vec![] }).collect(),
+    };
+    for input in source.inputs.iter().filter_map(|name| sources.get_input_by_name(name)) {
+        for_each_stored_input_item(app_config, input, |pli| {
+            result.total += 1;
+            let mut item = pli.clone();
+            for ((mapping, _, mapper), trace) in mappers.iter().zip(result.mappers.iter_mut()) {
+                let (Some(filter), Some(script)) = (mapper.t_filter.as_ref(), mapper.t_script.as_ref()) else { continue; };
+                if !filter.filter(&ValueProvider { pli: &item, match_as_ascii: mapping.match_as_ascii }) {
+                    continue;
+                }
+                trace.matched += 1;
+                let fields = (trace.items.len() < sample).then(|| get_trace_fields(&item));
+                let mut accessor = ValueAccessor { pli: &mut item, virtual_items: vec![], match_as_ascii: mapping.match_as_ascii };
+                match fields {
+                    Some(fields) => {
+                        let steps = script.eval_traced(&mut accessor, mapping.templates.as_ref());
+                        trace.items.push(MapperItemTraceDto {
+                            input: input.name.to_string(),
+                            cluster: pli.header.xtream_cluster,
+                            fields: fields.into_iter()
+                                .filter_map(|(field, value)| value.filter(|v| !v.is_empty()).map(|v| (field.to_string(), v.to_string())))
+                                .collect(),
+                            steps,
+                            virtual_items: accessor.virtual_items.len(),
+                        });
+                    }
+                    None => script.eval(&mut accessor, mapping.templates.as_ref()),
+                }
+            }
+        }).await;
+    }
+    Ok(result)
+}
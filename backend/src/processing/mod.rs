@@ -1,5 +1,7 @@
+pub(crate) mod filter_test;
 pub(crate) mod input_cache;
+pub(crate) mod mapper_trace;
 pub(crate) mod playlist_watch;
 pub(crate) mod parser;
 pub(crate) mod processor;
-
+pub(crate) mod rule_lint;
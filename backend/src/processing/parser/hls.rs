@@ -114,11 +114,75 @@ pub fn rewrite_hls(user: &ProxyUserCredentials, props: &RewriteHlsProps) -> Stri
     result.join("\r\n")
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct HlsMediaSegment {
+    pub url: String,
+    pub duration: f64,
+}
+
+/// Parsed upstream HLS playlist used for re-muxing.
+/// For a master playlist only `variant` is set, pointing to the stream with the highest bandwidth.
+#[derive(Debug, Default)]
+pub struct HlsPlaylistInfo {
+    pub variant: Option<String>,
+    pub media_sequence: u64,
+    pub target_duration: u64,
+    pub end_list: bool,
+    /// Encrypted or fragmented MP4 segments can't be re-served as plain MPEG-TS segments.
+    pub unsupported: bool,
+    pub segments: Vec<HlsMediaSegment>,
+}
+
+fn get_hls_attribute<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    line.split_once(':')?.1.split(',')
+        .filter_map(|attr| attr.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim().trim_matches('"'))
+}
+
+pub fn parse_hls_playlist(content: &str, playlist_url: &str) -> HlsPlaylistInfo {
+    let mut info = HlsPlaylistInfo::default();
+    let mut best_bandwidth: Option<u64> = None;
+    let mut pending_bandwidth: Option<u64> = None;
+    let mut pending_duration: Option<f64> = None;
+
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(value) = line.strip_prefix("#EXT-X-MEDIA-SEQUENCE:") {
+            info.media_sequence = value.trim().parse().unwrap_or(0);
+        } else if let Some(value) = line.strip_prefix("#EXT-X-TARGETDURATION:") {
+            info.target_duration = value.trim().parse().unwrap_or(0);
+        } else if let Some(value) = line.strip_prefix("#EXTINF:") {
+            pending_duration = value.split(',').next().and_then(|d| d.trim().parse().ok());
+        } else if line.starts_with("#EXT-X-STREAM-INF") {
+            pending_bandwidth = Some(get_hls_attribute(line, "BANDWIDTH").and_then(|b| b.parse().ok()).unwrap_or(0));
+        } else if line.starts_with("#EXT-X-ENDLIST") {
+            info.end_list = true;
+        } else if line.starts_with("#EXT-X-MAP") {
+            info.unsupported = true;
+        } else if line.starts_with("#EXT-X-KEY") {
+            if get_hls_attribute(line, "METHOD").is_some_and(|m| !m.eq_ignore_ascii_case("NONE")) {
+                info.unsupported = true;
+            }
+        } else if !line.starts_with('#') {
+            let url = rewrite_hls_url(playlist_url, line).to_string();
+            if let Some(bandwidth) = pending_bandwidth.take() {
+                if best_bandwidth.is_none_or(|best| bandwidth > best) {
+                    best_bandwidth = Some(bandwidth);
+                    info.variant = Some(url);
+                }
+            } else {
+                info.segments.push(HlsMediaSegment { url, duration: pending_duration.take().unwrap_or(0.0) });
+            }
+        }
+    }
+    info
+}
+
 #[cfg(test)]
 mod test {
     use rand::RngCore;
     use shared::utils::u32_to_base64;
-    use crate::processing::parser::hls::{rewrite_hls_url};
+    use crate::processing::parser::hls::{parse_hls_playlist, rewrite_hls_url};
 
     #[test]
     fn test_token_size() {
@@ -199,4 +263,25 @@ mod test {
         let out = rewrite_hls_url(base, fragment);
         assert_eq!(out, "http://example.com/hls/seg.ts#t=10");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_master_playlist_selects_highest_bandwidth() {
+        let content = "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=800000,RESOLUTION=640x360\nlow/index.m3u8\n#EXT-X-STREAM-INF:BANDWIDTH=2500000,RESOLUTION=1280x720\nhigh/index.m3u8\n";
+        let info = parse_hls_playlist(content, "http://example.com/live/master.m3u8");
+        assert_eq!(info.variant.as_deref(), Some("http://example.com/live/high/index.m3u8"));
+        assert!(info.segments.is_empty());
+    }
+
+    #[test]
+    fn parse_media_playlist() {
+        let content = "#EXTM3U\n#EXT-X-TARGETDURATION:6\n#EXT-X-MEDIA-SEQUENCE:120\n#EXTINF:5.96,\nseg120.ts\n#EXTINF:6.0,\nseg121.ts\n";
+        let info = parse_hls_playlist(content, "http://example.com/live/index.m3u8");
+        assert!(info.variant.is_none());
+        assert!(!info.unsupported);
+        assert_eq!(info.media_sequence, 120);
+        assert_eq!(info.target_duration, 6);
+        assert_eq!(info.segments.len(), 2);
+        assert_eq!(info.segments[1].url, "http://example.com/live/seg121.ts");
+        assert!((info.segments[0].duration - 5.96).abs() < f64::EPSILON);
+    }
+}
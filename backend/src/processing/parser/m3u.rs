@@ -206,9 +206,26 @@ fn process_header(input_name: &Arc<str>, video_suffixes: &[String], content: &st
     plih
 }
 
+/// The headers of `#EXTVLCOPT:http-user-agent=` and `#EXTVLCOPT:http-referrer=` lines.
+fn process_vlc_opt(line: &str, user_agent: &mut Option<String>, referrer: &mut Option<String>) {
+    if let Some((option, value)) = line.strip_prefix("#EXTVLCOPT:").and_then(|opt| opt.split_once('=')) {
+        let value = value.trim();
+        if value.is_empty() {
+            return;
+        }
+        match option.trim().to_lowercase().as_str() {
+            "http-user-agent" => *user_agent = Some(value.to_string()),
+            "http-referrer" | "http-referer" => *referrer = Some(value.to_string()),
+            _ => {}
+        }
+    }
+}
+
 pub async fn consume_m3u<F: FnMut(PlaylistItem)>(cfg: &Config, input: &ConfigInput, lines: DynReader, mut visit: F) {
     let mut header: Option<String> = None;
     let mut group: Option<String> = None;
+    let mut user_agent: Option<String> = None;
+    let mut referrer: Option<String> = None;
     let input_name = &input.name;
 
     let video_suffixes = match cfg.video.as_ref() {
@@ -228,6 +245,10 @@ pub async fn consume_m3u<F: FnMut(PlaylistItem)>(cfg: &Config, input: &ConfigInp
             group = Some(String::from(&line[8..]));
             continue;
         }
+        if line.starts_with("#EXTVLCOPT") {
+            process_vlc_opt(&line, &mut user_agent, &mut referrer);
+            continue;
+        }
         if line.starts_with('#') {
             continue;
         }
@@ -236,6 +257,12 @@ pub async fn consume_m3u<F: FnMut(PlaylistItem)>(cfg: &Config, input: &ConfigInp
             let header = &mut item.header;
             header.source_ordinal = ord_counter;
             ord_counter += 1;
+            if let Some(value) = user_agent.take() {
+                header.user_agent = value.intern();
+            }
+            if let Some(value) = referrer.take() {
+                header.referrer = value.intern();
+            }
                 if header.group.is_empty() {
                     if let Some(group_value) = group {
                         header.group = group_value.intern();
@@ -248,6 +275,8 @@ pub async fn consume_m3u<F: FnMut(PlaylistItem)>(cfg: &Config, input: &ConfigInp
         }
         header = None;
         group = None;
+        user_agent = None;
+        referrer = None;
     }
 }
 
@@ -280,7 +309,7 @@ pub async fn parse_m3u(cfg: &Config, input: &ConfigInput, lines: DynReader) -> V
 #[cfg(test)]
 mod test {
     use shared::utils::Internable;
-    use crate::processing::parser::m3u::process_header;
+    use crate::processing::parser::m3u::{process_header, process_vlc_opt};
 
     #[test]
     fn test_process_header_1() {
@@ -325,4 +354,15 @@ mod test {
         assert_eq!(pli.epg_channel_id, Some("abc-seven".intern())); // Should preserve original tvg-id
         assert_eq!(&*pli.group, "Sydney");
     }
+
+    #[test]
+    fn test_process_vlc_opt() {
+        let mut user_agent = None;
+        let mut referrer = None;
+        process_vlc_opt("#EXTVLCOPT:http-user-agent=Mozilla/5.0 (Smart TV)", &mut user_agent, &mut referrer);
+        process_vlc_opt("#EXTVLCOPT:http-referrer=https://provider.tv/", &mut user_agent, &mut referrer);
+        process_vlc_opt("#EXTVLCOPT:network-caching=1000", &mut user_agent, &mut referrer);
+        assert_eq!(user_agent.as_deref(), Some("Mozilla/5.0 (Smart TV)"));
+        assert_eq!(referrer.as_deref(), Some("https://provider.tv/"));
+    }
 }
\ No newline at end of file
@@ -148,6 +148,20 @@ impl TVGuide {
         }
     }
 
+    /// Rewrites the epg id attribute of the tag to the canonical id of the tvg-id registry.
+    fn normalize_tag_epg_id(id_cache: &EpgIdCache, canonical_ids: &mut HashMap<Arc<str>, Option<Arc<str>>>, tag: &mut XmlTag, attrib: &Arc<str>) {
+        let Some(registry) = id_cache.tvg_id_registry.as_ref() else {
+            return;
+        };
+        if let Some(epg_id) = tag.attributes.as_mut().and_then(|attributes| attributes.get_mut(attrib)) {
+            let canonical = canonical_ids.entry(Arc::clone(epg_id))
+                .or_insert_with(|| registry.normalize(epg_id));
+            if let Some(canonical) = canonical {
+                *epg_id = Arc::clone(canonical);
+            }
+        }
+    }
+
     fn try_fuzzy_matching(id_cache: &mut EpgIdCache, epg_id: &Arc<str>, tag: &XmlTag, fuzzy_matching: bool) -> bool {
         let mut matched = tag
             .normalized_epg_ids
@@ -265,9 +279,11 @@ impl TVGuide {
                 let mut tv_attributes: Option<HashMap<Arc<str>, Arc<str>>> = None;
                 let smart_match = id_cache.smart_match_config.enabled;
                 let fuzzy_matching = smart_match && id_cache.smart_match_config.fuzzy_matching;
+                let mut canonical_ids: HashMap<Arc<str>, Option<Arc<str>>> = HashMap::new();
                 let mut filter_tags = |mut tag: XmlTag| {
                     match tag.name.as_ref() {
                         EPG_TAG_CHANNEL => {
+                            Self::normalize_tag_epg_id(id_cache, &mut canonical_ids, &mut tag, &epg_attrib_id);
                             let tag_epg_id = tag.get_attribute_value(&epg_attrib_id).map_or_else(|| "".intern(), Internable::intern);
                             if !tag_epg_id.is_empty() && !id_cache.processed.contains(&tag_epg_id) {
                                 Self::prepare_tag(id_cache, &mut tag, smart_match);
@@ -283,6 +299,7 @@ impl TVGuide {
                             }
                         }
                         EPG_TAG_PROGRAMME => {
+                            Self::normalize_tag_epg_id(id_cache, &mut canonical_ids, &mut tag, &epg_attrib_channel);
                             if let Some(epg_id) = tag.get_attribute_value(&epg_attrib_channel) {
                                 if id_cache.processed.contains(epg_id) && id_cache.channel_epg_id.contains(epg_id) {
                                     children.push(Arc::new(tag));
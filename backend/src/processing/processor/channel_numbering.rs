@@ -0,0 +1,157 @@
+use crate::model::ConfigChannelNumbering;
+use crate::repository::{ChannelNumberEntry, ChannelNumbers};
+use shared::model::{PlaylistGroup, UUIDType};
+use std::collections::HashSet;
+
+// The numbers of channels missing in the playlist are kept for their return.
+const MAX_CHANNEL_NUMBER_AGE_SECS: i64 = 30 * 86_400;
+
+fn next_free(taken: &HashSet<u32>, mut number: u32) -> u32 {
+    while taken.contains(&number) {
+        number = number.saturating_add(1);
+    }
+    number
+}
+
+/// Assigns the channel numbers of the live channels in playlist order. Pinned numbers come first,
+/// followed by the persisted numbers, the upstream numbers and the next free number of the group range.
+/// The persisted numbers are updated with the assignments.
+pub(in crate::processing) fn assign_channel_numbers(playlist: &mut [PlaylistGroup], numbering: &ConfigChannelNumbering,
+                                                    persisted: &mut ChannelNumbers, now: i64) {
+    let channels: Vec<(usize, usize)> = playlist.iter().enumerate()
+        .flat_map(|(group_idx, group)| group.channels.iter().enumerate()
+            .filter(|(_, pli)| pli.header.item_type.is_live())
+            .map(move |(channel_idx, _)| (group_idx, channel_idx)))
+        .collect();
+    let uuids: Vec<UUIDType> = channels.iter().map(|&(g, c)| playlist[g].channels[c].header.uuid).collect();
+    let present: HashSet<&UUIDType> = uuids.iter().collect();
+    persisted.retain(|uuid, entry| present.contains(uuid) || now - entry.last_seen < MAX_CHANNEL_NUMBER_AGE_SECS);
+
+    let mut numbers: Vec<Option<u32>> = vec![None; channels.len()];
+    let mut taken: HashSet<u32> = HashSet::new();
+
+    for (idx, &(g, c)) in channels.iter().enumerate() {
+        if let Some(number) = numbering.get_pin(&playlist[g].channels[c]).filter(|number| !taken.contains(number)) {
+            taken.insert(number);
+            numbers[idx] = Some(number);
+        }
+    }
+    for (idx, uuid) in uuids.iter().enumerate() {
+        if numbers[idx].is_none() {
+            if let Some(entry) = persisted.get(uuid).filter(|entry| !taken.contains(&entry.number)) {
+                taken.insert(entry.number);
+                numbers[idx] = Some(entry.number);
+            }
+        }
+    }
+    // numbers of missing channels are reserved unless a pin took them
+    persisted.retain(|uuid, entry| present.contains(uuid) || taken.insert(entry.number));
+    if numbering.preserve_upstream {
+        for (idx, &(g, c)) in channels.iter().enumerate() {
+            let chno = playlist[g].channels[c].header.chno;
+            if numbers[idx].is_none() && chno > 0 && taken.insert(chno) {
+                numbers[idx] = Some(chno);
+            }
+        }
+    }
+
+    let mut next_default = numbering.start;
+    let mut next_in_range: Vec<u32> = numbering.ranges.iter().map(|range| range.start).collect();
+    for (idx, &(g, c)) in channels.iter().enumerate() {
+        let number = numbers[idx].unwrap_or_else(|| {
+            let next = match numbering.get_range_index(&playlist[g].channels[c].header.group) {
+                Some(range_idx) => &mut next_in_range[range_idx],
+                None => &mut next_default,
+            };
+            let number = next_free(&taken, *next);
+            *next = number.saturating_add(1);
+            taken.insert(number);
+            number
+        });
+        playlist[g].channels[c].header.chno = number;
+        persisted.insert(uuids[idx], ChannelNumberEntry { number, last_seen: now });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assign_channel_numbers, MAX_CHANNEL_NUMBER_AGE_SECS};
+    use crate::model::ConfigChannelNumbering;
+    use crate::repository::{ChannelNumberEntry, ChannelNumbers};
+    use shared::model::{ConfigChannelNumberingDto, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, UUIDType, XtreamCluster};
+
+    fn group(title: &str, channels: &[(&str, u8, u32)]) -> PlaylistGroup {
+        PlaylistGroup {
+            id: 1,
+            title: title.into(),
+            channels: channels.iter().map(|(name, uuid, chno)| PlaylistItem {
+                header: PlaylistItemHeader {
+                    uuid: UUIDType([*uuid; 32]),
+                    title: (*name).into(),
+                    name: (*name).into(),
+                    group: title.into(),
+                    chno: *chno,
+                    item_type: PlaylistItemType::Live,
+                    ..Default::default()
+                },
+            }).collect(),
+            xtream_cluster: XtreamCluster::Live,
+        }
+    }
+
+    fn numbers(playlist: &[PlaylistGroup]) -> Vec<(String, u32)> {
+        playlist.iter().flat_map(|group| group.channels.iter())
+            .map(|pli| (pli.header.title.to_string(), pli.header.chno))
+            .collect()
+    }
+
+    fn numbering(config: &str) -> ConfigChannelNumbering {
+        let mut dto: ConfigChannelNumberingDto = serde_saphyr::from_str(config).unwrap();
+        dto.prepare(None).unwrap();
+        ConfigChannelNumbering::from(&dto)
+    }
+
+    #[test]
+    fn test_assign_channel_numbers() {
+        let numbering = numbering(r#"
+start: 10
+ranges:
+  - group: "(?i)sport"
+    start: 500
+pins:
+  - filter: 'Title ~ "^BBC One"'
+    number: 1
+"#);
+        let mut persisted = ChannelNumbers::new();
+        let mut playlist = vec![
+            group("News", &[("CNN", 1, 0), ("BBC One", 2, 0)]),
+            group("Sports", &[("Sport 1", 3, 0), ("Sport 2", 4, 0)]),
+        ];
+        assign_channel_numbers(&mut playlist, &numbering, &mut persisted, 1000);
+        assert_eq!(numbers(&playlist), vec![("CNN".to_string(), 10), ("BBC One".to_string(), 1),
+                                             ("Sport 1".to_string(), 500), ("Sport 2".to_string(), 501)]);
+
+        // numbers are kept when channels are added, removed or reordered
+        let mut playlist = vec![
+            group("News", &[("Euronews", 5, 0), ("CNN", 1, 0)]),
+            group("Sports", &[("Sport 2", 4, 0)]),
+        ];
+        assign_channel_numbers(&mut playlist, &numbering, &mut persisted, 2000);
+        assert_eq!(numbers(&playlist), vec![("Euronews".to_string(), 11), ("CNN".to_string(), 10), ("Sport 2".to_string(), 501)]);
+        assert_eq!(persisted.get(&UUIDType([3; 32])), Some(&ChannelNumberEntry { number: 500, last_seen: 1000 }));
+
+        // the number of a missing channel is released after some time
+        let mut playlist = vec![group("Sports", &[("Sport 3", 6, 0)])];
+        assign_channel_numbers(&mut playlist, &numbering, &mut persisted, 1000 + MAX_CHANNEL_NUMBER_AGE_SECS);
+        assert_eq!(numbers(&playlist), vec![("Sport 3".to_string(), 500)]);
+    }
+
+    #[test]
+    fn test_assign_channel_numbers_preserve_upstream() {
+        let numbering = numbering("preserve_upstream: true");
+        let mut persisted = ChannelNumbers::new();
+        let mut playlist = vec![group("News", &[("CNN", 1, 2), ("BBC One", 2, 2), ("Euronews", 3, 0)])];
+        assign_channel_numbers(&mut playlist, &numbering, &mut persisted, 1000);
+        assert_eq!(numbers(&playlist), vec![("CNN".to_string(), 2), ("BBC One".to_string(), 1), ("Euronews".to_string(), 3)]);
+    }
+}
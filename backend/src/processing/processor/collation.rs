@@ -0,0 +1,144 @@
+use shared::utils::deunicode_string;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+// Private use characters are sorted after all folded latin letters.
+const AFTER_Z_1: &str = "\u{e001}";
+const AFTER_Z_2: &str = "\u{e002}";
+const AFTER_Z_3: &str = "\u{e003}";
+
+/// Letters which have their own place in the alphabet of the language,
+/// all other letters are sorted like their base letter.
+fn locale_tailoring(locale: &str) -> &'static [(char, &'static str)] {
+    match locale {
+        "sv" | "fi" => &[('å', AFTER_Z_1), ('ä', AFTER_Z_2), ('æ', AFTER_Z_2), ('ö', AFTER_Z_3), ('ø', AFTER_Z_3)],
+        "da" | "nb" | "nn" | "no" => &[('æ', AFTER_Z_1), ('ä', AFTER_Z_1), ('ø', AFTER_Z_2), ('ö', AFTER_Z_2), ('å', AFTER_Z_3)],
+        "es" => &[('ñ', "n\u{e000}")],
+        "tr" => &[('ç', "c\u{e000}"), ('ğ', "g\u{e000}"), ('ı', "h\u{e000}"), ('ö', "o\u{e000}"), ('ş', "s\u{e000}"), ('ü', "u\u{e000}")],
+        "pl" => &[('ą', "a\u{e000}"), ('ć', "c\u{e000}"), ('ę', "e\u{e000}"), ('ł', "l\u{e000}"), ('ń', "n\u{e000}"),
+            ('ó', "o\u{e000}"), ('ś', "s\u{e000}"), ('ź', "z\u{e000}"), ('ż', "z\u{e001}")],
+        "cs" | "sk" => &[('č', "c\u{e000}"), ('ř', "r\u{e000}"), ('š', "s\u{e000}"), ('ž', "z\u{e000}")],
+        _ => &[],
+    }
+}
+
+/// Compares sort values naturally and/or with the letter order of a language.
+pub(in crate::processing::processor) struct SortCollator {
+    natural: bool,
+    locale: Option<String>,
+    tailoring: &'static [(char, &'static str)],
+}
+
+impl SortCollator {
+    pub fn new(natural: bool, locale: Option<&str>) -> Self {
+        Self {
+            natural,
+            locale: locale.map(ToString::to_string),
+            tailoring: locale.map(locale_tailoring).unwrap_or_default(),
+        }
+    }
+
+    /// Case and accent insensitive key of the value, the tailored letters keep their place in the alphabet.
+    fn primary_key<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if self.locale.is_none() {
+            return Cow::Borrowed(value);
+        }
+        let turkish = self.locale.as_deref() == Some("tr");
+        let mut key = String::with_capacity(value.len());
+        let mut buf = [0u8; 4];
+        for c in value.chars() {
+            let c = match c {
+                'I' if turkish => 'ı',
+                'İ' if turkish => 'i',
+                _ => c,
+            };
+            for lower in c.to_lowercase() {
+                match self.tailoring.iter().find(|(letter, _)| *letter == lower) {
+                    Some((_, weight)) => key.push_str(weight),
+                    None => key.push_str(&deunicode_string(lower.encode_utf8(&mut buf)).to_lowercase()),
+                }
+            }
+        }
+        Cow::Owned(key)
+    }
+
+    fn compare_text(&self, a: &str, b: &str) -> Ordering {
+        if self.natural { natural_cmp(a, b) } else { a.cmp(b) }
+    }
+
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        if self.locale.is_none() && !self.natural {
+            return a.cmp(b);
+        }
+        let ordering = if self.locale.is_some() {
+            self.compare_text(&self.primary_key(a), &self.primary_key(b))
+        } else {
+            Ordering::Equal
+        };
+        ordering.then_with(|| self.compare_text(a, b)).then_with(|| a.cmp(b))
+    }
+}
+
+fn split_digits(value: &str) -> (&str, &str) {
+    value.split_at(value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len()))
+}
+
+/// Compares the digit runs of the values by their numeric value: `Channel 2` < `Channel 10`.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut rest_a, mut rest_b) = (a, b);
+    loop {
+        match (rest_a.chars().next(), rest_b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ca), Some(cb)) if ca.is_ascii_digit() && cb.is_ascii_digit() => {
+                let (digits_a, tail_a) = split_digits(rest_a);
+                let (digits_b, tail_b) = split_digits(rest_b);
+                let (number_a, number_b) = (digits_a.trim_start_matches('0'), digits_b.trim_start_matches('0'));
+                let ordering = number_a.len().cmp(&number_b.len()).then_with(|| number_a.cmp(number_b));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                rest_a = tail_a;
+                rest_b = tail_b;
+            }
+            (Some(ca), Some(cb)) => {
+                if ca != cb {
+                    return ca.cmp(&cb);
+                }
+                rest_a = &rest_a[ca.len_utf8()..];
+                rest_b = &rest_b[cb.len_utf8()..];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SortCollator;
+
+    fn sorted(collator: &SortCollator, values: &[&str]) -> Vec<String> {
+        let mut values: Vec<String> = values.iter().map(ToString::to_string).collect();
+        values.sort_by(|a, b| collator.compare(a, b));
+        values
+    }
+
+    #[test]
+    fn test_natural_sort() {
+        let collator = SortCollator::new(true, None);
+        assert_eq!(sorted(&collator, &["Channel 10", "Channel 2", "Channel 1", "Channel 02 HD", "Channel"]),
+                   vec!["Channel", "Channel 1", "Channel 2", "Channel 02 HD", "Channel 10"]);
+        let collator = SortCollator::new(false, None);
+        assert_eq!(sorted(&collator, &["Channel 10", "Channel 2"]), vec!["Channel 10", "Channel 2"]);
+    }
+
+    #[test]
+    fn test_locale_sort() {
+        let collator = SortCollator::new(false, Some("de"));
+        assert_eq!(sorted(&collator, &["Zdf", "Österreich", "arte", "ORF"]), vec!["arte", "ORF", "Österreich", "Zdf"]);
+        let collator = SortCollator::new(false, Some("sv"));
+        assert_eq!(sorted(&collator, &["Öresund", "Zeta", "Åland", "Alpha"]), vec!["Alpha", "Zeta", "Åland", "Öresund"]);
+        let collator = SortCollator::new(true, Some("es"));
+        assert_eq!(sorted(&collator, &["Ñu 2", "Nz", "O", "Ñu 10"]), vec!["Nz", "Ñu 2", "Ñu 10", "O"]);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::ptt::ptt_parse_title;
+use crate::repository::{ChannelFailoverSource, ChannelFailovers};
+use deunicode::deunicode;
+use indexmap::IndexMap;
+use shared::model::{ConfigDeduplicationDto, DeduplicationKey, DeduplicationStats, DeduplicationStrategy, PlaylistGroup, PlaylistItem};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+// tags which name the quality of the same channel
+const QUALITY_TAGS: &[&str] = &["sd", "hd", "fhd", "uhd", "qhd", "4k", "8k", "hevc", "h264", "h265", "raw", "50fps", "60fps"];
+
+fn is_resolution_token(token: &str) -> bool {
+    token.strip_suffix(['p', 'i']).is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn channel_title(pli: &PlaylistItem) -> &str {
+    if pli.header.title.is_empty() { &pli.header.name } else { &pli.header.title }
+}
+
+fn normalize_name(name: &str) -> String {
+    deunicode(name).to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| !token.is_empty() && !QUALITY_TAGS.contains(token) && !is_resolution_token(token))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn duplicate_key(pli: &PlaylistItem, key: DeduplicationKey) -> Option<String> {
+    let value = match key {
+        DeduplicationKey::Name => normalize_name(channel_title(pli)),
+        DeduplicationKey::Url => pli.header.url.trim().to_string(),
+        DeduplicationKey::EpgId => pli.header.epg_channel_id.as_deref().map(|id| id.trim().to_lowercase()).unwrap_or_default(),
+    };
+    (!value.is_empty()).then_some(value)
+}
+
+/// Vertical resolution from the PTT metadata of the name, `HD` and `UHD` are not detected by the parser.
+fn channel_quality(pli: &PlaylistItem) -> u32 {
+    let title = channel_title(pli);
+    if let Some(resolution) = ptt_parse_title(title).resolution {
+        let digits: String = resolution.chars().take_while(char::is_ascii_digit).collect();
+        if let Ok(lines) = digits.parse::<u32>() {
+            // resolutions like `1920x1080p` name the width first
+            return resolution.split_once('x').and_then(|(_, height)| height.trim_end_matches(['p', 'i']).parse().ok()).unwrap_or(lines);
+        }
+    }
+    let name = deunicode(title).to_lowercase();
+    let tokens = name.split(|c: char| !c.is_ascii_alphanumeric());
+    tokens.map(|token| match token {
+        "uhd" => 2160,
+        "hd" => 720,
+        "sd" => 576,
+        _ => 0,
+    }).max().unwrap_or(0)
+}
+
+fn failover_source(pli: &PlaylistItem) -> ChannelFailoverSource {
+    ChannelFailoverSource { input_name: Arc::clone(&pli.header.input_name), url: Arc::clone(&pli.header.url), quality: channel_quality(pli) }
+}
+
+/// Removes the quality tags from the words of the title, the title is kept if nothing else remains.
+fn strip_quality_tags(title: &str) -> String {
+    let stripped = title.split_whitespace()
+        .filter(|word| {
+            let token = deunicode(word).to_lowercase();
+            let token = token.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+            token.is_empty() || !(QUALITY_TAGS.contains(&token) || is_resolution_token(token))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    if stripped.is_empty() { title.to_string() } else { stripped }
+}
+
+fn remove_positions(playlist: &mut Vec<PlaylistGroup>, removed: &HashSet<(usize, usize)>) {
+    if removed.is_empty() {
+        return;
+    }
+    for (group_idx, group) in playlist.iter_mut().enumerate() {
+        let mut item_idx = 0;
+        group.channels.retain(|_| {
+            let keep = !removed.contains(&(group_idx, item_idx));
+            item_idx += 1;
+            keep
+        });
+    }
+    playlist.retain(|group| !group.channels.is_empty());
+}
+
+/// Collapses the quality variants of the live channels of the same input and group, like `ZDF FHD`, `ZDF HD` and `ZDF SD`,
+/// into the variant with the best quality, which is renamed without the quality tags.
+/// The variant ladder, ordered by quality, is added as failover sources of the kept channel. Returns the count of the collapsed channels.
+pub(in crate::processing) fn process_variant_collapsing(playlist: &mut Vec<PlaylistGroup>, failovers: &mut ChannelFailovers) -> usize {
+    let mut variant_sets: IndexMap<(usize, Arc<str>, String), Vec<usize>> = IndexMap::new();
+    for (group_idx, group) in playlist.iter().enumerate() {
+        for (item_idx, pli) in group.channels.iter().enumerate() {
+            if !pli.header.item_type.is_live() {
+                continue;
+            }
+            if let Some(key) = duplicate_key(pli, DeduplicationKey::Name) {
+                variant_sets.entry((group_idx, Arc::clone(&pli.header.input_name), key)).or_default().push(item_idx);
+            }
+        }
+    }
+
+    let mut removed: HashSet<(usize, usize)> = HashSet::new();
+    for ((group_idx, _, _), mut positions) in variant_sets.into_iter().filter(|(_, positions)| positions.len() > 1) {
+        let channels = &mut playlist[group_idx].channels;
+        // the sort is stable, the first variant wins on the same quality
+        positions.sort_by_cached_key(|item_idx| std::cmp::Reverse(channel_quality(&channels[*item_idx])));
+        let mut ladder: Vec<ChannelFailoverSource> = Vec::with_capacity(positions.len());
+        for item_idx in &positions {
+            let source = failover_source(&channels[*item_idx]);
+            if !ladder.iter().any(|variant| variant.url == source.url) {
+                ladder.push(source);
+            }
+        }
+        for item_idx in &positions[1..] {
+            removed.insert((group_idx, *item_idx));
+        }
+        let header = &mut channels[positions[0]].header;
+        header.title = strip_quality_tags(&header.title).into();
+        header.name = strip_quality_tags(&header.name).into();
+        if ladder.len() > 1 {
+            failovers.insert(header.uuid, ladder);
+        }
+    }
+
+    remove_positions(playlist, &removed);
+    removed.len()
+}
+
+/// Removes the duplicate live channels over all groups of the playlist. The duplicates are found by the configured key,
+/// the kept channel is the first one or the one with the best quality. With the `failover` strategy the urls of the removed
+/// duplicates, and the variants of a removed collapsed channel, are added as failover sources of the kept channel, ordered by quality.
+pub(in crate::processing) fn process_deduplication(playlist: &mut Vec<PlaylistGroup>, config: &ConfigDeduplicationDto, failovers: &mut ChannelFailovers) -> DeduplicationStats {
+    let mut duplicate_sets: IndexMap<String, Vec<(usize, usize)>> = IndexMap::new();
+    for (group_idx, group) in playlist.iter().enumerate() {
+        for (item_idx, pli) in group.channels.iter().enumerate() {
+            if !pli.header.item_type.is_live() {
+                continue;
+            }
+            if let Some(key) = duplicate_key(pli, config.key) {
+                duplicate_sets.entry(key).or_default().push((group_idx, item_idx));
+            }
+        }
+    }
+
+    let mut stats = DeduplicationStats::default();
+    let mut removed: HashSet<(usize, usize)> = HashSet::new();
+    for (_, mut positions) in duplicate_sets.into_iter().filter(|(_, positions)| positions.len() > 1) {
+        if config.strategy != DeduplicationStrategy::KeepFirst {
+            // the sort is stable, the first channel wins on the same quality
+            // a collapsed channel has the quality of its best variant
+            positions.sort_by_cached_key(|(group_idx, item_idx)| {
+                let pli = &playlist[*group_idx].channels[*item_idx];
+                std::cmp::Reverse(failovers.get(&pli.header.uuid).and_then(|ladder| ladder.first()).map_or_else(|| channel_quality(pli), |variant| variant.quality))
+            });
+        }
+        let (kept_group, kept_item) = positions[0];
+        let kept = &playlist[kept_group].channels[kept_item].header;
+        let mut sources: Vec<ChannelFailoverSource> = failovers.remove(&kept.uuid).unwrap_or_default();
+        for position in &positions[1..] {
+            removed.insert(*position);
+            stats.removed += 1;
+            if config.strategy == DeduplicationStrategy::Failover {
+                let pli = &playlist[position.0].channels[position.1];
+                let variants = failovers.remove(&pli.header.uuid).unwrap_or_default();
+                for source in std::iter::once(failover_source(pli)).chain(variants) {
+                    if source.url != kept.url && !sources.iter().any(|known| known.url == source.url) {
+                        stats.failover += 1;
+                        sources.push(source);
+                    }
+                }
+            }
+        }
+        if !sources.is_empty() {
+            failovers.insert(kept.uuid, sources);
+        }
+    }
+
+    remove_positions(playlist, &removed);
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_name, process_deduplication, process_variant_collapsing};
+    use crate::repository::ChannelFailovers;
+    use shared::model::{ConfigDeduplicationDto, DeduplicationKey, DeduplicationStrategy, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, XtreamCluster};
+
+    fn channel(input_name: &str, title: &str, url: &str) -> PlaylistItem {
+        let mut header = PlaylistItemHeader {
+            title: title.into(),
+            name: title.into(),
+            url: url.into(),
+            input_name: input_name.into(),
+            item_type: PlaylistItemType::Live,
+            xtream_cluster: XtreamCluster::Live,
+            ..Default::default()
+        };
+        header.gen_uuid();
+        PlaylistItem { header }
+    }
+
+    fn playlist() -> Vec<PlaylistGroup> {
+        vec![
+            PlaylistGroup {
+                id: 1,
+                title: "DE".into(),
+                channels: vec![channel("a", "ZDF HD", "http://a/zdf"), channel("a", "Das Erste", "http://a/ard")],
+                xtream_cluster: XtreamCluster::Live,
+            },
+            PlaylistGroup {
+                id: 2,
+                title: "DE Backup".into(),
+                channels: vec![channel("b", "ZDF FHD", "http://b/zdf"), channel("b", "Das Érste 720p", "http://b/ard"), channel("b", "ZDF", "http://a/zdf")],
+                xtream_cluster: XtreamCluster::Live,
+            },
+        ]
+    }
+
+    fn titles(playlist: &[PlaylistGroup]) -> Vec<String> {
+        playlist.iter().flat_map(|group| group.channels.iter()).map(|pli| pli.header.title.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Das Érste FHD 1080p"), "das erste");
+        assert_eq!(normalize_name("DE: ZDF-HD"), "de zdf");
+    }
+
+    #[test]
+    fn test_deduplication() {
+        let mut keep_first = playlist();
+        let mut failovers = ChannelFailovers::new();
+        let stats = process_deduplication(&mut keep_first, &ConfigDeduplicationDto { key: DeduplicationKey::Name, strategy: DeduplicationStrategy::KeepFirst }, &mut failovers);
+        assert_eq!(titles(&keep_first), vec!["ZDF HD", "Das Erste"]);
+        assert_eq!((stats.removed, stats.failover, failovers.len()), (3, 0, 0));
+
+        let mut best = playlist();
+        let stats = process_deduplication(&mut best, &ConfigDeduplicationDto { key: DeduplicationKey::Name, strategy: DeduplicationStrategy::KeepBestQuality }, &mut ChannelFailovers::new());
+        assert_eq!(titles(&best), vec!["ZDF FHD", "Das Érste 720p"]);
+        assert_eq!(stats.removed, 3);
+
+        let mut by_url = playlist();
+        let stats = process_deduplication(&mut by_url, &ConfigDeduplicationDto { key: DeduplicationKey::Url, strategy: DeduplicationStrategy::KeepFirst }, &mut ChannelFailovers::new());
+        assert_eq!(titles(&by_url), vec!["ZDF HD", "Das Erste", "ZDF FHD", "Das Érste 720p"]);
+        assert_eq!(stats.removed, 1);
+
+        let mut failover = playlist();
+        let mut failovers = ChannelFailovers::new();
+        let stats = process_deduplication(&mut failover, &ConfigDeduplicationDto { key: DeduplicationKey::Name, strategy: DeduplicationStrategy::Failover }, &mut failovers);
+        assert_eq!(titles(&failover), vec!["ZDF FHD", "Das Érste 720p"]);
+        // the removed `ZDF` has the url of `ZDF HD`
+        assert_eq!((stats.removed, stats.failover), (3, 2));
+        let zdf = &failover[0].channels[0].header;
+        assert_eq!(failovers.get(&zdf.uuid).unwrap().iter().map(|source| source.url.to_string()).collect::<Vec<_>>(), vec!["http://a/zdf"]);
+    }
+
+    #[test]
+    fn test_variant_collapsing() {
+        let mut playlist = vec![PlaylistGroup {
+            id: 1,
+            title: "DE".into(),
+            channels: vec![
+                channel("a", "ZDF HD", "http://a/zdf_hd"),
+                channel("a", "ZDF (SD)", "http://a/zdf_sd"),
+                channel("a", "ZDF FHD", "http://a/zdf_fhd"),
+                channel("b", "ZDF HD", "http://b/zdf_hd"),
+                channel("a", "Das Erste", "http://a/ard"),
+            ],
+            xtream_cluster: XtreamCluster::Live,
+        }];
+        let mut failovers = ChannelFailovers::new();
+        assert_eq!(process_variant_collapsing(&mut playlist, &mut failovers), 2);
+        assert_eq!(titles(&playlist), vec!["ZDF", "ZDF HD", "Das Erste"]);
+        let zdf = &playlist[0].channels[0].header;
+        assert_eq!(&*zdf.url, "http://a/zdf_fhd");
+        let ladder = failovers.get(&zdf.uuid).unwrap();
+        assert_eq!(ladder.iter().map(|variant| (variant.url.to_string(), variant.quality)).collect::<Vec<_>>(),
+                   vec![("http://a/zdf_fhd".to_string(), 1080), ("http://a/zdf_hd".to_string(), 720), ("http://a/zdf_sd".to_string(), 576)]);
+
+        // the variants of the removed duplicate are kept as failover
+        let stats = process_deduplication(&mut playlist, &ConfigDeduplicationDto { key: DeduplicationKey::Name, strategy: DeduplicationStrategy::Failover }, &mut failovers);
+        assert_eq!((stats.removed, stats.failover), (1, 1));
+        let zdf = &playlist[0].channels[0].header;
+        assert_eq!(failovers.get(&zdf.uuid).unwrap().last().map(|source| source.url.to_string()), Some("http://b/zdf_hd".to_string()));
+    }
+}
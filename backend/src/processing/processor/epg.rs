@@ -1,13 +1,27 @@
+#[cfg(feature = "server")]
+use crate::api::model::AppState;
+#[cfg(feature = "server")]
+use crate::api::scheduler::datetime_to_instant;
+use crate::model::{AppConfig, ConfigSource, ConfigTarget, PersistedEpgSource, SourcesConfig, TargetOutput};
 use crate::model::{Epg, TVGuide, XmlTag, XmlTagIcon, EPG_ATTRIB_ID};
-use crate::model::{EpgConfig, EpgSmartMatchConfig};
-use crate::model::FetchedPlaylist;
-use crate::processing::parser::xmltv::normalize_channel_name;
-use log::{debug, trace, warn};
+use crate::model::{ConfigInput, EpgConfig, EpgSmartMatchConfig, EpgSource};
+use crate::model::{parse_xmltv_for_web_ui_from_file, FetchedPlaylist};
+use crate::processing::parser::xmltv::{flatten_tvguide, normalize_channel_name};
+use crate::processing::processor::tvg_id_registry::TvgIdRegistry;
+use crate::repository::{ensure_target_storage_path, epg_write_for_target, iter_raw_m3u_target_playlist, iter_raw_xtream_target_playlist, load_epg_mappings};
+use crate::utils::{epg::get_input_raw_epg_file_path, normalize_title_for_matching, request};
+use chrono::Local;
+use cron::Schedule;
+use log::{debug, error, info, trace, warn};
 use rphonetic::{DoubleMetaphone, Encoder};
-use std::collections::{HashMap, HashSet};
-use shared::model::{EpgSmartMatchConfigDto, PlaylistItem, XtreamCluster};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use shared::error::{info_err, TuliproxError};
+use shared::model::{EpgChannel, EpgMappingCandidateDto, EpgMappingSuggestionDto, EpgMappingsDto, EpgSmartMatchConfigDto, PlaylistGroup, PlaylistItem, XtreamCluster};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
-use shared::utils::Internable;
+use shared::utils::{sanitize_sensitive_info, Internable};
+use tokio_util::sync::CancellationToken;
 
 pub struct EpgIdCache {
     pub channel_epg_id: HashSet<Arc<str>>,
@@ -18,6 +32,7 @@ pub struct EpgIdCache {
     pub metaphone: DoubleMetaphone,
     pub smart_match_enabled: bool, // smart match is enabled, normalizing names
     pub fuzzy_match_enabled: bool, // fuzzy matching enabled
+    pub tvg_id_registry: Option<TvgIdRegistry>, // rewrites the epg ids to canonical ids
 }
 
 impl EpgIdCache {
@@ -45,7 +60,7 @@ impl EpgIdCache {
             smart_match_enabled: normalize_config.enabled,
             fuzzy_match_enabled: normalize_config.enabled && normalize_config.fuzzy_matching,
             smart_match_config: normalize_config,
-
+            tvg_id_registry: None,
         }
     }
 
@@ -236,6 +251,52 @@ async fn assign_channel_epg(new_epg: &mut Vec<Epg>, fp: &mut FetchedPlaylist<'_>
     }
 }
 
+/// Assigns the epg ids of the accepted epg mappings, they take precedence over the playlist and smart match.
+fn assign_mapped_epg_ids(fp: &mut FetchedPlaylist<'_>, epg_mappings: &BTreeMap<String, String>) {
+    if epg_mappings.is_empty() {
+        return;
+    }
+    if !fp.is_memory() {
+        warn!("Disk based playlist modification is not supported!");
+        return;
+    }
+    fp.items_mut()
+        .filter(|chan| chan.header.xtream_cluster == XtreamCluster::Live && chan.header.item_type.is_live())
+        .for_each(|chan| {
+            if let Some(epg_id) = epg_mappings.get(&*chan.header.name) {
+                chan.header.epg_channel_id = Some(epg_id.intern());
+            }
+        });
+}
+
+/// Loads the tvg-id registry of the input, `None` if the normalization is disabled.
+async fn load_tvg_id_registry(epg_config: Option<&EpgConfig>, working_dir: &str) -> Option<TvgIdRegistry> {
+    let config = epg_config?.tvg_id_normalization.as_ref().filter(|config| config.enabled)?;
+    match TvgIdRegistry::load(config, working_dir).await {
+        Ok(registry) => Some(registry),
+        Err(err) => {
+            error!("{err}");
+            None
+        }
+    }
+}
+
+/// Rewrites the epg ids of the live channels to the canonical ids of the registry, unknown ids are kept.
+fn normalize_tvg_ids(fp: &mut FetchedPlaylist<'_>, registry: &TvgIdRegistry) {
+    if !fp.is_memory() {
+        warn!("Disk based playlist modification is not supported!");
+        return;
+    }
+    fp.items_mut()
+        .filter(|chan| chan.header.xtream_cluster == XtreamCluster::Live && chan.header.item_type.is_live())
+        .for_each(|chan| {
+            if let Some(canonical) = chan.header.epg_channel_id.as_deref().and_then(|epg_id| registry.normalize(epg_id)) {
+                trace!("Normalized epg id of channel {} to {canonical}", chan.header.name);
+                chan.header.epg_channel_id = Some(canonical);
+            }
+        });
+}
+
 /// Processes a fetched playlist and assigns EPG data to its channels.
 ///
 /// Collects EPG channel IDs from the playlist, initializes an EPG ID cache, and assigns EPG data to channels using normalization and smart matching if enabled. Logs a debug message if no EPG IDs are found and smart matching is disabled.
@@ -245,14 +306,19 @@ async fn assign_channel_epg(new_epg: &mut Vec<Epg>, fp: &mut FetchedPlaylist<'_>
 /// ```
 /// let mut playlist = FetchedPlaylist::default();
 /// let mut epg_data = Vec::new();
-/// process_playlist_epg(&mut playlist, &mut epg_data);
+/// process_playlist_epg(&mut playlist, &mut epg_data, &BTreeMap::new(), "/home/tuliprox");
 /// ```
-pub async fn process_playlist_epg(fp: &mut FetchedPlaylist<'_>, epg: &mut Vec<Epg>) {
+pub async fn process_playlist_epg(fp: &mut FetchedPlaylist<'_>, epg: &mut Vec<Epg>, epg_mappings: &BTreeMap<String, String>, working_dir: &str) {
     if fp.input.epg.is_none() {
         return;
     }
+    assign_mapped_epg_ids(fp, epg_mappings);
     // collect all epg_channel ids
     let mut id_cache = EpgIdCache::new(fp.input.epg.as_ref());
+    id_cache.tvg_id_registry = load_tvg_id_registry(fp.input.epg.as_ref(), working_dir).await;
+    if let Some(registry) = id_cache.tvg_id_registry.as_ref() {
+        normalize_tvg_ids(fp, registry);
+    }
     id_cache.collect_epg_id(fp);
 
     if id_cache.is_empty() && !id_cache.smart_match_enabled {
@@ -263,11 +329,275 @@ pub async fn process_playlist_epg(fp: &mut FetchedPlaylist<'_>, epg: &mut Vec<Ep
 }
 
 
+#[cfg(feature = "server")]
+/// Starts a refresh loop for each epg source which has its own `schedule`.
+///
+/// A scheduled refresh downloads only this epg source and rewrites the epg of all targets using the input,
+/// the playlists are not fetched again.
+pub fn exec_epg_scheduler(client: &reqwest::Client, app_state: &Arc<AppState>, cancel: &CancellationToken) {
+    let sources = app_state.app_config.sources.load();
+    for input in &sources.inputs {
+        let Some(epg_config) = input.epg.as_ref() else { continue; };
+        for epg_source in &epg_config.sources {
+            let Some(expression) = epg_source.schedule.as_deref() else { continue; };
+            match Schedule::from_str(expression) {
+                Ok(schedule) => {
+                    let input_name = Arc::clone(&input.name);
+                    let url = epg_source.url.clone();
+                    let app_state_clone = Arc::clone(app_state);
+                    let http_client = client.clone();
+                    let cancel_token = cancel.clone();
+                    tokio::spawn(async move {
+                        start_epg_scheduler(http_client, schedule, app_state_clone, input_name, url, cancel_token).await;
+                    });
+                }
+                Err(err) => error!("Invalid epg schedule '{expression}' for input {}: {err}", input.name),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+async fn start_epg_scheduler(client: reqwest::Client, schedule: Schedule, app_state: Arc<AppState>,
+                             input_name: Arc<str>, url: String, cancel: CancellationToken) {
+    let offset = *Local::now().offset();
+    while let Some(datetime) = schedule.upcoming(offset).next() {
+        tokio::select! {
+            () = tokio::time::sleep_until(tokio::time::Instant::from(datetime_to_instant(datetime))) => {
+                refresh_epg_source(&client, &app_state, &input_name, &url).await;
+            }
+            () = cancel.cancelled() => {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+async fn refresh_epg_source(client: &reqwest::Client, app_state: &Arc<AppState>, input_name: &Arc<str>, url: &str) {
+    let Some(_permit) = app_state.update_guard.try_playlist() else {
+        warn!("Playlist update in progress; epg refresh for input {input_name} skipped.");
+        return;
+    };
+    let app_config = &app_state.app_config;
+    let sources = app_config.sources.load();
+    let Some(input) = sources.get_input_by_name(input_name) else {
+        return;
+    };
+    let working_dir = app_config.config.load().working_dir.clone();
+    let file_path = match get_input_raw_epg_file_path(url, input, &working_dir) {
+        Ok(path) => path,
+        Err(err) => {
+            error!("Could not access epg file download directory: {err}");
+            return;
+        }
+    };
+    debug!("Refreshing epg {} for input {input_name}", sanitize_sensitive_info(url));
+    if let Err(err) = request::get_input_epg_content_as_file(app_config, client, input, None, &working_dir, url, &file_path).await {
+        error!("Failed to refresh epg for input {input_name}: {err}");
+        return;
+    }
+
+    for source in sources.sources.iter().filter(|source| source.inputs.contains(input_name)) {
+        for target in &source.targets {
+            if let Err(err) = update_target_epg(app_config, &sources, source, target).await {
+                error!("{err}");
+            }
+        }
+    }
+    info!("Epg for input {input_name} refreshed");
+}
+
+async fn load_target_live_channels(app_config: &AppConfig, target: &ConfigTarget, output: &TargetOutput) -> Option<Vec<PlaylistItem>> {
+    match output {
+        TargetOutput::Xtream(_) => iter_raw_xtream_target_playlist(app_config, target, XtreamCluster::Live).await
+            .map(|(_file_lock, iter)| iter.map(|item| PlaylistItem::from(&item)).collect()),
+        TargetOutput::M3u(_) => iter_raw_m3u_target_playlist(app_config, target, Some(XtreamCluster::Live)).await
+            .map(|(_file_lock, iter)| iter.map(|item| PlaylistItem::from(&item)).collect()),
+        TargetOutput::Strm(_) | TargetOutput::HdHomeRun(_) | TargetOutput::Plex(_) => None,
+    }
+}
+
+/// Rebuilds the target epg from the downloaded epg files of its inputs.
+/// The epg ids are taken from the stored target playlist, which already contains the smart matched ids.
+/// Returns the existing file of the epg source, local epg files are read in place, they are not downloaded.
+fn get_epg_source_file_path(epg_source: &EpgSource, input: &ConfigInput, working_dir: &str) -> Option<PathBuf> {
+    let file_path = match url::Url::parse(&epg_source.url) {
+        Ok(url) if url.scheme() == "file" => url.to_file_path().ok(),
+        _ => get_input_raw_epg_file_path(&epg_source.url, input, working_dir).ok(),
+    };
+    file_path.filter(|file_path| file_path.exists())
+}
+
+async fn update_target_epg(app_config: &Arc<AppConfig>, sources: &SourcesConfig, source: &ConfigSource, target: &ConfigTarget) -> Result<(), TuliproxError> {
+    let config = app_config.config.load();
+    let target_path = ensure_target_storage_path(&config, &target.name)?;
+    for output in &target.output {
+        let Some(channels) = load_target_live_channels(app_config, target, output).await else { continue; };
+
+        let mut new_epg = vec![];
+        for input_name in &source.inputs {
+            let Some(input) = sources.get_input_by_name(input_name) else { continue; };
+            let Some(epg_config) = input.epg.as_ref() else { continue; };
+            let epg_sources: Vec<PersistedEpgSource> = epg_config.sources.iter()
+                .filter_map(|epg_source| {
+                    get_epg_source_file_path(epg_source, input, &config.working_dir)
+                        .map(|file_path| PersistedEpgSource { file_path, priority: epg_source.priority, logo_override: epg_source.logo_override })
+                })
+                .collect();
+            if epg_sources.is_empty() {
+                continue;
+            }
+
+            let mut id_cache = EpgIdCache::new(None);
+            // the stored channels have the canonical ids, the epg files the provider ids
+            id_cache.tvg_id_registry = load_tvg_id_registry(Some(epg_config), &config.working_dir).await;
+            for channel in channels.iter().filter(|channel| &channel.header.input_name == input_name) {
+                if let Some(epg_id) = channel.header.epg_channel_id.as_ref().filter(|id| !id.is_empty()) {
+                    id_cache.channel_epg_id.insert(Arc::clone(epg_id));
+                }
+            }
+            if let Some(epg) = TVGuide::new(epg_sources).filter(&mut id_cache).await.and_then(TVGuide::merge) {
+                new_epg.push(epg);
+            }
+        }
+
+        let playlist = [PlaylistGroup { id: 0, title: "".intern(), channels, xtream_cluster: XtreamCluster::Live }];
+        epg_write_for_target(&config, target, &target_path, flatten_tvguide(&new_epg).as_ref(), output, Some(&playlist)).await
+            .map_err(|err| info_err!("Failed to update epg for target {}: {err}", target.name))?;
+    }
+    Ok(())
+}
+
+const EPG_MAPPING_MIN_SCORE: f64 = 0.8;
+const EPG_MAPPING_MAX_CANDIDATES: usize = 5;
+const EPG_MAPPING_MAX_SEARCH_RESULTS: usize = 25;
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_epg_mapping_candidate(epg_channel: &EpgChannel, score: f64) -> EpgMappingCandidateDto {
+    EpgMappingCandidateDto {
+        epg_channel_id: epg_channel.id.to_string(),
+        title: epg_channel.title.as_ref().map(ToString::to_string),
+        icon: epg_channel.icon.as_ref().map(ToString::to_string),
+        score: (score * 100.0).round() as u8,
+    }
+}
+
+/// Ranks the epg channels for the live channels which have no epg yet.
+/// Titles are compared normalized with the jaro winkler similarity.
+/// Channels without candidates are listed too, they can be assigned through the search.
+fn suggest_epg_mappings(channels: &[PlaylistItem], epg_channels: &[EpgChannel], mappings: &EpgMappingsDto) -> Vec<EpgMappingSuggestionDto> {
+    let epg_ids: HashSet<&str> = epg_channels.iter().map(|epg_channel| &*epg_channel.id).collect();
+    let normalized_epg_channels: Vec<(String, &EpgChannel)> = epg_channels.iter()
+        .map(|epg_channel| (normalize_title_for_matching(epg_channel.title.as_deref().unwrap_or(&epg_channel.id)), epg_channel))
+        .filter(|(normalized, _)| !normalized.is_empty())
+        .collect();
+
+    let mut suggested = HashSet::new();
+    channels.iter()
+        .filter(|channel| !mappings.accepted.contains_key(&*channel.header.name))
+        .filter(|channel| channel.header.epg_channel_id.as_deref().is_none_or(|epg_id| !epg_ids.contains(epg_id)))
+        .filter_map(|channel| {
+            let name = &channel.header.name;
+            let normalized = normalize_title_for_matching(name);
+            if normalized.is_empty() || !suggested.insert(Arc::clone(name)) {
+                return None;
+            }
+            let mut candidates: Vec<EpgMappingCandidateDto> = normalized_epg_channels.iter()
+                .filter(|(_, epg_channel)| !mappings.is_rejected(name, &epg_channel.id))
+                .filter_map(|(epg_name, epg_channel)| {
+                    let score = strsim::jaro_winkler(&normalized, epg_name);
+                    (score >= EPG_MAPPING_MIN_SCORE).then(|| to_epg_mapping_candidate(epg_channel, score))
+                })
+                .collect();
+            candidates.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.epg_channel_id.cmp(&b.epg_channel_id)));
+            candidates.truncate(EPG_MAPPING_MAX_CANDIDATES);
+            Some(EpgMappingSuggestionDto {
+                name: name.to_string(),
+                group: channel.header.group.to_string(),
+                epg_channel_id: channel.header.epg_channel_id.as_ref().map(ToString::to_string),
+                candidates,
+            })
+        })
+        .collect()
+}
+
+/// Searches the epg channels by id and title, a channel matches if it contains the query or is similar to it.
+fn search_epg_channels(epg_channels: &[EpgChannel], query: &str) -> Vec<EpgMappingCandidateDto> {
+    let normalized_query = normalize_title_for_matching(query);
+    let lower_query = query.trim().to_lowercase();
+    if normalized_query.is_empty() && lower_query.is_empty() {
+        return vec![];
+    }
+    let mut results: Vec<EpgMappingCandidateDto> = epg_channels.iter()
+        .filter_map(|epg_channel| {
+            let normalized = normalize_title_for_matching(epg_channel.title.as_deref().unwrap_or(&epg_channel.id));
+            let contained = (!normalized_query.is_empty() && normalized.contains(&normalized_query))
+                || (!lower_query.is_empty() && epg_channel.id.to_lowercase().contains(&lower_query));
+            let score = strsim::jaro_winkler(&normalized_query, &normalized);
+            (contained || score >= EPG_MAPPING_MIN_SCORE).then(|| to_epg_mapping_candidate(epg_channel, score))
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.epg_channel_id.cmp(&b.epg_channel_id)));
+    results.truncate(EPG_MAPPING_MAX_SEARCH_RESULTS);
+    results
+}
+
+/// Reads the epg channels of the downloaded epg files of all inputs of the target.
+async fn load_target_epg_channels(app_config: &AppConfig, target: &ConfigTarget) -> Vec<EpgChannel> {
+    let sources = app_config.sources.load();
+    let Some(source) = sources.sources.iter().find(|source| source.targets.iter().any(|t| t.id == target.id)) else {
+        return vec![];
+    };
+    let working_dir = app_config.config.load().working_dir.clone();
+    let mut epg_ids = HashSet::new();
+    let mut epg_channels = vec![];
+    for input_name in &source.inputs {
+        let Some(input) = sources.get_input_by_name(input_name) else { continue; };
+        let Some(epg_config) = input.epg.as_ref() else { continue; };
+        for epg_source in &epg_config.sources {
+            let Some(file_path) = get_epg_source_file_path(epg_source, input, &working_dir) else { continue; };
+            match parse_xmltv_for_web_ui_from_file(&file_path).await {
+                Ok(parsed) => epg_channels.extend(parsed.into_iter().filter(|epg_channel| epg_ids.insert(Arc::clone(&epg_channel.id)))),
+                Err(err) => error!("Failed to read epg {} for input {input_name}: {err}", file_path.display()),
+            }
+        }
+    }
+    epg_channels
+}
+
+/// Suggests epg channels for the live channels of the stored target playlist without epg.
+/// The candidates are taken from the downloaded epg files of the target inputs.
+pub async fn get_epg_mapping_suggestions(app_config: &AppConfig, target: &ConfigTarget) -> Vec<EpgMappingSuggestionDto> {
+    let mut channels = None;
+    for output in &target.output {
+        channels = load_target_live_channels(app_config, target, output).await;
+        if channels.is_some() {
+            break;
+        }
+    }
+    let Some(channels) = channels else {
+        return vec![];
+    };
+    let epg_channels = load_target_epg_channels(app_config, target).await;
+    let mappings = load_epg_mappings(app_config, &target.name).await;
+    suggest_epg_mappings(&channels, &epg_channels, &mappings)
+}
+
+/// Searches the epg channels of all epg sources of the target inputs for the manual mapping of a channel.
+pub async fn search_epg_mapping_channels(app_config: &AppConfig, target: &ConfigTarget, query: &str) -> Vec<EpgMappingCandidateDto> {
+    search_epg_channels(&load_target_epg_channels(app_config, target).await, query)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{get_epg_source_file_path, search_epg_channels, suggest_epg_mappings};
+    use crate::model::{ConfigInput, EpgSource};
     use rand::distr::Alphanumeric;
     use rand::Rng;
     use rphonetic::{DoubleMetaphone, Encoder};
+    use shared::model::{EpgChannel, EpgMappingDecisionDto, EpgMappingsDto, PlaylistItem, PlaylistItemHeader};
+    use shared::utils::Internable;
     use tokio::time::Instant;
 
     fn random_string() -> String {
@@ -278,6 +608,69 @@ mod tests {
             .collect()
     }
 
+    fn channel(name: &str, epg_channel_id: Option<&str>) -> PlaylistItem {
+        PlaylistItem {
+            header: PlaylistItemHeader {
+                name: name.intern(),
+                group: "News".intern(),
+                epg_channel_id: epg_channel_id.map(Internable::intern),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn epg_channel(id: &str, title: &str) -> EpgChannel {
+        let mut epg_channel = EpgChannel::new(id.intern());
+        epg_channel.title = Some(title.intern());
+        epg_channel
+    }
+
+    #[test]
+    fn test_suggest_epg_mappings() {
+        let channels = vec![
+            channel("CNN International HD", None),
+            channel("BBC One", Some("bbc1.uk")),
+            channel("Das Erste", Some("unknown.de")),
+            channel("Cartoon Network", None),
+        ];
+        let epg_channels = vec![
+            epg_channel("cnn.int", "CNN International"),
+            epg_channel("bbc1.uk", "BBC One"),
+            epg_channel("daserste.de", "Das Erste"),
+            epg_channel("zdf.de", "ZDF"),
+        ];
+        let mut mappings = EpgMappingsDto::default();
+
+        let suggestions = suggest_epg_mappings(&channels, &epg_channels, &mappings);
+        assert_eq!(suggestions.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), vec!["CNN International HD", "Das Erste", "Cartoon Network"]);
+        assert_eq!(suggestions[0].candidates[0].epg_channel_id, "cnn.int");
+        assert_eq!(suggestions[1].candidates[0].epg_channel_id, "daserste.de");
+        assert_eq!(suggestions[1].candidates[0].score, 100);
+        assert!(suggestions[2].candidates.is_empty());
+
+        mappings.apply(EpgMappingDecisionDto { name: "CNN International HD".to_string(), epg_channel_id: "cnn.int".to_string(), accept: true });
+        mappings.apply(EpgMappingDecisionDto { name: "Das Erste".to_string(), epg_channel_id: "daserste.de".to_string(), accept: false });
+        mappings.apply(EpgMappingDecisionDto { name: "Cartoon Network".to_string(), epg_channel_id: "zdf.de".to_string(), accept: true });
+        let suggestions = suggest_epg_mappings(&channels, &epg_channels, &mappings);
+        assert_eq!(suggestions.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), vec!["Das Erste"]);
+        assert!(suggestions[0].candidates.is_empty());
+    }
+
+    #[test]
+    fn test_search_epg_channels() {
+        let epg_channels = vec![
+            epg_channel("cnn.int", "CNN International"),
+            epg_channel("cnn.us", "CNN"),
+            epg_channel("daserste.de", "Das Erste"),
+        ];
+        let results = search_epg_channels(&epg_channels, "cnn");
+        assert_eq!(results.iter().map(|r| r.epg_channel_id.as_str()).collect::<Vec<_>>(), vec!["cnn.us", "cnn.int"]);
+        assert_eq!(results[0].score, 100);
+        assert_eq!(search_epg_channels(&epg_channels, "erste.de")[0].epg_channel_id, "daserste.de");
+        assert!(search_epg_channels(&epg_channels, "zdf").is_empty());
+        assert!(search_epg_channels(&epg_channels, " ").is_empty());
+    }
+
     #[test]
     fn test_phonetic() {
         let strings: Vec<String> = (0..5_000)
@@ -294,4 +687,21 @@ mod tests {
         let elapsed = now.elapsed();
         println!("Elapsed time: {}.{:03} secs", elapsed.as_secs(), elapsed.subsec_millis());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_epg_source_file_path() {
+        let working_dir = tempfile::tempdir().unwrap();
+        let local_file = working_dir.path().join("local_epg.xml");
+        std::fs::write(&local_file, "<tv></tv>").unwrap();
+        let input = ConfigInput { name: "input".intern(), ..ConfigInput::default() };
+        let epg_source = |url: String| EpgSource { url, priority: 0, logo_override: false, schedule: None };
+        let working_dir = working_dir.path().to_string_lossy().to_string();
+
+        let local_url = url::Url::from_file_path(&local_file).unwrap().to_string();
+        assert_eq!(get_epg_source_file_path(&epg_source(local_url), &input, &working_dir), Some(local_file.clone()));
+        let missing_url = url::Url::from_file_path(local_file.with_file_name("missing.xml")).unwrap().to_string();
+        assert_eq!(get_epg_source_file_path(&epg_source(missing_url), &input, &working_dir), None);
+        // remote sources are read from their download, it does not exist yet
+        assert_eq!(get_epg_source_file_path(&epg_source("http://provider.tv/epg.xml".to_string()), &input, &working_dir), None);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::model::ConfigGroupMapping;
+use indexmap::IndexMap;
+use shared::model::{PlaylistGroup, XtreamCluster};
+use std::sync::Arc;
+
+/// Moves the channels to the groups of their matching rules. The groups of the rules come first in the order of the rules,
+/// followed by the groups without a matching rule and the catch-all group.
+pub(in crate::processing) fn process_group_mapping(playlist: Vec<PlaylistGroup>, group_mapping: &ConfigGroupMapping) -> Vec<PlaylistGroup> {
+    let mut mapped: IndexMap<(XtreamCluster, Arc<str>), PlaylistGroup> = IndexMap::new();
+    let mut unmapped: Vec<PlaylistGroup> = Vec::new();
+    for mut group in playlist {
+        let mut channels = Vec::with_capacity(group.channels.len());
+        for mut pli in group.channels.drain(..) {
+            if let Some(new_group) = group_mapping.get_group(&pli) {
+                pli.header.group = Arc::clone(new_group);
+                mapped.entry((group.xtream_cluster, Arc::clone(new_group)))
+                    .or_insert_with(|| PlaylistGroup {
+                        id: 0,
+                        title: Arc::clone(new_group),
+                        channels: vec![],
+                        xtream_cluster: group.xtream_cluster,
+                    })
+                    .channels.push(pli);
+            } else {
+                channels.push(pli);
+            }
+        }
+        if !channels.is_empty() {
+            group.channels = channels;
+            unmapped.push(group);
+        }
+    }
+
+    let rule_index = |title: &Arc<str>| group_mapping.rules.iter().position(|rule| rule.group == *title);
+    mapped.sort_by_cached_key(|(_, title), _| rule_index(title).unwrap_or(usize::MAX));
+    let catch_all_start = mapped.values().position(|group| rule_index(&group.title).is_none()).unwrap_or(mapped.len());
+    let mut groups: Vec<PlaylistGroup> = mapped.into_values().collect();
+    let catch_all = groups.split_off(catch_all_start);
+    groups.extend(unmapped);
+    groups.extend(catch_all);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::process_group_mapping;
+    use crate::model::ConfigGroupMapping;
+    use shared::model::{ConfigGroupMappingDto, ConfigGroupMappingRuleDto, PlaylistGroup, PlaylistItem, PlaylistItemHeader, XtreamCluster};
+    use std::sync::Arc;
+
+    fn group(title: &str, channels: &[&str]) -> PlaylistGroup {
+        PlaylistGroup {
+            id: 1,
+            title: title.into(),
+            channels: channels.iter().map(|name| PlaylistItem {
+                header: PlaylistItemHeader {
+                    title: (*name).into(),
+                    name: (*name).into(),
+                    group: title.into(),
+                    ..Default::default()
+                },
+            }).collect(),
+            xtream_cluster: XtreamCluster::Live,
+        }
+    }
+
+    fn rule(group: &str, pattern: Option<&str>, filter: Option<&str>, priority: u16) -> ConfigGroupMappingRuleDto {
+        ConfigGroupMappingRuleDto {
+            group: group.to_string(),
+            pattern: pattern.map(ToString::to_string),
+            filter: filter.map(ToString::to_string),
+            priority,
+            t_filter: None,
+        }
+    }
+
+    fn titles(groups: &[PlaylistGroup]) -> Vec<(String, Vec<String>)> {
+        groups.iter().map(|group| (group.title.to_string(),
+                                   group.channels.iter().map(|pli| format!("{}:{}", pli.header.group, pli.header.title)).collect())).collect()
+    }
+
+    #[test]
+    fn test_group_mapping() {
+        let mut dto = ConfigGroupMappingDto {
+            rules: vec![
+                rule("Sports", Some("(?i)sport"), None, 0),
+                rule("Kids", None, Some(r#"Title ~ "(?i)kids""#), 10),
+            ],
+            catch_all: None,
+        };
+        dto.prepare(None).unwrap();
+        let playlist = vec![
+            group("DE Sport", &["Sport 1", "Kids Sport"]),
+            group("DE News", &["News 1"]),
+            group("UK Sports", &["Sky Sports"]),
+            group("UK Kids", &["Kids 1"]),
+        ];
+        let mapping = ConfigGroupMapping::from(&dto);
+        let result = process_group_mapping(playlist.clone(), &mapping);
+        assert_eq!(titles(&result), vec![
+            ("Kids".to_string(), vec!["Kids:Kids Sport".to_string(), "Kids:Kids 1".to_string()]),
+            ("Sports".to_string(), vec!["Sports:Sport 1".to_string(), "Sports:Sky Sports".to_string()]),
+            ("DE News".to_string(), vec!["DE News:News 1".to_string()]),
+        ]);
+        assert_eq!(result[0].id, 0);
+        assert_eq!(result[2].id, 1);
+
+        dto.catch_all = Some("Other".to_string());
+        let mapping = ConfigGroupMapping::from(&dto);
+        let result = process_group_mapping(playlist, &mapping);
+        assert_eq!(result.iter().map(|group| Arc::clone(&group.title)).collect::<Vec<_>>(),
+                   vec![Arc::from("Kids"), Arc::from("Sports"), Arc::from("Other")]);
+    }
+}
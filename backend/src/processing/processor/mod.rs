@@ -4,7 +4,14 @@ mod xtream;
 mod xtream_vod;
 mod xtream_series;
 pub mod epg;
+mod tvg_id_registry;
 mod sort;
+mod group_mapping;
+mod series_grouping;
+mod deduplication;
+mod channel_numbering;
+mod collation;
+#[cfg(feature = "trakt")]
 pub mod trakt;
 mod library;
 
@@ -17,7 +24,8 @@ macro_rules! create_resolve_options_function_for_xtream_target {
         paste::paste! {
             fn [<get_resolve_ $cluster _options>](target: &ConfigTarget, fpl: &FetchedPlaylist) -> (bool, u16) {
                 match target.get_xtream_output() {
-                    Some(xtream_output) => (xtream_output.[<resolve_ $cluster>] && fpl.input.input_type == InputType::Xtream,
+                    Some(xtream_output) => (xtream_output.[<resolve_ $cluster>] && fpl.input.input_type == InputType::Xtream
+                                               && !fpl.input.is_in_maintenance(),
                                            xtream_output.[<resolve_ $cluster _delay>]),
                     None => (false, 0)
                 }
@@ -1,11 +1,13 @@
 use crate::model::{AppConfig, ConfigFavourites, ConfigInput, ConfigRename, ReverseProxyDisabledHeaderConfig, TVGuide};
 use crate::utils::m3u;
+use crate::utils::network_route::check_network_route;
 use crate::utils::xtream;
+use crate::utils::media_server::register_target_media_servers;
 use crate::utils::{epg, StepMeasureCallback};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, Weak};
-use tokio::sync::{Mutex, OwnedRwLockWriteGuard, RwLock};
+use tokio::sync::{Mutex, OwnedRwLockWriteGuard, RwLock, Semaphore};
 use tokio::task::JoinSet;
 
 use crate::api::model::{EventManager, EventMessage, PlaylistStorageState, UpdateGuard};
@@ -16,61 +18,96 @@ use crate::model::Epg;
 
 use crate::model::FetchedPlaylist;
 use crate::model::Mapping;
-use crate::model::{ConfigTarget, ProcessTargets};
+use crate::model::{ConfigTarget, ProcessTargets, TraktMatchedIds};
 use crate::model::{InputStats, PlaylistStats, SourceStats, TargetStats};
 use crate::processing::input_cache;
 use crate::processing::input_cache::ClusterState;
 use crate::processing::parser::xmltv::flatten_tvguide;
 use crate::processing::playlist_watch::process_group_watch;
 use crate::processing::processor::epg::process_playlist_epg;
+use crate::processing::processor::group_mapping::process_group_mapping;
+use crate::processing::processor::series_grouping::process_series_grouping;
+use crate::processing::processor::deduplication::{process_deduplication, process_variant_collapsing};
+use crate::processing::processor::channel_numbering::assign_channel_numbers;
 use crate::processing::processor::library;
 use crate::processing::processor::sort::sort_playlist;
+#[cfg(feature = "trakt")]
 use crate::processing::processor::trakt::process_trakt_categories_for_target;
 use crate::processing::processor::xtream_series::playlist_resolve_series;
 use crate::processing::processor::xtream_vod::playlist_resolve_vod;
-use crate::repository::{load_input_playlist, persist_input_playlist, persist_playlist};
-use crate::repository::{CategoryKey, MemoryPlaylistSource, PlaylistSource};
+use crate::repository::{load_channel_numbers, load_epg_mappings, load_input_playlist, load_quarantined_uuids, persist_input_playlist, persist_playlist,
+                        save_channel_numbers, save_processing_stats, update_playlist_changes};
+use crate::repository::{CategoryKey, ChannelFailovers, MemoryPlaylistSource, PlaylistSource};
 use crate::utils::StepMeasure;
 use crate::utils::{debug_if_enabled, trace_if_enabled};
 use futures::StreamExt;
 use indexmap::IndexMap;
 use log::{debug, error, info, log_enabled, warn, Level};
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use shared::concat_string;
 use shared::error::{get_errors_notify_message, notify_err, TuliproxError};
 use shared::foundation::{get_field_value, set_field_value, ValueAccessor, ValueProvider};
-use shared::foundation::Filter;
+use shared::foundation::{Filter, FilterPlan};
 use shared::model::xtream_const::XTREAM_CLUSTER;
-use shared::model::UUIDType;
+use shared::model::{DeduplicationStats, NotificationKind, PlaylistChangeSummary, ProcessingStage, ProcessingTimings, UUIDType};
 use shared::model::{CounterModifier, FieldGetAccessor, FieldSetAccessor, InputType, ItemField,
-                    PlaylistGroup, PlaylistItem, PlaylistItemType, PlaylistUpdateState,
+                    PlaylistEntry, PlaylistGroup, PlaylistItem, PlaylistItemType, PlaylistUpdateState,
                     ProcessingOrder, XtreamCluster};
 use shared::utils::{create_alias_uuid, default_as_default, interner_gc, Internable};
 use std::time::Instant;
 
+/// Number of items which are pulled from a playlist source and processed in parallel at once.
+/// Chunks keep the memory footprint bounded for disk based sources.
+const PARALLEL_CHUNK_SIZE: usize = 4096;
+
+/// Applies `process` to the items on the rayon pool and hands the results to `consume` in source order.
+/// Regex evaluation of filters, mappers and renames dominates the processing time on large playlists,
+/// the items are independent of each other, so only the grouping has to be sequential.
+fn process_items_parallel<T, R, F, C>(mut items: impl Iterator<Item=T>, process: F, mut consume: C)
+where
+    T: Send,
+    R: IntoIterator,
+    R::Item: Send,
+    F: Fn(T) -> R + Sync + Send,
+    C: FnMut(R::Item),
+{
+    loop {
+        let chunk: Vec<T> = items.by_ref().take(PARALLEL_CHUNK_SIZE).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        let processed: Vec<R::Item> = chunk.into_par_iter().flat_map_iter(&process).collect();
+        processed.into_iter().for_each(&mut consume);
+    }
+}
+
 fn is_valid(pli: &PlaylistItem, filter: &Filter, match_as_ascii: bool) -> bool {
     let provider = ValueProvider { pli, match_as_ascii };
     filter.filter(&provider)
 }
 
 pub fn apply_filter_to_source(source: &mut dyn PlaylistSource, filter: &Filter) -> Option<Vec<PlaylistGroup>> {
+    let plan = FilterPlan::new(&[Some(filter)]);
+    let is_valid_item = |pli: &PlaylistItem| match &plan {
+        Some(plan) => plan.matcher().matches(0, &ValueProvider { pli, match_as_ascii: false }),
+        None => is_valid(pli, filter, false),
+    };
     let mut groups: IndexMap<CategoryKey, PlaylistGroup> = IndexMap::new();
-    for pli in source.into_items() {
-        if is_valid(&pli, filter, false) {
-            let group_title = pli.header.group.clone();
-            let cluster = pli.header.xtream_cluster;
-            let cat_id = pli.header.category_id;
-            let normalized_group = shared::utils::deunicode_string(&group_title).to_lowercase().intern();
-            let key = (cluster, normalized_group);
-            groups.entry(key)
-                .or_insert_with(|| PlaylistGroup {
-                    id: cat_id,
-                    title: group_title,
-                    channels: vec![],
-                    xtream_cluster: cluster,
-                })
-                .channels.push(pli);
-        }
-    }
+    process_items_parallel(source.into_items(), |pli| is_valid_item(&pli).then_some(pli), |pli| {
+        let group_title = pli.header.group.clone();
+        let cluster = pli.header.xtream_cluster;
+        let cat_id = pli.header.category_id;
+        let normalized_group = shared::utils::deunicode_string(&group_title).to_lowercase().intern();
+        let key = (cluster, normalized_group);
+        groups.entry(key)
+            .or_insert_with(|| PlaylistGroup {
+                id: cat_id,
+                title: group_title,
+                channels: vec![],
+                xtream_cluster: cluster,
+            })
+            .channels.push(pli);
+    });
 
     if groups.is_empty() { None } else { Some(groups.into_values().collect()) }
 }
@@ -82,7 +119,7 @@ fn filter_playlist(source: &mut dyn PlaylistSource, target: &ConfigTarget) -> Op
 pub fn apply_filter_to_playlist(playlist: &mut [PlaylistGroup], filter: &Filter) -> Option<Vec<PlaylistGroup>> {
     let mut new_playlist = Vec::with_capacity(128);
     for pg in playlist.iter_mut() {
-        let channels = pg.channels.iter()
+        let channels = pg.channels.par_iter()
             .filter(|&pli| is_valid(pli, filter, false)).cloned().collect::<Vec<PlaylistItem>>();
         if !channels.is_empty() {
             new_playlist.push(PlaylistGroup {
@@ -136,7 +173,7 @@ fn rename_playlist(source: &mut dyn PlaylistSource, target: &ConfigTarget) -> Op
     match &target.rename {
         Some(renames) if !renames.is_empty() => {
             let mut groups: IndexMap<(XtreamCluster, Arc<str>), PlaylistGroup> = IndexMap::new();
-            for mut pli in source.into_items() {
+            let rename_item = |mut pli: PlaylistItem| {
                 // Handle group rename first if it's in the renames
                 for r in renames {
                     if matches!(r.field, ItemField::Group) {
@@ -148,6 +185,9 @@ fn rename_playlist(source: &mut dyn PlaylistSource, target: &ConfigTarget) -> Op
                     }
                 }
                 exec_rename(&mut pli, Some(renames));
+                Some(pli)
+            };
+            process_items_parallel(source.into_items(), rename_item, |pli| {
                 let group_title = pli.header.group.clone();
                 let cluster = pli.header.xtream_cluster;
                 let cat_id = pli.header.category_id;
@@ -159,7 +199,7 @@ fn rename_playlist(source: &mut dyn PlaylistSource, target: &ConfigTarget) -> Op
                         xtream_cluster: cluster,
                     })
                     .channels.push(pli);
-            }
+            });
             Some(groups.into_values().collect())
         }
         _ => None
@@ -167,22 +207,31 @@ fn rename_playlist(source: &mut dyn PlaylistSource, target: &ConfigTarget) -> Op
 }
 
 
-fn map_channel(mut channel: PlaylistItem, mapping: &Mapping) -> (PlaylistItem, Vec<PlaylistItem>, bool) {
+fn map_channel(mut channel: PlaylistItem, mapping: &Mapping, plan: Option<&FilterPlan>) -> (PlaylistItem, Vec<PlaylistItem>, bool) {
     let mut matched = false;
     let mut virtual_items = vec![];
     if let Some(mapper) = &mapping.mapper {
         if !mapper.is_empty() {
             let ref_chan = &mut channel;
             let templates = mapping.templates.as_ref();
-            for m in mapper {
+            let mut plan_matcher = plan.map(FilterPlan::matcher);
+            for (index, m) in mapper.iter().enumerate() {
                 if let Some(script) = m.t_script.as_ref() {
                     if let Some(filter) = &m.t_filter {
                         let provider = ValueProvider { pli: ref_chan, match_as_ascii: mapping.match_as_ascii };
-                        if filter.filter(&provider) {
+                        let is_match = match plan_matcher.as_mut() {
+                            Some(plan_matcher) => plan_matcher.matches(index, &provider),
+                            None => filter.filter(&provider),
+                        };
+                        if is_match {
                             matched = true;
                             let mut accessor = ValueAccessor { pli: ref_chan, virtual_items: vec![], match_as_ascii: mapping.match_as_ascii };
                             script.eval(&mut accessor, templates);
                             virtual_items.extend(accessor.virtual_items.into_iter().map(|(_, pli)| pli));
+                            // the script may have changed the fields the following filters match against
+                            if let Some(plan_matcher) = plan_matcher.as_mut() {
+                                plan_matcher.reset();
+                            }
                         }
                     }
                 }
@@ -192,8 +241,8 @@ fn map_channel(mut channel: PlaylistItem, mapping: &Mapping) -> (PlaylistItem, V
     (channel, virtual_items, matched)
 }
 
-fn map_channel_and_flatten(channel: PlaylistItem, mapping: &Mapping) -> Vec<PlaylistItem> {
-    let (mapped_channel, mut virtual_items, _matched) = map_channel(channel, mapping);
+fn map_channel_and_flatten(channel: PlaylistItem, mapping: &Mapping, plan: Option<&FilterPlan>) -> Vec<PlaylistItem> {
+    let (mapped_channel, mut virtual_items, _matched) = map_channel(channel, mapping, plan);
     let mut result = Vec::with_capacity(1 + virtual_items.len());
 
     result.push(mapped_channel);
@@ -201,18 +250,29 @@ fn map_channel_and_flatten(channel: PlaylistItem, mapping: &Mapping) -> Vec<Play
     result
 }
 
+/// Combines the filters of all mappers of a mapping, the plan index is the mapper index.
+fn create_mapping_filter_plan(mapping: &Mapping) -> Option<FilterPlan> {
+    let mapper = mapping.mapper.as_ref()?;
+    let filters: Vec<Option<&Filter>> = mapper.iter().map(|m| m.t_filter.as_ref()).collect();
+    FilterPlan::new(&filters)
+}
+
 fn map_playlist(source: &mut dyn PlaylistSource, target: &ConfigTarget) -> Option<Vec<PlaylistGroup>> {
     let mapping_binding = target.mapping.load();
     let mappings = mapping_binding.as_ref()?;
-    let valid_mappings = mappings.iter().filter(|m| m.mapper.as_ref().is_some_and(|v| !v.is_empty()));
-    let iter: Box<dyn Iterator<Item=PlaylistItem>> = Box::new(source.into_items());
-    let mapped_iter = valid_mappings.fold(iter, |iter, mapping| {
-        Box::new(iter.flat_map(move |chan| map_channel_and_flatten(chan, mapping)))
-            as Box<dyn Iterator<Item=PlaylistItem>>
-    });
+    let valid_mappings: Vec<(&Mapping, Option<FilterPlan>)> = mappings.iter()
+        .filter(|m| m.mapper.as_ref().is_some_and(|v| !v.is_empty()))
+        .map(|m| (m, create_mapping_filter_plan(m)))
+        .collect();
+    // every mapping is applied to the output of the previous one, including the virtual items it created
+    let map_item = |chan: PlaylistItem| {
+        valid_mappings.iter().fold(vec![chan], |channels, (mapping, plan)| {
+            channels.into_iter().flat_map(|c| map_channel_and_flatten(c, mapping, plan.as_ref())).collect()
+        })
+    };
     let mut next_groups: IndexMap<CategoryKey, PlaylistGroup> = IndexMap::new();
     let mut grp_id: u32 = 0;
-    for channel in mapped_iter {
+    process_items_parallel(source.into_items(), map_item, |channel| {
         let group_title = channel.header.group.clone();
         let cluster = channel.header.xtream_cluster;
         next_groups.entry((cluster, group_title.clone()))
@@ -226,7 +286,7 @@ fn map_playlist(source: &mut dyn PlaylistSource, target: &ConfigTarget) -> Optio
                 }
             })
             .channels.push(channel);
-    }
+    });
 
     Some(next_groups.into_values().collect())
 }
@@ -273,7 +333,7 @@ fn is_input_enabled(input: &ConfigInput, user_targets: &ProcessTargets) -> bool
     input.enabled && (!user_targets.enabled || user_targets.has_input(input.id))
 }
 
-fn is_target_enabled(target: &ConfigTarget, user_targets: &ProcessTargets) -> bool {
+pub fn is_target_enabled(target: &ConfigTarget, user_targets: &ProcessTargets) -> bool {
     (!user_targets.enabled && target.enabled) || (user_targets.enabled && user_targets.has_target(target.id))
 }
 
@@ -319,11 +379,8 @@ async fn playlist_download_from_input(client: &reqwest::Client, app_config: &Arc
         return (vec![], vec![], true, false);
     }
 
-    let (playlist, errors, persisted) = match input.input_type {
-        InputType::M3u => {
-            let (p, e) = m3u::download_m3u_playlist(app_config, client, config, input).await;
-            (p, e, false)
-        }
+    let (mut playlist, mut errors, mut persisted) = match input.input_type {
+        InputType::M3u | InputType::Udp => m3u::download_m3u_playlist(app_config, client, config, input).await,
         InputType::Xtream => xtream::download_xtream_playlist(app_config, client, input, clusters_to_download.as_deref()).await,
         InputType::M3uBatch | InputType::XtreamBatch => (vec![], vec![], false),
         InputType::Library => {
@@ -332,6 +389,17 @@ async fn playlist_download_from_input(client: &reqwest::Client, app_config: &Arc
         }
     };
 
+    // A refresh outside the guardrails is treated as a provider glitch, the last stored playlist is used instead.
+    if !persisted {
+        let item_count = playlist.iter().map(|group| group.channels.len()).sum();
+        if let Err(err) = input.check_item_count(item_count) {
+            warn!("{}", err.message);
+            playlist.clear();
+            errors.push(err);
+            persisted = true;
+        }
+    }
+
     // Update Status
     if errors.is_empty() {
         if let InputType::Xtream = input.input_type {
@@ -369,11 +437,14 @@ async fn playlist_download_from_input(client: &reqwest::Client, app_config: &Arc
     (playlist, errors, false, persisted)
 }
 
+#[allow(clippy::too_many_lines)]
 async fn process_source(source_idx: usize, ctx: &PlaylistProcessingContext) -> (Vec<InputStats>, Vec<TargetStats>, Vec<TuliproxError>) {
     let sources = ctx.config.sources.load();
     let mut errors = vec![];
     let mut input_stats = HashMap::<Arc<str>, InputStats>::new();
     let mut target_stats = Vec::<TargetStats>::new();
+    // the inputs are downloaded once for all targets of the source
+    let mut source_timings = ProcessingTimings::default();
     if let Some(source) = sources.get_source_at(source_idx) {
         let mut source_playlists = Vec::with_capacity(128);
         let broadcast_step = create_broadcast_callback(ctx.event_manager.as_ref());
@@ -387,12 +458,24 @@ async fn process_source(source_idx: usize, ctx: &PlaylistProcessingContext) -> (
             if is_input_enabled(input, &ctx.user_targets) {
                 source_downloaded = true;
 
+                // During maintenance the provider is not requested, the last stored data is used and failures are not notified.
+                let in_maintenance = input.is_in_maintenance();
+                if in_maintenance {
+                    broadcast_step("Playlist download", &format!("Input '{}' is in maintenance, using the last data", input.name));
+                    info!("Input {} is in maintenance, using the last data", input.name);
+                } else if let Err(err) = check_network_route(&ctx.config, &ctx.client, &input.name).await {
+                    broadcast_step("Playlist download", &format!("Skipping input '{}', {err}", input.name));
+                    error!("Skipping input {}, {err}", input.name);
+                    errors.push(notify_err!("Skipping input {}, {err}", input.name));
+                    continue;
+                }
+
                 let start_time = Instant::now();
                 // Download the playlist for input
                 let (mut playlist_groups, mut error_list) = {
                     broadcast_step("Playlist download", &format!("Downloading input '{}'", input.name));
 
-                    let (mut download_err, playlist, error) = download_input(ctx, input).await;
+                    let (mut download_err, playlist, error) = download_input(ctx, input, &mut source_timings).await;
 
                     if let Some(err) = error {
                         broadcast_step("Playlist download", &format!("Failed to persist/load input '{}' playlist", input.name));
@@ -402,12 +485,17 @@ async fn process_source(source_idx: usize, ctx: &PlaylistProcessingContext) -> (
                     (playlist, download_err)
                 };
 
+                let epg_start = Instant::now();
                 let tvguide = if input.input_type == InputType::Library {
                     None
                 } else {
                     download_input_epg(ctx, input, &mut error_list).await
                 };
+                source_timings.add(ProcessingStage::Epg, epg_start.elapsed());
 
+                if in_maintenance {
+                    error_list.drain(..).for_each(|err| warn!("Input {} in maintenance: {}", input.name, err.message));
+                }
                 errors.append(&mut error_list);
                 let group_count = playlist_groups.get_group_count();
                 let channel_count = playlist_groups.get_channel_count();
@@ -415,7 +503,9 @@ async fn process_source(source_idx: usize, ctx: &PlaylistProcessingContext) -> (
                 if playlist_groups.is_empty() {
                     broadcast_step("Playlist download", &format!("Input '{}' playlist is empty", input.name));
                     info!("Source is empty {input_name}");
-                    errors.push(notify_err!("Source is empty {input_name}"));
+                    if !in_maintenance {
+                        errors.push(notify_err!("Source is empty {input_name}"));
+                    }
                 } else {
                     source_playlists.push(
                         FetchedPlaylist {
@@ -436,16 +526,24 @@ async fn process_source(source_idx: usize, ctx: &PlaylistProcessingContext) -> (
                 errors.push(notify_err!("Source at index {source_idx} is empty: {}", source.inputs.iter().map(Clone::clone).collect::<Vec<Arc<str>>>().join(", ")));
             } else {
                 debug_if_enabled!("Source has {} groups", source_playlists.iter_mut().map(FetchedPlaylist::get_channel_count).sum::<usize>());
-                for target in &source.targets {
-                    if is_target_enabled(target, &ctx.user_targets) {
-                        match process_playlist_for_target(ctx, &mut source_playlists, target,
-                                                          &mut input_stats, &mut errors).await {
-                            Ok(()) => {
-                                target_stats.push(TargetStats::success(&target.name));
-                            }
-                            Err(mut err) => {
-                                target_stats.push(TargetStats::failure(&target.name));
-                                errors.append(&mut err);
+                let target_workers = usize::from(ctx.config.config.load().process_parallel_targets);
+                if target_workers > 1 && source.targets.len() > 1 {
+                    process_targets_parallel(ctx, &mut source_playlists, &source.targets, target_workers,
+                                             &mut input_stats, &mut target_stats, &mut errors).await;
+                } else {
+                    for target in &source.targets {
+                        if is_target_enabled(target, &ctx.user_targets) {
+                            let (result, timings) = process_playlist_for_target(ctx, &mut source_playlists, target,
+                                                                                &mut input_stats, &mut errors).await;
+                            match result {
+                                Ok(update) => {
+                                    target_stats.push(TargetStats::success(&target.name).with_changes(update.changes)
+                                        .with_duplicates(update.duplicates).with_timings(timings));
+                                }
+                                Err(mut err) => {
+                                    target_stats.push(TargetStats::failure(&target.name).with_timings(timings));
+                                    errors.append(&mut err);
+                                }
                             }
                         }
                     }
@@ -453,6 +551,9 @@ async fn process_source(source_idx: usize, ctx: &PlaylistProcessingContext) -> (
             }
         }
     }
+    for timings in target_stats.iter_mut().filter_map(|stats| stats.timings.as_mut()) {
+        timings.merge(&source_timings);
+    }
     (input_stats.into_values().collect(), target_stats, errors)
 }
 
@@ -469,10 +570,12 @@ async fn download_input_epg(ctx: &PlaylistProcessingContext, input: &Arc<ConfigI
     tvguide
 }
 
-async fn download_input(ctx: &PlaylistProcessingContext, input: &Arc<ConfigInput>)
+/// The provider download is recorded as fetch stage, storing and loading the input playlist as parse stage.
+async fn download_input(ctx: &PlaylistProcessingContext, input: &Arc<ConfigInput>, timings: &mut ProcessingTimings)
                         -> (Vec<TuliproxError>, Box<dyn PlaylistSource>, Option<TuliproxError>) {
+    let fetch_start = Instant::now();
     // Coordination Logic
-    let need_download = !ctx.is_input_downloaded(&input.name).await;
+    let need_download = !input.is_in_maintenance() && !ctx.is_input_downloaded(&input.name).await;
 
     let (downloaded_playlist, download_err, was_cached, persisted) = if need_download {
         // Acquire named lock to prevent thundering herd on same input
@@ -494,7 +597,9 @@ async fn download_input(ctx: &PlaylistProcessingContext, input: &Arc<ConfigInput
     } else {
         (vec![], vec![], true, false)
     };
+    timings.add(ProcessingStage::Fetch, fetch_start.elapsed());
 
+    let parse_start = Instant::now();
     let (playlist, error) = if was_cached || persisted {
         match load_input_playlist(ctx, input, None).await {
             Ok(pl_source) => (pl_source, None),
@@ -505,6 +610,7 @@ async fn download_input(ctx: &PlaylistProcessingContext, input: &Arc<ConfigInput
         let (pl, err) = persist_input_playlist(&ctx.config, input, downloaded_playlist).await;
         (MemoryPlaylistSource::new(pl).boxed(), err)
     };
+    timings.add(ProcessingStage::Parse, parse_start.elapsed());
     (download_err, playlist, error)
 }
 
@@ -655,8 +761,17 @@ fn get_processing_pipe(target: &ConfigTarget) -> ProcessingPipe {
     }
 }
 
+/// Timing stage of each function of the processing pipe, renaming is counted as mapping.
+fn get_processing_pipe_stages(target: &ConfigTarget) -> [ProcessingStage; 3] {
+    match &target.processing_order {
+        ProcessingOrder::Frm | ProcessingOrder::Fmr => [ProcessingStage::Filter, ProcessingStage::Map, ProcessingStage::Map],
+        ProcessingOrder::Rfm | ProcessingOrder::Mfr => [ProcessingStage::Map, ProcessingStage::Filter, ProcessingStage::Map],
+        ProcessingOrder::Rmf | ProcessingOrder::Mrf => [ProcessingStage::Map, ProcessingStage::Map, ProcessingStage::Filter],
+    }
+}
+
 fn execute_pipe<'a>(target: &ConfigTarget, pipe: &ProcessingPipe, fpl: &FetchedPlaylist<'a>,
-                    duplicates: &mut HashSet<UUIDType>) -> FetchedPlaylist<'a> {
+                    duplicates: &mut HashSet<UUIDType>, step: &mut StepMeasure) -> FetchedPlaylist<'a> {
     let mut new_fpl = FetchedPlaylist {
         input: fpl.input,
         source: fpl.clone_source(),
@@ -665,15 +780,18 @@ fn execute_pipe<'a>(target: &ConfigTarget, pipe: &ProcessingPipe, fpl: &FetchedP
     if target.options.as_ref().is_some_and(|opt| opt.remove_duplicates) {
         new_fpl.deduplicate(duplicates);
     }
+    step.lap(ProcessingStage::Filter);
 
-    for f in pipe {
+    for (f, stage) in pipe.iter().zip(get_processing_pipe_stages(target)) {
         if let Some(groups) = f(new_fpl.source.as_mut(), target) {
             new_fpl.source = MemoryPlaylistSource::new(groups).boxed();
         }
+        step.lap(stage);
     }
     // Ensure source is memory-based for downstream mutable processing (VOD/series resolution)
     if !new_fpl.is_memory() {
         new_fpl.source = MemoryPlaylistSource::new(new_fpl.source.take_groups()).boxed();
+        step.lap(ProcessingStage::Map);
     }
     new_fpl
 }
@@ -703,29 +821,100 @@ fn flatten_groups(playlistgroups: Vec<PlaylistGroup>) -> Vec<PlaylistGroup> {
     sort_order
 }
 
-#[allow(clippy::too_many_arguments)]
+/// Result of a target update for the processing stats.
+struct TargetUpdate {
+    changes: Option<PlaylistChangeSummary>,
+    duplicates: Option<DeduplicationStats>,
+}
+
 async fn process_playlist_for_target(ctx: &PlaylistProcessingContext,
                                      playlists: &mut [FetchedPlaylist<'_>],
                                      target: &ConfigTarget,
                                      stats: &mut HashMap<Arc<str>, InputStats>,
                                      errors: &mut Vec<TuliproxError>,
-) -> Result<(), Vec<TuliproxError>> {
+) -> (Result<TargetUpdate, Vec<TuliproxError>>, ProcessingTimings) {
+    let broadcast_step = create_broadcast_callback(ctx.event_manager.as_ref());
+    let mut step = StepMeasure::new(&target.name, broadcast_step);
+    let (new_epg, new_playlist) = prepare_playlist_for_target(ctx, playlists, target, stats, errors, &mut step).await;
+    let result = finalize_playlist_for_target(ctx, target, new_epg, new_playlist, errors, &mut step).await;
+    (result, step.timings())
+}
+
+/// Processes the targets of a source with a pool of `workers`.
+/// The transformations and the series/vod resolution run sequentially, because they update the provider
+/// playlists which are shared between the targets (each info is fetched only once per input).
+/// The following CPU-heavy steps (merging, sorting, numbering) and persisting run in parallel.
+async fn process_targets_parallel(ctx: &PlaylistProcessingContext,
+                                  playlists: &mut [FetchedPlaylist<'_>],
+                                  targets: &[Arc<ConfigTarget>],
+                                  workers: usize,
+                                  stats: &mut HashMap<Arc<str>, InputStats>,
+                                  target_stats: &mut Vec<TargetStats>,
+                                  errors: &mut Vec<TuliproxError>) {
+    debug_if_enabled!("Parallel target processing enabled with {workers} workers");
+    let permits = Arc::new(Semaphore::new(workers));
+    let mut async_tasks = JoinSet::new();
+    for (index, target) in targets.iter().filter(|t| is_target_enabled(t, &ctx.user_targets)).enumerate() {
+        let broadcast_step = create_broadcast_callback(ctx.event_manager.as_ref());
+        let mut step = StepMeasure::new(&target.name, broadcast_step);
+        let (new_epg, new_playlist) = prepare_playlist_for_target(ctx, playlists, target, stats, errors, &mut step).await;
+        let Ok(permit) = Arc::clone(&permits).acquire_owned().await else {
+            break;
+        };
+        let task_ctx = ctx.clone();
+        let task_target = Arc::clone(target);
+        async_tasks.spawn(async move {
+            let mut task_errors = vec![];
+            let result = finalize_playlist_for_target(&task_ctx, &task_target, new_epg, new_playlist, &mut task_errors, &mut step).await;
+            drop(permit);
+            (index, task_target, result, step.timings(), task_errors)
+        });
+    }
+
+    let mut results = Vec::with_capacity(targets.len());
+    while let Some(result) = async_tasks.join_next().await {
+        match result {
+            Ok(target_result) => results.push(target_result),
+            Err(err) => error!("Target processing task failed: {err:?}"),
+        }
+    }
+    // keep the target order of the config for stats and errors
+    results.sort_by_key(|(index, ..)| *index);
+    for (_, target, result, timings, mut task_errors) in results {
+        errors.append(&mut task_errors);
+        match result {
+            Ok(update) => target_stats.push(TargetStats::success(&target.name).with_changes(update.changes)
+                .with_duplicates(update.duplicates).with_timings(timings)),
+            Err(mut err) => {
+                target_stats.push(TargetStats::failure(&target.name).with_timings(timings));
+                errors.append(&mut err);
+            }
+        }
+    }
+}
+
+async fn prepare_playlist_for_target(ctx: &PlaylistProcessingContext,
+                                     playlists: &mut [FetchedPlaylist<'_>],
+                                     target: &ConfigTarget,
+                                     stats: &mut HashMap<Arc<str>, InputStats>,
+                                     errors: &mut Vec<TuliproxError>,
+                                     step: &mut StepMeasure,
+) -> (Vec<Epg>, Vec<PlaylistGroup>) {
     debug_if_enabled!("Processing order is {}", &target.processing_order);
 
     let mut duplicates: HashSet<UUIDType> = HashSet::new();
     let mut processed_fetched_playlists: Vec<FetchedPlaylist> = vec![];
 
     debug!("Executing processing pipes");
-    let broadcast_step = create_broadcast_callback(ctx.event_manager.as_ref());
-
     let pipe = get_processing_pipe(target);
-    let mut step = StepMeasure::new(&target.name, broadcast_step);
     for provider_fpl in playlists.iter_mut() {
         step.broadcast("Executing transformations on '{}' playlist", &target.name);
-        let mut processed_fpl = execute_pipe(target, &pipe, provider_fpl, &mut duplicates);
+        let mut processed_fpl = execute_pipe(target, &pipe, provider_fpl, &mut duplicates, step);
         processed_fpl.sort_by_provider_ordinal();
+        step.lap(ProcessingStage::Sort);
         playlist_resolve_series(&ctx.config, &ctx.client, target, errors, &pipe, provider_fpl, &mut processed_fpl).await;
         playlist_resolve_vod(&ctx.config, &ctx.client, target, errors, provider_fpl, &mut processed_fpl).await;
+        step.lap(ProcessingStage::Fetch);
         // stats
         let input_entry_name = processed_fpl.input.name.clone();
         let group_count = processed_fpl.get_group_count();
@@ -737,21 +926,74 @@ async fn process_playlist_for_target(ctx: &PlaylistProcessingContext,
         processed_fetched_playlists.push(processed_fpl);
     }
     step.tick("filter rename map");
-    let (new_epg, mut new_playlist) = process_epg(&mut processed_fetched_playlists).await;
-    step.tick("epg");
+    let epg_mappings = load_epg_mappings(&ctx.config, &target.name).await;
+    let working_dir = ctx.config.config.load().working_dir.clone();
+    let result = process_epg(&mut processed_fetched_playlists, &epg_mappings.accepted, &working_dir).await;
+    step.tick_stage(ProcessingStage::Epg, "epg");
+    result
+}
+
+async fn finalize_playlist_for_target(ctx: &PlaylistProcessingContext,
+                                      target: &ConfigTarget,
+                                      new_epg: Vec<Epg>,
+                                      mut new_playlist: Vec<PlaylistGroup>,
+                                      errors: &mut Vec<TuliproxError>,
+                                      step: &mut StepMeasure,
+) -> Result<TargetUpdate, Vec<TuliproxError>> {
+    if target.options.as_ref().is_some_and(|options| options.remove_dead_channels > 0) {
+        let quarantined = load_quarantined_uuids(&ctx.config, &target.name).await;
+        if !quarantined.is_empty() {
+            let removed = remove_quarantined_channels(&mut new_playlist, &quarantined);
+            info!("Removed {removed} dead channels from target {}", target.name);
+        }
+    }
+
+    let mut channel_failovers = ChannelFailovers::new();
+    if target.is_collapse_variants() {
+        step.broadcast("Collapsing channel variants of '{}' playlist", &target.name);
+        let collapsed = process_variant_collapsing(&mut new_playlist, &mut channel_failovers);
+        info!("Collapsed {collapsed} channel variants of target {}", target.name);
+        step.tick("variant collapsing");
+    }
+
+    let mut duplicates = None;
+    if let Some(deduplication) = target.options.as_ref().and_then(|options| options.deduplication.as_ref()) {
+        step.broadcast("Removing duplicates of '{}' playlist", &target.name);
+        let stats = process_deduplication(&mut new_playlist, deduplication, &mut channel_failovers);
+        info!("Removed {} duplicate channels from target {}", stats.removed, target.name);
+        duplicates = Some(stats);
+        step.tick("deduplication");
+    }
+
+    if target.is_group_series() {
+        step.broadcast("Grouping series of '{}' playlist", &target.name);
+        new_playlist = process_series_grouping(new_playlist, |input_name| ctx.config.get_input_by_name(input_name)
+            .is_some_and(|input| matches!(input.input_type, InputType::M3u | InputType::M3uBatch)));
+        step.tick("series grouping");
+    }
+
+    if let Some(group_mapping) = target.group_mapping.as_ref() {
+        step.broadcast("Processing group mapping for '{}' playlist", &target.name);
+        new_playlist = process_group_mapping(new_playlist, group_mapping);
+        step.tick("group mapping");
+    }
 
     if target.favourites.is_some() {
         step.broadcast("Processing favourites for '{}' playlist", &target.name);
         process_favourites(&mut new_playlist, target.favourites.as_deref());
     }
+    step.lap(ProcessingStage::Map);
 
     if new_playlist.is_empty() {
         step.stop("");
         info!("Playlist is empty: {}", &target.name);
-        Ok(())
+        Ok(TargetUpdate { changes: None, duplicates })
     } else {
         // Process Trakt categories
-        if trakt_playlist(&ctx.client, target, errors, &mut new_playlist).await {
+        let mut trakt_matched_ids = TraktMatchedIds::default();
+        let trakt_processed = trakt_playlist(&ctx.client, target, errors, &mut new_playlist, &mut trakt_matched_ids).await;
+        step.lap(ProcessingStage::Fetch);
+        if trakt_processed {
             step.tick("trakt categories");
         }
 
@@ -761,20 +1003,46 @@ async fn process_playlist_for_target(ctx: &PlaylistProcessingContext,
         if sort_playlist(target, &mut flat_new_playlist) {
             step.tick("playlist sort");
         }
+        if let Some(channel_numbering) = target.channel_numbering.as_ref() {
+            let mut channel_numbers = load_channel_numbers(&ctx.config, &target.name).await;
+            assign_channel_numbers(&mut flat_new_playlist, channel_numbering, &mut channel_numbers, chrono::Utc::now().timestamp());
+            if let Err(err) = save_channel_numbers(&ctx.config, &target.name, &channel_numbers).await {
+                errors.push(err);
+            }
+        }
         assign_channel_no_playlist(&mut flat_new_playlist);
         step.tick("assigning channel numbers");
         map_playlist_counter(target, &mut flat_new_playlist);
-        step.tick("assigning channel counter");
+        step.tick_stage(ProcessingStage::Sort, "assigning channel counter");
 
         if process_watch(&ctx.config, &ctx.client, target, &flat_new_playlist).await {
             step.tick("group watches");
         }
-        let result = persist_playlist(&ctx.config, &mut flat_new_playlist, flatten_tvguide(&new_epg).as_ref(), target, ctx.playlist_state.as_ref()).await;
-        step.stop("Persisting playlists");
-        result
+        if let Err(err) = persist_playlist(&ctx.config, &mut flat_new_playlist, flatten_tvguide(&new_epg).as_ref(), target, ctx.playlist_state.as_ref(), &trakt_matched_ids, &channel_failovers).await {
+            step.lap(ProcessingStage::Persist);
+            step.stop("Persisting playlists");
+            return Err(err);
+        }
+        step.tick_stage(ProcessingStage::Persist, "Persisting playlists");
+        let changes = process_playlist_changes(&ctx.config, target, &flat_new_playlist).await;
+        step.lap(ProcessingStage::Persist);
+        step.stop("playlist changes");
+        Ok(TargetUpdate { changes, duplicates })
     }
 }
 
+/// Removes the live channels which are quarantined by the channel probe, returns the number of removed channels.
+fn remove_quarantined_channels(playlist: &mut Vec<PlaylistGroup>, quarantined: &HashSet<UUIDType>) -> usize {
+    let mut removed = 0;
+    for group in playlist.iter_mut() {
+        let count = group.channels.len();
+        group.channels.retain(|pli| !(pli.header.item_type.is_live() && quarantined.contains(&pli.get_uuid())));
+        removed += count - group.channels.len();
+    }
+    playlist.retain(|group| !group.channels.is_empty());
+    removed
+}
+
 pub fn process_favourites(playlist: &mut Vec<PlaylistGroup>, favourites_cfg: Option<&[ConfigFavourites]>) {
     if let Some(favourites) = favourites_cfg {
         let mut fav_groups: IndexMap<CategoryKey, Vec<PlaylistItem>> = IndexMap::new();
@@ -813,8 +1081,20 @@ pub fn process_favourites(playlist: &mut Vec<PlaylistGroup>, favourites_cfg: Opt
     }
 }
 
-async fn trakt_playlist(client: &reqwest::Client, target: &ConfigTarget, errors: &mut Vec<TuliproxError>, playlist: &mut Vec<PlaylistGroup>) -> bool {
-    match process_trakt_categories_for_target(client, playlist, target).await {
+#[cfg(not(feature = "trakt"))]
+#[allow(clippy::unused_async)]
+async fn trakt_playlist(_client: &reqwest::Client, target: &ConfigTarget, _errors: &mut Vec<TuliproxError>, _playlist: &mut Vec<PlaylistGroup>,
+                        _matched_ids: &mut TraktMatchedIds) -> bool {
+    if target.get_xtream_output().is_some_and(|output| output.trakt.as_ref().is_some_and(|trakt| trakt.enabled)) {
+        warn!("Trakt is configured for target {} but not available, tuliprox was built without the `trakt` feature", target.name);
+    }
+    false
+}
+
+#[cfg(feature = "trakt")]
+async fn trakt_playlist(client: &reqwest::Client, target: &ConfigTarget, errors: &mut Vec<TuliproxError>, playlist: &mut Vec<PlaylistGroup>,
+                        matched_ids: &mut TraktMatchedIds) -> bool {
+    match process_trakt_categories_for_target(client, playlist, target, matched_ids).await {
         Ok(Some(trakt_categories)) => {
             if !trakt_categories.is_empty() {
                 info!("Adding {} Trakt categories to playlist", trakt_categories.len());
@@ -832,14 +1112,15 @@ async fn trakt_playlist(client: &reqwest::Client, target: &ConfigTarget, errors:
     true
 }
 
-async fn process_epg(processed_fetched_playlists: &mut Vec<FetchedPlaylist<'_>>) -> (Vec<Epg>, Vec<PlaylistGroup>) {
+async fn process_epg(processed_fetched_playlists: &mut Vec<FetchedPlaylist<'_>>, epg_mappings: &BTreeMap<String, String>,
+                     working_dir: &str) -> (Vec<Epg>, Vec<PlaylistGroup>) {
     let mut new_playlist: Vec<PlaylistGroup> = vec![];
     let mut new_epg = vec![];
 
     // each fetched playlist can have its own epgl url.
     // we need to process each input epg.
     for fp in processed_fetched_playlists {
-        process_playlist_epg(fp, &mut new_epg).await;
+        process_playlist_epg(fp, &mut new_epg, epg_mappings, working_dir).await;
         new_playlist.extend(fp.source.take_groups());
     }
     (new_epg, new_playlist)
@@ -865,10 +1146,41 @@ async fn process_watch(app_config: &Arc<AppConfig>, client: &reqwest::Client, ta
     }
 }
 
+async fn process_playlist_changes(app_config: &AppConfig, target: &ConfigTarget, playlist: &[PlaylistGroup]) -> Option<PlaylistChangeSummary> {
+    match update_playlist_changes(app_config, target, playlist).await {
+        Ok(changes) => changes.map(|changes| {
+            let summary = changes.summary();
+            info!("Playlist changes for target {}: {summary}", target.name);
+            summary
+        }),
+        Err(err) => {
+            error!("{err}");
+            None
+        }
+    }
+}
+
+/// The updated targets are registered again, the urls change when the credentials of the user are rotated.
+async fn register_media_servers(client: &reqwest::Client, app_config: &AppConfig, stats: &[SourceStats]) -> Vec<TuliproxError> {
+    let updated: HashSet<&str> = stats.iter()
+        .flat_map(|source_stats| source_stats.targets.iter())
+        .filter(|target_stats| target_stats.success)
+        .map(|target_stats| target_stats.name.as_str())
+        .collect();
+    let sources = app_config.sources.load();
+    let mut errors = vec![];
+    for target in sources.sources.iter().flat_map(|source| source.targets.iter()) {
+        if !target.media_servers.is_empty() && updated.contains(target.name.as_str()) {
+            errors.extend(register_target_media_servers(client, app_config, target).await);
+        }
+    }
+    errors
+}
+
 pub async fn exec_processing(client: &reqwest::Client, app_config: Arc<AppConfig>, targets: Arc<ProcessTargets>,
                              event_manager: Option<Arc<EventManager>>, playlist_state: Option<Arc<PlaylistStorageState>>,
                              update_guard: Option<UpdateGuard>,
-                             disabled_headers: Option<ReverseProxyDisabledHeaderConfig>) {
+                             disabled_headers: Option<ReverseProxyDisabledHeaderConfig>) -> Vec<SourceStats> {
     let _guard = if let Some(guard) = update_guard {
         if let Some(permit) = guard.try_playlist() {
             Some(permit)
@@ -876,8 +1188,9 @@ pub async fn exec_processing(client: &reqwest::Client, app_config: Arc<AppConfig
             warn!("Playlist update already in progress; update skipped.");
             if let Some(events) = event_manager.as_ref() {
                 events.send_event(EventMessage::PlaylistUpdate(PlaylistUpdateState::Failure));
+                events.send_notification(NotificationKind::Warning, "Playlist update already in progress; update skipped.".to_string());
             }
-            return;
+            return Vec::new();
         }
     } else {
         None
@@ -896,7 +1209,8 @@ pub async fn exec_processing(client: &reqwest::Client, app_config: Arc<AppConfig
     };
 
     let start_time = Instant::now();
-    let (stats, errors) = process_sources(&ctx).await;
+    let (stats, mut errors) = process_sources(&ctx).await;
+    errors.extend(register_media_servers(client, &app_config, &stats).await);
     // log errors
     for err in &errors {
         error!("{}", err.message);
@@ -907,14 +1221,18 @@ pub async fn exec_processing(client: &reqwest::Client, app_config: Arc<AppConfig
         if let Ok(stats_msg) = serde_json::to_string(&stats) {
             info!("stats: {stats_msg}");
         }
+        if let Err(err) = save_processing_stats(&app_config, &stats).await {
+            error!("{err}");
+        }
         // send stats
-        send_message(&app_config, client, MessageContent::event_stats(stats)).await;
+        send_message(&app_config, client, MessageContent::event_stats(stats.clone())).await;
     }
 
     // send errors
     if let Some(message) = get_errors_notify_message!(errors, 255) {
         if let Some(events) = &event_manager {
             events.send_event(EventMessage::PlaylistUpdate(PlaylistUpdateState::Failure));
+            events.send_notification(NotificationKind::Error, message.clone());
         }
         send_message(&app_config, client, MessageContent::event_error(message)).await;
     } else if let Some(events) = &event_manager {
@@ -930,6 +1248,21 @@ pub async fn exec_processing(client: &reqwest::Client, app_config: Arc<AppConfig
     debug!("StringInterner GC removed {} strings", interner_gc());
 
     info!("{update_finished_message}");
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{process_items_parallel, PARALLEL_CHUNK_SIZE};
+
+    #[test]
+    fn test_process_items_parallel_keeps_order() {
+        let count = u32::try_from(PARALLEL_CHUNK_SIZE * 3 + 7).unwrap();
+        let mut result = Vec::new();
+        process_items_parallel(0..count, |i| if i % 3 == 0 { vec![] } else { vec![i, i] }, |i| result.push(i));
+        let expected: Vec<u32> = (0..count).filter(|i| i % 3 != 0).flat_map(|i| [i, i]).collect();
+        assert_eq!(result, expected);
+    }
 }
 
 // #[cfg(test)]
@@ -0,0 +1,257 @@
+use crate::ptt::ptt_parse_title;
+use deunicode::deunicode;
+use indexmap::IndexMap;
+use shared::concat_string;
+use shared::model::{EpisodeStreamProperties, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, SeriesStreamDetailEpisodeProperties,
+                    SeriesStreamDetailProperties, SeriesStreamDetailSeasonProperties, SeriesStreamProperties, StreamProperties, XtreamCluster};
+use shared::utils::{extract_extension_from_url, generate_playlist_uuid, Internable};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+struct SeriesEpisode {
+    season: u32,
+    episode: u32,
+    pli: PlaylistItem,
+}
+
+struct SyntheticSeries {
+    title: Arc<str>,
+    group: Arc<str>,
+    input_name: Arc<str>,
+    logo: Arc<str>,
+    year: Option<u32>,
+    episodes: Vec<SeriesEpisode>,
+}
+
+fn is_series_candidate(pli: &PlaylistItem) -> bool {
+    matches!(pli.header.item_type, PlaylistItemType::Live | PlaylistItemType::LiveUnknown | PlaylistItemType::LiveHls
+        | PlaylistItemType::LiveDash | PlaylistItemType::Video)
+}
+
+/// Groups the flat `S01E02` entries of the m3u inputs into series. Each series is a `SeriesInfo` with its seasons and episodes,
+/// followed by the episodes as `Series` items, like a resolved series of a xtream provider.
+/// The entries are grouped by input, group and the series title of the PTT parser, the series keep the group of their entries.
+pub(in crate::processing) fn process_series_grouping<F>(playlist: Vec<PlaylistGroup>, is_m3u_input: F) -> Vec<PlaylistGroup>
+where
+    F: Fn(&Arc<str>) -> bool,
+{
+    let mut series: IndexMap<(Arc<str>, Arc<str>, String), SyntheticSeries> = IndexMap::new();
+    let mut groups = Vec::with_capacity(playlist.len());
+    for mut group in playlist {
+        if group.xtream_cluster != XtreamCluster::Series {
+            let mut channels = Vec::with_capacity(group.channels.len());
+            for pli in group.channels.drain(..) {
+                if !is_series_candidate(&pli) || !is_m3u_input(&pli.header.input_name) {
+                    channels.push(pli);
+                    continue;
+                }
+                let header = &pli.header;
+                let metadata = ptt_parse_title(if header.title.is_empty() { &header.name } else { &header.title });
+                let (Some(season), Some(episode)) = (metadata.seasons.first().copied(), metadata.episodes.first().copied()) else {
+                    channels.push(pli);
+                    continue;
+                };
+                let title = metadata.title.trim();
+                if title.is_empty() {
+                    channels.push(pli);
+                    continue;
+                }
+                let key = (Arc::clone(&header.input_name), Arc::clone(&group.title), deunicode(title).to_lowercase());
+                let entry = series.entry(key).or_insert_with(|| SyntheticSeries {
+                    title: title.intern(),
+                    group: Arc::clone(&group.title),
+                    input_name: Arc::clone(&header.input_name),
+                    logo: Arc::clone(&header.logo),
+                    year: metadata.year,
+                    episodes: vec![],
+                });
+                if entry.logo.is_empty() {
+                    entry.logo = Arc::clone(&header.logo);
+                }
+                entry.year = entry.year.or(metadata.year);
+                entry.episodes.push(SeriesEpisode { season, episode, pli });
+            }
+            group.channels = channels;
+        }
+        if !group.channels.is_empty() {
+            groups.push(group);
+        }
+    }
+
+    let mut series_groups: IndexMap<Arc<str>, PlaylistGroup> = IndexMap::new();
+    for series in series.into_values() {
+        let group_title = Arc::clone(&series.group);
+        series_groups.entry(Arc::clone(&group_title))
+            .or_insert_with(|| PlaylistGroup {
+                id: 0,
+                title: group_title,
+                channels: vec![],
+                xtream_cluster: XtreamCluster::Series,
+            })
+            .channels.extend(create_series_items(series));
+    }
+    groups.extend(series_groups.into_values());
+    groups
+}
+
+fn container_extension(url: &str) -> Arc<str> {
+    extract_extension_from_url(url).map_or("", |ext| ext.trim_start_matches('.')).intern()
+}
+
+fn create_series_items(mut series: SyntheticSeries) -> Vec<PlaylistItem> {
+    series.episodes.sort_by_key(|episode| (episode.season, episode.episode));
+    let series_key = concat_string!(&series.group, "/", &deunicode(&series.title).to_lowercase());
+    let uuid = generate_playlist_uuid(&series.input_name, &series_key, PlaylistItemType::SeriesInfo, "");
+    let parent_code = (&uuid).intern();
+
+    let mut seasons: BTreeMap<u32, SeriesStreamDetailSeasonProperties> = BTreeMap::new();
+    let mut details_episodes = Vec::with_capacity(series.episodes.len());
+    let mut items = Vec::with_capacity(series.episodes.len() + 1);
+    for SeriesEpisode { season, episode, mut pli } in series.episodes {
+        let header = &mut pli.header;
+        let extension = container_extension(&header.url);
+        let logo = if header.logo.is_empty() { Arc::clone(&series.logo) } else { Arc::clone(&header.logo) };
+        let season_entry = seasons.entry(season).or_insert_with(|| SeriesStreamDetailSeasonProperties {
+            name: concat_string!(&series.title, " ", &season.to_string()).into(),
+            season_number: season,
+            episode_count: 0,
+            overview: None,
+            air_date: None,
+            cover: (!series.logo.is_empty()).then(|| Arc::clone(&series.logo)),
+            cover_tmdb: None,
+            cover_big: None,
+            duration: None,
+        });
+        season_entry.episode_count = season_entry.episode_count.saturating_add(1);
+        // the id is rewritten to the virtual id of the episode when the playlist is persisted
+        details_episodes.push(SeriesStreamDetailEpisodeProperties {
+            id: header.get_provider_id().unwrap_or_default(),
+            episode_num: episode,
+            season,
+            title: Arc::clone(&header.title),
+            container_extension: Arc::clone(&extension),
+            custom_sid: None,
+            added: "".intern(),
+            direct_source: Arc::clone(&header.url),
+            tmdb: None,
+            release_date: "".intern(),
+            plot: None,
+            crew: None,
+            duration_secs: 0,
+            duration: "".intern(),
+            movie_image: Arc::clone(&logo),
+            bitrate: 0,
+            rating: None,
+            video: None,
+            audio: None,
+        });
+
+        header.name = Arc::clone(&series.title);
+        header.logo = Arc::clone(&logo);
+        header.parent_code = Arc::clone(&parent_code);
+        header.item_type = PlaylistItemType::Series;
+        header.xtream_cluster = XtreamCluster::Series;
+        header.additional_properties = Some(StreamProperties::Episode(EpisodeStreamProperties {
+            episode_id: header.get_provider_id().unwrap_or_default(),
+            episode,
+            season,
+            added: None,
+            release_date: None,
+            tmdb: None,
+            movie_image: logo,
+            container_extension: extension,
+            video: None,
+            audio: None,
+        }));
+        items.push(pli);
+    }
+
+    let series_info = PlaylistItem {
+        header: PlaylistItemHeader {
+            uuid,
+            name: Arc::clone(&series.title),
+            title: Arc::clone(&series.title),
+            group: Arc::clone(&series.group),
+            logo: Arc::clone(&series.logo),
+            xtream_cluster: XtreamCluster::Series,
+            item_type: PlaylistItemType::SeriesInfo,
+            input_name: Arc::clone(&series.input_name),
+            additional_properties: Some(StreamProperties::Series(Box::new(SeriesStreamProperties {
+                name: Arc::clone(&series.title),
+                cover: Arc::clone(&series.logo),
+                release_date: series.year.map(|year| format!("{year}-01-01").into()),
+                details: Some(SeriesStreamDetailProperties {
+                    year: series.year,
+                    seasons: Some(seasons.into_values().collect()),
+                    episodes: Some(details_episodes),
+                }),
+                ..SeriesStreamProperties::default()
+            }))),
+            ..PlaylistItemHeader::default()
+        }
+    };
+    items.insert(0, series_info);
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::process_series_grouping;
+    use shared::model::{PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, StreamProperties, XtreamCluster};
+    use std::sync::Arc;
+
+    fn item(input_name: &str, title: &str, url: &str) -> PlaylistItem {
+        PlaylistItem {
+            header: PlaylistItemHeader {
+                title: title.into(),
+                name: title.into(),
+                group: "Shows".into(),
+                url: url.into(),
+                input_name: input_name.into(),
+                item_type: PlaylistItemType::Video,
+                xtream_cluster: XtreamCluster::Video,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_series_grouping() {
+        let playlist = vec![PlaylistGroup {
+            id: 1,
+            title: "Shows".into(),
+            channels: vec![
+                item("m3u", "Breaking Bad S01E02", "http://host/bb/s01e02.mkv"),
+                item("m3u", "The Movie 2020", "http://host/movie.mp4"),
+                item("m3u", "Breaking Bad S02E01", "http://host/bb/s02e01.mkv"),
+                item("m3u", "Breaking Bad S01E01", "http://host/bb/s01e01.mkv"),
+                item("xtream", "Dark S01E01", "http://host/dark/s01e01.mkv"),
+            ],
+            xtream_cluster: XtreamCluster::Video,
+        }];
+        let groups = process_series_grouping(playlist, |input_name: &Arc<str>| &**input_name == "m3u");
+        assert_eq!(groups.len(), 2);
+        let titles = |group: &PlaylistGroup| group.channels.iter().map(|pli| pli.header.title.to_string()).collect::<Vec<_>>();
+        assert_eq!(titles(&groups[0]), vec!["The Movie 2020", "Dark S01E01"]);
+
+        let series = &groups[1];
+        assert_eq!(series.xtream_cluster, XtreamCluster::Series);
+        assert_eq!(titles(series), vec!["Breaking Bad", "Breaking Bad S01E01", "Breaking Bad S01E02", "Breaking Bad S02E01"]);
+        let info = &series.channels[0].header;
+        assert_eq!(info.item_type, PlaylistItemType::SeriesInfo);
+        let Some(StreamProperties::Series(props)) = info.additional_properties.as_ref() else { panic!("series properties expected") };
+        let details = props.details.as_ref().unwrap();
+        assert_eq!(details.seasons.as_ref().unwrap().iter().map(|season| (season.season_number, season.episode_count)).collect::<Vec<_>>(), vec![(1, 2), (2, 1)]);
+        let episodes = details.episodes.as_ref().unwrap();
+        assert_eq!(episodes.iter().map(|episode| (episode.season, episode.episode_num)).collect::<Vec<_>>(), vec![(1, 1), (1, 2), (2, 1)]);
+        assert_eq!(&*episodes[0].direct_source, "http://host/bb/s01e01.mkv");
+        assert_eq!(&*episodes[0].container_extension, "mkv");
+
+        let parent_code: Arc<str> = info.uuid.to_string().into();
+        for episode in &series.channels[1..] {
+            assert_eq!(episode.header.item_type, PlaylistItemType::Series);
+            assert_eq!(episode.header.parent_code, parent_code);
+            assert_eq!(&*episode.header.name, "Breaking Bad");
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::model::{ConfigSortRule, ConfigTarget};
+use crate::processing::processor::collation::SortCollator;
 use shared::foundation::ValueProvider;
 use shared::model::{PlaylistGroup, SortOrder, SortTarget};
 use std::cmp::Ordering;
@@ -13,6 +14,7 @@ fn direction(order: SortOrder, ordering: Ordering) -> Ordering {
 }
 
 fn playlist_comparator(
+    collator: &SortCollator,
     sequence: Option<&Vec<Arc<regex::Regex>>>,
     order: SortOrder,
     value_a: &str,
@@ -66,7 +68,7 @@ fn playlist_comparator(
                     let va = caps_a.name(name).map(|m| m.as_str());
                     let vb = caps_b.name(name).map(|m| m.as_str());
                     let o = match (va, vb) {
-                        (Some(a), Some(b)) => a.cmp(b),
+                        (Some(a), Some(b)) => collator.compare(a, b),
                         (Some(_), None) => Ordering::Greater,
                         (None, Some(_)) => Ordering::Less,
                         _ => Ordering::Equal,
@@ -89,7 +91,7 @@ fn playlist_comparator(
         }
     } else {
         // No Regex-Sequence defined → fallback
-        let o = value_a.cmp(value_b);
+        let o = collator.compare(value_a, value_b);
         match order {
             SortOrder::Asc => o,
             SortOrder::Desc => o.reverse(),
@@ -108,8 +110,9 @@ pub(in crate::processing::processor) fn sort_playlist(
 
     let rules = &sort.rules;
     let match_as_ascii = sort.match_as_ascii;
-    sort_groups(playlist, rules, match_as_ascii);
-    sort_channels_in_groups(playlist, rules, match_as_ascii);
+    let collator = SortCollator::new(sort.natural, sort.locale.as_deref());
+    sort_groups(playlist, rules, match_as_ascii, &collator);
+    sort_channels_in_groups(playlist, rules, match_as_ascii, &collator);
 
     true
 }
@@ -118,6 +121,7 @@ fn sort_groups(
     groups: &mut [PlaylistGroup],
     rules: &[ConfigSortRule],
     match_as_ascii: bool,
+    collator: &SortCollator,
 ) {
     let group_rules: Vec<_> = rules
         .iter()
@@ -161,7 +165,7 @@ fn sort_groups(
                 (Some(_), None) => direction(rule.order, Ordering::Less),
                 (None, Some(_)) => direction(rule.order, Ordering::Greater),
                 (Some(va), Some(vb)) => {
-                    playlist_comparator(rule.sequence.as_ref(), rule.order, &va, &vb)
+                    playlist_comparator(collator, rule.sequence.as_ref(), rule.order, &va, &vb)
                 }
             };
 
@@ -178,6 +182,7 @@ fn sort_channels_in_groups(
     groups: &mut [PlaylistGroup],
     rules: &[ConfigSortRule],
     match_as_ascii: bool,
+    collator: &SortCollator,
 ) {
     let channel_rules: Vec<_> = rules
         .iter()
@@ -213,13 +218,13 @@ fn sort_channels_in_groups(
                     (Some(_), None) => direction(rule.order, Ordering::Less),
                     (None, Some(_)) => direction(rule.order, Ordering::Greater),
                     (Some(va), Some(vb)) => {
-                        playlist_comparator(rule.sequence.as_ref(), rule.order, va, vb)
+                        playlist_comparator(collator, rule.sequence.as_ref(), rule.order, va, vb)
                     }
                 };
 
                 if ord == Ordering::Equal {
                     if let (Some(va), Some(vb)) = (&va, &vb) {
-                        let fallback = direction(rule.order, va.cmp(vb));
+                        let fallback = direction(rule.order, collator.compare(va, vb));
                         if fallback != Ordering::Equal {
                             return fallback;
                         }
@@ -239,6 +244,7 @@ fn sort_channels_in_groups(
 #[cfg(test)]
 mod tests {
     use crate::model::ConfigSortRule;
+    use crate::processing::processor::collation::SortCollator;
     use crate::processing::processor::sort::playlist_comparator;
     use shared::foundation::Filter;
     use shared::model::{ItemField, PlaylistItem, PlaylistItemHeader, SortOrder, SortTarget};
@@ -289,7 +295,7 @@ mod tests {
             let vb = &b.header.title;
 
             let ord =
-                playlist_comparator(channel_sort.sequence.as_ref(), channel_sort.order, va, vb);
+                playlist_comparator(&SortCollator::new(false, None), channel_sort.sequence.as_ref(), channel_sort.order, va, vb);
 
             if ord != Ordering::Equal {
                 ord
@@ -379,6 +385,7 @@ mod tests {
 
         channels.sort_by(|a, b| {
             let ord = playlist_comparator(
+                &SortCollator::new(false, None),
                 channel_sort.sequence.as_ref(),
                 channel_sort.order,
                 &a.header.title,
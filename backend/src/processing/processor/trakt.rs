@@ -1,14 +1,27 @@
-use crate::model::{ConfigTarget, TraktListItem, TraktMatchItem};
+#[cfg(feature = "server")]
+use crate::api::model::AppState;
+#[cfg(feature = "server")]
+use crate::api::scheduler::{datetime_to_instant, next_schedule_run};
+use crate::model::{ConfigTarget, TraktListItem, TraktMatchItem, TraktMatchedIds};
 use crate::model::{TraktConfig, TraktListConfig, TraktMatchResult};
+use crate::repository::{ensure_target_storage_path, get_target_id_mapping, iter_raw_xtream_target_playlist,
+                        xtream_clear_cached_responses, xtream_write_cluster_playlist};
+#[cfg(feature = "server")]
+use crate::repository::load_target_into_memory_cache;
 use crate::utils::{extract_year_from_title, normalize_title_for_matching, TraktClient};
 use crate::utils::{trace_if_enabled, with};
-use log::{debug, info, trace, warn};
-use shared::error::TuliproxError;
-use shared::model::{FieldGetAccessor, FieldSetAccessor, PlaylistGroup, PlaylistItem, TraktContentType, XtreamCluster};
-use shared::utils::{Internable, CONSTANTS};
+use chrono_tz::Tz;
+use cron::Schedule;
+use log::{debug, error, info, trace, warn};
+use shared::error::{info_err, TuliproxError};
+use shared::model::{FieldGetAccessor, FieldSetAccessor, PlaylistGroup, PlaylistItem, TraktContentType, XtreamCluster, XtreamPlaylistItem};
+use shared::utils::{create_alias_uuid, Internable, CONSTANTS};
 use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::Arc;
 use strsim::normalized_levenshtein;
+use tokio_util::sync::CancellationToken;
 
 fn extract_quality(value: &str) -> Option<&str> {
     if let Some(caps) = CONSTANTS.re_quality.captures(value) {
@@ -162,7 +175,9 @@ fn create_category_from_matches<'a>(
                 }
             }
             header.group = group_title.clone();
+            // the copy needs its own uuid, otherwise it gets the virtual id of the original item
             header.gen_uuid();
+            header.uuid = create_alias_uuid(&header.uuid, &group_title);
             matched_items_by_cluster.entry(header.xtream_cluster).or_default().push(modified_item);
         });
     }
@@ -181,6 +196,7 @@ fn match_trakt_items_with_playlist<'a>(
     trakt_items: &'a [TraktListItem],
     playlist: &'a [PlaylistGroup],
     list_config: &'a TraktListConfig,
+    matched_ids: &mut TraktMatchedIds,
 ) -> Vec<PlaylistGroup> {
     let trakt_match_items: Vec<TraktMatchItem<'a>> = trakt_items
         .iter()
@@ -197,7 +213,8 @@ fn match_trakt_items_with_playlist<'a>(
                 let normalized_title = normalize_title_for_matching(&channel.header.title);
                 let channel_year = extract_year_from_title(&channel.header.title);
                 let channel_tmdb_id = channel.get_tmdb_id();
-                if let Some(matched) = find_best_match_for_item((channel, normalized_title, channel_year, channel_tmdb_id), &trakt_match_items, list_config) {
+                if let Some(matched) = find_best_match_for_item((channel, normalized_title.clone(), channel_year, channel_tmdb_id), &trakt_match_items, list_config) {
+                    matched_ids.insert(matched.trakt_item.content_type, normalized_title, matched.trakt_item.ids);
                     matches.push(matched);
                 }
             }
@@ -222,6 +239,7 @@ impl TraktCategoriesProcessor {
         playlist: &[PlaylistGroup],
         target: &ConfigTarget,
         trakt_config: &TraktConfig,
+        matched_ids: &mut TraktMatchedIds,
     ) -> Result<Option<Vec<PlaylistGroup>>, Vec<TuliproxError>> {
         if trakt_config.lists.is_empty() {
             debug!("No Trakt lists configured for target {}", target.name);
@@ -239,7 +257,7 @@ impl TraktCategoriesProcessor {
                 Ok(trakt_items) => {
                     debug!("Processing Trakt list {cache_key} with {} items", trakt_items.len());
 
-                    let categories = match_trakt_items_with_playlist(&trakt_items, playlist, list_config);
+                    let categories = match_trakt_items_with_playlist(&trakt_items, playlist, list_config, matched_ids);
                     for category in categories {
                         if !category.channels.is_empty() {
                             total_matches += category.channels.len();
@@ -266,6 +284,7 @@ pub async fn process_trakt_categories_for_target(
     http_client: &reqwest::Client,
     playlist: &[PlaylistGroup],
     target: &ConfigTarget,
+    matched_ids: &mut TraktMatchedIds,
 ) -> Result<Option<Vec<PlaylistGroup>>, Vec<TuliproxError>> {
     let Some(trakt_config) = target.get_xtream_output().and_then(|output| output.trakt.as_ref()) else {
         trace!("No Trakt configuration found for target {}", target.name);
@@ -276,9 +295,176 @@ pub async fn process_trakt_categories_for_target(
     }
 
     let processor = TraktCategoriesProcessor::new(http_client, trakt_config);
-    processor.process_trakt_categories(playlist, target, trakt_config).await
+    processor.process_trakt_categories(playlist, target, trakt_config, matched_ids).await
 }
 
+#[cfg(feature = "server")]
+/// Starts a refresh loop for each target whose trakt config has its own `schedule`.
+///
+/// A scheduled refresh fetches only the trakt lists and patches the trakt categories of the stored
+/// xtream playlist, the playlist is not processed again.
+pub fn exec_trakt_scheduler(client: &reqwest::Client, app_state: &Arc<AppState>, cancel: &CancellationToken) {
+    let sources = app_state.app_config.sources.load();
+    for (target_name, expression, timezone) in sources.get_trakt_schedules() {
+        match Schedule::from_str(expression) {
+            Ok(schedule) => {
+                let target_name = target_name.to_string();
+                let app_state_clone = Arc::clone(app_state);
+                let http_client = client.clone();
+                let cancel_token = cancel.clone();
+                tokio::spawn(async move {
+                    start_trakt_scheduler(http_client, schedule, timezone, app_state_clone, target_name, cancel_token).await;
+                });
+            }
+            Err(err) => error!("Invalid trakt schedule '{expression}' for target {target_name}: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+async fn start_trakt_scheduler(client: reqwest::Client, schedule: Schedule, timezone: Option<Tz>, app_state: Arc<AppState>,
+                               target_name: String, cancel: CancellationToken) {
+    while let Some(datetime) = next_schedule_run(&schedule, timezone) {
+        tokio::select! {
+            () = tokio::time::sleep_until(tokio::time::Instant::from(datetime_to_instant(datetime))) => {
+                refresh_trakt_categories(&client, &app_state, &target_name).await;
+            }
+            () = cancel.cancelled() => {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+async fn refresh_trakt_categories(client: &reqwest::Client, app_state: &Arc<AppState>, target_name: &str) {
+    let Some(_permit) = app_state.update_guard.try_playlist() else {
+        warn!("Playlist update in progress; trakt refresh for target {target_name} skipped.");
+        return;
+    };
+    let sources = app_state.app_config.sources.load();
+    let Some(target) = sources.sources.iter()
+        .flat_map(|source| source.targets.iter())
+        .find(|target| target.name == target_name) else {
+        return;
+    };
+    let Some(trakt_config) = target.get_xtream_output().and_then(|output| output.trakt.as_ref()).filter(|trakt| trakt.enabled) else {
+        return;
+    };
+    match patch_trakt_categories(client, app_state, target, trakt_config).await {
+        Ok(None) => info!("Trakt categories of target {target_name} are up to date"),
+        Ok(Some((added, removed))) => {
+            let config = app_state.app_config.config.load();
+            xtream_clear_cached_responses(&config, target_name).await;
+            app_state.playlists.query_cache.invalidate(target_name);
+            load_target_into_memory_cache(app_state, target).await;
+            info!("Trakt categories of target {target_name} refreshed: {added} added, {removed} removed");
+        }
+        Err(err) => error!("Failed to refresh trakt categories of target {target_name}: {err}"),
+    }
+}
+
+/// Returns the number of added and removed items of the trakt categories, `None` if nothing changed.
+fn trakt_categories_delta(old_ids: &[u32], new_ids: &[u32]) -> Option<(usize, usize)> {
+    if old_ids == new_ids {
+        return None;
+    }
+    let old_set: HashSet<&u32> = old_ids.iter().collect();
+    let new_set: HashSet<&u32> = new_ids.iter().collect();
+    Some((new_set.difference(&old_set).count(), old_set.difference(&new_set).count()))
+}
+
+#[cfg(feature = "server")]
+/// Matches the trakt lists against the stored xtream playlist and rewrites the clusters whose trakt categories changed.
+/// A list which can't be fetched keeps its category.
+async fn patch_trakt_categories(client: &reqwest::Client, app_state: &Arc<AppState>, target: &ConfigTarget,
+                                trakt_config: &TraktConfig) -> Result<Option<(usize, usize)>, TuliproxError> {
+    let app_config = &app_state.app_config;
+    let category_names: HashSet<&str> = trakt_config.lists.iter().map(|list| list.category_name.as_str()).collect();
+
+    let mut playlist = Vec::new();
+    let mut clusters = Vec::new();
+    for cluster in [XtreamCluster::Video, XtreamCluster::Series] {
+        let Some(items) = iter_raw_xtream_target_playlist(app_config, target, cluster).await
+            .map(|(_file_lock, iter)| iter.collect::<Vec<XtreamPlaylistItem>>()) else { continue; };
+        let (trakt_items, items): (Vec<XtreamPlaylistItem>, Vec<XtreamPlaylistItem>) = items.into_iter()
+            .partition(|item| category_names.contains(&*item.group));
+        playlist.push(PlaylistGroup {
+            id: 0,
+            title: "".intern(),
+            channels: items.iter().map(PlaylistItem::from).collect(),
+            xtream_cluster: cluster,
+        });
+        clusters.push((cluster, items, trakt_items));
+    }
+    if clusters.is_empty() {
+        return Err(info_err!("No xtream playlist stored for target {}", target.name));
+    }
+
+    let processor = TraktCategoriesProcessor::new(client, trakt_config);
+    let mut matched_ids = TraktMatchedIds::default();
+    let mut list_categories = Vec::with_capacity(trakt_config.lists.len());
+    for list_config in &trakt_config.lists {
+        match processor.client.get_list_items(list_config).await {
+            Ok(trakt_items) => list_categories.push((list_config, Some(match_trakt_items_with_playlist(&trakt_items, &playlist, list_config, &mut matched_ids)))),
+            Err(err) => {
+                warn!("Failed to fetch Trakt list {}:{}: {}", list_config.user, list_config.list_slug, err.message);
+                list_categories.push((list_config, None));
+            }
+        }
+    }
+
+    let config = app_config.config.load();
+    let target_path = ensure_target_storage_path(&config, &target.name)?;
+    let (mut target_id_mapping, file_lock) = get_target_id_mapping(app_config, &target_path, target.use_memory_cache).await?;
+    let mut new_trakt_items: HashMap<XtreamCluster, Vec<XtreamPlaylistItem>> = HashMap::new();
+    for (list_config, categories) in list_categories {
+        if let Some(categories) = categories {
+            for category in categories {
+                let items = new_trakt_items.entry(category.xtream_cluster).or_default();
+                for mut item in category.channels {
+                    let header = &mut item.header;
+                    let provider_id = header.get_provider_id().unwrap_or_default();
+                    header.virtual_id = target_id_mapping.get_and_update_virtual_id(&header.uuid, provider_id, header.item_type, 0);
+                    items.push(XtreamPlaylistItem::from(&item));
+                }
+            }
+        } else {
+            for (cluster, _, trakt_items) in &clusters {
+                new_trakt_items.entry(*cluster).or_default()
+                    .extend(trakt_items.iter().filter(|item| &*item.group == list_config.category_name.as_str()).cloned());
+            }
+        }
+    }
+
+    let (mut added, mut removed, mut changed) = (0, 0, false);
+    for (cluster, mut items, trakt_items) in clusters {
+        let mut new_items = new_trakt_items.remove(&cluster).unwrap_or_default();
+        let old_ids: Vec<u32> = trakt_items.iter().map(|item| item.virtual_id).collect();
+        let new_ids: Vec<u32> = new_items.iter().map(|item| item.virtual_id).collect();
+        let Some((cluster_added, cluster_removed)) = trakt_categories_delta(&old_ids, &new_ids) else { continue; };
+        debug!("Trakt categories of target {} cluster {cluster}: {cluster_added} added, {cluster_removed} removed", target.name);
+        // the trakt categories follow the playlist like after a playlist update
+        let mut source_ordinal = items.iter().map(|item| item.source_ordinal).max().unwrap_or_default();
+        for item in &mut new_items {
+            source_ordinal += 1;
+            item.source_ordinal = source_ordinal;
+        }
+        items.extend(new_items);
+        xtream_write_cluster_playlist(app_config, target, cluster, items).await?;
+        added += cluster_added;
+        removed += cluster_removed;
+        changed = true;
+    }
+
+    if changed {
+        target_id_mapping.persist().map_err(|err| info_err!("{err}"))?;
+    }
+    // the mapping lock must be released before the memory cache is reloaded
+    drop(target_id_mapping);
+    drop(file_lock);
+    Ok(changed.then_some((added, removed)))
+}
 
 #[cfg(test)]
 mod tests {
@@ -290,4 +476,12 @@ mod tests {
         assert!(quality.is_some());
         assert_eq!("UHD", quality.unwrap());
     }
+
+    #[test]
+    fn test_trakt_categories_delta() {
+        assert_eq!(trakt_categories_delta(&[1, 2, 3], &[1, 2, 3]), None);
+        assert_eq!(trakt_categories_delta(&[1, 2, 3], &[3, 2, 1]), Some((0, 0)));
+        assert_eq!(trakt_categories_delta(&[1, 2, 3], &[2, 3, 4, 5]), Some((2, 1)));
+        assert_eq!(trakt_categories_delta(&[], &[7]), Some((1, 0)));
+    }
 }
\ No newline at end of file
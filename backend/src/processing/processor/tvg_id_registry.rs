@@ -0,0 +1,224 @@
+use crate::model::EpgTvgIdNormalizationConfig;
+use crate::utils::make_absolute_path;
+use shared::error::{info_err, TuliproxError};
+use shared::utils::{deunicode_string, Internable};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// Quality tokens at the end of a channel name are not part of the channel identity.
+const QUALITY_TOKENS: &[&str] = &["uhd", "fhd", "hd", "sd", "4k", "hevc", "raw"];
+
+#[derive(serde::Deserialize)]
+struct RegistryChannel {
+    id: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    alt_names: Vec<String>,
+    #[serde(default)]
+    country: String,
+}
+
+/// Key of a channel name, lowercase alphanumeric without quality suffixes: `CNN International HD` is `cnninternational`.
+fn name_key(name: &str) -> String {
+    let normalized = deunicode_string(name).to_lowercase();
+    let mut tokens: Vec<&str> = normalized.split(|c: char| !c.is_ascii_alphanumeric()).filter(|t| !t.is_empty()).collect();
+    while tokens.len() > 1 && tokens.last().is_some_and(|t| QUALITY_TOKENS.contains(t)) {
+        tokens.pop();
+    }
+    tokens.concat()
+}
+
+/// Splits a tvg-id like `cnn.us@east` into the name part, the country suffix and the feed.
+fn split_tvg_id(id: &str) -> (&str, Option<&str>, Option<&str>) {
+    let (id, feed) = match id.split_once('@') {
+        Some((id, feed)) => (id, Some(feed).filter(|f| !f.is_empty())),
+        None => (id, None),
+    };
+    match id.rsplit_once('.') {
+        Some((name, country)) if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic())
+            && !QUALITY_TOKENS.contains(&country) => (name, Some(country), feed),
+        _ => (id, None, feed),
+    }
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Parses the channels csv of the iptv-org database, the columns are found by the header line.
+fn parse_registry_csv(content: &str) -> Vec<RegistryChannel> {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    let Some(header) = lines.next().map(split_csv_line) else {
+        return vec![];
+    };
+    let column = |name: &str| header.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+    let Some(id_col) = column("id") else {
+        return vec![];
+    };
+    let (name_col, alt_names_col, country_col) = (column("name"), column("alt_names"), column("country"));
+    lines.filter_map(|line| {
+        let fields = split_csv_line(line);
+        let field = |col: Option<usize>| col.and_then(|c| fields.get(c)).map(|f| f.trim().to_string()).unwrap_or_default();
+        let id = field(Some(id_col));
+        (!id.is_empty()).then(|| RegistryChannel {
+            name: field(name_col),
+            alt_names: field(alt_names_col).split(';').map(str::trim).filter(|n| !n.is_empty()).map(ToString::to_string).collect(),
+            country: field(country_col),
+            id,
+        })
+    }).collect()
+}
+
+fn parse_registry(content: &str) -> Result<Vec<RegistryChannel>, TuliproxError> {
+    if content.trim_start().starts_with('[') {
+        serde_json::from_str(content).map_err(|err| info_err!("Failed to parse tvg-id registry: {err}"))
+    } else {
+        Ok(parse_registry_csv(content))
+    }
+}
+
+/// Canonical tvg-ids looked up by the provider tvg-id, the channel name and the country.
+/// The canonical ids are lowercase like the ids of the epg files.
+#[derive(Default)]
+pub struct TvgIdRegistry {
+    mapping: HashMap<String, Arc<str>>,
+    ids: HashMap<String, Arc<str>>,
+    names: HashMap<(String, String), Arc<str>>,
+    // None if the name exists in more than one country
+    unique_names: HashMap<String, Option<Arc<str>>>,
+    country: Option<String>,
+}
+
+impl TvgIdRegistry {
+    fn new(config: &EpgTvgIdNormalizationConfig, channels: Vec<RegistryChannel>) -> Self {
+        let mut registry = Self {
+            mapping: config.mapping.iter().map(|(id, canonical)| (id.clone(), canonical.intern())).collect(),
+            country: config.country.clone(),
+            ..Self::default()
+        };
+        for channel in channels {
+            let canonical = channel.id.to_lowercase().intern();
+            let (id_name, id_country, _) = split_tvg_id(&canonical);
+            let country = if channel.country.is_empty() { id_country.unwrap_or_default().to_string() } else { channel.country.to_lowercase() };
+            for name in std::iter::once(id_name).chain(std::iter::once(channel.name.as_str())).chain(channel.alt_names.iter().map(String::as_str)) {
+                let key = name_key(name);
+                if key.is_empty() {
+                    continue;
+                }
+                registry.names.entry((key.clone(), country.clone())).or_insert_with(|| Arc::clone(&canonical));
+                registry.unique_names.entry(key)
+                    .and_modify(|entry| if entry.as_ref().is_some_and(|id| id != &canonical) { *entry = None; })
+                    .or_insert_with(|| Some(Arc::clone(&canonical)));
+            }
+            registry.ids.insert(canonical.to_string(), canonical);
+        }
+        registry
+    }
+
+    pub async fn load(config: &EpgTvgIdNormalizationConfig, working_dir: &str) -> Result<Self, TuliproxError> {
+        let channels = match config.registry.as_deref() {
+            Some(registry) => {
+                let path = make_absolute_path(registry, working_dir);
+                let content = tokio::fs::read_to_string(&path).await
+                    .map_err(|err| info_err!("Failed to read tvg-id registry {path}: {err}"))?;
+                parse_registry(&content)?
+            }
+            None => vec![],
+        };
+        Ok(Self::new(config, channels))
+    }
+
+    /// Returns the canonical id of the tvg-id, `None` if the tvg-id is unknown.
+    pub fn normalize(&self, tvg_id: &str) -> Option<Arc<str>> {
+        let tvg_id = tvg_id.trim().to_lowercase();
+        if tvg_id.is_empty() {
+            return None;
+        }
+        if let Some(canonical) = self.mapping.get(&tvg_id).or_else(|| self.ids.get(&tvg_id)) {
+            return Some(Arc::clone(canonical));
+        }
+        let (name, country, feed) = split_tvg_id(&tvg_id);
+        let key = name_key(name);
+        let canonical = match country.or(self.country.as_deref()) {
+            Some(country) => self.names.get(&(key, country.to_string())),
+            None => self.unique_names.get(&key).and_then(Option::as_ref),
+        }?;
+        Some(match feed {
+            Some(feed) => format!("{canonical}@{feed}").intern(),
+            None => Arc::clone(canonical),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{name_key, parse_registry, TvgIdRegistry};
+    use crate::model::EpgTvgIdNormalizationConfig;
+    use std::collections::BTreeMap;
+
+    const REGISTRY_CSV: &str = "id,name,alt_names,network,owners,country\n\
+        CNN.us,CNN,Cable News Network,,\"Warner Bros. Discovery, Inc.\",US\n\
+        CNNInternational.uk,CNN International,CNN Int,,,UK\n\
+        DasErste.de,Das Erste,ARD;ARD Das Erste,ARD,,DE\n\
+        Sport1.de,Sport1,,,,DE\n\
+        Sport1.hu,Sport1,,,,HU\n";
+
+    fn create_registry(country: Option<&str>) -> TvgIdRegistry {
+        let config = EpgTvgIdNormalizationConfig {
+            enabled: true,
+            country: country.map(ToString::to_string),
+            registry: None,
+            mapping: BTreeMap::from([("ard1".to_string(), "daserste.de".to_string())]),
+        };
+        TvgIdRegistry::new(&config, parse_registry(REGISTRY_CSV).unwrap())
+    }
+
+    #[test]
+    fn test_name_key() {
+        assert_eq!(name_key("CNN International HD"), "cnninternational");
+        assert_eq!(name_key("Das Erste"), "daserste");
+        assert_eq!(name_key("HD"), "hd");
+    }
+
+    #[test]
+    fn test_normalize_tvg_id() {
+        let registry = create_registry(Some("de"));
+        assert_eq!(registry.normalize("CNN.us").as_deref(), Some("cnn.us"));
+        assert_eq!(registry.normalize("cable.news.network.us").as_deref(), Some("cnn.us"));
+        assert_eq!(registry.normalize("cnn-int.uk").as_deref(), Some("cnninternational.uk"));
+        assert_eq!(registry.normalize("ard").as_deref(), Some("daserste.de"));
+        assert_eq!(registry.normalize("ARD1").as_deref(), Some("daserste.de"));
+        assert_eq!(registry.normalize("das.erste.hd@sd").as_deref(), Some("daserste.de@sd"));
+        assert_eq!(registry.normalize("sport1").as_deref(), Some("sport1.de"));
+        assert_eq!(registry.normalize("unknown.us"), None);
+
+        let registry = create_registry(None);
+        assert_eq!(registry.normalize("cnn").as_deref(), Some("cnn.us"));
+        assert_eq!(registry.normalize("sport1"), None);
+        assert_eq!(registry.normalize("sport1.hu").as_deref(), Some("sport1.hu"));
+    }
+
+    #[test]
+    fn test_parse_registry_json() {
+        let channels = parse_registry(r#"[{"id":"CNN.us","name":"CNN","alt_names":["Cable News Network"],"country":"US","is_nsfw":false}]"#).unwrap();
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].alt_names, vec!["Cable News Network".to_string()]);
+    }
+}
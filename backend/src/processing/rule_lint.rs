@@ -0,0 +1,175 @@
+use crate::model::{AppConfig, ConfigTarget, SourcesConfig, TargetOutput};
+use crate::repository::for_each_stored_input_item;
+use crate::utils::read_raw_mappings_file;
+use log::warn;
+use shared::foundation::{find_duplicate_renames, find_unreachable_branches, find_unused_templates, Filter, PlaylistRuleUsage, RenameRule, ValueProvider};
+use shared::model::{MappingsDto, PlaylistItem, ProcessingOrder, RuleLintKind, RuleLintWarningDto, SourcesConfigDto};
+
+const SCOPE_TEMPLATES: &str = "templates";
+const SCOPE_MAPPING_TEMPLATES: &str = "mapping templates";
+
+fn push_warnings(warnings: &mut Vec<RuleLintWarningDto>, kind: RuleLintKind, scope: &str, context: &str, messages: Vec<String>) {
+    warnings.extend(messages.into_iter().map(|message| {
+        let message = if context.is_empty() { message } else { format!("{context}: {message}") };
+        RuleLintWarningDto::new(kind, scope, message)
+    }));
+}
+
+fn push_unused_templates(warnings: &mut Vec<RuleLintWarningDto>, scope: &str, templates: &[shared::model::PatternTemplate], usages: &[&str]) {
+    for name in find_unused_templates(templates, usages) {
+        warnings.push(RuleLintWarningDto::new(RuleLintKind::UnusedTemplate, scope, format!("template `{name}` is never used")));
+    }
+}
+
+/// The templates are checked on the raw dto, the prepared config has the template references replaced.
+fn lint_source_templates(raw_sources: &SourcesConfigDto, warnings: &mut Vec<RuleLintWarningDto>) {
+    let Some(templates) = raw_sources.templates.as_ref() else { return; };
+    let mut usages: Vec<&str> = Vec::new();
+    for target in raw_sources.sources.iter().flat_map(|source| source.targets.iter()) {
+        usages.push(&target.filter);
+        for output in &target.output {
+            let filter = match output {
+                shared::model::TargetOutputDto::Xtream(output) => output.filter.as_deref(),
+                shared::model::TargetOutputDto::M3u(output) => output.filter.as_deref(),
+                shared::model::TargetOutputDto::Strm(output) => output.filter.as_deref(),
+                shared::model::TargetOutputDto::HdHomeRun(_) | shared::model::TargetOutputDto::Plex(_) => None,
+            };
+            usages.extend(filter);
+        }
+        for rename in target.rename.iter().flatten() {
+            usages.push(&rename.pattern);
+        }
+        for favourite in target.favourites.iter().flatten() {
+            usages.push(&favourite.filter);
+        }
+        for rule in target.sort.iter().flat_map(|sort| sort.rules.iter()) {
+            usages.push(&rule.filter);
+            usages.extend(rule.sequence.iter().flatten().map(String::as_str));
+        }
+    }
+    push_unused_templates(warnings, SCOPE_TEMPLATES, templates, &usages);
+}
+
+fn lint_filter(warnings: &mut Vec<RuleLintWarningDto>, scope: &str, context: &str, filter: &Filter) {
+    push_warnings(warnings, RuleLintKind::UnreachableBranch, scope, context, find_unreachable_branches(filter));
+}
+
+fn get_rename_rules(target: &ConfigTarget) -> Vec<RenameRule<'_>> {
+    target.rename.iter().flatten()
+        .map(|rename| RenameRule { field: rename.field, pattern: &rename.pattern, new_name: &rename.new_name })
+        .collect()
+}
+
+fn lint_target_rules(target: &ConfigTarget, warnings: &mut Vec<RuleLintWarningDto>) {
+    let scope = format!("target {}", target.name);
+    lint_filter(warnings, &scope, "filter", &target.filter);
+    for output in &target.output {
+        let (output_type, filter) = match output {
+            TargetOutput::Xtream(output) => ("xtream", output.filter.as_ref()),
+            TargetOutput::M3u(output) => ("m3u", output.filter.as_ref()),
+            TargetOutput::Strm(output) => ("strm", output.filter.as_ref()),
+            TargetOutput::HdHomeRun(_) | TargetOutput::Plex(_) => continue,
+        };
+        if let Some(filter) = filter {
+            lint_filter(warnings, &scope, &format!("{output_type} output filter"), filter);
+        }
+    }
+    for favourite in target.favourites.iter().flatten() {
+        lint_filter(warnings, &scope, &format!("favourites {}", favourite.group), &favourite.filter);
+    }
+    for rule in target.sort.iter().flat_map(|sort| sort.rules.iter()) {
+        lint_filter(warnings, &scope, "sort rule filter", &rule.filter);
+    }
+    push_warnings(warnings, RuleLintKind::ShadowedRename, &scope, "", find_duplicate_renames(&get_rename_rules(target)));
+}
+
+/// The filter regexes are checked when the filter sees the input playlist,
+/// the renames when they run before the mapping.
+async fn lint_target_playlist(app_config: &AppConfig, sources: &SourcesConfig, inputs: &[std::sync::Arc<str>], target: &ConfigTarget,
+                              warnings: &mut Vec<RuleLintWarningDto>) {
+    let check_filter = matches!(target.processing_order, ProcessingOrder::Frm | ProcessingOrder::Fmr);
+    let check_renames = matches!(target.processing_order, ProcessingOrder::Frm | ProcessingOrder::Rfm | ProcessingOrder::Rmf);
+    let mut filter_usage = PlaylistRuleUsage::new(check_filter.then_some(&target.filter), vec![]);
+    let mut rename_usage = PlaylistRuleUsage::new(None, if check_renames { get_rename_rules(target) } else { vec![] });
+    if filter_usage.is_empty() && rename_usage.is_empty() {
+        return;
+    }
+
+    let filter_before_rename = matches!(target.processing_order, ProcessingOrder::Frm);
+    let mut item_count = 0usize;
+    let mut add_item = |pli: &PlaylistItem| {
+        item_count += 1;
+        filter_usage.add_item(pli);
+        if !filter_before_rename || target.filter(&ValueProvider { pli, match_as_ascii: false }) {
+            rename_usage.add_item(pli);
+        }
+    };
+    for input in inputs.iter().filter_map(|name| sources.get_input_by_name(name)) {
+        for_each_stored_input_item(app_config, input, &mut add_item).await;
+    }
+
+    // Without a processed playlist there is nothing to match against.
+    if item_count > 0 {
+        let scope = format!("target {}", target.name);
+        push_warnings(warnings, RuleLintKind::UnmatchedRegex, &scope, "filter", filter_usage.unmatched());
+        push_warnings(warnings, RuleLintKind::UnmatchedRegex, &scope, "", rename_usage.unmatched());
+        push_warnings(warnings, RuleLintKind::ShadowedRename, &scope, "", rename_usage.shadowed());
+    }
+}
+
+fn lint_mappings(raw_mappings: &MappingsDto, warnings: &mut Vec<RuleLintWarningDto>) {
+    if let Some(templates) = raw_mappings.mappings.templates.as_ref() {
+        let mut usages: Vec<&str> = Vec::new();
+        for mapping in &raw_mappings.mappings.mapping {
+            for mapper in mapping.mapper.iter().flatten() {
+                usages.push(&mapper.filter);
+                usages.push(&mapper.script);
+            }
+            usages.extend(mapping.counter.iter().flatten().map(|counter| counter.filter.as_str()));
+        }
+        push_unused_templates(warnings, SCOPE_MAPPING_TEMPLATES, templates, &usages);
+    }
+
+    let mut mappings = raw_mappings.clone();
+    if let Err(err) = mappings.prepare() {
+        warn!("Failed to prepare mappings for the rule lint: {err}");
+        return;
+    }
+    for mapping in &mappings.mappings.mapping {
+        let scope = format!("mapping {}", mapping.id);
+        for (index, mapper) in mapping.mapper.iter().flatten().enumerate() {
+            if let Some(filter) = mapper.t_filter.as_ref() {
+                lint_filter(warnings, &scope, &format!("mapper #{}", index + 1), filter);
+            }
+        }
+        for (index, counter) in mapping.t_counter.iter().flatten().enumerate() {
+            lint_filter(warnings, &scope, &format!("counter #{}", index + 1), &counter.filter);
+        }
+    }
+}
+
+/// Checks the rules of the saved sources and the mappings for filter branches which can't match,
+/// regexes and renames without effect on the last processed input playlists and unused templates.
+/// `raw_sources` is the sources dto before `prepare`, it still has the template references.
+pub async fn lint_rules(app_config: &AppConfig, raw_sources: &SourcesConfigDto) -> Vec<RuleLintWarningDto> {
+    let mut warnings = Vec::new();
+    lint_source_templates(raw_sources, &mut warnings);
+
+    let sources = app_config.sources.load();
+    for source in &sources.sources {
+        for target in source.targets.iter().filter(|target| target.enabled) {
+            lint_target_rules(target, &mut warnings);
+            lint_target_playlist(app_config, &sources, &source.inputs, target, &mut warnings).await;
+        }
+    }
+
+    let mapping_file_path = app_config.paths.load().mapping_file_path.clone();
+    if let Some(mapping_file_path) = mapping_file_path {
+        match read_raw_mappings_file(&mapping_file_path, true) {
+            Ok(Some(raw_mappings)) => lint_mappings(&raw_mappings, &mut warnings),
+            Ok(None) => {}
+            Err(err) => warn!("Failed to read mappings for the rule lint: {err}"),
+        }
+    }
+    warnings
+}
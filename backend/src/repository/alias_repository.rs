@@ -172,6 +172,7 @@ pub fn csv_read_inputs_from_reader(
         InputType::M3uBatch | InputType::M3u => InputType::M3uBatch,
         InputType::XtreamBatch | InputType::Xtream => InputType::XtreamBatch,
         InputType::Library => InputType::Library,
+        InputType::Udp => InputType::Udp,
     };
     let mut result = vec![];
     let mut default_columns = vec![];
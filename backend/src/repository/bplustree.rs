@@ -1,4 +1,5 @@
 use crate::repository::storage::get_file_path_for_db_index;
+use crate::repository::REPOSITORY_METRICS;
 use crate::utils;
 use crate::utils::{binary_deserialize, binary_serialize};
 use fs2::FileExt as _;
@@ -15,6 +16,8 @@ use std::mem::size_of;
 #[cfg(unix)]
 use std::os::unix::fs::FileExt;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 use tempfile::NamedTempFile;
 
 // Constants (Restored)
@@ -23,7 +26,9 @@ pub const PAGE_SIZE_USIZE: usize = PAGE_SIZE as usize;
 const LEN_SIZE: usize = 4;
 const FLAG_SIZE: usize = 1;
 const MAGIC: &[u8; 4] = b"BTRE";
-const STORAGE_VERSION: u32 = 1;
+// Version 2 appends a checksum to the nodes, version 1 files are still readable.
+const STORAGE_VERSION: u32 = 2;
+const MIN_STORAGE_VERSION: u32 = 1;
 const HEADER_SIZE: u64 = PAGE_SIZE as u64;
 const ROOT_OFFSET_POS: u64 = 8;
 const METADATA_OFFSET_POS: u64 = 16;
@@ -57,6 +62,48 @@ const SLOT_SIZE: usize = 2; // u16
 
 const MAGIC_METADATA_TARGET_ID_MAPPING: u8 = 0x01;
 
+// Node flag byte, the checksum bit marks a crc32 of the node content appended after the payload
+const NODE_FLAG_LEAF: u8 = 0x01;
+const NODE_FLAG_CHECKSUM: u8 = 0x80;
+const CHECKSUM_SIZE: usize = 4;
+
+static VERIFY_CHECKSUMS: AtomicBool = AtomicBool::new(false);
+
+/// Enables the verification of the node checksums on read.
+/// Nodes written by older versions have no checksum and are not verified.
+pub fn set_verify_storage_checksums(verify: bool) {
+    VERIFY_CHECKSUMS.store(verify, Ordering::Relaxed);
+}
+
+const fn node_flag(is_leaf: bool) -> u8 {
+    if is_leaf { NODE_FLAG_CHECKSUM | NODE_FLAG_LEAF } else { NODE_FLAG_CHECKSUM }
+}
+
+const fn node_checksum_size(flag: u8) -> usize {
+    if flag & NODE_FLAG_CHECKSUM == 0 { 0 } else { CHECKSUM_SIZE }
+}
+
+/// `node` is the node content followed by the stored checksum.
+fn verify_node_checksum(node: &[u8], offset: u64) -> io::Result<()> {
+    if !VERIFY_CHECKSUMS.load(Ordering::Relaxed) || node.len() < CHECKSUM_SIZE {
+        return Ok(());
+    }
+    let (content, stored) = node.split_at(node.len() - CHECKSUM_SIZE);
+    if crc32fast::hash(content) != u32_from_bytes(stored)? {
+        REPOSITORY_METRICS.checksum_errors.inc();
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Node checksum mismatch at offset {offset}")));
+    }
+    Ok(())
+}
+
+fn read_storage_version(header: &[u8]) -> io::Result<u32> {
+    let version = u32::from_le_bytes(header[4..8].try_into().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid version slice"))?);
+    if !(MIN_STORAGE_VERSION..=STORAGE_VERSION).contains(&version) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unsupported storage version: {version}")));
+    }
+    Ok(version)
+}
+
 /*
     Page Header Layout
 
@@ -723,8 +770,8 @@ where
 
     /// Calculate the serialized size of this node in bytes (rounded up to block size)
     fn calculate_serialized_size(&self) -> io::Result<u64> {
-        // Header: is_leaf flag
-        let mut size = FLAG_SIZE;
+        // Header: node flag and trailing checksum
+        let mut size = FLAG_SIZE + CHECKSUM_SIZE;
 
         // Keys: length + serialized data
         let keys_encoded = binary_serialize(&self.keys)?;
@@ -762,7 +809,7 @@ where
             let info_encoded = binary_serialize(&self.value_info)?;
             let info_len = u32::try_from(info_encoded.len()).map_err(to_io_error)?;
 
-            let content_size = FLAG_SIZE + LEN_SIZE + keys_encoded.len() + LEN_SIZE + info_encoded.len();
+            let content_size = FLAG_SIZE + LEN_SIZE + keys_encoded.len() + LEN_SIZE + info_encoded.len() + CHECKSUM_SIZE;
             let blocks = content_size.div_ceil(PAGE_SIZE_USIZE);
 
             file.seek(SeekFrom::Start(offset))?;
@@ -774,7 +821,7 @@ where
             buffer[..capacity].fill(0);
 
             let mut pos = 0;
-            buffer[pos] = 1u8;
+            buffer[pos] = node_flag(true);
             pos += FLAG_SIZE;
 
             buffer[pos..pos + LEN_SIZE].copy_from_slice(&keys_len.to_le_bytes());
@@ -787,6 +834,10 @@ where
             pos += LEN_SIZE;
 
             buffer[pos..pos + info_encoded.len()].copy_from_slice(&info_encoded);
+            pos += info_encoded.len();
+
+            let checksum = crc32fast::hash(&buffer[..pos]);
+            buffer[pos..pos + CHECKSUM_SIZE].copy_from_slice(&checksum.to_le_bytes());
 
             file.write_all(buffer)?;
 
@@ -795,7 +846,7 @@ where
             let ptr_count = self.children.len();
             let ptr_encoded_size = 8 + 8 * ptr_count;
 
-            let content_size = FLAG_SIZE + LEN_SIZE + keys_encoded.len() + LEN_SIZE + ptr_encoded_size;
+            let content_size = FLAG_SIZE + LEN_SIZE + keys_encoded.len() + LEN_SIZE + ptr_encoded_size + CHECKSUM_SIZE;
             let blocks_needed = content_size.div_ceil(PAGE_SIZE_USIZE);
 
             let parent_start = offset;
@@ -812,11 +863,13 @@ where
 
             file.seek(SeekFrom::Start(parent_start))?;
             let mut data = Vec::with_capacity(blocks_needed * PAGE_SIZE_USIZE);
-            data.push(0u8);
+            data.push(node_flag(false));
             data.extend_from_slice(&keys_len.to_le_bytes());
             data.extend_from_slice(&keys_encoded);
             data.extend_from_slice(&pointers_len.to_le_bytes());
             data.extend_from_slice(&pointers_encoded);
+            let checksum = crc32fast::hash(&data);
+            data.extend_from_slice(&checksum.to_le_bytes());
 
             let pad_len = (blocks_needed * PAGE_SIZE_USIZE) - data.len();
             if pad_len > 0 {
@@ -1093,41 +1146,41 @@ where
         child_offsets: &[u64],
     ) -> io::Result<u64> {
         // Similar to serialize_to_block but for internal nodes with known child offsets
-        let buffer_slice = &mut buffer[..];
-        buffer_slice[0] = u8::from(self.is_leaf);
+        let keys_encoded = binary_serialize(&self.keys)?;
+        let keys_len = keys_encoded.len();
+        let pointer_encoded = binary_serialize(child_offsets)?;
+
+        // CRITICAL CHECK: Ensure keys, pointers and checksum fit into one block
+        if FLAG_SIZE + LEN_SIZE + keys_len + LEN_SIZE + pointer_encoded.len() + CHECKSUM_SIZE > PAGE_SIZE_USIZE {
+            return Err(io::Error::other(format!("Internal node overflow: keys ({}) + pointers ({}) exceeds block size. keys sizes might be too large for the current PAGE_SIZE ({}). Consider reducing key sizes or increasing PAGE_SIZE.", keys_len, pointer_encoded.len(), PAGE_SIZE_USIZE)));
+        }
+
+        let buffer_slice = &mut buffer[..PAGE_SIZE_USIZE];
+        buffer_slice[0] = node_flag(self.is_leaf);
         let mut write_pos = FLAG_SIZE;
 
         // Write keys
-        let keys_encoded = binary_serialize(&self.keys)?;
-        let keys_len = keys_encoded.len();
         buffer_slice[write_pos..write_pos + LEN_SIZE]
             .copy_from_slice(&u32::try_from(keys_len).map_err(to_io_error)?.to_le_bytes());
         write_pos += LEN_SIZE;
         buffer_slice[write_pos..write_pos + keys_len].copy_from_slice(&keys_encoded);
         write_pos += keys_len;
-        drop(keys_encoded);
-
-        let pointer_offset_within_first_block = offset + write_pos as u64;
-
-        // Zero unused portion and write first block
-        if write_pos < PAGE_SIZE_USIZE {
-            buffer_slice[write_pos..PAGE_SIZE_USIZE].fill(0u8);
-        }
-        file.seek(SeekFrom::Start(offset))?;
-        file.write_all(&buffer_slice[..PAGE_SIZE_USIZE])?;
 
         // Write child pointers
-        let pointer_encoded = binary_serialize(child_offsets)?;
         let pointer_len = u32::try_from(pointer_encoded.len()).map_err(to_io_error)?;
+        buffer_slice[write_pos..write_pos + LEN_SIZE].copy_from_slice(&pointer_len.to_le_bytes());
+        write_pos += LEN_SIZE;
+        buffer_slice[write_pos..write_pos + pointer_encoded.len()].copy_from_slice(&pointer_encoded);
+        write_pos += pointer_encoded.len();
 
-        // CRITICAL CHECK: Ensure pointers fit in the remaining space of the first block or we've allocated enough
-        if write_pos + LEN_SIZE + pointer_encoded.len() > PAGE_SIZE_USIZE {
-            return Err(io::Error::other(format!("Internal node overflow: keys ({}) + pointers ({}) exceeds block size. keys sizes might be too large for the current PAGE_SIZE ({}). Consider reducing key sizes or increasing PAGE_SIZE.", keys_len, pointer_encoded.len(), PAGE_SIZE_USIZE)));
-        }
+        let checksum = crc32fast::hash(&buffer_slice[..write_pos]);
+        buffer_slice[write_pos..write_pos + CHECKSUM_SIZE].copy_from_slice(&checksum.to_le_bytes());
+        write_pos += CHECKSUM_SIZE;
 
-        file.seek(SeekFrom::Start(pointer_offset_within_first_block))?;
-        file.write_all(&pointer_len.to_le_bytes())?;
-        file.write_all(&pointer_encoded)?;
+        // Zero unused portion and write the block
+        buffer_slice[write_pos..].fill(0u8);
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(buffer_slice)?;
 
         Ok(offset + PAGE_SIZE_USIZE as u64)
     }
@@ -1138,6 +1191,7 @@ where
         offset: u64,
         nested: bool,
     ) -> io::Result<(Self, Option<Vec<u64>>)> {
+        REPOSITORY_METRICS.node_loads.inc();
         file.seek(SeekFrom::Start(offset))?;
 
         let header_required = FLAG_SIZE + LEN_SIZE;
@@ -1147,7 +1201,8 @@ where
 
         file.read_exact(&mut buffer[0..header_required])?;
 
-        let is_leaf = buffer[0] != 0;
+        let is_leaf = buffer[0] & NODE_FLAG_LEAF != 0;
+        let checksum_size = node_checksum_size(buffer[0]);
         #[allow(clippy::range_plus_one)]
         let keys_len = u32_from_bytes(&buffer[FLAG_SIZE..FLAG_SIZE + LEN_SIZE])? as usize;
 
@@ -1165,12 +1220,15 @@ where
         let payload_len = u32_from_bytes(&buffer[read_pos..read_pos + LEN_SIZE])? as usize;
         read_pos += LEN_SIZE;
 
-        let total_required = min_required + payload_len;
+        let total_required = min_required + payload_len + checksum_size;
         if buffer.len() < total_required {
             buffer.resize(total_required, 0);
         }
 
         file.read_exact(&mut buffer[min_required..total_required])?;
+        if checksum_size > 0 {
+            verify_node_checksum(&buffer[..total_required], offset)?;
+        }
 
         let (value_info, values, children, children_pointer) = if is_leaf {
             let info: Vec<ValueInfo> = binary_deserialize(&buffer[read_pos..read_pos + payload_len])?;
@@ -1209,6 +1267,7 @@ where
         offset: u64,
         nested: bool,
     ) -> io::Result<(Self, Option<Vec<u64>>)> {
+        REPOSITORY_METRICS.node_loads.inc();
         let start = usize::try_from(offset).map_err(to_io_error)?;
         let header_end = start.checked_add(FLAG_SIZE + LEN_SIZE)
             .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "Mmap offset overflow"))?;
@@ -1217,6 +1276,7 @@ where
             return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Mmap access out of bounds"));
         }
 
+        let checksum_size = node_checksum_size(mmap[start]);
         let keys_len = u32_from_bytes(&mmap[start + FLAG_SIZE..start + FLAG_SIZE + LEN_SIZE])? as usize;
         let keys_start = header_end;
         let len_pos = keys_start
@@ -1228,7 +1288,7 @@ where
         }
         let payload_len = u32_from_bytes(&mmap[len_pos..len_pos + LEN_SIZE])? as usize;
         let total = len_pos
-            .checked_add(LEN_SIZE + payload_len)
+            .checked_add(LEN_SIZE + payload_len + checksum_size)
             .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "Mmap offset overflow"))?;
 
         if total > mmap.len() {
@@ -1241,6 +1301,9 @@ where
 
         //let slice = &mmap[start..];
         let slice = &mmap[start..total];
+        if checksum_size > 0 {
+            verify_node_checksum(slice, offset)?;
+        }
         Self::deserialize_from_block_slice(slice, file, nested)
     }
 
@@ -1250,7 +1313,7 @@ where
         nested: bool,
     ) -> io::Result<(Self, Option<Vec<u64>>)> {
         // Node type
-        let is_leaf = slice[0] == 1u8;
+        let is_leaf = slice[0] & NODE_FLAG_LEAF != 0;
         let mut read_pos = FLAG_SIZE;
 
         // ---- Keys ----
@@ -1306,6 +1369,7 @@ where
 
     /// Load a value based on its storage info
     fn load_value_from_info<R: Read + Seek>(file: &mut R, info: &ValueInfo) -> io::Result<V> {
+        REPOSITORY_METRICS.value_loads.inc();
         match info.mode {
             ValueStorageMode::Single(offset) => {
                 // Load single value (existing logic)
@@ -1599,6 +1663,7 @@ where
 
     /// Internal store without locking, used for compaction or initial save.
     fn store_internal(&mut self, filepath: &Path) -> io::Result<u64> {
+        let start = Instant::now();
         let tempfile = NamedTempFile::new()?;
         let mut file = utils::file_writer(&tempfile);
         let mut buffer = vec![0u8; PAGE_SIZE_USIZE];
@@ -1634,6 +1699,10 @@ where
                     return Err(string_to_io_error(format!("Temp file rename/copy did not work {} {err}", tempfile.path().to_string_lossy())));
                 }
                 self.dirty = false;
+                REPOSITORY_METRICS.tree_stores.inc();
+                REPOSITORY_METRICS.tree_store_bytes.add(result);
+                REPOSITORY_METRICS.tree_store_duration.observe(start.elapsed());
+                REPOSITORY_METRICS.record_tree_size(filepath, self.len(), result);
                 Ok(result)
             }
             Err(err) => Err(err),
@@ -1690,6 +1759,7 @@ where
 
 
     pub fn load(filepath: &Path) -> io::Result<Self> {
+        let start = Instant::now();
         let mut file = File::open(filepath)?;
 
         // Verify Header
@@ -1698,10 +1768,7 @@ where
         if &header[0..4] != MAGIC {
             return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid magic number"));
         }
-        let version = u32::from_le_bytes(header[4..8].try_into().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid version slice"))?);
-        if version != STORAGE_VERSION {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unsupported storage version: {version}")));
-        }
+        read_storage_version(&header)?;
         let root_offset = u64::from_le_bytes(header[8..16].try_into().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid root offset slice"))?);
 
         // Read metadata
@@ -1720,6 +1787,9 @@ where
         // Start after header block
         let (root, _) = BPlusTreeNode::<K, V>::deserialize_from_block(&mut reader, &mut buffer, root_offset, true)?;
 
+        REPOSITORY_METRICS.tree_loads.inc();
+        REPOSITORY_METRICS.tree_load_duration.observe(start.elapsed());
+
         let (inner_order, leaf_order) = calc_order::<K>();
         Ok(Self {
             root,
@@ -1747,6 +1817,177 @@ where
     }
 }
 
+/// Builds a `BPlusTree` file in one sequential pass from entries sorted by key.
+/// Values and leaf nodes are streamed to disk as they arrive and the internal levels are built
+/// bottom up in `finish`, so no node is ever split. Keys must be strictly ascending.
+///
+/// # Example
+/// ```ignore
+/// let mut builder = BPlusTreeBulkBuilder::<u32, Record>::new(&db_path)?;
+/// for record in sorted_records {
+///     builder.push(record.id, &record)?;
+/// }
+/// builder.finish()?;
+/// ```
+pub struct BPlusTreeBulkBuilder<K, V> {
+    filepath: PathBuf,
+    tempfile: NamedTempFile,
+    writer: io::BufWriter<File>,
+    node_buffer: Vec<u8>,
+    current_offset: u64,
+    current_leaf: BPlusTreeNode<K, V>,
+    leaf_pointers: Vec<(K, u64)>,
+    last_key: Option<K>,
+    leaf_order: usize,
+    entries: usize,
+    metadata: BPlusTreeMetadata,
+    start: Instant,
+    _lock: FileLock,
+}
+
+impl<K, V> BPlusTreeBulkBuilder<K, V>
+where
+    K: Ord + Serialize + for<'de> Deserialize<'de> + Clone,
+    V: Serialize + for<'de> Deserialize<'de> + Clone,
+{
+    pub fn new(filepath: &Path) -> io::Result<Self> {
+        let start = Instant::now();
+        let lock = FileLock::try_lock(filepath)?;
+        let tempfile = NamedTempFile::new_in(filepath.parent().unwrap_or(Path::new(".")))?;
+        let mut writer = io::BufWriter::new(tempfile.as_file().try_clone()?);
+        // The header is written in `finish` when the root offset is known
+        writer.write_all(&[0u8; PAGE_SIZE_USIZE])?;
+        let (_, leaf_order) = calc_order::<K>();
+        Ok(Self {
+            filepath: filepath.to_path_buf(),
+            tempfile,
+            writer,
+            node_buffer: vec![0u8; PAGE_SIZE_USIZE],
+            current_offset: HEADER_SIZE,
+            current_leaf: BPlusTreeNode::new(true),
+            leaf_pointers: Vec::new(),
+            last_key: None,
+            leaf_order,
+            entries: 0,
+            metadata: BPlusTreeMetadata::Empty,
+            start,
+            _lock: lock,
+        })
+    }
+
+    pub fn set_metadata(&mut self, data: BPlusTreeMetadata) {
+        self.metadata = data;
+    }
+
+    pub fn push(&mut self, key: K, value: &V) -> io::Result<()> {
+        if self.last_key.as_ref().is_some_and(|last| *last >= key) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Bulk load keys must be strictly ascending"));
+        }
+
+        let value_bytes = binary_serialize(value)?;
+        let (flag, payload) = compress_if_beneficial(&value_bytes);
+        self.writer.write_all(&[flag])?;
+        self.writer.write_all(&payload)?;
+
+        let stored_len = u32::try_from(1 + payload.len()).map_err(to_io_error)?;
+        let value_offset = self.current_offset;
+        self.current_offset += u64::from(stored_len);
+
+        self.last_key = Some(key.clone());
+        self.current_leaf.keys.push(key);
+        self.current_leaf.value_info.push(ValueInfo {
+            mode: ValueStorageMode::Single(value_offset),
+            length: stored_len,
+            compressed_cache: None,
+        });
+        self.entries += 1;
+
+        if self.current_leaf.keys.len() >= self.leaf_order {
+            self.flush_leaf()?;
+        }
+        Ok(())
+    }
+
+    fn flush_leaf(&mut self) -> io::Result<()> {
+        let leaf = std::mem::replace(&mut self.current_leaf, BPlusTreeNode::new(true));
+        let first_key = leaf.keys.first().cloned();
+        let node_offset = self.current_offset;
+        self.current_offset = leaf.serialize_to_block(&mut self.writer, &mut self.node_buffer, node_offset)?;
+        if let Some(key) = first_key {
+            self.leaf_pointers.push((key, node_offset));
+        }
+        Ok(())
+    }
+
+    /// Sorts `items` by key and bulk loads them into `filepath`.
+    /// The sort is stable and the last item wins for duplicate keys, same as with `BPlusTree::insert`.
+    pub fn store_items<F>(filepath: &Path, items: &mut [V], get_key: F) -> io::Result<u64>
+    where
+        F: Fn(&V) -> K,
+    {
+        items.sort_by_key(&get_key);
+        let mut builder = Self::new(filepath)?;
+        let mut iter = items.iter().peekable();
+        while let Some(item) = iter.next() {
+            let key = get_key(item);
+            if iter.peek().is_some_and(|next| get_key(next) == key) {
+                continue;
+            }
+            builder.push(key, item)?;
+        }
+        builder.finish()
+    }
+
+    /// Writes the internal levels and the header and atomically replaces the target file.
+    /// Returns the size of the written file.
+    pub fn finish(mut self) -> io::Result<u64> {
+        let empty = self.leaf_pointers.is_empty() && self.current_leaf.keys.is_empty();
+        let empty_root_offset = self.current_offset;
+        if empty || !self.current_leaf.keys.is_empty() {
+            self.flush_leaf()?;
+        }
+        self.writer.flush()?;
+        let Self { filepath, mut tempfile, writer, mut node_buffer, current_offset, leaf_pointers, entries, metadata, start, _lock, .. } = self;
+        drop(writer);
+
+        let tree = BPlusTree::<K, V>::new();
+        let file = tempfile.as_file_mut();
+        file.seek(SeekFrom::Start(current_offset))?;
+        let root_offset = if empty {
+            empty_root_offset
+        } else {
+            tree.build_levels_from_pointers(file, leaf_pointers, current_offset, &mut node_buffer)?
+        };
+        let file_size = file.seek(SeekFrom::End(0))?;
+
+        let meta_bytes = metadata.to_bytes();
+        if meta_bytes.len() > METADATA_MAX_SIZE {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Metadata too large for header page"));
+        }
+        let mut header = [0u8; PAGE_SIZE_USIZE];
+        header[0..4].copy_from_slice(MAGIC);
+        header[4..8].copy_from_slice(&STORAGE_VERSION.to_le_bytes());
+        header[8..16].copy_from_slice(&root_offset.to_le_bytes());
+        if !meta_bytes.is_empty() {
+            let len = u32::try_from(meta_bytes.len()).map_err(to_io_error)?;
+            header[16..20].copy_from_slice(&len.to_le_bytes());
+            header[20..20 + meta_bytes.len()].copy_from_slice(&meta_bytes);
+        }
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&header)?;
+        file.flush()?;
+        file.sync_all()?;
+
+        tempfile.persist(&filepath).map_err(to_io_error)?;
+
+        REPOSITORY_METRICS.tree_stores.inc();
+        REPOSITORY_METRICS.tree_store_bytes.add(file_size);
+        REPOSITORY_METRICS.tree_store_duration.observe(start.elapsed());
+        REPOSITORY_METRICS.record_tree_size(&filepath, entries, file_size);
+        Ok(file_size)
+    }
+}
+
 fn query_tree<K, V, R: Read + Seek>(
     file: &mut R,
     buffer: &mut Vec<u8>,
@@ -1759,6 +2000,7 @@ where
     V: Serialize + for<'de> Deserialize<'de> + Clone,
 {
     let mut offset = start_offset;
+    REPOSITORY_METRICS.tree_reads.inc();
     loop {
         // Try Cache First
         let (node, pointers) = if let Some(data) = cache.shift_remove(&offset) {
@@ -1766,6 +2008,7 @@ where
             let res = BPlusTreeNode::<K, V>::deserialize_from_block_slice(&data, file, false)
                 .map_err(BPlusTreeError::from)?;
             cache.insert(offset, data);
+            REPOSITORY_METRICS.node_cache_hits.inc();
             res
         } else {
             // Disk Read
@@ -1825,6 +2068,7 @@ where
     V: Serialize + for<'de> Deserialize<'de> + Clone,
 {
     let mut offset = start_offset;
+    REPOSITORY_METRICS.tree_reads.inc();
     loop {
         // Try Cache First
         let (node, pointers) = if let Some(data) = cache.shift_remove(&offset) {
@@ -1832,6 +2076,7 @@ where
             let res = BPlusTreeNode::<K, V>::deserialize_from_block_slice(&data, file, false)
                 .map_err(BPlusTreeError::from)?;
             cache.insert(offset, data);
+            REPOSITORY_METRICS.node_cache_hits.inc();
             res
         } else {
             // Disk Read
@@ -1896,6 +2141,7 @@ where
             let res = BPlusTreeNode::<K, V>::deserialize_from_block_slice(&data, file, false)
                 .map_err(BPlusTreeError::from)?;
             cache.insert(offset, data);
+            REPOSITORY_METRICS.node_cache_hits.inc();
             res
         } else {
             match BPlusTreeNode::<K, V>::deserialize_from_block(file, buffer, offset, false) {
@@ -1953,6 +2199,7 @@ where
     V: Serialize + for<'de> Deserialize<'de> + Clone,
 {
     let mut offset = start_offset;
+    REPOSITORY_METRICS.tree_reads.inc();
     loop {
         let (node, pointers) = BPlusTreeNode::<K, V>::deserialize_from_mmap(mmap, cursor, offset, false)?;
 
@@ -1995,6 +2242,7 @@ where
     V: Serialize + for<'de> Deserialize<'de> + Clone,
 {
     let mut offset = start_offset;
+    REPOSITORY_METRICS.tree_reads.inc();
     loop {
         let (node, pointers) = BPlusTreeNode::<K, V>::deserialize_from_mmap(mmap, cursor, offset, false)?;
 
@@ -2070,10 +2318,7 @@ where
         if &header[0..4] != MAGIC {
             return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid magic number"));
         }
-        let version = u32::from_le_bytes(header[4..8].try_into().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid version slice"))?);
-        if version != STORAGE_VERSION {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unsupported storage version: {version}")));
-        }
+        read_storage_version(&header)?;
         let root_offset = u64::from_le_bytes(header[8..16].try_into().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid root offset slice"))?);
 
         Ok(Self {
@@ -2533,9 +2778,11 @@ where
         if &header[0..4] != MAGIC {
             return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid magic number"));
         }
-        let version = u32::from_le_bytes(header[4..8].try_into().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid version slice"))?);
-        if version != STORAGE_VERSION {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unsupported storage version: {version}")));
+        if read_storage_version(&header)? < STORAGE_VERSION {
+            // nodes written from now on carry a checksum, older binaries can not read them
+            file.get_mut().seek(SeekFrom::Start(4))?;
+            file.get_mut().write_all(&STORAGE_VERSION.to_le_bytes())?;
+            file.seek(SeekFrom::Start(16))?;
         }
         let root_offset = u64::from_le_bytes(header[8..16].try_into().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid root offset slice"))?);
         let (inner_order, leaf_order) = calc_order::<K>();
@@ -3093,8 +3340,9 @@ where
 mod tests {
     use std::collections::HashSet;
     use std::io;
+    use std::sync::atomic::Ordering;
 
-    use crate::repository::bplustree::{BPlusTree, BPlusTreeQuery, BPlusTreeUpdate};
+    use crate::repository::bplustree::{set_verify_storage_checksums, BPlusTree, BPlusTreeBulkBuilder, BPlusTreeMetadata, BPlusTreeQuery, BPlusTreeUpdate, VERIFY_CHECKSUMS};
     use serde::{Deserialize, Serialize};
     use shared::utils::generate_random_string;
 
@@ -3237,6 +3485,65 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn bulk_builder_test() -> io::Result<()> {
+        let tempdir = tempfile::tempdir()?;
+        let filepath = tempdir.path().join("bulk_builder_test.bin");
+        let content = generate_random_string(512);
+        let test_size = 5_000u32;
+
+        let mut builder = BPlusTreeBulkBuilder::<u32, Record>::new(&filepath)?;
+        builder.set_metadata(BPlusTreeMetadata::TargetIdMapping(42));
+        for i in 0..test_size {
+            builder.push(i * 2, &Record { id: i * 2, data: format!("{content} {i}") })?;
+        }
+        builder.finish()?;
+
+        let mut query = BPlusTreeQuery::<u32, Record>::try_new(&filepath)?;
+        assert_eq!(query.len().unwrap(), test_size as usize);
+        for i in 0..test_size {
+            let record = query.query(&(i * 2)).unwrap().expect("record not found");
+            assert_eq!(record.id, i * 2);
+            assert_eq!(query.query(&(i * 2 + 1)).unwrap(), None);
+        }
+        assert_eq!(query.query_le(&7).unwrap().map(|r| r.id), Some(6));
+        let keys: Vec<u32> = query.iter().map(|(k, _)| k).collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+
+        let tree = BPlusTree::<u32, Record>::load(&filepath)?;
+        assert_eq!(tree.len(), test_size as usize);
+        assert_eq!(tree.get_metadata(), &BPlusTreeMetadata::TargetIdMapping(42));
+        Ok(())
+    }
+
+    #[test]
+    fn bulk_builder_store_items_test() -> io::Result<()> {
+        let tempdir = tempfile::tempdir()?;
+        let filepath = tempdir.path().join("bulk_builder_items_test.bin");
+
+        let mut items = vec![
+            Record { id: 3, data: "three".to_string() },
+            Record { id: 1, data: "one".to_string() },
+            Record { id: 3, data: "three updated".to_string() },
+        ];
+        BPlusTreeBulkBuilder::store_items(&filepath, &mut items, |r: &Record| r.id)?;
+
+        let mut query = BPlusTreeQuery::<u32, Record>::try_new(&filepath)?;
+        assert_eq!(query.len().unwrap(), 2);
+        assert_eq!(query.query(&3).unwrap().map(|r| r.data), Some("three updated".to_string()));
+
+        let mut builder = BPlusTreeBulkBuilder::<u32, Record>::new(&tempdir.path().join("unsorted.bin"))?;
+        builder.push(2, &items[0])?;
+        assert!(builder.push(1, &items[0]).is_err());
+
+        let empty_path = tempdir.path().join("empty.bin");
+        BPlusTreeBulkBuilder::<u32, Record>::new(&empty_path)?.finish()?;
+        let mut query = BPlusTreeQuery::<u32, Record>::try_new(&empty_path)?;
+        assert!(query.is_empty().unwrap());
+        assert_eq!(query.query(&1).unwrap(), None);
+        Ok(())
+    }
+
     #[test]
     fn len_test() -> io::Result<()> {
         let test_size = 100;
@@ -3759,6 +4066,48 @@ mod tests {
         Ok(())
     }
 
+    /// Restores the global checksum verification when the test ends, even if it panics.
+    struct VerifyChecksumsGuard(bool);
+
+    impl VerifyChecksumsGuard {
+        fn enable() -> Self {
+            Self(VERIFY_CHECKSUMS.swap(true, Ordering::Relaxed))
+        }
+    }
+
+    impl Drop for VerifyChecksumsGuard {
+        fn drop(&mut self) {
+            set_verify_storage_checksums(self.0);
+        }
+    }
+
+    #[test]
+    fn node_checksum_test() -> io::Result<()> {
+        let tempdir = tempfile::tempdir()?;
+        let filepath = tempdir.path().join("tree_checksum.bin");
+        let mut tree = BPlusTree::<u32, Record>::new();
+        for i in 0u32..100 {
+            tree.insert(i, Record { id: i, data: format!("Record {i}") });
+        }
+        tree.store(&filepath)?;
+
+        let _verify_checksums = VerifyChecksumsGuard::enable();
+        let mut tree_query = BPlusTreeQuery::<u32, Record>::try_new(&filepath)?;
+        assert!(tree_query.query(&42).map_err(|e| e.to_io())?.is_some());
+        drop(tree_query);
+
+        // Flip a bit in the keys of the root node
+        let mut bytes = std::fs::read(&filepath)?;
+        let root_offset = u64::from_le_bytes(bytes[8..16].try_into().expect("root offset"));
+        bytes[usize::try_from(root_offset).expect("root offset") + 6] ^= 0x01;
+        std::fs::write(&filepath, &bytes)?;
+
+        let mut tree_query = BPlusTreeQuery::<u32, Record>::try_new(&filepath)?;
+        let err = tree_query.query(&42).expect_err("Corrupted node should be detected");
+        assert!(err.to_string().contains("checksum"), "Unexpected error: {err}");
+        Ok(())
+    }
+
     #[test]
     fn upsert_batch_mixed_test() -> io::Result<()> {
         let tempdir = tempfile::tempdir()?;
@@ -0,0 +1,61 @@
+use crate::model::AppConfig;
+use crate::repository::{get_target_storage_path, storage_const};
+use crate::utils::{binary_deserialize, binary_serialize};
+use serde::{Deserialize, Serialize};
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::{PlaylistGroup, UUIDType};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Stream of a removed duplicate or quality variant, it is opened when the stream of the kept channel fails.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelFailoverSource {
+    pub input_name: Arc<str>,
+    pub url: Arc<str>,
+    /// Vertical resolution of the stream, `0` if unknown.
+    #[serde(default)]
+    pub quality: u32,
+}
+
+/// Failover sources of the kept channels by their uuid, collected by the deduplication and the variant collapsing.
+/// The variant ladder of a collapsed channel contains the kept variant too.
+pub type ChannelFailovers = HashMap<UUIDType, Vec<ChannelFailoverSource>>;
+
+/// Writes the failover sources by the virtual id of the kept channels, the file is removed if there are none.
+pub async fn write_channel_failovers(app_config: &AppConfig, target_path: &Path, playlist: &[PlaylistGroup],
+                                     failovers: &ChannelFailovers) -> Result<(), TuliproxError> {
+    let path = target_path.join(storage_const::FILE_CHANNEL_FAILOVER);
+    let by_virtual_id: HashMap<u32, &Vec<ChannelFailoverSource>> = if failovers.is_empty() {
+        HashMap::new()
+    } else {
+        playlist.iter()
+            .flat_map(|group| group.channels.iter())
+            .filter_map(|pli| failovers.get(&pli.header.uuid).map(|sources| (pli.header.virtual_id, sources)))
+            .collect()
+    };
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    if by_virtual_id.is_empty() {
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            tokio::fs::remove_file(&path).await
+                .map_err(|err| info_err!("Failed to remove channel failover file {}: {err}", path.display()))?;
+        }
+        return Ok(());
+    }
+    let encoded = binary_serialize(&by_virtual_id).map_err(|err| info_err!("Failed to encode channel failover: {err}"))?;
+    tokio::fs::write(&path, encoded).await
+        .map_err(|err| info_err!("Failed to write channel failover file {}: {err}", path.display()))
+}
+
+/// Returns the failover sources of the target by virtual id, empty if the target has none.
+pub async fn load_channel_failovers(app_config: &AppConfig, target_name: &str) -> HashMap<u32, Vec<ChannelFailoverSource>> {
+    let Some(path) = get_target_storage_path(&app_config.config.load(), target_name).map(|target_path| target_path.join(storage_const::FILE_CHANNEL_FAILOVER)) else {
+        return HashMap::new();
+    };
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    match tokio::fs::read(&path).await {
+        Ok(encoded) => binary_deserialize(&encoded).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
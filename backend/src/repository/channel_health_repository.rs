@@ -0,0 +1,103 @@
+use crate::model::AppConfig;
+use crate::repository::{get_channel_health_path, get_channel_quarantine_path, get_playback_errors_path};
+use crate::utils::json_write_documents_to_file;
+use log::error;
+use serde::de::DeserializeOwned;
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::{ChannelHealthDto, PlaybackErrorDto, QuarantinedChannelDto, UUIDType};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::str::FromStr;
+
+// Results of channels which were not probed for this time are removed.
+const MAX_CHANNEL_HEALTH_AGE_SECS: i64 = 7 * 86_400;
+const MAX_PLAYBACK_ERRORS: usize = 10_000;
+
+fn read_documents<T: DeserializeOwned>(path: &Path) -> Vec<T> {
+    match std::fs::read(path) {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+            error!("Failed to parse {}: {err}", path.display());
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Merges the probe results of the target into the stored results and counts the consecutive failures of the channels,
+/// the results of targets which are no longer probed are removed. Returns the probe results with their failure count.
+pub async fn save_channel_health(app_config: &AppConfig, target_name: &str, probed_targets: &[String], mut results: Vec<ChannelHealthDto>) -> Result<Vec<ChannelHealthDto>, TuliproxError> {
+    let path = get_channel_health_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut entries: Vec<ChannelHealthDto> = read_documents(&path);
+    let previous_failures: HashMap<&str, u16> = entries.iter()
+        .filter(|entry| entry.target == target_name)
+        .map(|entry| (entry.uuid.as_str(), entry.failures))
+        .collect();
+    for result in &mut results {
+        result.failures = if result.up { 0 } else { previous_failures.get(result.uuid.as_str()).map_or(1, |failures| failures.saturating_add(1)) };
+    }
+    let probed: HashSet<&str> = results.iter().map(|result| result.uuid.as_str()).collect();
+    let now = chrono::Utc::now().timestamp();
+    entries.retain(|entry| probed_targets.contains(&entry.target)
+        && now - entry.ts < MAX_CHANNEL_HEALTH_AGE_SECS
+        && !(entry.target == target_name && probed.contains(entry.uuid.as_str())));
+    entries.extend(results.iter().cloned());
+    json_write_documents_to_file(&path, &entries).await
+        .map_err(|err| info_err!("Failed to write channel health {}: {err}", path.display()))?;
+    Ok(results)
+}
+
+pub async fn load_channel_health(app_config: &AppConfig) -> Vec<ChannelHealthDto> {
+    let path = get_channel_health_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    read_documents(&path)
+}
+
+/// Replaces the quarantined channels of the target.
+pub async fn save_channel_quarantine(app_config: &AppConfig, target_name: &str, channels: Vec<QuarantinedChannelDto>) -> Result<(), TuliproxError> {
+    let path = get_channel_quarantine_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut entries: Vec<QuarantinedChannelDto> = read_documents(&path);
+    entries.retain(|entry| entry.target != target_name);
+    entries.extend(channels);
+    json_write_documents_to_file(&path, &entries).await
+        .map_err(|err| info_err!("Failed to write channel quarantine {}: {err}", path.display()))
+}
+
+pub async fn load_channel_quarantine(app_config: &AppConfig) -> Vec<QuarantinedChannelDto> {
+    let path = get_channel_quarantine_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    read_documents(&path)
+}
+
+pub async fn load_quarantined_uuids(app_config: &AppConfig, target_name: &str) -> HashSet<UUIDType> {
+    load_channel_quarantine(app_config).await.iter()
+        .filter(|channel| channel.target == target_name)
+        .filter_map(|channel| UUIDType::from_str(&channel.uuid).ok())
+        .collect()
+}
+
+/// Appends a reported playback error, errors older than a week are removed.
+pub async fn append_playback_error(app_config: &AppConfig, playback_error: PlaybackErrorDto) -> Result<(), TuliproxError> {
+    let path = get_playback_errors_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut entries: Vec<PlaybackErrorDto> = read_documents(&path);
+    let now = chrono::Utc::now().timestamp();
+    entries.retain(|entry| now - entry.ts < MAX_CHANNEL_HEALTH_AGE_SECS);
+    entries.push(playback_error);
+    if entries.len() > MAX_PLAYBACK_ERRORS {
+        entries.drain(..entries.len() - MAX_PLAYBACK_ERRORS);
+    }
+    json_write_documents_to_file(&path, &entries).await
+        .map_err(|err| info_err!("Failed to write playback errors {}: {err}", path.display()))
+}
+
+/// Playback errors reported since the given unix timestamp.
+pub async fn load_playback_errors(app_config: &AppConfig, since_ts: i64) -> Vec<PlaybackErrorDto> {
+    let path = get_playback_errors_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    let mut entries: Vec<PlaybackErrorDto> = read_documents(&path);
+    entries.retain(|entry| entry.ts >= since_ts);
+    entries
+}
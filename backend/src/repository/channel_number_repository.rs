@@ -0,0 +1,38 @@
+use crate::model::AppConfig;
+use crate::repository::{ensure_target_storage_path, get_target_storage_path, storage_const};
+use crate::utils::{binary_deserialize, binary_serialize};
+use serde::{Deserialize, Serialize};
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::UUIDType;
+use std::collections::BTreeMap;
+
+/// Channel number of a live channel, `last_seen` is the timestamp of the last update containing the channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelNumberEntry {
+    pub number: u32,
+    pub last_seen: i64,
+}
+
+pub type ChannelNumbers = BTreeMap<UUIDType, ChannelNumberEntry>;
+
+/// Returns the channel numbers assigned by the previous updates of the target.
+pub async fn load_channel_numbers(app_config: &AppConfig, target_name: &str) -> ChannelNumbers {
+    let Some(path) = get_target_storage_path(&app_config.config.load(), target_name).map(|path| path.join(storage_const::FILE_CHANNEL_NUMBERS)) else {
+        return ChannelNumbers::new();
+    };
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    match tokio::fs::read(&path).await {
+        Ok(encoded) => binary_deserialize(&encoded[..]).unwrap_or_default(),
+        Err(_) => ChannelNumbers::new(),
+    }
+}
+
+pub async fn save_channel_numbers(app_config: &AppConfig, target_name: &str, channel_numbers: &ChannelNumbers) -> Result<(), TuliproxError> {
+    let path = ensure_target_storage_path(&app_config.config.load(), target_name)?.join(storage_const::FILE_CHANNEL_NUMBERS);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let encoded: Vec<u8> = binary_serialize(channel_numbers)
+        .map_err(|err| info_err!("Failed to encode channel numbers {}: {err}", path.display()))?;
+    tokio::fs::write(&path, encoded).await
+        .map_err(|err| info_err!("Failed to write channel numbers {}: {err}", path.display()))
+}
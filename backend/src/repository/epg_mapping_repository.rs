@@ -0,0 +1,39 @@
+use crate::model::AppConfig;
+use crate::repository::{ensure_target_storage_path, get_target_storage_path, storage_const};
+use crate::utils::json_write_documents_to_file;
+use log::error;
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::{EpgMappingDecisionDto, EpgMappingsDto};
+use std::path::Path;
+
+fn read_mappings(path: &Path) -> EpgMappingsDto {
+    match std::fs::read(path) {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+            error!("Failed to parse epg mappings {}: {err}", path.display());
+            EpgMappingsDto::default()
+        }),
+        Err(_) => EpgMappingsDto::default(),
+    }
+}
+
+pub async fn load_epg_mappings(app_config: &AppConfig, target_name: &str) -> EpgMappingsDto {
+    let Some(path) = get_target_storage_path(&app_config.config.load(), target_name)
+        .map(|target_path| target_path.join(storage_const::FILE_EPG_MAPPING)) else {
+        return EpgMappingsDto::default();
+    };
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    read_mappings(&path)
+}
+
+pub async fn update_epg_mappings(app_config: &AppConfig, target_name: &str, decisions: Vec<EpgMappingDecisionDto>) -> Result<EpgMappingsDto, TuliproxError> {
+    let path = ensure_target_storage_path(&app_config.config.load(), target_name)?.join(storage_const::FILE_EPG_MAPPING);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut mappings = read_mappings(&path);
+    for decision in decisions {
+        mappings.apply(decision);
+    }
+    json_write_documents_to_file(&path, &mappings).await
+        .map_err(|err| info_err!("Failed to write epg mappings {}: {err}", path.display()))?;
+    Ok(mappings)
+}
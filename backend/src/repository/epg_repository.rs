@@ -1,4 +1,4 @@
-use crate::model::{Config, ConfigTarget, TargetOutput, XmlTagIcon};
+use crate::model::{Config, ConfigTarget, TargetOutput, XmlTag, XmlTagIcon};
 use crate::model::{Epg, EPG_ATTRIB_CHANNEL, EPG_ATTRIB_ID, EPG_TAG_CHANNEL, EPG_TAG_DISPLAY_NAME, EPG_TAG_ICON, EPG_TAG_PROGRAMME};
 use crate::repository::{m3u_get_epg_file_path_for_target, BPlusTree};
 use crate::repository::{xtream_get_epg_file_path_for_target, xtream_get_storage_path};
@@ -24,6 +24,27 @@ pub const XML_PREAMBLE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
 // // DOCTYPE via events (DO NOT USE):
 // writer.write_event_async(quick_xml::events::Event::DocType(quick_xml::events::BytesText::new(r#"tv SYSTEM "xmltv.dtd""#)))
 //     .await.map_err(|e| notify_err!("failed to write doctype: {}", e))?;
+/// Position of the `lang` attribute of the tag in the preferred languages, `de` also matches `de-AT`.
+/// Tags without or with another language come after all preferred languages.
+fn epg_language_rank(tag: &XmlTag, lang_attrib: &Arc<str>, languages: &[String]) -> usize {
+    tag.get_attribute_value(lang_attrib)
+        .and_then(|lang| {
+            let lang = lang.to_lowercase();
+            let primary = lang.split(['-', '_']).next().unwrap_or_default();
+            languages.iter().position(|preferred| *preferred == lang || preferred == primary)
+        })
+        .unwrap_or(languages.len())
+}
+
+/// Keeps the value of the tag if its language is preferred over the current one, the first tag wins on equal rank.
+fn select_epg_language_value(selected: &mut Option<(usize, Arc<str>)>, tag: &XmlTag, lang_attrib: &Arc<str>, languages: &[String]) {
+    let Some(value) = tag.value.as_ref() else { return; };
+    let rank = epg_language_rank(tag, lang_attrib, languages);
+    if selected.as_ref().is_none_or(|(selected_rank, _)| rank < *selected_rank) {
+        *selected = Some((rank, Arc::clone(value)));
+    }
+}
+
 pub fn epg_write_file(target: &ConfigTarget, epg: &Epg, path: &Path, playlist: Option<&[PlaylistGroup]>) -> Result<(), TuliproxError> {
     let tag_channel = EPG_TAG_CHANNEL.intern();
     let tag_programme = EPG_TAG_PROGRAMME.intern();
@@ -35,6 +56,8 @@ pub fn epg_write_file(target: &ConfigTarget, epg: &Epg, path: &Path, playlist: O
     let channel_id_attrib = EPG_ATTRIB_CHANNEL.intern();
     let start_attrib = "start".intern();
     let stop_attrib = "stop".intern();
+    let lang_attrib = "lang".intern();
+    let languages = target.options.as_ref().and_then(|options| options.epg_languages.as_deref()).unwrap_or_default();
 
     if epg.children.is_empty() {
         return Ok(());
@@ -61,7 +84,8 @@ pub fn epg_write_file(target: &ConfigTarget, epg: &Epg, path: &Path, playlist: O
             .filter(|tag| tag.name == tag_channel)
             .filter_map(|tag| {
                 let channel_id = tag.get_attribute_value(&epg_id_attrib)?;
-                let mut title = rename_map.get(channel_id).map(|v| Arc::clone(v));
+                let renamed_title = rename_map.get(channel_id).map(|v| Arc::clone(v));
+                let mut title = None;
                 let mut icon = match tag.icon {
                     XmlTagIcon::Src(ref url) => Some(Arc::clone(url)),
                     XmlTagIcon::Undefined | XmlTagIcon::Exists => None,
@@ -69,8 +93,8 @@ pub fn epg_write_file(target: &ConfigTarget, epg: &Epg, path: &Path, playlist: O
                 if let Some(children) = tag.children.as_ref() {
                     for child in children {
                         if child.name == tag_display_name {
-                            if title.is_none() {
-                                title.clone_from(&child.value);
+                            if renamed_title.is_none() {
+                                select_epg_language_value(&mut title, child, &lang_attrib, languages);
                             }
                         } else if icon.is_none() && child.name == tag_icon {
                             icon.clone_from(&child.value);
@@ -79,7 +103,7 @@ pub fn epg_write_file(target: &ConfigTarget, epg: &Epg, path: &Path, playlist: O
                 }
                 let channel = EpgChannel {
                     id: Arc::clone(channel_id),
-                    title,
+                    title: renamed_title.or_else(|| title.map(|(_, value)| value)),
                     icon,
                     programmes: vec![],
                 };
@@ -102,12 +126,13 @@ pub fn epg_write_file(target: &ConfigTarget, epg: &Epg, path: &Path, playlist: O
                         if let Some(children) = tag.children.as_ref() {
                             for child in children {
                                 if child.name == tag_title {
-                                    title.clone_from(&child.value);
+                                    select_epg_language_value(&mut title, child, &lang_attrib, languages);
                                 } else if child.name == tag_desc {
-                                    desc.clone_from(&child.value);
+                                    select_epg_language_value(&mut desc, child, &lang_attrib, languages);
                                 }
                             }
-                            channel.programmes.push(EpgProgramme::new_all(start_time, stop_time, Arc::clone(channel_id), title, desc));
+                            channel.programmes.push(EpgProgramme::new_all(start_time, stop_time, Arc::clone(channel_id),
+                                                                          title.map(|(_, value)| value), desc.map(|(_, value)| value)));
                         }
                     }
                 }
@@ -146,8 +171,43 @@ pub async fn epg_write_for_target(cfg: &Config, target: &ConfigTarget, target_pa
                 debug_if_enabled!("writing m3u epg to {}", path.display());
                 epg_write_file(target, epg_data, &path, playlist)?;
             }
-            TargetOutput::Strm(_) | TargetOutput::HdHomeRun(_) => {}
+            TargetOutput::Strm(_) | TargetOutput::HdHomeRun(_) | TargetOutput::Plex(_) => {}
         }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::select_epg_language_value;
+    use crate::model::XmlTag;
+    use shared::utils::Internable;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn title(lang: Option<&str>, value: &str) -> XmlTag {
+        let attributes = lang.map(|lang| HashMap::from([("lang".intern(), lang.intern())]));
+        let mut tag = XmlTag::new("title".intern(), attributes);
+        tag.value = Some(value.intern());
+        tag
+    }
+
+    fn select(tags: &[XmlTag], languages: &[String]) -> Option<Arc<str>> {
+        let lang_attrib = "lang".intern();
+        let mut selected = None;
+        for tag in tags {
+            select_epg_language_value(&mut selected, tag, &lang_attrib, languages);
+        }
+        selected.map(|(_, value)| value)
+    }
+
+    #[test]
+    fn test_select_epg_language() {
+        let tags = [title(Some("en"), "News"), title(Some("de-AT"), "Nachrichten"), title(None, "Nouvelles")];
+        assert_eq!(select(&tags, &["de".to_string(), "en".to_string()]).as_deref(), Some("Nachrichten"));
+        assert_eq!(select(&tags, &["fr".to_string(), "en".to_string()]).as_deref(), Some("News"));
+        // without a preferred language the first one is used
+        assert_eq!(select(&tags, &["fr".to_string()]).as_deref(), Some("News"));
+        assert_eq!(select(&tags, &[]).as_deref(), Some("News"));
+    }
+}
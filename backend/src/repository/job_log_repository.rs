@@ -0,0 +1,41 @@
+use crate::model::AppConfig;
+use crate::repository::get_job_log_path;
+use crate::utils::json_write_documents_to_file;
+use log::error;
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::JobLogEntryDto;
+use std::path::Path;
+
+const MAX_JOB_LOG_ENTRIES: usize = 200;
+
+fn read_job_log(path: &Path) -> Vec<JobLogEntryDto> {
+    match std::fs::read(path) {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+            error!("Failed to parse job log {}: {err}", path.display());
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub async fn add_job_log_entry(app_config: &AppConfig, entry: JobLogEntryDto) -> Result<(), TuliproxError> {
+    let path = get_job_log_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut entries = read_job_log(&path);
+    entries.push(entry);
+    if entries.len() > MAX_JOB_LOG_ENTRIES {
+        entries.drain(..entries.len() - MAX_JOB_LOG_ENTRIES);
+    }
+    json_write_documents_to_file(&path, &entries).await
+        .map_err(|err| info_err!("Failed to write job log {}: {err}", path.display()))
+}
+
+/// Newest first.
+pub async fn load_job_log(app_config: &AppConfig) -> Vec<JobLogEntryDto> {
+    let path = get_job_log_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    let mut entries = read_job_log(&path);
+    entries.reverse();
+    entries
+}
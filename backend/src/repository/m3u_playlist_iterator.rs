@@ -2,21 +2,85 @@ use shared::error::info_err;
 use shared::error::{TuliproxError};
 use crate::model::{AppConfig, ProxyUserCredentials};
 use crate::model::{ConfigTarget};
-use shared::model::{ConfigTargetOptions, M3uPlaylistItem, PlaylistItemType, ProxyType, TargetType, XtreamCluster};
+use shared::model::{ConfigTargetOptions, M3uPlaylistItem, PlaylistItemType, PlaylistStripField, ProxyType, TargetType, XtreamCluster};
 use crate::repository::{BPlusTreeQuery, PlaylistIteratorReader};
 use crate::repository::m3u_get_file_path_for_db;
 use crate::repository::{ensure_target_storage_path, get_file_path_for_db_index};
 use crate::repository::storage_const;
 use crate::repository::user_get_bouquet_filter;
+use crate::repository::get_playlist_watermark_line;
 use crate::utils::FileReadGuard;
+use crate::api::model::PlaylistQueryCache;
 use std::collections::HashSet;
+use std::io;
 use std::iter::Peekable;
+use std::path::Path;
+use std::sync::Arc;
 use log::error;
-use shared::utils::Internable;
+use shared::utils::{Internable, DASH_EXT};
+
+enum M3uItemSource {
+    Disk(Box<PlaylistIteratorReader<M3uPlaylistItem>>),
+    Cached(Arc<Vec<M3uPlaylistItem>>, usize),
+}
+
+impl Iterator for M3uItemSource {
+    type Item = io::Result<(u32, M3uPlaylistItem)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Disk(reader) => reader.next(),
+            Self::Cached(items, pos) => {
+                let item = items.get(*pos).cloned();
+                *pos += 1;
+                item.map(|item| Ok((item.virtual_id, item)))
+            }
+        }
+    }
+}
+
+fn open_m3u_reader(m3u_path: &Path) -> Result<PlaylistIteratorReader<M3uPlaylistItem>, TuliproxError> {
+    let index_path = get_file_path_for_db_index(m3u_path);
+    let reader = if index_path.exists() {
+         let query = BPlusTreeQuery::<u32, M3uPlaylistItem>::try_new(m3u_path)
+             .map_err(|err| info_err!("Could not open BPlusTreeQuery {m3u_path:?} - {err}"))?;
+         match query.disk_iter_sorted() {
+             Ok(reader) => PlaylistIteratorReader::Sorted(reader),
+             Err(err) => {
+                 error!("Sorted index error for m3u, fallback: {err}");
+                 let query = BPlusTreeQuery::<u32, M3uPlaylistItem>::try_new(m3u_path)
+                     .map_err(|err| info_err!("Could not open BPlusTreeQuery {m3u_path:?} - {err}"))?;
+                 PlaylistIteratorReader::Unsorted(query.disk_iter())
+             }
+         }
+    } else {
+         let query = BPlusTreeQuery::<u32, M3uPlaylistItem>::try_new(m3u_path)
+             .map_err(|err| info_err!("Could not open BPlusTreeQuery {m3u_path:?} - {err}"))?;
+         PlaylistIteratorReader::Unsorted(query.disk_iter())
+    };
+    Ok(reader)
+}
+
+/// Loads the m3u playlist of a `cache_warmup` target into the `query_cache`, unless it is already cached.
+pub async fn m3u_load_cached_items(cfg: &AppConfig, target: &ConfigTarget, query_cache: &PlaylistQueryCache) -> Result<Arc<Vec<M3uPlaylistItem>>, TuliproxError> {
+    if let Some(items) = query_cache.get_m3u_items(&target.name) {
+        return Ok(items);
+    }
+    let generation = query_cache.generation();
+    let config = cfg.config.load();
+    let target_path = ensure_target_storage_path(&config, target.name.as_str())?;
+    let m3u_path = m3u_get_file_path_for_db(&target_path);
+    let _file_lock = cfg.file_locks.read_lock(&m3u_path).await;
+    let items = open_m3u_reader(&m3u_path)?
+        .map(|entry| entry.map(|(_, item)| item))
+        .collect::<io::Result<Vec<M3uPlaylistItem>>>()
+        .map_err(|err| info_err!("Failed to read m3u playlist {m3u_path:?} - {err}"))?;
+    Ok(query_cache.put_m3u_items(&target.name, generation, items))
+}
 
 #[allow(clippy::struct_excessive_bools)]
 pub struct M3uPlaylistIterator {
-    reader: Peekable<PlaylistIteratorReader<M3uPlaylistItem>>,
+    reader: Peekable<M3uItemSource>,
     base_url: String,
     username: String,
     password: String,
@@ -24,10 +88,11 @@ pub struct M3uPlaylistIterator {
     mask_redirect_url: bool,
     include_type_in_url: bool,
     rewrite_resource: bool,
+    proxy_logos: bool,
     proxy_type: ProxyType,
     filter: Option<HashSet<String>>,
     lookup_item: Option<(M3uPlaylistItem, bool)>,
-    _file_lock: FileReadGuard,
+    _file_lock: Option<FileReadGuard>,
 }
 
 impl M3uPlaylistIterator {
@@ -36,41 +101,46 @@ impl M3uPlaylistIterator {
         target: &ConfigTarget,
         user: &ProxyUserCredentials,
     ) -> Result<Self, TuliproxError> {
+        Self::create(cfg, target, user, None).await
+    }
+
+    /// Like `new`, but the playlist of a `cache_warmup` target is served from and stored in the `query_cache`.
+    pub async fn new_cached(
+        cfg: &AppConfig,
+        target: &ConfigTarget,
+        user: &ProxyUserCredentials,
+        query_cache: &PlaylistQueryCache,
+    ) -> Result<Self, TuliproxError> {
+        Self::create(cfg, target, user, Some(query_cache)).await
+    }
+
+    async fn create(
+        cfg: &AppConfig,
+        target: &ConfigTarget,
+        user: &ProxyUserCredentials,
+        query_cache: Option<&PlaylistQueryCache>,
+    ) -> Result<Self, TuliproxError> {
 
         // TODO use playlist memory cache, but be aware of sorting !
 
         let m3u_output = target.get_m3u_output().ok_or_else(|| info_err!("Unexpected failure, missing m3u target output for target {}",  target.name))?;
         let config = cfg.config.load();
-        let target_path = ensure_target_storage_path(&config, target.name.as_str())?;
-        let m3u_path = m3u_get_file_path_for_db(&target_path);
-
-        let file_lock = cfg.file_locks.read_lock(&m3u_path).await;
-
-        let index_path = get_file_path_for_db_index(&m3u_path);
-        let reader = if index_path.exists() {
-             let query = BPlusTreeQuery::<u32, M3uPlaylistItem>::try_new(&m3u_path)
-                 .map_err(|err| info_err!("Could not open BPlusTreeQuery {m3u_path:?} - {err}"))?;
-             match query.disk_iter_sorted() {
-                 Ok(reader) => PlaylistIteratorReader::Sorted(reader),
-                 Err(err) => {
-                     error!("Sorted index error for m3u, fallback: {err}");
-                     let query = BPlusTreeQuery::<u32, M3uPlaylistItem>::try_new(&m3u_path)
-                         .map_err(|err| info_err!("Could not open BPlusTreeQuery {m3u_path:?} - {err}"))?;
-                     PlaylistIteratorReader::Unsorted(query.disk_iter())
-                 }
-             }
+
+        let (reader, file_lock) = if let Some(cache) = query_cache.filter(|_| target.is_cache_warmup()) {
+            (M3uItemSource::Cached(m3u_load_cached_items(cfg, target, cache).await?, 0), None)
         } else {
-             let query = BPlusTreeQuery::<u32, M3uPlaylistItem>::try_new(&m3u_path)
-                 .map_err(|err| info_err!("Could not open BPlusTreeQuery {m3u_path:?} - {err}"))?;
-             PlaylistIteratorReader::Unsorted(query.disk_iter())
-        }.peekable();
+            let target_path = ensure_target_storage_path(&config, target.name.as_str())?;
+            let m3u_path = m3u_get_file_path_for_db(&target_path);
+            let file_lock = cfg.file_locks.read_lock(&m3u_path).await;
+            (M3uItemSource::Disk(Box::new(open_m3u_reader(&m3u_path)?)), Some(file_lock))
+        };
+        let reader = reader.peekable();
 
         let filter = user_get_bouquet_filter(&config, &user.username, None, TargetType::M3u, XtreamCluster::Live).await;
 
-        let server_info = cfg.get_user_server_info(user);
         Ok(Self {
             reader,
-            base_url: server_info.get_base_url(),
+            base_url: cfg.get_target_base_url(target, user),
             username: user.username.clone(),
             password: user.password.clone(),
             target_options: target.options.clone(),
@@ -80,6 +150,7 @@ impl M3uPlaylistIterator {
             proxy_type: user.proxy,
             _file_lock: file_lock, // Save lock inside struct
             rewrite_resource: cfg.is_reverse_proxy_resource_rewrite_enabled(),
+            proxy_logos: cfg.is_logo_proxy_enabled(),
             lookup_item: None,
         })
     }
@@ -129,7 +200,7 @@ impl M3uPlaylistIterator {
         self.get_rewritten_url(m3u_pli, false, storage_const::M3U_RESOURCE_PATH)
     }
 
-    fn find_next_matching(reader: &mut Peekable<PlaylistIteratorReader<M3uPlaylistItem>>, set: &HashSet<String>) -> Option<M3uPlaylistItem> {
+    fn find_next_matching(reader: &mut Peekable<M3uItemSource>, set: &HashSet<String>) -> Option<M3uPlaylistItem> {
         loop {
             match reader.next() {
                 Some(Ok((_, item))) => {
@@ -189,7 +260,10 @@ impl M3uPlaylistIterator {
             let should_rewrite_urls = if is_redirect { self.mask_redirect_url } else { true };
 
             if should_rewrite_urls {
-                let stream_url = self.get_stream_url(&m3u_pli, self.include_type_in_url);
+                let mut stream_url = self.get_stream_url(&m3u_pli, self.include_type_in_url);
+                if !is_redirect && self.proxy_type.is_dash() && m3u_pli.item_type.is_live() {
+                    stream_url.push_str(DASH_EXT);
+                }
                 let resource_url = if self.rewrite_resource {
                     Some(self.get_resource_url(&m3u_pli))
                 } else {
@@ -200,7 +274,11 @@ impl M3uPlaylistIterator {
             } else {
                 // Keep original URL (clone required because target field is distinct)
                 m3u_pli.t_stream_url = m3u_pli.url.clone();
-                m3u_pli.t_resource_url = None;
+                m3u_pli.t_resource_url = if self.proxy_logos {
+                    Some(self.get_resource_url(&m3u_pli))
+                } else {
+                    None
+                };
             }
 
             (m3u_pli, has_next)
@@ -219,6 +297,7 @@ impl Iterator for M3uPlaylistIterator {
 pub struct M3uPlaylistM3uTextIterator {
     inner: M3uPlaylistIterator,
     started: bool,
+    watermark: Option<String>,
 }
 
 impl M3uPlaylistM3uTextIterator {
@@ -226,10 +305,26 @@ impl M3uPlaylistM3uTextIterator {
         cfg: &AppConfig,
         target: &ConfigTarget,
         user: &ProxyUserCredentials,
+        strip_fields: &[PlaylistStripField],
+        query_cache: &PlaylistQueryCache,
     ) -> Result<Self, TuliproxError> {
+        let watermark = target.get_m3u_output()
+            .filter(|output| output.watermark)
+            .map(|_| get_playlist_watermark_line(cfg, &user.username));
+        let mut inner = M3uPlaylistIterator::new_cached(cfg, target, user, query_cache).await?;
+        // the fields of the request are stripped in addition to the fields of the target
+        if !strip_fields.is_empty() {
+            let options = inner.target_options.get_or_insert_with(ConfigTargetOptions::default);
+            for field in strip_fields {
+                if !options.has_strip_field(*field) {
+                    options.strip_fields.push(*field);
+                }
+            }
+        }
         Ok(Self {
-            inner: M3uPlaylistIterator::new(cfg, target, user).await?,
+            inner,
             started: false,
+            watermark,
         })
     }
 }
@@ -242,6 +337,9 @@ impl Iterator for M3uPlaylistM3uTextIterator {
             self.started = true;
             return Some("#EXTM3U".to_string());
         }
+        if let Some(watermark) = self.watermark.take() {
+            return Some(watermark);
+        }
 
         // TODO hls and unknown reverse proxy
         self.inner.get_next().map(|(m3u_pli, _has_next)| {
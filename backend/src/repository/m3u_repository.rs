@@ -1,8 +1,10 @@
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
+use crate::api::model::PlaylistQueryCache;
 use crate::model::{AppConfig, ConfigInput, ProxyUserCredentials};
 use crate::model::{Config, ConfigTarget, M3uTargetOutput};
-use crate::repository::bplustree::{BPlusTree, BPlusTreeQuery};
-use crate::repository::m3u_playlist_iterator::M3uPlaylistM3uTextIterator;
+use crate::repository::bplustree::{BPlusTree, BPlusTreeBulkBuilder, BPlusTreeQuery};
+use crate::repository::m3u_playlist_iterator::{M3uPlaylistIterator, M3uPlaylistM3uTextIterator};
 use crate::repository::playlist_repository::get_input_m3u_playlist_file_path;
 use crate::repository::storage::{get_input_storage_path, get_target_storage_path};
 use crate::repository::storage_const;
@@ -14,7 +16,7 @@ use log::error;
 use shared::concat_string;
 use shared::error::{notify_err, str_to_io_error, string_to_io_error, TuliproxError};
 use shared::model::{M3uPlaylistItem, PlaylistGroup};
-use shared::model::{PlaylistItem, PlaylistItemType, XtreamCluster};
+use shared::model::{PlaylistItem, PlaylistItemType, PlaylistStripField, XtreamCluster};
 use std::io::Error;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -111,11 +113,9 @@ pub async fn m3u_write_playlist(
 
     task::spawn_blocking(move || -> Result<(), TuliproxError> {
         let _guard = file_lock;
-        let mut tree = BPlusTree::new();
-        for m3u in playlist.iter() {
-            tree.insert(m3u.virtual_id, m3u.clone());
-        }
-        tree.store_with_index(&m3u_path_clone, |pli| pli.source_ordinal).map_err(|err| cant_write_result!(&m3u_path_clone, err))?;
+        let mut items = playlist.as_ref().clone();
+        BPlusTreeBulkBuilder::store_items(&m3u_path_clone, &mut items, |pli: &M3uPlaylistItem| pli.virtual_id).map_err(|err| cant_write_result!(&m3u_path_clone, err))?;
+        BPlusTree::<u32, M3uPlaylistItem>::store_index(&m3u_path_clone, |pli| pli.source_ordinal).map_err(|err| cant_write_result!(&m3u_path_clone, err))?;
         Ok(())
     })
         .await
@@ -128,10 +128,28 @@ pub async fn m3u_load_rewrite_playlist(
     cfg: &AppConfig,
     target: &ConfigTarget,
     user: &ProxyUserCredentials,
+    strip_fields: &[PlaylistStripField],
+    query_cache: &PlaylistQueryCache,
 ) -> Result<M3uPlaylistM3uTextIterator, TuliproxError> {
-    M3uPlaylistM3uTextIterator::new(cfg, target, user).await
+    M3uPlaylistM3uTextIterator::new(cfg, target, user, strip_fields, query_cache).await
+}
+
+/// The live channels of the m3u playlist the user can see, grouped by their group in playlist order.
+pub async fn m3u_load_live_groups(
+    cfg: &AppConfig,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+) -> Result<IndexMap<Arc<str>, Vec<M3uPlaylistItem>>, TuliproxError> {
+    let mut groups: IndexMap<Arc<str>, Vec<M3uPlaylistItem>> = IndexMap::new();
+    for (pli, _has_next) in M3uPlaylistIterator::new(cfg, target, user).await? {
+        if pli.item_type.is_live() {
+            groups.entry(Arc::clone(&pli.group)).or_default().push(pli);
+        }
+    }
+    Ok(groups)
 }
 
+#[cfg(feature = "server")]
 pub async fn m3u_get_item_for_stream_id(stream_id: u32, app_state: &AppState, target: &ConfigTarget) -> Result<M3uPlaylistItem, Error> {
     if stream_id < 1 {
         return Err(str_to_io_error("id should start with 1"));
@@ -15,6 +15,20 @@ mod playlist_source;
 mod library_repository;
 mod sorted_index;
 mod alias_repository;
+mod repository_metrics;
+mod web_push_repository;
+mod playlist_changes_repository;
+mod notification_repository;
+mod ui_preferences_repository;
+mod epg_mapping_repository;
+mod playlist_watermark;
+mod stream_session_repository;
+mod job_log_repository;
+mod processing_stats_repository;
+mod channel_health_repository;
+mod channel_number_repository;
+mod search_index_repository;
+mod channel_failover_repository;
 
 pub use storage::*;
 pub use target_id_mapping::*;
@@ -31,4 +45,18 @@ pub use storage_const::*;
 pub use sorted_index::*;
 pub use alias_repository::*;
 pub use playlist_source::*;
-pub use library_repository::*;
\ No newline at end of file
+pub use library_repository::*;
+pub use repository_metrics::*;
+pub use web_push_repository::*;
+pub use playlist_changes_repository::*;
+pub use notification_repository::*;
+pub use ui_preferences_repository::*;
+pub use epg_mapping_repository::*;
+pub use playlist_watermark::*;
+pub use stream_session_repository::*;
+pub use job_log_repository::*;
+pub use processing_stats_repository::*;
+pub use channel_health_repository::*;
+pub use channel_number_repository::*;
+pub use search_index_repository::*;
+pub use channel_failover_repository::*;
\ No newline at end of file
@@ -0,0 +1,190 @@
+use crate::model::AppConfig;
+use crate::repository::get_notifications_path;
+use crate::utils::json_write_documents_to_file;
+use log::error;
+use serde::{Deserialize, Serialize};
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::{IpAnonymization, NotificationDto, NotificationKind};
+use shared::utils::anonymize_ips;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+const MAX_NOTIFICATIONS: usize = 200;
+
+/// The notifications are shared, the read state is stored per web ui user.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NotificationStore {
+    next_id: u64,
+    notifications: Vec<NotificationDto>,
+    read: HashMap<String, Vec<u64>>,
+}
+
+impl NotificationStore {
+    fn add(&mut self, kind: NotificationKind, message: String, ts: i64) -> NotificationDto {
+        self.next_id += 1;
+        let notification = NotificationDto { id: self.next_id, ts, kind, message, read: false };
+        self.notifications.push(notification.clone());
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.drain(..self.notifications.len() - MAX_NOTIFICATIONS);
+            let oldest_id = self.notifications.first().map_or(0, |n| n.id);
+            for ids in self.read.values_mut() {
+                ids.retain(|id| *id >= oldest_id);
+            }
+        }
+        notification
+    }
+
+    /// Newest first.
+    fn get_for_user(&self, username: &str) -> Vec<NotificationDto> {
+        let read = self.read.get(username);
+        self.notifications.iter().rev().map(|notification| NotificationDto {
+            read: read.is_some_and(|ids| ids.contains(&notification.id)),
+            ..notification.clone()
+        }).collect()
+    }
+
+    /// Returns the number of changed notifications.
+    fn anonymize_older_than(&mut self, ts: i64, mode: IpAnonymization) -> usize {
+        let mut changed = 0;
+        for notification in self.notifications.iter_mut().filter(|n| n.ts < ts) {
+            if let Cow::Owned(message) = anonymize_ips(&notification.message, mode) {
+                if message != notification.message {
+                    notification.message = message;
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Removes the read state of the user and the notifications naming the user.
+    fn remove_user(&mut self, username: &str) -> usize {
+        self.read.remove(username);
+        let count = self.notifications.len();
+        self.notifications.retain(|n| !n.message.split(|c: char| c.is_whitespace() || c == '\'' || c == '"').any(|word| word == username));
+        count - self.notifications.len()
+    }
+
+    /// An empty id list marks all notifications as read.
+    fn mark_read(&mut self, username: &str, ids: &[u64]) {
+        let read = self.read.entry(username.to_string()).or_default();
+        for notification in &self.notifications {
+            if (ids.is_empty() || ids.contains(&notification.id)) && !read.contains(&notification.id) {
+                read.push(notification.id);
+            }
+        }
+    }
+}
+
+fn read_store(path: &Path) -> NotificationStore {
+    match std::fs::read(path) {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+            error!("Failed to parse notifications {}: {err}", path.display());
+            NotificationStore::default()
+        }),
+        Err(_) => NotificationStore::default(),
+    }
+}
+
+async fn write_store(path: &Path, store: &NotificationStore) -> Result<(), TuliproxError> {
+    json_write_documents_to_file(path, store).await
+        .map_err(|err| info_err!("Failed to write notifications {}: {err}", path.display()))
+}
+
+pub async fn add_notification(app_config: &AppConfig, kind: NotificationKind, message: String) -> Result<NotificationDto, TuliproxError> {
+    let path = get_notifications_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut store = read_store(&path);
+    let notification = store.add(kind, message, chrono::Utc::now().timestamp());
+    write_store(&path, &store).await?;
+    Ok(notification)
+}
+
+pub async fn load_notifications(app_config: &AppConfig, username: &str) -> Vec<NotificationDto> {
+    let path = get_notifications_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    read_store(&path).get_for_user(username)
+}
+
+pub async fn mark_notifications_read(app_config: &AppConfig, username: &str, ids: &[u64]) -> Result<(), TuliproxError> {
+    let path = get_notifications_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut store = read_store(&path);
+    store.mark_read(username, ids);
+    write_store(&path, &store).await
+}
+
+/// Pseudonymizes the client ips of the notifications older than the given timestamp.
+pub async fn anonymize_notifications(app_config: &AppConfig, older_than_ts: i64, mode: IpAnonymization) -> Result<usize, TuliproxError> {
+    let path = get_notifications_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut store = read_store(&path);
+    let changed = store.anonymize_older_than(older_than_ts, mode);
+    if changed > 0 {
+        write_store(&path, &store).await?;
+    }
+    Ok(changed)
+}
+
+/// Returns the number of removed notifications.
+pub async fn remove_user_notifications(app_config: &AppConfig, username: &str) -> Result<usize, TuliproxError> {
+    let path = get_notifications_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut store = read_store(&path);
+    let removed = store.remove_user(username);
+    write_store(&path, &store).await?;
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NotificationStore, MAX_NOTIFICATIONS};
+    use shared::model::{IpAnonymization, NotificationKind};
+
+    #[test]
+    fn test_notification_read_state() {
+        let mut store = NotificationStore::default();
+        let first = store.add(NotificationKind::Info, "first".to_string(), 1);
+        let second = store.add(NotificationKind::Security, "second".to_string(), 2);
+        store.mark_read("admin", &[first.id]);
+
+        let admin = store.get_for_user("admin");
+        assert_eq!(admin.iter().map(|n| (n.id, n.read)).collect::<Vec<_>>(), vec![(second.id, false), (first.id, true)]);
+        assert!(store.get_for_user("viewer").iter().all(|n| !n.read));
+
+        store.mark_read("viewer", &[]);
+        assert!(store.get_for_user("viewer").iter().all(|n| n.read));
+    }
+
+    #[test]
+    fn test_notification_limit() {
+        let mut store = NotificationStore::default();
+        let first = store.add(NotificationKind::Info, "first".to_string(), 1);
+        store.mark_read("admin", &[first.id]);
+        for i in 0..MAX_NOTIFICATIONS {
+            store.add(NotificationKind::Warning, i.to_string(), 2);
+        }
+        assert_eq!(store.notifications.len(), MAX_NOTIFICATIONS);
+        assert!(store.notifications.iter().all(|n| n.id != first.id));
+        assert!(store.read["admin"].is_empty());
+    }
+
+    #[test]
+    fn test_notification_privacy() {
+        let mut store = NotificationStore::default();
+        store.add(NotificationKind::Security, "Failed login for user bob from 10.1.2.3".to_string(), 1);
+        store.add(NotificationKind::Security, "Failed login for user alice from 10.1.2.4".to_string(), 5);
+        store.mark_read("bob", &[]);
+
+        assert_eq!(store.anonymize_older_than(3, IpAnonymization::Truncate), 1);
+        assert_eq!(store.anonymize_older_than(3, IpAnonymization::Truncate), 0);
+        assert_eq!(store.notifications[0].message, "Failed login for user bob from 10.1.2.0");
+        assert_eq!(store.notifications[1].message, "Failed login for user alice from 10.1.2.4");
+
+        assert_eq!(store.remove_user("bob"), 1);
+        assert_eq!(store.notifications.len(), 1);
+        assert!(!store.read.contains_key("bob"));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::model::{AppConfig, ConfigTarget};
+use crate::repository::{ensure_target_storage_path, get_target_storage_path, storage_const};
+use crate::utils::{binary_deserialize, binary_serialize, json_write_documents_to_file};
+use log::error;
+use serde::{Deserialize, Serialize};
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::{PlaylistChangeItemDto, PlaylistChangesDto, PlaylistCompareDto, PlaylistGroup, PlaylistItemType, PlaylistMovedItemDto,
+                    PlaylistRenamedItemDto, UUIDType, XtreamCluster};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PlaylistSnapshotEntry {
+    name: String,
+    group: String,
+    cluster: XtreamCluster,
+}
+
+type PlaylistSnapshot = BTreeMap<UUIDType, PlaylistSnapshotEntry>;
+
+/// Series episodes are left out, they would bury the channel changes.
+fn create_snapshot(playlist: &[PlaylistGroup]) -> PlaylistSnapshot {
+    playlist.iter()
+        .flat_map(|group| group.channels.iter())
+        .filter(|pli| !matches!(pli.header.item_type, PlaylistItemType::Series | PlaylistItemType::LocalSeries))
+        .map(|pli| {
+            let header = &pli.header;
+            let name = if header.title.is_empty() { &header.name } else { &header.title };
+            (header.uuid, PlaylistSnapshotEntry {
+                name: name.to_string(),
+                group: header.group.to_string(),
+                cluster: header.xtream_cluster,
+            })
+        })
+        .collect()
+}
+
+fn to_change_item(entry: &PlaylistSnapshotEntry) -> PlaylistChangeItemDto {
+    PlaylistChangeItemDto {
+        name: entry.name.clone(),
+        group: entry.group.clone(),
+        cluster: entry.cluster,
+    }
+}
+
+fn collect_groups(snapshot: &PlaylistSnapshot) -> BTreeSet<(&str, &str)> {
+    snapshot.values().map(|entry| (entry.group.as_str(), entry.cluster.as_str())).collect()
+}
+
+/// Channels are matched by their uuid, which does not depend on the name or group.
+fn diff_snapshots(target: &str, timestamp: i64, old: &PlaylistSnapshot, new: &PlaylistSnapshot) -> PlaylistChangesDto {
+    let mut changes = PlaylistChangesDto {
+        target: target.to_string(),
+        timestamp,
+        ..PlaylistChangesDto::default()
+    };
+    for (uuid, entry) in new {
+        match old.get(uuid) {
+            None => changes.added.push(to_change_item(entry)),
+            Some(old_entry) => {
+                if old_entry.name != entry.name {
+                    changes.renamed.push(PlaylistRenamedItemDto {
+                        old_name: old_entry.name.clone(),
+                        name: entry.name.clone(),
+                        group: entry.group.clone(),
+                        cluster: entry.cluster,
+                    });
+                }
+                if old_entry.group != entry.group {
+                    changes.moved.push(PlaylistMovedItemDto {
+                        name: entry.name.clone(),
+                        old_group: old_entry.group.clone(),
+                        group: entry.group.clone(),
+                        cluster: entry.cluster,
+                    });
+                }
+            }
+        }
+    }
+    changes.removed = old.iter()
+        .filter(|(uuid, _)| !new.contains_key(uuid))
+        .map(|(_, entry)| to_change_item(entry))
+        .collect();
+
+    let old_groups = collect_groups(old);
+    let new_groups = collect_groups(new);
+    changes.groups_added = new_groups.difference(&old_groups).map(|(group, _)| (*group).to_string()).collect();
+    changes.groups_removed = old_groups.difference(&new_groups).map(|(group, _)| (*group).to_string()).collect();
+    changes.groups_added.dedup();
+    changes.groups_removed.dedup();
+
+    changes.added.sort_by(|a, b| (&a.group, &a.name).cmp(&(&b.group, &b.name)));
+    changes.removed.sort_by(|a, b| (&a.group, &a.name).cmp(&(&b.group, &b.name)));
+    changes.renamed.sort_by(|a, b| (&a.group, &a.name).cmp(&(&b.group, &b.name)));
+    changes.moved.sort_by(|a, b| (&a.group, &a.name).cmp(&(&b.group, &b.name)));
+    changes
+}
+
+fn sorted_change_items<'a>(entries: impl Iterator<Item=&'a PlaylistSnapshotEntry>) -> Vec<PlaylistChangeItemDto> {
+    let mut items: Vec<PlaylistChangeItemDto> = entries.map(to_change_item).collect();
+    items.sort_by(|a, b| (&a.group, &a.name).cmp(&(&b.group, &b.name)));
+    items
+}
+
+fn compare_snapshots(target_a: &str, target_b: &str, a: &PlaylistSnapshot, b: &PlaylistSnapshot) -> PlaylistCompareDto {
+    let groups_a = collect_groups(a);
+    let groups_b = collect_groups(b);
+    let mut groups_only_a: Vec<String> = groups_a.difference(&groups_b).map(|(group, _)| (*group).to_string()).collect();
+    let mut groups_only_b: Vec<String> = groups_b.difference(&groups_a).map(|(group, _)| (*group).to_string()).collect();
+    groups_only_a.dedup();
+    groups_only_b.dedup();
+    PlaylistCompareDto {
+        target_a: target_a.to_string(),
+        target_b: target_b.to_string(),
+        common: a.keys().filter(|uuid| b.contains_key(uuid)).count(),
+        only_a: sorted_change_items(a.iter().filter(|(uuid, _)| !b.contains_key(uuid)).map(|(_, entry)| entry)),
+        only_b: sorted_change_items(b.iter().filter(|(uuid, _)| !a.contains_key(uuid)).map(|(_, entry)| entry)),
+        groups_only_a,
+        groups_only_b,
+    }
+}
+
+async fn load_snapshot(path: &Path) -> Option<PlaylistSnapshot> {
+    let encoded = tokio::fs::read(path).await.ok()?;
+    binary_deserialize(&encoded[..]).ok()
+}
+
+async fn save_snapshot(path: &Path, snapshot: &PlaylistSnapshot) -> std::io::Result<()> {
+    let encoded: Vec<u8> = binary_serialize(snapshot)?;
+    tokio::fs::write(path, encoded).await
+}
+
+/// Compares the playlist with the snapshot of the previous processing run and stores the changes.
+/// Returns `None` for the first run of a target, there is nothing to compare with.
+pub async fn update_playlist_changes(app_config: &AppConfig, target: &ConfigTarget, playlist: &[PlaylistGroup]) -> Result<Option<PlaylistChangesDto>, TuliproxError> {
+    let config = app_config.config.load();
+    let target_path = ensure_target_storage_path(&config, &target.name)?;
+    let snapshot_path = target_path.join(storage_const::FILE_PLAYLIST_SNAPSHOT);
+    let changes_path = target_path.join(storage_const::FILE_PLAYLIST_CHANGES);
+
+    let new_snapshot = create_snapshot(playlist);
+    let changes = {
+        let _file_lock = app_config.file_locks.write_lock(&snapshot_path).await;
+        let changes = load_snapshot(&snapshot_path).await
+            .map(|old_snapshot| diff_snapshots(&target.name, chrono::Utc::now().timestamp(), &old_snapshot, &new_snapshot));
+        save_snapshot(&snapshot_path, &new_snapshot).await
+            .map_err(|err| info_err!("Failed to write playlist snapshot {}: {err}", snapshot_path.display()))?;
+        changes
+    };
+
+    if let Some(playlist_changes) = changes.as_ref() {
+        let _file_lock = app_config.file_locks.write_lock(&changes_path).await;
+        json_write_documents_to_file(&changes_path, playlist_changes).await
+            .map_err(|err| info_err!("Failed to write playlist changes {}: {err}", changes_path.display()))?;
+    }
+    Ok(changes)
+}
+
+/// Returns the changes of the last processing run of the target.
+pub async fn load_playlist_changes(app_config: &AppConfig, target_name: &str) -> Option<PlaylistChangesDto> {
+    let path = get_target_storage_path(&app_config.config.load(), target_name)?.join(storage_const::FILE_PLAYLIST_CHANGES);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    let content = tokio::fs::read(&path).await.ok()?;
+    serde_json::from_slice(&content)
+        .map_err(|err| error!("Failed to parse playlist changes {}: {err}", path.display()))
+        .ok()
+}
+
+async fn load_target_snapshot(app_config: &AppConfig, target_name: &str) -> Option<PlaylistSnapshot> {
+    let path = get_target_storage_path(&app_config.config.load(), target_name)?.join(storage_const::FILE_PLAYLIST_SNAPSHOT);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    load_snapshot(&path).await
+}
+
+/// Compares the playlists of the last processing runs of two targets.
+/// Returns `None` if one of the targets was not processed yet.
+pub async fn compare_target_playlists(app_config: &AppConfig, target_a: &str, target_b: &str) -> Option<PlaylistCompareDto> {
+    let snapshot_a = load_target_snapshot(app_config, target_a).await?;
+    let snapshot_b = load_target_snapshot(app_config, target_b).await?;
+    Some(compare_snapshots(target_a, target_b, &snapshot_a, &snapshot_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_snapshots, diff_snapshots, PlaylistSnapshot, PlaylistSnapshotEntry};
+    use shared::model::{UUIDType, XtreamCluster};
+
+    fn entry(name: &str, group: &str) -> PlaylistSnapshotEntry {
+        PlaylistSnapshotEntry { name: name.to_string(), group: group.to_string(), cluster: XtreamCluster::Live }
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let old: PlaylistSnapshot = [
+            (UUIDType([1; 32]), entry("News", "Info")),
+            (UUIDType([2; 32]), entry("Sport 1", "Sports")),
+            (UUIDType([3; 32]), entry("Kids", "Kids")),
+            (UUIDType([4; 32]), entry("Movies", "Cinema")),
+        ].into_iter().collect();
+        let new: PlaylistSnapshot = [
+            (UUIDType([1; 32]), entry("News HD", "Info")),
+            (UUIDType([2; 32]), entry("Sport 1", "Sports Premium")),
+            (UUIDType([3; 32]), entry("Kids", "Kids")),
+            (UUIDType([5; 32]), entry("Music", "Music")),
+        ].into_iter().collect();
+
+        let changes = diff_snapshots("test", 0, &old, &new);
+        assert_eq!(changes.added.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["Music"]);
+        assert_eq!(changes.removed.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["Movies"]);
+        assert_eq!(changes.renamed.len(), 1);
+        assert_eq!(changes.renamed[0].old_name, "News");
+        assert_eq!(changes.moved.len(), 1);
+        assert_eq!(changes.moved[0].old_group, "Sports");
+        assert_eq!(changes.groups_added, vec!["Music".to_string(), "Sports Premium".to_string()]);
+        assert_eq!(changes.groups_removed, vec!["Cinema".to_string(), "Sports".to_string()]);
+        assert_eq!(changes.summary().to_string(), "1 channel added, 1 removed, 1 renamed, 1 moved (groups 2 added, 2 removed)");
+
+        assert!(diff_snapshots("test", 0, &new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_compare_snapshots() {
+        let family: PlaylistSnapshot = [
+            (UUIDType([1; 32]), entry("News", "Info")),
+            (UUIDType([2; 32]), entry("Kids", "Kids")),
+            (UUIDType([3; 32]), entry("Cartoons", "Kids")),
+        ].into_iter().collect();
+        let parents: PlaylistSnapshot = [
+            (UUIDType([1; 32]), entry("News HD", "News")),
+            (UUIDType([4; 32]), entry("Sport 1", "Sports")),
+        ].into_iter().collect();
+
+        let compare = compare_snapshots("family", "parents", &family, &parents);
+        assert_eq!(compare.common, 1);
+        assert_eq!(compare.only_a.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["Cartoons", "Kids"]);
+        assert_eq!(compare.only_b.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["Sport 1"]);
+        assert_eq!(compare.groups_only_a, vec!["Info".to_string(), "Kids".to_string()]);
+        assert_eq!(compare.groups_only_b, vec!["News".to_string(), "Sports".to_string()]);
+
+        assert!(compare_snapshots("family", "family", &family, &family).is_equal());
+    }
+}
@@ -1,25 +1,32 @@
-use crate::api::model::{AppState, PlaylistM3uStorage, PlaylistStorage, PlaylistStorageState, PlaylistXtreamStorage};
+#[cfg(feature = "server")]
+use crate::api::model::AppState;
+use crate::api::model::{PlaylistM3uStorage, PlaylistQueryCache, PlaylistStorage, PlaylistStorageState, PlaylistXtreamStorage};
 use crate::model::Epg;
-use crate::model::{AppConfig, ConfigInput, ConfigTarget, TargetOutput};
+use crate::model::{AppConfig, Config, ConfigInput, ConfigTarget, TargetOutput, TraktMatchedIds};
 use crate::processing::processor::playlist::{apply_filter_to_playlist, PlaylistProcessingContext};
 use crate::repository::{BPlusTree, BPlusTreeQuery};
 use crate::repository::epg_write_for_target;
+use crate::repository::{iter_raw_m3u_input_playlist, iter_raw_xtream_input_playlist};
 use crate::repository::{load_input_m3u_playlist, m3u_get_file_path_for_db, m3u_write_playlist, persist_input_m3u_playlist};
 use crate::repository::{ensure_target_storage_path, get_input_storage_path, get_target_id_mapping_file, get_target_storage_path};
 use crate::repository::FILE_SUFFIX_DB;
 use crate::repository::write_strm_playlist;
+use crate::repository::write_playlist_search_index;
+use crate::repository::{write_channel_failovers, ChannelFailovers};
 use crate::repository::{TargetIdMapping, VirtualIdRecord};
-use crate::repository::{load_input_xtream_playlist, persist_input_xtream_playlist, xtream_get_file_path, xtream_get_storage_path, xtream_write_playlist};
+use crate::repository::{load_input_xtream_playlist, persist_input_xtream_playlist, xtream_clear_cached_responses, xtream_get_file_path, xtream_get_storage_path, xtream_write_playlist};
+use crate::repository::{m3u_load_cached_items, storage_const, xtream_load_cached_category_items, xtream_load_categories};
 use crate::utils;
 use log::{info, warn};
 use crate::repository::{LocalLibraryDiskPlaylistSource, M3uDiskPlaylistSource, MemoryPlaylistSource, PlaylistSource, XtreamDiskPlaylistSource};
 use shared::error::{info_err, TuliproxError};
 use shared::model::xtream_const::XTREAM_CLUSTER;
-use shared::model::{InputType, M3uPlaylistItem, PlaylistEntry, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, StreamProperties, XtreamCluster, XtreamPlaylistItem};
+use shared::model::{InputType, M3uPlaylistItem, PlaylistEntry, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, SeriesStreamDetailEpisodeProperties, StreamProperties, TargetType, XtreamCluster, XtreamPlaylistItem};
 use shared::utils::{is_dash_url, is_hls_url, Internable};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 use crate::repository::{load_input_local_library_playlist, persist_input_library_playlist};
 
 struct LocalEpisodeKey {
@@ -30,10 +37,24 @@ struct LocalEpisodeKey {
 pub struct ProviderEpisodeKey {
     pub(crate) provider_id: u32,
     pub(crate) virtual_id: u32,
+    pub(crate) url: Arc<str>,
 }
 
+impl ProviderEpisodeKey {
+    /// Episodes without provider id, like the grouped series of m3u inputs, are matched by their url.
+    fn matches(&self, episode: &SeriesStreamDetailEpisodeProperties) -> bool {
+        if self.provider_id == 0 {
+            !self.url.is_empty() && episode.direct_source == self.url
+        } else {
+            episode.id == self.provider_id
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
 pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [PlaylistGroup], epg: Option<&Epg>,
-                              target: &ConfigTarget, playlist_state: Option<&Arc<PlaylistStorageState>>) -> Result<(), Vec<TuliproxError>> {
+                              target: &ConfigTarget, playlist_state: Option<&Arc<PlaylistStorageState>>,
+                              trakt_matched_ids: &TraktMatchedIds, channel_failovers: &ChannelFailovers) -> Result<(), Vec<TuliproxError>> {
     let mut errors = vec![];
     let config = &app_config.config.load();
     let target_path = match ensure_target_storage_path(config, &target.name) {
@@ -90,7 +111,7 @@ pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [Playl
             TargetOutput::Xtream(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
             TargetOutput::M3u(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
             TargetOutput::Strm(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
-            TargetOutput::HdHomeRun(_) => None,
+            TargetOutput::HdHomeRun(_) | TargetOutput::Plex(_) => None,
         };
 
         let pl: &mut [PlaylistGroup] = if let Some(filtered_playlist) = filtered.as_mut() {
@@ -102,8 +123,8 @@ pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [Playl
         let result = match output {
             TargetOutput::Xtream(_xtream_output) => xtream_write_playlist(app_config, target, pl).await,
             TargetOutput::M3u(m3u_output) => m3u_write_playlist(app_config, target, m3u_output, &target_path, pl).await,
-            TargetOutput::Strm(strm_output) => write_strm_playlist(app_config, target, strm_output, pl).await,
-            TargetOutput::HdHomeRun(_hdhomerun_output) => Ok(()),
+            TargetOutput::Strm(strm_output) => write_strm_playlist(app_config, target, strm_output, pl, trakt_matched_ids).await,
+            TargetOutput::HdHomeRun(_) | TargetOutput::Plex(_) => Ok(()),
         };
 
         match result {
@@ -119,6 +140,13 @@ pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [Playl
         }
     }
 
+    if let Err(err) = write_playlist_search_index(app_config, &target_path, playlist).await {
+        errors.push(err);
+    }
+    if let Err(err) = write_channel_failovers(app_config, &target_path, playlist, channel_failovers).await {
+        errors.push(err);
+    }
+
     if let Err(err) = target_id_mapping.persist() {
         errors.push(info_err!("{err}"));
     }
@@ -127,6 +155,11 @@ pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [Playl
     drop(target_id_mapping);
     drop(file_lock);
 
+    xtream_clear_cached_responses(config, &target.name).await;
+    if let Some(playlist_storage) = playlist_state {
+        playlist_storage.query_cache.invalidate(&target.name);
+    }
+
     if target.use_memory_cache {
         if let Some(playlist_storage) = playlist_state {
             for output in &target.output {
@@ -147,9 +180,38 @@ pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [Playl
         }
     }
 
+    if target.is_cache_warmup() {
+        if let Some(playlist_storage) = playlist_state {
+            warm_up_query_cache(app_config, target, &playlist_storage.query_cache).await;
+        }
+    }
+
     if errors.is_empty() { Ok(()) } else { Err(errors) }
 }
 
+/// Loads the common player queries of the target into the query cache,
+/// the first wave of player refreshes after an update is served from memory.
+async fn warm_up_query_cache(app_config: &Arc<AppConfig>, target: &ConfigTarget, query_cache: &PlaylistQueryCache) {
+    let start_time = Instant::now();
+    if target.has_output(TargetType::Xtream) {
+        let config = app_config.config.load();
+        for (collection, cluster) in [(storage_const::COL_CAT_LIVE, XtreamCluster::Live),
+                                      (storage_const::COL_CAT_VOD, XtreamCluster::Video),
+                                      (storage_const::COL_CAT_SERIES, XtreamCluster::Series)] {
+            xtream_load_categories(&config, query_cache, &target.name, collection, cluster).await;
+        }
+        if let Err(err) = xtream_load_cached_category_items(XtreamCluster::Live, app_config, target, query_cache).await {
+            warn!("Cache warm-up of the live streams failed for target {}: {err}", target.name);
+        }
+    }
+    if target.has_output(TargetType::M3u) {
+        if let Err(err) = m3u_load_cached_items(app_config, target, query_cache).await {
+            warn!("Cache warm-up of the m3u playlist failed for target {}: {err}", target.name);
+        }
+    }
+    info!("Cache warm-up of target {} took {} ms", target.name, start_time.elapsed().as_millis());
+}
+
 fn assign_local_series_info_episode_key(local_library_series: &mut HashMap<Arc<str>, Vec<LocalEpisodeKey>>, header: &mut PlaylistItemHeader, item_type: PlaylistItemType) {
     // we need to rewrite local series info with the new virtual ids
     if item_type == PlaylistItemType::LocalSeries {
@@ -172,6 +234,7 @@ fn assign_provider_series_info_episode_key(provider_series: &mut HashMap<Arc<str
             .push(ProviderEpisodeKey {
                 provider_id: header.get_provider_id().unwrap_or_default(),
                 virtual_id: header.virtual_id,
+                url: header.url.clone(),
             });
     }
 }
@@ -211,7 +274,7 @@ where
             {
                 for episode in episodes.iter_mut() {
                     for episode_key in episode_keys {
-                        if episode.id == episode_key.provider_id {
+                        if episode_key.matches(episode) {
                             episode.id = episode_key.virtual_id;
                             break;
                         }
@@ -307,6 +370,7 @@ async fn load_m3u_target_storage(app_config: &AppConfig, target: &ConfigTarget)
 }
 
 
+#[cfg(feature = "server")]
 pub async fn load_playlists_into_memory_cache(app_state: &AppState) -> Result<(), TuliproxError> {
     for sources in &app_state.app_config.sources.load().sources {
         for target in &sources.targets {
@@ -316,6 +380,7 @@ pub async fn load_playlists_into_memory_cache(app_state: &AppState) -> Result<()
     Ok(())
 }
 
+#[cfg(feature = "server")]
 pub async fn load_target_into_memory_cache(app_state: &AppState, target: &Arc<ConfigTarget>) {
     if target.use_memory_cache {
         info!("Loading target {} into memory cache", target.name);
@@ -353,10 +418,10 @@ pub async fn persist_input_playlist(app_config: &Arc<AppConfig>, input: &ConfigI
                     return (playlist, Some(info_err!("Error creating input storage directory for input '{}' failed: {err}", input.name)));
                 }
             };
-            persist_input_xtream_playlist(app_config, &storage_path, playlist).await
+            persist_input_xtream_playlist(app_config, &storage_path, input.is_xtream_incremental(), playlist).await
         }
 
-        InputType::M3u | InputType::M3uBatch => {
+        InputType::M3u | InputType::M3uBatch | InputType::Udp => {
             // Persist M3U
             let working_dir = &app_config.config.load().working_dir;
             let storage_path = match get_input_storage_path(&input.name, working_dir) {
@@ -389,6 +454,25 @@ pub async fn persist_input_playlist(app_config: &Arc<AppConfig>, input: &ConfigI
     }
 }
 
+/// Calls `f` for each item of the last stored playlist of the input, library inputs are skipped.
+pub async fn for_each_stored_input_item<F: FnMut(&PlaylistItem)>(app_config: &AppConfig, input: &ConfigInput, mut f: F) {
+    match input.input_type {
+        InputType::Xtream | InputType::XtreamBatch => {
+            for cluster in [XtreamCluster::Live, XtreamCluster::Video, XtreamCluster::Series] {
+                if let Some((_guard, items)) = iter_raw_xtream_input_playlist(app_config, input, cluster).await {
+                    items.for_each(|item| f(&PlaylistItem::from(&item)));
+                }
+            }
+        }
+        InputType::M3u | InputType::M3uBatch | InputType::Udp => {
+            if let Some((_guard, items)) = iter_raw_m3u_input_playlist(app_config, input, None).await {
+                items.for_each(|item| f(&PlaylistItem::from(&item)));
+            }
+        }
+        InputType::Library => {}
+    }
+}
+
 pub async fn load_input_playlist(ctx: &PlaylistProcessingContext, input: &ConfigInput, clusters: Option<&[XtreamCluster]>) -> Result<Box<dyn PlaylistSource>, TuliproxError> {
     let app_config = &ctx.config;
     let working_dir = &app_config.config.load().working_dir;
@@ -411,7 +495,7 @@ pub async fn load_input_playlist(ctx: &PlaylistProcessingContext, input: &Config
                 Ok(Box::new(MemoryPlaylistSource::new(groups)))
             }
         }
-        InputType::M3u | InputType::M3uBatch => {
+        InputType::M3u | InputType::M3uBatch | InputType::Udp => {
             // Load M3U
             let file_path = get_input_m3u_playlist_file_path(&storage_path, &input.name);
             if disk_based_processing && file_path.exists() {
@@ -433,6 +517,19 @@ pub async fn load_input_playlist(ctx: &PlaylistProcessingContext, input: &Config
     }
 }
 
+/// Returns the time the playlists of the target were last written.
+pub fn get_target_playlist_last_update(cfg: &Config, target: &ConfigTarget) -> Option<SystemTime> {
+    let target_path = get_target_storage_path(cfg, &target.name)?;
+    target.output.iter()
+        .filter_map(|output| match output {
+            TargetOutput::Xtream(_) => xtream_get_storage_path(cfg, &target.name).map(|path| xtream_get_file_path(&path, XtreamCluster::Live)),
+            TargetOutput::M3u(_) => Some(m3u_get_file_path_for_db(&target_path)),
+            TargetOutput::Strm(_) | TargetOutput::HdHomeRun(_) | TargetOutput::Plex(_) => None,
+        })
+        .filter_map(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .max()
+}
+
 pub fn get_input_m3u_playlist_file_path(storage_path: &Path, input_name: &Arc<str>) -> PathBuf {
     let sanitized_input_name: String = input_name.chars()
         .map(|c| if c.is_alphanumeric() { c } else { '_' })
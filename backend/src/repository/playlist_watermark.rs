@@ -0,0 +1,65 @@
+use crate::model::AppConfig;
+
+const WATERMARK_DATA_ID: &str = "tuliprox.session";
+const WATERMARK_KEY_CONTEXT: &str = "tuliprox playlist watermark";
+const WATERMARK_LEN: usize = 24;
+
+fn create_watermark(secret: &[u8], username: &str) -> String {
+    let mut hasher = blake3::Hasher::new_derive_key(WATERMARK_KEY_CONTEXT);
+    hasher.update(secret);
+    hasher.update(username.as_bytes());
+    hasher.finalize().to_hex()[..WATERMARK_LEN].to_string()
+}
+
+/// The watermark is keyed with the `rewrite_secret` of the reverse proxy,
+/// without it anyone knowing a username can create its watermark.
+fn get_watermark_secret(app_config: &AppConfig) -> [u8; 16] {
+    app_config.get_reverse_proxy_rewrite_secret().unwrap_or_default()
+}
+
+/// Returns the playlist line carrying the watermark of the user.
+pub fn get_playlist_watermark_line(app_config: &AppConfig, username: &str) -> String {
+    let watermark = create_watermark(&get_watermark_secret(app_config), username);
+    format!("#EXT-X-SESSION-DATA:DATA-ID=\"{WATERMARK_DATA_ID}\",VALUE=\"{watermark}\"")
+}
+
+fn find_playlist_watermark(content: &str) -> Option<&str> {
+    content.lines()
+        .filter_map(|line| line.trim().strip_prefix("#EXT-X-SESSION-DATA:"))
+        .filter(|attributes| attributes.contains(&format!("DATA-ID=\"{WATERMARK_DATA_ID}\"")))
+        .find_map(|attributes| {
+            let value = attributes.split_once("VALUE=\"")?.1;
+            value.split_once('"').map(|(watermark, _)| watermark)
+        })
+}
+
+/// Finds the user whose watermark is embedded in the playlist content.
+/// Returns the watermark and the matching username if the user still exists.
+pub fn identify_playlist_watermark(app_config: &AppConfig, content: &str) -> Option<(String, Option<String>)> {
+    let watermark = find_playlist_watermark(content)?;
+    let secret = get_watermark_secret(app_config);
+    let username = app_config.api_proxy.load().as_ref().and_then(|api_proxy| {
+        api_proxy.user.iter()
+            .flat_map(|target_user| &target_user.credentials)
+            .find(|credentials| create_watermark(&secret, &credentials.username) == watermark)
+            .map(|credentials| credentials.username.clone())
+    });
+    Some((watermark.to_string(), username))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_watermark, find_playlist_watermark};
+
+    #[test]
+    fn test_find_playlist_watermark() {
+        let watermark = create_watermark(&[1u8; 16], "user1");
+        assert_eq!(watermark.len(), 24);
+        assert_ne!(watermark, create_watermark(&[1u8; 16], "user2"));
+        assert_ne!(watermark, create_watermark(&[2u8; 16], "user1"));
+
+        let content = format!("#EXTM3U\n#EXT-X-SESSION-DATA:DATA-ID=\"tuliprox.session\",VALUE=\"{watermark}\"\n#EXTINF:-1,Channel\nhttp://localhost/1\n");
+        assert_eq!(find_playlist_watermark(&content), Some(watermark.as_str()));
+        assert_eq!(find_playlist_watermark("#EXTM3U\n#EXTINF:-1,Channel\nhttp://localhost/1\n"), None);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::model::AppConfig;
+use crate::repository::get_processing_stats_path;
+use crate::utils::json_write_documents_to_file;
+use log::error;
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::{SourceStats, TargetProcessingStatsDto};
+use std::path::Path;
+
+fn read_processing_stats(path: &Path) -> Vec<TargetProcessingStatsDto> {
+    match std::fs::read(path) {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+            error!("Failed to parse processing stats {}: {err}", path.display());
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Replaces the stage timings of the processed targets, the other targets keep their last timings.
+fn merge_processing_stats(entries: &mut Vec<TargetProcessingStatsDto>, stats: &[SourceStats], ts: i64) {
+    for target in stats.iter().flat_map(|source| &source.targets) {
+        let Some(timings) = target.timings else { continue; };
+        let entry = TargetProcessingStatsDto { target: target.name.clone(), ts, success: target.success, timings };
+        match entries.iter_mut().find(|e| e.target == target.name) {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    }
+}
+
+pub async fn save_processing_stats(app_config: &AppConfig, stats: &[SourceStats]) -> Result<(), TuliproxError> {
+    let path = get_processing_stats_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut entries = read_processing_stats(&path);
+    merge_processing_stats(&mut entries, stats, chrono::Utc::now().timestamp());
+    json_write_documents_to_file(&path, &entries).await
+        .map_err(|err| info_err!("Failed to write processing stats {}: {err}", path.display()))
+}
+
+/// Stage timings of the last update of each target, targets which are no longer configured are left out.
+pub async fn load_processing_stats(app_config: &AppConfig) -> Vec<TargetProcessingStatsDto> {
+    let path = get_processing_stats_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    let sources = app_config.sources.load();
+    let mut entries = read_processing_stats(&path);
+    entries.retain(|entry| sources.sources.iter().flat_map(|source| &source.targets).any(|target| target.name == entry.target));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_processing_stats;
+    use shared::model::{ProcessingTimings, SourceStats, TargetProcessingStatsDto, TargetStats};
+
+    #[test]
+    fn test_merge_processing_stats() {
+        let timings = ProcessingTimings { fetch: 100, sort: 20, ..ProcessingTimings::default() };
+        let mut entries = vec![
+            TargetProcessingStatsDto { target: "a".to_string(), ts: 1, success: true, timings: ProcessingTimings::default() },
+            TargetProcessingStatsDto { target: "b".to_string(), ts: 1, success: true, timings: ProcessingTimings::default() },
+        ];
+        let stats = vec![SourceStats { inputs: vec![], targets: vec![
+            TargetStats::failure("a").with_timings(timings),
+            TargetStats::success("c").with_timings(timings),
+            TargetStats::success("d"),
+        ] }];
+        merge_processing_stats(&mut entries, &stats, 2);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0], TargetProcessingStatsDto { target: "a".to_string(), ts: 2, success: false, timings });
+        assert_eq!(entries[1].ts, 1);
+        assert_eq!(entries[2].target, "c");
+        assert_eq!(entries[2].timings.total(), 120);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::utils::{Counter, DurationHistogram, MetricsWriter};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Process wide counters of the on-disk `BPlusTree` storage.
+#[derive(Debug)]
+pub struct RepositoryMetrics {
+    /// Point lookups (`query`, `query_le`) against a stored tree.
+    pub tree_reads: Counter,
+    /// Nodes served from the query node cache.
+    pub node_cache_hits: Counter,
+    /// Nodes read from file or memory map.
+    pub node_loads: Counter,
+    /// Values read from file or memory map.
+    pub value_loads: Counter,
+    /// Nodes whose checksum did not match on read.
+    pub checksum_errors: Counter,
+    pub tree_stores: Counter,
+    pub tree_store_bytes: Counter,
+    pub tree_store_duration: DurationHistogram,
+    pub tree_loads: Counter,
+    pub tree_load_duration: DurationHistogram,
+    /// Latest stored size per tree file, keyed by `<dir>/<file>`.
+    tree_sizes: Mutex<BTreeMap<String, (u64, u64)>>,
+}
+
+impl RepositoryMetrics {
+    const fn new() -> Self {
+        Self {
+            tree_reads: Counter::new(),
+            node_cache_hits: Counter::new(),
+            node_loads: Counter::new(),
+            value_loads: Counter::new(),
+            checksum_errors: Counter::new(),
+            tree_stores: Counter::new(),
+            tree_store_bytes: Counter::new(),
+            tree_store_duration: DurationHistogram::new(),
+            tree_loads: Counter::new(),
+            tree_load_duration: DurationHistogram::new(),
+            tree_sizes: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn record_tree_size(&self, filepath: &Path, entries: usize, bytes: u64) {
+        let name = match (filepath.parent().and_then(Path::file_name), filepath.file_name()) {
+            (Some(dir), Some(file)) => format!("{}/{}", dir.to_string_lossy(), file.to_string_lossy()),
+            (None, Some(file)) => file.to_string_lossy().to_string(),
+            _ => return,
+        };
+        if let Ok(mut sizes) = self.tree_sizes.lock() {
+            sizes.insert(name, (entries as u64, bytes));
+        }
+    }
+
+    pub fn write(&self, writer: &mut MetricsWriter) {
+        writer.counter("tuliprox_bptree_reads_total", "Point lookups on stored B+ trees", self.tree_reads.get());
+        writer.counter("tuliprox_bptree_node_cache_hits_total", "B+ tree nodes served from the query cache", self.node_cache_hits.get());
+        writer.counter("tuliprox_bptree_node_loads_total", "B+ tree nodes read from disk", self.node_loads.get());
+        writer.counter("tuliprox_bptree_value_loads_total", "B+ tree values read from disk", self.value_loads.get());
+        writer.counter("tuliprox_bptree_checksum_errors_total", "B+ tree nodes with a checksum mismatch", self.checksum_errors.get());
+        writer.counter("tuliprox_bptree_stores_total", "B+ trees serialized to disk", self.tree_stores.get());
+        writer.counter("tuliprox_bptree_store_bytes_total", "Bytes written by B+ tree serialization", self.tree_store_bytes.get());
+        writer.histogram("tuliprox_bptree_store_duration_seconds", "B+ tree serialization time", &self.tree_store_duration);
+        writer.counter("tuliprox_bptree_loads_total", "B+ trees fully loaded into memory", self.tree_loads.get());
+        writer.histogram("tuliprox_bptree_load_duration_seconds", "B+ tree full load time", &self.tree_load_duration);
+
+        let sizes = self.tree_sizes.lock().map(|s| s.clone()).unwrap_or_default();
+        writer.labeled_gauge("tuliprox_bptree_entries", "Entries of the last stored B+ tree", "tree",
+                             sizes.iter().map(|(name, (entries, _))| (name.as_str(), *entries)));
+        writer.labeled_gauge("tuliprox_bptree_size_bytes", "File size of the last stored B+ tree", "tree",
+                             sizes.iter().map(|(name, (_, bytes))| (name.as_str(), *bytes)));
+    }
+}
+
+pub static REPOSITORY_METRICS: RepositoryMetrics = RepositoryMetrics::new();
@@ -0,0 +1,233 @@
+use crate::model::AppConfig;
+use crate::ptt::ptt_parse_title;
+use crate::repository::{get_target_storage_path, storage_const};
+use crate::utils::{binary_deserialize, binary_serialize};
+use deunicode::deunicode;
+use serde::{Deserialize, Serialize};
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::{PlaylistGroup, PlaylistItemType, PlaylistSearchHitDto, PlaylistSearchResultDto, XtreamCluster};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+const SCORE_EXACT: u32 = 2;
+const SCORE_PREFIX: u32 = 1;
+// added when the title starts with the query
+const SCORE_TITLE_PREFIX: u32 = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SearchIndexEntry {
+    virtual_id: u32,
+    cluster: XtreamCluster,
+    item_type: PlaylistItemType,
+    title: String,
+    group: String,
+    epg_id: Option<String>,
+    year: Option<u32>,
+}
+
+/// Inverted index over the title, group, epg id and the PTT metadata (year, resolution, codec, ...) of the
+/// channels, movies and series of a target. The tokens are ascii folded and lowercase, the postings are the
+/// ascending positions of the entries.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PlaylistSearchIndex {
+    entries: Vec<SearchIndexEntry>,
+    tokens: BTreeMap<String, Vec<u32>>,
+}
+
+fn normalize(text: &str) -> String {
+    deunicode(text).to_lowercase()
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item=String> + '_ {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+}
+
+fn collect_entry_tokens(entry: &SearchIndexEntry, parse_metadata: bool) -> (Vec<String>, Option<u32>) {
+    let mut texts = vec![normalize(&entry.title), normalize(&entry.group)];
+    if let Some(epg_id) = entry.epg_id.as_deref() {
+        let epg_id = normalize(epg_id);
+        texts.push(epg_id.replace(|c: char| !c.is_ascii_alphanumeric(), ""));
+        texts.push(epg_id);
+    }
+    let mut year = None;
+    // live channel names are no release names, the parser would only find noise
+    if parse_metadata {
+        let metadata = ptt_parse_title(&entry.title);
+        year = metadata.year;
+        texts.extend([metadata.resolution, metadata.quality, metadata.codec, metadata.episode_code, metadata.edition]
+            .into_iter().flatten().map(|text| normalize(&text)));
+        texts.extend(metadata.languages.iter().chain(metadata.networks.iter()).map(|text| normalize(text)));
+        if let Some(year) = year {
+            texts.push(year.to_string());
+        }
+    }
+    let mut tokens: Vec<String> = texts.iter().flat_map(|text| tokenize(text)).collect();
+    tokens.sort_unstable();
+    tokens.dedup();
+    (tokens, year)
+}
+
+impl PlaylistSearchIndex {
+    fn from_entries(mut entries: Vec<SearchIndexEntry>) -> Self {
+        let mut tokens: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for (position, entry) in (0u32..).zip(entries.iter_mut()) {
+            let (entry_tokens, year) = collect_entry_tokens(entry, entry.cluster != XtreamCluster::Live);
+            entry.year = year;
+            for token in entry_tokens {
+                tokens.entry(token).or_default().push(position);
+            }
+        }
+        Self { entries, tokens }
+    }
+
+    /// Series episodes are left out, the index lists the channels, movies and series.
+    fn create_entries(playlist: &[PlaylistGroup]) -> Vec<SearchIndexEntry> {
+        playlist.iter()
+            .flat_map(|group| group.channels.iter())
+            .filter(|pli| !matches!(pli.header.item_type, PlaylistItemType::Series | PlaylistItemType::LocalSeries))
+            .map(|pli| {
+                let header = &pli.header;
+                let title = if header.title.is_empty() { &header.name } else { &header.title };
+                SearchIndexEntry {
+                    virtual_id: header.virtual_id,
+                    cluster: header.xtream_cluster,
+                    item_type: header.item_type,
+                    title: title.to_string(),
+                    group: header.group.to_string(),
+                    epg_id: header.epg_channel_id.as_ref().filter(|id| !id.is_empty()).map(ToString::to_string),
+                    year: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Every token of the query has to match a token of the entry, the tokens match as prefix.
+    fn match_token(&self, query_token: &str) -> HashMap<u32, u32> {
+        let mut matches = HashMap::new();
+        for (token, postings) in self.tokens.range(query_token.to_string()..).take_while(|(token, _)| token.starts_with(query_token)) {
+            let score = if token == query_token { SCORE_EXACT } else { SCORE_PREFIX };
+            for position in postings {
+                let best = matches.entry(*position).or_insert(0);
+                *best = (*best).max(score);
+            }
+        }
+        matches
+    }
+
+    pub fn search(&self, query: &str, cluster: Option<XtreamCluster>, limit: usize) -> PlaylistSearchResultDto {
+        let query = normalize(query.trim());
+        let mut token_matches: Vec<HashMap<u32, u32>> = tokenize(&query).map(|token| self.match_token(&token)).collect();
+        // the smallest posting list drives the intersection
+        token_matches.sort_by_key(HashMap::len);
+        let Some((first, others)) = token_matches.split_first() else {
+            return PlaylistSearchResultDto::default();
+        };
+
+        let mut hits: Vec<(u32, &SearchIndexEntry)> = first.iter()
+            .filter_map(|(position, score)| {
+                let entry = self.entries.get(*position as usize)?;
+                if cluster.is_some_and(|cluster| cluster != entry.cluster) {
+                    return None;
+                }
+                let mut total = *score;
+                for matches in others {
+                    total += matches.get(position)?;
+                }
+                if normalize(&entry.title).starts_with(&query) {
+                    total += SCORE_TITLE_PREFIX;
+                }
+                Some((total, entry))
+            })
+            .collect();
+        let total = hits.len();
+        hits.sort_unstable_by(|(score_a, a), (score_b, b)| score_b.cmp(score_a).then_with(|| a.title.cmp(&b.title)));
+        hits.truncate(limit);
+
+        PlaylistSearchResultDto {
+            items: hits.into_iter().map(|(score, entry)| PlaylistSearchHitDto {
+                virtual_id: entry.virtual_id,
+                cluster: entry.cluster,
+                item_type: entry.item_type,
+                title: entry.title.clone(),
+                group: entry.group.clone(),
+                epg_id: entry.epg_id.clone(),
+                year: entry.year,
+                score,
+            }).collect(),
+            total,
+        }
+    }
+}
+
+/// Creates the search index of the persisted playlist, the PTT parsing runs on the blocking pool.
+pub async fn write_playlist_search_index(app_config: &AppConfig, target_path: &Path, playlist: &[PlaylistGroup]) -> Result<(), TuliproxError> {
+    let index_path = target_path.join(storage_const::FILE_SEARCH_INDEX);
+    let entries = PlaylistSearchIndex::create_entries(playlist);
+    let encoded = tokio::task::spawn_blocking(move || binary_serialize(&PlaylistSearchIndex::from_entries(entries)))
+        .await
+        .map_err(|err| info_err!("Failed to create search index: {err}"))?
+        .map_err(|err| info_err!("Failed to encode search index: {err}"))?;
+    let _file_lock = app_config.file_locks.write_lock(&index_path).await;
+    tokio::fs::write(&index_path, encoded).await
+        .map_err(|err| info_err!("Failed to write search index {}: {err}", index_path.display()))
+}
+
+/// Returns `None` if the target was not processed since the search index exists.
+pub async fn load_playlist_search_index(app_config: &AppConfig, target_name: &str) -> Option<PlaylistSearchIndex> {
+    let index_path = get_target_storage_path(&app_config.config.load(), target_name)?.join(storage_const::FILE_SEARCH_INDEX);
+    let encoded = {
+        let _file_lock = app_config.file_locks.read_lock(&index_path).await;
+        tokio::fs::read(&index_path).await.ok()?
+    };
+    tokio::task::spawn_blocking(move || binary_deserialize(&encoded).ok()).await.ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlaylistSearchIndex, SearchIndexEntry};
+    use shared::model::{PlaylistItemType, XtreamCluster};
+
+    fn entry(virtual_id: u32, cluster: XtreamCluster, title: &str, group: &str, epg_id: Option<&str>) -> SearchIndexEntry {
+        SearchIndexEntry {
+            virtual_id,
+            cluster,
+            item_type: if cluster == XtreamCluster::Live { PlaylistItemType::Live } else { PlaylistItemType::Video },
+            title: title.to_string(),
+            group: group.to_string(),
+            epg_id: epg_id.map(ToString::to_string),
+            year: None,
+        }
+    }
+
+    #[test]
+    fn test_search_index() {
+        let index = PlaylistSearchIndex::from_entries(vec![
+            entry(1, XtreamCluster::Live, "BBC One HD", "UK News", Some("bbc1.uk")),
+            entry(2, XtreamCluster::Live, "Das Erste", "DE", Some("daserste.de")),
+            entry(3, XtreamCluster::Video, "Amélie (2001) 1080p", "Movies FR", None),
+            entry(4, XtreamCluster::Video, "The Matrix 1999 2160p x265", "Movies", None),
+            entry(5, XtreamCluster::Video, "One Day 2011 720p", "Movies", None),
+        ]);
+        assert_eq!(index.entries.len(), 5);
+
+        let ids = |query: &str, cluster: Option<XtreamCluster>| index.search(query, cluster, 10).items.iter().map(|hit| hit.virtual_id).collect::<Vec<_>>();
+        assert_eq!(ids("amelie", None), vec![3]);
+        assert_eq!(ids("bbc1", None), vec![1]);
+        assert_eq!(ids("matrix 2160", None), vec![4]);
+        assert_eq!(ids("matrix 720p", None), Vec::<u32>::new());
+        // the title prefix ranks first
+        assert_eq!(ids("one", None), vec![5, 1]);
+        assert_eq!(ids("one", Some(XtreamCluster::Live)), vec![1]);
+        assert_eq!(ids("movies", None), vec![3, 5, 4]);
+        let limited = index.search("movies", None, 2);
+        assert_eq!((limited.total, limited.items.len()), (3, 2));
+
+        let result = index.search("2001", None, 10);
+        assert_eq!(result.total, 1);
+        assert_eq!(result.items[0].year, Some(2001));
+        assert!(index.search("  ", None, 10).items.is_empty());
+    }
+}
@@ -57,6 +57,46 @@ pub fn get_geoip_path(working_dir: &str) -> PathBuf {
     Path::new(working_dir).join("geoip.db")
 }
 
+pub fn get_web_push_key_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("web_push_vapid.pem")
+}
+
+pub fn get_web_push_subscriptions_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("web_push_subscriptions.json")
+}
+
+pub fn get_notifications_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("notifications.json")
+}
+
+pub fn get_job_log_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("job_log.json")
+}
+
+pub fn get_processing_stats_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("processing_stats.json")
+}
+
+pub fn get_channel_health_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("channel_health.json")
+}
+
+pub fn get_channel_quarantine_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("channel_quarantine.json")
+}
+
+pub fn get_playback_errors_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("playback_errors.json")
+}
+
+pub fn get_stream_sessions_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("stream_sessions")
+}
+
+pub fn get_ui_preferences_path(working_dir: &str) -> PathBuf {
+    Path::new(working_dir).join("ui_preferences.json")
+}
+
 pub fn get_file_path_for_db_index(db_path: &Path) -> PathBuf {
     db_path.with_extension(storage_const::FILE_SUFFIX_INDEX)
 }
\ No newline at end of file
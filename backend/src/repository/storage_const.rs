@@ -4,7 +4,15 @@ pub(in crate::repository) const FILE_SUFFIX_INDEX: &str = "idx";
 pub(in crate::repository) const FILE_ID_MAPPING: &str = "id_mapping.db";
 pub(in crate::repository) const FILE_STRM: &str = "strm";
 pub(in crate::repository) const FILE_M3U: &str = "m3u";
+pub(in crate::repository) const FILE_PLAYLIST_SNAPSHOT: &str = "playlist_snapshot.bin";
+pub(in crate::repository) const FILE_PLAYLIST_CHANGES: &str = "playlist_changes.json";
+pub(in crate::repository) const FILE_EPG_MAPPING: &str = "epg_mapping.json";
+pub(in crate::repository) const FILE_XTREAM_WATERMARK: &str = "xtream_watermark.json";
+pub(in crate::repository) const FILE_CHANNEL_NUMBERS: &str = "channel_numbers.bin";
+pub(in crate::repository) const FILE_SEARCH_INDEX: &str = "search_index.bin";
+pub(in crate::repository) const FILE_CHANNEL_FAILOVER: &str = "channel_failover.bin";
 pub const M3U_STREAM_PATH: &str = "m3u-stream";
+pub const M3U8_PATH: &str = "m3u8";
 pub const M3U_RESOURCE_PATH: &str = "resource/m3u";
 pub const EPG_RESOURCE_PATH: &str = "resource/epg";
 
@@ -17,4 +25,5 @@ pub(in crate::repository) const USER_VOD_BOUQUET: &str = "vod_bouquet.json";
 pub(in crate::repository) const USER_SERIES_BOUQUET: &str = "series_bouquet.json";
 pub(in crate::repository) const API_USER_DB_FILE: &str = "api_user.db";
 pub(in crate::repository) const PATH_XTREAM: &str = "xtream";
+pub(in crate::repository) const PATH_XTREAM_API_CACHE: &str = "api_cache";
 
@@ -0,0 +1,228 @@
+use crate::model::AppConfig;
+use crate::repository::get_stream_sessions_path;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::error;
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::{IpAnonymization, StreamSessionDto, StreamSessionQuery};
+use shared::utils::anonymize_ips;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+const SESSION_FILE_EXTENSION: &str = "jsonl";
+const SESSION_FILE_DATE_FORMAT: &str = "%Y-%m-%d";
+const MAX_QUERY_SESSIONS: usize = 5_000;
+
+fn ts_to_date(ts: u64) -> NaiveDate {
+    i64::try_from(ts).ok()
+        .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0))
+        .unwrap_or_default()
+        .date_naive()
+}
+
+/// The sessions are appended to one file per day of their end, older days are removed as a whole.
+fn get_session_file_name(end: u64) -> String {
+    format!("{}.{SESSION_FILE_EXTENSION}", ts_to_date(end).format(SESSION_FILE_DATE_FORMAT))
+}
+
+fn parse_session_file_date(path: &Path) -> Option<NaiveDate> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some(SESSION_FILE_EXTENSION) {
+        return None;
+    }
+    NaiveDate::parse_from_str(path.file_stem()?.to_str()?, SESSION_FILE_DATE_FORMAT).ok()
+}
+
+/// Session files sorted by their day, oldest first.
+fn list_session_files(dir: &Path) -> Vec<(NaiveDate, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut files: Vec<(NaiveDate, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| parse_session_file_date(&path).map(|date| (date, path)))
+        .collect();
+    files.sort_by_key(|(date, _)| *date);
+    files
+}
+
+fn parse_sessions(content: &str) -> impl Iterator<Item=StreamSessionDto> + '_ {
+    content.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<StreamSessionDto>(line).ok())
+}
+
+fn serialize_sessions(sessions: &[StreamSessionDto]) -> String {
+    let mut content = String::new();
+    for session in sessions {
+        if let Ok(line) = serde_json::to_string(session) {
+            content.push_str(&line);
+            content.push('\n');
+        }
+    }
+    content
+}
+
+fn matches_query(session: &StreamSessionDto, query: &StreamSessionQuery) -> bool {
+    session.overlaps(query.from, query.to)
+        && query.username.as_deref().is_none_or(|username| session.username == username)
+}
+
+/// Rewrites the session file with the changed sessions, returns the number of changed sessions.
+async fn update_session_file<F>(app_config: &AppConfig, path: &Path, update: F) -> Result<usize, TuliproxError>
+where
+    F: Fn(Vec<StreamSessionDto>) -> (Vec<StreamSessionDto>, usize),
+{
+    let _file_lock = app_config.file_locks.write_lock(path).await;
+    let Ok(content) = tokio::fs::read_to_string(path).await else {
+        return Ok(0);
+    };
+    let (sessions, changed) = update(parse_sessions(&content).collect());
+    if changed > 0 {
+        tokio::fs::write(path, serialize_sessions(&sessions)).await
+            .map_err(|err| info_err!("Failed to write stream sessions {}: {err}", path.display()))?;
+    }
+    Ok(changed)
+}
+
+pub async fn append_stream_session(app_config: &AppConfig, session: &StreamSessionDto) -> Result<(), TuliproxError> {
+    let dir = get_stream_sessions_path(&app_config.config.load().working_dir);
+    tokio::fs::create_dir_all(&dir).await
+        .map_err(|err| info_err!("Failed to create stream session directory {}: {err}", dir.display()))?;
+    let path = dir.join(get_session_file_name(session.end));
+    let mut line = serde_json::to_string(session)
+        .map_err(|err| info_err!("Failed to serialize stream session: {err}"))?;
+    line.push('\n');
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&path).await
+        .map_err(|err| info_err!("Failed to open stream sessions {}: {err}", path.display()))?;
+    file.write_all(line.as_bytes()).await
+        .map_err(|err| info_err!("Failed to write stream sessions {}: {err}", path.display()))
+}
+
+/// Returns the sessions active within the time range, newest first.
+pub async fn query_stream_sessions(app_config: &AppConfig, query: &StreamSessionQuery) -> Vec<StreamSessionDto> {
+    let dir = get_stream_sessions_path(&app_config.config.load().working_dir);
+    // a session is stored at its end, it can't be in a file before the start of the range
+    let from_date = query.from.map(ts_to_date);
+    let mut sessions = vec![];
+    for (date, path) in list_session_files(&dir) {
+        if from_date.is_some_and(|from_date| date < from_date) {
+            continue;
+        }
+        let _file_lock = app_config.file_locks.read_lock(&path).await;
+        match tokio::fs::read_to_string(&path).await {
+            Ok(content) => sessions.extend(parse_sessions(&content).filter(|session| matches_query(session, query))),
+            Err(err) => error!("Failed to read stream sessions {}: {err}", path.display()),
+        }
+    }
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.start));
+    sessions.truncate(MAX_QUERY_SESSIONS);
+    sessions
+}
+
+/// Removes the session files older than the given days, returns the number of removed files.
+pub async fn rotate_stream_sessions(app_config: &AppConfig, keep_days: u16) -> usize {
+    let dir = get_stream_sessions_path(&app_config.config.load().working_dir);
+    let oldest_date = Utc::now().date_naive() - chrono::Days::new(u64::from(keep_days));
+    let mut removed = 0;
+    for (_, path) in list_session_files(&dir).into_iter().filter(|(date, _)| *date < oldest_date) {
+        let _file_lock = app_config.file_locks.write_lock(&path).await;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => removed += 1,
+            Err(err) => error!("Failed to remove stream sessions {}: {err}", path.display()),
+        }
+    }
+    removed
+}
+
+/// Pseudonymizes the client ips of the sessions ended before the given timestamp.
+pub async fn anonymize_stream_sessions(app_config: &AppConfig, older_than_ts: i64, mode: IpAnonymization) -> Result<usize, TuliproxError> {
+    let dir = get_stream_sessions_path(&app_config.config.load().working_dir);
+    let older_than = u64::try_from(older_than_ts).unwrap_or_default();
+    let mut changed = 0;
+    for (date, path) in list_session_files(&dir) {
+        if date > ts_to_date(older_than) {
+            break;
+        }
+        changed += update_session_file(app_config, &path, |mut sessions| {
+            let mut count = 0;
+            for session in sessions.iter_mut().filter(|session| session.end < older_than) {
+                if let Cow::Owned(client_ip) = anonymize_ips(&session.client_ip, mode) {
+                    if client_ip != session.client_ip {
+                        session.client_ip = client_ip;
+                        count += 1;
+                    }
+                }
+            }
+            (sessions, count)
+        }).await?;
+    }
+    Ok(changed)
+}
+
+/// Returns the number of removed sessions of the user.
+pub async fn remove_user_stream_sessions(app_config: &AppConfig, username: &str) -> Result<usize, TuliproxError> {
+    let dir = get_stream_sessions_path(&app_config.config.load().working_dir);
+    let mut removed = 0;
+    for (_, path) in list_session_files(&dir) {
+        removed += update_session_file(app_config, &path, |mut sessions| {
+            let count = sessions.len();
+            sessions.retain(|session| session.username != username);
+            let removed = count - sessions.len();
+            (sessions, removed)
+        }).await?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_session_file_name, matches_query, parse_session_file_date, parse_sessions, serialize_sessions};
+    use shared::model::{PlaylistItemType, StreamSessionDto, StreamSessionQuery, XtreamCluster};
+    use std::path::Path;
+
+    fn session(username: &str, start: u64, end: u64) -> StreamSessionDto {
+        StreamSessionDto {
+            username: username.to_string(),
+            target_id: 1,
+            virtual_id: 10,
+            item_type: PlaylistItemType::Live,
+            cluster: XtreamCluster::Live,
+            title: "Channel".to_string(),
+            group: "News".to_string(),
+            provider: "provider".to_string(),
+            client_ip: "10.1.2.3".to_string(),
+            country: None,
+            user_agent: "player".to_string(),
+            start,
+            end,
+            bytes: 1024,
+        }
+    }
+
+    #[test]
+    fn test_session_files() {
+        let file_name = get_session_file_name(1_700_000_000);
+        assert_eq!(file_name, "2023-11-14.jsonl");
+        assert!(parse_session_file_date(Path::new(&file_name)).is_some());
+        assert!(parse_session_file_date(Path::new("2023-11-14.json")).is_none());
+        assert!(parse_session_file_date(Path::new("sessions.jsonl")).is_none());
+
+        let sessions = vec![session("alice", 100, 200), session("bob", 150, 300)];
+        let content = format!("{}invalid\n", serialize_sessions(&sessions));
+        assert_eq!(parse_sessions(&content).collect::<Vec<_>>(), sessions);
+    }
+
+    #[test]
+    fn test_matches_query() {
+        let session = session("alice", 100, 200);
+        assert!(matches_query(&session, &StreamSessionQuery::default()));
+        assert!(matches_query(&session, &StreamSessionQuery { from: Some(150), to: Some(500), username: None }));
+        assert!(matches_query(&session, &StreamSessionQuery { from: Some(0), to: Some(100), username: Some("alice".to_string()) }));
+        assert!(!matches_query(&session, &StreamSessionQuery { from: Some(201), to: None, username: None }));
+        assert!(!matches_query(&session, &StreamSessionQuery { from: None, to: Some(99), username: None }));
+        assert!(!matches_query(&session, &StreamSessionQuery { from: None, to: None, username: Some("bob".to_string()) }));
+    }
+}
@@ -1,7 +1,8 @@
 // Import the new MediaQuality struct
 use crate::model::MediaQuality;
-use crate::model::{ApiProxyServerInfo, AppConfig, ProxyUserCredentials};
-use crate::model::{ConfigTarget, StrmTargetOutput};
+use crate::model::{AppConfig, ProxyUserCredentials};
+use crate::model::{ConfigTarget, StrmTargetOutput, TraktMatchedIds};
+use crate::ptt::ptt_parse_title;
 use crate::repository::storage::ensure_target_storage_path;
 use crate::repository::storage_const;
 use crate::utils::{async_file_reader, async_file_writer, normalize_string_path, truncate_filename,
@@ -12,16 +13,20 @@ use log::{error, trace};
 use regex::Regex;
 use serde::Serialize;
 use shared::error::{info_err_res, TuliproxError};
-use shared::model::{ClusterFlags, PlaylistGroup, PlaylistItem, PlaylistItemType, StreamProperties, StrmExportStyle};
+use shared::model::{ClusterFlags, PlaylistGroup, PlaylistItem, PlaylistItemType, StrmExportStyle};
 use shared::utils::{arc_str_option_serde, arc_str_serde, extract_extension_from_url, hash_bytes,
                     hash_string_as_hex, is_blank_optional_arc_str, truncate_string, ExportStyleConfig, CONSTANTS};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs::{create_dir_all, remove_dir, remove_file, File};
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
 use shared::model::UUIDType;
 
+const ARR_MOVIES_DIR: &str = "movies";
+const ARR_SERIES_DIR: &str = "series";
+
 /// Sanitizes a string to be safe for use as a file or directory name by
 /// following a strict "allow-list" approach and discarding invalid characters.
 fn sanitize_for_filename(text: &str, underscore_whitespace: bool) -> String {
@@ -312,6 +317,13 @@ struct StrmFile {
     file_name: Arc<String>,
     dir_path: PathBuf,
     strm_info: StrmItemInfo,
+    nfo: Option<StrmNfo>,
+}
+
+/// NFO sidecar file, the path is relative to the strm directory.
+struct StrmNfo {
+    file_path: PathBuf,
+    content: String,
 }
 
 /// Formats names according to the official Kodi documentation, with `TMDb` ID for better matching.
@@ -567,6 +579,116 @@ fn format_for_jellyfin(
     }
 }
 
+/// Names and ids for Sonarr and Radarr, the title is cleaned with the PTT parser.
+struct ArrMetadata {
+    title: String,
+    year: Option<u32>,
+    season: u32,
+    episode: u32,
+    tmdb: Option<u32>,
+    tvdb: Option<u32>,
+    imdb: Option<String>,
+}
+
+impl ArrMetadata {
+    /// The ids are taken from the provider, the title like `Movie {tmdb-123}` or the Trakt matches, in this order.
+    fn new(strm_item_info: &StrmItemInfo, tmdb: Option<u32>, trakt_matched_ids: Option<&TraktMatchedIds>) -> Self {
+        let is_series = matches!(strm_item_info.item_type, PlaylistItemType::Series | PlaylistItemType::LocalSeries);
+        let raw_title = if is_series { strm_item_info.series_name.as_ref().unwrap_or(&strm_item_info.title) } else { &strm_item_info.title };
+        let ptt = ptt_parse_title(raw_title);
+        let title = if ptt.title.trim().is_empty() { raw_title.trim().to_string() } else { ptt.title.trim().to_string() };
+        let year = ptt.year.or_else(|| {
+            strm_item_info.release_date.as_ref()
+                .and_then(|release_date| extract_match(release_date, &CONSTANTS.export_style_config.year).1)
+                .and_then(|year| year.parse::<u32>().ok())
+        });
+        let (season, episode) = if is_series {
+            // the episode title can carry the numbers when the provider info has none
+            let episode_ptt = ptt_parse_title(&strm_item_info.title);
+            (strm_item_info.season.or_else(|| episode_ptt.seasons.first().copied()).unwrap_or(1),
+             strm_item_info.episode.or_else(|| episode_ptt.episodes.first().copied()).unwrap_or(1))
+        } else {
+            (0, 0)
+        };
+        let trakt_ids = trakt_matched_ids.and_then(|ids| if is_series { ids.get_series(raw_title) } else { ids.get_movie(raw_title) });
+        Self {
+            title,
+            year,
+            season,
+            episode,
+            tmdb: tmdb.or(strm_item_info.tmdb_id).or(ptt.tmdb).or_else(|| trakt_ids.and_then(|ids| ids.tmdb)),
+            tvdb: ptt.tvdb.or_else(|| trakt_ids.and_then(|ids| ids.tvdb)),
+            imdb: trakt_ids.and_then(|ids| ids.imdb.clone()),
+        }
+    }
+}
+
+/// Formats names for Sonarr and Radarr. Movies and series get their own root folder,
+/// so they can be added as separate libraries. The ids are written into NFO files instead of the names.
+/// Movie: /movies/Movie Name (Year)/Movie Name (Year).strm
+/// Series: /series/Show Name (Year)/Season 01/Show Name - S01E01.strm
+fn format_for_arr(
+    strm_item_info: &StrmItemInfo,
+    metadata: &ArrMetadata,
+    separator: &str,
+) -> (PathBuf, String) {
+    let mut dir_path = PathBuf::new();
+    let sanitized_title = sanitize_for_filename(&metadata.title, false);
+    let year_string = metadata.year.map_or(String::new(), |y| format!("{separator}({y})"));
+
+    match strm_item_info.item_type {
+        PlaylistItemType::Video
+        | PlaylistItemType::LocalVideo => {
+            let base_name = format!("{sanitized_title}{year_string}");
+            dir_path.push(ARR_MOVIES_DIR);
+            dir_path.push(&base_name);
+            (dir_path, base_name)
+        }
+        PlaylistItemType::Series
+        | PlaylistItemType::LocalSeries => {
+            let (season_num, episode_num) = (metadata.season, metadata.episode);
+            dir_path.push(ARR_SERIES_DIR);
+            dir_path.push(format!("{sanitized_title}{year_string}"));
+            dir_path.push(format!("Season{separator}{season_num:02}"));
+            (dir_path, format!("{sanitized_title} - S{season_num:02}E{episode_num:02}"))
+        }
+        _ => (PathBuf::new(), sanitize_for_filename(&strm_item_info.title, separator == "_")),
+    }
+}
+
+/// Radarr reads the `movie.nfo` of the movie folder, Sonarr the `tvshow.nfo` of the series folder.
+fn create_arr_nfo(strm_item_info: &StrmItemInfo, metadata: &ArrMetadata, dir_path: &Path) -> Option<StrmNfo> {
+    let (tag, file_path) = match strm_item_info.item_type {
+        PlaylistItemType::Video
+        | PlaylistItemType::LocalVideo => ("movie", dir_path.join("movie.nfo")),
+        PlaylistItemType::Series
+        | PlaylistItemType::LocalSeries => ("tvshow", dir_path.parent()?.join("tvshow.nfo")),
+        _ => return None,
+    };
+    let default_id = if tag == "tvshow" && metadata.tvdb.is_some() { "tvdb" } else { "tmdb" };
+
+    let mut nfo = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n");
+    let _ = writeln!(nfo, "<{tag}>");
+    let _ = writeln!(nfo, "  <title>{}</title>", quick_xml::escape::escape(&metadata.title));
+    if let Some(year) = metadata.year {
+        let _ = writeln!(nfo, "  <year>{year}</year>");
+    }
+    if let Some(tmdb_id) = metadata.tmdb {
+        let _ = writeln!(nfo, "  <uniqueid type=\"tmdb\"{}>{tmdb_id}</uniqueid>", if default_id == "tmdb" { " default=\"true\"" } else { "" });
+        let _ = writeln!(nfo, "  <tmdbid>{tmdb_id}</tmdbid>");
+    }
+    if let Some(tvdb_id) = metadata.tvdb {
+        let _ = writeln!(nfo, "  <uniqueid type=\"tvdb\"{}>{tvdb_id}</uniqueid>", if default_id == "tvdb" { " default=\"true\"" } else { "" });
+        let _ = writeln!(nfo, "  <tvdbid>{tvdb_id}</tvdbid>");
+    }
+    if let Some(imdb_id) = metadata.imdb.as_ref() {
+        let _ = writeln!(nfo, "  <uniqueid type=\"imdb\">{}</uniqueid>", quick_xml::escape::escape(imdb_id));
+        let _ = writeln!(nfo, "  <imdbid>{}</imdbid>", quick_xml::escape::escape(imdb_id));
+    }
+    let _ = writeln!(nfo, "</{tag}>");
+    Some(StrmNfo { file_path, content: nfo })
+}
+
 /// Generates style-compliant directory and file names by dispatching
 /// the call to a dedicated formatting function for the respective style.
 fn style_based_rename(
@@ -587,6 +709,7 @@ fn style_based_rename(
         StrmExportStyle::Plex => format_for_plex(strm_item_info, tmdb_id, separator, flat),
         StrmExportStyle::Emby => format_for_emby(strm_item_info, tmdb_id, separator, flat),
         StrmExportStyle::Jellyfin => format_for_jellyfin(strm_item_info, tmdb_id, separator, flat),
+        StrmExportStyle::Arr => format_for_arr(strm_item_info, &ArrMetadata::new(strm_item_info, Some(tmdb_id).filter(|&id| id > 0), None), separator),
     }
 }
 
@@ -595,6 +718,7 @@ fn prepare_strm_files(
     new_playlist: &mut [PlaylistGroup],
     _root_path: &Path,
     strm_target_output: &StrmTargetOutput,
+    trakt_matched_ids: &TraktMatchedIds,
 ) -> Vec<StrmFile> {
     let channel_count = new_playlist
         .iter()
@@ -610,17 +734,26 @@ fn prepare_strm_files(
     for pg in new_playlist.iter_mut() {
         for pli in pg.channels.iter_mut().filter(|c| filter_strm_item(c)) {
             let strm_item_info = extract_item_info(pli);
+            let separator = if strm_target_output.underscore_whitespace { "_" } else { " " };
 
-            let (dir_path, strm_file_name) = style_based_rename(
-                &strm_item_info,
-                pli.get_tmdb_id(),
-                strm_target_output.style,
-                strm_target_output.underscore_whitespace,
-                strm_target_output.flat,
-            );
+            // the arr style needs the metadata for the NFO files too
+            let (dir_path, strm_file_name, nfo) = if strm_target_output.style == StrmExportStyle::Arr {
+                let metadata = ArrMetadata::new(&strm_item_info, pli.get_tmdb_id(), Some(trakt_matched_ids));
+                let (dir_path, strm_file_name) = format_for_arr(&strm_item_info, &metadata, separator);
+                let nfo = create_arr_nfo(&strm_item_info, &metadata, &dir_path);
+                (dir_path, strm_file_name, nfo)
+            } else {
+                let (dir_path, strm_file_name) = style_based_rename(
+                    &strm_item_info,
+                    pli.get_tmdb_id(),
+                    strm_target_output.style,
+                    strm_target_output.underscore_whitespace,
+                    strm_target_output.flat,
+                );
+                (dir_path, strm_file_name, None)
+            };
 
             // Conditionally generate the quality string based on the new config flag
-            let separator = if strm_target_output.underscore_whitespace { "_" } else { " " };
             let quality_string = get_quality(strm_target_output, pli, separator);
 
             let final_filename = format!("{strm_file_name}{quality_string}");
@@ -634,6 +767,7 @@ fn prepare_strm_files(
                 file_name: Arc::clone(&filename),
                 dir_path,
                 strm_info: strm_item_info,
+                nfo,
             });
         }
     }
@@ -656,8 +790,8 @@ fn prepare_strm_files(
 
                 // Apply the specific multi-version naming convention for the selected style.
                 let new_filename = match strm_target_output.style {
-                    // Plex, Emby, and Kodi all follow the `Filename - Suffix` pattern.
-                    StrmExportStyle::Plex | StrmExportStyle::Emby | StrmExportStyle::Kodi => {
+                    // Plex, Emby, Kodi and the arr style all follow the `Filename - Suffix` pattern.
+                    StrmExportStyle::Plex | StrmExportStyle::Emby | StrmExportStyle::Kodi | StrmExportStyle::Arr => {
                         format!("{base_filename}{version_separator}{version_label}")
                     }
 
@@ -676,20 +810,7 @@ fn prepare_strm_files(
 
 fn get_quality(strm_target_output: &StrmTargetOutput, pli: &PlaylistItem, separator: &str) -> String {
     if strm_target_output.add_quality_to_filename {
-        let (audio, video) = match pli.header.additional_properties.as_ref() {
-            None => (None, None),
-            Some(props) => {
-                match props {
-                    StreamProperties::Live(_)
-                    | StreamProperties::Series(_) => (None, None),
-                    StreamProperties::Video(video) =>
-                        video.details.as_ref().map_or_else(|| (None, None), |d| (d.audio.as_deref(), d.video.as_deref())),
-                    StreamProperties::Episode(episode) =>
-                        (episode.audio.as_deref(), episode.video.as_deref())
-                }
-            }
-        };
-        if let Some(media_quality) = MediaQuality::from_ffprobe_info(audio, video) {
+        if let Some(media_quality) = MediaQuality::from_playlist_item(pli) {
             let formatted = media_quality.format_for_filename(separator);
             if !formatted.is_empty() {
                 // Hard-coded separator for filename clarity.
@@ -705,6 +826,7 @@ pub async fn write_strm_playlist(
     target: &ConfigTarget,
     target_output: &StrmTargetOutput,
     new_playlist: &mut [PlaylistGroup],
+    trakt_matched_ids: &TraktMatchedIds,
 ) -> Result<(), TuliproxError> {
     if new_playlist.is_empty() {
         return Ok(());
@@ -718,7 +840,7 @@ pub async fn write_strm_playlist(
         return info_err_res!("Failed to get file path for {}",target_output.directory);
     };
 
-    let user_and_server_info = get_credentials_and_server_info(app_config, target_output.username.as_deref());
+    let user_and_base_url = get_credentials_and_base_url(app_config, target, target_output.username.as_deref());
     let normalized_dir = normalize_string_path(&target_output.directory);
     let strm_file_prefix = hash_string_as_hex(&normalized_dir);
     let strm_index_path =
@@ -744,8 +866,13 @@ pub async fn write_strm_playlist(
         new_playlist,
         &root_path,
         target_output,
+        trakt_matched_ids,
     );
     for strm_file in strm_files {
+        if let Some(nfo) = strm_file.nfo.as_ref() {
+            write_nfo_file(&root_path, nfo, &mut failed, &mut processed_strm).await;
+        }
+
         // file paths
         let output_path = truncate_filename(&root_path.join(&strm_file.dir_path), 255);
         let file_path = output_path.join(format!("{}.strm", truncate_string(&strm_file.file_name, 250)));
@@ -754,7 +881,7 @@ pub async fn write_strm_playlist(
         let relative_file_path = get_relative_path_str(&file_path, &root_path);
 
         // create content
-        let url = get_strm_url(target_force_redirect, user_and_server_info.as_ref(), &strm_file.strm_info);
+        let url = get_strm_url(target_force_redirect, user_and_base_url.as_ref(), &strm_file.strm_info);
         let mut content = target_output.strm_props.as_ref().map_or_else(Vec::new, std::clone::Clone::clone);
         content.push(url.to_string());
         let content_text = content.join("\r\n");
@@ -845,6 +972,31 @@ async fn write_strm_index_file(
     Ok(())
 }
 
+/// Episodes and versions share the NFO file of their folder, it is written once.
+async fn write_nfo_file(root_path: &Path, nfo: &StrmNfo, failed: &mut Vec<String>, processed: &mut HashSet<String>) {
+    let file_path = root_path.join(&nfo.file_path);
+    let relative_file_path = get_relative_path_str(&file_path, root_path);
+    if processed.contains(&relative_file_path) {
+        return;
+    }
+    let content_as_bytes = nfo.content.as_bytes();
+    if file_path.exists() && has_strm_file_same_hash(&file_path, hash_bytes(content_as_bytes)).await {
+        processed.insert(relative_file_path);
+        return;
+    }
+    if let Some(output_path) = file_path.parent() {
+        if !ensure_strm_file_directory(failed, output_path).await {
+            return;
+        }
+    }
+    match write_strm_file(&file_path, content_as_bytes, None).await {
+        Ok(()) => {
+            processed.insert(relative_file_path);
+        }
+        Err(err) => failed.push(err),
+    }
+}
+
 async fn ensure_strm_file_directory(failed: &mut Vec<String>, output_path: &Path) -> bool {
     if !output_path.exists() {
         if let Err(e) = create_dir_all(output_path).await {
@@ -900,14 +1052,15 @@ async fn has_strm_file_same_hash(file_path: &PathBuf, content_hash: UUIDType) ->
     false
 }
 
-fn get_credentials_and_server_info(
+fn get_credentials_and_base_url(
     cfg: &AppConfig,
+    target: &ConfigTarget,
     username: Option<&str>,
-) -> Option<(ProxyUserCredentials, ApiProxyServerInfo)> {
+) -> Option<(ProxyUserCredentials, String)> {
     let username = username?;
     let credentials = cfg.get_user_credentials(username)?;
-    let server_info = cfg.get_user_server_info(&credentials);
-    Some((credentials, server_info))
+    let base_url = cfg.get_target_base_url(target, &credentials);
+    Some((credentials, base_url))
 }
 
 async fn read_strm_file_index(strm_file_index_path: &Path) -> std::io::Result<HashSet<String>> {
@@ -923,10 +1076,10 @@ async fn read_strm_file_index(strm_file_index_path: &Path) -> std::io::Result<Ha
 
 fn get_strm_url(
     target_force_redirect: Option<&ClusterFlags>,
-    user_and_server_info: Option<&(ProxyUserCredentials, ApiProxyServerInfo)>,
+    user_and_base_url: Option<&(ProxyUserCredentials, String)>,
     str_item_info: &StrmItemInfo,
 ) -> Arc<str> {
-    let Some((user, server_info)) = user_and_server_info else { return str_item_info.url.clone(); };
+    let Some((user, base_url)) = user_and_base_url else { return str_item_info.url.clone(); };
 
     let redirect = user.proxy.is_redirect(str_item_info.item_type) || target_force_redirect.is_some_and(|f| f.has_cluster(str_item_info.item_type));
     if redirect {
@@ -947,8 +1100,7 @@ fn get_strm_url(
         let ext = extract_extension_from_url(url)
             .map_or_else(String::new, std::string::ToString::to_string);
         format!(
-            "{}/{stream_type}/{}/{}/{}{ext}",
-            server_info.get_base_url(),
+            "{base_url}/{stream_type}/{}/{}/{}{ext}",
             user.username,
             user.password,
             str_item_info.virtual_id
@@ -1092,4 +1244,57 @@ async fn remove_empty_dirs(root_path: PathBuf) {
 //     async fn test_empty_dirs() {
 //         remove_empty_dirs(PathBuf::from("/tmp/hello")).await;
 //     }
-// }
\ No newline at end of file
+// }
+#[cfg(test)]
+mod tests {
+    use super::{create_arr_nfo, format_for_arr, ArrMetadata, StrmItemInfo};
+    use shared::model::PlaylistItemType;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn item_info(title: &str, series_name: Option<&str>, item_type: PlaylistItemType) -> StrmItemInfo {
+        StrmItemInfo {
+            group: Arc::from("Group"),
+            title: Arc::from(title),
+            item_type,
+            provider_id: None,
+            virtual_id: 1,
+            input_name: Arc::from("input"),
+            url: Arc::from("http://localhost/1.mp4"),
+            series_name: series_name.map(Arc::from),
+            release_date: None,
+            season: None,
+            episode: None,
+            added: None,
+            tmdb_id: None,
+        }
+    }
+
+    #[test]
+    fn test_format_for_arr_series() {
+        let info = item_info("Breaking Bad S02E05", Some("Breaking Bad (2008) {tvdb-81189}"), PlaylistItemType::Series);
+        let metadata = ArrMetadata::new(&info, None, None);
+        assert_eq!(metadata.tvdb, Some(81189));
+        let (dir_path, file_name) = format_for_arr(&info, &metadata, " ");
+        assert_eq!(dir_path, PathBuf::from("series/Breaking Bad (2008)/Season 02"));
+        assert_eq!(file_name, "Breaking Bad - S02E05");
+
+        let nfo = create_arr_nfo(&info, &metadata, &dir_path).unwrap();
+        assert_eq!(nfo.file_path, PathBuf::from("series/Breaking Bad (2008)/tvshow.nfo"));
+        assert!(nfo.content.contains("<uniqueid type=\"tvdb\" default=\"true\">81189</uniqueid>"));
+    }
+
+    #[test]
+    fn test_format_for_arr_movie() {
+        let info = item_info("The Matrix 1999", None, PlaylistItemType::Video);
+        let metadata = ArrMetadata::new(&info, Some(603), None);
+        let (dir_path, file_name) = format_for_arr(&info, &metadata, " ");
+        assert_eq!(dir_path, PathBuf::from("movies/The Matrix (1999)"));
+        assert_eq!(file_name, "The Matrix (1999)");
+
+        let nfo = create_arr_nfo(&info, &metadata, &dir_path).unwrap();
+        assert_eq!(nfo.file_path, PathBuf::from("movies/The Matrix (1999)/movie.nfo"));
+        assert!(nfo.content.contains("<uniqueid type=\"tmdb\" default=\"true\">603</uniqueid>"));
+        assert!(nfo.content.contains("<title>The Matrix</title>"));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::model::AppConfig;
+use crate::repository::get_ui_preferences_path;
+use crate::utils::json_write_documents_to_file;
+use log::error;
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::UiPreferencesDto;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Preferences per web ui user.
+type UiPreferencesStore = HashMap<String, UiPreferencesDto>;
+
+fn read_store(path: &Path) -> UiPreferencesStore {
+    match std::fs::read(path) {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+            error!("Failed to parse ui preferences {}: {err}", path.display());
+            UiPreferencesStore::default()
+        }),
+        Err(_) => UiPreferencesStore::default(),
+    }
+}
+
+pub async fn load_ui_preferences(app_config: &AppConfig, username: &str) -> UiPreferencesDto {
+    let path = get_ui_preferences_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    read_store(&path).remove(username).unwrap_or_default()
+}
+
+/// Merges the given settings into the stored preferences of the user and returns the result.
+pub async fn update_ui_preferences(app_config: &AppConfig, username: &str, update: UiPreferencesDto) -> Result<UiPreferencesDto, TuliproxError> {
+    let path = get_ui_preferences_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut store = read_store(&path);
+    let preferences = store.entry(username.to_string()).or_default();
+    preferences.merge(update);
+    let result = preferences.clone();
+    json_write_documents_to_file(&path, &store).await
+        .map_err(|err| info_err!("Failed to write ui preferences {}: {err}", path.display()))?;
+    Ok(result)
+}
+
+/// Returns true if preferences were stored for the user.
+pub async fn remove_ui_preferences(app_config: &AppConfig, username: &str) -> Result<bool, TuliproxError> {
+    let path = get_ui_preferences_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut store = read_store(&path);
+    if store.remove(username).is_none() {
+        return Ok(false);
+    }
+    json_write_documents_to_file(&path, &store).await
+        .map_err(|err| info_err!("Failed to write ui preferences {}: {err}", path.display()))?;
+    Ok(true)
+}
@@ -8,7 +8,7 @@ use crate::utils;
 use crate::utils::{file_exists_async, json_write_documents_to_file};
 use chrono::Local;
 use log::error;
-use shared::model::{PlaylistBouquetDto, PlaylistClusterBouquetDto, ProxyType, ProxyUserStatus, TargetType, XtreamCluster};
+use shared::model::{AccountSharingLimitsDto, PlaylistBouquetDto, PlaylistClusterBouquetDto, ProxyType, ProxyUserStatus, TargetType, XtreamCluster};
 use std::collections::{HashMap, HashSet};
 use std::io::Error;
 use std::path::{Path, PathBuf};
@@ -45,6 +45,89 @@ impl StoredProxyUserCredentialsDeprecated {
             status: stored.status,
             ui_enabled: stored.ui_enabled,
             comment: None,
+            allowed_countries: Vec::new(),
+            blocked_countries: Vec::new(),
+            account_sharing: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredProxyUserCredentialsWithoutCountries {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+}
+
+impl StoredProxyUserCredentialsWithoutCountries {
+    fn to(stored: &StoredProxyUserCredentialsWithoutCountries) -> ProxyUserCredentials {
+        ProxyUserCredentials {
+            username: stored.username.clone(),
+            password: stored.password.clone(),
+            token: stored.token.clone(),
+            proxy: stored.proxy,
+            server: stored.server.clone(),
+            epg_timeshift: stored.epg_timeshift.clone(),
+            created_at: stored.created_at,
+            exp_date: stored.exp_date,
+            max_connections: stored.max_connections.unwrap_or_default(),
+            status: stored.status,
+            ui_enabled: stored.ui_enabled,
+            comment: stored.comment.clone(),
+            allowed_countries: Vec::new(),
+            blocked_countries: Vec::new(),
+            account_sharing: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredProxyUserCredentialsWithoutAccountSharing {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub allowed_countries: Vec<String>,
+    pub blocked_countries: Vec<String>,
+}
+
+impl StoredProxyUserCredentialsWithoutAccountSharing {
+    fn to(stored: &StoredProxyUserCredentialsWithoutAccountSharing) -> ProxyUserCredentials {
+        ProxyUserCredentials {
+            username: stored.username.clone(),
+            password: stored.password.clone(),
+            token: stored.token.clone(),
+            proxy: stored.proxy,
+            server: stored.server.clone(),
+            epg_timeshift: stored.epg_timeshift.clone(),
+            created_at: stored.created_at,
+            exp_date: stored.exp_date,
+            max_connections: stored.max_connections.unwrap_or_default(),
+            status: stored.status,
+            ui_enabled: stored.ui_enabled,
+            comment: stored.comment.clone(),
+            allowed_countries: stored.allowed_countries.clone(),
+            blocked_countries: stored.blocked_countries.clone(),
+            account_sharing: None,
         }
     }
 }
@@ -68,6 +151,9 @@ struct StoredProxyUserCredentials {
     pub status: Option<ProxyUserStatus>,
     pub ui_enabled: bool,
     pub comment: Option<String>,
+    pub allowed_countries: Vec<String>,
+    pub blocked_countries: Vec<String>,
+    pub account_sharing: Option<AccountSharingLimitsDto>,
 }
 
 impl StoredProxyUserCredentials {
@@ -86,6 +172,9 @@ impl StoredProxyUserCredentials {
             status: proxy.status,
             ui_enabled: proxy.ui_enabled,
             comment: proxy.comment.clone(),
+            allowed_countries: proxy.allowed_countries.clone(),
+            blocked_countries: proxy.blocked_countries.clone(),
+            account_sharing: proxy.account_sharing.clone(),
         }
     }
 
@@ -103,6 +192,9 @@ impl StoredProxyUserCredentials {
             status: stored.status,
             ui_enabled: stored.ui_enabled,
             comment: stored.comment.clone(),
+            allowed_countries: stored.allowed_countries.clone(),
+            blocked_countries: stored.blocked_countries.clone(),
+            account_sharing: stored.account_sharing.clone(),
         }
     }
 }
@@ -170,62 +262,76 @@ pub async fn store_api_user(cfg: &AppConfig, target_users: &[TargetUser]) -> Res
     result
 }
 
-// TODO remove me if we get stable on user_db
-pub async fn load_api_user_deprecated(cfg: &AppConfig) -> Result<Vec<TargetUser>, Error> {
-    let path = get_api_user_db_path(cfg);
-    let lock = cfg.file_locks.read_lock(&path).await;
-    let user_tree = BPlusTree::<String, StoredProxyUserCredentialsDeprecated>::load(&path)?;
-    drop(lock);
+fn group_target_users<'a>(stored_users: impl Iterator<Item = (&'a str, ProxyUserCredentials)>) -> Vec<TargetUser> {
     let mut target_users: HashMap<String, TargetUser> = HashMap::new();
-    for (_uname, stored_user) in &user_tree {
-        let proxy_user: ProxyUserCredentials = StoredProxyUserCredentialsDeprecated::to(stored_user);
-        let target_name = stored_user.target.clone();
-        match target_users.entry(target_name) {
+    for (target_name, proxy_user) in stored_users {
+        match target_users.entry(target_name.to_string()) {
             std::collections::hash_map::Entry::Occupied(mut entry) => {
                 let target = entry.get_mut();
                 target.credentials.push(proxy_user);
             }
             std::collections::hash_map::Entry::Vacant(entry) => {
                 entry.insert(TargetUser {
-                    target: stored_user.target.clone(),
+                    target: target_name.to_string(),
                     credentials: vec![proxy_user],
                 });
             }
         }
     }
-    Ok(target_users.into_values().collect())
+    target_users.into_values().collect()
+}
+
+// TODO remove me if we get stable on user_db
+pub async fn load_api_user_deprecated(cfg: &AppConfig) -> Result<Vec<TargetUser>, Error> {
+    let path = get_api_user_db_path(cfg);
+    let lock = cfg.file_locks.read_lock(&path).await;
+    if let Ok(user_tree) = BPlusTree::<String, StoredProxyUserCredentialsWithoutAccountSharing>::load(&path) {
+        drop(lock);
+        return Ok(group_target_users(user_tree.iter().map(|(_uname, stored_user)|
+            (stored_user.target.as_str(), StoredProxyUserCredentialsWithoutAccountSharing::to(stored_user)))));
+    }
+    if let Ok(user_tree) = BPlusTree::<String, StoredProxyUserCredentialsWithoutCountries>::load(&path) {
+        drop(lock);
+        return Ok(group_target_users(user_tree.iter().map(|(_uname, stored_user)|
+            (stored_user.target.as_str(), StoredProxyUserCredentialsWithoutCountries::to(stored_user)))));
+    }
+    let user_tree = BPlusTree::<String, StoredProxyUserCredentialsDeprecated>::load(&path)?;
+    drop(lock);
+    Ok(group_target_users(user_tree.iter().map(|(_uname, stored_user)|
+        (stored_user.target.as_str(), StoredProxyUserCredentialsDeprecated::to(stored_user)))))
 }
 
 
 pub async fn load_api_user(cfg: &AppConfig) -> Result<Vec<TargetUser>, Error> {
     let path = get_api_user_db_path(cfg);
     let lock = cfg.file_locks.read_lock(&path).await;
-    let Ok(user_tree) = BPlusTree::<String, StoredProxyUserCredentials>::load(&path) else { return load_api_user_deprecated(cfg).await };
+    let Ok(user_tree) = BPlusTree::<String, StoredProxyUserCredentials>::load(&path) else {
+        drop(lock);
+        return load_api_user_deprecated(cfg).await;
+    };
     drop(lock);
-    let mut target_users: HashMap<String, TargetUser> = HashMap::new();
-    for (_uname, stored_user) in &user_tree {
-        let proxy_user: ProxyUserCredentials = StoredProxyUserCredentials::to(stored_user);
-        let target_name = stored_user.target.clone();
-        match target_users.entry(target_name) {
-            std::collections::hash_map::Entry::Occupied(mut entry) => {
-                let target = entry.get_mut();
-                target.credentials.push(proxy_user);
-            }
-            std::collections::hash_map::Entry::Vacant(entry) => {
-                entry.insert(TargetUser {
-                    target: stored_user.target.clone(),
-                    credentials: vec![proxy_user],
-                });
-            }
-        }
-    }
-    Ok(target_users.into_values().collect())
+    Ok(group_target_users(user_tree.iter().map(|(_uname, stored_user)|
+        (stored_user.target.as_str(), StoredProxyUserCredentials::to(stored_user)))))
 }
 
 pub fn get_user_storage_path(cfg: &Config, username: &str) -> Option<PathBuf> {
     cfg.user_config_dir.as_ref().and_then(|ucd| utils::get_file_path(ucd, Some(std::path::PathBuf::from(username))))
 }
 
+/// Removes the stored bouquets of the user, returns true if there was stored data.
+pub async fn remove_user_storage(cfg: &Config, username: &str) -> Result<bool, Error> {
+    if username.is_empty() || username == "." || username == ".." || username.contains(['/', '\\']) {
+        return Err(Error::other(format!("Invalid username {username}")));
+    }
+    match get_user_storage_path(cfg, username) {
+        Some(path) if file_exists_async(&path).await => {
+            tokio::fs::remove_dir_all(&path).await?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
 fn ensure_user_storage_path(cfg: &Config, username: &str) -> Option<PathBuf> {
     if let Some(path) = get_user_storage_path(cfg, username) {
         if !path.exists() && std::fs::create_dir_all(&path).is_err() {
@@ -450,6 +556,9 @@ mod tests {
                         status: Some(ProxyUserStatus::Active),
                         ui_enabled: true,
                         comment: None,
+                        allowed_countries: Vec::new(),
+                        blocked_countries: Vec::new(),
+                        account_sharing: None,
                     },
                     ProxyUserCredentials {
                         username: "Test2".to_string(),
@@ -464,6 +573,9 @@ mod tests {
                         status: Some(ProxyUserStatus::Expired),
                         ui_enabled: true,
                         comment: None,
+                        allowed_countries: Vec::new(),
+                        blocked_countries: Vec::new(),
+                        account_sharing: None,
                     },
                     ProxyUserCredentials {
                         username: "Test3".to_string(),
@@ -478,6 +590,9 @@ mod tests {
                         status: Some(ProxyUserStatus::Expired),
                         ui_enabled: true,
                         comment: None,
+                        allowed_countries: Vec::new(),
+                        blocked_countries: Vec::new(),
+                        account_sharing: None,
                     },
                     ProxyUserCredentials {
                         username: "Test4".to_string(),
@@ -492,6 +607,9 @@ mod tests {
                         status: Some(ProxyUserStatus::Expired),
                         ui_enabled: true,
                         comment: None,
+                        allowed_countries: Vec::new(),
+                        blocked_countries: Vec::new(),
+                        account_sharing: None,
                     }
                 ],
             };
@@ -0,0 +1,57 @@
+use crate::model::AppConfig;
+use crate::repository::{get_web_push_key_path, get_web_push_subscriptions_path};
+use crate::utils::{json_write_documents_to_file, VapidKey};
+use log::error;
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::WebPushSubscriptionDto;
+use std::path::Path;
+
+fn read_subscriptions(path: &Path) -> Vec<WebPushSubscriptionDto> {
+    match std::fs::read(path) {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+            error!("Failed to parse web push subscriptions {}: {err}", path.display());
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+async fn write_subscriptions(path: &Path, subscriptions: &[WebPushSubscriptionDto]) -> Result<(), TuliproxError> {
+    json_write_documents_to_file(path, &subscriptions).await
+        .map_err(|err| info_err!("Failed to write web push subscriptions {}: {err}", path.display()))
+}
+
+pub async fn load_web_push_key(app_config: &AppConfig) -> Result<VapidKey, TuliproxError> {
+    let path = get_web_push_key_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    VapidKey::load_or_create(&path)
+}
+
+pub async fn load_web_push_subscriptions(app_config: &AppConfig) -> Vec<WebPushSubscriptionDto> {
+    let path = get_web_push_subscriptions_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.read_lock(&path).await;
+    read_subscriptions(&path)
+}
+
+/// Stores the subscription, an existing subscription with the same endpoint is replaced.
+pub async fn add_web_push_subscription(app_config: &AppConfig, subscription: WebPushSubscriptionDto) -> Result<(), TuliproxError> {
+    let path = get_web_push_subscriptions_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut subscriptions = read_subscriptions(&path);
+    subscriptions.retain(|s| s.endpoint != subscription.endpoint);
+    subscriptions.push(subscription);
+    write_subscriptions(&path, &subscriptions).await
+}
+
+pub async fn remove_web_push_subscriptions(app_config: &AppConfig, endpoints: &[String]) -> Result<(), TuliproxError> {
+    let path = get_web_push_subscriptions_path(&app_config.config.load().working_dir);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    let mut subscriptions = read_subscriptions(&path);
+    let count = subscriptions.len();
+    subscriptions.retain(|s| !endpoints.contains(&s.endpoint));
+    if subscriptions.len() == count {
+        return Ok(());
+    }
+    write_subscriptions(&path, &subscriptions).await
+}
@@ -8,17 +8,80 @@ use crate::utils::FileReadGuard;
 use log::error;
 use shared::error::{TuliproxError, info_err, info_err_res};
 use shared::model::{PlaylistItemType, TargetType, XtreamCluster, XtreamMappingOptions, XtreamPlaylistItem};
+use indexmap::IndexMap;
 use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use crate::api::model::PlaylistQueryCache;
 use crate::repository::get_file_path_for_db_index;
 
+fn open_xtream_reader(xtream_path: &Path) -> Result<PlaylistIteratorReader<XtreamPlaylistItem>, TuliproxError> {
+    let index_path = get_file_path_for_db_index(xtream_path);
+    let reader = if index_path.exists() {
+         let query = BPlusTreeQuery::<u32, XtreamPlaylistItem>::try_new(xtream_path)
+            .map_err(|err| info_err!("Could not open BPlusTreeQuery {xtream_path:?} - {err}"))?;
+         match query.disk_iter_sorted() {
+             Ok(reader) => PlaylistIteratorReader::Sorted(reader),
+             Err(err) => {
+                 error!("Sorted index error, falling back to unsorted: {err}");
+                 // Query was consumed, re-open for fallback
+                 let query = BPlusTreeQuery::<u32, XtreamPlaylistItem>::try_new(xtream_path)
+                     .map_err(|err| info_err!("Could not open BPlusTreeQuery {xtream_path:?} - {err}"))?;
+                 PlaylistIteratorReader::Unsorted(query.disk_iter())
+             }
+         }
+    } else {
+         let query = BPlusTreeQuery::<u32, XtreamPlaylistItem>::try_new(xtream_path)
+             .map_err(|err| info_err!("Could not open BPlusTreeQuery {xtream_path:?} - {err}"))?;
+         PlaylistIteratorReader::Unsorted(query.disk_iter())
+    };
+    Ok(reader)
+}
+
+/// Loads the streams of all categories of the cluster into the `query_cache` with a single read of the playlist.
+/// Returns the number of cached categories.
+pub async fn xtream_load_cached_category_items(
+    cluster: XtreamCluster,
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    query_cache: &PlaylistQueryCache,
+) -> Result<usize, TuliproxError> {
+    let generation = query_cache.generation();
+    let config = app_config.config.load();
+    let storage_path = xtream_get_storage_path(&config, target.name.as_str())
+        .ok_or_else(|| info_err!("Failed to find xtream storage for target {}", &target.name))?;
+    let xtream_path = xtream_get_file_path(&storage_path, cluster);
+    if !xtream_path.exists() {
+        return Ok(0);
+    }
+    let _file_lock = app_config.file_locks.read_lock(&xtream_path).await;
+    let mut categories: IndexMap<u32, Vec<XtreamPlaylistItem>> = IndexMap::new();
+    for entry in open_xtream_reader(&xtream_path)? {
+        let (_, item) = entry.map_err(|err| info_err!("Failed to read xtream playlist {xtream_path:?} - {err}"))?;
+        if item.category_id > 0 && XtreamPlaylistIterator::matches_filters(cluster, None, &item) {
+            categories.entry(item.category_id).or_default().push(item);
+        }
+    }
+    let count = categories.len();
+    for (category_id, items) in categories {
+        query_cache.put_category_items(&target.name, cluster, category_id, generation, items);
+    }
+    Ok(count)
+}
+
+enum XtreamItemSource {
+    Disk(Box<PlaylistIteratorReader<XtreamPlaylistItem>>),
+    Cached(Arc<Vec<XtreamPlaylistItem>>, usize),
+}
+
 pub struct XtreamPlaylistIterator {
-    reader: PlaylistIteratorReader<XtreamPlaylistItem>,
+    source: XtreamItemSource,
     options: XtreamMappingOptions,
     cluster: XtreamCluster,
     // Use parsed numeric filter to avoid per-item String allocations (no to_string per check)
     filter_ids: Option<HashSet<u32>>,
     lookup_item: Option<(XtreamPlaylistItem, bool)>,  // this is for filtered iteration
-    _file_lock: FileReadGuard,
+    _file_lock: Option<FileReadGuard>,
 }
 
 impl XtreamPlaylistIterator {
@@ -29,10 +92,43 @@ impl XtreamPlaylistIterator {
         category_id: Option<u32>,
         user: &ProxyUserCredentials,
     ) -> Result<Self, TuliproxError> {
+        Self::create(cluster, app_config, target, category_id, user, None).await
+    }
+
+    /// Like `new`, but category listings are served from and stored in the `query_cache`.
+    pub async fn new_cached(
+        cluster: XtreamCluster,
+        app_config: &AppConfig,
+        target: &ConfigTarget,
+        category_id: Option<u32>,
+        user: &ProxyUserCredentials,
+        query_cache: &PlaylistQueryCache,
+    ) -> Result<Self, TuliproxError> {
+        Self::create(cluster, app_config, target, category_id, user, Some(query_cache)).await
+    }
+
+    async fn create(
+        cluster: XtreamCluster,
+        app_config: &AppConfig,
+        target: &ConfigTarget,
+        category_id: Option<u32>,
+        user: &ProxyUserCredentials,
+        query_cache: Option<&PlaylistQueryCache>,
+    ) -> Result<Self, TuliproxError> {
 
         // TODO use playlist memory cache and keep sorted
 
         let xtream_output = target.get_xtream_output().ok_or_else(|| info_err!("Unexpected: xtream output required for target {}", target.name))?;
+        let cached_category = query_cache.zip(category_id.filter(|cid| *cid > 0));
+        if let Some((cache, cid)) = cached_category {
+            if let Some(items) = cache.get_category_items(&target.name, cluster, cid) {
+                let base_url = app_config.get_target_base_url(target, user);
+                let options = xtream_mapping_option_from_target_options(target, xtream_output, app_config, user, Some(base_url.as_str()));
+                return Ok(Self::from_cached(items, options, cluster));
+            }
+        }
+        let generation = query_cache.map(PlaylistQueryCache::generation).unwrap_or_default();
+
         let config = app_config.config.load();
         if let Some(storage_path) = xtream_get_storage_path(&config, target.name.as_str()) {
             let xtream_path = xtream_get_file_path(&storage_path, cluster);
@@ -40,29 +136,10 @@ impl XtreamPlaylistIterator {
                 return info_err_res!("No {cluster} entries found for target {}", &target.name);
             }
             let file_lock = app_config.file_locks.read_lock(&xtream_path).await;
+            let reader = open_xtream_reader(&xtream_path)?;
 
-            let index_path = get_file_path_for_db_index(&xtream_path);
-            let reader = if index_path.exists() {
-                 let query = BPlusTreeQuery::<u32, XtreamPlaylistItem>::try_new(&xtream_path)
-                    .map_err(|err| info_err!("Could not open BPlusTreeQuery {xtream_path:?} - {err}"))?;
-                 match query.disk_iter_sorted() {
-                     Ok(reader) => PlaylistIteratorReader::Sorted(reader),
-                     Err(err) => {
-                         error!("Sorted index error, falling back to unsorted: {err}");
-                         // Query was consumed, re-open for fallback
-                         let query = BPlusTreeQuery::<u32, XtreamPlaylistItem>::try_new(&xtream_path)
-                             .map_err(|err| info_err!("Could not open BPlusTreeQuery {xtream_path:?} - {err}"))?;
-                         PlaylistIteratorReader::Unsorted(query.disk_iter())
-                     }
-                 }
-            } else {
-                 let query = BPlusTreeQuery::<u32, XtreamPlaylistItem>::try_new(&xtream_path)
-                     .map_err(|err| info_err!("Could not open BPlusTreeQuery {xtream_path:?} - {err}"))?;
-                 PlaylistIteratorReader::Unsorted(query.disk_iter())
-            };
-
-            let server_info = app_config.get_user_server_info(user);
-            let options = xtream_mapping_option_from_target_options(target, xtream_output, app_config, user, Some(server_info.get_base_url().as_str()));
+            let base_url = app_config.get_target_base_url(target, user);
+            let options = xtream_mapping_option_from_target_options(target, xtream_output, app_config, user, Some(base_url.as_str()));
 
             let filter = user_get_bouquet_filter(&config, &user.username, category_id, TargetType::Xtream, cluster).await;
             // Parse bouquet filter (strings) once into u32 set to minimize per-item allocations
@@ -75,19 +152,39 @@ impl XtreamPlaylistIterator {
                 }).collect()
             });
 
-            Ok(Self {
-                reader,
+            let mut iter = Self {
+                source: XtreamItemSource::Disk(Box::new(reader)),
                 options,
                 cluster,
                 filter_ids,
-                _file_lock: file_lock,
+                _file_lock: Some(file_lock),
                 lookup_item: None,
-            })
+            };
+
+            if let Some((cache, cid)) = cached_category {
+                let items: Vec<XtreamPlaylistItem> = std::iter::from_fn(|| iter.find_next_matching().map(|(item, _)| item)).collect();
+                let items = cache.put_category_items(&target.name, cluster, cid, generation, items);
+                return Ok(Self::from_cached(items, iter.options, cluster));
+            }
+
+            Ok(iter)
         } else {
             info_err_res!("Failed to find xtream storage for target {}", &target.name)
         }
     }
 
+    fn from_cached(items: Arc<Vec<XtreamPlaylistItem>>, options: XtreamMappingOptions, cluster: XtreamCluster) -> Self {
+        Self {
+            source: XtreamItemSource::Cached(items, 0),
+            options,
+            cluster,
+            // cached items are already filtered
+            filter_ids: None,
+            _file_lock: None,
+            lookup_item: None,
+        }
+    }
+
     fn matches_filters(cluster: XtreamCluster, filter_ids: Option<&HashSet<u32>>, item: &XtreamPlaylistItem) -> bool {
         // We can't serve episodes within series
         if cluster == XtreamCluster::Series
@@ -110,8 +207,15 @@ impl XtreamPlaylistIterator {
         let filter_ids = self.filter_ids.as_ref();
         let cluster = self.cluster;
 
+        if let XtreamItemSource::Cached(items, pos) = &mut self.source {
+            let item = items.get(*pos).cloned();
+            *pos += 1;
+            return item.map(|item| (item, false));
+        }
+
+        let XtreamItemSource::Disk(reader) = &mut self.source else { return None; };
         loop {
-            match self.reader.next() {
+            match reader.next() {
                 Some(Ok((_, item))) => {
                     if Self::matches_filters(cluster, filter_ids, &item) {
                         return Some((item, false));
@@ -161,9 +265,10 @@ impl XtreamPlaylistJsonIterator {
         target: &ConfigTarget,
         category_id: Option<u32>,
         user: &ProxyUserCredentials,
+        query_cache: &PlaylistQueryCache,
     ) -> Result<Self, TuliproxError> {
         Ok(Self {
-            inner: XtreamPlaylistIterator::new(cluster, config, target, category_id, user).await?
+            inner: XtreamPlaylistIterator::new_cached(cluster, config, target, category_id, user, query_cache).await?
         })
     }
 }
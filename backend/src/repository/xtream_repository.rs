@@ -1,8 +1,10 @@
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
+use crate::api::model::{PlaylistQueryCache, XtreamCategoryEntry, XtreamResponseKey};
 use crate::model::{ConfigInput, PlaylistXtreamCategory};
 use crate::model::{AppConfig, ProxyUserCredentials};
 use crate::model::{Config, ConfigTarget};
-use crate::repository::bplustree::{BPlusTree, BPlusTreeQuery, BPlusTreeUpdate};
+use crate::repository::bplustree::{BPlusTree, BPlusTreeBulkBuilder, BPlusTreeQuery, BPlusTreeUpdate};
 use crate::repository::playlist_scratch::PlaylistScratch;
 use crate::repository::storage::{get_input_storage_path, get_target_id_mapping_file, get_target_storage_path};
 use crate::repository::storage_const;
@@ -14,7 +16,7 @@ use crate::utils::FileReadGuard;
 use bytes::Bytes;
 use futures::{stream, Stream, StreamExt};
 use indexmap::IndexMap;
-use log::error;
+use log::{debug, error};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use shared::error::{info_err_res, notify_err, string_to_io_error, TuliproxError};
@@ -87,24 +89,19 @@ async fn write_playlists_to_file(
     storage_key: StorageKey,
     collections: Vec<(XtreamCluster, Vec<XtreamPlaylistItem>)>,
 ) -> Result<(), TuliproxError> {
-    for (cluster, playlist) in collections {
+    for (cluster, mut playlist) in collections {
         if playlist.is_empty() {
             continue;
         }
         let xtream_path = xtream_get_file_path(storage_path, cluster);
         {
             let _file_lock = app_config.file_locks.write_lock(&xtream_path).await;
-            let mut tree = BPlusTree::new();
-            for item in playlist {
-                tree.insert(match storage_key {
-                    StorageKey::VirtualId => item.virtual_id,
-                    StorageKey::ProviderId => item.provider_id,
-                }, item);
-            }
+            BPlusTreeBulkBuilder::store_items(&xtream_path, &mut playlist, |item: &XtreamPlaylistItem| match storage_key {
+                StorageKey::VirtualId => item.virtual_id,
+                StorageKey::ProviderId => item.provider_id,
+            }).map_err(|err| cant_write_result!(&xtream_path, err))?;
             if with_index {
-                tree.store_with_index(&xtream_path, |pli| pli.source_ordinal).map_err(|err| cant_write_result!(&xtream_path, err))?;
-            } else {
-                tree.store(&xtream_path).map_err(|err| cant_write_result!(&xtream_path, err))?;
+                BPlusTree::<u32, XtreamPlaylistItem>::store_index(&xtream_path, |pli| pli.source_ordinal).map_err(|err| cant_write_result!(&xtream_path, err))?;
             }
         }
     }
@@ -218,6 +215,132 @@ pub fn xtream_get_storage_path(cfg: &Config, target_name: &str) -> Option<PathBu
     get_target_storage_path(cfg, target_name).map(|target_path| target_path.join(PathBuf::from(storage_const::PATH_XTREAM)))
 }
 
+/// Upper bound of cached stream list responses on disk per target, the oldest files are removed first.
+const XTREAM_API_CACHE_MAX_FILES: usize = 1024;
+
+fn xtream_get_response_cache_dir(cfg: &Config, target_name: &str) -> Option<PathBuf> {
+    xtream_get_storage_path(cfg, target_name).map(|path| path.join(storage_const::PATH_XTREAM_API_CACHE))
+}
+
+/// The file names start with the hash of the username, the files of a user can be removed without an index.
+fn xtream_get_response_cache_user_prefix(username: &str) -> String {
+    let hash = blake3::hash(username.as_bytes()).to_hex();
+    concat_string!(&hash.as_str()[..16], "_")
+}
+
+fn xtream_get_response_cache_path(cfg: &Config, key: &XtreamResponseKey) -> Option<PathBuf> {
+    let category = key.category_id.map(|cid| cid.to_string()).unwrap_or_default();
+    let hash = blake3::hash(concat_string!(&key.action, "\0", &category).as_bytes()).to_hex();
+    let file_name = concat_string!(&xtream_get_response_cache_user_prefix(&key.username), hash.as_str(), ".json");
+    xtream_get_response_cache_dir(cfg, &key.target_name).map(|path| path.join(file_name))
+}
+
+async fn xtream_list_cached_response_files(dir: &Path) -> Vec<(PathBuf, std::time::SystemTime)> {
+    let mut files = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else { return files; };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            if let Ok(modified) = entry.metadata().await.and_then(|meta| meta.modified()) {
+                files.push((path, modified));
+            }
+        }
+    }
+    files
+}
+
+async fn xtream_remove_cached_response_file(path: &Path) {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => error!("Failed to remove xtream response cache file {}: {err}", path.display()),
+    }
+}
+
+/// Removes the oldest files until at most `max_files` are left.
+async fn xtream_trim_cached_responses(dir: &Path, max_files: usize) {
+    let mut files = xtream_list_cached_response_files(dir).await;
+    if files.len() <= max_files {
+        return;
+    }
+    files.sort_by_key(|(_, modified)| *modified);
+    let remove_count = files.len() - max_files;
+    for (path, _) in files.iter().take(remove_count) {
+        xtream_remove_cached_response_file(path).await;
+    }
+}
+
+async fn xtream_remove_user_cached_responses(dir: &Path, username: &str) {
+    let prefix = xtream_get_response_cache_user_prefix(username);
+    for (path, _) in xtream_list_cached_response_files(dir).await {
+        if path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with(&prefix)) {
+            xtream_remove_cached_response_file(&path).await;
+        }
+    }
+}
+
+/// Loads a cached stream list response with its creation time if it is younger than `max_age_secs`.
+/// Expired files are kept as stale fallback, they are overwritten on store and removed when the target is processed,
+/// the bouquet or the credentials of the user change or the cache exceeds `XTREAM_API_CACHE_MAX_FILES`.
+pub async fn xtream_load_cached_response(cfg: &Config, key: &XtreamResponseKey, max_age_secs: u64, now: u64) -> Option<(Bytes, u64)> {
+    let path = xtream_get_response_cache_path(cfg, key)?;
+    let modified = tokio::fs::metadata(&path).await.ok()?.modified().ok()?;
+    let created = modified.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+    if now.saturating_sub(created) >= max_age_secs {
+        return None;
+    }
+    tokio::fs::read(&path).await.ok().map(|content| (Bytes::from(content), created))
+}
+
+pub async fn xtream_store_cached_response(cfg: &Config, key: &XtreamResponseKey, content: &[u8]) {
+    let Some(path) = xtream_get_response_cache_path(cfg, key) else { return; };
+    if let Some(parent) = path.parent() {
+        if let Err(err) = tokio::fs::create_dir_all(parent).await {
+            error!("Failed to create xtream response cache directory {}: {err}", parent.display());
+            return;
+        }
+    }
+    // written to a temp file, a concurrent reader never sees a partial response
+    let tmp_path = path.with_extension("tmp");
+    let result = match tokio::fs::write(&tmp_path, content).await {
+        Ok(()) => tokio::fs::rename(&tmp_path, &path).await,
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        error!("Failed to write xtream response cache file {}: {err}", path.display());
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+        return;
+    }
+    if let Some(parent) = path.parent() {
+        xtream_trim_cached_responses(parent, XTREAM_API_CACHE_MAX_FILES).await;
+    }
+}
+
+/// Returns true if the playlist file of the cluster is currently locked for writing by processing.
+pub async fn xtream_is_playlist_locked(app_config: &AppConfig, target_name: &str, cluster: XtreamCluster) -> bool {
+    let config = app_config.config.load();
+    let Some(storage_path) = xtream_get_storage_path(&config, target_name) else { return false; };
+    let xtream_path = xtream_get_file_path(&storage_path, cluster);
+    app_config.file_locks.try_read_lock(&xtream_path).await.is_err()
+}
+
+/// Removes the cached stream list responses of the target from disk.
+pub async fn xtream_clear_cached_responses(cfg: &Config, target_name: &str) {
+    let Some(path) = xtream_get_response_cache_dir(cfg, target_name) else { return; };
+    match tokio::fs::remove_dir_all(&path).await {
+        Ok(()) => debug!("Cleared xtream response cache of target {target_name}"),
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => error!("Failed to clear xtream response cache {}: {err}", path.display()),
+    }
+}
+
+/// Removes the cached stream list responses of the user from disk.
+pub async fn xtream_clear_user_cached_responses(cfg: &Config, target_name: &str, username: &str) {
+    if let Some(path) = xtream_get_response_cache_dir(cfg, target_name) {
+        xtream_remove_user_cached_responses(&path, username).await;
+    }
+}
+
 pub fn xtream_get_epg_file_path_for_target(path: &Path) -> PathBuf {
     path.join(concat_string!("epg.", storage_const::FILE_SUFFIX_DB))
 }
@@ -319,6 +442,49 @@ pub async fn xtream_write_playlist(
     Ok(())
 }
 
+/// Rewrites the items and the categories of one cluster, the other clusters are untouched.
+/// Existing categories keep their ids, categories without items are removed.
+pub async fn xtream_write_cluster_playlist(
+    app_cfg: &Arc<AppConfig>,
+    target: &ConfigTarget,
+    cluster: XtreamCluster,
+    mut items: Vec<XtreamPlaylistItem>,
+) -> Result<(), TuliproxError> {
+    let path = {
+        let config = app_cfg.config.load();
+        ensure_xtream_storage_path(&config, target.name.as_str())?
+    };
+    let (mut cat_id_counter, existing_cat_ids) = load_old_category_ids(&path).await;
+    let mut categories: IndexMap<Arc<str>, CategoryEntry> = IndexMap::new();
+    for item in &mut items {
+        let entry = categories.entry(Arc::clone(&item.group)).or_insert_with(|| {
+            let category_id = existing_cat_ids.get(&(cluster, Arc::clone(&item.group))).copied().unwrap_or_else(|| {
+                cat_id_counter += 1;
+                cat_id_counter
+            });
+            CategoryEntry {
+                category_id,
+                category_name: Arc::clone(&item.group),
+                parent_id: 0,
+            }
+        });
+        item.category_id = entry.category_id;
+    }
+
+    let col_path = match cluster {
+        XtreamCluster::Live => get_live_cat_collection_path(&path),
+        XtreamCluster::Video => get_vod_cat_collection_path(&path),
+        XtreamCluster::Series => get_series_cat_collection_path(&path),
+    };
+    {
+        let _file_lock = app_cfg.file_locks.write_lock(&col_path).await;
+        let data: Vec<CategoryEntry> = categories.into_values().collect();
+        json_write_documents_to_file(&col_path, &data).await
+            .map_err(|err| cant_write_result!(&col_path, err))?;
+    }
+    write_playlists_to_file(app_cfg, &path, true, StorageKey::VirtualId, vec![(cluster, items)]).await
+}
+
 async fn create_categories(playlist: &mut [PlaylistGroup], path: &Path) -> Vec<(XtreamCluster, CategoryEntry)> {
     // preserve category_ids
     let (max_cat_id, existing_cat_ids) = load_old_category_ids(path).await;
@@ -386,6 +552,34 @@ pub fn xtream_get_collection_path(
     Err(string_to_io_error(format!("Can't find collection: {target_name}/{collection_name}")))
 }
 
+/// The categories of the cluster, served from and stored in the `query_cache`.
+pub async fn xtream_load_categories(
+    config: &Config,
+    query_cache: &PlaylistQueryCache,
+    target_name: &str,
+    collection: &str,
+    cluster: XtreamCluster,
+) -> Option<Arc<Vec<XtreamCategoryEntry>>> {
+    if let Some(categories) = query_cache.get_categories(target_name, cluster) {
+        return Some(categories);
+    }
+    let generation = query_cache.generation();
+    let file_path = xtream_get_collection_path(config, target_name, collection).ok()?;
+    match tokio::fs::read_to_string(&file_path).await {
+        Ok(content) => match serde_json::from_str::<Vec<XtreamCategoryEntry>>(&content) {
+            Ok(categories) => Some(query_cache.put_categories(target_name, cluster, generation, categories)),
+            Err(err) => {
+                error!("Failed to parse json file {}: {err}", file_path.display());
+                None
+            }
+        },
+        Err(err) => {
+            error!("Failed to read collection file {}: {err}", file_path.display());
+            None
+        }
+    }
+}
+
 async fn xtream_read_item_for_stream_id(
     cfg: &AppConfig,
     stream_id: u32,
@@ -430,6 +624,7 @@ macro_rules! try_cluster {
     };
 }
 
+#[cfg(feature = "server")]
 async fn xtream_get_item_for_stream_id_from_memory(
     virtual_id: u32,
     app_state: &Arc<AppState>,
@@ -503,6 +698,7 @@ async fn xtream_get_item_for_stream_id_from_memory(
     Ok(None)
 }
 
+#[cfg(feature = "server")]
 pub async fn xtream_get_item_for_stream_id(
     virtual_id: u32,
     app_state: &Arc<AppState>,
@@ -577,8 +773,9 @@ pub async fn xtream_load_rewrite_playlist(
     target: &ConfigTarget,
     category_id: Option<u32>,
     user: &ProxyUserCredentials,
+    query_cache: &PlaylistQueryCache,
 ) -> Result<XtreamPlaylistJsonIterator, TuliproxError> {
-    XtreamPlaylistJsonIterator::new(cluster, config, target, category_id, user).await
+    XtreamPlaylistJsonIterator::new(cluster, config, target, category_id, user, query_cache).await
 }
 
 pub async fn iter_raw_xtream_target_playlist(app_config: &AppConfig, target: &ConfigTarget, cluster: XtreamCluster) -> Option<(FileReadGuard, Box<dyn Iterator<Item=XtreamPlaylistItem> + Send>)> {
@@ -667,9 +864,11 @@ pub(crate) async fn xtream_get_playlist_categories(config: &Config, target_name:
 }
 
 #[allow(clippy::too_many_lines)]
-pub async fn persist_input_xtream_playlist(app_config: &Arc<AppConfig>, storage_path: &Path,
+pub async fn persist_input_xtream_playlist(app_config: &Arc<AppConfig>, storage_path: &Path, incremental: bool,
                                            playlist: Vec<PlaylistGroup>) -> (Vec<PlaylistGroup>, Option<TuliproxError>) {
     let mut errors = Vec::new();
+    let watermark = if incremental { xtream_load_watermark(storage_path).await } else { XtreamWatermark::default() };
+    let mut fetched_watermark = XtreamWatermark::default();
 
     let mut fetched_categories = PlaylistScratch::<Vec<Value>>::new(1_000);
     let mut fetched_scratch = PlaylistScratch::<Vec<PlaylistItem>>::new(50_000);
@@ -705,20 +904,13 @@ pub async fn persist_input_xtream_playlist(app_config: &Arc<AppConfig>, storage_
                 let stored_col = stored_scratch.get_mut(plg.xtream_cluster);
                 let fetched_col = fetched_scratch.get_mut(plg.xtream_cluster);
 
+                if let Some(new_stream_props) = pli.header.additional_properties.as_ref() {
+                    fetched_watermark.update(plg.xtream_cluster, new_stream_props);
+                }
                 if let Ok(provider_id) = pli.header.id.parse::<u32>() {
                     if let Some(stored_pli) = stored_col.get_mut(&provider_id) {
                         if let (Some(new_stream_props), Some(old_stream_props)) = (&mut pli.header.additional_properties, stored_pli.additional_properties.take()) {
-                            if !needs_update_info_details(new_stream_props, &old_stream_props) {
-                                match (new_stream_props, old_stream_props) {
-                                    (StreamProperties::Video(value_1), StreamProperties::Video(value_2)) => {
-                                        value_1.details = value_2.details;
-                                    }
-                                    (StreamProperties::Series(value_1), StreamProperties::Series(value_2)) => {
-                                        value_1.details = value_2.details;
-                                    }
-                                    _ => {}
-                                }
-                            }
+                            xtream_keep_stored_details(new_stream_props, old_stream_props, watermark.get(plg.xtream_cluster));
                         }
                     }
                 }
@@ -786,6 +978,12 @@ pub async fn persist_input_xtream_playlist(app_config: &Arc<AppConfig>, storage_
         }
     }
 
+    if incremental {
+        if let Err(err) = xtream_save_watermark(app_config, storage_path, &watermark.merge(&fetched_watermark)).await {
+            errors.push(format!("Persisting watermark failed: {err}"));
+        }
+    }
+
     let result = groups.into_iter().map(|(_, group)| group).collect();
 
     let err = if errors.is_empty() {
@@ -797,6 +995,87 @@ pub async fn persist_input_xtream_playlist(app_config: &Arc<AppConfig>, storage_
     (result, err)
 }
 
+/// Highest `added` (vod) and `last_modified` (series) timestamp of the last fetched catalog of an input.
+/// In incremental mode the entries which are not newer keep their resolved details.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XtreamWatermark {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vod: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series: Option<u64>,
+}
+
+impl XtreamWatermark {
+    pub fn get(&self, cluster: XtreamCluster) -> Option<u64> {
+        match cluster {
+            XtreamCluster::Live => None,
+            XtreamCluster::Video => self.vod,
+            XtreamCluster::Series => self.series,
+        }
+    }
+
+    pub fn set(&mut self, cluster: XtreamCluster, value: Option<u64>) {
+        match cluster {
+            XtreamCluster::Live => {}
+            XtreamCluster::Video => self.vod = value,
+            XtreamCluster::Series => self.series = value,
+        }
+    }
+
+    pub fn update(&mut self, cluster: XtreamCluster, props: &StreamProperties) {
+        if let Some(modified) = props.get_last_modified() {
+            self.set(cluster, Some(self.get(cluster).map_or(modified, |current| current.max(modified))));
+        }
+    }
+
+    /// The fetched marks replace the stored ones, clusters which were not fetched keep their mark.
+    pub fn merge(&self, fetched: &XtreamWatermark) -> XtreamWatermark {
+        XtreamWatermark {
+            vod: fetched.vod.or(self.vod),
+            series: fetched.series.or(self.series),
+        }
+    }
+}
+
+fn xtream_get_watermark_path(storage_path: &Path) -> PathBuf {
+    storage_path.join(storage_const::FILE_XTREAM_WATERMARK)
+}
+
+pub async fn xtream_load_watermark(storage_path: &Path) -> XtreamWatermark {
+    match tokio::fs::read_to_string(xtream_get_watermark_path(storage_path)).await {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => XtreamWatermark::default(),
+    }
+}
+
+pub async fn xtream_save_watermark(app_config: &AppConfig, storage_path: &Path, watermark: &XtreamWatermark) -> Result<(), Error> {
+    let path = xtream_get_watermark_path(storage_path);
+    let content = serde_json::to_string(watermark).map_err(|err| Error::other(err.to_string()))?;
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    tokio::fs::write(&path, content).await
+}
+
+/// Moves the resolved details of the stored entry to the fetched entry if the entry did not change.
+/// With a watermark every entry which is not newer than the watermark is unchanged,
+/// otherwise the timestamps of the fetched and the stored entry are compared.
+pub fn xtream_keep_stored_details(new_stream_props: &mut StreamProperties, old_stream_props: StreamProperties, watermark: Option<u64>) {
+    let unchanged = match (watermark, new_stream_props.get_last_modified()) {
+        (Some(watermark), Some(modified)) => modified <= watermark,
+        _ => !needs_update_info_details(new_stream_props, &old_stream_props),
+    };
+    if unchanged {
+        match (new_stream_props, old_stream_props) {
+            (StreamProperties::Video(value_1), StreamProperties::Video(value_2)) => {
+                value_1.details = value_2.details;
+            }
+            (StreamProperties::Series(value_1), StreamProperties::Series(value_2)) => {
+                value_1.details = value_2.details;
+            }
+            _ => {}
+        }
+    }
+}
+
 // Checks if the info has changed after the last update
 fn needs_update_info_details(
     new_stream_props: &StreamProperties,
@@ -951,3 +1230,81 @@ pub async fn load_input_xtream_playlist(app_config: &Arc<AppConfig>, storage_pat
     Ok(groups.into_values().collect())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::{xtream_keep_stored_details, xtream_get_response_cache_user_prefix, xtream_list_cached_response_files,
+                xtream_remove_user_cached_responses, xtream_trim_cached_responses, XtreamWatermark};
+    use shared::concat_string;
+    use shared::model::{StreamProperties, VideoStreamDetailProperties, VideoStreamProperties, XtreamCluster};
+
+    fn video(added: &str, details: bool) -> StreamProperties {
+        StreamProperties::Video(Box::new(VideoStreamProperties {
+            added: added.into(),
+            details: details.then(VideoStreamDetailProperties::default),
+            ..VideoStreamProperties::default()
+        }))
+    }
+
+    fn has_details(props: &StreamProperties) -> bool {
+        matches!(props, StreamProperties::Video(video) if video.details.is_some())
+    }
+
+    #[test]
+    fn test_xtream_keep_stored_details() {
+        let mut props = video("100", false);
+        xtream_keep_stored_details(&mut props, video("100", true), None);
+        assert!(has_details(&props));
+
+        let mut props = video("200", false);
+        xtream_keep_stored_details(&mut props, video("100", true), None);
+        assert!(!has_details(&props));
+
+        // entries not newer than the watermark are unchanged
+        let mut props = video("200", false);
+        xtream_keep_stored_details(&mut props, video("100", true), Some(200));
+        assert!(has_details(&props));
+
+        let mut props = video("300", false);
+        xtream_keep_stored_details(&mut props, video("300", true), Some(200));
+        assert!(!has_details(&props));
+    }
+
+    #[test]
+    fn test_xtream_watermark() {
+        let mut fetched = XtreamWatermark::default();
+        fetched.update(XtreamCluster::Video, &video("100", false));
+        fetched.update(XtreamCluster::Video, &video("300", false));
+        fetched.update(XtreamCluster::Video, &video("200", false));
+        fetched.update(XtreamCluster::Video, &video("", false));
+        assert_eq!(fetched.get(XtreamCluster::Video), Some(300));
+
+        let stored = XtreamWatermark { vod: Some(50), series: Some(70) };
+        assert_eq!(stored.merge(&fetched), XtreamWatermark { vod: Some(300), series: Some(70) });
+    }
+
+    #[tokio::test]
+    async fn test_xtream_cached_response_cleanup() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        let user1 = xtream_get_response_cache_user_prefix("user1");
+        let user2 = xtream_get_response_cache_user_prefix("user2");
+        let now = std::time::SystemTime::now();
+        for (idx, name) in [concat_string!(&user1, "a.json"), concat_string!(&user1, "b.json"),
+            concat_string!(&user2, "a.json"), concat_string!(&user2, "b.json")].iter().enumerate() {
+            let file = std::fs::File::create(dir.join(name)).unwrap();
+            file.set_modified(now - std::time::Duration::from_secs(100 - idx as u64)).unwrap();
+        }
+
+        xtream_remove_user_cached_responses(dir, "user1").await;
+        let mut names: Vec<String> = xtream_list_cached_response_files(dir).await.into_iter()
+            .map(|(path, _)| path.file_name().unwrap().to_string_lossy().to_string()).collect();
+        names.sort();
+        assert_eq!(names, vec![concat_string!(&user2, "a.json"), concat_string!(&user2, "b.json")]);
+
+        xtream_trim_cached_responses(dir, 1).await;
+        let names: Vec<String> = xtream_list_cached_response_files(dir).await.into_iter()
+            .map(|(path, _)| path.file_name().unwrap().to_string_lossy().to_string()).collect();
+        assert_eq!(names, vec![concat_string!(&user2, "b.json")]);
+    }
+}
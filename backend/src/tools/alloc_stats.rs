@@ -0,0 +1,80 @@
+use shared::model::AllocatorStatsDto;
+
+#[cfg(feature = "profiling")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    pub(super) static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+    pub(super) static PEAK_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+    pub(super) static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    pub(super) static DEALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+    /// Wraps the system allocator and counts the allocated bytes.
+    pub struct CountingAllocator;
+
+    impl CountingAllocator {
+        fn on_alloc(size: usize) {
+            let allocated = ALLOCATED.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+            PEAK_ALLOCATED.fetch_max(allocated, Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_dealloc(size: usize) {
+            ALLOCATED.fetch_sub(size as u64, Ordering::Relaxed);
+            DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                Self::on_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            Self::on_dealloc(layout.size());
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                Self::on_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                Self::on_dealloc(layout.size());
+                Self::on_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+}
+
+#[cfg(feature = "profiling")]
+pub use counting::CountingAllocator;
+
+#[cfg(feature = "profiling")]
+pub fn get_allocator_stats() -> Option<AllocatorStatsDto> {
+    use std::sync::atomic::Ordering;
+    Some(AllocatorStatsDto {
+        allocated: counting::ALLOCATED.load(Ordering::Relaxed),
+        peak_allocated: counting::PEAK_ALLOCATED.load(Ordering::Relaxed),
+        allocations: counting::ALLOCATIONS.load(Ordering::Relaxed),
+        deallocations: counting::DEALLOCATIONS.load(Ordering::Relaxed),
+    })
+}
+
+/// Without the `profiling` feature the system allocator is used directly and nothing is counted.
+#[cfg(not(feature = "profiling"))]
+pub fn get_allocator_stats() -> Option<AllocatorStatsDto> {
+    None
+}
@@ -1,5 +1,6 @@
 use crate::utils::{decode_base64_string, encode_base64_hash, encode_base64_string, traverse_dir};
-use shared::utils::{human_readable_byte_size, sanitize_sensitive_info};
+use shared::model::{CacheEvictionPolicy, CacheUsageDto};
+use shared::utils::{current_time_secs, human_readable_byte_size, sanitize_sensitive_info};
 use log::{debug, error, info, trace};
 use std::collections::{HashMap, VecDeque};
 use std::fs;
@@ -11,24 +12,39 @@ fn encode_cache_key(key: &str) -> String {
     encode_base64_hash(key)
 }
 
+struct CacheEntry {
+    path: PathBuf,
+    mime_type: Option<String>,
+    size: usize,
+    /// Time the file was stored, for scanned files the modification time
+    stored_ts: u64,
+    hits: u32,
+    /// The file name is a hash of the url, the url of scanned files is known after their first request
+    url: Option<String>,
+}
+
 /// `LRUResourceCache`
 ///
-/// A least-recently-used (LRU) file-based resource cache that stores files in a directory on disk,
+/// A file-based resource cache that stores files in a directory on disk,
 /// automatically managing their lifecycle based on a specified maximum cache size. The cache evicts
-/// the least recently used files when the size limit is exceeded.
+/// the least recently used (`lru`) or the least frequently used (`lfu`) files when the size limit is exceeded.
 ///
 /// # Fields
 /// - `capacity`: The maximum cache size in bytes. Once the cache size exceeds this value, files are evicted.
 /// - `cache_dir`: The directory where cached files are stored.
 /// - `current_size`: The current total size of all files in the cache, in bytes.
-/// - `cache`: A `HashMap` that maps a unique key to a tuple containing the file path and its size.
+/// - `cache`: A `HashMap` that maps a unique key to the cache entry with the file path, size and hit count.
 /// - `usage_order`: A `VecDeque` that tracks the access order of keys, with the oldest at the front.
+/// - `eviction`: The eviction policy, `lfu` evicts the entry with the fewest hits, the least recently used first.
+/// - `removed_keys`: The keys of the removed entries, only collected for the cluster cache index.
 pub struct LRUResourceCache {
     capacity: usize,  // Maximum size in bytes
     cache_dir: PathBuf,
     current_size: usize,  // Current size in bytes
-    cache: HashMap<String, (PathBuf, Option<String>, usize)>,
+    cache: HashMap<String, CacheEntry>,
     usage_order: VecDeque<String>,
+    eviction: CacheEvictionPolicy,
+    removed_keys: Option<Vec<String>>,
 }
 
 impl LRUResourceCache {
@@ -36,22 +52,49 @@ impl LRUResourceCache {
     ///   - Arguments:
     ///     - `capacity`: The maximum size of the cache in bytes.
     ///     - `cache_dir`: The directory path where cached files are stored.
+    ///     - `eviction`: The policy which selects the evicted files.
     ///
-    pub fn new(capacity: usize, cache_dir: &str) -> Self {
+    pub fn new(capacity: usize, cache_dir: &str, eviction: CacheEvictionPolicy) -> Self {
         // Estimate: assume average file size of 256KB
         let estimated_entries = (capacity / (256 * 1024)).clamp(64, 16384);
         Self {
             capacity,
             cache_dir: PathBuf::from(cache_dir),
             current_size: 0,
-            cache: HashMap::<String, (PathBuf, Option<String>, usize)>::with_capacity(estimated_entries),
+            cache: HashMap::<String, CacheEntry>::with_capacity(estimated_entries),
             usage_order: VecDeque::new(),
+            eviction,
+            removed_keys: None,
+        }
+    }
+
+    /// The key of the url in the cache, which is the file name without the mime type.
+    pub fn content_key(url: &str) -> String {
+        encode_cache_key(url)
+    }
+
+    /// Collects the keys of the removed entries until they are taken with `take_removed_keys`.
+    pub fn track_removed_keys(&mut self) {
+        self.removed_keys.get_or_insert_with(Vec::new);
+    }
+
+    pub fn take_removed_keys(&mut self) -> Vec<String> {
+        self.removed_keys.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn add_removed_key(&mut self, key: &str) {
+        if let Some(keys) = self.removed_keys.as_mut() {
+            keys.push(key.to_string());
         }
     }
 
-    pub fn update_config(&mut self, capacity: usize, cache_dir: &str) {
+    pub fn update_config(&mut self, capacity: usize, cache_dir: &str, eviction: CacheEvictionPolicy) {
         self.capacity = capacity;
         self.cache_dir = PathBuf::from(cache_dir);
+        self.eviction = eviction;
+        if self.current_size > self.capacity {
+            self.evict_if_needed();
+        }
     }
 
     /// - Scans the cache directory and populates the internal data structures with existing files and their sizes.
@@ -69,13 +112,16 @@ impl LRUResourceCache {
                 };
 
                 let file_size = usize::try_from(metadata.len()).unwrap_or(0);
+                let stored_ts = metadata.modified().ok()
+                    .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or_else(current_time_secs, |duration| duration.as_secs());
                 // we need to duplicate because of closure we can't call insert_to_cache
                 {  // insert_to_cache
 
                     let mut path = self.cache_dir.clone();
                     path.push(&file_name);
                     trace!("Added file to cache: {}", &path.to_string_lossy());
-                    self.cache.insert(key.clone(), (path.clone(), mime_type, file_size));
+                    self.cache.insert(key.clone(), CacheEntry { path, mime_type, size: file_size, stored_ts, hits: 0, url: None });
                     self.usage_order.push_back(key);
                     self.current_size += file_size;
                 }
@@ -90,8 +136,20 @@ impl LRUResourceCache {
         format!("{} / {}", human_readable_byte_size(self.current_size as u64), human_readable_byte_size(self.capacity as u64))
     }
 
+    pub fn get_usage(&self, max_age_days: u16) -> CacheUsageDto {
+        CacheUsageDto {
+            size: self.current_size as u64,
+            capacity: self.capacity as u64,
+            entries: self.cache.len(),
+            eviction: self.eviction,
+            max_age_days,
+            oldest_ts: self.cache.values().map(|entry| entry.stored_ts).min()
+                .and_then(|ts| i64::try_from(ts).ok()),
+        }
+    }
+
     ///   - Adds a new file to the cache.
-    ///   - Evicts the least recently used files if the cache size exceeds the capacity after the addition.
+    ///   - Evicts the least recently or least frequently used files if the cache size exceeds the capacity after the addition.
     ///   - Arguments:
     ///     - `url`: The unique identifier for the file.
     ///     - `file_size`: The size of the file in bytes.
@@ -99,17 +157,22 @@ impl LRUResourceCache {
     ///     - The `PathBuf` where the file is stored.
     pub fn add_content(&mut self, url: &str, mime_type: Option<String>, file_size: usize) -> std::io::Result<PathBuf> {
         let key = encode_cache_key(url);
-        let path = self.insert_to_cache(key, mime_type, file_size);
+        let path = self.insert_to_cache(key, url, mime_type, file_size);
         if self.current_size > self.capacity {
             self.evict_if_needed();
         }
         Ok(path)
     }
 
-    fn insert_to_cache(&mut self, key: String, mime_type: Option<String>, file_size: usize) -> PathBuf {
+    fn insert_to_cache(&mut self, key: String, url: &str, mime_type: Option<String>, file_size: usize) -> PathBuf {
         let path = self.get_store_path(&key, mime_type.as_deref());
         debug!("Added file to cache: {}", &path.to_string_lossy());
-        self.cache.insert(key.clone(), (path.clone(), mime_type, file_size));
+        let entry = CacheEntry { path: path.clone(), mime_type, size: file_size, stored_ts: current_time_secs(), hits: 0, url: Some(url.to_string()) };
+        if let Some(previous) = self.cache.insert(key.clone(), entry) {
+            // the content was stored again, the old entry is replaced
+            self.current_size -= previous.size;
+            self.remove_from_usage_order(&key);
+        }
         self.usage_order.push_back(key);
         self.current_size += file_size;
         path
@@ -130,54 +193,187 @@ impl LRUResourceCache {
         path
     }
 
+    fn remove_from_usage_order(&mut self, key: &str) {
+        if let Some(pos) = self.usage_order.iter().position(|k| k == key) {
+            self.usage_order.remove(pos);
+        }
+    }
 
     ///   - Retrieves a file from the cache if it exists.
-    ///   - Moves the file's key to the end of the usage queue to mark it as recently used.
+    ///   - Moves the file's key to the end of the usage queue to mark it as recently used and counts the hit.
     ///   - Arguments:
     ///     - `url`: The unique identifier for the file.
     ///   - Returns:
     ///     - The `PathBuf` of the file if it exists; `None` otherwise.
     pub fn get_content(&mut self, url: &str) -> Option<(PathBuf, Option<String>)> {
         let key = encode_cache_key(url);
-        {
-            if let Some((path, mime_type, size)) = self.cache.get(&key) {
-                if path.exists() {
-                    trace_if_enabled!("Responding resource from cache with key: {key} for url: {}", sanitize_sensitive_info(url));
-                    // Move to the end of the queue
-                    if let Some(pos) = self.usage_order.iter().position(|k| k == &key) {
-                        self.usage_order.remove(pos); // remove from queue
-                    }
-                    self.usage_order.push_back(key);  // add to the to end
-                    return Some((path.clone(), mime_type.clone()));
-                }
-                {
-                    trace_if_enabled!("Cache inconsistency: file missing for key: {key}, url: {}", sanitize_sensitive_info(url));
-                    // this should not happen, someone deleted the file manually and the cache is not in sync
-                    self.current_size -= size;
-                    self.cache.remove(&key);
-                    if let Some(pos) = self.usage_order.iter().position(|k| k == &key) {
-                        self.usage_order.remove(pos);
-                    }
-                }
+        let exists = self.cache.get(&key).map(|entry| entry.path.exists())?;
+        if exists {
+            trace_if_enabled!("Responding resource from cache with key: {key} for url: {}", sanitize_sensitive_info(url));
+            // Move to the end of the queue
+            self.remove_from_usage_order(&key);
+            let entry = self.cache.get_mut(&key)?;
+            entry.hits = entry.hits.saturating_add(1);
+            if entry.url.is_none() {
+                entry.url = Some(url.to_string());
             }
+            let result = (entry.path.clone(), entry.mime_type.clone());
+            self.usage_order.push_back(key);  // add to the to end
+            return Some(result);
+        }
+        trace_if_enabled!("Cache inconsistency: file missing for key: {key}, url: {}", sanitize_sensitive_info(url));
+        // this should not happen, someone deleted the file manually and the cache is not in sync
+        if let Some(entry) = self.cache.remove(&key) {
+            self.current_size -= entry.size;
+            self.add_removed_key(&key);
         }
+        self.remove_from_usage_order(&key);
         None
     }
 
+    fn remove_entry(&mut self, key: &str) -> Option<usize> {
+        let entry = self.cache.remove(key)?;
+        self.current_size -= entry.size;
+        self.add_removed_key(key);
+        if let Err(err) = fs::remove_file(&entry.path) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                error!("Failed to delete cached file {} {err}", entry.path.to_string_lossy());
+            }
+        } else {
+            debug!("Removed file from cache: {}", entry.path.to_string_lossy());
+        }
+        Some(entry.size)
+    }
+
+    fn select_eviction_candidate(&self) -> Option<usize> {
+        match self.eviction {
+            CacheEvictionPolicy::Lru => (!self.usage_order.is_empty()).then_some(0),
+            // the most recent entry is skipped, otherwise a new entry without hits would evict itself.
+            // min_by_key returns the first of equal hits, which is the least recently used one
+            CacheEvictionPolicy::Lfu => self.usage_order.iter().enumerate()
+                .take(self.usage_order.len().saturating_sub(1).max(1))
+                .min_by_key(|(_, key)| self.cache.get(*key).map_or(0, |entry| entry.hits))
+                .map(|(pos, _)| pos),
+        }
+    }
+
     fn evict_if_needed(&mut self) {
         // if the cache size is to small and one element exceeds the size than the cache won't work, we ignore this
         while self.current_size > self.capacity {
-            if let Some(oldest_file) = self.usage_order.pop_front() {
-                if let Some((file, _mime_type, size)) = self.cache.remove(&oldest_file) {
-                    self.current_size -= size;
-                    if let Err(err) = fs::remove_file(&file) {
-                        error!("Failed to delete cached file {} {err}", file.to_string_lossy());
-                    } else {
-                        debug!("Removed file from cache: {}", file.to_string_lossy());
-                    }
-                }
+            let Some(key) = self.select_eviction_candidate().and_then(|pos| self.usage_order.remove(pos)) else {
+                break;
+            };
+            self.remove_entry(&key);
+        }
+    }
+
+    fn remove_where<F>(&mut self, predicate: F) -> (usize, u64)
+    where
+        F: Fn(&CacheEntry) -> bool,
+    {
+        let keys: Vec<String> = self.cache.iter()
+            .filter(|(_, entry)| predicate(entry))
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut freed = 0u64;
+        for key in &keys {
+            if let Some(size) = self.remove_entry(key) {
+                freed += size as u64;
             }
         }
+        if !keys.is_empty() {
+            self.usage_order.retain(|key| self.cache.contains_key(key));
+        }
+        (keys.len(), freed)
+    }
+
+    /// Removes the entries stored more than `max_age_secs` ago.
+    /// Returns the count and the bytes of the removed files.
+    pub fn evict_expired(&mut self, max_age_secs: u64) -> (usize, u64) {
+        let min_ts = current_time_secs().saturating_sub(max_age_secs);
+        self.remove_where(|entry| entry.stored_ts < min_ts)
+    }
+
+    /// Removes the entries whose url matches the pattern, without a pattern all entries are removed.
+    /// Entries loaded by `scan` are matched after their url is known from a request.
+    pub fn purge(&mut self, pattern: Option<&regex::Regex>) -> (usize, u64) {
+        match pattern {
+            None => self.remove_where(|_| true),
+            Some(regex) => self.remove_where(|entry| entry.url.as_deref().is_some_and(|url| regex.is_match(url))),
+        }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::LRUResourceCache;
+    use shared::model::CacheEvictionPolicy;
+
+    fn create_cache(dir: &std::path::Path, capacity: usize, eviction: CacheEvictionPolicy) -> LRUResourceCache {
+        LRUResourceCache::new(capacity, &dir.to_string_lossy(), eviction)
+    }
+
+    fn add(cache: &mut LRUResourceCache, url: &str, size: usize) {
+        let path = cache.store_path(url, None);
+        std::fs::write(&path, vec![0u8; size]).unwrap();
+        cache.add_content(url, None, size).unwrap();
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = create_cache(dir.path(), 20, CacheEvictionPolicy::Lru);
+        add(&mut cache, "http://a", 10);
+        add(&mut cache, "http://b", 10);
+        assert!(cache.get_content("http://a").is_some());
+        add(&mut cache, "http://c", 10);
+        assert!(cache.get_content("http://b").is_none());
+        assert!(cache.get_content("http://a").is_some());
+        assert!(cache.get_content("http://c").is_some());
+    }
+
+    #[test]
+    fn test_lfu_eviction() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = create_cache(dir.path(), 20, CacheEvictionPolicy::Lfu);
+        add(&mut cache, "http://a", 10);
+        add(&mut cache, "http://b", 10);
+        assert!(cache.get_content("http://a").is_some());
+        assert!(cache.get_content("http://a").is_some());
+        assert!(cache.get_content("http://b").is_some());
+        // b was used last but has fewer hits
+        add(&mut cache, "http://c", 10);
+        assert!(cache.get_content("http://b").is_none());
+        assert!(cache.get_content("http://a").is_some());
+    }
+
+    #[test]
+    fn test_purge_and_expire() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = create_cache(dir.path(), 100, CacheEvictionPolicy::Lru);
+        add(&mut cache, "http://logos/a.png", 10);
+        add(&mut cache, "http://logos/b.png", 10);
+        add(&mut cache, "http://covers/c.jpg", 10);
+        let regex = regex::Regex::new("logos/").unwrap();
+        assert_eq!(cache.purge(Some(&regex)), (2, 20));
+        assert_eq!(cache.get_usage(0).entries, 1);
+        assert!(!cache.store_path("http://logos/a.png", None).exists());
+
+        assert_eq!(cache.evict_expired(3600), (0, 0));
+        assert_eq!(cache.purge(None), (1, 10));
+        assert_eq!(cache.get_usage(0).size, 0);
+    }
+
+    #[test]
+    fn test_removed_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = create_cache(dir.path(), 20, CacheEvictionPolicy::Lru);
+        add(&mut cache, "http://a", 10);
+        assert!(cache.take_removed_keys().is_empty());
+        cache.track_removed_keys();
+        add(&mut cache, "http://b", 10);
+        add(&mut cache, "http://c", 10);
+        assert_eq!(cache.take_removed_keys(), vec![LRUResourceCache::content_key("http://a")]);
+        assert!(cache.take_removed_keys().is_empty());
+    }
+}
@@ -1,2 +1,3 @@
 pub mod lru_cache;
-pub mod atomic_once_flag;
\ No newline at end of file
+pub mod atomic_once_flag;
+pub mod alloc_stats;
\ No newline at end of file
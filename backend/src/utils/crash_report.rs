@@ -0,0 +1,283 @@
+use crate::model::CrashReportConfig;
+use crate::utils::read_log_level_config;
+use chrono::Utc;
+use log::{error, info};
+use serde::Serialize;
+use serde_json::json;
+use shared::utils::sanitize_sensitive_info;
+use std::backtrace::Backtrace;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+use url::Url;
+
+const CRASH_REPORT_DIR: &str = "crash_reports";
+const SENTRY_TIMEOUT_SECS: u64 = 5;
+const SENTRY_CLIENT: &str = concat!("tuliprox/", env!("CARGO_PKG_VERSION"));
+
+static CRASH_REPORTER: OnceLock<CrashReporter> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CrashReportKind {
+    Panic,
+    Fatal,
+    Error,
+}
+
+impl CrashReportKind {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Panic => "panic",
+            Self::Fatal => "fatal",
+            Self::Error => "error",
+        }
+    }
+
+    const fn sentry_level(self) -> &'static str {
+        match self {
+            Self::Panic | Self::Fatal => "fatal",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub kind: CrashReportKind,
+    pub timestamp: String,
+    pub version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backtrace: Option<String>,
+}
+
+impl CrashReport {
+    fn new(kind: CrashReportKind, message: &str) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().simple().to_string(),
+            kind,
+            timestamp: Utc::now().to_rfc3339(),
+            version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            message: sanitize_sensitive_info(message).to_string(),
+            location: None,
+            thread: std::thread::current().name().map(ToString::to_string),
+            backtrace: None,
+        }
+    }
+
+    fn from_panic(info: &std::panic::PanicHookInfo<'_>) -> Self {
+        let payload = info.payload();
+        let message = payload.downcast_ref::<&str>().map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        let mut report = Self::new(CrashReportKind::Panic, &message);
+        report.location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        report.backtrace = Some(Backtrace::force_capture().to_string());
+        report
+    }
+}
+
+/// Target of a self-hosted Sentry/GlitchTip instance parsed from a DSN
+/// like `https://<public_key>@<host>/<project_id>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SentryDsn {
+    store_url: String,
+    public_key: String,
+}
+
+impl SentryDsn {
+    fn parse(dsn: &str) -> Option<Self> {
+        let url = Url::parse(dsn.trim()).ok()?;
+        let public_key = url.username();
+        if public_key.is_empty() {
+            return None;
+        }
+        let host = url.host_str()?;
+        let path = url.path().trim_end_matches('/');
+        let (prefix, project_id) = path.rsplit_once('/')?;
+        if project_id.is_empty() {
+            return None;
+        }
+        let port = url.port().map(|p| format!(":{p}")).unwrap_or_default();
+        Some(Self {
+            store_url: format!("{}://{host}{port}{prefix}/api/{project_id}/store/", url.scheme()),
+            public_key: public_key.to_string(),
+        })
+    }
+
+    fn auth_header(&self) -> String {
+        format!("Sentry sentry_version=7, sentry_client={SENTRY_CLIENT}, sentry_key={}", self.public_key)
+    }
+}
+
+struct CrashReporter {
+    dir: PathBuf,
+    sentry: Option<SentryDsn>,
+    environment: Option<String>,
+}
+
+impl CrashReporter {
+    fn write_report(&self, report: &CrashReport) {
+        if let Err(err) = std::fs::create_dir_all(&self.dir) {
+            error!("Failed to create crash report directory {}: {err}", self.dir.display());
+            return;
+        }
+        let file_name = format!("{}_{}_{}.json", Utc::now().format("%Y%m%d%H%M%S"), report.kind.as_str(), report.id);
+        let path = self.dir.join(file_name);
+        match serde_json::to_vec_pretty(report) {
+            Ok(content) => match std::fs::write(&path, content) {
+                Ok(()) => error!("Crash report written to {}", path.display()),
+                Err(err) => error!("Failed to write crash report {}: {err}", path.display()),
+            },
+            Err(err) => error!("Failed to serialize crash report: {err}"),
+        }
+    }
+
+    fn create_sentry_event(&self, report: &CrashReport) -> serde_json::Value {
+        let mut exception = json!({
+            "type": report.kind.as_str(),
+            "value": report.message,
+        });
+        if let Some(location) = &report.location {
+            exception["module"] = json!(location);
+        }
+        json!({
+            "event_id": report.id,
+            "timestamp": report.timestamp,
+            "platform": "native",
+            "level": report.kind.sentry_level(),
+            "logger": "tuliprox",
+            "release": format!("tuliprox@{}", report.version),
+            "environment": self.environment.as_deref().unwrap_or("production"),
+            "message": { "formatted": report.message },
+            "exception": { "values": [exception] },
+            "tags": {
+                "os": report.os,
+                "arch": report.arch,
+                "thread": report.thread.as_deref().unwrap_or("unnamed"),
+            },
+            "extra": {
+                "location": report.location,
+                "backtrace": report.backtrace,
+            },
+        })
+    }
+
+    /// Sends the report on a dedicated thread with its own blocking client,
+    /// so it can be used from the panic hook regardless of the async runtime state.
+    fn forward(&self, report: &CrashReport, wait: bool) {
+        let Some(sentry) = self.sentry.clone() else { return; };
+        let event = self.create_sentry_event(report);
+        let handle = std::thread::spawn(move || {
+            let client = match reqwest::blocking::Client::builder()
+                .timeout(Duration::from_secs(SENTRY_TIMEOUT_SECS))
+                .build() {
+                Ok(client) => client,
+                Err(err) => {
+                    error!("Failed to create crash report client: {err}");
+                    return;
+                }
+            };
+            match client.post(&sentry.store_url)
+                .header("X-Sentry-Auth", sentry.auth_header())
+                .json(&event)
+                .send() {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => error!("Failed to forward crash report, status code {}", response.status()),
+                Err(err) => error!("Failed to forward crash report: {err}"),
+            }
+        });
+        if wait {
+            let _ = handle.join();
+        }
+    }
+
+    fn handle(&self, report: &CrashReport, wait: bool) {
+        self.write_report(report);
+        self.forward(report, wait);
+    }
+}
+
+fn create_crash_reporter(working_dir: Option<&str>, config: &CrashReportConfig) -> CrashReporter {
+    let dir = config.dir.as_deref().filter(|d| !d.trim().is_empty()).map_or_else(
+        || PathBuf::from(working_dir.unwrap_or(".")).join(CRASH_REPORT_DIR),
+        PathBuf::from);
+    let sentry = config.sentry_dsn.as_deref().filter(|d| !d.trim().is_empty()).and_then(|dsn| {
+        let parsed = SentryDsn::parse(dsn);
+        if parsed.is_none() {
+            error!("Invalid crash report sentry_dsn, reports are only written locally");
+        }
+        parsed
+    });
+    CrashReporter {
+        dir,
+        sentry,
+        environment: config.environment.clone(),
+    }
+}
+
+/// Installs the panic hook which writes a structured crash report for every panic
+/// and forwards it to Sentry/GlitchTip when a `sentry_dsn` is configured.
+/// The settings are read from the `log.crash_report` section of the config file.
+pub fn init_crash_reporter(config_file: &str) {
+    let log_config = read_log_level_config(config_file);
+    let working_dir = log_config.as_ref().and_then(|c| c.working_dir.clone());
+    let crash_config = log_config.and_then(|c| c.log).and_then(|l| l.crash_report).unwrap_or_default();
+    if !crash_config.enabled {
+        return;
+    }
+
+    let reporter = create_crash_reporter(working_dir.as_deref(), &crash_config);
+    info!("Crash reports: {}{}", reporter.dir.display(), if reporter.sentry.is_some() { " (forwarding enabled)" } else { "" });
+    if CRASH_REPORTER.set(reporter).is_err() {
+        return;
+    }
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(reporter) = CRASH_REPORTER.get() {
+            reporter.handle(&CrashReport::from_panic(info), true);
+        }
+        default_hook(info);
+    }));
+}
+
+/// Records an error report. Fatal errors block until the report is forwarded,
+/// because the process terminates afterward.
+pub fn report_error(kind: CrashReportKind, message: &str) {
+    if let Some(reporter) = CRASH_REPORTER.get() {
+        reporter.handle(&CrashReport::new(kind, message), kind != CrashReportKind::Error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SentryDsn;
+
+    #[test]
+    fn test_parse_sentry_dsn() {
+        let dsn = SentryDsn::parse("https://abc123@glitchtip.example.com/42").unwrap();
+        assert_eq!(dsn.store_url, "https://glitchtip.example.com/api/42/store/");
+        assert_eq!(dsn.public_key, "abc123");
+
+        let dsn = SentryDsn::parse("http://key@localhost:9000/sentry/7").unwrap();
+        assert_eq!(dsn.store_url, "http://localhost:9000/sentry/api/7/store/");
+    }
+
+    #[test]
+    fn test_parse_invalid_sentry_dsn() {
+        assert!(SentryDsn::parse("https://glitchtip.example.com/42").is_none());
+        assert!(SentryDsn::parse("https://key@glitchtip.example.com/").is_none());
+        assert!(SentryDsn::parse("not a dsn").is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use base64::{engine::general_purpose, Engine as _};
-use openssl::symm::{Cipher, Crypter, Mode};
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher, Crypter, Mode};
 use rand::{RngCore, rngs::OsRng, TryRngCore};
 use shared::error::{TuliproxError, TuliproxErrorKind};
 
@@ -82,9 +82,90 @@ pub fn deobscure_text(secret: &[u8;16], encoded: &str) -> Result<String, Tulipro
     String::from_utf8(buf).map_err(|_err| TuliproxError::new(TuliproxErrorKind::Info, "Can't create utf8 string from decrypted".to_string()))
 }
 
+const SECRET_NONCE_LEN: usize = 12;
+const SECRET_TAG_LEN: usize = 16;
+const SECRET_SALT_LEN: usize = 16;
+
+/// Argon2id parameters of the secret key, stored with the random salt in the secrets file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SecretKeyParams {
+    pub salt: String,
+    /// Memory in KiB
+    pub mem_cost: u32,
+    pub time_cost: u32,
+    pub lanes: u32,
+}
+
+impl SecretKeyParams {
+    /// New random salt with the OWASP recommended parameters (19 MiB, 2 iterations).
+    pub fn generate() -> Self {
+        let mut salt = [0u8; SECRET_SALT_LEN];
+        if OsRng.try_fill_bytes(&mut salt).is_err() {
+            rand::rng().fill_bytes(&mut salt);
+        }
+        let config = argon2::Config::owasp2();
+        Self {
+            salt: encode_base64_string(&salt),
+            mem_cost: config.mem_cost,
+            time_cost: config.time_cost,
+            lanes: config.lanes,
+        }
+    }
+}
+
+/// Derives the AES-256 key for the secrets from the master key with Argon2id,
+/// a brute force of the master key needs the memory and time of the parameters for each guess.
+pub fn derive_secret_key(master_key: &str, params: &SecretKeyParams) -> Result<[u8; 32], TuliproxError> {
+    let salt = general_purpose::URL_SAFE_NO_PAD.decode(&params.salt)
+        .map_err(|_err| TuliproxError::new(TuliproxErrorKind::Info, "Can't decode the salt of the secret key".to_string()))?;
+    let config = argon2::Config {
+        variant: argon2::Variant::Argon2id,
+        version: argon2::Version::Version13,
+        mem_cost: params.mem_cost,
+        time_cost: params.time_cost,
+        lanes: params.lanes,
+        hash_length: 32,
+        ..argon2::Config::default()
+    };
+    let hash = argon2::hash_raw(master_key.as_bytes(), &salt, &config)
+        .map_err(|err| TuliproxError::new(TuliproxErrorKind::Info, format!("Can't derive the secret key: {err}")))?;
+    <[u8; 32]>::try_from(hash.as_slice())
+        .map_err(|_err| TuliproxError::new(TuliproxErrorKind::Info, "Invalid length of the secret key".to_string()))
+}
+
+/// Encrypts the text with AES-256-GCM, the result is the URL-safe Base64 of nonce + ciphertext + tag.
+pub fn encrypt_secret(key: &[u8; 32], text: &str) -> Result<String, TuliproxError> {
+    let mut nonce = [0u8; SECRET_NONCE_LEN];
+    if OsRng.try_fill_bytes(&mut nonce).is_err() {
+        rand::rng().fill_bytes(&mut nonce);
+    }
+    let mut tag = [0u8; SECRET_TAG_LEN];
+    let ciphertext = encrypt_aead(Cipher::aes_256_gcm(), key, Some(&nonce), &[], text.as_bytes(), &mut tag)
+        .map_err(|_err| TuliproxError::new(TuliproxErrorKind::Info, "Can't encrypt secret".to_string()))?;
+
+    let mut out = Vec::with_capacity(nonce.len() + ciphertext.len() + tag.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    out.extend_from_slice(&tag);
+    Ok(general_purpose::URL_SAFE_NO_PAD.encode(out))
+}
+
+pub fn decrypt_secret(key: &[u8; 32], encoded: &str) -> Result<String, TuliproxError> {
+    let data = general_purpose::URL_SAFE_NO_PAD.decode(encoded.trim()).map_err(|_err| TuliproxError::new(TuliproxErrorKind::Info, "Can't decode base64".to_string()))?;
+    if data.len() < SECRET_NONCE_LEN + SECRET_TAG_LEN {
+        return Err(TuliproxError::new(TuliproxErrorKind::Info, "Secret too short to contain nonce and tag".to_string()));
+    }
+
+    let (nonce, rest) = data.split_at(SECRET_NONCE_LEN);
+    let (ciphertext, tag) = rest.split_at(rest.len() - SECRET_TAG_LEN);
+    let plain = decrypt_aead(Cipher::aes_256_gcm(), key, Some(nonce), &[], ciphertext, tag)
+        .map_err(|_err| TuliproxError::new(TuliproxErrorKind::Info, "Can't decrypt secret".to_string()))?;
+    String::from_utf8(plain).map_err(|_err| TuliproxError::new(TuliproxErrorKind::Info, "Can't create utf8 string from decrypted".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::crypto_utils::{obscure_text, deobscure_text, deobfuscate_text, obfuscate_text};
+    use crate::utils::crypto_utils::{decrypt_secret, derive_secret_key, encrypt_secret, obscure_text, deobscure_text, deobfuscate_text, obfuscate_text, SecretKeyParams};
     use rand::{Rng};
 
     #[test]
@@ -105,4 +186,19 @@ mod tests {
 
         assert_eq!(decrypted, plain);
     }
+
+    #[test]
+    fn test_encrypt_secret() {
+        // small parameters to keep the test fast
+        let params = SecretKeyParams { mem_cost: 1024, time_cost: 1, ..SecretKeyParams::generate() };
+        let key = derive_secret_key("master key", &params).unwrap();
+        let encrypted = encrypt_secret(&key, "provider password").unwrap();
+        assert_eq!(decrypt_secret(&key, &encrypted).unwrap(), "provider password");
+        assert!(decrypt_secret(&derive_secret_key("wrong key", &params).unwrap(), &encrypted).is_err());
+
+        // the same master key with another salt gives another key
+        let other_salt = SecretKeyParams { mem_cost: 1024, time_cost: 1, ..SecretKeyParams::generate() };
+        assert_ne!(key, derive_secret_key("master key", &other_salt).unwrap());
+        assert_eq!(key, derive_secret_key("master key", &params).unwrap());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,279 @@
+use crate::auth::hash;
+use crate::utils::get_exe_path;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use rand::distr::Alphanumeric;
+use rand::Rng;
+use shared::model::ConfigPaths;
+use std::fmt::Write;
+use std::fs;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+pub const DEMO_PORT: u16 = 8901;
+pub const DEMO_USERNAME: &str = "demo";
+pub const DEMO_PASSWORD: &str = "demo";
+const DEMO_DIR: &str = "tuliprox-demo";
+/// Not resolvable, demo streams answer with the `channel unavailable` response.
+const DEMO_STREAM_HOST: &str = "http://stream.demo.invalid";
+const DEMO_EPG_FILE: &str = "demo-epg.xml";
+const EPG_HOURS_BEFORE: i64 = 6;
+const EPG_HOURS_AFTER: i64 = 48;
+
+struct DemoGroup {
+    title: &'static str,
+    channels: &'static [&'static str],
+    shows: &'static [&'static str],
+}
+
+struct DemoProvider {
+    name: &'static str,
+    groups: &'static [DemoGroup],
+    movies: &'static [&'static str],
+}
+
+const DEMO_PROVIDERS: &[DemoProvider] = &[
+    DemoProvider {
+        name: "demo-provider-1",
+        groups: &[
+            DemoGroup {
+                title: "News",
+                channels: &["World News 24", "Business Today", "Weather Now"],
+                shows: &["Morning Briefing", "Headlines", "Markets Live", "Evening Report", "Weather Outlook"],
+            },
+            DemoGroup {
+                title: "Sports",
+                channels: &["Sports Arena HD", "Football Channel", "Motor Racing TV"],
+                shows: &["Match Day Live", "Highlights", "Pit Lane", "Studio Talk", "Classic Matches"],
+            },
+            DemoGroup {
+                title: "Entertainment",
+                channels: &["Comedy Central Demo", "Drama One", "Reality Plus"],
+                shows: &["Late Night Show", "Family Drama", "Talent Search", "Sitcom Marathon", "Quiz Hour"],
+            },
+        ],
+        movies: &["The Silent Harbor", "Midnight Express Line", "Paper Planes", "Beyond the Ridge"],
+    },
+    DemoProvider {
+        name: "demo-provider-2",
+        groups: &[
+            DemoGroup {
+                title: "Kids",
+                channels: &["Cartoon Planet", "Junior Science"],
+                shows: &["Animated Adventures", "Puppet Theater", "Science for Kids", "Bedtime Stories"],
+            },
+            DemoGroup {
+                title: "Documentary",
+                channels: &["Nature World", "History Vault", "Planet Explorer"],
+                shows: &["Wild Oceans", "Ancient Empires", "Engineering Marvels", "Space Frontiers"],
+            },
+            DemoGroup {
+                title: "Music",
+                channels: &["Hits Radio TV", "Classic Rock Live"],
+                shows: &["Top 40", "Unplugged", "Concert Replay", "Artist Spotlight"],
+            },
+        ],
+        movies: &["Northern Lights", "The Last Orchard", "City of Glass"],
+    },
+];
+
+fn epg_channel_id(provider_idx: usize, channel: &str) -> String {
+    let name: String = channel.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    format!("{name}.p{}.demo", provider_idx + 1)
+}
+
+fn file_url(path: &Path) -> String {
+    Url::from_file_path(path).map_or_else(|()| path.to_string_lossy().to_string(), |url| url.to_string())
+}
+
+/// Single quoted yaml scalar
+fn yaml_str(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn create_provider_playlist(provider_idx: usize, provider: &DemoProvider) -> String {
+    let mut m3u = String::from("#EXTM3U\n");
+    let mut stream_id = 1;
+    for group in provider.groups {
+        for channel in group.channels {
+            let _ = writeln!(m3u, r#"#EXTINF:-1 tvg-id="{}" tvg-name="{channel}" group-title="{}",{channel}"#,
+                             epg_channel_id(provider_idx, channel), group.title);
+            let _ = writeln!(m3u, "{DEMO_STREAM_HOST}/{}/live/{stream_id}.ts", provider.name);
+            stream_id += 1;
+        }
+    }
+    for movie in provider.movies {
+        let _ = writeln!(m3u, r#"#EXTINF:-1 tvg-name="{movie}" group-title="Movies",{movie}"#);
+        let _ = writeln!(m3u, "{DEMO_STREAM_HOST}/{}/movie/{stream_id}.mp4", provider.name);
+        stream_id += 1;
+    }
+    m3u
+}
+
+fn format_xmltv_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%d%H%M%S +0000").to_string()
+}
+
+/// Hourly programmes around the current time, so the guide always has a current entry.
+fn create_epg(now: DateTime<Utc>) -> String {
+    let start = now.duration_trunc(Duration::hours(1)).unwrap_or(now) - Duration::hours(EPG_HOURS_BEFORE);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<tv generator-info-name=\"tuliprox demo\">\n");
+    for (provider_idx, provider) in DEMO_PROVIDERS.iter().enumerate() {
+        for channel in provider.groups.iter().flat_map(|group| group.channels.iter()) {
+            let _ = writeln!(xml, r#"  <channel id="{}"><display-name>{channel}</display-name></channel>"#, epg_channel_id(provider_idx, channel));
+        }
+    }
+    for (provider_idx, provider) in DEMO_PROVIDERS.iter().enumerate() {
+        for group in provider.groups {
+            for (channel_idx, channel) in group.channels.iter().enumerate() {
+                let channel_id = epg_channel_id(provider_idx, channel);
+                for hour in 0..EPG_HOURS_BEFORE + EPG_HOURS_AFTER {
+                    let show_start = start + Duration::hours(hour);
+                    let show = group.shows[(channel_idx + usize::try_from(hour).unwrap_or_default()) % group.shows.len()];
+                    let _ = writeln!(xml, r#"  <programme start="{}" stop="{}" channel="{channel_id}"><title>{show}</title><desc>{show} on {channel}, generated for the demo mode.</desc></programme>"#,
+                                     format_xmltv_time(show_start), format_xmltv_time(show_start + Duration::hours(1)));
+                }
+            }
+        }
+    }
+    xml.push_str("</tv>\n");
+    xml
+}
+
+fn create_config(demo_dir: &Path) -> String {
+    let secret: String = rand::rng().sample_iter(&Alphanumeric).take(64).map(char::from).collect();
+    let web_root = get_exe_path().join("web");
+    let web_root = if web_root.exists() { web_root.to_string_lossy().to_string() } else { String::from("./web") };
+    format!(r"api:
+  host: 0.0.0.0
+  port: {DEMO_PORT}
+  web_root: {}
+working_dir: {}
+update_on_boot: true
+web_ui:
+  enabled: true
+  user_ui_enabled: true
+  auth:
+    enabled: true
+    issuer: tuliprox
+    secret: {secret}
+",
+            yaml_str(&web_root), yaml_str(&demo_dir.join("data").to_string_lossy()))
+}
+
+fn create_sources(demo_dir: &Path) -> String {
+    let epg_url = file_url(&demo_dir.join(DEMO_EPG_FILE));
+    let mut yaml = String::from("inputs:\n");
+    for provider in DEMO_PROVIDERS {
+        let _ = write!(yaml, r"  - name: {}
+    type: m3u
+    url: {}
+    epg:
+      sources:
+        - url: {}
+",
+                       provider.name, yaml_str(&file_url(&demo_dir.join(format!("{}.m3u", provider.name)))), yaml_str(&epg_url));
+    }
+    yaml.push_str("sources:\n  - inputs:\n");
+    for provider in DEMO_PROVIDERS {
+        let _ = writeln!(yaml, "      - {}", provider.name);
+    }
+    yaml.push_str(r#"    targets:
+      - name: demo
+        output:
+          - type: xtream
+          - type: m3u
+        filter: 'Group ~ ".*"'
+"#);
+    yaml
+}
+
+fn create_api_proxy() -> String {
+    format!(r"server:
+  - name: default
+    protocol: http
+    host: localhost
+    port: '{DEMO_PORT}'
+    timezone: UTC
+    message: Welcome to the tuliprox demo
+user:
+  - target: demo
+    credentials:
+      - username: {DEMO_USERNAME}
+        password: {DEMO_PASSWORD}
+        proxy: reverse
+        server: default
+        max_connections: 2
+        status: Active
+        ui_enabled: true
+      - username: demo-redirect
+        password: {DEMO_PASSWORD}
+        proxy: redirect
+        server: default
+        status: Active
+        ui_enabled: true
+      - username: demo-trial
+        password: {DEMO_PASSWORD}
+        proxy: reverse
+        server: default
+        max_connections: 1
+        status: Trial
+        ui_enabled: true
+")
+}
+
+/// Writes the config, providers, epg and users of the demo mode into a temporary directory.
+/// The files are recreated on every start, the demo does not need any real provider.
+pub fn create_demo_config() -> Result<ConfigPaths, Error> {
+    let demo_dir: PathBuf = std::env::temp_dir().join(DEMO_DIR);
+    fs::create_dir_all(demo_dir.join("data"))?;
+
+    for (provider_idx, provider) in DEMO_PROVIDERS.iter().enumerate() {
+        fs::write(demo_dir.join(format!("{}.m3u", provider.name)), create_provider_playlist(provider_idx, provider))?;
+    }
+    fs::write(demo_dir.join(DEMO_EPG_FILE), create_epg(Utc::now()))?;
+
+    let config_file = demo_dir.join("config.yml");
+    let sources_file = demo_dir.join("source.yml");
+    let api_proxy_file = demo_dir.join("api-proxy.yml");
+    fs::write(&config_file, create_config(&demo_dir))?;
+    fs::write(&sources_file, create_sources(&demo_dir))?;
+    fs::write(&api_proxy_file, create_api_proxy())?;
+    let password_hash = hash(DEMO_PASSWORD.as_bytes()).ok_or_else(|| Error::other("Failed to hash demo password"))?;
+    fs::write(demo_dir.join("user.txt"), format!("{DEMO_USERNAME}: {password_hash}\n"))?;
+
+    Ok(ConfigPaths {
+        config_path: demo_dir.to_string_lossy().to_string(),
+        config_file_path: config_file.to_string_lossy().to_string(),
+        sources_file_path: sources_file.to_string_lossy().to_string(),
+        mapping_file_path: None,
+        mapping_files_used: None,
+        api_proxy_file_path: api_proxy_file.to_string_lossy().to_string(),
+        custom_stream_response_path: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_epg, create_provider_playlist, epg_channel_id, DEMO_PROVIDERS};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_demo_playlist_and_epg_ids_match() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 30, 0).unwrap();
+        let epg = create_epg(now);
+        assert!(epg.contains(r#"start="20260101120000 +0000""#));
+        for (provider_idx, provider) in DEMO_PROVIDERS.iter().enumerate() {
+            let m3u = create_provider_playlist(provider_idx, provider);
+            for channel in provider.groups.iter().flat_map(|group| group.channels.iter()) {
+                let channel_id = epg_channel_id(provider_idx, channel);
+                assert!(m3u.contains(&format!(r#"tvg-id="{channel_id}""#)));
+                assert!(epg.contains(&format!(r#"<channel id="{channel_id}">"#)));
+            }
+            assert!(m3u.contains(".mp4\n"));
+        }
+    }
+}
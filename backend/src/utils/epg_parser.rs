@@ -3,6 +3,7 @@ use chrono::{DateTime,  Offset, TimeZone, Utc};
 use chrono_tz::Tz;
 use crate::model::{ConfigTarget, ProxyUserCredentials};
 use shared::model::PlaylistItemType;
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
 
 
@@ -43,6 +44,7 @@ pub struct EpgProcessingOptions {
     pub encrypt_secret: [u8; 16],
 }
 
+#[cfg(feature = "server")]
 pub fn get_epg_processing_options(app_state: &Arc<AppState>, user: &ProxyUserCredentials, target: &Arc<ConfigTarget>) -> EpgProcessingOptions {
     let rewrite_resources = app_state.app_config.is_reverse_proxy_resource_rewrite_enabled();
     let encrypt_secret = app_state.app_config.get_reverse_proxy_rewrite_secret().unwrap_or_else(|| app_state.app_config.encrypt_secret);
@@ -51,10 +53,12 @@ pub fn get_epg_processing_options(app_state: &Arc<AppState>, user: &ProxyUserCre
     // If redirect is false and rewrite_resources is true → rewrite_urls = true → rewriting allowed
     // If redirect is false and rewrite_resources is false → rewrite_urls = false → no rewriting
     let redirect = user.proxy.is_redirect(PlaylistItemType::Live) || target.is_force_redirect(PlaylistItemType::Live);
-    let rewrite_urls = !redirect && rewrite_resources;
+    let rewrite_urls = (!redirect || app_state.app_config.is_logo_proxy_enabled()) && rewrite_resources;
 
-    // Use 0 for timeshift if None
-    let timeshift = parse_timeshift(user.epg_timeshift.as_deref()).unwrap_or(0);
+    // The user timeshift overrides the target timezone, use 0 for timeshift if both are None
+    let timeshift = parse_timeshift(user.epg_timeshift.as_deref())
+        .or_else(|| target.timezone.and_then(|tz| parse_timeshift(Some(tz.name()))))
+        .unwrap_or(0);
     EpgProcessingOptions {
         rewrite_urls,
         offset_minutes: timeshift,
@@ -0,0 +1,285 @@
+use crate::model::SourcesConfig;
+use crate::utils::{config_file_reader, get_secrets_file_path, load_secrets, open_file, prepare_sources_batch, read_config_file, read_mappings_file};
+use crate::utils;
+use regex::Regex;
+use shared::model::{ApiProxyConfigDto, ConfigPaths, MappingsDto, SourcesConfigDto, TemplateValue};
+use shared::utils::CONSTANTS;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static RE_ERROR_LINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"line (?P<line>\d+)").unwrap());
+static RE_TEMPLATE_NAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\w+$").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigDiagnosticLevel {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigDiagnostic {
+    pub level: ConfigDiagnosticLevel,
+    pub file: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let level = match self.level {
+            ConfigDiagnosticLevel::Error => "error",
+            ConfigDiagnosticLevel::Warning => "warning",
+        };
+        match self.line {
+            Some(line) => write!(f, "{level}: {}:{line}: {}", self.file, self.message),
+            None => write!(f, "{level}: {}: {}", self.file, self.message),
+        }
+    }
+}
+
+/// The content of a checked file, used to find the line of a reported value.
+struct CheckedFile {
+    path: String,
+    content: String,
+}
+
+impl CheckedFile {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            content: std::fs::read_to_string(path).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the first line containing the value, lines are counted from 1.
+    fn find_line(&self, value: &str) -> Option<usize> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        self.content.lines().position(|line| line.contains(value)).map(|idx| idx + 1)
+    }
+
+    /// Parse errors carry the line, for validation errors the value after the last colon is looked up.
+    fn find_error_line(&self, message: &str) -> Option<usize> {
+        if let Some(caps) = RE_ERROR_LINE.captures(message) {
+            return caps["line"].parse().ok();
+        }
+        message.rsplit_once(": ").and_then(|(_, value)| self.find_line(value.trim_matches(|c| c == '\'' || c == '"')))
+    }
+}
+
+#[derive(Default)]
+struct ConfigCheck {
+    diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl ConfigCheck {
+    fn add(&mut self, level: ConfigDiagnosticLevel, file: &str, line: Option<usize>, message: String) {
+        self.diagnostics.push(ConfigDiagnostic { level, file: file.to_string(), line, message });
+    }
+
+    fn error_at(&mut self, file: &CheckedFile, value: &str, message: String) {
+        self.add(ConfigDiagnosticLevel::Error, &file.path, file.find_line(value), message);
+    }
+
+    fn warning_at(&mut self, file: &CheckedFile, value: &str, message: String) {
+        self.add(ConfigDiagnosticLevel::Warning, &file.path, file.find_line(value), message);
+    }
+
+    fn read_error(&mut self, file: &CheckedFile, message: &str) {
+        self.add(ConfigDiagnosticLevel::Error, &file.path, file.find_error_line(message), message.to_string());
+    }
+
+    /// The preparation stops at the first problem, which is skipped when the reference checks already reported its line.
+    fn prepare_error(&mut self, file: &CheckedFile, message: &str) {
+        let line = file.find_error_line(message);
+        if line.is_none() || !self.diagnostics.iter().any(|d| d.level == ConfigDiagnosticLevel::Error && d.file == file.path && d.line == line) {
+            self.add(ConfigDiagnosticLevel::Error, &file.path, line, message.to_string());
+        }
+    }
+
+    fn parse<T: serde::de::DeserializeOwned>(&mut self, file: &CheckedFile) -> Option<T> {
+        match open_file(&PathBuf::from(&file.path)) {
+            Ok(reader) => match serde_saphyr::from_reader(config_file_reader(reader, true)) {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    self.read_error(file, &err.to_string());
+                    None
+                }
+            },
+            Err(err) => {
+                self.add(ConfigDiagnosticLevel::Error, &file.path, None, format!("Can't open file: {err}"));
+                None
+            }
+        }
+    }
+
+    fn check_templates(&mut self, file: &CheckedFile, templates: &HashSet<&str>, context: &str, value: &str) {
+        for caps in CONSTANTS.re_template_var.captures_iter(value) {
+            let name = &caps[1];
+            if RE_TEMPLATE_NAME.is_match(name) && !templates.contains(name) {
+                self.error_at(file, &format!("!{name}!"), format!("{context} references unknown template: {name}"));
+            }
+        }
+    }
+
+    fn check_sources(&mut self, file: &CheckedFile, sources: &SourcesConfigDto, mappings: Option<&MappingsDto>) {
+        let templates: HashSet<&str> = sources.templates.iter().flatten().map(|t| t.name.as_str()).collect();
+        for template in sources.templates.iter().flatten() {
+            let context = format!("Template {}", template.name);
+            match &template.value {
+                TemplateValue::Single(value) => self.check_templates(file, &templates, &context, value),
+                TemplateValue::Multi(values) => values.iter().for_each(|value| self.check_templates(file, &templates, &context, value)),
+            }
+        }
+
+        let used_inputs: HashSet<&str> = sources.sources.iter().flat_map(|s| s.inputs.iter()).map(|name| name.trim()).collect();
+        for input in &sources.inputs {
+            if !used_inputs.contains(input.name.trim()) {
+                self.warning_at(file, &input.name, format!("Input {} is not used by any source", input.name));
+            }
+            self.check_input_epg(file, input);
+        }
+
+        let target_names: HashSet<&str> = sources.sources.iter().flat_map(|s| s.targets.iter()).map(|t| t.name.trim()).collect();
+        let mapping_ids: Option<HashSet<&str>> = mappings.map(|m| m.mappings.mapping.iter().map(|m| m.id.as_str()).collect());
+        for source in &sources.sources {
+            for input_name in &source.inputs {
+                if !sources.inputs.iter().any(|input| input.name.trim() == input_name.trim()) {
+                    self.error_at(file, input_name, format!("Source references unknown input: {input_name}"));
+                }
+            }
+            for target in &source.targets {
+                self.check_templates(file, &templates, &format!("Filter of target {}", target.name), &target.filter);
+                for mapping_id in target.mapping.iter().flatten() {
+                    match mapping_ids.as_ref() {
+                        Some(ids) if ids.contains(mapping_id.trim()) => {}
+                        Some(_) => self.error_at(file, mapping_id, format!("Target {} references unknown mapping: {mapping_id}", target.name)),
+                        None => self.error_at(file, mapping_id, format!("Target {} references mapping {mapping_id}, but no mapping file is loaded", target.name)),
+                    }
+                }
+                if let Some(shadow_of) = target.shadow_of.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+                    if !target_names.contains(shadow_of) {
+                        self.error_at(file, shadow_of, format!("Shadow target {} references unknown target: {shadow_of}", target.name));
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_input_epg(&mut self, file: &CheckedFile, input: &shared::model::ConfigInputDto) {
+        let Some(epg) = input.epg.as_ref() else { return; };
+        for epg_source in epg.sources.iter().flatten() {
+            let epg_url = epg_source.url.trim();
+            if epg_url.is_empty() || epg_url.eq_ignore_ascii_case("auto") {
+                continue;
+            }
+            match url::Url::parse(epg_url) {
+                Ok(url) if url.scheme() == "file" => {
+                    if !url.to_file_path().is_ok_and(|path| path.exists()) {
+                        self.error_at(file, epg_url, format!("Epg file of input {} does not exist: {epg_url}", input.name));
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => self.error_at(file, epg_url, format!("Invalid epg url of input {}: {err}: {epg_url}", input.name)),
+            }
+        }
+        if let Some(registry) = epg.tvg_id_normalization.as_ref().and_then(|n| n.registry.as_deref()).map(str::trim) {
+            if !registry.is_empty() && !Path::new(registry).exists() {
+                self.error_at(file, registry, format!("Tvg-id registry of input {} does not exist: {registry}", input.name));
+            }
+        }
+    }
+
+    fn check_api_proxy(&mut self, file: &CheckedFile, api_proxy: &ApiProxyConfigDto, sources: Option<&SourcesConfigDto>) {
+        if let Some(sources) = sources {
+            let target_names: HashSet<&str> = sources.sources.iter().flat_map(|s| s.targets.iter()).map(|t| t.name.trim()).collect();
+            for target_user in &api_proxy.user {
+                if !target_names.contains(target_user.target.trim()) {
+                    let line = file.find_line(&format!("target: {}", target_user.target.trim())).or_else(|| file.find_line(&target_user.target));
+                    self.add(ConfigDiagnosticLevel::Error, &file.path, line, format!("User target does not exist: {}", target_user.target));
+                }
+            }
+        }
+        let mut api_proxy = api_proxy.clone();
+        if let Err(err) = api_proxy.prepare() {
+            self.prepare_error(file, &err.message);
+        }
+    }
+}
+
+/// Reads the config, sources, mapping and api-proxy files and validates them and the references between them.
+/// All found problems are returned instead of stopping at the first one.
+pub async fn check_config(paths: &ConfigPaths) -> Vec<ConfigDiagnostic> {
+    let mut check = ConfigCheck::default();
+
+    if let Err(err) = load_secrets(&paths.config_path) {
+        let secrets_file = get_secrets_file_path(&paths.config_path);
+        check.add(ConfigDiagnosticLevel::Error, &secrets_file.to_string_lossy(), None, err.message);
+    }
+
+    let config_file = CheckedFile::new(&paths.config_file_path);
+    let config = match read_config_file(&config_file.path, true, true) {
+        Ok(config) => Some(config),
+        Err(err) => {
+            check.read_error(&config_file, &err.message);
+            None
+        }
+    };
+
+    let mapping_path = paths.mapping_file_path.clone().unwrap_or_else(|| {
+        config.as_ref().and_then(|c| c.mapping_path.as_ref())
+            .map_or_else(|| utils::get_default_mappings_path(&paths.config_path), |path| utils::resolve_env_var(path))
+    });
+    let mapping_file = CheckedFile::new(&mapping_path);
+    let mappings = match read_mappings_file(&mapping_path, true) {
+        Ok(mappings) => mappings.map(|(_, mappings)| mappings),
+        Err(err) => {
+            check.read_error(&mapping_file, &err.message);
+            None
+        }
+    };
+
+    let sources_file = CheckedFile::new(&paths.sources_file_path);
+    let sources = check.parse::<SourcesConfigDto>(&sources_file);
+    if let Some(raw_sources) = sources.as_ref() {
+        check.check_sources(&sources_file, raw_sources, mappings.as_ref());
+        let mut sources_dto = raw_sources.clone();
+        let hdhr_config = config.as_ref().and_then(shared::model::ConfigDto::get_hdhr_device_overview);
+        let prepared = match sources_dto.prepare(true, hdhr_config.as_ref()) {
+            Ok(()) => prepare_sources_batch(&mut sources_dto, true).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = prepared.and_then(|()| SourcesConfig::try_from(&sources_dto)) {
+            check.prepare_error(&sources_file, &err.message);
+        }
+    }
+
+    let api_proxy_file = CheckedFile::new(&paths.api_proxy_file_path);
+    if Path::new(&api_proxy_file.path).exists() {
+        if let Some(api_proxy) = check.parse::<ApiProxyConfigDto>(&api_proxy_file) {
+            check.check_api_proxy(&api_proxy_file, &api_proxy, sources.as_ref());
+        }
+    }
+
+    check.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CheckedFile;
+
+    #[test]
+    fn test_find_error_line() {
+        let file = CheckedFile {
+            path: "source.yml".to_string(),
+            content: "inputs:\n  - name: provider\nsources:\n  - inputs:\n      - unknown\n".to_string(),
+        };
+        assert_eq!(file.find_error_line("invalid type: expected a string at line 4, column 3"), Some(4));
+        assert_eq!(file.find_error_line("Source references unknown input: 'unknown'"), Some(5));
+        assert_eq!(file.find_error_line("Something went wrong"), None);
+    }
+}
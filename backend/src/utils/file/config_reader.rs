@@ -1,3 +1,4 @@
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
 use crate::model::Config;
 use crate::model::{ApiProxyConfig, AppConfig, SourcesConfig};
@@ -7,17 +8,17 @@ use crate::repository::{
 use crate::utils;
 use crate::utils::{file_exists_async, file_reader};
 use crate::utils::sys_utils::exit;
-use crate::utils::{open_file, read_mappings_file, EnvResolvingReader, FileLockManager};
+use crate::utils::{load_secrets, open_file, quote_secret_references, read_mappings_file, EnvResolvingReader, FileLockManager};
 use arc_swap::{ArcSwap, ArcSwapAny};
 use chrono::Local;
 use log::{error, info, warn};
 use serde::Serialize;
 use shared::error::{info_err, info_err_res, TuliproxError};
 use shared::model::{ApiProxyConfigDto, AppConfigDto, ConfigDto, ConfigInputAliasDto, ConfigPaths, HdHomeRunDeviceOverview, InputType, MsgKind, SourcesConfigDto, TargetUserDto};
+use shared::foundation::RESOLUTION_CACHE;
 use shared::utils::CONSTANTS;
-use std::env;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs;
@@ -25,26 +26,12 @@ use shared::concat_string;
 use crate::utils::request::{is_uri};
 use url::Url;
 
-enum EitherReader<L, R> {
-    Left(L),
-    Right(R),
-}
-
-// `Read`-Trait for Either
-impl<L: Read, R: Read> Read for EitherReader<L, R> {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        match self {
-            EitherReader::Left(reader) => reader.read(buf),
-            EitherReader::Right(reader) => reader.read(buf),
-        }
-    }
-}
-
+/// Without resolving the `!secret` references are kept as strings, so they survive a rewrite of the file.
 pub fn config_file_reader(file: File, resolve_env: bool) -> impl Read {
     if resolve_env {
-        EitherReader::Left(EnvResolvingReader::new(file_reader(file)))
+        EnvResolvingReader::new(file_reader(file))
     } else {
-        EitherReader::Right(file_reader(file))
+        EnvResolvingReader::with_resolver(file_reader(file), quote_secret_references)
     }
 }
 
@@ -105,6 +92,13 @@ pub fn read_sources_file_from_path(
     }
 }
 
+/// Resolves the env variables and secrets of sources read without resolving, to validate changes before they are written.
+pub fn resolve_sources_config(sources: &SourcesConfigDto) -> Result<SourcesConfigDto, TuliproxError> {
+    let content = serde_saphyr::to_string(sources).map_err(|err| info_err!("Could not serialize sources: {err}"))?;
+    serde_saphyr::from_reader(EnvResolvingReader::new(file_reader(content.as_bytes())))
+        .map_err(|err| info_err!("Could not read sources: {err}"))
+}
+
 pub fn read_sources_file(
     sources_file: &str,
     resolve_env: bool,
@@ -274,6 +268,9 @@ pub async fn read_initial_app_config(
     let config_file = paths.config_file_path.as_str();
     let sources_file = paths.sources_file_path.as_str();
 
+    if resolve_env {
+        load_secrets(config_path)?;
+    }
     let config_dto = read_config_file(config_file, resolve_env, include_computed)?;
     let mut sources_dto = read_sources_file(
         sources_file,
@@ -336,6 +333,9 @@ pub async fn read_initial_app_config(
     }
 
     if server_mode {
+        if include_computed {
+            app_config.prepare_custom_stream_response();
+        }
         match read_api_proxy_config(&app_config, resolve_env).await {
             Ok(Some(api_proxy)) => app_config.set_api_proxy(api_proxy)?,
             Ok(None) => info!("Api-Proxy file: not used"),
@@ -497,6 +497,7 @@ where
     write_config_file(file_path, backup_dir, config, "source.yml").await
 }
 
+#[cfg(feature = "server")]
 pub async fn persist_source_config(
     app_state: &Arc<AppState>,
     source_file_path: Option<&Path>,
@@ -560,6 +561,7 @@ pub async fn persist_source_config(
     Ok(doc)
 }
 
+#[cfg(feature = "server")]
 pub async fn validate_and_persist_source_config(
     app_state: &Arc<AppState>,
     dto: SourcesConfigDto,
@@ -581,14 +583,15 @@ pub fn resolve_env_var(value: &str) -> String {
         .re_env_var
         .replace_all(value, |caps: &regex::Captures| {
             let var_name = &caps["var"];
-            env::var(var_name).unwrap_or_else(|e| {
-                error!("Could not resolve env var '{var_name}': {e}");
+            RESOLUTION_CACHE.get_env_var(var_name).unwrap_or_else(|| {
+                error!("Could not resolve env var '{var_name}': not set or not unicode");
                 format!("${{env:{var_name}}}")
             })
         })
         .to_string()
 }
 
+#[cfg(feature = "server")]
 pub async fn persist_messaging_templates(app_state: &Arc<AppState>, cfg: &mut ConfigDto) -> Result<(), TuliproxError> {
     let templates_dir = {
         let paths = app_state.app_config.paths.load();
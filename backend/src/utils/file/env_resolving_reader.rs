@@ -1,16 +1,27 @@
 use std::io::{self, BufRead, BufReader, Read, Cursor};
-use crate::utils::resolve_env_var;
+use crate::utils::{resolve_env_var, resolve_secrets};
 
 pub struct EnvResolvingReader<R: Read> {
     inner: BufReader<R>,
     buffer: Cursor<Vec<u8>>,
+    resolve: fn(&str) -> String,
+}
+
+fn resolve_config_line(line: &str) -> String {
+    resolve_secrets(&resolve_env_var(line))
 }
 
 impl<R: Read> EnvResolvingReader<R> {
+    /// Resolves env variables and secrets.
     pub(crate) fn new(reader: BufReader<R>) -> Self {
+        Self::with_resolver(reader, resolve_config_line)
+    }
+
+    pub(crate) fn with_resolver(reader: BufReader<R>, resolve: fn(&str) -> String) -> Self {
         Self {
             inner: reader,
             buffer: Cursor::new(Vec::new()),
+            resolve,
         }
     }
 
@@ -19,7 +30,7 @@ impl<R: Read> EnvResolvingReader<R> {
         self.buffer = Cursor::new(Vec::new());
 
         if self.inner.read_line(&mut line)? > 0 {
-            let processed_line = resolve_env_var(&line);
+            let processed_line = (self.resolve)(&line);
             self.buffer = Cursor::new(processed_line.into_bytes());
         }
 
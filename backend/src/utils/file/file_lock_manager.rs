@@ -8,6 +8,7 @@ use tokio::sync::{Mutex, RwLock};
 use tokio::sync::{OwnedRwLockReadGuard, OwnedRwLockWriteGuard};
 use shared::error::str_to_io_error;
 use path_clean::PathClean;
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -51,6 +52,15 @@ impl FileLockManager {
         FileReadGuard::new(guard)
     }
 
+    // Tries to acquire a read lock for the specified file and returns a FileReadGuard.
+    pub async fn try_read_lock(&self, path: &Path) -> io::Result<FileReadGuard> {
+        let file_lock = self.get_or_create_lock(Self::get_lock_key_for_path(path)).await;
+        match Arc::clone(&file_lock).try_read_owned() {
+            Ok(lock_guard) => Ok(FileReadGuard::new(lock_guard)),
+            Err(_) => Err(str_to_io_error("Failed to acquire read lock"))
+        }
+    }
+
     // Acquires a write lock for the specified file and returns a FileWriteGuard.
     pub async fn write_lock(&self, path: &Path) -> FileWriteGuard {
         let file_lock = self.get_or_create_lock(Self::get_lock_key_for_path(path)).await;
@@ -172,6 +182,7 @@ fn normalize_path(path: &Path) -> PathBuf {
     base.clean()
 }
 
+#[cfg(feature = "server")]
 pub fn exec_file_lock_prune(app_state: &Arc<AppState>) {
     let app_state = Arc::clone(app_state);
     tokio::spawn({
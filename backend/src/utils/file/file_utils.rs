@@ -252,6 +252,19 @@ pub async fn async_open_readonly_file(path: &Path) -> std::io::Result<tokio::fs:
     tokio::fs::OpenOptions::new().read(true).write(false).truncate(false).create(false).open(path).await
 }
 
+/// Writes a file with keys or secrets, on unix it is only readable by the owner (mode 0600).
+pub fn write_private_file(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    // the mode is only applied when the file is created, an existing file keeps its permissions
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    std::io::Write::write_all(&mut file, content)
+}
+
 pub fn rename_or_copy(src: &Path, dest: &Path, remove_old: bool) -> std::io::Result<()> {
     // Try to rename the file
     if fs::rename(src, dest).is_err() {
@@ -427,7 +440,7 @@ pub fn get_file_extension(path: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::normalize_string_path;
+    use super::{normalize_string_path, write_private_file};
 
     #[test]
     fn test_simple_relative_path() {
@@ -463,4 +476,17 @@ mod tests {
         let normalized = normalize_string_path(input);
         assert_eq!(normalized, "");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_private_file() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret.pem");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        write_private_file(&path, b"new").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+    }
 }
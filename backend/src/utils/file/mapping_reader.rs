@@ -27,8 +27,8 @@ fn read_mapping(mapping_file: &Path, resolve_var: bool, prepare_mappings: bool)
     Ok(None)
 }
 
-fn read_mappings_from_file(mappings_file: &Path, resolve_env: bool) -> Result<Option<(Vec<PathBuf>, MappingsDto)>, TuliproxError> {
-    match read_mapping(mappings_file, resolve_env, true) {
+fn read_mappings_from_file(mappings_file: &Path, resolve_env: bool, prepare_mappings: bool) -> Result<Option<(Vec<PathBuf>, MappingsDto)>, TuliproxError> {
+    match read_mapping(mappings_file, resolve_env, prepare_mappings) {
         Ok(mappings) => {
             match mappings {
                 None => Ok(None),
@@ -60,7 +60,7 @@ fn merge_mappings(mappings: Vec<MappingDto>) -> Vec<MappingDto> {
 
     map.into_values().collect()
 }
-fn merge_mapping_definitions(mappings: Vec<MappingsDto>) -> Result<Option<MappingsDto>, TuliproxError> {
+fn merge_mapping_definitions(mappings: Vec<MappingsDto>, prepare_mappings: bool) -> Result<Option<MappingsDto>, TuliproxError> {
     let mut merged_templates: Vec<PatternTemplate> = Vec::new();
     let mut merged_mapping: Vec<MappingDto> = Vec::new();
 
@@ -78,11 +78,13 @@ fn merge_mapping_definitions(mappings: Vec<MappingsDto>) -> Result<Option<Mappin
             mapping: merge_mappings(merged_mapping)
         }
     };
-    result.prepare()?;
+    if prepare_mappings {
+        result.prepare()?;
+    }
     Ok(Some(result))
 }
 
-fn read_mappings_from_directory(path: &Path, resolve_env: bool) -> Result<Option<(Vec<PathBuf>, MappingsDto)>, TuliproxError> {
+fn read_mappings_from_directory(path: &Path, resolve_env: bool, prepare_mappings: bool) -> Result<Option<(Vec<PathBuf>, MappingsDto)>, TuliproxError> {
     let mut files = vec![];
     let mut visit = |entry: &std::fs::DirEntry, metadata: &std::fs::Metadata| {
         if metadata.is_file() {
@@ -112,21 +114,21 @@ fn read_mappings_from_directory(path: &Path, resolve_env: bool) -> Result<Option
     if mappings.is_empty() {
         return Ok(None);
     }
-    match merge_mapping_definitions(mappings) {
+    match merge_mapping_definitions(mappings, prepare_mappings) {
         Ok(Some(merged_mappings)) => Ok(Some((loaded_mapping_files, merged_mappings))),
         Ok(None) => Ok(None),
         Err(err) => Err(err),
     }
 }
 
-pub fn read_mappings_file(mappings_file: &str, resolve_env: bool) -> Result<Option<(Vec<PathBuf>, MappingsDto)>, TuliproxError> {
+fn read_mappings_path(mappings_file: &str, resolve_env: bool, prepare_mappings: bool) -> Result<Option<(Vec<PathBuf>, MappingsDto)>, TuliproxError> {
     let path = PathBuf::from(mappings_file);
     match std::fs::metadata(&path) {
         Ok(metadata) => {
             if metadata.is_file() {
-                read_mappings_from_file(&path, resolve_env)
+                read_mappings_from_file(&path, resolve_env, prepare_mappings)
             } else if metadata.is_dir() {
-                read_mappings_from_directory(&path, resolve_env)
+                read_mappings_from_directory(&path, resolve_env, prepare_mappings)
             } else {
                 Ok(None)
             }
@@ -137,6 +139,15 @@ pub fn read_mappings_file(mappings_file: &str, resolve_env: bool) -> Result<Opti
     }
 }
 
+pub fn read_mappings_file(mappings_file: &str, resolve_env: bool) -> Result<Option<(Vec<PathBuf>, MappingsDto)>, TuliproxError> {
+    read_mappings_path(mappings_file, resolve_env, true)
+}
+
+/// Reads the mappings without preparing them, the templates are not yet applied to the filters and scripts.
+pub fn read_raw_mappings_file(mappings_file: &str, resolve_env: bool) -> Result<Option<MappingsDto>, TuliproxError> {
+    Ok(read_mappings_path(mappings_file, resolve_env, false)?.map(|(_, mappings)| mappings))
+}
+
 pub fn read_mappings(mappings_file: &str, resolve_env: bool) -> Result<Option<(Vec<PathBuf>, Mappings)>, TuliproxError> {
     match read_mappings_file(mappings_file, resolve_env)? {
         Some((paths, dto)) => Ok(Some((paths, Mappings::from(&dto)))),
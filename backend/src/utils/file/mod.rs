@@ -4,9 +4,13 @@ mod file_lock_manager;
 mod config_reader;
 mod env_resolving_reader;
 mod mapping_reader;
+mod secrets;
+mod config_check;
 
 pub use self::file_utils::*;
 pub use self::file_lock_manager::*;
 pub use self::config_reader::*;
 pub use self::mapping_reader::*;
-pub use self::env_resolving_reader::*;
\ No newline at end of file
+pub use self::env_resolving_reader::*;
+pub use self::secrets::*;
+pub use self::config_check::*;
\ No newline at end of file
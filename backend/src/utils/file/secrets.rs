@@ -0,0 +1,134 @@
+use crate::utils::{decrypt_secret, derive_secret_key, encrypt_secret, resolve_env_var, write_private_file, SecretKeyParams};
+use arc_swap::ArcSwap;
+use log::error;
+use regex::Regex;
+use shared::error::{info_err, info_err_res, TuliproxError};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock};
+
+pub const SECRET_KEY_ENV: &str = "TULIPROX_SECRET_KEY";
+pub const SECRETS_FILE_ENV: &str = "TULIPROX_SECRETS_FILE";
+const SECRETS_FILE: &str = "secrets.yml";
+
+static SECRETS: LazyLock<ArcSwap<HashMap<String, String>>> = LazyLock::new(|| ArcSwap::from_pointee(HashMap::new()));
+static RE_SECRET_NAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[\w.-]+$").unwrap());
+static RE_SECRET_REF: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#""!secret\s+(?P<dq>[\w.-]+)"|'!secret\s+(?P<sq>[\w.-]+)'|!secret\s+(?P<name>[\w.-]+)"#).unwrap());
+static RE_UNQUOTED_SECRET_REF: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(?P<prefix>^|[^"'\w])!secret\s+(?P<name>[\w.-]+)"#).unwrap());
+
+pub fn get_secrets_file_path(config_path: &str) -> PathBuf {
+    match std::env::var(SECRETS_FILE_ENV) {
+        Ok(path) if !path.trim().is_empty() => PathBuf::from(resolve_env_var(path.trim())),
+        _ => PathBuf::from(config_path).join(SECRETS_FILE),
+    }
+}
+
+/// The `kdf` header holds the salt and the parameters of the key derivation, the secrets are encrypted with the derived key.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SecretsFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kdf: Option<SecretKeyParams>,
+    #[serde(default)]
+    secrets: BTreeMap<String, String>,
+}
+
+fn get_secret_key(params: &SecretKeyParams) -> Result<[u8; 32], TuliproxError> {
+    match std::env::var(SECRET_KEY_ENV) {
+        Ok(master_key) if !master_key.is_empty() => derive_secret_key(&master_key, params),
+        _ => info_err_res!("The master key for the secrets is not set, set the environment variable {SECRET_KEY_ENV}"),
+    }
+}
+
+fn read_secrets_file(path: &PathBuf) -> Result<SecretsFile, TuliproxError> {
+    if !path.exists() {
+        return Ok(SecretsFile::default());
+    }
+    let file = File::open(path).map_err(|err| info_err!("Could not open secrets file {}: {err}", path.display()))?;
+    serde_saphyr::from_reader::<_, Option<SecretsFile>>(file)
+        .map(Option::unwrap_or_default)
+        .map_err(|err| info_err!("Could not read secrets file {}: {err}", path.display()))
+}
+
+/// Loads and decrypts the secrets file, the secrets are referenced with `!secret <name>` in the config files.
+pub fn load_secrets(config_path: &str) -> Result<(), TuliproxError> {
+    let path = get_secrets_file_path(config_path);
+    let file = read_secrets_file(&path)?;
+    let mut secrets = HashMap::with_capacity(file.secrets.len());
+    if !file.secrets.is_empty() {
+        let Some(params) = file.kdf.as_ref() else {
+            return info_err_res!("The secrets file {} has no kdf header, encrypt the secrets again", path.display());
+        };
+        let key = get_secret_key(params)?;
+        for (name, value) in file.secrets {
+            let decrypted = decrypt_secret(&key, &value)
+                .map_err(|err| info_err!("Could not decrypt secret {name} from {}: {err}", path.display()))?;
+            secrets.insert(name, decrypted);
+        }
+    }
+    SECRETS.store(Arc::new(secrets));
+    Ok(())
+}
+
+/// Encrypts the value and adds or replaces the secret in the secrets file.
+pub fn store_secret(config_path: &str, name: &str, value: &str) -> Result<PathBuf, TuliproxError> {
+    if !RE_SECRET_NAME.is_match(name) {
+        return info_err_res!("Invalid secret name {name}, only letters, digits, '_', '.' and '-' are allowed");
+    }
+    let path = get_secrets_file_path(config_path);
+    let mut file = read_secrets_file(&path)?;
+    if file.kdf.is_none() && !file.secrets.is_empty() {
+        return info_err_res!("The secrets file {} has no kdf header, encrypt the secrets again", path.display());
+    }
+    let params = file.kdf.get_or_insert_with(SecretKeyParams::generate);
+    let key = get_secret_key(params)?;
+    file.secrets.insert(name.to_string(), encrypt_secret(&key, value)?);
+    let content = serde_saphyr::to_string(&file).map_err(|err| info_err!("Could not serialize secrets: {err}"))?;
+    write_private_file(&path, content.as_bytes()).map_err(|err| info_err!("Could not write secrets file {}: {err}", path.display()))?;
+    Ok(path)
+}
+
+/// Replaces the `!secret <name>` references with the decrypted value as quoted yaml string.
+pub fn resolve_secrets(value: &str) -> String {
+    if !value.contains("!secret") {
+        return value.to_string();
+    }
+    let secrets = SECRETS.load();
+    RE_SECRET_REF
+        .replace_all(value, |caps: &regex::Captures| {
+            let name = caps.name("dq").or_else(|| caps.name("sq")).or_else(|| caps.name("name")).map_or("", |m| m.as_str());
+            secrets.get(name).map_or_else(|| {
+                error!("Could not resolve secret '{name}': not in the secrets file");
+                caps[0].to_string()
+            }, |secret| serde_json::to_string(secret).unwrap_or_default())
+        })
+        .to_string()
+}
+
+/// Quotes the `!secret <name>` references when the config is read without resolving,
+/// the references are kept when the file is written back.
+pub fn quote_secret_references(value: &str) -> String {
+    if !value.contains("!secret") {
+        return value.to_string();
+    }
+    RE_UNQUOTED_SECRET_REF.replace_all(value, "${prefix}\"!secret ${name}\"").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{quote_secret_references, resolve_secrets, SECRETS};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_resolve_secrets() {
+        SECRETS.store(Arc::new(HashMap::from([("provider.password".to_string(), "pa\"ss".to_string())])));
+        assert_eq!(resolve_secrets("password: !secret provider.password\n"), "password: \"pa\\\"ss\"\n");
+        assert_eq!(resolve_secrets("password: '!secret provider.password'\n"), "password: \"pa\\\"ss\"\n");
+        assert_eq!(resolve_secrets("password: !secret unknown\n"), "password: !secret unknown\n");
+
+        assert_eq!(quote_secret_references("password: !secret provider.password\n"), "password: \"!secret provider.password\"\n");
+        assert_eq!(quote_secret_references("password: '!secret provider.password'\n"), "password: '!secret provider.password'\n");
+    }
+}
@@ -1,8 +1,13 @@
 use crate::model::LogLevelConfig;
 use crate::utils::config_file_reader;
 use env_logger::{Builder, Target};
-use log::{error, info, LevelFilter};
+use log::{error, info, Level, LevelFilter, Log, Metadata, Record};
+use shared::model::LogEntryDto;
+use std::collections::VecDeque;
 use std::fs::File;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+use tokio::sync::broadcast;
 
 const LOG_ERROR_LEVEL_MOD: &[&str] = &[
     "reqwest::async_impl::client",
@@ -11,6 +16,106 @@ const LOG_ERROR_LEVEL_MOD: &[&str] = &[
     "tungstenite::protocol"
 ];
 
+const LOG_BUFFER_SIZE: usize = 1000;
+const LOG_CHANNEL_SIZE: usize = 256;
+
+static LOG_BUFFER: LazyLock<LogBuffer> = LazyLock::new(|| LogBuffer::new(LOG_BUFFER_SIZE));
+static LOG_FORWARDER: OnceLock<fn(Level, &str)> = OnceLock::new();
+
+/// Forwards the written records to an additional sink like the windows event log, only the first forwarder is used.
+pub fn set_log_forwarder(forwarder: fn(Level, &str)) {
+    let _ = LOG_FORWARDER.set(forwarder);
+}
+
+/// Keeps the last log entries for the log stream of the web ui and forwards new entries to the subscribers.
+struct LogBuffer {
+    capacity: usize,
+    entries: Mutex<VecDeque<Arc<LogEntryDto>>>,
+    sender: broadcast::Sender<Arc<LogEntryDto>>,
+}
+
+impl LogBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            sender: broadcast::channel(LOG_CHANNEL_SIZE).0,
+        }
+    }
+
+    fn push(&self, entry: LogEntryDto) {
+        let entry = Arc::new(entry);
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.len() >= self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back(Arc::clone(&entry));
+            // sent under the lock, a new subscriber gets each entry either from the buffer or the channel
+            let _ = self.sender.send(entry);
+        }
+    }
+
+    fn subscribe(&self, filter: &LogFilter) -> (Vec<Arc<LogEntryDto>>, broadcast::Receiver<Arc<LogEntryDto>>) {
+        let entries = self.entries.lock().map(|entries| entries.iter().filter(|entry| filter.matches(entry)).cloned().collect()).unwrap_or_default();
+        (entries, self.sender.subscribe())
+    }
+}
+
+/// Filter of the log stream, `level` is the lowest severity and `module` a prefix of the record target.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    pub level: Option<LevelFilter>,
+    pub module: Option<String>,
+}
+
+impl LogFilter {
+    pub fn new(level: Option<&str>, module: Option<&str>) -> Self {
+        Self {
+            level: level.map(str::trim).filter(|level| !level.is_empty()).map(get_log_level),
+            module: module.map(str::trim).filter(|module| !module.is_empty()).map(ToString::to_string),
+        }
+    }
+
+    pub fn matches(&self, entry: &LogEntryDto) -> bool {
+        self.level.is_none_or(|filter| Level::from_str(&entry.level).is_ok_and(|level| level <= filter))
+            && self.module.as_ref().is_none_or(|module| entry.target.starts_with(module.as_str()))
+    }
+}
+
+/// Returns the buffered log entries matching the filter and a receiver for the following entries.
+pub fn subscribe_log(filter: &LogFilter) -> (Vec<Arc<LogEntryDto>>, broadcast::Receiver<Arc<LogEntryDto>>) {
+    LOG_BUFFER.subscribe(filter)
+}
+
+/// Writes through the `env_logger` and keeps the written records in the log buffer.
+struct BufferedLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for BufferedLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.inner.matches(record) {
+            self.inner.log(record);
+            if let Some(forward) = LOG_FORWARDER.get() {
+                forward(record.level(), &record.args().to_string());
+            }
+            LOG_BUFFER.push(LogEntryDto {
+                ts: chrono::Utc::now().timestamp_millis(),
+                level: record.level().to_string(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
 
 fn get_log_level(log_level: &str) -> LevelFilter {
     match log_level.to_lowercase().as_str() {
@@ -23,6 +128,14 @@ fn get_log_level(log_level: &str) -> LevelFilter {
     }
 }
 
+/// Reads only the log relevant parts of the config file, before the whole config is parsed.
+pub fn read_log_level_config(config_file: &str) -> Option<LogLevelConfig> {
+    File::open(config_file).ok()
+        .and_then(|file| serde_saphyr::from_reader::<_, LogLevelConfig>(config_file_reader(file, true))
+            .map_err(|e| error!("Failed to parse log config file: {e}"))
+            .ok())
+}
+
 pub fn init_logger(user_log_level: Option<&str>, config_file: &str) {
 
 
@@ -42,10 +155,7 @@ pub fn init_logger(user_log_level: Option<&str>, config_file: &str) {
         .map(std::string::ToString::to_string) // cli-argument
         .or(env_log_level) // env
         .or_else(|| {               // config
-            File::open(config_file).ok()
-                .and_then(|file| serde_saphyr::from_reader::<_, LogLevelConfig>(config_file_reader(file, true))
-                    .map_err(|e| error!("Failed to parse log config file: {e}"))
-                    .ok())
+            read_log_level_config(config_file)
                 .and_then(|cfg| cfg.log.and_then(|l| l.log_level))
         })
         .unwrap_or_else(|| "info".to_string()); // Default
@@ -74,6 +184,43 @@ pub fn init_logger(user_log_level: Option<&str>, config_file: &str) {
     for module in LOG_ERROR_LEVEL_MOD {
         log_builder.filter_module(module, LevelFilter::Error);
     }
-    let _ = log_builder.try_init();
+    let logger = log_builder.build();
+    let max_level = logger.filter();
+    if log::set_boxed_logger(Box::new(BufferedLogger { inner: logger })).is_ok() {
+        log::set_max_level(max_level);
+    }
     info!("Log Level {}", &log_levels.join(", "));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{LogBuffer, LogFilter};
+    use shared::model::LogEntryDto;
+
+    fn entry(level: &str, target: &str) -> LogEntryDto {
+        LogEntryDto { ts: 0, level: level.to_string(), target: target.to_string(), message: String::new() }
+    }
+
+    #[test]
+    fn test_log_filter() {
+        let filter = LogFilter::new(Some("warn"), Some("tuliprox::processing"));
+        assert!(filter.matches(&entry("ERROR", "tuliprox::processing::processor")));
+        assert!(filter.matches(&entry("WARN", "tuliprox::processing")));
+        assert!(!filter.matches(&entry("INFO", "tuliprox::processing")));
+        assert!(!filter.matches(&entry("ERROR", "tuliprox::api")));
+        assert!(LogFilter::new(Some(""), None).matches(&entry("TRACE", "hyper")));
+    }
+
+    #[test]
+    fn test_log_buffer() {
+        let buffer = LogBuffer::new(2);
+        buffer.push(entry("INFO", "a"));
+        let (entries, mut rx) = buffer.subscribe(&LogFilter::default());
+        assert_eq!(entries.len(), 1);
+        buffer.push(entry("WARN", "b"));
+        buffer.push(entry("ERROR", "c"));
+        assert_eq!(rx.try_recv().unwrap().target, "b");
+        let (entries, _) = buffer.subscribe(&LogFilter::default());
+        assert_eq!(entries.iter().map(|entry| entry.target.as_str()).collect::<Vec<_>>(), vec!["b", "c"]);
+    }
+}
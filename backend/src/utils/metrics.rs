@@ -0,0 +1,156 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds of the duration histogram buckets in milliseconds.
+const DURATION_BUCKETS_MS: [u64; 12] = [1, 5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    pub const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    #[inline]
+    pub fn inc(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn add(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Cumulative duration histogram with fixed millisecond buckets.
+#[derive(Debug)]
+pub struct DurationHistogram {
+    buckets: [AtomicU64; DURATION_BUCKETS_MS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Default for DurationHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DurationHistogram {
+    pub const fn new() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; DURATION_BUCKETS_MS.len()],
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe(&self, duration: Duration) {
+        let millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        if let Some(idx) = DURATION_BUCKETS_MS.iter().position(|bound| millis <= *bound) {
+            self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(u64::try_from(duration.as_micros()).unwrap_or(u64::MAX), Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+/// Renders metrics in the Prometheus text exposition format.
+#[derive(Debug, Default)]
+pub struct MetricsWriter {
+    out: String,
+}
+
+impl MetricsWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn write_meta(&mut self, name: &str, help: &str, kind: &str) {
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let _ = writeln!(self.out, "# TYPE {name} {kind}");
+    }
+
+    pub fn counter(&mut self, name: &str, help: &str, value: u64) {
+        self.write_meta(name, help, "counter");
+        let _ = writeln!(self.out, "{name} {value}");
+    }
+
+    pub fn gauge(&mut self, name: &str, help: &str, value: u64) {
+        self.write_meta(name, help, "gauge");
+        let _ = writeln!(self.out, "{name} {value}");
+    }
+
+    /// Writes one gauge with a sample for each `(label_value, value)` pair.
+    pub fn labeled_gauge<'a, I>(&mut self, name: &str, help: &str, label: &str, values: I)
+    where
+        I: IntoIterator<Item = (&'a str, u64)>,
+    {
+        self.write_meta(name, help, "gauge");
+        for (label_value, value) in values {
+            let escaped = label_value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            let _ = writeln!(self.out, "{name}{{{label}=\"{escaped}\"}} {value}");
+        }
+    }
+
+    pub fn histogram(&mut self, name: &str, help: &str, histogram: &DurationHistogram) {
+        self.write_meta(name, help, "histogram");
+        let mut cumulative = 0;
+        for (bound, bucket) in DURATION_BUCKETS_MS.iter().zip(&histogram.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            #[allow(clippy::cast_precision_loss)]
+            let le = *bound as f64 / 1000.0;
+            let _ = writeln!(self.out, "{name}_bucket{{le=\"{le}\"}} {cumulative}");
+        }
+        let count = histogram.count();
+        #[allow(clippy::cast_precision_loss)]
+        let sum = histogram.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(self.out, "{name}_bucket{{le=\"+Inf\"}} {count}");
+        let _ = writeln!(self.out, "{name}_sum {sum}");
+        let _ = writeln!(self.out, "{name}_count {count}");
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DurationHistogram, MetricsWriter};
+    use std::time::Duration;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let histogram = DurationHistogram::new();
+        histogram.observe(Duration::from_micros(500));
+        histogram.observe(Duration::from_millis(20));
+        histogram.observe(Duration::from_secs(60));
+
+        let mut writer = MetricsWriter::new();
+        writer.histogram("test_duration_seconds", "Test", &histogram);
+        let output = writer.finish();
+        assert!(output.contains("test_duration_seconds_bucket{le=\"0.001\"} 1\n"));
+        assert!(output.contains("test_duration_seconds_bucket{le=\"0.025\"} 2\n"));
+        assert!(output.contains("test_duration_seconds_bucket{le=\"10\"} 2\n"));
+        assert!(output.contains("test_duration_seconds_bucket{le=\"+Inf\"} 3\n"));
+        assert!(output.contains("test_duration_seconds_count 3\n"));
+    }
+
+    #[test]
+    fn test_labeled_gauge_escapes_label_values() {
+        let mut writer = MetricsWriter::new();
+        writer.labeled_gauge("test_size_bytes", "Test", "tree", [("a\"b", 5)]);
+        assert!(writer.finish().contains("test_size_bytes{tree=\"a\\\"b\"} 5\n"));
+    }
+}
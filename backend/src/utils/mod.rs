@@ -5,6 +5,7 @@ mod network;
 mod crypto_utils;
 mod step_measure;
 mod logging;
+mod crash_report;
 mod trakt;
 mod json_utils;
 mod binary_utils;
@@ -12,15 +13,26 @@ mod telegram;
 mod geoip;
 mod db_viewer;
 mod epg_parser;
+mod metrics;
+mod web_push;
+mod demo;
+mod spreadsheet;
+mod service;
 
 pub use self::binary_utils::*;
 pub use self::logging::*;
+pub use self::crash_report::*;
 pub use self::trakt::*;
 pub use self::telegram::*;
 pub use self::geoip::*;
 pub use self::db_viewer::*;
 pub use shared::utils::*;
 pub use self::epg_parser::*;
+pub use self::metrics::*;
+pub use self::web_push::*;
+pub use self::demo::*;
+pub use self::spreadsheet::*;
+pub use self::service::*;
 
 #[macro_export]
 macro_rules! debug_if_enabled {
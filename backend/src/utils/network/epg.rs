@@ -3,7 +3,7 @@ use crate::model::{TVGuide};
 use crate::processing::processor::playlist::PlaylistProcessingContext;
 use crate::repository::get_input_storage_path;
 use crate::repository::storage_const;
-use crate::utils::{add_prefix_to_filename, prepare_file_path, request};
+use crate::utils::{add_prefix_to_filename, file_exists_async, prepare_file_path, request};
 use crate::utils::cleanup_unlisted_files_with_suffix;
 use log::debug;
 use shared::concat_string;
@@ -34,6 +34,11 @@ async fn download_epg_file(url: &str, ctx: &PlaylistProcessingContext,
     debug!("Getting epg file path for url: {}", sanitize_sensitive_info(url));
     let persist_file_path = get_input_raw_epg_file_path(url, input, working_dir).map_err(|e| info_err!("Could not access epg file download directory: {}", e))?;
 
+    if input.is_in_maintenance() && file_exists_async(&persist_file_path).await {
+        debug!("Input '{}' is in maintenance, using stored epg file: {}", input.name, persist_file_path.display());
+        return Ok(persist_file_path);
+    }
+
     if input.cache_duration_seconds > 0 {
         if let Ok(metadata) = tokio::fs::metadata(&persist_file_path).await {
             if let Ok(modified) = metadata.modified() {
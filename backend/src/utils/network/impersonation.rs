@@ -0,0 +1,140 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::tls::Version;
+use shared::model::ImpersonationProfile;
+
+const CHROME_HEADERS: &[(&str, &str)] = &[
+    ("sec-ch-ua", r#""Google Chrome";v="131", "Chromium";v="131", "Not_A Brand";v="24""#),
+    ("sec-ch-ua-mobile", "?0"),
+    ("sec-ch-ua-platform", r#""Windows""#),
+    ("upgrade-insecure-requests", "1"),
+    ("user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36"),
+    ("accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8"),
+    ("sec-fetch-site", "none"),
+    ("sec-fetch-mode", "navigate"),
+    ("sec-fetch-user", "?1"),
+    ("sec-fetch-dest", "document"),
+    ("accept-language", "en-US,en;q=0.9"),
+];
+
+const FIREFOX_HEADERS: &[(&str, &str)] = &[
+    ("user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:133.0) Gecko/20100101 Firefox/133.0"),
+    ("accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+    ("accept-language", "en-US,en;q=0.5"),
+    ("upgrade-insecure-requests", "1"),
+    ("sec-fetch-dest", "document"),
+    ("sec-fetch-mode", "navigate"),
+    ("sec-fetch-site", "none"),
+    ("sec-fetch-user", "?1"),
+];
+
+const SAFARI_HEADERS: &[(&str, &str)] = &[
+    ("sec-fetch-dest", "document"),
+    ("user-agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.1 Safari/605.1.15"),
+    ("accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+    ("sec-fetch-site", "none"),
+    ("sec-fetch-mode", "navigate"),
+    ("accept-language", "en-US,en;q=0.9"),
+];
+
+/// HTTP/2 settings of the browser: stream window, connection window and max header list size.
+struct Http2Settings {
+    stream_window: u32,
+    connection_window: u32,
+    max_header_list_size: Option<u32>,
+}
+
+const fn profile_headers(profile: ImpersonationProfile) -> &'static [(&'static str, &'static str)] {
+    match profile {
+        ImpersonationProfile::Chrome => CHROME_HEADERS,
+        ImpersonationProfile::Firefox => FIREFOX_HEADERS,
+        ImpersonationProfile::Safari => SAFARI_HEADERS,
+    }
+}
+
+const fn profile_http2_settings(profile: ImpersonationProfile) -> Http2Settings {
+    match profile {
+        ImpersonationProfile::Chrome => Http2Settings { stream_window: 6_291_456, connection_window: 15_728_640, max_header_list_size: Some(262_144) },
+        ImpersonationProfile::Firefox => Http2Settings { stream_window: 131_072, connection_window: 12_582_912, max_header_list_size: None },
+        ImpersonationProfile::Safari => Http2Settings { stream_window: 4_194_304, connection_window: 10_485_760, max_header_list_size: None },
+    }
+}
+
+pub fn get_impersonation_user_agent(profile: ImpersonationProfile) -> &'static str {
+    profile_headers(profile).iter()
+        .find_map(|(name, value)| (*name == "user-agent").then_some(*value))
+        .unwrap_or_default()
+}
+
+/// Applies the HTTP/2 settings and TLS versions of the browser to the client.
+/// The TLS handshake itself is the one of rustls, only the protocol versions follow the browser.
+pub fn impersonate_client(builder: reqwest::ClientBuilder, profile: ImpersonationProfile) -> reqwest::ClientBuilder {
+    let settings = profile_http2_settings(profile);
+    let builder = builder
+        .min_tls_version(Version::TLS_1_2)
+        .tls_sni(true)
+        .http2_initial_stream_window_size(settings.stream_window)
+        .http2_initial_connection_window_size(settings.connection_window);
+    match settings.max_header_list_size {
+        Some(size) => builder.http2_max_header_list_size(size),
+        None => builder,
+    }
+}
+
+/// Orders the headers like the browser and adds the missing browser headers,
+/// configured headers keep their value.
+fn order_impersonation_headers(profile: ImpersonationProfile, mut headers: HeaderMap) -> HeaderMap {
+    let mut ordered = HeaderMap::with_capacity(headers.len() + profile_headers(profile).len());
+    for (name, value) in profile_headers(profile) {
+        let name = HeaderName::from_static(name);
+        let value = headers.remove(&name).unwrap_or_else(|| HeaderValue::from_static(value));
+        ordered.insert(name, value);
+    }
+    let mut current: Option<HeaderName> = None;
+    for (name, value) in headers {
+        if name.is_some() {
+            current = name;
+        }
+        if let Some(name) = current.as_ref() {
+            ordered.append(name.clone(), value);
+        }
+    }
+    ordered
+}
+
+/// Applies the browser headers to the request, without a profile the request is unchanged.
+pub fn impersonate_request(client: &reqwest::Client, request: reqwest::RequestBuilder, profile: Option<ImpersonationProfile>) -> reqwest::RequestBuilder {
+    let Some(profile) = profile else {
+        return request;
+    };
+    match request.try_clone().map(reqwest::RequestBuilder::build) {
+        Some(Ok(mut built)) => {
+            let headers = std::mem::take(built.headers_mut());
+            *built.headers_mut() = order_impersonation_headers(profile, headers);
+            reqwest::RequestBuilder::from_parts(client.clone(), built)
+        }
+        _ => request,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_impersonation_user_agent, order_impersonation_headers};
+    use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
+    use shared::model::ImpersonationProfile;
+
+    #[test]
+    fn test_order_impersonation_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-custom", HeaderValue::from_static("1"));
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(USER_AGENT, HeaderValue::from_static(get_impersonation_user_agent(ImpersonationProfile::Firefox)));
+
+        let ordered = order_impersonation_headers(ImpersonationProfile::Firefox, headers);
+        let names: Vec<&str> = ordered.keys().map(reqwest::header::HeaderName::as_str).collect();
+        assert_eq!(names.first(), Some(&"user-agent"));
+        assert_eq!(names.get(1), Some(&"accept"));
+        assert_eq!(names.last(), Some(&"x-custom"));
+        assert_eq!(ordered.get(ACCEPT).unwrap(), "application/json");
+        assert!(ordered.contains_key("sec-fetch-mode"));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::model::{AppConfig, InputEgress, InputTlsConfig};
+use crate::utils::impersonation::impersonate_client;
+use crate::utils::request::create_client;
+use dashmap::DashMap;
+use log::error;
+use shared::model::ImpersonationProfile;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct InputClientKey {
+    profile: Option<ImpersonationProfile>,
+    egress: Option<InputEgress>,
+    tls: Option<InputTlsConfig>,
+}
+
+// The clients are built on first use and dropped when the config changes.
+static INPUT_CLIENTS: LazyLock<DashMap<InputClientKey, reqwest::Client>> = LazyLock::new(DashMap::new);
+
+#[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "illumos", target_os = "ios", target_os = "linux",
+    target_os = "macos", target_os = "solaris", target_os = "tvos", target_os = "visionos", target_os = "watchos"))]
+fn bind_interface(builder: reqwest::ClientBuilder, interface: &str) -> reqwest::ClientBuilder {
+    builder.interface(interface)
+}
+
+#[cfg(not(any(target_os = "android", target_os = "fuchsia", target_os = "illumos", target_os = "ios", target_os = "linux",
+    target_os = "macos", target_os = "solaris", target_os = "tvos", target_os = "visionos", target_os = "watchos")))]
+fn bind_interface(builder: reqwest::ClientBuilder, interface: &str) -> reqwest::ClientBuilder {
+    error!("Binding to the network interface {interface} is not supported on this platform");
+    builder
+}
+
+fn with_client_tls(mut builder: reqwest::ClientBuilder, tls: &InputTlsConfig) -> reqwest::ClientBuilder {
+    match tls.load_identity() {
+        Ok(Some(identity)) => builder = builder.identity(identity),
+        Ok(None) => {}
+        Err(err) => error!("{}", err.message),
+    }
+    match tls.load_ca_certificates() {
+        Ok(certificates) => {
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        Err(err) => error!("{}", err.message),
+    }
+    builder
+}
+
+fn create_input_client(app_config: &AppConfig, key: &InputClientKey) -> reqwest::Client {
+    let mut builder = match key.profile {
+        Some(profile) => impersonate_client(create_client(app_config), profile),
+        None => create_client(app_config).http1_only(),
+    };
+    let connect_timeout_secs = app_config.config.load().connect_timeout_secs;
+    if connect_timeout_secs > 0 {
+        builder = builder.connect_timeout(Duration::from_secs(u64::from(connect_timeout_secs)));
+    }
+    match key.egress.as_ref() {
+        Some(InputEgress::Interface(interface)) => builder = bind_interface(builder, interface),
+        Some(InputEgress::Address(address)) => builder = builder.local_address(*address),
+        None => {}
+    }
+    if let Some(tls) = key.tls.as_ref() {
+        builder = with_client_tls(builder, tls);
+    }
+    builder.build().unwrap_or_else(|err| {
+        error!("Failed to create input client: {err}");
+        reqwest::Client::new()
+    })
+}
+
+/// Returns the client for the upstream requests of the input.
+/// Inputs with an impersonation profile, an egress interface, a network route or client certificates get their own client,
+/// all others share the given client. The egress interface of the input takes precedence over a network route.
+pub fn get_input_client(app_config: &AppConfig, client: &reqwest::Client, input_name: &str, profile: Option<ImpersonationProfile>) -> reqwest::Client {
+    let egress = app_config.get_input_egress(input_name)
+        .or_else(|| app_config.config.load().get_network_route(input_name).map(|route| InputEgress::Interface(route.interface.clone())));
+    let tls = app_config.get_input_tls_config(input_name);
+    if profile.is_none() && egress.is_none() && tls.is_none() {
+        return client.clone();
+    }
+    let key = InputClientKey { profile, egress, tls };
+    if let Some(client) = INPUT_CLIENTS.get(&key) {
+        return client.clone();
+    }
+    INPUT_CLIENTS
+        .entry(key.clone())
+        .or_insert_with(|| create_input_client(app_config, &key))
+        .clone()
+}
+
+pub fn clear_input_clients() {
+    INPUT_CLIENTS.clear();
+}
@@ -4,14 +4,19 @@ use crate::utils::prepare_file_path;
 use crate::utils::request;
 use shared::error::TuliproxError;
 use shared::model::PlaylistGroup;
+use shared::notify_err;
+use shared::utils::human_readable_byte_size;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+/// Downloads and parses the playlist of the input.
+/// The returned flag is set when the download is discarded by the guardrails and the last stored playlist is kept.
 pub async fn download_m3u_playlist(
     app_config: &Arc<AppConfig>,
     client: &reqwest::Client,
     cfg: &Arc<Config>,
     input: &ConfigInput,
-) -> (Vec<PlaylistGroup>, Vec<TuliproxError>) {
+) -> (Vec<PlaylistGroup>, Vec<TuliproxError>, bool) {
     let working_dir = &cfg.working_dir;
     let input_source: InputSource = {
         match input.staged.as_ref() {
@@ -29,7 +34,17 @@ pub async fn download_m3u_playlist(
     )
     .await
     {
-        Ok(reader) => (m3u::parse_m3u(cfg, input, reader).await, vec![]),
-        Err(err) => (vec![], vec![err]),
+        Ok(reader) => {
+            let max_size = input.get_max_download_size();
+            let (reader, size_exceeded) = request::limit_content_size(reader, max_size);
+            let playlist = m3u::parse_m3u(cfg, input, reader).await;
+            if size_exceeded.load(Ordering::Relaxed) {
+                let max_size = human_readable_byte_size(max_size.unwrap_or_default());
+                (vec![], vec![notify_err!("Playlist of input {} exceeds the maximum size of {max_size}, keeping the last data", input.name)], true)
+            } else {
+                (playlist, vec![], false)
+            }
+        }
+        Err(err) => (vec![], vec![err], false),
     }
 }
@@ -0,0 +1,129 @@
+use crate::model::{AppConfig, ConfigTarget};
+use crate::utils::network::format_http_status;
+use log::{debug, info};
+use serde_json::{json, Map, Value};
+use shared::error::{info_err, info_err_res, TuliproxError};
+use shared::model::{MediaServerConfigDto, MediaServerType};
+use url::Url;
+
+const MEDIA_SERVER_TOKEN_HEADER: &str = "X-Emby-Token";
+
+/// Emby serves its api below `/emby`, Jellyfin at the root.
+fn get_api_url(media_server: &MediaServerConfigDto, path: &str) -> String {
+    match media_server.server_type {
+        MediaServerType::Jellyfin => format!("{}/{path}", media_server.url),
+        MediaServerType::Emby => format!("{}/emby/{path}", media_server.url),
+    }
+}
+
+/// Url of a player api endpoint with the credentials of the user.
+pub fn get_user_url(base_url: &str, path: &str, username: &str, password: &str, extra: &[(&str, &str)]) -> Result<Url, TuliproxError> {
+    let mut url = Url::parse(&format!("{base_url}/{path}")).map_err(|err| info_err!("Invalid server url {base_url}: {err}"))?;
+    url.query_pairs_mut()
+        .append_pair("username", username)
+        .append_pair("password", password)
+        .extend_pairs(extra);
+    Ok(url)
+}
+
+/// An entry registered for the user by an earlier run, the urls contain the old password after a credential rotation.
+fn is_registered_url(value: Option<&Value>, base_url: &str, username: &str) -> bool {
+    value.and_then(Value::as_str)
+        .and_then(|value| Url::parse(value).ok())
+        .is_some_and(|url| url.as_str().starts_with(base_url)
+            && url.query_pairs().any(|(key, value)| key == "username" && value == username))
+}
+
+/// Id of the existing entry which is updated instead of adding a new one.
+fn find_registered_id(entries: Option<&Value>, url_field: &str, name: Option<&str>, base_url: &str, username: &str) -> Option<String> {
+    entries?.as_array()?.iter()
+        .find(|entry| name.is_some_and(|name| entry.get("FriendlyName").and_then(Value::as_str) == Some(name))
+            || is_registered_url(entry.get(url_field), base_url, username))
+        .and_then(|entry| entry.get("Id").and_then(Value::as_str))
+        .map(ToString::to_string)
+}
+
+fn with_id(mut body: Map<String, Value>, id: Option<String>) -> Value {
+    if let Some(id) = id {
+        body.insert("Id".to_string(), Value::String(id));
+    }
+    Value::Object(body)
+}
+
+async fn send_request(media_server: &MediaServerConfigDto, request: reqwest::RequestBuilder) -> Result<reqwest::Response, TuliproxError> {
+    let response = request
+        .header(MEDIA_SERVER_TOKEN_HEADER, &media_server.api_key)
+        .send().await
+        .map_err(|err| info_err!("{} server {} not reachable: {err}", media_server.server_type, media_server.url))?;
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        info_err_res!("{} server {} responded with {}", media_server.server_type, media_server.url, format_http_status(response.status()))
+    }
+}
+
+/// Adds or updates the M3U tuner and the XMLTV guide of the target user on the media server.
+async fn register_media_server(client: &reqwest::Client, app_config: &AppConfig, target: &ConfigTarget,
+                               media_server: &MediaServerConfigDto) -> Result<(), TuliproxError> {
+    let Some(credentials) = app_config.get_user_credentials(&media_server.username) else {
+        return info_err_res!("User: {} does not exist", media_server.username);
+    };
+    let base_url = app_config.get_target_base_url(target, &credentials);
+    let m3u_url = get_user_url(&base_url, "get.php", &credentials.username, &credentials.password, &[("type", "m3u_plus")])?;
+    let epg_url = get_user_url(&base_url, "xmltv.php", &credentials.username, &credentials.password, &[])?;
+    let name = media_server.get_name(&target.name);
+
+    let live_tv_config: Value = send_request(media_server, client.get(get_api_url(media_server, "System/Configuration/livetv"))).await?
+        .json().await
+        .map_err(|err| info_err!("Invalid live tv config from {} server {}: {err}", media_server.server_type, media_server.url))?;
+
+    let tuner_id = find_registered_id(live_tv_config.get("TunerHosts"), "Url", Some(&name), &base_url, &credentials.username);
+    let mut tuner = Map::new();
+    tuner.insert("Type".to_string(), json!("m3u"));
+    tuner.insert("Url".to_string(), json!(m3u_url.as_str()));
+    tuner.insert("FriendlyName".to_string(), json!(name));
+    tuner.insert("ImportFavoritesOnly".to_string(), json!(false));
+    tuner.insert("AllowHWTranscoding".to_string(), json!(false));
+    send_request(media_server, client.post(get_api_url(media_server, "LiveTv/TunerHosts")).json(&with_id(tuner, tuner_id))).await?;
+
+    let listing_id = find_registered_id(live_tv_config.get("ListingProviders"), "Path", None, &base_url, &credentials.username);
+    let mut listing = Map::new();
+    listing.insert("Type".to_string(), json!("xmltv"));
+    listing.insert("Path".to_string(), json!(epg_url.as_str()));
+    listing.insert("EnableAllTuners".to_string(), json!(true));
+    send_request(media_server, client.post(get_api_url(media_server, "LiveTv/ListingProviders?ValidateListings=false&ValidateLogin=false"))
+        .json(&with_id(listing, listing_id))).await?;
+
+    info!("Registered target {} at {} server {}", target.name, media_server.server_type, media_server.url);
+    Ok(())
+}
+
+/// Registers the playlist and epg of the target with all its media servers.
+pub async fn register_target_media_servers(client: &reqwest::Client, app_config: &AppConfig, target: &ConfigTarget) -> Vec<TuliproxError> {
+    let mut errors = vec![];
+    for media_server in &target.media_servers {
+        debug!("Registering target {} at {} server {}", target.name, media_server.server_type, media_server.url);
+        if let Err(err) = register_media_server(client, app_config, target, media_server).await {
+            errors.push(err);
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_registered_id;
+    use serde_json::json;
+
+    #[test]
+    fn test_find_registered_id() {
+        let tuners = json!([
+            {"Id": "1", "FriendlyName": "other", "Url": "http://other/get.php?username=user&password=old"},
+            {"Id": "2", "FriendlyName": "hdhr", "Url": "http://tuliprox:8901/get.php?username=user&password=old&type=m3u_plus"},
+        ]);
+        assert_eq!(find_registered_id(Some(&tuners), "Url", Some("tuliprox all"), "http://tuliprox:8901", "user"), Some("2".to_string()));
+        assert_eq!(find_registered_id(Some(&tuners), "Url", Some("other"), "http://tuliprox:8901", "nobody"), Some("1".to_string()));
+        assert_eq!(find_registered_id(Some(&tuners), "Url", None, "http://tuliprox:8901", "nobody"), None);
+        assert_eq!(find_registered_id(None, "Path", None, "http://tuliprox:8901", "user"), None);
+    }
+}
@@ -1,6 +1,10 @@
 pub mod epg;
+pub mod impersonation;
+pub mod input_client;
 pub mod ip_checker;
 pub mod m3u;
+pub mod media_server;
+pub mod network_route;
 pub mod request;
 pub mod xtream;
 
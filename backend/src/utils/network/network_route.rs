@@ -0,0 +1,79 @@
+use crate::model::{AppConfig, InputEgress, NetworkRouteConfig};
+use crate::utils::input_client::get_input_client;
+use log::debug;
+use std::time::Duration;
+
+const NETWORK_ROUTE_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+// IFF_UP of the interface flags
+const INTERFACE_FLAG_UP: u32 = 0x1;
+
+fn parse_interface_flags(content: &str) -> Option<u32> {
+    let flags = content.trim();
+    u32::from_str_radix(flags.strip_prefix("0x").unwrap_or(flags), 16).ok()
+}
+
+#[cfg(target_os = "linux")]
+fn is_interface_up(interface: &str) -> bool {
+    std::fs::read_to_string(format!("/sys/class/net/{interface}/flags"))
+        .ok()
+        .and_then(|content| parse_interface_flags(&content))
+        .is_some_and(|flags| flags & INTERFACE_FLAG_UP != 0)
+}
+
+// Without sysfs only the check url can verify the route.
+#[cfg(not(target_os = "linux"))]
+fn is_interface_up(_interface: &str) -> bool {
+    true
+}
+
+async fn check_route_url(app_config: &AppConfig, client: &reqwest::Client, input_name: &str, route: &NetworkRouteConfig, url: &str) -> Result<(), String> {
+    let client = get_input_client(app_config, client, input_name, None);
+    match client.get(url).timeout(NETWORK_ROUTE_CHECK_TIMEOUT).send().await {
+        Ok(response) if response.status().is_success() => Ok(()),
+        Ok(response) => Err(format!("network route {} check failed with status {}", route.name, response.status())),
+        Err(err) => Err(format!("network route {} check failed: {err}", route.name)),
+    }
+}
+
+/// Verifies that the egress interface or the network route of the input is up.
+/// Inputs without a route are always reachable.
+pub async fn check_network_route(app_config: &AppConfig, client: &reqwest::Client, input_name: &str) -> Result<(), String> {
+    match app_config.get_input_egress(input_name) {
+        Some(InputEgress::Interface(interface)) => {
+            if !is_interface_up(&interface) {
+                return Err(format!("egress interface {interface} of input {input_name} is down"));
+            }
+            return Ok(());
+        }
+        // a local address is checked by the os when the requests are sent
+        Some(InputEgress::Address(_)) => return Ok(()),
+        None => {}
+    }
+    let route = {
+        let config = app_config.config.load();
+        match config.get_network_route(input_name) {
+            Some(route) => route.clone(),
+            None => return Ok(()),
+        }
+    };
+    if !is_interface_up(&route.interface) {
+        return Err(format!("network interface {} of route {} is down", route.interface, route.name));
+    }
+    if let Some(url) = route.check_url.as_deref() {
+        check_route_url(app_config, client, input_name, &route, url).await?;
+    }
+    debug!("Network route {} is up for input {input_name}", route.name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_interface_flags, INTERFACE_FLAG_UP};
+
+    #[test]
+    fn test_parse_interface_flags() {
+        assert_eq!(parse_interface_flags("0x1091\n").map(|flags| flags & INTERFACE_FLAG_UP), Some(1));
+        assert_eq!(parse_interface_flags("0x1090\n").map(|flags| flags & INTERFACE_FLAG_UP), Some(0));
+        assert_eq!(parse_interface_flags("up"), None);
+    }
+}
@@ -1,18 +1,22 @@
 use crate::api::model::persist_pipe_stream::tee_dyn_reader;
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
 use crate::model::{format_elapsed_time, AppConfig, InputSource, ReverseProxyDisabledHeaderConfig};
-use crate::model::{ConfigInput, ResourceRetryConfig};
+use crate::model::{ConfigInput, InputBasicAuth, ResourceRetryConfig};
 use crate::utils::compression::compression_utils::{is_deflate, is_gzip};
 use crate::utils::{async_file_reader, async_file_writer, debug_if_enabled};
 use crate::utils::{get_file_path, persist_file};
-use axum::http::header::RETRY_AFTER;
+use crate::utils::impersonation::{get_impersonation_user_agent, impersonate_request};
+use crate::utils::input_client::get_input_client;
+use base64::engine::general_purpose;
+use base64::Engine;
 use futures::{StreamExt, TryStreamExt};
 use log::{debug, error, log_enabled, trace, Level};
-use reqwest::header::CONTENT_ENCODING;
+use reqwest::header::{AUTHORIZATION, CONTENT_ENCODING, RETRY_AFTER};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::{StatusCode};
 use shared::error::{notify_err_res, string_to_io_error, TuliproxError};
-use shared::model::{InputFetchMethod, DEFAULT_USER_AGENT};
+use shared::model::{HeaderRulesDto, InputFetchMethod, DEFAULT_USER_AGENT};
 use shared::utils::{
     filter_request_header, human_readable_byte_size, sanitize_sensitive_info, ENCODING_DEFLATE,
     ENCODING_GZIP,
@@ -21,10 +25,12 @@ use std::collections::HashMap;
 use std::io::{Error, ErrorKind};
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 use tokio::fs::File;
-use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt, ReadBuf};
 use tokio_util::io::StreamReader;
 use url::Url;
 
@@ -43,7 +49,7 @@ pub enum MimeCategory {
 pub fn classify_content_type(headers: &[(String, String)]) -> MimeCategory {
     headers.iter()
         .find_map(|(k, v)| {
-            (k == axum::http::header::CONTENT_TYPE.as_str()).then_some(v)
+            (k == reqwest::header::CONTENT_TYPE.as_str()).then_some(v)
         })
         .map_or(MimeCategory::Unknown, |v| match v.to_lowercase().as_str() {
             v if v.starts_with("video/") || v == "application/octet-stream" => MimeCategory::Video,
@@ -245,6 +251,7 @@ pub async fn get_input_epg_content_as_file(
     }
 }
 
+#[cfg(feature = "server")]
 pub async fn get_input_text_content(
     app_state: &Arc<AppState>,
     client: &reqwest::Client,
@@ -434,7 +441,7 @@ pub fn get_request_headers<S: ::std::hash::BuildHasher + Default>(
                     {
                         continue;
                     }
-                    if key == axum::http::header::USER_AGENT {
+                    if key == reqwest::header::USER_AGENT {
                         has_user_agent = true;
                     }
                     headers.insert(key, value);
@@ -461,7 +468,7 @@ pub fn get_request_headers<S: ::std::hash::BuildHasher + Default>(
                 ) {
                     // Only insert if not already present (config takes precedence)
                     if !headers.contains_key(&name) {
-                        if name == axum::http::header::USER_AGENT {
+                        if name == reqwest::header::USER_AGENT {
                             has_user_agent = true;
                         }
                         headers.insert(name, val);
@@ -496,7 +503,7 @@ pub fn get_request_headers<S: ::std::hash::BuildHasher + Default>(
             .and_then(|ua| HeaderValue::from_str(ua).ok());
 
         headers.insert(
-            axum::http::header::USER_AGENT,
+            reqwest::header::USER_AGENT,
             config_ua.unwrap_or_else(|| HeaderValue::from_static(DEFAULT_USER_AGENT)),
         );
     }
@@ -504,6 +511,24 @@ pub fn get_request_headers<S: ::std::hash::BuildHasher + Default>(
     headers
 }
 
+/// Applies the header rules of a header policy: the headers are removed, added when missing and overridden.
+pub fn apply_header_rules(headers: &mut HeaderMap, rules: Option<&HeaderRulesDto>) {
+    let Some(rules) = rules else {
+        return;
+    };
+    for name in &rules.remove {
+        headers.remove(name.as_str());
+    }
+    for (name, value, replace) in rules.add.iter().map(|(name, value)| (name, value, false))
+        .chain(rules.override_headers.iter().map(|(name, value)| (name, value, true))) {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+            if replace || !headers.contains_key(&name) {
+                headers.insert(name, value);
+            }
+        }
+    }
+}
+
 // read local file content and return it as a string.
 // Gzipped file content is supported.
 pub async fn get_local_file_content(file_path: &Path) -> Result<String, std::io::Error> {
@@ -583,22 +608,34 @@ pub async fn get_remote_content_as_file(
     });
 
     let config = app_config.config.load();
-    let default_user_agent = config.default_user_agent.clone();
+    let default_user_agent = input.impersonate
+        .map_or_else(|| config.default_user_agent.clone(), |profile| Some(get_impersonation_user_agent(profile).to_string()));
     drop(config);
+    let client = get_input_client(app_config, client, &input.name, input.impersonate);
+
+    let mut merged = get_request_headers(
+        Some(&input.headers),
+        custom_headers.as_ref(),
+        None,
+        default_user_agent.as_deref(),
+    );
+    apply_basic_auth(&mut merged, input.get_basic_auth().as_ref(), url);
+    apply_header_rules(&mut merged, input.get_upstream_header_rules(None).as_ref());
+    let headers = header_map_to_hash_map(&merged);
 
     let response = send_with_retry(
         app_config,
         url,
         || {
-            get_client_request(
-                client,
+            impersonate_request(&client, get_client_request(
+                &client,
                 input.method,
-                Some(&input.headers),
+                Some(&headers),
                 url,
-                custom_headers.as_ref(),
+                None,
                 None,
                 default_user_agent.as_deref(),
-            )
+            ), input.impersonate)
         },
     )
         .await?;
@@ -628,6 +665,43 @@ pub async fn get_remote_content_as_file(
 
 pub type DynReader = Pin<Box<dyn AsyncRead + Send>>;
 
+/// Fails the read when more than `remaining` bytes are read and marks the content as exceeded.
+struct SizeLimitedReader {
+    inner: DynReader,
+    remaining: u64,
+    exceeded: Arc<AtomicBool>,
+}
+
+impl AsyncRead for SizeLimitedReader {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let filled_before = buf.filled().len();
+        match this.inner.as_mut().poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                let read = (buf.filled().len() - filled_before) as u64;
+                if read > this.remaining {
+                    this.exceeded.store(true, Ordering::Relaxed);
+                    buf.set_filled(filled_before);
+                    return Poll::Ready(Err(Error::other("content exceeds the maximum size")));
+                }
+                this.remaining -= read;
+                Poll::Ready(Ok(()))
+            }
+            poll => poll,
+        }
+    }
+}
+
+/// Limits the content of a playlist download to `max_size` bytes.
+/// The returned flag is set when the content was cut off and has to be discarded.
+pub fn limit_content_size(reader: DynReader, max_size: Option<u64>) -> (DynReader, Arc<AtomicBool>) {
+    let exceeded = Arc::new(AtomicBool::new(false));
+    match max_size {
+        Some(max_size) => (Box::pin(SizeLimitedReader { inner: reader, remaining: max_size, exceeded: Arc::clone(&exceeded) }), exceeded),
+        None => (reader, exceeded),
+    }
+}
+
 async fn build_decoded_stream_reader(
     response: reqwest::Response,
 ) -> Result<DynReader, std::io::Error> {
@@ -673,6 +747,29 @@ async fn build_decoded_stream_reader(
 }
 
 
+/// Sets the basic auth of the input, the credentials are only sent to the host of the input url.
+fn apply_basic_auth(headers: &mut HeaderMap, basic_auth: Option<&InputBasicAuth>, url: &Url) {
+    if let Some(auth) = basic_auth.filter(|auth| auth.is_for_url(url)) {
+        let credentials = general_purpose::STANDARD.encode(format!("{}:{}", auth.username, auth.password));
+        if let Ok(mut value) = HeaderValue::from_str(&format!("Basic {credentials}")) {
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+    }
+}
+
+fn header_map_to_hash_map(headers: &HeaderMap) -> HashMap<String, String> {
+    headers
+        .iter()
+        .map(|(k, v)| {
+            (
+                k.as_str().to_string(),
+                String::from_utf8_lossy(v.as_bytes()).to_string(),
+            )
+        })
+        .collect()
+}
+
 #[allow(clippy::implicit_hasher)]
 pub async fn get_remote_content_as_stream(
     app_config: &Arc<AppConfig>,
@@ -688,40 +785,35 @@ pub async fn get_remote_content_as_stream(
     });
 
     let config = app_config.config.load();
-    let default_user_agent = config.default_user_agent.clone();
+    let default_user_agent = input.impersonate
+        .map_or_else(|| config.default_user_agent.clone(), |profile| Some(get_impersonation_user_agent(profile).to_string()));
     let disabled_headers = config.get_disabled_headers();
     drop(config);
+    let client = get_input_client(app_config, client, &input.name, input.impersonate);
 
-    let merged = get_request_headers(
+    let mut merged = get_request_headers(
         Some(&input.headers),
         custom_headers.as_ref(),
         disabled_headers.as_ref(),
         default_user_agent.as_deref(),
     );
-
-    let headers: HashMap<String, String> = merged
-        .iter()
-        .map(|(k, v)| {
-            (
-                k.as_str().to_string(),
-                String::from_utf8_lossy(v.as_bytes()).to_string(),
-            )
-        })
-        .collect();
+    apply_basic_auth(&mut merged, input.basic_auth.as_ref(), url);
+    apply_header_rules(&mut merged, input.header_rules.as_ref());
+    let headers = header_map_to_hash_map(&merged);
 
     let response = send_with_retry(
         app_config,
         url,
         || {
-            get_client_request(
-                client,
+            impersonate_request(&client, get_client_request(
+                &client,
                 input.method,
                 Some(&headers),
                 url,
                 None,
                 None,
                 default_user_agent.as_deref(),
-            )
+            ), input.impersonate)
         },
     )
         .await?;
@@ -1078,6 +1170,16 @@ pub fn create_client(cfg: &AppConfig) -> reqwest::ClientBuilder {
     client
 }
 
+pub fn create_http_client(app_config: &AppConfig) -> reqwest::Client {
+    let mut builder = create_client(app_config).http1_only();
+    let config = app_config.config.load(); // because of RAII connection dropping
+    if config.connect_timeout_secs > 0 {
+        builder =
+            builder.connect_timeout(Duration::from_secs(u64::from(config.connect_timeout_secs)));
+    }
+    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+}
+
 pub fn parse_range(range: &str) -> Option<(u64, Option<u64>)> {
     // expect: "bytes=START-END"
     if !range.starts_with("bytes=") {
@@ -1105,7 +1207,10 @@ pub fn is_uri(url: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use super::{limit_content_size, DynReader};
     use shared::utils::{get_base_url_from_str, replace_url_extension, sanitize_sensitive_info};
+    use std::sync::atomic::Ordering;
+    use tokio::io::AsyncReadExt;
 
     #[test]
     fn test_url_mask() {
@@ -1152,7 +1257,7 @@ mod tests {
     #[test]
     fn test_get_request_headers_prioritization() {
         use super::{get_request_headers, DEFAULT_USER_AGENT};
-        use axum::http::header::USER_AGENT;
+        use reqwest::header::USER_AGENT;
         use std::collections::HashMap;
 
         // Case 1: No headers provided -> Default UA
@@ -1199,4 +1304,55 @@ mod tests {
         );
         assert_eq!(headers.get("X-Test").unwrap(), "From-Config");
     }
+
+    #[test]
+    fn test_apply_header_rules() {
+        use super::{apply_header_rules, get_request_headers};
+        use shared::model::HeaderRulesDto;
+        use std::collections::HashMap;
+
+        let mut client_headers = HashMap::new();
+        client_headers.insert("X-Forwarded-For".to_string(), b"10.0.0.1".to_vec());
+        client_headers.insert("Origin".to_string(), b"http://client".to_vec());
+        let mut headers = get_request_headers(None, Some(&client_headers), None, None);
+        let rules = HeaderRulesDto {
+            remove: vec!["x-forwarded-for".to_string()],
+            add: HashMap::from([("origin".to_string(), "http://provider".to_string()), ("referer".to_string(), "http://provider/".to_string())]),
+            override_headers: HashMap::from([("user-agent".to_string(), "Provider-UA".to_string())]),
+        };
+        apply_header_rules(&mut headers, Some(&rules));
+        assert!(headers.get("x-forwarded-for").is_none());
+        assert_eq!(headers.get("origin").unwrap(), "http://client");
+        assert_eq!(headers.get("referer").unwrap(), "http://provider/");
+        assert_eq!(headers.get("user-agent").unwrap(), "Provider-UA");
+    }
+
+    #[test]
+    fn test_apply_basic_auth() {
+        use super::apply_basic_auth;
+        use crate::model::InputBasicAuth;
+        use reqwest::header::{HeaderMap, AUTHORIZATION};
+        use url::Url;
+
+        let auth = InputBasicAuth { host: Some("provider.tv".to_string()), username: "user".to_string(), password: "pass".to_string() };
+        let mut headers = HeaderMap::new();
+        apply_basic_auth(&mut headers, Some(&auth), &Url::parse("http://epg.tv/guide.xml").unwrap());
+        assert!(headers.get(AUTHORIZATION).is_none());
+        apply_basic_auth(&mut headers, Some(&auth), &Url::parse("http://provider.tv/playlist.m3u").unwrap());
+        assert_eq!(headers.get(AUTHORIZATION).unwrap(), "Basic dXNlcjpwYXNz");
+    }
+
+    #[tokio::test]
+    async fn test_limit_content_size() {
+        let content: DynReader = Box::pin(&b"#EXTM3U"[..]);
+        let (mut reader, exceeded) = limit_content_size(content, Some(7));
+        let mut text = String::new();
+        assert!(reader.read_to_string(&mut text).await.is_ok());
+        assert!(!exceeded.load(Ordering::Relaxed));
+
+        let content: DynReader = Box::pin(&b"#EXTM3U"[..]);
+        let (mut reader, exceeded) = limit_content_size(content, Some(6));
+        assert!(reader.read_to_string(&mut String::new()).await.is_err());
+        assert!(exceeded.load(Ordering::Relaxed));
+    }
 }
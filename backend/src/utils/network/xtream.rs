@@ -1,3 +1,4 @@
+#[cfg(feature = "server")]
 use crate::api::model::AppState;
 use crate::messaging::send_message;
 use crate::model::{is_input_expired, xtream_mapping_option_from_target_options, AppConfig,
@@ -5,7 +6,7 @@ use crate::model::{is_input_expired, xtream_mapping_option_from_target_options,
 use crate::model::{InputSource, ProxyUserCredentials};
 use crate::processing::parser::xtream;
 use crate::processing::parser::xtream::parse_xtream_series_info;
-use crate::repository::BPlusTreeUpdate;
+use crate::repository::{xtream_keep_stored_details, xtream_load_watermark, xtream_save_watermark, BPlusTreeQuery, BPlusTreeUpdate, XtreamWatermark};
 use crate::repository::VirtualIdRecord;
 use crate::repository::{ensure_input_storage_path, get_input_storage_path, get_target_storage_path};
 use crate::repository::{get_live_cat_collection_path, get_series_cat_collection_path, get_vod_cat_collection_path, xtream_get_file_path, CategoryEntry};
@@ -18,10 +19,12 @@ use shared::error::TuliproxError;
 use shared::model::{PlaylistEntry, PlaylistGroup, ProxyUserStatus, SeriesStreamProperties,
                     StreamProperties, VideoStreamProperties, XtreamCluster, XtreamPlaylistItem,
                     XtreamSeriesInfo, XtreamVideoInfo, XtreamVideoInfoDoc};
-use shared::utils::{extract_extension_from_url, get_i64_from_serde_value, get_string_from_serde_value, sanitize_sensitive_info, Internable};
+use shared::model::xtream_const::XTREAM_CLUSTER;
+use shared::utils::{extract_extension_from_url, get_i64_from_serde_value, human_readable_byte_size, get_string_from_serde_value, sanitize_sensitive_info, Internable};
 use std::collections::HashMap;
 use std::io::Error;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 use std::str::FromStr;
 
 use crate::model::XtreamCategory;
@@ -70,6 +73,7 @@ pub async fn get_xtream_stream_info_content(app_config: &Arc<AppConfig>, client:
     }
 }
 
+#[cfg(feature = "server")]
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 pub async fn get_xtream_stream_info(client: &reqwest::Client,
                                     app_state: &Arc<AppState>,
@@ -82,8 +86,8 @@ pub async fn get_xtream_stream_info(client: &reqwest::Client,
     let xtream_output = target.get_xtream_output().ok_or_else(|| info_err!("Unexpected error, missing xtream output"))?;
 
     let app_config = &app_state.app_config;
-    let server_info = app_config.get_user_server_info(user);
-    let options = xtream_mapping_option_from_target_options(target, xtream_output, app_config, user, Some(server_info.get_base_url().as_str()));
+    let base_url = app_config.get_target_base_url(target, user);
+    let options = xtream_mapping_option_from_target_options(target, xtream_output, app_config, user, Some(base_url.as_str()));
 
     if let Some(content) = pli.get_resolved_info_document(&options) {
         return serde_json::to_string(&content).map_err(|err| info_err!("{err}"));
@@ -170,6 +174,7 @@ pub async fn get_xtream_stream_info(client: &reqwest::Client,
                                                         .push(ProviderEpisodeKey {
                                                             provider_id: episode_provider_id,
                                                             virtual_id: episode.header.virtual_id,
+                                                            url: episode.header.url.clone(),
                                                         });
                                                     if target.use_memory_cache {
                                                         in_memory_updates.push(
@@ -233,12 +238,13 @@ pub async fn get_xtream_stream_info(client: &reqwest::Client,
                                    target.name.replace(' ', "_").as_str(), &cluster, pli.get_virtual_id()))
 }
 
+#[cfg(feature = "server")]
 fn xtream_resolve_stream_info(app_state: &Arc<AppState>, user: &ProxyUserCredentials,
                               target: &ConfigTarget, xtream_output: &XtreamTargetOutput,
                               pli: &XtreamPlaylistItem) -> Option<Result<String, TuliproxError>> {
     let app_config = &app_state.app_config;
-    let server_info = app_config.get_user_server_info(user);
-    let options = xtream_mapping_option_from_target_options(target, xtream_output, app_config, user, Some(server_info.get_base_url().as_str()));
+    let base_url = app_config.get_target_base_url(target, user);
+    let options = xtream_mapping_option_from_target_options(target, xtream_output, app_config, user, Some(base_url.as_str()));
     if let Some(content) = pli.get_resolved_info_document(&options) {
         return Some(serde_json::to_string(&content).map_err(|err| info_err!("Failed to serialize stream info: {err}")));
     }
@@ -339,6 +345,7 @@ pub async fn notify_account_expire(exp_date: Option<i64>, app_config: &Arc<AppCo
     }
 }
 
+#[allow(clippy::too_many_lines)]
 pub async fn download_xtream_playlist(app_config: &Arc<AppConfig>, client: &reqwest::Client, input: &ConfigInput, clusters: Option<&[XtreamCluster]>)
                                       -> (Vec<PlaylistGroup>, Vec<TuliproxError>, bool) {
     let input_source: InputSource = {
@@ -367,6 +374,9 @@ pub async fn download_xtream_playlist(app_config: &Arc<AppConfig>, client: &reqw
     let cfg = app_config.config.load();
     let working_dir = &cfg.working_dir;
 
+    let max_size = input.get_max_download_size();
+    let mut staged_clusters = vec![];
+    let mut keep_stored = false;
     let mut errors = vec![];
     for (xtream_cluster, category, stream) in &ACTIONS {
         let is_requested = clusters.is_none_or(|c| c.contains(xtream_cluster));
@@ -383,20 +393,38 @@ pub async fn download_xtream_playlist(app_config: &Arc<AppConfig>, client: &reqw
                 request::get_input_json_content_as_stream(app_config, client, &input_source_stream, stream_file_path)
             ) {
                 (Ok(category_content), Ok(stream_content)) => {
+                    let (category_content, category_size_exceeded) = request::limit_content_size(category_content, max_size);
+                    let (stream_content, stream_size_exceeded) = request::limit_content_size(stream_content, max_size);
+                    let is_size_exceeded = || category_size_exceeded.load(Ordering::Relaxed) || stream_size_exceeded.load(Ordering::Relaxed);
                     if cfg.disk_based_processing {
                         // trace!("Using disk input playlist optimization for cluster {}", xtream_cluster);
-                        if let Err(err) = process_xtream_cluster_to_disk(app_config, input, *xtream_cluster, category_content, stream_content).await {
-                            error!("process_xtream_cluster_to_disk failed: {err}");
-                            errors.push(err);
+                        let staged = process_xtream_cluster_to_disk(app_config, input, *xtream_cluster, category_content, stream_content).await;
+                        if is_size_exceeded() {
+                            if let Ok(staged) = staged {
+                                discard_xtream_cluster(&staged).await;
+                            }
+                            errors.push(size_exceeded_error(input, *xtream_cluster, max_size));
                         } else {
-                            // trace!("process_xtream_cluster_to_disk succeeded for cluster {}", xtream_cluster);
+                            match staged {
+                                Ok(staged) => staged_clusters.push(staged),
+                                Err(err) => {
+                                    error!("process_xtream_cluster_to_disk failed: {err}");
+                                    errors.push(err);
+                                }
+                            }
                         }
                     } else {
                         // trace!("Using in-memory playlist parsing for cluster {}", xtream_cluster);
-                        match xtream::parse_xtream(input,
-                                                   *xtream_cluster,
-                                                   category_content,
-                                                   stream_content).await {
+                        let parsed = xtream::parse_xtream(input,
+                                                          *xtream_cluster,
+                                                          category_content,
+                                                          stream_content).await;
+                        if is_size_exceeded() {
+                            errors.push(size_exceeded_error(input, *xtream_cluster, max_size));
+                            keep_stored = true;
+                            continue;
+                        }
+                        match parsed {
                             Ok(sub_playlist_parsed) => {
                                 if let Some(mut xtream_sub_playlist) = sub_playlist_parsed {
                                     playlist_groups.append(&mut xtream_sub_playlist);
@@ -417,6 +445,27 @@ pub async fn download_xtream_playlist(app_config: &Arc<AppConfig>, client: &reqw
         }
     }
 
+    if !staged_clusters.is_empty() {
+        if let Err(err) = check_staged_item_count(app_config, input, &staged_clusters, &skip_cluster).await {
+            warn!("{}", err.message);
+            for staged in &staged_clusters {
+                discard_xtream_cluster(staged).await;
+            }
+            errors.push(err);
+        } else {
+            for staged in staged_clusters {
+                if let Err(err) = commit_xtream_cluster(app_config, staged).await {
+                    errors.push(err);
+                }
+            }
+        }
+    }
+
+    if keep_stored {
+        // The in-memory result is incomplete, the last stored playlist is used instead.
+        return (Vec::with_capacity(0), errors, true);
+    }
+
     for (grp_id, plg) in (1_u32..).zip(playlist_groups.iter_mut()) {
         plg.id = grp_id;
     }
@@ -424,6 +473,35 @@ pub async fn download_xtream_playlist(app_config: &Arc<AppConfig>, client: &reqw
     (playlist_groups, errors, cfg.disk_based_processing)
 }
 
+fn size_exceeded_error(input: &ConfigInput, cluster: XtreamCluster, max_size: Option<u64>) -> TuliproxError {
+    let max_size = human_readable_byte_size(max_size.unwrap_or_default());
+    notify_err!("Playlist {cluster} of input {} exceeds the maximum size of {max_size}, keeping the last data", input.name)
+}
+
+/// Checks the guardrails against the staged clusters and the stored items of the clusters which were not downloaded.
+async fn check_staged_item_count(app_config: &Arc<AppConfig>, input: &ConfigInput, staged_clusters: &[StagedXtreamCluster],
+                                 skip_cluster: &[XtreamCluster]) -> Result<(), TuliproxError> {
+    if input.guardrails.is_none() {
+        return Ok(());
+    }
+    let mut item_count: usize = staged_clusters.iter().map(|staged| staged.item_count).sum();
+    if let Some(storage_path) = staged_clusters.first().map(|staged| staged.storage_path.as_path()) {
+        for cluster in XTREAM_CLUSTER {
+            if !skip_cluster.contains(&cluster) && !staged_clusters.iter().any(|staged| staged.cluster == cluster) {
+                let xtream_path = xtream_get_file_path(storage_path, cluster);
+                if file_exists_async(&xtream_path).await {
+                    let _stored_lock = app_config.file_locks.read_lock(&xtream_path).await;
+                    item_count += BPlusTreeQuery::<u32, XtreamPlaylistItem>::try_new(&xtream_path)
+                        .ok()
+                        .and_then(|mut query| query.len().ok())
+                        .unwrap_or_default();
+                }
+            }
+        }
+    }
+    input.check_item_count(item_count)
+}
+
 async fn check_alias_user_state(app_config: &Arc<AppConfig>, client: &reqwest::Client, input: &ConfigInput) {
     if let Some(aliases) = input.aliases.as_ref() {
         for alias in aliases {
@@ -501,13 +579,26 @@ pub fn create_vod_info_from_item(target: &ConfigTarget, user: &ProxyUserCredenti
 
 const BATCH_SIZE: usize = 1000;
 
+/// Downloaded cluster in temporary files, it replaces the stored cluster when the guardrails of the input pass.
+struct StagedXtreamCluster {
+    cluster: XtreamCluster,
+    item_count: usize,
+    storage_path: PathBuf,
+    xtream_path: PathBuf,
+    col_path: PathBuf,
+    incremental: bool,
+    watermark: Option<u64>,
+    fetched_watermark: Option<u64>,
+}
+
+#[allow(clippy::too_many_lines)]
 async fn process_xtream_cluster_to_disk(
     app_config: &Arc<AppConfig>,
     input: &ConfigInput,
     cluster: XtreamCluster,
     categories: DynReader,
     streams: DynReader,
-) -> Result<(), TuliproxError> {
+) -> Result<StagedXtreamCluster, TuliproxError> {
     let cfg = app_config.config.load();
     // trace!("Starting process_xtream_cluster_to_disk for cluster {}", cluster);
     let storage_path = {
@@ -528,8 +619,20 @@ async fn process_xtream_cluster_to_disk(
         }).await
     });
 
+    // In incremental mode the unchanged entries keep the resolved details of the stored entries.
+    let incremental = input.is_xtream_incremental() && cluster != XtreamCluster::Live;
+    let watermark = if incremental { xtream_load_watermark(&storage_path).await } else { XtreamWatermark::default() };
+    let stored_lock = if incremental && file_exists_async(&xtream_path).await {
+        Some(app_config.file_locks.read_lock(&xtream_path).await)
+    } else {
+        None
+    };
+
     let xtream_path_for_consumer = xtream_path.clone();
     let consumer_task = tokio::task::spawn_blocking(move || {
+        let mut stored_query = stored_lock.as_ref()
+            .and_then(|_| BPlusTreeQuery::<u32, XtreamPlaylistItem>::try_new(&xtream_path_for_consumer).ok());
+        let mut fetched_watermark = XtreamWatermark::default();
         // trace!("Spawned consumer_task for cluster {}", cluster);
         let tmp_xtream_path = xtream_path_for_consumer.with_extension("tmp");
         // trace!("Creating fresh ghost database at {:?}", tmp_xtream_path);
@@ -547,11 +650,19 @@ async fn process_xtream_cluster_to_disk(
             })?;
 
         let mut buffer = Vec::with_capacity(BATCH_SIZE);
-        // let mut total_items = 0;
-
-        while let Some(item) = rx.blocking_recv() {
+        let mut total_items = 0;
+
+        while let Some(mut item) = rx.blocking_recv() {
+            if let Some(new_stream_props) = item.additional_properties.as_mut() {
+                fetched_watermark.update(cluster, new_stream_props);
+                if let Some(Ok(Some(stored_item))) = stored_query.as_mut().map(|query| query.query(&item.provider_id)) {
+                    if let Some(old_stream_props) = stored_item.additional_properties {
+                        xtream_keep_stored_details(new_stream_props, old_stream_props, watermark.get(cluster));
+                    }
+                }
+            }
             buffer.push(item);
-            // total_items += 1;
+            total_items += 1;
             if buffer.len() >= BATCH_SIZE {
                 let batch: Vec<(&u32, &XtreamPlaylistItem)> = buffer.iter().map(|i| (&i.provider_id, i)).collect();
                 tree.upsert_batch(&batch).map_err(|e| {
@@ -571,14 +682,16 @@ async fn process_xtream_cluster_to_disk(
                 notify_err!("Upsert failed {e}")
             })?;
         }
-        Ok::<(), TuliproxError>(())
+        drop(stored_query);
+        drop(stored_lock);
+        Ok::<(XtreamWatermark, usize), TuliproxError>((fetched_watermark, total_items))
     });
 
     let (parse_res, consumer_res) = futures::join!(parse_task, consumer_task);
     // trace!("Joined tasks for cluster {}", cluster);
 
     let categories = parse_res.map_err(|e| notify_err!("Parse task join err {e}"))??;
-    consumer_res.map_err(|e| notify_err!("Consumer task join err {e}"))??;
+    let (fetched_watermark, item_count) = consumer_res.map_err(|e| notify_err!("Consumer task join err {e}"))??;
 
     // 1. Save categories to a temporary file
     let col_path = match cluster {
@@ -589,8 +702,32 @@ async fn process_xtream_cluster_to_disk(
     let tmp_col_path = col_path.with_extension("tmp");
     save_xtream_categories_to_file(&tmp_col_path, &categories).await?;
 
+    Ok(StagedXtreamCluster {
+        cluster,
+        item_count,
+        storage_path,
+        xtream_path,
+        col_path,
+        incremental,
+        watermark: watermark.get(cluster),
+        fetched_watermark: fetched_watermark.get(cluster),
+    })
+}
+
+async fn discard_xtream_cluster(staged: &StagedXtreamCluster) {
+    for tmp_path in [staged.xtream_path.with_extension("tmp"), staged.col_path.with_extension("tmp")] {
+        if file_exists_async(&tmp_path).await {
+            let _ = tokio::fs::remove_file(tmp_path).await;
+        }
+    }
+}
+
+async fn commit_xtream_cluster(app_config: &Arc<AppConfig>, staged: StagedXtreamCluster) -> Result<(), TuliproxError> {
+    let StagedXtreamCluster { cluster, storage_path, xtream_path, col_path, incremental, watermark, fetched_watermark, .. } = staged;
+
     // 2. Success! Swap temporary files to permanent ones
     let tmp_xtream_path = xtream_path.with_extension("tmp");
+    let tmp_col_path = col_path.with_extension("tmp");
 
     // Acquire write lock to serialize compact/swap/cleanup operations across concurrent API calls
     let swap_lock = app_config.file_locks.write_lock(&xtream_path).await;
@@ -626,6 +763,14 @@ async fn process_xtream_cluster_to_disk(
     }
 
     drop(swap_lock);
+
+    if incremental {
+        let mut stored_watermark = xtream_load_watermark(&storage_path).await;
+        stored_watermark.set(cluster, fetched_watermark.or(watermark));
+        if let Err(err) = xtream_save_watermark(app_config, &storage_path, &stored_watermark).await {
+            error!("Failed to persist watermark for {cluster}: {err}");
+        }
+    }
     // trace!("Cluster {} updated successfully", cluster);
     Ok(())
 }
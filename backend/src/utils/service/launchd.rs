@@ -0,0 +1,105 @@
+use std::fmt::Write;
+
+const LAUNCHD_LABEL: &str = "io.github.euzu.tuliprox";
+
+fn escape_xml(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Creates the launchd agent, it is started on login and restarted when it exits.
+/// launchd has no log of its own, the output of tuliprox is written into the log file.
+fn create_launchd_plist(program: &str, args: &[String], log_file: &str, description: &str) -> String {
+    let mut arguments = String::new();
+    for arg in std::iter::once(program).chain(args.iter().map(String::as_str)) {
+        let _ = writeln!(arguments, "        <string>{}</string>", escape_xml(arg));
+    }
+    format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{LAUNCHD_LABEL}</string>
+    <key>ServiceDescription</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+{arguments}    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>StandardOutPath</key>
+    <string>{log_file}</string>
+    <key>StandardErrorPath</key>
+    <string>{log_file}</string>
+</dict>
+</plist>
+"#, escape_xml(description), log_file = escape_xml(log_file))
+}
+
+#[cfg(target_os = "macos")]
+fn home_path(path: &str) -> Result<std::path::PathBuf, String> {
+    std::env::var("HOME")
+        .map(|home| std::path::PathBuf::from(home).join(path))
+        .map_err(|_| "The home directory is unknown, HOME is not set".to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn plist_path() -> Result<std::path::PathBuf, String> {
+    home_path(&format!("Library/LaunchAgents/{LAUNCHD_LABEL}.plist"))
+}
+
+#[cfg(target_os = "macos")]
+fn launchctl(args: &[&str]) -> Result<(), String> {
+    let status = std::process::Command::new("launchctl").args(args).status()
+        .map_err(|err| format!("Failed to run launchctl: {err}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("launchctl {} failed with {status}", args.join(" ")))
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub(super) fn install(program: &str, args: &[String], description: &str) -> Result<(), String> {
+    let plist_file = plist_path()?;
+    let log_file = home_path(&format!("Library/Logs/{}.log", super::SERVICE_NAME))?;
+    let plist = create_launchd_plist(program, args, &log_file.to_string_lossy(), description);
+    if let Some(dir) = plist_file.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| format!("Failed to create {}: {err}", dir.display()))?;
+    }
+    std::fs::write(&plist_file, plist).map_err(|err| format!("Failed to write {}: {err}", plist_file.display()))?;
+    launchctl(&["load", "-w", &plist_file.to_string_lossy()])?;
+    println!("Service {LAUNCHD_LABEL} installed in {}, the log is written to {}", plist_file.display(), log_file.display());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub(super) fn uninstall() -> Result<(), String> {
+    let plist_file = plist_path()?;
+    if !plist_file.exists() {
+        return Err(format!("Service {LAUNCHD_LABEL} is not installed"));
+    }
+    // launchd sends SIGTERM, the server shuts down gracefully
+    launchctl(&["unload", "-w", &plist_file.to_string_lossy()])?;
+    std::fs::remove_file(&plist_file).map_err(|err| format!("Failed to remove {}: {err}", plist_file.display()))?;
+    println!("Service {LAUNCHD_LABEL} uninstalled");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::create_launchd_plist;
+
+    #[test]
+    fn test_create_launchd_plist() {
+        let args = vec!["--service".to_string(), "run".to_string(), "-p".to_string(), "/Users/me/tuliprox & co".to_string()];
+        let plist = create_launchd_plist("/usr/local/bin/tuliprox", &args, "/Users/me/Library/Logs/tuliprox.log", "Extended playlist proxy");
+        assert!(plist.contains("        <string>/usr/local/bin/tuliprox</string>\n        <string>--service</string>"));
+        assert!(plist.contains("<string>/Users/me/tuliprox &amp; co</string>\n    </array>"));
+        assert!(plist.contains("<key>StandardErrorPath</key>\n    <string>/Users/me/Library/Logs/tuliprox.log</string>"));
+    }
+}
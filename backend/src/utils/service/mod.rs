@@ -0,0 +1,119 @@
+#[cfg(windows)]
+mod windows;
+#[cfg(any(target_os = "macos", test))]
+mod launchd;
+
+use std::sync::LazyLock;
+use tokio::sync::Notify;
+
+pub const SERVICE_NAME: &str = "tuliprox";
+#[cfg(any(windows, target_os = "macos"))]
+const SERVICE_DESCRIPTION: &str = "Extended playlist proxy";
+#[cfg(not(any(windows, target_os = "macos")))]
+const SERVICE_UNSUPPORTED: &str = "The service is only supported on windows and macos, use a systemd unit or docker";
+
+static SERVICE_STOP: LazyLock<Notify> = LazyLock::new(Notify::new);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ServiceAction {
+    /// Registers tuliprox as service which starts with the system
+    Install,
+    /// Stops and removes the service
+    Uninstall,
+    /// Runs the server, started by the service manager
+    Run,
+}
+
+/// Called by the service control handler, the server shuts down like on `SIGTERM`.
+pub fn request_service_stop() {
+    SERVICE_STOP.notify_one();
+}
+
+/// Completes when the service manager requested the stop of the service.
+pub async fn service_stop_requested() {
+    SERVICE_STOP.notified().await;
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+fn current_program() -> Result<String, String> {
+    std::env::current_exe()
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|err| format!("Failed to resolve the tuliprox binary: {err}"))
+}
+
+/// Quotes the argument for the command line of the service, the paths can contain spaces.
+#[cfg(any(windows, test))]
+fn quote_arg(arg: &str) -> String {
+    if arg.is_empty() || arg.contains([' ', '\t', '"']) {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    } else {
+        arg.to_string()
+    }
+}
+
+#[cfg(any(windows, test))]
+fn service_command_line(program: &str, args: &[String]) -> String {
+    std::iter::once(program).chain(args.iter().map(String::as_str))
+        .map(quote_arg)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Registers the service, `args` are the arguments the service is started with.
+#[cfg(windows)]
+pub fn install_service(args: &[String]) -> Result<(), String> {
+    windows::install(&service_command_line(&current_program()?, args), SERVICE_DESCRIPTION)
+}
+
+/// Registers the service, `args` are the arguments the service is started with.
+#[cfg(target_os = "macos")]
+pub fn install_service(args: &[String]) -> Result<(), String> {
+    launchd::install(&current_program()?, args, SERVICE_DESCRIPTION)
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn install_service(_args: &[String]) -> Result<(), String> {
+    Err(SERVICE_UNSUPPORTED.to_string())
+}
+
+#[cfg(windows)]
+pub fn uninstall_service() -> Result<(), String> {
+    windows::uninstall()
+}
+
+#[cfg(target_os = "macos")]
+pub fn uninstall_service() -> Result<(), String> {
+    launchd::uninstall()
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn uninstall_service() -> Result<(), String> {
+    Err(SERVICE_UNSUPPORTED.to_string())
+}
+
+/// Runs the server as service. The service control manager of windows starts the service,
+/// the stop request is passed to the graceful shutdown and the warnings and errors are written to the event log.
+#[cfg(windows)]
+pub fn run_service(run: impl FnOnce() + Send + 'static) -> Result<(), String> {
+    windows::run(Box::new(run))
+}
+
+/// launchd stops the server with `SIGTERM`, it runs like in server mode.
+#[cfg(not(windows))]
+#[allow(clippy::unnecessary_wraps)]
+pub fn run_service(run: impl FnOnce() + Send + 'static) -> Result<(), String> {
+    run();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::service_command_line;
+
+    #[test]
+    fn test_service_command_line() {
+        let args = vec!["--service".to_string(), "run".to_string(), "-p".to_string(), "C:\\Program Files\\tuliprox\\config".to_string()];
+        assert_eq!(service_command_line("C:\\tuliprox\\tuliprox.exe", &args),
+                   "C:\\tuliprox\\tuliprox.exe --service run -p \"C:\\Program Files\\tuliprox\\config\"");
+    }
+}
@@ -0,0 +1,222 @@
+use crate::utils::service::{request_service_stop, SERVICE_NAME};
+use crate::utils::set_log_forwarder;
+use log::Level;
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use winapi::shared::minwindef::{DWORD, HKEY, LPVOID};
+use winapi::shared::winerror::{ERROR_CALL_NOT_IMPLEMENTED, ERROR_FAILED_SERVICE_CONTROLLER_CONNECT, NO_ERROR};
+use winapi::um::winbase::{RegisterEventSourceW, ReportEventW};
+use winapi::um::winnt::{DELETE, EVENTLOG_ERROR_TYPE, EVENTLOG_INFORMATION_TYPE, EVENTLOG_WARNING_TYPE, KEY_WRITE, LPWSTR,
+                        REG_DWORD, REG_EXPAND_SZ, REG_OPTION_NON_VOLATILE, SERVICE_AUTO_START, SERVICE_ERROR_NORMAL,
+                        SERVICE_WIN32_OWN_PROCESS, WORD};
+use winapi::um::winreg::{RegCloseKey, RegCreateKeyExW, RegDeleteKeyW, RegSetValueExW, HKEY_LOCAL_MACHINE};
+use winapi::um::winsvc::{ChangeServiceConfig2W, CloseServiceHandle, ControlService, CreateServiceW, DeleteService, OpenSCManagerW,
+                         OpenServiceW, RegisterServiceCtrlHandlerExW, SetServiceStatus, StartServiceCtrlDispatcherW, SC_HANDLE,
+                         SC_MANAGER_CONNECT, SC_MANAGER_CREATE_SERVICE, SERVICE_ACCEPT_SHUTDOWN, SERVICE_ACCEPT_STOP,
+                         SERVICE_ALL_ACCESS, SERVICE_CONFIG_DESCRIPTION, SERVICE_CONTROL_INTERROGATE, SERVICE_CONTROL_SHUTDOWN,
+                         SERVICE_CONTROL_STOP, SERVICE_DESCRIPTIONW, SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_START_PENDING,
+                         SERVICE_STATUS, SERVICE_STATUS_HANDLE, SERVICE_STOP, SERVICE_STOPPED, SERVICE_STOP_PENDING,
+                         SERVICE_TABLE_ENTRYW};
+
+const EVENT_LOG_KEY: &str = "SYSTEM\\CurrentControlSet\\Services\\EventLog\\Application\\tuliprox";
+// the message table of EventCreate.exe has the message `%1` for the ids 1 to 1000
+const EVENT_MESSAGE_FILE: &str = "%SystemRoot%\\System32\\EventCreate.exe";
+const EVENT_ID: DWORD = 1;
+// the graceful shutdown drains the streams and flushes the repositories
+const STOP_WAIT_HINT_MS: DWORD = 60_000;
+
+type ServiceRun = Box<dyn FnOnce() + Send>;
+
+static SERVICE_RUN: Mutex<Option<ServiceRun>> = Mutex::new(None);
+// the handles are pointers, they are only used through the winapi
+static STATUS_HANDLE: AtomicUsize = AtomicUsize::new(0);
+static EVENT_SOURCE: AtomicUsize = AtomicUsize::new(0);
+
+fn to_wide(value: &str) -> Vec<u16> {
+    OsStr::new(value).encode_wide().chain(std::iter::once(0)).collect()
+}
+
+fn last_error(action: &str) -> String {
+    format!("{action} failed: {}", std::io::Error::last_os_error())
+}
+
+struct ServiceHandle(SC_HANDLE);
+
+impl ServiceHandle {
+    fn new(handle: SC_HANDLE, action: &str) -> Result<Self, String> {
+        if handle.is_null() { Err(last_error(action)) } else { Ok(Self(handle)) }
+    }
+}
+
+impl Drop for ServiceHandle {
+    fn drop(&mut self) {
+        unsafe { CloseServiceHandle(self.0); }
+    }
+}
+
+fn open_service_manager(access: DWORD) -> Result<ServiceHandle, String> {
+    ServiceHandle::new(unsafe { OpenSCManagerW(std::ptr::null(), std::ptr::null(), access) },
+                       "Opening the service manager, run it as administrator")
+}
+
+fn register_event_source() -> Result<(), String> {
+    let key_name = to_wide(EVENT_LOG_KEY);
+    let mut key: HKEY = std::ptr::null_mut();
+    let status = unsafe {
+        RegCreateKeyExW(HKEY_LOCAL_MACHINE, key_name.as_ptr(), 0, std::ptr::null_mut(), REG_OPTION_NON_VOLATILE,
+                        KEY_WRITE, std::ptr::null_mut(), &raw mut key, std::ptr::null_mut())
+    };
+    if status != 0 {
+        return Err(format!("Creating the event log source failed with {status}"));
+    }
+    let message_file: Vec<u8> = to_wide(EVENT_MESSAGE_FILE).iter().flat_map(|c| c.to_le_bytes()).collect();
+    let types_supported: DWORD = DWORD::from(EVENTLOG_ERROR_TYPE | EVENTLOG_WARNING_TYPE | EVENTLOG_INFORMATION_TYPE);
+    let value_message_file = to_wide("EventMessageFile");
+    let value_types_supported = to_wide("TypesSupported");
+    unsafe {
+        RegSetValueExW(key, value_message_file.as_ptr(), 0, REG_EXPAND_SZ, message_file.as_ptr(), message_file.len() as DWORD);
+        RegSetValueExW(key, value_types_supported.as_ptr(), 0, REG_DWORD, types_supported.to_le_bytes().as_ptr(), 4);
+        RegCloseKey(key);
+    }
+    Ok(())
+}
+
+pub(super) fn install(command_line: &str, description: &str) -> Result<(), String> {
+    let manager = open_service_manager(SC_MANAGER_CONNECT | SC_MANAGER_CREATE_SERVICE)?;
+    let name = to_wide(SERVICE_NAME);
+    let binary_path = to_wide(command_line);
+    // the service runs as LocalSystem
+    let service = ServiceHandle::new(unsafe {
+        CreateServiceW(manager.0, name.as_ptr(), name.as_ptr(), SERVICE_ALL_ACCESS, SERVICE_WIN32_OWN_PROCESS,
+                       SERVICE_AUTO_START, SERVICE_ERROR_NORMAL, binary_path.as_ptr(), std::ptr::null(),
+                       std::ptr::null_mut(), std::ptr::null(), std::ptr::null(), std::ptr::null())
+    }, "Creating the service")?;
+    let mut description = to_wide(description);
+    let mut service_description = SERVICE_DESCRIPTIONW { lpDescription: description.as_mut_ptr() };
+    unsafe { ChangeServiceConfig2W(service.0, SERVICE_CONFIG_DESCRIPTION, (&raw mut service_description).cast()); }
+    if let Err(err) = register_event_source() {
+        eprintln!("{err}");
+    }
+    println!("Service {SERVICE_NAME} installed: {command_line}");
+    println!("Start it with: sc start {SERVICE_NAME}");
+    Ok(())
+}
+
+pub(super) fn uninstall() -> Result<(), String> {
+    let manager = open_service_manager(SC_MANAGER_CONNECT)?;
+    let name = to_wide(SERVICE_NAME);
+    let service = ServiceHandle::new(unsafe { OpenServiceW(manager.0, name.as_ptr(), SERVICE_STOP | SERVICE_QUERY_STATUS | DELETE) },
+                                     "Opening the service")?;
+    // a stopped service answers the stop with an error, it is removed anyway
+    let mut status: SERVICE_STATUS = unsafe { std::mem::zeroed() };
+    unsafe { ControlService(service.0, SERVICE_CONTROL_STOP, &raw mut status); }
+    if unsafe { DeleteService(service.0) } == 0 {
+        return Err(last_error("Removing the service"));
+    }
+    let key_name = to_wide(EVENT_LOG_KEY);
+    unsafe { RegDeleteKeyW(HKEY_LOCAL_MACHINE, key_name.as_ptr()); }
+    println!("Service {SERVICE_NAME} uninstalled");
+    Ok(())
+}
+
+fn report_event(event_type: WORD, message: &str) {
+    let source = EVENT_SOURCE.load(Ordering::Acquire);
+    if source == 0 {
+        return;
+    }
+    let message = to_wide(message);
+    let mut strings = [message.as_ptr()];
+    unsafe {
+        ReportEventW(source as LPVOID, event_type, 0, EVENT_ID, std::ptr::null_mut(), 1, 0,
+                     strings.as_mut_ptr(), std::ptr::null_mut());
+    }
+}
+
+/// The event log only gets the warnings and errors, the info logs would flood it.
+fn forward_to_event_log(level: Level, message: &str) {
+    match level {
+        Level::Error => report_event(EVENTLOG_ERROR_TYPE, message),
+        Level::Warn => report_event(EVENTLOG_WARNING_TYPE, message),
+        _ => {}
+    }
+}
+
+fn set_service_state(state: DWORD, wait_hint: DWORD) {
+    let handle = STATUS_HANDLE.load(Ordering::Acquire);
+    if handle == 0 {
+        return;
+    }
+    let mut status = SERVICE_STATUS {
+        dwServiceType: SERVICE_WIN32_OWN_PROCESS,
+        dwCurrentState: state,
+        dwControlsAccepted: if state == SERVICE_RUNNING { SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN } else { 0 },
+        dwWin32ExitCode: NO_ERROR,
+        dwServiceSpecificExitCode: 0,
+        dwCheckPoint: 0,
+        dwWaitHint: wait_hint,
+    };
+    unsafe { SetServiceStatus(handle as SERVICE_STATUS_HANDLE, &raw mut status); }
+}
+
+unsafe extern "system" fn service_control_handler(control: DWORD, _event_type: DWORD, _event_data: LPVOID, _context: LPVOID) -> DWORD {
+    match control {
+        SERVICE_CONTROL_STOP | SERVICE_CONTROL_SHUTDOWN => {
+            set_service_state(SERVICE_STOP_PENDING, STOP_WAIT_HINT_MS);
+            request_service_stop();
+            NO_ERROR
+        }
+        SERVICE_CONTROL_INTERROGATE => NO_ERROR,
+        _ => ERROR_CALL_NOT_IMPLEMENTED,
+    }
+}
+
+unsafe extern "system" fn service_main(_argc: DWORD, _argv: *mut LPWSTR) {
+    let name = to_wide(SERVICE_NAME);
+    let handle = unsafe { RegisterServiceCtrlHandlerExW(name.as_ptr(), Some(service_control_handler), std::ptr::null_mut()) };
+    if handle.is_null() {
+        return;
+    }
+    STATUS_HANDLE.store(handle as usize, Ordering::Release);
+    set_service_state(SERVICE_START_PENDING, 10_000);
+
+    let source = unsafe { RegisterEventSourceW(std::ptr::null(), name.as_ptr()) };
+    if !source.is_null() {
+        EVENT_SOURCE.store(source as usize, Ordering::Release);
+        set_log_forwarder(forward_to_event_log);
+    }
+    // services start in the system directory, relative paths of the config are resolved from the binary directory
+    if let Some(dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(std::path::Path::to_path_buf)) {
+        let _ = std::env::set_current_dir(dir);
+    }
+
+    set_service_state(SERVICE_RUNNING, 0);
+    report_event(EVENTLOG_INFORMATION_TYPE, "tuliprox service started");
+    let run = SERVICE_RUN.lock().ok().and_then(|mut run| run.take());
+    if let Some(run) = run {
+        run();
+    }
+    report_event(EVENTLOG_INFORMATION_TYPE, "tuliprox service stopped");
+    set_service_state(SERVICE_STOPPED, 0);
+}
+
+/// Blocks until the service is stopped, the service control manager calls `service_main` on its own thread.
+pub(super) fn run(run: ServiceRun) -> Result<(), String> {
+    if let Ok(mut service_run) = SERVICE_RUN.lock() {
+        *service_run = Some(run);
+    }
+    let name = to_wide(SERVICE_NAME);
+    let service_table = [
+        SERVICE_TABLE_ENTRYW { lpServiceName: name.as_ptr(), lpServiceProc: Some(service_main) },
+        SERVICE_TABLE_ENTRYW { lpServiceName: std::ptr::null(), lpServiceProc: None },
+    ];
+    if unsafe { StartServiceCtrlDispatcherW(service_table.as_ptr()) } == 0 {
+        let err = std::io::Error::last_os_error();
+        if err.raw_os_error() == Some(ERROR_FAILED_SERVICE_CONTROLLER_CONNECT as i32) {
+            return Err(format!("The service {SERVICE_NAME} can only be started by the service manager, use: sc start {SERVICE_NAME}"));
+        }
+        return Err(format!("Starting the service failed: {err}"));
+    }
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use std::fmt::Write as _;
+use std::io::Write;
+
+const UTF8_BOM: &str = "\u{feff}";
+const XLSX_MAX_SHEET_NAME_LEN: usize = 31;
+
+/// Values starting with these characters are evaluated as formula by spreadsheet applications.
+fn is_formula_start(value: &str) -> bool {
+    value.starts_with(['=', '+', '-', '@'])
+}
+
+fn write_csv_value(csv: &mut String, value: &str) {
+    let value = if is_formula_start(value) { format!("'{value}") } else { value.to_string() };
+    if value.contains([',', '"', '\n', '\r']) {
+        csv.push('"');
+        csv.push_str(&value.replace('"', "\"\""));
+        csv.push('"');
+    } else {
+        csv.push_str(&value);
+    }
+}
+
+/// Creates a comma separated file, the first row is the header.
+/// The byte order mark makes Excel read the file as utf-8.
+pub fn create_csv(rows: &[Vec<String>]) -> String {
+    let mut csv = String::from(UTF8_BOM);
+    for row in rows {
+        for (index, value) in row.iter().enumerate() {
+            if index > 0 {
+                csv.push(',');
+            }
+            write_csv_value(&mut csv, value);
+        }
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Reads a comma or semicolon separated file, the separator with the most occurrences in the first row is used.
+/// Quoted values can contain separators, line breaks and doubled quotes, empty rows are skipped.
+pub fn read_csv(content: &str) -> Vec<Vec<String>> {
+    let content = content.strip_prefix(UTF8_BOM).unwrap_or(content);
+    let header = content.lines().next().unwrap_or_default();
+    let separator = if header.matches(';').count() > header.matches(',').count() { ';' } else { ',' };
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c != '"' {
+                value.push(c);
+            } else if chars.next_if_eq(&'"').is_some() {
+                value.push('"');
+            } else {
+                quoted = false;
+            }
+        } else if c == '"' && value.trim().is_empty() {
+            value.clear();
+            quoted = true;
+        } else if c == separator {
+            row.push(std::mem::take(&mut value));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' {
+                chars.next_if_eq(&'\n');
+            }
+            row.push(std::mem::take(&mut value));
+            if row.iter().any(|value| !value.trim().is_empty()) {
+                rows.push(std::mem::take(&mut row));
+            } else {
+                row.clear();
+            }
+        } else {
+            value.push(c);
+        }
+    }
+    row.push(value);
+    if row.iter().any(|value| !value.trim().is_empty()) {
+        rows.push(row);
+    }
+    rows
+}
+
+/// Xml 1.0 does not allow control characters other than tab and line breaks.
+fn escape_xml(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\t' | '\n' | '\r' => result.push(c),
+            c if c.is_control() => {}
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+fn sanitize_sheet_name(name: &str) -> String {
+    let name: String = name.chars()
+        .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+        .take(XLSX_MAX_SHEET_NAME_LEN)
+        .collect();
+    if name.trim().is_empty() { String::from("Sheet1") } else { name }
+}
+
+fn create_sheet_xml(rows: &[Vec<String>]) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#);
+    for row in rows {
+        xml.push_str("<row>");
+        for value in row {
+            let _ = write!(xml, r#"<c t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#, escape_xml(value));
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+struct ZipEntry {
+    name: &'static str,
+    crc: u32,
+    compressed_size: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// Minimal zip archive with deflated entries, enough for the office open xml container.
+struct ZipWriter {
+    data: Vec<u8>,
+    entries: Vec<ZipEntry>,
+}
+
+fn to_u32(value: usize) -> std::io::Result<u32> {
+    u32::try_from(value).map_err(|_| std::io::Error::other("zip archive too large"))
+}
+
+impl ZipWriter {
+    const VERSION: u16 = 20;
+    const FLAG_UTF8: u16 = 0x0800;
+    const METHOD_DEFLATE: u16 = 8;
+    // 1980-01-01 00:00, the earliest dos date
+    const DOS_TIME: u16 = 0;
+    const DOS_DATE: u16 = 0x21;
+
+    fn new() -> Self {
+        Self { data: Vec::new(), entries: Vec::new() }
+    }
+
+    fn put_u16(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_entry_header(&mut self, entry_index: usize) -> std::io::Result<()> {
+        let (crc, compressed_size, size, name_len) = {
+            let entry = &self.entries[entry_index];
+            (entry.crc, entry.compressed_size, entry.size, u16::try_from(entry.name.len()).map_err(std::io::Error::other)?)
+        };
+        self.put_u16(Self::VERSION);
+        self.put_u16(Self::FLAG_UTF8);
+        self.put_u16(Self::METHOD_DEFLATE);
+        self.put_u16(Self::DOS_TIME);
+        self.put_u16(Self::DOS_DATE);
+        self.put_u32(crc);
+        self.put_u32(compressed_size);
+        self.put_u32(size);
+        self.put_u16(name_len);
+        // extra field length
+        self.put_u16(0);
+        Ok(())
+    }
+
+    fn add_file(&mut self, name: &'static str, content: &[u8]) -> std::io::Result<()> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content)?;
+        let compressed = encoder.finish()?;
+        let mut crc = Crc::new();
+        crc.update(content);
+
+        self.entries.push(ZipEntry {
+            name,
+            crc: crc.sum(),
+            compressed_size: to_u32(compressed.len())?,
+            size: to_u32(content.len())?,
+            offset: to_u32(self.data.len())?,
+        });
+        self.put_u32(0x0403_4b50);
+        self.put_entry_header(self.entries.len() - 1)?;
+        self.data.extend_from_slice(name.as_bytes());
+        self.data.extend_from_slice(&compressed);
+        Ok(())
+    }
+
+    fn finish(mut self) -> std::io::Result<Vec<u8>> {
+        let central_directory_offset = to_u32(self.data.len())?;
+        for index in 0..self.entries.len() {
+            self.put_u32(0x0201_4b50);
+            // version made by
+            self.put_u16(Self::VERSION);
+            self.put_entry_header(index)?;
+            // comment length, disk number, internal and external attributes
+            self.put_u16(0);
+            self.put_u16(0);
+            self.put_u16(0);
+            self.put_u32(0);
+            let (offset, name) = (self.entries[index].offset, self.entries[index].name);
+            self.put_u32(offset);
+            self.data.extend_from_slice(name.as_bytes());
+        }
+        let central_directory_size = to_u32(self.data.len())? - central_directory_offset;
+        let entry_count = u16::try_from(self.entries.len()).map_err(std::io::Error::other)?;
+        self.put_u32(0x0605_4b50);
+        // disk numbers
+        self.put_u16(0);
+        self.put_u16(0);
+        self.put_u16(entry_count);
+        self.put_u16(entry_count);
+        self.put_u32(central_directory_size);
+        self.put_u32(central_directory_offset);
+        // comment length
+        self.put_u16(0);
+        Ok(self.data)
+    }
+}
+
+/// Creates an xlsx workbook with a single sheet, the first row is the header.
+/// All values are written as text.
+pub fn create_xlsx(sheet_name: &str, rows: &[Vec<String>]) -> std::io::Result<Vec<u8>> {
+    let content_types = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#;
+    let rels = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+    let workbook = format!(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+                           escape_xml(&sanitize_sheet_name(sheet_name)));
+    let workbook_rels = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#;
+
+    let mut zip = ZipWriter::new();
+    zip.add_file("[Content_Types].xml", content_types.as_bytes())?;
+    zip.add_file("_rels/.rels", rels.as_bytes())?;
+    zip.add_file("xl/workbook.xml", workbook.as_bytes())?;
+    zip.add_file("xl/_rels/workbook.xml.rels", workbook_rels.as_bytes())?;
+    zip.add_file("xl/worksheets/sheet1.xml", create_sheet_xml(rows).as_bytes())?;
+    zip.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_csv, create_xlsx, read_csv, sanitize_sheet_name};
+    use flate2::read::DeflateDecoder;
+    use std::io::Read;
+
+    fn row(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_create_csv() {
+        let csv = create_csv(&[row(&["name", "group"]), row(&["News, HD", "=cmd"]), row(&["Say \"hi\"", "Info"])]);
+        assert_eq!(csv, "\u{feff}name,group\r\n\"News, HD\",'=cmd\r\n\"Say \"\"hi\"\"\",Info\r\n");
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let rows = read_csv("\u{feff}name,group\r\n\"News, HD\",Info\r\n\r\n\"Say \"\"hi\"\"\",\"multi\nline\"");
+        assert_eq!(rows, vec![row(&["name", "group"]), row(&["News, HD", "Info"]), row(&["Say \"hi\"", "multi\nline"])]);
+        assert_eq!(read_csv("user;pass\nalice;secret\n"), vec![row(&["user", "pass"]), row(&["alice", "secret"])]);
+    }
+
+    #[test]
+    fn test_sanitize_sheet_name() {
+        assert_eq!(sanitize_sheet_name("family/kids [DE]"), "familykids DE");
+        assert_eq!(sanitize_sheet_name("???"), "Sheet1");
+        assert_eq!(sanitize_sheet_name(&"x".repeat(40)).len(), 31);
+    }
+
+    #[test]
+    fn test_create_xlsx() {
+        let xlsx = create_xlsx("family", &[row(&["name"]), row(&["News & <Info>"])]).unwrap();
+        assert_eq!(&xlsx[..4], &[0x50, 0x4b, 0x03, 0x04]);
+        let eocd = xlsx.len() - 22;
+        assert_eq!(&xlsx[eocd..eocd + 4], &[0x50, 0x4b, 0x05, 0x06]);
+        assert_eq!(u16::from_le_bytes([xlsx[eocd + 10], xlsx[eocd + 11]]), 5);
+
+        // the sheet is the last entry, it starts at the offset of its central directory record
+        let cd_offset = u32::from_le_bytes(xlsx[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+        let mut record = cd_offset;
+        let mut sheet_offset = 0;
+        for _ in 0..5 {
+            let name_len = u16::from_le_bytes([xlsx[record + 28], xlsx[record + 29]]) as usize;
+            sheet_offset = u32::from_le_bytes(xlsx[record + 42..record + 46].try_into().unwrap()) as usize;
+            record += 46 + name_len;
+        }
+        let name_len = u16::from_le_bytes([xlsx[sheet_offset + 26], xlsx[sheet_offset + 27]]) as usize;
+        let compressed_size = u32::from_le_bytes(xlsx[sheet_offset + 18..sheet_offset + 22].try_into().unwrap()) as usize;
+        let start = sheet_offset + 30 + name_len;
+        assert_eq!(&xlsx[sheet_offset + 30..start], b"xl/worksheets/sheet1.xml");
+        let mut sheet = String::new();
+        DeflateDecoder::new(&xlsx[start..start + compressed_size]).read_to_string(&mut sheet).unwrap();
+        assert!(sheet.contains("<t xml:space=\"preserve\">News &amp; &lt;Info&gt;</t>"));
+    }
+}
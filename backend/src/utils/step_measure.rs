@@ -1,4 +1,5 @@
 use log::{debug, log_enabled, Level};
+use shared::model::{ProcessingStage, ProcessingTimings};
 use std::time::{Duration, Instant};
 
 fn format_duration(duration: Duration) -> String {
@@ -24,6 +25,8 @@ pub struct StepMeasure {
     name: String,
     start: Instant,
     step_start: Instant,
+    stage_start: Instant,
+    timings: ProcessingTimings,
     callback: StepMeasureCallback,
 }
 
@@ -37,10 +40,27 @@ impl StepMeasure {
             name: name.to_owned(),
             start: Instant::now(),
             step_start: Instant::now(),
+            stage_start: Instant::now(),
+            timings: ProcessingTimings::default(),
             callback: Box::new(cb),
         }
     }
 
+    /// Adds the time since the last recorded stage to the given stage, the timings are recorded without debug logging.
+    pub fn lap(&mut self, stage: ProcessingStage) {
+        self.timings.add(stage, self.stage_start.elapsed());
+        self.stage_start = Instant::now();
+    }
+
+    pub fn tick_stage(&mut self, stage: ProcessingStage, step: &str) {
+        self.lap(stage);
+        self.tick(step);
+    }
+
+    pub fn timings(&self) -> ProcessingTimings {
+        self.timings
+    }
+
     pub fn broadcast(&self, step: &str, msg: &str) {
         (self.callback)(step, msg);
     }
@@ -1,7 +1,9 @@
 #[macro_export]
 macro_rules! exit {
     ($($arg:tt)*) => {{
-        error!($($arg)*);
+        let msg = format!($($arg)*);
+        error!("{msg}");
+        $crate::utils::report_error($crate::utils::CrashReportKind::Fatal, &msg);
         std::process::exit(1);
     }};
 }
@@ -1,7 +1,10 @@
 // Common utilities for Trakt functionality
+#[cfg(feature = "trakt")]
 mod client;
+#[cfg(feature = "trakt")]
 mod errors;
 
+#[cfg(feature = "trakt")]
 pub use self::client::*;
 
 use shared::utils::{deunicode_string, CONSTANTS};
@@ -0,0 +1,267 @@
+use crate::utils::{decode_base64_string, encode_base64_string, write_private_file};
+use openssl::bn::BigNumContext;
+use openssl::derive::Deriver;
+use openssl::ec::{EcGroup, EcKey, EcPoint, PointConversionForm};
+use openssl::ecdsa::EcdsaSig;
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::sign::Signer;
+use openssl::symm::{encrypt_aead, Cipher};
+use rand::{RngCore, rngs::OsRng, TryRngCore};
+use shared::error::TuliproxError;
+use shared::info_err;
+use shared::model::WebPushSubscriptionDto;
+use std::path::Path;
+use url::Url;
+
+/// Record size announced in the `aes128gcm` header, payloads are always sent as a single record.
+const RECORD_SIZE: u32 = 4096;
+/// Push services accept up to 4096 bytes, the header and padding delimiter take 103 bytes of them.
+pub const WEB_PUSH_MAX_PAYLOAD: usize = 3993;
+const VAPID_TOKEN_TTL_SECS: i64 = 12 * 3600;
+
+fn p256_group() -> Result<EcGroup, ErrorStack> {
+    EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)
+}
+
+fn public_key_bytes(key: &EcKey<Private>) -> Result<Vec<u8>, ErrorStack> {
+    let mut ctx = BigNumContext::new()?;
+    key.public_key().to_bytes(key.group(), PointConversionForm::UNCOMPRESSED, &mut ctx)
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    if OsRng.try_fill_bytes(&mut bytes).is_err() {
+        rand::rng().fill_bytes(&mut bytes);
+    }
+    bytes
+}
+
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> Result<Vec<u8>, ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    for chunk in data {
+        signer.update(chunk)?;
+    }
+    signer.sign_to_vec()
+}
+
+/// HKDF (RFC 5869) with SHA-256, the output is at most one hash long.
+fn hkdf_sha256(salt: &[u8], ikm: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, ErrorStack> {
+    let prk = hmac_sha256(salt, &[ikm])?;
+    let mut okm = hmac_sha256(&prk, &[info, &[1u8]])?;
+    okm.truncate(len);
+    Ok(okm)
+}
+
+/// Application server key used to sign the VAPID token (RFC 8292).
+pub struct VapidKey {
+    key: EcKey<Private>,
+    public_key: Vec<u8>,
+}
+
+impl VapidKey {
+    fn new(key: EcKey<Private>) -> Result<Self, ErrorStack> {
+        let public_key = public_key_bytes(&key)?;
+        Ok(Self { key, public_key })
+    }
+
+    /// Loads the key from the given pem file, a new key is created and stored if the file does not exist.
+    pub fn load_or_create(path: &Path) -> Result<Self, TuliproxError> {
+        if path.exists() {
+            let pem = std::fs::read(path).map_err(|err| info_err!("Failed to read web push key {}: {err}", path.display()))?;
+            let key = EcKey::private_key_from_pem(&pem).map_err(|err| info_err!("Invalid web push key {}: {err}", path.display()))?;
+            return Self::new(key).map_err(|err| info_err!("Invalid web push key {}: {err}", path.display()));
+        }
+        let key = p256_group().and_then(|group| EcKey::generate(&group))
+            .map_err(|err| info_err!("Failed to create web push key: {err}"))?;
+        let pem = key.private_key_to_pem().map_err(|err| info_err!("Failed to create web push key: {err}"))?;
+        write_private_file(path, &pem).map_err(|err| info_err!("Failed to write web push key {}: {err}", path.display()))?;
+        Self::new(key).map_err(|err| info_err!("Failed to create web push key: {err}"))
+    }
+
+    /// Public key as base64 url encoded uncompressed point, used as `applicationServerKey` in the browser.
+    pub fn public_key_base64(&self) -> String {
+        encode_base64_string(&self.public_key)
+    }
+
+    /// Creates the ES256 signed JWT for the origin of the push endpoint.
+    fn create_token(&self, endpoint: &str, subject: &str) -> Result<String, TuliproxError> {
+        let url = Url::parse(endpoint).map_err(|err| info_err!("Invalid web push endpoint {endpoint}: {err}"))?;
+        let audience = url.origin().ascii_serialization();
+        let expires = chrono::Utc::now().timestamp() + VAPID_TOKEN_TTL_SECS;
+        let header = encode_base64_string(br#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = serde_json::json!({"aud": audience, "exp": expires, "sub": subject});
+        let claims = encode_base64_string(claims.to_string().as_bytes());
+        let unsigned = format!("{header}.{claims}");
+        let signature = self.sign(unsigned.as_bytes()).map_err(|err| info_err!("Failed to sign web push token: {err}"))?;
+        Ok(format!("{unsigned}.{}", encode_base64_string(&signature)))
+    }
+
+    /// JWS ES256 signature, the raw `r || s` concatenation instead of DER.
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+        let digest = openssl::sha::sha256(data);
+        let signature = EcdsaSig::sign(&digest, &self.key)?;
+        let mut result = signature.r().to_vec_padded(32)?;
+        result.extend(signature.s().to_vec_padded(32)?);
+        Ok(result)
+    }
+
+    pub fn authorization_header(&self, endpoint: &str, subject: &str) -> Result<String, TuliproxError> {
+        let token = self.create_token(endpoint, subject)?;
+        Ok(format!("vapid t={token}, k={}", self.public_key_base64()))
+    }
+}
+
+fn encrypt_with_keys(subscription: &WebPushSubscriptionDto, payload: &[u8], local_key: &EcKey<Private>, salt: &[u8; 16]) -> Result<Vec<u8>, ErrorStack> {
+    let group = p256_group()?;
+    let mut ctx = BigNumContext::new()?;
+    let ua_public = decode_base64_string(&subscription.keys.p256dh);
+    let auth_secret = decode_base64_string(&subscription.keys.auth);
+    let ua_point = EcPoint::from_bytes(&group, &ua_public, &mut ctx)?;
+    let ua_key = PKey::from_ec_key(EcKey::from_public_key(&group, &ua_point)?)?;
+    let as_key = PKey::from_ec_key(local_key.clone())?;
+    let as_public = public_key_bytes(local_key)?;
+
+    let mut deriver = Deriver::new(&as_key)?;
+    deriver.set_peer(&ua_key)?;
+    let shared_secret = deriver.derive_to_vec()?;
+
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(&ua_public);
+    key_info.extend_from_slice(&as_public);
+    let ikm = hkdf_sha256(&auth_secret, &shared_secret, &key_info, 32)?;
+    let cek = hkdf_sha256(salt, &ikm, b"Content-Encoding: aes128gcm\0", 16)?;
+    let nonce = hkdf_sha256(salt, &ikm, b"Content-Encoding: nonce\0", 12)?;
+
+    // single and therefore last record, padded with the delimiter only
+    let mut plaintext = Vec::with_capacity(payload.len() + 1);
+    plaintext.extend_from_slice(payload);
+    plaintext.push(2);
+    let mut tag = [0u8; 16];
+    let ciphertext = encrypt_aead(Cipher::aes_128_gcm(), &cek, Some(&nonce), &[], &plaintext, &mut tag)?;
+
+    let mut body = Vec::with_capacity(86 + ciphertext.len() + tag.len());
+    body.extend_from_slice(salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    #[allow(clippy::cast_possible_truncation)]
+    body.push(as_public.len() as u8);
+    body.extend_from_slice(&as_public);
+    body.extend_from_slice(&ciphertext);
+    body.extend_from_slice(&tag);
+    Ok(body)
+}
+
+/// Encrypts the payload for the subscription with the `aes128gcm` content encoding (RFC 8291).
+pub fn web_push_encrypt(subscription: &WebPushSubscriptionDto, payload: &[u8]) -> Result<Vec<u8>, TuliproxError> {
+    if payload.len() > WEB_PUSH_MAX_PAYLOAD {
+        return Err(info_err!("Web push payload too large: {} bytes", payload.len()));
+    }
+    let local_key = p256_group().and_then(|group| EcKey::generate(&group))
+        .map_err(|err| info_err!("Failed to create web push session key: {err}"))?;
+    let salt = random_bytes::<16>();
+    encrypt_with_keys(subscription, payload, &local_key, &salt)
+        .map_err(|err| info_err!("Failed to encrypt web push message for {}: {err}", subscription.endpoint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encrypt_with_keys, hkdf_sha256, p256_group, public_key_bytes, VapidKey};
+    use crate::utils::{decode_base64_string, encode_base64_string};
+    use openssl::bn::{BigNum, BigNumContext};
+    use openssl::derive::Deriver;
+    use openssl::ec::{EcKey, EcPoint};
+    use openssl::ecdsa::EcdsaSig;
+    use openssl::pkey::PKey;
+    use openssl::symm::{decrypt_aead, Cipher};
+    use shared::model::{WebPushSubscriptionDto, WebPushSubscriptionKeysDto};
+
+    #[test]
+    fn test_encrypt_can_be_decrypted_by_user_agent() {
+        let group = p256_group().unwrap();
+        let ua_key = EcKey::generate(&group).unwrap();
+        let auth = [7u8; 16];
+        let subscription = WebPushSubscriptionDto {
+            endpoint: "https://push.example.com/abc".to_string(),
+            keys: WebPushSubscriptionKeysDto {
+                p256dh: encode_base64_string(&public_key_bytes(&ua_key).unwrap()),
+                auth: encode_base64_string(&auth),
+            },
+        };
+        let as_key = EcKey::generate(&group).unwrap();
+        let salt = [3u8; 16];
+        let body = encrypt_with_keys(&subscription, b"provider down", &as_key, &salt).unwrap();
+
+        assert_eq!(&body[..16], &salt);
+        assert_eq!(u32::from_be_bytes(body[16..20].try_into().unwrap()), 4096);
+        assert_eq!(body[20], 65);
+        let as_public = &body[21..86];
+        let mut ctx = BigNumContext::new().unwrap();
+        let as_point = EcPoint::from_bytes(&group, as_public, &mut ctx).unwrap();
+        let as_public_key = PKey::from_ec_key(EcKey::from_public_key(&group, &as_point).unwrap()).unwrap();
+
+        let ua_private_key = PKey::from_ec_key(ua_key.clone()).unwrap();
+        let mut deriver = Deriver::new(&ua_private_key).unwrap();
+        deriver.set_peer(&as_public_key).unwrap();
+        let shared_secret = deriver.derive_to_vec().unwrap();
+        let ua_public = decode_base64_string(&subscription.keys.p256dh);
+        let mut key_info = b"WebPush: info\0".to_vec();
+        key_info.extend_from_slice(&ua_public);
+        key_info.extend_from_slice(as_public);
+        let ikm = hkdf_sha256(&auth, &shared_secret, &key_info, 32).unwrap();
+        let cek = hkdf_sha256(&salt, &ikm, b"Content-Encoding: aes128gcm\0", 16).unwrap();
+        let nonce = hkdf_sha256(&salt, &ikm, b"Content-Encoding: nonce\0", 12).unwrap();
+
+        let (ciphertext, tag) = body[86..].split_at(body.len() - 86 - 16);
+        let plaintext = decrypt_aead(Cipher::aes_128_gcm(), &cek, Some(&nonce), &[], ciphertext, tag).unwrap();
+        assert_eq!(plaintext, b"provider down\x02");
+    }
+
+    #[test]
+    fn test_encrypt_rfc8291_example() {
+        // example from RFC 8291 section 5 with the fixed application server key and salt
+        let group = p256_group().unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let as_private = BigNum::from_slice(&decode_base64_string("yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw")).unwrap();
+        let as_public = decode_base64_string("BP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A8");
+        let as_point = EcPoint::from_bytes(&group, &as_public, &mut ctx).unwrap();
+        let as_key = EcKey::from_private_components(&group, &as_private, &as_point).unwrap();
+        let subscription = WebPushSubscriptionDto {
+            endpoint: "https://push.example.net/push/JzLQ3raZJfFBR0aqvOMsLrt54w4rJUsV".to_string(),
+            keys: WebPushSubscriptionKeysDto {
+                p256dh: "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4".to_string(),
+                auth: "BTBZMqHH6r4Tts7J_aSIgg".to_string(),
+            },
+        };
+        let salt: [u8; 16] = decode_base64_string("DGv6ra1nlYgDCS1FRnbzlw").try_into().unwrap();
+        let body = encrypt_with_keys(&subscription, b"When I grow up, I want to be a watermelon", &as_key, &salt).unwrap();
+
+        let expected = decode_base64_string(concat!(
+            "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_",
+            "yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLVWGNWQexSgSxsj_Qulcy4a-fN",
+        ));
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn test_vapid_token_signature() {
+        let key = VapidKey::new(EcKey::generate(&p256_group().unwrap()).unwrap()).unwrap();
+        let token = key.create_token("https://push.example.com/send/123", "mailto:admin@example.com").unwrap();
+        let parts: Vec<&str> = token.split('.').collect();
+        assert_eq!(parts.len(), 3);
+        let claims: serde_json::Value = serde_json::from_slice(&decode_base64_string(parts[1])).unwrap();
+        assert_eq!(claims["aud"], "https://push.example.com");
+        assert_eq!(claims["sub"], "mailto:admin@example.com");
+
+        let signature = decode_base64_string(parts[2]);
+        assert_eq!(signature.len(), 64);
+        let signature = EcdsaSig::from_private_components(
+            BigNum::from_slice(&signature[..32]).unwrap(),
+            BigNum::from_slice(&signature[32..]).unwrap(),
+        ).unwrap();
+        let digest = openssl::sha::sha256(format!("{}.{}", parts[0], parts[1]).as_bytes());
+        assert!(signature.verify(&digest, &key.key).unwrap());
+    }
+}
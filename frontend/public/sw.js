@@ -0,0 +1,128 @@
+// Tuliprox service worker: static asset cache, offline page and web push notifications.
+const CACHE_NAME = 'tuliprox-static-v1';
+const OFFLINE_PAGE = 'offline.html';
+const PRECACHE_FILES = [
+  OFFLINE_PAGE,
+  'manifest.webmanifest',
+  'assets/tuliprox-logo.svg',
+  'assets/favicon.ico',
+  'assets/fonts.css',
+];
+// trunk build artifacts carry a content hash, older versions are dropped when a new one is cached
+const BUILD_ARTIFACT = /\/(frontend-[^/]*\.(?:js|wasm)|main-[^/]*\.css)$/;
+const BYPASS_PATHS = ['api/', 'auth/', 'ws'];
+
+const scopeUrl = (path) => new URL(path, self.registration.scope).href;
+
+self.addEventListener('install', (event) => {
+  event.waitUntil(
+    caches.open(CACHE_NAME)
+      .then((cache) => cache.addAll(PRECACHE_FILES.map(scopeUrl)))
+      .then(() => self.skipWaiting())
+  );
+});
+
+self.addEventListener('activate', (event) => {
+  event.waitUntil(
+    caches.keys()
+      .then((keys) => Promise.all(keys.filter((key) => key !== CACHE_NAME).map((key) => caches.delete(key))))
+      .then(() => self.clients.claim())
+  );
+});
+
+const relativePath = (url) => url.href.startsWith(self.registration.scope)
+  ? url.href.substring(self.registration.scope.length)
+  : null;
+
+const isStaticAsset = (url, path) => path.startsWith('assets/')
+  || BUILD_ARTIFACT.test(url.pathname)
+  || PRECACHE_FILES.includes(path);
+
+// e.g. `frontend.wasm` for `frontend-<hash>_bg.wasm`
+const artifactKind = (pathname) => {
+  const match = pathname.match(BUILD_ARTIFACT);
+  return match ? match[1].split('-')[0] + pathname.substring(pathname.lastIndexOf('.')) : null;
+};
+
+const pruneBuildArtifacts = async (cache, url) => {
+  const kind = artifactKind(url.pathname);
+  if (!kind) {
+    return;
+  }
+  for (const request of await cache.keys()) {
+    const pathname = new URL(request.url).pathname;
+    if (pathname !== url.pathname && artifactKind(pathname) === kind) {
+      await cache.delete(request);
+    }
+  }
+};
+
+// serve from cache and refresh the entry in the background
+const staleWhileRevalidate = async (event, url) => {
+  const cache = await caches.open(CACHE_NAME);
+  const cached = await cache.match(event.request);
+  const update = fetch(event.request).then(async (response) => {
+    if (response.ok && response.type === 'basic') {
+      await cache.put(event.request, response.clone());
+      await pruneBuildArtifacts(cache, url);
+    }
+    return response;
+  });
+  if (cached) {
+    event.waitUntil(update.catch(() => undefined));
+    return cached;
+  }
+  return update;
+};
+
+const navigate = async (request) => {
+  try {
+    return await fetch(request);
+  } catch (err) {
+    const offline = await caches.match(scopeUrl(OFFLINE_PAGE));
+    return offline || Response.error();
+  }
+};
+
+self.addEventListener('fetch', (event) => {
+  const request = event.request;
+  if (request.method !== 'GET') {
+    return;
+  }
+  const url = new URL(request.url);
+  const path = relativePath(url);
+  if (path === null || BYPASS_PATHS.some((prefix) => path.startsWith(prefix))) {
+    return;
+  }
+  if (request.mode === 'navigate') {
+    event.respondWith(navigate(request));
+  } else if (isStaticAsset(url, path)) {
+    event.respondWith(staleWhileRevalidate(event, url));
+  }
+});
+
+self.addEventListener('push', (event) => {
+  let message = {};
+  try {
+    message = event.data ? event.data.json() : {};
+  } catch (err) {
+    message = { body: event.data.text() };
+  }
+  const title = message.title || 'Tuliprox';
+  event.waitUntil(self.registration.showNotification(title, {
+    body: message.body || '',
+    icon: scopeUrl('assets/tuliprox-logo.svg'),
+    tag: message.kind,
+    timestamp: message.timestamp ? Date.parse(message.timestamp) : Date.now(),
+  }));
+});
+
+self.addEventListener('notificationclick', (event) => {
+  event.notification.close();
+  event.waitUntil(
+    self.clients.matchAll({ type: 'window', includeUncontrolled: true }).then((windows) => {
+      const client = windows.find((w) => w.url.startsWith(self.registration.scope));
+      return client ? client.focus() : self.clients.openWindow(self.registration.scope);
+    })
+  );
+});
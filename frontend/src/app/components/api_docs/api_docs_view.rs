@@ -0,0 +1,117 @@
+use crate::app::components::input::Input;
+use crate::app::components::{ApiEndpoint, ApiEndpointView, ApiParameter, CollapsePanel, NoContent};
+use crate::hooks::use_service_context;
+use serde_json::Value;
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+const METHODS: [&str; 4] = ["get", "post", "put", "delete"];
+
+fn json_str(value: &Value, key: &str) -> String {
+    value.get(key).and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+/// Groups the operations of the spec by their first tag, in the order of the tag list.
+fn parse_spec(spec: &Value) -> Vec<(String, Vec<Rc<ApiEndpoint>>)> {
+    let mut groups: Vec<(String, Vec<Rc<ApiEndpoint>>)> = spec.get("tags").and_then(Value::as_array)
+        .map(|tags| tags.iter().map(|tag| (json_str(tag, "name"), Vec::new())).collect())
+        .unwrap_or_default();
+    let Some(paths) = spec.get("paths").and_then(Value::as_object) else { return groups; };
+    for (path, operations) in paths {
+        for method in METHODS {
+            let Some(operation) = operations.get(method) else { continue; };
+            let parameters = operation.get("parameters").and_then(Value::as_array)
+                .map(|params| params.iter().map(|param| ApiParameter {
+                    name: json_str(param, "name"),
+                    location: json_str(param, "in"),
+                    required: param.get("required").and_then(Value::as_bool).unwrap_or(false),
+                    description: json_str(param, "description"),
+                }).collect())
+                .unwrap_or_default();
+            let example = operation.pointer("/requestBody/content/application~1json/example")
+                .and_then(|example| serde_json::to_string_pretty(example).ok())
+                .or_else(|| operation.get("requestBody").map(|_| String::new()));
+            let endpoint = Rc::new(ApiEndpoint {
+                method: method.to_string(),
+                path: path.clone(),
+                summary: json_str(operation, "summary"),
+                description: json_str(operation, "description"),
+                parameters,
+                example,
+            });
+            let tag = operation.pointer("/tags/0").and_then(Value::as_str).unwrap_or_default();
+            if let Some((_, endpoints)) = groups.iter_mut().find(|(name, _)| name == tag) {
+                endpoints.push(endpoint);
+            } else {
+                groups.push((tag.to_string(), vec![endpoint]));
+            }
+        }
+    }
+    groups
+}
+
+#[function_component]
+pub fn ApiDocsView() -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let groups = use_state(|| None::<Rc<Vec<(String, Vec<Rc<ApiEndpoint>>)>>>);
+    let filter = use_state(String::new);
+
+    {
+        let services = services.clone();
+        let groups = groups.clone();
+        use_effect_with((), move |()| {
+            spawn_local(async move {
+                let spec = services.api_docs.get_spec().await;
+                groups.set(Some(Rc::new(spec.as_ref().map(parse_spec).unwrap_or_default())));
+            });
+            || ()
+        });
+    }
+
+    let handle_filter = {
+        let filter = filter.clone();
+        Callback::from(move |value: String| filter.set(value.trim().to_lowercase()))
+    };
+
+    let render_groups = || -> Html {
+        let Some(groups) = groups.as_ref() else { return Html::default(); };
+        let matches = |endpoint: &ApiEndpoint| filter.is_empty()
+            || endpoint.path.to_lowercase().contains(&*filter)
+            || endpoint.summary.to_lowercase().contains(&*filter);
+        let content: Vec<Html> = groups.iter().filter_map(|(tag, endpoints)| {
+            let visible: Vec<&Rc<ApiEndpoint>> = endpoints.iter().filter(|endpoint| matches(endpoint)).collect();
+            if visible.is_empty() {
+                return None;
+            }
+            Some(html! {
+                <CollapsePanel class="tp__api-docs__group" title={tag.clone()}>
+                    { for visible.into_iter().map(|endpoint| html! {
+                        <ApiEndpointView key={format!("{}{}", endpoint.method, endpoint.path)} endpoint={Rc::clone(endpoint)} />
+                    })}
+                </CollapsePanel>
+            })
+        }).collect();
+        if content.is_empty() {
+            html! { <NoContent/> }
+        } else {
+            content.into_iter().collect::<Html>()
+        }
+    };
+
+    html! {
+      <div class="tp__api-docs">
+        <div class="tp__api-docs__header">
+          <h1>{ translate.t("LABEL.API_DOCS") }</h1>
+        </div>
+        <div class="tp__api-docs__toolbar">
+          <Input name="filter" icon="Search" placeholder={translate.t("LABEL.SEARCH")} on_change={handle_filter} />
+        </div>
+        <div class="tp__api-docs__body">
+          { render_groups() }
+        </div>
+      </div>
+    }
+}
@@ -0,0 +1,144 @@
+use crate::app::components::input::Input;
+use crate::app::components::{TextArea, TextButton};
+use crate::hooks::use_service_context;
+use crate::html_if;
+use crate::services::RawResponse;
+use std::collections::HashMap;
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiParameter {
+    pub name: String,
+    pub location: String,
+    pub required: bool,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiEndpoint {
+    pub method: String,
+    pub path: String,
+    pub summary: String,
+    pub description: String,
+    pub parameters: Vec<ApiParameter>,
+    pub example: Option<String>,
+}
+
+impl ApiEndpoint {
+    /// Path with the path parameters replaced and the query parameters appended.
+    fn request_path(&self, values: &HashMap<String, String>) -> String {
+        let mut path = self.path.clone();
+        let mut query = Vec::new();
+        for param in &self.parameters {
+            let value = values.get(&param.name).map_or("", |v| v.trim());
+            let encoded = String::from(js_sys::encode_uri_component(value));
+            if param.location == "path" {
+                path = path.replace(&format!("{{{}}}", param.name), &encoded);
+            } else if !value.is_empty() {
+                query.push(format!("{}={encoded}", param.name));
+            }
+        }
+        if query.is_empty() { path } else { format!("{path}?{}", query.join("&")) }
+    }
+}
+
+fn format_response_body(response: &RawResponse) -> String {
+    serde_json::from_str::<serde_json::Value>(&response.body).ok()
+        .and_then(|json| serde_json::to_string_pretty(&json).ok())
+        .unwrap_or_else(|| response.body.clone())
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ApiEndpointViewProps {
+    pub endpoint: Rc<ApiEndpoint>,
+}
+
+#[function_component]
+pub fn ApiEndpointView(props: &ApiEndpointViewProps) -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let expanded = use_state(|| false);
+    let values = use_mut_ref(HashMap::<String, String>::new);
+    let body = use_mut_ref(|| props.endpoint.example.clone().unwrap_or_default());
+    let response = use_state(|| None::<Result<RawResponse, String>>);
+
+    let handle_toggle = {
+        let expanded = expanded.clone();
+        Callback::from(move |_| expanded.set(!*expanded))
+    };
+
+    let handle_execute = {
+        let endpoint = Rc::clone(&props.endpoint);
+        let values = values.clone();
+        let body = body.clone();
+        let response = response.clone();
+        let services = services.clone();
+        Callback::from(move |_| {
+            let path = endpoint.request_path(&values.borrow());
+            let content = endpoint.example.as_ref().map(|_| body.borrow().clone());
+            let method = endpoint.method.clone();
+            let response = response.clone();
+            let services = services.clone();
+            spawn_local(async move {
+                let result = services.api_docs.execute(&method, &path, content).await;
+                response.set(Some(result.map_err(|err| err.to_string())));
+            });
+        })
+    };
+
+    let endpoint = &props.endpoint;
+    html! {
+        <div class={classes!("tp__api-endpoint", format!("tp__api-endpoint--{}", endpoint.method))}>
+            <div class="tp__api-endpoint__header" onclick={handle_toggle}>
+                <span class="tp__api-endpoint__method">{ endpoint.method.to_uppercase() }</span>
+                <span class="tp__api-endpoint__path">{ &endpoint.path }</span>
+                <span class="tp__api-endpoint__summary">{ &endpoint.summary }</span>
+            </div>
+            { html_if!(*expanded, {
+                <div class="tp__api-endpoint__body">
+                    { html_if!(!endpoint.description.is_empty(), {
+                        <span class="tp__api-endpoint__description">{ &endpoint.description }</span>
+                    })}
+                    { for endpoint.parameters.iter().map(|param| {
+                        let values = values.clone();
+                        let name = param.name.clone();
+                        let label = format!("{}{} ({})", param.name, if param.required { "*" } else { "" }, param.location);
+                        html! {
+                            <Input name={param.name.clone()} label={label} placeholder={param.description.clone()}
+                                on_change={Callback::from(move |value: String| { values.borrow_mut().insert(name.clone(), value); })} />
+                        }
+                    })}
+                    { html_if!(endpoint.example.is_some(), {
+                        <TextArea name="body" label={translate.t("LABEL.REQUEST_BODY")} rows={6} value={body.borrow().clone()}
+                            on_change={{
+                                let body = body.clone();
+                                Callback::from(move |value: String| *body.borrow_mut() = value)
+                            }} />
+                    })}
+                    <div class="tp__api-endpoint__toolbar">
+                        <TextButton class="primary" name="execute" icon="PlayArrow" title={translate.t("LABEL.EXECUTE")} onclick={handle_execute} />
+                    </div>
+                    {
+                        match &*response {
+                            Some(Ok(result)) => html! {
+                                <div class="tp__api-endpoint__response">
+                                    <span class="tp__api-endpoint__status">{ format!("{} {}", translate.t("LABEL.STATUS"), result.status) }</span>
+                                    <pre>{ format_response_body(result) }</pre>
+                                </div>
+                            },
+                            Some(Err(err)) => html! {
+                                <div class="tp__api-endpoint__response">
+                                    <span class="tp__api-endpoint__status">{ err }</span>
+                                </div>
+                            },
+                            None => Html::default(),
+                        }
+                    }
+                </div>
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,5 @@
+mod api_docs_view;
+mod api_endpoint_view;
+
+pub use self::api_docs_view::*;
+pub use self::api_endpoint_view::*;
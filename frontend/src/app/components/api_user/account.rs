@@ -0,0 +1,150 @@
+use crate::app::components::{Card, IconButton, TextButton};
+use crate::hooks::use_service_context;
+use crate::model::{BusyStatus, DialogResult, EventMessage};
+use crate::services::DialogService;
+use shared::model::{TargetType, UserAccountDto};
+use shared::utils::unix_ts_to_str;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_hooks::use_clipboard;
+use yew_i18n::use_translation;
+
+#[function_component]
+pub fn ApiUserAccount() -> Html {
+    let translate = use_translation();
+    let service_ctx = use_service_context();
+    let dialog = use_context::<DialogService>().expect("Dialog service not found");
+    let clipboard = use_clipboard();
+    let account = use_state(|| None as Option<Rc<UserAccountDto>>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let account = account.clone();
+        let services = service_ctx.clone();
+        let translate = translate.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                match services.user_api.get_account().await {
+                    Ok(acc) => account.set(acc),
+                    Err(_) => services.toastr.error(translate.t("MESSAGES.DOWNLOAD.USER_ACCOUNT.FAIL")),
+                }
+            });
+            || {}
+        });
+    }
+
+    let copy_to_clipboard = {
+        let clipboard = clipboard.clone();
+        let dialog = dialog.clone();
+        Callback::from(move |text: String| {
+            if *clipboard.is_supported {
+                clipboard.write_text(text);
+            } else {
+                let dlg = dialog.clone();
+                spawn_local(async move {
+                    let _result = dlg.content(html! {<input value={text} readonly={true} class="tp__copy-input"/>}, None, false).await;
+                });
+            }
+        })
+    };
+
+    let handle_regenerate_password = {
+        let services = service_ctx.clone();
+        let translate = translate.clone();
+        let dialog = dialog.clone();
+        let reload = reload.clone();
+        Callback::from(move |_| {
+            let services = services.clone();
+            let translate = translate.clone();
+            let dialog = dialog.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                if dialog.confirm(&translate.t("MESSAGES.CONFIRM_REGENERATE_PASSWORD")).await != DialogResult::Ok {
+                    return;
+                }
+                services.event.broadcast(EventMessage::Busy(BusyStatus::Show));
+                match services.user_api.regenerate_password().await {
+                    Ok(_) => {
+                        services.toastr.success(translate.t("MESSAGES.SAVE.PASSWORD.SUCCESS"));
+                        reload.set(*reload + 1);
+                    }
+                    Err(_) => services.toastr.error(translate.t("MESSAGES.SAVE.PASSWORD.FAIL")),
+                }
+                services.event.broadcast(EventMessage::Busy(BusyStatus::Hide));
+            });
+        })
+    };
+
+    let render_url = |label: &str, url: Option<&String>| -> Html {
+        match url {
+            Some(url) => {
+                let copy = copy_to_clipboard.clone();
+                let url_value = url.clone();
+                html! {
+                    <div class="tp__api-user-account__row">
+                        <label>{translate.t(label)}</label>
+                        <span class="tp__api-user-account__url">{url}</span>
+                        <IconButton name="copy" icon="Clipboard" onclick={Callback::from(move |_| copy.emit(url_value.clone()))} />
+                    </div>
+                }
+            }
+            None => html! {},
+        }
+    };
+
+    let Some(acc) = account.as_ref() else {
+        return html! {};
+    };
+    let outputs = acc.outputs.iter().map(TargetType::to_string).collect::<Vec<_>>().join(", ");
+
+    html! {
+        <div class="tp__api-user-account">
+            <div class="tp__api-user-account__header tp__list-list__header">
+                <h1>{translate.t("TITLE.USER_ACCOUNT")}</h1>
+                <TextButton class="primary" name="regenerate_password"
+                        icon="Refresh"
+                        title={translate.t("LABEL.REGENERATE_PASSWORD")}
+                        onclick={handle_regenerate_password}></TextButton>
+            </div>
+            <Card class="tp__api-user-account__content">
+                <div class="tp__api-user-account__row">
+                    <label>{translate.t("LABEL.USERNAME")}</label>
+                    <span>{&acc.username}</span>
+                </div>
+                <div class="tp__api-user-account__row">
+                    <label>{translate.t("LABEL.PLAYLIST")}</label>
+                    <span>{&acc.target}</span>
+                </div>
+                <div class="tp__api-user-account__row">
+                    <label>{translate.t("LABEL.STATUS")}</label>
+                    <span>{acc.status.map(|status| status.to_string()).unwrap_or_default()}</span>
+                </div>
+                <div class="tp__api-user-account__row">
+                    <label>{translate.t("LABEL.EXP_DATE")}</label>
+                    <span>{acc.exp_date.and_then(unix_ts_to_str).unwrap_or_default()}</span>
+                </div>
+                <div class="tp__api-user-account__row">
+                    <label>{translate.t("LABEL.ACTIVE_CONNECTIONS")}</label>
+                    <span>{
+                        if acc.max_connections > 0 {
+                            format!("{} / {}", acc.active_connections, acc.max_connections)
+                        } else {
+                            acc.active_connections.to_string()
+                        }
+                    }</span>
+                </div>
+                <div class="tp__api-user-account__row">
+                    <label>{translate.t("LABEL.OUTPUTS")}</label>
+                    <span>{outputs}</span>
+                </div>
+                <div class="tp__api-user-account__row">
+                    <label>{translate.t("LABEL.SERVER")}</label>
+                    <span class="tp__api-user-account__url">{&acc.server_url}</span>
+                </div>
+                { render_url("LABEL.M3U_URL", acc.m3u_url.as_ref()) }
+                { render_url("LABEL.EPG_URL", acc.epg_url.as_ref()) }
+            </Card>
+        </div>
+    }
+}
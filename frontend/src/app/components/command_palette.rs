@@ -0,0 +1,256 @@
+use crate::app::components::AppIcon;
+use crate::app::context::{ConfigContext, PlaylistContext};
+use crate::hooks::use_service_context;
+use crate::model::{EventMessage, ViewType};
+use crate::utils::{fuzzy_score, html_if};
+use shared::model::WebUiRole;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{window, HtmlInputElement};
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+const MAX_RESULTS: usize = 50;
+
+#[derive(Clone, PartialEq)]
+enum CommandAction {
+    View(ViewType),
+    RefreshTarget(String),
+    OpenUser(String, String),
+}
+
+#[derive(Clone, PartialEq)]
+struct Command {
+    label: String,
+    category: String,
+    icon: &'static str,
+    shortcut: Option<String>,
+    action: CommandAction,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CommandPaletteProps {
+    pub onview: Callback<ViewType>,
+}
+
+/// `Ctrl+K` opens the palette, `Alt+<digit>` opens the pages of the sidebar.
+#[function_component]
+pub fn CommandPalette(props: &CommandPaletteProps) -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let config_ctx = use_context::<ConfigContext>().expect("Config context not found");
+    let playlist_ctx = use_context::<PlaylistContext>().expect("Playlist context not found");
+    let is_open = use_state(|| false);
+    let query = use_state(String::new);
+    let selected = use_state(|| 0usize);
+    let input_ref = use_node_ref();
+    let keydown_handle = use_mut_ref(|| None::<Closure<dyn FnMut(KeyboardEvent)>>);
+
+    let pages: Rc<Vec<ViewType>> = Rc::new(ViewType::ALL.iter()
+        .filter(|view| services.auth.has_role(view.required_role()))
+        .copied()
+        .collect());
+
+    {
+        let keydown_handle = keydown_handle.clone();
+        let set_open = is_open.clone();
+        let set_query = query.clone();
+        let set_selected = selected.clone();
+        let onview = props.onview.clone();
+        use_effect_with(pages.clone(), move |pages| {
+            let pages = pages.clone();
+            let closure = Closure::<dyn FnMut(KeyboardEvent)>::wrap(Box::new(move |event: KeyboardEvent| {
+                if (event.ctrl_key() || event.meta_key()) && event.key().eq_ignore_ascii_case("k") {
+                    event.prevent_default();
+                    set_query.set(String::new());
+                    set_selected.set(0);
+                    set_open.set(true);
+                } else if event.key() == "Escape" {
+                    set_open.set(false);
+                } else if event.alt_key() && !event.ctrl_key() && !event.meta_key() {
+                    let page = event.code().strip_prefix("Digit")
+                        .and_then(|digit| digit.parse::<usize>().ok())
+                        .and_then(|digit| ViewType::ALL.get((digit + 9) % 10))
+                        .filter(|view| pages.contains(view));
+                    if let Some(view) = page {
+                        event.prevent_default();
+                        set_open.set(false);
+                        onview.emit(*view);
+                    }
+                }
+            }));
+
+            let window = window().expect("no global window");
+            window
+                .add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())
+                .expect("could not add event listener");
+            *keydown_handle.borrow_mut() = Some(closure);
+
+            move || {
+                if let Some(closure) = keydown_handle.borrow_mut().take() {
+                    let _ = window.remove_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref());
+                }
+            }
+        });
+    }
+
+    {
+        let input_ref = input_ref.clone();
+        use_effect_with(*is_open, move |open| {
+            if *open {
+                if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                    let _ = input.focus();
+                }
+            }
+        });
+    }
+
+    let commands = {
+        let mut commands: Vec<Command> = pages.iter().map(|view| Command {
+            label: translate.t(view.label()),
+            category: translate.t("LABEL.PAGES"),
+            icon: view.icon(),
+            shortcut: view.shortcut(),
+            action: CommandAction::View(*view),
+        }).collect();
+        if services.auth.has_role(WebUiRole::Operator) {
+            if let Some(sources) = playlist_ctx.sources.as_ref().as_ref() {
+                commands.extend(sources.iter().flat_map(|(_inputs, targets)| targets.iter()).map(|target| Command {
+                    label: format!("{} {}", translate.t("LABEL.REFRESH_TARGET"), target.name),
+                    category: translate.t("LABEL.TARGETS"),
+                    icon: "Refresh",
+                    shortcut: None,
+                    action: CommandAction::RefreshTarget(target.name.clone()),
+                }));
+            }
+            if let Some(api_proxy) = config_ctx.config.as_ref().and_then(|cfg| cfg.api_proxy.as_ref()) {
+                commands.extend(api_proxy.user.iter().flat_map(|target_user| target_user.credentials.iter()
+                    .map(|credentials| Command {
+                        label: format!("{} {} ({})", translate.t("LABEL.OPEN_USER"), credentials.username, target_user.target),
+                        category: translate.t("LABEL.USERS"),
+                        icon: "UserOutline",
+                        shortcut: None,
+                        action: CommandAction::OpenUser(target_user.target.clone(), credentials.username.clone()),
+                    })));
+            }
+        }
+        commands
+    };
+
+    let results: Rc<Vec<Command>> = {
+        let mut scored: Vec<(i32, Command)> = commands.into_iter()
+            .filter_map(|command| fuzzy_score(&query, &command.label).map(|score| (score, command)))
+            .collect();
+        // the sort is stable, equal scores keep the order of pages, targets and users
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        Rc::new(scored.into_iter().map(|(_, command)| command).take(MAX_RESULTS).collect())
+    };
+
+    let execute = {
+        let services = services.clone();
+        let translate = translate.clone();
+        let onview = props.onview.clone();
+        let set_open = is_open.clone();
+        Callback::from(move |action: CommandAction| {
+            set_open.set(false);
+            match action {
+                CommandAction::View(view) => onview.emit(view),
+                CommandAction::RefreshTarget(target) => {
+                    let services = services.clone();
+                    let translate = translate.clone();
+                    spawn_local(async move {
+                        if services.playlist.update_targets(&[target.as_str()]).await {
+                            services.toastr.success(translate.t("MESSAGES.PLAYLIST_UPDATE.SUCCESS"));
+                        } else {
+                            services.toastr.error(translate.t("MESSAGES.PLAYLIST_UPDATE.FAIL"));
+                        }
+                    });
+                }
+                CommandAction::OpenUser(target, username) => {
+                    onview.emit(ViewType::Users);
+                    services.event.broadcast(EventMessage::OpenUser(target, username));
+                }
+            }
+        })
+    };
+
+    let handle_input = {
+        let set_query = query.clone();
+        let set_selected = selected.clone();
+        Callback::from(move |event: InputEvent| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            set_query.set(input.value());
+            set_selected.set(0);
+        })
+    };
+
+    let handle_keydown = {
+        let set_selected = selected.clone();
+        let results = results.clone();
+        let execute = execute.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            let count = results.len();
+            match event.key().as_str() {
+                "ArrowDown" if count > 0 => {
+                    event.prevent_default();
+                    set_selected.set((*set_selected + 1) % count);
+                }
+                "ArrowUp" if count > 0 => {
+                    event.prevent_default();
+                    set_selected.set((*set_selected + count - 1) % count);
+                }
+                "Enter" => {
+                    event.prevent_default();
+                    if let Some(command) = results.get(*set_selected) {
+                        execute.emit(command.action.clone());
+                    }
+                }
+                _ => {}
+            }
+        })
+    };
+
+    let handle_close = {
+        let set_open = is_open.clone();
+        Callback::from(move |_| set_open.set(false))
+    };
+
+    if !*is_open {
+        return html! {};
+    }
+
+    html! {
+        <div class="tp__command-palette" onclick={handle_close}>
+            <div class="tp__command-palette__dialog" onclick={Callback::from(|event: MouseEvent| event.stop_propagation())}>
+                <input ref={input_ref} class="tp__command-palette__input" type="text" autocomplete="off"
+                    placeholder={translate.t("MESSAGES.COMMAND_PALETTE.PLACEHOLDER")}
+                    value={(*query).clone()} oninput={handle_input} onkeydown={handle_keydown} />
+                <ul class="tp__command-palette__list">
+                    { for results.iter().enumerate().map(|(index, command)| {
+                        let execute = execute.clone();
+                        let action = command.action.clone();
+                        let set_selected = selected.clone();
+                        html! {
+                            <li class={classes!("tp__command-palette__item", if index == *selected { "active" } else { "" })}
+                                onclick={Callback::from(move |_| execute.emit(action.clone()))}
+                                onmouseenter={Callback::from(move |_| set_selected.set(index))}>
+                                <span class="tp__command-palette__icon"><AppIcon name={command.icon} /></span>
+                                <span class="tp__command-palette__label">{ &command.label }</span>
+                                <span class="tp__command-palette__category">{ &command.category }</span>
+                                { html_if!(command.shortcut.is_some(), {
+                                    <kbd class="tp__command-palette__shortcut">{ command.shortcut.clone().unwrap_or_default() }</kbd>
+                                })}
+                            </li>
+                        }
+                    }) }
+                </ul>
+                { html_if!(results.is_empty(), {
+                    <span class="tp__command-palette__empty">{ translate.t("MESSAGES.COMMAND_PALETTE.NO_RESULTS") }</span>
+                })}
+                <span class="tp__command-palette__hint">{ translate.t("MESSAGES.COMMAND_PALETTE.HINT") }</span>
+            </div>
+        </div>
+    }
+}
@@ -2,7 +2,7 @@ use crate::app::context::ConfigContext;
 use yew::prelude::*;
 use yew_i18n::use_translation;
 use shared::model::{ConfigApiDto};
-use crate::{config_field, config_field_empty, edit_field_number_u16, edit_field_text, generate_form_reducer, html_if};
+use crate::{config_field, config_field_bool, config_field_bool_empty, config_field_empty, edit_field_bool, edit_field_number_u16, edit_field_text, generate_form_reducer, html_if};
 use crate::app::components::AppIcon;
 use crate::app::components::config::config_view_context::ConfigViewContext;
 use crate::app::components::config::config_page::{ConfigForm, LABEL_API_CONFIG};
@@ -10,6 +10,8 @@ use crate::app::components::config::config_page::{ConfigForm, LABEL_API_CONFIG};
 const LABEL_HOST: &str = "LABEL.HOST";
 const LABEL_PORT: &str = "LABEL.PORT";
 const LABEL_WEB_ROOT: &str = "LABEL.WEB_ROOT";
+const LABEL_DEBUG_ENDPOINTS: &str = "LABEL.DEBUG_ENDPOINTS";
+const LABEL_SHUTDOWN_GRACE_SECS: &str = "LABEL.SHUTDOWN_GRACE_SECS";
 
 // Generate form reducer for edit mode
 generate_form_reducer!(
@@ -19,6 +21,8 @@ generate_form_reducer!(
         Host => host: String,
         Port => port: u16,
         WebRoot => web_root: String,
+        DebugEndpoints => debug_endpoints: bool,
+        ShutdownGraceSecs => shutdown_grace_secs: u16,
     }
 );
 
@@ -66,6 +70,8 @@ pub fn ApiConfigView() -> Html {
                 { config_field_empty!(translate.t(LABEL_HOST)) }
                 { config_field_empty!(translate.t(LABEL_PORT)) }
                 { config_field_empty!(translate.t(LABEL_WEB_ROOT)) }
+                { config_field_bool_empty!(translate.t(LABEL_DEBUG_ENDPOINTS)) }
+                { config_field_empty!(translate.t(LABEL_SHUTDOWN_GRACE_SECS)) }
             </>
         }
     };
@@ -77,6 +83,8 @@ pub fn ApiConfigView() -> Html {
                     { config_field!(config.config.api, translate.t(LABEL_HOST), host) }
                     { config_field!(config.config.api, translate.t(LABEL_PORT), port) }
                     { config_field!(config.config.api, translate.t(LABEL_WEB_ROOT), web_root) }
+                    { config_field_bool!(config.config.api, translate.t(LABEL_DEBUG_ENDPOINTS), debug_endpoints) }
+                    { config_field!(config.config.api, translate.t(LABEL_SHUTDOWN_GRACE_SECS), shutdown_grace_secs) }
                 </>
             }
         } else {
@@ -90,6 +98,8 @@ pub fn ApiConfigView() -> Html {
                 { edit_field_text!(form_state, translate.t(LABEL_HOST), host, ApiConfigFormAction::Host) }
                 { edit_field_number_u16!(form_state, translate.t(LABEL_PORT), port, ApiConfigFormAction::Port) }
                 { edit_field_text!(form_state, translate.t(LABEL_WEB_ROOT), web_root, ApiConfigFormAction::WebRoot) }
+                { edit_field_bool!(form_state, translate.t(LABEL_DEBUG_ENDPOINTS), debug_endpoints, ApiConfigFormAction::DebugEndpoints) }
+                { edit_field_number_u16!(form_state, translate.t(LABEL_SHUTDOWN_GRACE_SECS), shutdown_grace_secs, ApiConfigFormAction::ShutdownGraceSecs) }
             </>
         }
     };
@@ -24,6 +24,7 @@ pub fn update_config(config: &mut ConfigDto, forms: Vec<ConfigForm>) {
                 } else {
                     config.schedules = schedules_cfg.schedules.clone();
                 }
+                config.schedule_spread_mins = schedules_cfg.schedule_spread_mins.filter(|mins| *mins > 0);
             },
             ConfigForm::Video(_, mut video_cfg) =>  set_config_field!(config, video_cfg, video),
             ConfigForm::Messaging(_, mut messaging_cfg) => set_config_field!(config, messaging_cfg, messaging),
@@ -8,10 +8,11 @@ use log::warn;
 use yew::platform::spawn_local;
 use yew::prelude::*;
 use yew_i18n::use_translation;
-use shared::model::{ConfigDto, SourcesConfigDto};
+use shared::model::{ConfigDto, SourcesConfigDto, WebUiRole};
 use crate::app::components::config::config_update::update_config;
 use crate::app::{ConfigContext};
 use crate::hooks::use_service_context;
+use crate::services::{ToastCloseMode, ToastOptions};
 
 const LABEL_CONFIG: &str = "LABEL.CONFIG";
 const LABEL_EDIT: &str = "LABEL.EDIT";
@@ -212,8 +213,13 @@ pub fn ConfigView() -> Html {
 
                 if let Some(sources_dto) = modified_sources {
                     match services.config.save_sources(sources_dto).await {
-                        Ok(()) => {
+                        Ok(warnings) => {
                             services.toastr.success(translate.t("MESSAGES.SAVE.SOURCES_CONFIG.SUCCESS"));
+                            for warning in warnings {
+                                services.toastr.warning_with_options(
+                                    format!("{}: {warning}", translate.t("MESSAGES.SAVE.SOURCES_CONFIG.LINT_WARNING")),
+                                    ToastOptions { close_mode: ToastCloseMode::Manual });
+                            }
                         }
                         Err(err) => {
                             ok = false;
@@ -281,7 +287,9 @@ pub fn ConfigView() -> Html {
         on_form_change: on_form_change.clone(),
     };
 
-    let geo_ip_enabled = config_ctx.config.as_ref().is_some_and(|c| c.config.is_geoip_enabled());
+    let geo_ip_enabled = config_ctx.config.as_ref().is_some_and(|c| c.config.is_geoip_enabled())
+        && services_ctx.auth.has_role(WebUiRole::Operator);
+    let can_edit = services_ctx.auth.is_admin();
 
     html! {
         <ContextProvider<ConfigViewContext> context={context}>
@@ -296,11 +304,13 @@ pub fn ConfigView() -> Html {
                         onclick={handle_update_content.clone()}></TextButton>
                 })}
                 </div>
+               {html_if!(can_edit, {
                <TextButton name="config_edit"
                     class={ if *edit_mode { "secondary" } else { "primary" }}
                     icon={ if *edit_mode { "Unlocked" } else { "Locked" }}
                     title={ if *edit_mode { translate.t(LABEL_EDIT) } else { translate.t(LABEL_VIEW) }}
                     onclick={handle_config_edit}></TextButton>
+               })}
 
             </div>
             <div class="tp__config-view__body">
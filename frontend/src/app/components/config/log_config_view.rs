@@ -1,9 +1,9 @@
 use std::rc::Rc;
 use yew::prelude::*;
 use yew_i18n::use_translation;
-use shared::model::{LogConfigDto};
+use shared::model::{IpAnonymization, LogConfigDto};
 use crate::app::context::ConfigContext;
-use crate::{config_field_bool, config_field_child, edit_field_bool, generate_form_reducer};
+use crate::{config_field, config_field_bool, config_field_child, edit_field_bool, edit_field_number_u16, generate_form_reducer};
 use crate::app::components::config::config_view_context::ConfigViewContext;
 use crate::app::components::config::config_page::{ConfigForm, LABEL_LOG_CONFIG};
 use crate::app::components::{Card, Chip, RadioButtonGroup};
@@ -11,8 +11,28 @@ use crate::app::components::{Card, Chip, RadioButtonGroup};
 const LABEL_LOG_LEVEL: &str =  "LABEL.LOG_LEVEL";
 const LABEL_LOG_ACTIVE_USER: &str =  "LABEL.LOG_ACTIVE_USER";
 const LABEL_LOG_SANITIZE_SENSITIVE_INFO: &str =  "LABEL.SANITIZE_SENSITIVE_INFO";
+const LABEL_IP_ANONYMIZATION: &str =  "LABEL.IP_ANONYMIZATION";
+const LABEL_RETENTION_DAYS: &str =  "LABEL.RETENTION_DAYS";
+const LABEL_SESSION_HISTORY_DAYS: &str =  "LABEL.SESSION_HISTORY_DAYS";
 
 const LOG_LEVELS: [&str; 5] = ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"];
+const IP_ANONYMIZATION_MODES: [&str; 3] = ["none", "truncate", "hash"];
+
+fn ip_anonymization_to_str(mode: IpAnonymization) -> &'static str {
+    match mode {
+        IpAnonymization::None => "none",
+        IpAnonymization::Truncate => "truncate",
+        IpAnonymization::Hash => "hash",
+    }
+}
+
+fn ip_anonymization_from_str(mode: &str) -> IpAnonymization {
+    match mode {
+        "truncate" => IpAnonymization::Truncate,
+        "hash" => IpAnonymization::Hash,
+        _ => IpAnonymization::None,
+    }
+}
 
 generate_form_reducer!(
     state: LogConfigFormState { form: LogConfigDto },
@@ -21,6 +41,9 @@ generate_form_reducer!(
         LogLevel => log_level: Option<String>,
         SanitizeSensitiveInfo => sanitize_sensitive_info: bool,
         LogActiveUser => log_active_user: bool,
+        IpAnonymization => ip_anonymization: IpAnonymization,
+        RetentionDays => retention_days: u16,
+        SessionHistoryDays => session_history_days: u16,
     }
 );
 
@@ -33,6 +56,9 @@ pub fn LogConfigView() -> Html {
     let log_level_options = use_memo((), |_| {
         LOG_LEVELS.iter().map(ToString::to_string).collect::<Vec<String>>()
     });
+    let ip_anonymization_options = use_memo((), |_| {
+        IP_ANONYMIZATION_MODES.iter().map(ToString::to_string).collect::<Vec<String>>()
+    });
 
     let form_state: UseReducerHandle<LogConfigFormState> = use_reducer(|| {
         LogConfigFormState { form: LogConfigDto::default(), modified: false }
@@ -70,6 +96,11 @@ pub fn LogConfigView() -> Html {
             <Card class="tp__config-view__card">
             { config_field_bool!(log_state.form, translate.t(LABEL_LOG_ACTIVE_USER),  log_active_user) }
             { config_field_bool!(log_state.form, translate.t(LABEL_LOG_SANITIZE_SENSITIVE_INFO),  sanitize_sensitive_info) }
+            { config_field_child!(translate.t(LABEL_IP_ANONYMIZATION), {
+                html! { <div><Chip label={ip_anonymization_to_str(log_state.form.ip_anonymization).to_string()} /></div> }
+            })}
+            { config_field!(log_state.form, translate.t(LABEL_RETENTION_DAYS), retention_days) }
+            { config_field!(log_state.form, translate.t(LABEL_SESSION_HISTORY_DAYS), session_history_days) }
            </Card>
             <Card class="tp__config-view__card">
             <div class="tp__log-config-view__header tp__config-view-page__header">
@@ -88,11 +119,26 @@ pub fn LogConfigView() -> Html {
     let render_edit_mode = || {
         let forms = form_state.clone();
         let log_level_selection = Rc::new(forms.form.log_level.as_ref().map_or_else(Vec::new, |l| vec![l.to_uppercase()]));
+        let ip_forms = form_state.clone();
+        let ip_anonymization_selection = Rc::new(vec![ip_anonymization_to_str(form_state.form.ip_anonymization).to_string()]);
         html! {
             <>
             <Card class="tp__config-view__card">
             { edit_field_bool!(form_state, translate.t(LABEL_LOG_ACTIVE_USER), log_active_user, LogConfigFormAction::LogActiveUser) }
             { edit_field_bool!(form_state, translate.t(LABEL_LOG_SANITIZE_SENSITIVE_INFO),  sanitize_sensitive_info, LogConfigFormAction::SanitizeSensitiveInfo) }
+            { config_field_child!(translate.t(LABEL_IP_ANONYMIZATION), {
+               html! { <RadioButtonGroup
+                    multi_select={false} none_allowed={false}
+                    on_select={Callback::from(move |selections: Rc<Vec<String>>| {
+                        let mode = selections.first().map_or(IpAnonymization::None, |m| ip_anonymization_from_str(m));
+                        ip_forms.dispatch(LogConfigFormAction::IpAnonymization(mode));
+                    })}
+                    options={ip_anonymization_options.clone()}
+                    selected={ip_anonymization_selection}
+                />
+            }})}
+            { edit_field_number_u16!(form_state, translate.t(LABEL_RETENTION_DAYS), retention_days, LogConfigFormAction::RetentionDays) }
+            { edit_field_number_u16!(form_state, translate.t(LABEL_SESSION_HISTORY_DAYS), session_history_days, LogConfigFormAction::SessionHistoryDays) }
             </Card>
             <Card class="tp__config-view__card">
             { config_field_child!(translate.t(LABEL_LOG_LEVEL), {
@@ -163,6 +163,7 @@ macro_rules! edit_field_text_option {
                         }));
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -186,6 +187,7 @@ macro_rules! edit_field_textarea_option {
                         }));
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -213,6 +215,7 @@ macro_rules! edit_field_text {
                         instance.dispatch($action(value.into()));
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -229,6 +232,7 @@ macro_rules! edit_field_bool {
                      readonly={false}
                      on_change={Callback::from(move |value| instance.dispatch($action(value)))} />
                 <label>{$label}</label>
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -254,6 +258,7 @@ macro_rules! edit_field_number {
                         }
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -279,6 +284,7 @@ macro_rules! edit_field_number_u8 {
                         }
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -305,6 +311,7 @@ macro_rules! edit_field_number_f64 {
                         })
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -330,6 +337,7 @@ macro_rules! edit_field_number_u16 {
                         }
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -356,6 +364,7 @@ macro_rules! edit_field_number_i16 {
                         }
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -381,6 +390,7 @@ macro_rules! edit_field_number_u64 {
                         }
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -406,6 +416,7 @@ macro_rules! edit_field_number_usize {
                         }
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -431,6 +442,7 @@ macro_rules! edit_field_number_option {
                         }
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -450,6 +462,7 @@ macro_rules! edit_field_date {
                         instance.dispatch($action(value));
                     })}
                 />
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -473,6 +486,7 @@ macro_rules! edit_field_list {
                         let list = value.iter().map(|t| t.label.clone()).collect();
                         instance.dispatch($action(list));
                      })}/>
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
@@ -500,6 +514,7 @@ macro_rules! edit_field_list_option {
                             instance.dispatch($action(Some(list)));
                         }
                      })}/>
+                <$crate::app::components::FieldHelp name={stringify!($field)} />
             </div>
         }
     }};
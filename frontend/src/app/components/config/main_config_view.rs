@@ -5,13 +5,15 @@ use crate::app::context::ConfigContext;
 use crate::app::components::config::config_view_context::ConfigViewContext;
 use crate::app::components::config::config_page::{ConfigForm, LABEL_MAIN_CONFIG};
 use crate::{config_field_optional, config_field_bool, config_field, edit_field_text_option, edit_field_bool,
-            generate_form_reducer, edit_field_number, edit_field_number_option, edit_field_text};
+            generate_form_reducer, edit_field_number, edit_field_number_u16, edit_field_number_option, edit_field_text};
 
 const LABEL_UPDATE_ON_BOOT: &str = "LABEL.UPDATE_ON_BOOT";
 const LABEL_CONFIG_HOT_RELOAD: &str = "LABEL.CONFIG_HOT_RELOAD";
 const LABEL_USER_ACCESS_CONTROL: &str = "LABEL.USER_ACCESS_CONTROL";
 const LABEL_PROCESS_PARALLEL: &str = "LABEL.PROCESS_PARALLEL";
+const LABEL_PROCESS_PARALLEL_TARGETS: &str = "LABEL.PROCESS_PARALLEL_TARGETS";
 const LABEL_DISK_BASED_PROCESSING: &str = "LABEL.DISK_BASED_PROCESSING";
+const LABEL_VERIFY_STORAGE_CHECKSUMS: &str = "LABEL.VERIFY_STORAGE_CHECKSUMS";
 const LABEL_WORKING_DIR: &str = "LABEL.WORKING_DIR";
 const LABEL_DEFAULT_USER_AGENT: &str = "LABEL.DEFAULT_USER_AGENT";
 const LABEL_MAPPING_PATH: &str = "LABEL.MAPPING_PATH";
@@ -31,7 +33,9 @@ generate_form_reducer!(
         UserAccessControl => user_access_control: bool,
         AcceptInsecureSslCertificates => accept_insecure_ssl_certificates: bool,
         ProcessParallel => process_parallel: bool,
+        ProcessParallelTargets => process_parallel_targets: u16,
         DiskBasedProcessing => disk_based_processing: bool,
+        VerifyStorageChecksums => verify_storage_checksums: bool,
         WorkingDir => working_dir: String,
         DefaultUserAgent => default_user_agent: Option<String>,
         MappingPath => mapping_path: Option<String>,
@@ -83,7 +87,9 @@ pub fn MainConfigView() -> Html {
                 { config_field_bool!(form_state.form, translate.t(LABEL_USER_ACCESS_CONTROL), user_access_control) }
                 { config_field_bool!(form_state.form, translate.t(LABEL_ACCEPT_INSECURE_SSL_CERTIFICATES), accept_insecure_ssl_certificates) }
                 { config_field_bool!(form_state.form, translate.t(LABEL_PROCESS_PARALLEL), process_parallel) }
+                { config_field!(form_state.form, translate.t(LABEL_PROCESS_PARALLEL_TARGETS), process_parallel_targets) }
                 { config_field_bool!(form_state.form, translate.t(LABEL_DISK_BASED_PROCESSING), disk_based_processing) }
+                { config_field_bool!(form_state.form, translate.t(LABEL_VERIFY_STORAGE_CHECKSUMS), verify_storage_checksums) }
                 { config_field!(form_state.form, translate.t(LABEL_WORKING_DIR), working_dir) }
                 { config_field_optional!(form_state.form, translate.t(LABEL_DEFAULT_USER_AGENT), default_user_agent) }
                 { config_field_optional!(form_state.form, translate.t(LABEL_MAPPING_PATH), mapping_path) }
@@ -103,7 +109,9 @@ pub fn MainConfigView() -> Html {
             { edit_field_bool!(form_state, translate.t(LABEL_USER_ACCESS_CONTROL), user_access_control, MainConfigFormAction::UserAccessControl) }
             { edit_field_bool!(form_state, translate.t(LABEL_ACCEPT_INSECURE_SSL_CERTIFICATES), accept_insecure_ssl_certificates, MainConfigFormAction::AcceptInsecureSslCertificates) }
             { edit_field_bool!(form_state, translate.t(LABEL_PROCESS_PARALLEL), process_parallel, MainConfigFormAction::ProcessParallel) }
+            { edit_field_number_u16!(form_state, translate.t(LABEL_PROCESS_PARALLEL_TARGETS), process_parallel_targets, MainConfigFormAction::ProcessParallelTargets) }
             { edit_field_bool!(form_state, translate.t(LABEL_DISK_BASED_PROCESSING), disk_based_processing, MainConfigFormAction::DiskBasedProcessing) }
+            { edit_field_bool!(form_state, translate.t(LABEL_VERIFY_STORAGE_CHECKSUMS), verify_storage_checksums, MainConfigFormAction::VerifyStorageChecksums) }
             { edit_field_text!(form_state, translate.t(LABEL_WORKING_DIR), working_dir, MainConfigFormAction::WorkingDir) }
             { edit_field_text_option!(form_state, translate.t(LABEL_DEFAULT_USER_AGENT), default_user_agent, MainConfigFormAction::DefaultUserAgent) }
             { edit_field_text_option!(form_state, translate.t(LABEL_MAPPING_PATH), mapping_path, MainConfigFormAction::MappingPath) }
@@ -4,7 +4,7 @@ use crate::app::components::{Card, Chip, RadioButtonGroup, TextArea};
 use crate::app::ConfigContext;
 use crate::{config_field, config_field_bool, config_field_bool_empty, config_field_child, config_field_custom, config_field_empty, config_field_hide, config_field_optional, edit_field_bool, edit_field_list, edit_field_text, edit_field_text_option, generate_form_reducer};
 use shared::model::{DiscordMessagingConfigDto, MessagingConfigDto, MsgKind, PushoverMessagingConfigDto,
-                    RestMessagingConfigDto, TelegramMessagingConfigDto};
+                    RestMessagingConfigDto, TelegramMessagingConfigDto, WebPushMessagingConfigDto, WebhookMessagingConfigDto};
 use std::rc::Rc;
 use std::str::FromStr;
 use yew::prelude::*;
@@ -25,6 +25,11 @@ const LABEL_METHOD: &str = "LABEL.METHOD";
 const LABEL_HEADERS: &str = "LABEL.HEADERS";
 const LABEL_WEBHOOK_URL: &str = "LABEL.WEBHOOK_URL";
 const LABEL_ADD_HEADER: &str = "LABEL.ADD_HEADER";
+const LABEL_WEB_PUSH: &str = "LABEL.WEB_PUSH";
+const LABEL_SUBJECT: &str = "LABEL.SUBJECT";
+const LABEL_WEBHOOK: &str = "LABEL.WEBHOOK";
+const LABEL_NAME: &str = "LABEL.NAME";
+const LABEL_BODY_TEMPLATE: &str = "LABEL.BODY_TEMPLATE";
 
 generate_form_reducer!(
     state: TelegramMessagingConfigFormState { form: TelegramMessagingConfigDto },
@@ -67,6 +72,14 @@ generate_form_reducer!(
     }
 );
 
+generate_form_reducer!(
+    state: WebPushMessagingConfigFormState { form: WebPushMessagingConfigDto },
+    action_name: WebPushMessagingConfigFormAction,
+    fields {
+        Subject => subject: String,
+    }
+);
+
 generate_form_reducer!(
     state: MessagingConfigFormState { form: MessagingConfigDto },
     action_name: MessagingConfigFormAction,
@@ -104,6 +117,12 @@ pub fn MessagingConfigView() -> Html {
             modified: false,
         });
 
+    let web_push_state =
+        use_reducer(|| WebPushMessagingConfigFormState {
+            form: WebPushMessagingConfigDto::default(),
+            modified: false,
+        });
+
     let messaging_state =
         use_reducer(|| MessagingConfigFormState {
             form: MessagingConfigDto::default(),
@@ -130,6 +149,7 @@ pub fn MessagingConfigView() -> Html {
         let rest_state = rest_state.clone();
         let pushover_state = pushover_state.clone();
         let discord_state = discord_state.clone();
+        let web_push_state = web_push_state.clone();
 
         let dependencies = (
             messaging_state.modified,
@@ -137,20 +157,23 @@ pub fn MessagingConfigView() -> Html {
             rest_state.modified,
             pushover_state.modified,
             discord_state.modified,
+            web_push_state.modified,
             messaging_state,
             telegram_state,
             rest_state,
             pushover_state,
             discord_state,
+            web_push_state,
         );
-        use_effect_with(dependencies, move |(mm, tm, rm, pm, dm, m, t, r, p, d)| {
+        use_effect_with(dependencies, move |(mm, tm, rm, pm, dm, wm, m, t, r, p, d, w)| {
             let mut form = m.form.clone();
             form.telegram = Some(t.form.clone());
             form.rest = Some(r.form.clone());
             form.pushover = Some(p.form.clone());
             form.discord = Some(d.form.clone());
+            form.web_push = Some(w.form.clone());
 
-            let modified = *mm || *tm || *rm || *pm || *dm;
+            let modified = *mm || *tm || *rm || *pm || *dm || *wm;
             on_form_change.emit(ConfigForm::Messaging(modified, form));
         });
     }
@@ -193,6 +216,13 @@ pub fn MessagingConfigView() -> Html {
             || ()
         });
 
+        let web_push_state = web_push_state.clone();
+        let web_push_cfg = msg_config.web_push.as_ref().map_or_else(WebPushMessagingConfigDto::default, |t| t.clone());
+        use_effect_with((web_push_cfg, config_view_ctx.edit_mode.clone()), move |(web_push_cfg, _mode)| {
+            web_push_state.dispatch(WebPushMessagingConfigFormAction::SetAll(web_push_cfg.clone()));
+            || ()
+        });
+
         use_effect_with((msg_config, config_view_ctx.edit_mode.clone()), move |(msg_config, _mode)| {
             msg_state.dispatch(MessagingConfigFormAction::SetAll(msg_config.clone()));
             || ()
@@ -313,6 +343,46 @@ pub fn MessagingConfigView() -> Html {
         },
     };
 
+    let render_web_push = |web_push: Option<&WebPushMessagingConfigDto>| match web_push {
+        Some(entry) => html! {
+          <Card class="tp__config-view__card">
+              <h1>{translate.t(LABEL_WEB_PUSH)}</h1>
+              { config_field!(entry, translate.t(LABEL_SUBJECT), subject) }
+          </Card>
+        },
+        None => html! {
+          <Card class="tp__config-view__card">
+              <h1>{translate.t(LABEL_WEB_PUSH)}</h1>
+              { config_field_empty!(translate.t(LABEL_SUBJECT)) }
+          </Card>
+        },
+    };
+
+    let render_webhook = |entry: &WebhookMessagingConfigDto| html! {
+      <Card class="tp__config-view__card">
+          <h1>{translate.t(LABEL_WEBHOOK)}</h1>
+          { config_field!(entry, translate.t(LABEL_NAME), name) }
+          { config_field_hide!(entry, translate.t(LABEL_URL), url) }
+          { config_field_optional!(entry, translate.t(LABEL_METHOD), method) }
+          { config_field_child!(translate.t(LABEL_HEADERS), {
+              html! {
+                  <div class="tp__config-view__tags">
+                      { for entry.headers.keys().map(|h| html! { <Chip label={h.clone()} /> }) }
+                  </div>
+              }
+          })}
+          { config_field_child!(translate.t(LABEL_NOTIFY_ON), {
+              html! {
+                  <div class="tp__config-view__tags">
+                      { for entry.notify_on.iter().map(|k| html! { <Chip label={k.to_string()} /> }) }
+                  </div>
+              }
+          })}
+          { config_field_optional!(entry, translate.t(LABEL_BODY_TEMPLATE), body) }
+          { render_templates_view(&entry.templates) }
+      </Card>
+    };
+
     let render_view_mode = || {
         let msg_state = messaging_state.clone();
         html! {
@@ -335,6 +405,8 @@ pub fn MessagingConfigView() -> Html {
           {render_rest(msg_state.form.rest.as_ref())}
           {render_pushover(msg_state.form.pushover.as_ref())}
           {render_discord(msg_state.form.discord.as_ref())}
+          {render_web_push(msg_state.form.web_push.as_ref())}
+          { for msg_state.form.webhooks.iter().map(&render_webhook) }
         </div>
         </>
         }
@@ -466,6 +538,11 @@ pub fn MessagingConfigView() -> Html {
                         })}
                     </div>
                 </Card>
+
+                <Card class="tp__config-view__card">
+                    <h1>{translate.t(LABEL_WEB_PUSH)}</h1>
+                    { edit_field_text!(web_push_state, translate.t(LABEL_SUBJECT), subject, WebPushMessagingConfigFormAction::Subject) }
+                </Card>
             </div>
             </>
         }
@@ -1,21 +1,43 @@
 #![allow(clippy::large_enum_variant)]
 
+use std::rc::Rc;
 use yew::prelude::*;
 use yew_i18n::use_translation;
-use shared::model::{CacheConfigDto, GeoIpConfigDto, RateLimitConfigDto, ResourceRetryConfigDto, ReverseProxyConfigDto, ReverseProxyDisabledHeaderConfigDto, StreamBufferConfigDto, StreamConfigDto};
+use shared::model::{CacheConfigDto, CacheEvictionPolicy, GeoIpConfigDto, HlsRemuxConfigDto, RateLimitConfigDto, ResourceRetryConfigDto, ReverseProxyConfigDto, ReverseProxyDisabledHeaderConfigDto, StreamBufferConfigDto, StreamConfigDto};
 use shared::utils::{default_secret, format_float_localized};
 use crate::app::context::ConfigContext;
 use crate::app::components::config::config_view_context::ConfigViewContext;
 use crate::app::components::config::config_page::{ConfigForm, LABEL_REVERSE_PROXY_CONFIG};
-use crate::app::components::{Card};
-use crate::{config_field, config_field_bool, config_field_custom, config_field_hide, config_field_optional,
+use crate::app::components::{Card, Chip, RadioButtonGroup};
+use crate::{config_field, config_field_bool, config_field_child, config_field_custom, config_field_hide, config_field_optional,
             edit_field_bool, edit_field_list, edit_field_number, edit_field_number_f64,
-            edit_field_number_u64, edit_field_number_usize, edit_field_text, edit_field_text_option, generate_form_reducer};
+            edit_field_number_u16, edit_field_number_u64, edit_field_number_usize, edit_field_text, edit_field_text_option, generate_form_reducer};
 
 const LABEL_CACHE: &str = "LABEL.CACHE";
 const LABEL_ENABLED: &str = "LABEL.ENABLED";
 const LABEL_SIZE: &str = "LABEL.SIZE";
 const LABEL_DIRECTORY: &str = "LABEL.DIRECTORY";
+const LABEL_PROXY_LOGOS: &str = "LABEL.PROXY_LOGOS";
+const LABEL_LOGO_WIDTH: &str = "LABEL.LOGO_WIDTH";
+const LABEL_XTREAM_API_TTL_SECS: &str = "LABEL.XTREAM_API_TTL_SECS";
+const LABEL_MAX_AGE_DAYS: &str = "LABEL.MAX_AGE_DAYS";
+const LABEL_EVICTION: &str = "LABEL.EVICTION";
+
+const EVICTION_POLICIES: [&str; 2] = ["lru", "lfu"];
+
+fn eviction_to_str(policy: CacheEvictionPolicy) -> &'static str {
+    match policy {
+        CacheEvictionPolicy::Lru => "lru",
+        CacheEvictionPolicy::Lfu => "lfu",
+    }
+}
+
+fn eviction_from_str(policy: &str) -> CacheEvictionPolicy {
+    match policy {
+        "lfu" => CacheEvictionPolicy::Lfu,
+        _ => CacheEvictionPolicy::Lru,
+    }
+}
 
 const LABEL_STREAM: &str = "LABEL.STREAM";
 const LABEL_RETRY: &str = "LABEL.RETRY";
@@ -35,6 +57,7 @@ const LABEL_SHARED_BURST_BUFFER_MB: &str = "LABEL.SHARED_BURST_BUFFER_BYTES";
 
 const LABEL_SETTINGS: &str = "LABEL.SETTINGS";
 const LABEL_RESOURCE_REWRITE_DISABLED: &str = "LABEL.RESOURCE_REWRITE_DISABLED";
+const LABEL_COMPRESSION_DISABLED: &str = "LABEL.COMPRESSION_DISABLED";
 const LABEL_REWRITE_SECRET: &str = "LABEL.REWRITE_SECRET";
 const LABEL_RESOURCE_RETRY: &str = "LABEL.RESOURCE_RETRY";
 const LABEL_MAX_ATTEMPTS: &str = "LABEL.MAX_ATTEMPTS";
@@ -48,6 +71,10 @@ const LABEL_CUSTOM_HEADERS: &str = "LABEL.CUSTOM_HEADERS";
 const LABEL_ADD_HEADER: &str = "LABEL.ADD_HEADER";
 const LABEL_GEOIP: &str = "LABEL.GEOIP";
 const LABEL_URL: &str = "LABEL.URL";
+const LABEL_HLS_REMUX: &str = "LABEL.HLS_REMUX";
+const LABEL_SEGMENT_SECS: &str = "LABEL.SEGMENT_SECS";
+const LABEL_PLAYLIST_SEGMENTS: &str = "LABEL.PLAYLIST_SEGMENTS";
+const LABEL_IDLE_TIMEOUT_SECS: &str = "LABEL.IDLE_TIMEOUT_SECS";
 
 generate_form_reducer!(
     state: CacheConfigFormState { form: CacheConfigDto },
@@ -56,6 +83,11 @@ generate_form_reducer!(
         Enabled => enabled: bool,
         Size => size: Option<String>,
         Dir => dir: Option<String>,
+        ProxyLogos => proxy_logos: bool,
+        LogoWidth => logo_width: u16,
+        XtreamApiTtlSecs => xtream_api_ttl_secs: u32,
+        MaxAgeDays => max_age_days: u16,
+        Eviction => eviction: CacheEvictionPolicy,
     }
 );
 
@@ -111,11 +143,23 @@ generate_form_reducer!(
     }
 );
 
+generate_form_reducer!(
+    state: HlsRemuxConfigFormState { form: HlsRemuxConfigDto },
+    action_name: HlsRemuxConfigFormAction,
+    fields {
+        Enabled => enabled: bool,
+        SegmentSecs => segment_secs: u16,
+        PlaylistSegments => playlist_segments: u16,
+        IdleTimeoutSecs => idle_timeout_secs: u16,
+    }
+);
+
 generate_form_reducer!(
     state: ReverseProxyConfigFormState { form: ReverseProxyConfigDto },
     action_name: ReverseProxyConfigFormAction,
     fields {
         ResourceRewriteDisabled => resource_rewrite_disabled: bool,
+        CompressionDisabled => compression_disabled: bool,
         RewriteSecret => rewrite_secret: String,
     }
 );
@@ -136,6 +180,9 @@ pub fn ReverseProxyConfigView() -> Html {
     let translate = use_translation();
     let config_ctx = use_context::<ConfigContext>().expect("ConfigContext not found");
     let config_view_ctx = use_context::<ConfigViewContext>().expect("ConfigViewContext not found");
+    let eviction_options = use_memo((), |_| {
+        EVICTION_POLICIES.iter().map(ToString::to_string).collect::<Vec<String>>()
+    });
 
     let reverse_proxy_state: UseReducerHandle<ReverseProxyConfigFormState> = use_reducer(|| {
         ReverseProxyConfigFormState { form: ReverseProxyConfigDto { rewrite_secret: default_secret(), ..Default::default() }, modified: false }
@@ -164,6 +211,10 @@ pub fn ReverseProxyConfigView() -> Html {
         StreamBufferConfigFormState { form: StreamBufferConfigDto::default(), modified: false }
     });
 
+    let hls_state: UseReducerHandle<HlsRemuxConfigFormState> = use_reducer(|| {
+        HlsRemuxConfigFormState { form: HlsRemuxConfigDto::default(), modified: false }
+    });
+
     {
         let on_form_change = config_view_ctx.on_form_change.clone();
         let reverse_proxy_state = reverse_proxy_state.clone();
@@ -174,6 +225,7 @@ pub fn ReverseProxyConfigView() -> Html {
         let stream_state = stream_state.clone();
         let geoip_state = geoip_state.clone();
         let stream_buffer_state = stream_buffer_state.clone();
+        let hls_state = hls_state.clone();
 
         use_effect_with(
             (
@@ -185,8 +237,9 @@ pub fn ReverseProxyConfigView() -> Html {
                 stream_state,
                 geoip_state,
                 stream_buffer_state,
+                hls_state,
             ),
-            move |(rp, disabled_header, cache, rl, resource_retry, stream, geoip, stream_buffer)| {
+            move |(rp, disabled_header, cache, rl, resource_retry, stream, geoip, stream_buffer, hls)| {
                 let mut form = rp.form.clone();
                 let mut stream_form = stream.form.clone();
                 stream_form.buffer = if stream_buffer.form.is_empty() {
@@ -200,6 +253,11 @@ pub fn ReverseProxyConfigView() -> Html {
                 form.resource_retry = Some(resource_retry.form.clone());
                 form.stream = Some(stream_form);
                 form.geoip = Some(geoip.form.clone());
+                form.hls = if hls.form.is_empty() {
+                    None
+                } else {
+                    Some(hls.form.clone())
+                };
                 form.disabled_header = if disabled_header.form.is_empty() {
                     None
                 } else {
@@ -213,7 +271,8 @@ pub fn ReverseProxyConfigView() -> Html {
                     || resource_retry.modified
                     || stream.modified
                     || geoip.modified
-                    || stream_buffer.modified;
+                    || stream_buffer.modified
+                    || hls.modified;
                 on_form_change.emit(ConfigForm::ReverseProxy(modified, form));
             },
         );
@@ -228,6 +287,7 @@ pub fn ReverseProxyConfigView() -> Html {
         let stream_state = stream_state.clone();
         let geoip_state = geoip_state.clone();
         let stream_buffer_state = stream_buffer_state.clone();
+        let hls_state = hls_state.clone();
 
         let reverse_proxy_cfg = config_ctx.config.as_ref().and_then(|c| c.config.reverse_proxy.clone());
         use_effect_with((reverse_proxy_cfg, config_view_ctx.edit_mode.clone()), move |(cfg, _mode)| {
@@ -240,6 +300,7 @@ pub fn ReverseProxyConfigView() -> Html {
                 stream_state.dispatch(StreamConfigFormAction::SetAll(rp.stream.as_ref().map_or_else(StreamConfigDto::default, |s| s.clone())));
                 geoip_state.dispatch(GeoIpConfigFormAction::SetAll(rp.geoip.as_ref().map_or_else(GeoIpConfigDto::default, |s| s.clone())));
                 stream_buffer_state.dispatch(StreamBufferConfigFormAction::SetAll(rp.stream.as_ref().and_then(|s| s.buffer.clone()).unwrap_or_default()));
+                hls_state.dispatch(HlsRemuxConfigFormAction::SetAll(rp.hls.clone().unwrap_or_default()));
             } else {
                 reverse_proxy_state.dispatch(ReverseProxyConfigFormAction::SetAll(ReverseProxyConfigDto::default()));
                 disabled_header_state.dispatch(ReverseProxyDisabledHeaderConfigFormAction::SetAll(ReverseProxyDisabledHeaderConfigDto::default()));
@@ -249,6 +310,7 @@ pub fn ReverseProxyConfigView() -> Html {
                 stream_state.dispatch(StreamConfigFormAction::SetAll(StreamConfigDto::default()));
                 geoip_state.dispatch(GeoIpConfigFormAction::SetAll(GeoIpConfigDto::default()));
                 stream_buffer_state.dispatch(StreamBufferConfigFormAction::SetAll(StreamBufferConfigDto::default()));
+                hls_state.dispatch(HlsRemuxConfigFormAction::SetAll(HlsRemuxConfigDto::default()));
             }
             || ()
         });
@@ -261,6 +323,13 @@ pub fn ReverseProxyConfigView() -> Html {
                 { config_field_bool!(cache_state.form, translate.t(LABEL_ENABLED), enabled) }
                 { config_field_optional!(cache_state.form, translate.t(LABEL_SIZE), size) }
                 { config_field_optional!(cache_state.form, translate.t(LABEL_DIRECTORY), dir) }
+                { config_field_bool!(cache_state.form, translate.t(LABEL_PROXY_LOGOS), proxy_logos) }
+                { config_field!(cache_state.form, translate.t(LABEL_LOGO_WIDTH), logo_width) }
+                { config_field!(cache_state.form, translate.t(LABEL_XTREAM_API_TTL_SECS), xtream_api_ttl_secs) }
+                { config_field!(cache_state.form, translate.t(LABEL_MAX_AGE_DAYS), max_age_days) }
+                { config_field_child!(translate.t(LABEL_EVICTION), {
+                    html! { <div><Chip label={eviction_to_str(cache_state.form.eviction).to_string()} /></div> }
+                })}
             </Card>
         }
     };
@@ -299,6 +368,18 @@ pub fn ReverseProxyConfigView() -> Html {
         }
     };
 
+    let render_hls = || {
+        html! {
+            <Card class="tp__config-view__card">
+                <h1>{translate.t(LABEL_HLS_REMUX)}</h1>
+                { config_field_bool!(hls_state.form, translate.t(LABEL_ENABLED), enabled) }
+                { config_field!(hls_state.form, translate.t(LABEL_SEGMENT_SECS), segment_secs) }
+                { config_field!(hls_state.form, translate.t(LABEL_PLAYLIST_SEGMENTS), playlist_segments) }
+                { config_field!(hls_state.form, translate.t(LABEL_IDLE_TIMEOUT_SECS), idle_timeout_secs) }
+            </Card>
+        }
+    };
+
     let render_geoip = || {
         html! {
             <Card class="tp__config-view__card">
@@ -314,6 +395,7 @@ pub fn ReverseProxyConfigView() -> Html {
             <Card class="tp__config-view__card">
                 <h1>{translate.t(LABEL_SETTINGS)}</h1>
                 { config_field_bool!(reverse_proxy_state.form, translate.t(LABEL_RESOURCE_REWRITE_DISABLED), resource_rewrite_disabled) }
+                { config_field_bool!(reverse_proxy_state.form, translate.t(LABEL_COMPRESSION_DISABLED), compression_disabled) }
                 { config_field_hide!(reverse_proxy_state.form, translate.t(LABEL_REWRITE_SECRET), rewrite_secret) }
             </Card>
         }
@@ -324,6 +406,7 @@ pub fn ReverseProxyConfigView() -> Html {
             <Card class="tp__config-view__card">
                 <h1>{translate.t(LABEL_SETTINGS)}</h1>
                 { edit_field_bool!(reverse_proxy_state, translate.t(LABEL_RESOURCE_REWRITE_DISABLED), resource_rewrite_disabled, ReverseProxyConfigFormAction::ResourceRewriteDisabled) }
+                { edit_field_bool!(reverse_proxy_state, translate.t(LABEL_COMPRESSION_DISABLED), compression_disabled, ReverseProxyConfigFormAction::CompressionDisabled) }
                 { edit_field_text!(reverse_proxy_state, translate.t(LABEL_REWRITE_SECRET), rewrite_secret, ReverseProxyConfigFormAction::RewriteSecret, true) }
             </Card>
         }
@@ -393,13 +476,42 @@ pub fn ReverseProxyConfigView() -> Html {
         </Card>
     };
 
-    let render_cache_edit = || html! {
+    let render_hls_edit = || html! {
+        <Card class="tp__config-view__card">
+            <h1>{translate.t(LABEL_HLS_REMUX)}</h1>
+            { edit_field_bool!(hls_state, translate.t(LABEL_ENABLED), enabled, HlsRemuxConfigFormAction::Enabled) }
+            { edit_field_number_u16!(hls_state, translate.t(LABEL_SEGMENT_SECS), segment_secs, HlsRemuxConfigFormAction::SegmentSecs) }
+            { edit_field_number_u16!(hls_state, translate.t(LABEL_PLAYLIST_SEGMENTS), playlist_segments, HlsRemuxConfigFormAction::PlaylistSegments) }
+            { edit_field_number_u16!(hls_state, translate.t(LABEL_IDLE_TIMEOUT_SECS), idle_timeout_secs, HlsRemuxConfigFormAction::IdleTimeoutSecs) }
+        </Card>
+    };
+
+    let render_cache_edit = || {
+      let eviction_state = cache_state.clone();
+      let eviction_selection = Rc::new(vec![eviction_to_str(cache_state.form.eviction).to_string()]);
+      html! {
       <Card class="tp__config-view__card">
         <h1>{translate.t(LABEL_CACHE)}</h1>
         { edit_field_bool!(cache_state, translate.t(LABEL_ENABLED), enabled, CacheConfigFormAction::Enabled) }
         { edit_field_text_option!(cache_state, translate.t(LABEL_SIZE), size, CacheConfigFormAction::Size) }
         { edit_field_text_option!(cache_state, translate.t(LABEL_DIRECTORY), dir, CacheConfigFormAction::Dir) }
+        { edit_field_bool!(cache_state, translate.t(LABEL_PROXY_LOGOS), proxy_logos, CacheConfigFormAction::ProxyLogos) }
+        { edit_field_number_u16!(cache_state, translate.t(LABEL_LOGO_WIDTH), logo_width, CacheConfigFormAction::LogoWidth) }
+        { edit_field_number!(cache_state, translate.t(LABEL_XTREAM_API_TTL_SECS), xtream_api_ttl_secs, CacheConfigFormAction::XtreamApiTtlSecs) }
+        { edit_field_number_u16!(cache_state, translate.t(LABEL_MAX_AGE_DAYS), max_age_days, CacheConfigFormAction::MaxAgeDays) }
+        { config_field_child!(translate.t(LABEL_EVICTION), {
+           html! { <RadioButtonGroup
+                multi_select={false} none_allowed={false}
+                on_select={Callback::from(move |selections: Rc<Vec<String>>| {
+                    let policy = selections.first().map_or(CacheEvictionPolicy::Lru, |p| eviction_from_str(p));
+                    eviction_state.dispatch(CacheConfigFormAction::Eviction(policy));
+                })}
+                options={eviction_options.clone()}
+                selected={eviction_selection}
+            />
+        }})}
       </Card>
+      }
     };
 
     let render_rate_limit_edit = || html! {
@@ -445,6 +557,7 @@ pub fn ReverseProxyConfigView() -> Html {
                 { render_rate_limit() }
                 { render_stream() }
                 { render_stream_buffer() }
+                { render_hls() }
             </div>
         }
     };
@@ -459,6 +572,7 @@ pub fn ReverseProxyConfigView() -> Html {
             { render_rate_limit_edit() }
             { render_stream_edit() }
             { render_stream_buffer_edit() }
+            { render_hls_edit() }
         </div>
     };
 
@@ -4,11 +4,11 @@ use crate::app::components::{Card, Chip, DropDownOption, DropDownSelection, Icon
 use crate::app::ConfigContext;
 use yew::prelude::*;
 use yew_i18n::use_translation;
-use shared::model::{ScheduleConfigDto, SchedulesConfigDto};
+use shared::model::{get_schedule_spread_offsets, ScheduleConfigDto, SchedulesConfigDto};
 use crate::app::components::config::config_page::{ConfigForm, LABEL_SCHEDULES_CONFIG};
 use crate::app::components::config::config_view_context::ConfigViewContext;
 use crate::app::components::config::HasFormData;
-use crate::{config_field_child, generate_form_reducer, html_if};
+use crate::{config_field_child, edit_field_number_option, generate_form_reducer, html_if};
 use crate::app::components::input::Input;
 use crate::app::components::select::Select;
 use crate::hooks::use_service_context;
@@ -16,12 +16,23 @@ use crate::hooks::use_service_context;
 const LABEL_SCHEDULE: &str = "LABEL.SCHEDULE";
 const LABEL_TARGETS: &str = "LABEL.TARGETS";
 const LABEL_ALL: &str = "LABEL.ALL";
+const LABEL_SCHEDULE_SPREAD_MINS: &str = "LABEL.SCHEDULE_SPREAD_MINS";
+const LABEL_START_OFFSET: &str = "LABEL.START_OFFSET";
+
+fn format_start_offset(secs: u32) -> String {
+    match (secs / 60, secs % 60) {
+        (0, secs) => format!("+{secs}s"),
+        (mins, 0) => format!("+{mins}m"),
+        (mins, secs) => format!("+{mins}m {secs}s"),
+    }
+}
 
 generate_form_reducer!(
     state: SchedulesConfigFormState { form: SchedulesConfigDto },
     action_name: SchedulesConfigFormAction,
     fields {
       Schedules => schedules: Option<Vec<ScheduleConfigDto>>,
+      ScheduleSpreadMins => schedule_spread_mins: Option<u32>,
     }
 );
 
@@ -117,6 +128,7 @@ pub fn SchedulesConfigView() -> Html {
                         let dto = ScheduleConfigDto {
                             schedule: schedule.clone(),
                             targets,
+                            timezone: None,
                         };
                         let mut new_schedules = form_state.data().schedules.as_ref().cloned().unwrap_or_default();
 
@@ -159,7 +171,11 @@ pub fn SchedulesConfigView() -> Html {
 
     let render_view_mode = |deletable: bool| {
         match form_state.data().schedules.as_ref() {
-            Some(schedules) => html! {
+            Some(schedules) => {
+              let spread_secs = form_state.data().schedule_spread_mins.unwrap_or(0).saturating_mul(60);
+              let expressions: Vec<&str> = schedules.iter().map(|entry| entry.schedule.as_str()).collect();
+              let offsets = get_schedule_spread_offsets(&expressions, spread_secs);
+              html! {
                 <Card class="tp__config-view__card">
                  <div class="tp__schedules-config-view__schedule">
                     <table class="tp__config-view__table tp__table__table ">
@@ -167,11 +183,12 @@ pub fn SchedulesConfigView() -> Html {
                             <tr>
                                 {html_if!(deletable, {<th></th>})}
                                 <th>{ translate.t(LABEL_SCHEDULE) }</th>
+                                {html_if!(spread_secs > 0, {<th>{ translate.t(LABEL_START_OFFSET) }</th>})}
                                 <th>{ translate.t(LABEL_TARGETS) }</th>
                             </tr>
                         </thead>
                         <tbody>
-                            { for schedules.iter().map(|entry| {
+                            { for schedules.iter().zip(offsets).map(|(entry, offset)| {
                                 let handle_remove_clone = handle_remove.clone();
                                 let schedule = entry.schedule.clone();
                                 html! {
@@ -184,6 +201,7 @@ pub fn SchedulesConfigView() -> Html {
                                         </td>
                                     })}
                                     <td>{ entry.schedule.clone() }</td>
+                                    {html_if!(spread_secs > 0, {<td>{ if offset > 0 { format_start_offset(offset) } else { String::from("-") } }</td>})}
                                     <td>
                                         <div class="tp__config-view__tags">
                                         {
@@ -206,6 +224,7 @@ pub fn SchedulesConfigView() -> Html {
                     </table>
                 </div>
               </Card>
+              }
             },
             None => html! { <NoContent /> }
         }
@@ -225,6 +244,7 @@ pub fn SchedulesConfigView() -> Html {
                 />
            }})}
           <IconButton name="AddSchedule" icon="ScheduleAdd" class="primary" onclick={handle_add_schedule} />
+          { edit_field_number_option!(form_state, translate.t(LABEL_SCHEDULE_SPREAD_MINS), schedule_spread_mins, SchedulesConfigFormAction::ScheduleSpreadMins) }
           </div>
       }
     };
@@ -5,7 +5,7 @@ use crate::app::components::config::config_page::{ConfigForm, LABEL_WEB_UI_CONFI
 use crate::{config_field, config_field_bool, config_field_child, config_field_hide, config_field_optional, edit_field_bool, edit_field_list_option, edit_field_number, edit_field_number_u64, edit_field_text, edit_field_text_option, generate_form_reducer, html_if};
 use yew::prelude::*;
 use yew_i18n::use_translation;
-use shared::model::{WebUiConfigDto, ContentSecurityPolicyConfigDto, WebAuthConfigDto};
+use shared::model::{WebUiConfigDto, ContentSecurityPolicyConfigDto, PublicStatusConfigDto, WebAuthConfigDto};
 
 // Labels
 const LABEL_AUTH: &str = "LABEL.AUTH";
@@ -20,6 +20,8 @@ const LABEL_USER_UI_ENABLED: &str = "LABEL.USER_UI_ENABLED";
 const LABEL_CONTENT_SECURITY_POLICY: &str = "LABEL.CONTENT_SECURITY_POLICY";
 const LABEL_CONTENT_SECURITY_POLICY_CUSTOM_ATTRIBUTES: &str = "LABEL.CUSTOM_ATTRIBUTES";
 const LABEL_PATH: &str = "LABEL.PATH";
+const LABEL_PUBLIC_STATUS: &str = "LABEL.PUBLIC_STATUS";
+const LABEL_MAINTENANCE: &str = "LABEL.MAINTENANCE";
 
 // Reducers for form states
 generate_form_reducer!(
@@ -55,6 +57,15 @@ generate_form_reducer!(
     }
 );
 
+generate_form_reducer!(
+    state: PublicStatusConfigFormState { form: PublicStatusConfigDto },
+    action_name: PublicStatusConfigFormAction,
+    fields {
+        Enabled => enabled: bool,
+        Maintenance => maintenance: Option<String>,
+    }
+);
+
 #[function_component]
 pub fn WebUiConfigView() -> Html {
     let translate = use_translation();
@@ -71,6 +82,9 @@ pub fn WebUiConfigView() -> Html {
     let csp_state: UseReducerHandle<CspConfigFormState> = use_reducer(|| {
         CspConfigFormState { form: ContentSecurityPolicyConfigDto::default(), modified: false }
     });
+    let public_status_state: UseReducerHandle<PublicStatusConfigFormState> = use_reducer(|| {
+        PublicStatusConfigFormState { form: PublicStatusConfigDto::default(), modified: false }
+    });
 
     // Notify parent when form changes
     {
@@ -78,14 +92,17 @@ pub fn WebUiConfigView() -> Html {
         let webui_state = webui_state.clone();
         let auth_state = auth_state.clone();
         let csp_state = csp_state.clone();
-        let deps = (webui_state.modified, auth_state.modified, csp_state.modified, webui_state, auth_state, csp_state);
+        let public_status_state = public_status_state.clone();
+        let deps = (webui_state.modified, auth_state.modified, csp_state.modified, public_status_state.modified,
+                    webui_state, auth_state, csp_state, public_status_state);
         use_effect_with(deps,
-                        move |(wm, am, cm, w, a, c)| {
+                        move |(wm, am, cm, pm, w, a, c, p)| {
             let mut form = w.form.clone();
             form.auth = Some(a.form.clone());
             form.content_security_policy = Some(c.form.clone());
+            form.public_status = Some(p.form.clone());
 
-            let modified = *wm || *am || *cm;
+            let modified = *wm || *am || *cm || *pm;
             on_form_change.emit(ConfigForm::WebUi(modified, form));
         });
     }
@@ -95,6 +112,7 @@ pub fn WebUiConfigView() -> Html {
         let webui_state = webui_state.clone();
         let auth_state = auth_state.clone();
         let csp_state = csp_state.clone();
+        let public_status_state = public_status_state.clone();
 
         let webui_cfg = config_ctx.config.as_ref().and_then(|c| c.config.web_ui.clone());
         use_effect_with((webui_cfg, config_view_ctx.edit_mode.clone()), move |(cfg, _mode)| {
@@ -110,11 +128,12 @@ pub fn WebUiConfigView() -> Html {
                 } else {
                     csp_state.dispatch(CspConfigFormAction::SetAll(ContentSecurityPolicyConfigDto::default()));
                 }
-
+                public_status_state.dispatch(PublicStatusConfigFormAction::SetAll(webui.public_status.clone().unwrap_or_default()));
             } else {
                 webui_state.dispatch(WebUiConfigFormAction::SetAll(WebUiConfigDto::default()));
                 auth_state.dispatch(WebUiAuthConfigFormAction::SetAll(WebAuthConfigDto::default()));
                 csp_state.dispatch(CspConfigFormAction::SetAll(ContentSecurityPolicyConfigDto::default()));
+                public_status_state.dispatch(PublicStatusConfigFormAction::SetAll(PublicStatusConfigDto::default()));
             }
             || ()
         });
@@ -157,6 +176,11 @@ pub fn WebUiConfigView() -> Html {
             { config_field!(auth_state.form, translate.t(LABEL_TOKEN_TTL_MINS), token_ttl_mins) }
             { config_field_optional!(auth_state.form, translate.t(LABEL_USERFILE), userfile) }
             </Card>
+            <Card class="tp__config-view__card">
+            <h1>{translate.t(LABEL_PUBLIC_STATUS)}</h1>
+            { config_field_bool!(public_status_state.form, translate.t(LABEL_ENABLED), enabled) }
+            { config_field_optional!(public_status_state.form, translate.t(LABEL_MAINTENANCE), maintenance) }
+            </Card>
         </>
         }
     };
@@ -185,6 +209,11 @@ pub fn WebUiConfigView() -> Html {
                     { edit_field_number!(auth_state, translate.t(LABEL_TOKEN_TTL_MINS), token_ttl_mins, WebUiAuthConfigFormAction::TokenTtlMins) }
                     { edit_field_text_option!(auth_state, translate.t(LABEL_USERFILE), userfile, WebUiAuthConfigFormAction::Userfile) }
                 </Card>
+                <Card class="tp__config-view__card">
+                    <h1>{translate.t(LABEL_PUBLIC_STATUS)}</h1>
+                    { edit_field_bool!(public_status_state, translate.t(LABEL_ENABLED), enabled, PublicStatusConfigFormAction::Enabled) }
+                    { edit_field_text_option!(public_status_state, translate.t(LABEL_MAINTENANCE), maintenance, PublicStatusConfigFormAction::Maintenance) }
+                </Card>
             </>
         }
     };
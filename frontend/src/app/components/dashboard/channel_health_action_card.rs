@@ -0,0 +1,68 @@
+use crate::app::components::{ActionCard, TextButton};
+use crate::hooks::use_service_context;
+use crate::html_if;
+use log::error;
+use shared::model::ChannelHealthReportDto;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+#[function_component]
+pub fn ChannelHealthActionCard() -> Html {
+    let services = use_service_context();
+    let translate = use_translation();
+    let report = use_state(|| None::<ChannelHealthReportDto>);
+
+    let fetch_report = {
+        let services_ctx = services.clone();
+        let report_state = report.clone();
+        Callback::from(move |()| {
+            let services_ctx = services_ctx.clone();
+            let report_state = report_state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match services_ctx.status.get_channel_health().await {
+                    Ok(result) => report_state.set(result),
+                    Err(err) => error!("{err}"),
+                }
+            });
+        })
+    };
+
+    {
+        let fetch_report = fetch_report.clone();
+        use_effect_with((), move |()| {
+            fetch_report.emit(());
+            || ()
+        });
+    }
+
+    let handle_update = {
+        let fetch_report = fetch_report.clone();
+        Callback::from(move |_| fetch_report.emit(()))
+    };
+
+    let sources = report.as_ref().map(|r| r.sources.as_slice()).unwrap_or_default();
+
+    html! {
+        <ActionCard icon="Live" classname="tp__channel-health" title={translate.t("LABEL.CHANNEL_HEALTH")}
+            subtitle={if sources.is_empty() { translate.t("LABEL.NO_CHANNEL_PROBES") } else { String::new() }}>
+          { html_if!(!sources.is_empty(), {
+            <ul class="tp__channel-health__sources">
+              { for sources.iter().map(|source| html! {
+                  <li key={source.input_name.clone()}>
+                    <span class="tp__channel-health__source">{ &source.input_name }</span>
+                    <span class={classes!("tp__channel-health__dead", if source.down > 0 { "tp__channel-health__dead--warn" } else { "" })}>
+                      { format!("{:.1}% {} ({}/{})", source.dead_percentage, translate.t("LABEL.DEAD"), source.down, source.probed) }
+                    </span>
+                    { html_if!(source.playback_errors > 0, {
+                      <span class="tp__channel-health__playback-errors">
+                        { format!("{} {}", source.playback_errors, translate.t("LABEL.PLAYBACK_ERRORS")) }
+                      </span>
+                    }) }
+                  </li>
+              }) }
+            </ul>
+          }) }
+          <TextButton name="channel_health" title={translate.t("LABEL.UPDATE")} icon="Refresh" onclick={handle_update} />
+        </ActionCard>
+    }
+}
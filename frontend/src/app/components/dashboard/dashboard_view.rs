@@ -1,7 +1,7 @@
 use yew::prelude::*;
 use yew_i18n::use_translation;
 use crate::app::components::{Card, DiscordActionCard, UserActionCard, VersionActionCard,
-                             DocumentationActionCard, IpinfoActionCard, GithubActionCard};
+                             DocumentationActionCard, IpinfoActionCard, GithubActionCard, ChannelHealthActionCard};
 use crate::app::context::StatusContext;
 
 #[function_component]
@@ -25,6 +25,7 @@ pub fn DashboardView() -> Html {
               <Card><DiscordActionCard /></Card>
               <Card><GithubActionCard /></Card>
               <Card><IpinfoActionCard /></Card>
+              <Card><ChannelHealthActionCard /></Card>
             </div>
         </div>
       </div>
@@ -6,12 +6,15 @@ mod documentation_action_card;
 mod github_action_card;
 mod status_card;
 mod ipinfo_action_card;
+mod channel_health_action_card;
+mod processing_timings_card;
 mod dashboard_view;
 mod stats_view;
 mod playlist_progress_status_card;
 
 mod streams_view;
 mod streams_table;
+mod stream_sessions_table;
 
 pub use self::action_card::*;
 pub use self::status_card::*;
@@ -20,9 +23,12 @@ pub use self::version_action_card::*;
 pub use self::discord_action_card::*;
 pub use self::documentation_action_card::*;
 pub use self::ipinfo_action_card::*;
+pub use self::channel_health_action_card::*;
+pub use self::processing_timings_card::*;
 pub use self::github_action_card::*;
 pub use self::dashboard_view::*;
 pub use self::stats_view::*;
 pub use self::playlist_progress_status_card::*;
 pub use self::streams_view::*;
 pub use self::streams_table::*;
+pub use self::stream_sessions_table::*;
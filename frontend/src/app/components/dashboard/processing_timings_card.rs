@@ -0,0 +1,93 @@
+use crate::app::components::{Card, TextButton};
+use crate::hooks::use_service_context;
+use crate::html_if;
+use log::error;
+use shared::model::{ProcessingTimings, TargetProcessingStatsDto};
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+fn format_millis(millis: u64) -> String {
+    if millis < 1_000 {
+        format!("{millis} ms")
+    } else {
+        format!("{}.{} s", millis / 1_000, (millis % 1_000) / 100)
+    }
+}
+
+#[function_component]
+pub fn ProcessingTimingsCard() -> Html {
+    let services = use_service_context();
+    let translate = use_translation();
+    let stats = use_state(Vec::<TargetProcessingStatsDto>::new);
+
+    let fetch_stats = {
+        let services_ctx = services.clone();
+        let stats_state = stats.clone();
+        Callback::from(move |()| {
+            let services_ctx = services_ctx.clone();
+            let stats_state = stats_state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match services_ctx.status.get_processing_stats().await {
+                    Ok(result) => stats_state.set(result.unwrap_or_default()),
+                    Err(err) => error!("{err}"),
+                }
+            });
+        })
+    };
+
+    {
+        let fetch_stats = fetch_stats.clone();
+        use_effect_with((), move |()| {
+            fetch_stats.emit(());
+            || ()
+        });
+    }
+
+    let handle_update = {
+        let fetch_stats = fetch_stats.clone();
+        Callback::from(move |_| fetch_stats.emit(()))
+    };
+
+    // all bars share the scale of the slowest target
+    let max_total = stats.iter().map(|entry| entry.timings.total()).max().unwrap_or(0).max(1);
+
+    html! {
+        <Card class="tp__processing-timings">
+          <div class="tp__processing-timings__header">
+            <h2>{ translate.t("LABEL.PROCESSING_TIMINGS") }</h2>
+            <TextButton name="processing_timings" title={translate.t("LABEL.UPDATE")} icon="Refresh" onclick={handle_update} />
+          </div>
+          { html_if!(stats.is_empty(), {
+            <span class="tp__processing-timings__empty">{ translate.t("LABEL.NO_PROCESSING_TIMINGS") }</span>
+          }) }
+          { for stats.iter().map(|entry| {
+              let total = entry.timings.total();
+              html! {
+                <div key={entry.target.clone()} class="tp__processing-timings__target">
+                  <span class={classes!("tp__processing-timings__name", if entry.success { "" } else { "tp__processing-timings__name--failed" })}>
+                    { &entry.target }
+                  </span>
+                  <div class="tp__processing-timings__bar" style={format!("width:{}%", (total.saturating_mul(100) / max_total).max(1))}>
+                    { for entry.timings.stages().iter().filter(|(_, millis)| *millis > 0).map(|(stage, millis)| html! {
+                        <span class={format!("tp__processing-timings__stage tp__processing-timings__stage--{stage}")}
+                              style={format!("flex-grow:{millis}")}
+                              title={format!("{stage}: {}", format_millis(*millis))}></span>
+                    }) }
+                  </div>
+                  <span class="tp__processing-timings__total">{ format_millis(total) }</span>
+                </div>
+              }
+          }) }
+          { html_if!(!stats.is_empty(), {
+            <div class="tp__processing-timings__legend">
+              { for ProcessingTimings::default().stages().iter().map(|(stage, _)| html! {
+                  <span class="tp__processing-timings__legend-item">
+                    <span class={format!("tp__processing-timings__stage tp__processing-timings__stage--{stage}")}></span>
+                    { stage.to_string() }
+                  </span>
+              }) }
+            </div>
+          }) }
+        </Card>
+    }
+}
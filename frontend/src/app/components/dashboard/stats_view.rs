@@ -1,7 +1,7 @@
 use yew::prelude::*;
 use yew_i18n::use_translation;
 use shared::utils::human_readable_byte_size;
-use crate::app::components::{Card, PlaylistProgressStatusCard, StatusCard, StatusContext};
+use crate::app::components::{Card, PlaylistProgressStatusCard, ProcessingTimingsCard, StatusCard, StatusContext};
 
 #[function_component]
 pub fn StatsView() -> Html {
@@ -45,6 +45,44 @@ pub fn StatsView() -> Html {
         }
     };
 
+    let render_provider_failovers = || -> Html {
+        match status_ctx.status.as_ref().and_then(|stats| stats.provider_failovers.as_ref()) {
+            Some(map) if !map.is_empty() => html! {
+                <div class="tp__stats__body-group">
+                    { for map.iter().map(|(input, failovers)| html! {
+                        <Card>
+                            <StatusCard
+                                title={input.to_string()}
+                                data={failovers.to_string()}
+                                footer={translate.t("LABEL.PROVIDER_FAILOVERS")}
+                            />
+                        </Card>
+                    }) }
+                </div>
+            },
+            _ => Html::default(),
+        }
+    };
+
+    let render_geoip_denied = || -> Html {
+        match status_ctx.status.as_ref().and_then(|stats| stats.geoip_denied.as_ref()) {
+            Some(map) if !map.is_empty() => html! {
+                <div class="tp__stats__body-group">
+                    { for map.iter().map(|(country, denied)| html! {
+                        <Card>
+                            <StatusCard
+                                title={country.to_string()}
+                                data={denied.to_string()}
+                                footer={translate.t("LABEL.GEOIP_DENIED")}
+                            />
+                        </Card>
+                    }) }
+                </div>
+            },
+            _ => Html::default(),
+        }
+    };
+
     let (mem, cpu) = status_ctx.system_info.as_ref().map_or_else(|| ("n/a".to_string(), "n/a".to_string()),
         |system| (format!("{} / {}", human_readable_byte_size(system.memory_usage), human_readable_byte_size(system.memory_total)), format!("{:.2}%", system.cpu_usage)));
 
@@ -66,11 +104,14 @@ pub fn StatsView() -> Html {
             <div class="tp__stats__body-group">
                 <Card><PlaylistProgressStatusCard /></Card>
             </div>
+            <ProcessingTimingsCard />
             <div class="tp__stats__body-group">
                 <Card><StatusCard title={translate.t("LABEL.ACTIVE_USERS")} data={users} /></Card>
                 <Card><StatusCard title={translate.t("LABEL.ACTIVE_USER_CONNECTIONS")} data={connections} /></Card>
                 { render_active_provider_connections() }
             </div>
+            { render_provider_failovers() }
+            { render_geoip_denied() }
         </div>
       </div>
     }
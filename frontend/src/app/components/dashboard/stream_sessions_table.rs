@@ -0,0 +1,144 @@
+use crate::app::components::date_input::DateInput;
+use crate::app::components::{NoContent, RevealContent, Table, TableDefinition, TextButton};
+use crate::app::ConfigContext;
+use crate::hooks::use_service_context;
+use crate::model::{BusyStatus, EventMessage};
+use crate::utils::t_safe;
+use shared::model::{SortOrder, StreamSessionDto};
+use shared::utils::{human_readable_byte_size, unix_ts_to_str};
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+const DAY_SECS: i64 = 86_400;
+
+const HEADERS: [&str; 10] = [
+    "USERNAME",
+    "CHANNEL",
+    "GROUP",
+    "CLIENT_IP",
+    "COUNTRY",
+    "PROVIDER",
+    "USER_AGENT",
+    "START",
+    "DURATION",
+    "SIZE",
+];
+
+fn format_duration(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let seconds = seconds % 60;
+    format!("{hours:02}:{minutes:02}:{seconds:02}")
+}
+
+/// History of the finished stream sessions, filtered by the selected days.
+#[function_component]
+pub fn StreamSessionsTable() -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let config_ctx = use_context::<ConfigContext>().expect("Config context not found");
+    let from = use_state(|| None::<i64>);
+    let to = use_state(|| None::<i64>);
+    let sessions = use_state(|| None::<Vec<Rc<StreamSessionDto>>>);
+    let reload = use_state(|| 0_u32);
+
+    {
+        let services = services.clone();
+        let sessions = sessions.clone();
+        use_effect_with((*from, *to, *reload), move |(from, to, _)| {
+            // the selected days are included completely
+            let from = from.and_then(|ts| u64::try_from(ts).ok());
+            let to = to.and_then(|ts| u64::try_from(ts + DAY_SECS - 1).ok());
+            services.event.broadcast(EventMessage::Busy(BusyStatus::Show));
+            spawn_local(async move {
+                let result = services.streams.get_stream_sessions(from, to).await;
+                sessions.set(Some(result));
+                services.event.broadcast(EventMessage::Busy(BusyStatus::Hide));
+            });
+        });
+    }
+
+    let headers = use_memo(config_ctx, |cfg| {
+        let include_country = cfg.config.as_ref().is_some_and(|app_cfg| app_cfg.config.is_geoip_enabled());
+        HEADERS.iter()
+            .filter(|h| include_country || **h != "COUNTRY")
+            .copied()
+            .collect::<Vec<&str>>()
+    });
+
+    let render_header_cell = {
+        let translate = translate.clone();
+        let headers = headers.clone();
+        Callback::<usize, Html>::from(move |col| {
+            html! { headers.get(col).map_or_else(String::new, |header| translate.t(&format!("LABEL.{header}"))) }
+        })
+    };
+
+    let render_data_cell = {
+        let translate = translate.clone();
+        let headers = headers.clone();
+        Callback::<(usize, usize, Rc<StreamSessionDto>), Html>::from(move |(_row, col, dto): (usize, usize, Rc<StreamSessionDto>)| {
+            match headers[col] {
+                "USERNAME" => html! { &dto.username },
+                "CHANNEL" => html! { &dto.title },
+                "GROUP" => html! { &dto.group },
+                "CLIENT_IP" => html! { &dto.client_ip },
+                "COUNTRY" => html! { dto.country.as_ref().map_or_else(String::new, |c| t_safe(&translate, &format!("COUNTRY.{c}")).unwrap_or_else(|| c.to_string())) },
+                "PROVIDER" => html! { &dto.provider },
+                "USER_AGENT" => html! { <RevealContent preview={Some(html! { &dto.user_agent })}>{ &dto.user_agent }</RevealContent> },
+                "START" => html! { i64::try_from(dto.start).ok().and_then(unix_ts_to_str).unwrap_or_default() },
+                "DURATION" => html! { <span class="tp__stream-table__duration">{ format_duration(dto.end.saturating_sub(dto.start)) }</span> },
+                "SIZE" => html! { human_readable_byte_size(dto.bytes) },
+                _ => html! {},
+            }
+        })
+    };
+
+    let table_definition = {
+        let num_cols = headers.len();
+        use_memo(((*sessions).clone(), (*headers).clone()), move |(sessions, _)| {
+            sessions.as_ref().filter(|list| !list.is_empty()).map(|list|
+                Rc::new(TableDefinition::<StreamSessionDto> {
+                    items: Some(Rc::new(list.clone())),
+                    num_cols,
+                    is_sortable: Callback::<usize, bool>::from(|_col| false),
+                    on_sort: Callback::<Option<(usize, SortOrder)>, ()>::from(|_args| {}),
+                    render_header_cell,
+                    render_data_cell,
+                }))
+        })
+    };
+
+    let handle_from = {
+        let from = from.clone();
+        Callback::from(move |ts: Option<i64>| from.set(ts))
+    };
+    let handle_to = {
+        let to = to.clone();
+        Callback::from(move |ts: Option<i64>| to.set(ts))
+    };
+    let handle_reload = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set((*reload).wrapping_add(1)))
+    };
+
+    html! {
+        <div class="tp__stream-sessions">
+            <div class="tp__stream-sessions__filter">
+                <DateInput name="from" label={translate.t("LABEL.FROM")} value={*from} on_change={handle_from} />
+                <DateInput name="to" label={translate.t("LABEL.TO")} value={*to} on_change={handle_to} />
+                <TextButton name="reload" icon="Refresh" title={translate.t("LABEL.REFRESH")} onclick={handle_reload} />
+            </div>
+            <div class="tp__stream-sessions__table">
+                {
+                    match table_definition.as_ref() {
+                        Some(definition) => html! { <Table::<StreamSessionDto> definition={definition.clone()} /> },
+                        None => html! { <NoContent /> },
+                    }
+                }
+            </div>
+        </div>
+    }
+}
@@ -4,11 +4,11 @@ use crate::app::components::{AppIcon, RevealContent, Table, TableDefinition, Tog
 use crate::app::ConfigContext;
 use crate::hooks::use_service_context;
 use crate::services::DialogService;
-use crate::utils::t_safe;
+use crate::utils::{html_if, t_safe};
 use gloo_timers::callback::Interval;
 use gloo_utils::window;
 use shared::error::{info_err_res, TuliproxError};
-use shared::model::{PlaylistItemType, ProtocolMessage, SortOrder, StreamChannel, StreamInfo, UserCommand};
+use shared::model::{PlaylistItemType, ProtocolMessage, SortOrder, StreamChannel, StreamInfo, UserCommand, WebUiRole};
 use shared::utils::{current_time_secs, default_kick_secs, strip_port};
 use std::fmt::Display;
 use std::rc::Rc;
@@ -79,6 +79,7 @@ pub struct StreamsTableProps {
 pub fn StreamsTable(props: &StreamsTableProps) -> Html {
     let translate = use_translation();
     let service_ctx = use_service_context();
+    let can_kick = service_ctx.auth.has_role(WebUiRole::Operator);
     let dialog = use_context::<DialogService>().expect("Dialog service not found");
     let clipboard = use_clipboard();
     let config_ctx = use_context::<ConfigContext>().expect("Config context not found");
@@ -302,7 +303,9 @@ pub fn StreamsTable(props: &StreamsTableProps) -> Html {
                   <>
                    <Table::<StreamInfo> definition={definition.clone()} />
                     <PopupMenu is_open={*popup_is_open} anchor_ref={(*popup_anchor_ref).clone()} on_close={handle_popup_close}>
+                        { html_if!(can_kick, {
                         <MenuItem icon="Disconnect" name={StreamsTableAction::Kick.to_string()} label={translate.t("LABEL.KICK")} onclick={&handle_menu_click} class="tp__delete_action"></MenuItem>
+                        })}
                         <MenuItem icon="Clipboard" name={StreamsTableAction::CopyLinkTuliproxVirtualId.to_string()} label={translate.t("LABEL.COPY_LINK_TULIPROX_VIRTUAL_ID")} onclick={&handle_menu_click}></MenuItem>
                         <MenuItem icon="Clipboard" name={StreamsTableAction::CopyLinkTuliproxWebPlayerUrl.to_string()} label={translate.t("LABEL.COPY_LINK_TULIPROX_WEBPLAYER_URL")} onclick={&handle_menu_click}></MenuItem>
                         <MenuItem icon="Clipboard" name={StreamsTableAction::CopyLinkProviderUrl.to_string()} label={translate.t("LABEL.COPY_LINK_PROVIDER_URL")} onclick={&handle_menu_click}></MenuItem>
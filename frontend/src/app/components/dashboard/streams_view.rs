@@ -1,7 +1,7 @@
 use std::rc::Rc;
 use yew::prelude::*;
 use yew_i18n::use_translation;
-use crate::app::components::{Card, StatusCard, StreamsTable};
+use crate::app::components::{Card, StatusCard, StreamSessionsTable, StreamsTable, TextButton};
 use crate::app::StatusContext;
 use crate::hooks::use_service_context;
 use crate::model::EventMessage;
@@ -12,6 +12,7 @@ pub fn StreamsView() -> Html {
     let service_ctx = use_service_context();
     let status_ctx = use_context::<StatusContext>().expect("Status context not found");
     let provider_connections = use_state(|| 0);
+    let show_history = use_state(|| false);
 
     let memo_streams = {
         let status = status_ctx.status.clone();
@@ -44,24 +45,48 @@ pub fn StreamsView() -> Html {
         })
     }
 
+    let handle_toggle_history = {
+        let show_history = show_history.clone();
+        Callback::from(move |_| show_history.set(!*show_history))
+    };
+
     html! {
       <div class="tp__streams">
         <div class="tp__streams__header">
          <h1>{ translate.t("LABEL.STREAMS")}</h1>
+         {
+            if *show_history {
+                html! { <TextButton name="active_streams" icon="Streams" title={translate.t("LABEL.ACTIVE_STREAMS")} onclick={handle_toggle_history} /> }
+            } else {
+                html! { <TextButton name="session_history" icon="Clock" title={translate.t("LABEL.SESSION_HISTORY")} onclick={handle_toggle_history} /> }
+            }
+         }
         </div>
-        <div class="tp__streams__body">
-           <div class="tp__stats__body-group">
-                <Card><StatusCard title={translate.t("LABEL.ACTIVE_USERS")} data={status_ctx.status.as_ref().map_or_else(|| "n/a".to_string(), |status| status.active_users.to_string())} /></Card>
-                <Card><StatusCard title={translate.t("LABEL.ACTIVE_USER_CONNECTIONS")} data={status_ctx.status.as_ref().map_or_else(|| "n/a".to_string(), |status| status.active_user_connections.to_string())} /></Card>
-                <Card>
-                    <StatusCard
-                        title={translate.t("LABEL.ACTIVE_PROVIDER_CONNECTIONS")}
-                        data={(*provider_connections).to_string()}
-                    />
-                 </Card>
-            </div>
-            <StreamsTable streams={ (*memo_streams).clone() } />
-        </div>
+        {
+          if *show_history {
+            html! {
+              <div class="tp__streams__body">
+                <StreamSessionsTable />
+              </div>
+            }
+          } else {
+            html! {
+              <div class="tp__streams__body">
+                 <div class="tp__stats__body-group">
+                      <Card><StatusCard title={translate.t("LABEL.ACTIVE_USERS")} data={status_ctx.status.as_ref().map_or_else(|| "n/a".to_string(), |status| status.active_users.to_string())} /></Card>
+                      <Card><StatusCard title={translate.t("LABEL.ACTIVE_USER_CONNECTIONS")} data={status_ctx.status.as_ref().map_or_else(|| "n/a".to_string(), |status| status.active_user_connections.to_string())} /></Card>
+                      <Card>
+                          <StatusCard
+                              title={translate.t("LABEL.ACTIVE_PROVIDER_CONNECTIONS")}
+                              data={(*provider_connections).to_string()}
+                          />
+                       </Card>
+                  </div>
+                  <StreamsTable streams={ (*memo_streams).clone() } />
+              </div>
+            }
+          }
+        }
       </div>
     }
 }
\ No newline at end of file
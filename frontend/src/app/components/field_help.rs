@@ -0,0 +1,65 @@
+use crate::app::components::AppIcon;
+use shared::model::ConfigHelpDto;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+const LABEL_DEFAULT_VALUE: &str = "LABEL.DEFAULT_VALUE";
+
+/// Help section for the fields of a form, fields without a context render no help.
+#[derive(Clone, PartialEq)]
+pub struct FieldHelpContext {
+    pub help: Rc<ConfigHelpDto>,
+    pub section: &'static str,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FieldHelpProviderProps {
+    pub help: Option<Rc<ConfigHelpDto>>,
+    pub section: &'static str,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[function_component]
+pub fn FieldHelpProvider(props: &FieldHelpProviderProps) -> Html {
+    match props.help.as_ref() {
+        Some(help) => {
+            let context = FieldHelpContext { help: Rc::clone(help), section: props.section };
+            html! {
+                <ContextProvider<FieldHelpContext> context={context}>
+                    { for props.children.iter() }
+                </ContextProvider<FieldHelpContext>>
+            }
+        }
+        None => html! { <>{ for props.children.iter() }</> },
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FieldHelpProps {
+    pub name: AttrValue,
+}
+
+#[function_component]
+pub fn FieldHelp(props: &FieldHelpProps) -> Html {
+    let translate = use_translation();
+    let help_ctx = use_context::<FieldHelpContext>();
+
+    let Some(option) = help_ctx.as_ref().and_then(|ctx| ctx.help.get_option(ctx.section, &props.name)) else {
+        return html! {};
+    };
+
+    let mut tooltip = format!("{} ({})", option.name, option.value_type);
+    if let Some(default) = option.default.as_ref() {
+        tooltip.push_str(&format!(" - {}: {default}", translate.t(LABEL_DEFAULT_VALUE)));
+    }
+    tooltip.push('\n');
+    tooltip.push_str(&option.description);
+
+    html! {
+        <span class="tp__field-help" title={tooltip}>
+            <AppIcon name="Help" />
+        </span>
+    }
+}
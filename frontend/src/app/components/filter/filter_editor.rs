@@ -1,11 +1,43 @@
+use std::rc::Rc;
+use gloo_timers::callback::Timeout;
 use web_sys::InputEvent;
 use crate::app::ConfigContext;
-use shared::model::PatternTemplate;
+use crate::hooks::use_service_context;
+use shared::model::{FilterTestRequest, FilterTestResultDto, PatternTemplate};
+use yew::platform::spawn_local;
 use yew::{classes, function_component, html, use_context, use_effect_with, use_state, Callback, Html, Properties, TargetCast};
 use yew_i18n::use_translation;
 use shared::foundation::{get_filter};
 use crate::app::components::{CollapsePanel, FilterView};
 
+const FILTER_TEST_DEBOUNCE_MS: u32 = 500;
+
+fn render_filter_test(result: &FilterTestResultDto, label_matched: &str) -> Html {
+    html! {
+        <>
+          <span class="tp__filter-editor__test-summary">{ format!("{label_matched}: {} / {}", result.matched, result.total) }</span>
+          <ul class="tp__filter-editor__test-items">
+            { for result.items.iter().map(|item| html! {
+                <li>
+                  <span class="tp__filter-editor__test-item-group">{ &item.group }</span>
+                  <span class="tp__filter-editor__test-item-title">{ &item.title }</span>
+                  <span class="tp__filter-editor__test-item-cluster">{ item.cluster.to_string() }</span>
+                  <ul class="tp__filter-editor__test-clauses">
+                    { for item.clauses.iter().map(|clause| html! {
+                        <li>
+                          <span class="tp__filter-editor__test-clause">{ &clause.clause }</span>
+                          { for clause.value.iter().map(|value| html! { <span class="tp__filter-editor__test-clause-value">{ value }</span> }) }
+                          { for clause.templates.iter().map(|name| html! { <span class="tp__filter-editor__test-clause-template">{ format!("!{name}!") }</span> }) }
+                        </li>
+                    }) }
+                  </ul>
+                </li>
+            }) }
+          </ul>
+        </>
+    }
+}
+
 #[derive(Properties, Clone, PartialEq, Debug)]
 pub struct FilterEditorProps {
     #[prop_or_default]
@@ -13,6 +45,9 @@ pub struct FilterEditorProps {
     #[prop_or_default]
     pub on_filter_change: Callback<Option<String>>,
     pub on_templates_change: Callback<Option<Vec<PatternTemplate>>>,
+    /// Target whose stored input playlists are used for the live preview
+    #[prop_or_default]
+    pub target: Option<String>,
 }
 
 #[function_component]
@@ -24,6 +59,8 @@ pub fn FilterEditor(props: &FilterEditorProps) -> Html {
     let filter_state = use_state(|| None);
     let parsed_filter_state = use_state(|| None);
     let valid_filter_state = use_state(|| true);
+    let filter_test_state = use_state(|| None::<Rc<FilterTestResultDto>>);
+    let services = use_service_context();
 
     {
         let templates = templates_state.clone();
@@ -65,6 +102,30 @@ pub fn FilterEditor(props: &FilterEditorProps) -> Html {
         });
     }
 
+    {
+        // The preview is requested when the user stops typing, a new input cancels the pending request.
+        let filter_test = filter_test_state.clone();
+        let services = services.clone();
+        let valid = (*parsed_filter_state).is_some();
+        use_effect_with(((*filter_state).clone(), props.target.clone(), valid), move |(flt, target, valid)| {
+            let request = match (flt.as_ref(), target.as_ref()) {
+                (Some(filter), Some(target)) if *valid => Some(FilterTestRequest {
+                    filter: filter.clone(),
+                    target: Some(target.clone()),
+                    ..FilterTestRequest::default()
+                }),
+                _ => None,
+            };
+            let timeout = request.map(|request| Timeout::new(FILTER_TEST_DEBOUNCE_MS, move || {
+                spawn_local(async move {
+                    let result = services.playlist.test_filter(request).await.ok().flatten();
+                    filter_test.set(result.map(Rc::new));
+                });
+            }));
+            move || drop(timeout)
+        });
+    }
+
     let handle_filter_input = {
       let filter = filter_state.clone();
       let on_filter_change = props.on_filter_change.clone();
@@ -113,6 +174,21 @@ pub fn FilterEditor(props: &FilterEditorProps) -> Html {
             <div class="tp__filter-editor__preview">
                 <FilterView inline={false} pretty={true} filter={(*parsed_filter_state).clone()} />
             </div>
+            { if props.target.is_some() {
+                html! {
+                  <CollapsePanel class="tp__filter-editor__test" expanded={true} title={translate.t("LABEL.FILTER_PREVIEW")}>
+                    <div class="tp__filter-editor__test-content">
+                      { match (*filter_test_state).as_ref() {
+                          Some(result) => render_filter_test(result, &translate.t("LABEL.MATCHED")),
+                          None => html! {},
+                      } }
+                    </div>
+                  </CollapsePanel>
+                }
+              } else {
+                html! {}
+              }
+            }
         </div>
     }
 }
\ No newline at end of file
@@ -17,6 +17,9 @@ pub struct FilterInputProps {
     pub filter: Option<String>,
     #[prop_or_default]
     pub on_change: Callback<Option<String>>,
+    /// Target for the live preview of the matched items in the editor
+    #[prop_or_default]
+    pub target: Option<String>,
 }
 
 #[function_component]
@@ -76,6 +79,7 @@ pub fn FilterInput(props: &FilterInputProps) -> Html {
         let on_change = props.on_change.clone();
         let handle_templates_edit = handle_templates_edit.clone();
         let dialog_actions = dialog_actions.clone();
+        let target = props.target.clone();
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
             e.stop_propagation();
@@ -88,6 +92,7 @@ pub fn FilterInput(props: &FilterInputProps) -> Html {
             let filter_state = filter_state.clone();
             let templates_state = templates_state.clone();
             let on_change = on_change.clone();
+            let target = target.clone();
             spawn_local(async move {
                 // we need this refcell because the state hook does not update
                 // when we close the dialog
@@ -101,7 +106,7 @@ pub fn FilterInput(props: &FilterInputProps) -> Html {
                     })
                 };
 
-                let filter_view = html! {<FilterEditor filter={current_filter}
+                let filter_view = html! {<FilterEditor filter={current_filter} {target}
                     on_filter_change={handle_filter_edit}
                     on_templates_change={handle_templates_edit} />};
                 let result = dlg.content(filter_view, (*actions).clone(), false).await;
@@ -1,12 +1,15 @@
-use crate::app::components::{AppIcon, DashboardView, EpgView, IconButton, InputRow, Panel, PlaylistEditorView, PlaylistExplorerView, PlaylistUpdateView, Sidebar, SourceEditor, StatsView, StreamsView, ToastrView, UserlistView, WebsocketStatus};
-use crate::app::context::{ConfigContext, PlaylistContext, StatusContext};
+use crate::app::components::{ApiDocsView, AppIcon, CommandPalette, DashboardView, EpgView, IconButton, InputRow, LanguageSwitch, LogsView, NotificationCenter, Panel, PlaylistEditorView, PlaylistExplorerView, PlaylistUpdateView, Sidebar, SourceEditor, StatsView, StreamsView, ToastrView, UserlistView, WebsocketStatus};
+use crate::app::context::{ConfigContext, LanguageContext, PlaylistContext, StatusContext};
 use crate::hooks::{use_server_status, use_service_context};
-use crate::model::{EventMessage, ViewType};
-use shared::model::{AppConfigDto, ConfigInputDto, LibraryScanSummaryStatus, PlaylistUpdateState, StatusCheck, SystemInfo};
+use crate::model::{save_language, EventMessage, ViewType};
+use log::error;
+use shared::model::{AppConfigDto, ConfigInputDto, LibraryScanSummaryStatus, PlaylistUpdateState, StatusCheck, SystemInfo, UiPreferencesDto, WebUiRole};
 use std::collections::HashMap;
 use std::future;
 use std::rc::Rc;
+use std::str::FromStr;
 use std::sync::Arc;
+use yew::platform::spawn_local;
 use yew::prelude::*;
 use yew::suspense::use_future;
 use yew_i18n::use_translation;
@@ -15,6 +18,7 @@ use crate::app::components::loading_indicator::{BusyIndicator};
 use crate::provider::DialogProvider;
 use crate::services::{ToastCloseMode, ToastOptions};
 use crate::app::components::theme::Theme;
+use crate::utils::html_if;
 
 #[function_component]
 pub fn Home() -> Html {
@@ -24,14 +28,51 @@ pub fn Home() -> Html {
     let status = use_state(|| None::<Rc<StatusCheck>>);
     let system_info = use_state(|| None::<Rc<SystemInfo>>);
     let view_visible = use_state(|| ViewType::Dashboard);
+    let is_admin = services.auth.is_admin();
+    let can_operate = services.auth.has_role(WebUiRole::Operator);
     let theme = use_state(Theme::get_current_theme);
+    let language_ctx = use_context::<LanguageContext>().expect("Language context not found");
+
+    {
+        // the local storage is only a cache, the preferences of the account win
+        let services_ctx = services.clone();
+        let set_theme = theme.clone();
+        let language_ctx = language_ctx.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                let Some(preferences) = services_ctx.preferences.get_preferences().await else {
+                    return;
+                };
+                if let Some(stored_theme) = preferences.theme.as_deref().and_then(|t| Theme::from_str(t).ok()) {
+                    if stored_theme != *set_theme {
+                        stored_theme.switch_theme();
+                        set_theme.set(stored_theme);
+                    }
+                }
+                if let Some(code) = preferences.language {
+                    if *language_ctx.language != code && language_ctx.languages.iter().any(|l| l.code == code) {
+                        save_language(&code);
+                        language_ctx.language.set(code);
+                    }
+                }
+            });
+        });
+    }
 
     let handle_theme_switch = {
+        let services_ctx = services.clone();
         let set_theme = theme.clone();
         Callback::from(move |_| {
             let new_theme = if *set_theme == Theme::Dark { Theme::Bright } else { Theme::Dark };
             new_theme.switch_theme();
             set_theme.set(new_theme);
+            let services_ctx = services_ctx.clone();
+            let update = UiPreferencesDto { theme: Some(new_theme.to_string()), ..UiPreferencesDto::default() };
+            spawn_local(async move {
+                if let Err(err) = services_ctx.preferences.update_preferences(update).await {
+                    error!("{err}");
+                }
+            });
         })
     };
 
@@ -152,8 +193,6 @@ pub fn Home() -> Html {
         Callback::from(move |view| view_vis.set(view))
     };
 
-    //<div class={"app-header__toolbar"}><select onchange={handle_language} defaultValue={i18next.language}>{services.config().getUiConfig().languages.map(l => <option key={l} value={l}>{l}</option>)}</select></div>
-
     html! {
         <ContextProvider<ConfigContext> context={config_context}>
         <ContextProvider<StatusContext> context={status_context}>
@@ -162,7 +201,8 @@ pub fn Home() -> Html {
             <ToastrView />
             <div class="tp__app">
                <BusyIndicator />
-               <Sidebar onview={handle_view_change}/>
+               <Sidebar onview={handle_view_change.clone()} active={*view_visible}/>
+               <CommandPalette onview={handle_view_change}/>
 
               <div class="tp__app-main">
                     <div class="tp__app-main__header tp__app-header">
@@ -177,6 +217,8 @@ pub fn Home() -> Html {
                         </div>
                         <div class={"tp__app-header-toolbar"}>
                             <WebsocketStatus/>
+                            <NotificationCenter/>
+                            <LanguageSwitch />
                             <IconButton name="Theme" icon={if *theme == Theme::Bright {"Moon"} else {"Sun"}} onclick={handle_theme_switch} />
                             <IconButton name="Logout" icon="Logout" onclick={handle_logout} />
                         </div>
@@ -191,27 +233,43 @@ pub fn Home() -> Html {
                        <Panel class="tp__full-width" value={ViewType::Streams.to_string()} active={view_visible.to_string()}>
                         <StreamsView/>
                        </Panel>
+                       { html_if!(can_operate, {
                        <Panel class="tp__full-width" value={ViewType::Users.to_string()} active={view_visible.to_string()}>
                           <UserlistView/>
                        </Panel>
+                       })}
                        <Panel class="tp__full-width" value={ViewType::Config.to_string()} active={view_visible.to_string()}>
                           <ConfigView/>
                        </Panel>
+                       { html_if!(is_admin, {
                        <Panel class="tp__full-width tp__full-height" value={ViewType::SourceEditor.to_string()} active={view_visible.to_string()}>
                           <SourceEditor/>
                        </Panel>
+                       })}
+                       { html_if!(can_operate, {
                        <Panel class="tp__full-width" value={ViewType::PlaylistUpdate.to_string()} active={view_visible.to_string()}>
                         <PlaylistUpdateView/>
                        </Panel>
+                       })}
+                       { html_if!(is_admin, {
                        <Panel class="tp__full-width" value={ViewType::PlaylistEditor.to_string()} active={view_visible.to_string()}>
                         <PlaylistEditorView/>
                        </Panel>
+                       })}
                        <Panel class="tp__full-width" value={ViewType::PlaylistExplorer.to_string()} active={view_visible.to_string()}>
                         <PlaylistExplorerView/>
                        </Panel>
                        <Panel class="tp__full-width" value={ViewType::PlaylistEpg.to_string()} active={view_visible.to_string()}>
                         <EpgView/>
                        </Panel>
+                       { html_if!(can_operate, {
+                       <Panel class="tp__full-width" value={ViewType::Logs.to_string()} active={view_visible.to_string()}>
+                        <LogsView/>
+                       </Panel>
+                       })}
+                       <Panel class="tp__full-width" value={ViewType::ApiDocs.to_string()} active={view_visible.to_string()}>
+                        <ApiDocsView/>
+                       </Panel>
                     </div>
               </div>
             </div>
@@ -0,0 +1,48 @@
+use std::rc::Rc;
+use yew::prelude::*;
+use crate::app::components::{DropDownIconButton, DropDownOption, DropDownSelection};
+use crate::app::LanguageContext;
+use crate::hooks::use_service_context;
+use crate::model::save_language;
+use log::error;
+use shared::model::UiPreferencesDto;
+use yew::platform::spawn_local;
+
+#[function_component]
+pub fn LanguageSwitch() -> Html {
+    let language_ctx = use_context::<LanguageContext>().expect("Language context not found");
+    let services = use_service_context();
+
+    let options = use_memo(((*language_ctx.language).clone(), Rc::clone(&language_ctx.languages)), |(language, languages)| {
+        languages.iter()
+            .map(|l| DropDownOption::new(&l.code, html! { { l.name.clone() } }, &l.code == language))
+            .collect::<Vec<_>>()
+    });
+
+    let handle_select = {
+        let set_language = language_ctx.language.clone();
+        Callback::from(move |(_name, selection): (String, DropDownSelection)| {
+            if let DropDownSelection::Single(code) = selection {
+                if *set_language != code {
+                    save_language(&code);
+                    let services = services.clone();
+                    let update = UiPreferencesDto { language: Some(code.clone()), ..UiPreferencesDto::default() };
+                    spawn_local(async move {
+                        if let Err(err) = services.preferences.update_preferences(update).await {
+                            error!("{err}");
+                        }
+                    });
+                    set_language.set(code);
+                }
+            }
+        })
+    };
+
+    if language_ctx.languages.len() < 2 {
+        return html! {};
+    }
+
+    html! {
+        <DropDownIconButton name="Language" icon="Language" options={options} on_select={handle_select} />
+    }
+}
@@ -0,0 +1,158 @@
+use crate::app::components::input::Input;
+use crate::app::components::select::Select;
+use crate::app::components::{DropDownOption, DropDownSelection, NoContent, TextButton};
+use crate::hooks::use_service_context;
+use crate::services::LogStream;
+use crate::utils::set_timeout;
+use shared::model::LogEntryDto;
+use shared::utils::unix_ts_to_str;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+const MAX_ENTRIES: usize = 1000;
+const REFRESH_MILLIS: i32 = 250;
+const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+#[function_component]
+pub fn LogsView() -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let level = use_state(|| "info".to_string());
+    let module = use_state(String::new);
+    let module_input = use_mut_ref(String::new);
+    let paused = use_state(|| false);
+    let paused_flag = use_mut_ref(|| false);
+    let entries = use_mut_ref(VecDeque::<Rc<LogEntryDto>>::new);
+    let stream = use_mut_ref(|| None::<LogStream>);
+    let list_ref = use_node_ref();
+    let force_update = use_force_update();
+
+    {
+        let services = services.clone();
+        let entries = entries.clone();
+        let stream = stream.clone();
+        let paused_flag = paused_flag.clone();
+        let force_update = force_update.clone();
+        use_effect_with(((*level).clone(), (*module).clone()), move |(level, module)| {
+            entries.borrow_mut().clear();
+            force_update.force_update();
+            // the list is refreshed at most every REFRESH_MILLIS, a busy log would otherwise render for each entry
+            let refresh_pending = Rc::new(Cell::new(false));
+            let on_entry = {
+                let entries = entries.clone();
+                Callback::from(move |entry: LogEntryDto| {
+                    {
+                        let mut entries = entries.borrow_mut();
+                        if entries.len() >= MAX_ENTRIES {
+                            entries.pop_front();
+                        }
+                        entries.push_back(Rc::new(entry));
+                    }
+                    if !*paused_flag.borrow() && !refresh_pending.replace(true) {
+                        let refresh_pending = Rc::clone(&refresh_pending);
+                        let force_update = force_update.clone();
+                        set_timeout(move || {
+                            refresh_pending.set(false);
+                            force_update.force_update();
+                        }, REFRESH_MILLIS);
+                    }
+                })
+            };
+            *stream.borrow_mut() = services.log.open_stream(level, module, on_entry);
+            move || {
+                stream.borrow_mut().take();
+            }
+        });
+    }
+
+    {
+        let list_ref = list_ref.clone();
+        let paused = *paused;
+        use_effect(move || {
+            if !paused {
+                if let Some(list) = list_ref.cast::<web_sys::Element>() {
+                    list.set_scroll_top(list.scroll_height());
+                }
+            }
+        });
+    }
+
+    let level_options = Rc::new(LEVELS.iter()
+        .map(|name| DropDownOption::new(name, html! { { name.to_uppercase() } }, *name == level.as_str()))
+        .collect::<Vec<_>>());
+
+    let handle_level = {
+        let level = level.clone();
+        Callback::from(move |(_, selection): (String, DropDownSelection)| {
+            if let DropDownSelection::Single(selected) = selection {
+                level.set(selected);
+            }
+        })
+    };
+
+    let handle_module_change = {
+        let module_input = module_input.clone();
+        Callback::from(move |value: String| *module_input.borrow_mut() = value)
+    };
+
+    let handle_apply = {
+        let module = module.clone();
+        let module_input = module_input.clone();
+        Callback::from(move |_| module.set(module_input.borrow().trim().to_string()))
+    };
+
+    let handle_pause = {
+        let paused = paused.clone();
+        let paused_flag = paused_flag.clone();
+        Callback::from(move |_| {
+            *paused_flag.borrow_mut() = !*paused;
+            paused.set(!*paused);
+        })
+    };
+
+    let handle_clear = {
+        let entries = entries.clone();
+        let force_update = force_update.clone();
+        Callback::from(move |_| {
+            entries.borrow_mut().clear();
+            force_update.force_update();
+        })
+    };
+
+    let render_entries = || {
+        let entries = entries.borrow();
+        if entries.is_empty() {
+            return html! { <NoContent/> };
+        }
+        entries.iter().map(|entry| html! {
+            <div class={format!("tp__logs-view__entry tp__logs-view__entry--{}", entry.level.to_lowercase())}>
+                <span class="tp__logs-view__time">{ unix_ts_to_str(entry.ts).unwrap_or_default() }</span>
+                <span class="tp__logs-view__level">{ &entry.level }</span>
+                <span class="tp__logs-view__target">{ &entry.target }</span>
+                <span class="tp__logs-view__message">{ &entry.message }</span>
+            </div>
+        }).collect::<Html>()
+    };
+
+    html! {
+        <div class="tp__logs-view">
+            <div class="tp__logs-view__header">
+                <h1>{ translate.t("LABEL.LOGS") }</h1>
+            </div>
+            <div class="tp__logs-view__toolbar">
+                <Select name="level" options={level_options} on_select={handle_level} />
+                <Input name="module" placeholder={translate.t("LABEL.LOG_MODULE")} on_change={handle_module_change} />
+                <TextButton name="apply" icon="Search" title={translate.t("LABEL.APPLY")} onclick={handle_apply} />
+                <TextButton name="pause" icon={if *paused { "PlayArrow" } else { "Hourglass" }}
+                    title={translate.t(if *paused { "LABEL.RESUME" } else { "LABEL.PAUSE" })} onclick={handle_pause} />
+                <TextButton name="clear" icon="Clear" title={translate.t("LABEL.CLEAR")} onclick={handle_clear} />
+            </div>
+            <div class="tp__logs-view__body" ref={list_ref}>
+                { render_entries() }
+            </div>
+        </div>
+    }
+}
@@ -52,6 +52,12 @@ mod source_editor;
 mod cluster_flags_input;
 mod title_card;
 mod filter;
+mod field_help;
+mod language_switch;
+mod command_palette;
+mod notification_center;
+mod api_docs;
+mod logs_view;
 // pub use self::input::*;
 // pub use self::menu_item::*;
 // pub use self::popup_menu::*;
@@ -99,4 +105,10 @@ pub(crate) use self::websocket_status::*;
 pub(crate) use self::source_editor::*;
 pub(crate) use self::title_card::*;
 pub(crate) use self::filter::*;
+pub(crate) use self::field_help::*;
+pub(crate) use self::language_switch::*;
+pub(crate) use self::command_palette::*;
+pub(crate) use self::notification_center::*;
 pub(crate) use self::textarea::*;
+pub(crate) use self::api_docs::*;
+pub(crate) use self::logs_view::*;
@@ -0,0 +1,165 @@
+use crate::app::components::{IconButton, NoContent};
+use crate::hooks::use_service_context;
+use crate::model::EventMessage;
+use crate::utils::html_if;
+use log::error;
+use shared::model::{NotificationDto, NotificationKind};
+use shared::utils::unix_ts_to_str;
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+enum NotificationAction {
+    Set(Vec<NotificationDto>),
+    Add(NotificationDto),
+    /// An empty id list marks all notifications as read.
+    MarkRead(Vec<u64>),
+}
+
+#[derive(Default, PartialEq)]
+struct NotificationState {
+    notifications: Vec<NotificationDto>,
+}
+
+impl NotificationState {
+    fn unread_count(&self) -> usize {
+        self.notifications.iter().filter(|n| !n.read).count()
+    }
+}
+
+impl Reducible for NotificationState {
+    type Action = NotificationAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut notifications = self.notifications.clone();
+        match action {
+            NotificationAction::Set(list) => notifications = list,
+            NotificationAction::Add(notification) => {
+                if !notifications.iter().any(|n| n.id == notification.id) {
+                    notifications.insert(0, notification);
+                }
+            }
+            NotificationAction::MarkRead(ids) => {
+                for notification in &mut notifications {
+                    if ids.is_empty() || ids.contains(&notification.id) {
+                        notification.read = true;
+                    }
+                }
+            }
+        }
+        Rc::new(Self { notifications })
+    }
+}
+
+fn kind_class(kind: NotificationKind) -> &'static str {
+    match kind {
+        NotificationKind::Info => "info",
+        NotificationKind::Warning => "warning",
+        NotificationKind::Error => "error",
+        NotificationKind::Security => "security",
+    }
+}
+
+/// Bell of the header with the drawer of the server notifications.
+#[function_component]
+pub fn NotificationCenter() -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let state = use_reducer(NotificationState::default);
+    let is_open = use_state(|| false);
+
+    {
+        let services = services.clone();
+        let state = state.clone();
+        use_effect_with((), move |_| {
+            let loader = services.clone();
+            let load_state = state.clone();
+            spawn_local(async move {
+                load_state.dispatch(NotificationAction::Set(loader.notification.get_notifications().await));
+            });
+            let subid = services.event.subscribe(move |msg| {
+                if let EventMessage::Notification(notification) = msg {
+                    state.dispatch(NotificationAction::Add(notification));
+                }
+            });
+            move || services.event.unsubscribe(subid)
+        });
+    }
+
+    let mark_read = {
+        let services = services.clone();
+        let state = state.clone();
+        Callback::from(move |ids: Vec<u64>| {
+            let services = services.clone();
+            let state = state.clone();
+            spawn_local(async move {
+                match services.notification.mark_read(ids.clone()).await {
+                    Ok(()) => state.dispatch(NotificationAction::MarkRead(ids)),
+                    Err(err) => error!("{err}"),
+                }
+            });
+        })
+    };
+
+    let handle_toggle = {
+        let set_open = is_open.clone();
+        Callback::from(move |_| set_open.set(!*set_open))
+    };
+
+    let handle_close = {
+        let set_open = is_open.clone();
+        Callback::from(move |_: MouseEvent| set_open.set(false))
+    };
+
+    let handle_close_button = {
+        let set_open = is_open.clone();
+        Callback::from(move |(_, _): (String, MouseEvent)| set_open.set(false))
+    };
+
+    let handle_mark_all = {
+        let mark_read = mark_read.clone();
+        Callback::from(move |_| mark_read.emit(Vec::new()))
+    };
+
+    let unread = state.unread_count();
+
+    html! {
+        <div class="tp__notification-center">
+            <IconButton name="Notifications" icon="Bell" hint={translate.t("LABEL.NOTIFICATIONS")} onclick={handle_toggle} />
+            { html_if!(unread > 0, {
+                <span class="tp__notification-center__badge">{ if unread > 99 { "99+".to_string() } else { unread.to_string() } }</span>
+            })}
+            { html_if!(*is_open, {
+                <div class="tp__notification-center__backdrop" onclick={handle_close}>
+                    <div class="tp__notification-center__drawer" onclick={Callback::from(|event: MouseEvent| event.stop_propagation())}>
+                        <div class="tp__notification-center__header">
+                            <h2>{ translate.t("LABEL.NOTIFICATIONS") }</h2>
+                            <IconButton name="MarkAllRead" icon="DoneAll" hint={translate.t("LABEL.MARK_ALL_READ")} onclick={handle_mark_all} />
+                            <IconButton name="Close" icon="Clear" onclick={handle_close_button} />
+                        </div>
+                        { html_if!(state.notifications.is_empty(), { <NoContent /> }) }
+                        <ul class="tp__notification-center__list">
+                            { for state.notifications.iter().map(|notification| {
+                                let onclick = if notification.read {
+                                    Callback::noop()
+                                } else {
+                                    let mark_read = mark_read.clone();
+                                    let id = notification.id;
+                                    Callback::from(move |_: MouseEvent| mark_read.emit(vec![id]))
+                                };
+                                html! {
+                                    <li class={classes!("tp__notification-center__item", kind_class(notification.kind), if notification.read { "" } else { "unread" })} {onclick}>
+                                        <span class="tp__notification-center__kind">{ translate.t(&format!("LABEL.NOTIFICATION_KIND.{}", kind_class(notification.kind).to_uppercase())) }</span>
+                                        <span class="tp__notification-center__time">{ unix_ts_to_str(notification.ts).unwrap_or_default() }</span>
+                                        <span class="tp__notification-center__message">{ &notification.message }</span>
+                                    </li>
+                                }
+                            }) }
+                        </ul>
+                    </div>
+                </div>
+            })}
+        </div>
+    }
+}
@@ -1,6 +1,6 @@
 use crate::app::components::{Card, Chip, NoContent};
 use crate::{config_field, config_field_bool, config_field_child, config_field_optional};
-use shared::model::{EpgConfigDto, EpgSmartMatchConfigDto, EpgSourceDto};
+use shared::model::{EpgConfigDto, EpgSmartMatchConfigDto, EpgSourceDto, EpgTvgIdNormalizationConfigDto};
 use yew::prelude::*;
 use yew_i18n::use_translation;
 
@@ -109,9 +109,41 @@ pub fn EpgConfigView(props: &EpgConfigViewProps) -> Html {
         }
     };
 
+    let render_tvg_id_normalization = |tvg_id_normalization: Option<&EpgTvgIdNormalizationConfigDto>| {
+        if let Some(entry) = tvg_id_normalization {
+            html! {
+                <Card class="tp__config-view__card">
+                  <h1>{translate.t("LABEL.TVG_ID_NORMALIZATION")}</h1>
+                  { config_field_bool!(entry, translate.t("LABEL.ENABLED"), enabled) }
+                  { config_field_optional!(entry, translate.t("LABEL.COUNTRY"), country) }
+                  { config_field_optional!(entry, translate.t("LABEL.TVG_ID_REGISTRY"), registry) }
+                  { config_field_child!(translate.t("LABEL.MAPPING"), {
+                        html! {
+                            <div class="tp__config-view__tags">
+                                {
+                                    for entry.mapping.iter().map(|(id, canonical)| html! {
+                                        <Chip label={format!("{id} → {canonical}")} />
+                                    })
+                                }
+                            </div>
+                        }
+                    })}
+                </Card>
+            }
+        } else {
+            html! {
+                <Card class="tp__config-view__card">
+                    <h1>{translate.t("LABEL.TVG_ID_NORMALIZATION")}</h1>
+                    <NoContent />
+                </Card>
+            }
+        }
+    };
+
     let render_empty = || html! {
         <div class="tp__epg-config-view__body tp__config-view-page__body">
              {render_smart_match(None)}
+             {render_tvg_id_normalization(None)}
              {render_sources(None)}
         </div>
     };
@@ -123,6 +155,7 @@ pub fn EpgConfigView(props: &EpgConfigViewProps) -> Html {
                html! {
                 <div class="tp__epg-config-view__body tp__config-view-page__body">
                   {render_smart_match(epg.smart_match.as_ref())}
+                  {render_tvg_id_normalization(epg.tvg_id_normalization.as_ref())}
                   {render_sources(epg.sources.as_ref())}
                 </div>
                }
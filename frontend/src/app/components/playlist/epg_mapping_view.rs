@@ -0,0 +1,228 @@
+use crate::app::components::{Card, IconButton, NoContent};
+use crate::hooks::use_service_context;
+use crate::model::{BusyStatus, EventMessage};
+use crate::utils::set_timeout;
+use shared::model::{EpgMappingCandidateDto, EpgMappingDecisionDto, EpgMappingSuggestionDto};
+use std::rc::Rc;
+use web_sys::HtmlInputElement;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+const SEARCH_DELAY_MILLIS: i32 = 300;
+const SEARCH_MIN_LENGTH: usize = 2;
+
+enum EpgMappingAction {
+    Set(Vec<EpgMappingSuggestionDto>),
+    Decided(EpgMappingDecisionDto),
+}
+
+#[derive(Default, PartialEq)]
+struct EpgMappingState {
+    suggestions: Option<Vec<EpgMappingSuggestionDto>>,
+}
+
+impl Reducible for EpgMappingState {
+    type Action = EpgMappingAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            EpgMappingAction::Set(list) => Rc::new(Self { suggestions: Some(list) }),
+            EpgMappingAction::Decided(decision) => {
+                // an accepted channel is done, a rejected candidate is removed from the channel
+                let suggestions = self.suggestions.as_ref().map(|list| list.iter()
+                    .filter(|suggestion| !(decision.accept && suggestion.name == decision.name))
+                    .cloned()
+                    .map(|mut suggestion| {
+                        if suggestion.name == decision.name {
+                            suggestion.candidates.retain(|candidate| candidate.epg_channel_id != decision.epg_channel_id);
+                        }
+                        suggestion
+                    })
+                    .collect());
+                Rc::new(Self { suggestions })
+            }
+        }
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+struct EpgMappingSearchProps {
+    target_id: u16,
+    name: String,
+    on_decision: Callback<EpgMappingDecisionDto>,
+}
+
+/// Search as you type over the epg channels of all epg sources of the target, a result is assigned with one click.
+#[function_component]
+fn EpgMappingSearch(props: &EpgMappingSearchProps) -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let results = use_state(Vec::<EpgMappingCandidateDto>::new);
+    // only the response of the latest input is shown
+    let generation = use_mut_ref(|| 0_u32);
+
+    let handle_input = {
+        let results = results.clone();
+        let generation = generation.clone();
+        let target_id = props.target_id;
+        Callback::from(move |event: InputEvent| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            let query = input.value().trim().to_string();
+            let current = {
+                let mut value = generation.borrow_mut();
+                *value = value.wrapping_add(1);
+                *value
+            };
+            if query.chars().count() < SEARCH_MIN_LENGTH {
+                results.set(vec![]);
+                return;
+            }
+            let services = services.clone();
+            let results = results.clone();
+            let generation = generation.clone();
+            set_timeout(move || {
+                if *generation.borrow() != current {
+                    return;
+                }
+                spawn_local(async move {
+                    let found = services.playlist.search_epg_mapping_channels(target_id, &query).await;
+                    if *generation.borrow() == current {
+                        results.set(found);
+                    }
+                });
+            }, SEARCH_DELAY_MILLIS);
+        })
+    };
+
+    html! {
+        <div class="tp__epg-mapping-view__search">
+            <input class="tp__epg-mapping-view__search-input" type="text" autocomplete="off"
+                placeholder={translate.t("MESSAGES.EPG_MAPPING.SEARCH_PLACEHOLDER")} oninput={handle_input} />
+            <ul class="tp__epg-mapping-view__candidates">
+                { for results.iter().map(|candidate| {
+                    let on_decision = props.on_decision.clone();
+                    let decision = EpgMappingDecisionDto {
+                        name: props.name.clone(),
+                        epg_channel_id: candidate.epg_channel_id.clone(),
+                        accept: true,
+                    };
+                    html! {
+                        <li class="tp__epg-mapping-view__candidate">
+                            <span class="tp__epg-mapping-view__score">{ format!("{}%", candidate.score) }</span>
+                            <span>{ candidate.title.as_deref().unwrap_or_default() }</span>
+                            <span class="tp__epg-mapping-view__id">{ &candidate.epg_channel_id }</span>
+                            <IconButton name="assign" icon="Accept" hint={translate.t("LABEL.ASSIGN")}
+                                onclick={Callback::from(move |_| on_decision.emit(decision.clone()))} />
+                        </li>
+                    }
+                }) }
+            </ul>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct EpgMappingViewProps {
+    pub target_id: u16,
+}
+
+/// Suggested epg channels for the channels of a target without epg, each one can be accepted or rejected
+/// or any epg channel of the target inputs can be searched and assigned.
+#[function_component]
+pub fn EpgMappingView(props: &EpgMappingViewProps) -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let state = use_reducer(EpgMappingState::default);
+
+    {
+        let services = services.clone();
+        let state = state.clone();
+        use_effect_with(props.target_id, move |target_id| {
+            let target_id = *target_id;
+            services.event.broadcast(EventMessage::Busy(BusyStatus::Show));
+            spawn_local(async move {
+                let result = services.playlist.get_epg_mapping_suggestions(target_id).await;
+                state.dispatch(EpgMappingAction::Set(result));
+                services.event.broadcast(EventMessage::Busy(BusyStatus::Hide));
+            });
+        });
+    }
+
+    let handle_decision = {
+        let services = services.clone();
+        let translate = translate.clone();
+        let state = state.clone();
+        let target_id = props.target_id;
+        Callback::from(move |decision: EpgMappingDecisionDto| {
+            let services = services.clone();
+            let translate = translate.clone();
+            let state = state.clone();
+            spawn_local(async move {
+                match services.playlist.update_epg_mappings(target_id, vec![decision.clone()]).await {
+                    Ok(()) => {
+                        if decision.accept {
+                            services.toastr.success(translate.t("MESSAGES.EPG_MAPPING.ACCEPTED"));
+                        }
+                        state.dispatch(EpgMappingAction::Decided(decision));
+                    }
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    let render_suggestion = |suggestion: &EpgMappingSuggestionDto| {
+        html! {
+            <li class="tp__epg-mapping-view__item">
+                <div class="tp__epg-mapping-view__channel">
+                    <span class="tp__epg-mapping-view__name">{ &suggestion.name }</span>
+                    <span class="tp__epg-mapping-view__group">{ &suggestion.group }</span>
+                    { if let Some(epg_channel_id) = suggestion.epg_channel_id.as_ref() {
+                        html! { <span class="tp__epg-mapping-view__current">{ epg_channel_id }</span> }
+                    } else { html! {} } }
+                </div>
+                <ul class="tp__epg-mapping-view__candidates">
+                    { for suggestion.candidates.iter().map(|candidate| {
+                        let decide = |accept: bool| {
+                            let handle_decision = handle_decision.clone();
+                            let decision = EpgMappingDecisionDto {
+                                name: suggestion.name.clone(),
+                                epg_channel_id: candidate.epg_channel_id.clone(),
+                                accept,
+                            };
+                            Callback::from(move |_| handle_decision.emit(decision.clone()))
+                        };
+                        html! {
+                            <li class="tp__epg-mapping-view__candidate">
+                                <span class="tp__epg-mapping-view__score">{ format!("{}%", candidate.score) }</span>
+                                <span>{ candidate.title.as_deref().unwrap_or_default() }</span>
+                                <span class="tp__epg-mapping-view__id">{ &candidate.epg_channel_id }</span>
+                                <IconButton name="accept" icon="Accept" hint={translate.t("LABEL.ACCEPT")} onclick={decide(true)} />
+                                <IconButton name="reject" icon="Cancel" hint={translate.t("LABEL.REJECT")} onclick={decide(false)} />
+                            </li>
+                        }
+                    }) }
+                </ul>
+                <EpgMappingSearch target_id={props.target_id} name={suggestion.name.clone()} on_decision={handle_decision.clone()} />
+            </li>
+        }
+    };
+
+    html! {
+        <Card class="tp__epg-mapping-view">
+            <h2>{ translate.t("LABEL.EPG_MAPPING") }</h2>
+            <span class="tp__epg-mapping-view__hint">{ translate.t("MESSAGES.EPG_MAPPING.HINT") }</span>
+            {
+                match state.suggestions.as_ref() {
+                    Some(list) if !list.is_empty() => html! {
+                        <ul class="tp__epg-mapping-view__list">
+                            { for list.iter().map(render_suggestion) }
+                        </ul>
+                    },
+                    _ => html! { <NoContent /> },
+                }
+            }
+        </Card>
+    }
+}
@@ -1,4 +1,4 @@
-use crate::app::components::{Breadcrumbs, EpgSourceSelector, NoContent};
+use crate::app::components::{Breadcrumbs, EpgMappingView, EpgSourceSelector, NoContent, TextButton};
 use crate::hooks::use_service_context;
 use crate::model::{BusyStatus, EventMessage};
 use crate::utils::set_timeout;
@@ -32,6 +32,8 @@ pub fn EpgView() -> Html {
     let services = use_service_context();
     let translate = use_translation();
     let epg = use_state::<Option<EpgTv>, _>(|| None);
+    let mapping_target = use_state::<Option<u16>, _>(|| None);
+    let show_mapping = use_state(|| false);
     let breadcrumbs = use_state(|| Rc::new(vec![translate.t("LABEL.PLAYLISTS"), translate.t("LABEL.PLAYLIST_EPG")]));
     let container_ref = use_node_ref();
     let now_line_ref = use_node_ref();
@@ -42,8 +44,16 @@ pub fn EpgView() -> Html {
     let handle_select_source = {
         let service_ctx = services.clone();
         let epg_set = epg.clone();
+        let mapping_target = mapping_target.clone();
+        let show_mapping = show_mapping.clone();
         Callback::from(move |req: PlaylistEpgRequest| {
             epg_set.set(None);
+            // epg mappings are stored per target
+            mapping_target.set(match &req {
+                PlaylistEpgRequest::Target(target_id) => Some(*target_id),
+                _ => None,
+            });
+            show_mapping.set(false);
             let service_ctx = service_ctx.clone();
             let epg_set = epg_set.clone();
             service_ctx.event.broadcast(EventMessage::Busy(BusyStatus::Show));
@@ -154,13 +164,25 @@ pub fn EpgView() -> Html {
         });
     }
 
+    let handle_toggle_mapping = {
+        let show_mapping = show_mapping.clone();
+        Callback::from(move |_| show_mapping.set(!*show_mapping))
+    };
+
     html! {
         <div class="tp__epg tp__list-view">
             <Breadcrumbs items={&*breadcrumbs}/>
             <div class="tp__epg__header">
                 <h1>{translate.t("LABEL.PLAYLIST_EPG")}</h1>
+                { if mapping_target.is_some() {
+                    html! { <TextButton name="epg_mapping" icon="Epg" title={translate.t("LABEL.EPG_MAPPING")} onclick={handle_toggle_mapping} /> }
+                } else { html! {} } }
             </div>
             <EpgSourceSelector on_select={handle_select_source} />
+            { match (*mapping_target, *show_mapping) {
+                (Some(target_id), true) => html! { <EpgMappingView {target_id} /> },
+                _ => html! {},
+            } }
             <div class="tp__epg__body" ref={container_ref}>
                 {
                     if epg.is_none() {
@@ -1,20 +1,38 @@
+use shared::model::{HeaderPolicyDto, HeaderRulesDto};
 use std::collections::HashMap;
 use yew::prelude::*;
 
 #[derive(Properties, Clone, PartialEq, Debug)]
 pub struct InputHeadersProps {
     pub headers: HashMap<String, String>,
+    #[prop_or_default]
+    pub policy: Option<HeaderPolicyDto>,
+}
+
+fn render_rules(direction: &str, rules: Option<&HeaderRulesDto>) -> Html {
+    let Some(rules) = rules else {
+        return html! {};
+    };
+    html! {
+        <>
+            { rules.override_headers.iter().map(|(key, value)| html! { <li>{ format!("{direction} override {key}: {value}") }</li> }).collect::<Html>() }
+            { rules.add.iter().map(|(key, value)| html! { <li>{ format!("{direction} add {key}: {value}") }</li> }).collect::<Html>() }
+            { rules.remove.iter().map(|key| html! { <li>{ format!("{direction} remove {key}") }</li> }).collect::<Html>() }
+        </>
+    }
 }
 
 #[function_component]
 pub fn InputHeaders(props: &InputHeadersProps) -> Html {
-    if props.headers.is_empty() {
+    if props.headers.is_empty() && props.policy.as_ref().is_none_or(HeaderPolicyDto::is_empty) {
         html! {}
     } else {
        html! {
             <div class="tp__input-headers">
                 <ul>
                     { props.headers.iter().map(|(key, value)| html! { <li>{ key } {":"} {value}</li> }).collect::<Html>() }
+                    { render_rules("upstream", props.policy.as_ref().and_then(|policy| policy.upstream.as_ref())) }
+                    { render_rules("client", props.policy.as_ref().and_then(|policy| policy.client.as_ref())) }
                 </ul>
             </div>
         }
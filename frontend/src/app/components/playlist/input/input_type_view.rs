@@ -19,6 +19,7 @@ pub fn InputTypeView(props: &InputTypeViewProps) -> Html {
         InputType::M3uBatch => "LABEL.M3U_BATCH",
         InputType::XtreamBatch => "LABEL.XTREAM_BATCH",
         InputType::Library => "LABEL.LIBRARY",
+        InputType::Udp => "LABEL.UDP",
     };
 
     html! {
@@ -21,6 +21,7 @@ pub fn StagedInputView(props: &StagedInputViewProps) -> Html {
                 InputType::M3uBatch => "LABEL.M3U_BATCH",
                 InputType::XtreamBatch => "LABEL.XTREAM_BATCH",
                 InputType::Library => "LABEL.LIBRARY",
+                InputType::Udp => "LABEL.UDP",
             };
             html! {
                 <div class="tp__staged-input-view">
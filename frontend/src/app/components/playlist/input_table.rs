@@ -138,7 +138,7 @@ pub fn InputTable(props: &InputTableProps) -> Html {
                                       <EpgConfigView epg={ dto.epg.clone() } />
                                    </RevealContent> }),
                             13 => html! { <RevealContent preview={ html!{ dto.headers.iter().next().map_or_else(String::new, |(key, value)| format!("{key}: {value}")) } }>
-                                        <InputHeaders headers={dto.headers.clone()} />
+                                        <InputHeaders headers={dto.headers.clone()} policy={dto.header_policy.clone()} />
                                     </RevealContent> },
                             14 => html_if!(dto.staged.is_some(),
                                  { <RevealContent preview={ html!{ dto.staged.as_ref().map_or_else(String::new, |s| s.url.clone())} }>
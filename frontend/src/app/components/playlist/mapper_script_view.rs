@@ -1,7 +1,13 @@
 use std::ops::Deref;
+use std::rc::Rc;
 use regex::Regex;
 use shared::foundation::{AssignmentTarget, BuiltInFunction, ExprId, Expression, ForEachExpr, ForEachKey, MapCase, MapCaseKey, MapKey, MapperScript, MatchCase, MatchCaseKey, RegexSource, Statement};
+use shared::model::{MapperItemTraceDto, MapperStepTraceDto, MapperTraceDto, MapperTraceRequest};
+use yew::platform::spawn_local;
 use yew::prelude::*;
+use yew_i18n::use_translation;
+use crate::app::components::{CollapsePanel, TextButton};
+use crate::hooks::use_service_context;
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct MapperScriptViewProps {
@@ -10,18 +16,54 @@ pub struct MapperScriptViewProps {
     #[prop_or(false)]
     pub inline: bool,
     pub script: Option<MapperScript>,
+    /// Target, mapping id and mapper index, the script can be traced against the stored playlist when set
+    #[prop_or_default]
+    pub trace: Option<(String, String, usize)>,
 }
 
 #[function_component]
 pub fn MapperScriptView(props: &MapperScriptViewProps) -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let trace_state = use_state(|| None::<Rc<MapperTraceDto>>);
+
+    let handle_trace = {
+        let services = services.clone();
+        let trace_state = trace_state.clone();
+        let trace = props.trace.clone();
+        Callback::from(move |_name: String| {
+            if let Some((target, mapping, mapper)) = trace.clone() {
+                let services = services.clone();
+                let trace_state = trace_state.clone();
+                spawn_local(async move {
+                    let request = MapperTraceRequest { target, mapping: Some(mapping), sample: None };
+                    match services.playlist.trace_mapper(request).await {
+                        Ok(Some(result)) => trace_state.set(result.mappers.into_iter().find(|m| m.mapper == mapper).map(Rc::new)),
+                        Ok(None) => trace_state.set(None),
+                        Err(err) => services.toastr.error(err.to_string()),
+                    }
+                });
+            }
+        })
+    };
+
     html! {
         <div class={classes!("tp__mapper-script", if props.inline {"tp__mapper-script__inline"} else {""} )}>
             {
                 match props.script.as_ref() {
                     Some(script) => html! {
+                        <>
                         <pre class="tp__mapper-script__code">
                             { render_script(script, props.pretty, 0/*, false, 1*/) }
                         </pre>
+                        if props.trace.is_some() {
+                            <TextButton class="secondary" name="trace" icon="Regexp" title={translate.t("LABEL.TRACE")} onclick={handle_trace} />
+                        }
+                        { match (*trace_state).as_ref() {
+                            Some(trace) => render_trace(trace, script, &translate.t("LABEL.MATCHED")),
+                            None => html! {},
+                        } }
+                        </>
                     },
                     None => html! { },
                 }
@@ -30,6 +72,63 @@ pub fn MapperScriptView(props: &MapperScriptViewProps) -> Html {
     }
 }
 
+fn render_trace_step(step: &MapperStepTraceDto, script: &MapperScript) -> Html {
+    let statement = match script.statements.get(step.statement) {
+        Some(Statement::Expression(expr_id)) => render_expression(expr_id, script, &mut FormatParams { pretty: false, level: 0 }),
+        _ => html! {},
+    };
+    html! {
+        <li class="tp__mapper-script__trace-step">
+            <pre class="tp__mapper-script__code">{ statement }</pre>
+            { for step.patterns.iter().map(|pattern| html! {
+                <div class={classes!("tp__mapper-script__trace-pattern", if pattern.captures.is_empty() {"tp__mapper-script__trace-pattern--nomatch"} else {""})}>
+                    <span class="regex">{ &pattern.pattern }</span>
+                    <span class="tp__mapper-script__trace-value">{ pattern.value.as_deref().unwrap_or("null") }</span>
+                    { for pattern.captures.iter().map(|(name, value)| html! {
+                        <span class="tp__mapper-script__trace-capture">{ format!("{name}: {value}") }</span>
+                    }) }
+                </div>
+            }) }
+            { for step.changes.iter().map(|change| html! {
+                <div class="tp__mapper-script__trace-change">
+                    { render_field(&change.field) }
+                    <span class="tp__mapper-script__trace-value">{ change.before.as_deref().unwrap_or("null") }</span>
+                    {" => "}
+                    <span class="tp__mapper-script__trace-value">{ change.after.as_deref().unwrap_or("null") }</span>
+                </div>
+            }) }
+            { for step.error.iter().map(|err| html! { <div class="tp__mapper-script__trace-error">{ err }</div> }) }
+        </li>
+    }
+}
+
+fn render_trace_item(item: &MapperItemTraceDto, script: &MapperScript) -> Html {
+    let title = item.fields.iter().find(|(field, _)| field == "title")
+        .or_else(|| item.fields.iter().find(|(field, _)| field == "name"))
+        .map(|(_, value)| value.as_str()).unwrap_or_default();
+    html! {
+        <CollapsePanel class="tp__mapper-script__trace-item" expanded={false} title={format!("{} - {title} ({})", item.input, item.cluster)}>
+            <ul class="tp__mapper-script__trace-fields">
+                { for item.fields.iter().map(|(field, value)| html! {
+                    <li>{ render_field(field) }{" "}<span class="tp__mapper-script__trace-value">{ value }</span></li>
+                }) }
+            </ul>
+            <ol class="tp__mapper-script__trace-steps">
+                { for item.steps.iter().map(|step| render_trace_step(step, script)) }
+            </ol>
+        </CollapsePanel>
+    }
+}
+
+fn render_trace(trace: &MapperTraceDto, script: &MapperScript, label_matched: &str) -> Html {
+    html! {
+        <div class="tp__mapper-script__trace">
+            <span class="tp__mapper-script__trace-summary">{ format!("{label_matched}: {}", trace.matched) }</span>
+            { for trace.items.iter().map(|item| render_trace_item(item, script)) }
+        </div>
+    }
+}
+
 struct FormatParams {
     pub pretty: bool,
     pub level: usize,
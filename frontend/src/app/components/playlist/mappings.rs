@@ -6,6 +6,9 @@ use crate::app::components::{Accordion, AccordionPanel, ConfigContext, FilterVie
 #[derive(Properties, PartialEq, Clone)]
 pub struct PlaylistMappingsProps {
     pub mappings: Option<Vec<String>>,
+    /// The scripts can be traced against the stored playlist of the target
+    #[prop_or_default]
+    pub target: Option<String>,
 }
 
 #[function_component]
@@ -31,11 +34,12 @@ pub fn PlaylistMappings(props: &PlaylistMappingsProps) -> Html {
         })
     };
 
-    let render_mapper = |mapper: &MapperDto| {
+    let render_mapper = |mapping: &MappingDto, idx: usize, mapper: &MapperDto| {
+        let trace = props.target.as_ref().map(|target| (target.clone(), mapping.id.clone(), idx));
         html! {
             <div class="tp__playlist-mappings__mapping-mapper-content">
                 <FilterView filter={mapper.t_filter.clone()} />
-                <MapperScriptView script={mapper.t_script.clone()} pretty={true}/>
+                <MapperScriptView script={mapper.t_script.clone()} pretty={true} {trace}/>
             </div>
         }
     };
@@ -66,7 +70,7 @@ pub fn PlaylistMappings(props: &PlaylistMappingsProps) -> Html {
                         for mapping.mapper.iter().flatten().enumerate().map(|(idx, mapper)| {
                            html! {
                               <AccordionPanel id={format!("script-{}", idx+1)} title={format!("{}-{}", translate.t("LABEL.SCRIPT"), idx+1)} >
-                                  { render_mapper(mapper) }
+                                  { render_mapper(mapping, idx, mapper) }
                               </AccordionPanel>
                             }
                         })
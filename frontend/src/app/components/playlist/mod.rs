@@ -19,7 +19,9 @@ mod mapper_script_view;
 mod mapper_counter_view;
 mod epg_config_view;
 mod epg_view;
+mod epg_mapping_view;
 mod epg_source_selector;
+mod playlist_compare_view;
 
 use std::rc::Rc;
 use yew_i18n::YewI18n;
@@ -46,6 +48,8 @@ pub use self::mapper_script_view::*;
 pub use self::mapper_counter_view::*;
 pub use self::epg_config_view::*;
 pub use self::epg_view::*;
+pub use self::epg_mapping_view::*;
+pub use self::playlist_compare_view::*;
 
 pub fn make_tags(data: &[(bool, &str)], translate: &YewI18n) -> Vec<Rc<Tag>> {
     data.iter()
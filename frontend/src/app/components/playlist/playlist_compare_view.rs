@@ -0,0 +1,134 @@
+use crate::app::components::select::Select;
+use crate::app::components::{Card, DropDownOption, DropDownSelection, NoContent, PlaylistContext, TextButton};
+use crate::hooks::use_service_context;
+use crate::model::{BusyStatus, EventMessage};
+use shared::model::{PlaylistChangeItemDto, PlaylistCompareDto};
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+
+const TARGET_A: &str = "target_a";
+const TARGET_B: &str = "target_b";
+
+fn render_side(title: &str, groups: &[String], channels: &[PlaylistChangeItemDto], label_groups: &str, label_channels: &str) -> Html {
+    html! {
+        <Card class="tp__playlist-compare-view__side">
+            <h2>{ title }</h2>
+            <span class="tp__playlist-compare-view__label">{ format!("{label_groups} ({})", groups.len()) }</span>
+            <ul class="tp__playlist-compare-view__list">
+                { for groups.iter().map(|group| html! { <li>{ group }</li> }) }
+            </ul>
+            <span class="tp__playlist-compare-view__label">{ format!("{label_channels} ({})", channels.len()) }</span>
+            <ul class="tp__playlist-compare-view__list">
+                { for channels.iter().map(|item| html! {
+                    <li>
+                        <span class="tp__playlist-compare-view__group">{ &item.group }</span>
+                        <span>{ &item.name }</span>
+                        <span class="tp__playlist-compare-view__cluster">{ item.cluster.to_string() }</span>
+                    </li>
+                }) }
+            </ul>
+        </Card>
+    }
+}
+
+#[function_component]
+pub fn PlaylistCompareView() -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let playlist_ctx = use_context::<PlaylistContext>().expect("Playlist context not found");
+    let target_a = use_state(|| None::<String>);
+    let target_b = use_state(|| None::<String>);
+    let compare = use_state(|| None::<Rc<PlaylistCompareDto>>);
+
+    let target_names = use_memo(playlist_ctx.sources.clone(), |sources| {
+        sources.as_ref().as_ref()
+            .map(|data| data.iter().flat_map(|(_inputs, targets)| targets.iter()).map(|target| target.name.clone()).collect::<Vec<_>>())
+            .unwrap_or_default()
+    });
+
+    let create_options = |selected: Option<&String>| {
+        Rc::new(target_names.iter()
+            .map(|name| DropDownOption::new(name, html! { name }, selected == Some(name)))
+            .collect::<Vec<_>>())
+    };
+
+    let handle_select = {
+        let set_target_a = target_a.clone();
+        let set_target_b = target_b.clone();
+        let set_compare = compare.clone();
+        Callback::from(move |(name, selection): (String, DropDownSelection)| {
+            if let DropDownSelection::Single(target) = selection {
+                if name == TARGET_A {
+                    set_target_a.set(Some(target));
+                } else {
+                    set_target_b.set(Some(target));
+                }
+                set_compare.set(None);
+            }
+        })
+    };
+
+    let handle_compare = {
+        let services = services.clone();
+        let translate = translate.clone();
+        let target_a = target_a.clone();
+        let target_b = target_b.clone();
+        let set_compare = compare.clone();
+        Callback::from(move |_| {
+            let (Some(a), Some(b)) = ((*target_a).clone(), (*target_b).clone()) else {
+                services.toastr.warning(translate.t("MESSAGES.PLAYLIST_COMPARE.SELECT_TARGETS"));
+                return;
+            };
+            let services = services.clone();
+            let translate = translate.clone();
+            let set_compare = set_compare.clone();
+            services.event.broadcast(EventMessage::Busy(BusyStatus::Show));
+            spawn_local(async move {
+                let result = services.playlist.compare_targets(&a, &b).await;
+                if result.is_none() {
+                    services.toastr.warning(translate.t("MESSAGES.PLAYLIST_COMPARE.NOT_PROCESSED"));
+                }
+                set_compare.set(result.map(Rc::new));
+                services.event.broadcast(EventMessage::Busy(BusyStatus::Hide));
+            });
+        })
+    };
+
+    let render_result = || {
+        let Some(result) = compare.as_ref() else {
+            return html! { <NoContent /> };
+        };
+        if result.is_equal() {
+            return html! { <span class="tp__playlist-compare-view__summary">{ translate.t("MESSAGES.PLAYLIST_COMPARE.EQUAL") }</span> };
+        }
+        let label_groups = translate.t("LABEL.GROUPS_ONLY_IN");
+        let label_channels = translate.t("LABEL.CHANNELS_ONLY_IN");
+        html! {
+            <>
+            <span class="tp__playlist-compare-view__summary">{ format!("{}: {}", translate.t("LABEL.COMMON_CHANNELS"), result.common) }</span>
+            <div class="tp__playlist-compare-view__result">
+                { render_side(&result.target_a, &result.groups_only_a, &result.only_a, &label_groups, &label_channels) }
+                { render_side(&result.target_b, &result.groups_only_b, &result.only_b, &label_groups, &label_channels) }
+            </div>
+            </>
+        }
+    };
+
+    html! {
+        <div class="tp__playlist-compare-view tp__list-list">
+            <div class="tp__playlist-compare-view__header tp__list-list__header">
+                <h1>{ translate.t("LABEL.COMPARE_TARGETS") }</h1>
+            </div>
+            <div class="tp__playlist-compare-view__body tp__list-list__body">
+                <div class="tp__playlist-compare-view__selection">
+                    <Select name={TARGET_A} options={create_options(target_a.as_ref())} on_select={handle_select.clone()} />
+                    <Select name={TARGET_B} options={create_options(target_b.as_ref())} on_select={handle_select} />
+                    <TextButton class="primary" name="compare" icon="Refresh" title={translate.t("LABEL.COMPARE")} onclick={handle_compare} />
+                </div>
+                { render_result() }
+            </div>
+        </div>
+    }
+}
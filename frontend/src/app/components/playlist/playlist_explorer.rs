@@ -1,13 +1,14 @@
 use crate::app::components::menu_item::MenuItem;
 use crate::app::components::popup_menu::PopupMenu;
-use crate::app::components::{AppIcon, Chip, IconButton, NoContent, Panel, Search};
+use crate::app::components::select::Select;
+use crate::app::components::{AppIcon, Chip, DropDownOption, DropDownSelection, IconButton, NoContent, Panel, Search};
 use crate::app::context::PlaylistExplorerContext;
 use crate::hooks::use_service_context;
 use crate::html_if;
 use crate::model::{BusyStatus, EventMessage};
 use crate::services::DialogService;
 use shared::error::{info_err_res, TuliproxError};
-use shared::model::{PlaylistRequest, SearchRequest, SeriesStreamDetailEpisodeProperties, SeriesStreamProperties, UiPlaylistGroup, UiPlaylistItem, VirtualId, XtreamCluster};
+use shared::model::{PlaylistBrowseFilter, PlaylistBrowseRequest, PlaylistBrowseSortField, PlaylistBrowseSortOrder, PlaylistRequest, SearchRequest, SeriesStreamDetailEpisodeProperties, SeriesStreamProperties, UiPlaylistGroup, UiPlaylistItem, VirtualId, XtreamCluster};
 use shared::utils::format_float_localized;
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -23,6 +24,24 @@ const COPY_LINK_TULIPROX_VIRTUAL_ID: &str = "copy_link_tuliprox_virtual_id";
 const COPY_LINK_TULIPROX_WEBPLAYER_URL: &str = "copy_link_tuliprox_webplayer_url";
 const COPY_LINK_PROVIDER_URL: &str = "copy_link_provider_url";
 
+const SORT_SELECT: &str = "sort";
+const SORT_DEFAULT: &str = "default";
+const SORT_NAME_ASC: &str = "name_asc";
+const SORT_NAME_DESC: &str = "name_desc";
+const SORT_GROUP: &str = "group";
+const BROWSE_PAGE_SIZE: u16 = 200;
+// distance in pixels to the end of the list at which the next page is loaded
+const BROWSE_SCROLL_THRESHOLD: i32 = 400;
+
+fn browse_sort(name: &str) -> (PlaylistBrowseSortField, PlaylistBrowseSortOrder) {
+    match name {
+        SORT_NAME_ASC => (PlaylistBrowseSortField::Name, PlaylistBrowseSortOrder::Asc),
+        SORT_NAME_DESC => (PlaylistBrowseSortField::Name, PlaylistBrowseSortOrder::Desc),
+        SORT_GROUP => (PlaylistBrowseSortField::Group, PlaylistBrowseSortOrder::Asc),
+        _ => (PlaylistBrowseSortField::Default, PlaylistBrowseSortOrder::Asc),
+    }
+}
+
 #[derive(Clone)]
 struct ChannelSelection {
     virtual_id: VirtualId,
@@ -64,6 +83,36 @@ impl FromStr for ExplorerAction {
     }
 }
 
+/// Group or search result of a stored playlist, which is loaded page by page from the server.
+#[derive(Clone)]
+struct BrowseQuery {
+    group: Rc<UiPlaylistGroup>,
+    filter: PlaylistBrowseFilter,
+}
+
+#[derive(Default)]
+struct BrowseState {
+    query: Option<BrowseQuery>,
+    sort: (PlaylistBrowseSortField, PlaylistBrowseSortOrder),
+    channels: Vec<Rc<UiPlaylistItem>>,
+    next_cursor: Option<String>,
+    loading: bool,
+    // responses of an older query are dropped
+    generation: u32,
+}
+
+impl BrowseState {
+    fn cancel(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        self.loading = false;
+    }
+}
+
+enum BrowseLoad {
+    First(BrowseQuery),
+    Next,
+}
+
 enum ExplorerLevel {
     Categories,
     Group(Rc<UiPlaylistGroup>),
@@ -83,6 +132,109 @@ pub fn PlaylistExplorer() -> Html {
     let popup_is_open = use_state(|| false);
     let clipboard = use_clipboard();
     let cluster_visible = use_state(|| XtreamCluster::Live);
+    let browse_state = use_mut_ref(BrowseState::default);
+    let browse_sort_name = use_state(|| SORT_DEFAULT.to_string());
+    let paged = context.playlist_request.as_ref().is_some_and(|r| matches!(r, PlaylistRequest::Target(_) | PlaylistRequest::Input(_)));
+
+    let load_browse_page = {
+        let browse_state = browse_state.clone();
+        let set_current_item = current_item.clone();
+        let services = service_ctx.clone();
+        let translate = translate.clone();
+        let ctx = context.clone();
+        Callback::from(move |load: BrowseLoad| {
+            let Some(source) = ctx.playlist_request.as_ref().cloned() else { return; };
+            let (query, cursor, generation, (sort_by, sort_order)) = {
+                let mut state = browse_state.borrow_mut();
+                match load {
+                    BrowseLoad::First(query) => {
+                        state.cancel();
+                        state.query = Some(query);
+                        state.channels.clear();
+                        state.next_cursor = None;
+                    }
+                    BrowseLoad::Next => {
+                        if state.loading || state.next_cursor.is_none() {
+                            return;
+                        }
+                    }
+                }
+                let Some(query) = state.query.clone() else { return; };
+                state.loading = true;
+                (query, state.next_cursor.clone(), state.generation, state.sort)
+            };
+            let first_page = cursor.is_none();
+            let request = PlaylistBrowseRequest {
+                source,
+                cluster: query.group.xtream_cluster,
+                filter: query.filter.clone(),
+                sort_by,
+                sort_order,
+                cursor,
+                limit: Some(BROWSE_PAGE_SIZE),
+            };
+            if first_page {
+                services.event.broadcast(EventMessage::Busy(BusyStatus::Show));
+            }
+            let browse_state = browse_state.clone();
+            let set_current_item = set_current_item.clone();
+            let services = services.clone();
+            let translate = translate.clone();
+            spawn_local(async move {
+                let page = services.playlist.browse_playlist(&request).await;
+                if first_page {
+                    services.event.broadcast(EventMessage::Busy(BusyStatus::Hide));
+                }
+                let mut state = browse_state.borrow_mut();
+                if state.generation != generation {
+                    return;
+                }
+                state.loading = false;
+                if let Some(page) = page {
+                    state.channels.extend(page.items.into_iter().map(Rc::new));
+                    state.next_cursor = page.next_cursor;
+                } else {
+                    services.toastr.error(translate.t("MESSAGES.PLAYLIST.BROWSE_FAILED"));
+                }
+                set_current_item.set(ExplorerLevel::Group(Rc::new(UiPlaylistGroup {
+                    id: query.group.id,
+                    title: query.group.title.clone(),
+                    channels: state.channels.clone(),
+                    xtream_cluster: query.group.xtream_cluster,
+                })));
+            });
+        })
+    };
+
+    let handle_sort_select = {
+        let browse_state = browse_state.clone();
+        let set_sort_name = browse_sort_name.clone();
+        let load_browse_page = load_browse_page.clone();
+        Callback::from(move |(_name, selection): (String, DropDownSelection)| {
+            if let DropDownSelection::Single(sort_name) = selection {
+                let query = {
+                    let mut state = browse_state.borrow_mut();
+                    state.sort = browse_sort(&sort_name);
+                    state.query.clone()
+                };
+                set_sort_name.set(sort_name);
+                if let Some(query) = query {
+                    load_browse_page.emit(BrowseLoad::First(query));
+                }
+            }
+        })
+    };
+
+    let handle_group_scroll = {
+        let load_browse_page = load_browse_page.clone();
+        Callback::from(move |event: Event| {
+            if let Some(list) = event.target_dyn_into::<web_sys::Element>() {
+                if list.scroll_top() + list.client_height() + BROWSE_SCROLL_THRESHOLD >= list.scroll_height() {
+                    load_browse_page.emit(BrowseLoad::Next);
+                }
+            }
+        })
+    };
 
     let handle_cluster_change = {
         let cluster_vis = cluster_visible.clone();
@@ -163,10 +315,12 @@ pub fn PlaylistExplorer() -> Html {
 
     let handle_series_onclick = {
         let set_current_item = current_item.clone();
+        let browse_state = browse_state.clone();
         Callback::from(move |(dto, event): (Rc<UiPlaylistItem>, MouseEvent)| {
             event.prevent_default();
             event.stop_propagation();
             if let ExplorerLevel::Group(ref group) = *set_current_item {
+                browse_state.borrow_mut().cancel();
                 load_series_info(group.clone(), dto.clone());
             }
         })
@@ -178,8 +332,14 @@ pub fn PlaylistExplorer() -> Html {
         let set_selected_channel = selected_channel.clone();
         let set_popup_is_open = popup_is_open.clone();
         let set_anchor_ref = popup_anchor_ref.clone();
+        let browse_state = browse_state.clone();
         use_effect_with(context.playlist.clone(), move |new_playlist| {
             set_current_item.set(ExplorerLevel::Categories);
+            let mut state = browse_state.borrow_mut();
+            state.cancel();
+            state.query = None;
+            state.channels.clear();
+            state.next_cursor = None;
             set_playlist.set((**new_playlist).clone());
             // Reset popup state and selection when the underlying data changes
             set_selected_channel.set(None);
@@ -276,10 +436,12 @@ pub fn PlaylistExplorer() -> Html {
 
     let handle_back_click = {
         let current_item = current_item.clone();
+        let browse_state = browse_state.clone();
         Callback::from(move |_| {
             match *current_item {
                 ExplorerLevel::Categories => {}
                 ExplorerLevel::Group(_) => {
+                    browse_state.borrow_mut().cancel();
                     current_item.set(ExplorerLevel::Categories);
                 }
                 ExplorerLevel::SeriesInfo(ref group, _, _) => {
@@ -294,7 +456,32 @@ pub fn PlaylistExplorer() -> Html {
         let set_playlist = playlist.clone();
         let set_current_item = current_item.clone();
         let context = context.clone();
+        let browse_state = browse_state.clone();
+        let load_browse_page = load_browse_page.clone();
+        let cluster = *cluster_visible;
         Callback::from(move |search_req| {
+            if paged {
+                // the search of a stored playlist runs on the server for the visible cluster
+                let (title, name) = match &search_req {
+                    SearchRequest::Clear => {
+                        browse_state.borrow_mut().cancel();
+                        set_current_item.set(ExplorerLevel::Categories);
+                        return;
+                    }
+                    SearchRequest::Text(text, _) => (text.as_str(), regex::escape(text)),
+                    SearchRequest::Regexp(text, _) => (text.as_str(), text.clone()),
+                };
+                load_browse_page.emit(BrowseLoad::First(BrowseQuery {
+                    group: Rc::new(UiPlaylistGroup {
+                        id: 0,
+                        title: title.into(),
+                        channels: vec![],
+                        xtream_cluster: cluster,
+                    }),
+                    filter: PlaylistBrowseFilter { name: Some(name), ..PlaylistBrowseFilter::default() },
+                }));
+                return;
+            }
             match search_req {
                 SearchRequest::Clear => set_playlist.set((*context.playlist).clone()),
                 SearchRequest::Text(ref _text, ref _search_fields)
@@ -321,8 +508,14 @@ pub fn PlaylistExplorer() -> Html {
 
     let handle_category_select = {
         let set_current_item = current_item.clone();
+        let load_browse_page = load_browse_page.clone();
         Callback::from(move |(group, _event): (Rc<UiPlaylistGroup>, MouseEvent)| {
-            set_current_item.set(ExplorerLevel::Group(group));
+            if paged {
+                let filter = PlaylistBrowseFilter { group: Some(group.title.to_string()), ..PlaylistBrowseFilter::default() };
+                load_browse_page.emit(BrowseLoad::First(BrowseQuery { group, filter }));
+            } else {
+                set_current_item.set(ExplorerLevel::Group(group));
+            }
         })
     };
 
@@ -510,7 +703,8 @@ pub fn PlaylistExplorer() -> Html {
     let render_group = |group: &Rc<UiPlaylistGroup>| {
         html! {
                 <div class="tp__playlist-explorer__group">
-                  <div class={format!("tp__playlist-explorer__group-list tp__playlist-explorer__group-list-{}", group.xtream_cluster.to_string().to_lowercase())}>
+                  <div class={format!("tp__playlist-explorer__group-list tp__playlist-explorer__group-list-{}", group.xtream_cluster.to_string().to_lowercase())}
+                       onscroll={paged.then(|| handle_group_scroll.clone())}>
                   {
                       group.channels.iter().map(render_channel).collect::<Html>()
                   }
@@ -615,6 +809,15 @@ pub fn PlaylistExplorer() -> Html {
         }
     };
 
+    let sort_options = Rc::new([
+        (SORT_DEFAULT, "LABEL.SORT_DEFAULT"),
+        (SORT_NAME_ASC, "LABEL.SORT_NAME_ASC"),
+        (SORT_NAME_DESC, "LABEL.SORT_NAME_DESC"),
+        (SORT_GROUP, "LABEL.SORT_GROUP"),
+    ].into_iter()
+        .map(|(name, label)| DropDownOption::new(name, html! { translate.t(label) }, *browse_sort_name == name))
+        .collect::<Vec<_>>());
+
     html! {
       <div class="tp__playlist-explorer">
         <div class="tp__playlist-explorer__header">
@@ -630,6 +833,9 @@ pub fn PlaylistExplorer() -> Html {
                   }
                 </div>
                 <div class="tp__playlist-explorer__header-toolbar-search">
+                  { html_if!(paged, {
+                      <Select name={SORT_SELECT} icon="Sort" options={sort_options} on_select={handle_sort_select} />
+                  })}
                   <Search onsearch={handle_search}/>
                 </div>
             </div>
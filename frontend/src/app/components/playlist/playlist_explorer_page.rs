@@ -5,6 +5,7 @@ use shared::error::{TuliproxError, info_err_res};
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PlaylistExplorerPage {
     SourceSelector,
+    Compare,
 }
 
 impl FromStr for PlaylistExplorerPage {
@@ -13,6 +14,7 @@ impl FromStr for PlaylistExplorerPage {
     fn from_str(s: &str) -> Result<Self, TuliproxError> {
         match s.to_lowercase().as_str() {
             "source-selector" => Ok(PlaylistExplorerPage::SourceSelector),
+            "compare" => Ok(PlaylistExplorerPage::Compare),
             _ => info_err_res!("Unknown page type: {s}"),
         }
     }
@@ -22,6 +24,7 @@ impl Display for PlaylistExplorerPage {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{}", match *self {
             Self::SourceSelector => "source-selector",
+            Self::Compare => "compare",
         })
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::app::components::{Breadcrumbs, Panel, PlaylistExplorerPage, PlaylistSourceSelector};
+use crate::app::components::{Breadcrumbs, Panel, PlaylistCompareView, PlaylistExplorerPage, PlaylistSourceSelector};
 use crate::app::context::PlaylistExplorerContext;
 use std::rc::Rc;
 use yew::prelude::*;
@@ -31,6 +31,7 @@ pub fn PlaylistExplorerView() -> Html {
         use_effect_with(view_visible_dep, move |_| {
             match *view_visible {
                 PlaylistExplorerPage::SourceSelector => breadcrumbs.set(Rc::new(vec![translate.t("LABEL.PLAYLIST_EXPLORER"), translate.t("LABEL.SOURCES")])),
+                PlaylistExplorerPage::Compare => breadcrumbs.set(Rc::new(vec![translate.t("LABEL.PLAYLIST_EXPLORER"), translate.t("LABEL.COMPARE_TARGETS")])),
             }
         });
     };
@@ -50,6 +51,9 @@ pub fn PlaylistExplorerView() -> Html {
                     <PlaylistSourceSelector />
                     <PlaylistExplorer />
                 </Panel>
+                <Panel value={PlaylistExplorerPage::Compare.to_string()} active={active_page.to_string()}>
+                    <PlaylistCompareView />
+                </Panel>
             </div>
         </div>
        </ContextProvider<PlaylistExplorerContext>>
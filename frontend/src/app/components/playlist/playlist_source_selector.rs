@@ -1,5 +1,6 @@
 use crate::app::components::{Card, CollapsePanel, InputRow, Panel, PlaylistContext, RadioButtonGroup, TextButton};
 use crate::app::context::PlaylistExplorerContext;
+use crate::app::components::PlaylistExplorerPage;
 use crate::hooks::use_service_context;
 use crate::html_if;
 use crate::model::{BusyStatus, EventMessage, ExplorerSourceType};
@@ -52,6 +53,15 @@ pub fn PlaylistSourceSelector(props: &PlaylistSourceSelectorProps) -> Html {
         })
     };
 
+    let handle_compare = {
+        let playlist_explorer_ctx = playlist_explorer_ctx.clone();
+        Callback::from(move |_| {
+            if let Some(ctx) = playlist_explorer_ctx.as_ref() {
+                ctx.active_page.set(PlaylistExplorerPage::Compare);
+            }
+        })
+    };
+
     let handle_source_download = {
         let services = services_ctx.clone();
         let set_loading = loading.clone();
@@ -61,7 +71,7 @@ pub fn PlaylistSourceSelector(props: &PlaylistSourceSelectorProps) -> Html {
                 on_select.emit(request)
             })
         } else {
-            let playlist_explorer_ctx_clone = playlist_explorer_ctx.expect("PlaylistExplorer context not found").clone();
+            let playlist_explorer_ctx_clone = playlist_explorer_ctx.clone().expect("PlaylistExplorer context not found");
             Callback::from(move |request: PlaylistRequest| {
                 if !*set_loading {
                     let services = services.clone();
@@ -71,7 +81,12 @@ pub fn PlaylistSourceSelector(props: &PlaylistSourceSelectorProps) -> Html {
                     let set_loading = set_loading.clone();
                     let req = request;
                     spawn_local(async move {
-                        let playlist = services.playlist.get_playlist_categories(&req).await;
+                        // stored playlists are browsed page by page, custom providers are downloaded completely
+                        let playlist = if matches!(req, PlaylistRequest::Target(_) | PlaylistRequest::Input(_)) {
+                            services.playlist.get_playlist_groups(&req).await
+                        } else {
+                            services.playlist.get_playlist_categories(&req).await
+                        };
                         playlist_explorer_ctx_clone.playlist.set(playlist);
                         playlist_explorer_ctx_clone.playlist_request.set(Some(req));
                         set_loading.set(false);
@@ -259,6 +274,9 @@ pub fn PlaylistSourceSelector(props: &PlaylistSourceSelectorProps) -> Html {
         { html_if!(!props.hide_title, {
             <div class="tp__playlist-source-selector__header tp__list-list__header">
               <h1>{ translate.t("LABEL.SOURCES")}</h1>
+              { html_if!(playlist_explorer_ctx.is_some(), {
+                  <TextButton name="compare_targets" icon="Target" title={translate.t("LABEL.COMPARE_TARGETS")} onclick={handle_compare} />
+              })}
             </div>
         })}
         <div class="tp__playlist-source-selector__body tp__list-list__body">
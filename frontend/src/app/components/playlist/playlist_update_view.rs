@@ -8,6 +8,8 @@ use shared::model::ConfigTargetDto;
 use crate::app::ConfigContext;
 use crate::hooks::use_service_context;
 use crate::html_if;
+use crate::model::{DialogResult, EventMessage};
+use crate::services::DialogService;
 
 const LABEL_UPDATE_LOCAL_LIBRARY: &str = "LABEL.UPDATE_LOCAL_LIBRARY";
 const ACTION_UPDATE_LIBRARY: &str = "update_library";
@@ -18,6 +20,7 @@ pub fn PlaylistUpdateView() -> Html {
     let playlist_ctx = use_context::<PlaylistContext>().expect("Playlist context not found");
     let config_ctx =  use_context::<ConfigContext>().expect("Config context not found");
     let services_ctx = use_service_context();
+    let dialog = use_context::<DialogService>().expect("Dialog service not found");
     let breadcrumbs = use_state(|| Rc::new(vec![translate.t("LABEL.PLAYLISTS"), translate.t("LABEL.UPDATE")]));
     let selected_targets = use_list::<Rc<ConfigTargetDto>>(vec![]);
 
@@ -40,6 +43,34 @@ pub fn PlaylistUpdateView() -> Html {
         })
     };
 
+    {
+        // the server asks before it processes the targets of changed mappings
+        let services_ctx = services_ctx.clone();
+        let translate = translate.clone();
+        let dialog = dialog.clone();
+        use_effect_with((), move |_| {
+            let services = services_ctx.clone();
+            let subid = services_ctx.event.subscribe(move |msg| {
+                if let EventMessage::MappingReprocess(targets) = msg {
+                    let services = services.clone();
+                    let translate = translate.clone();
+                    let dialog = dialog.clone();
+                    spawn_local(async move {
+                        let title = format!("{}\n{}", translate.t("MESSAGES.CONFIRM_MAPPING_REPROCESS"), targets.join(", "));
+                        if dialog.confirm(&title).await != DialogResult::Ok {
+                            return;
+                        }
+                        let update_target_names = targets.iter().map(String::as_str).collect::<Vec<&str>>();
+                        match services.playlist.update_targets(&update_target_names).await {
+                            true => { services.toastr.success(translate.t("MESSAGES.PLAYLIST_UPDATE.SUCCESS")); }
+                            false => { services.toastr.error(translate.t("MESSAGES.PLAYLIST_UPDATE.FAIL")); }
+                        }
+                    });
+                }
+            });
+            move || services_ctx.event.unsubscribe(subid)
+        });
+    }
 
     let handle_update = {
         let translate = translate.clone();
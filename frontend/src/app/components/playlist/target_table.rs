@@ -106,7 +106,7 @@ pub fn TargetTable(props: &TargetTableProps) -> Html {
                     8 => {
                         let mapping_oneliner = dto.mapping.as_ref().map(|v| v.join(", ")).unwrap_or_default();
                         html_if!(!mapping_oneliner.is_empty(),
-                            { <RevealContent preview={Some(html! { mapping_oneliner })}><PlaylistMappings mappings={dto.mapping.clone()} /></RevealContent> })
+                            { <RevealContent preview={Some(html! { mapping_oneliner })}><PlaylistMappings mappings={dto.mapping.clone()} target={Some(dto.name.clone())} /></RevealContent> })
                     },
                     9 => html! { <PlaylistProcessing order={dto.processing_order} /> },
                     10 => html! { <TargetWatch  target={Rc::clone(&dto)} /> },
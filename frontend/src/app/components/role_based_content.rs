@@ -12,7 +12,7 @@ pub fn RoleBasedContent() -> Html {
        return html! { "Not authorized" };
     }
 
-    if services.auth.is_admin() {
+    if services.auth.is_web_ui_user() {
         html! {  <Switch<AppRoute> render={switch} /> }
     } else if services.auth.is_user() {
         html! {  <ApiUserView /> }
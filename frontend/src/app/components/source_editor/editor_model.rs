@@ -2,7 +2,7 @@ use std::fmt;
 use std::rc::Rc;
 use serde::{Deserialize, Serialize};
 use yew::{Callback, UseStateHandle};
-use shared::model::{ConfigInputDto, ConfigTargetDto, InputType, TargetOutputDto};
+use shared::model::{ConfigHelpDto, ConfigInputDto, ConfigTargetDto, InputType, TargetOutputDto};
 
 pub const BLOCK_WIDTH: f32 = 100.0;
 pub const BLOCK_HEIGHT: f32 = 50.0;
@@ -19,6 +19,7 @@ pub enum BlockType {
     OutputM3u,
     OutputXtream,
     OutputHdHomeRun,
+    OutputPlex,
     OutputStrm,
 }
 
@@ -31,6 +32,7 @@ impl BlockType {
     pub const OUTPUT_M3U: &'static str = "OutputM3u";
     pub const OUTPUT_XTREAM: &'static str = "OutputXtream";
     pub const OUTPUT_HDHOMERUN: &'static str = "OutputHdHomeRun";
+    pub const OUTPUT_PLEX: &'static str = "OutputPlex";
     pub const OUTPUT_STRM: &'static str = "OutputStrm";
 
     pub fn is_input(&self) -> bool {
@@ -42,7 +44,7 @@ impl BlockType {
     }
 
     pub fn is_output(&self) -> bool {
-        matches!(self, Self::OutputXtream | Self::OutputM3u | Self::OutputHdHomeRun | Self::OutputStrm)
+        matches!(self, Self::OutputXtream | Self::OutputM3u | Self::OutputHdHomeRun | Self::OutputPlex | Self::OutputStrm)
     }
 }
 
@@ -57,6 +59,7 @@ impl From<&str> for BlockType {
             BlockType::OUTPUT_M3U => BlockType::OutputM3u,
             BlockType::OUTPUT_XTREAM => BlockType::OutputXtream,
             BlockType::OUTPUT_HDHOMERUN => BlockType::OutputHdHomeRun,
+            BlockType::OUTPUT_PLEX => BlockType::OutputPlex,
             BlockType::OUTPUT_STRM => BlockType::OutputStrm,
             _ => BlockType::Target, // fallback
         }
@@ -73,7 +76,8 @@ impl From<InputType> for BlockType {
     fn from(s: InputType) -> Self {
         match s {
             InputType::M3uBatch
-            | InputType::M3u => BlockType::InputM3u,
+            | InputType::M3u
+            | InputType::Udp => BlockType::InputM3u,
             InputType::XtreamBatch
             | InputType::Xtream => BlockType::InputXtream,
             InputType::Library => BlockType::InputLibrary,
@@ -93,6 +97,7 @@ impl fmt::Display for BlockType {
             BlockType::OutputM3u => Self::OUTPUT_M3U,
             BlockType::OutputXtream => Self::OUTPUT_XTREAM,
             BlockType::OutputHdHomeRun => Self::OUTPUT_HDHOMERUN,
+            BlockType::OutputPlex => Self::OUTPUT_PLEX,
             BlockType::OutputStrm => Self::OUTPUT_STRM,
         };
         write!(f, "{}", s)
@@ -175,5 +180,6 @@ pub enum EditMode {
 #[derive(Clone, PartialEq)]
 pub struct SourceEditorContext {
     pub on_form_change: Callback<(BlockId, BlockInstance)>,
-    pub edit_mode: UseStateHandle<EditMode>
+    pub edit_mode: UseStateHandle<EditMode>,
+    pub config_help: Option<Rc<ConfigHelpDto>>,
 }
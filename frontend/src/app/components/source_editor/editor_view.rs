@@ -2,8 +2,8 @@ use crate::app::components::source_editor::layout::layout;
 use crate::app::components::{can_connect, Block, BlockId, BlockInstance, BlockType, BlockView, Connection, EditMode, InputRow, PortStatus, SourceEditorContext, SourceEditorForm, SourceEditorSidebar, TextButton, BLOCK_HEADER_HEIGHT, BLOCK_HEIGHT, BLOCK_PORT_HEIGHT, BLOCK_WIDTH};
 use crate::app::{ConfigContext, PlaylistContext};
 use crate::hooks::use_service_context;
-use shared::model::{ConfigInputDto, ConfigSourceDto, ConfigTargetDto, HdHomeRunTargetOutputDto,
-                    M3uTargetOutputDto, StrmTargetOutputDto, TargetOutputDto, XtreamTargetOutputDto};
+use shared::model::{ConfigHelpDto, ConfigInputDto, ConfigSourceDto, ConfigTargetDto, HdHomeRunTargetOutputDto,
+                    M3uTargetOutputDto, PlexTargetOutputDto, StrmTargetOutputDto, TargetOutputDto, XtreamTargetOutputDto};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
@@ -13,7 +13,7 @@ use yew::platform::spawn_local;
 use yew::prelude::*;
 use yew_i18n::use_translation;
 use crate::model::DialogResult;
-use crate::services::DialogService;
+use crate::services::{DialogService, ToastCloseMode, ToastOptions};
 
 const PENDING_LINE: &str = "pending-line";
 const SELECTION_RECT: &str = "selection-rect";
@@ -201,6 +201,7 @@ fn create_instance(block_type: BlockType) -> BlockInstance {
         BlockType::OutputM3u => BlockInstance::Output(Rc::new(TargetOutputDto::M3u(M3uTargetOutputDto::default()))),
         BlockType::OutputXtream => BlockInstance::Output(Rc::new(TargetOutputDto::Xtream(XtreamTargetOutputDto::default()))),
         BlockType::OutputHdHomeRun => BlockInstance::Output(Rc::new(TargetOutputDto::HdHomeRun(HdHomeRunTargetOutputDto::default()))),
+        BlockType::OutputPlex => BlockInstance::Output(Rc::new(TargetOutputDto::Plex(PlexTargetOutputDto::default()))),
         BlockType::OutputStrm => BlockInstance::Output(Rc::new(TargetOutputDto::Strm(StrmTargetOutputDto::default()))),
     }
 }
@@ -220,6 +221,7 @@ fn create_output_instance(output: &TargetOutputDto) -> (BlockInstance, BlockType
         TargetOutputDto::M3u(dto) => (BlockInstance::Output(Rc::new(TargetOutputDto::M3u(dto.clone()))), BlockType::OutputM3u),
         TargetOutputDto::Strm(dto) => (BlockInstance::Output(Rc::new(TargetOutputDto::Strm(dto.clone()))), BlockType::OutputStrm),
         TargetOutputDto::HdHomeRun(dto) => (BlockInstance::Output(Rc::new(TargetOutputDto::HdHomeRun(dto.clone()))), BlockType::OutputHdHomeRun),
+        TargetOutputDto::Plex(dto) => (BlockInstance::Output(Rc::new(TargetOutputDto::Plex(dto.clone()))), BlockType::OutputPlex),
     }
 }
 
@@ -239,6 +241,18 @@ pub fn SourceEditor() -> Html {
     // Delete mode toggle
     let delete_mode = use_state(|| false);
     let cursor_grabbing = use_state(|| false);
+    let config_help = use_state(|| None::<Rc<ConfigHelpDto>>);
+
+    {
+        let services = services.clone();
+        let config_help = config_help.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                config_help.set(services.config.get_config_help().await);
+            });
+            || ()
+        });
+    }
 
     {
         let playlists = playlist_ctx.clone();
@@ -428,7 +442,14 @@ pub fn SourceEditor() -> Html {
             let translate = translate.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 match services.config.save_sources(sources_config).await {
-                    Ok(()) => services.toastr.success(translate.t("MESSAGES.SAVE.SOURCES_CONFIG.SUCCESS")),
+                    Ok(warnings) => {
+                        services.toastr.success(translate.t("MESSAGES.SAVE.SOURCES_CONFIG.SUCCESS"));
+                        for warning in warnings {
+                            services.toastr.warning_with_options(
+                                format!("{}: {warning}", translate.t("MESSAGES.SAVE.SOURCES_CONFIG.LINT_WARNING")),
+                                ToastOptions { close_mode: ToastCloseMode::Manual });
+                        }
+                    }
                     Err(err) => services.toastr.error(err.to_string()),
                 }
             });
@@ -1019,6 +1040,7 @@ pub fn SourceEditor() -> Html {
     let editor_context = SourceEditorContext {
         on_form_change: form_changed,
         edit_mode: edit_mode.clone(),
+        config_help: (*config_help).clone(),
     };
 
     let edited_block_id = match *edit_mode {
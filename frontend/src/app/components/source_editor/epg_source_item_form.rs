@@ -1,5 +1,5 @@
 use crate::app::components::{Card, TextButton};
-use crate::{edit_field_bool, edit_field_number_i16, edit_field_text, generate_form_reducer};
+use crate::{edit_field_bool, edit_field_number_i16, edit_field_text, edit_field_text_option, generate_form_reducer};
 use shared::model::EpgSourceDto;
 use yew::{function_component, html, use_reducer, Callback, Html, Properties, UseReducerHandle};
 use yew_i18n::use_translation;
@@ -7,6 +7,7 @@ use yew_i18n::use_translation;
 const LABEL_EPG_SOURCE_URL: &str = "LABEL.EPG_SOURCE_URL";
 const LABEL_EPG_PRIORITY: &str = "LABEL.PRIORITY";
 const LABEL_EPG_LOGO_OVERRIDE: &str = "LABEL.EPG_LOGO_OVERRIDE";
+const LABEL_EPG_SCHEDULE: &str = "LABEL.SCHEDULE";
 
 generate_form_reducer!(
     state: EpgSourceFormState { form: EpgSourceDto },
@@ -15,6 +16,7 @@ generate_form_reducer!(
         Url => url: String,
         Priority => priority: i16,
         LogoOverride => logo_override: bool,
+        Schedule => schedule: Option<String>,
     }
 );
 
@@ -36,6 +38,7 @@ pub fn EpgSourceItemForm(props: &EpgSourceItemFormProps) -> Html {
                 url: String::new(),
                 priority: 0,
                 logo_override: false,
+                schedule: None,
             }),
             modified: false,
         }
@@ -64,6 +67,7 @@ pub fn EpgSourceItemForm(props: &EpgSourceItemFormProps) -> Html {
             { edit_field_text!(form_state, translate.t(LABEL_EPG_SOURCE_URL), url, EpgSourceFormAction::Url) }
             { edit_field_number_i16!(form_state, translate.t(LABEL_EPG_PRIORITY), priority, EpgSourceFormAction::Priority) }
             { edit_field_bool!(form_state, translate.t(LABEL_EPG_LOGO_OVERRIDE), logo_override, EpgSourceFormAction::LogoOverride) }
+            { edit_field_text_option!(form_state, translate.t(LABEL_EPG_SCHEDULE), schedule, EpgSourceFormAction::Schedule) }
 
             <div class="tp__form-page__toolbar">
                 <TextButton
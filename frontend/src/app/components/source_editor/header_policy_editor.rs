@@ -0,0 +1,98 @@
+use crate::app::components::key_value_editor::KeyValueEditor;
+use crate::app::components::{Tag, TagList};
+use crate::config_field_child;
+use shared::model::{HeaderPolicyDto, HeaderRulesDto};
+use std::collections::HashMap;
+use std::rc::Rc;
+use yew::{function_component, html, Callback, Html, Properties};
+use yew_i18n::use_translation;
+
+const LABEL_UPSTREAM_HEADERS: &str = "LABEL.UPSTREAM_HEADERS";
+const LABEL_CLIENT_HEADERS: &str = "LABEL.CLIENT_HEADERS";
+const LABEL_OVERRIDE_HEADERS: &str = "LABEL.OVERRIDE_HEADERS";
+const LABEL_ADD_MISSING_HEADERS: &str = "LABEL.ADD_MISSING_HEADERS";
+const LABEL_REMOVE_HEADERS: &str = "LABEL.REMOVE_HEADERS";
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct HeaderPolicyEditorProps {
+    pub policy: HeaderPolicyDto,
+    pub on_change: Callback<HeaderPolicyDto>,
+}
+
+#[function_component]
+pub fn HeaderPolicyEditor(props: &HeaderPolicyEditorProps) -> Html {
+    let translate = use_translation();
+
+    let render_rules = |label: &str, rules: Option<&HeaderRulesDto>, is_upstream: bool| {
+        let rules = rules.cloned().unwrap_or_default();
+        let policy = props.policy.clone();
+        let on_change = props.on_change.clone();
+        let update = Rc::new(move |change: &dyn Fn(&mut HeaderRulesDto)| {
+            let mut policy = policy.clone();
+            let target = if is_upstream { &mut policy.upstream } else { &mut policy.client };
+            let mut header_rules = target.take().unwrap_or_default();
+            change(&mut header_rules);
+            *target = (!header_rules.is_empty()).then_some(header_rules);
+            on_change.emit(policy);
+        });
+
+        let remove_tags = rules.remove.iter()
+            .map(|name| Rc::new(Tag { label: name.clone(), class: None }))
+            .collect::<Vec<Rc<Tag>>>();
+        let update_override = Rc::clone(&update);
+        let update_add = Rc::clone(&update);
+        let update_remove = update;
+
+        html! {
+            <div class="tp__header-policy">
+                <h2>{ label.to_string() }</h2>
+                { config_field_child!(translate.t(LABEL_OVERRIDE_HEADERS), {
+                    html! {
+                        <KeyValueEditor
+                            entries={rules.override_headers.clone()}
+                            readonly={false}
+                            key_placeholder={translate.t("LABEL.HEADER_NAME")}
+                            value_placeholder={translate.t("LABEL.HEADER_VALUE")}
+                            on_change={Callback::from(move |headers: HashMap<String, String>| {
+                                update_override(&|rules: &mut HeaderRulesDto| rules.override_headers.clone_from(&headers));
+                            })}
+                        />
+                    }
+                })}
+                { config_field_child!(translate.t(LABEL_ADD_MISSING_HEADERS), {
+                    html! {
+                        <KeyValueEditor
+                            entries={rules.add.clone()}
+                            readonly={false}
+                            key_placeholder={translate.t("LABEL.HEADER_NAME")}
+                            value_placeholder={translate.t("LABEL.HEADER_VALUE")}
+                            on_change={Callback::from(move |headers: HashMap<String, String>| {
+                                update_add(&|rules: &mut HeaderRulesDto| rules.add.clone_from(&headers));
+                            })}
+                        />
+                    }
+                })}
+                { config_field_child!(translate.t(LABEL_REMOVE_HEADERS), {
+                    html! {
+                        <TagList
+                            tags={remove_tags}
+                            readonly={false}
+                            placeholder={translate.t("LABEL.HEADER_NAME")}
+                            on_change={Callback::from(move |tags: Vec<Rc<Tag>>| {
+                                let names = tags.iter().map(|tag| tag.label.clone()).collect::<Vec<String>>();
+                                update_remove(&|rules: &mut HeaderRulesDto| rules.remove.clone_from(&names));
+                            })}
+                        />
+                    }
+                })}
+            </div>
+        }
+    };
+
+    html! {
+        <div class="tp__header-policy-editor">
+            { render_rules(&translate.t(LABEL_UPSTREAM_HEADERS), props.policy.upstream.as_ref(), true) }
+            { render_rules(&translate.t(LABEL_CLIENT_HEADERS), props.policy.client.as_ref(), false) }
+        </div>
+    }
+}
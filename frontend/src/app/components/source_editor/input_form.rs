@@ -1,9 +1,11 @@
 use crate::app::components::config::HasFormData;
 use crate::app::components::key_value_editor::KeyValueEditor;
 use crate::app::components::select::Select;
-use crate::app::components::{AliasItemForm, BlockId, BlockInstance, Card, DropDownOption, DropDownSelection, EditMode, EpgSourceItemForm, IconButton, Panel, RadioButtonGroup, SourceEditorContext, TextButton, TitledCard};
-use crate::{config_field_child, edit_field_bool, edit_field_date, edit_field_number_i16, edit_field_number_u16, edit_field_text, edit_field_text_option, generate_form_reducer};
-use shared::model::{ConfigInputAliasDto, ConfigInputDto, ConfigInputOptionsDto, EpgConfigDto, EpgSourceDto, InputFetchMethod, InputType, StagedInputDto};
+use crate::app::components::{AliasItemForm, BlockId, BlockInstance, Card, DropDownOption, DropDownSelection, EditMode, EpgSourceItemForm, FieldHelp, HeaderPolicyEditor, FieldHelpProvider, IconButton, Panel, RadioButtonGroup, SourceEditorContext, TextButton, TitledCard};
+use crate::{config_field_child, edit_field_bool, edit_field_date, edit_field_list_option, edit_field_number_i16, edit_field_number_u16, edit_field_text, edit_field_text_option,
+            generate_form_reducer};
+use shared::model::{ConfigInputAliasDto, ConfigInputDto, ConfigInputOptionsDto, EpgConfigDto, EpgSourceDto, HeaderPolicyDto, ImpersonationProfile, InputFetchMethod, InputType, StagedInputDto,
+                    CONFIG_HELP_SECTION_INPUT, CONFIG_HELP_SECTION_INPUT_OPTIONS, CONFIG_HELP_SECTION_STAGED_INPUT};
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::rc::Rc;
@@ -18,6 +20,7 @@ const LABEL_NAME: &str = "LABEL.NAME";
 const LABEL_INPUT_TYPE: &str = "LABEL.INPUT_TYPE";
 const LABEL_FETCH_METHOD: &str = "LABEL.METHOD";
 const LABEL_HEADERS: &str = "LABEL.HEADERS";
+const LABEL_HEADER_POLICY: &str = "LABEL.HEADER_POLICY";
 const LABEL_URL: &str = "LABEL.URL";
 const LABEL_EPG_SOURCES: &str = "LABEL.EPG_SOURCES";
 const LABEL_USERNAME: &str = "LABEL.USERNAME";
@@ -36,7 +39,12 @@ const LABEL_XTREAM_SKIP_VOD: &str = "LABEL.VOD";
 const LABEL_XTREAM_SKIP_SERIES: &str = "LABEL.SERIES";
 const LABEL_XTREAM_LIVE_STREAM_USE_PREFIX: &str = "LABEL.LIVE_STREAM_USE_PREFIX";
 const LABEL_XTREAM_LIVE_STREAM_WITHOUT_EXTENSION: &str = "LABEL.LIVE_STREAM_WITHOUT_EXTENSION";
+const LABEL_XTREAM_INCREMENTAL: &str = "LABEL.INCREMENTAL_UPDATE";
 const LABEL_CACHE_DURATION: &str = "LABEL.CACHE_DURATION";
+const LABEL_IMPERSONATE: &str = "LABEL.IMPERSONATE";
+const LABEL_EGRESS_INTERFACE: &str = "LABEL.EGRESS_INTERFACE";
+const LABEL_MAINTENANCE_WINDOWS: &str = "LABEL.MAINTENANCE_WINDOWS";
+const LABEL_ADD_MAINTENANCE_WINDOW: &str = "LABEL.ADD_MAINTENANCE_WINDOW";
 
 const LABEL_MAIN: &str = "LABEL.MAIN_CONFIG";
 const LABEL_OPTIONS: &str = "LABEL.OPTIONS";
@@ -98,6 +106,7 @@ generate_form_reducer!(
       XtreamSkipSeries => xtream_skip_series: bool,
       XtreamLiveStreamUsePrefix => xtream_live_stream_use_prefix: bool,
       XtreamLiveStreamWithoutExtension => xtream_live_stream_without_extension: bool,
+      XtreamIncremental => xtream_incremental: bool,
     }
 );
 
@@ -129,6 +138,9 @@ generate_form_reducer!(
         Method => method: InputFetchMethod,
         ExpDate => exp_date: Option<i64>,
         CacheDuration => cache_duration: Option<String>,
+        Impersonate => impersonate: Option<ImpersonationProfile>,
+        EgressInterface => egress_interface: Option<String>,
+        MaintenanceWindows => maintenance_windows: Option<Vec<String>>,
     }
 );
 
@@ -147,12 +159,19 @@ pub struct ConfigInputViewProps {
 pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
     let translate = use_translation();
     let source_editor_ctx = use_context::<SourceEditorContext>();
+    let config_help = source_editor_ctx.as_ref().and_then(|ctx| ctx.config_help.clone());
     let fetch_methods = use_memo((), |_| {
         [InputFetchMethod::GET, InputFetchMethod::POST]
             .iter()
             .map(ToString::to_string)
             .collect::<Vec<String>>()
     });
+    let impersonation_profiles = use_memo((), |_| {
+        [ImpersonationProfile::Chrome, ImpersonationProfile::Firefox, ImpersonationProfile::Safari]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+    });
     let view_visible = use_state(|| InputFormPage::Main);
 
     // let on_tab_click = {
@@ -189,6 +208,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
     let epg_sources_state = use_state(Vec::<EpgSourceDto>::new);
     let aliases_state = use_state(Vec::<ConfigInputAliasDto>::new);
     let headers_state = use_state(HashMap::<String, String>::new);
+    let header_policy_state = use_state(HeaderPolicyDto::default);
 
     // State for showing item forms
     let show_epg_form_state = use_state(|| false);
@@ -218,6 +238,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
         let epg_sources_state = epg_sources_state.clone();
         let aliases_state = aliases_state.clone();
         let headers_state = headers_state.clone();
+        let header_policy_state = header_policy_state.clone();
 
         let config_input = props.input.clone();
 
@@ -235,6 +256,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
 
                 // Load headers
                 headers_state.set(input.headers.clone());
+                header_policy_state.set(input.header_policy.clone().unwrap_or_default());
 
                 // Load EPG sources
                 epg_sources_state.set(input.epg.as_ref().and_then(|epg| epg.sources.clone()).unwrap_or_default());
@@ -246,6 +268,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
                 input_options_state.dispatch(ConfigInputOptionsFormAction::SetAll(ConfigInputOptionsDto::default()));
                 staged_input_state.dispatch(StagedInputFormAction::SetAll(StagedInputDto::default()));
                 headers_state.set(HashMap::new());
+                header_policy_state.set(HeaderPolicyDto::default());
                 epg_sources_state.set(Vec::new());
                 aliases_state.set(Vec::new());
             }
@@ -371,6 +394,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
 
     let render_options = || {
         html! {
+            <FieldHelpProvider help={config_help.clone()} section={CONFIG_HELP_SECTION_INPUT_OPTIONS}>
             <Card class="tp__config-view__card">
             <TitledCard title={translate.t(LABEL_SKIP)}>
               <div class="tp__config-view__cols-3">
@@ -381,7 +405,9 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
             </TitledCard>
             { edit_field_bool!(input_options_state, translate.t(LABEL_XTREAM_LIVE_STREAM_USE_PREFIX), xtream_live_stream_use_prefix, ConfigInputOptionsFormAction::XtreamLiveStreamUsePrefix) }
             { edit_field_bool!(input_options_state, translate.t(LABEL_XTREAM_LIVE_STREAM_WITHOUT_EXTENSION), xtream_live_stream_without_extension, ConfigInputOptionsFormAction::XtreamLiveStreamWithoutExtension) }
+            { edit_field_bool!(input_options_state, translate.t(LABEL_XTREAM_INCREMENTAL), xtream_incremental, ConfigInputOptionsFormAction::XtreamIncremental) }
             </Card>
+            </FieldHelpProvider>
         }
     };
 
@@ -390,6 +416,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
         let staged_input_state_1 = staged_input_state.clone();
         let staged_input_state_2 = staged_input_state.clone();
         html! {
+            <FieldHelpProvider help={config_help.clone()} section={CONFIG_HELP_SECTION_STAGED_INPUT}>
             <Card class="tp__config-view__card">
                 { edit_field_text!(staged_input_state, translate.t(LABEL_URL),  url, StagedInputFormAction::Url) }
                 <div class="tp__config-view__cols-2">
@@ -436,14 +463,18 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
 
                 //{ edit_field_list!(staged_input_state, translate.t(LABEL_HEADERS), headers, StagedInputFormAction::Headers, translate.t(LABEL_ADD_HEADER)) }
             </Card>
+            </FieldHelpProvider>
         }
     };
 
     let render_input = || {
         let input_method_selection = Rc::new(vec![input_form_state.form.method.to_string()]);
         let input_form_state_disp = input_form_state.clone();
+        let impersonate_selection = Rc::new(input_form_state.form.impersonate.iter().map(ToString::to_string).collect::<Vec<String>>());
+        let input_form_state_impersonate = input_form_state.clone();
 
         html! {
+            <FieldHelpProvider help={config_help.clone()} section={CONFIG_HELP_SECTION_INPUT}>
              <Card class="tp__config-view__card">
                <div class="tp__config-view__cols-2">
                { edit_field_text!(input_form_state, translate.t(LABEL_NAME),  name, ConfigInputFormAction::Name) }
@@ -457,8 +488,11 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
                { edit_field_number_i16!(input_form_state, translate.t(LABEL_PRIORITY), priority, ConfigInputFormAction::Priority) }
                { edit_field_date!(input_form_state, translate.t(LABEL_EXP_DATE), exp_date, ConfigInputFormAction::ExpDate) }
                { edit_field_text_option!(input_form_state, translate.t(LABEL_CACHE_DURATION), cache_duration, ConfigInputFormAction::CacheDuration) }
+               { edit_field_text_option!(input_form_state, translate.t(LABEL_EGRESS_INTERFACE), egress_interface, ConfigInputFormAction::EgressInterface) }
                { config_field_child!(translate.t(LABEL_FETCH_METHOD), {
                    html! {
+                     <>
+                       <FieldHelp name="method" />
                        <RadioButtonGroup
                         multi_select={false} none_allowed={false}
                         on_select={Callback::from(move |selections: Rc<Vec<String>>| {
@@ -469,10 +503,28 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
                         options={fetch_methods.clone()}
                         selected={input_method_selection}
                     />
+                     </>
+               }})}
+               { config_field_child!(translate.t(LABEL_IMPERSONATE), {
+                   html! {
+                     <>
+                       <FieldHelp name="impersonate" />
+                       <RadioButtonGroup
+                        multi_select={false} none_allowed={true}
+                        on_select={Callback::from(move |selections: Rc<Vec<String>>| {
+                            input_form_state_impersonate.dispatch(ConfigInputFormAction::Impersonate(
+                                selections.first().and_then(|first| first.parse::<ImpersonationProfile>().ok())));
+                        })}
+                        options={impersonation_profiles.clone()}
+                        selected={impersonate_selection}
+                    />
+                     </>
                }})}
                </div>
                { edit_field_text_option!(input_form_state, translate.t(LABEL_PERSIST), persist, ConfigInputFormAction::Persist) }
+               { edit_field_list_option!(input_form_state, translate.t(LABEL_MAINTENANCE_WINDOWS), maintenance_windows, ConfigInputFormAction::MaintenanceWindows, translate.t(LABEL_ADD_MAINTENANCE_WINDOW)) }
             </Card>
+            </FieldHelpProvider>
         }
     };
 
@@ -536,6 +588,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
 
     let render_advanced = || {
         let headers = headers_state.clone();
+        let header_policy = header_policy_state.clone();
         let epg_sources = epg_sources_state.clone();
         let show_epg_form = show_epg_form_state.clone();
 
@@ -563,6 +616,19 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
                       }
                   })}
 
+                  // Header Policy Section
+                  { config_field_child!(translate.t(LABEL_HEADER_POLICY), {
+                      let header_policy_set = header_policy.clone();
+                      html! {
+                        <HeaderPolicyEditor
+                            policy={(*header_policy).clone()}
+                            on_change={Callback::from(move |policy: HeaderPolicyDto| {
+                                header_policy_set.set(policy);
+                            })}
+                        />
+                      }
+                  })}
+
                   // EPG Sources Section
                   { config_field_child!(translate.t(LABEL_EPG_SOURCES), {
                       let epg_sources_list = epg_sources.clone();
@@ -609,6 +675,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
         let input_options_state = input_options_state.clone();
         let staged_input_state = staged_input_state.clone();
         let headers_state = headers_state.clone();
+        let header_policy_state = header_policy_state.clone();
         let epg_sources_state = epg_sources_state.clone();
         let aliases_state = aliases_state.clone();
 
@@ -631,12 +698,17 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
 
             // Handle Headers
             input.headers = (*headers_state).clone();
+            input.header_policy = if header_policy_state.is_empty() {
+                None
+            } else {
+                Some((*header_policy_state).clone())
+            };
 
             // Handle EPG: update sources but preserve other fields if present
             let epg_sources = (*epg_sources_state).clone();
             if let Some(mut epg_cfg) = input.epg.take() {
                 epg_cfg.sources = if epg_sources.is_empty() { None } else { Some(epg_sources) };
-                input.epg = if epg_cfg.sources.is_some() || epg_cfg.smart_match.is_some() {
+                input.epg = if epg_cfg.sources.is_some() || epg_cfg.smart_match.is_some() || epg_cfg.tvg_id_normalization.is_some() {
                     Some(epg_cfg)
                 } else {
                     None
@@ -11,10 +11,12 @@ mod output_xtream_form;
 mod output_m3u_form;
 mod output_strm_form;
 mod output_hdhomerun_form;
+mod output_plex_form;
 mod layout;
 mod epg_source_item_form;
 mod alias_item_form;
 mod trakt_list_item_form;
+mod header_policy_editor;
 
 pub(crate) use editor_view::*;
 pub(crate) use editor_model::*;
@@ -29,6 +31,8 @@ pub(crate) use output_xtream_form::*;
 pub(crate) use output_m3u_form::*;
 pub(crate) use output_strm_form::*;
 pub(crate) use output_hdhomerun_form::*;
+pub(crate) use output_plex_form::*;
 pub(crate) use epg_source_item_form::*;
 pub(crate) use alias_item_form::*;
-pub(crate) use trakt_list_item_form::*;
\ No newline at end of file
+pub(crate) use trakt_list_item_form::*;
+pub(crate) use header_policy_editor::*;
\ No newline at end of file
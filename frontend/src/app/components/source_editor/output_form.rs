@@ -1,4 +1,4 @@
-use crate::app::components::{BlockId, BlockType, EditMode, SourceEditorContext, XtreamTargetOutputView, M3uTargetOutputView, StrmTargetOutputView, HdHomeRunTargetOutputView};
+use crate::app::components::{BlockId, BlockType, EditMode, SourceEditorContext, XtreamTargetOutputView, M3uTargetOutputView, StrmTargetOutputView, HdHomeRunTargetOutputView, PlexTargetOutputView};
 use shared::model::TargetOutputDto;
 use std::rc::Rc;
 use yew::{function_component, html, use_context, Html, Properties};
@@ -46,6 +46,14 @@ pub fn ConfigOutputView(props: &ConfigOutputViewProps) -> Html {
 
                     html! { <HdHomeRunTargetOutputView block_id={block_id} output={output} /> }
                 }
+                BlockType::OutputPlex => {
+                    let output = props.output.as_ref()
+                        .and_then(|to| if let TargetOutputDto::Plex(plex) = &**to {
+                        Some(Rc::new(plex.clone()))
+                    } else { None });
+
+                    html! { <PlexTargetOutputView block_id={block_id} output={output} /> }
+                }
                 BlockType::OutputStrm => {
                     let output = props.output.as_ref()
                         .and_then(|to| if let TargetOutputDto::Strm(strm) = &**to {
@@ -1,8 +1,8 @@
 use crate::app::components::config::HasFormData;
 use crate::app::components::select::Select;
-use crate::app::components::{BlockId, BlockInstance, Card, DropDownOption, DropDownSelection, EditMode, SourceEditorContext, TextButton};
+use crate::app::components::{BlockId, BlockInstance, Card, DropDownOption, DropDownSelection, EditMode, FieldHelpProvider, SourceEditorContext, TextButton};
 use crate::{config_field_child, edit_field_text, generate_form_reducer};
-use shared::model::{HdHomeRunTargetOutputDto, TargetOutputDto, TargetType};
+use shared::model::{HdHomeRunTargetOutputDto, TargetOutputDto, TargetType, CONFIG_HELP_SECTION_HDHOMERUN_OUTPUT};
 use std::rc::Rc;
 use yew::{function_component, html, use_context, use_effect_with, use_memo, use_reducer, Callback, Html, Properties, UseReducerHandle};
 use yew_i18n::use_translation;
@@ -69,6 +69,7 @@ pub fn HdHomeRunTargetOutputView(props: &HdHomeRunTargetOutputViewProps) -> Html
     let render_output = || {
         let output_form_state_1 = output_form_state.clone();
         html! {
+            <FieldHelpProvider help={source_editor_ctx.config_help.clone()} section={CONFIG_HELP_SECTION_HDHOMERUN_OUTPUT}>
             <Card class="tp__config-view__card">
                 { edit_field_text!(output_form_state, translate.t(LABEL_DEVICE), device, HdHomeRunTargetOutputFormAction::Device) }
                 { edit_field_text!(output_form_state, translate.t(LABEL_USERNAME), username, HdHomeRunTargetOutputFormAction::Username) }
@@ -97,6 +98,7 @@ pub fn HdHomeRunTargetOutputView(props: &HdHomeRunTargetOutputViewProps) -> Html
                     }
                 })}
             </Card>
+            </FieldHelpProvider>
         }
     };
 
@@ -1,7 +1,7 @@
 use crate::app::components::config::HasFormData;
-use crate::app::components::{BlockId, BlockInstance, Card, EditMode, FilterInput, SourceEditorContext, TextButton};
+use crate::app::components::{BlockId, BlockInstance, Card, EditMode, FieldHelp, FieldHelpProvider, FilterInput, SourceEditorContext, TextButton};
 use crate::{config_field_child, edit_field_bool, edit_field_text_option, generate_form_reducer};
-use shared::model::{M3uTargetOutputDto, TargetOutputDto};
+use shared::model::{M3uTargetOutputDto, TargetOutputDto, CONFIG_HELP_SECTION_M3U_OUTPUT};
 use std::rc::Rc;
 use yew::{function_component, html, use_context, use_effect_with, use_reducer, Callback, Html, Properties, UseReducerHandle};
 use yew_i18n::use_translation;
@@ -9,6 +9,8 @@ use yew_i18n::use_translation;
 const LABEL_FILENAME: &str = "LABEL.FILENAME";
 const LABEL_INCLUDE_TYPE_IN_URL: &str = "LABEL.INCLUDE_TYPE_IN_URL";
 const LABEL_MASK_REDIRECT_URL: &str = "LABEL.MASK_REDIRECT_URL";
+const LABEL_WATERMARK: &str = "LABEL.WATERMARK";
+const LABEL_HLS_OUTPUT: &str = "LABEL.HLS_OUTPUT";
 const LABEL_FILTER: &str = "LABEL.FILTER";
 
 generate_form_reducer!(
@@ -18,6 +20,8 @@ generate_form_reducer!(
         Filename => filename: Option<String>,
         IncludeTypeInUrl => include_type_in_url: bool,
         MaskRedirectUrl => mask_redirect_url: bool,
+        Watermark => watermark: bool,
+        Hls => hls: bool,
         Filter => filter: Option<String>,
     }
 );
@@ -56,18 +60,25 @@ pub fn M3uTargetOutputView(props: &M3uTargetOutputViewProps) -> Html {
     let render_output = || {
         let output_form_state_1 = output_form_state.clone();
         html! {
+            <FieldHelpProvider help={source_editor_ctx.config_help.clone()} section={CONFIG_HELP_SECTION_M3U_OUTPUT}>
             <Card class="tp__config-view__card">
                 { edit_field_text_option!(output_form_state, translate.t(LABEL_FILENAME), filename, M3uTargetOutputFormAction::Filename) }
                 { edit_field_bool!(output_form_state, translate.t(LABEL_INCLUDE_TYPE_IN_URL), include_type_in_url, M3uTargetOutputFormAction::IncludeTypeInUrl) }
                 { edit_field_bool!(output_form_state, translate.t(LABEL_MASK_REDIRECT_URL), mask_redirect_url, M3uTargetOutputFormAction::MaskRedirectUrl) }
+                { edit_field_bool!(output_form_state, translate.t(LABEL_WATERMARK), watermark, M3uTargetOutputFormAction::Watermark) }
+                { edit_field_bool!(output_form_state, translate.t(LABEL_HLS_OUTPUT), hls, M3uTargetOutputFormAction::Hls) }
                 { config_field_child!(translate.t(LABEL_FILTER), {
                        html! {
+                            <>
+                            <FieldHelp name="filter" />
                             <FilterInput filter={output_form_state_1.form.filter.clone()} on_change={Callback::from(move |new_filter| {
                                 output_form_state_1.dispatch(M3uTargetOutputFormAction::Filter(new_filter));
                             })} />
+                            </>
                        }
                 })}
             </Card>
+            </FieldHelpProvider>
         }
     };
 
@@ -0,0 +1,173 @@
+use crate::app::components::config::HasFormData;
+use crate::app::components::select::Select;
+use crate::app::components::{BlockId, BlockInstance, Card, DropDownOption, DropDownSelection, EditMode, FieldHelp, FieldHelpProvider, SourceEditorContext, Tag, TagList, TextButton};
+use crate::{config_field_child, edit_field_bool, edit_field_number, edit_field_text, generate_form_reducer};
+use shared::model::{PlexChannelRangeDto, PlexTargetOutputDto, TargetOutputDto, TargetType, CONFIG_HELP_SECTION_PLEX_OUTPUT};
+use std::rc::Rc;
+use yew::{function_component, html, use_context, use_effect_with, use_memo, use_reducer, Callback, Html, Properties, UseReducerHandle};
+use yew_i18n::use_translation;
+
+const LABEL_DEVICE: &str = "LABEL.DEVICE";
+const LABEL_USERNAME: &str = "LABEL.USERNAME";
+const LABEL_USE_OUTPUT: &str = "LABEL.USE_OUTPUT";
+const LABEL_CHANNEL_START: &str = "LABEL.CHANNEL_START";
+const LABEL_CHANNEL_RANGES: &str = "LABEL.CHANNEL_RANGES";
+const LABEL_USE_CHANNEL_NUMBERS: &str = "LABEL.USE_CHANNEL_NUMBERS";
+const LABEL_ADD_CHANNEL_RANGE: &str = "LABEL.ADD_CHANNEL_RANGE";
+
+generate_form_reducer!(
+    state: PlexTargetOutputFormState { form: PlexTargetOutputDto },
+    action_name: PlexTargetOutputFormAction,
+    fields {
+        Device => device: String,
+        Username => username: String,
+        UseOutput => use_output: Option<TargetType>,
+        ChannelStart => channel_start: u32,
+        ChannelRanges => channel_ranges: Option<Vec<PlexChannelRangeDto>>,
+        UseChannelNumbers => use_channel_numbers: bool,
+    }
+);
+
+/// Channel ranges are edited as `start:group` tags.
+fn parse_channel_range(text: &str) -> Option<PlexChannelRangeDto> {
+    let (start, group) = text.split_once(':')?;
+    let start = start.trim().parse::<u32>().ok().filter(|start| *start > 0)?;
+    let group = group.trim();
+    (!group.is_empty()).then(|| PlexChannelRangeDto { group: group.to_string(), start })
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct PlexTargetOutputViewProps {
+    pub(crate) block_id: BlockId,
+    pub(crate) output: Option<Rc<PlexTargetOutputDto>>,
+}
+
+#[function_component]
+pub fn PlexTargetOutputView(props: &PlexTargetOutputViewProps) -> Html {
+    let translate = use_translation();
+    let source_editor_ctx = use_context::<SourceEditorContext>().expect("SourceEditorContext not found");
+
+    let output_form_state: UseReducerHandle<PlexTargetOutputFormState> =
+        use_reducer(|| PlexTargetOutputFormState {
+            form: PlexTargetOutputDto::default(),
+            modified: false,
+        });
+
+    let target_types = use_memo(output_form_state.form.use_output, |use_output| {
+        let default_type = use_output.unwrap_or(TargetType::M3u);
+        [
+            TargetType::M3u,
+            TargetType::Xtream,
+        ]
+            .iter()
+            .map(|t| DropDownOption {
+                id: t.to_string(),
+                label: html! { t.to_string() },
+                selected: *t == default_type,
+            }).collect::<Vec<DropDownOption>>()
+    });
+
+    {
+        let output_form_state = output_form_state.clone();
+        let config_output = props.output.clone();
+
+        use_effect_with(config_output, move |cfg| {
+            if let Some(output) = cfg {
+                output_form_state.dispatch(PlexTargetOutputFormAction::SetAll(output.as_ref().clone()));
+            } else {
+                output_form_state.dispatch(PlexTargetOutputFormAction::SetAll(PlexTargetOutputDto::default()));
+            }
+            || ()
+        });
+    }
+
+    let render_output = || {
+        let output_form_state_1 = output_form_state.clone();
+        let output_form_state_2 = output_form_state.clone();
+        let channel_range_tags = output_form_state.form.channel_ranges.as_ref().map_or_else(Vec::new, |ranges| ranges.iter()
+            .map(|range| Rc::new(Tag { label: format!("{}:{}", range.start, range.group), class: None }))
+            .collect::<Vec<Rc<Tag>>>());
+        html! {
+            <FieldHelpProvider help={source_editor_ctx.config_help.clone()} section={CONFIG_HELP_SECTION_PLEX_OUTPUT}>
+            <Card class="tp__config-view__card">
+                { edit_field_text!(output_form_state, translate.t(LABEL_DEVICE), device, PlexTargetOutputFormAction::Device) }
+                { edit_field_text!(output_form_state, translate.t(LABEL_USERNAME), username, PlexTargetOutputFormAction::Username) }
+                { config_field_child!(translate.t(LABEL_USE_OUTPUT), {
+                    html! {
+                        <Select
+                            name={"use_output"}
+                            multi_select={false}
+                            on_select={Callback::from(move |(_, selections):(String, DropDownSelection)| {
+                                match selections {
+                                    DropDownSelection::Empty => {
+                                        output_form_state_1.dispatch(PlexTargetOutputFormAction::UseOutput(Some(TargetType::M3u)));
+                                    }
+                                    DropDownSelection::Single(option) => {
+                                        output_form_state_1.dispatch(PlexTargetOutputFormAction::UseOutput(Some(option.parse::<TargetType>().unwrap_or(TargetType::M3u))));
+                                    }
+                                    DropDownSelection::Multi(options) => {
+                                        if let Some(first) = options.first() {
+                                            output_form_state_1.dispatch(PlexTargetOutputFormAction::UseOutput(Some(first.parse::<TargetType>().unwrap_or(TargetType::M3u))));
+                                        }
+                                    }
+                                }
+                            })}
+                            options={target_types.clone()}
+                        />
+                    }
+                })}
+                { edit_field_number!(output_form_state, translate.t(LABEL_CHANNEL_START), channel_start, PlexTargetOutputFormAction::ChannelStart) }
+                <div class="tp__form-field tp__form-field__list">
+                    <label>{translate.t(LABEL_CHANNEL_RANGES)}</label>
+                    <TagList
+                         tags={channel_range_tags}
+                         placeholder={translate.t(LABEL_ADD_CHANNEL_RANGE)}
+                         readonly={false}
+                         on_change={Callback::from(move |value: Vec<Rc<Tag>>| {
+                            let ranges: Vec<PlexChannelRangeDto> = value.iter().filter_map(|t| parse_channel_range(&t.label)).collect();
+                            output_form_state_2.dispatch(PlexTargetOutputFormAction::ChannelRanges(if ranges.is_empty() { None } else { Some(ranges) }));
+                         })}/>
+                    <FieldHelp name={"channel_ranges"} />
+                </div>
+                { edit_field_bool!(output_form_state, translate.t(LABEL_USE_CHANNEL_NUMBERS), use_channel_numbers, PlexTargetOutputFormAction::UseChannelNumbers) }
+            </Card>
+            </FieldHelpProvider>
+        }
+    };
+
+    let handle_apply = {
+        let source_editor_ctx = source_editor_ctx.clone();
+        let output_form_state = output_form_state.clone();
+        let block_id = props.block_id;
+        Callback::from(move |_| {
+            let output = output_form_state.data().clone();
+            source_editor_ctx.on_form_change.emit((block_id, BlockInstance::Output(Rc::new(TargetOutputDto::Plex(output)))));
+            source_editor_ctx.edit_mode.set(EditMode::Inactive);
+        })
+    };
+
+    let handle_cancel = {
+        let source_editor_ctx = source_editor_ctx.clone();
+        Callback::from(move |_| {
+            source_editor_ctx.edit_mode.set(EditMode::Inactive);
+        })
+    };
+
+    html! {
+        <div class="tp__source-editor-form tp__config-view-page">
+            <div class="tp__source-editor-form__toolbar tp__form-page__toolbar">
+                <TextButton class="secondary" name="cancel_plex_output"
+                    icon="Cancel"
+                    title={ translate.t("LABEL.CANCEL")}
+                    onclick={handle_cancel}></TextButton>
+                <TextButton class="primary" name="apply_plex_output"
+                    icon="Accept"
+                    title={ translate.t("LABEL.OK")}
+                    onclick={handle_apply}></TextButton>
+            </div>
+            <div class="tp__input-form__body">
+                { render_output() }
+            </div>
+        </div>
+    }
+}
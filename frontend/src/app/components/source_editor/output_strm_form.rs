@@ -1,8 +1,8 @@
 use crate::app::components::config::HasFormData;
 use crate::app::components::select::Select;
-use crate::app::components::{BlockId, BlockInstance, Card, DropDownOption, DropDownSelection, EditMode, FilterInput, IconButton, Panel, SourceEditorContext, TextButton};
+use crate::app::components::{BlockId, BlockInstance, Card, DropDownOption, DropDownSelection, EditMode, FieldHelp, FieldHelpProvider, FilterInput, IconButton, Panel, SourceEditorContext, TextButton};
 use crate::{config_field_child, edit_field_bool, edit_field_list_option, edit_field_text, edit_field_text_option, generate_form_reducer};
-use shared::model::{StrmExportStyle, StrmTargetOutputDto, TargetOutputDto};
+use shared::model::{StrmExportStyle, StrmTargetOutputDto, TargetOutputDto, CONFIG_HELP_SECTION_STRM_OUTPUT};
 use std::fmt::Display;
 use std::rc::Rc;
 use std::str::FromStr;
@@ -109,6 +109,7 @@ pub fn StrmTargetOutputView(props: &StrmTargetOutputViewProps) -> Html {
             StrmExportStyle::Plex,
             StrmExportStyle::Emby,
             StrmExportStyle::Jellyfin,
+            StrmExportStyle::Arr,
         ]
             .iter()
             .map(|s| DropDownOption {
@@ -136,6 +137,7 @@ pub fn StrmTargetOutputView(props: &StrmTargetOutputViewProps) -> Html {
         let output_form_state_1 = output_form_state.clone();
         let output_form_state_2 = output_form_state.clone();
         html! {
+            <FieldHelpProvider help={source_editor_ctx.config_help.clone()} section={CONFIG_HELP_SECTION_STRM_OUTPUT}>
             <Card class="tp__config-view__card">
                 { edit_field_text!(output_form_state, translate.t(LABEL_DIRECTORY), directory, StrmTargetOutputFormAction::Directory) }
                 { edit_field_text_option!(output_form_state, translate.t(LABEL_USERNAME), username, StrmTargetOutputFormAction::Username) }
@@ -165,17 +167,22 @@ pub fn StrmTargetOutputView(props: &StrmTargetOutputViewProps) -> Html {
                 })}
                 { config_field_child!(translate.t(LABEL_FILTER), {
                    html! {
+                        <>
+                        <FieldHelp name="filter" />
                         <FilterInput filter={output_form_state_2.form.filter.clone()} on_change={Callback::from(move |new_filter| {
                             output_form_state_2.dispatch(StrmTargetOutputFormAction::Filter(new_filter));
                         })} />
+                        </>
                    }
                 })}
             </Card>
+            </FieldHelpProvider>
         }
     };
 
     let render_options = || {
         html! {
+            <FieldHelpProvider help={source_editor_ctx.config_help.clone()} section={CONFIG_HELP_SECTION_STRM_OUTPUT}>
             <Card class="tp__config-view__card">
                 { edit_field_bool!(output_form_state, translate.t(LABEL_FLAT), flat, StrmTargetOutputFormAction::Flat) }
                 { edit_field_bool!(output_form_state, translate.t(LABEL_UNDERSCORE_WHITESPACE), underscore_whitespace, StrmTargetOutputFormAction::UnderscoreWhitespace) }
@@ -183,6 +190,7 @@ pub fn StrmTargetOutputView(props: &StrmTargetOutputViewProps) -> Html {
                 { edit_field_bool!(output_form_state, translate.t(LABEL_ADD_QUALITY_TO_FILENAME), add_quality_to_filename, StrmTargetOutputFormAction::AddQualityToFilename) }
                 { edit_field_list_option!(output_form_state, translate.t(LABEL_STRM_PROPS), strm_props, StrmTargetOutputFormAction::StrmProps, translate.t(LABEL_ADD_PROPERTY)) }
             </Card>
+            </FieldHelpProvider>
         }
     };
 
@@ -1,7 +1,7 @@
 use crate::app::components::config::HasFormData;
-use crate::app::components::{BlockId, BlockInstance, Card, EditMode, IconButton, Panel, SourceEditorContext, TextButton, TraktListItemForm, TitledCard, FilterInput};
-use crate::{config_field_child, edit_field_bool, edit_field_number_u16, edit_field_text, generate_form_reducer};
-use shared::model::{TargetOutputDto, TraktApiConfigDto, TraktConfigDto, TraktContentType, TraktListConfigDto, XtreamTargetOutputDto};
+use crate::app::components::{BlockId, BlockInstance, Card, EditMode, FieldHelp, FieldHelpProvider, IconButton, Panel, SourceEditorContext, TextButton, TraktListItemForm, TitledCard, FilterInput};
+use crate::{config_field_child, edit_field_bool, edit_field_number_u16, edit_field_text, edit_field_text_option, generate_form_reducer};
+use shared::model::{TargetOutputDto, TraktApiConfigDto, TraktConfigDto, TraktContentType, TraktListConfigDto, XtreamTargetOutputDto, CONFIG_HELP_SECTION_XTREAM_OUTPUT};
 use std::fmt::Display;
 use std::rc::Rc;
 use std::str::FromStr;
@@ -28,6 +28,7 @@ const LABEL_USER_AGENT: &str = "LABEL.API_USER_AGENT";
 const LABEL_MAIN: &str = "LABEL.MAIN_CONFIG";
 const LABEL_TRAKT: &str = "LABEL.TRAKT";
 const LABEL_ENABLED: &str = "LABEL.ENABLED";
+const LABEL_SCHEDULE: &str = "LABEL.SCHEDULE";
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum XtreamOutputFormPage {
@@ -66,6 +67,7 @@ generate_form_reducer!(
     action_name: TraktConfigFormAction,
     fields {
         Enabled => enabled: bool,
+        Schedule => schedule: Option<String>,
     }
 );
 
@@ -215,6 +217,7 @@ pub fn XtreamTargetOutputView(props: &XtreamTargetOutputViewProps) -> Html {
     let render_output = || {
         let output_form_state_1 = output_form_state.clone();
         html! {
+            <FieldHelpProvider help={source_editor_ctx.config_help.clone()} section={CONFIG_HELP_SECTION_XTREAM_OUTPUT}>
             <Card class="tp__config-view__card">
                 <TitledCard title={translate.t(LABEL_SKIP_DIRECT_SOURCE)}>
                   <div class="tp__config-view__cols-3">
@@ -237,12 +240,16 @@ pub fn XtreamTargetOutputView(props: &XtreamTargetOutputViewProps) -> Html {
                 </TitledCard>
                 { config_field_child!(translate.t(LABEL_FILTER), {
                        html! {
+                            <>
+                            <FieldHelp name="filter" />
                             <FilterInput filter={output_form_state_1.form.filter.clone()} on_change={Callback::from(move |new_filter| {
                                 output_form_state_1.dispatch(XtreamTargetOutputFormAction::Filter(new_filter));
                             })} />
+                            </>
                        }
                 })}
             </Card>
+            </FieldHelpProvider>
         }
     };
 
@@ -262,6 +269,7 @@ pub fn XtreamTargetOutputView(props: &XtreamTargetOutputViewProps) -> Html {
                 } else {
                 // Trakt API Configuration
                 { edit_field_bool!(trakt_form, translate.t(LABEL_ENABLED), enabled, TraktConfigFormAction::Enabled) }
+                { edit_field_text_option!(trakt_form, translate.t(LABEL_SCHEDULE), schedule, TraktConfigFormAction::Schedule) }
                 <div class="tp__form-section">
                     <h3>{translate.t(LABEL_API_CONFIGURATION)}</h3>
                     { edit_field_text!(trakt_api_form, translate.t(LABEL_TRAKT_API_KEY), api_key, TraktApiConfigFormAction::ApiKey) }
@@ -377,6 +385,7 @@ pub fn XtreamTargetOutputView(props: &XtreamTargetOutputViewProps) -> Html {
                     enabled: trakt_state.data().enabled,
                     api: trakt_api_state.data().clone(),
                     lists: trakt_lists,
+                    schedule: trakt_state.data().schedule.clone(),
                 })
             };
 
@@ -7,7 +7,7 @@ use crate::app::components::{Block, BlockType, Connection};
 /// Target can connect to:
 ///   - 1x OutputM3u
 ///   - 1x OutputXtream
-///   - 1x OutputHdhomerun
+///   - 1x OutputHdhomerun or OutputPlex
 ///   - up to 4x OutputStrm
 pub fn can_connect(from_block: &Block, to_block: &Block, connections: &[Connection], blocks: &[Block]) -> bool {
     // Prevent self-connection
@@ -55,7 +55,7 @@ pub fn can_connect(from_block: &Block, to_block: &Block, connections: &[Connecti
                 match out_block.block_type {
                     BlockType::OutputM3u => count_m3u += 1,
                     BlockType::OutputXtream => count_xtream += 1,
-                    BlockType::OutputHdHomeRun => count_hdhomerun += 1,
+                    BlockType::OutputHdHomeRun | BlockType::OutputPlex => count_hdhomerun += 1,
                     BlockType::OutputStrm => count_strm += 1,
                     _ => {}
                 }
@@ -65,7 +65,7 @@ pub fn can_connect(from_block: &Block, to_block: &Block, connections: &[Connecti
         match to_block.block_type {
             BlockType::OutputM3u if count_m3u >= 1 => return false,
             BlockType::OutputXtream if count_xtream >= 1 => return false,
-            BlockType::OutputHdHomeRun if count_hdhomerun >= 1 => return false,
+            BlockType::OutputHdHomeRun | BlockType::OutputPlex if count_hdhomerun >= 1 => return false,
             BlockType::OutputStrm if count_strm >= 4 => return false,
             _ => {}
         }
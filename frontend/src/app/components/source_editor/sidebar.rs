@@ -14,10 +14,11 @@ pub const BLOCK_TYPES_TARGET: [BlockType; 1] = [
     BlockType::Target,
 ];
 
-pub const BLOCK_TYPES_OUTPUT: [BlockType; 4] = [
+pub const BLOCK_TYPES_OUTPUT: [BlockType; 5] = [
     BlockType::OutputM3u,
     BlockType::OutputXtream,
     BlockType::OutputHdHomeRun,
+    BlockType::OutputPlex,
     BlockType::OutputStrm];
 
 
@@ -1,8 +1,9 @@
 use crate::app::components::config::HasFormData;
 use crate::app::components::select::Select;
-use crate::app::components::{BlockId, BlockInstance, Card, ClusterFlagsInput, ClusterFlagsInputMode, DropDownOption, DropDownSelection, EditMode, FilterInput, IconButton, Panel, SourceEditorContext, TextButton};
-use crate::{config_field_child, edit_field_bool, edit_field_list_option, edit_field_text, generate_form_reducer};
-use shared::model::{ClusterFlags, ConfigTargetDto, ConfigTargetOptions, ProcessingOrder};
+use crate::app::components::{BlockId, BlockInstance, Card, ClusterFlagsInput, ClusterFlagsInputMode, DropDownOption, DropDownSelection, EditMode, FieldHelp, FieldHelpProvider, FilterInput, IconButton, Panel, SourceEditorContext, TextButton};
+use crate::{config_field_child, edit_field_bool, edit_field_list_option, edit_field_number_u16, edit_field_text, edit_field_text_option, generate_form_reducer};
+use crate::app::ConfigContext;
+use shared::model::{ClusterFlags, ConfigSortDto, ConfigTargetDto, ConfigTargetOptions, ProcessingOrder, CONFIG_HELP_SECTION_TARGET, CONFIG_HELP_SECTION_TARGET_OPTIONS};
 use std::fmt::Display;
 use std::rc::Rc;
 use std::str::FromStr;
@@ -19,11 +20,20 @@ const LABEL_WATCH: &str = "LABEL.WATCH";
 const LABEL_ADD_MAPPING: &str = "LABEL.ADD_MAPPING";
 const LABEL_ADD_WATCH: &str = "LABEL.ADD_WATCH";
 const LABEL_USE_MEMORY_CACHE: &str = "LABEL.USE_MEMORY_CACHE";
+const LABEL_SHADOW_OF: &str = "LABEL.SHADOW_OF";
 const LABEL_PROCESSING_ORDER: &str = "LABEL.PROCESSING_ORDER";
+const LABEL_SORT_PRESET: &str = "LABEL.SORT_PRESET";
 const LABEL_IGNORE_LOGO: &str = "LABEL.IGNORE_LOGO";
 const LABEL_SHARE_LIVE_STREAMS: &str = "LABEL.SHARE_LIVE_STREAMS";
 const LABEL_REMOVE_DUPLICATES: &str = "LABEL.REMOVE_DUPLICATES";
+const LABEL_REMOVE_DEAD_CHANNELS: &str = "LABEL.REMOVE_DEAD_CHANNELS";
+const LABEL_CACHE_WARMUP: &str = "LABEL.CACHE_WARMUP";
+const LABEL_GROUP_SERIES: &str = "LABEL.GROUP_SERIES";
+const LABEL_COLLAPSE_VARIANTS: &str = "LABEL.COLLAPSE_VARIANTS";
+const LABEL_TIMEZONE: &str = "LABEL.TIMEZONE";
 const LABEL_FORCE_REDIRECT: &str = "LABEL.FORCE_REDIRECT";
+const LABEL_EPG_LANGUAGES: &str = "LABEL.EPG_LANGUAGES";
+const LABEL_ADD_EPG_LANGUAGE: &str = "LABEL.ADD_EPG_LANGUAGE";
 const LABEL_MAIN: &str = "LABEL.MAIN_CONFIG";
 const LABEL_OPTIONS: &str = "LABEL.OPTIONS";
 
@@ -60,7 +70,6 @@ impl Display for TargetFormPage {
 }
 
 
-// pub sort: Option<ConfigSortDto>,
 // pub rename: Option<Vec<ConfigRenameDto>>,
 // pub favourites: Option<Vec<ConfigFavouritesDto>>,
 
@@ -71,7 +80,13 @@ generate_form_reducer!(
         IgnoreLogo => ignore_logo: bool,
         ShareLiveStreams => share_live_streams: bool,
         RemoveDuplicates => remove_duplicates: bool,
+        RemoveDeadChannels => remove_dead_channels: u16,
+        CacheWarmup => cache_warmup: bool,
+        GroupSeries => group_series: bool,
+        CollapseVariants => collapse_variants: bool,
+        Timezone => timezone: Option<String>,
         ForceRedirect => force_redirect: Option<ClusterFlags>,
+        EpgLanguages => epg_languages: Option<Vec<String>>,
     }
 );
 
@@ -86,9 +101,19 @@ generate_form_reducer!(
         Mapping => mapping: Option<Vec<String>>,
         Watch => watch: Option<Vec<String>>,
         UseMemoryCache => use_memory_cache: bool,
+        ShadowOf => shadow_of: Option<String>,
+        Sort => sort: Option<ConfigSortDto>,
     }
 );
 
+/// Sets the preset of the sort, a sort without preset and own settings is removed.
+fn set_sort_preset(sort: Option<&ConfigSortDto>, preset: Option<String>) -> Option<ConfigSortDto> {
+    let mut sort = sort.cloned().unwrap_or_default();
+    sort.preset = preset;
+    sort.t_preset = None;
+    (sort != ConfigSortDto::default()).then_some(sort)
+}
+
 #[derive(Properties, PartialEq, Clone)]
 pub struct ConfigTargetViewProps {
     pub(crate) block_id: BlockId,
@@ -99,6 +124,7 @@ pub struct ConfigTargetViewProps {
 pub fn ConfigTargetView(props: &ConfigTargetViewProps) -> Html {
     let translate = use_translation();
     let source_editor_ctx = use_context::<SourceEditorContext>().expect("SourceEditorContext not found");
+    let config_ctx = use_context::<ConfigContext>().expect("ConfigContext not found");
 
     let target_form_state: UseReducerHandle<ConfigTargetFormState> =
         use_reducer(|| ConfigTargetFormState {
@@ -142,6 +168,20 @@ pub fn ConfigTargetView(props: &ConfigTargetViewProps) -> Html {
             }).collect::<Vec<DropDownOption>>()
     });
 
+    let sort_presets = use_memo((config_ctx.config.clone(), target_form_state.clone()), |(config, target_state)| {
+        let selected = target_state.form.sort.as_ref().and_then(|sort| sort.preset.clone()).unwrap_or_default();
+        std::iter::once(String::new())
+            .chain(config.as_ref()
+                .and_then(|cfg| cfg.sources.sort_presets.as_ref())
+                .map(|presets| presets.iter().map(|preset| preset.name.clone()).collect::<Vec<_>>())
+                .unwrap_or_default())
+            .map(|name| DropDownOption {
+                selected: name == selected,
+                label: if name.is_empty() { html! { "-" } } else { html! { name.clone() } },
+                id: name,
+            }).collect::<Vec<DropDownOption>>()
+    });
+
     {
         let target_form_state = target_form_state.clone();
         let target_options_state = target_options_state.clone();
@@ -166,14 +206,22 @@ pub fn ConfigTargetView(props: &ConfigTargetViewProps) -> Html {
     let render_options = || {
         let target_options_state_1 = target_options_state.clone();
         html! {
+            <FieldHelpProvider help={source_editor_ctx.config_help.clone()} section={CONFIG_HELP_SECTION_TARGET_OPTIONS}>
             <Card class="tp__config-view__card">
             <div class="tp__config-view__cols-2">
             { edit_field_bool!(target_options_state, translate.t(LABEL_IGNORE_LOGO), ignore_logo,  ConfigTargetOptionsFormAction::IgnoreLogo) }
             { edit_field_bool!(target_options_state, translate.t(LABEL_SHARE_LIVE_STREAMS), share_live_streams, ConfigTargetOptionsFormAction::ShareLiveStreams) }
             </div>
             { edit_field_bool!(target_options_state, translate.t(LABEL_REMOVE_DUPLICATES), remove_duplicates, ConfigTargetOptionsFormAction::RemoveDuplicates) }
+            { edit_field_number_u16!(target_options_state, translate.t(LABEL_REMOVE_DEAD_CHANNELS), remove_dead_channels, ConfigTargetOptionsFormAction::RemoveDeadChannels) }
+            { edit_field_bool!(target_options_state, translate.t(LABEL_CACHE_WARMUP), cache_warmup, ConfigTargetOptionsFormAction::CacheWarmup) }
+            { edit_field_bool!(target_options_state, translate.t(LABEL_GROUP_SERIES), group_series, ConfigTargetOptionsFormAction::GroupSeries) }
+            { edit_field_bool!(target_options_state, translate.t(LABEL_COLLAPSE_VARIANTS), collapse_variants, ConfigTargetOptionsFormAction::CollapseVariants) }
+            { edit_field_text_option!(target_options_state, translate.t(LABEL_TIMEZONE), timezone, ConfigTargetOptionsFormAction::Timezone) }
             { config_field_child!(translate.t(LABEL_FORCE_REDIRECT), {
                html! {
+                  <>
+                    <FieldHelp name="force_redirect" />
                     <ClusterFlagsInput
                         name="force_redirect"
                         value={target_options_state.form.force_redirect}
@@ -181,32 +229,45 @@ pub fn ConfigTargetView(props: &ConfigTargetViewProps) -> Html {
                         on_change={Callback::from(move |(_name, flags):(String, Option<ClusterFlags>)| {
                         target_options_state_1.dispatch(ConfigTargetOptionsFormAction::ForceRedirect(flags));
                     })}
-                />
+                    />
+                  </>
             }})}
+            { edit_field_list_option!(target_options_state, translate.t(LABEL_EPG_LANGUAGES), epg_languages, ConfigTargetOptionsFormAction::EpgLanguages, translate.t(LABEL_ADD_EPG_LANGUAGE)) }
             </Card>
+            </FieldHelpProvider>
         }
     };
 
     let render_target = || {
         let target_form_state_1 = target_form_state.clone();
         let target_form_state_2 = target_form_state.clone();
+        let target_form_state_3 = target_form_state.clone();
         html! {
+            <FieldHelpProvider help={source_editor_ctx.config_help.clone()} section={CONFIG_HELP_SECTION_TARGET}>
             <Card class="tp__config-view__card">
             <div class="tp__config-view__cols-2">
             { edit_field_bool!(target_form_state, translate.t(LABEL_ENABLED), enabled,  ConfigTargetFormAction::Enabled) }
             { edit_field_bool!(target_form_state, translate.t(LABEL_USE_MEMORY_CACHE), use_memory_cache,  ConfigTargetFormAction::UseMemoryCache) }
             </div>
             { edit_field_text!(target_form_state, translate.t(LABEL_NAME), name, ConfigTargetFormAction::Name) }
+            { edit_field_text_option!(target_form_state, translate.t(LABEL_SHADOW_OF), shadow_of, ConfigTargetFormAction::ShadowOf) }
             { config_field_child!(translate.t(LABEL_FILTER), {
                    html! {
-                        <FilterInput filter={target_form_state_2.form.filter.clone()} on_change={Callback::from(move |new_filter: Option<String>| {
+                      <>
+                        <FieldHelp name="filter" />
+                        <FilterInput filter={target_form_state_2.form.filter.clone()}
+                            target={Some(target_form_state_2.form.name.clone()).filter(|name| !name.is_empty())}
+                            on_change={Callback::from(move |new_filter: Option<String>| {
                             target_form_state_2.dispatch(ConfigTargetFormAction::Filter(new_filter.unwrap_or_default()));
                         })} />
+                      </>
                    }
             })}
 
             { config_field_child!(translate.t(LABEL_PROCESSING_ORDER), {
                    html! {
+                     <>
+                       <FieldHelp name="processing_order" />
                        <Select
                         name={"processing_order"}
                         multi_select={false}
@@ -227,10 +288,29 @@ pub fn ConfigTargetView(props: &ConfigTargetViewProps) -> Html {
                         })}
                         options={processing_orders.clone()}
                     />
+                     </>
+               }})}
+            { config_field_child!(translate.t(LABEL_SORT_PRESET), {
+                   html! {
+                       <Select
+                        name={"sort_preset"}
+                        multi_select={false}
+                        on_select={Callback::from(move |(_, selections):(String, DropDownSelection)| {
+                            let preset = match selections {
+                                DropDownSelection::Empty => None,
+                                DropDownSelection::Single(option) => Some(option),
+                                DropDownSelection::Multi(options) => options.into_iter().next(),
+                            }.filter(|name| !name.is_empty());
+                            let sort = set_sort_preset(target_form_state_3.form.sort.as_ref(), preset);
+                            target_form_state_3.dispatch(ConfigTargetFormAction::Sort(sort));
+                        })}
+                        options={sort_presets.clone()}
+                    />
                }})}
             { edit_field_list_option!(target_form_state, translate.t(LABEL_MAPPING), mapping, ConfigTargetFormAction::Mapping, translate.t(LABEL_ADD_MAPPING)) }
             { edit_field_list_option!(target_form_state, translate.t(LABEL_WATCH), watch, ConfigTargetFormAction::Watch, translate.t(LABEL_ADD_WATCH)) }
             </Card>
+            </FieldHelpProvider>
         }
     };
 
@@ -0,0 +1,270 @@
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_i18n::use_translation;
+use shared::model::{UserImportAction, UserImportFormat, UserImportRequest, UserImportResultDto};
+use crate::app::components::select::Select;
+use crate::app::components::{Card, DropDownOption, DropDownSelection, TextArea, TextButton, ToggleSwitch, UserlistContext, UserlistPage};
+use crate::app::{PlaylistContext, TargetUser};
+use crate::config_field_child;
+use crate::hooks::use_service_context;
+
+const FORMAT_CSV: &str = "csv";
+const FORMAT_XTREAM: &str = "xtream";
+
+fn action_label(action: UserImportAction) -> (&'static str, &'static str) {
+    match action {
+        UserImportAction::Create => ("LABEL.CREATE", "create"),
+        UserImportAction::Update => ("LABEL.UPDATE", "update"),
+        UserImportAction::Skip => ("LABEL.SKIP", "skip"),
+    }
+}
+
+#[function_component]
+pub fn UserImport() -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let userlist_ctx = use_context::<UserlistContext>().expect("Userlist context not found");
+    let playlist_ctx = use_context::<PlaylistContext>().expect("Playlist context not found");
+
+    let selected_target = use_state(|| None::<String>);
+    let format = use_state(UserImportFormat::default);
+    let content = use_state(String::new);
+    let update_existing = use_state(|| false);
+    // the import is only possible for the previewed content
+    let preview = use_state(|| None::<Rc<UserImportResultDto>>);
+
+    let targets = use_memo((playlist_ctx.clone(), (*selected_target).clone()), |(playlist_ctx, selected)| {
+        playlist_ctx.sources.as_ref().as_ref().map_or_else(Vec::new, |sources| sources.iter().flat_map(|(_, t)| t)
+            .map(|t| DropDownOption {
+                id: t.name.clone(),
+                label: html! { t.name.clone() },
+                selected: selected.as_ref() == Some(&t.name),
+            }).collect::<Vec<DropDownOption>>())
+    });
+
+    let formats = use_memo(*format, |format| vec![
+        DropDownOption { id: FORMAT_CSV.to_string(), label: html! { "CSV" }, selected: *format == UserImportFormat::Csv },
+        DropDownOption { id: FORMAT_XTREAM.to_string(), label: html! { "Xtream Panel" }, selected: *format == UserImportFormat::Xtream },
+    ]);
+
+    let create_request = {
+        let selected_target = selected_target.clone();
+        let format = format.clone();
+        let content = content.clone();
+        let update_existing = update_existing.clone();
+        move |dry_run: bool| (*selected_target).as_ref().map(|target| UserImportRequest {
+            target: target.clone(),
+            format: *format,
+            content: (*content).clone(),
+            update_existing: *update_existing,
+            dry_run,
+        })
+    };
+
+    let handle_back = {
+        let userlist_ctx = userlist_ctx.clone();
+        let preview = preview.clone();
+        Callback::from(move |_| {
+            preview.set(None);
+            userlist_ctx.active_page.set(UserlistPage::List);
+        })
+    };
+
+    let handle_preview = {
+        let services = services.clone();
+        let translate = translate.clone();
+        let preview = preview.clone();
+        let create_request = create_request.clone();
+        Callback::from(move |_| {
+            let Some(request) = create_request(true) else {
+                services.toastr.error(translate.t("MESSAGES.SAVE.USER.TARGET_NOT_SELECTED"));
+                return;
+            };
+            let services = services.clone();
+            let preview = preview.clone();
+            spawn_local(async move {
+                match services.user.import_users(request).await {
+                    Ok(result) => preview.set(Some(Rc::new(result))),
+                    Err(err) => {
+                        preview.set(None);
+                        services.toastr.error(err.to_string());
+                    }
+                }
+            });
+        })
+    };
+
+    let handle_import = {
+        let services = services.clone();
+        let translate = translate.clone();
+        let userlist = userlist_ctx.clone();
+        let preview = preview.clone();
+        Callback::from(move |_| {
+            let Some(request) = create_request(false) else { return; };
+            if preview.as_ref().is_none_or(|result| result.created + result.updated == 0) {
+                services.toastr.warning(translate.t("MESSAGES.SAVE.USER.IMPORT_EMPTY"));
+                return;
+            }
+            let services = services.clone();
+            let translate = translate.clone();
+            let userlist = userlist.clone();
+            let preview = preview.clone();
+            let target = request.target.clone();
+            spawn_local(async move {
+                match services.user.import_users(request).await {
+                    Ok(result) => {
+                        let imported: Vec<Rc<TargetUser>> = result.entries.iter()
+                            .filter_map(|entry| entry.user.as_ref())
+                            .map(|user| Rc::new(TargetUser { target: target.clone(), credentials: Rc::new(user.clone()) }))
+                            .collect();
+                        let mut user_list: Vec<Rc<TargetUser>> = userlist.users.as_ref().map_or_else(Vec::new, |users| users.iter()
+                            .filter(|user| !imported.iter().any(|new_user| new_user.credentials.username == user.credentials.username))
+                            .cloned().collect());
+                        user_list.extend(imported);
+                        userlist.users.set(Some(Rc::new(user_list)));
+                        preview.set(Some(Rc::new(result)));
+                        services.toastr.success(translate.t("MESSAGES.SAVE.USER.IMPORT_SUCCESS"));
+                    }
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    let handle_content = {
+        let content = content.clone();
+        let preview = preview.clone();
+        Callback::from(move |value: String| {
+            preview.set(None);
+            content.set(value);
+        })
+    };
+
+    let render_preview = |result: &UserImportResultDto| html! {
+        <div class="tp__userlist-import__preview">
+            <div class="tp__userlist-import__summary">
+                <span>{ format!("{}: {}", translate.t("LABEL.CREATED"), result.created) }</span>
+                <span>{ format!("{}: {}", translate.t("LABEL.UPDATED"), result.updated) }</span>
+                <span>{ format!("{}: {}", translate.t("LABEL.SKIPPED"), result.skipped) }</span>
+            </div>
+            <div class="tp__table__container">
+                <table class="tp__table__table tp__userlist-import__columns">
+                    <thead>
+                        <tr><th>{ translate.t("LABEL.COLUMN") }</th><th>{ translate.t("LABEL.FIELD") }</th></tr>
+                    </thead>
+                    <tbody>
+                        { for result.columns.iter().map(|column| html! {
+                            <tr class={classes!(column.field.is_none().then_some("tp__userlist-import__ignored"))}>
+                                <td>{ &column.column }</td>
+                                <td>{ column.field.clone().unwrap_or_else(|| translate.t("LABEL.IGNORED")) }</td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            </div>
+            <div class="tp__table__container">
+                <table class="tp__table__table tp__userlist-import__entries">
+                    <thead>
+                        <tr>
+                            <th>{ translate.t("LABEL.ROW") }</th>
+                            <th>{ translate.t("LABEL.USERNAME") }</th>
+                            <th>{ translate.t("LABEL.ACTION") }</th>
+                            <th>{ translate.t("LABEL.REASON") }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for result.entries.iter().map(|entry| {
+                            let (label, class) = action_label(entry.action);
+                            html! {
+                            <tr class={format!("tp__userlist-import__entry-{class}")}>
+                                <td>{ entry.row }</td>
+                                <td>{ &entry.username }</td>
+                                <td>{ translate.t(label) }</td>
+                                <td>{ entry.reason.clone().unwrap_or_default() }</td>
+                            </tr>
+                        }}) }
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    };
+
+    let set_selected_target = selected_target.clone();
+    let set_format = format.clone();
+    let set_update_existing = update_existing.clone();
+    let reset_target_preview = preview.clone();
+    let reset_format_preview = preview.clone();
+    let reset_update_preview = preview.clone();
+    html! {
+      <div class="tp__userlist-import tp__list-create">
+        <div class="tp__userlist-import__header tp__list-create__header">
+           <h1>{ translate.t("LABEL.IMPORT_USERS") }</h1>
+           <TextButton class="primary" name="userlist"
+               icon="Userlist"
+               title={ translate.t("LABEL.LIST")}
+               onclick={handle_back}></TextButton>
+        </div>
+        <div class="tp__userlist-import__body tp__list-create__body">
+            <Card>
+              <div class="tp__userlist-import__form tp__form-page">
+                <div class="tp__form-page__body">
+                  { config_field_child!(translate.t("LABEL.PLAYLIST"), {
+                     html! { <Select name="target"
+                          multi_select={false}
+                          on_select={Callback::from(move |(_name, selections):(String, DropDownSelection)| {
+                              let target = match selections {
+                                  DropDownSelection::Empty => None,
+                                  DropDownSelection::Single(option) => Some(option),
+                                  DropDownSelection::Multi(options) => options.first().cloned(),
+                              };
+                              reset_target_preview.set(None);
+                              set_selected_target.set(target);
+                          })}
+                          options={targets.clone()}
+                      /> }
+                  })}
+                  { config_field_child!(translate.t("LABEL.FORMAT"), {
+                     html! { <Select name="format"
+                          multi_select={false}
+                          on_select={Callback::from(move |(_name, selections):(String, DropDownSelection)| {
+                              let format = match selections {
+                                  DropDownSelection::Single(option) if option == FORMAT_XTREAM => UserImportFormat::Xtream,
+                                  _ => UserImportFormat::Csv,
+                              };
+                              reset_format_preview.set(None);
+                              set_format.set(format);
+                          })}
+                          options={formats.clone()}
+                      /> }
+                  })}
+                  { config_field_child!(translate.t("LABEL.UPDATE_EXISTING_USERS"), {
+                     html! { <ToggleSwitch value={*update_existing} readonly={false}
+                          on_change={Callback::from(move |value: bool| {
+                              reset_update_preview.set(None);
+                              set_update_existing.set(value);
+                          })} /> }
+                  })}
+                  <TextArea name="content" rows={12} value={(*content).clone()} on_change={handle_content}
+                      placeholder={ if *format == UserImportFormat::Csv { "username,password,max_connections,exp_date,status" } else { "[{\"username\": \"...\", \"password\": \"...\"}]" } } />
+                </div>
+                <div class="tp__form-page__toolbar">
+                   <TextButton class="secondary" name="preview_import"
+                       icon="Show"
+                       title={ translate.t("LABEL.PREVIEW")}
+                       onclick={handle_preview}></TextButton>
+                   <TextButton class="primary" name="import"
+                       icon="Inbox"
+                       title={ translate.t("LABEL.IMPORT")}
+                       onclick={handle_import}></TextButton>
+                </div>
+              </div>
+            </Card>
+            { match (*preview).as_ref() {
+                Some(result) => html! { <Card>{ render_preview(result) }</Card> },
+                None => html! {},
+            } }
+        </div>
+      </div>
+    }
+}
@@ -21,6 +21,13 @@ pub fn UserlistList() -> Html {
         })
     };
 
+    let handle_import = {
+        let userlist_ctx = userlist_ctx.clone();
+        Callback::from(move |_| {
+            userlist_ctx.active_page.set(UserlistPage::Import);
+        })
+    };
+
     let handle_search = {
         let userlist_ctx = userlist_ctx.clone();
         Callback::from(move |search_req| {
@@ -44,6 +51,10 @@ pub fn UserlistList() -> Html {
           <h1>{ translate.t("LABEL.USERS")}</h1>
           <div class="tp__userlist-list__header-toolbar">
               <Search min_length={1} onsearch={handle_search} options={search_fields.clone()}/>
+              <TextButton class="secondary" name="import_userlist"
+                icon="Inbox"
+                title={ translate.t("LABEL.IMPORT")}
+                onclick={handle_import}></TextButton>
               <TextButton class="primary" name="new_userlist"
                 icon="PersonAdd"
                 title={ translate.t("LABEL.NEW_USER")}
@@ -2,6 +2,7 @@ mod page;
 mod userlist_view;
 mod list;
 mod edit;
+mod import;
 mod user_table;
 mod proxy_type_view;
 mod proxy_type_input;
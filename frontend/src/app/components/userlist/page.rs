@@ -6,6 +6,7 @@ use shared::error::{TuliproxError, info_err_res};
 pub enum UserlistPage {
     List,
     Edit,
+    Import,
 }
 
 impl FromStr for UserlistPage {
@@ -15,6 +16,7 @@ impl FromStr for UserlistPage {
         match s.to_lowercase().as_str() {
             "list" => Ok(UserlistPage::List),
             "edit" => Ok(UserlistPage::Edit),
+            "import" => Ok(UserlistPage::Import),
             _ => info_err_res!("Unknown page type: {s}"),
         }
     }
@@ -25,6 +27,7 @@ impl Display for UserlistPage {
         write!(f, "{}", match *self {
             Self::List => "list",
             Self::Edit => "edit",
+            Self::Import => "import",
         })
     }
 }
\ No newline at end of file
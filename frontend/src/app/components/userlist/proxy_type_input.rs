@@ -11,7 +11,7 @@ fn get_flags(pt: ProxyType)-> (bool, bool, bool, bool, bool) {
             let series_flag = cluster.contains(ClusterFlags::Series);
             (false, true, live_flag, vod_flag, series_flag)
         }
-        ProxyType::Redirect => (true, false, false, false, false),
+        ProxyType::Redirect | ProxyType::Dash | ProxyType::Auto => (matches!(pt, ProxyType::Redirect), false, false, false, false),
     }
 }
 
@@ -62,6 +62,14 @@ pub fn ProxyTypeInput(props: &ProxyTypeInputProps) -> Html {
       })
     };
 
+    let handle_dash_click = {
+        let onchange = props.on_change.clone();
+        Callback::from(move |_| onchange.emit(ProxyType::Dash))
+    };
+    let handle_auto_click = {
+        let onchange = props.on_change.clone();
+        Callback::from(move |_| onchange.emit(ProxyType::Auto))
+    };
     let handle_redirect_click = {
         let emit_change = handle_change.clone();
         Callback::from(move |_| {
@@ -118,6 +126,8 @@ pub fn ProxyTypeInput(props: &ProxyTypeInputProps) -> Html {
     };
 
     let (redirect, reverse, reverse_live, reverse_vod, reverse_series) = selections;
+    let dash = props.value.is_dash();
+    let auto = props.value.is_auto();
 
     html! {
         <div class="tp__proxy-type-input">
@@ -133,6 +143,14 @@ pub fn ProxyTypeInput(props: &ProxyTypeInputProps) -> Html {
                 <span onclick={handle_reverse_series_click} class={classes!("tp__chip", "tp__proxy-type-input__reverse-series", if reverse_series {"active"} else if reverse {"redirect-active"} else {""})}>{ translate.t("LABEL.SERIES_SHORT") }</span>
             </span>
           </span>
+
+          <span onclick={handle_dash_click} class={classes!("tp__chip", "tp__proxy-type-input__dash", if dash {"active"} else {""})}>
+            <span>{ translate.t("LABEL.DASH") }</span>
+          </span>
+
+          <span onclick={handle_auto_click} class={classes!("tp__chip", "tp__proxy-type-input__auto", if auto {"active"} else {""})}>
+            <span>{ translate.t("LABEL.AUTO") }</span>
+          </span>
         </div>
     }
 }
\ No newline at end of file
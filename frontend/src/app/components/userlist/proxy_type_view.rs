@@ -45,5 +45,11 @@ pub fn ProxyTypeView(props: &ProxyTypeViewProps) -> Html {
         ProxyType::Redirect => html! {
             <Chip label={translate.t("LABEL.REDIRECT")} class={"tp__proxy-type__redirect"} />
         },
+        ProxyType::Dash => html! {
+            <Chip label={translate.t("LABEL.DASH")} class={"tp__proxy-type__reverse"} />
+        },
+        ProxyType::Auto => html! {
+            <Chip label={translate.t("LABEL.AUTO")} class={"tp__proxy-type__reverse"} />
+        },
     }
 }
\ No newline at end of file
@@ -1,8 +1,11 @@
 use crate::app::components::userlist::edit::UserEdit;
+use crate::app::components::userlist::import::UserImport;
 use crate::app::components::userlist::list::UserlistList;
 use crate::app::components::userlist::page::UserlistPage;
 use crate::app::components::{Breadcrumbs, Panel, TargetUser};
 use crate::app::context::{ConfigContext, UserlistContext};
+use crate::hooks::use_service_context;
+use crate::model::EventMessage;
 use std::rc::Rc;
 use yew::prelude::*;
 use yew_i18n::use_translation;
@@ -10,6 +13,7 @@ use yew_i18n::use_translation;
 #[function_component]
 pub fn UserlistView() -> Html {
     let translate = use_translation();
+    let services = use_service_context();
     let config_ctx = use_context::<ConfigContext>().expect("Config context not found");
 
     let breadcrumbs = use_state(|| Rc::new(vec![translate.t("LABEL.USERLIST"), translate.t("LABEL.LIST")]));
@@ -40,6 +44,28 @@ pub fn UserlistView() -> Html {
         });
     }
     
+    {
+        // the command palette opens users with an event
+        let services_ctx = services.clone();
+        let selected_user = selected_user.clone();
+        let active_page = active_page.clone();
+        use_effect_with((*users).clone(), move |users| {
+            let users = users.clone();
+            let services = services_ctx.clone();
+            let subid = services.event.subscribe(move |msg| {
+                if let EventMessage::OpenUser(target, username) = msg {
+                    let user = users.as_ref().and_then(|list| list.iter()
+                        .find(|u| u.target == target && u.credentials.username == username).cloned());
+                    if user.is_some() {
+                        selected_user.set(user);
+                        active_page.set(UserlistPage::Edit);
+                    }
+                }
+            });
+            move || services_ctx.event.unsubscribe(subid)
+        });
+    }
+
     let userlist_context = UserlistContext {
         selected_user: selected_user.clone(),
         filtered_users: filtered_user.clone(),
@@ -69,6 +95,7 @@ pub fn UserlistView() -> Html {
             match *view_visible {
                 UserlistPage::List => breadcrumbs.set(Rc::new(vec![translate.t("LABEL.USERS"), translate.t("LABEL.LIST")])),
                 UserlistPage::Edit => breadcrumbs.set(Rc::new(vec![translate.t("LABEL.USERS"), translate.t( if selected_user.is_none() { "LABEL.CREATE" } else {"LABEL.EDIT" })])),
+                UserlistPage::Import => breadcrumbs.set(Rc::new(vec![translate.t("LABEL.USERS"), translate.t("LABEL.IMPORT")])),
             }
         });
     };
@@ -85,6 +112,9 @@ pub fn UserlistView() -> Html {
                     <Panel value={UserlistPage::Edit.to_string()} active={active_page.to_string()}>
                         <UserEdit />
                     </Panel>
+                    <Panel value={UserlistPage::Import.to_string()} active={active_page.to_string()}>
+                        <UserImport />
+                    </Panel>
                 </div>
             </div>
         </ContextProvider<UserlistContext>>
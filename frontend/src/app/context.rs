@@ -2,9 +2,17 @@ use std::rc::Rc;
 use yew::UseStateHandle;
 use shared::model::{AppConfigDto, ConfigTargetDto, PlaylistRequest, ProxyUserCredentialsDto, SearchRequest, StatusCheck, SystemInfo, UiPlaylistCategories};
 use crate::app::components::{InputRow, PlaylistEditorPage, PlaylistExplorerPage, UserlistPage};
+use crate::model::UiLanguage;
 
 type SingleSource = (Vec<Rc<InputRow>>, Vec<Rc<ConfigTargetDto>>);
 
+/// Available web ui languages, setting `language` loads its translations.
+#[derive(Clone, PartialEq)]
+pub struct LanguageContext {
+    pub languages: Rc<Vec<UiLanguage>>,
+    pub language: UseStateHandle<String>,
+}
+
 #[derive(Clone, PartialEq)]
 pub struct PlaylistContext {
     pub sources: Rc<Option<Rc<Vec<SingleSource>>>>,
@@ -11,12 +11,13 @@ use crate::provider::IconContextProvider;
 use crate::provider::ServiceContextProvider;
 use yew_i18n::I18nProvider;
 use yew::prelude::*;
+use yew::platform::spawn_local;
 use yew_hooks::{use_async_with_options, UseAsyncOptions};
 use yew_router::prelude::*;
 use crate::app::components::{Authentication, Home, LoadingScreen, Login, RoleBasedContent};
 use crate::error::Error;
 use crate::hooks::{IconDefinition};
-use crate::model::WebConfig;
+use crate::model::{get_language_fallbacks, get_preferred_language, UiLanguage, WebConfig, DEFAULT_LANGUAGE};
 use crate::services::request_get;
 pub use crate::app::components::{ConfirmDialog, ContentDialog};
 pub use context::*;
@@ -45,6 +46,26 @@ fn flatten_json(value: &Value, prefix: String, map: &mut HashMap<String, serde_j
     }
 }
 
+/// Loads the translation files of the fallback chain, the keys of the first file win.
+async fn load_translations(fallbacks: &[String]) -> Value {
+    let futures = fallbacks.iter()
+        .map(|lang| async move {
+            let url = format!("assets/i18n/{lang}.json");
+            let result: Result<Option<Value>, Error> = request_get(&url, None, None).await;
+            (lang, result)
+        })
+        .collect::<Vec<_>>();
+    let results = join_all(futures).await;
+    let mut translations = HashMap::<String, serde_json::Value>::new();
+    for (lang, result) in results.into_iter().rev() {
+        match result {
+            Ok(Some(i18n)) => flatten_json(&i18n, String::new(), &mut translations),
+            Ok(None) => {}
+            Err(err) => error!("Failed to load translations {lang}: {err}"),
+        }
+    }
+    Value::Object(translations.into_iter().collect())
+}
 
 /// App routes
 #[derive(Routable, Debug, Clone, PartialEq, Eq)]
@@ -68,39 +89,46 @@ pub fn switch(route: AppRoute) -> Html {
 
 #[function_component]
 pub fn App() -> Html {
-    let supported_languages = vec!["en"];
-    let translations_state = use_state(|| None);
+    let languages_state = use_state(|| None::<Rc<Vec<UiLanguage>>>);
+    let language_state = use_state(|| DEFAULT_LANGUAGE.to_string());
+    let translations_state = use_state(|| None::<(&'static str, Value)>);
     let configuration_state = use_state(|| None);
     let icon_state = use_state(|| None);
 
     {
-        let trans_state = translations_state.clone();
-        let languages = supported_languages.clone();
+        let set_languages = languages_state.clone();
+        let set_language = language_state.clone();
         use_async_with_options::<_, (), Error>(async move {
-            let futures = languages.iter()
-                .map(|lang| async move {
-                    let url = format!("assets/i18n/{lang}.json");
-                    let result: Result<Option<Value>, Error> = request_get(&url, None, None).await;
-                    (lang.to_string(), result)
-                })
-                .collect::<Vec<_>>();
-            let results = join_all(futures).await;
-            let mut translations = HashMap::<String, serde_json::Value>::new();
-            for (lang, result) in results {
-                if let Ok(i18n) = result {
-                    let mut lang_translations = HashMap::<String, serde_json::Value>::new();
-                    if let Some(i18n) = i18n {
-                        flatten_json(&i18n, String::new(), &mut lang_translations);
-                    }
-                    let map: serde_json::Map<String, Value> = lang_translations.into_iter().collect();
-                    translations.insert(lang, Value::Object(map));
+            let languages = match request_get::<Vec<UiLanguage>>("assets/i18n/index.json", None, None).await {
+                Ok(Some(languages)) if !languages.is_empty() => languages,
+                Ok(_) => UiLanguage::default_languages(),
+                Err(err) => {
+                    error!("Failed to load languages {err}");
+                    UiLanguage::default_languages()
                 }
-            }
-            trans_state.set(Some(translations));
+            };
+            set_language.set(get_preferred_language(&languages));
+            set_languages.set(Some(Rc::new(languages)));
             Ok(())
         }, UseAsyncOptions::enable_auto());
     }
 
+    {
+        let trans_state = translations_state.clone();
+        let deps = ((*languages_state).clone(), (*language_state).clone());
+        use_effect_with(deps, move |(languages, language)| {
+            if let Some(languages) = languages {
+                let fallbacks = get_language_fallbacks(languages, language);
+                // The provider needs static language names, leaked once per language switch.
+                let language: &'static str = Box::leak(language.clone().into_boxed_str());
+                spawn_local(async move {
+                    let translations = load_translations(&fallbacks).await;
+                    trans_state.set(Some((language, translations)));
+                });
+            }
+        });
+    }
+
     {
         let config_state = configuration_state.clone();
         use_async_with_options::<_, (), Error>(async move {
@@ -147,7 +175,12 @@ pub fn App() -> Html {
     || icon_state.as_ref().is_none(){
         return html! { <LoadingScreen/> };
     }
-    let transl = translations_state.as_ref().unwrap();
+    let (language, translation) = translations_state.as_ref().unwrap();
+    let translations = HashMap::from([(language.to_string(), translation.clone())]);
+    let language_context = LanguageContext {
+        languages: languages_state.as_ref().map(Rc::clone).unwrap_or_default(),
+        language: language_state.clone(),
+    };
     let config: &WebConfig = configuration_state.as_ref().unwrap();
     let icons: &Vec<Rc<IconDefinition>> = icon_state.as_ref().unwrap();
 
@@ -155,11 +188,14 @@ pub fn App() -> Html {
         <BrowserRouter>
             <ServiceContextProvider config={config.clone()}>
                 <IconContextProvider icons={icons.clone()}>
-                    <I18nProvider supported_languages={supported_languages} translations={transl.clone()}>
-                        <Authentication>
-                            <RoleBasedContent />
-                        </Authentication>
-                    </I18nProvider>
+                    <ContextProvider<LanguageContext> context={language_context}>
+                        // the key remounts the provider, it reads the translations only once
+                        <I18nProvider key={*language} supported_languages={vec![*language]} translations={translations}>
+                            <Authentication>
+                                <RoleBasedContent />
+                            </Authentication>
+                        </I18nProvider>
+                    </ContextProvider<LanguageContext>>
                 </IconContextProvider>
             </ServiceContextProvider>
         </BrowserRouter>
@@ -9,12 +9,6 @@ pub struct ErrorSetInfo {
     pub errors: HashMap<String, Vec<String>>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
-pub struct ErrorInfo {
-    pub error: String,
-}
-
 /// Define all possible errors
 #[derive(ThisError, Clone, Debug, PartialEq, Eq)]
 pub enum Error {
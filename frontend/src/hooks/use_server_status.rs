@@ -7,6 +7,7 @@ use shared::model::{ActiveUserConnectionChange, StatusCheck, SystemInfo};
 use crate::hooks::use_service_context;
 use yew::platform::spawn_local;
 use crate::model::EventMessage;
+use crate::services::store_status_snapshot;
 
 type ServerStatusState = (UseStateHandle<RefCell<Option<Rc<StatusCheck>>>>, UseStateHandle<RefCell<Option<Rc<SystemInfo>>>>);
 
@@ -30,6 +31,7 @@ pub fn use_server_status(
             let subid = services_ctx.event.subscribe(move |msg| {
                 match msg {
                     EventMessage::ServerStatus(server_status) => {
+                        store_status_snapshot(&server_status);
                         *status_holder_signal.borrow_mut() = Some(Rc::clone(&server_status));
                         status_signal.set(Some(server_status));
                     }
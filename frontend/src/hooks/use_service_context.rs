@@ -1,7 +1,7 @@
 use std::rc::Rc;
 use yew::prelude::*;
 use crate::model::WebConfig;
-use crate::services::{AuthService, ConfigService, EventService, PlaylistService, StatusService, StreamsService, ToastrService, UserApiService, UserService, WebSocketService};
+use crate::services::{ApiDocsService, AuthService, ConfigService, EventService, LogService, NotificationService, PlaylistService, StatusService, StreamsService, ToastrService, UiPreferencesService, UserApiService, UserService, WebSocketService};
 
 pub struct Services {
     pub auth: Rc<AuthService>,
@@ -14,6 +14,10 @@ pub struct Services {
     pub playlist: Rc<PlaylistService>,
     pub toastr: Rc<ToastrService>,
     pub websocket: Rc<WebSocketService>,
+    pub notification: Rc<NotificationService>,
+    pub preferences: Rc<UiPreferencesService>,
+    pub api_docs: Rc<ApiDocsService>,
+    pub log: Rc<LogService>,
 }
 
 impl Services {
@@ -28,6 +32,10 @@ impl Services {
         let user = Rc::new(UserService::new(Rc::clone(&event)));
         let user_api = Rc::new(UserApiService::new());
         let websocket = Rc::new(WebSocketService::new(Rc::clone(&status), Rc::clone(&event)));
+        let notification = Rc::new(NotificationService::new());
+        let preferences = Rc::new(UiPreferencesService::new());
+        let api_docs = Rc::new(ApiDocsService::new());
+        let log = Rc::new(LogService::new());
         Self {
             auth,
             config,
@@ -38,7 +46,11 @@ impl Services {
             user,
             user_api,
             toastr,
-            websocket
+            websocket,
+            notification,
+            preferences,
+            api_docs,
+            log,
         }
     }
 }
@@ -1,6 +1,8 @@
 use frontend::app::App;
+use frontend::utils::register_service_worker;
 
 pub fn main() {
     wasm_logger::init(wasm_logger::Config::default());
     yew::Renderer::<App>::new().render();
+    register_service_worker();
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use std::rc::Rc;
 use std::sync::Arc;
-use shared::model::{ActiveUserConnectionChange, ConfigType, LibraryScanSummary, PlaylistUpdateState, StatusCheck, SystemInfo};
+use shared::model::{ActiveUserConnectionChange, ConfigType, LibraryScanSummary, NotificationDto, PlaylistUpdateState, StatusCheck, SystemInfo};
 use crate::model::BusyStatus;
 
 #[allow(clippy::large_enum_variant)]
@@ -18,5 +18,8 @@ pub enum EventMessage {
     PlaylistUpdateProgress(String, String),
     WebSocketStatus(bool),
     SystemInfoUpdate(SystemInfo),
-    LibraryScanProgress(LibraryScanSummary)
+    LibraryScanProgress(LibraryScanSummary),
+    OpenUser(String, String), // target, username
+    Notification(NotificationDto),
+    MappingReprocess(Vec<String>), // targets of changed mappings
 }
\ No newline at end of file
@@ -4,10 +4,12 @@ mod web_config;
 mod dialog;
 mod event_message;
 mod busy_status;
+mod ui_language;
 
 pub use self::view_type::*;
 pub use self::explorer_source_type::*;
 pub use self::web_config::*;
 pub use self::dialog::*;
 pub use self::busy_status::*;
-pub use self::event_message::*;
\ No newline at end of file
+pub use self::event_message::*;
+pub use self::ui_language::*;
\ No newline at end of file
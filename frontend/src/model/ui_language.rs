@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use web_sys::window;
+use crate::utils::{get_local_storage_item, remove_local_storage_item, set_local_storage_item};
+
+pub const TP_LANGUAGE_KEY: &str = "tp-language";
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Entry of `assets/i18n/index.json`, `code` is the name of the translation file.
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize, Debug)]
+pub struct UiLanguage {
+    pub code: String,
+    pub name: String,
+}
+
+impl UiLanguage {
+    pub fn default_languages() -> Vec<UiLanguage> {
+        vec![UiLanguage { code: DEFAULT_LANGUAGE.to_string(), name: "English".to_string() }]
+    }
+}
+
+fn find_language<'a>(languages: &'a [UiLanguage], code: &str) -> Option<&'a UiLanguage> {
+    languages.iter().find(|l| l.code.eq_ignore_ascii_case(code))
+        .or_else(|| code.split(['-', '_']).next().and_then(|base| languages.iter().find(|l| l.code.eq_ignore_ascii_case(base))))
+}
+
+/// The stored language, otherwise the browser language if it is available, otherwise english.
+pub fn get_preferred_language(languages: &[UiLanguage]) -> String {
+    let browser_language = window().and_then(|w| w.navigator().language());
+    get_local_storage_item(TP_LANGUAGE_KEY).into_iter()
+        .chain(browser_language)
+        .find_map(|code| find_language(languages, &code).map(|l| l.code.clone()))
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string())
+}
+
+pub fn save_language(code: &str) {
+    if code == DEFAULT_LANGUAGE {
+        remove_local_storage_item(TP_LANGUAGE_KEY);
+    } else {
+        set_local_storage_item(TP_LANGUAGE_KEY, code);
+    }
+}
+
+/// Translation files to load for a language, missing keys are taken from the next file.
+/// `de-AT` falls back to `de` and then to english.
+pub fn get_language_fallbacks(languages: &[UiLanguage], code: &str) -> Vec<String> {
+    let mut chain = vec![code.to_string()];
+    if let Some((base, _)) = code.split_once(['-', '_']) {
+        if let Some(language) = find_language(languages, base) {
+            chain.push(language.code.clone());
+        }
+    }
+    if !chain.iter().any(|c| c == DEFAULT_LANGUAGE) {
+        chain.push(DEFAULT_LANGUAGE.to_string());
+    }
+    chain
+}
@@ -1,6 +1,7 @@
 use std::fmt;
 use std::str::FromStr;
 use shared::error::{info_err_res, TuliproxError};
+use shared::model::WebUiRole;
 
 const DASHBOARD: &str = "dashboard";
 const STATS: &str = "stats";
@@ -12,9 +13,11 @@ const PLAYLIST_EDITOR: &str = "playlist_editor";
 const PLAYLIST_EXPLORER: &str = "playlist_explorer";
 const PLAYLIST_EPG: &str = "playlist_epg";
 const SOURCE_EDITOR: &str = "source_editor";
+const LOGS: &str = "logs";
+const API_DOCS: &str = "api_docs";
 
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ViewType {
     Dashboard,
     Stats,
@@ -25,7 +28,74 @@ pub enum ViewType {
     PlaylistUpdate,
     PlaylistEditor,
     PlaylistExplorer,
-    PlaylistEpg
+    PlaylistEpg,
+    Logs,
+    ApiDocs,
+}
+
+impl ViewType {
+    /// Pages in the order of the sidebar, `Alt+1` opens the first page, `Alt+0` the tenth.
+    pub const ALL: [ViewType; 12] = [
+        ViewType::Dashboard,
+        ViewType::Stats,
+        ViewType::Streams,
+        ViewType::Config,
+        ViewType::Users,
+        ViewType::SourceEditor,
+        ViewType::PlaylistUpdate,
+        ViewType::PlaylistEditor,
+        ViewType::PlaylistExplorer,
+        ViewType::PlaylistEpg,
+        ViewType::Logs,
+        ViewType::ApiDocs,
+    ];
+
+    pub fn required_role(&self) -> WebUiRole {
+        match self {
+            ViewType::Users | ViewType::PlaylistUpdate | ViewType::Logs => WebUiRole::Operator,
+            ViewType::SourceEditor | ViewType::PlaylistEditor => WebUiRole::Admin,
+            _ => WebUiRole::Viewer,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ViewType::Dashboard => "LABEL.DASHBOARD",
+            ViewType::Stats => "LABEL.STATS",
+            ViewType::Streams => "LABEL.STREAMS",
+            ViewType::Users => "LABEL.USER",
+            ViewType::Config => "LABEL.CONFIG",
+            ViewType::SourceEditor => "LABEL.SOURCE_EDITOR",
+            ViewType::PlaylistUpdate => "LABEL.UPDATE",
+            ViewType::PlaylistEditor => "LABEL.PLAYLIST",
+            ViewType::PlaylistExplorer => "LABEL.PLAYLIST_VIEWER",
+            ViewType::PlaylistEpg => "LABEL.PLAYLIST_EPG",
+            ViewType::Logs => "LABEL.LOGS",
+            ViewType::ApiDocs => "LABEL.API_DOCS",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ViewType::Dashboard => "DashboardOutline",
+            ViewType::Stats => "Stats",
+            ViewType::Streams => "Streams",
+            ViewType::Users => "UserOutline",
+            ViewType::Config => "Config",
+            ViewType::SourceEditor => "SourceEditor",
+            ViewType::PlaylistUpdate => "Refresh",
+            ViewType::PlaylistEditor => "PlayArrowOutline",
+            ViewType::PlaylistExplorer => "Live",
+            ViewType::PlaylistEpg => "Epg",
+            ViewType::Logs => "Log",
+            ViewType::ApiDocs => "Book",
+        }
+    }
+
+    /// `Alt+<digit>` shortcut of the page, only the first ten pages have one.
+    pub fn shortcut(&self) -> Option<String> {
+        ViewType::ALL.iter().position(|v| v == self).filter(|index| *index < 10).map(|index| format!("Alt+{}", (index + 1) % 10))
+    }
 }
 
 impl FromStr for ViewType {
@@ -43,6 +113,8 @@ impl FromStr for ViewType {
             PLAYLIST_EDITOR => Ok(ViewType::PlaylistEditor),
             PLAYLIST_EXPLORER => Ok(ViewType::PlaylistExplorer),
             PLAYLIST_EPG => Ok(ViewType::PlaylistEpg),
+            LOGS => Ok(ViewType::Logs),
+            API_DOCS => Ok(ViewType::ApiDocs),
             _ => info_err_res!("Unknown view type: {s}"),
         }
     }
@@ -61,6 +133,8 @@ impl fmt::Display for ViewType {
             ViewType::PlaylistEditor => PLAYLIST_EDITOR,
             ViewType::PlaylistExplorer => PLAYLIST_EXPLORER,
             ViewType::PlaylistEpg => PLAYLIST_EPG,
+            ViewType::Logs => LOGS,
+            ViewType::ApiDocs => API_DOCS,
         };
         write!(f, "{s}")
     }
@@ -0,0 +1,40 @@
+use crate::error::Error;
+use crate::services::{get_base_href, request_get, request_raw, RawResponse};
+use log::error;
+use serde_json::Value;
+use shared::utils::concat_path_leading_slash;
+
+pub struct ApiDocsService {
+    api_path: String,
+    spec_path: String,
+}
+
+impl Default for ApiDocsService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApiDocsService {
+    pub fn new() -> Self {
+        let base_href = get_base_href();
+        Self {
+            api_path: concat_path_leading_slash(&base_href, "api/v1"),
+            spec_path: concat_path_leading_slash(&base_href, "api/v1/openapi.json"),
+        }
+    }
+
+    /// `OpenAPI` description of the v1 api.
+    pub async fn get_spec(&self) -> Option<Value> {
+        request_get::<Value>(&self.spec_path, None, None).await
+            .unwrap_or_else(|err| {
+                error!("{err}");
+                None
+            })
+    }
+
+    /// Executes a request against the v1 api, the path is relative to `api/v1`.
+    pub async fn execute(&self, method: &str, path: &str, body: Option<String>) -> Result<RawResponse, Error> {
+        request_raw(method, &concat_path_leading_slash(&self.api_path, path), body).await
+    }
+}
@@ -2,7 +2,7 @@ use std::cell::RefCell;
 use super::{check_dummy_token, get_base_href, request_post, set_token};
 use futures_signals::signal::Mutable;
 use futures_signals::signal::SignalExt;
-use shared::model::{Claims, TokenResponse, UserCredential, ROLE_ADMIN, ROLE_USER, TOKEN_NO_AUTH};
+use shared::model::{Claims, TokenResponse, UserCredential, WebUiRole, ROLE_ADMIN, ROLE_USER, TOKEN_NO_AUTH};
 use std::future::Future;
 use shared::utils::{concat_path, concat_path_leading_slash};
 use base64::{engine::general_purpose, Engine as _};
@@ -36,8 +36,21 @@ impl AuthService {
     pub fn get_username(&self) -> String {
       self.username.borrow().to_string()
     }
+    pub fn get_web_ui_role(&self) -> Option<WebUiRole> {
+        WebUiRole::from_claims(&self.roles.borrow())
+    }
+
+    /// Admins, operators and viewers use the web ui, api users have their own ui.
+    pub fn is_web_ui_user(&self) -> bool {
+        self.get_web_ui_role().is_some()
+    }
+
+    pub fn has_role(&self, required: WebUiRole) -> bool {
+        self.get_web_ui_role().is_some_and(|role| role.is_allowed(required))
+    }
+
     pub fn is_admin(&self) -> bool {
-        self.roles.borrow().iter().any(|r| r == ROLE_ADMIN)
+        self.has_role(WebUiRole::Admin)
     }
 
     pub fn is_user(&self) -> bool {
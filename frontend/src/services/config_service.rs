@@ -1,6 +1,6 @@
 use crate::model::WebConfig;
 use crate::services::{get_base_href, request_get, request_post, EventService};
-use shared::model::{AppConfigDto, ConfigDto, ConfigInputDto, IpCheckDto, LibraryScanRequest,  SourcesConfigDto, TargetOutputDto};
+use shared::model::{AppConfigDto, ConfigDto, ConfigHelpDto, ConfigInputDto, IpCheckDto, LibraryScanRequest, RuleLintWarningDto, SourcesConfigDto, TargetOutputDto};
 use std::cell::RefCell;
 use std::future::Future;
 use std::rc::Rc;
@@ -16,10 +16,12 @@ use crate::error::Error;
 pub struct ConfigService {
     pub ui_config: Rc<WebConfig>,
     pub server_config: RefCell<Option<Rc<AppConfigDto>>>,
+    config_help: RefCell<Option<Rc<ConfigHelpDto>>>,
     config_channel: Mutable<Option<Rc<AppConfigDto>>>,
     is_fetching: AtomicBool,
     config_path: String,
     sources_path: String,
+    help_path: String,
     ip_check_path: String,
     batch_input_content_path: String,
     geoip_path: String,
@@ -34,10 +36,12 @@ impl ConfigService {
         Self {
             ui_config: Rc::new(config.clone()),
             server_config: RefCell::new(None),
+            config_help: RefCell::new(None),
             config_channel: Mutable::new(None),
             is_fetching: AtomicBool::new(false),
             config_path: config_path.clone(),
             sources_path: concat_path(&config_path, "sources"),
+            help_path: concat_path(&config_path, "help"),
             ip_check_path: concat_path_leading_slash(&base_href, "api/v1/ipinfo"),
             batch_input_content_path: concat_path_leading_slash(&base_href, "api/v1/config/batchContent"),
             geoip_path: concat_path_leading_slash(&base_href, "api/v1/geoip/update"),
@@ -90,7 +94,7 @@ impl ConfigService {
                                     o.t_filter = o.filter.as_ref().and_then(|flt| get_filter(flt, templates.as_ref()).map_err(|e| error!("Failed to parse M3U output filter: {}", e)).ok()),
                                 TargetOutputDto::Strm(o) =>
                                     o.t_filter = o.filter.as_ref().and_then(|flt| get_filter(flt, templates.as_ref()).map_err(|e| error!("Failed to parse Strm output filter: {}", e)).ok()),
-                                TargetOutputDto::HdHomeRun(_) => {}
+                                TargetOutputDto::HdHomeRun(_) | TargetOutputDto::Plex(_) => {}
                             }
                         }
                     }
@@ -122,6 +126,19 @@ impl ConfigService {
         self.is_fetching.store(false, Ordering::Release);
     }
 
+    /// The help does not change at runtime, it is fetched only once.
+    pub async fn get_config_help(&self) -> Option<Rc<ConfigHelpDto>> {
+        if let Some(help) = self.config_help.borrow().as_ref() {
+            return Some(Rc::clone(help));
+        }
+        let help = request_get::<ConfigHelpDto>(&self.help_path, None, None).await.unwrap_or_else(|err| {
+            error!("{err}");
+            None
+        }).map(Rc::new);
+        self.config_help.replace(help.clone());
+        help
+    }
+
     pub async fn get_ip_info(&self) -> Option<IpCheckDto> {
         request_get::<IpCheckDto>(&self.ip_check_path, None, None).await.unwrap_or_else(|err| {
             error!("{err}");
@@ -154,12 +171,13 @@ impl ConfigService {
         }
     }
 
-    pub async fn save_sources(&self, dto: SourcesConfigDto) -> Result<(), Error> {
+    /// Returns the rule lint warnings of the saved config.
+    pub async fn save_sources(&self, dto: SourcesConfigDto) -> Result<Vec<RuleLintWarningDto>, Error> {
         self.event_service.set_config_change_message_blocked(true);
-        match request_post::<SourcesConfigDto, ()>(&self.sources_path, dto, None, None).await {
-            Ok(_) => {
+        match request_post::<SourcesConfigDto, Vec<RuleLintWarningDto>>(&self.sources_path, dto, None, None).await {
+            Ok(warnings) => {
                 self.event_service.set_config_change_message_blocked(false);
-                Ok(())
+                Ok(warnings.unwrap_or_default())
             }
             Err(err) => {
                 self.event_service.set_config_change_message_blocked(false);
@@ -0,0 +1,72 @@
+use crate::services::{get_base_href, get_token};
+use log::error;
+use shared::model::LogEntryDto;
+use shared::utils::concat_path_leading_slash;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, MessageEvent, WebSocket};
+use yew::Callback;
+
+/// Open log stream, the websocket is closed when the stream is dropped.
+pub struct LogStream {
+    socket: WebSocket,
+    _onopen: Closure<dyn FnMut(Event)>,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+}
+
+impl Drop for LogStream {
+    fn drop(&mut self) {
+        self.socket.set_onopen(None);
+        self.socket.set_onmessage(None);
+        let _ = self.socket.close();
+    }
+}
+
+pub struct LogService {
+    ws_path: String,
+}
+
+impl Default for LogService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogService {
+    pub fn new() -> Self {
+        let base_href = get_base_href();
+        Self {
+            ws_path: concat_path_leading_slash(&base_href, "ws/logs"),
+        }
+    }
+
+    /// Opens the log stream with the level and module filter, the server sends the buffered entries first.
+    pub fn open_stream(&self, level: &str, module: &str, on_entry: Callback<LogEntryDto>) -> Option<LogStream> {
+        let params: Vec<String> = [("level", level), ("module", module)].iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| format!("{name}={}", String::from(js_sys::encode_uri_component(value))))
+            .collect();
+        let url = if params.is_empty() { self.ws_path.clone() } else { format!("{}?{}", self.ws_path, params.join("&")) };
+        let socket = WebSocket::new(&url)
+            .map_err(|err| error!("Failed to open log stream: {err:?}"))
+            .ok()?;
+
+        let auth_socket = socket.clone();
+        let onopen = Closure::<dyn FnMut(Event)>::wrap(Box::new(move |_event: Event| {
+            // the first message authorizes the stream
+            if let Err(err) = auth_socket.send_with_str(&get_token().unwrap_or_default()) {
+                error!("Failed to authorize log stream: {err:?}");
+            }
+        }));
+        socket.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::wrap(Box::new(move |event: MessageEvent| {
+            if let Some(entry) = event.data().as_string().and_then(|text| serde_json::from_str::<LogEntryDto>(&text).ok()) {
+                on_entry.emit(entry);
+            }
+        }));
+        socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        Some(LogStream { socket, _onopen: onopen, _onmessage: onmessage })
+    }
+}
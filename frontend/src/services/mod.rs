@@ -10,6 +10,10 @@ mod event_service;
 mod user_service;
 mod user_api_service;
 mod streams_service;
+mod notification_service;
+mod ui_preferences_service;
+mod api_docs_service;
+mod log_service;
 
 pub use self::auth_service::*;
 pub use self::config_service::*;
@@ -22,4 +26,8 @@ pub use self::toastr_service::*;
 pub use self::event_service::*;
 pub use self::user_service::*;
 pub use self::user_api_service::*;
-pub use self::streams_service::*;
\ No newline at end of file
+pub use self::streams_service::*;
+pub use self::notification_service::*;
+pub use self::ui_preferences_service::*;
+pub use self::api_docs_service::*;
+pub use self::log_service::*;
\ No newline at end of file
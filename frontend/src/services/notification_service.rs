@@ -0,0 +1,40 @@
+use crate::error::Error;
+use crate::services::{get_base_href, request_get, request_post};
+use log::error;
+use shared::model::{NotificationDto, NotificationReadRequest};
+use shared::utils::{concat_path, concat_path_leading_slash};
+
+pub struct NotificationService {
+    notification_path: String,
+}
+
+impl Default for NotificationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationService {
+    pub fn new() -> Self {
+        let base_href = get_base_href();
+        Self {
+            notification_path: concat_path_leading_slash(&base_href, "api/v1/notifications"),
+        }
+    }
+
+    /// Newest first, the read state is the one of the logged-in user.
+    pub async fn get_notifications(&self) -> Vec<NotificationDto> {
+        request_get::<Vec<NotificationDto>>(&self.notification_path, None, None).await
+            .unwrap_or_else(|err| {
+                error!("{err}");
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    /// An empty id list marks all notifications as read.
+    pub async fn mark_read(&self, ids: Vec<u64>) -> Result<(), Error> {
+        let path = concat_path(&self.notification_path, "read");
+        request_post::<NotificationReadRequest, ()>(&path, NotificationReadRequest { ids }, None, None).await.map(|_| ())
+    }
+}
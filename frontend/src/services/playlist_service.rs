@@ -1,6 +1,7 @@
-use crate::services::{get_base_href, request_post};
+use crate::services::{get_base_href, request_get, request_post};
 use log::error;
-use shared::model::{EpgChannel, EpgTv, PlaylistEpgRequest, PlaylistRequest, SeriesStreamProperties, UiPlaylistCategories, UiPlaylistGroup, UiPlaylistItem, WebplayerUrlRequest, XtreamCluster, XtreamSeriesInfoDoc};
+use crate::error::Error;
+use shared::model::{EpgChannel, EpgMappingCandidateDto, EpgMappingDecisionDto, EpgMappingSuggestionDto, EpgMappingsDto, EpgTv, FilterTestRequest, FilterTestResultDto, MapperTraceRequest, MapperTraceResultDto, PlaylistBrowseGroupDto, PlaylistBrowseGroupsRequest, PlaylistBrowsePageDto, PlaylistBrowseRequest, PlaylistCompareDto, PlaylistEpgRequest, PlaylistRequest, SeriesStreamProperties, UiPlaylistCategories, UiPlaylistGroup, UiPlaylistItem, WebplayerUrlRequest, XtreamCluster, XtreamSeriesInfoDoc};
 
 use futures::join;
 use indexmap::IndexMap;
@@ -16,6 +17,12 @@ pub struct PlaylistService {
     playlist_api_epg_path: String,
     playlist_api_series_info_path: String,
     playlist_api_episode_info_path: String,
+    playlist_api_compare_path: String,
+    playlist_api_browse_path: String,
+    playlist_api_browse_groups_path: String,
+    epg_mapping_api_path: String,
+    filter_test_api_path: String,
+    mapper_trace_api_path: String,
 }
 impl Default for PlaylistService {
     fn default() -> Self {
@@ -35,6 +42,12 @@ impl PlaylistService {
             playlist_api_epg_path: concat_path_leading_slash(&base_href, "api/v1/playlist/epg"),
             playlist_api_series_info_path: concat_path_leading_slash(&base_href, "api/v1/playlist/series_info"),
             playlist_api_episode_info_path: concat_path_leading_slash(&base_href, "api/v1/playlist/series/episode"),
+            playlist_api_compare_path: concat_path_leading_slash(&base_href, "api/v1/playlist/compare"),
+            playlist_api_browse_path: concat_path_leading_slash(&base_href, "api/v1/playlist/browse"),
+            playlist_api_browse_groups_path: concat_path_leading_slash(&base_href, "api/v1/playlist/browse/groups"),
+            epg_mapping_api_path: concat_path_leading_slash(&base_href, "api/v1/epg/mapping"),
+            filter_test_api_path: concat_path_leading_slash(&base_href, "api/v1/filter/test"),
+            mapper_trace_api_path: concat_path_leading_slash(&base_href, "api/v1/mapping/trace"),
         }
     }
     pub async fn update_targets(&self, targets: &[&str]) -> bool {
@@ -73,6 +86,47 @@ impl PlaylistService {
         None
     }
 
+    /// Loads only the groups of a stored playlist, the channels of a group are loaded page by page with `browse_playlist`.
+    pub async fn get_playlist_groups(&self, playlist_request: &PlaylistRequest) -> Option<Rc<UiPlaylistCategories>> {
+        let request = |cluster| PlaylistBrowseGroupsRequest { source: playlist_request.clone(), cluster };
+        let (live_res, vod_res, series_res) = join!(
+            request_post::<PlaylistBrowseGroupsRequest, Vec<PlaylistBrowseGroupDto>>(&self.playlist_api_browse_groups_path, request(XtreamCluster::Live), None, None),
+            request_post::<PlaylistBrowseGroupsRequest, Vec<PlaylistBrowseGroupDto>>(&self.playlist_api_browse_groups_path, request(XtreamCluster::Video), None, None),
+            request_post::<PlaylistBrowseGroupsRequest, Vec<PlaylistBrowseGroupDto>>(&self.playlist_api_browse_groups_path, request(XtreamCluster::Series), None, None),
+        );
+
+        let to_groups = |result: Result<Option<Vec<PlaylistBrowseGroupDto>>, Error>, cluster: XtreamCluster| {
+            result.map_or_else(|err| {
+                error!("Failed to fetch {cluster} groups: {err}");
+                None
+            }, |r| r.map(|groups| groups.into_iter().map(|group| Rc::new(UiPlaylistGroup {
+                id: group.id,
+                title: group.title.into(),
+                channels: vec![],
+                xtream_cluster: cluster,
+            })).collect::<Vec<_>>()))
+        };
+        let live = to_groups(live_res, XtreamCluster::Live);
+        let vod = to_groups(vod_res, XtreamCluster::Video);
+        let series = to_groups(series_res, XtreamCluster::Series);
+
+        if live.is_some() || vod.is_some() || series.is_some() {
+            return Some(Rc::new(UiPlaylistCategories {
+                live,
+                vod,
+                series,
+            }));
+        }
+        None
+    }
+
+    pub async fn browse_playlist(&self, request: &PlaylistBrowseRequest) -> Option<PlaylistBrowsePageDto> {
+        request_post::<&PlaylistBrowseRequest, PlaylistBrowsePageDto>(&self.playlist_api_browse_path, request, None, Some(ACCEPT_PREFER_CBOR.to_string())).await.unwrap_or_else(|err| {
+            error!("{err}");
+            None
+        })
+    }
+
     pub async fn get_playlist_webplayer_url(&self, target_id: u16, virtual_id: u32, cluster: XtreamCluster) -> Option<String> {
         let request = WebplayerUrlRequest {
             target_id,
@@ -112,6 +166,52 @@ impl PlaylistService {
             None
         })
     }
+
+    /// Returns `None` if one of the targets was not processed yet.
+    pub async fn compare_targets(&self, target_a: &str, target_b: &str) -> Option<PlaylistCompareDto> {
+        let path = format!("{}/{}/{}", self.playlist_api_compare_path,
+                           String::from(js_sys::encode_uri_component(target_a)), String::from(js_sys::encode_uri_component(target_b)));
+        request_get::<PlaylistCompareDto>(&path, None, None).await.unwrap_or_else(|err| {
+            error!("{err}");
+            None
+        })
+    }
+
+    pub async fn get_epg_mapping_suggestions(&self, target_id: u16) -> Vec<EpgMappingSuggestionDto> {
+        let path = format!("{}/{target_id}", self.epg_mapping_api_path);
+        request_get::<Vec<EpgMappingSuggestionDto>>(&path, None, None).await
+            .unwrap_or_else(|err| {
+                error!("{err}");
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    pub async fn search_epg_mapping_channels(&self, target_id: u16, query: &str) -> Vec<EpgMappingCandidateDto> {
+        let path = format!("{}/{target_id}/search?q={}", self.epg_mapping_api_path, String::from(js_sys::encode_uri_component(query)));
+        request_get::<Vec<EpgMappingCandidateDto>>(&path, None, None).await
+            .unwrap_or_else(|err| {
+                error!("{err}");
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    /// Dry run of the filter against the stored playlist, the items are returned with the clauses they matched.
+    pub async fn test_filter(&self, request: FilterTestRequest) -> Result<Option<FilterTestResultDto>, Error> {
+        request_post::<FilterTestRequest, FilterTestResultDto>(&self.filter_test_api_path, request, None, None).await
+    }
+
+    /// Runs the mappings of a target on a sample of the stored playlist and returns the steps of the scripts.
+    pub async fn trace_mapper(&self, request: MapperTraceRequest) -> Result<Option<MapperTraceResultDto>, Error> {
+        request_post::<MapperTraceRequest, MapperTraceResultDto>(&self.mapper_trace_api_path, request, None, None).await
+    }
+
+    /// Accepted mappings are assigned on the next playlist update.
+    pub async fn update_epg_mappings(&self, target_id: u16, decisions: Vec<EpgMappingDecisionDto>) -> Result<(), Error> {
+        let path = format!("{}/{target_id}", self.epg_mapping_api_path);
+        request_post::<Vec<EpgMappingDecisionDto>, EpgMappingsDto>(&path, decisions, None, None).await.map(|_| ())
+    }
 }
 
 fn to_ui_playlist_groups(list: Vec<UiPlaylistItem>, xtream_cluster: XtreamCluster) -> Vec<Rc<UiPlaylistGroup>> {
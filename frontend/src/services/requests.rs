@@ -1,9 +1,10 @@
-use crate::error::{Error, ErrorInfo, ErrorSetInfo};
+use crate::error::{Error, ErrorSetInfo};
 use gloo_storage::{LocalStorage, Storage};
 use log::error;
 use reqwasm::http::Request;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
+use shared::model::ApiErrorResponse;
 use shared::utils::{bin_deserialize, CONTENT_TYPE_CBOR, CONTENT_TYPE_JSON};
 use web_sys::window;
 
@@ -124,17 +125,17 @@ where
                     let ct = response.headers().get("content-type").unwrap_or_default();
                     let is_json = ct.contains(CONTENT_TYPE_JSON);
                     let is_bin = !is_json && ct.contains(CONTENT_TYPE_CBOR);
-                    let data: Result<ErrorInfo, _> = if is_bin {
+                    let data: Result<ApiErrorResponse, _> = if is_bin {
                         match response.binary().await {
-                            Ok(bytes) => bin_deserialize::<ErrorInfo>(&bytes).map_err(|_| Error::DeserializeError),
+                            Ok(bytes) => bin_deserialize::<ApiErrorResponse>(&bytes).map_err(|_| Error::DeserializeError),
                             Err(_) => Err(Error::DeserializeError)
                         }
                     } else {
-                        response.json::<ErrorInfo>().await.map_err(|_| Error::DeserializeError)
+                        response.json::<ApiErrorResponse>().await.map_err(|_| Error::DeserializeError)
                     };
 
                     if let Ok(data) = data {
-                        Err(Error::BadRequest(data.error))
+                        Err(Error::BadRequest(data.to_string()))
                     } else {
                         Err(Error::BadRequest("400".to_string()))
                     }
@@ -213,6 +214,42 @@ where
     request(RequestMethod::Put, url, body, content_type, response_type).await
 }
 
+/// Status, content type and body of a response, the status is not mapped to an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawResponse {
+    pub status: u16,
+    pub content_type: String,
+    pub body: String,
+}
+
+/// Sends the request with the session token and returns the plain response, used to try out the api.
+pub async fn request_raw(method: &str, url: &str, body: Option<String>) -> Result<RawResponse, Error> {
+    let mut request = match method {
+        "post" => Request::post(url),
+        "put" => Request::put(url),
+        "delete" => Request::delete(url),
+        _ => Request::get(url),
+    };
+    if let Some(body) = body {
+        request = request.body(body).header("Content-Type", CONTENT_TYPE_JSON);
+    }
+    if let Some(token) = get_token() {
+        request = request.header("Authorization", format!("Bearer {token}").as_str());
+    }
+    match request.send().await {
+        Ok(response) => {
+            let status = response.status();
+            let content_type = response.headers().get("content-type").unwrap_or_default();
+            let body = response.text().await.map_err(|_| Error::RequestError)?;
+            Ok(RawResponse { status, content_type, body })
+        }
+        Err(e) => {
+            error!("{e}");
+            Err(Error::RequestError)
+        }
+    }
+}
+
 /// Set limit for pagination
 pub fn limit(count: u32, p: u32) -> String {
     let offset = if p > 0 { p * count } else { 0 };
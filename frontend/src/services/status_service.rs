@@ -1,10 +1,22 @@
 use std::rc::Rc;
 use crate::services::{get_base_href, request_get};
-use shared::model::StatusCheck;
+use crate::utils::set_local_storage_item;
+use shared::model::{ChannelHealthReportDto, StatusCheck, TargetProcessingStatsDto};
 use shared::utils::concat_path_leading_slash;
 
+// read by the offline page of the service worker
+const STATUS_SNAPSHOT_KEY: &str = "tuliprox.status_snapshot";
+
+pub fn store_status_snapshot(status: &StatusCheck) {
+    if let Ok(snapshot) = serde_json::to_string(status) {
+        set_local_storage_item(STATUS_SNAPSHOT_KEY, &snapshot);
+    }
+}
+
 pub struct StatusService {
     status_path: String,
+    channel_health_path: String,
+    processing_stats_path: String,
 }
 
 impl Default for StatusService {
@@ -18,10 +30,20 @@ impl StatusService {
         let base_href = get_base_href();
         Self {
             status_path: concat_path_leading_slash(&base_href, "api/v1/status"),
+            channel_health_path: concat_path_leading_slash(&base_href, "api/v1/channels/health"),
+            processing_stats_path: concat_path_leading_slash(&base_href, "api/v1/stats/processing"),
         }
     }
 
     pub async fn get_server_status(&self) -> Result<Option<Rc<StatusCheck>>, crate::error::Error> {
         request_get::<Rc<StatusCheck>>(&self.status_path, None, None).await
     }
+
+    pub async fn get_channel_health(&self) -> Result<Option<ChannelHealthReportDto>, crate::error::Error> {
+        request_get::<ChannelHealthReportDto>(&self.channel_health_path, None, None).await
+    }
+
+    pub async fn get_processing_stats(&self) -> Result<Option<Vec<TargetProcessingStatsDto>>, crate::error::Error> {
+        request_get::<Vec<TargetProcessingStatsDto>>(&self.processing_stats_path, None, None).await
+    }
 }
\ No newline at end of file
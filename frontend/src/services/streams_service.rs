@@ -1,10 +1,12 @@
 use std::rc::Rc;
 use crate::services::{get_base_href, request_get};
-use shared::model::StreamInfo;
+use log::error;
+use shared::model::{StreamInfo, StreamSessionDto};
 use shared::utils::concat_path_leading_slash;
 
 pub struct StreamsService {
     streams_path: String,
+    sessions_path: String,
 }
 
 impl Default for StreamsService {
@@ -18,10 +20,25 @@ impl StreamsService {
         let base_href = get_base_href();
         Self {
             streams_path: concat_path_leading_slash(&base_href, "api/v1/streams"),
+            sessions_path: concat_path_leading_slash(&base_href, "api/v1/sessions"),
         }
     }
 
     pub async fn get_streams_info(&self) -> Result<Option<Vec<Rc<StreamInfo>>>, crate::error::Error> {
         request_get::<Vec<Rc<StreamInfo>>>(&self.streams_path, None, None).await
     }
-}
\ No newline at end of file
+
+    /// Finished stream sessions active within the time range, newest first.
+    pub async fn get_stream_sessions(&self, from: Option<u64>, to: Option<u64>) -> Vec<Rc<StreamSessionDto>> {
+        let params: Vec<String> = [("from", from), ("to", to)].iter()
+            .filter_map(|(name, value)| value.map(|value| format!("{name}={value}")))
+            .collect();
+        let path = if params.is_empty() { self.sessions_path.clone() } else { format!("{}?{}", self.sessions_path, params.join("&")) };
+        request_get::<Vec<Rc<StreamSessionDto>>>(&path, None, None).await
+            .unwrap_or_else(|err| {
+                error!("{err}");
+                None
+            })
+            .unwrap_or_default()
+    }
+}
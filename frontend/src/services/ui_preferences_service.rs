@@ -0,0 +1,38 @@
+use crate::error::Error;
+use crate::services::{get_base_href, request_get, request_post};
+use log::error;
+use shared::model::UiPreferencesDto;
+use shared::utils::concat_path_leading_slash;
+
+pub struct UiPreferencesService {
+    preferences_path: String,
+}
+
+impl Default for UiPreferencesService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UiPreferencesService {
+    pub fn new() -> Self {
+        let base_href = get_base_href();
+        Self {
+            preferences_path: concat_path_leading_slash(&base_href, "api/v1/preferences"),
+        }
+    }
+
+    /// Preferences of the logged-in user.
+    pub async fn get_preferences(&self) -> Option<UiPreferencesDto> {
+        request_get::<UiPreferencesDto>(&self.preferences_path, None, None).await
+            .unwrap_or_else(|err| {
+                error!("{err}");
+                None
+            })
+    }
+
+    /// Only the given settings are changed, the other stored preferences are kept.
+    pub async fn update_preferences(&self, update: UiPreferencesDto) -> Result<(), Error> {
+        request_post::<UiPreferencesDto, UiPreferencesDto>(&self.preferences_path, update, None, None).await.map(|_| ())
+    }
+}
@@ -1,6 +1,6 @@
 use std::rc::Rc;
 use log::error;
-use shared::model::{PlaylistBouquetDto, PlaylistCategoriesDto};
+use shared::model::{PlaylistBouquetDto, PlaylistCategoriesDto, UserAccountDto, UserPasswordDto};
 use shared::utils::{concat_path_leading_slash};
 use crate::error::Error;
 use crate::services::{get_base_href, request_get, request_post};
@@ -9,6 +9,8 @@ use crate::services::{get_base_href, request_get, request_post};
 pub struct UserApiService {
     user_playlist_categories_path: String,
     user_playlist_bouquet_path: String,
+    user_account_path: String,
+    user_account_password_path: String,
 }
 
 impl UserApiService {
@@ -17,6 +19,8 @@ impl UserApiService {
         Self {
             user_playlist_categories_path: concat_path_leading_slash(&base_href, "api/v1/user/playlist/categories"),
             user_playlist_bouquet_path: concat_path_leading_slash(&base_href, "api/v1/user/playlist/bouquet"),
+            user_account_path: concat_path_leading_slash(&base_href, "api/v1/user/account"),
+            user_account_password_path: concat_path_leading_slash(&base_href, "api/v1/user/account/password"),
         }
     }
 
@@ -32,6 +36,18 @@ impl UserApiService {
             .inspect_err(|err| error!("{err}"))
     }
 
+    pub async fn get_account(&self) -> Result<Option<Rc<UserAccountDto>>, Error> {
+        request_get::<Rc<UserAccountDto>>(&self.user_account_path, None, None)
+            .await
+            .inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn regenerate_password(&self) -> Result<Option<UserPasswordDto>, Error> {
+        request_post::<(), UserPasswordDto>(&self.user_account_password_path, (), None, None)
+            .await
+            .inspect_err(|err| error!("{err}"))
+    }
+
     pub async fn save_playlist_bouquet(&self, bouquet: &PlaylistBouquetDto) -> Result<(), Error> {
         request_post::<&PlaylistBouquetDto, ()>(&self.user_playlist_bouquet_path, bouquet, None, None)
             .await
@@ -1,12 +1,13 @@
 use std::rc::Rc;
 use log::error;
-use shared::model::ProxyUserCredentialsDto;
+use shared::model::{ProxyUserCredentialsDto, UserImportRequest, UserImportResultDto};
 use shared::utils::{concat_path, concat_path_leading_slash};
 use crate::error::Error;
 use crate::services::{get_base_href, request_delete, request_post, request_put, EventService};
 
 pub struct UserService {
     user_path: String,
+    user_import_path: String,
     event_service: Rc<EventService>,
 }
 
@@ -15,6 +16,7 @@ impl UserService {
         let base_href = get_base_href();
         Self {
             user_path: concat_path_leading_slash(&base_href, "api/v1/user"),
+            user_import_path: concat_path_leading_slash(&base_href, "api/v1/users/import"),
             event_service,
         }
     }
@@ -61,4 +63,19 @@ impl UserService {
             }
         }
     }
+
+    /// Returns the preview of the import for a dry run, otherwise the result of the saved import.
+    pub async fn import_users(&self, request: UserImportRequest) -> Result<UserImportResultDto, Error> {
+        let dry_run = request.dry_run;
+        self.event_service.set_config_change_message_blocked(!dry_run);
+        let result = request_post::<UserImportRequest, UserImportResultDto>(&self.user_import_path, request, None, None).await;
+        self.event_service.set_config_change_message_blocked(false);
+        match result {
+            Ok(result) => Ok(result.unwrap_or_default()),
+            Err(err) => {
+                error!("{err}");
+                Err(err)
+            }
+        }
+    }
 }
@@ -297,6 +297,12 @@ fn handle_socket_protocol_msg(event: MessageEvent, event_service: &Rc<EventServi
                     ProtocolMessage::LibraryScanProgressResponse(msg) => {
                         event_service.broadcast(EventMessage::LibraryScanProgress(msg));
                     }
+                    ProtocolMessage::NotificationResponse(notification) => {
+                        event_service.broadcast(EventMessage::Notification(notification));
+                    }
+                    ProtocolMessage::MappingReprocessResponse(targets) => {
+                        event_service.broadcast(EventMessage::MappingReprocess(targets));
+                    }
                     ProtocolMessage::Version(_) => {
                         attempt_counter.set(0);
                         event_service.broadcast(EventMessage::WebSocketStatus(true));
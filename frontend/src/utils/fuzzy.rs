@@ -0,0 +1,33 @@
+/// Scores how well `query` matches `text`, `None` if the characters of the query
+/// don't appear in the text in the same order.
+/// Consecutive matches and matches at word starts score higher, a higher score is a better match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut last_match: Option<usize> = None;
+    let mut prev_char: Option<char> = None;
+    for (index, c) in text.chars().flat_map(char::to_lowercase).enumerate() {
+        if query_index < query.len() && c == query[query_index] {
+            score += 1;
+            if last_match.is_some_and(|last| last + 1 == index) {
+                score += 5;
+            }
+            if prev_char.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 10;
+            }
+            last_match = Some(index);
+            query_index += 1;
+        }
+        prev_char = Some(c);
+    }
+    if query_index == query.len() {
+        // shorter texts are better matches
+        Some(score * 100 - i32::try_from(text.len()).unwrap_or(i32::MAX).min(99))
+    } else {
+        None
+    }
+}
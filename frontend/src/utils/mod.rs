@@ -1,10 +1,14 @@
 mod storage;
+mod service_worker;
+mod fuzzy;
 
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::Closure;
 use web_sys::window;
 use yew_i18n::YewI18n;
 pub use storage::*;
+pub use service_worker::*;
+pub use fuzzy::*;
 
 #[macro_export]
 macro_rules! html_if {
@@ -0,0 +1,25 @@
+use crate::services::get_base_href;
+use log::{debug, error};
+use shared::utils::concat_path_leading_slash;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::window;
+
+/// Registers `sw.js` for offline support and web push.
+/// Service workers are only available in secure contexts (https or localhost), otherwise nothing is registered.
+pub fn register_service_worker() {
+    let Some(navigator) = window().map(|w| w.navigator()) else {
+        return;
+    };
+    if !js_sys::Reflect::has(&navigator, &JsValue::from_str("serviceWorker")).unwrap_or(false) {
+        debug!("Service worker not supported");
+        return;
+    }
+    let script_url = concat_path_leading_slash(&get_base_href(), "sw.js");
+    let promise = navigator.service_worker().register(&script_url);
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = JsFuture::from(promise).await {
+            error!("Failed to register service worker: {err:?}");
+        }
+    });
+}
@@ -15,6 +15,7 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::sync::Arc;
 use crate::foundation::value_provider::ValueProvider;
+use crate::foundation::resolution_cache::{templates_fingerprint, RESOLUTION_CACHE};
 
 #[derive(Debug, Clone)]
 pub struct CompiledRegex {
@@ -109,6 +110,28 @@ fn get_caption<'a>(provider: &'a ValueProvider<'a>, rewc: &'a CompiledRegex) ->
     (false, "".intern())
 }
 
+pub(crate) fn matches_item_type(provider: &ValueProvider, field: ItemField, item_type: PlaylistItemType) -> bool {
+    if let Some(value) = provider.get(field.as_str()) {
+        get_filter_item_type(&value).is_some_and(|pli_type| {
+            let is_match = match item_type {
+                PlaylistItemType::Video => matches!(pli_type, PlaylistItemType::Video | PlaylistItemType::LocalVideo),
+                PlaylistItemType::Series => matches!(pli_type, PlaylistItemType::Series | PlaylistItemType::LocalSeries),
+                _ => pli_type.eq(&item_type),
+            };
+            if log_enabled!(Level::Trace) {
+                if is_match {
+                    trace!("Match found: {field:?} {value}");
+                } else {
+                    trace!("Match failed: {field:?} {value}");
+                }
+            }
+            is_match
+        })
+    } else {
+        false
+    }
+}
+
 impl Filter {
     pub fn filter(&self, provider: &ValueProvider) -> bool {
         match self {
@@ -129,27 +152,7 @@ impl Filter {
                 }
                 is_match
             }
-            Self::TypeComparison(field, item_type) => {
-                if let Some(value) = provider.get(field.as_str()) {
-                    get_filter_item_type(&value).is_some_and(|pli_type| {
-                        let is_match = match item_type {
-                            PlaylistItemType::Video => matches!(pli_type, PlaylistItemType::Video | PlaylistItemType::LocalVideo),
-                            PlaylistItemType::Series => matches!(pli_type, PlaylistItemType::Series | PlaylistItemType::LocalSeries),
-                            _ => pli_type.eq(item_type),
-                        };
-                        if log_enabled!(Level::Trace) {
-                            if is_match {
-                                trace!("Match found: {field:?} {value}");
-                            } else {
-                                trace!("Match failed: {self}: {field:?} {value}");
-                            }
-                        }
-                        is_match
-                    })
-                } else {
-                    false
-                }
-            }
+            Self::TypeComparison(field, item_type) => matches_item_type(provider, *field, *item_type),
             Self::Group(expr) => expr.filter(provider),
             Self::UnaryExpression(op, expr) => match op {
                 UnaryOperator::Not => !expr.filter(provider),
@@ -166,6 +169,62 @@ impl Filter {
     }
 }
 
+/// A clause of a filter which decided the match of an item, with the value the regex matched.
+pub struct FilterClauseMatch<'a> {
+    pub clause: &'a Filter,
+    pub value: Option<Arc<str>>,
+}
+
+impl Filter {
+    /// Returns the clauses which decided the match, `None` if the filter does not match the item.
+    /// An `OR` is decided by its first matching side, a `NOT` by itself.
+    pub fn explain(&self, provider: &ValueProvider) -> Option<Vec<FilterClauseMatch<'_>>> {
+        let mut clauses = Vec::new();
+        self.collect_matched_clauses(provider, &mut clauses).then_some(clauses)
+    }
+
+    fn collect_matched_clauses<'a>(&'a self, provider: &ValueProvider, clauses: &mut Vec<FilterClauseMatch<'a>>) -> bool {
+        let len = clauses.len();
+        let is_match = match self {
+            Self::FieldComparison(field, rewc) => {
+                let value = if field == &ItemField::Caption {
+                    let (is_match, value) = get_caption(provider, rewc);
+                    is_match.then_some(value)
+                } else {
+                    provider.get(field.as_str()).filter(|value| rewc.re.is_match(value))
+                };
+                value.map(|value| clauses.push(FilterClauseMatch { clause: self, value: Some(value) })).is_some()
+            }
+            Self::TypeComparison(field, item_type) => {
+                let is_match = matches_item_type(provider, *field, *item_type);
+                if is_match {
+                    clauses.push(FilterClauseMatch { clause: self, value: provider.get(field.as_str()) });
+                }
+                is_match
+            }
+            Self::Group(expr) => expr.collect_matched_clauses(provider, clauses),
+            Self::UnaryExpression(UnaryOperator::Not, expr) => {
+                let is_match = !expr.collect_matched_clauses(provider, clauses);
+                clauses.truncate(len);
+                if is_match {
+                    clauses.push(FilterClauseMatch { clause: self, value: None });
+                }
+                is_match
+            }
+            Self::BinaryExpression(left, BinaryOperator::And, right) => {
+                left.collect_matched_clauses(provider, clauses) && right.collect_matched_clauses(provider, clauses)
+            }
+            Self::BinaryExpression(left, BinaryOperator::Or, right) => {
+                left.collect_matched_clauses(provider, clauses) || right.collect_matched_clauses(provider, clauses)
+            }
+        };
+        if !is_match {
+            clauses.truncate(len);
+        }
+        is_match
+    }
+}
+
 impl Filter {
     const LIVE: &'static str = "live";
     const VOD: &'static str = "vod";
@@ -481,7 +540,26 @@ fn build_dependency_graph(
     Ok(graph)
 }
 
+/// Resolves the dependencies between the templates. The resolved values are written back to `templates`.
+/// Results are cached by template content, so unchanged template lists are resolved once per reload.
 pub fn prepare_templates(templates: &mut Vec<PatternTemplate>) -> Result<Vec<PatternTemplate>, TuliproxError> {
+    let fingerprint = templates_fingerprint(templates);
+    if let Some(prepared) = RESOLUTION_CACHE.get_prepared_templates(fingerprint) {
+        let resolved: HashMap<&str, &TemplateValue> = prepared.iter().map(|t| (t.name.as_str(), &t.value)).collect();
+        for template in templates.iter_mut() {
+            template.prepare();
+            if let Some(value) = resolved.get(template.name.as_str()) {
+                template.value = (*value).clone();
+            }
+        }
+        return Ok(prepared);
+    }
+    let prepared = resolve_template_graph(templates)?;
+    RESOLUTION_CACHE.put_prepared_templates(fingerprint, &prepared);
+    Ok(prepared)
+}
+
+fn resolve_template_graph(templates: &mut Vec<PatternTemplate>) -> Result<Vec<PatternTemplate>, TuliproxError> {
     let graph = build_dependency_graph(templates)?;
     let mut template_values = HashMap::new();
     let mut template_map = HashMap::with_capacity(templates.len());
@@ -571,6 +649,25 @@ pub fn apply_templates_to_pattern(
     pattern: &str,
     templates_list: Option<&Vec<PatternTemplate>>,
     allow_multi: bool,
+) -> Result<TemplateValue, TuliproxError> {
+    match templates_list {
+        Some(templates) if !templates.is_empty() => {
+            let fingerprint = templates_fingerprint(templates);
+            if let Some(value) = RESOLUTION_CACHE.get_pattern(fingerprint, pattern, allow_multi) {
+                return Ok(value);
+            }
+            let value = resolve_templates_in_pattern(pattern, templates_list, allow_multi)?;
+            RESOLUTION_CACHE.put_pattern(fingerprint, pattern, allow_multi, &value);
+            Ok(value)
+        }
+        _ => resolve_templates_in_pattern(pattern, templates_list, allow_multi),
+    }
+}
+
+fn resolve_templates_in_pattern(
+    pattern: &str,
+    templates_list: Option<&Vec<PatternTemplate>>,
+    allow_multi: bool,
 ) -> Result<TemplateValue, TuliproxError> {
     let mut new_pattern = TemplateValue::Single(pattern.to_string());
 
@@ -887,4 +984,22 @@ mod tests {
             Err(e) => panic!("{e}"),
         }
     }
+
+    #[test]
+    fn test_filter_explain() {
+        let filter = get_filter(r#"(Group ~ "^US" OR Name ~ "News") AND NOT Name ~ "24/7""#, None).unwrap();
+        let explain = |name: &str, group: &str| {
+            let chan = create_mock_pli(name, group);
+            filter.explain(&ValueProvider { pli: &chan, match_as_ascii: false })
+                .map(|clauses| clauses.iter()
+                    .map(|c| format!("{} => {}", c.clause, c.value.as_deref().unwrap_or("-")))
+                    .collect::<Vec<_>>())
+        };
+        assert_eq!(explain("Entertainment", "US Channels"),
+                   Some(vec![r#"Group ~ "^US" => US Channels"#.to_string(), r#"NOT Name ~ "24/7" => -"#.to_string()]));
+        assert_eq!(explain("News", "FR Channels"),
+                   Some(vec![r#"Name ~ "News" => News"#.to_string(), r#"NOT Name ~ "24/7" => -"#.to_string()]));
+        assert_eq!(explain("24/7: Cars", "US Channels"), None);
+        assert_eq!(explain("Cars", "FR Channels"), None);
+    }
 }
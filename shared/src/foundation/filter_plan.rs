@@ -0,0 +1,273 @@
+use crate::foundation::filter::{matches_item_type, BinaryOperator, Filter, UnaryOperator};
+use crate::foundation::value_provider::ValueProvider;
+use crate::model::{ItemField, PlaylistItemType};
+use aho_corasick::AhoCorasick;
+use log::{log_enabled, trace, Level};
+use regex::RegexSet;
+use regex_syntax::hir::literal::{ExtractKind, Extractor};
+
+/// Patterns with more alternative prefix literals are scanned without prefilter.
+const MAX_PREFILTER_LITERALS: usize = 64;
+
+#[derive(Debug)]
+enum PlanExpr {
+    Match(usize),
+    Type(ItemField, PlaylistItemType),
+    Not(Box<PlanExpr>),
+    And(Box<PlanExpr>, Box<PlanExpr>),
+    Or(Box<PlanExpr>, Box<PlanExpr>),
+}
+
+/// All patterns on one field, matched with a single scan of the field value.
+#[derive(Debug)]
+struct FieldPatterns {
+    field: ItemField,
+    set: RegexSet,
+    /// Plan wide pattern id of each pattern in `set`.
+    pattern_ids: Vec<usize>,
+    /// At least one of these literals occurs in every matching value.
+    /// `None` if a pattern has no usable literal and the set has to be run for each value.
+    prefilter: Option<AhoCorasick>,
+}
+
+impl FieldPatterns {
+    fn values(&self, provider: &ValueProvider) -> [Option<std::sync::Arc<str>>; 2] {
+        // caption matches if the title or the name matches
+        if self.field == ItemField::Caption {
+            [provider.get(ItemField::Title.as_str()), provider.get(ItemField::Name.as_str())]
+        } else {
+            [provider.get(self.field.as_str()), None]
+        }
+    }
+}
+
+/// Matching plan compiled from a list of filters.
+///
+/// The regular expressions of all filters are grouped by field into one `RegexSet`,
+/// so each field of an item is scanned once, no matter how many filters test it.
+/// Fields whose patterns all start with known literals are skipped entirely
+/// when none of the literals occurs in the value.
+#[derive(Debug)]
+pub struct FilterPlan {
+    fields: Vec<FieldPatterns>,
+    /// Index into `fields` for each plan wide pattern id.
+    pattern_fields: Vec<usize>,
+    filters: Vec<Option<PlanExpr>>,
+}
+
+struct PlanBuilder {
+    patterns: Vec<(ItemField, String)>,
+}
+
+impl PlanBuilder {
+    fn pattern_id(&mut self, field: ItemField, pattern: &str) -> usize {
+        if let Some(id) = self.patterns.iter().position(|(f, p)| *f == field && p == pattern) {
+            id
+        } else {
+            self.patterns.push((field, pattern.to_string()));
+            self.patterns.len() - 1
+        }
+    }
+
+    fn compile(&mut self, filter: &Filter) -> PlanExpr {
+        match filter {
+            Filter::FieldComparison(field, rewc) => PlanExpr::Match(self.pattern_id(*field, &rewc.restr)),
+            Filter::TypeComparison(field, item_type) => PlanExpr::Type(*field, *item_type),
+            Filter::Group(expr) => self.compile(expr),
+            Filter::UnaryExpression(UnaryOperator::Not, expr) => PlanExpr::Not(Box::new(self.compile(expr))),
+            Filter::BinaryExpression(left, op, right) => {
+                let left = Box::new(self.compile(left));
+                let right = Box::new(self.compile(right));
+                match op {
+                    BinaryOperator::And => PlanExpr::And(left, right),
+                    BinaryOperator::Or => PlanExpr::Or(left, right),
+                }
+            }
+        }
+    }
+}
+
+fn prefix_literals(pattern: &str) -> Option<Vec<Vec<u8>>> {
+    let hir = regex_syntax::parse(pattern).ok()?;
+    let seq = Extractor::new().kind(ExtractKind::Prefix).extract(&hir);
+    let literals = seq.literals()?;
+    if literals.is_empty() || literals.len() > MAX_PREFILTER_LITERALS || literals.iter().any(|l| l.as_bytes().is_empty()) {
+        return None;
+    }
+    Some(literals.iter().map(|l| l.as_bytes().to_vec()).collect())
+}
+
+fn create_prefilter(patterns: &[&str]) -> Option<AhoCorasick> {
+    let mut literals = Vec::new();
+    for pattern in patterns {
+        literals.extend(prefix_literals(pattern)?);
+    }
+    AhoCorasick::new(literals).ok()
+}
+
+impl FilterPlan {
+    /// Compiles the given filters, the index of a filter is used to evaluate it with [`FilterPlanMatcher::matches`].
+    /// Returns `None` if the patterns can't be combined; the filters have to be evaluated one by one then.
+    pub fn new(filters: &[Option<&Filter>]) -> Option<Self> {
+        let mut builder = PlanBuilder { patterns: Vec::new() };
+        let exprs: Vec<Option<PlanExpr>> = filters.iter().map(|f| f.map(|f| builder.compile(f))).collect();
+
+        let mut fields: Vec<FieldPatterns> = Vec::new();
+        let mut pattern_fields = vec![0; builder.patterns.len()];
+        let mut grouped: Vec<(ItemField, Vec<usize>)> = Vec::new();
+        for (id, (field, _)) in builder.patterns.iter().enumerate() {
+            match grouped.iter_mut().find(|(f, _)| f == field) {
+                Some((_, ids)) => ids.push(id),
+                None => grouped.push((*field, vec![id])),
+            }
+        }
+        for (field, pattern_ids) in grouped {
+            let patterns: Vec<&str> = pattern_ids.iter().map(|id| builder.patterns[*id].1.as_str()).collect();
+            let set = RegexSet::new(&patterns).ok()?;
+            for id in &pattern_ids {
+                pattern_fields[*id] = fields.len();
+            }
+            fields.push(FieldPatterns {
+                field,
+                set,
+                prefilter: create_prefilter(&patterns),
+                pattern_ids,
+            });
+        }
+        if log_enabled!(Level::Trace) {
+            trace!("Filter plan: {} filters, {} patterns on {} fields", exprs.len(), pattern_fields.len(), fields.len());
+        }
+
+        Some(Self {
+            fields,
+            pattern_fields,
+            filters: exprs,
+        })
+    }
+
+    pub fn matcher(&self) -> FilterPlanMatcher<'_> {
+        FilterPlanMatcher {
+            plan: self,
+            scanned: vec![false; self.fields.len()],
+            matched: vec![false; self.pattern_fields.len()],
+        }
+    }
+}
+
+/// Evaluates the filters of a [`FilterPlan`] against one item.
+/// Fields are scanned lazily on first use and the results are shared by all filters,
+/// call [`FilterPlanMatcher::reset`] when the item changes.
+pub struct FilterPlanMatcher<'a> {
+    plan: &'a FilterPlan,
+    scanned: Vec<bool>,
+    matched: Vec<bool>,
+}
+
+impl FilterPlanMatcher<'_> {
+    pub fn reset(&mut self) {
+        self.scanned.fill(false);
+        self.matched.fill(false);
+    }
+
+    fn scan_field(&mut self, field_index: usize, provider: &ValueProvider) {
+        self.scanned[field_index] = true;
+        let field = &self.plan.fields[field_index];
+        for value in field.values(provider).into_iter().flatten() {
+            if field.prefilter.as_ref().is_some_and(|prefilter| !prefilter.is_match(value.as_bytes())) {
+                continue;
+            }
+            for index in &field.set.matches(&value) {
+                self.matched[field.pattern_ids[index]] = true;
+            }
+        }
+    }
+
+    fn is_match(&mut self, pattern_id: usize, provider: &ValueProvider) -> bool {
+        let field_index = self.plan.pattern_fields[pattern_id];
+        if !self.scanned[field_index] {
+            self.scan_field(field_index, provider);
+        }
+        self.matched[pattern_id]
+    }
+
+    fn eval(&mut self, expr: &PlanExpr, provider: &ValueProvider) -> bool {
+        match expr {
+            PlanExpr::Match(pattern_id) => self.is_match(*pattern_id, provider),
+            PlanExpr::Type(field, item_type) => matches_item_type(provider, *field, *item_type),
+            PlanExpr::Not(expr) => !self.eval(expr, provider),
+            PlanExpr::And(left, right) => self.eval(left, provider) && self.eval(right, provider),
+            PlanExpr::Or(left, right) => self.eval(left, provider) || self.eval(right, provider),
+        }
+    }
+
+    /// Returns the result of the filter at `index`, a missing filter never matches.
+    pub fn matches(&mut self, index: usize, provider: &ValueProvider) -> bool {
+        let plan = self.plan;
+        plan.filters.get(index).and_then(Option::as_ref).is_some_and(|expr| self.eval(expr, provider))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{prefix_literals, FilterPlan};
+    use crate::foundation::filter::get_filter;
+    use crate::foundation::value_provider::ValueProvider;
+    use crate::model::{PlaylistItem, PlaylistItemHeader, PlaylistItemType};
+    use crate::utils::Internable;
+
+    fn create_mock_pli(title: &str, group: &str, item_type: PlaylistItemType) -> PlaylistItem {
+        PlaylistItem {
+            header: PlaylistItemHeader {
+                title: title.intern(),
+                name: title.intern(),
+                group: group.intern(),
+                item_type,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_plan_matches_filter() {
+        let filters = [
+            get_filter(r#"Group ~ "^DE" AND NOT Title ~ "(?i)test""#, None).unwrap(),
+            get_filter(r#"Caption ~ "HD$" OR Type = vod"#, None).unwrap(),
+            get_filter(r#"Group ~ "^DE" AND (Title ~ "Sport" OR Title ~ "News")"#, None).unwrap(),
+            get_filter(r#"Name ~ ".*""#, None).unwrap(),
+        ];
+        let plan = FilterPlan::new(&filters.iter().map(Some).collect::<Vec<_>>()).unwrap();
+        let items = [
+            create_mock_pli("Sport HD", "DE: Sport", PlaylistItemType::Live),
+            create_mock_pli("Test News", "DE: News", PlaylistItemType::Live),
+            create_mock_pli("Movie", "EN: Movies", PlaylistItemType::Video),
+            create_mock_pli("", "", PlaylistItemType::Live),
+        ];
+        for pli in &items {
+            let provider = ValueProvider { pli, match_as_ascii: false };
+            let mut matcher = plan.matcher();
+            for (index, filter) in filters.iter().enumerate() {
+                assert_eq!(matcher.matches(index, &provider), filter.filter(&provider), "{filter} on {}", pli.header.title);
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_filter_does_not_match() {
+        let filter = get_filter(r#"Group ~ "A""#, None).unwrap();
+        let plan = FilterPlan::new(&[None, Some(&filter)]).unwrap();
+        let pli = create_mock_pli("x", "A", PlaylistItemType::Live);
+        let provider = ValueProvider { pli: &pli, match_as_ascii: false };
+        let mut matcher = plan.matcher();
+        assert!(!matcher.matches(0, &provider));
+        assert!(matcher.matches(1, &provider));
+    }
+
+    #[test]
+    fn test_prefix_literals() {
+        assert_eq!(prefix_literals("^DE"), Some(vec![b"DE".to_vec()]));
+        assert_eq!(prefix_literals("(Sport|News)"), Some(vec![b"Sport".to_vec(), b"News".to_vec()]));
+        assert_eq!(prefix_literals("HD$"), Some(vec![b"HD".to_vec()]));
+        assert!(prefix_literals(".*").is_none());
+        assert!(prefix_literals("[a-z]+").is_none());
+    }
+}
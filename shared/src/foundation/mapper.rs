@@ -2,7 +2,8 @@
 
 use crate::error::{info_err, info_err_res, TuliproxError};
 use crate::foundation::mapper::EvalResult::{AnyValue, Failure, Named, Number, Undefined, Value};
-use crate::model::{FieldGetAccessor, FieldSetAccessor, PatternTemplate, PlaylistItem, PlaylistItemType, TemplateValue};
+use crate::model::{FieldGetAccessor, FieldSetAccessor, MapperFieldChangeDto, MapperPatternTraceDto, MapperStepTraceDto, PatternTemplate, PlaylistItem,
+                   PlaylistItemType, TemplateValue, MAPPER_FIELDS};
 use crate::utils::{deunicode_string, Capitalize, Internable};
 use log::{debug, error, trace};
 use pest::iterators::{Pair, Pairs};
@@ -94,6 +95,14 @@ impl ValueAccessor<'_> {
     }
 }
 
+/// The fields a mapper can set, `caption` and `epg_id` are aliases and left out.
+pub fn get_trace_fields(pli: &PlaylistItem) -> Vec<(&'static str, Option<Arc<str>>)> {
+    MAPPER_FIELDS.iter()
+        .filter(|field| !matches!(**field, "caption" | "epg_id"))
+        .map(|field| (*field, pli.header.get_field(field)))
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ExprId(pub usize);
 
@@ -297,6 +306,33 @@ impl MapperScript {
         }
     }
 
+    /// Evaluates the script like `eval` and records the regex evaluations and field changes of every statement.
+    pub fn eval_traced(&self, setter: &mut ValueAccessor, templates: Option<&Vec<PatternTemplate>>) -> Vec<MapperStepTraceDto> {
+        let ctx = &mut MapperContext::new(&self.expressions, templates);
+        ctx.patterns = Some(vec![]);
+        let mut steps = Vec::with_capacity(self.statements.len());
+        for (index, stmt) in self.statements.iter().enumerate() {
+            let Statement::Expression(expr_id) = stmt else { continue; };
+            let before = get_trace_fields(setter.pli);
+            let result = expr_id.eval(ctx, setter);
+            let changes = before.into_iter().zip(get_trace_fields(setter.pli))
+                .filter(|((_, before), (_, after))| before != after)
+                .map(|((field, before), (_, after))| MapperFieldChangeDto {
+                    field: field.to_string(),
+                    before: before.map(|v| v.to_string()),
+                    after: after.map(|v| v.to_string()),
+                })
+                .collect();
+            steps.push(MapperStepTraceDto {
+                statement: index,
+                error: if let Failure(err) = result { Some(err) } else { None },
+                patterns: ctx.patterns.replace(vec![]).unwrap_or_default(),
+                changes,
+            });
+        }
+        steps
+    }
+
     pub fn get_expr_by_id(&self, id: usize) -> Option<&Expression> {
         self.expressions.get(id)
     }
@@ -726,6 +762,8 @@ pub struct MapperContext<'a> {
     expressions: &'a Vec<Expression>,
     variables: HashMap<String, EvalResult>,
     templates: Option<HashMap<String, &'a PatternTemplate>>,
+    // regex evaluations are only recorded for a traced run
+    patterns: Option<Vec<MapperPatternTraceDto>>,
 }
 
 impl<'a> MapperContext<'a> {
@@ -744,6 +782,7 @@ impl<'a> MapperContext<'a> {
                     Some(hash_map)
                 }
             }),
+            patterns: None,
         }
     }
 
@@ -1160,7 +1199,7 @@ impl Expression {
             }
             Expression::StringLiteral(s) => Value(s.clone()),
             Expression::NumberLiteral(num) => Number(*num),
-            Expression::RegexExpr { field, pattern: _pattern, re_pattern } => {
+            Expression::RegexExpr { field, pattern, re_pattern } => {
                 let source = match field {
                     RegexSource::Identifier(ident) => {
                         match ctx.get_var(ident) {
@@ -1190,6 +1229,9 @@ impl Expression {
                             }
                         }
                     }
+                    if let Some(patterns) = ctx.patterns.as_mut() {
+                        patterns.push(MapperPatternTraceDto { pattern: pattern.clone(), value: Some(val.to_string()), captures: values.clone() });
+                    }
                     if values.is_empty() {
                         return Undefined;
                     } else if values.len() == 1 {
@@ -1197,6 +1239,9 @@ impl Expression {
                     }
                     return Named(values);
                 }
+                if let Some(patterns) = ctx.patterns.as_mut() {
+                    patterns.push(MapperPatternTraceDto { pattern: pattern.clone(), value: None, captures: vec![] });
+                }
                 Undefined
             }
             Expression::Assignment { target, expr } => {
@@ -1696,6 +1741,27 @@ mod tests {
         // assert_eq!(ctx.variables.get("result").unwrap(), "US: TNT East HD");
     }
 
+    #[test]
+    fn test_mapper_eval_traced() {
+        let dsl = r#"
+            # quality
+            quality = @Caption ~ "(?i)\b(HD|SD)\b"
+            coast = @Caption ~ "(?i)\b(EAST|WEST)\b"
+            @Group = concat("Quality ", uppercase(quality))
+    "#;
+        let mapper = MapperScript::parse(dsl, None).expect("Parsing failed");
+        let mut pli = PlaylistItem { header: PlaylistItemHeader { title: "Channel hd".into(), group: "Old".into(), ..Default::default() } };
+        let mut accessor = ValueAccessor { pli: &mut pli, virtual_items: vec![], match_as_ascii: false };
+        let steps = mapper.eval_traced(&mut accessor, None);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].patterns[0].captures, vec![("1".to_string(), "hd".to_string())]);
+        assert!(steps[0].changes.is_empty());
+        assert_eq!(steps[1].patterns[0].value.as_deref(), Some("Channel hd"));
+        assert!(steps[1].patterns[0].captures.is_empty());
+        assert_eq!(steps[2].changes, vec![MapperFieldChangeDto { field: "group".to_string(), before: Some("Old".to_string()), after: Some("Quality HD".to_string()) }]);
+        assert_eq!(&*pli.header.group, "Quality HD");
+    }
+
     #[test]
     fn test_complex() {
         let script = r#"
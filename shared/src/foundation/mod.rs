@@ -1,9 +1,15 @@
 mod filter;
+mod filter_plan;
 mod mapper;
+mod resolution_cache;
+mod rule_lint;
 mod value_provider;
 
-pub use filter::{Filter, CompiledRegex,
+pub use filter::{Filter, FilterClauseMatch, CompiledRegex,
                  prepare_templates, get_filter, apply_templates_to_pattern,
                  apply_templates_to_pattern_single};
+pub use filter_plan::*;
 pub use mapper::*;
+pub use resolution_cache::*;
+pub use rule_lint::*;
 pub use value_provider::*;
@@ -0,0 +1,123 @@
+use crate::model::{PatternTemplate, TemplateValue};
+use dashmap::DashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::LazyLock;
+
+/// Upper bound of cached entries per kind, the cache is dropped when it is reached.
+const MAX_CACHE_ENTRIES: usize = 50_000;
+
+type PatternKey = (u64, String, bool);
+
+/// Caches env var lookups and template resolution results while config files are parsed.
+///
+/// Template results are keyed by a fingerprint of the templates, so identical mapping and source files
+/// resolve each pattern only once, even across hot reloads and validation requests.
+/// Env vars are cached until the next reload, which clears the cache.
+#[derive(Default)]
+pub struct ResolutionCache {
+    env_vars: DashMap<String, Option<String>>,
+    patterns: DashMap<PatternKey, TemplateValue>,
+    prepared_templates: DashMap<u64, Vec<PatternTemplate>>,
+}
+
+pub static RESOLUTION_CACHE: LazyLock<ResolutionCache> = LazyLock::new(ResolutionCache::default);
+
+fn hash_template_value(value: &TemplateValue, hasher: &mut DefaultHasher) {
+    match value {
+        TemplateValue::Single(value) => {
+            0u8.hash(hasher);
+            value.hash(hasher);
+        }
+        TemplateValue::Multi(values) => {
+            1u8.hash(hasher);
+            values.hash(hasher);
+        }
+    }
+}
+
+pub fn templates_fingerprint(templates: &[PatternTemplate]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    templates.len().hash(&mut hasher);
+    for template in templates {
+        template.name.hash(&mut hasher);
+        hash_template_value(&template.value, &mut hasher);
+    }
+    hasher.finish()
+}
+
+impl ResolutionCache {
+    /// Drops all cached entries, called when config files are reloaded.
+    pub fn clear(&self) {
+        self.env_vars.clear();
+        self.patterns.clear();
+        self.prepared_templates.clear();
+    }
+
+    pub fn get_env_var(&self, name: &str) -> Option<String> {
+        if let Some(value) = self.env_vars.get(name) {
+            return value.clone();
+        }
+        let value = std::env::var(name).ok();
+        if self.env_vars.len() >= MAX_CACHE_ENTRIES {
+            self.env_vars.clear();
+        }
+        self.env_vars.insert(name.to_string(), value.clone());
+        value
+    }
+
+    pub(crate) fn get_pattern(&self, fingerprint: u64, pattern: &str, allow_multi: bool) -> Option<TemplateValue> {
+        self.patterns.get(&(fingerprint, pattern.to_string(), allow_multi)).map(|value| value.clone())
+    }
+
+    pub(crate) fn put_pattern(&self, fingerprint: u64, pattern: &str, allow_multi: bool, value: &TemplateValue) {
+        if self.patterns.len() >= MAX_CACHE_ENTRIES {
+            self.patterns.clear();
+        }
+        self.patterns.insert((fingerprint, pattern.to_string(), allow_multi), value.clone());
+    }
+
+    pub(crate) fn get_prepared_templates(&self, fingerprint: u64) -> Option<Vec<PatternTemplate>> {
+        self.prepared_templates.get(&fingerprint).map(|templates| templates.clone())
+    }
+
+    pub(crate) fn put_prepared_templates(&self, fingerprint: u64, templates: &[PatternTemplate]) {
+        if self.prepared_templates.len() >= MAX_CACHE_ENTRIES {
+            self.prepared_templates.clear();
+        }
+        self.prepared_templates.insert(fingerprint, templates.to_vec());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{templates_fingerprint, RESOLUTION_CACHE};
+    use crate::foundation::{apply_templates_to_pattern_single, prepare_templates};
+    use crate::model::{PatternTemplate, TemplateValue};
+
+    fn template(name: &str, value: &str) -> PatternTemplate {
+        PatternTemplate { name: name.to_string(), value: TemplateValue::Single(value.to_string()), placeholder: String::new() }
+    }
+
+    #[test]
+    fn test_fingerprint_depends_on_content() {
+        let first = vec![template("A", "a"), template("B", "b")];
+        let second = vec![template("A", "a"), template("B", "c")];
+        assert_eq!(templates_fingerprint(&first), templates_fingerprint(&first.clone()));
+        assert_ne!(templates_fingerprint(&first), templates_fingerprint(&second));
+    }
+
+    #[test]
+    fn test_cached_resolution_uses_template_content() {
+        let mut first = vec![template("QUALITY", "HD|FHD"), template("NAME", "Sport !QUALITY!")];
+        let first = prepare_templates(&mut first).unwrap();
+        assert_eq!(apply_templates_to_pattern_single("!NAME!", Some(&first)).unwrap(), "Sport HD|FHD");
+        assert_eq!(apply_templates_to_pattern_single("!NAME!", Some(&first)).unwrap(), "Sport HD|FHD");
+
+        let mut second = vec![template("QUALITY", "4K"), template("NAME", "Sport !QUALITY!")];
+        let second = prepare_templates(&mut second).unwrap();
+        assert_eq!(apply_templates_to_pattern_single("!NAME!", Some(&second)).unwrap(), "Sport 4K");
+
+        RESOLUTION_CACHE.clear();
+        assert_eq!(apply_templates_to_pattern_single("!NAME!", Some(&first)).unwrap(), "Sport HD|FHD");
+    }
+}
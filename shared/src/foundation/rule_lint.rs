@@ -0,0 +1,351 @@
+use crate::foundation::filter::{BinaryOperator, CompiledRegex, Filter, UnaryOperator};
+use crate::foundation::value_provider::{get_field_value, set_field_value, ValueProvider};
+use crate::model::{ItemField, PatternTemplate, PlaylistItem, TemplateValue};
+use regex::Regex;
+use std::sync::Arc;
+
+/// An unanchored regex which can match the empty string matches every value.
+fn matches_every_value(restr: &str) -> bool {
+    regex_syntax::parse(restr).is_ok_and(|hir| {
+        let properties = hir.properties();
+        properties.minimum_len() == Some(0) && properties.look_set().is_empty()
+    })
+}
+
+/// A comparison on a field the item does not have never matches.
+fn is_always_present(field: ItemField) -> bool {
+    !matches!(field, ItemField::Genre | ItemField::Type)
+}
+
+fn collect_conjuncts<'a>(filter: &'a Filter, conjuncts: &mut Vec<&'a Filter>) {
+    match filter {
+        Filter::Group(expr) => collect_conjuncts(expr, conjuncts),
+        Filter::BinaryExpression(left, BinaryOperator::And, right) => {
+            collect_conjuncts(left, conjuncts);
+            collect_conjuncts(right, conjuncts);
+        }
+        _ => conjuncts.push(filter),
+    }
+}
+
+/// An item has only one type, `Type = live AND Type = movie` matches nothing.
+fn has_conflicting_types(filter: &Filter) -> bool {
+    let mut conjuncts = Vec::new();
+    collect_conjuncts(filter, &mut conjuncts);
+    let mut types = conjuncts.iter().filter_map(|f| match f {
+        Filter::TypeComparison(_, item_type) => Some(*item_type),
+        _ => None,
+    });
+    types.next().is_some_and(|first| types.any(|item_type| item_type != first))
+}
+
+/// `Some(true)` if the filter matches every item, `Some(false)` if it matches no item.
+fn constant_result(filter: &Filter) -> Option<bool> {
+    match filter {
+        Filter::FieldComparison(field, rewc) => (is_always_present(*field) && matches_every_value(&rewc.restr)).then_some(true),
+        Filter::TypeComparison(_, _) => None,
+        Filter::Group(expr) => constant_result(expr),
+        Filter::UnaryExpression(UnaryOperator::Not, expr) => constant_result(expr).map(|result| !result),
+        Filter::BinaryExpression(left, BinaryOperator::And, right) => match (constant_result(left), constant_result(right)) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => has_conflicting_types(filter).then_some(false),
+        },
+        Filter::BinaryExpression(left, BinaryOperator::Or, right) => match (constant_result(left), constant_result(right)) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        },
+    }
+}
+
+fn collect_unreachable_branches(filter: &Filter, warnings: &mut Vec<String>) {
+    match filter {
+        Filter::Group(expr) | Filter::UnaryExpression(_, expr) => collect_unreachable_branches(expr, warnings),
+        Filter::BinaryExpression(left, op, right) => {
+            collect_unreachable_branches(left, warnings);
+            let left_result = constant_result(left);
+            match op {
+                BinaryOperator::Or if left_result == Some(true) => {
+                    warnings.push(format!("`{right}` is unreachable, `{left}` always matches"));
+                    return;
+                }
+                BinaryOperator::And if left_result == Some(false) => {
+                    warnings.push(format!("`{right}` is unreachable, `{left}` never matches"));
+                    return;
+                }
+                _ => {}
+            }
+            collect_unreachable_branches(right, warnings);
+            let right_result = constant_result(right);
+            match op {
+                BinaryOperator::Or if right_result == Some(true) => warnings.push(format!("`{left}` has no effect, `{right}` always matches")),
+                BinaryOperator::And if right_result == Some(false) => warnings.push(format!("`{left}` has no effect, `{right}` never matches")),
+                _ if left == right => warnings.push(format!("`{right}` repeats `{left}`")),
+                BinaryOperator::And if has_conflicting_types(filter) => warnings.push(format!("`{filter}` never matches, an item has only one type")),
+                _ => {}
+            }
+        }
+        Filter::FieldComparison(_, _) | Filter::TypeComparison(_, _) => {}
+    }
+}
+
+/// Returns a message for each branch of the filter which can't change the result,
+/// because the other side of the operator always or never matches.
+pub fn find_unreachable_branches(filter: &Filter) -> Vec<String> {
+    let mut warnings = Vec::new();
+    collect_unreachable_branches(filter, &mut warnings);
+    if warnings.is_empty() && constant_result(filter) == Some(false) {
+        warnings.push(format!("`{filter}` never matches"));
+    }
+    warnings
+}
+
+fn collect_filter_patterns<'a>(filter: &'a Filter, patterns: &mut Vec<(ItemField, &'a CompiledRegex)>) {
+    match filter {
+        Filter::FieldComparison(field, rewc) => {
+            if !patterns.iter().any(|(f, p)| f == field && p.restr == rewc.restr) {
+                patterns.push((*field, rewc));
+            }
+        }
+        Filter::TypeComparison(_, _) => {}
+        Filter::Group(expr) | Filter::UnaryExpression(_, expr) => collect_filter_patterns(expr, patterns),
+        Filter::BinaryExpression(left, _, right) => {
+            collect_filter_patterns(left, patterns);
+            collect_filter_patterns(right, patterns);
+        }
+    }
+}
+
+/// Rename rule of a target, in the order the rules are applied.
+pub struct RenameRule<'a> {
+    pub field: ItemField,
+    pub pattern: &'a Arc<Regex>,
+    pub new_name: &'a str,
+}
+
+fn describe_rename(index: usize, rule: &RenameRule) -> String {
+    format!("rename #{} `{} ~ \"{}\"`", index + 1, rule.field, rule.pattern.as_str())
+}
+
+/// Returns a message for each rename rule with the field and pattern of an earlier rule,
+/// the earlier rule already replaced all matches.
+pub fn find_duplicate_renames(renames: &[RenameRule]) -> Vec<String> {
+    renames.iter().enumerate()
+        .filter_map(|(index, rule)| {
+            renames[..index].iter()
+                .position(|earlier| earlier.field == rule.field && earlier.pattern.as_str() == rule.pattern.as_str())
+                .map(|earlier| format!("{} is shadowed by rename #{} with the same pattern", describe_rename(index, rule), earlier + 1))
+        })
+        .collect()
+}
+
+/// The caption is the title or the name.
+fn is_same_value(a: ItemField, b: ItemField) -> bool {
+    a == b || (matches!(a, ItemField::Caption) && matches!(b, ItemField::Title | ItemField::Name))
+        || (matches!(b, ItemField::Caption) && matches!(a, ItemField::Title | ItemField::Name))
+}
+
+fn field_matches(provider: &ValueProvider, field: ItemField, re: &Regex) -> bool {
+    if field == ItemField::Caption {
+        [ItemField::Title, ItemField::Name].iter().any(|f| provider.get(f.as_str()).is_some_and(|value| re.is_match(&value)))
+    } else {
+        provider.get(field.as_str()).is_some_and(|value| re.is_match(&value))
+    }
+}
+
+struct RenameUsage<'a> {
+    rule: RenameRule<'a>,
+    matched: bool,
+    shadowed_by: Option<usize>,
+}
+
+/// Collects which filter patterns and rename rules match the items of a playlist.
+/// The renames are applied like in the processing, a rule which matches the original value
+/// but never the value left by the earlier rules is shadowed by them.
+pub struct PlaylistRuleUsage<'a> {
+    patterns: Vec<(ItemField, &'a CompiledRegex, bool)>,
+    renames: Vec<RenameUsage<'a>>,
+}
+
+impl<'a> PlaylistRuleUsage<'a> {
+    pub fn new(filter: Option<&'a Filter>, renames: Vec<RenameRule<'a>>) -> Self {
+        let mut patterns = Vec::new();
+        if let Some(filter) = filter {
+            collect_filter_patterns(filter, &mut patterns);
+        }
+        Self {
+            patterns: patterns.into_iter().map(|(field, rewc)| (field, rewc, false)).collect(),
+            renames: renames.into_iter().map(|rule| RenameUsage { rule, matched: false, shadowed_by: None }).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty() && self.renames.is_empty()
+    }
+
+    pub fn add_item(&mut self, pli: &PlaylistItem) {
+        let provider = ValueProvider { pli, match_as_ascii: false };
+        for (field, rewc, matched) in self.patterns.iter_mut().filter(|(_, _, matched)| !*matched) {
+            *matched = field_matches(&provider, *field, &rewc.re);
+        }
+
+        if self.renames.is_empty() {
+            return;
+        }
+        let mut item = pli.clone();
+        let mut changed: Vec<(ItemField, usize)> = Vec::new();
+        for (index, usage) in self.renames.iter_mut().enumerate() {
+            let field = usage.rule.field;
+            let value = get_field_value(&item, field);
+            if usage.rule.pattern.is_match(&value) {
+                usage.matched = true;
+                let new_value = usage.rule.pattern.replace_all(&value, usage.rule.new_name);
+                if *new_value != *value {
+                    let new_value = new_value.into_owned();
+                    set_field_value(&mut item, field, new_value);
+                    changed.push((field, index));
+                }
+            } else if !usage.matched && usage.shadowed_by.is_none() && usage.rule.pattern.is_match(&get_field_value(pli, field)) {
+                usage.shadowed_by = changed.iter().rev().find(|(f, _)| is_same_value(*f, field)).map(|(_, earlier)| *earlier);
+            }
+        }
+    }
+
+    /// Returns a message for each filter pattern and rename rule which matched no item.
+    pub fn unmatched(&self) -> Vec<String> {
+        let patterns = self.patterns.iter()
+            .filter(|(_, _, matched)| !*matched)
+            .map(|(field, rewc, _)| format!("`{field} ~ \"{}\"` matches nothing in the last processed playlist", rewc.restr));
+        let renames = self.renames.iter().enumerate()
+            .filter(|(_, usage)| !usage.matched && usage.shadowed_by.is_none())
+            .map(|(index, usage)| format!("{} matches nothing in the last processed playlist", describe_rename(index, &usage.rule)));
+        patterns.chain(renames).collect()
+    }
+
+    /// Returns a message for each rename rule which only matched values the earlier rules had already changed.
+    pub fn shadowed(&self) -> Vec<String> {
+        self.renames.iter().enumerate()
+            .filter(|(_, usage)| !usage.matched)
+            .filter_map(|(index, usage)| usage.shadowed_by.map(|earlier| {
+                format!("{} is shadowed by rename #{}, it only matches values the earlier rule already changed", describe_rename(index, &usage.rule), earlier + 1)
+            }))
+            .collect()
+    }
+}
+
+fn template_values(template: &PatternTemplate) -> Vec<&str> {
+    match &template.value {
+        TemplateValue::Single(value) => vec![value.as_str()],
+        TemplateValue::Multi(values) => values.iter().map(String::as_str).collect(),
+    }
+}
+
+/// Returns the names of the templates which are neither used by a rule nor by a used template.
+/// The templates have to be unprepared, prepared templates have their references replaced.
+pub fn find_unused_templates<'a>(templates: &'a [PatternTemplate], usages: &[&str]) -> Vec<&'a str> {
+    let placeholders: Vec<String> = templates.iter().map(|t| format!("!{}!", t.name)).collect();
+    let mut used = vec![false; templates.len()];
+    let mut pending: Vec<&str> = usages.to_vec();
+    while let Some(text) = pending.pop() {
+        for (index, placeholder) in placeholders.iter().enumerate() {
+            if !used[index] && text.contains(placeholder.as_str()) {
+                used[index] = true;
+                pending.extend(template_values(&templates[index]));
+            }
+        }
+    }
+    templates.iter().zip(used)
+        .filter(|(_, used)| !*used)
+        .map(|(template, _)| template.name.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_duplicate_renames, find_unreachable_branches, find_unused_templates, PlaylistRuleUsage, RenameRule};
+    use crate::foundation::filter::get_filter;
+    use crate::model::{ItemField, PatternTemplate, PlaylistItem, PlaylistItemHeader, PlaylistItemType, TemplateValue, REGEX_CACHE};
+    use crate::utils::Internable;
+
+    fn create_mock_pli(title: &str, group: &str) -> PlaylistItem {
+        PlaylistItem {
+            header: PlaylistItemHeader {
+                title: title.intern(),
+                name: title.intern(),
+                group: group.intern(),
+                item_type: PlaylistItemType::Live,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn unreachable(filter: &str) -> Vec<String> {
+        find_unreachable_branches(&get_filter(filter, None).unwrap())
+    }
+
+    #[test]
+    fn test_find_unreachable_branches() {
+        assert!(unreachable(r#"Group ~ ".*""#).is_empty());
+        assert!(unreachable(r#"Group ~ "^DE" AND NOT Title ~ "(?i)test""#).is_empty());
+        assert!(unreachable(r#"Type = live OR Type = movie"#).is_empty());
+        assert_eq!(unreachable(r#"Group ~ ".*" OR Title ~ "News""#), vec![r#"`Title ~ "News"` is unreachable, `Group ~ ".*"` always matches"#]);
+        assert_eq!(unreachable(r#"Title ~ "News" OR Name ~ "x*""#), vec![r#"`Title ~ "News"` has no effect, `Name ~ "x*"` always matches"#]);
+        assert_eq!(unreachable(r#"NOT Group ~ "" AND Title ~ "News""#), vec![r#"`Title ~ "News"` is unreachable, `NOT Group ~ ""` never matches"#]);
+        assert_eq!(unreachable(r#"Type = live AND Group ~ "DE" AND Type = movie"#),
+                   vec![r#"`Type = live AND Group ~ "DE" AND Type = movie` never matches, an item has only one type"#]);
+        assert_eq!(unreachable(r#"Group ~ "DE" OR Group ~ "DE""#), vec![r#"`Group ~ "DE"` repeats `Group ~ "DE"`"#]);
+        assert!(unreachable(r#"NOT Title ~ "^""#).is_empty());
+        assert_eq!(unreachable(r#"NOT Title ~ ".*""#), vec![r#"`NOT Title ~ ".*"` never matches"#]);
+    }
+
+    #[test]
+    fn test_playlist_rule_usage() {
+        let filter = get_filter(r#"Group ~ "^DE" AND (Title ~ "Sport" OR Title ~ "Cinema")"#, None).unwrap();
+        let hd = REGEX_CACHE.get_or_compile(" HD$").unwrap();
+        let hd_suffix = REGEX_CACHE.get_or_compile("HD$").unwrap();
+        let kids = REGEX_CACHE.get_or_compile("Kids").unwrap();
+        let renames = vec![
+            RenameRule { field: ItemField::Title, pattern: &hd, new_name: "" },
+            RenameRule { field: ItemField::Caption, pattern: &hd_suffix, new_name: "" },
+            RenameRule { field: ItemField::Group, pattern: &kids, new_name: "Children" },
+        ];
+        assert!(find_duplicate_renames(&renames).is_empty());
+
+        let mut usage = PlaylistRuleUsage::new(Some(&filter), renames);
+        for pli in [create_mock_pli("Sport HD", "DE: Sport"), create_mock_pli("News HD", "DE: News")] {
+            usage.add_item(&pli);
+        }
+        assert_eq!(usage.unmatched(), vec![
+            r#"`Title ~ "Cinema"` matches nothing in the last processed playlist"#.to_string(),
+            r#"rename #3 `Group ~ "Kids"` matches nothing in the last processed playlist"#.to_string(),
+        ]);
+        assert_eq!(usage.shadowed(), vec![
+            r#"rename #2 `Caption ~ "HD$"` is shadowed by rename #1, it only matches values the earlier rule already changed"#.to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_find_duplicate_renames() {
+        let hd = REGEX_CACHE.get_or_compile("HD").unwrap();
+        let renames = vec![
+            RenameRule { field: ItemField::Title, pattern: &hd, new_name: "" },
+            RenameRule { field: ItemField::Group, pattern: &hd, new_name: "" },
+            RenameRule { field: ItemField::Title, pattern: &hd, new_name: "FHD" },
+        ];
+        assert_eq!(find_duplicate_renames(&renames), vec![r#"rename #3 `Title ~ "HD"` is shadowed by rename #1 with the same pattern"#]);
+    }
+
+    #[test]
+    fn test_find_unused_templates() {
+        let template = |name: &str, value: &str| PatternTemplate { name: name.to_string(), value: TemplateValue::Single(value.to_string()), placeholder: String::new() };
+        let templates = vec![
+            template("COUNTRY", "(DE|AT)"),
+            template("COUNTRY_GROUP", "^!COUNTRY!:"),
+            template("SPORT", "Sport"),
+            template("UNUSED", "x"),
+        ];
+        let usages = [r#"Group ~ "!COUNTRY_GROUP!""#, r#"Title ~ "!SPORT!""#];
+        assert_eq!(find_unused_templates(&templates, &usages), vec!["UNUSED"]);
+    }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Stable error codes of the api error responses.
+/// The codes are part of the api, existing codes are never renamed or reused.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApiErrorCode {
+    /// Missing or malformed request parameters
+    InvalidArguments,
+    /// The request body could not be parsed or failed the validation
+    InvalidContent,
+    /// The operation is not supported for the requested entity
+    UnsupportedOperation,
+    /// The feature needed for the request is not configured or disabled
+    NotConfigured,
+    /// The same operation is already running
+    AlreadyInProgress,
+    /// The entity already exists
+    Duplicate,
+    /// The entity is still referenced and can't be removed
+    InUse,
+    NotFound,
+    SourceNotFound,
+    TargetNotFound,
+    InputNotFound,
+    UserNotFound,
+    ChannelNotFound,
+    WatermarkNotFound,
+    /// A provider or an external service answered with an error
+    UpstreamError,
+    InternalError,
+}
+
+impl ApiErrorCode {
+    pub const ALL: [ApiErrorCode; 16] = [
+        ApiErrorCode::InvalidArguments,
+        ApiErrorCode::InvalidContent,
+        ApiErrorCode::UnsupportedOperation,
+        ApiErrorCode::NotConfigured,
+        ApiErrorCode::AlreadyInProgress,
+        ApiErrorCode::Duplicate,
+        ApiErrorCode::InUse,
+        ApiErrorCode::NotFound,
+        ApiErrorCode::SourceNotFound,
+        ApiErrorCode::TargetNotFound,
+        ApiErrorCode::InputNotFound,
+        ApiErrorCode::UserNotFound,
+        ApiErrorCode::ChannelNotFound,
+        ApiErrorCode::WatermarkNotFound,
+        ApiErrorCode::UpstreamError,
+        ApiErrorCode::InternalError,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ApiErrorCode::InvalidArguments => "INVALID_ARGUMENTS",
+            ApiErrorCode::InvalidContent => "INVALID_CONTENT",
+            ApiErrorCode::UnsupportedOperation => "UNSUPPORTED_OPERATION",
+            ApiErrorCode::NotConfigured => "NOT_CONFIGURED",
+            ApiErrorCode::AlreadyInProgress => "ALREADY_IN_PROGRESS",
+            ApiErrorCode::Duplicate => "DUPLICATE",
+            ApiErrorCode::InUse => "IN_USE",
+            ApiErrorCode::NotFound => "NOT_FOUND",
+            ApiErrorCode::SourceNotFound => "SOURCE_NOT_FOUND",
+            ApiErrorCode::TargetNotFound => "TARGET_NOT_FOUND",
+            ApiErrorCode::InputNotFound => "INPUT_NOT_FOUND",
+            ApiErrorCode::UserNotFound => "USER_NOT_FOUND",
+            ApiErrorCode::ChannelNotFound => "CHANNEL_NOT_FOUND",
+            ApiErrorCode::WatermarkNotFound => "WATERMARK_NOT_FOUND",
+            ApiErrorCode::UpstreamError => "UPSTREAM_ERROR",
+            ApiErrorCode::InternalError => "INTERNAL_ERROR",
+        }
+    }
+}
+
+impl Display for ApiErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Body of all api error responses.
+/// `message` is a fixed text per error, `detail` carries the request specific part like the name of the missing target.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiErrorResponse {
+    pub code: ApiErrorCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ApiErrorResponse {
+    pub fn new(code: ApiErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            detail: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        let detail = detail.into();
+        self.detail = (!detail.trim().is_empty()).then_some(detail);
+        self
+    }
+}
+
+impl Display for ApiErrorResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{}: {detail}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiErrorCode, ApiErrorResponse};
+
+    #[test]
+    fn test_api_error_codes_are_stable() {
+        for code in ApiErrorCode::ALL {
+            let serialized = serde_json::to_string(&code).unwrap();
+            assert_eq!(serialized, format!("\"{}\"", code.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_api_error_response_serialization() {
+        let error = ApiErrorResponse::new(ApiErrorCode::TargetNotFound, "Target not found").with_detail("movies");
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json, serde_json::json!({"code": "TARGET_NOT_FOUND", "message": "Target not found", "detail": "movies"}));
+        assert_eq!(error.to_string(), "Target not found: movies");
+
+        let error = ApiErrorResponse::new(ApiErrorCode::InvalidArguments, "Invalid arguments").with_detail(" ");
+        assert!(error.detail.is_none());
+        assert_eq!(serde_json::to_value(&error).unwrap(), serde_json::json!({"code": "INVALID_ARGUMENTS", "message": "Invalid arguments"}));
+    }
+}
@@ -4,6 +4,40 @@ pub const TOKEN_NO_AUTH: &str = "authorized";
 
 pub const ROLE_ADMIN: &str = "ADMIN";
 pub const ROLE_USER: &str = "USER";
+pub const ROLE_OPERATOR: &str = "OPERATOR";
+pub const ROLE_VIEWER: &str = "VIEWER";
+
+/// Role of a web ui user, each role includes the permissions of the roles before it.
+/// Viewers can only look, operators can also trigger updates and manage api users,
+/// admins can change the configuration.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebUiRole {
+    Viewer,
+    Operator,
+    #[default]
+    Admin,
+}
+
+impl WebUiRole {
+    pub const fn claim(self) -> &'static str {
+        match self {
+            Self::Viewer => ROLE_VIEWER,
+            Self::Operator => ROLE_OPERATOR,
+            Self::Admin => ROLE_ADMIN,
+        }
+    }
+
+    /// The highest web ui role of the token claims, `None` for api users.
+    pub fn from_claims(roles: &[String]) -> Option<Self> {
+        [Self::Admin, Self::Operator, Self::Viewer].into_iter()
+            .find(|role| roles.iter().any(|r| r == role.claim()))
+    }
+
+    pub fn is_allowed(self, required: Self) -> bool {
+        self >= required
+    }
+}
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Claims {
@@ -31,3 +65,18 @@ pub struct TokenResponse {
     pub token: String,
     pub username: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{WebUiRole, ROLE_OPERATOR, ROLE_USER, ROLE_VIEWER};
+
+    #[test]
+    fn test_web_ui_role() {
+        assert_eq!(WebUiRole::from_claims(&[ROLE_VIEWER.to_string(), ROLE_OPERATOR.to_string()]), Some(WebUiRole::Operator));
+        assert_eq!(WebUiRole::from_claims(&[ROLE_USER.to_string()]), None);
+        assert!(WebUiRole::Admin.is_allowed(WebUiRole::Operator));
+        assert!(!WebUiRole::Viewer.is_allowed(WebUiRole::Operator));
+        let role: WebUiRole = serde_json::from_str("\"viewer\"").unwrap();
+        assert_eq!(role, WebUiRole::Viewer);
+    }
+}
@@ -0,0 +1,32 @@
+use crate::model::CacheEvictionPolicy;
+use serde::{Deserialize, Serialize};
+
+/// Usage of the reverse proxy resource cache.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CacheUsageDto {
+    /// Bytes of the cached files
+    pub size: u64,
+    /// Configured maximum size in bytes
+    pub capacity: u64,
+    pub entries: usize,
+    pub eviction: CacheEvictionPolicy,
+    #[serde(default)]
+    pub max_age_days: u16,
+    /// Store time of the oldest entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oldest_ts: Option<i64>,
+}
+
+/// Removes the cached resources whose url matches the regex `pattern`, without a pattern the cache is cleared.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CachePurgeRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CachePurgeResultDto {
+    pub removed: usize,
+    /// Bytes of the removed files
+    pub freed: u64,
+}
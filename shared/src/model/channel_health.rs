@@ -0,0 +1,251 @@
+use crate::utils::{is_blank_optional_string, is_zero_u16};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Result of the last probe of a live channel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelHealthDto {
+    pub target: String,
+    pub input_name: String,
+    pub virtual_id: u32,
+    /// Playlist uuid of the channel, stable across playlist updates
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub uuid: String,
+    pub title: String,
+    pub up: bool,
+    /// Consecutive failed probes
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub failures: u16,
+    /// Milliseconds until the first bytes of the stream were received
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Container sniffed from the first bytes, like `mpegts` or `hls`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub codecs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Unix timestamp of the probe
+    pub ts: i64,
+}
+
+/// Dead channels of the probed sample of an input.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SourceChannelHealthDto {
+    pub input_name: String,
+    pub probed: usize,
+    pub down: usize,
+    pub dead_percentage: f64,
+    /// Playback errors reported by clients
+    #[serde(default)]
+    pub playback_errors: usize,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackErrorKind {
+    /// The stream request failed with a http status
+    Http,
+    /// The playback stalled
+    Stall,
+    /// The player could not decode the stream
+    Media,
+}
+
+/// Playback error reported by the preview player of the web ui or the user portal.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaybackErrorReportDto {
+    pub target_id: u16,
+    pub virtual_id: u32,
+    pub kind: PlaybackErrorKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub message: Option<String>,
+}
+
+/// Reported playback error, correlated with the stream session of the user.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaybackErrorDto {
+    pub username: String,
+    pub target: String,
+    pub input_name: String,
+    pub virtual_id: u32,
+    pub title: String,
+    pub kind: PlaybackErrorKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub message: Option<String>,
+    /// Unix timestamp of the start of the stream session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_start: Option<u64>,
+    /// Provider of the stream session
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub provider: Option<String>,
+    /// Unix timestamp of the report
+    pub ts: i64,
+}
+
+/// Reported playback errors of a channel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelPlaybackErrorsDto {
+    pub target: String,
+    pub input_name: String,
+    pub virtual_id: u32,
+    pub title: String,
+    pub errors: usize,
+    pub stalls: usize,
+    /// Errors which were reported during a stream session
+    pub session_errors: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_status: Option<u16>,
+    pub last_ts: i64,
+}
+
+/// Channel removed from a target playlist by `remove_dead_channels`, it is restored when a probe succeeds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuarantinedChannelDto {
+    pub target: String,
+    pub input_name: String,
+    pub uuid: String,
+    pub virtual_id: u32,
+    pub title: String,
+    /// Provider url, quarantined channels are probed although they are not in the playlist
+    pub url: String,
+    /// Unix timestamp of the quarantine
+    pub since: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ChannelHealthReportDto {
+    pub sources: Vec<SourceChannelHealthDto>,
+    pub channels: Vec<ChannelHealthDto>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quarantine: Vec<QuarantinedChannelDto>,
+    /// Channels with reported playback errors, most errors first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub playback_errors: Vec<ChannelPlaybackErrorsDto>,
+}
+
+impl ChannelHealthReportDto {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(channels: Vec<ChannelHealthDto>, quarantine: Vec<QuarantinedChannelDto>, playback_errors: &[PlaybackErrorDto]) -> Self {
+        // probed, down, playback errors
+        let mut counts: BTreeMap<&str, (usize, usize, usize)> = BTreeMap::new();
+        for channel in &channels {
+            let (probed, down, _) = counts.entry(channel.input_name.as_str()).or_default();
+            *probed += 1;
+            if !channel.up {
+                *down += 1;
+            }
+        }
+        let mut channel_errors: BTreeMap<(&str, u32), ChannelPlaybackErrorsDto> = BTreeMap::new();
+        for error in playback_errors {
+            counts.entry(error.input_name.as_str()).or_default().2 += 1;
+            let entry = channel_errors.entry((error.target.as_str(), error.virtual_id)).or_insert_with(|| ChannelPlaybackErrorsDto {
+                target: error.target.clone(),
+                input_name: error.input_name.clone(),
+                virtual_id: error.virtual_id,
+                title: error.title.clone(),
+                errors: 0,
+                stalls: 0,
+                session_errors: 0,
+                last_status: None,
+                last_ts: error.ts,
+            });
+            entry.errors += 1;
+            if error.kind == PlaybackErrorKind::Stall {
+                entry.stalls += 1;
+            }
+            if error.session_start.is_some() {
+                entry.session_errors += 1;
+            }
+            if error.ts >= entry.last_ts {
+                entry.last_ts = error.ts;
+                entry.last_status = error.status.or(entry.last_status);
+            }
+        }
+        let sources = counts.into_iter()
+            .map(|(input_name, (probed, down, playback_errors))| SourceChannelHealthDto {
+                input_name: input_name.to_string(),
+                probed,
+                down,
+                dead_percentage: if probed == 0 { 0.0 } else { (down as f64 * 1000.0 / probed as f64).round() / 10.0 },
+                playback_errors,
+            })
+            .collect();
+        let mut playback_errors: Vec<ChannelPlaybackErrorsDto> = channel_errors.into_values().collect();
+        playback_errors.sort_by(|a, b| b.errors.cmp(&a.errors).then(b.last_ts.cmp(&a.last_ts)));
+        Self { sources, channels, quarantine, playback_errors }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelHealthDto, ChannelHealthReportDto, PlaybackErrorDto, PlaybackErrorKind};
+
+    fn channel(input_name: &str, up: bool) -> ChannelHealthDto {
+        ChannelHealthDto {
+            target: "all".to_string(),
+            input_name: input_name.to_string(),
+            virtual_id: 1,
+            uuid: String::new(),
+            title: "Channel".to_string(),
+            up,
+            failures: 0,
+            latency_ms: None,
+            status: None,
+            format: None,
+            codecs: Vec::new(),
+            error: None,
+            ts: 0,
+        }
+    }
+
+    #[test]
+    fn test_channel_health_report() {
+        let report = ChannelHealthReportDto::new(vec![channel("b", true), channel("a", false), channel("a", true), channel("a", true)], Vec::new(), &[]);
+        assert_eq!(report.sources.len(), 2);
+        assert_eq!(report.sources[0].input_name, "a");
+        assert_eq!((report.sources[0].probed, report.sources[0].down), (3, 1));
+        assert!((report.sources[0].dead_percentage - 33.3).abs() < f64::EPSILON);
+        assert!(report.sources[1].dead_percentage.abs() < f64::EPSILON);
+    }
+
+    fn playback_error(input_name: &str, virtual_id: u32, kind: PlaybackErrorKind, session_start: Option<u64>, ts: i64) -> PlaybackErrorDto {
+        PlaybackErrorDto {
+            username: "user".to_string(),
+            target: "all".to_string(),
+            input_name: input_name.to_string(),
+            virtual_id,
+            title: format!("Channel {virtual_id}"),
+            kind,
+            status: (kind == PlaybackErrorKind::Http).then_some(502),
+            message: None,
+            session_start,
+            provider: None,
+            ts,
+        }
+    }
+
+    #[test]
+    fn test_channel_health_report_playback_errors() {
+        let errors = [
+            playback_error("a", 1, PlaybackErrorKind::Http, None, 10),
+            playback_error("c", 2, PlaybackErrorKind::Stall, Some(5), 20),
+            playback_error("c", 2, PlaybackErrorKind::Stall, Some(5), 30),
+        ];
+        let report = ChannelHealthReportDto::new(vec![channel("a", false)], Vec::new(), &errors);
+        assert_eq!(report.sources.iter().map(|s| (s.input_name.as_str(), s.probed, s.playback_errors)).collect::<Vec<_>>(),
+                   vec![("a", 1, 1), ("c", 0, 2)]);
+        assert!(report.sources[1].dead_percentage.abs() < f64::EPSILON);
+        assert_eq!(report.playback_errors.len(), 2);
+        let channel = &report.playback_errors[0];
+        assert_eq!((channel.virtual_id, channel.errors, channel.stalls, channel.session_errors, channel.last_ts), (2, 2, 2, 2, 30));
+        assert_eq!(report.playback_errors[1].last_status, Some(502));
+    }
+}
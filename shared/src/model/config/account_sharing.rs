@@ -0,0 +1,92 @@
+use crate::error::{TuliproxError, TuliproxErrorKind};
+use crate::utils::{default_account_sharing_block_secs, default_account_sharing_window_secs, is_false, is_zero_u16};
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountSharingAction {
+    /// Sends a message, the user can continue.
+    #[default]
+    Alert,
+    /// Sends a message and denies the streams of the user for `block_secs`.
+    Block,
+}
+
+impl fmt::Display for AccountSharingAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AccountSharingAction::Alert => "alert",
+            AccountSharingAction::Block => "block",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Detects shared accounts by the distinct clients of a user within a sliding window.
+/// A limit of 0 disables the check.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AccountSharingConfigDto {
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub enabled: bool,
+    #[serde(default = "default_account_sharing_window_secs")]
+    pub window_secs: u64,
+    /// Distinct client ip addresses.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub max_ips: u16,
+    /// Distinct client countries resolved with the geoip db.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub max_countries: u16,
+    /// Distinct client fingerprints (ip and user agent).
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub max_devices: u16,
+    #[serde(default)]
+    pub action: AccountSharingAction,
+    #[serde(default = "default_account_sharing_block_secs")]
+    pub block_secs: u64,
+}
+
+impl Default for AccountSharingConfigDto {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_secs: default_account_sharing_window_secs(),
+            max_ips: 0,
+            max_countries: 0,
+            max_devices: 0,
+            action: AccountSharingAction::default(),
+            block_secs: default_account_sharing_block_secs(),
+        }
+    }
+}
+
+impl AccountSharingConfigDto {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled && self.max_ips == 0 && self.max_countries == 0 && self.max_devices == 0
+    }
+
+    pub(crate) fn prepare(&self) -> Result<(), TuliproxError> {
+        if self.enabled && self.window_secs == 0 {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "Account sharing window_secs must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Overrides the account sharing limits of the reverse proxy for a user.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AccountSharingLimitsDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ips: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_countries: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_devices: Option<u16>,
+}
+
+impl AccountSharingLimitsDto {
+    pub fn is_empty(&self) -> bool {
+        self.max_ips.is_none() && self.max_countries.is_none() && self.max_devices.is_none()
+    }
+}
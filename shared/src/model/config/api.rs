@@ -1,3 +1,5 @@
+use crate::utils::{default_shutdown_grace_secs, is_default_shutdown_grace_secs, is_false};
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ConfigApiDto {
@@ -5,6 +7,12 @@ pub struct ConfigApiDto {
     pub port: u16,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub web_root: String,
+    /// Exposes the runtime diagnostics under `api/v1/debug` for admins.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub debug_endpoints: bool,
+    /// Seconds the active streams can continue after a shutdown signal, new streams are rejected.
+    #[serde(default = "default_shutdown_grace_secs", skip_serializing_if = "is_default_shutdown_grace_secs")]
+    pub shutdown_grace_secs: u16,
 }
 
 impl ConfigApiDto {
@@ -1,6 +1,6 @@
-use crate::utils::{is_blank_optional_string, is_true, default_as_true, deserialize_timestamp};
+use crate::utils::{is_blank_optional_string, is_true, default_as_true, deserialize_timestamp, normalize_country_codes};
 use crate::error::{TuliproxError, TuliproxErrorKind};
-use crate::model::{ProxyType, ProxyUserStatus};
+use crate::model::{AccountSharingLimitsDto, ProxyType, ProxyUserStatus};
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub enum UserConnectionPermission {
@@ -34,11 +34,25 @@ pub struct ProxyUserCredentialsDto {
     pub ui_enabled: bool,
     #[serde(default, skip_serializing_if = "is_blank_optional_string")]
     pub comment: Option<String>,
+    /// Country codes of the clients allowed to use the account, empty allows all countries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_countries: Vec<String>,
+    /// Country codes of the clients denied to use the account.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_countries: Vec<String>,
+    /// Overrides the account sharing limits of the reverse proxy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_sharing: Option<AccountSharingLimitsDto>,
 }
 
 impl ProxyUserCredentialsDto {
     pub fn prepare(&mut self) {
         self.trim();
+        normalize_country_codes(&mut self.allowed_countries);
+        normalize_country_codes(&mut self.blocked_countries);
+        if self.account_sharing.as_ref().is_some_and(AccountSharingLimitsDto::is_empty) {
+            self.account_sharing = None;
+        }
     }
 
     fn trim(&mut self) {
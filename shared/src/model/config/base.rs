@@ -1,7 +1,8 @@
 use crate::error::{TuliproxError, TuliproxErrorKind};
-use crate::model::{ConfigApiDto, HdHomeRunConfigDto, IpCheckConfigDto, LibraryConfigDto, LogConfigDto, MessagingConfigDto,
-                   ProxyConfigDto, ReverseProxyConfigDto, ScheduleConfigDto, VideoConfigDto, WebUiConfigDto};
-use crate::utils::{is_false, default_connect_timeout_secs, is_default_connect_timeout_secs, is_blank_optional_string,
+use crate::model::{prepare_network_routes, ConfigApiDto, HdHomeRunConfigDto, IpCheckConfigDto, LibraryConfigDto, LogConfigDto, MappingReprocessConfigDto, MessagingConfigDto,
+                   NetworkRouteConfigDto, ProxyConfigDto, ChannelProbeConfigDto, UserExpirationConfigDto, ReverseProxyConfigDto, RuntimeConfigDto, ClusterConfigDto, ScheduleConfigDto, ScheduleRetryConfigDto, VideoConfigDto,
+                   WebUiConfigDto};
+use crate::utils::{is_false, is_zero_u16, default_connect_timeout_secs, is_default_connect_timeout_secs, is_blank_optional_string,
                    default_supported_video_extensions};
 
 pub const DEFAULT_USER_AGENT: &str = "VLC/3.0.16 LibVLC/3.0.16";
@@ -16,6 +17,8 @@ fn default_default_user_agent() -> Option<String> {
 pub struct ConfigDto {
     #[serde(default, skip_serializing_if = "is_false")]
     pub process_parallel: bool,
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub process_parallel_targets: u16,
     pub api: ConfigApiDto,
     pub working_dir: String,
     #[serde(default = "default_default_user_agent", skip_serializing_if = "is_blank_optional_string")]
@@ -32,6 +35,12 @@ pub struct ConfigDto {
     pub video: Option<VideoConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub schedules: Option<Vec<ScheduleConfigDto>>,
+    /// Spreads the start times of schedules with the same expression over this window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_spread_mins: Option<u32>,
+    /// Retries the failed targets of a scheduled run with exponential backoff
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_retry: Option<ScheduleRetryConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub log: Option<LogConfigDto>,
     #[serde(default, skip_serializing_if = "is_false")]
@@ -44,8 +53,14 @@ pub struct ConfigDto {
     pub update_on_boot: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub config_hot_reload: bool,
+    /// Re-processes the targets of changed mappings after a hot reload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mapping_reprocess: Option<MappingReprocessConfigDto>,
     #[serde(default, skip_serializing_if = "is_false")]
     pub disk_based_processing: bool,
+    /// Verifies the checksums of the database pages on read to detect silent corruption
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub verify_storage_checksums: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub accept_insecure_ssl_certificates: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -61,7 +76,17 @@ pub struct ConfigDto {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ipcheck: Option<IpCheckConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_routes: Option<Vec<NetworkRouteConfigDto>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_probe: Option<ChannelProbeConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_expiration: Option<UserExpirationConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub library: Option<LibraryConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<RuntimeConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cluster: Option<ClusterConfigDto>,
 }
 
 // This MainConfigDto is a copy of ConfigDto simple fields for form editing.
@@ -70,6 +95,8 @@ pub struct ConfigDto {
 pub struct MainConfigDto {
     #[serde(default, skip_serializing_if = "is_false")]
     pub process_parallel: bool,
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub process_parallel_targets: u16,
     pub working_dir: String,
     #[serde(default = "default_default_user_agent", skip_serializing_if = "is_blank_optional_string")]
     pub default_user_agent: Option<String>,
@@ -85,6 +112,9 @@ pub struct MainConfigDto {
     pub user_access_control: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub disk_based_processing: bool,
+    /// Verifies the checksums of the database pages on read to detect silent corruption
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub verify_storage_checksums: bool,
     #[serde(default = "default_connect_timeout_secs", skip_serializing_if = "is_default_connect_timeout_secs")]
     pub connect_timeout_secs: u32,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -101,7 +131,9 @@ impl Default for MainConfigDto {
     fn default() -> Self {
         MainConfigDto {
             process_parallel: false,
+            process_parallel_targets: 0,
             disk_based_processing: false,
+            verify_storage_checksums: false,
             working_dir: String::new(),
             default_user_agent: default_default_user_agent(),
             backup_dir: None,
@@ -122,7 +154,9 @@ impl From<&ConfigDto> for MainConfigDto {
     fn from(config: &ConfigDto) -> Self {
         Self {
             process_parallel: config.process_parallel,
+            process_parallel_targets: config.process_parallel_targets,
             disk_based_processing: config.disk_based_processing,
+            verify_storage_checksums: config.verify_storage_checksums,
             working_dir: config.working_dir.clone(),
             default_user_agent: config.default_user_agent.clone(),
             backup_dir: config.backup_dir.clone(),
@@ -145,6 +179,8 @@ impl From<&ConfigDto> for MainConfigDto {
 pub struct SchedulesConfigDto {
     #[serde(default)]
     pub schedules: Option<Vec<ScheduleConfigDto>>,
+    #[serde(default)]
+    pub schedule_spread_mins: Option<u32>,
 }
 
 impl SchedulesConfigDto {
@@ -157,6 +193,7 @@ impl From<&ConfigDto> for SchedulesConfigDto {
     fn from(config: &ConfigDto) -> Self {
         Self {
             schedules: config.schedules.clone(),
+            schedule_spread_mins: config.schedule_spread_mins,
         }
     }
 }
@@ -193,6 +230,21 @@ impl ConfigDto {
         if let Some(ipcheck) = self.ipcheck.as_mut() {
             ipcheck.prepare()?;
         }
+        if let Some(routes) = self.network_routes.as_mut() {
+            prepare_network_routes(routes)?;
+        }
+        if let Some(channel_probe) = self.channel_probe.as_mut() {
+            channel_probe.prepare()?;
+        }
+        if let Some(user_expiration) = self.user_expiration.as_mut() {
+            user_expiration.prepare()?;
+        }
+        if let Some(runtime) = self.runtime.as_mut() {
+            runtime.prepare()?;
+        }
+        if let Some(cluster) = self.cluster.as_mut() {
+            cluster.prepare()?;
+        }
 
         if let Some(messaging) = &mut self.messaging {
             messaging.prepare(include_computed)?;
@@ -266,7 +318,9 @@ impl ConfigDto {
 
     pub fn update_from_main_config(&mut self, main_config: &MainConfigDto) {
         self.process_parallel = main_config.process_parallel;
+        self.process_parallel_targets = main_config.process_parallel_targets;
         self.disk_based_processing = main_config.disk_based_processing;
+        self.verify_storage_checksums = main_config.verify_storage_checksums;
         self.working_dir = main_config.working_dir.clone();
         self.default_user_agent = main_config.default_user_agent.clone();
         self.backup_dir = main_config.backup_dir.clone();
@@ -1,9 +1,23 @@
 use std::path::PathBuf;
 use crate::error::{TuliproxError};
 use crate::{info_err_res};
-use crate::utils::{is_blank_optional_str, is_blank_optional_string, parse_size_base_2};
+use crate::utils::{is_blank_optional_str, is_blank_optional_string, is_false, is_zero_u16, is_zero_u32, parse_size_base_2};
 use path_clean::PathClean;
 
+/// Which entry is removed when the cache exceeds its size, `lru` the least recently used, `lfu` the least frequently used.
+#[derive(Debug, Copy, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheEvictionPolicy {
+    #[default]
+    Lru,
+    Lfu,
+}
+
+impl CacheEvictionPolicy {
+    pub fn is_lru(&self) -> bool {
+        *self == CacheEvictionPolicy::Lru
+    }
+}
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -14,11 +28,27 @@ pub struct CacheConfigDto {
     pub size: Option<String>,
     #[serde(default, skip_serializing_if = "is_blank_optional_string")]
     pub dir: Option<String>,
+    /// Logos are served through the cache also for users with the `redirect` proxy type.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub proxy_logos: bool,
+    /// Logos wider than this are downscaled and stored as webp, 0 keeps the original logo.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub logo_width: u16,
+    /// Seconds the xtream stream list responses are cached in memory and on disk, 0 disables the cache.
+    #[serde(default, skip_serializing_if = "is_zero_u32")]
+    pub xtream_api_ttl_secs: u32,
+    /// Entries stored longer than this are removed by the cleanup task, 0 keeps them until they are evicted.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub max_age_days: u16,
+    #[serde(default, skip_serializing_if = "CacheEvictionPolicy::is_lru")]
+    pub eviction: CacheEvictionPolicy,
 }
 
 impl CacheConfigDto {
     pub fn is_empty(&self) -> bool {
         !self.enabled && is_blank_optional_str(self.size.as_deref()) && is_blank_optional_str(self.dir.as_deref())
+            && !self.proxy_logos && self.logo_width == 0 && self.xtream_api_ttl_secs == 0
+            && self.max_age_days == 0 && self.eviction.is_lru()
     }
 
     pub(crate) fn prepare(&mut self, working_dir: &str) -> Result<(), TuliproxError> {
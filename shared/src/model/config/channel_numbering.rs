@@ -0,0 +1,104 @@
+use crate::error::{info_err_res, TuliproxError};
+use crate::foundation::{get_filter, Filter};
+use crate::model::PatternTemplate;
+use crate::utils::{default_channel_number_start, is_default_channel_number_start, is_false};
+
+/// Channel numbers for the live channels of the groups matching the `group` regex.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigChannelNumberRangeDto {
+    pub group: String,
+    pub start: u32,
+}
+
+/// Fixed channel number for the first live channel matching the `filter`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigChannelNumberPinDto {
+    pub filter: String,
+    pub number: u32,
+    #[serde(skip)]
+    pub t_filter: Option<Filter>,
+}
+
+/// Logical channel numbers of the live channels of a target. Assigned numbers are persisted,
+/// a channel keeps its number between the updates.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigChannelNumberingDto {
+    #[serde(default = "default_channel_number_start", skip_serializing_if = "is_default_channel_number_start")]
+    pub start: u32,
+    /// Keeps the channel numbers of the provider (`tvg-chno`) as long as they are not taken
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub preserve_upstream: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranges: Vec<ConfigChannelNumberRangeDto>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pins: Vec<ConfigChannelNumberPinDto>,
+}
+
+impl Default for ConfigChannelNumberingDto {
+    fn default() -> Self {
+        Self {
+            start: default_channel_number_start(),
+            preserve_upstream: false,
+            ranges: Vec::new(),
+            pins: Vec::new(),
+        }
+    }
+}
+
+impl ConfigChannelNumberingDto {
+    pub fn prepare(&mut self, templates: Option<&Vec<PatternTemplate>>) -> Result<(), TuliproxError> {
+        if self.start == 0 {
+            return info_err_res!("channel numbering `start` has to be greater than 0");
+        }
+        for range in &mut self.ranges {
+            range.group = range.group.trim().to_string();
+            if range.start == 0 {
+                return info_err_res!("channel number range `start` has to be greater than 0 for group {}", range.group);
+            }
+            if let Err(err) = crate::model::REGEX_CACHE.get_or_compile(&range.group) {
+                return info_err_res!("Invalid channel number range group {}: {err}", range.group);
+            }
+        }
+        self.ranges.retain(|range| !range.group.is_empty());
+        for pin in &mut self.pins {
+            if pin.number == 0 {
+                return info_err_res!("channel number pin `number` has to be greater than 0 for filter {}", pin.filter);
+            }
+            pin.t_filter = Some(get_filter(&pin.filter, templates)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigChannelNumberPinDto, ConfigChannelNumberRangeDto, ConfigChannelNumberingDto};
+
+    #[test]
+    fn test_channel_numbering_prepare() {
+        let mut numbering: ConfigChannelNumberingDto = serde_saphyr::from_str(r#"
+preserve_upstream: true
+ranges:
+  - group: " (?i)sport "
+    start: 500
+pins:
+  - filter: 'Title ~ "^BBC One"'
+    number: 1
+"#).unwrap();
+        assert!(numbering.prepare(None).is_ok());
+        assert_eq!(numbering.start, 1);
+        assert_eq!(numbering.ranges[0].group, "(?i)sport");
+        assert!(numbering.pins[0].t_filter.is_some());
+
+        let range = |group: &str, start: u32| ConfigChannelNumberRangeDto { group: group.to_string(), start };
+        let pin = |filter: &str, number: u32| ConfigChannelNumberPinDto { filter: filter.to_string(), number, t_filter: None };
+        assert!(ConfigChannelNumberingDto { start: 0, ..Default::default() }.prepare(None).is_err());
+        assert!(ConfigChannelNumberingDto { ranges: vec![range("(sport", 10)], ..Default::default() }.prepare(None).is_err());
+        assert!(ConfigChannelNumberingDto { ranges: vec![range("sport", 0)], ..Default::default() }.prepare(None).is_err());
+        assert!(ConfigChannelNumberingDto { pins: vec![pin(r#"Title ~ "News""#, 0)], ..Default::default() }.prepare(None).is_err());
+        assert!(ConfigChannelNumberingDto { pins: vec![pin("Title ~", 1)], ..Default::default() }.prepare(None).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::error::{TuliproxError, TuliproxErrorKind};
+use crate::utils::{default_channel_probe_interval_mins, default_channel_probe_sample_size, default_channel_probe_timeout_secs};
+
+/// Periodically probes a random sample of the live channels of the targets.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelProbeConfigDto {
+    #[serde(default = "default_channel_probe_interval_mins")]
+    pub interval_mins: u32,
+    /// Number of live channels probed per target and run.
+    #[serde(default = "default_channel_probe_sample_size")]
+    pub sample_size: u16,
+    #[serde(default = "default_channel_probe_timeout_secs")]
+    pub timeout_secs: u16,
+    /// Names of the probed targets, all targets are probed if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+}
+
+impl Default for ChannelProbeConfigDto {
+    fn default() -> Self {
+        Self {
+            interval_mins: default_channel_probe_interval_mins(),
+            sample_size: default_channel_probe_sample_size(),
+            timeout_secs: default_channel_probe_timeout_secs(),
+            targets: Vec::new(),
+        }
+    }
+}
+
+impl ChannelProbeConfigDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        if self.interval_mins == 0 {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "channel_probe interval_mins must be > 0".to_string()));
+        }
+        if self.sample_size == 0 {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "channel_probe sample_size must be > 0".to_string()));
+        }
+        if self.timeout_secs == 0 {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "channel_probe timeout_secs must be > 0".to_string()));
+        }
+        self.targets.retain_mut(|target| {
+            *target = target.trim().to_string();
+            !target.is_empty()
+        });
+        Ok(())
+    }
+}
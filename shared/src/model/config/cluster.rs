@@ -0,0 +1,79 @@
+use crate::error::{TuliproxError, TuliproxErrorKind};
+use crate::utils::{default_cluster_key_prefix, default_cluster_sync_interval_millis, is_blank_optional_string, is_false};
+
+const MIN_SYNC_INTERVAL_MILLIS: u64 = 100;
+
+/// Shares the connection counts and the user sessions of multiple instances behind a load balancer through redis.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ClusterConfigDto {
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub enabled: bool,
+    /// Like `redis://:password@redis:6379/0`
+    #[serde(default)]
+    pub redis_url: String,
+    /// Unique name of the instance, defaults to the hostname.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub node_id: Option<String>,
+    /// Prefix of the redis keys, instances with the same prefix share their state.
+    #[serde(default = "default_cluster_key_prefix")]
+    pub key_prefix: String,
+    #[serde(default = "default_cluster_sync_interval_millis")]
+    pub sync_interval_millis: u64,
+}
+
+impl Default for ClusterConfigDto {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            redis_url: String::new(),
+            node_id: None,
+            key_prefix: default_cluster_key_prefix(),
+            sync_interval_millis: default_cluster_sync_interval_millis(),
+        }
+    }
+}
+
+impl ClusterConfigDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.redis_url = self.redis_url.trim().to_string();
+        self.key_prefix = self.key_prefix.trim().to_string();
+        if self.key_prefix.is_empty() {
+            self.key_prefix = default_cluster_key_prefix();
+        }
+        self.node_id = self.node_id.as_ref().map(|id| id.trim().to_string()).filter(|id| !id.is_empty());
+        if !self.enabled {
+            return Ok(());
+        }
+        if !(self.redis_url.starts_with("redis://") || self.redis_url.starts_with("redis+unix://") || self.redis_url.starts_with("unix://")) {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "cluster redis_url must start with redis:// or unix://".to_string()));
+        }
+        if self.sync_interval_millis < MIN_SYNC_INTERVAL_MILLIS {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, format!("cluster sync_interval_millis must be >= {MIN_SYNC_INTERVAL_MILLIS}")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClusterConfigDto;
+
+    #[test]
+    fn test_prepare_cluster_config() {
+        let mut config = ClusterConfigDto { enabled: true, redis_url: " redis://redis:6379/0 ".to_string(), node_id: Some(" ".to_string()), ..ClusterConfigDto::default() };
+        assert!(config.prepare().is_ok());
+        assert_eq!(config.redis_url, "redis://redis:6379/0");
+        assert!(config.node_id.is_none());
+
+        config.redis_url = "http://redis:6379".to_string();
+        assert!(config.prepare().is_err());
+
+        config.redis_url = "redis://redis".to_string();
+        config.sync_interval_millis = 10;
+        assert!(config.prepare().is_err());
+
+        config.enabled = false;
+        assert!(config.prepare().is_ok());
+    }
+}
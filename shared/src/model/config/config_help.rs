@@ -0,0 +1,255 @@
+use crate::model::{ConfigInputDto, ConfigInputOptionsDto, ConfigTargetDto, ConfigTargetOptions, HdHomeRunTargetOutputDto,
+                   M3uTargetOutputDto, PlexTargetOutputDto, StagedInputDto, StrmTargetOutputDto, XtreamTargetOutputDto};
+use serde::Serialize;
+use serde_json::Value;
+
+pub const CONFIG_HELP_SECTION_INPUT: &str = "input";
+pub const CONFIG_HELP_SECTION_INPUT_OPTIONS: &str = "input_options";
+pub const CONFIG_HELP_SECTION_STAGED_INPUT: &str = "staged_input";
+pub const CONFIG_HELP_SECTION_TARGET: &str = "target";
+pub const CONFIG_HELP_SECTION_TARGET_OPTIONS: &str = "target_options";
+pub const CONFIG_HELP_SECTION_XTREAM_OUTPUT: &str = "xtream_output";
+pub const CONFIG_HELP_SECTION_M3U_OUTPUT: &str = "m3u_output";
+pub const CONFIG_HELP_SECTION_STRM_OUTPUT: &str = "strm_output";
+pub const CONFIG_HELP_SECTION_HDHOMERUN_OUTPUT: &str = "hdhomerun_output";
+pub const CONFIG_HELP_SECTION_PLEX_OUTPUT: &str = "plex_output";
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct ConfigOptionHelpDto {
+    /// Name of the option in the config file
+    pub name: String,
+    #[serde(rename = "type")]
+    pub value_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct ConfigHelpSectionDto {
+    pub name: String,
+    pub options: Vec<ConfigOptionHelpDto>,
+}
+
+impl ConfigHelpSectionDto {
+    pub fn get_option(&self, name: &str) -> Option<&ConfigOptionHelpDto> {
+        self.options.iter().find(|option| option.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct ConfigHelpDto {
+    pub sections: Vec<ConfigHelpSectionDto>,
+}
+
+impl ConfigHelpDto {
+    pub fn get_section(&self, name: &str) -> Option<&ConfigHelpSectionDto> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    pub fn get_option(&self, section: &str, name: &str) -> Option<&ConfigOptionHelpDto> {
+        self.get_section(section).and_then(|s| s.get_option(name))
+    }
+}
+
+/// Strips the module paths from the rust type name, `core::option::Option<alloc::string::String>` becomes `Option<String>`.
+fn value_type_name<T>(_value: &T) -> String {
+    let type_name = std::any::type_name::<T>();
+    let mut result = String::with_capacity(type_name.len());
+    let mut segment = String::new();
+    for c in type_name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            result.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            result.push(c);
+        }
+    }
+    result.push_str(segment.rsplit("::").next().unwrap_or_default());
+    result.replace("Arc<str>", "String")
+}
+
+/// Empty values are not shown as default.
+fn default_value<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_value(value).ok()? {
+        Value::Null => None,
+        Value::String(text) => (!text.is_empty()).then_some(text),
+        Value::Array(list) if list.is_empty() => None,
+        Value::Object(map) if map.is_empty() => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// Creates the help section for the fields of a config dto.
+/// The defaults and types are taken from the `Default` instance of the dto,
+/// renamed serde fields are given with `field as "name"`.
+macro_rules! config_help_section {
+    ($section:expr, $dto:ty, { $($field:ident $(as $key:literal)? => $description:literal),* $(,)? }) => {{
+        let defaults = <$dto>::default();
+        ConfigHelpSectionDto {
+            name: $section.to_string(),
+            options: vec![$(
+                ConfigOptionHelpDto {
+                    name: config_help_section!(@key $field $(, $key)?).to_string(),
+                    value_type: value_type_name(&defaults.$field),
+                    default: default_value(&defaults.$field),
+                    description: $description.to_string(),
+                }
+            ),*],
+        }
+    }};
+    (@key $field:ident) => { stringify!($field) };
+    (@key $field:ident, $key:literal) => { $key };
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn create_config_help() -> ConfigHelpDto {
+    ConfigHelpDto {
+        sections: vec![
+            config_help_section!(CONFIG_HELP_SECTION_INPUT, ConfigInputDto, {
+                name => "Unique name of the input.",
+                input_type as "type" => "Type of the input, `m3u`, `xtream`, `udp` or one of the batch types.",
+                enabled => "Disabled inputs are skipped during processing.",
+                url => "For `m3u` the download url or a local file (can be gzip), for `xtream` `http://<hostname>:<port>`.",
+                username => "Provider username, mandatory for `xtream`.",
+                password => "Provider password, mandatory for `xtream`.",
+                persist => "Filename to persist the downloaded playlist, `{}` is replaced with the current timestamp.",
+                cache_duration => "Reuse the cached provider playlist for this duration instead of downloading it again, like `12h`, `1d` or `30m`.",
+                exp_date => "Expiration date of the provider account, `YYYY-MM-DD HH:MM:SS` or unix timestamp.",
+                priority => "Lower numbers are processed first, negative numbers are allowed.",
+                max_connections => "Maximum number of concurrent provider connections, `0` means unlimited.",
+                method => "Http method of the playlist request, `GET` or `POST`.",
+                headers => "Additional http headers for the provider requests.",
+                header_policy => "Headers which are removed, added or overridden: `upstream` for the playlist and stream requests to the provider, `client` for the stream responses.",
+                auth => "Http `basic` auth (`username`, `password`) and a `client_certificate` with `client_key` for mutual TLS, `ca_certificate` adds root certificates of the upstream.",
+                egress_interface => "Network interface (like `wg0`) or local ip address for the upstream requests of the input, overrides a network route.",
+                impersonate => "Sends the playlist and api requests like a browser, `chrome`, `firefox` or `safari`.",
+                maintenance_windows => "Daily `HH:MM-HH:MM` windows in local time, the input keeps its last data and failures are not notified.",
+                guardrails => "Sanity limits `max_size`, `max_items` and `min_items` for a refresh, outside the limits the input keeps its last data and the failure is notified.",
+                epg => "Xmltv epg sources of the input.",
+                options => "Input options, see the input options.",
+                aliases => "Same provider with different credentials.",
+                staged => "Side loaded playlist used for updates instead of the provider playlist.",
+                panel_api => "Provider panel api for account operations.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_INPUT_OPTIONS, ConfigInputOptionsDto, {
+                xtream_skip_live => "Skip the live section of the xtream provider.",
+                xtream_skip_vod => "Skip the vod section of the xtream provider.",
+                xtream_skip_series => "Skip the series section of the xtream provider.",
+                xtream_live_stream_use_prefix => "Add the `/live/` prefix to the live stream links.",
+                xtream_live_stream_without_extension => "Do not add the `.ts` extension to the live stream links.",
+                xtream_incremental => "Keep the resolved vod and series info of the entries not newer than the last update and resolve only the changed entries.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_STAGED_INPUT, StagedInputDto, {
+                input_type as "type" => "Type of the staged input, `m3u` or `xtream`.",
+                url => "For `m3u` the download url or a local file (can be gzip), for `xtream` `http://<hostname>:<port>`.",
+                username => "Username, mandatory for `xtream`.",
+                password => "Password, mandatory for `xtream`.",
+                method => "Http method of the playlist request, `GET` or `POST`.",
+                headers => "Additional http headers for the playlist requests.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_TARGET, ConfigTargetDto, {
+                name => "Name of the target, has to be unique unless it is `default`.",
+                enabled => "Disabled targets are skipped during processing.",
+                filter => "Filter statement selecting the playlist entries of the target.",
+                output => "List of output formats, `xtream`, `m3u`, `strm`, `hdhomerun` or `plex`.",
+                sort => "Sort rules for groups and channels.",
+                rename => "Rename rules applied to the playlist entries.",
+                mapping => "Ids of the mappings applied to the playlist.",
+                favourites => "Adds filtered entries to favourite groups after mapping.",
+                group_mapping => "Ordered rules moving channels to curated groups by a regex on the group title or a filter, with priorities and a `catch_all` group.",
+                channel_numbering => "Persistent logical channel numbers of the live channels with a `start` number, group `ranges`, `pins` by filter and `preserve_upstream` numbers.",
+                processing_order => "Order of filter (f), rename (r) and map (m), like `frm` or `mrf`.",
+                watch => "Regular expressions of groups to watch for changes.",
+                use_memory_cache => "Keeps the playlist in memory, reduces disk access but uses more RAM.",
+                options => "Target options, see the target options.",
+                media_servers => "Jellyfin or Emby servers the M3U and EPG urls of the target are registered with after each update.",
+                shadow_of => "Name of the target this target shadows, it is processed with the same data but only reachable through the preview url.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_TARGET_OPTIONS, ConfigTargetOptions, {
+                ignore_logo => "Logo attributes are ignored to avoid caching logo files on devices.",
+                share_live_streams => "Share live stream connections in reverse proxy mode, each shared channel needs at least 12 MB memory.",
+                remove_duplicates => "Tries to remove duplicates by url.",
+                force_redirect => "Streams of the selected clusters are always redirected, even for reverse proxy users.",
+                epg_languages => "Preferred epg languages like `de` or `en`, the first available language of a title or description is written.",
+                remove_dead_channels => "Live channels which failed this many consecutive channel probes are removed until a probe succeeds again, `0` disables it.",
+                strip_fields => "Fields left out of the m3u and xtream playlists for low-memory devices, `logo`, `description` and `attributes`.",
+                cache_warmup => "Loads the m3u playlist, the live categories and the live streams of each category into memory after each update.",
+                group_series => "Groups the `S01E02` style entries of m3u inputs into series with seasons and episodes for the xtream series api.",
+                deduplication => "Detects duplicate live channels by `name`, `url` or `epg_id` and keeps the first, the best quality or the best with the others as failover.",
+                collapse_variants => "Collapses the `FHD`, `HD` and `SD` variants of a live channel of the same input and group into one channel, the stream falls back down the quality ladder on errors.",
+                header_policy => "Headers which are removed, added or overridden: `upstream` for the stream requests to the provider, `client` for the stream responses.",
+                timezone => "Timezone of the target users like `Europe/Berlin`, schedules of the target run in this timezone and it is the default epg timeshift of the users.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_XTREAM_OUTPUT, XtreamTargetOutputDto, {
+                skip_live_direct_source => "Ignore the `direct_source` property of the provider for live streams.",
+                skip_video_direct_source => "Ignore the `direct_source` property of the provider for movies.",
+                skip_series_direct_source => "Ignore the `direct_source` property of the provider for series.",
+                resolve_series => "Fetch the series info of every series, can cause a lot of provider requests.",
+                resolve_series_delay => "Seconds between the series info requests to avoid a provider ban.",
+                resolve_vod => "Fetch the movie info of every movie, can cause a lot of provider requests.",
+                resolve_vod_delay => "Seconds between the movie info requests to avoid a provider ban.",
+                trakt => "Adds trakt list matches to the playlist.",
+                filter => "Output filter, applied after all transformations.",
+                server_info => "Replaces the `server_name`, `timezone`, `port`, `https_port` and `message` of the xtream `server_info` response for the users of the target.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_M3U_OUTPUT, M3uTargetOutputDto, {
+                filename => "Filename of the m3u export.",
+                include_type_in_url => "Adds the stream type `live`, `movie` or `series` to the stream urls.",
+                mask_redirect_url => "Uses the urls from `api_proxy.yml` for users in redirect mode.",
+                watermark => "Embeds a per-user watermark into the playlist to trace leaked playlist files back to the user.",
+                hls => "Publishes each live channel as HLS playlist and a HLS master playlist per group for web players.",
+                filter => "Output filter, applied after all transformations.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_STRM_OUTPUT, StrmTargetOutputDto, {
+                directory => "Export directory of the strm files.",
+                username => "User for the stream urls, needs an `xtream` output.",
+                style => "Naming style for the media server, `kodi`, `plex`, `emby`, `jellyfin` or `arr` for Sonarr/Radarr with NFO files.",
+                flat => "Creates a flat directory structure with category tags in folder names.",
+                underscore_whitespace => "Replaces all whitespaces with `_` in path and filename.",
+                cleanup => "Deletes the export directory before writing, don't point it at an existing media folder.",
+                strm_props => "Stream properties written into the strm files, like `#KODIPROP` entries.",
+                add_quality_to_filename => "Adds media quality tags to the filename, like `[1080p|x265|HDR]`.",
+                filter => "Output filter, applied after all transformations.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_HDHOMERUN_OUTPUT, HdHomeRunTargetOutputDto, {
+                device => "Name of the HdHomeRun device from the main config.",
+                username => "User for the stream urls.",
+                use_output => "Output used for the lineup, `m3u` or `xtream`.",
+            }),
+            config_help_section!(CONFIG_HELP_SECTION_PLEX_OUTPUT, PlexTargetOutputDto, {
+                device => "Name of the HdHomeRun device from the main config which is added as tuner in Plex.",
+                username => "User for the stream urls.",
+                use_output => "Output used for the lineup, `m3u` or `xtream`. Only live channels are listed.",
+                channel_start => "First channel number for the channels outside of the channel ranges.",
+                channel_ranges => "Channel numbers per group, a list of `group` regex and `start` number, the first matching range is used.",
+                use_channel_numbers => "Keeps the channel numbers of the playlist (`tvg-chno`) as long as they are not taken.",
+            }),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_config_help, value_type_name, CONFIG_HELP_SECTION_INPUT, CONFIG_HELP_SECTION_XTREAM_OUTPUT};
+
+    #[test]
+    fn test_value_type_name() {
+        assert_eq!(value_type_name(&Some(String::new())), "Option<String>");
+        assert_eq!(value_type_name(&Some(vec![1u16])), "Option<Vec<u16>>");
+        assert_eq!(value_type_name(&std::sync::Arc::<str>::from("")), "String");
+    }
+
+    #[test]
+    fn test_config_help() {
+        let help = create_config_help();
+        let delay = help.get_option(CONFIG_HELP_SECTION_XTREAM_OUTPUT, "resolve_series_delay").unwrap();
+        assert_eq!(delay.value_type, "u16");
+        assert_eq!(delay.default.as_deref(), Some("2"));
+        let input_type = help.get_option(CONFIG_HELP_SECTION_INPUT, "type").unwrap();
+        assert_eq!(input_type.value_type, "InputType");
+        assert!(help.get_option(CONFIG_HELP_SECTION_INPUT, "username").unwrap().default.is_none());
+        assert!(help.sections.iter().flat_map(|s| s.options.iter()).all(|o| !o.description.is_empty()));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{info_err_res};
 use crate::error::{TuliproxError};
 use crate::model::EpgSmartMatchConfigDto;
-use crate::utils::{is_false};
+use crate::utils::{is_blank_optional_string, is_false};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -11,11 +12,14 @@ pub struct EpgSourceDto {
     pub priority: i16,
     #[serde(default, skip_serializing_if = "is_false")]
     pub logo_override: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
 }
 
 impl EpgSourceDto {
     pub fn prepare(&mut self) {
         self.url = self.url.trim().to_string();
+        self.schedule = self.schedule.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(ToString::to_string);
     }
 
     pub fn is_valid(&self) -> bool {
@@ -23,7 +27,39 @@ impl EpgSourceDto {
     }
 }
 
+/// Rewrites the provider tvg-ids to the canonical ids of a registry like the iptv-org channel database.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct EpgTvgIdNormalizationConfigDto {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Country code used for tvg-ids without a country suffix.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub country: Option<String>,
+    /// Csv or json file with the registry channels.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub registry: Option<String>,
+    /// Provider tvg-id to canonical id, takes precedence over the registry.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mapping: BTreeMap<String, String>,
+}
 
+impl EpgTvgIdNormalizationConfigDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.country = self.country.as_deref().map(|c| c.trim().to_lowercase()).filter(|c| !c.is_empty());
+        if let Some(country) = self.country.as_deref() {
+            if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+                return info_err_res!("Invalid tvg-id normalization country {country}, expected a two letter country code");
+            }
+        }
+        self.registry = self.registry.as_deref().map(str::trim).filter(|r| !r.is_empty()).map(ToString::to_string);
+        self.mapping = std::mem::take(&mut self.mapping).into_iter()
+            .map(|(id, canonical)| (id.trim().to_lowercase(), canonical.trim().to_lowercase()))
+            .filter(|(id, canonical)| !id.is_empty() && !canonical.is_empty())
+            .collect();
+        Ok(())
+    }
+}
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Default)]
 #[serde(deny_unknown_fields)]
@@ -32,6 +68,8 @@ pub struct EpgConfigDto {
     pub sources: Option<Vec<EpgSourceDto>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub smart_match: Option<EpgSmartMatchConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tvg_id_normalization: Option<EpgTvgIdNormalizationConfigDto>,
     #[serde(skip)]
     pub t_sources: Vec<EpgSourceDto>,
 }
@@ -55,6 +93,7 @@ impl EpgConfigDto {
                                         url: provider_url,
                                         priority: epg_source.priority,
                                         logo_override: epg_source.logo_override,
+                                        schedule: epg_source.schedule.clone(),
                                     });
                                 }
                                 Err(err) => return info_err_res!("{err}")
@@ -69,6 +108,10 @@ impl EpgConfigDto {
             if let Some(smart_match) = self.smart_match.as_mut() {
                 smart_match.prepare()?;
             }
+
+            if let Some(tvg_id_normalization) = self.tvg_id_normalization.as_mut() {
+                tvg_id_normalization.prepare()?;
+            }
         }
         Ok(())
     }
@@ -1,3 +1,4 @@
+use crate::utils::normalize_country_codes;
 
 pub fn default_geoip_url() -> String { String::from("https://raw.githubusercontent.com/sapics/ip-location-db/refs/heads/main/asn-country/asn-country-ipv4.csv") }
 
@@ -8,10 +9,21 @@ pub struct GeoIpConfigDto {
     pub enabled: bool,
     #[serde(default = "default_geoip_url")]
     pub url: String,
+    /// Country codes of the clients allowed to use the player api and streams, empty allows all countries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_countries: Vec<String>,
+    /// Country codes of the clients denied to use the player api and streams.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_countries: Vec<String>,
 }
 
 impl GeoIpConfigDto {
     pub fn is_empty(&self) -> bool {
-        !self.enabled && self.url.trim().is_empty()
+        !self.enabled && self.url.trim().is_empty() && self.allowed_countries.is_empty() && self.blocked_countries.is_empty()
     }
-}
\ No newline at end of file
+
+    pub fn prepare(&mut self) {
+        normalize_country_codes(&mut self.allowed_countries);
+        normalize_country_codes(&mut self.blocked_countries);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::error::{info_err_res, TuliproxError};
+use crate::foundation::{apply_templates_to_pattern_single, get_filter, Filter};
+use crate::model::PatternTemplate;
+use crate::utils::{get_trimmed_string, is_blank_optional_string, is_zero_u16};
+
+/// Moves the channels matching the `pattern` of their group title and the `filter` to `group`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigGroupMappingRuleDto {
+    pub group: String,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub filter: Option<String>,
+    /// Rules with a higher priority are checked first, rules with the same priority in their order
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub priority: u16,
+    #[serde(skip)]
+    pub t_filter: Option<Filter>,
+}
+
+impl ConfigGroupMappingRuleDto {
+    pub fn prepare(&mut self, templates: Option<&Vec<PatternTemplate>>) -> Result<(), TuliproxError> {
+        self.group = self.group.trim().to_string();
+        if self.group.is_empty() {
+            return info_err_res!("group mapping rule requires a group");
+        }
+        self.pattern = get_trimmed_string(self.pattern.as_deref());
+        self.filter = get_trimmed_string(self.filter.as_deref());
+        if self.pattern.is_none() && self.filter.is_none() {
+            return info_err_res!("group mapping rule for {} requires a pattern or a filter", self.group);
+        }
+        if let Some(pattern) = self.pattern.as_mut() {
+            *pattern = apply_templates_to_pattern_single(pattern, templates)?;
+            if let Err(err) = crate::model::REGEX_CACHE.get_or_compile(pattern) {
+                return info_err_res!("can't parse regex: {pattern} {err}");
+            }
+        }
+        self.t_filter = match self.filter.as_deref() {
+            Some(filter) => Some(get_filter(filter, templates)?),
+            None => None,
+        };
+        Ok(())
+    }
+}
+
+/// Reorganizes the groups of a target, channels without a matching rule are moved to the `catch_all` group
+/// or keep their group.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigGroupMappingDto {
+    #[serde(default)]
+    pub rules: Vec<ConfigGroupMappingRuleDto>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub catch_all: Option<String>,
+}
+
+impl ConfigGroupMappingDto {
+    pub fn prepare(&mut self, templates: Option<&Vec<PatternTemplate>>) -> Result<(), TuliproxError> {
+        for rule in &mut self.rules {
+            rule.prepare(templates)?;
+        }
+        self.catch_all = get_trimmed_string(self.catch_all.as_deref());
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.catch_all.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigGroupMappingDto, ConfigGroupMappingRuleDto};
+
+    fn rule(group: &str, pattern: Option<&str>, filter: Option<&str>) -> ConfigGroupMappingRuleDto {
+        ConfigGroupMappingRuleDto {
+            group: group.to_string(),
+            pattern: pattern.map(ToString::to_string),
+            filter: filter.map(ToString::to_string),
+            priority: 0,
+            t_filter: None,
+        }
+    }
+
+    #[test]
+    fn test_group_mapping_prepare() {
+        let mut mapping = ConfigGroupMappingDto {
+            rules: vec![rule(" Sports ", Some("(?i)sport"), None), rule("Kids", None, Some(r#"Title ~ "(?i)kids""#))],
+            catch_all: Some("  ".to_string()),
+        };
+        assert!(mapping.prepare(None).is_ok());
+        assert_eq!(mapping.rules[0].group, "Sports");
+        assert!(mapping.rules[1].t_filter.is_some());
+        assert_eq!(mapping.catch_all, None);
+
+        assert!(ConfigGroupMappingDto { rules: vec![rule("News", None, None)], catch_all: None }.prepare(None).is_err());
+        assert!(ConfigGroupMappingDto { rules: vec![rule("News", Some("(news"), None)], catch_all: None }.prepare(None).is_err());
+        assert!(ConfigGroupMappingDto { rules: vec![rule(" ", Some("news"), None)], catch_all: None }.prepare(None).is_err());
+    }
+}
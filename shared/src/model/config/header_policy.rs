@@ -0,0 +1,127 @@
+use crate::error::TuliproxError;
+use crate::info_err_res;
+use std::collections::HashMap;
+
+fn is_valid_header_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+fn prepare_header_names(headers: &mut HashMap<String, String>, owner: &str) -> Result<(), TuliproxError> {
+    let mut prepared = HashMap::with_capacity(headers.len());
+    for (name, value) in headers.drain() {
+        let name = name.trim().to_lowercase();
+        if !is_valid_header_name(&name) {
+            return info_err_res!("Invalid header name '{name}' in header policy of {owner}");
+        }
+        prepared.insert(name, value.trim().to_string());
+    }
+    *headers = prepared;
+    Ok(())
+}
+
+/// Header changes, applied in the order `remove`, `add` and `override`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct HeaderRulesDto {
+    /// Headers which are removed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove: Vec<String>,
+    /// Headers which are added when they are missing.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub add: HashMap<String, String>,
+    /// Headers which are set, an existing value is replaced.
+    #[serde(default, rename = "override", skip_serializing_if = "HashMap::is_empty")]
+    pub override_headers: HashMap<String, String>,
+}
+
+impl HeaderRulesDto {
+    pub fn is_empty(&self) -> bool {
+        self.remove.is_empty() && self.add.is_empty() && self.override_headers.is_empty()
+    }
+
+    pub fn prepare(&mut self, owner: &str) -> Result<(), TuliproxError> {
+        self.remove.retain_mut(|name| {
+            *name = name.trim().to_lowercase();
+            !name.is_empty()
+        });
+        if let Some(name) = self.remove.iter().find(|name| !is_valid_header_name(name)) {
+            return info_err_res!("Invalid header name '{name}' in header policy of {owner}");
+        }
+        prepare_header_names(&mut self.add, owner)?;
+        prepare_header_names(&mut self.override_headers, owner)
+    }
+
+    /// Rules of `first` extended by the rules of `second`, a header removed by `second` is not set by `first`.
+    pub fn merged(first: Option<&Self>, second: Option<&Self>) -> Option<Self> {
+        match (first, second) {
+            (None, None) => None,
+            (Some(rules), None) | (None, Some(rules)) => Some(rules.clone()),
+            (Some(first), Some(second)) => {
+                let mut rules = first.clone();
+                for name in &second.remove {
+                    rules.add.remove(name);
+                    rules.override_headers.remove(name);
+                    if !rules.remove.contains(name) {
+                        rules.remove.push(name.clone());
+                    }
+                }
+                rules.add.extend(second.add.iter().map(|(name, value)| (name.clone(), value.clone())));
+                rules.override_headers.extend(second.override_headers.iter().map(|(name, value)| (name.clone(), value.clone())));
+                Some(rules)
+            }
+        }
+    }
+}
+
+/// Header policy of an input or a target, `upstream` changes the requests to the provider,
+/// `client` the stream responses to the clients.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct HeaderPolicyDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<HeaderRulesDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<HeaderRulesDto>,
+}
+
+impl HeaderPolicyDto {
+    pub fn is_empty(&self) -> bool {
+        self.upstream.as_ref().is_none_or(HeaderRulesDto::is_empty)
+            && self.client.as_ref().is_none_or(HeaderRulesDto::is_empty)
+    }
+
+    pub fn prepare(&mut self, owner: &str) -> Result<(), TuliproxError> {
+        for rules in [&mut self.upstream, &mut self.client] {
+            if let Some(header_rules) = rules.as_mut() {
+                header_rules.prepare(owner)?;
+                if header_rules.is_empty() {
+                    *rules = None;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeaderPolicyDto, HeaderRulesDto};
+
+    #[test]
+    fn test_header_policy_prepare_and_merge() {
+        let mut policy: HeaderPolicyDto = serde_saphyr::from_str("upstream:\n  remove: [' X-Forwarded-For ']\n  override: {Referer: 'http://provider.tv/'}\nclient:\n  add: {}\n").unwrap();
+        policy.prepare("input").unwrap();
+        assert!(policy.client.is_none());
+        let upstream = policy.upstream.as_ref().unwrap();
+        assert_eq!(upstream.remove, vec!["x-forwarded-for"]);
+        assert_eq!(upstream.override_headers.get("referer").map(String::as_str), Some("http://provider.tv/"));
+
+        let target = HeaderRulesDto { remove: vec!["referer".to_string()], ..HeaderRulesDto::default() };
+        let merged = HeaderRulesDto::merged(Some(upstream), Some(&target)).unwrap();
+        assert!(merged.override_headers.is_empty());
+        assert_eq!(merged.remove, vec!["x-forwarded-for", "referer"]);
+
+        let mut invalid = HeaderRulesDto { remove: vec!["bad header".to_string()], ..HeaderRulesDto::default() };
+        assert!(invalid.prepare("input").is_err());
+    }
+}
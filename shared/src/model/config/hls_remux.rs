@@ -0,0 +1,49 @@
+use crate::error::{TuliproxError, TuliproxErrorKind};
+use crate::utils::{default_hls_idle_timeout_secs, default_hls_playlist_segments, default_hls_segment_secs,
+                   is_default_hls_idle_timeout_secs, is_default_hls_playlist_segments, is_default_hls_segment_secs};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HlsRemuxConfigDto {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_hls_segment_secs", skip_serializing_if = "is_default_hls_segment_secs")]
+    pub segment_secs: u16,
+    #[serde(default = "default_hls_playlist_segments", skip_serializing_if = "is_default_hls_playlist_segments")]
+    pub playlist_segments: u16,
+    #[serde(default = "default_hls_idle_timeout_secs", skip_serializing_if = "is_default_hls_idle_timeout_secs")]
+    pub idle_timeout_secs: u16,
+}
+
+impl Default for HlsRemuxConfigDto {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            segment_secs: default_hls_segment_secs(),
+            playlist_segments: default_hls_playlist_segments(),
+            idle_timeout_secs: default_hls_idle_timeout_secs(),
+        }
+    }
+}
+
+impl HlsRemuxConfigDto {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled
+            && is_default_hls_segment_secs(&self.segment_secs)
+            && is_default_hls_playlist_segments(&self.playlist_segments)
+            && is_default_hls_idle_timeout_secs(&self.idle_timeout_secs)
+    }
+
+    pub(crate) fn prepare(&self) -> Result<(), TuliproxError> {
+        if self.segment_secs == 0 || self.segment_secs > 30 {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "HLS remux segment_secs must be between 1 and 30".to_string()));
+        }
+        if self.playlist_segments < 2 {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "HLS remux playlist_segments must be at least 2".to_string()));
+        }
+        if self.idle_timeout_secs < self.segment_secs {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "HLS remux idle_timeout_secs can't be lower than segment_secs".to_string()));
+        }
+        Ok(())
+    }
+}
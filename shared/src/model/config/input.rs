@@ -1,10 +1,10 @@
 use super::PanelApiConfigDto;
 use crate::error::{TuliproxError, TuliproxErrorKind};
-use crate::model::EpgConfigDto;
-use crate::utils::{arc_str_serde, default_as_true, deserialize_timestamp, get_credentials_from_url_str, get_trimmed_string,
+use crate::model::{EpgConfigDto, HeaderPolicyDto};
+use crate::utils::{arc_str_serde, default_as_true, default_failover_max_failures, default_failover_recovery_secs, deserialize_timestamp, get_credentials_from_url_str, get_trimmed_string,
                    is_false, is_true, is_zero_u16, sanitize_sensitive_info,
                    serialize_option_vec_flow_map_items, trim_last_slash};
-use crate::utils::{is_blank_optional_string, Internable};
+use crate::utils::{is_blank_optional_string, parse_size_base_2, Internable};
 use crate::{check_input_connections, check_input_credentials, info_err_res};
 
 use enum_iterator::Sequence;
@@ -65,6 +65,8 @@ pub enum InputType {
     XtreamBatch,
     #[serde(rename = "library")]
     Library,
+    #[serde(rename = "udp")]
+    Udp,
 }
 
 
@@ -74,6 +76,7 @@ impl InputType {
     const M3U_BATCH: &'static str = "m3u_batch";
     const XTREAM_BATCH: &'static str = "xtream_batch";
     const LIBRARY: &'static str = "library";
+    const UDP: &'static str = "udp";
 }
 
 impl Display for InputType {
@@ -84,6 +87,7 @@ impl Display for InputType {
             Self::M3uBatch => Self::M3U_BATCH,
             Self::XtreamBatch => Self::XTREAM_BATCH,
             Self::Library => Self::LIBRARY,
+            Self::Udp => Self::UDP,
         })
     }
 }
@@ -102,6 +106,8 @@ impl FromStr for InputType {
             Ok(Self::XtreamBatch)
         } else if s.eq(Self::LIBRARY) {
             Ok(Self::Library)
+        } else if s.eq(Self::UDP) {
+            Ok(Self::Udp)
         } else {
             info_err_res!("Unknown InputType: {}", s)
         }
@@ -153,6 +159,56 @@ impl FromStr for InputFetchMethod {
     }
 }
 
+/// Browser the provider requests of an input pretend to come from.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    serde::Serialize,
+    serde::Deserialize,
+    Sequence,
+    PartialEq,
+    Eq,
+    Hash
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ImpersonationProfile {
+    Chrome,
+    Firefox,
+    Safari,
+}
+
+impl ImpersonationProfile {
+    const CHROME: &'static str = "chrome";
+    const FIREFOX: &'static str = "firefox";
+    const SAFARI: &'static str = "safari";
+}
+
+impl Display for ImpersonationProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Self::Chrome => Self::CHROME,
+            Self::Firefox => Self::FIREFOX,
+            Self::Safari => Self::SAFARI,
+        })
+    }
+}
+
+impl FromStr for ImpersonationProfile {
+    type Err = TuliproxError;
+
+    fn from_str(s: &str) -> Result<Self, TuliproxError> {
+        if s.eq(Self::CHROME) {
+            Ok(Self::Chrome)
+        } else if s.eq(Self::FIREFOX) {
+            Ok(Self::Firefox)
+        } else if s.eq(Self::SAFARI) {
+            Ok(Self::Safari)
+        } else {
+            info_err_res!("Unknown impersonation profile: {}", s)
+        }
+    }
+}
 
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -168,6 +224,8 @@ pub struct ConfigInputOptionsDto {
     pub xtream_live_stream_use_prefix: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub xtream_live_stream_without_extension: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub xtream_incremental: bool,
 }
 
 impl Default for ConfigInputOptionsDto {
@@ -178,6 +236,7 @@ impl Default for ConfigInputOptionsDto {
             xtream_skip_series: false,
             xtream_live_stream_use_prefix: default_as_true(),
             xtream_live_stream_without_extension: false,
+            xtream_incremental: false,
         }
     }
 }
@@ -189,6 +248,7 @@ impl ConfigInputOptionsDto {
             && !self.xtream_skip_series
             && self.xtream_live_stream_use_prefix
             && !self.xtream_live_stream_without_extension
+            && !self.xtream_incremental
     }
 
     pub fn clean(&mut self) {
@@ -197,6 +257,202 @@ impl ConfigInputOptionsDto {
         self.xtream_skip_series = false;
         self.xtream_live_stream_use_prefix = default_as_true();
         self.xtream_live_stream_without_extension = false;
+        self.xtream_incremental = false;
+    }
+}
+
+/// Switches a stream to the next provider of the input (the input and its aliases ordered by priority)
+/// when the current provider fails to open the stream.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct InputFailoverConfigDto {
+    #[serde(default = "default_as_true")]
+    pub enabled: bool,
+    /// Consecutive failures after which a provider is skipped.
+    #[serde(default = "default_failover_max_failures")]
+    pub max_failures: u16,
+    /// Seconds an unhealthy provider is skipped after its last failure.
+    #[serde(default = "default_failover_recovery_secs")]
+    pub recovery_secs: u64,
+}
+
+impl Default for InputFailoverConfigDto {
+    fn default() -> Self {
+        Self {
+            enabled: default_as_true(),
+            max_failures: default_failover_max_failures(),
+            recovery_secs: default_failover_recovery_secs(),
+        }
+    }
+}
+
+/// Decision of the `auto` proxy type, a stream is redirected to the provider
+/// unless the provider is unhealthy or the client country can't access the provider directly.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct InputAutoProxyDto {
+    /// Consecutive stream failures after which the streams of the provider are proxied.
+    #[serde(default = "default_failover_max_failures")]
+    pub max_failures: u16,
+    /// Seconds the streams are proxied after the last failure of the provider.
+    #[serde(default = "default_failover_recovery_secs")]
+    pub recovery_secs: u64,
+    /// Country codes of clients which are always proxied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proxy_countries: Vec<String>,
+    /// Country codes of clients which are redirected, clients from other countries are proxied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirect_countries: Vec<String>,
+}
+
+impl Default for InputAutoProxyDto {
+    fn default() -> Self {
+        Self {
+            max_failures: default_failover_max_failures(),
+            recovery_secs: default_failover_recovery_secs(),
+            proxy_countries: Vec::new(),
+            redirect_countries: Vec::new(),
+        }
+    }
+}
+
+impl InputAutoProxyDto {
+    fn prepare(&mut self, input_name: &str) -> Result<(), TuliproxError> {
+        if self.max_failures == 0 {
+            return info_err_res!("auto_proxy max_failures for input {input_name} must be at least 1");
+        }
+        for countries in [&mut self.proxy_countries, &mut self.redirect_countries] {
+            countries.retain_mut(|country| {
+                *country = country.trim().to_uppercase();
+                !country.is_empty()
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Sanity limits for a refresh of the input. A refresh outside the limits is treated as a provider glitch,
+/// the input keeps its last data and the failure is notified.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct InputGuardrailsDto {
+    /// Maximum size of a downloaded playlist, like `200MB`.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub max_size: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+}
+
+impl InputGuardrailsDto {
+    pub fn is_empty(&self) -> bool {
+        self.max_size.is_none() && self.max_items.is_none() && self.min_items.is_none()
+    }
+
+    pub fn get_max_size_bytes(&self) -> Option<u64> {
+        self.max_size.as_deref().and_then(|size| parse_size_base_2(size).ok())
+    }
+
+    fn prepare(&mut self, input_name: &str) -> Result<(), TuliproxError> {
+        self.max_size = get_trimmed_string(self.max_size.as_deref());
+        if let Some(max_size) = self.max_size.as_deref() {
+            match parse_size_base_2(max_size) {
+                Ok(0) => return info_err_res!("guardrails max_size for input {input_name} must be greater than 0"),
+                Ok(_) => {}
+                Err(err) => return info_err_res!("guardrails max_size for input {input_name} is invalid: {err}"),
+            }
+        }
+        if let (Some(min_items), Some(max_items)) = (self.min_items, self.max_items) {
+            if min_items > max_items {
+                return info_err_res!("guardrails min_items for input {input_name} is greater than max_items");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct InputBasicAuthDto {
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+}
+
+/// Authentication at secured upstreams: http basic auth and a client certificate for mutual TLS.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct InputAuthDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub basic: Option<InputBasicAuthDto>,
+    /// PEM file with the client certificate chain, it can contain the private key too.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub client_certificate: Option<String>,
+    /// PEM file with the private key of the client certificate.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub client_key: Option<String>,
+    /// PEM file with additional root certificates to verify the upstream.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub ca_certificate: Option<String>,
+}
+
+impl InputAuthDto {
+    pub fn is_empty(&self) -> bool {
+        self.basic.is_none() && self.client_certificate.is_none() && self.client_key.is_none() && self.ca_certificate.is_none()
+    }
+
+    fn prepare(&mut self, input_name: &str) -> Result<(), TuliproxError> {
+        if let Some(basic) = self.basic.as_mut() {
+            basic.username = basic.username.trim().to_string();
+            if basic.username.is_empty() {
+                return info_err_res!("auth basic username for input {input_name} is mandatory");
+            }
+        }
+        self.client_certificate = get_trimmed_string(self.client_certificate.as_deref());
+        self.client_key = get_trimmed_string(self.client_key.as_deref());
+        self.ca_certificate = get_trimmed_string(self.ca_certificate.as_deref());
+        if self.client_key.is_some() && self.client_certificate.is_none() {
+            return info_err_res!("auth client_key for input {input_name} needs a client_certificate");
+        }
+        Ok(())
+    }
+}
+
+/// Daily time range `HH:MM-HH:MM` in local time during which the provider is maintained,
+/// the range ends on the next day if the end is before the start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    start_mins: u16,
+    end_mins: u16,
+}
+
+impl MaintenanceWindow {
+    fn parse_time(value: &str) -> Option<u16> {
+        let (hours, minutes) = value.trim().split_once(':')?;
+        let hours = hours.parse::<u16>().ok().filter(|hours| *hours < 24)?;
+        let minutes = minutes.parse::<u16>().ok().filter(|minutes| *minutes < 60)?;
+        Some(hours * 60 + minutes)
+    }
+
+    /// `minute_of_day` is the minute since midnight, the end of the window is exclusive.
+    pub fn contains(&self, minute_of_day: u16) -> bool {
+        if self.start_mins <= self.end_mins {
+            (self.start_mins..self.end_mins).contains(&minute_of_day)
+        } else {
+            minute_of_day >= self.start_mins || minute_of_day < self.end_mins
+        }
+    }
+}
+
+impl FromStr for MaintenanceWindow {
+    type Err = TuliproxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('-').and_then(|(start, end)| Some((Self::parse_time(start)?, Self::parse_time(end)?))) {
+            Some((start_mins, end_mins)) if start_mins != end_mins => Ok(Self { start_mins, end_mins }),
+            _ => info_err_res!("Invalid maintenance window '{s}', expected HH:MM-HH:MM"),
+        }
     }
 }
 
@@ -290,8 +546,17 @@ pub struct ConfigInputDto {
     pub input_type: InputType,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, String>,
+    /// Headers which are removed, added or overridden for the provider requests and the client responses of the input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_policy: Option<HeaderPolicyDto>,
     #[serde(default)]
     pub url: String,
+    /// Basic auth and client certificate for the requests to the upstream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<InputAuthDto>,
+    /// Network interface or local ip address the upstream requests are bound to, like a `WireGuard` tunnel.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub egress_interface: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub epg: Option<EpgConfigDto>,
     #[serde(default, skip_serializing_if = "is_blank_optional_string")]
@@ -330,6 +595,17 @@ pub struct ConfigInputDto {
     pub exp_date: Option<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub panel_api: Option<PanelApiConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<InputFailoverConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonate: Option<ImpersonationProfile>,
+    /// Daily `HH:MM-HH:MM` windows in which the provider is skipped without alerts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance_windows: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guardrails: Option<InputGuardrailsDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_proxy: Option<InputAutoProxyDto>,
 }
 
 impl Default for ConfigInputDto {
@@ -339,7 +615,10 @@ impl Default for ConfigInputDto {
             name: "".intern(),
             input_type: InputType::default(),
             headers: HashMap::new(),
+            header_policy: None,
             url: String::new(),
+            auth: None,
+            egress_interface: None,
             epg: None,
             username: None,
             password: None,
@@ -355,6 +634,11 @@ impl Default for ConfigInputDto {
             staged: None,
             exp_date: None,
             panel_api: None,
+            failover: None,
+            impersonate: None,
+            maintenance_windows: None,
+            guardrails: None,
+            auto_proxy: None,
         }
     }
 }
@@ -397,6 +681,57 @@ impl ConfigInputDto {
             panel_api.prepare(&self.name)?;
         }
 
+        if self.failover.as_ref().is_some_and(|failover| failover.max_failures == 0) {
+            return info_err_res!("failover max_failures for input {} must be at least 1", self.name);
+        }
+
+        if let Some(windows) = self.maintenance_windows.as_mut() {
+            windows.retain_mut(|window| {
+                *window = window.trim().to_string();
+                !window.is_empty()
+            });
+            for window in windows.iter() {
+                if let Err(err) = window.parse::<MaintenanceWindow>() {
+                    return info_err_res!("{} for input {}", err.message, self.name);
+                }
+            }
+            if windows.is_empty() {
+                self.maintenance_windows = None;
+            }
+        }
+
+        if let Some(guardrails) = self.guardrails.as_mut() {
+            guardrails.prepare(&self.name)?;
+            if guardrails.is_empty() {
+                self.guardrails = None;
+            }
+        }
+
+        if let Some(auto_proxy) = self.auto_proxy.as_mut() {
+            auto_proxy.prepare(&self.name)?;
+        }
+
+        if let Some(auth) = self.auth.as_mut() {
+            auth.prepare(&self.name)?;
+            if auth.is_empty() {
+                self.auth = None;
+            }
+        }
+
+        self.egress_interface = get_trimmed_string(self.egress_interface.as_deref());
+        if let Some(egress) = self.egress_interface.as_deref() {
+            if egress.chars().any(char::is_whitespace) {
+                return info_err_res!("Invalid egress_interface '{egress}' for input {}", self.name);
+            }
+        }
+
+        if let Some(header_policy) = self.header_policy.as_mut() {
+            header_policy.prepare(&format!("input {}", self.name))?;
+            if header_policy.is_empty() {
+                self.header_policy = None;
+            }
+        }
+
         Ok(current_index)
     }
 
@@ -530,3 +865,40 @@ impl ConfigInputDto {
         Err(TuliproxError::new(TuliproxErrorKind::Info, format!("No matching input or alias found for input '{input_name}' with username '{username}'")))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{InputGuardrailsDto, MaintenanceWindow};
+
+    #[test]
+    fn test_maintenance_window() {
+        let window = "02:00-04:30".parse::<MaintenanceWindow>().unwrap();
+        assert!(!window.contains(119));
+        assert!(window.contains(120));
+        assert!(window.contains(269));
+        assert!(!window.contains(270));
+
+        let window = " 23:30 - 01:00 ".parse::<MaintenanceWindow>().unwrap();
+        assert!(window.contains(23 * 60 + 45));
+        assert!(window.contains(30));
+        assert!(!window.contains(60));
+        assert!(!window.contains(12 * 60));
+
+        assert!("24:00-01:00".parse::<MaintenanceWindow>().is_err());
+        assert!("02:00-02:00".parse::<MaintenanceWindow>().is_err());
+        assert!("02:00".parse::<MaintenanceWindow>().is_err());
+    }
+
+    #[test]
+    fn test_guardrails() {
+        let mut guardrails = InputGuardrailsDto { max_size: Some(" 20MB ".to_string()), max_items: Some(100), min_items: Some(10) };
+        assert!(guardrails.prepare("input").is_ok());
+        assert_eq!(guardrails.get_max_size_bytes(), Some(20 * 1_048_576));
+
+        guardrails.min_items = Some(200);
+        assert!(guardrails.prepare("input").is_err());
+
+        let mut guardrails = InputGuardrailsDto { max_size: Some("big".to_string()), ..InputGuardrailsDto::default() };
+        assert!(guardrails.prepare("input").is_err());
+    }
+}
@@ -1,4 +1,65 @@
-use crate::utils::{is_true, is_false, default_as_true, is_blank_optional_string, is_blank_optional_str};
+use crate::utils::{is_true, is_false, is_zero_u16, default_as_true, is_blank_optional_string, is_blank_optional_str};
+
+/// Pseudonymization of client ips, `truncate` keeps the network part, `hash` replaces the ip with a keyed hash.
+#[derive(Debug, Copy, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IpAnonymization {
+    #[default]
+    None,
+    Truncate,
+    Hash,
+}
+
+impl IpAnonymization {
+    pub fn is_none(&self) -> bool {
+        *self == IpAnonymization::None
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CrashReportConfigDto {
+    #[serde(default = "default_as_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub dir: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub sentry_dsn: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub environment: Option<String>,
+}
+
+impl Default for CrashReportConfigDto {
+    fn default() -> Self {
+        CrashReportConfigDto {
+            enabled: default_as_true(),
+            dir: None,
+            sentry_dsn: None,
+            environment: None,
+        }
+    }
+}
+
+impl CrashReportConfigDto {
+    pub fn is_empty(&self) -> bool {
+        self.enabled
+            && is_blank_optional_str(self.dir.as_deref())
+            && is_blank_optional_str(self.sentry_dsn.as_deref())
+            && is_blank_optional_str(self.environment.as_deref())
+    }
+
+    pub fn clean(&mut self) {
+        if is_blank_optional_str(self.dir.as_deref()) {
+            self.dir = None;
+        }
+        if is_blank_optional_str(self.sentry_dsn.as_deref()) {
+            self.sentry_dsn = None;
+        }
+        if is_blank_optional_str(self.environment.as_deref()) {
+            self.environment = None;
+        }
+    }
+}
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -9,6 +70,16 @@ pub struct LogConfigDto {
     pub log_active_user: bool,
     #[serde(default, skip_serializing_if = "is_blank_optional_string")]
     pub log_level: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crash_report: Option<CrashReportConfigDto>,
+    #[serde(default, skip_serializing_if = "IpAnonymization::is_none")]
+    pub ip_anonymization: IpAnonymization,
+    /// Days after which the client ips of the stored data are pseudonymized, 0 keeps them.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub retention_days: u16,
+    /// Days the stream session history is kept, 0 disables the history.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub session_history_days: u16,
 }
 
 impl Default for LogConfigDto {
@@ -17,6 +88,10 @@ impl Default for LogConfigDto {
             sanitize_sensitive_info: default_as_true(),
             log_active_user: false,
             log_level: None,
+            crash_report: None,
+            ip_anonymization: IpAnonymization::None,
+            retention_days: 0,
+            session_history_days: 0,
         }
     }
 }
@@ -24,11 +99,19 @@ impl Default for LogConfigDto {
 impl LogConfigDto {
     pub fn is_empty(&self) -> bool {
         self.sanitize_sensitive_info && !self.log_active_user && is_blank_optional_str(self.log_level.as_deref())
+            && self.crash_report.as_ref().is_none_or(CrashReportConfigDto::is_empty)
+            && self.ip_anonymization.is_none() && self.retention_days == 0 && self.session_history_days == 0
     }
 
     pub fn clean(&mut self) {
         if is_blank_optional_str(self.log_level.as_deref()) {
             self.log_level = None;
         }
+        if let Some(crash_report) = self.crash_report.as_mut() {
+            crash_report.clean();
+            if crash_report.is_empty() {
+                self.crash_report = None;
+            }
+        }
     }
-}
\ No newline at end of file
+}
@@ -12,7 +12,7 @@ macro_rules! check_input_credentials {
             $this.password = $crate::utils::get_trimmed_string($this.password.as_deref());
      }
      match $input_type {
-            InputType::M3u => {
+            InputType::M3u | InputType::Udp => {
                 if $this.username.is_some() || $this.password.is_some() {
                     // TODO only for initial check
                     //return Err(info_err!("Input types of m3u should not use username or password"));
@@ -61,7 +61,7 @@ macro_rules! check_input_connections {
     ($this:ident, $input_type:expr, $alias:expr) => {
 
      match $input_type {
-            InputType::M3u |InputType::Xtream  => {
+            InputType::M3u | InputType::Xtream | InputType::Udp => {
             }
             InputType::M3uBatch => {
                 if !$alias {
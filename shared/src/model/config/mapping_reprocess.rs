@@ -0,0 +1,22 @@
+use crate::utils::{default_mapping_reprocess_debounce_secs, is_false};
+
+/// Re-processes the targets referencing changed mappings after a hot reload of the mapping files.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MappingReprocessConfigDto {
+    /// Waits for further changes before the processing starts, each change restarts the delay.
+    #[serde(default = "default_mapping_reprocess_debounce_secs")]
+    pub debounce_secs: u64,
+    /// Asks in the web ui before the processing starts.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub confirm: bool,
+}
+
+impl Default for MappingReprocessConfigDto {
+    fn default() -> Self {
+        Self {
+            debounce_secs: default_mapping_reprocess_debounce_secs(),
+            confirm: false,
+        }
+    }
+}
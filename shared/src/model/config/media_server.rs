@@ -0,0 +1,63 @@
+use crate::error::TuliproxError;
+use crate::info_err_res;
+use crate::utils::{default_as_true, is_true};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaServerType {
+    #[default]
+    Jellyfin,
+    Emby,
+}
+
+impl fmt::Display for MediaServerType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            MediaServerType::Jellyfin => "Jellyfin",
+            MediaServerType::Emby => "Emby",
+        })
+    }
+}
+
+/// Jellyfin or Emby server the M3U and EPG urls of a target are registered with.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MediaServerConfigDto {
+    #[serde(default = "default_as_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+    #[serde(rename = "type", default)]
+    pub server_type: MediaServerType,
+    /// Base url of the server, like `http://jellyfin:8096`
+    pub url: String,
+    pub api_key: String,
+    /// User whose playlist urls are registered
+    pub username: String,
+    /// Name of the tuner on the server, defaults to `tuliprox <target>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl MediaServerConfigDto {
+    pub fn prepare(&mut self, target_name: &str) -> Result<(), TuliproxError> {
+        self.url = self.url.trim().trim_end_matches('/').to_string();
+        self.api_key = self.api_key.trim().to_string();
+        self.username = self.username.trim().to_string();
+        self.name = self.name.as_ref().map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+        if self.url.is_empty() {
+            return info_err_res!("Media server url is required for target: {target_name}");
+        }
+        if self.api_key.is_empty() {
+            return info_err_res!("Media server api_key is required for target: {target_name}");
+        }
+        if self.username.is_empty() {
+            return info_err_res!("Media server username is required for target: {target_name}");
+        }
+        Ok(())
+    }
+
+    pub fn get_name(&self, target_name: &str) -> String {
+        self.name.clone().unwrap_or_else(|| format!("tuliprox {target_name}"))
+    }
+}
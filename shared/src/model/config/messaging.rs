@@ -1,4 +1,5 @@
 use crate::error::TuliproxError;
+use crate::info_err_res;
 use crate::model::MsgKind;
 use crate::utils::{is_false, is_blank_optional_string, is_blank_optional_str};
 
@@ -74,6 +75,74 @@ impl PushoverMessagingConfigDto {
     }
 }
 
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WebPushMessagingConfigDto {
+    /// Contact of the server operator, sent to the push services (`mailto:` or `https:` url).
+    pub subject: String,
+}
+
+impl WebPushMessagingConfigDto {
+    pub fn is_empty(&self) -> bool {
+        self.subject.trim().is_empty()
+    }
+
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.subject = self.subject.trim().to_string();
+        if !self.subject.starts_with("mailto:") && !self.subject.starts_with("https://") {
+            return info_err_res!("web_push `subject` must be a `mailto:` or `https://` url");
+        }
+        Ok(())
+    }
+}
+
+const WEBHOOK_METHODS: [&str; 4] = ["GET", "POST", "PUT", "PATCH"];
+
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookMessagingConfigDto {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub method: Option<String>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub headers: std::collections::HashMap<String, String>,
+    /// Handlebars template of the request body, used for all kinds without an own template.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub templates: std::collections::HashMap<MsgKind, String>,
+    /// Restricts the webhook to these kinds, empty means all kinds of `notify_on`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notify_on: Vec<MsgKind>,
+}
+
+impl WebhookMessagingConfigDto {
+    pub fn is_empty(&self) -> bool {
+        self.url.trim().is_empty()
+            && is_blank_optional_str(self.method.as_deref())
+            && self.headers.is_empty()
+            && is_blank_optional_str(self.body.as_deref())
+            && self.templates.is_empty()
+    }
+
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.name = self.name.trim().to_string();
+        self.url = self.url.trim().to_string();
+        if url::Url::parse(&self.url).is_err() {
+            return info_err_res!("webhook `url` is invalid: {}", self.url);
+        }
+        if let Some(method) = self.method.as_mut() {
+            *method = method.trim().to_uppercase();
+            if !WEBHOOK_METHODS.contains(&method.as_str()) {
+                return info_err_res!("webhook `method` must be one of {}: {method}", WEBHOOK_METHODS.join(", "));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct MessagingConfigDto {
@@ -87,6 +156,10 @@ pub struct MessagingConfigDto {
     pub pushover: Option<PushoverMessagingConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub discord: Option<DiscordMessagingConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_push: Option<WebPushMessagingConfigDto>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookMessagingConfigDto>,
 }
 
 impl MessagingConfigDto {
@@ -96,6 +169,8 @@ impl MessagingConfigDto {
             && (self.rest.is_none()  || self.rest.as_ref().is_some_and(|c| c.is_empty()))
             && (self.pushover.is_none() || self.pushover.as_ref().is_some_and(|c| c.is_empty()))
             && (self.discord.is_none() || self.discord.as_ref().is_some_and(|c| c.is_empty()))
+            && (self.web_push.is_none() || self.web_push.as_ref().is_some_and(|c| c.is_empty()))
+            && self.webhooks.iter().all(WebhookMessagingConfigDto::is_empty)
     }
 
     pub fn clean(&mut self) {
@@ -111,9 +186,21 @@ impl MessagingConfigDto {
         if self.discord.as_ref().is_some_and(|c| c.is_empty()) {
             self.discord = None;
         }
+        if self.web_push.as_ref().is_some_and(|c| c.is_empty()) {
+            self.web_push = None;
+        }
+        self.webhooks.retain(|c| !c.is_empty());
     }
 
     pub fn prepare(&mut self, _include_computed: bool) -> Result<(), TuliproxError> {
+        if let Some(web_push) = self.web_push.as_mut() {
+            if !web_push.is_empty() {
+                web_push.prepare()?;
+            }
+        }
+        for webhook in self.webhooks.iter_mut().filter(|c| !c.is_empty()) {
+            webhook.prepare()?;
+        }
         Ok(())
     }
 }
\ No newline at end of file
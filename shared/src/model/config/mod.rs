@@ -1,10 +1,12 @@
 mod trakt;
+mod media_server;
 mod base;
 mod web_ui;
 mod web_auth;
 mod messaging;
 mod hdhomerun;
 mod ipcheck;
+mod network_route;
 mod source;
 mod target;
 mod sort;
@@ -35,7 +37,19 @@ mod proxy_type;
 mod proxy_user_status;
 mod favourites;
 mod geoip;
+mod account_sharing;
+mod hls_remux;
 mod library;
+mod config_help;
+mod channel_probe;
+mod user_expiration;
+mod group_mapping;
+mod channel_numbering;
+mod mapping_reprocess;
+mod runtime;
+mod cluster;
+mod public_url;
+mod header_policy;
 
 pub use proxy_type::*;
 pub use proxy_user_status::*;
@@ -48,6 +62,7 @@ pub use web_ui::*;
 pub use messaging::*;
 pub use hdhomerun::*;
 pub use ipcheck::*;
+pub use network_route::*;
 pub use source::*;
 pub use sort::*;
 pub use target::*;
@@ -63,8 +78,11 @@ pub use cache::*;
 pub use rate_limit::*;
 pub use reverse_proxy::*;
 pub use geoip::*;
+pub use account_sharing::*;
+pub use hls_remux::*;
 pub use proxy::*;
 pub use trakt::*;
+pub use media_server::*;
 pub use rename::*;
 pub use pattern_template::*;
 pub use paths::*;
@@ -73,4 +91,14 @@ pub use config_type::*;
 pub use playlist_update_state::*;
 pub use favourites::*;
 pub use library::*;
+pub use config_help::*;
+pub use channel_probe::*;
+pub use user_expiration::*;
+pub use group_mapping::*;
+pub use channel_numbering::*;
+pub use mapping_reprocess::*;
+pub use runtime::*;
+pub use cluster::*;
+pub use public_url::*;
+pub use header_policy::*;
 pub use crate::apply_batch_aliases;
\ No newline at end of file
@@ -0,0 +1,84 @@
+use crate::error::{TuliproxError, TuliproxErrorKind};
+use crate::utils::is_blank_optional_string;
+use std::collections::HashSet;
+
+/// Routes the upstream traffic of the listed inputs through a network interface like a `WireGuard` tunnel.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkRouteConfigDto {
+    pub name: String,
+    pub interface: String,
+    #[serde(default)]
+    pub inputs: Vec<String>,
+    /// Requested through the interface before the inputs are processed.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub check_url: Option<String>,
+}
+
+impl NetworkRouteConfigDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "Network route name missing".to_string()));
+        }
+        self.interface = self.interface.trim().to_string();
+        if self.interface.is_empty() {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, format!("Network route {} has no interface", self.name)));
+        }
+        self.inputs = self.inputs.iter().map(|input| input.trim().to_string()).filter(|input| !input.is_empty()).collect();
+        self.check_url = self.check_url.as_ref().map(|url| url.trim().to_string()).filter(|url| !url.is_empty());
+        if let Some(url) = self.check_url.as_ref() {
+            if url::Url::parse(url).is_err() {
+                return Err(TuliproxError::new(TuliproxErrorKind::Info, format!("Network route {} has an invalid check_url {url}", self.name)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Prepares the routes, route names are unique and an input is routed through one route only.
+pub fn prepare_network_routes(routes: &mut [NetworkRouteConfigDto]) -> Result<(), TuliproxError> {
+    let mut names = HashSet::new();
+    let mut inputs = HashSet::new();
+    for route in routes.iter_mut() {
+        route.prepare()?;
+        if !names.insert(route.name.clone()) {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, format!("Network route name {} is not unique", route.name)));
+        }
+        for input in &route.inputs {
+            if !inputs.insert(input.clone()) {
+                return Err(TuliproxError::new(TuliproxErrorKind::Info, format!("Input {input} is assigned to more than one network route")));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{prepare_network_routes, NetworkRouteConfigDto};
+
+    fn route(name: &str, inputs: &[&str]) -> NetworkRouteConfigDto {
+        NetworkRouteConfigDto {
+            name: name.to_string(),
+            interface: " wg0 ".to_string(),
+            inputs: inputs.iter().map(ToString::to_string).collect(),
+            check_url: Some(String::new()),
+        }
+    }
+
+    #[test]
+    fn test_prepare_network_routes() {
+        let mut routes = vec![route("vpn", &["input_1", " "]), route("vpn_2", &["input_2"])];
+        assert!(prepare_network_routes(&mut routes).is_ok());
+        assert_eq!(routes[0].interface, "wg0");
+        assert_eq!(routes[0].inputs, vec!["input_1".to_string()]);
+        assert_eq!(routes[0].check_url, None);
+
+        let mut routes = vec![route("vpn", &["input_1"]), route("vpn", &["input_2"])];
+        assert!(prepare_network_routes(&mut routes).is_err());
+
+        let mut routes = vec![route("vpn", &["input_1"]), route("vpn_2", &["input_1"])];
+        assert!(prepare_network_routes(&mut routes).is_err());
+    }
+}
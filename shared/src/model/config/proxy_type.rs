@@ -10,12 +10,19 @@ pub enum ProxyType {
     Reverse(Option<ClusterFlags>),
     #[default]
     Redirect,
+    /// Reverse proxy, live channels are additionally served as DASH manifest
+    Dash,
+    /// Reverse proxy urls in the playlist, each stream request is redirected or proxied
+    /// depending on the provider health and the client country.
+    Auto,
 }
 
 impl PartialEq for ProxyType {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (ProxyType::Redirect, ProxyType::Redirect) => true,
+            (ProxyType::Redirect, ProxyType::Redirect)
+            | (ProxyType::Dash, ProxyType::Dash)
+            | (ProxyType::Auto, ProxyType::Auto) => true,
             (ProxyType::Reverse(a), ProxyType::Reverse(b)) => {
                 let a_flags = a.map_or(0u16, |f| if f.has_full_flags() { 0u16 } else { f.bits() } );
                 let b_flags = b.map_or(0u16, |f| if f.has_full_flags() { 0u16 } else { f.bits() } );
@@ -31,10 +38,12 @@ impl Eq for ProxyType {}
 impl Ord for ProxyType {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match (self, other) {
-            (ProxyType::Redirect, ProxyType::Redirect) => std::cmp::Ordering::Equal,
-            (ProxyType::Reverse(_), ProxyType::Reverse(_)) => std::cmp::Ordering::Equal,
-            (ProxyType::Redirect, _) => std::cmp::Ordering::Less,
-            (ProxyType::Reverse(_), _) => std::cmp::Ordering::Greater,
+            (ProxyType::Redirect, ProxyType::Redirect)
+            | (ProxyType::Reverse(_), ProxyType::Reverse(_))
+            | (ProxyType::Dash, ProxyType::Dash)
+            | (ProxyType::Auto, ProxyType::Auto) => std::cmp::Ordering::Equal,
+            (ProxyType::Redirect, _) | (ProxyType::Reverse(_), ProxyType::Dash | ProxyType::Auto) | (ProxyType::Dash, ProxyType::Auto) => std::cmp::Ordering::Less,
+            (ProxyType::Reverse(_) | ProxyType::Dash | ProxyType::Auto, _) => std::cmp::Ordering::Greater,
         }
     }
 }
@@ -56,6 +65,12 @@ impl Hash for ProxyType {
                 let flags = flags_opt.map_or(0u16, |f| if f.has_full_flags() { 0u16 } else { f.bits() } );
                 flags.hash(state);
             }
+            ProxyType::Dash => {
+                2u8.hash(state);
+            }
+            ProxyType::Auto => {
+                3u8.hash(state);
+            }
         }
     }
 }
@@ -63,6 +78,8 @@ impl Hash for ProxyType {
 impl ProxyType {
     const REVERSE: &'static str = "reverse";
     const REDIRECT: &'static str = "redirect";
+    const DASH: &'static str = "dash";
+    const AUTO: &'static str = "auto";
 
     pub fn is_redirect(&self, item_type: PlaylistItemType) -> bool {
         if item_type.is_local() {
@@ -78,11 +95,19 @@ impl ProxyType {
                 }
                 true
             },
-            ProxyType::Reverse(None) => false,
+            ProxyType::Reverse(None) | ProxyType::Dash | ProxyType::Auto => false,
             ProxyType::Redirect => true
         }
     }
 
+    pub fn is_dash(&self) -> bool {
+        matches!(self, ProxyType::Dash)
+    }
+
+    pub fn is_auto(&self) -> bool {
+        matches!(self, ProxyType::Auto)
+    }
+
     pub fn is_reverse(&self, item_type: PlaylistItemType) -> bool {
         !self.is_redirect(item_type)
     }
@@ -99,6 +124,8 @@ impl Display for ProxyType {
                 }
             }
             Self::Redirect => write!(f, "{}", Self::REDIRECT),
+            Self::Dash => write!(f, "{}", Self::DASH),
+            Self::Auto => write!(f, "{}", Self::AUTO),
         }
     }
 }
@@ -113,6 +140,12 @@ impl FromStr for ProxyType {
         if s == Self::REVERSE {
             return Ok(Self::Reverse(None));
         }
+        if s == Self::DASH {
+            return Ok(Self::Dash);
+        }
+        if s == Self::AUTO {
+            return Ok(Self::Auto);
+        }
 
         if let Some(suffix) = s.strip_prefix(Self::REVERSE) {
             if let Ok(force_redirect) = ClusterFlags::try_from(suffix) {
@@ -135,6 +168,10 @@ impl<'de> Deserialize<'de> for ProxyType {
         let raw: String = Deserialize::deserialize(deserializer)?;
         if raw == ProxyType::REDIRECT {
             return Ok(ProxyType::Redirect);
+        } else if raw == ProxyType::DASH {
+            return Ok(ProxyType::Dash);
+        } else if raw == ProxyType::AUTO {
+            return Ok(ProxyType::Auto);
         } else if raw.starts_with(ProxyType::REVERSE) {
             return ProxyType::from_str(raw.as_str()).map_err(serde::de::Error::custom);
         }
@@ -149,6 +186,8 @@ impl Serialize for ProxyType {
     {
         match *self {
             ProxyType::Redirect => serializer.serialize_str(ProxyType::REDIRECT),
+            ProxyType::Dash => serializer.serialize_str(ProxyType::DASH),
+            ProxyType::Auto => serializer.serialize_str(ProxyType::AUTO),
             ProxyType::Reverse(None) => serializer.serialize_str(ProxyType::REVERSE),
             ProxyType::Reverse(Some(ref force_redirect)) => {
                 serializer.serialize_str(&format!("{}{}", ProxyType::REVERSE, force_redirect))
@@ -0,0 +1,83 @@
+use crate::error::{info_err_res, TuliproxError};
+use crate::utils::{get_trimmed_string, is_blank_optional_string};
+
+/// Vanity domain of a target, the listed users get the urls of the playlists with this base url.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigPublicUrlDomainDto {
+    pub url: String,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+/// Public endpoint of the urls in the playlists of a target, like a cdn host or an external domain.
+/// It replaces the base url of the api proxy server of the user, the internal listen address is not affected.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigPublicUrlDto {
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub path_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub domains: Vec<ConfigPublicUrlDomainDto>,
+}
+
+fn prepare_url(target_name: &str, url: &str) -> Result<String, TuliproxError> {
+    let url = url.trim().trim_end_matches('/');
+    match url::Url::parse(url) {
+        Ok(parsed) if parsed.has_host() && matches!(parsed.scheme(), "http" | "https") => Ok(url.to_string()),
+        _ => info_err_res!("Invalid public url {url} for target {target_name}, an http or https url is required"),
+    }
+}
+
+impl ConfigPublicUrlDto {
+    pub fn is_empty(&self) -> bool {
+        self.base_url.is_none() && self.path_prefix.is_none() && self.domains.is_empty()
+    }
+
+    pub fn prepare(&mut self, target_name: &str) -> Result<(), TuliproxError> {
+        self.base_url = match get_trimmed_string(self.base_url.as_deref()) {
+            Some(base_url) => Some(prepare_url(target_name, &base_url)?),
+            None => None,
+        };
+        self.path_prefix = self.path_prefix.as_deref()
+            .map(|prefix| prefix.trim().trim_matches('/').to_string())
+            .filter(|prefix| !prefix.is_empty());
+        for domain in &mut self.domains {
+            domain.url = prepare_url(target_name, &domain.url)?;
+            domain.users = domain.users.iter().map(|user| user.trim().to_string()).filter(|user| !user.is_empty()).collect();
+            if domain.users.is_empty() {
+                return info_err_res!("Public url domain {} of target {target_name} has no users", domain.url);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigPublicUrlDomainDto, ConfigPublicUrlDto};
+
+    #[test]
+    fn test_public_url_prepare() {
+        let mut public_url = ConfigPublicUrlDto {
+            base_url: Some(" https://cdn.example.com/ ".to_string()),
+            path_prefix: Some("/tv/".to_string()),
+            domains: vec![ConfigPublicUrlDomainDto { url: "http://vip.example.com".to_string(), users: vec![" alice ".to_string(), String::new()] }],
+        };
+        assert!(public_url.prepare("test").is_ok());
+        assert_eq!(public_url.base_url.as_deref(), Some("https://cdn.example.com"));
+        assert_eq!(public_url.path_prefix.as_deref(), Some("tv"));
+        assert_eq!(public_url.domains[0].users, vec!["alice".to_string()]);
+
+        let mut invalid = ConfigPublicUrlDto { base_url: Some("cdn.example.com".to_string()), ..ConfigPublicUrlDto::default() };
+        assert!(invalid.prepare("test").is_err());
+
+        let mut no_users = ConfigPublicUrlDto {
+            domains: vec![ConfigPublicUrlDomainDto { url: "http://vip.example.com".to_string(), users: vec![] }],
+            ..ConfigPublicUrlDto::default()
+        };
+        assert!(no_users.prepare("test").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::error::{TuliproxError, TuliproxErrorKind};
-use crate::model::{CacheConfigDto, GeoIpConfigDto, RateLimitConfigDto, StreamConfigDto};
+use crate::model::{AccountSharingConfigDto, CacheConfigDto, GeoIpConfigDto, HlsRemuxConfigDto, RateLimitConfigDto, StreamConfigDto};
 use crate::utils::{is_false, default_resource_retry_attempts,
                    default_resource_retry_backoff_ms,
                    default_resource_retry_backoff_multiplier,
@@ -40,6 +40,8 @@ impl ReverseProxyDisabledHeaderConfigDto {
 pub struct ReverseProxyConfigDto {
     #[serde(default, skip_serializing_if = "is_false")]
     pub resource_rewrite_disabled: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub compression_disabled: bool,
     pub rewrite_secret: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub resource_retry: Option<ResourceRetryConfigDto>,
@@ -53,11 +55,16 @@ pub struct ReverseProxyConfigDto {
     pub rate_limit: Option<RateLimitConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub geoip: Option<GeoIpConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_sharing: Option<AccountSharingConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hls: Option<HlsRemuxConfigDto>,
 }
 
 impl ReverseProxyConfigDto {
     pub fn is_empty(&self) -> bool {
         !self.resource_rewrite_disabled
+            && !self.compression_disabled
             && self.disabled_header.as_ref().is_none_or(|d| d.is_empty())
             && self
                 .resource_retry
@@ -67,6 +74,8 @@ impl ReverseProxyConfigDto {
             && (self.cache.is_none() || self.cache.as_ref().is_some_and(|c| c.is_empty()))
             && (self.rate_limit.is_none() || self.rate_limit.as_ref().is_some_and(|r| r.is_empty()))
             && (self.geoip.is_none() || self.geoip.as_ref().is_some_and(|g| g.is_empty()))
+            && self.account_sharing.as_ref().is_none_or(AccountSharingConfigDto::is_empty)
+            && self.hls.as_ref().is_none_or(HlsRemuxConfigDto::is_empty)
     }
 
     pub fn clean(&mut self) {
@@ -95,6 +104,12 @@ impl ReverseProxyConfigDto {
         if self.geoip.as_ref().is_some_and(GeoIpConfigDto::is_empty) {
             self.geoip = None;
         }
+        if self.account_sharing.as_ref().is_some_and(AccountSharingConfigDto::is_empty) {
+            self.account_sharing = None;
+        }
+        if self.hls.as_ref().is_some_and(HlsRemuxConfigDto::is_empty) {
+            self.hls = None;
+        }
     }
 
     pub(crate) fn prepare(&mut self, working_dir: &str) -> Result<(), TuliproxError> {
@@ -112,11 +127,25 @@ impl ReverseProxyConfigDto {
             cache.prepare(working_dir)?;
         }
 
+        if let Some(geoip) = self.geoip.as_mut() {
+            geoip.prepare();
+        }
+
+        if let Some(account_sharing) = self.account_sharing.as_ref() {
+            account_sharing.prepare()?;
+        }
+
         if let Some(rate_limit) = self.rate_limit.as_mut() {
             if rate_limit.enabled {
                 rate_limit.prepare()?;
             }
         }
+
+        if let Some(hls) = self.hls.as_ref() {
+            if hls.enabled {
+                hls.prepare()?;
+            }
+        }
         Ok(())
     }
 }
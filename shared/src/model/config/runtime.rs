@@ -0,0 +1,58 @@
+use crate::error::{TuliproxError, TuliproxErrorKind};
+use crate::utils::{is_blank_optional_string, is_zero_u16, parse_size_base_2};
+
+const MIN_THREAD_STACK_SIZE: u64 = 64 * 1024;
+
+/// Tuning of the tokio runtime, it is applied at startup.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeConfigDto {
+    /// Threads executing the async tasks, 0 uses one thread per cpu core.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub worker_threads: u16,
+    /// Upper limit of the threads used for blocking file io, 0 uses the tokio default of 512.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub max_blocking_threads: u16,
+    /// Stack size of the worker and blocking threads like `4MB`, the default is 2MB.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub thread_stack_size: Option<String>,
+}
+
+impl RuntimeConfigDto {
+    pub fn get_thread_stack_size(&self) -> Result<Option<usize>, TuliproxError> {
+        let Some(size) = self.thread_stack_size.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+            return Ok(None);
+        };
+        match parse_size_base_2(size) {
+            Ok(bytes) if bytes >= MIN_THREAD_STACK_SIZE => Ok(usize::try_from(bytes).ok()),
+            Ok(_) => Err(TuliproxError::new(TuliproxErrorKind::Info, format!("runtime thread_stack_size must be at least 64KB: {size}"))),
+            Err(err) => Err(TuliproxError::new(TuliproxErrorKind::Info, format!("Failed to parse runtime thread_stack_size {size}: {err}"))),
+        }
+    }
+
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.get_thread_stack_size()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RuntimeConfigDto;
+
+    #[test]
+    fn test_thread_stack_size() {
+        let mut config = RuntimeConfigDto { thread_stack_size: Some("4MB".to_string()), ..RuntimeConfigDto::default() };
+        assert_eq!(config.get_thread_stack_size().unwrap(), Some(4 * 1024 * 1024));
+        assert!(config.prepare().is_ok());
+
+        config.thread_stack_size = Some(" ".to_string());
+        assert_eq!(config.get_thread_stack_size().unwrap(), None);
+
+        config.thread_stack_size = Some("16KB".to_string());
+        assert!(config.prepare().is_err());
+
+        config.thread_stack_size = Some("big".to_string());
+        assert!(config.prepare().is_err());
+    }
+}
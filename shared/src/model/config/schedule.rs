@@ -1,3 +1,5 @@
+use crate::utils::{default_schedule_retry_attempts, is_blank_optional_string, default_schedule_retry_backoff_secs, default_schedule_retry_max_backoff_secs};
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ScheduleConfigDto {
@@ -5,4 +7,60 @@ pub struct ScheduleConfigDto {
     pub schedule: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub targets: Option<Vec<String>>,
-}
\ No newline at end of file
+    /// Timezone name the cron expression is evaluated in, defaults to the common timezone of the targets or the server timezone.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub timezone: Option<String>,
+}
+
+/// Retries the targets which failed in a scheduled run before the next scheduled run.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduleRetryConfigDto {
+    /// Retries after the scheduled run, `0` disables the retry.
+    #[serde(default = "default_schedule_retry_attempts")]
+    pub max_attempts: u8,
+    /// Delay of the first retry, doubled for each further retry.
+    #[serde(default = "default_schedule_retry_backoff_secs")]
+    pub backoff_secs: u64,
+    #[serde(default = "default_schedule_retry_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+}
+
+impl Default for ScheduleRetryConfigDto {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_schedule_retry_attempts(),
+            backoff_secs: default_schedule_retry_backoff_secs(),
+            max_backoff_secs: default_schedule_retry_max_backoff_secs(),
+        }
+    }
+}
+
+/// Offsets in seconds which spread the start times of schedules with the same expression evenly over the window.
+/// The first schedule of each expression keeps its time, a window of 0 disables the spreading.
+pub fn get_schedule_spread_offsets<S: AsRef<str>>(expressions: &[S], window_secs: u32) -> Vec<u32> {
+    let normalized: Vec<String> = expressions.iter()
+        .map(|expression| expression.as_ref().split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    normalized.iter().enumerate().map(|(index, expression)| {
+        if window_secs == 0 {
+            return 0;
+        }
+        let count = normalized.iter().filter(|e| *e == expression).count();
+        let position = normalized[..index].iter().filter(|e| *e == expression).count();
+        // count is at least 1 because the expression itself is part of the list
+        u32::try_from(u64::from(window_secs) * position as u64 / count as u64).unwrap_or(window_secs)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_schedule_spread_offsets;
+
+    #[test]
+    fn test_schedule_spread_offsets() {
+        let expressions = ["0 0 * * * * *", "0  0 * * * * *", "0 30 * * * * *", "0 0 * * * * *"];
+        assert_eq!(get_schedule_spread_offsets(&expressions, 600), vec![0, 200, 0, 400]);
+        assert_eq!(get_schedule_spread_offsets(&expressions, 0), vec![0, 0, 0, 0]);
+    }
+}
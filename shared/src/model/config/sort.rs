@@ -1,6 +1,7 @@
 use crate::error::{TuliproxError, TuliproxErrorKind};
 use crate::foundation::{apply_templates_to_pattern, get_filter, Filter};
 use crate::model::{ItemField, PatternTemplate, TemplateValue};
+use crate::utils::{is_blank_optional_string, is_false};
 use crate::{handle_tuliprox_error_result_list, info_err, info_err_res};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -161,15 +162,77 @@ impl ConfigSortRuleDto {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ConfigSortDto {
+    /// Name of a `sort_presets` entry, its rules are applied before the own rules.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub preset: Option<String>,
     #[serde(default)]
     pub match_as_ascii: bool,
+    /// Numbers are compared by value, `Channel 2` is sorted before `Channel 10`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub natural: bool,
+    /// Language like `de` or `sv`, the values are compared case and accent insensitive with the letter order of the language.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub locale: Option<String>,
     #[serde(default)]
     pub rules: Vec<ConfigSortRuleDto>,
+    #[serde(skip)]
+    pub t_preset: Option<Box<ConfigSortDto>>,
 }
 
 impl ConfigSortDto {
     pub fn prepare(&mut self, templates: Option<&Vec<PatternTemplate>>) -> Result<(), TuliproxError> {
+        self.locale = self.locale.as_deref()
+            .and_then(|locale| locale.trim().split(['-', '_']).next())
+            .map(str::to_lowercase)
+            .filter(|locale| !locale.is_empty());
+        if let Some(locale) = self.locale.as_deref() {
+            if !(2..=3).contains(&locale.len()) || !locale.chars().all(|c| c.is_ascii_alphabetic()) {
+                return info_err_res!("Invalid sort locale: {locale}");
+            }
+        }
         handle_tuliprox_error_result_list!(TuliproxErrorKind::Info, self.rules.iter_mut().map(|rule| rule.prepare(templates)));
+        if let Some(preset) = self.t_preset.as_mut() {
+            preset.prepare(templates)?;
+        }
         Ok(())
     }
+
+    /// Looks up the `preset` of the sort, the preset is resolved before `prepare`.
+    pub fn resolve_preset(&mut self, presets: Option<&Vec<ConfigSortPresetDto>>) -> Result<(), TuliproxError> {
+        self.preset = self.preset.as_deref().map(str::trim).filter(|name| !name.is_empty()).map(ToString::to_string);
+        self.t_preset = match self.preset.as_deref() {
+            Some(name) => match presets.and_then(|presets| presets.iter().find(|preset| preset.name == name)) {
+                Some(preset) => Some(Box::new(preset.sort.clone())),
+                None => return info_err_res!("Unknown sort preset: {name}"),
+            },
+            None => None,
+        };
+        Ok(())
+    }
+}
+
+/// Named sort settings, targets refer to them with `sort.preset`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigSortPresetDto {
+    pub name: String,
+    pub sort: ConfigSortDto,
+}
+
+/// Checks the presets, the names are unique and a preset can't refer to another preset.
+pub fn prepare_sort_presets(presets: &mut [ConfigSortPresetDto]) -> Result<(), TuliproxError> {
+    let mut names = std::collections::HashSet::new();
+    for preset in presets.iter_mut() {
+        preset.name = preset.name.trim().to_string();
+        if preset.name.is_empty() {
+            return info_err_res!("Sort preset name missing");
+        }
+        if !names.insert(preset.name.clone()) {
+            return info_err_res!("Sort preset name {} is not unique", preset.name);
+        }
+        if preset.sort.preset.as_deref().is_some_and(|name| !name.trim().is_empty()) {
+            return info_err_res!("Sort preset {} can't use another preset", preset.name);
+        }
+    }
+    Ok(())
 }
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::info_err_res;
 use crate::error::{TuliproxError};
 use crate::foundation::prepare_templates;
-use crate::model::{ConfigInputDto, HdHomeRunDeviceOverview, PatternTemplate};
+use crate::model::{prepare_sort_presets, ConfigInputDto, ConfigSortPresetDto, HdHomeRunDeviceOverview, PatternTemplate};
 use crate::model::config::target::ConfigTargetDto;
 use crate::utils::{arc_str_vec_serde, default_as_default, Internable};
 
@@ -35,6 +35,8 @@ impl ConfigSourceDto {
 pub struct SourcesConfigDto {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub templates: Option<Vec<PatternTemplate>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_presets: Option<Vec<ConfigSortPresetDto>>,
     pub inputs: Vec<ConfigInputDto>,
     pub sources: Vec<ConfigSourceDto>,
 }
@@ -42,8 +44,12 @@ pub struct SourcesConfigDto {
 impl SourcesConfigDto {
     pub fn prepare(&mut self, include_computed: bool, hdhr_config: Option<&HdHomeRunDeviceOverview>) -> Result<(), TuliproxError> {
         self.prepare_templates()?;
+        if let Some(presets) = self.sort_presets.as_mut() {
+            prepare_sort_presets(presets)?;
+        }
         self.prepare_sources(include_computed, hdhr_config)?;
         self.check_unique_target_names()?;
+        self.check_shadow_targets()?;
         Ok(())
     }
 
@@ -68,6 +74,9 @@ impl SourcesConfigDto {
             }
 
             for target in &mut source.targets {
+                if let Some(sort) = target.sort.as_mut() {
+                    sort.resolve_preset(self.sort_presets.as_ref())?;
+                }
                 // prepare target templates
                 let prepare_result = match &self.templates {
                     Some(templ) => target.prepare(target_index, Some(templ), hdhr_config),
@@ -112,6 +121,23 @@ impl SourcesConfigDto {
         Ok(())
     }
 
+    /// A shadow target references an existing target which is not a shadow itself.
+    fn check_shadow_targets(&self) -> Result<(), TuliproxError> {
+        let targets: Vec<&ConfigTargetDto> = self.sources.iter().flat_map(|source| source.targets.iter()).collect();
+        for target in &targets {
+            if let Some(shadow_of) = target.shadow_of.as_deref() {
+                match targets.iter().find(|t| t.name == shadow_of) {
+                    None => return info_err_res!("Shadow target {} references unknown target: {shadow_of}", target.name),
+                    Some(shadowed) if shadowed.shadow_of.is_some() => {
+                        return info_err_res!("Shadow target {} can't shadow the shadow target: {shadow_of}", target.name);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_input(&self, name: &Arc<str>) -> Option<&ConfigInputDto> {
         self.inputs.iter().find(|i| &i.name == name)
     }
@@ -1,12 +1,99 @@
 use crate::utils::is_blank_optional_string;
 use log::warn;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use crate::error::{TuliproxError, TuliproxErrorKind};
 use crate::{info_err_res, handle_tuliprox_error_result_list};
 use crate::foundation::{get_filter, Filter};
-use crate::model::{ClusterFlags, ConfigFavouritesDto, ConfigRenameDto, ConfigSortDto, HdHomeRunDeviceOverview,
-                   PatternTemplate, ProcessingOrder, StrmExportStyle, TargetType, TraktConfigDto};
+use crate::model::{ClusterFlags, ConfigChannelNumberingDto, ConfigFavouritesDto, ConfigGroupMappingDto, ConfigPublicUrlDto, ConfigRenameDto, ConfigSortDto, HdHomeRunDeviceOverview, HeaderPolicyDto,
+                   MediaServerConfigDto, PatternTemplate, ProcessingOrder, StrmExportStyle, TargetType, TraktConfigDto};
 use crate::utils::{is_true, is_false, default_as_true, default_resolve_delay_secs, default_as_default,
-                   is_default_resolve_delay_secs, is_zero_u16, is_config_target_options_empty, is_default_processing_order};
+                   is_default_resolve_delay_secs, is_zero_u16, is_config_target_options_empty, is_default_processing_order,
+                   default_plex_channel_start, is_default_plex_channel_start, get_trimmed_string};
+/// Fields left out of the generated playlists to produce lightweight playlists for low-memory devices.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistStripField {
+    /// Logos, covers and backdrops
+    Logo,
+    /// Plot, cast, director, genre and trailers
+    Description,
+    /// Attributes most players don't use
+    Attributes,
+}
+
+impl PlaylistStripField {
+    const LOGO: &'static str = "logo";
+    const DESCRIPTION: &'static str = "description";
+    const ATTRIBUTES: &'static str = "attributes";
+
+    /// Parses a comma separated list like `logo,description`, unknown fields are ignored.
+    pub fn parse_list(value: &str) -> Vec<PlaylistStripField> {
+        let mut fields = Vec::new();
+        for field in value.split(',').filter_map(|field| PlaylistStripField::from_str(field.trim()).ok()) {
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        fields
+    }
+}
+
+impl Display for PlaylistStripField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Logo => Self::LOGO,
+            Self::Description => Self::DESCRIPTION,
+            Self::Attributes => Self::ATTRIBUTES,
+        })
+    }
+}
+
+impl FromStr for PlaylistStripField {
+    type Err = TuliproxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            Self::LOGO => Ok(Self::Logo),
+            Self::DESCRIPTION => Ok(Self::Description),
+            Self::ATTRIBUTES => Ok(Self::Attributes),
+            _ => info_err_res!("Unknown strip field: {s}"),
+        }
+    }
+}
+
+/// Field which identifies duplicate live channels.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DeduplicationKey {
+    /// Name without case, accents and quality tags like `HD` or `FHD`
+    #[default]
+    Name,
+    Url,
+    EpgId,
+}
+
+/// Which channel of a duplicate set is kept.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DeduplicationStrategy {
+    #[default]
+    KeepFirst,
+    /// The channel with the highest resolution
+    KeepBestQuality,
+    /// The best channel is kept, the urls of the others are used when its stream can't be opened
+    Failover,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigDeduplicationDto {
+    #[serde(default)]
+    pub key: DeduplicationKey,
+    #[serde(default)]
+    pub strategy: DeduplicationStrategy,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ConfigTargetOptions {
@@ -18,6 +105,32 @@ pub struct ConfigTargetOptions {
     pub remove_duplicates: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub force_redirect: Option<ClusterFlags>,
+    /// Preferred languages of the epg titles and descriptions, the first available one is written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epg_languages: Option<Vec<String>>,
+    /// Live channels which failed this many consecutive probes of the `channel_probe` are removed until they recover.
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub remove_dead_channels: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip_fields: Vec<PlaylistStripField>,
+    /// Loads the playlist queries of the players into the memory cache after each update.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub cache_warmup: bool,
+    /// Groups the `S01E02` entries of m3u inputs into series with seasons and episodes.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub group_series: bool,
+    /// Detects and removes duplicate live channels over all inputs of the target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplication: Option<ConfigDeduplicationDto>,
+    /// Collapses the quality variants of a live channel (`FHD`, `HD`, `SD`) into one channel which streams the best working variant.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub collapse_variants: bool,
+    /// Headers which are removed, added or overridden for the provider stream requests and the stream responses of the target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_policy: Option<HeaderPolicyDto>,
+    /// Timezone name of the target users (e.g. `Europe/Berlin`), used for the schedules of the target and the default epg timeshift.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub timezone: Option<String>,
 }
 
 impl ConfigTargetOptions {
@@ -26,6 +139,51 @@ impl ConfigTargetOptions {
         && !self.share_live_streams
         && !self.remove_duplicates
         && (self.force_redirect.is_none() || self.force_redirect.is_some_and(|f| f.has_full_flags() || f.is_empty()))
+        && self.epg_languages.as_ref().is_none_or(Vec::is_empty)
+        && self.remove_dead_channels == 0
+        && self.strip_fields.is_empty()
+        && !self.cache_warmup
+        && !self.group_series
+        && self.deduplication.is_none()
+        && !self.collapse_variants
+        && self.header_policy.is_none()
+        && self.timezone.as_ref().is_none_or(|tz| tz.trim().is_empty())
+    }
+
+    pub fn has_strip_field(&self, field: PlaylistStripField) -> bool {
+        self.strip_fields.contains(&field)
+    }
+}
+
+/// Values of the xtream `server_info` response which replace the values of the api proxy server of the user.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct XtreamServerInfoDto {
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub server_name: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub timezone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub message: Option<String>,
+}
+
+impl XtreamServerInfoDto {
+    pub fn is_empty(&self) -> bool {
+        self.server_name.is_none()
+            && self.timezone.is_none()
+            && self.port.is_none()
+            && self.https_port.is_none()
+            && self.message.is_none()
+    }
+
+    pub fn prepare(&mut self) {
+        for value in [&mut self.server_name, &mut self.timezone, &mut self.message] {
+            *value = value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(ToString::to_string);
+        }
     }
 }
 
@@ -51,6 +209,8 @@ pub struct XtreamTargetOutputDto {
     pub trakt: Option<TraktConfigDto>,
     #[serde(default, skip_serializing_if = "is_blank_optional_string")]
     pub filter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_info: Option<XtreamServerInfoDto>,
     #[serde(skip)]
     pub t_filter: Option<Filter>,
 }
@@ -67,6 +227,7 @@ impl Default for XtreamTargetOutputDto {
             resolve_vod_delay: default_resolve_delay_secs(),
             trakt: None,
             filter: None,
+            server_info: None,
             t_filter: None,
         }
     }
@@ -80,6 +241,12 @@ impl XtreamTargetOutputDto {
         if let Some(trakt) = &mut self.trakt {
             trakt.prepare();
         }
+        if let Some(server_info) = &mut self.server_info {
+            server_info.prepare();
+        }
+        if self.server_info.as_ref().is_some_and(XtreamServerInfoDto::is_empty) {
+            self.server_info = None;
+        }
         Ok(())
     }
 
@@ -91,9 +258,11 @@ impl XtreamTargetOutputDto {
             || self.resolve_vod
             || self.trakt.is_some()
             || self.filter.is_some()
+            || self.server_info.is_some()
     }
 }
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct M3uTargetOutputDto {
@@ -103,6 +272,11 @@ pub struct M3uTargetOutputDto {
     pub include_type_in_url: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub mask_redirect_url: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub watermark: bool,
+    /// Publishes the live channels as HLS playlists with a master playlist per group
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub hls: bool,
     #[serde(default, skip_serializing_if = "is_blank_optional_string")]
     pub filter: Option<String>,
     #[serde(skip)]
@@ -122,6 +296,8 @@ impl M3uTargetOutputDto {
         self.filename.is_some()
             || self.include_type_in_url
             || self.mask_redirect_url
+            || self.watermark
+            || self.hls
             || self.filter.is_some()
     }
 }
@@ -178,6 +354,67 @@ impl Default for HdHomeRunTargetOutputDto {
     }
 }
 
+/// Channel number range for the live channels of the matching groups.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PlexChannelRangeDto {
+    /// Regular expression for the group name
+    pub group: String,
+    pub start: u32,
+}
+
+/// HdHomeRun tuner for the Plex Live TV setup with numeric guide numbers
+/// and a matching XMLTV guide.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlexTargetOutputDto {
+    pub device: String,
+    pub username: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_output: Option<TargetType>,
+    #[serde(default = "default_plex_channel_start", skip_serializing_if = "is_default_plex_channel_start")]
+    pub channel_start: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ranges: Option<Vec<PlexChannelRangeDto>>,
+    /// Keeps the channel numbers of the playlist (`tvg-chno`) as vanity numbers
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub use_channel_numbers: bool,
+}
+
+impl Default for PlexTargetOutputDto {
+    fn default() -> Self {
+        Self {
+            device: String::new(),
+            username: String::new(),
+            use_output: Some(TargetType::M3u),
+            channel_start: default_plex_channel_start(),
+            channel_ranges: None,
+            use_channel_numbers: false,
+        }
+    }
+}
+
+impl PlexTargetOutputDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        if self.channel_start == 0 {
+            return info_err_res!("Plex output `channel_start` has to be greater than 0");
+        }
+        if let Some(ranges) = self.channel_ranges.as_mut() {
+            for range in ranges.iter_mut() {
+                range.group = range.group.trim().to_string();
+                if range.start == 0 {
+                    return info_err_res!("Plex channel range `start` has to be greater than 0 for group {}", range.group);
+                }
+                if let Err(err) = regex::Regex::new(&range.group) {
+                    return info_err_res!("Invalid plex channel range group {}: {err}", range.group);
+                }
+            }
+            ranges.retain(|range| !range.group.is_empty());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields, tag = "type", rename_all = "lowercase")]
 pub enum TargetOutputDto {
@@ -185,6 +422,7 @@ pub enum TargetOutputDto {
     M3u(M3uTargetOutputDto),
     Strm(StrmTargetOutputDto),
     HdHomeRun(HdHomeRunTargetOutputDto),
+    Plex(PlexTargetOutputDto),
 }
 
 impl TargetOutputDto {
@@ -193,7 +431,8 @@ impl TargetOutputDto {
             TargetOutputDto::Xtream(output) => output.prepare(templates),
             TargetOutputDto::M3u(output) => output.prepare(templates),
             TargetOutputDto::Strm(output) => output.prepare(templates),
-            TargetOutputDto::HdHomeRun(_) => Ok(())
+            TargetOutputDto::HdHomeRun(_) => Ok(()),
+            TargetOutputDto::Plex(output) => output.prepare(),
         }
     }
 }
@@ -220,12 +459,24 @@ pub struct ConfigTargetDto {
     pub mapping: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub favourites: Option<Vec<ConfigFavouritesDto>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_mapping: Option<ConfigGroupMappingDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_numbering: Option<ConfigChannelNumberingDto>,
     #[serde(default, skip_serializing_if = "is_default_processing_order")]
     pub processing_order: ProcessingOrder,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub watch: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "is_false")]
     pub use_memory_cache: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_servers: Option<Vec<MediaServerConfigDto>>,
+    /// Name of the target this target shadows, a shadow target is only reachable through the preview url.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub shadow_of: Option<String>,
+    /// Public endpoint of the urls in the playlists of this target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<ConfigPublicUrlDto>,
     #[serde(skip)]
     pub t_filter: Option<Filter>,
 }
@@ -243,9 +494,14 @@ impl Default for ConfigTargetDto {
             rename: None,
             mapping: None,
             favourites: None,
+            group_mapping: None,
+            channel_numbering: None,
             processing_order: ProcessingOrder::default(),
             watch: None,
             use_memory_cache: false,
+            media_servers: None,
+            shadow_of: None,
+            public_url: None,
             t_filter: None,
         }
     }
@@ -262,6 +518,19 @@ impl ConfigTargetDto {
         if self.name.is_empty() {
             return info_err_res!("target name required");
         }
+        if let Some(languages) = self.options.as_mut().and_then(|options| options.epg_languages.as_mut()) {
+            languages.iter_mut().for_each(|lang| *lang = lang.trim().to_lowercase());
+            languages.retain(|lang| !lang.is_empty());
+        }
+        if let Some(options) = self.options.as_mut() {
+            options.timezone = options.timezone.as_deref().map(str::trim).filter(|tz| !tz.is_empty()).map(ToString::to_string);
+            if let Some(header_policy) = options.header_policy.as_mut() {
+                header_policy.prepare(&format!("target {}", self.name))?;
+                if header_policy.is_empty() {
+                    options.header_policy = None;
+                }
+            }
+        }
 
         let mut m3u_cnt = 0;
         let mut xtream_cnt = 0;
@@ -342,11 +611,37 @@ impl ConfigTargetDto {
                         }
                     }
                 }
+                TargetOutputDto::Plex(plex_output) => {
+                    // the plex output is served by a hdhomerun device
+                    hdhr_cnt += 1;
+                    plex_output.username = plex_output.username.trim().to_string();
+                    if plex_output.username.is_empty() {
+                        return info_err_res!("Username is required for Plex type: {}", self.name);
+                    }
+
+                    plex_output.device = plex_output.device.trim().to_string();
+                    if plex_output.device.is_empty() {
+                        return info_err_res!("Device is required for Plex type: {}", self.name);
+                    }
+
+                    if let Some(use_output) = plex_output.use_output.as_ref() {
+                        match &use_output {
+                            TargetType::M3u => { hdhomerun_needs_m3u = true; }
+                            TargetType::Xtream => { hdhomerun_needs_xtream = true; }
+                            _ => return info_err_res!("Plex output option `use_output` only accepts `m3u` or `xtream` for target: {}", self.name),
+                        }
+                    }
+                    if let Some(hdhr_devices) = hdhr_config {
+                        if !hdhr_devices.devices.contains(&plex_output.device) {
+                            return info_err_res!("Plex output device is not defined: {}", plex_output.device);
+                        }
+                    }
+                }
             }
         }
 
         if m3u_cnt > 1 || xtream_cnt > 1 || hdhr_cnt > 1 {
-            return info_err_res!("Multiple output formats with same type : {}", self.name);
+            return info_err_res!("Multiple output formats with same type (HdHomeRun and Plex count as one) : {}", self.name);
         }
 
         if strm_cnt > 0 && strm_needs_xtream && xtream_cnt == 0 {
@@ -355,13 +650,13 @@ impl ConfigTargetDto {
 
         if hdhr_cnt > 0 {
             if xtream_cnt == 0 && m3u_cnt == 0 {
-                return info_err_res!("HdHomeRun output is only permitted when used in combination with xtream or m3u output: {}", self.name);
+                return info_err_res!("HdHomeRun or Plex output is only permitted when used in combination with xtream or m3u output: {}", self.name);
             }
             if hdhomerun_needs_m3u && m3u_cnt == 0 {
-                return info_err_res!("HdHomeRun output has `use_output=m3u` but no `m3u` output defined: {}", self.name);
+                return info_err_res!("HdHomeRun or Plex output has `use_output=m3u` but no `m3u` output defined: {}", self.name);
             }
             if hdhomerun_needs_xtream && xtream_cnt == 0 {
-                return info_err_res!("HdHomeRun output has `use_output=xtream` but no `xtream` output defined: {}", self.name);
+                return info_err_res!("HdHomeRun or Plex output has `use_output=xtream` but no `xtream` output defined: {}", self.name);
             }
 
             if let Some(hdhr_devices) = hdhr_config {
@@ -371,12 +666,44 @@ impl ConfigTargetDto {
             }
         }
 
+        for media_server in self.media_servers.iter_mut().flatten() {
+            media_server.prepare(&self.name)?;
+        }
+
+        self.shadow_of = get_trimmed_string(self.shadow_of.as_deref());
+        if self.shadow_of.is_some() {
+            if hdhr_cnt > 0 {
+                return info_err_res!("Shadow target {} can't have a HdHomeRun or Plex output", self.name);
+            }
+            if self.media_servers.as_ref().is_some_and(|media_servers| !media_servers.is_empty()) {
+                return info_err_res!("Shadow target {} can't be registered with media servers", self.name);
+            }
+        }
+
+        if let Some(public_url) = self.public_url.as_mut() {
+            public_url.prepare(&self.name)?;
+            if public_url.is_empty() {
+                self.public_url = None;
+            }
+        }
+
         if let Some(favourites) = self.favourites.as_mut() {
             for favourite in favourites {
                 favourite.prepare(templates)?;
             }
         }
 
+        if let Some(group_mapping) = self.group_mapping.as_mut() {
+            group_mapping.prepare(templates)?;
+            if group_mapping.is_empty() {
+                self.group_mapping = None;
+            }
+        }
+
+        if let Some(channel_numbering) = self.channel_numbering.as_mut() {
+            channel_numbering.prepare(templates)?;
+        }
+
         if let Some(watch) = &self.watch {
             for pat in watch {
                 if let Err(err) = crate::model::REGEX_CACHE.get_or_compile(pat) {
@@ -400,4 +727,46 @@ impl ConfigTargetDto {
             Err(err) => Err(err),
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::{ConfigTargetOptions, PlaylistStripField, XtreamServerInfoDto, XtreamTargetOutputDto};
+    use crate::model::{M3uPlaylistItem, PlaylistItem, PlaylistItemHeader};
+
+    #[test]
+    fn test_strip_fields() {
+        assert_eq!(PlaylistStripField::parse_list("logo, Attributes,unknown,logo"),
+                   vec![PlaylistStripField::Logo, PlaylistStripField::Attributes]);
+
+        let item = M3uPlaylistItem::from(&PlaylistItem {
+            header: PlaylistItemHeader {
+                name: "News".into(),
+                title: "News".into(),
+                group: "Info".into(),
+                logo: "http://logo/news.png".into(),
+                time_shift: "2".into(),
+                url: "http://provider/news.ts".into(),
+                ..Default::default()
+            },
+        });
+        let full = item.to_m3u(None, false);
+        assert!(full.contains("tvg-logo=") && full.contains("timeshift="));
+        let options = ConfigTargetOptions { strip_fields: vec![PlaylistStripField::Logo, PlaylistStripField::Attributes], ..Default::default() };
+        assert_eq!(item.to_m3u(Some(&options), false),
+                   "#EXTINF:-1 tvg-id=\"\" tvg-name=\"News\" group-title=\"Info\",News\nhttp://provider/news.ts");
+    }
+
+    #[test]
+    fn test_xtream_server_info_prepare() {
+        let mut output = XtreamTargetOutputDto {
+            server_info: Some(XtreamServerInfoDto { server_name: Some(" My TV ".to_string()), message: Some("  ".to_string()), ..XtreamServerInfoDto::default() }),
+            ..XtreamTargetOutputDto::default()
+        };
+        output.prepare(None).unwrap();
+        assert_eq!(output.server_info, Some(XtreamServerInfoDto { server_name: Some("My TV".to_string()), ..XtreamServerInfoDto::default() }));
+
+        output.server_info = Some(XtreamServerInfoDto { timezone: Some(" ".to_string()), ..XtreamServerInfoDto::default() });
+        output.prepare(None).unwrap();
+        assert!(output.server_info.is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::model::DEFAULT_USER_AGENT;
-use crate::utils::{default_as_true, default_trakt_fuzzy_threshold, is_true,
+use crate::utils::{default_as_true, default_trakt_fuzzy_threshold, is_blank_optional_string, is_true,
                    TRAKT_API_KEY, TRAKT_API_URL, TRAKT_API_VERSION};
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -96,6 +96,9 @@ pub struct TraktConfigDto {
     #[serde(default)]
     pub api: TraktApiConfigDto,
     pub lists: Vec<TraktListConfigDto>,
+    /// Cron expression, the lists are synchronized on their own schedule between the playlist updates.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub schedule: Option<String>,
 }
 
 impl Default for TraktConfigDto {
@@ -104,6 +107,7 @@ impl Default for TraktConfigDto {
             enabled: true,
             api: TraktApiConfigDto::default(),
             lists: Vec::new(),
+            schedule: None,
         }
     }
 }
@@ -111,5 +115,6 @@ impl Default for TraktConfigDto {
 impl TraktConfigDto {
     pub fn prepare(&mut self) {
         self.api.prepare();
+        self.schedule = self.schedule.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(ToString::to_string);
     }
 }
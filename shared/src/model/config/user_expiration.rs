@@ -0,0 +1,49 @@
+use crate::error::{TuliproxError, TuliproxErrorKind};
+use crate::utils::{default_as_true, default_user_expiration_interval_mins};
+
+/// Periodically checks the expiration dates of the api-proxy users.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct UserExpirationConfigDto {
+    #[serde(default = "default_user_expiration_interval_mins")]
+    pub interval_mins: u32,
+    /// Sets the status of expired users to `Expired`.
+    #[serde(default = "default_as_true")]
+    pub disable_expired: bool,
+    /// Expired users are deleted this many days after their expiration date, they are kept if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_after_days: Option<u32>,
+    /// A message is sent this many days before the expiration date of a user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_days_before: Option<u32>,
+    /// Handlebars template of the expiry message with `username`, `target`, `exp_date` and `days_left`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_template: Option<String>,
+}
+
+impl Default for UserExpirationConfigDto {
+    fn default() -> Self {
+        Self {
+            interval_mins: default_user_expiration_interval_mins(),
+            disable_expired: true,
+            delete_after_days: None,
+            notify_days_before: None,
+            notify_template: None,
+        }
+    }
+}
+
+impl UserExpirationConfigDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        if self.interval_mins == 0 {
+            return Err(TuliproxError::new(TuliproxErrorKind::Info, "user_expiration interval_mins must be > 0".to_string()));
+        }
+        if self.notify_days_before == Some(0) {
+            self.notify_days_before = None;
+        }
+        self.notify_template = self.notify_template.take()
+            .map(|template| template.trim().to_string())
+            .filter(|template| !template.is_empty());
+        Ok(())
+    }
+}
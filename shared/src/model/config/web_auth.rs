@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use crate::model::WebUiRole;
 use crate::utils::{is_true, default_as_true, default_token_ttl_mins, is_default_token_ttl_mins, is_blank_optional_string, is_blank_optional_str};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -11,6 +13,9 @@ pub struct WebAuthConfigDto {
     pub token_ttl_mins: u32,
     #[serde(default, skip_serializing_if = "is_blank_optional_string")]
     pub userfile: Option<String>,
+    /// Role of the userfile users, users without an entry are admins.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub roles: HashMap<String, WebUiRole>,
 }
 
 impl Default for WebAuthConfigDto {
@@ -21,6 +26,7 @@ impl Default for WebAuthConfigDto {
             secret: String::new(),
             token_ttl_mins: default_token_ttl_mins(),
             userfile: None,
+            roles: HashMap::new(),
         }
     }
 }
@@ -33,5 +39,6 @@ impl WebAuthConfigDto {
             && self.issuer.trim().is_empty()
             && self.secret.trim().is_empty()
             && is_blank_optional_str(self.userfile.as_deref())
+            && self.roles.is_empty()
     }
 }
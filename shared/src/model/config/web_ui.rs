@@ -20,6 +20,7 @@ const RESERVED_PATHS: &[&str] = &[
     "apiget",
     "m3u",
     "resource",
+    "public",
 ];
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
@@ -67,6 +68,25 @@ impl ContentSecurityPolicyConfigDto {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PublicStatusConfigDto {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub maintenance: Option<String>,
+}
+
+impl PublicStatusConfigDto {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled && is_blank_optional_str(self.maintenance.as_deref())
+    }
+
+    pub fn prepare(&mut self) {
+        self.maintenance = self.maintenance.as_deref().map(str::trim).filter(|m| !m.is_empty()).map(ToString::to_string);
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct WebUiConfigDto {
@@ -84,6 +104,8 @@ pub struct WebUiConfigDto {
     pub player_server: Option<String>,
     #[serde(default = "default_kick_secs", skip_serializing_if = "is_default_kick_secs")]
     pub kick_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_status: Option<PublicStatusConfigDto>,
 }
 
 impl Default for WebUiConfigDto {
@@ -96,6 +118,7 @@ impl Default for WebUiConfigDto {
             auth: None,
             player_server: None,
             kick_secs: default_kick_secs(),
+            public_status: None,
         }
     }
 }
@@ -114,6 +137,7 @@ impl WebUiConfigDto {
                     .as_ref()
                     .is_some_and(|c| c.is_empty()))
             && (self.auth.is_none() || self.auth.as_ref().is_some_and(|c| c.is_empty()))
+            && (self.public_status.is_none() || self.public_status.as_ref().is_some_and(PublicStatusConfigDto::is_empty))
     }
 
     pub fn clean(&mut self) {
@@ -127,6 +151,9 @@ impl WebUiConfigDto {
         if self.auth.as_ref().is_some_and(|c| c.is_empty()) {
             self.auth = None;
         }
+        if self.public_status.as_ref().is_some_and(PublicStatusConfigDto::is_empty) {
+            self.public_status = None;
+        }
 
         if is_blank_optional_str(self.path.as_deref()) {
             self.path = None;
@@ -164,6 +191,9 @@ impl WebUiConfigDto {
         if let Some(csp) = &self.content_security_policy {
             csp.validate()?;
         }
+        if let Some(public_status) = self.public_status.as_mut() {
+            public_status.prepare();
+        }
         Ok(())
     }
 }
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProcessStatsDto {
+    pub pid: u32,
+    /// Resident memory in bytes
+    pub memory: u64,
+    /// Virtual memory in bytes
+    pub virtual_memory: u64,
+}
+
+/// Counters of the allocator, only available when tuliprox is built with the `profiling` feature.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AllocatorStatsDto {
+    /// Bytes currently allocated
+    pub allocated: u64,
+    /// Highest value of `allocated` since the start
+    pub peak_allocated: u64,
+    pub allocations: u64,
+    pub deallocations: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeStatsDto {
+    pub workers: usize,
+    pub alive_tasks: usize,
+    /// Tasks waiting in the global queue of the scheduler
+    pub global_queue_depth: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskStatsDto {
+    pub active_users: usize,
+    pub active_user_connections: usize,
+    pub active_user_streams: usize,
+    pub active_provider_connections: usize,
+}
+
+/// Runtime diagnostics returned by `api/v1/debug/stats`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DebugStatsDto {
+    pub process: ProcessStatsDto,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocator: Option<AllocatorStatsDto>,
+    pub runtime: RuntimeStatsDto,
+    pub tasks: TaskStatsDto,
+    /// `true` if the cpu profile endpoint is compiled in
+    pub profiling: bool,
+}
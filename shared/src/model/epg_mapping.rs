@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An xmltv channel suggested for a playlist channel, `score` is the similarity in percent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EpgMappingCandidateDto {
+    pub epg_channel_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    pub score: u8,
+}
+
+/// A live channel of a target without a matching epg, with the ranked candidates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EpgMappingSuggestionDto {
+    pub name: String,
+    pub group: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epg_channel_id: Option<String>,
+    pub candidates: Vec<EpgMappingCandidateDto>,
+}
+
+/// Accepted or rejected suggestion for a channel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EpgMappingDecisionDto {
+    pub name: String,
+    pub epg_channel_id: String,
+    pub accept: bool,
+}
+
+/// Epg mappings of a target, keyed by the channel name.
+/// Accepted mappings are assigned on the next playlist update, rejected candidates are not suggested again.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EpgMappingsDto {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accepted: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rejected: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl EpgMappingsDto {
+    pub fn apply(&mut self, decision: EpgMappingDecisionDto) {
+        if decision.accept {
+            self.rejected.remove(&decision.name);
+            self.accepted.insert(decision.name, decision.epg_channel_id);
+        } else {
+            if self.accepted.get(&decision.name) == Some(&decision.epg_channel_id) {
+                self.accepted.remove(&decision.name);
+            }
+            self.rejected.entry(decision.name).or_default().insert(decision.epg_channel_id);
+        }
+    }
+
+    pub fn is_rejected(&self, name: &str, epg_channel_id: &str) -> bool {
+        self.rejected.get(name).is_some_and(|ids| ids.contains(epg_channel_id))
+    }
+}
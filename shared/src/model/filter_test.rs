@@ -0,0 +1,46 @@
+use crate::model::XtreamCluster;
+use serde::{Deserialize, Serialize};
+
+pub const FILTER_TEST_DEFAULT_LIMIT: usize = 100;
+
+/// Dry run of a filter expression against the stored playlist of a target or an input.
+/// The target filter sees the playlists of the inputs of its source, these are used for a target.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FilterTestRequest {
+    pub filter: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    /// Maximum number of returned matches, the counts include all items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// Clause of the filter which decided the match of an item.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FilterClauseMatchDto {
+    pub clause: String,
+    /// The value of the item the regex matched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Templates the regex of the clause was built from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FilterTestMatchDto {
+    pub input: String,
+    pub group: String,
+    pub title: String,
+    pub cluster: XtreamCluster,
+    pub clauses: Vec<FilterClauseMatchDto>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FilterTestResultDto {
+    pub total: usize,
+    pub matched: usize,
+    pub items: Vec<FilterTestMatchDto>,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Run of a schedule, the retries of its failed targets are logged as further attempts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JobLogEntryDto {
+    pub ts: i64,
+    pub schedule: String,
+    /// `1` is the scheduled run, higher attempts are retries
+    pub attempt: u8,
+    pub targets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<String>,
+    /// Timestamp of the retry of the failed targets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_retry_ts: Option<i64>,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Log record of the server, streamed to the web ui.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LogEntryDto {
+    /// Unix timestamp in milliseconds
+    pub ts: i64,
+    pub level: String,
+    /// Module path of the record
+    pub target: String,
+    pub message: String,
+}
@@ -0,0 +1,77 @@
+use crate::model::XtreamCluster;
+use serde::{Deserialize, Serialize};
+
+pub const MAPPER_TRACE_DEFAULT_SAMPLE: usize = 10;
+
+/// Debug run of the mappings of a target against a sample of the stored playlists of its inputs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapperTraceRequest {
+    pub target: String,
+    /// Only the mapping with this id is traced, otherwise all mappings of the target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mapping: Option<String>,
+    /// Maximum number of traced items per mapping
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample: Option<usize>,
+}
+
+/// Evaluation of a regex of the script.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapperPatternTraceDto {
+    pub pattern: String,
+    /// The text the regex was applied to, `None` if the source had no value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The captured groups, empty if the regex did not match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub captures: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapperFieldChangeDto {
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+/// One evaluated top level statement of the script.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapperStepTraceDto {
+    /// Index into the statements of the script
+    pub statement: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<MapperPatternTraceDto>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<MapperFieldChangeDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapperItemTraceDto {
+    pub input: String,
+    pub cluster: XtreamCluster,
+    /// The non-empty fields of the item before the script ran
+    pub fields: Vec<(String, String)>,
+    pub steps: Vec<MapperStepTraceDto>,
+    /// Number of virtual items created by the script
+    pub virtual_items: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapperTraceDto {
+    pub mapping: String,
+    /// Index of the mapper within the mapping
+    pub mapper: usize,
+    /// Number of items matched by the filter of the mapper
+    pub matched: usize,
+    pub items: Vec<MapperItemTraceDto>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapperTraceResultDto {
+    pub total: usize,
+    pub mappers: Vec<MapperTraceDto>,
+}
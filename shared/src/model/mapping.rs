@@ -13,7 +13,8 @@ pub const COUNTER_FIELDS: &[&str] = &["name", "title", "caption", "chno"];
 pub const MAPPER_FIELDS: &[&str] = &[
     "name", "title", "caption", "group", "id", "chno", "logo",
     "logo_small", "parent_code", "audio_track",
-    "time_shift", "rec", "url", "epg_channel_id", "epg_id"
+    "time_shift", "rec", "url", "epg_channel_id", "epg_id",
+    "user_agent", "referrer"
 ];
 
 #[macro_export]
@@ -48,4 +48,27 @@ impl FromStr for MsgKind {
             info_err_res!("Unknown MsgKind: {}", s)
         }
     }
-}
\ No newline at end of file
+}
+
+/// Keys of a browser push subscription, as returned by `PushSubscription.toJSON()`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct WebPushSubscriptionKeysDto {
+    pub p256dh: String,
+    pub auth: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct WebPushSubscriptionDto {
+    pub endpoint: String,
+    pub keys: WebPushSubscriptionKeysDto,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct WebPushUnsubscribeDto {
+    pub endpoint: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct WebPushPublicKeyDto {
+    pub public_key: String,
+}
@@ -32,11 +32,31 @@ mod regex_cache;
 mod uuidtype;
 mod short_epg;
 mod stats;
+mod playlist_changes;
+mod rule_lint;
 mod ui_playlist_item;
+mod notification;
+mod ui_preferences;
+mod epg_mapping;
+mod stream_session;
+mod filter_test;
+mod mapper_trace;
+mod job_log;
+mod log_entry;
+mod channel_health;
+mod user_account;
+mod api_error;
+mod cache_usage;
+mod debug_stats;
+mod user_import;
+mod playlist_browse;
+mod playlist_search;
 pub use self::cluster_flags::*;
 pub use self::playlist::*;
 pub use self::messaging::*;
 pub use self::stats::*;
+pub use self::playlist_changes::*;
+pub use self::rule_lint::*;
 pub use self::item_field::*;
 pub use self::target_type::*;
 pub use self::processing_order::*;
@@ -64,4 +84,20 @@ pub use self::xtream::*;
 pub use self::playlist_info_document::*;
 pub use self::regex_cache::*;
 pub use self::uuidtype::*;
-pub use self::ui_playlist_item::*;
\ No newline at end of file
+pub use self::ui_playlist_item::*;
+pub use self::notification::*;
+pub use self::ui_preferences::*;
+pub use self::epg_mapping::*;
+pub use self::stream_session::*;
+pub use self::filter_test::*;
+pub use self::mapper_trace::*;
+pub use self::job_log::*;
+pub use self::log_entry::*;
+pub use self::channel_health::*;
+pub use self::user_account::*;
+pub use self::api_error::*;
+pub use self::cache_usage::*;
+pub use self::debug_stats::*;
+pub use self::user_import::*;
+pub use self::playlist_browse::*;
+pub use self::playlist_search::*;
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationKind {
+    Info,
+    Warning,
+    Error,
+    Security,
+}
+
+/// Notification of the web ui, `read` is the state for the requesting web ui user.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationDto {
+    pub id: u64,
+    /// Unix timestamp in seconds
+    pub ts: i64,
+    pub kind: NotificationKind,
+    pub message: String,
+    #[serde(default)]
+    pub read: bool,
+}
+
+/// Marks the notifications as read, an empty list marks all notifications as read.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationReadRequest {
+    #[serde(default)]
+    pub ids: Vec<u64>,
+}
@@ -1,6 +1,6 @@
 use crate::utils::{arc_str_option_serde, arc_str_serde, extract_extension_from_url, generate_playlist_uuid,
                    get_provider_id, Internable};
-use crate::model::{xtream_const, ClusterFlags, CommonPlaylistItem, ConfigTargetOptions, EpisodeStreamProperties,
+use crate::model::{xtream_const, ClusterFlags, CommonPlaylistItem, ConfigTargetOptions, PlaylistStripField, EpisodeStreamProperties,
                    SeriesStreamProperties, StreamProperties, VideoStreamProperties, XtreamInfoDocument};
 use enum_iterator::Sequence;
 use serde::{Deserialize, Serialize};
@@ -266,6 +266,10 @@ pub struct PlaylistItemHeader {
     pub input_name: Arc<str>,
     #[serde(default)]
     pub source_ordinal: u32,
+    #[serde(default, with = "arc_str_serde")]
+    pub user_agent: Arc<str>,
+    #[serde(default, with = "arc_str_serde")]
+    pub referrer: Arc<str>,
 }
 
 impl Default for PlaylistItemHeader {
@@ -292,6 +296,8 @@ impl Default for PlaylistItemHeader {
             category_id: 0,
             input_name: "".intern(),
             source_ordinal: 0,
+            user_agent: "".intern(),
+            referrer: "".intern(),
         }
     }
 }
@@ -419,7 +425,7 @@ macro_rules! generate_field_accessor_impl_for_playlist_item_header {
     }
 }
 
-generate_field_accessor_impl_for_playlist_item_header!(id, /*virtual_id,*/ title, name, logo, logo_small, parent_code, audio_track, time_shift, rec, url;);
+generate_field_accessor_impl_for_playlist_item_header!(id, /*virtual_id,*/ title, name, logo, logo_small, parent_code, audio_track, time_shift, rec, url, user_agent, referrer;);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct M3uPlaylistItem {
@@ -458,13 +464,18 @@ pub struct M3uPlaylistItem {
     pub t_resource_url: Option<String>,
     #[serde(default)]
     pub source_ordinal: u32,
+    #[serde(default, with = "arc_str_serde")]
+    pub user_agent: Arc<str>,
+    #[serde(default, with = "arc_str_serde")]
+    pub referrer: Arc<str>,
 }
 
 impl M3uPlaylistItem {
     #[allow(clippy::missing_panics_doc)]
     pub fn to_m3u(&self, target_options: Option<&ConfigTargetOptions>, rewrite_urls: bool) -> String {
         let options = target_options.as_ref();
-        let ignore_logo = options.is_some_and(|o| o.ignore_logo);
+        let ignore_logo = options.is_some_and(|o| o.ignore_logo || o.has_strip_field(PlaylistStripField::Logo));
+        let strip_attributes = options.is_some_and(|o| o.has_strip_field(PlaylistStripField::Attributes));
         let mut line = String::with_capacity(256);
         let _ = write!(&mut line, "#EXTINF:-1 tvg-id=\"{}\" tvg-name=\"{}\" group-title=\"{}\"",
                        self.epg_channel_id.as_ref().map_or("", |o| o.as_ref()),
@@ -481,14 +492,24 @@ impl M3uPlaylistItem {
         if self.chno != 0 {
             let _ = write!(line, " tvg-chno=\"{}\"", self.chno);
         }
-        to_m3u_non_empty_fields!(self, line,
-            (parent_code, "parent-code"),
-            (audio_track, "audio-track"),
-            (time_shift, "timeshift"),
-            (rec, "tvg-rec"););
+        if !strip_attributes {
+            to_m3u_non_empty_fields!(self, line,
+                (parent_code, "parent-code"),
+                (audio_track, "audio-track"),
+                (time_shift, "timeshift"),
+                (rec, "tvg-rec"););
+        }
 
+        let _ = write!(&mut line, ",{}", self.title);
+        // players connecting to the url send the headers required by the provider
+        if !self.user_agent.is_empty() {
+            let _ = write!(&mut line, "\n#EXTVLCOPT:http-user-agent={}", self.user_agent);
+        }
+        if !self.referrer.is_empty() {
+            let _ = write!(&mut line, "\n#EXTVLCOPT:http-referrer={}", self.referrer);
+        }
         let url = if self.t_stream_url.is_empty() { &self.url } else { &self.t_stream_url };
-        let _ = write!(&mut line, ",{}\n{}", self.title, url);
+        let _ = write!(&mut line, "\n{url}");
         line
     }
 
@@ -608,7 +629,7 @@ macro_rules! generate_field_accessor_impl_for_m3u_playlist_item {
     }
 }
 
-generate_field_accessor_impl_for_m3u_playlist_item!(title, name, provider_id, logo, logo_small, parent_code, audio_track, time_shift, rec, url;);
+generate_field_accessor_impl_for_m3u_playlist_item!(title, name, provider_id, logo, logo_small, parent_code, audio_track, time_shift, rec, url, user_agent, referrer;);
 
 impl From<M3uPlaylistItem> for CommonPlaylistItem {
     fn from(item: M3uPlaylistItem) -> Self {
@@ -622,11 +643,14 @@ pub struct XtreamMappingOptions {
     pub skip_video_direct_source: bool,
     pub skip_series_direct_source: bool,
     pub rewrite_resource_url: bool,
+    /// The resources are rewritten also for redirected item types, they are served from the logo cache.
+    pub proxy_logos: bool,
     pub force_redirect: Option<ClusterFlags>,
     pub reverse_item_types: PlaylistItemTypeSet,
     pub username: String,
     pub password: String,
     pub base_url: Option<String>,
+    pub strip_fields: Vec<PlaylistStripField>,
 }
 
 impl XtreamMappingOptions {
@@ -636,7 +660,7 @@ impl XtreamMappingOptions {
     }
 
     pub fn get_resource_url(&self, xtream_cluster: XtreamCluster, item_type: PlaylistItemType, virtual_id: VirtualId) -> Option<String> {
-        let is_reverse = self.is_reverse(item_type);
+        let is_reverse = self.is_reverse(item_type) || self.proxy_logos;
         let resource_url = if is_reverse && self.rewrite_resource_url && self.base_url.is_some() {
             let resource_url = format!("{}/resource/{}/{}/{}/{}", self.base_url.as_ref().map_or_else(String::new, |b| b.clone()),
                                        xtream_cluster.as_stream_type(), self.username, self.password, virtual_id);
@@ -1026,6 +1050,8 @@ impl From<&PlaylistItem> for M3uPlaylistItem {
             t_stream_url: Arc::clone(&header.url),
             t_resource_url: None,
             source_ordinal: header.source_ordinal,
+            user_agent: Arc::clone(&header.user_agent),
+            referrer: Arc::clone(&header.referrer),
         }
     }
 }
@@ -1084,6 +1110,8 @@ impl From<&XtreamPlaylistItem> for PlaylistItem {
             time_shift: "".intern(),
             additional_properties: item.additional_properties.clone(),
             source_ordinal: item.source_ordinal,
+            user_agent: "".intern(),
+            referrer: "".intern(),
         };
 
         PlaylistItem {
@@ -1116,6 +1144,8 @@ impl From<&M3uPlaylistItem> for PlaylistItem {
             time_shift: item.time_shift.clone(),
             additional_properties: None,
             source_ordinal: item.source_ordinal,
+            user_agent: item.user_agent.clone(),
+            referrer: item.referrer.clone(),
         };
 
         PlaylistItem {
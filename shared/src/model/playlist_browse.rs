@@ -0,0 +1,72 @@
+use crate::model::{PlaylistItemType, PlaylistRequest, UiPlaylistItem, XtreamCluster};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistBrowseSortField {
+    /// Order of the stored playlist
+    #[default]
+    Default,
+    Name,
+    Group,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistBrowseSortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistBrowseFilter {
+    /// Title of the group, exact match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Case-insensitive regex on the title and name of the items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<PlaylistItemType>,
+}
+
+/// Page of the stored playlist of a target or an input, the next page is requested with the `next_cursor` of the previous one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlaylistBrowseRequest {
+    pub source: PlaylistRequest,
+    pub cluster: XtreamCluster,
+    #[serde(default)]
+    pub filter: PlaylistBrowseFilter,
+    #[serde(default)]
+    pub sort_by: PlaylistBrowseSortField,
+    #[serde(default)]
+    pub sort_order: PlaylistBrowseSortOrder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlaylistBrowsePageDto {
+    pub items: Vec<UiPlaylistItem>,
+    /// Number of items matching the filter
+    pub total: usize,
+    /// Missing on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlaylistBrowseGroupsRequest {
+    pub source: PlaylistRequest,
+    pub cluster: XtreamCluster,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistBrowseGroupDto {
+    pub id: u32,
+    pub title: String,
+    pub count: usize,
+}
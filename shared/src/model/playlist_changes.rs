@@ -0,0 +1,135 @@
+use crate::model::XtreamCluster;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistChangeItemDto {
+    pub name: String,
+    pub group: String,
+    pub cluster: XtreamCluster,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistRenamedItemDto {
+    pub old_name: String,
+    pub name: String,
+    pub group: String,
+    pub cluster: XtreamCluster,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistMovedItemDto {
+    pub name: String,
+    pub old_group: String,
+    pub group: String,
+    pub cluster: XtreamCluster,
+}
+
+/// Differences of a target playlist to the playlist of the previous processing run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistChangesDto {
+    pub target: String,
+    /// Unix timestamp of the processing run
+    pub timestamp: i64,
+    pub added: Vec<PlaylistChangeItemDto>,
+    pub removed: Vec<PlaylistChangeItemDto>,
+    pub renamed: Vec<PlaylistRenamedItemDto>,
+    pub moved: Vec<PlaylistMovedItemDto>,
+    pub groups_added: Vec<String>,
+    pub groups_removed: Vec<String>,
+}
+
+impl PlaylistChangesDto {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.moved.is_empty()
+            && self.groups_added.is_empty()
+            && self.groups_removed.is_empty()
+    }
+
+    pub fn summary(&self) -> PlaylistChangeSummary {
+        PlaylistChangeSummary {
+            added: self.added.len(),
+            removed: self.removed.len(),
+            renamed: self.renamed.len(),
+            moved: self.moved.len(),
+            groups_added: self.groups_added.len(),
+            groups_removed: self.groups_removed.len(),
+        }
+    }
+}
+
+/// Channels and groups which only exist in one of two target playlists.
+/// Channels are matched by their uuid, targets built from the same inputs share them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistCompareDto {
+    pub target_a: String,
+    pub target_b: String,
+    /// Number of channels in both playlists
+    pub common: usize,
+    pub only_a: Vec<PlaylistChangeItemDto>,
+    pub only_b: Vec<PlaylistChangeItemDto>,
+    pub groups_only_a: Vec<String>,
+    pub groups_only_b: Vec<String>,
+}
+
+impl PlaylistCompareDto {
+    pub fn is_equal(&self) -> bool {
+        self.only_a.is_empty() && self.only_b.is_empty() && self.groups_only_a.is_empty() && self.groups_only_b.is_empty()
+    }
+}
+
+/// Change counts of a processing run, part of the target stats.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistChangeSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub renamed: usize,
+    pub moved: usize,
+    pub groups_added: usize,
+    pub groups_removed: usize,
+}
+
+impl PlaylistChangeSummary {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Display for PlaylistChangeSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts = [(self.added, "added"), (self.removed, "removed"), (self.renamed, "renamed"), (self.moved, "moved")];
+        let mut first = true;
+        for (count, label) in counts.into_iter().filter(|(count, _)| *count > 0) {
+            if first {
+                write!(f, "{count} {} {label}", if count == 1 { "channel" } else { "channels" })?;
+                first = false;
+            } else {
+                write!(f, ", {count} {label}")?;
+            }
+        }
+        if first {
+            write!(f, "no channel changes")?;
+        }
+        if self.groups_added > 0 || self.groups_removed > 0 {
+            write!(f, " (groups {} added, {} removed)", self.groups_added, self.groups_removed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlaylistChangeSummary;
+
+    #[test]
+    fn test_summary_display() {
+        let summary = PlaylistChangeSummary { added: 12, removed: 3, ..PlaylistChangeSummary::default() };
+        assert_eq!(summary.to_string(), "12 channels added, 3 removed");
+        let summary = PlaylistChangeSummary { moved: 2, groups_added: 1, ..PlaylistChangeSummary::default() };
+        assert_eq!(summary.to_string(), "2 channels moved (groups 1 added, 0 removed)");
+        assert_eq!(PlaylistChangeSummary::default().to_string(), "no channel changes");
+    }
+}
@@ -1,7 +1,7 @@
 use crate::utils::{arc_str_serde, arc_str_option_serde, arc_str_vec_serde, Internable};
 use crate::model::info_doc_utils::InfoDocUtils;
 use crate::model::{
-    LiveStreamProperties, SeriesStreamProperties, StreamProperties, VideoStreamProperties,
+    LiveStreamProperties, PlaylistStripField, SeriesStreamProperties, StreamProperties, VideoStreamProperties,
     XtreamCluster, XtreamEmptyDoc, XtreamInfoDocument, XtreamMappingOptions, XtreamPlaylistItem,
     XtreamSeriesInfoData, XtreamSeriesInfoDoc, XtreamVideoInfoData,
     XtreamVideoInfoDoc, XtreamVideoMovieData,
@@ -202,7 +202,7 @@ impl XtreamPlaylistItem {
     }
 
     pub fn to_document(&self, options: &XtreamMappingOptions) -> XtreamDocument {
-        if let Some(props) = self.additional_properties.as_ref() {
+        let mut document = if let Some(props) = self.additional_properties.as_ref() {
             match props {
                 StreamProperties::Live(live) => self.live_to_document(options, live),
                 StreamProperties::Video(video) => self.video_to_document(options, video),
@@ -212,7 +212,9 @@ impl XtreamPlaylistItem {
         } else {
             let resource_url = options.get_resource_url(self.xtream_cluster, self.item_type, self.virtual_id);
             self.to_document_no_props(resource_url)
-        }
+        };
+        document.strip(&options.strip_fields);
+        document
     }
 
     fn series_to_document(&self, options: &XtreamMappingOptions, series: &SeriesStreamProperties) -> XtreamDocument {
@@ -383,3 +385,44 @@ pub enum XtreamDocument {
     Episode(XtreamEmptyDoc),
 }
 
+impl XtreamDocument {
+    /// Clears the stripped fields, the keys are kept because the clients expect them.
+    pub fn strip(&mut self, fields: &[PlaylistStripField]) {
+        for field in fields {
+            match (field, &mut *self) {
+                (PlaylistStripField::Logo, Self::Live(doc)) => doc.stream_icon = "".intern(),
+                (PlaylistStripField::Logo, Self::Video(doc)) => doc.stream_icon = "".intern(),
+                (PlaylistStripField::Logo, Self::Series(doc)) => {
+                    doc.cover = "".intern();
+                    doc.backdrop_path = Vec::new();
+                }
+                (PlaylistStripField::Description, Self::Video(doc)) => doc.trailer = "".intern(),
+                (PlaylistStripField::Description, Self::Series(doc)) => {
+                    let empty_str = "".intern();
+                    doc.plot = Arc::clone(&empty_str);
+                    doc.cast = Arc::clone(&empty_str);
+                    doc.director = Arc::clone(&empty_str);
+                    doc.genre = Arc::clone(&empty_str);
+                    doc.youtube_trailer = empty_str;
+                }
+                (PlaylistStripField::Attributes, Self::Live(doc)) => {
+                    doc.added = "".intern();
+                    doc.custom_sid = None;
+                }
+                (PlaylistStripField::Attributes, Self::Video(doc)) => {
+                    let empty_str = "".intern();
+                    doc.added = Arc::clone(&empty_str);
+                    doc.tmdb = empty_str;
+                    doc.custom_sid = None;
+                }
+                (PlaylistStripField::Attributes, Self::Series(doc)) => {
+                    let empty_str = "".intern();
+                    doc.last_modified = Arc::clone(&empty_str);
+                    doc.tmdb = empty_str;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
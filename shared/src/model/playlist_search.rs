@@ -0,0 +1,24 @@
+use crate::model::{PlaylistItemType, XtreamCluster};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistSearchHitDto {
+    pub virtual_id: u32,
+    pub cluster: XtreamCluster,
+    pub item_type: PlaylistItemType,
+    pub title: String,
+    pub group: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epg_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    /// Higher is better, exact token matches rank before prefix matches
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaylistSearchResultDto {
+    pub items: Vec<PlaylistSearchHitDto>,
+    /// Number of matches, the items are limited
+    pub total: usize,
+}
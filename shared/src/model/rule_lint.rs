@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleLintKind {
+    UnreachableBranch,
+    UnmatchedRegex,
+    ShadowedRename,
+    UnusedTemplate,
+}
+
+/// Warning of the rule lint, the config is valid but a rule does not do what it seems to do.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuleLintWarningDto {
+    pub kind: RuleLintKind,
+    /// Target or mapping the rule belongs to, `templates` for the source templates
+    pub scope: String,
+    pub message: String,
+}
+
+impl RuleLintWarningDto {
+    pub fn new(kind: RuleLintKind, scope: &str, message: String) -> Self {
+        Self { kind, scope: scope.to_string(), message }
+    }
+}
+
+impl Display for RuleLintWarningDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.scope, self.message)
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt::Display;
+use std::time::Duration;
 use serde::{Deserialize, Serialize, Serializer, Deserializer};
-use crate::model::InputType;
+use crate::model::{InputType, PlaylistChangeSummary};
 
 pub fn format_elapsed_time(seconds: u64) -> String {
     if seconds < 60 {
@@ -72,20 +73,137 @@ impl Display for InputStats {
 }
 
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessingStage {
+    Fetch,
+    Parse,
+    Filter,
+    Map,
+    Sort,
+    Persist,
+    Epg,
+}
+
+/// Milliseconds spent in each stage of a playlist update of a target.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProcessingTimings {
+    pub fetch: u64,
+    pub parse: u64,
+    pub filter: u64,
+    pub map: u64,
+    pub sort: u64,
+    pub persist: u64,
+    pub epg: u64,
+}
+
+impl ProcessingTimings {
+    pub fn add(&mut self, stage: ProcessingStage, duration: Duration) {
+        let millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        let value = match stage {
+            ProcessingStage::Fetch => &mut self.fetch,
+            ProcessingStage::Parse => &mut self.parse,
+            ProcessingStage::Filter => &mut self.filter,
+            ProcessingStage::Map => &mut self.map,
+            ProcessingStage::Sort => &mut self.sort,
+            ProcessingStage::Persist => &mut self.persist,
+            ProcessingStage::Epg => &mut self.epg,
+        };
+        *value = value.saturating_add(millis);
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.fetch = self.fetch.saturating_add(other.fetch);
+        self.parse = self.parse.saturating_add(other.parse);
+        self.filter = self.filter.saturating_add(other.filter);
+        self.map = self.map.saturating_add(other.map);
+        self.sort = self.sort.saturating_add(other.sort);
+        self.persist = self.persist.saturating_add(other.persist);
+        self.epg = self.epg.saturating_add(other.epg);
+    }
+
+    /// Stages in pipeline order with their milliseconds.
+    pub fn stages(&self) -> [(ProcessingStage, u64); 7] {
+        [
+            (ProcessingStage::Fetch, self.fetch),
+            (ProcessingStage::Parse, self.parse),
+            (ProcessingStage::Filter, self.filter),
+            (ProcessingStage::Map, self.map),
+            (ProcessingStage::Sort, self.sort),
+            (ProcessingStage::Persist, self.persist),
+            (ProcessingStage::Epg, self.epg),
+        ]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.stages().iter().fold(0, |total, (_, millis)| total.saturating_add(*millis))
+    }
+}
+
+impl Display for ProcessingStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            ProcessingStage::Fetch => "fetch",
+            ProcessingStage::Parse => "parse",
+            ProcessingStage::Filter => "filter",
+            ProcessingStage::Map => "map",
+            ProcessingStage::Sort => "sort",
+            ProcessingStage::Persist => "persist",
+            ProcessingStage::Epg => "epg",
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TargetStats {
     #[serde(rename = "target")]
     pub name: String,
     pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<PlaylistChangeSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<ProcessingTimings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicates: Option<DeduplicationStats>,
+}
+
+/// Duplicate live channels of a target update, `failover` counts the removed channels kept as failover source.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeduplicationStats {
+    pub removed: usize,
+    pub failover: usize,
 }
 
 impl TargetStats {
     pub fn success(name: &str) -> Self {
-        Self  {name: name.to_string(), success: true}
+        Self  {name: name.to_string(), success: true, changes: None, timings: None, duplicates: None}
     }
     pub fn failure(name: &str) -> Self {
-        Self  {name: name.to_string(), success: false}
+        Self  {name: name.to_string(), success: false, changes: None, timings: None, duplicates: None}
+    }
+
+    pub fn with_duplicates(mut self, duplicates: Option<DeduplicationStats>) -> Self {
+        self.duplicates = duplicates;
+        self
     }
+
+    pub fn with_changes(mut self, changes: Option<PlaylistChangeSummary>) -> Self {
+        self.changes = changes;
+        self
+    }
+
+    pub fn with_timings(mut self, timings: ProcessingTimings) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+}
+
+/// Stage timings of the last playlist update of a target.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TargetProcessingStatsDto {
+    pub target: String,
+    pub ts: i64,
+    pub success: bool,
+    pub timings: ProcessingTimings,
 }
 
 impl Display for TargetStats {
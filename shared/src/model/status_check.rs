@@ -18,6 +18,12 @@ pub struct StatusCheck {
     pub active_user_streams: Vec<StreamInfo>,
     #[serde(default)]
     pub active_provider_connections: Option<BTreeMap<Arc<str>, usize>>,
+    /// Provider switches of the input failover per input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_failovers: Option<BTreeMap<Arc<str>, usize>>,
+    /// Requests denied by the geoip access policies per country
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geoip_denied: Option<BTreeMap<Arc<str>, usize>>,
 }
 
 impl Default for StatusCheck {
@@ -31,7 +37,30 @@ impl Default for StatusCheck {
             active_users: 0,
             active_user_connections: 0,
             active_provider_connections: None,
+            provider_failovers: None,
+            geoip_denied: None,
             active_user_streams: Vec::new(),
         }
     }
-}
\ No newline at end of file
+}
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PublicServiceStatus {
+    Operational,
+    Degraded,
+    Maintenance,
+}
+
+/// Aggregate service health for the unauthenticated status page.
+/// Must not contain user, connection or provider details.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PublicStatusDto {
+    pub status: PublicServiceStatus,
+    pub streaming: PublicServiceStatus,
+    /// Unix timestamp of the last playlist update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playlist_updated_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub maintenance: Option<String>,
+    pub server_time: String,
+}
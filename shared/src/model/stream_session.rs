@@ -0,0 +1,43 @@
+use crate::model::{PlaylistItemType, XtreamCluster};
+use crate::utils::is_blank_optional_string;
+use serde::{Deserialize, Serialize};
+
+/// A finished stream session of the session history, timestamps are unix timestamps in seconds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamSessionDto {
+    pub username: String,
+    pub target_id: u16,
+    pub virtual_id: u32,
+    pub item_type: PlaylistItemType,
+    pub cluster: XtreamCluster,
+    pub title: String,
+    pub group: String,
+    pub provider: String,
+    pub client_ip: String,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub user_agent: String,
+    pub start: u64,
+    pub end: u64,
+    #[serde(default)]
+    pub bytes: u64,
+}
+
+impl StreamSessionDto {
+    /// The session was active within the time range.
+    pub fn overlaps(&self, from: Option<u64>, to: Option<u64>) -> bool {
+        from.is_none_or(|from| self.end >= from) && to.is_none_or(|to| self.start <= to)
+    }
+}
+
+/// Filter of the session history, without a range all stored sessions are returned.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StreamSessionQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<u64>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub username: Option<String>,
+}
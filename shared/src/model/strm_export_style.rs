@@ -14,6 +14,9 @@ pub enum StrmExportStyle {
     Emby,
     #[serde(rename = "jellyfin")]
     Jellyfin,
+    /// Sonarr and Radarr library layout with NFO sidecar files
+    #[serde(rename = "arr")]
+    Arr,
 }
 
 impl StrmExportStyle {
@@ -21,6 +24,7 @@ impl StrmExportStyle {
     const PLEX: &'static str = "Plex";
     const EMBY: &'static str = "Emby";
     const JELLYFIN: &'static str = "Jellyfin";
+    const ARR: &'static str = "Arr";
 }
 
 impl Display for StrmExportStyle {
@@ -30,6 +34,7 @@ impl Display for StrmExportStyle {
             Self::Plex => Self::PLEX,
             Self::Emby => Self::EMBY,
             Self::Jellyfin => Self::JELLYFIN,
+            Self::Arr => Self::ARR,
         })
     }
 }
@@ -43,6 +48,7 @@ impl FromStr for StrmExportStyle {
             Self::PLEX => Ok(Self::Plex),
             Self::EMBY => Ok(Self::Emby),
             Self::JELLYFIN => Ok(Self::Jellyfin),
+            Self::ARR => Ok(Self::Arr),
             _ => Err(format!("Unknown StrmExportStyle: {}", s))
         }
     }
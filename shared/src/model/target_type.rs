@@ -12,6 +12,8 @@ pub enum TargetType {
     Strm,
     #[serde(rename = "hdhomerun")]
     HdHomeRun,
+    #[serde(rename = "plex")]
+    Plex,
 }
 
 impl TargetType {
@@ -19,6 +21,7 @@ impl TargetType {
     const XTREAM: &'static str = "Xtream";
     const STRM: &'static str = "Strm";
     const HDHOMERUN: &'static str = "HdHomeRun";
+    const PLEX: &'static str = "Plex";
 }
 
 impl Display for TargetType {
@@ -28,6 +31,7 @@ impl Display for TargetType {
             Self::Xtream => Self::XTREAM,
             Self::Strm => Self::STRM,
             Self::HdHomeRun => Self::HDHOMERUN,
+            Self::Plex => Self::PLEX,
         })
     }
 }
@@ -41,6 +45,7 @@ impl FromStr for TargetType {
             Self::XTREAM => Ok(Self::Xtream),
             Self::STRM => Ok(Self::Strm),
             Self::HDHOMERUN => Ok(Self::HdHomeRun),
+            Self::PLEX => Ok(Self::Plex),
             _ => Err(format!("Unknown TargetType: {}", s))
         }
     }
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Web ui settings of an account, stored on the server so they follow the user across browsers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UiPreferencesDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Settings of the single views like table columns, page sizes or the dashboard layout, keyed by view.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub views: BTreeMap<String, serde_json::Value>,
+}
+
+impl UiPreferencesDto {
+    /// Applies the given settings, unset fields are kept and a `null` view setting is removed.
+    pub fn merge(&mut self, update: UiPreferencesDto) {
+        if update.theme.is_some() {
+            self.theme = update.theme;
+        }
+        if update.language.is_some() {
+            self.language = update.language;
+        }
+        for (view, settings) in update.views {
+            if settings.is_null() {
+                self.views.remove(&view);
+            } else {
+                self.views.insert(view, settings);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UiPreferencesDto;
+
+    #[test]
+    fn test_merge_ui_preferences() {
+        let mut preferences = UiPreferencesDto {
+            theme: Some("dark".to_string()),
+            language: Some("de".to_string()),
+            views: [("streams".to_string(), serde_json::json!({"page_size": 50}))].into_iter().collect(),
+        };
+        preferences.merge(UiPreferencesDto { theme: Some("bright".to_string()), ..UiPreferencesDto::default() });
+        assert_eq!(preferences.theme.as_deref(), Some("bright"));
+        assert_eq!(preferences.language.as_deref(), Some("de"));
+        assert_eq!(preferences.views.len(), 1);
+
+        preferences.merge(UiPreferencesDto {
+            views: [("streams".to_string(), serde_json::Value::Null)].into_iter().collect(),
+            ..UiPreferencesDto::default()
+        });
+        assert!(preferences.views.is_empty());
+    }
+}
@@ -0,0 +1,30 @@
+use crate::model::{ProxyUserStatus, TargetType};
+
+/// Account overview of a proxy user for the user self-service pages.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+pub struct UserAccountDto {
+    pub username: String,
+    pub target: String,
+    #[serde(default)]
+    pub status: Option<ProxyUserStatus>,
+    #[serde(default)]
+    pub exp_date: Option<i64>,
+    #[serde(default)]
+    pub max_connections: u32,
+    #[serde(default)]
+    pub active_connections: u32,
+    #[serde(default)]
+    pub outputs: Vec<TargetType>,
+    /// Base url of the xtream server of the user
+    #[serde(default)]
+    pub server_url: String,
+    #[serde(default)]
+    pub m3u_url: Option<String>,
+    #[serde(default)]
+    pub epg_url: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+pub struct UserPasswordDto {
+    pub password: String,
+}
@@ -0,0 +1,65 @@
+use crate::model::ProxyUserCredentialsDto;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserImportFormat {
+    /// Comma or semicolon separated values, the first row is the header with the field names
+    #[default]
+    Csv,
+    /// Json export of the lines of an xtream panel
+    Xtream,
+}
+
+/// Bulk import of playlist users into a target.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserImportRequest {
+    pub target: String,
+    #[serde(default)]
+    pub format: UserImportFormat,
+    pub content: String,
+    /// Existing users are updated and moved to the target, otherwise they are skipped
+    #[serde(default)]
+    pub update_existing: bool,
+    /// Only the preview of the import is returned, nothing is saved
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserImportAction {
+    Create,
+    Update,
+    Skip,
+}
+
+/// Column of the import and the user field it is assigned to, unknown columns are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserImportColumnDto {
+    pub column: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UserImportEntryDto {
+    /// Row of the csv or index of the panel line, starting with 1
+    pub row: usize,
+    pub username: String,
+    pub action: UserImportAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<ProxyUserCredentialsDto>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UserImportResultDto {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub columns: Vec<UserImportColumnDto>,
+    pub entries: Vec<UserImportEntryDto>,
+}
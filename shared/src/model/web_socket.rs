@@ -1,7 +1,7 @@
 use std::io;
 use std::sync::Arc;
 use bytes::Bytes;
-use crate::model::{ActiveUserConnectionChange, ConfigType, LibraryScanSummary, PlaylistUpdateState, StatusCheck, SystemInfo};
+use crate::model::{ActiveUserConnectionChange, ConfigType, LibraryScanSummary, NotificationDto, PlaylistUpdateState, StatusCheck, SystemInfo};
 use serde::{Deserialize, Serialize};
 use crate::model::user_command::UserCommand;
 
@@ -42,7 +42,7 @@ pub enum WsCloseCode {
     // Unsupported,
     // Abnormal,
     // Invalid,
-    // Policy,
+    Policy,
     // Size,
     // Extension,
     // Error,
@@ -60,7 +60,7 @@ impl WsCloseCode {
             // WsCloseCode::Unsupported => 1003,
             // WsCloseCode::Abnormal => 1006,
             // WsCloseCode::Invalid => 1007,
-            // WsCloseCode::Policy => 1008,
+            WsCloseCode::Policy => 1008,
             // WsCloseCode::Size => 1009,
             // WsCloseCode::Extension => 1010,
             // WsCloseCode::Error => 1011,
@@ -93,6 +93,8 @@ pub enum ProtocolMessage {
     UserActionResponse(bool),
     SystemInfoResponse(SystemInfo),
     LibraryScanProgressResponse(LibraryScanSummary),
+    NotificationResponse(NotificationDto),
+    MappingReprocessResponse(Vec<String>),
 }
 
 impl ProtocolMessage {
@@ -1,7 +1,7 @@
 use regex::Regex;
 use std::collections::HashSet;
 use std::string::ToString;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU8};
 use std::sync::{Arc, LazyLock};
 
 
@@ -20,6 +20,7 @@ pub const HLS_EXT: &str = ".m3u8";
 pub const DASH_EXT: &str = ".mpd";
 
 pub const HLS_PREFIX: &str = "hls";
+pub const HLS_REMUX_PREFIX: &str = "hlsr";
 pub const CUSTOM_VIDEO_PREFIX: &str = "cvs";
 
 pub const HLS_EXT_QUERY: &str = ".m3u8?";
@@ -92,6 +93,7 @@ pub struct Constants {
     pub re_whitespace: Regex,
     pub re_hls_uri: Regex,
     pub sanitize: AtomicBool,
+    pub ip_anonymization: AtomicU8,
     pub export_style_config: ExportStyleConfig,
     pub country_codes: HashSet<&'static str>,
     pub allowed_output_formats: Vec<String>,
@@ -129,6 +131,7 @@ pub static CONSTANTS: LazyLock<Constants> = LazyLock::new(||
         re_hls_uri: Regex::new(r#"URI=["']([^"']+)["']"#).unwrap(),
 
         sanitize: AtomicBool::new(true),
+        ip_anonymization: AtomicU8::new(0),
         export_style_config: ExportStyleConfig {
             season: Regex::new(r"[Ss]\d{1,2}").unwrap(),
             episode: Regex::new(r"[Ee]\d{1,2}").unwrap(),
@@ -4,6 +4,9 @@ use crate::model::{ConfigTargetOptions, LibraryMetadataFormat, ProcessingOrder};
 pub const fn is_zero_u16(v: &u16) -> bool {
     *v == 0
 }
+pub const fn is_zero_u32(v: &u32) -> bool {
+    *v == 0
+}
 pub const fn is_true(v: &bool) -> bool {
     *v
 }
@@ -58,6 +61,24 @@ pub const fn default_grace_period_timeout_secs() -> u64 {
 pub const fn is_default_grace_period_timeout_secs(v: &u64) -> bool {
     *v == default_grace_period_timeout_secs()
 }
+pub const fn default_hls_segment_secs() -> u16 {
+    4
+}
+pub const fn is_default_hls_segment_secs(v: &u16) -> bool {
+    *v == default_hls_segment_secs()
+}
+pub const fn default_hls_playlist_segments() -> u16 {
+    6
+}
+pub const fn is_default_hls_playlist_segments(v: &u16) -> bool {
+    *v == default_hls_playlist_segments()
+}
+pub const fn default_hls_idle_timeout_secs() -> u16 {
+    30
+}
+pub const fn is_default_hls_idle_timeout_secs(v: &u16) -> bool {
+    *v == default_hls_idle_timeout_secs()
+}
 pub const fn default_panel_api_provision_timeout_secs() -> u64 {
     65
 }
@@ -73,6 +94,54 @@ pub const fn default_panel_api_alias_pool_min() -> u16 {
 pub const fn default_panel_api_alias_pool_max() -> u16 {
     1
 }
+pub const fn default_failover_max_failures() -> u16 {
+    2
+}
+pub const fn default_failover_recovery_secs() -> u64 {
+    300
+}
+pub const fn default_channel_probe_interval_mins() -> u32 {
+    360
+}
+pub const fn default_channel_probe_sample_size() -> u16 {
+    20
+}
+pub const fn default_channel_probe_timeout_secs() -> u16 {
+    10
+}
+pub const fn default_user_expiration_interval_mins() -> u32 {
+    60
+}
+pub const fn default_schedule_retry_attempts() -> u8 {
+    3
+}
+pub const fn default_schedule_retry_backoff_secs() -> u64 {
+    60
+}
+pub const fn default_schedule_retry_max_backoff_secs() -> u64 {
+    3600
+}
+pub const fn default_mapping_reprocess_debounce_secs() -> u64 {
+    30
+}
+pub const fn default_account_sharing_window_secs() -> u64 {
+    3600
+}
+pub const fn default_account_sharing_block_secs() -> u64 {
+    3600
+}
+pub const fn default_cluster_sync_interval_millis() -> u64 {
+    1000
+}
+pub fn default_cluster_key_prefix() -> String {
+    String::from("tuliprox")
+}
+pub const fn default_shutdown_grace_secs() -> u16 {
+    5
+}
+pub const fn is_default_shutdown_grace_secs(v: &u16) -> bool {
+    *v == default_shutdown_grace_secs()
+}
 pub const fn default_connect_timeout_secs() -> u32 {
     6
 }
@@ -270,6 +339,18 @@ pub fn is_default_device_type(value: &String) -> bool {
 pub fn is_default_device_udn(value: &String) -> bool {
     value == DEFAULT_DEVICE_UDN
 }
+pub const fn default_plex_channel_start() -> u32 {
+    1
+}
+pub const fn is_default_plex_channel_start(v: &u32) -> bool {
+    *v == default_plex_channel_start()
+}
+pub const fn default_channel_number_start() -> u32 {
+    1
+}
+pub const fn is_default_channel_number_start(v: &u32) -> bool {
+    *v == default_channel_number_start()
+}
 
 //////////////////////////
 // trakt
@@ -1,4 +1,16 @@
+use crate::model::IpAnonymization;
+use crate::utils::CONSTANTS;
+use regex::Captures;
 use std::borrow::Cow;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::LazyLock;
+
+/// The key changes with every start, hashed ips can only be correlated within one run.
+static IP_HASH_KEY: LazyLock<[u8; 32]> = LazyLock::new(|| {
+    let mut key = [0u8; 32];
+    fastrand::fill(&mut key);
+    key
+});
 
 pub fn strip_port<'a>(input: &'a str) -> Cow<'a, str> {
     // IPv6 with port: [2001:db8::1]:8080
@@ -22,4 +34,76 @@ pub fn strip_port<'a>(input: &'a str) -> Cow<'a, str> {
     } else {
         Cow::Borrowed(input)
     }
-}
\ No newline at end of file
+}
+fn truncate_ip(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            IpAddr::V4(Ipv4Addr::new(a, b, c, 0))
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => truncate_ip(IpAddr::V4(v4)),
+            None => {
+                let segments = v6.segments();
+                IpAddr::V6(Ipv6Addr::new(segments[0], segments[1], segments[2], 0, 0, 0, 0, 0))
+            }
+        },
+    }
+}
+
+/// Pseudonymizes a single ip address, IPv4 is truncated to `/24` and IPv6 to `/48`.
+/// Text which is no ip address is returned unchanged.
+pub fn anonymize_ip(ip: &str, mode: IpAnonymization) -> Cow<'_, str> {
+    let Ok(addr) = ip.parse::<IpAddr>() else {
+        return Cow::Borrowed(ip);
+    };
+    match mode {
+        IpAnonymization::None => Cow::Borrowed(ip),
+        IpAnonymization::Truncate => Cow::Owned(truncate_ip(addr).to_string()),
+        IpAnonymization::Hash => {
+            let hash = blake3::keyed_hash(&IP_HASH_KEY, addr.to_string().as_bytes());
+            Cow::Owned(format!("ip-{}", &hash.to_hex()[..12]))
+        }
+    }
+}
+
+/// Pseudonymizes all ip addresses inside the text.
+pub fn anonymize_ips(text: &str, mode: IpAnonymization) -> Cow<'_, str> {
+    if mode.is_none() {
+        return Cow::Borrowed(text);
+    }
+    let replacer = |caps: &Captures| anonymize_ip(&caps[0], mode).into_owned();
+    // IPv6 first, a mapped IPv6 address contains an IPv4 address
+    match CONSTANTS.re_ipv6.replace_all(text, replacer) {
+        Cow::Borrowed(_) => CONSTANTS.re_ipv4.replace_all(text, replacer),
+        Cow::Owned(replaced) => Cow::Owned(CONSTANTS.re_ipv4.replace_all(&replaced, replacer).into_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{anonymize_ip, anonymize_ips};
+    use crate::model::IpAnonymization;
+
+    #[test]
+    fn test_anonymize_ip() {
+        assert_eq!(anonymize_ip("192.168.1.42", IpAnonymization::Truncate), "192.168.1.0");
+        assert_eq!(anonymize_ip("2001:db8:85a3:8d3:1319:8a2e:370:7348", IpAnonymization::Truncate), "2001:db8:85a3::");
+        assert_eq!(anonymize_ip("::ffff:10.0.0.7", IpAnonymization::Truncate), "10.0.0.0");
+        assert_eq!(anonymize_ip("192.168.1.42", IpAnonymization::None), "192.168.1.42");
+        assert_eq!(anonymize_ip("no ip", IpAnonymization::Hash), "no ip");
+
+        let hashed = anonymize_ip("192.168.1.42", IpAnonymization::Hash);
+        assert!(hashed.starts_with("ip-") && hashed.len() == 15);
+        assert_eq!(hashed, anonymize_ip("192.168.1.42", IpAnonymization::Hash));
+        assert_ne!(hashed, anonymize_ip("192.168.1.43", IpAnonymization::Hash));
+    }
+
+    #[test]
+    fn test_anonymize_ips() {
+        assert_eq!(anonymize_ips("Failed login for user bob from 10.1.2.3", IpAnonymization::Truncate),
+                   "Failed login for user bob from 10.1.2.0");
+        assert_eq!(anonymize_ips("client 10.1.2.3:4711 at 12:30:15", IpAnonymization::Truncate), "client 10.1.2.0:4711 at 12:30:15");
+        assert!(!anonymize_ips("from 10.1.2.3", IpAnonymization::Hash).contains("10.1.2.3"));
+    }
+}
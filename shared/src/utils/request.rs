@@ -1,7 +1,8 @@
 use std::borrow::Cow;
 use std::sync::atomic::Ordering;
 use url::Url;
-use crate::utils::{CONSTANTS, DASH_EXT, DASH_EXT_FRAGMENT, DASH_EXT_QUERY, HLS_EXT, HLS_EXT_FRAGMENT, HLS_EXT_QUERY};
+use crate::model::IpAnonymization;
+use crate::utils::{anonymize_ips, CONSTANTS, DASH_EXT, DASH_EXT_FRAGMENT, DASH_EXT_QUERY, HLS_EXT, HLS_EXT_FRAGMENT, HLS_EXT_QUERY};
 
 
 pub const CONTENT_TYPE_JSON: &str = "application/json";
@@ -11,22 +12,40 @@ pub const ACCEPT_PREFER_CBOR: &str = "application/cbor, application/json;q=0.9";
 pub fn set_sanitize_sensitive_info(value: bool) {
     CONSTANTS.sanitize.store(value, Ordering::Relaxed);
 }
+
+pub fn set_ip_anonymization(mode: IpAnonymization) {
+    CONSTANTS.ip_anonymization.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn get_ip_anonymization() -> IpAnonymization {
+    match CONSTANTS.ip_anonymization.load(Ordering::Relaxed) {
+        1 => IpAnonymization::Truncate,
+        2 => IpAnonymization::Hash,
+        _ => IpAnonymization::None,
+    }
+}
+
+/// With ip anonymization the client ips are pseudonymized instead of masked.
 pub fn sanitize_sensitive_info(query: &str) -> Cow<'_, str> {
+    let ip_anonymization = get_ip_anonymization();
     if !CONSTANTS.sanitize.load(Ordering::Relaxed) {
-        return Cow::Borrowed(query);
+        return anonymize_ips(query, ip_anonymization);
     }
 
-    let mut result = query.to_owned();
+    let mut result = anonymize_ips(query, ip_anonymization).into_owned();
+    let mask_ips = ip_anonymization.is_none();
 
-    for (re, replacement) in &[
-        (&CONSTANTS.re_credentials, "$1***"),
-        (&CONSTANTS.re_ipv4, "$1***"),
-        (&CONSTANTS.re_ipv6, "$1***"),
-        (&CONSTANTS.re_stream_url, "$1***/$2/***"),
-        (&CONSTANTS.re_url, "$1***/$2"),
-        (&CONSTANTS.re_password, "$1***"),
+    for (re, replacement, is_ip) in &[
+        (&CONSTANTS.re_credentials, "$1***", false),
+        (&CONSTANTS.re_ipv4, "$1***", true),
+        (&CONSTANTS.re_ipv6, "$1***", true),
+        (&CONSTANTS.re_stream_url, "$1***/$2/***", false),
+        (&CONSTANTS.re_url, "$1***/$2", false),
+        (&CONSTANTS.re_password, "$1***", false),
     ] {
-        result = re.replace_all(&result, *replacement).into_owned();
+        if mask_ips || !is_ip {
+            result = re.replace_all(&result, *replacement).into_owned();
+        }
     }
     Cow::Owned(result)
 }
@@ -108,6 +108,14 @@ pub fn mask_credentials(s: &str) -> String {
     }
 }
 
+/// Trims and uppercases the country codes, empty entries are removed.
+pub fn normalize_country_codes(countries: &mut Vec<String>) {
+    countries.retain_mut(|country| {
+        *country = country.trim().to_uppercase();
+        !country.is_empty()
+    });
+}
+
 pub fn humanize_snake_case(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
     let mut capitalize_next = true;